@@ -11,7 +11,7 @@ use nimiq_vrf::{Rng, VrfEntropy, VrfUseCase};
 
 use crate::{
     error::{BlockchainError, BlockchainEvent, Direction},
-    ChainInfo, ForkEvent,
+    ChainInfo, ForkEvent, ReorgEvent,
 };
 
 /// Defines several basic methods for blockchains.
@@ -162,4 +162,7 @@ pub trait AbstractBlockchain {
     /// Stream of Fork Events.
     // FIXME Get rid of this
     fn fork_notifier_as_stream(&self) -> BoxStream<'static, ForkEvent>;
+
+    /// Stream of Reorg Events, one for every rebranch of the main chain.
+    fn subscribe_reorgs(&self) -> BoxStream<'static, ReorgEvent>;
 }
@@ -12,6 +12,18 @@ pub enum ForkEvent {
     Detected(ForkProof),
 }
 
+/// Structured description of a rebranch, emitted whenever the main chain switches to a fork.
+/// Both vectors are ordered by block height, ascending.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReorgEvent {
+    /// The hashes of the blocks that were removed from the main chain.
+    pub reverted: Vec<Blake2bHash>,
+    /// The hashes of the blocks that were adopted onto the main chain.
+    pub applied: Vec<Blake2bHash>,
+    /// The hash of the last block shared by the old and the new main chain.
+    pub common_ancestor: Blake2bHash,
+}
+
 /// Events from the blockchain.
 /// Note that `Finalized` and `EpochFinalized` will be sent **in addition** to `Extended` events.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -63,6 +75,8 @@ pub enum BlockchainError {
     BlockBodyNotFound,
     #[error("Block is not a macro block")]
     BlockIsNotMacro,
+    #[error("Block is not finalized yet: {0}")]
+    BlockNotFinalized(u32),
     #[error("No validators found")]
     NoValidatorsFound,
     #[error("Invalid epoch ID")]
@@ -121,8 +135,15 @@ pub enum PushError {
     EquivocationAlreadyIncluded(EquivocationLocator),
     #[error("Accounts trie is incomplete and thus cannot be verified.")]
     IncompleteAccountsTrie,
+    #[error("Block vetoed: {0}")]
+    Vetoed(#[from] BlockVeto),
 }
 
+/// The reason why a post-validation hook rejected an otherwise valid block.
+#[derive(Error, Clone, Debug, PartialEq, Eq)]
+#[error("{0}")]
+pub struct BlockVeto(pub String);
+
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Deserialize, Serialize)]
 #[repr(u8)]
 pub enum Direction {
@@ -2,8 +2,8 @@ pub use abstract_blockchain::AbstractBlockchain;
 pub use chain_info::ChainInfo;
 pub use chain_ordering::*;
 pub use error::{
-    BlockchainError, BlockchainEvent, ChunksPushError, ChunksPushResult, Direction, ForkEvent,
-    PushError, PushResult,
+    BlockVeto, BlockchainError, BlockchainEvent, ChunksPushError, ChunksPushResult, Direction,
+    ForkEvent, PushError, PushResult, ReorgEvent,
 };
 
 mod abstract_blockchain;
@@ -6,6 +6,7 @@ use nimiq_block::{Block, MacroBlock};
 use nimiq_blockchain::Blockchain;
 use nimiq_blockchain_interface::{
     AbstractBlockchain, BlockchainError, BlockchainEvent, ChainInfo, Direction, ForkEvent,
+    ReorgEvent,
 };
 use nimiq_hash::Blake2bHash;
 use nimiq_light_blockchain::LightBlockchain;
@@ -230,4 +231,8 @@ impl AbstractBlockchain for BlockchainReadProxy<'_> {
     fn fork_notifier_as_stream(&self) -> BoxStream<'static, ForkEvent> {
         gen_blockchain_match!(self, BlockchainReadProxy, fork_notifier_as_stream)
     }
+
+    fn subscribe_reorgs(&self) -> BoxStream<'static, ReorgEvent> {
+        gen_blockchain_match!(self, BlockchainReadProxy, subscribe_reorgs)
+    }
 }
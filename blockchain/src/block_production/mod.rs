@@ -1,3 +1,5 @@
+use std::cell::RefCell;
+
 use nimiq_account::{Account, AccountsError, BlockState};
 use nimiq_block::{
     EquivocationProof, MacroBlock, MacroBody, MacroHeader, MicroBlock, MicroBody, MicroHeader,
@@ -7,11 +9,13 @@ use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_bls::KeyPair as BlsKeyPair;
 use nimiq_database::{mdbx::MdbxReadTransaction as DBTransaction, traits::WriteTransaction};
 use nimiq_hash::{Blake2bHash, Blake2sHash, Hash};
-use nimiq_keys::KeyPair as SchnorrKeyPair;
-use nimiq_primitives::policy::Policy;
+use nimiq_keys::{Ed25519PublicKey, Ed25519Signature, KeyPair as SchnorrKeyPair};
+use nimiq_primitives::{coin::Coin, policy::Policy};
+use nimiq_serde::Serialize;
 use nimiq_transaction::{
     historic_transaction::HistoricTransaction, inherent::Inherent, Transaction,
 };
+use nimiq_vrf::VrfSeed;
 use rand::{CryptoRng, Rng, RngCore};
 use thiserror::Error;
 
@@ -25,6 +29,8 @@ pub enum BlockProducerError {
     HistoryError,
     #[error("Accounts are incomplete")]
     AccountsIncomplete,
+    #[error("Failed to sign: {0}")]
+    SigningError(String),
 }
 
 impl BlockProducerError {
@@ -50,6 +56,119 @@ impl BlockProducerError {
     }
 }
 
+/// Produces the signatures a block producer needs to create a block. This allows producing
+/// blocks without having direct access to the validator's signing key.
+pub trait BlockSigner {
+    /// The public key the produced signatures can be verified with.
+    fn signing_public_key(&self) -> Ed25519PublicKey;
+
+    /// Signs the hash of a micro block header.
+    fn sign_micro_header(
+        &self,
+        header: &MicroHeader,
+    ) -> Result<Ed25519Signature, BlockProducerError>;
+
+    /// Computes the VRF seed of the block at `block_number` from the seed of its predecessor.
+    fn next_vrf_seed(
+        &self,
+        prev_seed: &VrfSeed,
+        block_number: u32,
+    ) -> Result<VrfSeed, BlockProducerError>;
+}
+
+impl BlockSigner for SchnorrKeyPair {
+    fn signing_public_key(&self) -> Ed25519PublicKey {
+        self.public
+    }
+
+    fn sign_micro_header(
+        &self,
+        header: &MicroHeader,
+    ) -> Result<Ed25519Signature, BlockProducerError> {
+        let hash: Blake2bHash = header.hash();
+        Ok(self.sign(hash.as_slice()))
+    }
+
+    fn next_vrf_seed(
+        &self,
+        prev_seed: &VrfSeed,
+        block_number: u32,
+    ) -> Result<VrfSeed, BlockProducerError> {
+        Ok(prev_seed.sign_next(self, block_number))
+    }
+}
+
+/// Signs with a local key pair, drawing the VRF randomness from the given rng.
+struct KeyPairSigner<'a, R> {
+    key_pair: &'a SchnorrKeyPair,
+    rng: RefCell<&'a mut R>,
+}
+
+impl<'a, R: RngCore + CryptoRng> KeyPairSigner<'a, R> {
+    fn new(key_pair: &'a SchnorrKeyPair, rng: &'a mut R) -> Self {
+        Self {
+            key_pair,
+            rng: RefCell::new(rng),
+        }
+    }
+}
+
+impl<R: RngCore + CryptoRng> BlockSigner for KeyPairSigner<'_, R> {
+    fn signing_public_key(&self) -> Ed25519PublicKey {
+        self.key_pair.public
+    }
+
+    fn sign_micro_header(
+        &self,
+        header: &MicroHeader,
+    ) -> Result<Ed25519Signature, BlockProducerError> {
+        self.key_pair.sign_micro_header(header)
+    }
+
+    fn next_vrf_seed(
+        &self,
+        prev_seed: &VrfSeed,
+        block_number: u32,
+    ) -> Result<VrfSeed, BlockProducerError> {
+        let mut rng = self.rng.borrow_mut();
+        Ok(prev_seed.sign_next_with_rng(self.key_pair, block_number, &mut **rng))
+    }
+}
+
+/// A candidate micro block assembled by `BlockProducer::preview_micro_block`.
+#[derive(Clone, Debug)]
+pub struct MicroBlockPreview {
+    /// The block number of the candidate block.
+    pub block_number: u32,
+    /// The timestamp of the candidate block.
+    pub timestamp: u64,
+    /// The body of the candidate block, including the execution result of each transaction.
+    pub body: MicroBody,
+    /// The serialized size of the body in bytes.
+    pub body_size: usize,
+    /// The sum of the fees paid by the included transactions.
+    pub total_fees: Coin,
+    /// The inherents the block would apply.
+    pub inherents: Vec<Inherent>,
+    /// The accounts tree root after applying the block.
+    pub state_root: Blake2bHash,
+    /// The root of the accounts tree diff of the block.
+    pub diff_root: Blake2bHash,
+    /// The history root after applying the block.
+    pub history_root: Blake2bHash,
+}
+
+impl MicroBlockPreview {
+    /// The number of included transactions that would fail to execute.
+    pub fn num_failed_transactions(&self) -> usize {
+        self.body
+            .transactions
+            .iter()
+            .filter(|txn| txn.failed())
+            .count()
+    }
+}
+
 /// Struct that contains all necessary information to actually produce blocks.
 /// It has the validator keys for this validator.
 #[derive(Clone)]
@@ -115,6 +234,37 @@ impl BlockProducer {
         skip_block_proof: Option<SkipBlockProof>,
         // The rng seed. We need this parameterized in order to have determinism when running unit tests.
         rng: &mut R,
+    ) -> Result<MicroBlock, BlockProducerError> {
+        Self::next_micro_block_with_signer(
+            &KeyPairSigner::new(&self.signing_key, rng),
+            blockchain,
+            timestamp,
+            equivocation_proofs,
+            transactions,
+            extra_data,
+            skip_block_proof,
+        )
+    }
+
+    /// Creates the next micro block, using the given signer to produce the VRF seed and the block
+    /// signature.
+    pub fn next_micro_block_with_signer<S: BlockSigner + ?Sized>(
+        // The signer of the block producer.
+        signer: &S,
+        // The (upgradable) read locked guard to the blockchain.
+        blockchain: &Blockchain,
+        // The timestamp for the block.
+        timestamp: u64,
+        // Proofs of any misbehavior by malicious validators. An equivocation proof may be submitted
+        // during the batch when it happened or until the end of the reporting window, but not after
+        // that.
+        mut equivocation_proofs: Vec<EquivocationProof>,
+        // The transactions to be included in the block body.
+        transactions: Vec<Transaction>,
+        // Extra data for this block.
+        extra_data: Vec<u8>,
+        // Skip block proof.
+        skip_block_proof: Option<SkipBlockProof>,
     ) -> Result<MicroBlock, BlockProducerError> {
         equivocation_proofs.sort_by_key(|proof| proof.sort_key());
 
@@ -150,7 +300,7 @@ impl BlockProducer {
             // leader.
             prev_seed.clone()
         } else {
-            prev_seed.sign_next_with_rng(&self.signing_key, block_number, rng)
+            signer.next_vrf_seed(&prev_seed, block_number)?
         };
 
         // Create the inherents from the equivocation proofs or skip block info.
@@ -237,11 +387,10 @@ impl BlockProducer {
                     .expect("should find producer")
                     .number,
             );
-            assert_eq!(self.signing_key.public, epoch_validator.signing_key);
+            assert_eq!(signer.signing_public_key(), epoch_validator.signing_key);
 
             // Signs the block header using the signing key.
-            let hash = header.hash();
-            let signature = self.signing_key.sign(hash.as_slice());
+            let signature = signer.sign_micro_header(&header)?;
             MicroJustification::Micro(signature)
         };
 
@@ -253,6 +402,84 @@ impl BlockProducer {
         })
     }
 
+    /// Assembles the micro block that would follow the current head, without signing it or
+    /// committing anything to the blockchain. The candidate block contains the given
+    /// transactions and the inherents resulting from the equivocation proofs.
+    pub fn preview_micro_block(
+        // The (upgradable) read locked guard to the blockchain.
+        blockchain: &Blockchain,
+        // The timestamp for the block.
+        timestamp: u64,
+        // Proofs of any misbehavior by malicious validators.
+        mut equivocation_proofs: Vec<EquivocationProof>,
+        // The transactions to be included in the block body.
+        transactions: Vec<Transaction>,
+    ) -> Result<MicroBlockPreview, BlockProducerError> {
+        equivocation_proofs.sort_by_key(|proof| proof.sort_key());
+
+        let block_number = blockchain.block_number() + 1;
+        let timestamp = u64::max(timestamp, blockchain.timestamp());
+
+        let inherents =
+            blockchain.create_punishment_inherents(block_number, &equivocation_proofs, None, None);
+
+        // Calculate the state root on a transaction that is never committed.
+        let block_state = BlockState::new(block_number, timestamp);
+        let (state_root, diff_root, executed_txns) = blockchain
+            .state
+            .accounts
+            .exercise_transactions(&transactions, &inherents, &block_state)
+            .map_err(|error| {
+                BlockProducerError::accounts_error(
+                    blockchain,
+                    error,
+                    transactions,
+                    inherents.clone(),
+                )
+            })?;
+
+        let hist_txs = HistoricTransaction::from(
+            blockchain.network_id,
+            block_number,
+            timestamp,
+            executed_txns.clone(),
+            inherents.clone(),
+            equivocation_proofs
+                .iter()
+                .map(|proof| proof.locator())
+                .collect(),
+        );
+
+        let mut txn = blockchain.write_transaction();
+        let (history_root, _) = blockchain
+            .history_store
+            .add_to_history(&mut txn, block_number, &hist_txs)
+            .ok_or(BlockProducerError::HistoryError)?;
+        txn.abort();
+
+        let total_fees = executed_txns
+            .iter()
+            .map(|txn| txn.get_raw_transaction().fee)
+            .sum();
+
+        let body = MicroBody {
+            equivocation_proofs,
+            transactions: executed_txns,
+        };
+
+        Ok(MicroBlockPreview {
+            block_number,
+            timestamp,
+            body_size: body.serialized_size(),
+            total_fees,
+            inherents,
+            state_root,
+            diff_root,
+            history_root,
+            body,
+        })
+    }
+
     /// Creates a proposal for the next macro block (checkpoint or election). It is just a proposal,
     /// NOT a complete block. It still needs to go through the Tendermint protocol in order to be
     /// finalized.
@@ -293,6 +520,30 @@ impl BlockProducer {
         extra_data: Vec<u8>,
         // The rng seed. We need this parameterized in order to have determinism when running unit tests.
         rng: &mut R,
+    ) -> Result<MacroBlock, BlockProducerError> {
+        Self::next_macro_block_proposal_with_signer(
+            &KeyPairSigner::new(&self.signing_key, rng),
+            blockchain,
+            timestamp,
+            round,
+            extra_data,
+        )
+    }
+
+    /// Creates a proposal for the next macro block (checkpoint or election), using the given
+    /// signer to produce the VRF seed.
+    // Note: Needs to be called with the Blockchain lock held.
+    pub fn next_macro_block_proposal_with_signer<S: BlockSigner + ?Sized>(
+        // The signer of the block producer.
+        signer: &S,
+        // The (upgradable) read locked guard to the blockchain.
+        blockchain: &Blockchain,
+        // The timestamp for the block proposal.
+        timestamp: u64,
+        // The round for the block proposal.
+        round: u32,
+        // Extra data for this block.
+        extra_data: Vec<u8>,
     ) -> Result<MacroBlock, BlockProducerError> {
         // The network ID stays unchanged for the whole blockchain.
         let network = blockchain.head().network();
@@ -318,11 +569,7 @@ impl BlockProducer {
 
         // Calculate the seed for this block by signing the previous block seed with the validator
         // key.
-        let seed =
-            blockchain
-                .head()
-                .seed()
-                .sign_next_with_rng(&self.signing_key, block_number, rng);
+        let seed = signer.next_vrf_seed(blockchain.head().seed(), block_number)?;
 
         // If this is an election block, calculate the validator set for the next epoch.
         let validators = match Policy::is_election_block_at(block_number) {
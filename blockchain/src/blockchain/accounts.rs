@@ -1,3 +1,6 @@
+#[cfg(feature = "metrics")]
+use std::time::Instant;
+
 use nimiq_account::{
     Account, Accounts, BlockLogger, BlockState, RevertInfo, TransactionOperationReceipt,
 };
@@ -10,8 +13,11 @@ use nimiq_primitives::{
     trie::{error::IncompleteTrie, trie_diff::TrieDiff, trie_proof::TrieProof},
 };
 use nimiq_serde::Deserialize;
+use nimiq_transaction::inherent::Inherent;
 use nimiq_trie::WriteTransactionProxy;
 
+#[cfg(feature = "metrics")]
+use crate::chain_metrics::PushStage;
 use crate::{interface::HistoryInterface, Blockchain};
 
 /// Subset of the accounts in the accounts tree
@@ -27,13 +33,14 @@ pub struct AccountsChunk {
 impl Blockchain {
     /// Updates the accounts given a block.
     /// Expects a full block with body.
+    /// Returns the total size of the block's historic transactions and the inherents it created.
     pub fn commit_accounts(
         &self,
         block: &Block,
         diff: Option<TrieDiff>,
         txn: &mut WriteTransactionProxy,
         block_logger: &mut BlockLogger,
-    ) -> Result<u64, PushError> {
+    ) -> Result<(u64, Vec<Inherent>), PushError> {
         // Get the accounts from the state.
         let accounts = &self.state.accounts;
         let block_state = BlockState::new(block.block_number(), block.timestamp());
@@ -53,13 +60,9 @@ impl Blockchain {
                     return Err(PushError::MissingAccountsTrieDiff);
                 }
 
-                let total_tx_size = self
-                    .history_store
-                    .add_block(txn.raw(), block, inherents)
-                    .expect("Failed to store history")
-                    .1;
+                let total_tx_size = self.add_block_to_history(txn, block, &inherents);
 
-                Ok(total_tx_size)
+                Ok((total_tx_size, inherents))
             }
             Block::Micro(ref micro_block) => {
                 // Get the body of the block.
@@ -122,17 +125,37 @@ impl Blockchain {
                     &revert_info,
                 );
 
-                let total_tx_size = self
-                    .history_store
-                    .add_block(txn.raw(), block, inherents)
-                    .expect("Failed to store history")
-                    .1;
+                let total_tx_size = self.add_block_to_history(txn, block, &inherents);
 
-                Ok(total_tx_size)
+                Ok((total_tx_size, inherents))
             }
         }
     }
 
+    /// Appends the block with its inherents to the history store.
+    /// Returns the total size of the block's historic transactions.
+    fn add_block_to_history(
+        &self,
+        txn: &mut WriteTransactionProxy,
+        block: &Block,
+        inherents: &[Inherent],
+    ) -> u64 {
+        #[cfg(feature = "metrics")]
+        let start = Instant::now();
+
+        let total_tx_size = self
+            .history_store
+            .add_block(txn.raw(), block, inherents.to_vec())
+            .expect("Failed to store history")
+            .1;
+
+        #[cfg(feature = "metrics")]
+        self.metrics
+            .observe_push_stage(PushStage::HistoryStoreAppend, start.elapsed());
+
+        total_tx_size
+    }
+
     /// Reverts the accounts given a block. This only applies to micro blocks and skip blocks, since
     /// macro blocks are final and can't be reverted.
     pub(crate) fn revert_accounts(
@@ -1,8 +1,10 @@
 use std::sync::Arc;
 
 use nimiq_account::{Accounts, BlockLog};
-use nimiq_block::Block;
-use nimiq_blockchain_interface::{BlockchainError, BlockchainEvent, ChainInfo, ForkEvent};
+use nimiq_block::{Block, ShortTransactionId};
+use nimiq_blockchain_interface::{
+    BlockchainError, BlockchainEvent, ChainInfo, ForkEvent, ReorgEvent,
+};
 use nimiq_database::{
     mdbx::{MdbxDatabase, MdbxReadTransaction, MdbxWriteTransaction},
     traits::{Database, WriteTransaction},
@@ -12,9 +14,11 @@ use nimiq_hash::Blake2bHash;
 use nimiq_primitives::{
     coin::Coin, networks::NetworkId, policy::Policy, slots_allocation::Validators, trie::TrieItem,
 };
+use nimiq_transaction::Transaction;
 use nimiq_utils::time::OffsetTime;
 use tokio::sync::broadcast;
 
+use super::replay::BlockchainRecorder;
 #[cfg(feature = "metrics")]
 use crate::chain_metrics::BlockchainMetrics;
 use crate::{
@@ -39,6 +43,8 @@ pub struct Blockchain {
     pub notifier: broadcast::Sender<BlockchainEvent>,
     /// The fork notifier processes fork events.
     pub fork_notifier: broadcast::Sender<ForkEvent>,
+    /// The reorg notifier processes structured rebranch events.
+    pub reorg_notifier: broadcast::Sender<ReorgEvent>,
     /// The log notifier processes all events regarding accounts changes.
     pub log_notifier: broadcast::Sender<BlockLog>,
     /// The chain store is a database containing all of the chain infos, blocks and receipts.
@@ -60,6 +66,8 @@ pub struct Blockchain {
     pub(crate) genesis_block_number: u32,
     /// The Genesis hash used for various checks
     pub(crate) genesis_hash: Blake2bHash,
+    /// Records the inputs applied to the blockchain, if enabled.
+    pub(crate) recorder: BlockchainRecorder,
 }
 
 /// Contains various blockchain configuration knobs
@@ -69,8 +77,18 @@ pub struct BlockchainConfig {
     /// Maximum number of epochs (other than the current one) that the ChainStore will store fully.
     /// Epochs older than this number will be pruned.
     pub max_epochs_stored: u32,
+    /// Number of epochs (other than the current one) for which the history is kept if
+    /// `keep_history` is set. Older epochs are pruned at epoch boundaries. If `None`, the full
+    /// history is kept.
+    pub history_retention_epochs: Option<u32>,
     /// Enables/Disables indices in the history store.
     pub index_history: bool,
+    /// Verifies the signatures of a block in parallel. Only takes effect if the `parallel`
+    /// feature is enabled.
+    pub parallel_verification: bool,
+    /// Number of batches for which the chain infos of forked blocks are kept after their batch
+    /// was finalized. If `None`, they are kept until their epoch is pruned.
+    pub fork_retention_batches: Option<u32>,
 }
 
 impl Default for BlockchainConfig {
@@ -78,7 +96,10 @@ impl Default for BlockchainConfig {
         Self {
             keep_history: true,
             max_epochs_stored: Policy::MIN_EPOCHS_STORED,
+            history_retention_epochs: None,
             index_history: true,
+            parallel_verification: false,
+            fork_retention_batches: None,
         }
     }
 }
@@ -180,16 +201,25 @@ impl Blockchain {
         let chain_store = ChainStore::new(env.clone(), Arc::clone(&history_store));
 
         Ok(match chain_store.get_head(None) {
-            Some(head_hash) => Blockchain::load(
-                env,
-                config,
-                chain_store,
-                history_store,
-                time,
-                network_id,
-                genesis_block,
-                head_hash,
-            )?,
+            Some(head_hash) => {
+                let head_hash = Blockchain::recover_head(
+                    &env,
+                    &config,
+                    &chain_store,
+                    &history_store,
+                    head_hash,
+                )?;
+                Blockchain::load(
+                    env,
+                    config,
+                    chain_store,
+                    history_store,
+                    time,
+                    network_id,
+                    genesis_block,
+                    head_hash,
+                )?
+            }
             None => Blockchain::init(
                 env,
                 config,
@@ -305,13 +335,14 @@ impl Blockchain {
             }
         };
 
-        Ok(Blockchain {
+        let blockchain = Blockchain {
             db: env,
             config,
             network_id,
             time,
             notifier: broadcast::Sender::new(BROADCAST_MAX_CAPACITY),
             fork_notifier: broadcast::Sender::new(BROADCAST_MAX_CAPACITY),
+            reorg_notifier: broadcast::Sender::new(BROADCAST_MAX_CAPACITY),
             log_notifier: broadcast::Sender::new(BROADCAST_MAX_CAPACITY),
             chain_store,
             history_store,
@@ -333,7 +364,13 @@ impl Blockchain {
             genesis_timestamp,
             genesis_block_number,
             genesis_hash,
-        })
+            recorder: BlockchainRecorder::default(),
+        };
+
+        // Catch up on history that is outside of a retention window that was shrunk since.
+        blockchain.prune_history_backlog();
+
+        Ok(blockchain)
     }
 
     /// Initializes a blockchain.
@@ -389,6 +426,7 @@ impl Blockchain {
             time,
             notifier: broadcast::Sender::new(BROADCAST_MAX_CAPACITY),
             fork_notifier: broadcast::Sender::new(BROADCAST_MAX_CAPACITY),
+            reorg_notifier: broadcast::Sender::new(BROADCAST_MAX_CAPACITY),
             log_notifier: broadcast::Sender::new(BROADCAST_MAX_CAPACITY),
             chain_store,
             history_store,
@@ -410,6 +448,7 @@ impl Blockchain {
             genesis_timestamp,
             genesis_block_number,
             genesis_hash,
+            recorder: BlockchainRecorder::default(),
         })
     }
 
@@ -432,6 +471,16 @@ impl Blockchain {
 
 pub trait TransactionVerificationCache: Send + Sync {
     fn is_known(&self, tx_hash: &Blake2bHash) -> bool;
+
+    /// Returns the known transactions matching the given short ids of the block with the given
+    /// hash, in the same order. This is used to reconstruct the bodies of compact blocks.
+    fn get_by_short_ids(
+        &self,
+        _block_hash: &Blake2bHash,
+        short_ids: &[ShortTransactionId],
+    ) -> Vec<Option<Transaction>> {
+        vec![None; short_ids.len()]
+    }
 }
 
 struct DefaultTransactionVerificationCache {}
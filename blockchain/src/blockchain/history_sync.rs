@@ -20,7 +20,8 @@ use nimiq_transaction::{
 };
 use parking_lot::{RwLockUpgradableReadGuard, RwLockWriteGuard};
 
-use crate::{interface::HistoryInterface, Blockchain};
+use super::{replay::RecordedInput, BlockContext};
+use crate::{interface::HistoryInterface, Blockchain, PostValidationHook};
 
 /// Implements methods to push macro blocks into the chain when an history node is syncing. This
 /// type of syncing is called history syncing. It works by having the node get all the election
@@ -42,6 +43,29 @@ impl Blockchain {
         this: RwLockUpgradableReadGuard<Self>,
         block: Block,
         history: &[HistoricTransaction],
+    ) -> Result<PushResult, PushError> {
+        Self::push_history_sync_with_hook(this, block, history, &())
+    }
+
+    /// Pushes a macro block using the history sync method like [`Blockchain::push_history_sync`].
+    /// The post-validation hook can veto the block once the history has been applied to the
+    /// accounts and is informed about the result of the push.
+    pub fn push_history_sync_with_hook<F: PostValidationHook>(
+        this: RwLockUpgradableReadGuard<Self>,
+        block: Block,
+        history: &[HistoricTransaction],
+        post_validation_hook: &F,
+    ) -> Result<PushResult, PushError> {
+        let result = Self::do_push_history_sync(this, block, history, post_validation_hook);
+        post_validation_hook.post_validation(result.as_ref());
+        result
+    }
+
+    fn do_push_history_sync<F: PostValidationHook>(
+        this: RwLockUpgradableReadGuard<Self>,
+        block: Block,
+        history: &[HistoricTransaction],
+        post_validation_hook: &F,
     ) -> Result<PushResult, PushError> {
         // Check that it is a macro block. We can't push micro blocks with this function.
         assert!(
@@ -49,6 +73,11 @@ impl Blockchain {
             "You can't push micro blocks with history sync!"
         );
 
+        this.recorder.record(|| RecordedInput::HistorySync {
+            block: block.clone(),
+            history: history.to_vec(),
+        });
+
         // Create a new database read transaction.
         let read_txn = this.read_transaction();
 
@@ -84,13 +113,16 @@ impl Blockchain {
         block.verify_macro_successor(this.state.macro_info.head.unwrap_macro_ref())?;
 
         // Verify that the block is valid for the current validators.
-        block.verify_validators(this.current_validators().unwrap())?;
+        block.verify_validators_with(
+            this.current_validators().unwrap(),
+            this.config.parallel_verification,
+        )?;
 
         drop(read_txn);
 
         // Extend the chain with this block.
         let prev_macro_info = this.state.macro_info.clone();
-        Blockchain::extend_history_sync(this, block, history, prev_macro_info)
+        Blockchain::extend_history_sync(this, block, history, prev_macro_info, post_validation_hook)
     }
 
     /// Extends the current chain with transactions from the validity sync process
@@ -98,6 +130,10 @@ impl Blockchain {
         this: RwLockUpgradableReadGuard<Blockchain>,
         history: &[HistoricTransaction],
     ) -> Blake2bHash {
+        this.recorder.record(|| RecordedInput::ValiditySync {
+            history: history.to_vec(),
+        });
+
         let mut txn = this.write_transaction();
         let mut txns_per_block: BTreeMap<u32, Vec<HistoricTransaction>> = BTreeMap::new();
 
@@ -129,11 +165,12 @@ impl Blockchain {
     }
 
     /// Extends the current chain with a macro block (election or checkpoint) during history sync.
-    fn extend_history_sync(
+    fn extend_history_sync<F: PostValidationHook>(
         this: RwLockUpgradableReadGuard<Blockchain>,
         block: Block,
         history: &[HistoricTransaction],
         mut prev_macro_info: ChainInfo,
+        post_validation_hook: &F,
     ) -> Result<PushResult, PushError> {
         // Create a new database write transaction.
         let mut txn = this.write_transaction();
@@ -292,9 +329,9 @@ impl Blockchain {
             block_inherents.push(vec![]);
         }
 
-        // We go over the blocks one more time and add the FinalizeBatch and FinalizeEpoch inherents
-        // to the macro blocks. This is necessary because the History Store doesn't store those inherents
-        // so we need to add them again in order to correctly sync.
+        // We go over the blocks one more time and add the FinalizeBatch, FinalizeEpoch and
+        // RemoveTombstones inherents to the macro blocks. This is necessary because the History
+        // Store doesn't store those inherents so we need to add them again in order to correctly sync.
         for (i, block_number) in block_numbers.iter().enumerate() {
             if Policy::is_macro_block_at(*block_number) {
                 block_inherents
@@ -307,10 +344,21 @@ impl Blockchain {
                         .get_mut(i)
                         .unwrap()
                         .push(Inherent::FinalizeEpoch);
+
+                    if Policy::version_at(*block_number) >= 2 {
+                        block_inherents
+                            .get_mut(i)
+                            .unwrap()
+                            .push(Inherent::RemoveTombstones);
+                    }
                 }
             }
         }
 
+        // Collect the logs of all applied blocks for the post-validation hook.
+        let mut block_logger =
+            BlockLogger::new_applied(block_hash.clone(), block.block_number(), block.timestamp());
+
         // Update the accounts tree, one block at a time.
         for i in 0..block_numbers.len() {
             // Extract the transactions from the block
@@ -326,7 +374,7 @@ impl Blockchain {
                 &txns,
                 &block_inherents[i],
                 &block_state,
-                &mut BlockLogger::empty(),
+                &mut block_logger,
             );
 
             // Check if the receipts contain an error.
@@ -349,6 +397,21 @@ impl Blockchain {
         }
         this.state.accounts.finalize_batch(&mut (&mut txn).into());
 
+        // Give the hook the chance to veto the block now that its effects are known.
+        let block_log = block_logger.block_log();
+        let inherents: Vec<Inherent> = block_inherents.into_iter().flatten().collect();
+        let context = BlockContext {
+            block: &block,
+            transaction_logs: block_log.transaction_logs(),
+            inherents: &inherents,
+            inherent_logs: block_log.inherent_logs(),
+        };
+        if let Err(veto) = post_validation_hook.check_block(&context) {
+            warn!(%block, reason = "vetoed", %veto, "Rejecting block");
+            txn.abort();
+            return Err(PushError::Vetoed(veto));
+        }
+
         // Unwrap the block.
         let macro_block = block.unwrap_macro_ref();
 
@@ -408,7 +471,11 @@ impl Blockchain {
             return Err(PushError::InvalidBlock(BlockError::AccountsHashMismatch));
         }
 
-        this.chain_store.finalize_batch(&mut txn);
+        let pruned_forks = this.chain_store.finalize_batch(
+            &mut txn,
+            Policy::batch_at(macro_block.block_number()),
+            this.config.fork_retention_batches,
+        );
 
         // Give up database transactions and push lock before creating notifications.
         txn.commit();
@@ -431,9 +498,12 @@ impl Blockchain {
 
         let this = RwLockWriteGuard::downgrade_to_upgradable(this);
 
+        #[cfg(feature = "metrics")]
+        this.metrics.note_pruned_forks(&pruned_forks);
         debug!(
             %block,
             num_transactions = block.num_transactions(),
+            num_pruned_forks = pruned_forks.num_blocks,
             kind = "history_sync",
             "Accepted block",
         );
@@ -27,6 +27,11 @@ impl Blockchain {
             // On election the previous epoch needs to be finalized.
             // We can rely on `state` here, since we cannot revert macro blocks.
             inherents.push(self.finalize_previous_epoch());
+
+            // From version 2 of the protocol on, the tombstones of deleted validators are removed.
+            if Policy::version_at(macro_block.block_number()) >= 2 {
+                inherents.push(Inherent::RemoveTombstones);
+            }
         }
 
         inherents
@@ -216,6 +221,9 @@ impl Blockchain {
         // Remember the number of eligible slots that a validator had (that was able to accept the inherent)
         let mut num_eligible_slots_for_accepted_tx = Vec::new();
 
+        // Remember the validators of the accepted inherents to split their rewards with their stakers.
+        let mut validators_for_accepted_tx = Vec::new();
+
         // Remember that the total amount of reward must be burned. The reward for a slot is burned
         // either because the slot was penalized or because the corresponding validator was unable to
         // accept the inherent.
@@ -278,6 +286,7 @@ impl Blockchain {
             let account = self.state.accounts.get_complete(&tx.recipient, Some(&txn));
             if account.account_type() == AccountType::Basic {
                 num_eligible_slots_for_accepted_tx.push(num_eligible_slots);
+                validators_for_accepted_tx.push(validator);
                 transactions.push(tx);
             } else {
                 debug!(
@@ -306,6 +315,27 @@ impl Blockchain {
         let index = lookup.sample(&mut rng);
         transactions[index].value += remainder;
 
+        // From protocol version 2 on, the rewards are split between the validators and their
+        // stakers according to the validators' commission. The stakers' share is paid to the
+        // staking contract, which credits it to the stakers.
+        let mut stakers_transactions = Vec::new();
+        if Policy::version_at(macro_header.block_number) >= 2 {
+            for (tx, validator) in transactions.iter_mut().zip(validators_for_accepted_tx) {
+                let commission = staking_contract
+                    .get_validator_commission(&data_store.read(&txn), &validator.address);
+                let stakers_reward = validator.stakers_reward(tx.value, commission);
+                if !stakers_reward.is_zero() {
+                    tx.value -= stakers_reward;
+                    stakers_transactions.push(RewardTransaction {
+                        validator_address: validator.address,
+                        recipient: Policy::STAKING_CONTRACT_ADDRESS,
+                        value: stakers_reward,
+                    });
+                }
+            }
+        }
+        transactions.append(&mut stakers_transactions);
+
         // Do not create reward transactions for zero rewards
         transactions.retain(|transaction| !transaction.value.is_zero());
 
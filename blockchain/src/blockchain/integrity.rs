@@ -0,0 +1,128 @@
+use nimiq_database::traits::WriteTransaction;
+use nimiq_primitives::policy::Policy;
+
+use crate::{chain_store::OrphanedRecords, interface::HistoryInterface, Blockchain};
+
+/// The result of a database integrity check. See [`Blockchain::check_integrity`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct IntegrityReport {
+    /// The stored head or its chain info is missing.
+    pub head_missing: bool,
+    /// The state root of the stored head does not match the accounts trie.
+    pub accounts_inconsistent: bool,
+    /// The history root or history length of the stored head does not match the history store.
+    pub history_inconsistent: bool,
+    /// The records in the chain store that are not consistently referenced.
+    pub orphaned: OrphanedRecords,
+    /// Whether the orphaned records were repaired.
+    pub repaired: bool,
+    /// Whether the accounts trie was repaired to match the state root of the stored head.
+    pub accounts_repaired: bool,
+}
+
+impl IntegrityReport {
+    /// Returns true if no inconsistencies were found.
+    pub fn is_consistent(&self) -> bool {
+        !self.head_missing
+            && !self.accounts_inconsistent
+            && !self.history_inconsistent
+            && self.orphaned.is_empty()
+    }
+}
+
+impl Blockchain {
+    /// Walks the chain store, the accounts trie and the history store and cross-validates their
+    /// roots and indices against the stored head.
+    ///
+    /// If `repair` is set, orphaned chain store records are repaired and the hashes of an
+    /// accounts trie that is inconsistent with the head are recomputed. If that doesn't repair the
+    /// accounts, the head is rewound to the last valid macro block when the blockchain is loaded.
+    pub fn check_integrity(&self, repair: bool) -> IntegrityReport {
+        let mut report = IntegrityReport::default();
+        let mut head_state_root = None;
+        let txn = self.read_transaction();
+
+        let head_info = self.chain_store.get_head(Some(&txn)).and_then(|head_hash| {
+            self.chain_store
+                .get_chain_info(&head_hash, false, Some(&txn))
+                .ok()
+        });
+
+        match head_info {
+            Some(head_info) => {
+                let head = &head_info.head;
+
+                // The accounts root is only known if the accounts trie is complete.
+                if let Some(accounts_hash) = self.state.accounts.get_root_hash(Some(&txn)) {
+                    if head.state_root() != &accounts_hash {
+                        error!(
+                            block_number = head.block_number(),
+                            head_state_root = %head.state_root(),
+                            %accounts_hash,
+                            "Head is inconsistent with the accounts trie"
+                        );
+                        report.accounts_inconsistent = true;
+                        head_state_root = Some(head.state_root().clone());
+                    }
+                }
+
+                // A head that was adopted without the history of its epoch (e.g. through zkp
+                // sync) cannot be verified against the history store.
+                let history_len = self
+                    .history_store
+                    .total_len_at_epoch(Policy::epoch_at(head.block_number()), Some(&txn));
+                let history_root = self
+                    .history_store
+                    .get_history_tree_root(head.block_number(), Some(&txn));
+                let has_history = history_len > 0 || head_info.history_tree_len > 0;
+                if has_history
+                    && (history_root.as_ref() != Some(head.history_root())
+                        || history_len as u64 != head_info.history_tree_len)
+                {
+                    error!(
+                        block_number = head.block_number(),
+                        head_history_root = %head.history_root(),
+                        ?history_root,
+                        head_history_len = head_info.history_tree_len,
+                        history_len,
+                        "Head is inconsistent with the history store"
+                    );
+                    report.history_inconsistent = true;
+                }
+            }
+            None => {
+                error!("Head is missing from the chain store");
+                report.head_missing = true;
+            }
+        }
+
+        report.orphaned = self.chain_store.find_orphaned_records(Some(&txn));
+        txn.close();
+
+        if let (Some(state_root), true) = (head_state_root, repair) {
+            report.accounts_repaired =
+                Blockchain::rehash_accounts(&self.db, &self.state.accounts, &state_root);
+        }
+
+        if !report.orphaned.is_empty() {
+            warn!(
+                index_entries = report.orphaned.index_entries.len(),
+                unindexed_chain_infos = report.orphaned.unindexed_chain_infos.len(),
+                blocks = report.orphaned.blocks.len(),
+                accounts_diffs = report.orphaned.accounts_diffs.len(),
+                repair,
+                "Found orphaned records in the chain store"
+            );
+
+            if repair {
+                let mut txn = self.write_transaction();
+                self.chain_store
+                    .repair_orphaned_records(&mut txn, &report.orphaned);
+                txn.commit();
+                report.repaired = true;
+            }
+        }
+
+        report
+    }
+}
@@ -1,4 +1,7 @@
-use nimiq_blockchain_interface::{PushError, PushResult};
+use nimiq_account::{Log, TransactionLog};
+use nimiq_block::Block;
+use nimiq_blockchain_interface::{BlockVeto, PushError, PushResult};
+use nimiq_transaction::inherent::Inherent;
 
 mod abstract_blockchain;
 pub mod accounts;
@@ -6,17 +9,46 @@ pub mod accounts;
 pub mod blockchain;
 pub mod history_sync;
 pub mod inherents;
+pub mod integrity;
 pub mod push;
+pub mod randomness;
 pub(super) mod rebranch_utils;
+mod records;
+mod recovery;
+pub mod replay;
 pub mod slots;
+pub mod snapshot;
 pub mod verify;
 pub mod wrappers;
 pub mod zkp_sync;
 
+/// The context of a block that passed validation and was applied to the accounts, but was not yet
+/// committed to the database.
+pub struct BlockContext<'a> {
+    /// The block that is about to be committed.
+    /// During history sync, this is the macro block being pushed and the logs and inherents
+    /// cover all blocks that are applied together with it.
+    pub block: &'a Block,
+    /// The logs of the block's transactions.
+    pub transaction_logs: &'a [TransactionLog],
+    /// The inherents created by the block.
+    pub inherents: &'a [Inherent],
+    /// The logs of the block's inherents.
+    pub inherent_logs: &'a [Log],
+}
+
 /// A post-validation hook that can be registered with the blockchain to run after block validation.
 /// For `extend` and `rebranch` operations, this is called before a block is committed to the database.
 /// It thus provides a timing advantage.
 pub trait PostValidationHook {
+    /// Inspects a block before it is committed to the database and optionally vetoes it.
+    /// This is called for every block that is applied, i.e. also for each block adopted during a
+    /// rebranch and for macro blocks pushed during history sync. A vetoed block is rejected and the
+    /// push fails with [`PushError::Vetoed`], which does not mark the block as invalid.
+    fn check_block(&self, _context: &BlockContext<'_>) -> Result<(), BlockVeto> {
+        Ok(())
+    }
+
     /// Run the post-validation hook.
     /// For `extend` and `rebranch` this is called before a block is committed to the database.
     fn post_validation(&self, push_result: Result<&PushResult, &PushError>);
@@ -28,6 +60,11 @@ impl PostValidationHook for () {
 }
 
 impl<F: PostValidationHook> PostValidationHook for Option<F> {
+    fn check_block(&self, context: &BlockContext<'_>) -> Result<(), BlockVeto> {
+        self.as_ref()
+            .map_or(Ok(()), |hook| hook.check_block(context))
+    }
+
     fn post_validation(&self, push_result: Result<&PushResult, &PushError>) {
         self.as_ref()
             .inspect(|hook| hook.post_validation(push_result));
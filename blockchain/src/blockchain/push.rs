@@ -4,7 +4,7 @@ use nimiq_account::{BlockLog, BlockLogger};
 use nimiq_block::{Block, ForkProof, MicroBlock};
 use nimiq_blockchain_interface::{
     AbstractBlockchain, BlockchainEvent, ChainInfo, ChainOrdering, ChunksPushError,
-    ChunksPushResult, ForkEvent, PushError, PushResult,
+    ChunksPushResult, ForkEvent, PushError, PushResult, ReorgEvent,
 };
 use nimiq_database::{
     mdbx::{MdbxReadTransaction, MdbxWriteTransaction},
@@ -23,8 +23,13 @@ use nimiq_trie::WriteTransactionProxy as TrieMdbxWriteTransaction;
 use parking_lot::{RwLockUpgradableReadGuard, RwLockWriteGuard};
 use tokio::sync::broadcast;
 
-use super::PostValidationHook;
-use crate::{interface::HistoryInterface, Blockchain};
+use super::{replay::RecordedInput, BlockContext, PostValidationHook};
+#[cfg(feature = "metrics")]
+use crate::chain_metrics::PushStage;
+use crate::{chain_store::PrunedForks, interface::HistoryInterface, Blockchain};
+
+/// Maximum number of epochs whose history is pruned when pushing an election block.
+const MAX_HISTORY_EPOCHS_PRUNED_PER_ELECTION: u32 = 2;
 
 fn send_vec(log_notifier: &broadcast::Sender<BlockLog>, logs: Vec<BlockLog>) {
     for log in logs {
@@ -192,6 +197,10 @@ impl Blockchain {
             this.get_missing_accounts_range(None).is_none(),
             "Should call push only for complete tries"
         );
+        this.recorder.record(|| RecordedInput::Block {
+            block: block.clone(),
+            trusted: false,
+        });
         Self::push_wrapperfn(this, block, false, None, vec![], post_validation_hook)
             .map(|res| res.0)
     }
@@ -210,6 +219,11 @@ impl Blockchain {
         chunks: Vec<TrieChunkWithStart>,
         post_validation_hook: &F,
     ) -> Result<(PushResult, Result<ChunksPushResult, ChunksPushError>), PushError> {
+        this.recorder.record(|| RecordedInput::BlockWithChunks {
+            block: block.clone(),
+            diff: diff.clone(),
+            chunks: chunks.clone(),
+        });
         Self::push_wrapperfn(this, block, false, Some(diff), chunks, post_validation_hook)
     }
 
@@ -226,6 +240,10 @@ impl Blockchain {
         block: Block,
         post_validation_hook: &F,
     ) -> Result<PushResult, PushError> {
+        this.recorder.record(|| RecordedInput::Block {
+            block: block.clone(),
+            trusted: true,
+        });
         Self::push_wrapperfn(this, block, true, None, vec![], post_validation_hook).map(|res| res.0)
     }
 
@@ -234,6 +252,18 @@ impl Blockchain {
         &self,
         chunks: Vec<TrieChunkWithStart>,
         block_hash: &Blake2bHash,
+    ) -> Result<ChunksPushResult, ChunksPushError> {
+        self.recorder.record(|| RecordedInput::Chunks {
+            block_hash: block_hash.clone(),
+            chunks: chunks.clone(),
+        });
+        self.apply_chunks(chunks, block_hash)
+    }
+
+    fn apply_chunks(
+        &self,
+        chunks: Vec<TrieChunkWithStart>,
+        block_hash: &Blake2bHash,
     ) -> Result<ChunksPushResult, ChunksPushError> {
         let state_root = self.state.main_chain.head.state_root();
         let mut chunk_result = Ok(ChunksPushResult::EmptyChunks);
@@ -247,6 +277,7 @@ impl Blockchain {
                 chunk_data.chunk,
                 chunk_data.start_key
             );
+            let start_key = chunk_data.start_key.clone();
             let result = self.state.accounts.commit_chunk(
                 &mut (&mut txn).into(),
                 chunk_data.chunk,
@@ -261,6 +292,7 @@ impl Blockchain {
                     break;
                 }
                 Ok(TrieChunkPushResult::Applied) => {
+                    self.record_applied_chunk(&mut txn, &start_key);
                     chunks_committed += 1;
                     chunk_result = Ok(ChunksPushResult::Chunks(chunks_committed, chunks_ignored));
                 }
@@ -334,8 +366,13 @@ impl Blockchain {
             block_number,
             chain_info.head.timestamp(),
         );
-        let total_tx_size =
-            this.check_and_commit(&chain_info.head, diff, &mut txn, &mut block_logger)?;
+        let total_tx_size = this.check_and_commit(
+            &chain_info.head,
+            diff,
+            &mut txn,
+            &mut block_logger,
+            post_validation_hook,
+        )?;
 
         chain_info.on_main_chain = true;
         chain_info.set_cumulative_hist_tx_size(&prev_info, total_tx_size);
@@ -350,29 +387,18 @@ impl Blockchain {
             .put_chain_info(&mut txn, chain_info.head.parent_hash(), &prev_info, false);
         this.chain_store.set_head(&mut txn, &block_hash);
 
-        if is_macro_block {
-            this.chain_store.finalize_batch(&mut txn);
-        }
+        let pruned_forks = if is_macro_block {
+            this.chain_store.finalize_batch(
+                &mut txn,
+                Policy::batch_at(block_number),
+                this.config.fork_retention_batches,
+            )
+        } else {
+            PrunedForks::default()
+        };
 
         if is_election_block {
-            let max_epochs_stored =
-                cmp::max(this.config.max_epochs_stored, Policy::MIN_EPOCHS_STORED);
-
-            // Calculate the epoch to be pruned.
-            let pruned_epoch = Policy::epoch_at(block_number).checked_sub(max_epochs_stored);
-
-            if let Some(pruned_epoch) = pruned_epoch {
-                // Prune the Chain Store.
-                this.chain_store.prune_epoch(pruned_epoch, &mut txn);
-
-                if !this.config.keep_history {
-                    // Prune the History Store.
-                    // We will never prune pre-genesis data here.
-                    if pruned_epoch > 0 {
-                        this.history_store.remove_history(&mut txn, pruned_epoch);
-                    }
-                }
-            }
+            this.prune_epochs(&mut txn, Policy::epoch_at(block_number));
         }
 
         // Call the post-validation hook before commiting to the database.
@@ -403,11 +429,21 @@ impl Blockchain {
         let this = RwLockWriteGuard::downgrade_to_upgradable(this);
 
         // Try to apply any chunks we received.
-        let chunk_result = this.commit_chunks(chunks, &block_hash);
+        let chunk_result = this.apply_chunks(chunks, &block_hash);
         let duration = start.elapsed();
 
         #[cfg(feature = "metrics")]
-        this.metrics.note_extend(&this.state.main_chain.head);
+        {
+            this.metrics.note_extend(&this.state.main_chain.head);
+            this.metrics.note_pruned_forks(&pruned_forks);
+        }
+        if pruned_forks.num_blocks > 0 {
+            debug!(
+                num_blocks = pruned_forks.num_blocks,
+                num_bytes = pruned_forks.num_bytes,
+                "Pruned forked blocks",
+            );
+        }
         debug!(
             block = %this.state.main_chain.head,
             num_transactions = this.state.main_chain.head.num_transactions(),
@@ -416,6 +452,9 @@ impl Blockchain {
             "Accepted block",
         );
 
+        #[cfg(feature = "metrics")]
+        let events_start = Instant::now();
+
         // We shouldn't log errors if there are no listeners.
         this.notifier
             .send(BlockchainEvent::Extended(block_hash.clone()))
@@ -436,6 +475,10 @@ impl Blockchain {
             .send(block_logger.build(total_tx_size))
             .ok();
 
+        #[cfg(feature = "metrics")]
+        this.metrics
+            .observe_push_stage(PushStage::EventsDispatch, events_start.elapsed());
+
         Ok((PushResult::Extended, chunk_result))
     }
 
@@ -467,29 +510,37 @@ impl Blockchain {
         );
 
         let mut write_txn = this.write_transaction();
-        let (revert_chain, block_logs) =
-            match this.rebranch_to(&mut fork_chain, &mut ancestor, &mut write_txn) {
-                Ok(r) => r,
-                Err(remove_chain) => {
-                    // Failed to apply blocks. All blocks within remove chain must be removed.
-                    // To do that the txn must be aborted first, as the changes need to be undone first.
-                    write_txn.abort();
-
-                    // Delete invalid fork blocks from store.
-                    // Create a new write transaction which will be committed.
-                    let mut write_txn = this.write_transaction();
-                    for block in remove_chain {
-                        this.chain_store.remove_chain_info(
-                            &mut write_txn,
-                            &block.0,
-                            block.1.head.block_number(),
-                        );
-                    }
-                    write_txn.commit();
-
-                    return Err(PushError::InvalidFork);
+        let (revert_chain, block_logs) = match this.rebranch_to(
+            &mut fork_chain,
+            &mut ancestor,
+            &mut write_txn,
+            post_validation_hook,
+        ) {
+            Ok(r) => r,
+            Err((remove_chain, error)) => {
+                // Failed to apply blocks. All blocks within remove chain must be removed.
+                // To do that the txn must be aborted first, as the changes need to be undone first.
+                write_txn.abort();
+
+                // Delete invalid fork blocks from store.
+                // Create a new write transaction which will be committed.
+                let mut write_txn = this.write_transaction();
+                for block in remove_chain {
+                    this.chain_store.remove_chain_info(
+                        &mut write_txn,
+                        &block.0,
+                        block.1.head.block_number(),
+                    );
                 }
-            };
+                write_txn.commit();
+
+                // A vetoed fork is not invalid, so the veto is passed on as is.
+                return Err(match error {
+                    PushError::Vetoed(veto) => PushError::Vetoed(veto),
+                    _ => PushError::InvalidFork,
+                });
+            }
+        };
 
         // Commit transaction & update head.
         let new_head_hash = &fork_chain[0].0;
@@ -524,7 +575,7 @@ impl Blockchain {
         let this = RwLockWriteGuard::downgrade_to_upgradable(this);
 
         // Try to apply any chunks we received.
-        let chunk_result = this.commit_chunks(chunks, new_head_hash);
+        let chunk_result = this.apply_chunks(chunks, new_head_hash);
 
         let mut reverted_blocks = Vec::with_capacity(revert_chain.len());
         for (hash, chain_info) in revert_chain.into_iter().rev() {
@@ -558,10 +609,26 @@ impl Blockchain {
         this.metrics
             .note_rebranch(&reverted_blocks, &adopted_blocks);
 
+        let reorg_event = ReorgEvent {
+            reverted: reverted_blocks
+                .iter()
+                .map(|(hash, _)| hash.clone())
+                .collect(),
+            applied: adopted_blocks
+                .iter()
+                .map(|(hash, _)| hash.clone())
+                .collect(),
+            common_ancestor: ancestor.0,
+        };
+
+        #[cfg(feature = "metrics")]
+        let events_start = Instant::now();
+
         // We do not log errors if there are no listeners.
         this.notifier
             .send(BlockchainEvent::Rebranched(reverted_blocks, adopted_blocks))
             .ok();
+        this.reorg_notifier.send(reorg_event).ok();
         if this.state.main_chain.head.is_election() {
             this.notifier
                 .send(BlockchainEvent::EpochFinalized(
@@ -576,16 +643,24 @@ impl Blockchain {
 
         send_vec(&this.log_notifier, block_logs);
 
+        #[cfg(feature = "metrics")]
+        this.metrics
+            .observe_push_stage(PushStage::EventsDispatch, events_start.elapsed());
+
         Ok((PushResult::Rebranched, chunk_result))
     }
 
-    pub(super) fn check_and_commit(
+    pub(super) fn check_and_commit<F: PostValidationHook>(
         &self,
         block: &Block,
         diff: Option<TrieDiff>,
         txn: &mut MdbxWriteTransaction,
         block_logger: &mut BlockLogger,
+        post_validation_hook: &F,
     ) -> Result<u64, PushError> {
+        #[cfg(feature = "metrics")]
+        let start = Instant::now();
+
         // Check transactions against replay attacks. This is only necessary for micro blocks.
         if block.is_micro() {
             let transactions = block.transactions();
@@ -615,13 +690,14 @@ impl Blockchain {
 
         // Commit block to AccountsTree.
         let total_tx_size;
+        let inherents;
         {
             let is_complete = self.state.accounts.is_complete(Some(txn));
             let mut txn: TrieMdbxWriteTransaction = txn.into();
             if is_complete {
                 txn.start_recording();
             }
-            total_tx_size = self.commit_accounts(block, diff, &mut txn, block_logger).inspect_err(|e| {
+            (total_tx_size, inherents) = self.commit_accounts(block, diff, &mut txn, block_logger).inspect_err(|e| {
                 warn!(%block, reason = "commit failed", error = e as &dyn Error, "Rejecting block");
                 #[cfg(feature = "metrics")]
                 self.metrics.note_invalid_block();
@@ -639,9 +715,112 @@ impl Blockchain {
             return Err(e);
         }
 
+        #[cfg(feature = "metrics")]
+        self.metrics
+            .observe_push_stage(PushStage::StateCommit, start.elapsed());
+
+        // Give the hook the chance to veto the block now that its effects are known.
+        let block_log = block_logger.block_log();
+        let context = BlockContext {
+            block,
+            transaction_logs: block_log.transaction_logs(),
+            inherents: &inherents,
+            inherent_logs: block_log.inherent_logs(),
+        };
+        if let Err(veto) = post_validation_hook.check_block(&context) {
+            warn!(%block, reason = "vetoed", %veto, "Rejecting block");
+            return Err(PushError::Vetoed(veto));
+        }
+
         Ok(total_tx_size)
     }
 
+    /// Prunes the chain store and the history store once the election block of the given epoch
+    /// was pushed.
+    ///
+    /// The chain store keeps `max_epochs_stored` epochs. The history is pruned along with it
+    /// unless `keep_history` is set, in which case it is kept for `history_retention_epochs` or
+    /// forever if no retention window is configured.
+    pub(super) fn prune_epochs(&self, txn: &mut MdbxWriteTransaction, epoch_number: u32) {
+        let max_epochs_stored = cmp::max(self.config.max_epochs_stored, Policy::MIN_EPOCHS_STORED);
+
+        // Prune the Chain Store.
+        if let Some(pruned_epoch) = epoch_number.checked_sub(max_epochs_stored) {
+            self.chain_store.prune_epoch(pruned_epoch, txn);
+        }
+
+        // Prune the History Store. Besides the epoch that just left the retention window, this
+        // catches up on at most one more epoch in case older epochs are still stored.
+        self.prune_history(txn, epoch_number, MAX_HISTORY_EPOCHS_PRUNED_PER_ELECTION);
+    }
+
+    /// Prunes the history of all epochs that are outside of the retention window in chunks of
+    /// one epoch per write transaction. Older epochs remain stored if the retention window was
+    /// shrunk, so they are pruned on startup instead of within the transaction of a single block.
+    pub(super) fn prune_history_backlog(&self) {
+        let epoch_number = Policy::epoch_at(self.state.election_head.block_number());
+        loop {
+            let mut txn = self.write_transaction();
+            let done = self.prune_history(&mut txn, epoch_number, 1);
+            txn.commit();
+            if done {
+                break;
+            }
+        }
+    }
+
+    /// Prunes the history of up to `max_epochs` of the oldest stored epochs that are outside of
+    /// the retention window at the given epoch. Returns `true` if no such epoch remains.
+    fn prune_history(
+        &self,
+        txn: &mut MdbxWriteTransaction,
+        epoch_number: u32,
+        max_epochs: u32,
+    ) -> bool {
+        let history_epochs_stored = if self.config.keep_history {
+            match self.config.history_retention_epochs {
+                Some(epochs) => cmp::max(epochs, Policy::MIN_EPOCHS_STORED),
+                None => return true,
+            }
+        } else {
+            cmp::max(self.config.max_epochs_stored, Policy::MIN_EPOCHS_STORED)
+        };
+
+        // We will never prune pre-genesis data here.
+        let Some(pruned_epoch) = epoch_number
+            .checked_sub(history_epochs_stored)
+            .filter(|epoch| *epoch > 0)
+        else {
+            return true;
+        };
+
+        // The stored epochs are contiguous, so walk back until we find an epoch that has been
+        // pruned already and prune the oldest epochs first.
+        let mut oldest_epoch = pruned_epoch + 1;
+        while oldest_epoch > 1
+            && self
+                .history_store
+                .total_len_at_epoch(oldest_epoch - 1, Some(txn))
+                > 0
+        {
+            oldest_epoch -= 1;
+        }
+        if oldest_epoch > pruned_epoch {
+            return true;
+        }
+
+        let last_epoch = cmp::min(pruned_epoch, oldest_epoch + max_epochs - 1);
+        for epoch in oldest_epoch..=last_epoch {
+            self.history_store.remove_history(txn, epoch);
+        }
+        debug!(
+            from_epoch = oldest_epoch,
+            to_epoch = last_epoch,
+            "Pruned history"
+        );
+        last_epoch == pruned_epoch
+    }
+
     fn detect_forks(
         &self,
         txn: &MdbxReadTransaction,
@@ -0,0 +1,42 @@
+use nimiq_block::{Block, RandomnessProof};
+use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainError};
+use nimiq_primitives::policy::Policy;
+
+use crate::Blockchain;
+
+impl Blockchain {
+    /// Returns the proof of the randomness beacon output of the finalized block at the given
+    /// block number. The proof can be verified against the election block preceding the batch of
+    /// the block, see [`RandomnessProof::verify`].
+    pub fn get_randomness_proof(
+        &self,
+        block_number: u32,
+    ) -> Result<RandomnessProof, BlockchainError> {
+        let macro_block_number = if Policy::is_macro_block_at(block_number) {
+            block_number
+        } else {
+            Policy::macro_block_after(block_number)
+        };
+        if macro_block_number > self.macro_head().block_number() {
+            return Err(BlockchainError::BlockNotFinalized(block_number));
+        }
+
+        let txn = self.read_transaction();
+        let mut micro_headers = Vec::new();
+        for block_number in block_number..macro_block_number {
+            match self.get_block_at(block_number, false, Some(&txn))? {
+                Block::Micro(block) => micro_headers.push(block.header),
+                Block::Macro(_) => return Err(BlockchainError::InconsistentState),
+            }
+        }
+        let macro_block = self
+            .get_block_at(macro_block_number, false, Some(&txn))?
+            .unwrap_macro();
+        txn.close();
+
+        Ok(RandomnessProof {
+            micro_headers,
+            macro_block,
+        })
+    }
+}
@@ -6,7 +6,7 @@ use nimiq_database::mdbx::{MdbxReadTransaction, MdbxWriteTransaction};
 use nimiq_hash::Blake2bHash;
 use nimiq_primitives::trie::trie_diff::TrieDiff;
 
-use crate::Blockchain;
+use crate::{Blockchain, PostValidationHook};
 
 impl Blockchain {
     /// Finds the common ancestor between the current main chain in the context of `txn` and the fork chain given by
@@ -81,16 +81,18 @@ impl Blockchain {
     /// After that applies all blocks given as target_chain in reverse order or until a block fails
     /// to be applied.
     ///
-    /// Returns the reverted chain as `.1` and the block logs as `.2` or the blocks which are on a faulty fork.
+    /// Returns the reverted chain as `.1` and the block logs as `.2` or the blocks which are on a faulty fork
+    /// together with the reason why they could not be applied.
     /// It does _not_ deal with the faulty blocks.
-    pub(super) fn rebranch_to(
+    pub(super) fn rebranch_to<F: PostValidationHook>(
         &self,
         target_chain: &mut [(Blake2bHash, ChainInfo, Option<TrieDiff>)],
         ancestor: &mut (Blake2bHash, ChainInfo, Option<TrieDiff>),
         write_txn: &mut MdbxWriteTransaction,
+        post_validation_hook: &F,
     ) -> Result<
         (Vec<(Blake2bHash, ChainInfo)>, Vec<BlockLog>),
-        Vec<(Blake2bHash, ChainInfo, Option<TrieDiff>)>,
+        (Vec<(Blake2bHash, ChainInfo, Option<TrieDiff>)>, PushError),
     > {
         // Keeps track of the currently investigated block
         let mut current = (self.state.head_hash.clone(), self.state.main_chain.clone());
@@ -128,7 +130,7 @@ impl Blockchain {
                             "Failed to revert chunk while rebranching",
                         );
                         // The revert failed, but there are no blocks to remove.
-                        vec![]
+                        (vec![], PushError::InvalidFork)
                     })?;
             }
 
@@ -200,6 +202,7 @@ impl Blockchain {
                 block.2.clone(),
                 write_txn,
                 &mut block_logger,
+                post_validation_hook,
             ) {
                 Ok(total_tx_size)
                     // push the logs into the logs collection
@@ -222,7 +225,7 @@ impl Blockchain {
                         .into_iter()
                         .chain(target_chain_iter.cloned())
                         .collect();
-                    return Err(remove_chain);
+                    return Err((remove_chain, e));
                 }
             }
         }
@@ -0,0 +1,53 @@
+use std::io::{self, Read, Write};
+
+use nimiq_serde::{Deserialize, DeserializeError, Serialize};
+
+/// Writes a length-prefixed record.
+pub(super) fn write_record<W: Write + ?Sized, T: Serialize>(
+    writer: &mut W,
+    record: &T,
+) -> io::Result<()> {
+    let bytes = record.serialize_to_vec();
+    writer.write_all(&(bytes.len() as u32).to_be_bytes())?;
+    writer.write_all(&bytes)
+}
+
+/// Reads a length-prefixed record.
+pub(super) fn read_record<R: Read, T: Deserialize, E>(reader: &mut R) -> Result<T, E>
+where
+    E: From<io::Error> + From<DeserializeError>,
+{
+    read_optional_record(reader)?
+        .ok_or_else(|| io::Error::from(io::ErrorKind::UnexpectedEof).into())
+}
+
+/// Reads a length-prefixed record. Returns `None` if the reader is exhausted before the record
+/// starts.
+pub(super) fn read_optional_record<R: Read, T: Deserialize, E>(
+    reader: &mut R,
+) -> Result<Option<T>, E>
+where
+    E: From<io::Error> + From<DeserializeError>,
+{
+    let mut len = [0u8; 4];
+    let mut read = 0;
+    while read < len.len() {
+        match reader.read(&mut len[read..]) {
+            Ok(0) if read == 0 => return Ok(None),
+            Ok(0) => return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into()),
+            Ok(n) => read += n,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+            Err(error) => return Err(error.into()),
+        }
+    }
+    let len = u32::from_be_bytes(len) as usize;
+
+    // Don't trust the length for the allocation.
+    let mut bytes = Vec::new();
+    reader.take(len as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != len {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into());
+    }
+
+    Ok(Some(T::deserialize_all(&bytes)?))
+}
@@ -0,0 +1,171 @@
+use nimiq_account::Accounts;
+use nimiq_blockchain_interface::BlockchainError;
+use nimiq_database::{
+    mdbx::MdbxDatabase,
+    traits::{Database, WriteTransaction},
+};
+use nimiq_hash::Blake2bHash;
+use nimiq_primitives::policy::Policy;
+
+use crate::{
+    chain_store::ChainStore, history_store_proxy::MergedHistoryStoreProxy,
+    interface::HistoryInterface, Blockchain, BlockchainConfig,
+};
+
+impl Blockchain {
+    /// Recomputes the hashes of the accounts trie from the stored accounts and keeps them if the
+    /// root hash then matches the given state root. This repairs corrupted hashes in the trie
+    /// without having to discard any blocks. Returns whether the accounts were repaired.
+    pub(super) fn rehash_accounts(
+        env: &MdbxDatabase,
+        accounts: &Accounts,
+        state_root: &Blake2bHash,
+    ) -> bool {
+        let mut txn = env.write_transaction();
+        if accounts.tree.rehash(&mut (&mut txn).into()).is_err()
+            || accounts.get_root_hash(Some(&txn)).as_ref() != Some(state_root)
+        {
+            txn.abort();
+            return false;
+        }
+        txn.commit();
+
+        log::warn!(%state_root, "Repaired the accounts state by recomputing its hashes");
+        true
+    }
+
+    /// Checks the consistency of the stored head and rewinds the chain to the last valid macro
+    /// block if the head is corrupted, e.g. after a crash or disk failure. The discarded blocks
+    /// are synced again once the node is connected to the network.
+    ///
+    /// The head is considered corrupted if its chain info or body is missing or if its state root
+    /// does not match the accounts trie.
+    ///
+    /// Returns the hash of the head to load the blockchain from.
+    pub(super) fn recover_head(
+        env: &MdbxDatabase,
+        config: &BlockchainConfig,
+        chain_store: &ChainStore,
+        history_store: &MergedHistoryStoreProxy,
+        head_hash: Blake2bHash,
+    ) -> Result<Blake2bHash, BlockchainError> {
+        let accounts = Accounts::new(env.clone());
+        let accounts_hash = accounts.get_root_hash(None);
+
+        let head_block_number = match chain_store.get_chain_info(&head_hash, true, None) {
+            Ok(head_info) => {
+                match &accounts_hash {
+                    Some(accounts_hash) if head_info.head.state_root() != accounts_hash => {
+                        log::error!(
+                            block_number = head_info.head.block_number(),
+                            head_state_root = %head_info.head.state_root(),
+                            %accounts_hash,
+                            "Main chain head is inconsistent with the accounts state",
+                        );
+                        if Blockchain::rehash_accounts(env, &accounts, head_info.head.state_root())
+                        {
+                            return Ok(head_hash);
+                        }
+                    }
+                    // The head is consistent, nothing to recover.
+                    _ => return Ok(head_hash),
+                }
+                head_info.head.block_number()
+            }
+            Err(error) => {
+                // Without the head's chain info, we do not know where the chain ends. Start at the
+                // last block in the history store and follow the main chain blocks stored after it.
+                log::error!(%head_hash, %error, "Main chain head is missing or incomplete");
+                let (_, mut block_number) = history_store.history_store_range(None);
+                while chain_store
+                    .get_chain_info_at(block_number + 1, false, None)
+                    .is_ok()
+                {
+                    block_number += 1;
+                }
+                block_number
+            }
+        };
+
+        if head_block_number <= Policy::genesis_block_number() {
+            return Err(BlockchainError::InconsistentState);
+        }
+
+        // Find the latest macro block on the main chain before the head that is fully stored.
+        let mut block_number = Policy::last_macro_block(head_block_number - 1);
+        let (target_hash, mut target_info) = loop {
+            if let Some(target) = chain_store
+                .get_chain_info_at(block_number, false, None)
+                .ok()
+                .map(|chain_info| chain_info.head.hash())
+                .and_then(|hash| {
+                    let chain_info = chain_store.get_chain_info(&hash, true, None).ok()?;
+                    Some((hash, chain_info))
+                })
+            {
+                break target;
+            }
+
+            if block_number <= Policy::genesis_block_number() {
+                log::error!("Could not find a valid macro block to rewind to");
+                return Err(BlockchainError::InconsistentState);
+            }
+            block_number = Policy::last_macro_block(block_number - 1);
+        };
+
+        // The accounts can only be kept if they match the state of the macro block. Otherwise,
+        // the state is synced again, which is only possible for nodes that do not keep the history.
+        let keep_accounts = accounts_hash.as_ref() == Some(target_info.head.state_root());
+        if !keep_accounts && config.keep_history {
+            log::error!(
+                block_number,
+                "Cannot recover the accounts state of a history node, resync from scratch"
+            );
+            return Err(BlockchainError::InconsistentState);
+        }
+
+        log::warn!(
+            from = head_block_number,
+            to = block_number,
+            %target_hash,
+            reset_accounts = !keep_accounts,
+            "Rewinding corrupted main chain to the last valid macro block",
+        );
+
+        let mut txn = env.write_transaction();
+
+        // Discard the main chain blocks after the macro block.
+        for discarded in (block_number + 1)..=head_block_number {
+            if let Ok(chain_info) = chain_store.get_chain_info_at(discarded, false, Some(&txn)) {
+                chain_store.remove_chain_info(&mut txn, &chain_info.head.hash(), discarded);
+            }
+        }
+
+        // Discard the history after the macro block.
+        let epoch_number = Policy::epoch_at(block_number + 1);
+        for discarded_epoch in (epoch_number + 1)..=Policy::epoch_at(head_block_number) {
+            history_store.remove_history(&mut txn, discarded_epoch);
+        }
+        if Policy::is_election_block_at(block_number) {
+            history_store.remove_history(&mut txn, epoch_number);
+        } else {
+            let history_len = history_store.total_len_at_epoch(epoch_number, Some(&txn));
+            let num_hist_txs = history_len.saturating_sub(target_info.history_tree_len as usize);
+            if num_hist_txs > 0 {
+                history_store.remove_partial_history(&mut txn, epoch_number, num_hist_txs);
+            }
+        }
+
+        if !keep_accounts {
+            accounts.reinitialize_as_incomplete(&mut (&mut txn).into());
+        }
+
+        target_info.main_chain_successor = None;
+        chain_store.put_chain_info(&mut txn, &target_hash, &target_info, false);
+        chain_store.set_head(&mut txn, &target_hash);
+
+        txn.commit();
+
+        Ok(target_hash)
+    }
+}
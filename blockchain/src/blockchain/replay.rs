@@ -0,0 +1,250 @@
+use std::io::{self, Read, Write};
+
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use nimiq_block::Block;
+use nimiq_blockchain_interface::{
+    AbstractBlockchain, ChunksPushError, ChunksPushResult, PushError, PushResult,
+};
+use nimiq_hash::Blake2bHash;
+use nimiq_primitives::trie::{trie_chunk::TrieChunkWithStart, trie_diff::TrieDiff};
+use nimiq_serde::{Deserialize, DeserializeError, Serialize};
+use nimiq_transaction::historic_transaction::HistoricTransaction;
+use nimiq_zkp::NanoProof;
+use parking_lot::{Mutex, RwLock};
+use thiserror::Error;
+
+use super::records::{self, write_record};
+use crate::Blockchain;
+
+/// The header of a recording. It is followed by the [`RecordedInput`]s in the order in which they
+/// were applied to the blockchain.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordingHeader {
+    /// The version of the recording format.
+    pub version: u8,
+    /// The hash of the genesis block of the recorded blockchain.
+    pub genesis_hash: Blake2bHash,
+    /// The hash of the head when the recording started.
+    pub head_hash: Blake2bHash,
+    /// The block number of the head when the recording started.
+    pub head_block_number: u32,
+}
+
+impl RecordingHeader {
+    /// The current version of the recording format.
+    pub const VERSION: u8 = 2;
+}
+
+/// An input applied to a blockchain.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum RecordedInput {
+    /// A block pushed with [`Blockchain::push`], or [`Blockchain::trusted_push`] if `trusted` is set.
+    Block { block: Block, trusted: bool },
+    /// A block pushed with [`Blockchain::push_with_chunks`].
+    BlockWithChunks {
+        block: Block,
+        diff: TrieDiff,
+        chunks: Vec<TrieChunkWithStart>,
+    },
+    /// Chunks committed with [`Blockchain::commit_chunks`].
+    Chunks {
+        block_hash: Blake2bHash,
+        chunks: Vec<TrieChunkWithStart>,
+    },
+    /// A macro block pushed with [`Blockchain::push_history_sync`].
+    HistorySync {
+        block: Block,
+        history: Vec<HistoricTransaction>,
+    },
+    /// Historic transactions added with [`Blockchain::extend_validity_sync`].
+    ValiditySync { history: Vec<HistoricTransaction> },
+    /// A macro block pushed with [`Blockchain::push_macro`].
+    Macro { block: Block },
+    /// An election block pushed with [`Blockchain::push_zkp`] together with the compressed
+    /// proof, which is verified again on replay.
+    Zkp { block: Block, proof: Vec<u8> },
+    /// An election block pushed with [`Blockchain::update_previous_slots`].
+    PreviousSlots { block: Block },
+}
+
+/// The result of replaying a single input.
+#[derive(Debug)]
+pub enum ReplayResult {
+    Push(Result<PushResult, PushError>),
+    PushWithChunks(Result<(PushResult, Result<ChunksPushResult, ChunksPushError>), PushError>),
+    Chunks(Result<ChunksPushResult, ChunksPushError>),
+    ValiditySync(Blake2bHash),
+}
+
+#[derive(Debug, Error)]
+pub enum ReplayError {
+    #[error("Unsupported recording version {0}")]
+    UnsupportedVersion(u8),
+    #[error("Recording is for genesis block {0}, but the blockchain has genesis block {1}")]
+    GenesisMismatch(Blake2bHash, Blake2bHash),
+    #[error("Recording starts at block {0}, but the head is {1}")]
+    HeadMismatch(Blake2bHash, Blake2bHash),
+    #[error("Malformed recording: {0}")]
+    Deserialize(#[from] DeserializeError),
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// Writes the inputs applied to a blockchain to a recording, if recording is enabled.
+#[derive(Default)]
+pub(crate) struct BlockchainRecorder {
+    writer: Mutex<Option<Box<dyn Write + Send>>>,
+}
+
+impl RecordedInput {
+    /// Creates the input for an election block pushed with the given zk proof.
+    pub(crate) fn zkp(block: &Block, proof: &NanoProof) -> Self {
+        let mut serialized_proof = Vec::with_capacity(proof.compressed_size());
+        proof
+            .serialize_compressed(&mut serialized_proof)
+            .expect("Serializing a proof into a vector can't fail");
+        RecordedInput::Zkp {
+            block: block.clone(),
+            proof: serialized_proof,
+        }
+    }
+}
+
+impl BlockchainRecorder {
+    /// Records the input returned by `input`, which is only called if recording is enabled.
+    /// Recording is stopped if the input can't be written.
+    pub(crate) fn record<F: FnOnce() -> RecordedInput>(&self, input: F) {
+        let mut writer_guard = self.writer.lock();
+        let Some(writer) = writer_guard.as_mut() else {
+            return;
+        };
+
+        // Flush every input such that the recording is usable even if the node crashes.
+        if let Err(error) = write_record(writer, &input()).and_then(|_| writer.flush()) {
+            error!(%error, "Failed to record blockchain input, stopping the recording");
+            *writer_guard = None;
+        }
+    }
+}
+
+impl Blockchain {
+    /// Starts recording all inputs applied to this blockchain to `writer`, replacing any
+    /// previous recording.
+    ///
+    /// The recording can be replayed with [`replay`](Self::replay) into a blockchain that is at
+    /// the head at which the recording started, e.g. a fresh instance if the recording was
+    /// started at genesis.
+    pub fn start_recording<W: Write + Send + 'static>(
+        &self,
+        mut writer: W,
+    ) -> io::Result<RecordingHeader> {
+        let header = RecordingHeader {
+            version: RecordingHeader::VERSION,
+            genesis_hash: self.genesis_hash.clone(),
+            head_hash: self.head_hash(),
+            head_block_number: self.block_number(),
+        };
+        write_record(&mut writer, &header)?;
+        writer.flush()?;
+
+        *self.recorder.writer.lock() = Some(Box::new(writer));
+        info!(head = %header.head_hash, "Started recording blockchain inputs");
+        Ok(header)
+    }
+
+    /// Stops recording the inputs applied to this blockchain.
+    pub fn stop_recording(&self) {
+        if let Some(mut writer) = self.recorder.writer.lock().take() {
+            if let Err(error) = writer.flush() {
+                error!(%error, "Failed to flush the recording");
+            }
+            info!("Stopped recording blockchain inputs");
+        }
+    }
+
+    /// Replays a recording created by [`start_recording`](Self::start_recording) into the given
+    /// blockchain, which must be at the head at which the recording started.
+    ///
+    /// The inputs are applied in the order they were recorded, and `on_input` is called with every
+    /// input and the result of applying it. Replaying a recording into two blockchains at the same
+    /// head leads to the same state.
+    pub fn replay<R: Read, F: FnMut(&RecordedInput, &ReplayResult)>(
+        this: &RwLock<Blockchain>,
+        mut reader: R,
+        mut on_input: F,
+    ) -> Result<RecordingHeader, ReplayError> {
+        let header = records::read_record::<_, RecordingHeader, ReplayError>(&mut reader)?;
+        if header.version != RecordingHeader::VERSION {
+            return Err(ReplayError::UnsupportedVersion(header.version));
+        }
+        {
+            let blockchain = this.read();
+            if header.genesis_hash != blockchain.genesis_hash {
+                return Err(ReplayError::GenesisMismatch(
+                    header.genesis_hash,
+                    blockchain.genesis_hash.clone(),
+                ));
+            }
+            if header.head_hash != blockchain.head_hash() {
+                return Err(ReplayError::HeadMismatch(
+                    header.head_hash,
+                    blockchain.head_hash(),
+                ));
+            }
+        }
+
+        while let Some(input) =
+            records::read_optional_record::<_, RecordedInput, ReplayError>(&mut reader)?
+        {
+            let result = Self::apply_input(this, input.clone());
+            on_input(&input, &result);
+        }
+
+        Ok(header)
+    }
+
+    fn apply_input(this: &RwLock<Blockchain>, input: RecordedInput) -> ReplayResult {
+        match input {
+            RecordedInput::Block {
+                block,
+                trusted: false,
+            } => ReplayResult::Push(Blockchain::push(this.upgradable_read(), block)),
+            RecordedInput::Block {
+                block,
+                trusted: true,
+            } => ReplayResult::Push(Blockchain::trusted_push(this.upgradable_read(), block, &())),
+            RecordedInput::BlockWithChunks {
+                block,
+                diff,
+                chunks,
+            } => ReplayResult::PushWithChunks(Blockchain::push_with_chunks(
+                this.upgradable_read(),
+                block,
+                diff,
+                chunks,
+                &(),
+            )),
+            RecordedInput::Chunks { block_hash, chunks } => {
+                ReplayResult::Chunks(this.read().commit_chunks(chunks, &block_hash))
+            }
+            RecordedInput::HistorySync { block, history } => ReplayResult::Push(
+                Blockchain::push_history_sync(this.upgradable_read(), block, &history),
+            ),
+            RecordedInput::ValiditySync { history } => ReplayResult::ValiditySync(
+                Blockchain::extend_validity_sync(this.upgradable_read(), &history),
+            ),
+            RecordedInput::Macro { block } => {
+                ReplayResult::Push(Blockchain::push_macro(this.upgradable_read(), block))
+            }
+            RecordedInput::Zkp { block, proof } => {
+                ReplayResult::Push(match NanoProof::deserialize_compressed(&proof[..]) {
+                    Ok(proof) => Blockchain::push_zkp(this.upgradable_read(), block, proof, false),
+                    Err(_) => Err(PushError::InvalidZKP),
+                })
+            }
+            RecordedInput::PreviousSlots { block } => ReplayResult::Push(
+                Blockchain::update_previous_slots(this.upgradable_read(), block),
+            ),
+        }
+    }
+}
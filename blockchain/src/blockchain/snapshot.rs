@@ -0,0 +1,217 @@
+use std::io::{self, Read, Write};
+
+use nimiq_account::BlockLogger;
+use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainError, PushError};
+use nimiq_database::{mdbx::MdbxReadTransaction, traits::WriteTransaction};
+use nimiq_hash::Blake2bHash;
+use nimiq_primitives::{
+    account::AccountError,
+    key_nibbles::KeyNibbles,
+    policy::Policy,
+    trie::trie_chunk::{TrieChunk, TrieChunkPushResult},
+};
+use nimiq_serde::{Deserialize, DeserializeError, Serialize};
+use thiserror::Error;
+
+use super::records::{self, write_record};
+use crate::Blockchain;
+
+/// The header of a state snapshot. It is followed by the trie chunks of the accounts trie in
+/// ascending key order, the last of which has no end key.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateSnapshotHeader {
+    /// The version of the snapshot format.
+    pub version: u8,
+    /// The hash of the macro block the snapshot was taken at.
+    pub block_hash: Blake2bHash,
+    /// The block number of the macro block the snapshot was taken at.
+    pub block_number: u32,
+    /// The state root of the macro block, against which every chunk can be verified.
+    pub state_root: Blake2bHash,
+}
+
+impl StateSnapshotHeader {
+    /// The current version of the snapshot format.
+    pub const VERSION: u8 = 1;
+}
+
+#[derive(Debug, Error)]
+pub enum StateSnapshotError {
+    #[error("Block {0} is not the current macro head")]
+    UnavailableBlock(Blake2bHash),
+    #[error("The accounts trie is incomplete")]
+    IncompleteState,
+    #[error("Unsupported snapshot version {0}")]
+    UnsupportedVersion(u8),
+    #[error("Snapshot was taken at block {0}, but the head is {1}")]
+    HeadMismatch(Blake2bHash, Blake2bHash),
+    #[error("Snapshot state root doesn't match the state root of the head")]
+    StateRootMismatch,
+    #[error("Chunk doesn't continue the previous chunk")]
+    UnexpectedChunk,
+    #[error("Invalid chunk: {0}")]
+    InvalidChunk(#[from] AccountError),
+    #[error("Failed to revert to the macro head: {0}")]
+    Revert(#[from] PushError),
+    #[error(transparent)]
+    Blockchain(#[from] BlockchainError),
+    #[error("Malformed snapshot: {0}")]
+    Deserialize(#[from] DeserializeError),
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+}
+
+impl Blockchain {
+    /// Writes a snapshot of the accounts trie at the given macro block to `writer`.
+    ///
+    /// Only the current macro head can be exported. If the head is a micro block, the blocks after
+    /// the macro head are reverted in a database transaction that is never committed.
+    /// The snapshot consists of a [`StateSnapshotHeader`] followed by the trie chunks of the
+    /// accounts trie, each of which comes with a proof against the state root of the block.
+    pub fn export_state_snapshot<W: Write>(
+        &self,
+        block_hash: &Blake2bHash,
+        mut writer: W,
+    ) -> Result<StateSnapshotHeader, StateSnapshotError> {
+        if *block_hash != self.state.macro_head_hash {
+            return Err(StateSnapshotError::UnavailableBlock(block_hash.clone()));
+        }
+
+        let header = StateSnapshotHeader {
+            version: StateSnapshotHeader::VERSION,
+            block_hash: block_hash.clone(),
+            block_number: self.state.macro_info.head.block_number(),
+            state_root: self.state.macro_info.head.state_root().clone(),
+        };
+
+        if self.state.head_hash == self.state.macro_head_hash {
+            let txn = self.read_transaction();
+            self.write_state_snapshot(&txn, &header, &mut writer)?;
+            return Ok(header);
+        }
+
+        // Revert the micro blocks on top of the macro head. The transaction is dropped without
+        // being committed, which leaves the actual state untouched.
+        let mut txn = self.write_transaction();
+        let mut current = (self.state.head_hash.clone(), self.state.main_chain.clone());
+        while current.0 != self.state.macro_head_hash {
+            // Chunks committed after the macro block can't be reverted here.
+            if current.1.prev_missing_range.is_some() {
+                return Err(StateSnapshotError::IncompleteState);
+            }
+
+            let block = &current.1.head;
+            let mut block_logger =
+                BlockLogger::new_reverted(current.0.clone(), block.block_number());
+            self.revert_accounts(
+                &self.state.accounts,
+                &mut (&mut txn).into(),
+                block,
+                &mut block_logger,
+            )?;
+
+            let prev_hash = block.parent_hash().clone();
+            let prev_info = self
+                .chain_store
+                .get_chain_info(&prev_hash, true, Some(&txn))?;
+            current = (prev_hash, prev_info);
+        }
+
+        self.write_state_snapshot(&txn, &header, &mut writer)?;
+        Ok(header)
+    }
+
+    fn write_state_snapshot<W: Write>(
+        &self,
+        txn: &MdbxReadTransaction,
+        header: &StateSnapshotHeader,
+        writer: &mut W,
+    ) -> Result<(), StateSnapshotError> {
+        if self.state.accounts.get_root_hash(Some(txn)).as_ref() != Some(&header.state_root) {
+            return Err(StateSnapshotError::IncompleteState);
+        }
+
+        write_record(writer, header)?;
+
+        let mut start_key = KeyNibbles::ROOT;
+        loop {
+            let chunk = self.state.accounts.get_chunk(
+                start_key,
+                Policy::state_chunks_max_size() as usize,
+                Some(txn),
+            );
+            write_record(writer, &chunk)?;
+
+            match chunk.end_key {
+                Some(end_key) => start_key = end_key,
+                None => break,
+            }
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    /// Replaces the accounts trie with a snapshot created by
+    /// [`export_state_snapshot`](Self::export_state_snapshot).
+    ///
+    /// The snapshot must have been taken at the current head, e.g. a freshly macro synced node.
+    /// Every chunk is verified against the state root of the head and nothing is committed unless
+    /// the whole snapshot is valid.
+    pub fn import_state_snapshot<R: Read>(
+        &self,
+        mut reader: R,
+    ) -> Result<StateSnapshotHeader, StateSnapshotError> {
+        let header: StateSnapshotHeader = read_record(&mut reader)?;
+        if header.version != StateSnapshotHeader::VERSION {
+            return Err(StateSnapshotError::UnsupportedVersion(header.version));
+        }
+        if header.block_hash != self.head_hash() {
+            return Err(StateSnapshotError::HeadMismatch(
+                header.block_hash,
+                self.head_hash(),
+            ));
+        }
+        let state_root = self.state.main_chain.head.state_root();
+        if header.state_root != *state_root {
+            return Err(StateSnapshotError::StateRootMismatch);
+        }
+
+        let mut txn = self.write_transaction();
+        self.state
+            .accounts
+            .reinitialize_as_incomplete(&mut (&mut txn).into());
+
+        let mut start_key = KeyNibbles::ROOT;
+        loop {
+            let chunk: TrieChunk = read_record(&mut reader)?;
+            let end_key = chunk.end_key.clone();
+
+            let result = self.state.accounts.commit_chunk(
+                &mut (&mut txn).into(),
+                chunk,
+                state_root.clone(),
+                start_key,
+            )?;
+            if result == TrieChunkPushResult::Ignored {
+                return Err(StateSnapshotError::UnexpectedChunk);
+            }
+
+            match end_key {
+                Some(end_key) => start_key = end_key,
+                None => break,
+            }
+        }
+
+        if self.state.accounts.get_root_hash(Some(&txn)).as_ref() != Some(state_root) {
+            return Err(StateSnapshotError::StateRootMismatch);
+        }
+
+        txn.commit();
+        Ok(header)
+    }
+}
+
+fn read_record<R: Read, T: Deserialize>(reader: &mut R) -> Result<T, StateSnapshotError> {
+    records::read_record(reader)
+}
@@ -1,3 +1,6 @@
+#[cfg(feature = "metrics")]
+use std::time::Instant;
+
 use nimiq_account::BlockLogger;
 use nimiq_block::{Block, BlockError, MacroBlock, MacroBody};
 use nimiq_blockchain_interface::{AbstractBlockchain, ChainInfo, PushError};
@@ -7,7 +10,10 @@ use nimiq_database::{
 };
 use nimiq_hash::Hash;
 use nimiq_primitives::policy::Policy;
+use nimiq_transaction::ExecutedTransaction;
 
+#[cfg(feature = "metrics")]
+use crate::chain_metrics::PushStage;
 use crate::{interface::HistoryInterface, BlockProducer, Blockchain};
 
 /// Implements methods to verify the validity of blocks.
@@ -21,6 +27,9 @@ impl Blockchain {
         block: &Block,
         trusted: bool,
     ) -> Result<(), PushError> {
+        #[cfg(feature = "metrics")]
+        let start = Instant::now();
+
         // We expect full blocks (with body) here.
         if !block.has_body() {
             return Err(PushError::InvalidBlock(BlockError::MissingBody));
@@ -87,31 +96,77 @@ impl Blockchain {
 
             // Verify that the block is valid for the given proposer.
             block.verify_proposer(&proposer.signing_key, predecessor.seed())?;
+        }
 
-            // Verify that the block is valid for the current validators.
-            block.verify_validators(self.current_validators().unwrap())?;
+        #[cfg(feature = "metrics")]
+        self.metrics
+            .observe_push_stage(PushStage::HeaderVerification, start.elapsed());
 
-            // Verify that the transactions in the block are valid.
-            self.verify_transactions(block)?;
+        if !trusted {
+            #[cfg(feature = "metrics")]
+            let start = Instant::now();
+
+            // Verify that the block is valid for the current validators and that the
+            // transactions in the block are valid.
+            let (validators, transactions) = self.join(
+                || {
+                    block.verify_validators_with(
+                        self.current_validators().unwrap(),
+                        self.config.parallel_verification,
+                    )
+                },
+                || self.verify_transactions(block),
+            );
+            validators?;
+            transactions?;
 
             // Verify that the equivocation proofs are valid.
             self.verify_equivocation_proofs(block, txn)?;
+
+            #[cfg(feature = "metrics")]
+            self.metrics
+                .observe_push_stage(PushStage::BodyVerification, start.elapsed());
         }
 
         Ok(())
     }
 
     fn verify_transactions(&self, block: &Block) -> Result<(), BlockError> {
-        if let Some(transactions) = block.transactions() {
-            for transaction in transactions {
-                let transaction = transaction.get_raw_transaction();
-                if !self.tx_verification_cache.is_known(&transaction.hash()) {
-                    transaction.verify(self.network_id)?;
-                }
+        let Some(transactions) = block.transactions() else {
+            return Ok(());
+        };
+
+        let verify = |transaction: &ExecutedTransaction| -> Result<(), BlockError> {
+            let transaction = transaction.get_raw_transaction();
+            if !self.tx_verification_cache.is_known(&transaction.hash()) {
+                transaction.verify(self.network_id)?;
             }
+            Ok(())
+        };
+
+        #[cfg(feature = "parallel")]
+        if self.config.parallel_verification {
+            use rayon::prelude::*;
+            return transactions.par_iter().try_for_each(verify);
         }
 
-        Ok(())
+        transactions.iter().try_for_each(verify)
+    }
+
+    /// Runs both closures, in parallel if parallel verification is enabled.
+    fn join<A, B, RA, RB>(&self, a: A, b: B) -> (RA, RB)
+    where
+        A: FnOnce() -> RA + Send,
+        B: FnOnce() -> RB + Send,
+        RA: Send,
+        RB: Send,
+    {
+        #[cfg(feature = "parallel")]
+        if self.config.parallel_verification {
+            return rayon::join(a, b);
+        }
+
+        (a(), b())
     }
 
     /// Verifies a block against the blockchain state AFTER it gets updated with the block (ex: checking if
@@ -497,8 +552,8 @@ impl Blockchain {
         );
 
         let mut write_txn = self.write_transaction();
-        if let Err(remove_chain) =
-            Blockchain::rebranch_to(self, &mut fork_chain, &mut ancestor, &mut write_txn)
+        if let Err((remove_chain, error)) =
+            Blockchain::rebranch_to(self, &mut fork_chain, &mut ancestor, &mut write_txn, &())
         {
             // Failed to apply blocks. All blocks within revert chain must be removed.
             // To do that the txn must be aborted first, as the txn will be committed and
@@ -517,7 +572,11 @@ impl Blockchain {
             }
             write_txn.commit();
 
-            return Err(PushError::InvalidFork);
+            // A vetoed fork is not invalid, so the veto is passed on as is.
+            return Err(match error {
+                PushError::Vetoed(veto) => PushError::Vetoed(veto),
+                _ => PushError::InvalidFork,
+            });
         }
         // The state is now prepared contained within `write_txn` to just invoke verify_proposal_state.
         self.verify_proposal_state(block, &mut write_txn)
@@ -5,7 +5,10 @@ use std::sync::Arc;
 use nimiq_account::{Account, BlockState, DataStore, ReservedBalance, StakingContract};
 use nimiq_block::Block;
 use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainError, ChainInfo, Direction};
-use nimiq_database::{mdbx::MdbxReadTransaction as DBTransaction, traits::WriteTransaction};
+use nimiq_database::{
+    mdbx::{MdbxReadTransaction as DBTransaction, MdbxWriteTransaction},
+    traits::WriteTransaction,
+};
 use nimiq_hash::Blake2bHash;
 use nimiq_keys::Address;
 use nimiq_primitives::{
@@ -15,7 +18,10 @@ use nimiq_transaction::{historic_transaction::RawTransactionHash, Transaction};
 
 #[cfg(feature = "metrics")]
 use crate::chain_metrics::BlockchainMetrics;
-use crate::{blockchain_state::BlockchainState, interface::HistoryInterface, Blockchain};
+use crate::{
+    blockchain_state::BlockchainState, chain_store::StateSyncProgress, interface::HistoryInterface,
+    Blockchain,
+};
 
 /// Implements several wrapper functions.
 impl Blockchain {
@@ -150,6 +156,11 @@ impl Blockchain {
             .data_store(&Policy::STAKING_CONTRACT_ADDRESS)
     }
 
+    /// Returns the contract data store for the account with the given address.
+    pub fn get_account_store(&self, address: &Address) -> DataStore {
+        self.state.accounts.data_store(address)
+    }
+
     /// Returns the number of accounts in the Accounts Tree. An account id defined as any leaf node
     /// in the tree.
     pub fn get_number_accounts(&self) -> u64 {
@@ -230,6 +241,48 @@ impl Blockchain {
         self.state.accounts.tree.get_missing_range(txn)
     }
 
+    /// Retrieves the persisted state sync progress. The progress is only returned if it was
+    /// recorded at the current start of the missing range of the accounts trie, i.e. if it
+    /// still describes the current state of the accounts trie.
+    pub fn get_state_sync_progress(&self) -> Option<StateSyncProgress> {
+        let txn = self.read_transaction();
+        let missing_range = self.get_missing_accounts_range(Some(&txn))?;
+
+        self.chain_store
+            .get_state_sync_progress(Some(&txn))
+            .filter(|progress| progress.next_key == missing_range.start)
+    }
+
+    /// Records a chunk starting at `start_key` that was just applied to the accounts trie in the
+    /// state sync progress, within the transaction the chunk was committed in. The progress thus
+    /// always resumes at the actual start of the missing range. It is removed once the accounts
+    /// trie is complete.
+    pub(crate) fn record_applied_chunk(
+        &self,
+        txn: &mut MdbxWriteTransaction,
+        start_key: &KeyNibbles,
+    ) {
+        let Some(missing_range) = self.get_missing_accounts_range(Some(txn)) else {
+            self.chain_store.clear_state_sync_progress(txn);
+            return;
+        };
+
+        // Progress recorded at a different key belongs to a previous sync of the accounts trie.
+        let chunks_applied = self
+            .chain_store
+            .get_state_sync_progress(Some(txn))
+            .filter(|progress| progress.next_key == *start_key)
+            .map_or(0, |progress| progress.chunks_applied);
+
+        self.chain_store.set_state_sync_progress(
+            txn,
+            &StateSyncProgress {
+                chunks_applied: chunks_applied + 1,
+                next_key: missing_range.start,
+            },
+        );
+    }
+
     /// Removes the history of a given epoch
     pub fn remove_epoch_history(&mut self, epoch_number: u32) {
         let mut txn = self.write_transaction();
@@ -1,4 +1,4 @@
-use std::{cmp, mem};
+use std::mem;
 
 use nimiq_block::Block;
 use nimiq_blockchain_interface::{
@@ -9,6 +9,7 @@ use nimiq_primitives::policy::Policy;
 use nimiq_zkp::{verify::verify, NanoProof, ZKP_VERIFYING_DATA};
 use parking_lot::{RwLockUpgradableReadGuard, RwLockWriteGuard};
 
+use super::replay::RecordedInput;
 use crate::{interface::HistoryInterface, Blockchain};
 
 /// Implements methods to sync a full node via ZKP.
@@ -29,6 +30,8 @@ impl Blockchain {
         // Must be an election block.
         assert!(block.is_election());
 
+        this.recorder.record(|| RecordedInput::zkp(&block, &proof));
+
         let block_hash_blake2b = block.hash();
 
         let read_txn = this.read_transaction();
@@ -165,6 +168,10 @@ impl Blockchain {
         // Must be a macro block.
         assert!(block.is_election());
 
+        this.recorder.record(|| RecordedInput::PreviousSlots {
+            block: block.clone(),
+        });
+
         if this.state.previous_slots.is_some() {
             return Ok(PushResult::Ignored);
         }
@@ -221,6 +228,10 @@ impl Blockchain {
         // Must be a macro block.
         assert!(block.is_macro());
 
+        this.recorder.record(|| RecordedInput::Macro {
+            block: block.clone(),
+        });
+
         let read_txn = this.read_transaction();
 
         // Check if we already know this block.
@@ -243,7 +254,10 @@ impl Blockchain {
         block.verify_macro_successor(this.state.macro_info.head.unwrap_macro_ref())?;
 
         // Verify that the block is valid for the current validators.
-        block.verify_validators(this.current_validators().unwrap())?;
+        block.verify_validators_with(
+            this.current_validators().unwrap(),
+            this.config.parallel_verification,
+        )?;
 
         // At this point we know that the block is correct. We just have to push it.
 
@@ -267,24 +281,7 @@ impl Blockchain {
 
         // Since it's a macro block, we have to clear the ChainStore.
         if is_election_block {
-            let max_epochs_stored =
-                cmp::max(this.config.max_epochs_stored, Policy::MIN_EPOCHS_STORED);
-
-            // Calculate the epoch to be pruned.
-            let pruned_epoch = Policy::epoch_at(block_number).checked_sub(max_epochs_stored);
-
-            if let Some(pruned_epoch) = pruned_epoch {
-                // Prune the Chain Store.
-                this.chain_store.prune_epoch(pruned_epoch, &mut txn);
-
-                if !this.config.keep_history {
-                    // Prune the History Store.
-                    // We will never prune pre-genesis data here.
-                    if pruned_epoch > 0 {
-                        this.history_store.remove_history(&mut txn, pruned_epoch);
-                    }
-                }
-            }
+            this.prune_epochs(&mut txn, Policy::epoch_at(block_number));
         }
 
         txn.commit();
@@ -1,18 +1,42 @@
+use std::time::Duration;
+
 use nimiq_block::{Block, EquivocationProof};
 use nimiq_blockchain_interface::{ChunksPushError, ChunksPushResult, PushError, PushResult};
 use nimiq_hash::Blake2bHash;
 use prometheus_client::{
     encoding::{EncodeLabelSet, EncodeLabelValue},
-    metrics::{counter::Counter, family::Family},
+    metrics::{counter::Counter, family::Family, histogram::Histogram},
     registry::Registry,
 };
 
-#[derive(Default)]
+use crate::chain_store::PrunedForks;
+
 pub struct BlockchainMetrics {
     block_push_counts: Family<PushResultLabels, Counter>,
     transactions_counts: Family<TransactionProcessedLabels, Counter>,
     skip_blocks: Counter,
     equivocation_counts: Family<EquivocationProofLabels, Counter>,
+    pruned_fork_blocks: Counter,
+    pruned_fork_bytes: Counter,
+    push_stage_durations: Family<PushStageLabels, Histogram, fn() -> Histogram>,
+}
+
+impl Default for BlockchainMetrics {
+    fn default() -> Self {
+        BlockchainMetrics {
+            block_push_counts: Default::default(),
+            transactions_counts: Default::default(),
+            skip_blocks: Default::default(),
+            equivocation_counts: Default::default(),
+            pruned_fork_blocks: Default::default(),
+            pruned_fork_bytes: Default::default(),
+            push_stage_durations: Family::new_with_constructor(|| {
+                Histogram::new([
+                    0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5,
+                ])
+            }),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
@@ -31,6 +55,26 @@ enum BlockPushResult {
     Invalid,
 }
 
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct PushStageLabels {
+    stage: PushStage,
+}
+
+/// The stages of applying a block to the chain.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, EncodeLabelValue)]
+pub enum PushStage {
+    /// Intrinsic checks of the block, its successor relation and its proposer.
+    HeaderVerification,
+    /// Verification of the justification, the transactions and the equivocation proofs.
+    BodyVerification,
+    /// Committing the block to the accounts trie and verifying the resulting state.
+    StateCommit,
+    /// Appending the block's transactions and inherents to the history store.
+    HistoryStoreAppend,
+    /// Notifying the blockchain event listeners.
+    EventsDispatch,
+}
+
 #[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
 struct TransactionProcessedLabels {
     ty: TransactionProcessed,
@@ -79,6 +123,31 @@ impl BlockchainMetrics {
             "Count of equivocation proofs applied",
             self.equivocation_counts.clone(),
         );
+
+        registry.register(
+            "pruned_fork_blocks",
+            "Number of forked blocks pruned from the chain store",
+            self.pruned_fork_blocks.clone(),
+        );
+
+        registry.register(
+            "pruned_fork_bytes",
+            "Bytes reclaimed by pruning forked blocks from the chain store",
+            self.pruned_fork_bytes.clone(),
+        );
+
+        registry.register(
+            "push_stage_durations",
+            "Time in seconds spent per block push stage, the state commit includes the history store append",
+            self.push_stage_durations.clone(),
+        );
+    }
+
+    #[inline]
+    pub fn observe_push_stage(&self, stage: PushStage, duration: Duration) {
+        self.push_stage_durations
+            .get_or_create(&PushStageLabels { stage })
+            .observe(duration.as_secs_f64());
     }
 
     #[inline]
@@ -139,6 +208,13 @@ impl BlockchainMetrics {
         }
     }
 
+    #[inline]
+    pub fn note_pruned_forks(&self, pruned_forks: &PrunedForks) {
+        self.pruned_fork_blocks
+            .inc_by(pruned_forks.num_blocks as u64);
+        self.pruned_fork_bytes.inc_by(pruned_forks.num_bytes as u64);
+    }
+
     #[inline]
     pub fn note_rebranch(
         &self,
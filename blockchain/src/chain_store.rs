@@ -1,4 +1,4 @@
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 use nimiq_account::RevertInfo;
 use nimiq_block::{Block, BlockType, EquivocationProof, MacroBody, MicroBody};
@@ -6,11 +6,12 @@ use nimiq_blockchain_interface::{BlockchainError, ChainInfo, Direction};
 use nimiq_database::{
     declare_table,
     mdbx::{MdbxDatabase, MdbxReadTransaction, MdbxWriteTransaction, OptionalTransaction},
+    migration::{Migration, MigrationFailure, MigrationProgress},
     traits::{Database, DupReadCursor, ReadCursor, ReadTransaction, WriteTransaction},
 };
 use nimiq_database_value_derive::DbSerializable;
 use nimiq_hash::Blake2bHash;
-use nimiq_primitives::{policy::Policy, trie::trie_diff::TrieDiff};
+use nimiq_primitives::{key_nibbles::KeyNibbles, policy::Policy, trie::trie_diff::TrieDiff};
 use nimiq_serde::{Deserialize, Serialize};
 use nimiq_transaction::{historic_transaction::HistoricTransactionData, reward::RewardTransaction};
 
@@ -23,6 +24,8 @@ declare_table!(StoredBlockTable, "StoredBlockTable", Blake2bHash => Block);
 declare_table!(HeightIndex, "HeightIndex", u32 => dup(Blake2bHash));
 declare_table!(RevertTable, "Receipts", u32 => RevertInfo);
 declare_table!(AccountsDiffTable, "AccountsDiff", Blake2bHash => TrieDiff);
+declare_table!(ForkIndex, "ForkIndex", u32 => dup(Blake2bHash));
+declare_table!(StateSyncTable, "StateSync", () => StateSyncProgress);
 
 /// The non-header content of a block except that transactions are not stored to
 /// optimize blocks storage. This assumes that a block has been pushed and that there
@@ -112,6 +115,72 @@ impl PushedBlock {
     }
 }
 
+/// The progress of the state sync. It is persisted together with the key it was recorded at,
+/// such that the progress can be resumed after a restart.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, DbSerializable)]
+pub struct StateSyncProgress {
+    /// Number of chunks that were applied to the accounts trie so far.
+    pub chunks_applied: u64,
+    /// The start of the missing range of the accounts trie after the last applied chunk.
+    pub next_key: KeyNibbles,
+}
+
+impl StateSyncProgress {
+    /// Number of leading nibbles considered when estimating the synced fraction of the key space.
+    const ESTIMATION_NIBBLES: usize = 8;
+
+    /// Estimates the total number of chunks needed to sync the accounts trie, assuming that the
+    /// chunks applied so far are representative for the remaining key space.
+    /// Returns `None` if no estimation is possible yet.
+    pub fn estimated_total(&self) -> Option<u64> {
+        let mut synced_fraction = 0f64;
+        let mut weight = 1f64;
+        for i in 0..Self::ESTIMATION_NIBBLES {
+            weight /= 16f64;
+            synced_fraction += self.next_key.get(i).unwrap_or(0) as f64 * weight;
+        }
+
+        if self.chunks_applied == 0 || synced_fraction <= 0f64 {
+            return None;
+        }
+
+        let estimate = (self.chunks_applied as f64 / synced_fraction).ceil() as u64;
+        Some(estimate.max(self.chunks_applied))
+    }
+}
+
+/// The forked blocks that were removed from the chain store when finalizing a batch.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PrunedForks {
+    /// Number of forked blocks that were removed.
+    pub num_blocks: usize,
+    /// Serialized size in bytes of the removed chain infos and accounts diffs.
+    pub num_bytes: usize,
+}
+
+/// Records in the chain store that are not consistently referenced, e.g. because a write was
+/// interrupted by an unclean shutdown.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OrphanedRecords {
+    /// Height index entries that reference a missing chain info.
+    pub index_entries: Vec<(u32, Blake2bHash)>,
+    /// Main chain infos that are missing from the height index.
+    pub unindexed_chain_infos: Vec<(u32, Blake2bHash)>,
+    /// Block bodies without a chain info.
+    pub blocks: Vec<Blake2bHash>,
+    /// Accounts diffs without a chain info.
+    pub accounts_diffs: Vec<Blake2bHash>,
+}
+
+impl OrphanedRecords {
+    pub fn is_empty(&self) -> bool {
+        self.index_entries.is_empty()
+            && self.unindexed_chain_infos.is_empty()
+            && self.blocks.is_empty()
+            && self.accounts_diffs.is_empty()
+    }
+}
+
 /// A struct that contains the DB tables to store the chain related data such as
 /// chain table, block table, height index table, revert table and accounts diff table.
 #[derive(Debug)]
@@ -132,6 +201,10 @@ pub struct ChainStore {
     revert_table: RevertTable,
     /// A database of accounts trie diffs for a block.
     accounts_diff_table: AccountsDiffTable,
+    /// A database of the hashes of forked blocks indexed by the batch they were abandoned in.
+    fork_idx: ForkIndex,
+    /// A database of the state sync progress.
+    state_sync_table: StateSyncTable,
     /// A reference to the history store to recover micro block transactions.
     history_store: Arc<MergedHistoryStoreProxy>,
 }
@@ -147,6 +220,8 @@ impl ChainStore {
             height_idx: HeightIndex,
             revert_table: RevertTable,
             accounts_diff_table: AccountsDiffTable,
+            fork_idx: ForkIndex,
+            state_sync_table: StateSyncTable,
             history_store,
         };
 
@@ -167,6 +242,10 @@ impl ChainStore {
         chain_store
             .db
             .create_regular_table(&chain_store.accounts_diff_table);
+        chain_store.db.create_dup_table(&chain_store.fork_idx);
+        chain_store
+            .db
+            .create_regular_table(&chain_store.state_sync_table);
 
         chain_store
     }
@@ -178,6 +257,8 @@ impl ChainStore {
         txn.clear_table(&self.height_idx);
         txn.clear_table(&self.revert_table);
         txn.clear_table(&self.accounts_diff_table);
+        txn.clear_table(&self.fork_idx);
+        txn.clear_table(&self.state_sync_table);
         txn.clear_table(&self.head_table);
     }
 
@@ -190,6 +271,26 @@ impl ChainStore {
         txn.put(&self.head_table, &(), hash);
     }
 
+    pub fn get_state_sync_progress(
+        &self,
+        txn_option: Option<&MdbxReadTransaction>,
+    ) -> Option<StateSyncProgress> {
+        let txn = txn_option.or_new(&self.db);
+        txn.get(&self.state_sync_table, &())
+    }
+
+    pub fn set_state_sync_progress(
+        &self,
+        txn: &mut MdbxWriteTransaction,
+        progress: &StateSyncProgress,
+    ) {
+        txn.put(&self.state_sync_table, &(), progress);
+    }
+
+    pub fn clear_state_sync_progress(&self, txn: &mut MdbxWriteTransaction) {
+        txn.remove(&self.state_sync_table, &());
+    }
+
     pub fn get_chain_info(
         &self,
         hash: &Blake2bHash,
@@ -631,14 +732,25 @@ impl ChainStore {
         txn.clear_table(&self.stored_block_table);
     }
 
-    /// Finalizes a batch by removing blocks that were only stored
-    pub fn finalize_batch(&self, txn: &mut MdbxWriteTransaction) {
+    /// Finalizes a batch by removing blocks that were only stored.
+    ///
+    /// If a `fork_retention` is given, the chain infos of the forked blocks in this batch are kept
+    /// for that many batches and pruned afterwards. Otherwise they are only removed once their
+    /// epoch is pruned.
+    pub fn finalize_batch(
+        &self,
+        txn: &mut MdbxWriteTransaction,
+        batch_number: u32,
+        fork_retention: Option<u32>,
+    ) -> PrunedForks {
+        let mut forks = Vec::new();
         let mut cursor = WriteTransaction::cursor(txn, &self.stored_block_table);
         let mut pos: Option<(Blake2bHash, Block)> = cursor.first();
 
         // Remove the item first from the height index table
         while let Some((hash, block)) = pos {
             txn.remove_item(&self.height_idx, &block.block_number(), &hash);
+            forks.push(hash);
             pos = cursor.next();
         }
         // Then clear the stored block table
@@ -646,6 +758,122 @@ impl ChainStore {
         // Macro blocks are final and receipts for the previous batch are no longer necessary
         // as rebranching across this block is not possible.
         txn.clear_table(&self.revert_table);
+
+        let Some(fork_retention) = fork_retention else {
+            return PrunedForks::default();
+        };
+
+        // Blocks that were stored and later adopted are on the main chain and must be kept.
+        for hash in forks {
+            let on_fork = txn
+                .get(&self.chain_table, &hash)
+                .is_some_and(|chain_info| !chain_info.on_main_chain);
+            if on_fork {
+                txn.put(&self.fork_idx, &batch_number, &hash);
+            }
+        }
+
+        match batch_number.checked_sub(fork_retention) {
+            Some(last_pruned_batch) => self.prune_forks(last_pruned_batch, txn),
+            None => PrunedForks::default(),
+        }
+    }
+
+    /// Removes the forked blocks that were abandoned in or before the given batch.
+    fn prune_forks(&self, last_pruned_batch: u32, txn: &mut MdbxWriteTransaction) -> PrunedForks {
+        let forks: Vec<(u32, Blake2bHash)> = WriteTransaction::dup_cursor(txn, &self.fork_idx)
+            .into_iter_start()
+            .take_while(|(batch_number, _)| *batch_number <= last_pruned_batch)
+            .collect();
+
+        let mut pruned = PrunedForks::default();
+        for (batch_number, hash) in forks {
+            // The chain info might already be gone if its epoch was pruned in the meantime.
+            if let Some(chain_info) = txn.get(&self.chain_table, &hash) {
+                pruned.num_blocks += 1;
+                pruned.num_bytes += chain_info.serialized_size();
+                txn.remove(&self.chain_table, &hash);
+            }
+            if let Some(diff) = txn.get(&self.accounts_diff_table, &hash) {
+                pruned.num_bytes += diff.serialized_size();
+                txn.remove(&self.accounts_diff_table, &hash);
+            }
+            txn.remove_item(&self.fork_idx, &batch_number, &hash);
+        }
+
+        pruned
+    }
+
+    /// Cross-validates the chain infos with the height index, the block tables and the accounts
+    /// diffs. This walks all of these tables and should only be used for maintenance.
+    pub fn find_orphaned_records(
+        &self,
+        txn_option: Option<&MdbxReadTransaction>,
+    ) -> OrphanedRecords {
+        let txn = txn_option.or_new(&self.db);
+        let mut orphaned = OrphanedRecords::default();
+
+        let mut indexed = HashSet::new();
+        for (height, hash) in txn.dup_cursor(&self.height_idx).into_iter_start() {
+            if txn.get(&self.chain_table, &hash).is_none() {
+                orphaned.index_entries.push((height, hash));
+            } else {
+                indexed.insert((height, hash));
+            }
+        }
+
+        // Forked blocks are removed from the height index when their batch is finalized, only
+        // main chain blocks must always be indexed.
+        for (hash, chain_info) in txn.cursor(&self.chain_table).into_iter_start() {
+            let height = chain_info.head.block_number();
+            if chain_info.on_main_chain && !indexed.contains(&(height, hash.clone())) {
+                orphaned.unindexed_chain_infos.push((height, hash));
+            }
+        }
+
+        let pushed_blocks = txn
+            .cursor(&self.pushed_block_table)
+            .into_iter_start()
+            .map(|(hash, _)| hash);
+        let stored_blocks = txn
+            .cursor(&self.stored_block_table)
+            .into_iter_start()
+            .map(|(hash, _)| hash);
+        orphaned.blocks = pushed_blocks
+            .chain(stored_blocks)
+            .filter(|hash| txn.get(&self.chain_table, hash).is_none())
+            .collect();
+
+        orphaned.accounts_diffs = txn
+            .cursor(&self.accounts_diff_table)
+            .into_iter_start()
+            .map(|(hash, _)| hash)
+            .filter(|hash| txn.get(&self.chain_table, hash).is_none())
+            .collect();
+
+        orphaned
+    }
+
+    /// Repairs the given orphaned records: dangling records are removed and chain infos that are
+    /// missing from the height index are indexed again.
+    pub fn repair_orphaned_records(
+        &self,
+        txn: &mut MdbxWriteTransaction,
+        orphaned: &OrphanedRecords,
+    ) {
+        for (height, hash) in &orphaned.index_entries {
+            txn.remove_item(&self.height_idx, height, hash);
+        }
+        for (height, hash) in &orphaned.unindexed_chain_infos {
+            txn.put(&self.height_idx, height, hash);
+        }
+        for hash in &orphaned.blocks {
+            txn.remove(&self.pushed_block_table, hash);
+            txn.remove(&self.stored_block_table, hash);
+        }
+        for hash in &orphaned.accounts_diffs {
+            txn.remove(&self.accounts_diff_table, hash);
+        }
     }
 
     /// Puts a revert info for a block height
@@ -695,3 +923,58 @@ impl ChainStore {
         }
     }
 }
+
+/// Migration indexing the forked blocks that were stored before the fork index existed, such that
+/// they are pruned once the fork retention is over instead of being kept until their epoch is
+/// pruned.
+///
+/// Only the forks of finalized batches are indexed, forks of the current batch might still be
+/// adopted and are indexed when the batch is finalized.
+pub struct IndexForksMigration;
+
+impl Migration for IndexForksMigration {
+    fn version(&self) -> u32 {
+        1
+    }
+
+    fn description(&self) -> &str {
+        "Index forked blocks"
+    }
+
+    fn create_tables(&self, db: &MdbxDatabase) {
+        db.create_regular_table(&HeadTable);
+        db.create_regular_table(&ChainTable);
+        db.create_dup_table(&ForkIndex);
+    }
+
+    fn migrate(
+        &self,
+        _db: &MdbxDatabase,
+        txn: &mut MdbxWriteTransaction,
+        progress: &mut MigrationProgress,
+    ) -> Result<(), MigrationFailure> {
+        let Some(head_hash) = txn.get(&HeadTable, &()) else {
+            return Ok(());
+        };
+        let head = txn
+            .get(&ChainTable, &head_hash)
+            .ok_or("Chain info of the head block is missing")?;
+        let last_finalized_batch =
+            Policy::batch_at(Policy::last_macro_block(head.head.block_number()));
+
+        let forks: Vec<(u32, Blake2bHash)> = WriteTransaction::cursor(txn, &ChainTable)
+            .into_iter_start()
+            .filter(|(_, chain_info)| !chain_info.on_main_chain)
+            .map(|(hash, chain_info)| (Policy::batch_at(chain_info.head.block_number()), hash))
+            .filter(|(batch_number, _)| *batch_number <= last_finalized_batch)
+            .collect();
+
+        progress.set_total(forks.len() as u64);
+        for (batch_number, hash) in forks {
+            txn.put(&ForkIndex, &batch_number, &hash);
+            progress.advance(1);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+use nimiq_database_value_derive::DbSerializable;
+use nimiq_keys::Address;
+use nimiq_primitives::coin::Coin;
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_transaction::historic_transaction::{HistoricTransaction, HistoricTransactionData};
+
+/// A compact summary of the activity of an address in the transaction history.
+/// It only covers transactions and reward inherents, as these are the historic transactions
+/// indexed by address.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, DbSerializable)]
+pub struct AddressSummary {
+    /// The block number of the first transaction involving the address.
+    pub first_seen: u32,
+    /// The block number of the latest transaction involving the address.
+    pub last_seen: u32,
+    /// The number of transactions involving the address.
+    pub tx_count: u64,
+    /// The total value received by the address.
+    pub total_received: Coin,
+    /// The total value (including fees) sent by the address.
+    pub total_sent: Coin,
+}
+
+impl AddressSummary {
+    /// Creates the summary of a single transaction.
+    pub(crate) fn new(block_number: u32, received: Coin, sent: Coin) -> Self {
+        Self {
+            first_seen: block_number,
+            last_seen: block_number,
+            tx_count: 1,
+            total_received: received,
+            total_sent: sent,
+        }
+    }
+
+    /// Adds the activity covered by another summary to this one.
+    pub fn merge(&mut self, other: &AddressSummary) {
+        self.first_seen = self.first_seen.min(other.first_seen);
+        self.last_seen = self.last_seen.max(other.last_seen);
+        self.tx_count += other.tx_count;
+        self.total_received = self.total_received.saturating_add(other.total_received);
+        self.total_sent = self.total_sent.saturating_add(other.total_sent);
+    }
+
+    /// Removes the activity of a single transaction from this summary.
+    /// The first and last seen block numbers need to be updated separately.
+    pub(crate) fn remove(&mut self, received: Coin, sent: Coin) {
+        self.tx_count = self.tx_count.saturating_sub(1);
+        self.total_received = self.total_received.saturating_sub(received);
+        self.total_sent = self.total_sent.saturating_sub(sent);
+    }
+
+    /// Returns the addresses involved in the given historic transaction, together with the
+    /// value each of them received and sent.
+    pub(crate) fn activity(hist_tx: &HistoricTransaction) -> Vec<(Address, Coin, Coin)> {
+        match &hist_tx.data {
+            HistoricTransactionData::Basic(tx) => {
+                let raw_tx = tx.get_raw_transaction();
+                // Failed transactions only pay the fee.
+                let value = if tx.succeeded() {
+                    raw_tx.value
+                } else {
+                    Coin::ZERO
+                };
+                let sent = value.saturating_add(raw_tx.fee);
+
+                if raw_tx.sender == raw_tx.recipient {
+                    vec![(raw_tx.sender.clone(), value, sent)]
+                } else {
+                    vec![
+                        (raw_tx.sender.clone(), Coin::ZERO, sent),
+                        (raw_tx.recipient.clone(), value, Coin::ZERO),
+                    ]
+                }
+            }
+            HistoricTransactionData::Reward(ev) => {
+                vec![(ev.reward_address.clone(), ev.value, Coin::ZERO)]
+            }
+            HistoricTransactionData::Equivocation(_)
+            | HistoricTransactionData::Penalize(_)
+            | HistoricTransactionData::Jail(_) => vec![],
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use nimiq_serde::{Deserialize, Serialize};
+
+use super::utils::{BlockOrderedIndex, EpochBasedIndex};
+
+/// The position of a historic transaction in the index of the addresses it involves.
+/// Cursors are ordered chronologically, which allows to page through the transactions of an
+/// address by requesting the transactions before the last cursor of the previous page.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct AddressTxCursor {
+    /// The block number of the transaction.
+    pub block_number: u32,
+    /// The epoch of the history tree containing the transaction.
+    pub epoch_number: u32,
+    /// The leaf index of the transaction in the history tree of its epoch.
+    pub leaf_index: u32,
+}
+
+impl From<&BlockOrderedIndex> for AddressTxCursor {
+    fn from(index: &BlockOrderedIndex) -> Self {
+        AddressTxCursor {
+            block_number: index.index,
+            epoch_number: index.value.epoch_number,
+            leaf_index: index.value.index,
+        }
+    }
+}
+
+impl From<AddressTxCursor> for BlockOrderedIndex {
+    fn from(cursor: AddressTxCursor) -> Self {
+        BlockOrderedIndex::new(
+            cursor.block_number,
+            EpochBasedIndex::new(cursor.epoch_number, cursor.leaf_index),
+        )
+    }
+}
@@ -1,4 +1,8 @@
-use std::{collections::BTreeMap, ops::Range};
+use std::{
+    collections::{btree_map::Entry, BTreeMap, BTreeSet},
+    iter,
+    ops::Range,
+};
 
 use nimiq_block::MicroBlock;
 use nimiq_database::{
@@ -15,8 +19,9 @@ use nimiq_mmr::{
     error::Error as MMRError,
     mmr::proof::{RangeProof, SizeProof},
 };
-use nimiq_primitives::policy::Policy;
+use nimiq_primitives::{coin::Coin, policy::Policy};
 use nimiq_transaction::{
+    address_filter::AddressFilter,
     historic_transaction::{HistoricTransaction, HistoricTransactionData, RawTransactionHash},
     history_proof::HistoryTreeProof,
     inherent::Inherent,
@@ -24,8 +29,10 @@ use nimiq_transaction::{
 };
 
 use super::{
+    address_summary::AddressSummary,
     interface::HistoryInterface,
-    utils::{EpochBasedIndex, OrderedHash},
+    utils::{BlockOrderedIndex, EpochBasedIndex, OrderedHash},
+    AddressTxCursor,
 };
 use crate::{history::HistoryTreeChunk, interface::HistoryIndexInterface, HistoryStore};
 
@@ -33,6 +40,12 @@ use crate::{history::HistoryTreeChunk, interface::HistoryIndexInterface, History
 declare_table!(TxHashTable, "LeafIndexByTxHash", RawTransactionHash => EpochBasedIndex);
 // `Address` -> `EpochBasedIndex` -> `Blake2bHash`
 declare_table!(AddressTable, "TxHashesByAddress", Address => EpochBasedIndex => Blake2bHash);
+// `Address` -> `Block number` -> `EpochBasedIndex`
+declare_table!(AddressBlockTable, "TxIndicesByAddressAndBlock", Address => u32 => EpochBasedIndex);
+// `Address` -> `AddressSummary`
+declare_table!(AddressSummaryTable, "SummaryByAddress", Address => AddressSummary);
+// `Batch number` -> `AddressFilter`
+declare_table!(AddressFilterTable, "AddressFilterByBatch", u32 => AddressFilter);
 
 #[derive(Debug)]
 /// A struct that contains databases to store history indices.
@@ -46,6 +59,14 @@ pub struct HistoryStoreIndex {
     /// A database of all raw transaction (and reward inherent) hashes indexed by their sender and
     /// recipient addresses.
     address_table: AddressTable,
+    /// A database of the epoch numbers and leaf indices of all transactions (and reward inherents)
+    /// indexed by their sender and recipient addresses and ordered by block number.
+    address_block_table: AddressBlockTable,
+    /// A database of the activity summaries indexed by address, covering the same historic
+    /// transactions as the address table.
+    address_summary_table: AddressSummaryTable,
+    /// A database of the filters over all addresses in the address table, indexed by batch number.
+    address_filter_table: AddressFilterTable,
     /// The history store.
     history_store: HistoryStore,
 }
@@ -58,10 +79,16 @@ impl HistoryStoreIndex {
             db,
             tx_hash_table: TxHashTable,
             address_table: AddressTable,
+            address_block_table: AddressBlockTable,
+            address_summary_table: AddressSummaryTable,
+            address_filter_table: AddressFilterTable,
         };
 
         index.db.create_regular_table(&index.tx_hash_table);
         index.db.create_dup_table(&index.address_table);
+        index.db.create_dup_table(&index.address_block_table);
+        index.db.create_regular_table(&index.address_summary_table);
+        index.db.create_regular_table(&index.address_filter_table);
 
         index.rebuild_index_if_necessary();
         index
@@ -74,10 +101,24 @@ impl HistoryStoreIndex {
             WriteTransaction::dup_cursor(&txn, &self.history_store.hist_tx_table);
 
         trace!("Check if history index needs to be rebuilt.");
-        // Check if last transaction is part of index.
+        // Check if last transaction is part of index and whether the address summaries, block
+        // indices and filters exist.
         if let Some((_, hist_tx)) = hist_tx_cursor.last() {
             let raw_tx_hash = hist_tx.value.tx_hash();
-            if txn.get(&self.tx_hash_table, &raw_tx_hash).is_none() {
+            let has_summaries = WriteTransaction::cursor(&txn, &self.address_summary_table)
+                .first()
+                .is_some();
+            let has_filters = WriteTransaction::cursor(&txn, &self.address_filter_table)
+                .first()
+                .is_some();
+            let has_block_indices = WriteTransaction::dup_cursor(&txn, &self.address_block_table)
+                .first()
+                .is_some();
+            if txn.get(&self.tx_hash_table, &raw_tx_hash).is_none()
+                || !has_summaries
+                || !has_filters
+                || !has_block_indices
+            {
                 info!("History index out-of-date. Starting to rebuild index (this can take a long time).");
                 self.rebuild_index(&mut txn);
                 debug!("Committing rebuilt index.");
@@ -87,40 +128,136 @@ impl HistoryStoreIndex {
         }
     }
 
+    /// Removes the given historic transactions from the indices.
+    /// Returns the batches whose address filters need to be rebuilt once the transactions have
+    /// been removed from the history store.
     fn remove_txns_from_history(
         &self,
         txn: &mut MdbxWriteTransaction,
         epoch_number: u32,
         leaf_indices: Range<u32>,
-    ) {
+    ) -> BTreeSet<u32> {
+        let mut removed_activity: BTreeMap<Address, Vec<(Coin, Coin)>> = BTreeMap::new();
+        let mut batches = BTreeSet::new();
+
         for leaf_index in leaf_indices.clone() {
             let tx_opt = self
                 .history_store
                 .get_historic_tx(epoch_number, leaf_index, Some(txn));
 
             let Some(hist_tx) = tx_opt else { continue };
+            batches.insert(Policy::batch_at(hist_tx.block_number));
 
             // Remove it from the transaction hash database.
             let tx_hash = hist_tx.tx_hash();
             txn.remove(&self.tx_hash_table, &tx_hash);
 
+            let index = EpochBasedIndex::new(epoch_number, leaf_index);
             let ordered_hash = OrderedHash {
-                index: EpochBasedIndex::new(epoch_number, leaf_index),
+                index,
                 value: tx_hash.into(),
             };
+            let block_index = BlockOrderedIndex::new(hist_tx.block_number, index);
             match &hist_tx.data {
                 HistoricTransactionData::Basic(tx) => {
                     let tx = tx.get_raw_transaction();
                     txn.remove_item(&self.address_table, &tx.sender, &ordered_hash);
                     txn.remove_item(&self.address_table, &tx.recipient, &ordered_hash);
+                    txn.remove_item(&self.address_block_table, &tx.sender, &block_index);
+                    txn.remove_item(&self.address_block_table, &tx.recipient, &block_index);
                 }
                 HistoricTransactionData::Reward(ev) => {
                     txn.remove_item(&self.address_table, &ev.reward_address, &ordered_hash);
+                    txn.remove_item(&self.address_block_table, &ev.reward_address, &block_index);
                 }
                 HistoricTransactionData::Equivocation(_)
                 | HistoricTransactionData::Penalize(_)
                 | HistoricTransactionData::Jail(_) => {}
             }
+
+            for (address, received, sent) in AddressSummary::activity(&hist_tx) {
+                removed_activity
+                    .entry(address)
+                    .or_default()
+                    .push((received, sent));
+            }
+        }
+
+        for (address, removed) in removed_activity {
+            self.remove_from_address_summary(txn, &address, &removed);
+        }
+
+        batches
+    }
+
+    /// Recomputes the address filters of the given batches from the historic transactions that
+    /// are left in the history store. Filters of batches without any addresses are removed.
+    fn rebuild_address_filters(&self, txn: &mut MdbxWriteTransaction, batches: BTreeSet<u32>) {
+        for batch in batches {
+            let first_block = Policy::first_block_of_batch(batch)
+                .unwrap_or(0)
+                .max(Policy::genesis_block_number());
+            let last_block = Policy::macro_block_of(batch).unwrap_or(u32::MAX);
+
+            let mut filter = AddressFilter::new();
+            for block_number in first_block..=last_block {
+                for hist_tx in self
+                    .history_store
+                    .get_block_transactions(block_number, Some(txn))
+                {
+                    for (address, _, _) in AddressSummary::activity(&hist_tx) {
+                        filter.insert(&address);
+                    }
+                }
+            }
+
+            if filter.is_empty() {
+                txn.remove(&self.address_filter_table, &batch);
+            } else {
+                txn.put(&self.address_filter_table, &batch, &filter);
+            }
+        }
+    }
+
+    /// Removes the given activity from the summary of an address. The first and last seen block
+    /// numbers are determined from the remaining entries in the address table.
+    fn remove_from_address_summary(
+        &self,
+        txn: &mut MdbxWriteTransaction,
+        address: &Address,
+        removed: &[(Coin, Coin)],
+    ) {
+        let Some(mut summary) = txn.get(&self.address_summary_table, address) else {
+            return;
+        };
+        for (received, sent) in removed {
+            summary.remove(*received, *sent);
+        }
+
+        let seen = {
+            let mut cursor = WriteTransaction::dup_cursor(txn, &self.address_table);
+            cursor.set_key(address).and_then(|_| {
+                let first = cursor.first_duplicate()?;
+                let last = cursor.last_duplicate()?;
+                Some((first.index, last.index))
+            })
+        };
+        let seen = seen.and_then(|(first, last)| {
+            let block_number_of = |index: EpochBasedIndex| {
+                self.history_store
+                    .get_historic_tx(index.epoch_number, index.index, Some(txn))
+                    .map(|hist_tx| hist_tx.block_number)
+            };
+            Some((block_number_of(first)?, block_number_of(last)?))
+        });
+
+        match seen {
+            Some((first_seen, last_seen)) if summary.tx_count > 0 => {
+                summary.first_seen = first_seen;
+                summary.last_seen = last_seen;
+                txn.put(&self.address_summary_table, address, &summary);
+            }
+            _ => txn.remove(&self.address_summary_table, address),
         }
     }
 
@@ -130,12 +267,29 @@ impl HistoryStoreIndex {
         &self,
         hashes: &mut BTreeMap<RawTransactionHash, EpochBasedIndex>,
         addresses: &mut BTreeMap<Address, Vec<OrderedHash>>,
+        address_blocks: &mut BTreeMap<Address, Vec<BlockOrderedIndex>>,
+        summaries: &mut BTreeMap<Address, AddressSummary>,
+        filters: &mut BTreeMap<u32, AddressFilter>,
         epoch_number: u32,
         leaf_index: u32,
         hist_tx: &HistoricTransaction,
     ) {
         let key = EpochBasedIndex::new(epoch_number, leaf_index);
 
+        let filter = filters
+            .entry(Policy::batch_at(hist_tx.block_number))
+            .or_default();
+        for (address, received, sent) in AddressSummary::activity(hist_tx) {
+            filter.insert(&address);
+            let summary = AddressSummary::new(hist_tx.block_number, received, sent);
+            match summaries.entry(address) {
+                Entry::Vacant(entry) => {
+                    entry.insert(summary);
+                }
+                Entry::Occupied(mut entry) => entry.get_mut().merge(&summary),
+            }
+        }
+
         // The raw tx hash corresponds to the hash without the execution result.
         // Thus for basic historic transactions we want discoverability for the raw transaction.
         let raw_tx_hash = hist_tx.tx_hash();
@@ -145,6 +299,7 @@ impl HistoryStoreIndex {
             index: key,
             value: raw_tx_hash.into(),
         };
+        let block_index = BlockOrderedIndex::new(hist_tx.block_number, key);
         match &hist_tx.data {
             HistoricTransactionData::Basic(tx) => {
                 let tx = tx.get_raw_transaction();
@@ -156,6 +311,14 @@ impl HistoryStoreIndex {
                     .entry(tx.recipient.clone())
                     .or_default()
                     .push(ordered_hash);
+                address_blocks
+                    .entry(tx.sender.clone())
+                    .or_default()
+                    .push(block_index.clone());
+                address_blocks
+                    .entry(tx.recipient.clone())
+                    .or_default()
+                    .push(block_index);
             }
             HistoricTransactionData::Reward(ev) => {
                 // We only add reward inherents to the address database.
@@ -163,6 +326,10 @@ impl HistoryStoreIndex {
                     .entry(ev.reward_address.clone())
                     .or_default()
                     .push(ordered_hash);
+                address_blocks
+                    .entry(ev.reward_address.clone())
+                    .or_default()
+                    .push(block_index);
             }
             // Do not index equivocation or punishments events, since I do not see a use case
             // for this at the time.
@@ -195,16 +362,25 @@ impl HistoryStoreIndex {
         // Clear the tables.
         txn.clear_table(&self.tx_hash_table);
         txn.clear_table(&self.address_table);
+        txn.clear_table(&self.address_block_table);
+        txn.clear_table(&self.address_summary_table);
+        txn.clear_table(&self.address_filter_table);
 
         // Iterate over all epochs and leafs.
         let mut hashes = BTreeMap::new();
         let mut addresses = BTreeMap::new();
+        let mut address_blocks = BTreeMap::new();
+        let mut summaries = BTreeMap::new();
+        let mut filters = BTreeMap::new();
         let cursor = WriteTransaction::dup_cursor(txn, &self.history_store.hist_tx_table);
         debug!("Reading historic transactions.");
         for (epoch_number, hist_tx) in cursor.into_iter_start() {
             self.put_historic_tx(
                 &mut hashes,
                 &mut addresses,
+                &mut address_blocks,
+                &mut summaries,
+                &mut filters,
                 epoch_number,
                 hist_tx.index,
                 &hist_tx.value,
@@ -225,6 +401,58 @@ impl HistoryStoreIndex {
                 addresses_cursor.append(address, ordered_hash);
             }
         }
+
+        debug!("Writing address block index");
+        let mut address_blocks_cursor =
+            WriteTransaction::dup_cursor(txn, &self.address_block_table);
+        for (address, block_indices) in address_blocks.iter() {
+            for block_index in block_indices.iter() {
+                address_blocks_cursor.append(address, block_index);
+            }
+        }
+
+        debug!("Writing address summaries");
+        let mut summaries_cursor = WriteTransaction::cursor(txn, &self.address_summary_table);
+        for (address, summary) in summaries.iter() {
+            summaries_cursor.append(address, summary);
+        }
+
+        debug!("Writing address filters");
+        let mut filters_cursor = WriteTransaction::cursor(txn, &self.address_filter_table);
+        for (batch, filter) in filters.iter().filter(|(_, filter)| !filter.is_empty()) {
+            filters_cursor.append(batch, filter);
+        }
+    }
+
+    /// Moves the cursor to the most recent transaction of the given address before the given
+    /// cursor (exclusive), or to the most recent transaction of the address if no cursor is given.
+    /// Returns `None` if there is no such transaction.
+    fn seek_last_block_index_before(
+        cursor: &mut CursorProxy<'_, AddressBlockTable>,
+        address: &Address,
+        before: Option<AddressTxCursor>,
+    ) -> Option<BlockOrderedIndex> {
+        let Some(before) = before else {
+            cursor.set_key(address)?;
+            return cursor.last_duplicate();
+        };
+
+        // Seek to the first transaction in the block of the cursor or later. If there's none, all
+        // transactions of the address are before the cursor.
+        let Some(mut block_index) = cursor.set_lowerbound_subkey(address, &before.block_number)
+        else {
+            cursor.set_key(address)?;
+            return cursor.last_duplicate();
+        };
+
+        // Skip the transactions of the same block that are still before the cursor.
+        while AddressTxCursor::from(&block_index) < before {
+            match cursor.next_duplicate() {
+                Some((_, next)) => block_index = next,
+                None => return Some(block_index),
+            }
+        }
+        cursor.prev_duplicate().map(|(_, block_index)| block_index)
     }
 
     /// Returns an iterator containing all transaction (and reward inherents) hashes corresponding to the given
@@ -359,6 +587,9 @@ impl HistoryInterface for HistoryStoreIndex {
         self.history_store.clear(txn);
         txn.clear_table(&self.tx_hash_table);
         txn.clear_table(&self.address_table);
+        txn.clear_table(&self.address_block_table);
+        txn.clear_table(&self.address_summary_table);
+        txn.clear_table(&self.address_filter_table);
     }
 
     fn length_at(
@@ -518,8 +749,39 @@ impl HistoryInterface for HistoryStoreIndex {
             // Sort everything first and then put with a cursor for improved database performance.
             let mut hashes = BTreeMap::new();
             let mut addresses = BTreeMap::new();
+            let mut address_blocks = BTreeMap::new();
+            let mut summaries = BTreeMap::new();
+            let mut filters = BTreeMap::new();
             for (tx, i) in hist_txs.iter().zip(leaf_idx.iter()) {
-                self.put_historic_tx(&mut hashes, &mut addresses, epoch_number, *i, tx);
+                self.put_historic_tx(
+                    &mut hashes,
+                    &mut addresses,
+                    &mut address_blocks,
+                    &mut summaries,
+                    &mut filters,
+                    epoch_number,
+                    *i,
+                    tx,
+                );
+            }
+
+            // Update the summaries of all involved addresses.
+            for (address, mut summary) in summaries {
+                if let Some(existing) = txn.get(&self.address_summary_table, &address) {
+                    summary.merge(&existing);
+                }
+                txn.put(&self.address_summary_table, &address, &summary);
+            }
+
+            // Add the addresses to the filters of their batches.
+            for (batch, mut filter) in filters {
+                if filter.is_empty() {
+                    continue;
+                }
+                if let Some(existing) = txn.get(&self.address_filter_table, &batch) {
+                    filter.merge(&existing);
+                }
+                txn.put(&self.address_filter_table, &batch, &filter);
             }
 
             // Put the hashes and addresses into the respective databases.
@@ -533,6 +795,13 @@ impl HistoryInterface for HistoryStoreIndex {
                     address_cursor.put(address, ordered_hash);
                 }
             }
+            let mut address_block_cursor =
+                WriteTransaction::dup_cursor(txn, &self.address_block_table);
+            for (address, block_indices) in address_blocks.iter() {
+                for block_index in block_indices.iter() {
+                    address_block_cursor.put(address, block_index);
+                }
+            }
             return Some((root, size));
         }
         None
@@ -552,10 +821,11 @@ impl HistoryInterface for HistoryStoreIndex {
 
         // Remove each of the historic transactions in the history tree from the extended
         // transaction database.
-        self.remove_txns_from_history(txn, epoch_number, leaf_indices.clone());
+        let batches = self.remove_txns_from_history(txn, epoch_number, leaf_indices.clone());
         let txns_size =
             self.history_store
                 .remove_txns_from_history(txn, epoch_number, leaf_indices);
+        self.rebuild_address_filters(txn, batches);
 
         // Return the history root.
         Some((root, txns_size))
@@ -567,9 +837,10 @@ impl HistoryInterface for HistoryStoreIndex {
         let (_, leaf_indices) =
             self.history_store
                 .remove_leaves_from_history(txn, epoch_number, None)?;
-        self.remove_txns_from_history(txn, epoch_number, leaf_indices);
+        let batches = self.remove_txns_from_history(txn, epoch_number, leaf_indices);
         self.history_store
             .remove_epoch_from_history(txn, epoch_number);
+        self.rebuild_address_filters(txn, batches);
 
         Some(())
     }
@@ -656,6 +927,84 @@ impl HistoryIndexInterface for HistoryStoreIndex {
             .collect()
     }
 
+    /// Returns the historic transactions (and reward inherents) corresponding to the given address
+    /// together with their cursors. It fetches the transactions from most recent to least recent
+    /// up to the given limit. If a cursor is given, only the transactions before it are returned.
+    fn get_transactions_by_address(
+        &self,
+        address: &Address,
+        before: Option<AddressTxCursor>,
+        limit: u16,
+        txn_option: Option<&MdbxReadTransaction>,
+    ) -> Vec<(AddressTxCursor, HistoricTransaction)> {
+        if limit == 0 {
+            return vec![];
+        }
+
+        let txn = txn_option.or_new(&self.db);
+        let mut cursor = txn.dup_cursor(&self.address_block_table);
+
+        let Some(last) = Self::seek_last_block_index_before(&mut cursor, address, before) else {
+            return vec![];
+        };
+
+        iter::successors(Some(last), |_| {
+            cursor.prev_duplicate().map(|(_, block_index)| block_index)
+        })
+        .take(limit as usize)
+        .filter_map(|block_index| {
+            let hist_tx = self.history_store.get_historic_tx(
+                block_index.value.epoch_number,
+                block_index.value.index,
+                Some(&txn),
+            )?;
+            Some((AddressTxCursor::from(&block_index), hist_tx))
+        })
+        .collect()
+    }
+
+    /// Returns the cursor of the transaction with the given hash, or `None` if it isn't known.
+    fn get_address_tx_cursor(
+        &self,
+        raw_tx_hash: &Blake2bHash,
+        txn_option: Option<&MdbxReadTransaction>,
+    ) -> Option<AddressTxCursor> {
+        let txn = txn_option.or_new(&self.db);
+
+        let index = self.get_leaf_indices_by_tx_hash(raw_tx_hash, Some(&txn))?;
+        let hist_tx =
+            self.history_store
+                .get_historic_tx(index.epoch_number, index.index, Some(&txn))?;
+
+        Some(AddressTxCursor {
+            block_number: hist_tx.block_number,
+            epoch_number: index.epoch_number,
+            leaf_index: index.index,
+        })
+    }
+
+    /// Returns the activity summary of the given address, or `None` if the address never appeared
+    /// in a transaction.
+    fn get_address_summary(
+        &self,
+        address: &Address,
+        txn_option: Option<&MdbxReadTransaction>,
+    ) -> Option<AddressSummary> {
+        let txn = txn_option.or_new(&self.db);
+        txn.get(&self.address_summary_table, address)
+    }
+
+    /// Returns the filter over all addresses touched in the given batch, or `None` if there were
+    /// no such addresses.
+    fn get_address_filter(
+        &self,
+        batch_number: u32,
+        txn_option: Option<&MdbxReadTransaction>,
+    ) -> Option<AddressFilter> {
+        let txn = txn_option.or_new(&self.db);
+        txn.get(&self.address_filter_table, &batch_number)
+    }
+
     /// Returns a proof for transactions with the given hashes. The proof also includes the extended
     /// transactions.
     /// The verifier state is used for those cases where the verifier might have an incomplete MMR,
@@ -1305,6 +1654,225 @@ mod tests {
         assert_eq!(query_5[1], *hashes[1]);
     }
 
+    #[test]
+    fn get_transactions_by_address_works() {
+        // Initialize History Store.
+        let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+        let history_store = HistoryStoreIndex::new(env.clone(), NetworkId::UnitAlbatross);
+
+        // Create historic transactions.
+        let hist_txs = gen_hist_txs();
+
+        // Add historic transactions to History Store.
+        let mut txn = env.write_transaction();
+        history_store.add_to_history(&mut txn, Policy::genesis_block_number(), &hist_txs[..3]);
+        history_store.add_to_history(&mut txn, Policy::genesis_block_number() + 2, &hist_txs[3..]);
+
+        let sender =
+            Address::from_user_friendly_address("NQ09 VF5Y 1PKV MRM4 5LE1 55KV P6R2 GXYJ XYQF")
+                .unwrap();
+        let page = |before, limit, txn: &MdbxReadTransaction| {
+            history_store
+                .get_transactions_by_address(&sender, before, limit, Some(txn))
+                .into_iter()
+                .map(|(cursor, hist_tx)| (cursor, hist_tx.tx_hash()))
+                .collect::<Vec<_>>()
+        };
+        let hashes: Vec<_> = hist_txs.iter().map(|hist_tx| hist_tx.tx_hash()).collect();
+
+        // Page through the transactions of the sender, which spans blocks.
+        let page_1 = page(None, 2, &txn);
+        assert_eq!(page_1.len(), 2);
+        assert_eq!(page_1[0].1, hashes[6]);
+        assert_eq!(page_1[1].1, hashes[5]);
+        assert!(page_1[0].0 > page_1[1].0);
+
+        let page_2 = page(Some(page_1[1].0), 2, &txn);
+        assert_eq!(page_2.len(), 2);
+        assert_eq!(page_2[0].1, hashes[3]);
+        assert_eq!(page_2[1].1, hashes[1]);
+
+        let page_3 = page(Some(page_2[1].0), 2, &txn);
+        assert_eq!(page_3.len(), 1);
+        assert_eq!(page_3[0].1, hashes[0]);
+
+        assert!(page(Some(page_3[0].0), 2, &txn).is_empty());
+        assert!(page(None, 0, &txn).is_empty());
+
+        // A cursor in the middle of a block continues within the block.
+        let page_4 = page(Some(page_1[0].0), 99, &txn);
+        assert_eq!(page_4.len(), 4);
+        assert_eq!(page_4[0].1, hashes[5]);
+
+        // The cursor can be obtained from a transaction hash.
+        assert_eq!(
+            history_store.get_address_tx_cursor(&hashes[5].deref().clone(), Some(&txn)),
+            Some(page_1[1].0)
+        );
+        assert_eq!(
+            history_store.get_address_tx_cursor(&Blake2bHash::default(), Some(&txn)),
+            None
+        );
+
+        // A cursor after all transactions returns the most recent ones.
+        let after_all = AddressTxCursor {
+            block_number: Policy::genesis_block_number() + 3,
+            epoch_number: 0,
+            leaf_index: 0,
+        };
+        assert_eq!(page(Some(after_all), 99, &txn).len(), 5);
+
+        // Reward inherents are indexed by their reward address.
+        let rewarded =
+            Address::from_user_friendly_address("NQ04 B79B R4FF 4NGU A9H0 2PT9 9ART 5A88 J73T")
+                .unwrap();
+        let rewards = history_store.get_transactions_by_address(&rewarded, None, 99, Some(&txn));
+        assert_eq!(rewards.len(), 3);
+        assert_eq!(rewards[0].1.tx_hash(), hashes[7]);
+
+        // Removed transactions are removed from the index.
+        history_store.remove_partial_history(&mut txn, 1, 8);
+        let page_5 = page(None, 99, &txn);
+        assert_eq!(page_5.len(), 2);
+        assert_eq!(page_5[0].1, hashes[1]);
+        assert_eq!(page_5[1].1, hashes[0]);
+    }
+
+    #[test]
+    fn get_address_summary_works() {
+        let genesis_block_number = Policy::genesis_block_number();
+        // Initialize History Store.
+        let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+        let history_store = HistoryStoreIndex::new(env.clone(), NetworkId::UnitAlbatross);
+
+        // Create historic transactions.
+        let hist_txs = gen_hist_txs();
+
+        // Add historic transactions to History Store.
+        let mut txn = env.write_transaction();
+        history_store.add_to_history(&mut txn, genesis_block_number, &hist_txs[..3]);
+        history_store.add_to_history(&mut txn, genesis_block_number + 2, &hist_txs[3..]);
+
+        let sender =
+            Address::from_user_friendly_address("NQ09 VF5Y 1PKV MRM4 5LE1 55KV P6R2 GXYJ XYQF")
+                .unwrap();
+        let reward_address =
+            Address::from_user_friendly_address("NQ04 B79B R4FF 4NGU A9H0 2PT9 9ART 5A88 J73T")
+                .unwrap();
+
+        // Verify method works.
+        let summary = history_store
+            .get_address_summary(&sender, Some(&txn))
+            .unwrap();
+        assert_eq!(summary.first_seen, genesis_block_number);
+        assert_eq!(summary.last_seen, genesis_block_number + 2);
+        assert_eq!(summary.tx_count, 5);
+        assert_eq!(summary.total_received, Coin::ZERO);
+        assert_eq!(summary.total_sent, Coin::from_u64_unchecked(15));
+
+        let summary = history_store
+            .get_address_summary(&Address::burn_address(), Some(&txn))
+            .unwrap();
+        assert_eq!(summary.tx_count, 5);
+        assert_eq!(summary.total_received, Coin::from_u64_unchecked(15));
+        assert_eq!(summary.total_sent, Coin::ZERO);
+
+        // Jail, penalize and equivocation inherents are not part of the summary.
+        let summary = history_store
+            .get_address_summary(&reward_address, Some(&txn))
+            .unwrap();
+        assert_eq!(summary.tx_count, 3);
+        assert_eq!(summary.total_received, Coin::from_u64_unchecked(13));
+
+        assert!(history_store
+            .get_address_summary(
+                &Address::from_user_friendly_address(
+                    "NQ28 1U7R M38P GN5A 7J8R GE62 8QS7 PK2S 4S31"
+                )
+                .unwrap(),
+                Some(&txn)
+            )
+            .is_none());
+
+        // Remove the historic transactions of the last block.
+        history_store.remove_partial_history(&mut txn, 1, 6);
+
+        // Verify the summaries were updated.
+        let summary = history_store
+            .get_address_summary(&sender, Some(&txn))
+            .unwrap();
+        assert_eq!(summary.first_seen, genesis_block_number);
+        assert_eq!(summary.last_seen, genesis_block_number + 1);
+        assert_eq!(summary.tx_count, 3);
+        assert_eq!(summary.total_sent, Coin::from_u64_unchecked(4));
+
+        let summary = history_store
+            .get_address_summary(&reward_address, Some(&txn))
+            .unwrap();
+        assert_eq!(summary.last_seen, genesis_block_number + 1);
+        assert_eq!(summary.tx_count, 2);
+        assert_eq!(summary.total_received, Coin::from_u64_unchecked(6));
+
+        // Remove all remaining historic transactions.
+        history_store.remove_history(&mut txn, 1);
+        history_store.remove_history(&mut txn, 0);
+
+        assert!(history_store
+            .get_address_summary(&sender, Some(&txn))
+            .is_none());
+    }
+
+    #[test]
+    fn get_address_filter_works() {
+        let genesis_block_number = Policy::genesis_block_number();
+        // Initialize History Store.
+        let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+        let history_store = HistoryStoreIndex::new(env.clone(), NetworkId::UnitAlbatross);
+
+        // Create historic transactions.
+        let hist_txs = gen_hist_txs();
+
+        // Add historic transactions to History Store.
+        let mut txn = env.write_transaction();
+        history_store.add_to_history(&mut txn, genesis_block_number, &hist_txs[..3]);
+        history_store.add_to_history(&mut txn, genesis_block_number + 1, &hist_txs[3..5]);
+        history_store.add_to_history(&mut txn, genesis_block_number + 2, &hist_txs[5..]);
+
+        let sender =
+            Address::from_user_friendly_address("NQ09 VF5Y 1PKV MRM4 5LE1 55KV P6R2 GXYJ XYQF")
+                .unwrap();
+        let reward_address =
+            Address::from_user_friendly_address("NQ04 B79B R4FF 4NGU A9H0 2PT9 9ART 5A88 J73T")
+                .unwrap();
+        let unrelated =
+            Address::from_user_friendly_address("NQ28 1U7R M38P GN5A 7J8R GE62 8QS7 PK2S 4S31")
+                .unwrap();
+
+        // Verify method works.
+        let filter = history_store.get_address_filter(1, Some(&txn)).unwrap();
+        assert!(filter.contains(&sender));
+        assert!(filter.contains(&Address::burn_address()));
+        assert!(filter.contains(&reward_address));
+        assert!(!filter.contains(&unrelated));
+
+        assert!(history_store.get_address_filter(0, Some(&txn)).is_some());
+        assert!(history_store.get_address_filter(2, Some(&txn)).is_none());
+
+        // Remove the historic transactions of the last block. The filter is rebuilt from the
+        // remaining transactions of the batch.
+        history_store.remove_partial_history(&mut txn, 1, 6);
+
+        let filter = history_store.get_address_filter(1, Some(&txn)).unwrap();
+        assert!(filter.contains(&sender));
+        assert!(filter.contains(&reward_address));
+
+        // Remove all remaining historic transactions of the batch.
+        history_store.remove_partial_history(&mut txn, 1, 2);
+
+        assert!(history_store.get_address_filter(1, Some(&txn)).is_none());
+        assert!(history_store.get_address_filter(0, Some(&txn)).is_some());
+    }
+
     #[test]
     fn prove_works() {
         // Initialize History Store.
@@ -9,13 +9,14 @@ use nimiq_mmr::{
     mmr::proof::{RangeProof, SizeProof},
 };
 use nimiq_transaction::{
+    address_filter::AddressFilter,
     historic_transaction::{HistoricTransaction, RawTransactionHash},
     history_proof::HistoryTreeProof,
     inherent::Inherent,
     EquivocationLocator,
 };
 
-use crate::HistoryTreeChunk;
+use crate::{AddressSummary, AddressTxCursor, HistoryTreeChunk};
 
 /// Defines several methods to interact with a history store.
 pub trait HistoryInterface: Debug {
@@ -207,6 +208,41 @@ pub trait HistoryIndexInterface: HistoryInterface {
         txn_option: Option<&MdbxReadTransaction>,
     ) -> Vec<Blake2bHash>;
 
+    /// Returns the historic transactions (and reward inherents) corresponding to the given address
+    /// together with their cursors. It fetches the transactions from most recent to least recent
+    /// up to the given limit. If a cursor is given, only the transactions before it are returned,
+    /// such that the cursor of the last transaction of a page can be used to fetch the next one.
+    fn get_transactions_by_address(
+        &self,
+        address: &Address,
+        before: Option<AddressTxCursor>,
+        limit: u16,
+        txn_option: Option<&MdbxReadTransaction>,
+    ) -> Vec<(AddressTxCursor, HistoricTransaction)>;
+
+    /// Returns the cursor of the transaction with the given hash, or `None` if it isn't known.
+    fn get_address_tx_cursor(
+        &self,
+        raw_tx_hash: &Blake2bHash,
+        txn_option: Option<&MdbxReadTransaction>,
+    ) -> Option<AddressTxCursor>;
+
+    /// Returns the activity summary of the given address, or `None` if the address never appeared
+    /// in a transaction.
+    fn get_address_summary(
+        &self,
+        address: &Address,
+        txn_option: Option<&MdbxReadTransaction>,
+    ) -> Option<AddressSummary>;
+
+    /// Returns the filter over all addresses touched in the given batch, or `None` if there were
+    /// no such addresses.
+    fn get_address_filter(
+        &self,
+        batch_number: u32,
+        txn_option: Option<&MdbxReadTransaction>,
+    ) -> Option<AddressFilter>;
+
     /// Returns a proof for transactions with the given hashes. The proof also includes the extended
     /// transactions.
     /// The verifier state is used for those cases where the verifier might have an incomplete MMR,
@@ -10,13 +10,18 @@ use nimiq_mmr::{
 };
 use nimiq_primitives::policy::Policy;
 use nimiq_transaction::{
+    address_filter::AddressFilter,
     historic_transaction::{HistoricTransaction, RawTransactionHash},
     history_proof::HistoryTreeProof,
     inherent::Inherent,
     EquivocationLocator,
 };
 
-use super::interface::{HistoryIndexInterface, HistoryInterface};
+use super::{
+    address_summary::AddressSummary,
+    address_tx_cursor::AddressTxCursor,
+    interface::{HistoryIndexInterface, HistoryInterface},
+};
 
 /// A wrapper around two history stores, one for the pre-genesis epoch and one for the main epoch.
 #[derive(Debug)]
@@ -378,6 +383,87 @@ impl<S: HistoryInterface + HistoryIndexInterface> HistoryIndexInterface for Hist
         tx_hashes
     }
 
+    fn get_transactions_by_address(
+        &self,
+        address: &Address,
+        before: Option<AddressTxCursor>,
+        limit: u16,
+        txn_option: Option<&MdbxReadTransaction>,
+    ) -> Vec<(AddressTxCursor, HistoricTransaction)> {
+        let mut txs = self
+            .main
+            .get_transactions_by_address(address, before, limit, txn_option);
+
+        // Fill up from pre-genesis if necessary. Pre-genesis transactions precede all transactions
+        // in the main database, so the same cursor applies.
+        if txs.len() < limit as usize && self.pre_genesis.is_some() {
+            let mut pre_genesis_txs = self
+                .pre_genesis
+                .as_ref()
+                .unwrap()
+                .get_transactions_by_address(address, before, limit - txs.len() as u16, None);
+            txs.append(&mut pre_genesis_txs);
+        }
+
+        txs
+    }
+
+    fn get_address_tx_cursor(
+        &self,
+        raw_tx_hash: &Blake2bHash,
+        txn_option: Option<&MdbxReadTransaction>,
+    ) -> Option<AddressTxCursor> {
+        self.main
+            .get_address_tx_cursor(raw_tx_hash, txn_option)
+            .or_else(|| {
+                self.pre_genesis
+                    .as_ref()?
+                    .get_address_tx_cursor(raw_tx_hash, None)
+            })
+    }
+
+    fn get_address_summary(
+        &self,
+        address: &Address,
+        txn_option: Option<&MdbxReadTransaction>,
+    ) -> Option<AddressSummary> {
+        let summary = self.main.get_address_summary(address, txn_option);
+        let pre_genesis_summary = self
+            .pre_genesis
+            .as_ref()
+            .and_then(|pre_genesis| pre_genesis.get_address_summary(address, None));
+
+        match (summary, pre_genesis_summary) {
+            (Some(mut summary), Some(pre_genesis_summary)) => {
+                summary.merge(&pre_genesis_summary);
+                Some(summary)
+            }
+            (summary, pre_genesis_summary) => summary.or(pre_genesis_summary),
+        }
+    }
+
+    fn get_address_filter(
+        &self,
+        batch_number: u32,
+        txn_option: Option<&MdbxReadTransaction>,
+    ) -> Option<AddressFilter> {
+        let filter = self.main.get_address_filter(batch_number, txn_option);
+        // Only the first batch can contain pre-genesis transactions.
+        let pre_genesis_filter = self
+            .pre_genesis
+            .as_ref()
+            .filter(|_| batch_number == 0)
+            .and_then(|pre_genesis| pre_genesis.get_address_filter(batch_number, None));
+
+        match (filter, pre_genesis_filter) {
+            (Some(mut filter), Some(pre_genesis_filter)) => {
+                filter.merge(&pre_genesis_filter);
+                Some(filter)
+            }
+            (filter, pre_genesis_filter) => filter.or(pre_genesis_filter),
+        }
+    }
+
     fn prove(
         &self,
         epoch_number: u32,
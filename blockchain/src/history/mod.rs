@@ -1,8 +1,12 @@
+pub use address_summary::AddressSummary;
+pub use address_tx_cursor::AddressTxCursor;
 pub use history_store::HistoryStore;
 pub use history_store_index::HistoryStoreIndex;
 pub use history_tree_chunk::{HistoryTreeChunk, CHUNK_SIZE};
 pub use merged_history_store::HistoryStoreMerger;
 
+mod address_summary;
+mod address_tx_cursor;
 mod history_store;
 mod history_store_index;
 pub mod history_store_proxy;
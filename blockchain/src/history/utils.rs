@@ -6,6 +6,7 @@ use nimiq_hash::Blake2bHash;
 use nimiq_transaction::historic_transaction::HistoricTransaction;
 
 pub type OrderedHash = IndexedValue<EpochBasedIndex, Blake2bHash>;
+pub type BlockOrderedIndex = IndexedValue<u32, EpochBasedIndex>;
 pub type IndexedTransaction = IndexedValue<u32, HistoricTransaction>;
 pub type IndexedHash = IndexedValue<u32, Blake2bHash>;
 
@@ -1,10 +1,13 @@
 #[macro_use]
 extern crate log;
 
-pub use block_production::{BlockProducer, BlockProducerError};
+pub use block_production::{BlockProducer, BlockProducerError, BlockSigner, MicroBlockPreview};
 pub use blockchain::{
     blockchain::{Blockchain, BlockchainConfig, TransactionVerificationCache},
-    PostValidationHook,
+    integrity::IntegrityReport,
+    replay::{RecordedInput, RecordingHeader, ReplayError, ReplayResult},
+    snapshot::{StateSnapshotError, StateSnapshotHeader},
+    BlockContext, PostValidationHook,
 };
 pub use history::*;
 
@@ -1,7 +1,12 @@
 use std::convert::TryInto;
 
 use nimiq_block::MacroHeader;
-use nimiq_primitives::{coin::Coin, policy::Policy};
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_keys::Address;
+use nimiq_primitives::{account::AccountType, coin::Coin, policy::Policy};
+use thiserror::Error;
+
+use crate::Blockchain;
 
 /// Parses the genesis supply and timestamp from the genesis block. We require both values to
 /// calculate the block rewards.
@@ -20,12 +25,14 @@ pub fn genesis_parameters(genesis_block: &MacroHeader) -> (Coin, u64) {
     (supply, genesis_block.timestamp)
 }
 
+// Compute the timestamp (in ms) at which a batch is expected to end
+pub fn batch_target_timestamp(previous_timestamp: u64) -> u64 {
+    previous_timestamp + Policy::BLOCK_SEPARATION_TIME * (Policy::blocks_per_batch() as u64)
+}
+
 // Compute the current batch delay(in ms)
 pub fn batch_delay(previous_timestamp: u64, current_timestamp: u64) -> u64 {
-    let target_ts =
-        previous_timestamp + Policy::BLOCK_SEPARATION_TIME * (Policy::blocks_per_batch() as u64);
-
-    current_timestamp.saturating_sub(target_ts)
+    current_timestamp.saturating_sub(batch_target_timestamp(previous_timestamp))
 }
 
 /// Compute the block reward for a batch from the current macro block, the previous macro block,
@@ -37,10 +44,23 @@ pub fn block_reward_for_batch(
     genesis_supply: Coin,
     genesis_timestamp: u64,
 ) -> Coin {
-    let current_timestamp = current_block.timestamp;
-
-    let previous_timestamp = previous_macro.timestamp;
+    block_reward_for_batch_at(
+        current_block.timestamp,
+        previous_macro.timestamp,
+        genesis_supply,
+        genesis_timestamp,
+    )
+}
 
+/// Compute the block reward for a batch ending at the given timestamp from the timestamp of the
+/// previous macro block and the genesis parameters.
+/// This does not include the reward from transaction fees.
+pub fn block_reward_for_batch_at(
+    current_timestamp: u64,
+    previous_timestamp: u64,
+    genesis_supply: Coin,
+    genesis_timestamp: u64,
+) -> Coin {
     assert!(current_timestamp >= previous_timestamp);
     assert!(previous_timestamp >= genesis_timestamp);
 
@@ -61,7 +81,7 @@ pub fn block_reward_for_batch(
     let batch_delay_penalty = Policy::batch_delay_penalty(batch_delay);
 
     debug!(
-        block_number = current_block.block_number,
+        timestamp = current_timestamp,
         delay = batch_delay,
         penalty = batch_delay_penalty,
         "Computed the batch delay and penalty (if any)",
@@ -84,3 +104,175 @@ pub fn block_reward_for_batch_with_genesis(
 
     block_reward_for_batch(current_block, previous_macro, supply, timestamp)
 }
+
+/// The expected reward of a validator for the batch that is paid out by the next macro block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RewardPreview {
+    /// The epoch of the validator slots that are rewarded.
+    pub epoch_number: u32,
+    /// The block number of the macro block that pays out the rewards.
+    pub payout_block_number: u32,
+    /// The delay (in ms) of the batch, assuming the payout block is produced at the given time.
+    pub batch_delay: u64,
+    /// The block reward for the batch after the batch delay penalty, excluding transaction fees.
+    pub block_reward: Coin,
+    /// The transaction fees that are paid out in addition to the block reward.
+    pub tx_fees: Coin,
+    /// The reward for a single slot.
+    pub slot_reward: Coin,
+    /// The number of slots of the validator.
+    pub num_slots: u16,
+    /// The number of slots of the validator that are punished and thus don't receive a reward.
+    pub num_punished_slots: u16,
+    /// The address the reward is paid to.
+    pub reward_address: Address,
+    /// The reward paid to the reward address. It is zero if the reward address can't accept it.
+    pub reward: Coin,
+    /// The part of the reward that is shared among the validator's stakers, according to the
+    /// validator's commission. It is zero before protocol version 2.
+    pub stakers_reward: Coin,
+    /// The part of the reward of the validator that is burned instead.
+    pub burned: Coin,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum RewardPreviewError {
+    #[error("The next macro block doesn't pay out rewards")]
+    NoRewards,
+    #[error("Rewards for epoch {0} aren't pending")]
+    EpochNotPending(u32),
+    #[error("Validator {0} has no slots in the epoch")]
+    ValidatorNotElected(Address),
+    #[error("The validator slots of the epoch are not known")]
+    SlotsUnavailable,
+    #[error("Validator {0} doesn't exist anymore")]
+    ValidatorNotFound(Address),
+    #[error("The accounts trie is incomplete")]
+    IncompleteAccounts,
+}
+
+impl Blockchain {
+    /// Computes the expected reward of a validator for the batch paid out by the next macro block.
+    /// The epoch number must be the one of the validator slots that are rewarded by that block.
+    /// The payout block is assumed to be produced at `timestamp`, or at its target time if that
+    /// is later.
+    ///
+    /// The preview takes the batch delay penalty and the slots punished so far into account. The
+    /// remainder of the reward pot is not included, as it is given to a random slot.
+    pub fn preview_reward(
+        &self,
+        epoch_number: u32,
+        validator_address: &Address,
+        timestamp: u64,
+    ) -> Result<RewardPreview, RewardPreviewError> {
+        let payout_block_number = Policy::macro_block_after(self.block_number());
+
+        // Batch 0 is finalized by definition and doesn't pay out rewards.
+        if Policy::batch_at(payout_block_number) - 1 == 0 {
+            return Err(RewardPreviewError::NoRewards);
+        }
+
+        // Rewards are paid for the slots of the previous batch, see `create_reward_transactions`.
+        let (reward_epoch, validator_slots) = if Policy::first_batch_of_epoch(payout_block_number) {
+            (
+                Policy::epoch_at(payout_block_number) - 1,
+                self.state.previous_slots.as_ref(),
+            )
+        } else {
+            (
+                Policy::epoch_at(payout_block_number),
+                self.state.current_slots.as_ref(),
+            )
+        };
+        if epoch_number != reward_epoch {
+            return Err(RewardPreviewError::EpochNotPending(epoch_number));
+        }
+        let validator_slot = validator_slots
+            .ok_or(RewardPreviewError::SlotsUnavailable)?
+            .get_validator_by_address(validator_address)
+            .ok_or_else(|| RewardPreviewError::ValidatorNotElected(validator_address.clone()))?;
+
+        // Total reward for the batch.
+        let previous_timestamp = self
+            .state
+            .macro_info
+            .head
+            .unwrap_macro_ref()
+            .header
+            .timestamp;
+        let timestamp = timestamp.max(batch_target_timestamp(previous_timestamp));
+        let block_reward = block_reward_for_batch_at(
+            timestamp,
+            previous_timestamp,
+            self.genesis_supply,
+            self.genesis_timestamp,
+        );
+        let tx_fees = self.state.macro_info.cum_tx_fees;
+        let slot_reward = (block_reward + tx_fees) / Policy::SLOTS as u64;
+
+        let txn = self.read_transaction();
+        let staking_contract = self
+            .get_staking_contract_if_complete(Some(&txn))
+            .ok_or(RewardPreviewError::IncompleteAccounts)?;
+        let num_punished_slots = staking_contract
+            .punished_slots
+            .previous_batch_punished_slots()
+            .iter()
+            .filter(|slot| validator_slot.owns_slot(*slot as u16))
+            .count() as u16;
+        let num_eligible_slots = validator_slot.num_slots() - num_punished_slots;
+
+        let mut reward = slot_reward
+            .checked_mul(num_eligible_slots as u64)
+            .expect("Overflow in reward");
+        let mut burned = slot_reward
+            .checked_mul(num_punished_slots as u64)
+            .expect("Overflow in reward");
+
+        // The validator might have been deleted after the slots were selected.
+        let validator = staking_contract
+            .get_validator(
+                &self.get_staking_contract_store().read(&txn),
+                validator_address,
+            )
+            .ok_or_else(|| RewardPreviewError::ValidatorNotFound(validator_address.clone()))?;
+
+        // The reward is burned if the reward address can't accept it.
+        // TODO Improve this check: it assumes that only BasicAccounts can receive transactions.
+        let account = self
+            .state
+            .accounts
+            .get(&validator.reward_address, Some(&txn))
+            .map_err(|_| RewardPreviewError::IncompleteAccounts)?;
+        if account.account_type() != AccountType::Basic {
+            burned += reward;
+            reward = Coin::ZERO;
+        }
+
+        // The stakers' share of the reward is paid to the staking contract.
+        let mut stakers_reward = Coin::ZERO;
+        if Policy::version_at(payout_block_number) >= 2 {
+            let commission = staking_contract.get_validator_commission(
+                &self.get_staking_contract_store().read(&txn),
+                validator_address,
+            );
+            stakers_reward = validator.stakers_reward(reward, commission);
+            reward -= stakers_reward;
+        }
+
+        Ok(RewardPreview {
+            epoch_number,
+            payout_block_number,
+            batch_delay: batch_delay(previous_timestamp, timestamp),
+            block_reward,
+            tx_fees,
+            slot_reward,
+            num_slots: validator_slot.num_slots(),
+            num_punished_slots,
+            reward_address: validator.reward_address,
+            reward,
+            stakers_reward,
+            burned,
+        })
+    }
+}
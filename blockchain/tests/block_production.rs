@@ -243,6 +243,48 @@ fn it_can_produce_micro_blocks_with_equivocation_proofs() {
     }
 }
 
+#[test]
+fn it_can_preview_micro_blocks() {
+    let time = Arc::new(OffsetTime::new());
+    let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+    let blockchain = Arc::new(RwLock::new(
+        Blockchain::new(
+            env,
+            BlockchainConfig::default(),
+            NetworkId::UnitAlbatross,
+            time,
+        )
+        .unwrap(),
+    ));
+    let producer = BlockProducer::new(signing_key(), voting_key());
+
+    let bc = blockchain.upgradable_read();
+    let timestamp = bc.timestamp() + Policy::BLOCK_SEPARATION_TIME;
+
+    let preview = BlockProducer::preview_micro_block(&bc, timestamp, vec![], vec![]).unwrap();
+    assert_eq!(preview.block_number, bc.block_number() + 1);
+    assert_eq!(preview.total_fees, Coin::ZERO);
+    assert_eq!(preview.num_failed_transactions(), 0);
+    assert!(preview.inherents.is_empty());
+
+    // Previewing doesn't change the blockchain.
+    assert_eq!(bc.block_number(), bc.get_genesis_block_number());
+
+    // The produced block matches the preview.
+    let block = producer
+        .next_micro_block(&bc, timestamp, vec![], vec![], vec![], None)
+        .unwrap();
+    assert_eq!(block.header.state_root, preview.state_root);
+    assert_eq!(block.header.diff_root, preview.diff_root);
+    assert_eq!(block.header.history_root, preview.history_root);
+    assert_eq!(block.body, Some(preview.body));
+
+    assert_eq!(
+        Blockchain::push(bc, Block::Micro(block)),
+        Ok(PushResult::Extended)
+    );
+}
+
 #[test]
 fn it_can_produce_macro_blocks() {
     let time = Arc::new(OffsetTime::new());
@@ -461,6 +503,43 @@ fn it_can_produce_a_chain_with_txns() {
     }
 }
 
+#[test]
+fn it_can_push_a_chain_with_txns_using_parallel_verification() {
+    let time = Arc::new(OffsetTime::new());
+    let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+    let config = BlockchainConfig {
+        parallel_verification: true,
+        ..Default::default()
+    };
+    let blockchain = Arc::new(RwLock::new(
+        Blockchain::new(env, config, NetworkId::UnitAlbatross, time).unwrap(),
+    ));
+    let producer = BlockProducer::new(signing_key(), voting_key());
+
+    fill_micro_blocks_with_txns(&producer, &blockchain, 5, 0);
+
+    let blockchain = blockchain.upgradable_read();
+    let macro_block_proposal = producer
+        .next_macro_block_proposal(
+            &blockchain,
+            blockchain.timestamp() + Policy::BLOCK_SEPARATION_TIME,
+            0u32,
+            vec![],
+        )
+        .unwrap();
+
+    let block = sign_macro_block(
+        &producer.voting_key,
+        macro_block_proposal.header,
+        macro_block_proposal.body,
+    );
+
+    assert_eq!(
+        Blockchain::push(blockchain, Block::Macro(block)),
+        Ok(PushResult::Extended)
+    );
+}
+
 #[test]
 fn it_can_revert_failed_transactions() {
     let time = Arc::new(OffsetTime::new());
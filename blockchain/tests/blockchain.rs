@@ -1,16 +1,23 @@
 use std::sync::Arc;
 
 use nimiq_block::{Block, BlockError};
-use nimiq_blockchain::Blockchain;
-use nimiq_blockchain_interface::{AbstractBlockchain, PushError, PushResult};
-use nimiq_hash::Hash;
-use nimiq_primitives::policy::Policy;
+use nimiq_blockchain::{
+    chain_store::{IndexForksMigration, PrunedForks},
+    BlockContext, Blockchain, BlockchainConfig, PostValidationHook,
+};
+use nimiq_blockchain_interface::{
+    AbstractBlockchain, BlockVeto, BlockchainError, PushError, PushResult,
+};
+use nimiq_database::{mdbx::MdbxDatabase, migration::Migrator, traits::WriteTransaction};
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_primitives::{networks::NetworkId, policy::Policy};
 use nimiq_tendermint::ProposalMessage;
 use nimiq_test_log::test;
 use nimiq_test_utils::{
     block_production::TemporaryBlockProducer,
     test_custom_block::{finalize_macro_block, next_macro_block_proposal},
 };
+use nimiq_utils::time::OffsetTime;
 
 #[test]
 fn prune_epoch_micro_blocks() {
@@ -118,6 +125,183 @@ fn prune_epoch_micro_blocks() {
         .is_err());
 }
 
+/// Pushes three micro blocks at the same height and returns the hash of the one that is on the
+/// main chain as well as the hashes of the two forks.
+fn push_forks(temp_producer: &TemporaryBlockProducer) -> (Blake2bHash, Vec<Blake2bHash>) {
+    let blockchain = &temp_producer.blockchain;
+    let producer = &temp_producer.producer;
+
+    let micro_blocks: Vec<_> = [0x42, 0x32, 0x82]
+        .into_iter()
+        .enumerate()
+        .map(|(i, extra_data)| {
+            let bc_read = blockchain.read();
+            producer
+                .next_micro_block(
+                    &bc_read,
+                    bc_read.timestamp() + 100 * (i as u64 + 1),
+                    vec![],
+                    vec![],
+                    vec![extra_data],
+                    None,
+                )
+                .unwrap()
+        })
+        .collect();
+
+    for (i, micro_block) in micro_blocks.iter().enumerate() {
+        let result = Blockchain::push(
+            blockchain.upgradable_read(),
+            Block::Micro(micro_block.clone()),
+        )
+        .unwrap();
+        assert_ne!(result, PushResult::Known, "block {i} must be new");
+    }
+
+    let main_hash = blockchain.read().head_hash();
+    let fork_hashes: Vec<Blake2bHash> = micro_blocks
+        .iter()
+        .map(|block| block.hash())
+        .filter(|hash| *hash != main_hash)
+        .collect();
+    assert_eq!(fork_hashes.len(), 2);
+
+    (main_hash, fork_hashes)
+}
+
+#[test]
+fn finalize_batch_prunes_forks_after_retention() {
+    let temp_producer = TemporaryBlockProducer::new();
+    let (main_hash, fork_hashes) = push_forks(&temp_producer);
+
+    let bc_read = temp_producer.blockchain.read();
+    let mut txn = bc_read.write_transaction();
+    let batch = Policy::batch_at(bc_read.block_number());
+
+    // The forks are kept for one more batch.
+    let pruned = bc_read.chain_store.finalize_batch(&mut txn, batch, Some(1));
+    assert_eq!(pruned, PrunedForks::default());
+    for hash in &fork_hashes {
+        assert!(bc_read
+            .chain_store
+            .get_chain_info(hash, false, Some(&txn))
+            .is_ok());
+    }
+
+    let pruned = bc_read
+        .chain_store
+        .finalize_batch(&mut txn, batch + 1, Some(1));
+    assert_eq!(pruned.num_blocks, 2);
+    assert!(pruned.num_bytes > 0);
+    for hash in &fork_hashes {
+        assert!(bc_read
+            .chain_store
+            .get_chain_info(hash, false, Some(&txn))
+            .is_err());
+    }
+    assert!(bc_read
+        .chain_store
+        .get_chain_info(&main_hash, false, Some(&txn))
+        .is_ok());
+}
+
+#[test]
+fn fork_index_migration_indexes_finalized_forks() {
+    let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+    let blockchain = Blockchain::new(
+        env.clone(),
+        BlockchainConfig::default(),
+        NetworkId::UnitAlbatross,
+        Arc::new(OffsetTime::new()),
+    )
+    .unwrap();
+    let temp_producer = TemporaryBlockProducer::with_blockchain(blockchain);
+
+    // Without a fork retention, the forks are not indexed when their batch is finalized.
+    let (_, finalized_forks) = push_forks(&temp_producer);
+    let batch = Policy::batch_at(temp_producer.blockchain.read().block_number());
+    while !Policy::is_macro_block_at(temp_producer.blockchain.read().block_number()) {
+        temp_producer.next_block(vec![], false);
+    }
+    let (main_hash, current_forks) = push_forks(&temp_producer);
+
+    Migrator::new().with(IndexForksMigration).run(&env).unwrap();
+
+    // Only the forks of the finalized batch were indexed, the forks of the current batch are
+    // indexed once it is finalized.
+    let bc_read = temp_producer.blockchain.read();
+    let mut txn = bc_read.write_transaction();
+    let pruned = bc_read
+        .chain_store
+        .finalize_batch(&mut txn, batch + 1, Some(1));
+    assert_eq!(pruned.num_blocks, 2);
+    for hash in &finalized_forks {
+        assert!(bc_read
+            .chain_store
+            .get_chain_info(hash, false, Some(&txn))
+            .is_err());
+    }
+    for hash in current_forks.iter().chain([&main_hash]) {
+        assert!(bc_read
+            .chain_store
+            .get_chain_info(hash, false, Some(&txn))
+            .is_ok());
+    }
+}
+
+struct VetoAll;
+
+impl PostValidationHook for VetoAll {
+    fn check_block(&self, context: &BlockContext<'_>) -> Result<(), BlockVeto> {
+        Err(BlockVeto(format!(
+            "block #{} is not allowed",
+            context.block.block_number()
+        )))
+    }
+
+    fn post_validation(&self, _push_result: Result<&PushResult, &PushError>) {}
+}
+
+#[test]
+fn post_validation_hook_can_veto_blocks() {
+    let temp_producer = TemporaryBlockProducer::new();
+    let block = temp_producer.next_block_no_push(vec![], false);
+    let head_hash = temp_producer.blockchain.read().head_hash();
+
+    let result = Blockchain::push_with_hook(
+        temp_producer.blockchain.upgradable_read(),
+        block.clone(),
+        &VetoAll,
+    );
+    assert!(matches!(result, Err(PushError::Vetoed(_))));
+    assert_eq!(temp_producer.blockchain.read().head_hash(), head_hash);
+
+    // Without the hook the block is accepted.
+    assert_eq!(temp_producer.push(block), Ok(PushResult::Extended));
+}
+
+#[test]
+fn vetoed_rebranch_is_not_an_invalid_fork() {
+    let temp_producer1 = TemporaryBlockProducer::new();
+    let temp_producer2 = TemporaryBlockProducer::new();
+
+    let ancestor = temp_producer1.next_block(vec![], false);
+    assert_eq!(temp_producer2.push(ancestor), Ok(PushResult::Extended));
+
+    let fork1 = temp_producer1.next_block(vec![0x48], false);
+    let fork2 = temp_producer2.next_block(vec![], false);
+    let better = temp_producer1.next_block(vec![], false);
+    assert_eq!(temp_producer2.push(fork1), Ok(PushResult::Forked));
+
+    let result = Blockchain::push_with_hook(
+        temp_producer2.blockchain.upgradable_read(),
+        better,
+        &VetoAll,
+    );
+    assert!(matches!(result, Err(PushError::Vetoed(_))));
+    assert_eq!(temp_producer2.blockchain.read().head_hash(), fork2.hash());
+}
+
 #[test]
 fn can_detect_invalid_punished_set() {
     let temp_producer = TemporaryBlockProducer::new();
@@ -169,3 +353,138 @@ fn can_detect_invalid_punished_set() {
         Err(PushError::InvalidBlock(BlockError::InvalidValidators))
     );
 }
+
+#[test]
+fn it_rewinds_a_corrupted_head_to_the_last_macro_block() {
+    let temp_producer = TemporaryBlockProducer::new();
+
+    // Produce a full batch and a few micro blocks on top of it.
+    for _ in 0..Policy::blocks_per_batch() + 3 {
+        temp_producer.next_block(vec![], false);
+    }
+
+    let (env, macro_head_hash) = {
+        let blockchain = temp_producer.blockchain.read();
+        assert_eq!(
+            blockchain.block_number(),
+            Policy::genesis_block_number() + Policy::blocks_per_batch() + 3
+        );
+
+        // Corrupt the head by pointing it to a block that does not exist.
+        let mut txn = blockchain.write_transaction();
+        blockchain
+            .chain_store
+            .set_head(&mut txn, &Blake2bHash::default());
+        txn.commit();
+
+        (
+            blockchain.state.accounts.env.clone(),
+            blockchain.macro_head_hash(),
+        )
+    };
+    drop(temp_producer);
+
+    // Loading the blockchain again rewinds it to the last macro block.
+    let blockchain = Blockchain::new(
+        env,
+        BlockchainConfig::default(),
+        NetworkId::UnitAlbatross,
+        Arc::new(OffsetTime::new()),
+    )
+    .unwrap();
+
+    assert_eq!(blockchain.head_hash(), macro_head_hash);
+    assert_eq!(
+        blockchain.block_number(),
+        Policy::genesis_block_number() + Policy::blocks_per_batch()
+    );
+    assert!(blockchain
+        .get_block_at(blockchain.block_number() + 1, false, None)
+        .is_err());
+}
+
+#[test]
+fn it_finds_and_repairs_orphaned_records() {
+    let temp_producer = TemporaryBlockProducer::new();
+    for _ in 0..Policy::blocks_per_batch() + 3 {
+        temp_producer.next_block(vec![], false);
+    }
+
+    let blockchain = temp_producer.blockchain.read();
+    assert!(blockchain.check_integrity(false).is_consistent());
+
+    // Store an accounts diff for a block that does not exist.
+    let orphan_hash = Blake2bHash::default();
+    let mut txn = blockchain.write_transaction();
+    blockchain
+        .chain_store
+        .put_accounts_diff(&mut txn, &orphan_hash, &Default::default());
+    txn.commit();
+
+    let report = blockchain.check_integrity(false);
+    assert!(!report.is_consistent());
+    assert!(!report.head_missing);
+    assert!(!report.accounts_inconsistent);
+    assert!(!report.history_inconsistent);
+    assert_eq!(report.orphaned.accounts_diffs, vec![orphan_hash]);
+    assert!(!report.repaired);
+
+    let report = blockchain.check_integrity(true);
+    assert!(report.repaired);
+    assert!(blockchain.check_integrity(false).is_consistent());
+}
+
+#[test]
+fn it_proves_the_randomness_of_finalized_blocks() {
+    let temp_producer = TemporaryBlockProducer::new();
+    for _ in 0..Policy::blocks_per_batch() + 3 {
+        temp_producer.next_block(vec![], false);
+    }
+
+    let blockchain = temp_producer.blockchain.read();
+    let genesis_block = blockchain.election_head().clone();
+    let block_number = Policy::genesis_block_number() + 5;
+
+    let proof = blockchain.get_randomness_proof(block_number).unwrap();
+    assert_eq!(proof.block_number(), block_number);
+    assert_eq!(
+        proof.block_hash(),
+        blockchain
+            .get_block_at(block_number, false, None)
+            .unwrap()
+            .hash()
+    );
+    assert_eq!(
+        proof.verify(&genesis_block),
+        Ok(blockchain
+            .get_block_at(block_number, false, None)
+            .unwrap()
+            .seed()
+            .randomness())
+    );
+
+    // A tampered chain segment is rejected.
+    let mut tampered = proof.clone();
+    tampered.micro_headers[0].seed = blockchain
+        .get_block_at(block_number + 1, false, None)
+        .unwrap()
+        .seed()
+        .clone();
+    tampered.micro_headers[0].cached_hash = None;
+    assert_eq!(
+        tampered.verify(&genesis_block),
+        Err(BlockError::InvalidParentHash)
+    );
+
+    // The macro block itself can be proven as well.
+    let macro_block_number = Policy::genesis_block_number() + Policy::blocks_per_batch();
+    let proof = blockchain.get_randomness_proof(macro_block_number).unwrap();
+    assert!(proof.micro_headers.is_empty());
+    assert!(proof.verify(&genesis_block).is_ok());
+
+    // Blocks of the current batch are not finalized yet.
+    assert!(matches!(
+        blockchain.get_randomness_proof(macro_block_number + 1),
+        Err(BlockchainError::BlockNotFinalized(_))
+    ));
+}
@@ -1,10 +1,12 @@
+use std::sync::Arc;
+
 use nimiq_block::{
     Block, DoubleProposalProof, DoubleVoteProof, EquivocationProof, ForkProof, MicroHeader,
 };
-use nimiq_blockchain::interface::HistoryInterface;
+use nimiq_blockchain::{interface::HistoryInterface, BlockProducer, Blockchain, BlockchainConfig};
 use nimiq_blockchain_interface::{AbstractBlockchain, PushResult};
 use nimiq_bls::AggregateSignature;
-use nimiq_database::traits::WriteTransaction;
+use nimiq_database::{mdbx::MdbxDatabase, traits::WriteTransaction};
 use nimiq_genesis::NetworkId;
 use nimiq_hash::{Blake2sHash, HashOutput};
 use nimiq_keys::{KeyPair, PrivateKey};
@@ -15,7 +17,9 @@ use nimiq_serde::Deserialize;
 use nimiq_test_log::test;
 use nimiq_test_utils::{
     block_production::TemporaryBlockProducer,
-    blockchain::{generate_transactions, produce_macro_blocks, validator_address},
+    blockchain::{
+        generate_transactions, produce_macro_blocks, signing_key, validator_address, voting_key,
+    },
     test_custom_block::{next_micro_block, BlockConfig},
 };
 use nimiq_transaction::{
@@ -25,6 +29,8 @@ use nimiq_transaction::{
     },
     ExecutedTransaction, Transaction,
 };
+use nimiq_utils::time::OffsetTime;
+use parking_lot::RwLock;
 
 fn key_pair_with_funds() -> KeyPair {
     let priv_key = PrivateKey::deserialize_from_vec(
@@ -437,3 +443,113 @@ fn it_pushes_macro_block_with_rewards() {
         i += 1;
     }
 }
+
+#[test]
+fn it_prunes_history_outside_of_the_retention_window() {
+    let time = Arc::new(OffsetTime::new());
+    let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+    let config = BlockchainConfig {
+        history_retention_epochs: Some(1),
+        ..Default::default()
+    };
+    let blockchain = Arc::new(RwLock::new(
+        Blockchain::new(env, config, NetworkId::UnitAlbatross, time).unwrap(),
+    ));
+    let producer = BlockProducer::new(signing_key(), voting_key());
+
+    // Nothing is pruned before the first election block.
+    produce_macro_blocks(
+        &producer,
+        &blockchain,
+        Policy::batches_per_epoch() as usize - 1,
+    );
+    assert!(blockchain.read().history_store.total_len_at_epoch(1, None) > 0);
+
+    // Only the history of the latest epoch is kept at every election block.
+    produce_macro_blocks(
+        &producer,
+        &blockchain,
+        2 * Policy::batches_per_epoch() as usize + 1,
+    );
+    let blockchain = blockchain.read();
+    assert!(Policy::is_election_block_at(blockchain.block_number()));
+
+    let epoch_number = Policy::epoch_at(blockchain.block_number());
+    assert!(
+        blockchain
+            .history_store
+            .total_len_at_epoch(epoch_number, None)
+            > 0
+    );
+    for pruned_epoch in 1..epoch_number {
+        assert_eq!(
+            blockchain
+                .history_store
+                .total_len_at_epoch(pruned_epoch, None),
+            0
+        );
+    }
+    // No block of a pruned epoch remains in the history store.
+    let (first_block, _) = blockchain.history_store.history_store_range(None);
+    assert!(Policy::epoch_at(first_block) >= epoch_number);
+}
+
+#[test]
+fn it_prunes_the_history_backlog_on_startup() {
+    let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+    let blockchain = Arc::new(RwLock::new(
+        Blockchain::new(
+            env.clone(),
+            BlockchainConfig::default(),
+            NetworkId::UnitAlbatross,
+            Arc::new(OffsetTime::new()),
+        )
+        .unwrap(),
+    ));
+    let producer = BlockProducer::new(signing_key(), voting_key());
+
+    // Keep the full history of three epochs.
+    produce_macro_blocks(
+        &producer,
+        &blockchain,
+        3 * Policy::batches_per_epoch() as usize,
+    );
+    let epoch_number = Policy::epoch_at(blockchain.read().block_number());
+    for epoch in 1..=epoch_number {
+        assert!(
+            blockchain
+                .read()
+                .history_store
+                .total_len_at_epoch(epoch, None)
+                > 0
+        );
+    }
+    drop(blockchain);
+
+    // Restarting with a retention window prunes all epochs outside of it.
+    let config = BlockchainConfig {
+        history_retention_epochs: Some(1),
+        ..Default::default()
+    };
+    let blockchain = Blockchain::new(
+        env,
+        config,
+        NetworkId::UnitAlbatross,
+        Arc::new(OffsetTime::new()),
+    )
+    .unwrap();
+    assert!(
+        blockchain
+            .history_store
+            .total_len_at_epoch(epoch_number, None)
+            > 0
+    );
+    for pruned_epoch in 1..epoch_number {
+        assert_eq!(
+            blockchain
+                .history_store
+                .total_len_at_epoch(pruned_epoch, None),
+            0
+        );
+    }
+}
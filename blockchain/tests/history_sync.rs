@@ -1,7 +1,10 @@
 use std::sync::Arc;
 
-use nimiq_blockchain::{interface::HistoryInterface, BlockProducer, Blockchain, BlockchainConfig};
-use nimiq_blockchain_interface::{AbstractBlockchain, PushResult};
+use nimiq_blockchain::{
+    interface::HistoryInterface, BlockContext, BlockProducer, Blockchain, BlockchainConfig,
+    PostValidationHook,
+};
+use nimiq_blockchain_interface::{AbstractBlockchain, BlockVeto, PushError, PushResult};
 use nimiq_database::mdbx::MdbxDatabase;
 use nimiq_genesis::NetworkId;
 use nimiq_primitives::policy::Policy;
@@ -97,6 +100,68 @@ fn history_sync_does_not_need_to_revert_when_pushing_empty_batch() {
     );
 }
 
+struct VetoAll;
+
+impl PostValidationHook for VetoAll {
+    fn check_block(&self, context: &BlockContext<'_>) -> Result<(), BlockVeto> {
+        Err(BlockVeto(format!(
+            "block #{} is not allowed",
+            context.block.block_number()
+        )))
+    }
+
+    fn post_validation(&self, _push_result: Result<&PushResult, &PushError>) {}
+}
+
+#[test]
+fn history_sync_applies_the_post_validation_hook() {
+    let blockchain1 = Arc::new(RwLock::new(
+        Blockchain::new(
+            MdbxDatabase::new_volatile(Default::default()).unwrap(),
+            BlockchainConfig::default(),
+            NetworkId::UnitAlbatross,
+            Arc::new(OffsetTime::new()),
+        )
+        .unwrap(),
+    ));
+
+    let producer = BlockProducer::new(signing_key(), voting_key());
+    fill_micro_blocks_with_txns(&producer, &blockchain1, 1, 1);
+    produce_macro_blocks(&producer, &blockchain1, 1);
+
+    let macro_block = blockchain1.read().state.macro_info.head.clone();
+    let history = blockchain1
+        .read()
+        .history_store
+        .get_epoch_transactions(Policy::epoch_at(macro_block.block_number()), None);
+
+    let blockchain2 = Arc::new(RwLock::new(
+        Blockchain::new(
+            MdbxDatabase::new_volatile(Default::default()).unwrap(),
+            BlockchainConfig::default(),
+            NetworkId::UnitAlbatross,
+            Arc::new(OffsetTime::new()),
+        )
+        .unwrap(),
+    ));
+    let head_hash = blockchain2.read().head_hash();
+
+    let result = Blockchain::push_history_sync_with_hook(
+        blockchain2.upgradable_read(),
+        macro_block.clone(),
+        &history,
+        &VetoAll,
+    );
+    assert!(matches!(result, Err(PushError::Vetoed(_))));
+    assert_eq!(blockchain2.read().head_hash(), head_hash);
+
+    // Without the hook the block is accepted.
+    assert_eq!(
+        Blockchain::push_history_sync(blockchain2.upgradable_read(), macro_block, &history),
+        Ok(PushResult::Extended)
+    );
+}
+
 // Tests if the basic history sync works. It will try to push a succession of election and checkpoint
 // blocks. It does test if election blocks can be pushed after checkpoint blocks and vice-versa. It
 // does NOT test if macro blocks can be pushed with micro blocks already in the blockchain.
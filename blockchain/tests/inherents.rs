@@ -5,7 +5,7 @@ use nimiq_block::{
     Block, DoubleProposalProof, DoubleVoteProof, ForkProof, MacroBlock, MacroBody, MacroHeader,
     SkipBlockInfo,
 };
-use nimiq_blockchain::{Blockchain, BlockchainConfig};
+use nimiq_blockchain::{reward::RewardPreviewError, Blockchain, BlockchainConfig};
 use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_bls::AggregateSignature;
 use nimiq_database::{mdbx::MdbxDatabase, traits::WriteTransaction};
@@ -699,3 +699,54 @@ async fn create_fork_proof() {
     // Verify that the fork proof was generated
     assert!(fork_rx.next().await.is_some());
 }
+
+#[test]
+fn it_can_preview_rewards() {
+    let temp_producer = TemporaryBlockProducer::new();
+
+    // Batch 0 is finalized by definition and doesn't pay out rewards.
+    assert_eq!(
+        temp_producer
+            .blockchain
+            .read()
+            .preview_reward(0, &validator_address(), 0),
+        Err(RewardPreviewError::NoRewards)
+    );
+
+    // Produce the first batch and the micro blocks of the second one, such that the next macro
+    // block pays out rewards.
+    for _ in 0..2 * Policy::blocks_per_batch() - 1 {
+        temp_producer.next_block(vec![], false);
+    }
+    let macro_block = temp_producer.next_block_no_push(vec![], false);
+    assert!(macro_block.is_macro());
+
+    let blockchain = temp_producer.blockchain.read();
+    let epoch_number = Policy::epoch_at(macro_block.block_number() - Policy::blocks_per_batch());
+
+    assert_eq!(
+        blockchain.preview_reward(epoch_number + 1, &validator_address(), 0),
+        Err(RewardPreviewError::EpochNotPending(epoch_number + 1))
+    );
+    assert_eq!(
+        blockchain.preview_reward(epoch_number, &Address::burn_address(), 0),
+        Err(RewardPreviewError::ValidatorNotElected(
+            Address::burn_address()
+        ))
+    );
+
+    let preview = blockchain
+        .preview_reward(epoch_number, &validator_address(), macro_block.timestamp())
+        .unwrap();
+    assert_eq!(preview.payout_block_number, macro_block.block_number());
+    assert_eq!(preview.num_slots, Policy::SLOTS);
+    assert_eq!(preview.num_punished_slots, 0);
+    assert_eq!(preview.burned, Coin::ZERO);
+
+    // The preview matches the reward paid out by the macro block, apart from the remainder.
+    let rewards = &macro_block.unwrap_macro().body.unwrap().transactions;
+    let remainder = (preview.block_reward + preview.tx_fees) % Policy::SLOTS as u64;
+    assert_eq!(rewards.len(), 1);
+    assert_eq!(rewards[0].recipient, preview.reward_address);
+    assert_eq!(rewards[0].value, preview.reward + remainder);
+}
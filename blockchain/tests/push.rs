@@ -1,5 +1,6 @@
 use std::path::Path;
 
+use futures::{FutureExt, StreamExt};
 use nimiq_block::{
     Block, BlockError, DoubleProposalProof, DoubleVoteProof, EquivocationProofError, ForkProof,
 };
@@ -7,7 +8,7 @@ use nimiq_blockchain::Blockchain;
 use nimiq_blockchain_interface::{
     AbstractBlockchain, PushError,
     PushError::{InvalidBlock, InvalidEquivocationProof},
-    PushResult,
+    PushResult, ReorgEvent,
 };
 use nimiq_bls::AggregateSignature;
 use nimiq_hash::{Blake2bHash, Blake2sHash, HashOutput};
@@ -268,6 +269,41 @@ fn it_works_with_valid_blocks() {
     simply_push_macro_block(&config, &Ok(PushResult::Extended));
 }
 
+#[test]
+fn it_emits_reorg_events() {
+    let temp_producer1 = TemporaryBlockProducer::new();
+    let temp_producer2 = TemporaryBlockProducer::new();
+    let mut reorg_rx = temp_producer2.blockchain.read().subscribe_reorgs();
+
+    // [0] - [0] - [0]
+    //          \- [0]
+    let ancestor = temp_producer1.next_block(vec![], false);
+    assert_eq!(
+        temp_producer2.push(ancestor.clone()),
+        Ok(PushResult::Extended)
+    );
+
+    let fork1 = temp_producer1.next_block(vec![0x48], false);
+    let fork2 = temp_producer2.next_block(vec![], false);
+    let better = temp_producer1.next_block(vec![], false);
+
+    assert_eq!(temp_producer2.push(fork1.clone()), Ok(PushResult::Forked));
+    assert!(reorg_rx.next().now_or_never().is_none());
+
+    assert_eq!(
+        temp_producer2.push(better.clone()),
+        Ok(PushResult::Rebranched)
+    );
+    assert_eq!(
+        reorg_rx.next().now_or_never(),
+        Some(Some(ReorgEvent {
+            reverted: vec![fork2.hash()],
+            applied: vec![fork1.hash(), better.hash()],
+            common_ancestor: ancestor.hash(),
+        }))
+    );
+}
+
 #[test]
 fn it_validates_network() {
     expect_push_micro_block(
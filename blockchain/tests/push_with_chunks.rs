@@ -1,4 +1,5 @@
 use nimiq_account::RevertInfo;
+use nimiq_blockchain::chain_store::StateSyncProgress;
 use nimiq_blockchain_interface::{
     AbstractBlockchain, ChunksPushError, ChunksPushResult, PushResult,
 };
@@ -184,6 +185,46 @@ fn can_push_valid_chunks() {
     );
 }
 
+#[test]
+fn can_persist_state_sync_progress() {
+    let temp_producer1 = TemporaryBlockProducer::new();
+    let temp_producer2 = TemporaryBlockProducer::new_incomplete();
+
+    let block = temp_producer1.next_block(vec![], false);
+    let chunk1 = temp_producer1.get_chunk(KeyNibbles::ROOT, 1);
+    let chunk2_start = chunk1.chunk.end_key.clone().unwrap();
+
+    assert_eq!(
+        temp_producer2.blockchain.read().get_state_sync_progress(),
+        None
+    );
+    assert_eq!(
+        temp_producer2.push_with_chunks(block, TrieDiff::default(), vec![chunk1]),
+        Ok((PushResult::Extended, Ok(ChunksPushResult::Chunks(1, 0))))
+    );
+
+    // The progress resumes at the start of the missing range after the committed chunk.
+    assert_eq!(
+        temp_producer2.blockchain.read().get_state_sync_progress(),
+        Some(StateSyncProgress {
+            chunks_applied: 1,
+            next_key: chunk2_start.clone(),
+        })
+    );
+
+    // Once the trie is complete, the progress is removed.
+    let block = temp_producer1.next_block(vec![], false);
+    let chunk2 = temp_producer1.get_chunk(chunk2_start, 3);
+    assert_eq!(
+        temp_producer2.push_with_chunks(block, TrieDiff::default(), vec![chunk2]),
+        Ok((PushResult::Extended, Ok(ChunksPushResult::Chunks(1, 0))))
+    );
+    assert_eq!(
+        temp_producer2.blockchain.read().get_state_sync_progress(),
+        None
+    );
+}
+
 #[test]
 fn can_ignore_chunks_with_invalid_start_key() {
     let temp_producer1 = TemporaryBlockProducer::new();
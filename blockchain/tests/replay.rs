@@ -0,0 +1,130 @@
+use std::io::{Seek, SeekFrom};
+
+use nimiq_blockchain::{Blockchain, RecordedInput, ReplayError, ReplayResult};
+use nimiq_blockchain_interface::{AbstractBlockchain, PushError, PushResult};
+use nimiq_primitives::policy::Policy;
+use nimiq_test_log::test;
+use nimiq_test_utils::block_production::TemporaryBlockProducer;
+use nimiq_zkp::NanoProof;
+
+#[test]
+fn it_replays_recorded_inputs() {
+    let temp_producer1 = TemporaryBlockProducer::new();
+    let temp_producer2 = TemporaryBlockProducer::new();
+    let temp_producer3 = TemporaryBlockProducer::new();
+
+    let mut recording = tempfile::tempfile().unwrap();
+    let header = temp_producer1
+        .blockchain
+        .read()
+        .start_recording(recording.try_clone().unwrap())
+        .unwrap();
+    assert_eq!(
+        header.head_hash,
+        temp_producer2.blockchain.read().head_hash()
+    );
+
+    // Record a full batch as well as a known block.
+    let mut last_block = None;
+    for _ in 0..Policy::blocks_per_batch() + 2 {
+        last_block = Some(temp_producer1.next_block(vec![], false));
+    }
+    assert_eq!(
+        temp_producer1.push(last_block.unwrap()),
+        Ok(PushResult::Known)
+    );
+    temp_producer1.blockchain.read().stop_recording();
+
+    // Inputs after the recording stopped aren't recorded.
+    temp_producer3.next_block(vec![], false);
+    temp_producer1.next_block(vec![], false);
+
+    recording.seek(SeekFrom::Start(0)).unwrap();
+    let mut results = vec![];
+    nimiq_blockchain::Blockchain::replay(
+        &temp_producer2.blockchain,
+        &recording,
+        |input, result| {
+            assert!(matches!(input, RecordedInput::Block { trusted: false, .. }));
+            match result {
+                ReplayResult::Push(Ok(result)) => results.push(result.clone()),
+                _ => panic!("Unexpected replay result {result:?}"),
+            }
+        },
+    )
+    .unwrap();
+
+    let num_blocks = Policy::blocks_per_batch() as usize + 2;
+    assert_eq!(results.len(), num_blocks + 1);
+    assert!(results[..num_blocks]
+        .iter()
+        .all(|result| *result == PushResult::Extended));
+    assert_eq!(results[num_blocks], PushResult::Known);
+
+    // Both blockchains end up in the same state.
+    let blockchain1 = temp_producer1.blockchain.read();
+    let blockchain2 = temp_producer2.blockchain.read();
+    let block2 = blockchain2.head();
+    let block1 = blockchain1
+        .get_block_at(block2.block_number(), false, None)
+        .unwrap();
+    assert_eq!(block1.hash(), block2.hash());
+    assert_eq!(
+        blockchain2.state.accounts.get_root_hash(None).as_ref(),
+        Some(block2.state_root())
+    );
+
+    // The recording can't be replayed on top of a different head.
+    recording.seek(SeekFrom::Start(0)).unwrap();
+    assert!(matches!(
+        nimiq_blockchain::Blockchain::replay(&temp_producer3.blockchain, &recording, |_, _| {}),
+        Err(ReplayError::HeadMismatch(..))
+    ));
+}
+
+#[test]
+fn it_verifies_recorded_zk_proofs() {
+    let temp_producer1 = TemporaryBlockProducer::new();
+    let temp_producer2 = TemporaryBlockProducer::new();
+    let temp_producer3 = TemporaryBlockProducer::new();
+
+    let mut election_block = None;
+    for _ in 0..Policy::blocks_per_epoch() {
+        election_block = Some(temp_producer1.next_block(vec![], false));
+    }
+    let election_block = election_block.unwrap();
+    assert!(election_block.is_election());
+
+    // Record an election block pushed with a proof that was trusted, but is invalid.
+    let mut recording = tempfile::tempfile().unwrap();
+    temp_producer2
+        .blockchain
+        .read()
+        .start_recording(recording.try_clone().unwrap())
+        .unwrap();
+    assert_eq!(
+        Blockchain::push_zkp(
+            temp_producer2.blockchain.upgradable_read(),
+            election_block,
+            NanoProof::default(),
+            true,
+        ),
+        Ok(PushResult::Extended)
+    );
+    temp_producer2.blockchain.read().stop_recording();
+
+    // The proof is verified on replay.
+    recording.seek(SeekFrom::Start(0)).unwrap();
+    let mut results = vec![];
+    Blockchain::replay(&temp_producer3.blockchain, &recording, |input, result| {
+        assert!(matches!(input, RecordedInput::Zkp { .. }));
+        match result {
+            ReplayResult::Push(result) => {
+                results.push(matches!(result, Err(PushError::InvalidZKP)))
+            }
+            _ => panic!("Unexpected replay result {result:?}"),
+        }
+    })
+    .unwrap();
+    assert_eq!(results, vec![true]);
+}
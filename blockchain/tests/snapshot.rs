@@ -0,0 +1,76 @@
+use nimiq_blockchain::StateSnapshotError;
+use nimiq_blockchain_interface::{AbstractBlockchain, PushResult};
+use nimiq_primitives::policy::Policy;
+use nimiq_test_log::test;
+use nimiq_test_utils::block_production::TemporaryBlockProducer;
+
+#[test]
+fn it_exports_and_imports_state_snapshots() {
+    let temp_producer1 = TemporaryBlockProducer::new();
+    let temp_producer2 = TemporaryBlockProducer::new_incomplete();
+
+    // Produce a whole batch, which ends with a macro block.
+    for _ in 0..Policy::blocks_per_batch() {
+        let block = temp_producer1.next_block(vec![], false);
+        assert_eq!(temp_producer2.push(block), Ok(PushResult::Extended));
+    }
+    let macro_hash = temp_producer1.blockchain.read().macro_head_hash();
+
+    let mut snapshot = vec![];
+    let header = temp_producer1
+        .blockchain
+        .read()
+        .export_state_snapshot(&macro_hash, &mut snapshot)
+        .unwrap();
+    assert_eq!(header.block_hash, macro_hash);
+
+    // The macro block can still be exported after micro blocks were produced on top of it.
+    temp_producer1.next_block(vec![], false);
+    temp_producer1.next_block(vec![], false);
+    {
+        let blockchain1 = temp_producer1.blockchain.read();
+        let mut snapshot_after = vec![];
+        blockchain1
+            .export_state_snapshot(&macro_hash, &mut snapshot_after)
+            .unwrap();
+        assert_eq!(snapshot, snapshot_after);
+
+        // The state of the chain is left untouched.
+        assert_eq!(
+            blockchain1.state.accounts.get_root_hash(None).as_ref(),
+            Some(blockchain1.head().state_root())
+        );
+
+        // Micro blocks can't be exported.
+        assert!(matches!(
+            blockchain1.export_state_snapshot(&blockchain1.head_hash(), &mut vec![]),
+            Err(StateSnapshotError::UnavailableBlock(_))
+        ));
+
+        // The snapshot doesn't match the head anymore.
+        assert!(matches!(
+            blockchain1.import_state_snapshot(&snapshot[..]),
+            Err(StateSnapshotError::HeadMismatch(..))
+        ));
+    }
+
+    // Import the snapshot into the incomplete chain.
+    let blockchain2 = temp_producer2.blockchain.read();
+    assert!(!blockchain2.state.accounts.is_complete(None));
+
+    assert!(matches!(
+        blockchain2.import_state_snapshot(&snapshot[..snapshot.len() - 1]),
+        Err(StateSnapshotError::Io(_))
+    ));
+    assert!(!blockchain2.state.accounts.is_complete(None));
+
+    assert_eq!(
+        blockchain2.import_state_snapshot(&snapshot[..]).unwrap(),
+        header
+    );
+    assert!(blockchain2.state.accounts.is_complete(None));
+    assert_eq!(
+        blockchain2.state.accounts.get_root_hash(None),
+        Some(header.state_root)
+    );
+}
@@ -7,6 +7,7 @@ use std::{
 use ark_ec::AffineRepr;
 use ark_mnt6_753::G2Affine;
 use ark_serialize::CanonicalDeserialize;
+use nimiq_hash::{Blake2bHash, Blake2bHasher, Hasher};
 
 use crate::PublicKey;
 
@@ -51,6 +52,12 @@ impl CompressedPublicKey {
     pub fn to_hex(&self) -> String {
         hex::encode(self.as_ref())
     }
+
+    /// Returns the fingerprint of the public key, which is the hash of its compressed form.
+    /// Voting keys are too long to be displayed in full, the fingerprint can be shown instead.
+    pub fn fingerprint(&self) -> Blake2bHash {
+        Blake2bHasher::default().digest(self.as_ref())
+    }
 }
 
 impl Eq for CompressedPublicKey {}
@@ -11,7 +11,7 @@ pub use nimiq::{
         logging::{initialize_logging, log_error_cause_chain},
         metrics_server::NimiqTaskMonitor,
         panic::initialize_panic_reporting,
-        signal_handling::initialize_signal_handler,
+        signal_handling::{initialize_signal_handler, on_shutdown},
     },
 };
 use nimiq_time::interval;
@@ -97,6 +97,11 @@ async fn main_inner() -> Result<(), Error> {
     let consensus = client.consensus_proxy();
     let mempool = client.mempool();
 
+    // Persist the pending transactions on shutdown, if the mempool is configured to do so.
+    if let Some(mempool) = mempool.clone() {
+        on_shutdown(move || mempool.persist());
+    }
+
     let zkp_component = client.take_zkp_component().unwrap();
     spawn(zkp_component); //ITODO get metrics on this? ask JD
 
@@ -148,6 +153,7 @@ async fn main_inner() -> Result<(), Error> {
             mempool,
             client.consensus_proxy(),
             client.network(),
+            client.bls_cache(),
             &nimiq_task_metric,
         )
     }
@@ -0,0 +1,101 @@
+use std::{sync::Arc, time::Duration};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use futures::{stream::BoxStream, StreamExt};
+use nimiq_consensus::consensus::transaction_gossip::{TransactionBatcher, TransactionGossipConfig};
+use nimiq_keys::Address;
+use nimiq_network_interface::network::Network;
+use nimiq_network_mock::{MockHub, MockNetwork};
+use nimiq_primitives::{coin::Coin, networks::NetworkId};
+use nimiq_transaction::{
+    gossip::{TransactionBatchTopic, TransactionGossipCache},
+    Transaction, TransactionTopic,
+};
+use tokio::{runtime::Runtime, sync::mpsc};
+
+criterion_group!(benches, propagation_latency);
+criterion_main!(benches);
+
+fn transactions(count: usize) -> Vec<Transaction> {
+    (1..=count as u64)
+        .map(|value| {
+            Transaction::new_basic(
+                Address::default(),
+                Address::default(),
+                Coin::from_u64_unchecked(value),
+                Coin::ZERO,
+                1,
+                NetworkId::UnitAlbatross,
+            )
+        })
+        .collect()
+}
+
+/// Returns the sending network and the stream of transactions received by its peer.
+async fn networks() -> (Arc<MockNetwork>, BoxStream<'static, Transaction>) {
+    let mut hub = MockHub::new();
+    let sender = Arc::new(hub.new_network());
+    let receiver = hub.new_network();
+    sender.dial_mock(&receiver);
+
+    let individual = receiver
+        .subscribe::<TransactionTopic>()
+        .await
+        .unwrap()
+        .map(|(tx, _)| tx);
+    let batched = receiver
+        .subscribe::<TransactionBatchTopic>()
+        .await
+        .unwrap()
+        .flat_map(|(batch, _)| futures::stream::iter(batch.transactions));
+
+    (sender, futures::stream::select(individual, batched).boxed())
+}
+
+/// Measures the time until all transactions sent at once were received by a peer.
+fn propagation_latency(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("transaction_propagation");
+
+    for count in [10, 100, 1000] {
+        group.bench_with_input(
+            BenchmarkId::new("individual", count),
+            &count,
+            |b, &count| {
+                b.to_async(&runtime).iter(|| async move {
+                    let (sender, mut received) = networks().await;
+                    for tx in transactions(count) {
+                        sender.publish::<TransactionTopic>(tx).await.unwrap();
+                    }
+                    for _ in 0..count {
+                        received.next().await.unwrap();
+                    }
+                })
+            },
+        );
+
+        group.bench_with_input(BenchmarkId::new("batched", count), &count, |b, &count| {
+            b.to_async(&runtime).iter(|| async move {
+                let (sender, mut received) = networks().await;
+                let config = TransactionGossipConfig {
+                    batching: true,
+                    batch_delay: Duration::from_millis(5),
+                    ..Default::default()
+                };
+                let (tx_sender, rx) = mpsc::channel(TransactionBatcher::<MockNetwork>::QUEUE_SIZE);
+                let batcher =
+                    TransactionBatcher::new(sender, config, TransactionGossipCache::default());
+                tokio::spawn(batcher.run(rx));
+
+                for tx in transactions(count) {
+                    tx_sender.send(tx).await.unwrap();
+                }
+                for _ in 0..count {
+                    received.next().await.unwrap();
+                }
+            })
+        });
+    }
+
+    group.finish();
+}
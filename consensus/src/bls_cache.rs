@@ -2,9 +2,28 @@ use hashlink::LruCache;
 use nimiq_bls::LazyPublicKey as BlsLazyPublicKey;
 use nimiq_primitives::policy::Policy;
 
-/// LRU cache for BLS remembering uncompression of BLS compressed public key.
+/// Hit, miss and eviction counters of a [`BlsCache`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct BlsCacheStats {
+    /// Number of cached keys that were already present in the cache.
+    pub hits: u64,
+    /// Number of cached keys that were not present in the cache.
+    pub misses: u64,
+    /// Number of keys that were evicted from the cache.
+    pub evictions: u64,
+}
+
+/// Cache for BLS remembering uncompression of BLS compressed public key.
+///
+/// Keys are evicted according to a segmented LRU policy: newly cached keys enter the
+/// probationary segment and are promoted to the protected segment once they are cached again.
+/// Keys that are only seen once, e.g. during sync of old epochs, are therefore evicted before
+/// the keys of validators that keep showing up.
 pub struct BlsCache {
-    inner: LruCache<BlsLazyPublicKey, ()>,
+    probationary: LruCache<BlsLazyPublicKey, ()>,
+    protected: LruCache<BlsLazyPublicKey, ()>,
+    capacity: usize,
+    stats: BlsCacheStats,
 }
 
 impl Default for BlsCache {
@@ -14,11 +33,18 @@ impl Default for BlsCache {
 }
 
 impl BlsCache {
-    fn with_capacity(capacity: usize) -> BlsCache {
+    /// The share of the capacity reserved for the protected segment, in percent.
+    const PROTECTED_SHARE: usize = 80;
+
+    pub fn with_capacity(capacity: usize) -> BlsCache {
         BlsCache {
-            inner: LruCache::new(capacity),
+            probationary: LruCache::new_unbounded(),
+            protected: LruCache::new_unbounded(),
+            capacity,
+            stats: BlsCacheStats::default(),
         }
     }
+
     pub fn new_test() -> BlsCache {
         BlsCache::with_capacity(100)
     }
@@ -26,6 +52,66 @@ impl BlsCache {
 
 impl BlsCache {
     pub fn cache(&mut self, data: &BlsLazyPublicKey) {
-        self.inner.insert(data.clone(), ());
+        if self.protected.get(data).is_some() {
+            self.stats.hits += 1;
+        } else if self.probationary.remove(data).is_some() {
+            self.stats.hits += 1;
+            self.protected.insert(data.clone(), ());
+            self.demote();
+        } else {
+            self.stats.misses += 1;
+            self.probationary.insert(data.clone(), ());
+        }
+        self.evict();
+    }
+
+    /// Returns the maximum number of keys held by the cache.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Changes the maximum number of keys held by the cache, evicting keys if necessary.
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.demote();
+        self.evict();
+    }
+
+    /// Returns the number of keys currently held by the cache.
+    pub fn len(&self) -> usize {
+        self.probationary.len() + self.protected.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn stats(&self) -> BlsCacheStats {
+        self.stats
+    }
+
+    fn protected_capacity(&self) -> usize {
+        self.capacity * Self::PROTECTED_SHARE / 100
+    }
+
+    /// Moves the least recently used keys of an overfull protected segment back to the
+    /// probationary segment.
+    fn demote(&mut self) {
+        while self.protected.len() > self.protected_capacity() {
+            let Some((key, ())) = self.protected.remove_lru() else {
+                break;
+            };
+            self.probationary.insert(key, ());
+        }
+    }
+
+    /// Evicts keys until the capacity is respected, starting with the probationary segment.
+    fn evict(&mut self) {
+        while self.len() > self.capacity {
+            if self.probationary.remove_lru().is_none() && self.protected.remove_lru().is_none() {
+                break;
+            }
+            self.stats.evictions += 1;
+        }
     }
 }
@@ -0,0 +1,167 @@
+use std::sync::Arc;
+
+use nimiq_block::Block;
+use nimiq_blockchain_interface::{AbstractBlockchain, Direction};
+use nimiq_blockchain_proxy::BlockchainProxy;
+use nimiq_hash::Blake2bHash;
+use nimiq_network_interface::{network::Network, peer_info::Services};
+use nimiq_primitives::{networks::NetworkId, policy::Policy};
+use thiserror::Error;
+
+use super::ResolveBlockRangeRequest;
+use crate::messages::{RequestMissingBlocks, ResponseBlocks};
+
+#[derive(Clone, Debug, Error, PartialEq, Eq)]
+pub enum ResolveBlockRangeError {
+    #[error("Invalid block range {0}..={1}")]
+    InvalidRange(u32, u32),
+    #[error("Block {0} is not known to our blockchain")]
+    UnknownBlock(u32),
+    #[error("No peer provided valid blocks up to block {0}")]
+    Unavailable(u32),
+    #[error("The consensus dropped the request")]
+    Dropped,
+    #[error("Too many block ranges are being resolved concurrently")]
+    TooManyRequests,
+}
+
+/// Fetches the blocks of the requested range from our peers and sends them in ascending order.
+///
+/// The range is fetched in segments that don't cross batch boundaries. The last block of every
+/// segment must be known to our blockchain, all blocks of the segment are then verified by
+/// following the parent hashes back from it. Sending stops at the first error, or once the
+/// receiver was dropped.
+pub(crate) async fn resolve_block_range<N: Network>(
+    network: Arc<N>,
+    blockchain: BlockchainProxy,
+    request: ResolveBlockRangeRequest,
+) {
+    let ResolveBlockRangeRequest {
+        from,
+        to,
+        include_body,
+        block_sender,
+    } = request;
+
+    if from > to {
+        block_sender
+            .send(Err(ResolveBlockRangeError::InvalidRange(from, to)))
+            .await
+            .ok();
+        return;
+    }
+
+    let mut start = from;
+    loop {
+        let end = to.min(Policy::macro_block_after(start) - 1);
+        match resolve_segment(&network, &blockchain, start, end, include_body).await {
+            Ok(blocks) => {
+                for block in blocks {
+                    if block_sender.send(Ok(block)).await.is_err() {
+                        return;
+                    }
+                }
+            }
+            Err(error) => {
+                block_sender.send(Err(error)).await.ok();
+                return;
+            }
+        }
+
+        if end == to {
+            return;
+        }
+        start = end + 1;
+    }
+}
+
+/// Fetches the blocks `start..=end`, which must not contain any macro block other than `start`.
+async fn resolve_segment<N: Network>(
+    network: &Arc<N>,
+    blockchain: &BlockchainProxy,
+    start: u32,
+    end: u32,
+    include_body: bool,
+) -> Result<Vec<Block>, ResolveBlockRangeError> {
+    let (target_hash, locators, services, network_id) = {
+        let blockchain = blockchain.read();
+        let target_hash = blockchain
+            .get_block_at(end, false)
+            .map_err(|_| ResolveBlockRangeError::UnknownBlock(end))?
+            .hash();
+        // The predecessor of the segment lets the responder stop early, otherwise it returns all
+        // blocks back to the preceding macro block.
+        let locators: Vec<Blake2bHash> = start
+            .checked_sub(1)
+            .and_then(|block_number| blockchain.get_block_at(block_number, false).ok())
+            .map(|block| block.hash())
+            .into_iter()
+            .collect();
+        // Only history nodes are guaranteed to store blocks of past epochs.
+        let services = if Policy::epoch_at(start) < Policy::epoch_at(blockchain.block_number()) {
+            Services::HISTORY
+        } else {
+            Services::FULL_BLOCKS
+        };
+        (target_hash, locators, services, blockchain.network_id())
+    };
+
+    let peers = network
+        .get_peers_by_services(services, 1)
+        .await
+        .map_err(|_| ResolveBlockRangeError::Unavailable(end))?;
+
+    let request = RequestMissingBlocks {
+        target_hash: target_hash.clone(),
+        include_body,
+        locators,
+        direction: Direction::Backward,
+    };
+
+    for peer_id in peers {
+        match network.request(request.clone(), peer_id).await {
+            Ok(Ok(ResponseBlocks { blocks })) => {
+                match verify_segment(blocks, start, &target_hash, include_body, network_id) {
+                    Some(blocks) => return Ok(blocks),
+                    None => debug!(%peer_id, start, end, "Received invalid block range"),
+                }
+            }
+            Ok(Err(error)) => debug!(%peer_id, %error, start, end, "Failed to resolve block range"),
+            Err(error) => debug!(%peer_id, ?error, start, end, "Failed to request block range"),
+        }
+    }
+
+    Err(ResolveBlockRangeError::Unavailable(end))
+}
+
+/// Checks that the given blocks form a chain from block number `start` up to the block with
+/// `target_hash`, dropping any preceding blocks.
+fn verify_segment(
+    mut blocks: Vec<Block>,
+    start: u32,
+    target_hash: &Blake2bHash,
+    include_body: bool,
+    network_id: NetworkId,
+) -> Option<Vec<Block>> {
+    blocks.retain(|block| block.block_number() >= start);
+
+    if blocks.first()?.block_number() != start || blocks.last()?.hash() != *target_hash {
+        return None;
+    }
+
+    if blocks
+        .iter()
+        .any(|block| block.verify(network_id).is_err() || (include_body && !block.has_body()))
+    {
+        return None;
+    }
+
+    if blocks
+        .windows(2)
+        .any(|pair| pair[1].verify_immediate_successor(&pair[0]).is_err())
+    {
+        return None;
+    }
+
+    Some(blocks)
+}
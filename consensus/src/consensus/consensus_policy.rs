@@ -0,0 +1,145 @@
+use std::{fmt, sync::Arc};
+
+use nimiq_network_interface::peer_info::{PeerInfo, Services};
+
+/// A requirement that a minimum number of peers match a given predicate.
+#[derive(Clone)]
+struct PeerRequirement {
+    description: String,
+    min_peers: usize,
+    predicate: Arc<dyn Fn(&PeerInfo) -> bool + Send + Sync>,
+}
+
+/// The criteria our peers need to satisfy for consensus to be established.
+///
+/// Consensus always requires a minimum number of synced peers. On top of that, constraints on the
+/// quality of those peers can be added, e.g. that some of them provide the history service.
+/// Consensus is lost as soon as any of the criteria is no longer satisfied.
+#[derive(Clone)]
+pub struct ConsensusPolicy {
+    /// Minimum number of peers necessary to reach consensus.
+    pub min_peers: usize,
+    requirements: Vec<PeerRequirement>,
+}
+
+impl ConsensusPolicy {
+    /// Creates a policy that only requires `min_peers` peers.
+    pub fn new(min_peers: usize) -> Self {
+        Self {
+            min_peers,
+            requirements: vec![],
+        }
+    }
+
+    /// Requires at least `min_peers` peers that provide all of the given `services`.
+    pub fn with_min_peers_providing(self, services: Services, min_peers: usize) -> Self {
+        self.with_requirement(
+            format!("{} peers providing {:?}", min_peers, services),
+            min_peers,
+            move |peer_info| peer_info.get_services().contains(services),
+        )
+    }
+
+    /// Requires at least one validator peer.
+    pub fn with_validator_peer(self) -> Self {
+        self.with_min_peers_providing(Services::VALIDATOR, 1)
+    }
+
+    /// Requires at least `min_peers` peers matching the given predicate.
+    pub fn with_requirement<F>(
+        mut self,
+        description: impl Into<String>,
+        min_peers: usize,
+        predicate: F,
+    ) -> Self
+    where
+        F: Fn(&PeerInfo) -> bool + Send + Sync + 'static,
+    {
+        self.requirements.push(PeerRequirement {
+            description: description.into(),
+            min_peers,
+            predicate: Arc::new(predicate),
+        });
+        self
+    }
+
+    /// Checks whether the given peers satisfy this policy.
+    pub fn is_satisfied(&self, peers: &[PeerInfo]) -> bool {
+        peers.len() >= self.min_peers
+            && self.requirements.iter().all(|requirement| {
+                peers
+                    .iter()
+                    .filter(|peer_info| (requirement.predicate)(peer_info))
+                    .count()
+                    >= requirement.min_peers
+            })
+    }
+}
+
+impl From<usize> for ConsensusPolicy {
+    fn from(min_peers: usize) -> Self {
+        Self::new(min_peers)
+    }
+}
+
+impl fmt::Debug for ConsensusPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConsensusPolicy")
+            .field("min_peers", &self.min_peers)
+            .field(
+                "requirements",
+                &self
+                    .requirements
+                    .iter()
+                    .map(|requirement| &requirement.description)
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nimiq_network_interface::{
+        multiaddr,
+        peer_info::{PeerInfo, Services},
+    };
+
+    use super::ConsensusPolicy;
+
+    fn peer(services: Services) -> PeerInfo {
+        PeerInfo::new(multiaddr!(Memory(1u64)), services)
+    }
+
+    #[test]
+    fn it_requires_min_peers() {
+        let policy = ConsensusPolicy::new(2);
+        assert!(!policy.is_satisfied(&[peer(Services::FULL_BLOCKS)]));
+        assert!(policy.is_satisfied(&[peer(Services::FULL_BLOCKS), peer(Services::empty())]));
+        assert!(ConsensusPolicy::new(0).is_satisfied(&[]));
+    }
+
+    #[test]
+    fn it_requires_peers_providing_services() {
+        let policy = ConsensusPolicy::new(2)
+            .with_min_peers_providing(Services::HISTORY, 2)
+            .with_validator_peer();
+
+        let history = peer(Services::FULL_BLOCKS | Services::HISTORY);
+        let validator = peer(Services::FULL_BLOCKS | Services::HISTORY | Services::VALIDATOR);
+
+        assert!(!policy.is_satisfied(&[history.clone(), history.clone()]));
+        assert!(!policy.is_satisfied(&[validator.clone(), peer(Services::FULL_BLOCKS)]));
+        assert!(policy.is_satisfied(&[history, validator]));
+    }
+
+    #[test]
+    fn it_evaluates_custom_requirements() {
+        let policy = ConsensusPolicy::new(1).with_requirement("no mempool", 1, |peer_info| {
+            !peer_info.get_services().contains(Services::MEMPOOL)
+        });
+
+        assert!(!policy.is_satisfied(&[peer(Services::MEMPOOL)]));
+        assert!(policy.is_satisfied(&[peer(Services::MEMPOOL), peer(Services::FULL_BLOCKS)]));
+    }
+}
@@ -1,14 +1,14 @@
 use std::{
-    collections::{BTreeMap, HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc,
     },
 };
 
-use futures::stream::BoxStream;
-use nimiq_account::{Account, Staker, Validator};
-use nimiq_block::Block;
+use futures::{future, stream::BoxStream, StreamExt};
+use nimiq_account::{Account, Staker, Tombstone, Validator};
+use nimiq_block::{Block, MacroBlock};
 use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_blockchain_proxy::BlockchainProxy;
 use nimiq_hash::Blake2bHash;
@@ -18,24 +18,45 @@ use nimiq_network_interface::{
     peer_info::Services,
     request::{OutboundRequestError, RequestError},
 };
-use nimiq_primitives::{key_nibbles::KeyNibbles, policy::Policy};
+use nimiq_primitives::policy::Policy;
 use nimiq_transaction::{
-    historic_transaction::HistoricTransaction, ControlTransaction, ControlTransactionTopic,
-    Transaction, TransactionTopic,
+    gossip::TransactionGossipCache, historic_transaction::HistoricTransaction, ControlTransaction,
+    ControlTransactionTopic, Transaction, TransactionTopic,
 };
-use tokio::sync::{broadcast, mpsc, oneshot};
-use tokio_stream::wrappers::BroadcastStream;
+use nimiq_utils::spawn;
+#[cfg(feature = "full")]
+use parking_lot::Mutex;
+use tokio::sync::{broadcast, mpsc, oneshot, Mutex as AsyncMutex};
+use tokio_stream::wrappers::{BroadcastStream, ReceiverStream};
 
-use super::{ConsensusRequest, ResolveBlockError, ResolveBlockRequest};
+#[cfg(feature = "full")]
+use super::UpgradeToFullSyncRequest;
+use super::{
+    block_range::ResolveBlockRangeError, ConsensusRequest, ResolveAccountsRequest,
+    ResolveBlockError, ResolveBlockRangeRequest, ResolveBlockRequest,
+};
 use crate::{
-    consensus::remote_data_store::RemoteDataStore,
+    consensus::{
+        remote_data_store::{RemoteData, RemoteDataKey, RemoteDataStore},
+        transaction_inclusion::{
+            send_and_track, send_with_updates, TransactionInclusion, TransactionState,
+        },
+    },
     messages::{
-        AddressNotification, AddressSubscriptionOperation, AddressSubscriptionTopic,
-        RequestBlocksProof, RequestSubscribeToAddress, RequestTransactionReceiptsByAddress,
-        RequestTransactionsProof, ResponseBlocksProof,
+        AddressNotification, AddressSubscriptionFilter, AddressSubscriptionOperation,
+        AddressSubscriptionTopic, RequestAddressFilters, RequestBlocksProof, RequestInherentsProof,
+        RequestSubscribeToAddress, RequestSubscribeToAddressWithFilter,
+        RequestTransactionReceiptsByAddress, RequestTransactionsProof, RequestValidatorPunishments,
+        ResponseBlocksProof, ResponseValidatorPunishments,
     },
     ConsensusEvent,
 };
+#[cfg(feature = "full")]
+use crate::{BlsCache, SyncerModeError};
+
+/// The sender of the address notifications we receive, once we subscribed to them.
+pub(crate) type AddressNotifications<N> =
+    Arc<AsyncMutex<Option<broadcast::Sender<(AddressNotification, <N as Network>::PubsubId)>>>>;
 
 pub struct ConsensusProxy<N: Network> {
     pub blockchain: BlockchainProxy,
@@ -44,6 +65,9 @@ pub struct ConsensusProxy<N: Network> {
     pub(crate) synced_validity_window_flag: Arc<AtomicBool>,
     pub(crate) events: broadcast::Sender<ConsensusEvent>,
     pub(crate) request: mpsc::Sender<ConsensusRequest<N>>,
+    pub(crate) transaction_gossip_cache: TransactionGossipCache,
+    pub(crate) transaction_batcher: Option<mpsc::Sender<Transaction>>,
+    pub(crate) address_notifications: AddressNotifications<N>,
 }
 
 impl<N: Network> Clone for ConsensusProxy<N> {
@@ -55,22 +79,67 @@ impl<N: Network> Clone for ConsensusProxy<N> {
             synced_validity_window_flag: Arc::clone(&self.synced_validity_window_flag),
             events: self.events.clone(),
             request: self.request.clone(),
+            transaction_gossip_cache: self.transaction_gossip_cache.clone(),
+            transaction_batcher: self.transaction_batcher.clone(),
+            address_notifications: Arc::clone(&self.address_notifications),
         }
     }
 }
 
 impl<N: Network> ConsensusProxy<N> {
+    /// Number of resolved blocks that are buffered until they are consumed.
+    const RESOLVE_BLOCK_RANGE_BUFFER: usize = 64;
+
+    /// Number of address notifications that are buffered for each subscriber.
+    const ADDRESS_NOTIFICATIONS_BUFFER: usize = 64;
+
     pub async fn send_transaction(&self, tx: Transaction) -> Result<(), N::Error> {
         match ControlTransaction::try_from(tx) {
             Ok(ctx) => self.network.publish::<ControlTransactionTopic>(ctx).await,
             Err(err) => {
-                self.network
-                    .publish::<TransactionTopic>(err.into_inner())
-                    .await
+                let tx = err.into_inner();
+                if let Some(batcher) = &self.transaction_batcher {
+                    match batcher.send(tx).await {
+                        Ok(()) => return Ok(()),
+                        // The batcher stopped, publish the transaction directly.
+                        Err(mpsc::error::SendError(tx)) => {
+                            return self.network.publish::<TransactionTopic>(tx).await
+                        }
+                    }
+                }
+                self.network.publish::<TransactionTopic>(tx).await
             }
         }
     }
 
+    /// Returns the transactions this node provides to peers pulling announced transactions.
+    pub fn transaction_gossip_cache(&self) -> TransactionGossipCache {
+        self.transaction_gossip_cache.clone()
+    }
+
+    /// Sends the transaction to the network and returns a stream reporting its inclusion in a
+    /// block, its finalization, or the expiry of its validity window, after which the stream ends.
+    /// Light clients don't store block bodies, the inclusion is then proven by `min_peers` peers
+    /// providing the transaction history.
+    pub async fn send_transaction_tracked(
+        &self,
+        tx: Transaction,
+        min_peers: usize,
+    ) -> Result<BoxStream<'static, TransactionInclusion>, N::Error> {
+        send_and_track(self.clone(), tx, min_peers).await
+    }
+
+    /// Sends the transaction and returns a stream of its state: `Pending` once it was sent, then
+    /// `Included` and finally `Confirmed` or `Expired`. A reverted inclusion is reported as
+    /// `Pending` again.
+    pub async fn send_transaction_with_updates(
+        &self,
+        tx: Transaction,
+        min_peers: usize,
+    ) -> Result<BoxStream<'static, TransactionState>, N::Error> {
+        send_with_updates(self.clone(), tx, min_peers).await
+    }
+
     pub fn is_established(&self) -> bool {
         self.established_flag.load(Ordering::Acquire)
     }
@@ -85,18 +154,38 @@ impl<N: Network> ConsensusProxy<N> {
         BroadcastStream::new(self.events.subscribe())
     }
 
-    /// Subscribe to remote address notification events
+    /// Subscribe to remote address notification events. All subscribers share our subscription to
+    /// the notification topic, which is created by the first one.
     pub async fn subscribe_address_notifications(
         &self,
-    ) -> BoxStream<(AddressNotification, N::PubsubId)> {
-        let txn_stream = self
-            .network
-            .subscribe_subtopic::<AddressSubscriptionTopic>(
-                self.network.get_local_peer_id().to_string(),
-            )
-            .await;
-
-        txn_stream.unwrap()
+    ) -> Result<BoxStream<'static, (AddressNotification, N::PubsubId)>, N::Error> {
+        let mut address_notifications = self.address_notifications.lock().await;
+        if address_notifications.is_none() {
+            let mut notifications = self
+                .network
+                .subscribe_subtopic::<AddressSubscriptionTopic>(
+                    self.network.get_local_peer_id().to_string(),
+                )
+                .await?;
+
+            let (sender, _) = broadcast::channel(Self::ADDRESS_NOTIFICATIONS_BUFFER);
+            let forward_sender = sender.clone();
+            spawn(async move {
+                while let Some(notification) = notifications.next().await {
+                    // There might be no subscriber at the moment.
+                    let _ = forward_sender.send(notification);
+                }
+            });
+            *address_notifications = Some(sender);
+        }
+
+        let receiver = address_notifications
+            .as_ref()
+            .expect("Subscribed to address notifications")
+            .subscribe();
+        Ok(BroadcastStream::new(receiver)
+            .filter_map(|notification| future::ready(notification.ok()))
+            .boxed())
     }
 
     pub async fn request_transaction_receipts_by_address(
@@ -156,6 +245,59 @@ impl<N: Network> ConsensusProxy<N> {
         Ok(receipts)
     }
 
+    /// Returns the batches within `count` batches from `start_batch` that may contain transactions
+    /// touching any of the given addresses, according to the address filters of our peers.
+    /// A batch is considered relevant if the filter of any of the peers matches, so a single peer
+    /// can't hide transactions. Batches after the head of all peers are not returned.
+    pub async fn request_relevant_batches(
+        &self,
+        addresses: Vec<Address>,
+        start_batch: u32,
+        count: u16,
+        min_peers: usize,
+    ) -> Result<Vec<u32>, RequestError> {
+        let mut relevant_batches = BTreeSet::new();
+
+        for peer_id in self
+            .get_peers_for_service(Services::TRANSACTION_INDEX, min_peers)
+            .await?
+        {
+            log::debug!(
+                peer_id = %peer_id,
+                "Performing address filters request to peer",
+            );
+            let response = self
+                .network
+                .request::<RequestAddressFilters>(
+                    RequestAddressFilters { start_batch, count },
+                    peer_id,
+                )
+                .await;
+
+            match response {
+                Ok(Ok(response)) => {
+                    for (batch, filter) in (start_batch..).zip(response.filters.iter()) {
+                        let is_relevant = filter.as_ref().is_some_and(|filter| {
+                            addresses.iter().any(|address| filter.contains(address))
+                        });
+                        if is_relevant {
+                            relevant_batches.insert(batch);
+                        }
+                    }
+                }
+                Ok(Err(error)) => {
+                    log::debug!(peer=%peer_id, err=%error, "Peer couldn't provide address filters");
+                }
+                Err(error) => {
+                    // If there was a request error with this peer we log an error
+                    log::error!(peer=%peer_id, err=%error, "There was an error requesting address filters from peer");
+                }
+            }
+        }
+
+        Ok(relevant_batches.into_iter().collect())
+    }
+
     pub async fn request_transaction_by_hash_and_block_number(
         &self,
         tx_hash: Blake2bHash,
@@ -339,7 +481,7 @@ impl<N: Network> ConsensusProxy<N> {
                     Ok(Ok(response)) => {
                         // We verify the transaction using the proof
                         log::debug!(peer = %peer_id, block = %response.block, "New txns proof and block from peer");
-                        let mut verification_result = response
+                        let verification_result = response
                             .proof
                             .verify(response.block.history_root().clone())
                             .unwrap_or(false);
@@ -351,79 +493,21 @@ impl<N: Network> ConsensusProxy<N> {
                         }
 
                         // Verify that the transaction proof fits to the chain
-                        if response.block.block_number() <= election_head.block_number() {
-                            let block_hash = response.block.hash();
-                            let mut already_proven = false;
-
-                            if response.block.block_number() == Policy::genesis_block_number() {
-                                let genesis_hash = self.blockchain.read().get_genesis_hash();
-
-                                if genesis_hash == response.block.hash() {
-                                    already_proven = true;
-                                } else {
-                                    log::warn!(peer = %peer_id, "The genesis hash from the peer does not match our own");
-                                    continue;
-                                }
-                            } else if election_head.hash() == block_hash
-                                || election_head.header.parent_election_hash == block_hash
-                            {
-                                already_proven = true;
-                            } else if let Some(ref interlink) = election_head.header.interlink {
-                                already_proven = interlink.contains(&block_hash);
-                            }
-
-                            if !already_proven {
-                                // Request block inclusion proofs for txns of previous epochs
-                                let block_proof = match self
-                                    .network
-                                    .request::<RequestBlocksProof>(
-                                        RequestBlocksProof {
-                                            election_head: election_head.block_number(),
-                                            blocks: vec![response.block.block_number()],
-                                        },
-                                        peer_id,
-                                    )
-                                    .await
-                                {
-                                    Ok(Ok(ResponseBlocksProof { proof })) => proof,
-                                    Ok(Err(error)) => {
-                                        log::debug!(%error, peer = %peer_id, "Error on remote side while requesting block proof");
-                                        continue;
-                                    }
-                                    Err(error) => {
-                                        log::debug!(%error, peer = %peer_id, "Error requesting block proof");
-                                        continue;
-                                    }
-                                };
-
-                                // Verify that the block is part of the chain using the block inclusion proof
-                                if let Block::Macro(macro_block) = response.block {
-                                    verification_result = verification_result
-                                        && block_proof
-                                            .is_block_proven(&election_head, &macro_block);
-                                } else {
-                                    log::debug!(peer = %peer_id, "Macro block expected in tx proof response");
-                                    continue;
-                                }
-                            }
-                        } else if response.block.block_number() <= checkpoint_head.block_number() {
-                            // Check that the transaction inclusion proof actually proofs inclusion in the block we know
-                            if response.block.hash() != checkpoint_head.hash() {
-                                log::debug!(peer = %peer_id, "BlockProof does not correspond to expected checkpoint block");
-                                continue;
-                            }
-                        } else if response.block.hash() != current_head_hash {
-                            log::debug!(block_number = %response.block.block_number(), peer=%peer_id, "BlockProof does not correspond to expected block");
+                        if !self
+                            .verify_proving_block(
+                                peer_id,
+                                response.block,
+                                &election_head,
+                                &checkpoint_head,
+                                &current_head_hash,
+                            )
+                            .await
+                        {
                             continue;
                         }
 
-                        if verification_result {
-                            for tx in response.proof.history {
-                                verified_transactions.insert(tx.tx_hash(), tx);
-                            }
-                        } else {
-                            // The proof didn't verify so we continue with another peer
-                            log::warn!(peer = %peer_id, "The transaction block proof from this peer did not verify");
+                        for tx in response.proof.history {
+                            verified_transactions.insert(tx.tx_hash(), tx);
                         }
                     }
                     Ok(Err(error)) => {
@@ -446,6 +530,211 @@ impl<N: Network> ConsensusProxy<N> {
         Ok(transactions)
     }
 
+    /// Requests all inherents (e.g. rewards, penalties and jails) of the blocks from
+    /// `start_block` to `end_block` (both inclusive) and verifies their inclusion against the
+    /// history root of a block known to be part of the chain. The range has to be within one
+    /// epoch and cover at most [`RequestInherentsProof::MAX_BLOCKS`] blocks.
+    ///
+    /// Only the inclusion of the returned inherents is proven: a peer could still omit some of
+    /// them, which is why the inherents of all `min_peers` queried peers are merged.
+    /// The inherents are returned in ascending block order.
+    pub async fn prove_inherents_by_block_range(
+        &self,
+        start_block: u32,
+        end_block: u32,
+        min_peers: usize,
+    ) -> Result<Vec<HistoricTransaction>, RequestError> {
+        let blockchain = self.blockchain.read();
+        let election_head = blockchain.election_head().clone();
+        let checkpoint_head = blockchain.macro_head().clone();
+        let current_head_hash = blockchain.head_hash();
+        let current_block_number = blockchain.block_number();
+        drop(blockchain);
+
+        if start_block > end_block
+            || end_block > current_block_number
+            || Policy::epoch_at(start_block) != Policy::epoch_at(end_block)
+            || end_block - start_block >= RequestInherentsProof::MAX_BLOCKS
+        {
+            return Err(RequestError::OutboundRequest(OutboundRequestError::Other(
+                "Invalid block range".to_string(),
+            )));
+        }
+
+        // Choose the proving block like for transaction proofs.
+        let block_number = if Policy::is_election_block_at(end_block) {
+            end_block
+        } else if end_block < election_head.block_number() {
+            Policy::election_block_after(end_block)
+        } else if end_block <= checkpoint_head.block_number() {
+            checkpoint_head.block_number()
+        } else {
+            current_block_number
+        };
+
+        let mut verified_inherents = HashMap::new();
+        let mut has_verified_proof = false;
+        for peer_id in self
+            .get_peers_for_service(Services::TRANSACTION_INDEX, min_peers)
+            .await?
+        {
+            let response = self
+                .network
+                .request::<RequestInherentsProof>(
+                    RequestInherentsProof {
+                        start_block,
+                        end_block,
+                        block_number,
+                    },
+                    peer_id,
+                )
+                .await;
+            let response = match response {
+                Ok(Ok(response)) => response,
+                Ok(Err(error)) => {
+                    log::debug!(peer = %peer_id, %error, "We requested an inherents proof but the peer couldn't provide any");
+                    continue;
+                }
+                Err(error) => {
+                    log::error!(peer = %peer_id, %error, "There was an error requesting inherents proof from peer");
+                    continue;
+                }
+            };
+
+            if !response
+                .proof
+                .verify(response.block.history_root().clone())
+                .unwrap_or(false)
+            {
+                log::warn!(peer = %peer_id, "The inherents history proof from this peer did not verify");
+                continue;
+            }
+
+            // Only accept inherents of the requested range.
+            if response.proof.history.iter().any(|hist_tx| {
+                !hist_tx.is_not_basic()
+                    || hist_tx.block_number < start_block
+                    || hist_tx.block_number > end_block
+            }) {
+                log::warn!(peer = %peer_id, "The inherents proof from this peer contains unrequested transactions");
+                continue;
+            }
+
+            if !self
+                .verify_proving_block(
+                    peer_id,
+                    response.block,
+                    &election_head,
+                    &checkpoint_head,
+                    &current_head_hash,
+                )
+                .await
+            {
+                continue;
+            }
+
+            for hist_tx in response.proof.history {
+                verified_inherents.insert(hist_tx.tx_hash(), hist_tx);
+            }
+            has_verified_proof = true;
+        }
+
+        // Without a single verified proof, an empty result would falsely claim that there are no
+        // inherents in the range.
+        if !has_verified_proof {
+            return Err(RequestError::OutboundRequest(OutboundRequestError::Other(
+                "No peer provided a valid inherents proof".to_string(),
+            )));
+        }
+
+        let mut inherents: Vec<_> = verified_inherents.into_values().collect();
+        inherents.sort_by_key(|hist_tx| hist_tx.block_number);
+
+        Ok(inherents)
+    }
+
+    /// Verifies that the block a history proof was created against is part of our chain.
+    /// Blocks of finalized epochs are proven with a block inclusion proof requested from the peer.
+    async fn verify_proving_block(
+        &self,
+        peer_id: N::PeerId,
+        block: Block,
+        election_head: &MacroBlock,
+        checkpoint_head: &MacroBlock,
+        current_head_hash: &Blake2bHash,
+    ) -> bool {
+        if block.block_number() <= election_head.block_number() {
+            let block_hash = block.hash();
+
+            if block.block_number() == Policy::genesis_block_number() {
+                let genesis_hash = self.blockchain.read().get_genesis_hash();
+                if genesis_hash != block_hash {
+                    log::warn!(peer = %peer_id, "The genesis hash from the peer does not match our own");
+                    return false;
+                }
+                return true;
+            }
+
+            if election_head.hash() == block_hash
+                || election_head.header.parent_election_hash == block_hash
+                || election_head
+                    .header
+                    .interlink
+                    .as_ref()
+                    .is_some_and(|interlink| interlink.contains(&block_hash))
+            {
+                return true;
+            }
+
+            // Request block inclusion proofs for blocks of previous epochs
+            let block_proof = match self
+                .network
+                .request::<RequestBlocksProof>(
+                    RequestBlocksProof {
+                        election_head: election_head.block_number(),
+                        blocks: vec![block.block_number()],
+                    },
+                    peer_id,
+                )
+                .await
+            {
+                Ok(Ok(ResponseBlocksProof { proof })) => proof,
+                Ok(Err(error)) => {
+                    log::debug!(%error, peer = %peer_id, "Error on remote side while requesting block proof");
+                    return false;
+                }
+                Err(error) => {
+                    log::debug!(%error, peer = %peer_id, "Error requesting block proof");
+                    return false;
+                }
+            };
+
+            // Verify that the block is part of the chain using the block inclusion proof
+            let Block::Macro(macro_block) = block else {
+                log::debug!(peer = %peer_id, "Macro block expected in tx proof response");
+                return false;
+            };
+            if !block_proof.is_block_proven(election_head, &macro_block) {
+                // The proof didn't verify so we continue with another peer
+                log::warn!(peer = %peer_id, "The transaction block proof from this peer did not verify");
+                return false;
+            }
+            true
+        } else if block.block_number() <= checkpoint_head.block_number() {
+            // Check that the transaction inclusion proof actually proofs inclusion in the block we know
+            if block.hash() != checkpoint_head.hash() {
+                log::debug!(peer = %peer_id, "BlockProof does not correspond to expected checkpoint block");
+                return false;
+            }
+            true
+        } else if &block.hash() != current_head_hash {
+            log::debug!(block_number = %block.block_number(), peer=%peer_id, "BlockProof does not correspond to expected block");
+            false
+        } else {
+            true
+        }
+    }
+
     /// Gets a set of accounts given their addresses. The returned type is a
     /// BTreeMap of addresses to an optional `Account`. If an account was not
     /// found, then `None` is returned in its corresponding entry.
@@ -454,30 +743,9 @@ impl<N: Network> ConsensusProxy<N> {
         addresses: Vec<Address>,
         min_peers: usize,
     ) -> Result<BTreeMap<Address, Option<Account>>, RequestError> {
-        let mut keys = HashMap::<KeyNibbles, Address>::from_iter(
-            addresses
-                .iter()
-                .map(|address| (KeyNibbles::from(address), address.clone())),
-        );
-        let accounts: BTreeMap<KeyNibbles, Option<Account>> = RemoteDataStore::get_trie(
-            Arc::clone(&self.network),
-            self.blockchain.clone(),
-            &keys.keys().cloned().collect::<Vec<KeyNibbles>>(),
-            min_peers,
-        )
-        .await?;
-
-        let accounts = accounts
-            .iter()
-            .map(|(key, account)| {
-                (
-                    keys.remove(key)
-                        .expect("Key must be in the proven accounts"),
-                    account.clone(),
-                )
-            })
-            .collect();
-        Ok(accounts)
+        self.remote_data_store(min_peers)
+            .get_accounts(addresses)
+            .await
     }
 
     /// Gets a set of validators given their addresses. The returned type is a
@@ -488,12 +756,9 @@ impl<N: Network> ConsensusProxy<N> {
         addresses: Vec<Address>,
         min_peers: usize,
     ) -> Result<BTreeMap<Address, Option<Validator>>, RequestError> {
-        let remote_ds = RemoteDataStore {
-            network: Arc::clone(&self.network),
-            blockchain: self.blockchain.clone(),
-            min_peers,
-        };
-        remote_ds.get_validators(addresses).await
+        self.remote_data_store(min_peers)
+            .get_validators(addresses)
+            .await
     }
 
     /// Gets a set of stakers given their addresses. The returned type is a
@@ -504,12 +769,87 @@ impl<N: Network> ConsensusProxy<N> {
         addresses: Vec<Address>,
         min_peers: usize,
     ) -> Result<BTreeMap<Address, Option<Staker>>, RequestError> {
-        let remote_ds = RemoteDataStore {
-            network: Arc::clone(&self.network),
-            blockchain: self.blockchain.clone(),
+        self.remote_data_store(min_peers)
+            .get_stakers(addresses)
+            .await
+    }
+
+    /// Gets a set of tombstones given the addresses of the deleted validators. The returned
+    /// type is a BTreeMap of addresses to an optional `Tombstone`. If a tombstone was not
+    /// found, then `None` is returned in its corresponding entry.
+    pub async fn request_tombstones_by_addresses(
+        &self,
+        addresses: Vec<Address>,
+        min_peers: usize,
+    ) -> Result<BTreeMap<Address, Option<Tombstone>>, RequestError> {
+        self.remote_data_store(min_peers)
+            .get_tombstones(addresses)
+            .await
+    }
+
+    /// Gets a set of arbitrary entries of the accounts trie, proven against the state root
+    /// of a block known to the local blockchain. If an entry was not found, then `None` is
+    /// returned in its corresponding entry.
+    pub async fn request_remote_data(
+        &self,
+        keys: Vec<RemoteDataKey>,
+        min_peers: usize,
+    ) -> Result<BTreeMap<RemoteDataKey, Option<RemoteData>>, RequestError> {
+        self.remote_data_store(min_peers).get(keys).await
+    }
+
+    /// Requests the punishments of a validator within the window retained by the staking contract.
+    /// The punishments are not proven, so the response of the peer with the most recent head is
+    /// returned. Returns `None` if none of the peers knows the validator.
+    pub async fn request_validator_punishments(
+        &self,
+        validator_address: Address,
+        min_peers: usize,
+    ) -> Result<Option<ResponseValidatorPunishments>, RequestError> {
+        let mut latest_response: Option<ResponseValidatorPunishments> = None;
+
+        for peer_id in self
+            .get_peers_for_service(Services::ACCOUNTS_PROOF, min_peers)
+            .await?
+        {
+            let response = self
+                .network
+                .request::<RequestValidatorPunishments>(
+                    RequestValidatorPunishments {
+                        validator_address: validator_address.clone(),
+                    },
+                    peer_id,
+                )
+                .await;
+
+            match response {
+                Ok(Ok(response)) => {
+                    if latest_response
+                        .as_ref()
+                        .map_or(true, |latest| latest.block_number < response.block_number)
+                    {
+                        latest_response = Some(response);
+                    }
+                }
+                Ok(Err(error)) => {
+                    log::debug!(peer=%peer_id, err=%error, "Peer couldn't provide validator punishments");
+                }
+                Err(error) => {
+                    // If there was a request error with this peer we log an error
+                    log::error!(peer=%peer_id, err=%error, "There was an error requesting validator punishments from peer");
+                }
+            }
+        }
+
+        Ok(latest_response)
+    }
+
+    fn remote_data_store(&self, min_peers: usize) -> RemoteDataStore<N> {
+        RemoteDataStore::new(
+            Arc::clone(&self.network),
+            self.blockchain.clone(),
             min_peers,
-        };
-        remote_ds.get_stakers(addresses).await
+        )
     }
 
     pub async fn subscribe_to_addresses(
@@ -517,6 +857,24 @@ impl<N: Network> ConsensusProxy<N> {
         addresses: Vec<Address>,
         min_peers: usize,
         peer_id: Option<N::PeerId>,
+    ) -> Result<(), RequestError> {
+        self.subscribe_to_addresses_with_filter(
+            addresses,
+            AddressSubscriptionFilter::default(),
+            min_peers,
+            peer_id,
+        )
+        .await
+    }
+
+    /// Subscribes to the given addresses, only receiving notifications about the transactions that
+    /// pass the given filter. Subscribing to an address again replaces its previous filter.
+    pub async fn subscribe_to_addresses_with_filter(
+        &self,
+        addresses: Vec<Address>,
+        filter: AddressSubscriptionFilter,
+        min_peers: usize,
+        peer_id: Option<N::PeerId>,
     ) -> Result<(), RequestError> {
         // If we are provided a peer_id we perform the request only to this specific peer
         let peers = if let Some(peer_id) = peer_id {
@@ -540,16 +898,29 @@ impl<N: Network> ConsensusProxy<N> {
 
         // Subscribe to all peers that could provide the necessary services
         for peer_id in peers {
-            let response = self
-                .network
-                .request::<RequestSubscribeToAddress>(
-                    RequestSubscribeToAddress {
-                        operation: AddressSubscriptionOperation::Subscribe,
-                        addresses: addresses.clone(),
-                    },
-                    peer_id,
-                )
-                .await;
+            // Subscriptions without a filter use the original request, which is understood by
+            // all peers.
+            let response = if filter == AddressSubscriptionFilter::default() {
+                self.network
+                    .request::<RequestSubscribeToAddress>(
+                        RequestSubscribeToAddress {
+                            operation: AddressSubscriptionOperation::Subscribe,
+                            addresses: addresses.clone(),
+                        },
+                        peer_id,
+                    )
+                    .await
+            } else {
+                self.network
+                    .request::<RequestSubscribeToAddressWithFilter>(
+                        RequestSubscribeToAddressWithFilter {
+                            addresses: addresses.clone(),
+                            filter: filter.clone(),
+                        },
+                        peer_id,
+                    )
+                    .await
+            };
 
             match response {
                 Ok(Ok(())) => {
@@ -638,4 +1009,86 @@ impl<N: Network> ConsensusProxy<N> {
         // the channel drops in which case the resolve block request will fail.
         receiver.await.map_err(ResolveBlockError::ReceiveError)?
     }
+
+    /// Asks the consensus to resolve the blocks `from..=to`. The blocks are fetched from peers
+    /// that are able to provide them and are streamed back in ascending order once verified
+    /// against our own chain. The stream ends after the last block or the first error.
+    pub async fn resolve_block_range(
+        &self,
+        from: u32,
+        to: u32,
+        include_body: bool,
+    ) -> Result<BoxStream<'static, Result<Block, ResolveBlockRangeError>>, ResolveBlockRangeError>
+    {
+        let (block_sender, receiver) = mpsc::channel(Self::RESOLVE_BLOCK_RANGE_BUFFER);
+
+        let request = ResolveBlockRangeRequest {
+            from,
+            to,
+            include_body,
+            block_sender,
+        };
+
+        self.request
+            .send(ConsensusRequest::ResolveBlockRange(request))
+            .await
+            .map_err(|_| ResolveBlockRangeError::Dropped)?;
+
+        Ok(ReceiverStream::new(receiver).boxed())
+    }
+
+    /// Asks the consensus to resolve the accounts with the given addresses. The accounts are
+    /// requested from at least `min_peers` peers providing accounts proofs and are only returned
+    /// once their trie proofs were verified. If an account was not found, then `None` is returned
+    /// in its corresponding entry.
+    pub async fn resolve_accounts(
+        &self,
+        addresses: Vec<Address>,
+        min_peers: usize,
+    ) -> Result<BTreeMap<Address, Option<Account>>, RequestError> {
+        let (response_sender, receiver) = oneshot::channel();
+
+        let request = ResolveAccountsRequest {
+            addresses,
+            min_peers,
+            response_sender,
+        };
+
+        self.request
+            .send(ConsensusRequest::ResolveAccounts(request))
+            .await
+            .map_err(|_| RequestError::OutboundRequest(OutboundRequestError::SendError))?;
+
+        receiver
+            .await
+            .map_err(|_| RequestError::OutboundRequest(OutboundRequestError::SenderFutureDropped))?
+    }
+
+    #[cfg(feature = "full")]
+    /// Upgrades a light syncing node to full sync without restarting it.
+    ///
+    /// This requires the node to run on a full blockchain. The returned future resolves once the
+    /// new syncer has replaced the old one, the state sync then continues in the background.
+    pub async fn upgrade_to_full_sync(
+        &self,
+        bls_cache: Arc<Mutex<BlsCache>>,
+        full_sync_threshold: u32,
+    ) -> Result<(), SyncerModeError> {
+        let (response_sender, receiver) = oneshot::channel();
+
+        let request = UpgradeToFullSyncRequest {
+            bls_cache,
+            full_sync_threshold,
+            response_sender,
+        };
+
+        self.request
+            .send(ConsensusRequest::UpgradeToFullSync(request))
+            .await
+            .map_err(|_| SyncerModeError::ConsensusUnavailable)?;
+
+        receiver
+            .await
+            .map_err(|_| SyncerModeError::ConsensusUnavailable)?
+    }
 }
@@ -0,0 +1,45 @@
+use nimiq_network_interface::{network::Network, peer_info::PeerInfo};
+
+/// The outcome of the last head request sent to our synced peers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HeadRequestOutcome {
+    /// Number of peers whose head block we know.
+    pub num_known_blocks: usize,
+    /// Number of peers whose head block we didn't know.
+    pub num_unknown_blocks: usize,
+}
+
+/// The state of the consensus that criteria for established consensus are evaluated on.
+pub struct EstablishedContext<'a, N: Network> {
+    /// Whether consensus is currently established.
+    pub established: bool,
+    /// Our synced peers, together with their peer info if known.
+    pub peers: &'a [(N::PeerId, Option<PeerInfo>)],
+    /// Whether our state is complete, i.e. we are not syncing the accounts tree.
+    pub state_complete: bool,
+    /// Number of block announcements that we accepted and that extended our chain.
+    pub accepted_block_announcements: usize,
+    /// The outcome of the head request that just finished, if any.
+    pub head_request: Option<HeadRequestOutcome>,
+}
+
+/// Decides when consensus counts as established, replacing the built-in heuristics.
+///
+/// The criteria are evaluated whenever the state of the consensus might have changed. Consensus is
+/// established once they are satisfied and lost as soon as they are no longer satisfied. While
+/// consensus is not established, head requests are sent to our synced peers and their outcome is
+/// reported in [`EstablishedContext::head_request`].
+///
+/// Closures taking an [`EstablishedContext`] implement this trait.
+pub trait EstablishedCriteria<N: Network>: Send + Sync {
+    fn is_satisfied(&self, context: &EstablishedContext<'_, N>) -> bool;
+}
+
+impl<N: Network, F> EstablishedCriteria<N> for F
+where
+    F: Fn(&EstablishedContext<'_, N>) -> bool + Send + Sync,
+{
+    fn is_satisfied(&self, context: &EstablishedContext<'_, N>) -> bool {
+        self(context)
+    }
+}
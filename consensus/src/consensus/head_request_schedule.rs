@@ -0,0 +1,94 @@
+use std::time::Duration;
+
+use nimiq_primitives::policy::Policy;
+
+/// How often head requests are sent to our peers.
+///
+/// While consensus is not established or the chain is stalled, head requests are sent every
+/// `min_interval`. While the chain progresses normally, the interval doubles with every head
+/// request up to `max_interval`.
+#[derive(Clone, Debug)]
+pub struct HeadRequestSchedule {
+    /// Interval between head requests while consensus is not established or the chain is stalled.
+    pub min_interval: Duration,
+    /// Maximum interval between head requests while the chain progresses normally.
+    pub max_interval: Duration,
+    /// Number of block separation times without an accepted block after which the chain is
+    /// considered to be stalled.
+    pub stall_threshold: u32,
+}
+
+impl HeadRequestSchedule {
+    /// Returns whether the chain is considered to be stalled, given the time since we last
+    /// accepted a block.
+    pub fn is_stalled(&self, since_last_block: Option<Duration>) -> bool {
+        let stall_timeout = Duration::from_millis(Policy::BLOCK_SEPARATION_TIME)
+            .saturating_mul(self.stall_threshold);
+        !since_last_block.is_some_and(|elapsed| elapsed <= stall_timeout)
+    }
+
+    /// Returns the interval before the next head request, following a head request that was sent
+    /// after waiting for `current`.
+    pub fn next_interval(
+        &self,
+        current: Duration,
+        established: bool,
+        since_last_block: Option<Duration>,
+    ) -> Duration {
+        if !established || self.is_stalled(since_last_block) {
+            return self.min_interval;
+        }
+        current
+            .saturating_mul(2)
+            .min(self.max_interval)
+            .max(self.min_interval)
+    }
+}
+
+impl Default for HeadRequestSchedule {
+    fn default() -> Self {
+        Self {
+            min_interval: Duration::from_secs(5),
+            max_interval: Duration::from_secs(60),
+            stall_threshold: 10,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::HeadRequestSchedule;
+
+    #[test]
+    fn it_backs_off_while_the_chain_progresses() {
+        let schedule = HeadRequestSchedule::default();
+        let since_last_block = Some(Duration::from_secs(1));
+
+        let mut interval = schedule.min_interval;
+        for expected in [10, 20, 40, 60, 60] {
+            interval = schedule.next_interval(interval, true, since_last_block);
+            assert_eq!(interval, Duration::from_secs(expected));
+        }
+    }
+
+    #[test]
+    fn it_tightens_when_stalled_or_not_established() {
+        let schedule = HeadRequestSchedule::default();
+        let interval = schedule.max_interval;
+
+        assert!(schedule.is_stalled(None));
+        assert!(schedule.is_stalled(Some(Duration::from_secs(11))));
+        assert!(!schedule.is_stalled(Some(Duration::from_secs(10))));
+
+        assert_eq!(
+            schedule.next_interval(interval, true, Some(Duration::from_secs(11))),
+            schedule.min_interval
+        );
+        assert_eq!(
+            schedule.next_interval(interval, false, Some(Duration::from_secs(1))),
+            schedule.min_interval
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use std::{
+    collections::BTreeMap,
     future::Future,
     pin::Pin,
     sync::{
@@ -9,50 +10,92 @@ use std::{
     time::Duration,
 };
 
+#[cfg(feature = "full")]
+use futures::future::BoxFuture;
 use futures::{FutureExt, StreamExt};
 use instant::Instant;
+use nimiq_account::Account;
 use nimiq_block::Block;
 use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_blockchain_proxy::BlockchainProxy;
 #[cfg(feature = "full")]
 use nimiq_blockchain_proxy::BlockchainReadProxy;
 use nimiq_hash::Blake2bHash;
-use nimiq_network_interface::{network::Network, request::request_handler};
+use nimiq_keys::Address;
+use nimiq_network_interface::{
+    network::Network,
+    peer_info::{PeerInfo, Services},
+    request::{request_handler, RequestError},
+};
 use nimiq_time::{interval, Interval};
+use nimiq_transaction::{
+    gossip::{RequestTransactionsByHash, TransactionGossipCache},
+    Transaction,
+};
 use nimiq_utils::{spawn, WakerExt};
 use nimiq_zkp_component::zkp_component::ZKPComponentProxy;
+#[cfg(feature = "full")]
+use parking_lot::Mutex;
 use tokio::sync::{
     broadcast,
     mpsc::{self, error::SendError},
     oneshot::{self, error::RecvError},
+    Semaphore,
 };
 use tokio_stream::wrappers::BroadcastStream;
 
-use self::consensus_proxy::ConsensusProxy;
 #[cfg(feature = "full")]
 use self::remote_event_dispatcher::RemoteEventDispatcher;
+use self::{
+    block_range::{resolve_block_range, ResolveBlockRangeError},
+    consensus_policy::ConsensusPolicy,
+    consensus_proxy::{AddressNotifications, ConsensusProxy},
+    established_criteria::{EstablishedContext, EstablishedCriteria, HeadRequestOutcome},
+    head_request_schedule::HeadRequestSchedule,
+    recovery::{Recovery, RecoveryStrategy},
+    remote_data_store::RemoteDataStore,
+    transaction_gossip::{TransactionBatcher, TransactionGossipConfig},
+};
 use crate::{
     consensus::head_requests::{HeadRequests, HeadRequestsResult},
     messages::{RequestBlock, RequestHead, RequestMacroChain, RequestMissingBlocks},
-    sync::{live::block_queue::BlockSource, syncer::LiveSyncPushEvent, syncer_proxy::SyncerProxy},
+    sync::{
+        live::block_queue::BlockSource,
+        syncer::{LiveSyncPushEvent, SyncProgress},
+        syncer_proxy::SyncerProxy,
+    },
 };
 #[cfg(feature = "full")]
 use crate::{
     messages::{
-        RequestBatchSet, RequestBlocksProof, RequestHistoryChunk,
-        RequestTransactionReceiptsByAddress, RequestTransactionsProof, RequestTrieProof,
+        RequestAddressFilters, RequestBatchSet, RequestBlocksProof, RequestHistoryChunk,
+        RequestInherentsProof, RequestMissingTransactions, RequestTransactionReceiptsByAddress,
+        RequestTransactionsProof, RequestTrieProof, RequestValidatorPunishments,
     },
-    sync::live::{diff_queue::RequestTrieDiff, state_queue::RequestChunk},
+    sync::{
+        live::{diff_queue::RequestTrieDiff, state_queue::RequestChunk},
+        syncer_proxy::SyncerMode,
+    },
+    BlsCache, SyncerModeError,
 };
 
+pub mod block_range;
+pub mod consensus_policy;
 pub mod consensus_proxy;
+pub mod established_criteria;
+pub mod head_request_schedule;
 mod head_requests;
-mod remote_data_store;
+pub mod recovery;
+pub mod remote_data_store;
 #[cfg(feature = "full")]
 mod remote_event_dispatcher;
+pub mod transaction_gossip;
+pub mod transaction_inclusion;
+pub mod transaction_sender;
 
 /// Events that are generated by the consensus component to convey the two possible states of consensus:
-/// Established consensus (by satisfying some specific consensus criteria), or we lost it
+/// Established consensus (by satisfying some specific consensus criteria), or we lost it.
+/// Additionally, changes in the composition of our synced peers are reported.
 #[derive(Clone)]
 pub enum ConsensusEvent {
     /// Consensus is established
@@ -61,6 +104,46 @@ pub enum ConsensusEvent {
     Established { synced_validity_window: bool },
     /// Consensus was lost
     Lost,
+    /// The set of synced peers changed.
+    PeerSetChanged {
+        /// The number of synced peers.
+        num_peers: usize,
+        /// The number of synced peers providing the full transaction history.
+        num_history_peers: usize,
+        /// The number of synced peers that are validators.
+        num_validator_peers: usize,
+    },
+    /// The state sync made progress.
+    SyncProgress {
+        /// The number of chunks applied to the accounts trie, including those applied before a restart.
+        chunks_applied: u64,
+        /// The estimated total number of chunks, if an estimation is possible yet.
+        estimated_total: Option<u64>,
+    },
+}
+
+/// The composition of our synced peers, as last reported by [`ConsensusEvent::PeerSetChanged`].
+#[derive(Clone, Copy, Default, Eq, PartialEq)]
+struct PeerSet {
+    num_peers: usize,
+    num_history_peers: usize,
+    num_validator_peers: usize,
+}
+
+impl PeerSet {
+    fn from_peers(peers: &[PeerInfo]) -> Self {
+        let count = |services| {
+            peers
+                .iter()
+                .filter(|peer_info| peer_info.get_services().contains(services))
+                .count()
+        };
+        PeerSet {
+            num_peers: peers.len(),
+            num_history_peers: count(Services::HISTORY),
+            num_validator_peers: count(Services::VALIDATOR),
+        }
+    }
 }
 
 /// This enum is used to represent different kinds of events that are generated by other peers.
@@ -117,9 +200,56 @@ pub struct ResolveBlockRequest<N: Network> {
     pub(crate) response_sender: oneshot::Sender<Result<Block, ResolveBlockError<N>>>,
 }
 
+/// Requests the consensus to fetch the blocks `from..=to` from the network. The verified blocks
+/// are sent in ascending order over a channel, which is closed once the range was resolved.
+pub struct ResolveBlockRangeRequest {
+    /// Block number of the first block to resolve.
+    pub(crate) from: u32,
+
+    /// Block number of the last block to resolve.
+    pub(crate) to: u32,
+
+    /// Whether to resolve the blocks including their bodies.
+    pub(crate) include_body: bool,
+
+    /// Sender to a channel where the resolved blocks are being awaited.
+    pub(crate) block_sender: mpsc::Sender<Result<Block, ResolveBlockRangeError>>,
+}
+
+/// Requests the consensus to fetch the accounts with the given addresses from the network. The
+/// accounts are requested together with trie proofs, which are verified before responding.
+pub struct ResolveAccountsRequest {
+    /// The addresses of the accounts to resolve.
+    pub(crate) addresses: Vec<Address>,
+
+    /// The minimum number of peers providing accounts proofs to request the accounts from.
+    pub(crate) min_peers: usize,
+
+    /// Sender to a oneshot channel where the verified accounts are being awaited.
+    pub(crate) response_sender:
+        oneshot::Sender<Result<BTreeMap<Address, Option<Account>>, RequestError>>,
+}
+
+#[cfg(feature = "full")]
+/// Requests the consensus to upgrade its light syncer to a full syncer in-place.
+pub struct UpgradeToFullSyncRequest {
+    /// The BLS cache used by the full syncer.
+    pub(crate) bls_cache: Arc<Mutex<BlsCache>>,
+
+    /// The threshold used by the full syncer to decide whether to perform a full sync.
+    pub(crate) full_sync_threshold: u32,
+
+    /// Sender to a oneshot channel where the result of the upgrade is being awaited.
+    pub(crate) response_sender: oneshot::Sender<Result<(), SyncerModeError>>,
+}
+
 /// Enumeration of all ConsensusRequests available.
 pub enum ConsensusRequest<N: Network> {
     ResolveBlock(ResolveBlockRequest<N>),
+    ResolveBlockRange(ResolveBlockRangeRequest),
+    ResolveAccounts(ResolveAccountsRequest),
+    #[cfg(feature = "full")]
+    UpgradeToFullSync(UpgradeToFullSyncRequest),
 }
 
 pub struct Consensus<N: Network> {
@@ -137,8 +267,37 @@ pub struct Consensus<N: Network> {
     head_requests: Option<HeadRequests<N>>,
     head_requests_time: Option<Instant>,
     head_requests_interval: Interval,
+    /// The time to wait after the last head request before starting the next one.
+    head_requests_timeout: Duration,
+    head_request_schedule: HeadRequestSchedule,
+    /// The time at which we last accepted a block.
+    last_block_time: Option<Instant>,
+
+    policy: ConsensusPolicy,
+
+    /// Custom criteria for established consensus replacing the built-in heuristics, if set.
+    established_criteria: Option<Arc<dyn EstablishedCriteria<N>>>,
+
+    /// The composition of our synced peers that was last reported.
+    peer_set: PeerSet,
 
-    min_peers: usize,
+    /// The state sync progress that was last reported.
+    sync_progress: Option<SyncProgress>,
+
+    /// Automatic recovery after consensus was lost, if enabled.
+    recovery: Option<Recovery<N::AddressType, N::PeerId>>,
+
+    /// The transactions we provide to peers pulling announced transactions.
+    transaction_gossip_cache: TransactionGossipCache,
+    /// Sender to the task batching our transactions, if batching is enabled.
+    transaction_batcher: Option<mpsc::Sender<Transaction>>,
+
+    /// The address notifications we receive from our peers, shared by all subscribers. The
+    /// network only allows us to subscribe to our notification topic once.
+    address_notifications: AddressNotifications<N>,
+
+    /// Limits the number of block ranges that are resolved concurrently.
+    block_range_permits: Arc<Semaphore>,
 
     /// Sender and Receiver of a consensus request channel used to relay requests from any source
     /// to the Consensus instance. Currently the only source is a ConsensusProxy instance, but
@@ -157,6 +316,14 @@ pub struct Consensus<N: Network> {
 
     zkp_proxy: ZKPComponentProxy<N>,
 
+    /// A syncer that is being built to replace the current one, together with the sender
+    /// notifying the requester once the switch happened.
+    #[cfg(feature = "full")]
+    pending_sync_upgrade: Option<(
+        BoxFuture<'static, SyncerProxy<N>>,
+        oneshot::Sender<Result<(), SyncerModeError>>,
+    )>,
+
     waker: Option<Waker>,
 }
 
@@ -167,9 +334,9 @@ impl<N: Network> Consensus<N> {
     /// Minimum number of block announcements extending the chain for consensus to be established.
     const MIN_BLOCKS_ESTABLISHED: usize = 5;
 
-    /// Timeout after which head requests will be performed (again) to determine consensus
-    /// established state and to advance the chain.
-    const HEAD_REQUESTS_TIMEOUT: Duration = Duration::from_secs(5);
+    /// Maximum number of block ranges that are resolved concurrently. Further requests are
+    /// rejected until one of them finished.
+    const MAX_CONCURRENT_BLOCK_RANGES: usize = 4;
 
     pub fn from_network(
         blockchain: BlockchainProxy,
@@ -181,7 +348,7 @@ impl<N: Network> Consensus<N> {
             blockchain,
             network,
             syncer,
-            Self::MIN_PEERS_ESTABLISHED,
+            ConsensusPolicy::new(Self::MIN_PEERS_ESTABLISHED),
             zkp_proxy,
         )
     }
@@ -190,7 +357,7 @@ impl<N: Network> Consensus<N> {
         blockchain: BlockchainProxy,
         network: Arc<N>,
         syncer: SyncerProxy<N>,
-        min_peers: usize,
+        policy: impl Into<ConsensusPolicy>,
         zkp_proxy: ZKPComponentProxy<N>,
     ) -> Self {
         Self::init_network_request_receivers(&network, &blockchain);
@@ -207,6 +374,15 @@ impl<N: Network> Consensus<N> {
             }
         }
         let synced_validity_window_flag = Arc::new(AtomicBool::new(synced_validity_window_flag));
+        let head_request_schedule = HeadRequestSchedule::default();
+
+        let transaction_gossip_cache = TransactionGossipCache::default();
+        let stream = network.receive_requests::<RequestTransactionsByHash>();
+        spawn(Box::pin(request_handler(
+            &network,
+            stream,
+            &transaction_gossip_cache,
+        )));
 
         Consensus {
             blockchain,
@@ -219,15 +395,70 @@ impl<N: Network> Consensus<N> {
             synced_validity_window_flag,
             head_requests: None,
             head_requests_time: None,
-            head_requests_interval: interval(Self::HEAD_REQUESTS_TIMEOUT),
-            min_peers,
+            head_requests_interval: interval(head_request_schedule.min_interval),
+            head_requests_timeout: head_request_schedule.min_interval,
+            head_request_schedule,
+            last_block_time: None,
+            policy: policy.into(),
+            established_criteria: None,
+            peer_set: PeerSet::default(),
+            sync_progress: None,
+            recovery: None,
+            transaction_gossip_cache,
+            transaction_batcher: None,
+            address_notifications: AddressNotifications::default(),
+            block_range_permits: Arc::new(Semaphore::new(Self::MAX_CONCURRENT_BLOCK_RANGES)),
             // Choose a small buffer as having a lot of items buffered here indicates a bigger problem.
             requests: mpsc::channel(10),
             zkp_proxy,
+            #[cfg(feature = "full")]
+            pending_sync_upgrade: None,
             waker: None,
         }
     }
 
+    /// Enables the automatic recovery after consensus was lost, using the given strategy.
+    /// The `seeds` are dialed again if the strategy asks for it.
+    pub fn with_recovery(mut self, strategy: RecoveryStrategy, seeds: Vec<N::AddressType>) -> Self {
+        self.recovery = Some(Recovery::new(strategy, seeds));
+        self
+    }
+
+    /// Replaces the built-in heuristics deciding when consensus is established with the given
+    /// criteria. The consensus policy then only determines whether head requests are sent.
+    pub fn with_established_criteria(
+        mut self,
+        criteria: impl EstablishedCriteria<N> + 'static,
+    ) -> Self {
+        self.established_criteria = Some(Arc::new(criteria));
+        self
+    }
+
+    /// Sets the schedule of the head requests that determine consensus established state and
+    /// advance the chain.
+    pub fn with_head_request_schedule(mut self, schedule: HeadRequestSchedule) -> Self {
+        self.head_requests_timeout = schedule.min_interval;
+        self.head_requests_interval = interval(schedule.min_interval);
+        self.head_request_schedule = schedule;
+        self
+    }
+
+    /// Sets how the transactions sent through the consensus proxies are gossiped. Proxies
+    /// created before calling this keep publishing transactions individually.
+    pub fn with_transaction_gossip(mut self, config: TransactionGossipConfig) -> Self {
+        if config.batching {
+            let (tx, rx) = mpsc::channel(TransactionBatcher::<N>::QUEUE_SIZE);
+            let batcher = TransactionBatcher::new(
+                Arc::clone(&self.network),
+                config,
+                self.transaction_gossip_cache.clone(),
+            );
+            spawn(batcher.run(rx));
+            self.transaction_batcher = Some(tx);
+        }
+        self
+    }
+
     #[cfg(feature = "full")]
     fn init_remote_event_dispatcher(network: &Arc<N>, blockchain: &BlockchainProxy) {
         // We spawn the Remote Event Dispatcher into its own task (this is only available for full nodes and history nodes)
@@ -282,13 +513,25 @@ impl<N: Network> Consensus<N> {
 
                     let stream = network.receive_requests::<RequestTransactionReceiptsByAddress>();
                     spawn(Box::pin(request_handler(network, stream, blockchain)));
+
+                    let stream = network.receive_requests::<RequestAddressFilters>();
+                    spawn(Box::pin(request_handler(network, stream, blockchain)));
+
+                    let stream = network.receive_requests::<RequestInherentsProof>();
+                    spawn(Box::pin(request_handler(network, stream, blockchain)));
                 }
 
                 let stream = network.receive_requests::<RequestTrieProof>();
                 spawn(Box::pin(request_handler(network, stream, blockchain)));
 
+                let stream = network.receive_requests::<RequestValidatorPunishments>();
+                spawn(Box::pin(request_handler(network, stream, blockchain)));
+
                 let stream = network.receive_requests::<RequestBlocksProof>();
                 spawn(Box::pin(request_handler(network, stream, blockchain)));
+
+                let stream = network.receive_requests::<RequestMissingTransactions>();
+                spawn(Box::pin(request_handler(network, stream, blockchain)));
             }
             BlockchainProxy::Light(_) => {}
         }
@@ -306,6 +549,59 @@ impl<N: Network> Consensus<N> {
         self.sync.num_peers()
     }
 
+    /// Returns the policy our peers need to satisfy for consensus to be established.
+    pub fn policy(&self) -> &ConsensusPolicy {
+        &self.policy
+    }
+
+    /// Checks whether our synced peers satisfy the consensus policy.
+    fn policy_satisfied(&self) -> bool {
+        self.policy.is_satisfied(&self.synced_peer_infos())
+    }
+
+    /// Returns the peer infos of our synced peers.
+    fn synced_peer_infos(&self) -> Vec<PeerInfo> {
+        self.sync
+            .peers()
+            .into_iter()
+            .filter_map(|peer_id| self.network.get_peer_info(peer_id))
+            .collect()
+    }
+
+    /// Checks whether the composition of our synced peers changed since it was last reported.
+    fn check_peer_set(&mut self) -> Option<ConsensusEvent> {
+        let peer_set = PeerSet::from_peers(&self.synced_peer_infos());
+        if peer_set == self.peer_set {
+            return None;
+        }
+
+        self.peer_set = peer_set;
+        Some(ConsensusEvent::PeerSetChanged {
+            num_peers: peer_set.num_peers,
+            num_history_peers: peer_set.num_history_peers,
+            num_validator_peers: peer_set.num_validator_peers,
+        })
+    }
+
+    /// Checks whether the state sync made progress since it was last reported.
+    fn check_sync_progress(&mut self) -> Option<ConsensusEvent> {
+        let sync_progress = self.sync.state_sync_progress();
+        if sync_progress == self.sync_progress {
+            return None;
+        }
+
+        self.sync_progress = sync_progress;
+        sync_progress.map(|progress| ConsensusEvent::SyncProgress {
+            chunks_applied: progress.chunks_applied,
+            estimated_total: progress.estimated_total,
+        })
+    }
+
+    /// Returns the sync mode the syncer is currently running in.
+    pub fn sync_mode(&self) -> SyncerMode {
+        self.sync.mode()
+    }
+
     pub fn proxy(&self) -> ConsensusProxy<N> {
         ConsensusProxy {
             blockchain: self.blockchain.clone(),
@@ -314,6 +610,9 @@ impl<N: Network> Consensus<N> {
             synced_validity_window_flag: Arc::clone(&self.synced_validity_window_flag),
             events: self.events.clone(),
             request: self.requests.0.clone(),
+            transaction_gossip_cache: self.transaction_gossip_cache.clone(),
+            transaction_batcher: self.transaction_batcher.clone(),
+            address_notifications: Arc::clone(&self.address_notifications),
         }
     }
 
@@ -379,7 +678,7 @@ impl<N: Network> Consensus<N> {
     /// - we know at least 2/3 of the head blocks of our peers
     ///
     /// The latter check is started immediately once we reach the minimum number of peers
-    /// and is potentially repeated in an interval of `HEAD_REQUESTS_TIMEOUT` until one
+    /// and is potentially repeated in the interval given by the head request schedule until one
     /// of the conditions above is true.
     /// Any unknown blocks resulting of the head check are handled similarly as block announcements
     /// via the block queue.
@@ -387,12 +686,14 @@ impl<N: Network> Consensus<N> {
         &mut self,
         finished_head_request: Option<HeadRequestsResult<N>>,
     ) -> Option<ConsensusEvent> {
+        if let Some(criteria) = self.established_criteria.clone() {
+            return self.check_established_criteria(&*criteria, finished_head_request);
+        }
+
         // We can only lose established state right now if we drop below our minimum peer threshold.
         if self.is_established() {
-            if self.num_agents() < self.min_peers {
-                warn!("Lost consensus!");
-                self.established_flag.swap(false, Ordering::Release);
-                return Some(ConsensusEvent::Lost);
+            if !self.policy_satisfied() {
+                return Some(self.lose_consensus());
             }
             // Check if validity window availability changed.
             if let (_, Some(event)) = self.check_validity_window() {
@@ -405,22 +706,15 @@ impl<N: Network> Consensus<N> {
             // Then, we check that we either:
             // - accepted a minimum number of block announcements, or
             // - know the head state of a majority of our peers
-            if self.num_agents() >= self.min_peers && self.sync.state_complete() {
+            if self.policy_satisfied() && self.sync.state_complete() {
                 if self.sync.accepted_block_announcements() >= Self::MIN_BLOCKS_ESTABLISHED {
                     info!("Consensus established, number of accepted announcements satisfied.");
-                    self.established_flag.swap(true, Ordering::Release);
 
                     // Also stop any other checks.
                     self.head_requests = None;
                     self.head_requests_time = None;
 
-                    self.zkp_proxy
-                        .request_zkp_from_peers(self.sync.peers(), false);
-
-                    let (synced_validity_window, _) = self.check_validity_window();
-                    return Some(ConsensusEvent::Established {
-                        synced_validity_window,
-                    });
+                    return Some(self.establish_consensus());
                 } else {
                     // The head state check is carried out immediately after we reach the minimum
                     // number of peers and then after certain time intervals until consensus is reached.
@@ -430,15 +724,7 @@ impl<N: Network> Consensus<N> {
                         // We would like that 2/3 of our peers have a known state.
                         if head_request.num_known_blocks >= 2 * head_request.num_unknown_blocks {
                             info!("Consensus established, 2/3 of heads known.");
-                            self.established_flag.swap(true, Ordering::Release);
-
-                            self.zkp_proxy
-                                .request_zkp_from_peers(self.sync.peers(), false);
-
-                            let (synced_validity_window, _) = self.check_validity_window();
-                            return Some(ConsensusEvent::Established {
-                                synced_validity_window,
-                            });
+                            return Some(self.establish_consensus());
                         }
                     }
 
@@ -450,6 +736,68 @@ impl<N: Network> Consensus<N> {
         None
     }
 
+    /// Checks the established state using custom criteria instead of the built-in heuristics.
+    fn check_established_criteria(
+        &mut self,
+        criteria: &dyn EstablishedCriteria<N>,
+        finished_head_request: Option<HeadRequestsResult<N>>,
+    ) -> Option<ConsensusEvent> {
+        let peers: Vec<_> = self
+            .sync
+            .peers()
+            .into_iter()
+            .map(|peer_id| (peer_id, self.network.get_peer_info(peer_id)))
+            .collect();
+        let established = self.is_established();
+        let context = EstablishedContext {
+            established,
+            peers: &peers,
+            state_complete: self.sync.state_complete(),
+            accepted_block_announcements: self.sync.accepted_block_announcements(),
+            head_request: finished_head_request.map(|result| HeadRequestOutcome {
+                num_known_blocks: result.num_known_blocks,
+                num_unknown_blocks: result.num_unknown_blocks,
+            }),
+        };
+        let satisfied = criteria.is_satisfied(&context);
+
+        match (established, satisfied) {
+            (true, true) => self.check_validity_window().1,
+            (true, false) => Some(self.lose_consensus()),
+            (false, true) => {
+                info!("Consensus established, custom criteria satisfied.");
+                Some(self.establish_consensus())
+            }
+            (false, false) => {
+                if self.policy_satisfied() {
+                    self.request_heads();
+                }
+                None
+            }
+        }
+    }
+
+    fn establish_consensus(&mut self) -> ConsensusEvent {
+        self.established_flag.swap(true, Ordering::Release);
+
+        self.zkp_proxy
+            .request_zkp_from_peers(self.sync.peers(), false);
+
+        let (synced_validity_window, _) = self.check_validity_window();
+        ConsensusEvent::Established {
+            synced_validity_window,
+        }
+    }
+
+    fn lose_consensus(&mut self) -> ConsensusEvent {
+        warn!("Lost consensus!");
+        self.established_flag.swap(false, Ordering::Release);
+        if let Some(recovery) = self.recovery.as_mut() {
+            recovery.start();
+        }
+        ConsensusEvent::Lost
+    }
+
     /// Requests heads from connected peers.
     fn request_heads(&mut self) {
         // Wait for an ongoing head request to finish.
@@ -460,14 +808,21 @@ impl<N: Network> Consensus<N> {
         // We need at least one synced peer to perform a head request.
         // Specifying `min_peers = 0` in the consensus config allows the first seed node
         // on a network to establish consensus without any other nodes present.
-        if self.num_agents() == 0 && self.min_peers > 0 {
+        if self.num_agents() == 0 && self.policy.min_peers > 0 {
             return;
         }
 
+        // Don't wait for a long timeout if the chain stalled or we don't have consensus.
+        let established = self.is_established();
+        let since_last_block = self.last_block_time.map(|time| time.elapsed());
+        if !established || self.head_request_schedule.is_stalled(since_last_block) {
+            self.head_requests_timeout = self.head_request_schedule.min_interval;
+        }
+
         // This is the case if `head_requests_time` is unset or the timeout is hit.
         let should_start_request = self
             .head_requests_time
-            .map(|time| time.elapsed() >= Self::HEAD_REQUESTS_TIMEOUT)
+            .map(|time| time.elapsed() >= self.head_requests_timeout)
             .unwrap_or(true);
         if !should_start_request {
             return;
@@ -484,16 +839,140 @@ impl<N: Network> Consensus<N> {
             self.blockchain.clone(),
         ));
 
+        self.head_requests_timeout = self.head_request_schedule.next_interval(
+            self.head_requests_timeout,
+            established,
+            since_last_block,
+        );
         self.head_requests_time = Some(Instant::now());
-        self.head_requests_interval = interval(Self::HEAD_REQUESTS_TIMEOUT);
+        self.head_requests_interval = interval(self.head_requests_timeout);
 
         // Wake up the task such that we start working on the head request immediately.
         self.waker.wake();
     }
 
+    /// Tries to regain consensus after it was lost, as configured by the recovery strategy.
+    /// Attempts are made with an exponential backoff until consensus is established again.
+    fn recover(&mut self) {
+        let established = self.is_established();
+        let Some(recovery) = self.recovery.as_mut() else {
+            return;
+        };
+        if established {
+            recovery.stop();
+            return;
+        }
+        if !recovery.is_due() {
+            return;
+        }
+
+        info!(
+            attempt = recovery.attempts(),
+            "Trying to recover lost consensus"
+        );
+        let strategy = recovery.strategy.clone();
+
+        if strategy.reconnect_seeds {
+            for seed in recovery.seeds.clone() {
+                let network = Arc::clone(&self.network);
+                spawn(async move {
+                    if let Err(error) = network.dial_address(seed).await {
+                        debug!(%error, "Failed to dial seed");
+                    }
+                });
+            }
+        }
+
+        // Peers that were already used in this recovery are not asked again, they are either
+        // still syncing or were not able to help us.
+        let peers = recovery.new_peers(self.network.get_peers());
+        if peers.is_empty() {
+            return;
+        }
+
+        if strategy.request_zkp {
+            self.zkp_proxy.request_zkp_from_peers(peers.clone(), false);
+        }
+
+        if strategy.restart_macro_sync {
+            let synced_peers = self.sync.peers();
+            for peer_id in peers {
+                if !synced_peers.contains(&peer_id) {
+                    self.sync.add_peer(peer_id);
+                }
+            }
+        }
+    }
+
     fn resolve_block(&mut self, request: ResolveBlockRequest<N>) {
         self.sync.resolve_block(request)
     }
+
+    /// Fetches a range of blocks from our peers. The request runs in the background, streaming
+    /// the verified blocks back. The request is rejected if too many ranges are being resolved
+    /// already.
+    fn resolve_block_range(&mut self, request: ResolveBlockRangeRequest) {
+        let Ok(permit) = Arc::clone(&self.block_range_permits).try_acquire_owned() else {
+            // The channel is still empty, so this can only fail if the receiver was dropped.
+            request
+                .block_sender
+                .try_send(Err(ResolveBlockRangeError::TooManyRequests))
+                .ok();
+            return;
+        };
+
+        let network = Arc::clone(&self.network);
+        let blockchain = self.blockchain.clone();
+        spawn(async move {
+            resolve_block_range(network, blockchain, request).await;
+            drop(permit);
+        });
+    }
+
+    /// Requests the accounts and their trie proofs from peers providing accounts proofs. The
+    /// request runs in the background, the verified accounts are sent back once it finished.
+    fn resolve_accounts(&mut self, request: ResolveAccountsRequest) {
+        let remote_data_store = RemoteDataStore::new(
+            Arc::clone(&self.network),
+            self.blockchain.clone(),
+            request.min_peers,
+        );
+
+        spawn(async move {
+            let result = remote_data_store.get_accounts(request.addresses).await;
+            request.response_sender.send(result).ok();
+        });
+    }
+
+    /// Starts building a full syncer that replaces the current light syncer once it is ready.
+    /// Consensus stays established during the switch, the state sync happens in the background.
+    #[cfg(feature = "full")]
+    fn upgrade_to_full_sync(&mut self, request: UpgradeToFullSyncRequest) {
+        if self.pending_sync_upgrade.is_some() {
+            request
+                .response_sender
+                .send(Err(SyncerModeError::SwitchInProgress))
+                .ok();
+            return;
+        }
+
+        if let Err(error) = self.sync.check_switch_to(SyncerMode::Full) {
+            request.response_sender.send(Err(error)).ok();
+            return;
+        }
+
+        let future = SyncerProxy::new_full(
+            self.blockchain.clone(),
+            Arc::clone(&self.network),
+            request.bls_cache,
+            self.zkp_proxy.clone(),
+            self.network.subscribe_events(),
+            request.full_sync_threshold,
+        )
+        .boxed();
+
+        self.pending_sync_upgrade = Some((future, request.response_sender));
+    }
 }
 
 impl<N: Network> Future for Consensus<N> {
@@ -505,10 +984,12 @@ impl<N: Network> Future for Consensus<N> {
             match event {
                 LiveSyncPushEvent::AcceptedAnnouncedBlock(_) => {
                     // Reset the head request timer when an announced block was accepted.
+                    self.last_block_time = Some(Instant::now());
                     self.head_requests_time = Some(Instant::now());
-                    self.head_requests_interval = interval(Self::HEAD_REQUESTS_TIMEOUT);
+                    self.head_requests_interval = interval(self.head_requests_timeout);
                 }
                 LiveSyncPushEvent::AcceptedBufferedBlock(_, remaining_in_buffer) => {
+                    self.last_block_time = Some(Instant::now());
                     if !self.is_established() {
                         // Note: this output is parsed by our testing infrastructure (specifically devnet.sh),
                         // so please test that nothing breaks in there if you change this.
@@ -564,10 +1045,34 @@ impl<N: Network> Future for Consensus<N> {
             }
         }
 
+        // Report changes in the composition of our synced peers.
+        if let Some(event) = self.check_peer_set() {
+            self.events.send(event).ok();
+        }
+
+        // Report the progress of the state sync.
+        if let Some(event) = self.check_sync_progress() {
+            self.events.send(event).ok();
+        }
+
         // Check if a ConsensusRequest was received
         while let Poll::Ready(Some(request)) = self.requests.1.poll_recv(cx) {
             match request {
                 ConsensusRequest::ResolveBlock(request) => self.resolve_block(request),
+                ConsensusRequest::ResolveBlockRange(request) => self.resolve_block_range(request),
+                ConsensusRequest::ResolveAccounts(request) => self.resolve_accounts(request),
+                #[cfg(feature = "full")]
+                ConsensusRequest::UpgradeToFullSync(request) => self.upgrade_to_full_sync(request),
+            }
+        }
+
+        // Swap in the new syncer once it is ready.
+        #[cfg(feature = "full")]
+        if let Some((ref mut future, _)) = self.pending_sync_upgrade {
+            if let Poll::Ready(syncer) = future.poll_unpin(cx) {
+                let (_, response_sender) = self.pending_sync_upgrade.take().unwrap();
+                self.sync.replace(syncer);
+                response_sender.send(Ok(())).ok();
             }
         }
 
@@ -577,6 +1082,10 @@ impl<N: Network> Future for Consensus<N> {
         // Advance consensus and catch-up through head requests.
         self.request_heads();
 
+        // Try to recover consensus if it was lost. The head requests interval above makes sure
+        // that we are polled regularly.
+        self.recover();
+
         self.waker.store_waker(cx);
         Poll::Pending
     }
@@ -0,0 +1,154 @@
+use std::{collections::HashSet, hash::Hash, time::Duration};
+
+use instant::Instant;
+
+/// How the consensus tries to recover after it was lost.
+///
+/// Recovery attempts start once consensus is lost and are repeated with an exponential backoff
+/// until consensus is established again.
+#[derive(Clone, Debug)]
+pub struct RecoveryStrategy {
+    /// Delay before the first recovery attempt after consensus was lost.
+    pub initial_backoff: Duration,
+    /// Maximum delay between two recovery attempts.
+    pub max_backoff: Duration,
+    /// Dial the seed nodes again.
+    pub reconnect_seeds: bool,
+    /// Request the ZKP from our peers again.
+    pub request_zkp: bool,
+    /// Put the connected peers that are not live syncing through macro sync again.
+    pub restart_macro_sync: bool,
+}
+
+impl RecoveryStrategy {
+    /// Returns the delay before the given recovery attempt, starting at 0.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_backoff)
+    }
+}
+
+impl Default for RecoveryStrategy {
+    fn default() -> Self {
+        Self {
+            initial_backoff: Duration::from_secs(5),
+            max_backoff: Duration::from_secs(300),
+            reconnect_seeds: true,
+            request_zkp: true,
+            restart_macro_sync: true,
+        }
+    }
+}
+
+/// The state of the recovery of a consensus.
+pub(crate) struct Recovery<A, P> {
+    pub(crate) strategy: RecoveryStrategy,
+    pub(crate) seeds: Vec<A>,
+    /// The peers that were already asked for the ZKP and put through macro sync since consensus
+    /// was lost. Each peer is only used once per recovery.
+    recovered_peers: HashSet<P>,
+    attempt: u32,
+    next_attempt: Option<Instant>,
+}
+
+impl<A, P: Copy + Eq + Hash> Recovery<A, P> {
+    pub(crate) fn new(strategy: RecoveryStrategy, seeds: Vec<A>) -> Self {
+        Self {
+            strategy,
+            seeds,
+            recovered_peers: HashSet::new(),
+            attempt: 0,
+            next_attempt: None,
+        }
+    }
+
+    /// Schedules the first recovery attempt.
+    pub(crate) fn start(&mut self) {
+        self.attempt = 0;
+        self.recovered_peers.clear();
+        self.next_attempt = Some(Instant::now() + self.strategy.backoff(0));
+    }
+
+    /// Returns the given peers that were not used in this recovery yet and marks them as used.
+    pub(crate) fn new_peers(&mut self, peers: Vec<P>) -> Vec<P> {
+        peers
+            .into_iter()
+            .filter(|peer_id| self.recovered_peers.insert(*peer_id))
+            .collect()
+    }
+
+    /// Stops any further recovery attempts.
+    pub(crate) fn stop(&mut self) {
+        self.next_attempt = None;
+    }
+
+    /// Returns whether a recovery attempt is due. If so, the next attempt is scheduled.
+    pub(crate) fn is_due(&mut self) -> bool {
+        match self.next_attempt {
+            Some(next_attempt) if next_attempt <= Instant::now() => {
+                self.attempt = self.attempt.saturating_add(1);
+                self.next_attempt = Some(Instant::now() + self.strategy.backoff(self.attempt));
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the number of recovery attempts made since consensus was lost.
+    pub(crate) fn attempts(&self) -> u32 {
+        self.attempt
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Recovery, RecoveryStrategy};
+
+    #[test]
+    fn it_backs_off_exponentially() {
+        let strategy = RecoveryStrategy {
+            initial_backoff: Duration::from_secs(5),
+            max_backoff: Duration::from_secs(60),
+            ..Default::default()
+        };
+
+        assert_eq!(strategy.backoff(0), Duration::from_secs(5));
+        assert_eq!(strategy.backoff(2), Duration::from_secs(20));
+        assert_eq!(strategy.backoff(4), Duration::from_secs(60));
+        assert_eq!(strategy.backoff(u32::MAX), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn it_only_attempts_recovery_once_started() {
+        let strategy = RecoveryStrategy {
+            initial_backoff: Duration::ZERO,
+            ..Default::default()
+        };
+        let mut recovery = Recovery::<(), u32>::new(strategy, vec![]);
+        assert!(!recovery.is_due());
+
+        recovery.start();
+        assert!(recovery.is_due());
+        assert!(recovery.is_due());
+        assert_eq!(recovery.attempts(), 2);
+
+        recovery.stop();
+        assert!(!recovery.is_due());
+    }
+
+    #[test]
+    fn it_uses_each_peer_once_per_recovery() {
+        let mut recovery = Recovery::<(), u32>::new(RecoveryStrategy::default(), vec![]);
+        recovery.start();
+
+        assert_eq!(recovery.new_peers(vec![1, 2, 2]), vec![1, 2]);
+        assert_eq!(recovery.new_peers(vec![1, 2, 3]), vec![3]);
+
+        // The peers can be used again once consensus is lost again.
+        recovery.start();
+        assert_eq!(recovery.new_peers(vec![1, 3]), vec![1, 3]);
+    }
+}
@@ -4,7 +4,10 @@ use std::{
 };
 
 use futures::StreamExt;
-use nimiq_account::{Staker, StakingContractStore, Tombstone, Validator};
+use nimiq_account::{
+    Account, ContractStore, ScheduledWithdrawal, Staker, StakerRewards, StakingContractStore,
+    Tombstone, Validator, VestingContract, VestingContractBeneficiary,
+};
 use nimiq_block::Block;
 use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_blockchain_proxy::BlockchainProxy;
@@ -15,20 +18,25 @@ use nimiq_network_interface::{
     peer_info::Services,
     request::{OutboundRequestError, RequestError},
 };
-use nimiq_primitives::{key_nibbles::KeyNibbles, policy::Policy};
-use nimiq_serde::Deserialize;
+use nimiq_primitives::{
+    key_nibbles::KeyNibbles, policy::Policy, trie::trie_proof::TrieProofBuilder,
+};
+use nimiq_serde::{Deserialize, DeserializeError};
 
 use crate::messages::RequestTrieProof;
 
-/// The Remote Data Store is a component to remotely request data from the staking
-/// contract such as:
+/// The Remote Data Store is a component to remotely request data from the accounts trie,
+/// such as accounts or the entries of the staking contract:
 /// - Validators
-/// - Stakers
+/// - Stakers and their reward bookkeeping
 /// - Tombstones
 ///
-/// It also serves as an utility function to extract any type from the Accounts Trie
-/// remotely.
-pub(crate) struct RemoteDataStore<N: Network> {
+/// as well as the entries of the generic data store of other contracts (see [`ContractStore`]).
+///
+/// All data is requested together with a trie proof, which is verified against the state root
+/// of the block the proof was created for. This allows light clients to query any entry of the
+/// accounts trie.
+pub struct RemoteDataStore<N: Network> {
     /// An Arc of the network to make requests.
     pub(crate) network: Arc<N>,
     /// A blockchain proxy for verifying proofs of data received.
@@ -37,19 +45,101 @@ pub(crate) struct RemoteDataStore<N: Network> {
     pub(crate) min_peers: usize,
 }
 
-/// Internal Remote operations the Remote Data Store can perform over addresses
-enum RemoteDataStoreOps {
-    /// Gets a set of validators by their addresses
-    Validator(Vec<Address>),
-    /// Gets a set of stakers by their addresses
-    Staker(Vec<Address>),
-    /// Gets a set of tombstones by their addresses
-    Tombstone(Vec<Address>),
+/// An entry of the accounts trie that can be requested from remote peers.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum RemoteDataKey {
+    /// The account with the given address.
+    Account(Address),
+    /// The validator with the given address.
+    Validator(Address),
+    /// The staker with the given address.
+    Staker(Address),
+    /// The reward bookkeeping of the staker with the given address.
+    StakerRewards(Address),
+    /// The scheduled withdrawals of the staker with the given address.
+    StakerWithdrawals(Address),
+    /// The tombstone of the deleted validator with the given address.
+    Tombstone(Address),
+    /// The beneficiaries of the vesting contract with the given address.
+    VestingBeneficiaries(Address),
+}
+
+impl RemoteDataKey {
+    /// Returns the key of this entry in the accounts trie.
+    pub fn to_key_nibbles(&self) -> KeyNibbles {
+        let staking_contract_key = KeyNibbles::from(&Policy::STAKING_CONTRACT_ADDRESS);
+        match self {
+            RemoteDataKey::Account(address) => KeyNibbles::from(address),
+            RemoteDataKey::Validator(address) => {
+                &staking_contract_key + &StakingContractStore::validator_key(address)
+            }
+            RemoteDataKey::Staker(address) => {
+                &staking_contract_key + &StakingContractStore::staker_key(address)
+            }
+            RemoteDataKey::StakerRewards(address) => {
+                &staking_contract_key + &StakingContractStore::staker_rewards_key(address)
+            }
+            RemoteDataKey::StakerWithdrawals(address) => {
+                &staking_contract_key + &StakingContractStore::staker_withdrawals_key(address)
+            }
+            RemoteDataKey::Tombstone(address) => {
+                &staking_contract_key + &StakingContractStore::tombstone_key(address)
+            }
+            RemoteDataKey::VestingBeneficiaries(address) => {
+                &KeyNibbles::from(address) + &VestingContract::beneficiaries_key()
+            }
+        }
+    }
+
+    /// Deserializes a value stored under this key in the accounts trie.
+    fn deserialize_value(&self, value: &[u8]) -> Result<RemoteData, DeserializeError> {
+        Ok(match self {
+            RemoteDataKey::Account(_) => RemoteData::Account(Account::deserialize_from_vec(value)?),
+            RemoteDataKey::Validator(_) => {
+                RemoteData::Validator(Validator::deserialize_from_vec(value)?)
+            }
+            RemoteDataKey::Staker(_) => RemoteData::Staker(Staker::deserialize_from_vec(value)?),
+            RemoteDataKey::StakerRewards(_) => {
+                RemoteData::StakerRewards(StakerRewards::deserialize_from_vec(value)?)
+            }
+            RemoteDataKey::StakerWithdrawals(_) => RemoteData::StakerWithdrawals(
+                Vec::<ScheduledWithdrawal>::deserialize_from_vec(value)?,
+            ),
+            RemoteDataKey::Tombstone(_) => {
+                RemoteData::Tombstone(Tombstone::deserialize_from_vec(value)?)
+            }
+            RemoteDataKey::VestingBeneficiaries(_) => RemoteData::VestingBeneficiaries(
+                Vec::<VestingContractBeneficiary>::deserialize_from_vec(value)?,
+            ),
+        })
+    }
+}
+
+/// A proven entry of the accounts trie obtained from remote peers.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RemoteData {
+    Account(Account),
+    Validator(Validator),
+    Staker(Staker),
+    StakerRewards(StakerRewards),
+    StakerWithdrawals(Vec<ScheduledWithdrawal>),
+    Tombstone(Tombstone),
+    VestingBeneficiaries(Vec<VestingContractBeneficiary>),
 }
 
 impl<N: Network> RemoteDataStore<N> {
     const MAX_BLOCK_DRIFT: usize = 10;
 
+    /// Creates a remote data store requesting data from at least `min_peers` peers that provide
+    /// accounts proofs.
+    pub fn new(network: Arc<N>, blockchain: BlockchainProxy, min_peers: usize) -> Self {
+        RemoteDataStore {
+            network,
+            blockchain,
+            min_peers,
+        }
+    }
+
     /// Waits for the block with given `block_hash` to be encountered and returns it. Will only wait for
     /// [RemoteDataStore::MAX_BLOCK_DRIFT] events that add blocks.
     ///
@@ -111,14 +201,59 @@ impl<N: Network> RemoteDataStore<N> {
         None
     }
 
-    /// Gets a proof for a deserializable item in a remote accounts trie and returns
-    /// the item if a valid proof was obtained or `None` if not.
-    pub(crate) async fn get_trie<T: Deserialize>(
+    /// Gets a proof for deserializable items in a remote accounts trie and returns the items
+    /// if a valid proof was obtained. Items that are not present in the trie are `None`.
+    pub async fn get_trie<T: Deserialize>(
         network: Arc<N>,
         blockchain: BlockchainProxy,
         keys: &[KeyNibbles],
         min_peers: usize,
     ) -> Result<BTreeMap<KeyNibbles, Option<T>>, RequestError> {
+        Self::get_trie_values(network, blockchain, keys, min_peers)
+            .await?
+            .into_iter()
+            .map(|(key, value)| {
+                let item = value
+                    .map(|value| T::deserialize_from_vec(&value))
+                    .transpose()
+                    .map_err(|error| Self::deserialize_error(&key, error))?;
+                Ok((key, item))
+            })
+            .collect()
+    }
+
+    /// Gets a proof for the given keys in a remote accounts trie and returns the raw values
+    /// if a valid proof was obtained. Values that are not present in the trie are `None`.
+    ///
+    /// The keys are proven by as few proofs as possible: duplicate keys are only requested once
+    /// and the nodes shared by the keys of a proof are only downloaded once.
+    pub async fn get_trie_values(
+        network: Arc<N>,
+        blockchain: BlockchainProxy,
+        keys: &[KeyNibbles],
+        min_peers: usize,
+    ) -> Result<BTreeMap<KeyNibbles, Option<Vec<u8>>>, RequestError> {
+        let builders = TrieProofBuilder::new()
+            .with_keys(keys.iter().cloned())
+            .split(RequestTrieProof::MAX_KEYS);
+
+        let mut values = BTreeMap::new();
+        for builder in builders {
+            values.extend(
+                Self::request_proven_values(&network, &blockchain, &builder, min_peers).await?,
+            );
+        }
+
+        Ok(values)
+    }
+
+    /// Requests a single proof for the keys of the given builder and returns the proven values.
+    async fn request_proven_values(
+        network: &Arc<N>,
+        blockchain: &BlockchainProxy,
+        builder: &TrieProofBuilder,
+        min_peers: usize,
+    ) -> Result<BTreeMap<KeyNibbles, Option<Vec<u8>>>, RequestError> {
         // First we tell the network to provide us with a vector that contains all the connected peers that support such services
         // Note: If the network could not provide enough peers that satisfies our requirement, then an error would be returned
         let peers = network
@@ -137,11 +272,14 @@ impl<N: Network> RemoteDataStore<N> {
                 peer_id = %peer_id,
                 "Performing accounts by address request to peer",
             );
-            log::debug!("Getting accounts for {:?}", keys.iter());
+            log::debug!(
+                "Getting accounts for {:?}",
+                builder.keys().collect::<Vec<_>>()
+            );
             let response = network
                 .request::<RequestTrieProof>(
                     RequestTrieProof {
-                        keys: keys.to_vec(),
+                        keys: builder.keys().cloned().collect(),
                     },
                     peer_id,
                 )
@@ -151,23 +289,15 @@ impl<N: Network> RemoteDataStore<N> {
                 Ok(Ok(response)) => {
                     // Get the block referenced by the proof, or discard the proof as it cannot be verified
                     let Some(block) =
-                        Self::get_or_await_block(&blockchain, &response.block_hash).await
+                        Self::get_or_await_block(blockchain, &response.block_hash).await
                     else {
                         // If the block for the proof couldn't be found, use another peer
                         continue;
                     };
 
                     // Now we need to verify the proof
-                    if let Ok(values) = response
-                        .proof
-                        .verify_values(block.state_root(), &keys.iter().collect::<Vec<_>>())
-                    {
-                        return Ok(values
-                            .into_iter()
-                            .map(|(key, value)| {
-                                (key, value.map(|v| T::deserialize_from_vec(&v).unwrap()))
-                            })
-                            .collect());
+                    if let Ok(values) = builder.verify(response.proof, block.state_root()) {
+                        return Ok(values);
                     }
 
                     // If the proof does not verify, we disconnect from the peer
@@ -192,88 +322,161 @@ impl<N: Network> RemoteDataStore<N> {
         ))
     }
 
+    fn deserialize_error(key: &KeyNibbles, error: DeserializeError) -> RequestError {
+        log::error!(%key, %error, "Proven trie value could not be deserialized");
+        RequestError::OutboundRequest(OutboundRequestError::Other(format!(
+            "Failed to deserialize value at {key}: {error}"
+        )))
+    }
+
+    /// Gets a set of entries of the accounts trie. The returned type is a BTreeMap of keys
+    /// to an optional entry. If an entry was not found, then `None` is returned in its
+    /// corresponding entry.
+    pub async fn get(
+        &self,
+        keys: Vec<RemoteDataKey>,
+    ) -> Result<BTreeMap<RemoteDataKey, Option<RemoteData>>, RequestError> {
+        let mut keys_to_data_keys: HashMap<KeyNibbles, RemoteDataKey> = keys
+            .into_iter()
+            .map(|key| (key.to_key_nibbles(), key))
+            .collect();
+
+        let trie_keys: Vec<KeyNibbles> = keys_to_data_keys.keys().cloned().collect();
+
+        let values = Self::get_trie_values(
+            Arc::clone(&self.network),
+            self.blockchain.clone(),
+            &trie_keys,
+            self.min_peers,
+        )
+        .await?;
+
+        values
+            .into_iter()
+            .map(|(trie_key, value)| {
+                let key = keys_to_data_keys
+                    .remove(&trie_key)
+                    .expect("Key should have been requested");
+                let data = value
+                    .map(|value| key.deserialize_value(&value))
+                    .transpose()
+                    .map_err(|error| Self::deserialize_error(&trie_key, error))?;
+                Ok((key, data))
+            })
+            .collect()
+    }
+
+    /// Gets a set of accounts given their addresses. The returned type is a
+    /// BTreeMap of addresses to an optional `Account`. If an account was not
+    /// found, then `None` is returned in its corresponding entry.
+    pub async fn get_accounts(
+        &self,
+        addresses: Vec<Address>,
+    ) -> Result<BTreeMap<Address, Option<Account>>, RequestError> {
+        self.exec(addresses, RemoteDataKey::Account, |data| match data {
+            RemoteData::Account(account) => Some(account),
+            _ => None,
+        })
+        .await
+    }
+
     /// Gets a set of validators given their addresses. The returned type is a
     /// BTreeMap of addresses to an optional `Validator`. If a validator was not
     /// found, then `None` is returned in its corresponding entry.
-    pub(crate) async fn get_validators(
+    pub async fn get_validators(
         &self,
         addresses: Vec<Address>,
     ) -> Result<BTreeMap<Address, Option<Validator>>, RequestError> {
-        self.exec(RemoteDataStoreOps::Validator(addresses)).await
+        self.exec(addresses, RemoteDataKey::Validator, |data| match data {
+            RemoteData::Validator(validator) => Some(validator),
+            _ => None,
+        })
+        .await
     }
 
     /// Gets a set of stakers given their addresses. The returned type is a
     /// BTreeMap of addresses to an optional `Staker`. If a staker was not
     /// found, then `None` is returned in its corresponding entry.
-    pub(crate) async fn get_stakers(
+    pub async fn get_stakers(
         &self,
         addresses: Vec<Address>,
     ) -> Result<BTreeMap<Address, Option<Staker>>, RequestError> {
-        self.exec(RemoteDataStoreOps::Staker(addresses)).await
+        self.exec(addresses, RemoteDataKey::Staker, |data| match data {
+            RemoteData::Staker(staker) => Some(staker),
+            _ => None,
+        })
+        .await
     }
 
     /// Gets a set of tombstones given their addresses. The returned type is a
     /// BTreeMap of addresses to an optional `Tombstone`. If a tombstone was not
     /// found, then `None` is returned in its corresponding entry.
-    #[allow(dead_code)]
-    pub(crate) async fn get_tombstones(
+    pub async fn get_tombstones(
         &self,
         addresses: Vec<Address>,
     ) -> Result<BTreeMap<Address, Option<Tombstone>>, RequestError> {
-        self.exec(RemoteDataStoreOps::Tombstone(addresses)).await
+        self.exec(addresses, RemoteDataKey::Tombstone, |data| match data {
+            RemoteData::Tombstone(tombstone) => Some(tombstone),
+            _ => None,
+        })
+        .await
     }
 
-    async fn exec<T: Deserialize + Clone>(
+    /// Gets a set of entries of the generic data store of a contract given their namespace and
+    /// keys. The returned type is a BTreeMap of keys to an optional value. If an entry was not
+    /// found, then `None` is returned in its corresponding entry.
+    pub async fn get_contract_entries<T: Deserialize>(
         &self,
-        op: RemoteDataStoreOps,
-    ) -> Result<BTreeMap<Address, Option<T>>, RequestError> {
-        let staking_contract_key = KeyNibbles::from(&Policy::STAKING_CONTRACT_ADDRESS);
-        let mut keys_to_address: HashMap<KeyNibbles, Address> = match op {
-            RemoteDataStoreOps::Validator(addresses) => {
-                HashMap::from_iter(addresses.iter().map(|address| {
-                    (
-                        &staking_contract_key + &StakingContractStore::validator_key(address),
-                        address.clone(),
-                    )
-                }))
-            }
-            RemoteDataStoreOps::Staker(addresses) => {
-                HashMap::from_iter(addresses.iter().map(|address| {
-                    (
-                        &staking_contract_key + &StakingContractStore::staker_key(address),
-                        address.clone(),
-                    )
-                }))
-            }
-            RemoteDataStoreOps::Tombstone(addresses) => {
-                HashMap::from_iter(addresses.iter().map(|address| {
-                    (
-                        &staking_contract_key + &StakingContractStore::tombstone_key(address),
-                        address.clone(),
-                    )
-                }))
-            }
-        };
-
-        let keys: Vec<KeyNibbles> = keys_to_address.keys().cloned().collect();
+        contract: &Address,
+        namespace: u8,
+        keys: Vec<Vec<u8>>,
+    ) -> Result<BTreeMap<Vec<u8>, Option<T>>, RequestError> {
+        let mut trie_keys_to_keys = HashMap::with_capacity(keys.len());
+        for key in keys {
+            let trie_key = ContractStore::trie_key(contract, namespace, &key).map_err(|error| {
+                RequestError::OutboundRequest(OutboundRequestError::Other(error.to_string()))
+            })?;
+            trie_keys_to_keys.insert(trie_key, key);
+        }
+        let trie_keys: Vec<KeyNibbles> = trie_keys_to_keys.keys().cloned().collect();
 
-        let items = Self::get_trie::<T>(
+        Ok(Self::get_trie(
             Arc::clone(&self.network),
             self.blockchain.clone(),
-            &keys,
+            &trie_keys,
             self.min_peers,
         )
-        .await?;
+        .await?
+        .into_iter()
+        .map(|(trie_key, value)| {
+            let key = trie_keys_to_keys
+                .remove(&trie_key)
+                .expect("Key should have been requested");
+            (key, value)
+        })
+        .collect())
+    }
 
-        Ok(items
-            .iter()
-            .map(|(key, item)| {
-                (
-                    keys_to_address
-                        .remove(key)
-                        .expect("Key should have an address"),
-                    item.clone(),
-                )
+    async fn exec<T>(
+        &self,
+        addresses: Vec<Address>,
+        to_key: fn(Address) -> RemoteDataKey,
+        from_data: fn(RemoteData) -> Option<T>,
+    ) -> Result<BTreeMap<Address, Option<T>>, RequestError> {
+        let keys = addresses.into_iter().map(to_key).collect();
+
+        Ok(self
+            .get(keys)
+            .await?
+            .into_iter()
+            .map(|(key, data)| {
+                let address = match key {
+                    RemoteDataKey::Account(address)
+                    | RemoteDataKey::Validator(address)
+                    | RemoteDataKey::Staker(address)
+                    | RemoteDataKey::Tombstone(address) => address,
+                };
+                (address, data.and_then(from_data))
             })
             .collect())
     }
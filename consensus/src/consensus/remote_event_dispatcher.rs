@@ -16,14 +16,15 @@ use nimiq_network_interface::{
     request::{request_handler, Handle},
 };
 use nimiq_primitives::account::AccountType;
-use nimiq_transaction::account::staking_contract::IncomingStakingTransactionData;
+use nimiq_transaction::{account::staking_contract::IncomingStakingTransactionData, Transaction};
 use nimiq_utils::spawn;
 use parking_lot::RwLock;
 
 use crate::{
     messages::{
-        AddressNotification, AddressSubscriptionOperation, AddressSubscriptionTopic,
-        NotificationEvent, RequestSubscribeToAddress,
+        AddressNotification, AddressSubscriptionFilter, AddressSubscriptionOperation,
+        AddressSubscriptionTopic, NotificationEvent, RequestSubscribeToAddress,
+        RequestSubscribeToAddressWithFilter,
     },
     SubscribeToAddressesError,
     SubscribeToAddressesError::*,
@@ -34,6 +35,32 @@ pub const MAX_SUBSCRIBED_PEERS: usize = 50;
 /// The max number of addresses that can be subscribed, per peer.
 pub const MAX_SUBSCRIBED_PEERS_ADDRESSES: usize = 250;
 
+/// Subscribes the peer to the given addresses, replacing the filter of any of them that was
+/// already subscribed to.
+fn subscribe<N: Network>(
+    peer_id: N::PeerId,
+    state: &RwLock<RemoteEventDispatcherState<N>>,
+    addresses: &[Address],
+    filter: &AddressSubscriptionFilter,
+) -> Result<(), SubscribeToAddressesError> {
+    if let Some(peer_addresses) = state.read().subscribed_peers.get(&peer_id) {
+        // We need to check if this peer already has too many addresses subscribed to us
+        if peer_addresses.len() > MAX_SUBSCRIBED_PEERS_ADDRESSES {
+            return Err(TooManyAddresses);
+        }
+    } else {
+        // If this is a new peer, we need to check if we can attend it
+        if state.read().number_of_peers() > MAX_SUBSCRIBED_PEERS {
+            return Err(TooManyPeers);
+        }
+    }
+
+    state
+        .write()
+        .add_addresses(&peer_id, addresses.to_vec(), filter);
+    Ok(())
+}
+
 impl<N: Network> Handle<N, Arc<RwLock<RemoteEventDispatcherState<N>>>>
     for RequestSubscribeToAddress
 {
@@ -43,23 +70,12 @@ impl<N: Network> Handle<N, Arc<RwLock<RemoteEventDispatcherState<N>>>>
         state: &Arc<RwLock<RemoteEventDispatcherState<N>>>,
     ) -> Result<(), SubscribeToAddressesError> {
         match self.operation {
-            AddressSubscriptionOperation::Subscribe => {
-                if let Some(peer_addresses) = state.read().subscribed_peers.get(&peer_id) {
-                    // We need to check if this peer already has too many addresses subscribed to us
-                    if peer_addresses.len() > MAX_SUBSCRIBED_PEERS_ADDRESSES {
-                        return Err(TooManyAddresses);
-                    }
-                } else {
-                    // If this is a new peer, we need to check if we can attend it
-                    if state.read().number_of_peers() > MAX_SUBSCRIBED_PEERS {
-                        return Err(TooManyPeers);
-                    }
-                }
-
-                state
-                    .write()
-                    .add_addresses(&peer_id, self.addresses.clone());
-            }
+            AddressSubscriptionOperation::Subscribe => subscribe(
+                peer_id,
+                state,
+                &self.addresses,
+                &AddressSubscriptionFilter::default(),
+            ),
 
             AddressSubscriptionOperation::Unsubscribe => {
                 // If we don't know this peer, we don't do anything
@@ -70,17 +86,30 @@ impl<N: Network> Handle<N, Arc<RwLock<RemoteEventDispatcherState<N>>>>
                 state
                     .write()
                     .remove_addresses(&peer_id, self.addresses.clone());
+                Ok(())
             }
         }
-        Ok(())
+    }
+}
+
+impl<N: Network> Handle<N, Arc<RwLock<RemoteEventDispatcherState<N>>>>
+    for RequestSubscribeToAddressWithFilter
+{
+    fn handle(
+        &self,
+        peer_id: N::PeerId,
+        state: &Arc<RwLock<RemoteEventDispatcherState<N>>>,
+    ) -> Result<(), SubscribeToAddressesError> {
+        subscribe(peer_id, state, &self.addresses, &self.filter)
     }
 }
 
 /// The state that is maintained by the remote event dispatcher:
 /// essentially the addresses and peers that are subscribed to us.
 pub struct RemoteEventDispatcherState<N: Network> {
-    /// HashMap containing a mapping from peers to their interesting addresses
-    subscribed_peers: HashMap<N::PeerId, HashSet<Address>>,
+    /// HashMap containing a mapping from peers to their interesting addresses,
+    /// together with the filter the peer set for each of them
+    subscribed_peers: HashMap<N::PeerId, HashMap<Address, AddressSubscriptionFilter>>,
 
     /// Maintains the current list of interesting addresses and the peers that are interested in those addresses
     subscriptions: HashMap<Address, HashSet<N::PeerId>>,
@@ -105,16 +134,21 @@ impl<N: Network> RemoteEventDispatcherState<N> {
         self.subscribed_peers.len()
     }
 
-    /// Adds a new address for an specific peer.
-    pub fn add_addresses(&mut self, peer_id: &N::PeerId, addresses: Vec<Address>) {
-        // If we already knew this peer, then we just update its interesting addresses
-        if let Some(interesting_addresses) = self.subscribed_peers.get_mut(peer_id) {
-            interesting_addresses.extend(addresses.iter().cloned())
-        } else {
-            // Otherwise, we insert a new entry for this peer
-            self.subscribed_peers
-                .insert(*peer_id, HashSet::from_iter(addresses.iter().cloned()));
-        }
+    /// Adds new addresses for an specific peer. The filter replaces the one previously set for
+    /// any of these addresses.
+    pub fn add_addresses(
+        &mut self,
+        peer_id: &N::PeerId,
+        addresses: Vec<Address>,
+        filter: &AddressSubscriptionFilter,
+    ) {
+        // If we already knew this peer, then we just update its interesting addresses,
+        // otherwise we insert a new entry for this peer
+        self.subscribed_peers.entry(*peer_id).or_default().extend(
+            addresses
+                .iter()
+                .map(|address| (address.clone(), filter.clone())),
+        );
 
         // Now we update our address mapping
         for address in addresses {
@@ -132,7 +166,7 @@ impl<N: Network> RemoteEventDispatcherState<N> {
     pub fn remove_peer(&mut self, peer_id: &N::PeerId) {
         if let Some(peer_addresses) = self.subscribed_peers.get(peer_id) {
             // Obtain the addresses that are interested to this peer and remove the peer from those addresses.
-            peer_addresses.iter().for_each(|address| {
+            peer_addresses.keys().for_each(|address| {
                 if let Some(peers) = self.subscriptions.get_mut(address) {
                     peers.remove(peer_id);
                 }
@@ -159,9 +193,26 @@ impl<N: Network> RemoteEventDispatcherState<N> {
         }
     }
 
-    /// Obtains the peers that are currently subscribed to us.
-    pub fn get_peers(&self, address: &Address) -> Option<HashSet<N::PeerId>> {
-        self.subscriptions.get(address).cloned()
+    /// Obtains the peers that are currently subscribed to us for the given address and whose
+    /// filter lets the given transaction pass.
+    pub fn get_peers(
+        &self,
+        address: &Address,
+        transaction: &Transaction,
+    ) -> Option<HashSet<N::PeerId>> {
+        let peers = self.subscriptions.get(address)?;
+        Some(
+            peers
+                .iter()
+                .filter(|peer_id| {
+                    self.subscribed_peers
+                        .get(peer_id)
+                        .and_then(|peer_addresses| peer_addresses.get(address))
+                        .is_some_and(|filter| filter.matches(transaction))
+                })
+                .cloned()
+                .collect(),
+        )
     }
 }
 
@@ -197,7 +248,9 @@ impl<N: Network> RemoteEventDispatcher<N> {
 
         // Spawn the network receiver that will take care of processing address subscription requests
         let stream = network.receive_requests::<RequestSubscribeToAddress>();
+        spawn(request_handler(&network, stream, &Arc::clone(&state)));
 
+        let stream = network.receive_requests::<RequestSubscribeToAddressWithFilter>();
         spawn(request_handler(&network, stream, &Arc::clone(&state)));
 
         let blockchain_event_rx = blockchain.read().notifier_as_stream();
@@ -217,11 +270,12 @@ impl<N: Network> RemoteEventDispatcher<N> {
     fn add_notification_receipts(
         &self,
         address: &Address,
-        txn_hash: Blake2bHash,
+        txn: &Transaction,
         block_number: u32,
         peer_receipts: &mut HashMap<N::PeerId, Vec<(Blake2bHash, u32)>>,
     ) {
-        if let Some(peers) = self.state.read().get_peers(address) {
+        if let Some(peers) = self.state.read().get_peers(address, txn) {
+            let txn_hash: Blake2bHash = txn.hash();
             for peer in peers {
                 if let Some(receipts) = peer_receipts.get_mut(&peer) {
                     receipts.push((txn_hash.clone(), block_number))
@@ -279,7 +333,7 @@ impl<N: Network> Future for RemoteEventDispatcher<N> {
                         // Process transaction sender
                         self.add_notification_receipts(
                             &txn.sender,
-                            txn.hash(),
+                            txn,
                             block.block_number(),
                             &mut peer_receipts,
                         );
@@ -287,7 +341,7 @@ impl<N: Network> Future for RemoteEventDispatcher<N> {
                         // Process transaction recipients
                         self.add_notification_receipts(
                             &txn.recipient,
-                            txn.hash(),
+                            txn,
                             block.block_number(),
                             &mut peer_receipts,
                         );
@@ -302,7 +356,7 @@ impl<N: Network> Future for RemoteEventDispatcher<N> {
                             {
                                 self.add_notification_receipts(
                                     &staker_address,
-                                    txn.hash(),
+                                    txn,
                                     block.block_number(),
                                     &mut peer_receipts,
                                 );
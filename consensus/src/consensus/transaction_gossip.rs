@@ -0,0 +1,147 @@
+use std::{mem, pin::pin, sync::Arc, time::Duration};
+
+use futures::{future, FutureExt};
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_network_interface::network::{Network, MIN_SUPPORTED_MSG_SIZE};
+use nimiq_serde::Serialize;
+use nimiq_time::sleep;
+use nimiq_transaction::{
+    gossip::{
+        TransactionAnnouncement, TransactionAnnouncementTopic, TransactionBatch,
+        TransactionBatchTopic, TransactionGossipCache,
+    },
+    Transaction,
+};
+use tokio::sync::mpsc;
+
+/// How regular transactions sent by this node are gossiped.
+///
+/// If batching is enabled, small transactions are collected for up to `batch_delay` and published
+/// together, while transactions larger than `announce_threshold` are only announced by their hash
+/// and pulled by the peers that don't know them yet. Nodes that don't subscribe to the batch and
+/// announcement topics won't receive these transactions, which is why batching is disabled by
+/// default.
+#[derive(Clone, Debug)]
+pub struct TransactionGossipConfig {
+    /// Whether transactions are batched and announced instead of being published individually.
+    pub batching: bool,
+    /// Maximum time a transaction waits for other transactions to be published with.
+    pub batch_delay: Duration,
+    /// Maximum serialized size of a batch in bytes.
+    pub max_batch_size: usize,
+    /// Serialized size in bytes above which transactions are announced instead of being batched.
+    pub announce_threshold: usize,
+}
+
+impl Default for TransactionGossipConfig {
+    fn default() -> Self {
+        Self {
+            batching: false,
+            batch_delay: Duration::from_millis(50),
+            max_batch_size: 64 * 1024,
+            announce_threshold: 1024,
+        }
+    }
+}
+
+/// Collects the transactions sent by this node into batches and announcements.
+pub struct TransactionBatcher<N: Network> {
+    network: Arc<N>,
+    config: TransactionGossipConfig,
+    cache: TransactionGossipCache,
+    transactions: Vec<Transaction>,
+    /// The serialized size of the transactions in the current batch.
+    size: usize,
+    announcements: Vec<Blake2bHash>,
+}
+
+impl<N: Network> TransactionBatcher<N> {
+    /// Number of transactions that can be queued before sending a transaction waits for the
+    /// batcher.
+    pub const QUEUE_SIZE: usize = 1024;
+
+    pub fn new(
+        network: Arc<N>,
+        mut config: TransactionGossipConfig,
+        cache: TransactionGossipCache,
+    ) -> Self {
+        // Leave some room for the message framing.
+        config.max_batch_size = config.max_batch_size.min(MIN_SUPPORTED_MSG_SIZE / 2);
+        Self {
+            network,
+            config,
+            cache,
+            transactions: vec![],
+            size: 0,
+            announcements: vec![],
+        }
+    }
+
+    /// Publishes the transactions received from `rx` until all senders are dropped.
+    pub async fn run(mut self, mut rx: mpsc::Receiver<Transaction>) {
+        while let Some(transaction) = rx.recv().await {
+            self.push(transaction).await;
+
+            // Wait for more transactions to be batched with the first one.
+            let mut deadline = pin!(sleep(self.config.batch_delay).fuse());
+            loop {
+                let transaction = futures::select! {
+                    _ = deadline => break,
+                    transaction = rx.recv().fuse() => transaction,
+                };
+                match transaction {
+                    Some(transaction) => self.push(transaction).await,
+                    None => break,
+                }
+            }
+
+            self.flush().await;
+        }
+    }
+
+    async fn push(&mut self, transaction: Transaction) {
+        let size = transaction.serialized_size();
+        if size > self.config.announce_threshold {
+            if self.announcements.len() >= TransactionAnnouncement::MAX_HASHES {
+                self.flush().await;
+            }
+            self.announcements.push(transaction.hash());
+            self.cache.insert(transaction);
+            return;
+        }
+
+        if self.size + size > self.config.max_batch_size {
+            self.flush().await;
+        }
+        self.size += size;
+        self.transactions.push(transaction);
+    }
+
+    async fn flush(&mut self) {
+        let transactions = mem::take(&mut self.transactions);
+        let hashes = mem::take(&mut self.announcements);
+        self.size = 0;
+
+        let batch = async {
+            if transactions.is_empty() {
+                return Ok(());
+            }
+            self.network
+                .publish::<TransactionBatchTopic>(TransactionBatch { transactions })
+                .await
+        };
+        let announcement = async {
+            if hashes.is_empty() {
+                return Ok(());
+            }
+            self.network
+                .publish::<TransactionAnnouncementTopic>(TransactionAnnouncement { hashes })
+                .await
+        };
+
+        let (batch, announcement) = future::join(batch, announcement).await;
+        if let Err(error) = batch.and(announcement) {
+            warn!(%error, "Failed to publish transactions");
+        }
+    }
+}
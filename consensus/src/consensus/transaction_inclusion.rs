@@ -0,0 +1,391 @@
+use std::collections::VecDeque;
+
+use futures::{
+    stream::{self, BoxStream},
+    StreamExt,
+};
+use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainEvent};
+use nimiq_blockchain_proxy::BlockchainProxy;
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_keys::Address;
+use nimiq_network_interface::network::Network;
+use nimiq_primitives::policy::Policy;
+use nimiq_transaction::Transaction;
+
+use super::consensus_proxy::ConsensusProxy;
+use crate::messages::{AddressNotification, AddressSubscriptionFilter};
+
+/// Progress of a transaction towards its finalization in the chain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TransactionInclusion {
+    /// The transaction was included in a block that is not final yet.
+    Included {
+        block_number: u32,
+        block_hash: Blake2bHash,
+        /// The validator that produced the block, if known.
+        producer: Option<Address>,
+    },
+    /// The block including the transaction was reverted by a rebranch.
+    Reverted { block_number: u32 },
+    /// The block including the transaction was finalized by a macro block.
+    Finalized { block_number: u32 },
+    /// The validity window of the transaction ended without it being finalized.
+    Expired,
+}
+
+/// State of a submitted transaction, as reported by
+/// [`ConsensusProxy::send_transaction_with_updates`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TransactionState {
+    /// The transaction was sent to the network but is not included in the chain. This is also
+    /// reported again if the block including it was reverted.
+    Pending,
+    /// The transaction was included in a block that is not final yet.
+    Included {
+        block_number: u32,
+        block_hash: Blake2bHash,
+    },
+    /// The block including the transaction was finalized.
+    Confirmed { block_number: u32 },
+    /// The validity window of the transaction ended without it being confirmed.
+    Expired,
+}
+
+impl From<TransactionInclusion> for TransactionState {
+    fn from(inclusion: TransactionInclusion) -> Self {
+        match inclusion {
+            TransactionInclusion::Included {
+                block_number,
+                block_hash,
+                ..
+            } => TransactionState::Included {
+                block_number,
+                block_hash,
+            },
+            TransactionInclusion::Reverted { .. } => TransactionState::Pending,
+            TransactionInclusion::Finalized { block_number } => {
+                TransactionState::Confirmed { block_number }
+            }
+            TransactionInclusion::Expired => TransactionState::Expired,
+        }
+    }
+}
+
+enum TrackerEvent {
+    Blockchain(BlockchainEvent),
+    Notification(AddressNotification),
+}
+
+/// Follows a transaction through the chain.
+struct InclusionTracker<N: Network> {
+    consensus: ConsensusProxy<N>,
+    tx_hash: Blake2bHash,
+    expires_after: u32,
+    min_peers: usize,
+    events: BoxStream<'static, TrackerEvent>,
+    /// The number and hash of the block the transaction is currently included in.
+    included: Option<(u32, Blake2bHash)>,
+    /// The number of the block the transaction was proven to be included in, if we don't know
+    /// that block yet.
+    proven_at: Option<u32>,
+    /// Light blockchains don't store block bodies, the inclusion is then proven by peers providing
+    /// the transaction history.
+    light: bool,
+    pending: VecDeque<TransactionInclusion>,
+    done: bool,
+}
+
+impl<N: Network> InclusionTracker<N> {
+    /// Returns the next inclusion event, or `None` once the transaction is finalized or expired.
+    async fn next(&mut self) -> Option<TransactionInclusion> {
+        while self.pending.is_empty() && !self.done {
+            match self.events.next().await? {
+                TrackerEvent::Blockchain(
+                    event @ (BlockchainEvent::Extended(_)
+                    | BlockchainEvent::HistoryAdopted(_)
+                    | BlockchainEvent::Rebranched(..)),
+                ) => self.on_head_changed(event).await,
+                TrackerEvent::Blockchain(
+                    BlockchainEvent::Finalized(_) | BlockchainEvent::EpochFinalized(_),
+                ) => self.on_finalized().await,
+                TrackerEvent::Blockchain(BlockchainEvent::Stored(_)) => {}
+                TrackerEvent::Notification(notification) => {
+                    self.on_notification(notification).await
+                }
+            }
+        }
+        self.pending.pop_front()
+    }
+
+    async fn on_head_changed(&mut self, event: BlockchainEvent) {
+        if let BlockchainEvent::Rebranched(_, ref reverted_blocks) = event {
+            if let Some((block_number, block_hash)) = self.included.clone() {
+                if reverted_blocks.iter().any(|(hash, _)| *hash == block_hash) {
+                    self.included = None;
+                    self.pending
+                        .push_back(TransactionInclusion::Reverted { block_number });
+                }
+            }
+        }
+
+        if self.included.is_some() {
+            return;
+        }
+
+        if self.light {
+            match self.proven_at {
+                Some(block_number) => self.on_included_at(block_number),
+                None => self.prove_inclusion().await,
+            }
+            return;
+        }
+
+        let blockchain = self.consensus.blockchain.read();
+        for block_hash in event.added_hashes() {
+            // Macro blocks don't contain any transactions.
+            let Some(block) = blockchain
+                .get_block(&block_hash, true)
+                .ok()
+                .filter(|block| block.is_micro())
+            else {
+                continue;
+            };
+            let Some(transactions) = block.transactions() else {
+                continue;
+            };
+            if transactions
+                .iter()
+                .any(|tx| tx.get_raw_transaction().hash::<Blake2bHash>() == self.tx_hash)
+            {
+                let producer = blockchain
+                    .get_proposer_of(&block_hash)
+                    .ok()
+                    .map(|slot| slot.validator.address);
+                self.included = Some((block.block_number(), block_hash.clone()));
+                self.pending.push_back(TransactionInclusion::Included {
+                    block_number: block.block_number(),
+                    block_hash,
+                    producer,
+                });
+                break;
+            }
+        }
+    }
+
+    /// Handles a notification of a peer about the inclusion of the transaction. The inclusion is
+    /// only reported once it was proven by `min_peers` peers.
+    async fn on_notification(&mut self, notification: AddressNotification) {
+        if self.included.is_some() {
+            return;
+        }
+
+        let Some((_, block_number)) = notification
+            .receipts
+            .into_iter()
+            .find(|(tx_hash, _)| *tx_hash == self.tx_hash)
+        else {
+            return;
+        };
+
+        let Ok(tx) = self
+            .consensus
+            .request_transaction_by_hash_and_block_number(
+                self.tx_hash.clone(),
+                block_number,
+                self.min_peers,
+            )
+            .await
+        else {
+            debug!(tx_hash = %self.tx_hash, block_number, "Failed to prove notified transaction inclusion");
+            return;
+        };
+        if tx.tx_hash() != self.tx_hash.clone().into() || self.included.is_some() {
+            return;
+        }
+
+        self.proven_at = Some(tx.block_number);
+        self.on_included_at(tx.block_number);
+    }
+
+    /// Reports the inclusion of the transaction in our block at the given height, as proven by
+    /// our peers. Nothing is reported if we don't know a block at that height yet.
+    fn on_included_at(&mut self, block_number: u32) {
+        let blockchain = self.consensus.blockchain.read();
+        let Ok(block) = blockchain.get_block_at(block_number, false) else {
+            return;
+        };
+        let block_hash = block.hash();
+        let producer = blockchain
+            .get_proposer_of(&block_hash)
+            .ok()
+            .map(|slot| slot.validator.address);
+        drop(blockchain);
+
+        self.proven_at = None;
+        self.included = Some((block_number, block_hash.clone()));
+        self.pending.push_back(TransactionInclusion::Included {
+            block_number,
+            block_hash,
+            producer,
+        });
+    }
+
+    /// Asks our peers to prove the inclusion of the transaction in our chain, as light blockchains
+    /// can't find the transaction in the blocks they adopt. Nothing is reported if no peer was able
+    /// to prove the inclusion.
+    async fn prove_inclusion(&mut self) {
+        let Ok(tx) = self
+            .consensus
+            .request_transaction_by_hash(self.tx_hash.clone(), self.min_peers)
+            .await
+        else {
+            return;
+        };
+        if tx.tx_hash() != self.tx_hash.clone().into() {
+            return;
+        }
+
+        // The head might have changed while we waited for the proof.
+        if self.included.is_none() {
+            self.proven_at = Some(tx.block_number);
+            self.on_included_at(tx.block_number);
+        }
+    }
+
+    async fn on_finalized(&mut self) {
+        let macro_block_number = self.consensus.blockchain.read().macro_head().block_number();
+
+        if let Some((block_number, _)) = self.included {
+            if block_number <= macro_block_number {
+                self.finish(TransactionInclusion::Finalized { block_number });
+            }
+            return;
+        }
+
+        if self.light {
+            if let Ok(tx) = self
+                .consensus
+                .request_transaction_by_hash(self.tx_hash.clone(), self.min_peers)
+                .await
+            {
+                if tx.block_number <= macro_block_number {
+                    self.finish(TransactionInclusion::Finalized {
+                        block_number: tx.block_number,
+                    });
+                    return;
+                }
+            }
+        }
+
+        if macro_block_number > self.expires_after {
+            self.finish(TransactionInclusion::Expired);
+        }
+    }
+
+    fn finish(&mut self, event: TransactionInclusion) {
+        self.pending.push_back(event);
+        self.done = true;
+    }
+}
+
+/// Sends the transaction and returns a stream reporting its inclusion in the chain. The stream
+/// ends once the transaction is finalized or its validity window expired.
+///
+/// Inclusion in a block is reported as soon as we adopt that block. If our blockchain does not
+/// store block bodies, the inclusion is reported once it was proven by `min_peers` peers
+/// providing the transaction history, which is requested whenever our head changes.
+pub(crate) async fn send_and_track<N: Network>(
+    consensus: ConsensusProxy<N>,
+    tx: Transaction,
+    min_peers: usize,
+) -> Result<BoxStream<'static, TransactionInclusion>, N::Error> {
+    track(consensus, tx, min_peers, stream::empty().boxed()).await
+}
+
+/// Sends the transaction and returns a stream of its state. The stream starts with
+/// [`TransactionState::Pending`] once the transaction was sent and ends once the transaction is
+/// confirmed or expired.
+///
+/// If our blockchain does not store block bodies, we subscribe to the sender address at
+/// `min_peers` peers such that they notify us about the inclusion of the transaction. A notified
+/// inclusion is only reported once it was proven by our peers.
+pub(crate) async fn send_with_updates<N: Network>(
+    consensus: ConsensusProxy<N>,
+    tx: Transaction,
+    min_peers: usize,
+) -> Result<BoxStream<'static, TransactionState>, N::Error> {
+    let notifications = if matches!(consensus.blockchain, BlockchainProxy::Light(_)) {
+        let notifications = consensus
+            .subscribe_address_notifications()
+            .await?
+            .map(|(notification, _)| notification)
+            .boxed();
+
+        // The subscription is not removed afterwards, as the address might be subscribed to for
+        // other purposes as well.
+        if let Err(error) = consensus
+            .subscribe_to_addresses_with_filter(
+                vec![tx.sender.clone()],
+                AddressSubscriptionFilter::default(),
+                min_peers,
+                None,
+            )
+            .await
+        {
+            // The transaction is then only reported once it is confirmed.
+            warn!(%error, "Failed to subscribe to transaction sender");
+        }
+        notifications
+    } else {
+        stream::empty().boxed()
+    };
+
+    let inclusion = track(consensus, tx, min_peers, notifications).await?;
+    Ok(stream::once(async { TransactionState::Pending })
+        .chain(inclusion.map(TransactionState::from))
+        .boxed())
+}
+
+async fn track<N: Network>(
+    consensus: ConsensusProxy<N>,
+    tx: Transaction,
+    min_peers: usize,
+    notifications: BoxStream<'static, AddressNotification>,
+) -> Result<BoxStream<'static, TransactionInclusion>, N::Error> {
+    let tx_hash = tx.hash::<Blake2bHash>();
+    let light = matches!(consensus.blockchain, BlockchainProxy::Light(_));
+    let expires_after = tx
+        .validity_start_height
+        .saturating_add(Policy::transaction_validity_window_blocks());
+
+    // Subscribe before sending the transaction such that we can't miss the block including it.
+    let events = stream::select(
+        consensus
+            .blockchain
+            .read()
+            .notifier_as_stream()
+            .map(TrackerEvent::Blockchain),
+        notifications.map(TrackerEvent::Notification),
+    )
+    .boxed();
+    consensus.send_transaction(tx).await?;
+
+    let tracker = InclusionTracker {
+        consensus,
+        tx_hash,
+        expires_after,
+        min_peers,
+        events,
+        included: None,
+        proven_at: None,
+        light,
+        pending: VecDeque::new(),
+        done: false,
+    };
+
+    Ok(stream::unfold(tracker, |mut tracker| async move {
+        let event = tracker.next().await?;
+        Some((event, tracker))
+    })
+    .boxed())
+}
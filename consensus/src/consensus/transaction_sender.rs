@@ -0,0 +1,230 @@
+use std::{collections::VecDeque, sync::Arc};
+
+use futures::{
+    future::{self, Either},
+    stream::{self, BoxStream},
+    StreamExt,
+};
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_network_interface::network::{Network, NetworkEvent, SubscribeEvents};
+use nimiq_transaction::Transaction;
+
+use super::{consensus_proxy::ConsensusProxy, transaction_inclusion::TransactionState};
+
+/// Re-signs an expired transaction, typically with a higher fee and a new validity start height.
+/// Returning `None` gives up on the transaction.
+pub type FeeEscalation = dyn Fn(&Transaction) -> Option<Transaction> + Send + Sync;
+
+/// Configuration of the [`TransactionSender`].
+#[derive(Clone, Debug)]
+pub struct TransactionSenderConfig {
+    /// The number of peers that have to prove the inclusion of a transaction if our blockchain
+    /// does not store block bodies.
+    pub min_peers: usize,
+    /// The maximum number of times an expired transaction is replaced by a re-signed one.
+    pub max_escalations: usize,
+}
+
+impl Default for TransactionSenderConfig {
+    fn default() -> Self {
+        Self {
+            min_peers: 1,
+            max_escalations: 3,
+        }
+    }
+}
+
+/// Events reported by [`TransactionSender::send`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TransactionSenderEvent {
+    /// The state of the currently tracked transaction changed.
+    State {
+        tx_hash: Blake2bHash,
+        state: TransactionState,
+    },
+    /// The pending transaction was broadcast again since a new peer joined.
+    Rebroadcast { tx_hash: Blake2bHash },
+    /// The expired transaction was replaced by a re-signed one, which is tracked from now on.
+    Replaced {
+        old_tx_hash: Blake2bHash,
+        new_tx_hash: Blake2bHash,
+    },
+}
+
+/// Sends transactions and keeps them alive until they are confirmed: pending transactions are
+/// rebroadcast whenever a new peer joins and expired transactions are optionally re-signed
+/// through a fee escalation callback and sent again.
+pub struct TransactionSender<N: Network> {
+    consensus: ConsensusProxy<N>,
+    config: TransactionSenderConfig,
+    fee_escalation: Option<Arc<FeeEscalation>>,
+}
+
+impl<N: Network> TransactionSender<N> {
+    pub fn new(consensus: ConsensusProxy<N>, config: TransactionSenderConfig) -> Self {
+        Self {
+            consensus,
+            config,
+            fee_escalation: None,
+        }
+    }
+
+    /// Sets the callback that re-signs transactions once they expired without being confirmed.
+    /// A transaction is only replaced once it can no longer be included in the chain, such that
+    /// at most one of the transactions is ever executed.
+    pub fn with_fee_escalation<F>(mut self, fee_escalation: F) -> Self
+    where
+        F: Fn(&Transaction) -> Option<Transaction> + Send + Sync + 'static,
+    {
+        self.fee_escalation = Some(Arc::new(fee_escalation));
+        self
+    }
+
+    /// Sends the transaction and returns a stream of events about it and its replacements. The
+    /// stream ends once a transaction is confirmed or the last one expired.
+    pub async fn send(
+        &self,
+        tx: Transaction,
+    ) -> Result<BoxStream<'static, TransactionSenderEvent>, N::Error> {
+        // Subscribe before sending the transaction such that we don't miss any joining peer.
+        let peer_events = self.consensus.network.subscribe_events();
+        let tx_hash = tx.hash::<Blake2bHash>();
+        let states = self
+            .consensus
+            .send_transaction_with_updates(tx.clone(), self.config.min_peers)
+            .await?;
+
+        let tracker = SenderTracker {
+            consensus: self.consensus.clone(),
+            config: self.config.clone(),
+            fee_escalation: self.fee_escalation.clone(),
+            tx,
+            tx_hash,
+            states,
+            peer_events,
+            included: false,
+            num_escalations: 0,
+            pending: VecDeque::new(),
+            done: false,
+        };
+
+        Ok(stream::unfold(tracker, |mut tracker| async move {
+            let event = tracker.next().await?;
+            Some((event, tracker))
+        })
+        .boxed())
+    }
+}
+
+/// Follows a transaction and its replacements until one of them is confirmed.
+struct SenderTracker<N: Network> {
+    consensus: ConsensusProxy<N>,
+    config: TransactionSenderConfig,
+    fee_escalation: Option<Arc<FeeEscalation>>,
+    /// The currently tracked transaction.
+    tx: Transaction,
+    tx_hash: Blake2bHash,
+    states: BoxStream<'static, TransactionState>,
+    peer_events: SubscribeEvents<N::PeerId>,
+    /// Whether the current transaction is included in a block.
+    included: bool,
+    num_escalations: usize,
+    pending: VecDeque<TransactionSenderEvent>,
+    done: bool,
+}
+
+impl<N: Network> SenderTracker<N> {
+    /// Returns the next event, or `None` once a transaction is confirmed or the last one expired.
+    async fn next(&mut self) -> Option<TransactionSenderEvent> {
+        while self.pending.is_empty() && !self.done {
+            let event = match future::select(self.states.next(), self.peer_events.next()).await {
+                Either::Left((state, _)) => Either::Left(state),
+                Either::Right((peer_event, _)) => Either::Right(peer_event),
+            };
+
+            match event {
+                Either::Left(Some(state)) => self.on_state(state).await,
+                Either::Left(None) => self.done = true,
+                Either::Right(Some(Ok(NetworkEvent::PeerJoined(..)))) => self.rebroadcast().await,
+                Either::Right(Some(_)) => {}
+                Either::Right(None) => self.peer_events = stream::pending().boxed(),
+            }
+        }
+        self.pending.pop_front()
+    }
+
+    async fn on_state(&mut self, state: TransactionState) {
+        self.pending.push_back(TransactionSenderEvent::State {
+            tx_hash: self.tx_hash.clone(),
+            state: state.clone(),
+        });
+
+        match state {
+            TransactionState::Pending => self.included = false,
+            TransactionState::Included { .. } => self.included = true,
+            TransactionState::Confirmed { .. } => self.done = true,
+            TransactionState::Expired => self.escalate().await,
+        }
+    }
+
+    async fn rebroadcast(&mut self) {
+        if self.included {
+            return;
+        }
+
+        if let Err(error) = self.consensus.send_transaction(self.tx.clone()).await {
+            debug!(%error, tx_hash = %self.tx_hash, "Failed to rebroadcast transaction");
+            return;
+        }
+        self.pending.push_back(TransactionSenderEvent::Rebroadcast {
+            tx_hash: self.tx_hash.clone(),
+        });
+    }
+
+    /// Replaces the expired transaction by a re-signed one, or ends the stream if there is none.
+    async fn escalate(&mut self) {
+        self.done = true;
+
+        if self.num_escalations >= self.config.max_escalations {
+            return;
+        }
+        let Some(new_tx) = self
+            .fee_escalation
+            .as_ref()
+            .and_then(|fee_escalation| fee_escalation(&self.tx))
+        else {
+            return;
+        };
+
+        let new_tx_hash = new_tx.hash::<Blake2bHash>();
+        let states = match self
+            .consensus
+            .send_transaction_with_updates(new_tx.clone(), self.config.min_peers)
+            .await
+        {
+            Ok(states) => states,
+            Err(error) => {
+                warn!(%error, tx_hash = %new_tx_hash, "Failed to send replacement transaction");
+                return;
+            }
+        };
+
+        debug!(
+            old_tx_hash = %self.tx_hash,
+            %new_tx_hash,
+            fee = %new_tx.fee,
+            "Replaced expired transaction"
+        );
+        self.pending.push_back(TransactionSenderEvent::Replaced {
+            old_tx_hash: self.tx_hash.clone(),
+            new_tx_hash: new_tx_hash.clone(),
+        });
+
+        self.num_escalations += 1;
+        self.tx = new_tx;
+        self.tx_hash = new_tx_hash;
+        self.states = states;
+        self.included = false;
+        self.done = false;
+    }
+}
@@ -2,6 +2,8 @@ use nimiq_blockchain_interface::BlockchainError;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::sync::syncer_proxy::SyncerMode;
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("Blockchain error: {0}")]
@@ -16,6 +18,26 @@ pub enum SyncError {
     NoValidSyncTarget,
 }
 
+/// Errors that can occur when switching the sync mode of a running syncer.
+#[derive(Clone, Debug, Error, Eq, PartialEq)]
+pub enum SyncerModeError {
+    /// The syncer is already running in the requested mode
+    #[error("Syncer is already in {0} mode")]
+    AlreadyInMode(SyncerMode),
+    /// Switching between these modes is not supported without a restart
+    #[error("Switching from {from} to {to} mode is not supported")]
+    UnsupportedTransition { from: SyncerMode, to: SyncerMode },
+    /// The requested mode needs a full blockchain
+    #[error("{0} mode requires a full blockchain")]
+    RequiresFullBlockchain(SyncerMode),
+    /// A mode switch is already in progress
+    #[error("A sync mode switch is already in progress")]
+    SwitchInProgress,
+    /// The consensus could not be reached to perform the switch
+    #[error("Consensus is unavailable")]
+    ConsensusUnavailable,
+}
+
 /// Different errors that can be obtained when subscribing to transaction addresses.
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, Error, Eq, PartialEq, Serialize, Deserialize)]
@@ -1,9 +1,10 @@
 #[macro_use]
 extern crate log;
 
-pub use bls_cache::BlsCache;
+pub use bls_cache::{BlsCache, BlsCacheStats};
 pub use consensus::{consensus_proxy::ConsensusProxy, Consensus, ConsensusEvent, RemoteEvent};
-pub use error::{Error, SubscribeToAddressesError};
+pub use error::{Error, SubscribeToAddressesError, SyncerModeError};
+pub use sync::syncer_proxy::SyncerMode;
 
 mod bls_cache;
 
@@ -4,7 +4,7 @@ use std::{cmp, collections::HashSet};
 
 use nimiq_block::Block;
 #[cfg(feature = "full")]
-use nimiq_block::BlockInclusionProof;
+use nimiq_block::{BlockInclusionProof, MicroBlock};
 #[cfg(feature = "full")]
 use nimiq_blockchain::interface::{HistoryIndexInterface, HistoryInterface};
 #[cfg(feature = "full")]
@@ -638,6 +638,45 @@ impl<N: Network> Handle<N, Arc<RwLock<Blockchain>>> for RequestTransactionsProof
     }
 }
 
+#[cfg(feature = "full")]
+impl<N: Network> Handle<N, Arc<RwLock<Blockchain>>> for RequestInherentsProof {
+    fn handle(
+        &self,
+        _peer_id: N::PeerId,
+        blockchain: &Arc<RwLock<Blockchain>>,
+    ) -> Result<ResponseTransactionsProof, ResponseInherentsProofError> {
+        // Validate request. All blocks have to be proven against the history tree of one epoch.
+        if self.start_block > self.end_block
+            || self.end_block > self.block_number
+            || Policy::epoch_at(self.start_block) != Policy::epoch_at(self.block_number)
+        {
+            return Err(ResponseInherentsProofError::InvalidRange);
+        }
+        if self.end_block - self.start_block >= Self::MAX_BLOCKS {
+            return Err(ResponseInherentsProofError::TooManyBlocks);
+        }
+
+        let hashes: Vec<Blake2bHash> = {
+            let blockchain = blockchain.read();
+            (self.start_block..=self.end_block)
+                .flat_map(|block_number| {
+                    blockchain
+                        .history_store
+                        .get_block_transactions(block_number, None)
+                })
+                .filter(|hist_tx| hist_tx.is_not_basic())
+                .map(|hist_tx| hist_tx.tx_hash().into())
+                .collect()
+        };
+
+        Ok(RequestTransactionsProof::prove_txns_with_block_number(
+            blockchain,
+            &hashes,
+            self.block_number,
+        )?)
+    }
+}
+
 impl RequestTransactionReceiptsByAddress {
     const MAX_RECEIPTS: u16 = 500;
 }
@@ -649,42 +688,115 @@ impl<N: Network> Handle<N, Arc<RwLock<Blockchain>>> for RequestTransactionReceip
         blockchain: &Arc<RwLock<Blockchain>>,
     ) -> ResponseTransactionReceiptsByAddress {
         let blockchain = blockchain.read();
+        let Some(history_index) = blockchain.history_store.history_index() else {
+            return ResponseTransactionReceiptsByAddress { receipts: vec![] };
+        };
 
-        // Get the transaction hashes for this address.
-        let raw_tx_hashes = blockchain
-            .history_store
-            .history_index()
-            .unwrap()
-            .get_tx_hashes_by_address(
+        // Continue after the given transaction, if any.
+        let before = match &self.start_at {
+            Some(hash) => match history_index.get_address_tx_cursor(hash, None) {
+                Some(cursor) => Some(cursor),
+                None => return ResponseTransactionReceiptsByAddress { receipts: vec![] },
+            },
+            None => None,
+        };
+
+        let receipts = history_index
+            .get_transactions_by_address(
                 &self.address,
+                before,
                 self.max
                     .unwrap_or(Self::MAX_RECEIPTS)
                     .min(Self::MAX_RECEIPTS),
-                self.start_at.clone(),
                 None,
-            );
+            )
+            .into_iter()
+            .map(|(_, hist_tx)| (hist_tx.tx_hash().into(), hist_tx.block_number))
+            .collect();
 
-        let mut receipts = vec![];
+        ResponseTransactionReceiptsByAddress { receipts }
+    }
+}
 
-        for hash in raw_tx_hashes {
-            // Get all the historic transactions that correspond to this hash.
-            receipts.extend(
-                blockchain
-                    .history_store
-                    .history_index()
-                    .unwrap()
-                    .get_hist_tx_by_hash(&hash, None)
-                    .iter()
-                    .map(|hist_tx| (hist_tx.tx_hash().into(), hist_tx.block_number)),
-            );
+impl RequestAddressFilters {
+    const MAX_BATCHES: u16 = 100;
+}
+#[cfg(feature = "full")]
+impl<N: Network> Handle<N, Arc<RwLock<Blockchain>>> for RequestAddressFilters {
+    fn handle(
+        &self,
+        _peer_id: N::PeerId,
+        blockchain: &Arc<RwLock<Blockchain>>,
+    ) -> Result<ResponseAddressFilters, ResponseAddressFiltersError> {
+        // Validate request.
+        if self.count > Self::MAX_BATCHES {
+            return Err(ResponseAddressFiltersError::TooManyBatches);
         }
 
-        ResponseTransactionReceiptsByAddress { receipts }
+        let blockchain = blockchain.read();
+        let current_batch = Policy::batch_at(blockchain.block_number());
+        if self.start_batch > current_batch {
+            return Err(ResponseAddressFiltersError::RequestedFromFuture(
+                self.start_batch,
+                current_batch,
+            ));
+        }
+
+        let history_index = blockchain.history_store.history_index().unwrap();
+        let end_batch = (current_batch + 1).min(self.start_batch.saturating_add(self.count as u32));
+        let filters = (self.start_batch..end_batch)
+            .map(|batch| history_index.get_address_filter(batch, None))
+            .collect();
+
+        Ok(ResponseAddressFilters { filters })
+    }
+}
+
+#[cfg(feature = "full")]
+impl<N: Network> Handle<N, Arc<RwLock<Blockchain>>> for RequestValidatorPunishments {
+    fn handle(
+        &self,
+        _peer_id: N::PeerId,
+        blockchain: &Arc<RwLock<Blockchain>>,
+    ) -> Result<ResponseValidatorPunishments, ResponseValidatorPunishmentsError> {
+        let blockchain = blockchain.read();
+        let block_number = blockchain.block_number();
+        let staking_contract = blockchain
+            .get_staking_contract_if_complete(None)
+            .ok_or(ResponseValidatorPunishmentsError::IncompleteState)?;
+
+        // The previous batch belongs to the previous epoch if the current batch is the first one
+        // of its epoch.
+        // NOTE: Fields `current_slots` and `previous_slots` are expected to always be set.
+        let previous_batch_slots = if Policy::first_batch_of_epoch(block_number + 1) {
+            blockchain.previous_validators()
+        } else {
+            blockchain.current_validators()
+        };
+        let previous_batch_slot_range = previous_batch_slots
+            .and_then(|validators| validators.get_slots_by_address(&self.validator_address));
+
+        let data_store = blockchain.get_staking_contract_store();
+        let db_txn = blockchain.read_transaction();
+        let punishments = staking_contract
+            .get_validator_punishments(
+                &data_store.read(&db_txn),
+                &self.validator_address,
+                block_number,
+                previous_batch_slot_range,
+            )
+            .ok_or(ResponseValidatorPunishmentsError::UnknownValidator)?;
+
+        Ok(ResponseValidatorPunishments {
+            punishments,
+            block_number,
+            block_hash: blockchain.head_hash(),
+        })
     }
 }
 
 impl RequestTrieProof {
-    const MAX_KEYS: usize = 255;
+    pub(crate) const MAX_KEYS: usize = 255;
 }
 #[cfg(feature = "full")]
 impl<N: Network> Handle<N, Arc<RwLock<Blockchain>>> for RequestTrieProof {
@@ -698,7 +810,7 @@ impl<N: Network> Handle<N, Arc<RwLock<Blockchain>>> for RequestTrieProof {
             return Err(ResponseTrieProofError::TooManyKeys);
         }
 
-        // We only prove accounts that exist in our current state
+        // All keys are proven by a single proof, duplicate keys are only proven once.
         let blockchain = blockchain.read();
         match blockchain.get_accounts_proof(self.keys.iter().collect()) {
             Err(IncompleteTrie) => Err(ResponseTrieProofError::IncompleteTrie),
@@ -761,3 +873,34 @@ impl<N: Network> Handle<N, Arc<RwLock<Blockchain>>> for RequestBlocksProof {
         })
     }
 }
+
+#[cfg(feature = "full")]
+impl<N: Network> Handle<N, Arc<RwLock<Blockchain>>> for RequestMissingTransactions {
+    fn handle(
+        &self,
+        _peer_id: N::PeerId,
+        blockchain: &Arc<RwLock<Blockchain>>,
+    ) -> Result<Vec<Transaction>, MissingTransactionsError> {
+        let block = blockchain
+            .read()
+            .get_block(&self.block_hash, true)
+            .map_err(|_| MissingTransactionsError::UnknownBlock)?;
+        // Only micro blocks are relayed as compact blocks.
+        let Block::Micro(MicroBlock {
+            body: Some(body), ..
+        }) = block
+        else {
+            return Err(MissingTransactionsError::UnknownBlock);
+        };
+
+        self.indexes
+            .iter()
+            .map(|&index| {
+                body.transactions
+                    .get(index as usize)
+                    .map(|tx| tx.get_raw_transaction().clone())
+                    .ok_or(MissingTransactionsError::InvalidIndex(index))
+            })
+            .collect()
+    }
+}
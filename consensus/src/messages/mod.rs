@@ -3,9 +3,10 @@ use std::{
     io::Write,
 };
 
+use nimiq_account::punished_slots::ValidatorPunishments;
 use nimiq_block::{
-    Block, BlockBody, BlockInclusionProof, BlockType, MacroBlock, MacroHeader, MicroBlock,
-    MicroHeader, MicroJustification, TendermintProof,
+    Block, BlockBody, BlockInclusionProof, BlockType, CompactMicroBody, MacroBlock, MacroHeader,
+    MicroBlock, MicroHeader, MicroJustification, TendermintProof,
 };
 #[cfg(feature = "full")]
 use nimiq_blockchain::HistoryTreeChunk;
@@ -18,10 +19,16 @@ use nimiq_network_interface::{
     network::Topic,
     request::{RequestCommon, RequestMarker},
 };
-use nimiq_primitives::{key_nibbles::KeyNibbles, trie::trie_proof::TrieProof};
+use nimiq_primitives::{
+    account::AccountType, key_nibbles::KeyNibbles, trie::trie_proof::TrieProof,
+};
 use nimiq_serde::{Deserialize, Serialize, SerializedMaxSize};
 use nimiq_transaction::{
-    historic_transaction::HistoricTransaction, history_proof::HistoryTreeProof,
+    account::staking_contract::{IncomingStakingTransactionData, OutgoingStakingTransactionData},
+    address_filter::AddressFilter,
+    historic_transaction::HistoricTransaction,
+    history_proof::HistoryTreeProof,
+    Transaction,
 };
 use thiserror::Error;
 
@@ -133,6 +140,35 @@ pub struct BlockBodyMessage {
     pub body: BlockBody,
 }
 
+impl BlockBodyMessage {
+    /// Converts the message into a compact body message. Returns `None` for macro bodies, which
+    /// are always published in full.
+    pub fn to_compact(&self, block_hash: Blake2bHash) -> Option<CompactBlockBodyMessage> {
+        match &self.body {
+            BlockBody::Micro(body) => Some(CompactBlockBodyMessage {
+                header_message_hash: self.header_message_hash.clone(),
+                body: CompactMicroBody::new(body, &block_hash),
+                block_hash,
+            }),
+            BlockBody::Macro(_) => None,
+        }
+    }
+}
+
+/// A micro block body with the transactions replaced by their short ids, see [`CompactMicroBody`].
+///
+/// Receivers reconstruct the body from their mempool and request the remaining transactions
+/// from the peer they received the message from using [`RequestMissingTransactions`].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CompactBlockBodyMessage {
+    /// Hash of the corresponding [`BlockHeaderMessage`].
+    pub header_message_hash: Blake2bHash,
+    /// Hash of the block. The short ids are keyed with it and it is used to request missing
+    /// transactions.
+    pub block_hash: Blake2bHash,
+    pub body: CompactMicroBody,
+}
+
 /// GossipSub topics to publish block headers and block bodies.
 #[derive(Clone, Debug, Default)]
 pub struct BlockHeaderTopic;
@@ -158,6 +194,18 @@ impl Topic for BlockBodyTopic {
     const MAX_MESSAGES: u32 = BlockHeaderTopic::MAX_MESSAGES;
 }
 
+#[derive(Clone, Debug, Default)]
+pub struct CompactBlockBodyTopic;
+
+impl Topic for CompactBlockBodyTopic {
+    type Item = CompactBlockBodyMessage;
+
+    const BUFFER_SIZE: usize = 16;
+    const NAME: &'static str = "compact-block-body";
+    const VALIDATE: bool = true;
+    const MAX_MESSAGES: u32 = BlockHeaderTopic::MAX_MESSAGES;
+}
+
 /*
 The consensus module uses the following messages:
 200 RequestResponseMessage<RequestBlockHashes>
@@ -320,6 +368,7 @@ impl RequestCommon for RequestHistoryChunk {
     const TYPE_ID: u16 = 204;
     type Response = Result<HistoryChunk, HistoryChunkError>;
     const MAX_REQUESTS: u32 = 500;
+    const SEEDING: bool = true;
 }
 
 #[cfg(feature = "full")]
@@ -523,11 +572,47 @@ impl RequestCommon for RequestTransactionsProof {
     const MAX_REQUESTS: u32 = 1000;
 }
 
+/// Request a proof for all inherents (e.g. rewards, penalties and jails) of the blocks from
+/// `start_block` to `end_block` (both inclusive). The proof is created against the history root
+/// of the block at `block_number`, which has to be in the same epoch and is chosen like the
+/// block number of a [`RequestTransactionsProof`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RequestInherentsProof {
+    pub start_block: u32,
+    pub end_block: u32,
+    pub block_number: u32,
+}
+
+impl RequestInherentsProof {
+    /// The maximum number of blocks a single request may cover.
+    pub const MAX_BLOCKS: u32 = 1000;
+}
+
+impl RequestCommon for RequestInherentsProof {
+    type Kind = RequestMarker;
+    const TYPE_ID: u16 = 223;
+    type Response = Result<ResponseTransactionsProof, ResponseInherentsProofError>;
+    const MAX_REQUESTS: u32 = 100;
+}
+
+#[derive(Clone, Debug, Deserialize, Error, Serialize)]
+pub enum ResponseInherentsProofError {
+    #[error("invalid block range")]
+    InvalidRange,
+    #[error("too many blocks")]
+    TooManyBlocks,
+    #[error("{0}")]
+    Proof(#[from] ResponseTransactionProofError),
+    #[error("unknown error")]
+    #[serde(other)]
+    Other,
+}
+
 /// Returns the latest transactions for a given address. All the transactions
 /// where the given address is listed as a recipient or as a sender are considered. Reward
 /// transactions are also returned. It has an option to specify the maximum number of transactions
 /// to fetch. It has also an option to retrieve transactions before a given transaction hash.
-/// If this hash is not found or does not belong to this address, it will return an empty list.
+/// If this hash is not found, it will return an empty list.
 /// The transactions are returned in descending order, meaning the latest transaction is the first.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RequestTransactionReceiptsByAddress {
@@ -549,6 +634,105 @@ pub struct ResponseTransactionReceiptsByAddress {
     pub receipts: Vec<(Blake2bHash, u32)>,
 }
 
+/// Request the address filters of a range of consecutive batches, starting at `start_batch`.
+/// Light clients can use them to determine which batches may contain transactions relevant to
+/// an address before requesting any receipts.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RequestAddressFilters {
+    pub start_batch: u32,
+    pub count: u16,
+}
+
+impl RequestCommon for RequestAddressFilters {
+    type Kind = RequestMarker;
+    const TYPE_ID: u16 = 220;
+    type Response = Result<ResponseAddressFilters, ResponseAddressFiltersError>;
+    const MAX_REQUESTS: u32 = 20;
+}
+
+/// Response to [`RequestAddressFilters`].
+#[derive(Serialize, Deserialize)]
+pub struct ResponseAddressFilters {
+    /// The filters of the requested batches, in ascending order. A batch without a filter did not
+    /// touch any address. Batches after the current head are omitted.
+    pub filters: Vec<Option<AddressFilter>>,
+}
+
+#[derive(Clone, Debug, Deserialize, Error, Serialize)]
+pub enum ResponseAddressFiltersError {
+    #[error("too many batches")]
+    TooManyBatches,
+    #[error("requested filters from future batch {0}, current batch is {1}")]
+    RequestedFromFuture(u32, u32),
+    #[error("unknown error")]
+    #[serde(other)]
+    Other,
+}
+
+/// Request the punishments of a validator within the window retained by the staking contract,
+/// i.e. the slots it lost rewards for in the previous and the current batch as well as when it
+/// was jailed. Explorers can use this to present the reliability of a validator.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RequestValidatorPunishments {
+    pub validator_address: Address,
+}
+
+impl RequestCommon for RequestValidatorPunishments {
+    type Kind = RequestMarker;
+    const TYPE_ID: u16 = 221;
+    type Response = Result<ResponseValidatorPunishments, ResponseValidatorPunishmentsError>;
+    const MAX_REQUESTS: u32 = 50;
+}
+
+/// Response to [`RequestValidatorPunishments`].
+#[derive(Serialize, Deserialize)]
+pub struct ResponseValidatorPunishments {
+    /// The punishments of the validator.
+    pub punishments: ValidatorPunishments,
+    /// The number of the block that was used to answer the request.
+    pub block_number: u32,
+    /// The hash of the block that was used to answer the request.
+    pub block_hash: Blake2bHash,
+}
+
+#[derive(Clone, Debug, Deserialize, Error, Serialize)]
+pub enum ResponseValidatorPunishmentsError {
+    #[error("incomplete state")]
+    IncompleteState,
+    #[error("unknown validator")]
+    UnknownValidator,
+    #[error("unknown error")]
+    #[serde(other)]
+    Other,
+}
+
+/// Request the transactions at the given positions of the body of a micro block. This is used
+/// to complete compact block bodies, see [`CompactBlockBodyMessage`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RequestMissingTransactions {
+    pub block_hash: Blake2bHash,
+    pub indexes: Vec<u32>,
+}
+
+impl RequestCommon for RequestMissingTransactions {
+    type Kind = RequestMarker;
+    const TYPE_ID: u16 = 222;
+    /// The requested transactions, in the order of the requested indexes.
+    type Response = Result<Vec<Transaction>, MissingTransactionsError>;
+    const MAX_REQUESTS: u32 = 100;
+}
+
+#[derive(Clone, Debug, Deserialize, Error, Serialize)]
+pub enum MissingTransactionsError {
+    #[error("unknown block")]
+    UnknownBlock,
+    #[error("invalid transaction index {0}")]
+    InvalidIndex(u32),
+    #[error("unknown error")]
+    #[serde(other)]
+    Other,
+}
+
 /// Request a proof for the values corresponding to some keys or their absence from the accounts trie.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RequestTrieProof {
@@ -622,6 +806,98 @@ pub enum AddressSubscriptionOperation {
     Unsubscribe,
 }
 
+/// The operations that can be performed on the staking contract.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[repr(u8)]
+pub enum StakingOperation {
+    CreateValidator,
+    UpdateValidator,
+    DeactivateValidator,
+    ReactivateValidator,
+    RetireValidator,
+    DeleteValidator,
+    CreateStaker,
+    AddStake,
+    UpdateStaker,
+    SetActiveStake,
+    RetireStake,
+    RemoveStake,
+    SetRewardCompounding,
+    ScheduleUnstake,
+    ClaimWithdrawals,
+    SetValidatorCommission,
+}
+
+impl StakingOperation {
+    /// Returns the staking operation performed by the given transaction, if it is a staking
+    /// transaction.
+    pub fn from_transaction(transaction: &Transaction) -> Option<Self> {
+        if transaction.recipient_type == AccountType::Staking {
+            let operation = match IncomingStakingTransactionData::parse(transaction).ok()? {
+                IncomingStakingTransactionData::CreateValidator { .. } => Self::CreateValidator,
+                IncomingStakingTransactionData::UpdateValidator { .. } => Self::UpdateValidator,
+                IncomingStakingTransactionData::DeactivateValidator { .. } => {
+                    Self::DeactivateValidator
+                }
+                IncomingStakingTransactionData::ReactivateValidator { .. } => {
+                    Self::ReactivateValidator
+                }
+                IncomingStakingTransactionData::RetireValidator { .. } => Self::RetireValidator,
+                IncomingStakingTransactionData::CreateStaker { .. } => Self::CreateStaker,
+                IncomingStakingTransactionData::AddStake { .. } => Self::AddStake,
+                IncomingStakingTransactionData::UpdateStaker { .. } => Self::UpdateStaker,
+                IncomingStakingTransactionData::SetActiveStake { .. } => Self::SetActiveStake,
+                IncomingStakingTransactionData::RetireStake { .. } => Self::RetireStake,
+                IncomingStakingTransactionData::SetRewardCompounding { .. } => {
+                    Self::SetRewardCompounding
+                }
+                IncomingStakingTransactionData::ScheduleUnstake { .. } => Self::ScheduleUnstake,
+                IncomingStakingTransactionData::ClaimWithdrawals { .. } => Self::ClaimWithdrawals,
+                IncomingStakingTransactionData::SetValidatorCommission { .. } => {
+                    Self::SetValidatorCommission
+                }
+            };
+            Some(operation)
+        } else if transaction.sender_type == AccountType::Staking {
+            let operation = match OutgoingStakingTransactionData::parse(transaction).ok()? {
+                OutgoingStakingTransactionData::DeleteValidator => Self::DeleteValidator,
+                OutgoingStakingTransactionData::RemoveStake => Self::RemoveStake,
+            };
+            Some(operation)
+        } else {
+            None
+        }
+    }
+}
+
+/// Restricts the transactions a peer is notified about for its subscribed addresses.
+/// Empty lists don't restrict the notifications.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressSubscriptionFilter {
+    /// Only notify about transactions whose recipient is an account of one of these types.
+    pub recipient_types: Vec<AccountType>,
+    /// Only notify about staking transactions performing one of these operations.
+    pub staking_operations: Vec<StakingOperation>,
+}
+
+impl AddressSubscriptionFilter {
+    /// Returns whether a notification about the given transaction passes this filter.
+    pub fn matches(&self, transaction: &Transaction) -> bool {
+        if !self.recipient_types.is_empty()
+            && !self.recipient_types.contains(&transaction.recipient_type)
+        {
+            return false;
+        }
+
+        if !self.staking_operations.is_empty() {
+            return StakingOperation::from_transaction(transaction)
+                .is_some_and(|operation| self.staking_operations.contains(&operation));
+        }
+
+        true
+    }
+}
+
 /// This request is used to subscribe or unsubscribe from specific addresses.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RequestSubscribeToAddress {
@@ -638,6 +914,25 @@ impl RequestCommon for RequestSubscribeToAddress {
     const MAX_REQUESTS: u32 = 10;
 }
 
+/// This request is used to subscribe to specific addresses, only being notified about the
+/// transactions that pass the given filter. Addresses are unsubscribed from using
+/// [`RequestSubscribeToAddress`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RequestSubscribeToAddressWithFilter {
+    /// The addresses which are interesting to the peer
+    pub addresses: Vec<Address>,
+    /// The filter applied to the notifications about the subscribed addresses.
+    /// It replaces any filter previously set for these addresses.
+    pub filter: AddressSubscriptionFilter,
+}
+
+impl RequestCommon for RequestSubscribeToAddressWithFilter {
+    type Kind = RequestMarker;
+    const TYPE_ID: u16 = 224;
+    type Response = Result<(), SubscribeToAddressesError>;
+    const MAX_REQUESTS: u32 = 10;
+}
+
 /// Different kind of events that could generate notifications
 #[derive(Serialize, Deserialize, Copy, Clone, Debug)]
 #[repr(u8)]
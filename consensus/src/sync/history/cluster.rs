@@ -159,6 +159,15 @@ pub struct SyncCluster<TNetwork: Network> {
 impl<TNetwork: Network + 'static> SyncCluster<TNetwork> {
     const NUM_PENDING_BATCH_SETS: usize = 5;
     const NUM_PENDING_CHUNKS: usize = 12;
+    /// The number of parallel requests per peer once the cluster has more peers than the
+    /// minimal pending sizes above account for.
+    const NUM_PENDING_BATCH_SETS_PER_PEER: usize = 2;
+    const NUM_PENDING_CHUNKS_PER_PEER: usize = 4;
+    /// Upper bounds for the number of parallel requests, independent of the number of peers.
+    const MAX_PENDING_BATCH_SETS: usize = 20;
+    const MAX_PENDING_CHUNKS: usize = 64;
+    /// The number of consecutive failed requests after which a peer is removed from the cluster.
+    const MAX_PEER_FAILURES: usize = 3;
 
     pub(crate) fn for_epoch(
         blockchain: Arc<RwLock<Blockchain>>,
@@ -214,6 +223,8 @@ impl<TNetwork: Network + 'static> SyncCluster<TNetwork> {
                 validators: blockchain.election_head().get_validators().unwrap(),
             }
         };
+        // Both queues spread their requests across all peers of the cluster and reorder the
+        // responses on arrival. Peers that repeatedly fail to respond are removed from the cluster.
         let epoch_ids_queue = epoch_ids
             .iter()
             .map(|epoch_id| (epoch_id.clone(), None))
@@ -254,7 +265,12 @@ impl<TNetwork: Network + 'static> SyncCluster<TNetwork> {
                 true
             },
             batch_verify_state,
-        );
+        )
+        .with_pending_per_peer(
+            Self::NUM_PENDING_BATCH_SETS_PER_PEER,
+            Self::MAX_PENDING_BATCH_SETS,
+        )
+        .with_max_peer_failures(Self::MAX_PEER_FAILURES);
 
         let history_queue = SyncQueue::new(
             Arc::clone(&network),
@@ -269,7 +285,9 @@ impl<TNetwork: Network + 'static> SyncCluster<TNetwork> {
                 }
                 .boxed()
             },
-        );
+        )
+        .with_pending_per_peer(Self::NUM_PENDING_CHUNKS_PER_PEER, Self::MAX_PENDING_CHUNKS)
+        .with_max_peer_failures(Self::MAX_PEER_FAILURES);
         Self {
             id,
             epoch_ids,
@@ -642,6 +660,13 @@ impl<TNetwork: Network + 'static> SyncCluster<TNetwork> {
         Ok(chunk)
     }
 
+    /// The number of batch sets whose history is downloaded in parallel. It grows with the
+    /// number of peers in the cluster.
+    fn num_pending_batch_sets(&self) -> usize {
+        (Self::NUM_PENDING_BATCH_SETS_PER_PEER * self.batch_set_queue.num_peers())
+            .clamp(Self::NUM_PENDING_BATCH_SETS, Self::MAX_PENDING_BATCH_SETS)
+    }
+
     fn pop_complete_epoch(&mut self) -> Option<PendingBatchSet> {
         if !self.pending_batch_sets.is_empty() && self.pending_batch_sets[0].is_complete() {
             self.num_epochs_finished += 1;
@@ -656,7 +681,7 @@ impl<TNetwork: Network + 'static> Stream for SyncCluster<TNetwork> {
     type Item = Result<BatchSet, SyncClusterResult>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
-        while self.pending_batch_sets.len() < Self::NUM_PENDING_BATCH_SETS {
+        while self.pending_batch_sets.len() < self.num_pending_batch_sets() {
             let result = match self.batch_set_queue.poll_next_unpin(cx) {
                 Poll::Ready(Some(result)) => result,
                 _ => break,
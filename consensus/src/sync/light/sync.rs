@@ -7,8 +7,10 @@ use std::{
 
 use futures::{future::BoxFuture, FutureExt};
 use nimiq_block::Block;
+use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_blockchain_proxy::BlockchainProxy;
 use nimiq_hash::Blake2bHash;
+use nimiq_light_blockchain::LightBlockchain;
 use nimiq_network_interface::{
     network::{CloseReason, Network, SubscribeEvents},
     request::RequestError,
@@ -231,7 +233,7 @@ impl<TNetwork: Network> LightMacroSync<TNetwork> {
             },
         );
 
-        Self {
+        let sync = Self {
             blockchain,
             network,
             network_event_rx,
@@ -250,6 +252,39 @@ impl<TNetwork: Network> LightMacroSync<TNetwork> {
             validity_queue,
             #[cfg(feature = "full")]
             synced_validity_peers: Vec::new(),
+        };
+
+        sync.apply_sync_checkpoint();
+        sync
+    }
+
+    /// Pushes the zkp state into a light blockchain if it is more recent. The zkp component
+    /// initializes its state from the newest sync checkpoint, such that a light blockchain starts
+    /// syncing from there instead of from genesis.
+    fn apply_sync_checkpoint(&self) {
+        let BlockchainProxy::Light(ref light_blockchain) = self.blockchain else {
+            return;
+        };
+
+        let zkp_state = self.zkp_component_proxy.get_zkp_state();
+        let Some(proof) = zkp_state.latest_proof else {
+            return;
+        };
+
+        let light_blockchain = light_blockchain.upgradable_read();
+        if zkp_state.latest_block.block_number() <= light_blockchain.block_number() {
+            return;
+        }
+
+        let block_number = zkp_state.latest_block.block_number();
+        match LightBlockchain::push_zkp(
+            light_blockchain,
+            Block::Macro(zkp_state.latest_block),
+            proof,
+            true,
+        ) {
+            Ok(result) => log::debug!(block_number, ?result, "Applied sync checkpoint"),
+            Err(error) => log::warn!(block_number, ?error, "Failed to apply sync checkpoint"),
         }
     }
 
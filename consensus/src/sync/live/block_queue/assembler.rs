@@ -6,22 +6,41 @@ use std::{
     time::Duration,
 };
 
-use futures::{stream::BoxStream, Stream, StreamExt};
+use futures::{
+    future::BoxFuture,
+    stream::{BoxStream, FuturesUnordered},
+    FutureExt, Stream, StreamExt,
+};
 use instant::Instant;
-use nimiq_block::{Block, BlockBody, MacroBlock, MicroBlock};
+use nimiq_block::{Block, BlockBody, MacroBlock, MicroBlock, ShortTransactionId};
 use nimiq_hash::{Blake2bHash, Blake2sHash, Hash};
 use nimiq_network_interface::network::{CloseReason, MsgAcceptance, Network, PubsubId};
 use nimiq_time::{interval, Interval};
+use nimiq_transaction::Transaction;
 use nimiq_utils::spawn;
 
 use crate::{
-    messages::{BlockBodyMessage, BlockBodyTopic, BlockHeaderMessage, BlockHeaderTopic},
-    sync::live::block_queue::BlockSource,
+    messages::{
+        BlockBodyMessage, BlockHeaderMessage, BlockHeaderTopic, CompactBlockBodyMessage,
+        RequestBlock, RequestMissingTransactions,
+    },
+    sync::live::block_queue::{BlockSource, BodyId},
 };
 
 type PubsubHeader<N> = (BlockHeaderMessage, <N as Network>::PubsubId);
 type PubsubBody<N> = (BlockBodyMessage, <N as Network>::PubsubId);
-type CachedBody<N> = (BlockBody, <N as Network>::PubsubId);
+type PubsubCompactBody<N> = (CompactBlockBodyMessage, <N as Network>::PubsubId);
+type CachedBody<N> = (BlockBody, BodyId<N>);
+/// A reconstructed compact body together with the hash of its block.
+type ReconstructedBody<N> = (BlockBodyMessage, Blake2bHash, BodyId<N>);
+type PendingBody<N> = BoxFuture<'static, Result<ReconstructedBody<N>, BodyId<N>>>;
+type RequestedBody<N> = BoxFuture<'static, Result<(BlockBodyMessage, BodyId<N>), BodyId<N>>>;
+
+/// Looks up known transactions by their short ids in the block with the given hash, returning
+/// them in the same order. It is used to reconstruct compact block bodies, usually from the
+/// mempool.
+pub type TransactionLookup =
+    Arc<dyn Fn(&Blake2bHash, &[ShortTransactionId]) -> Vec<Option<Transaction>> + Send + Sync>;
 
 #[derive(Clone, Eq, Hash, PartialEq)]
 struct CachedBodyKey {
@@ -33,8 +52,16 @@ pub struct BlockAssembler<N: Network> {
     network: Arc<N>,
     header_stream: BoxStream<'static, PubsubHeader<N>>,
     body_stream: BoxStream<'static, PubsubBody<N>>,
+    compact_body_stream: BoxStream<'static, PubsubCompactBody<N>>,
+    transaction_lookup: TransactionLookup,
+    /// Compact bodies waiting for their missing transactions.
+    pending_bodies: FuturesUnordered<PendingBody<N>>,
+    /// Full bodies requested because a reconstructed compact body didn't match its header.
+    requested_bodies: FuturesUnordered<RequestedBody<N>>,
     cached_headers: TimeLimitedCache<Blake2bHash, PubsubHeader<N>>,
     cached_bodies: TimeLimitedCache<CachedBodyKey, CachedBody<N>>,
+    /// Reconstructed compact bodies waiting for their header, by header message hash.
+    cached_reconstructed_bodies: TimeLimitedCache<Blake2bHash, ReconstructedBody<N>>,
 }
 
 impl<N: Network> BlockAssembler<N> {
@@ -44,13 +71,20 @@ impl<N: Network> BlockAssembler<N> {
         network: Arc<N>,
         header_stream: BoxStream<'static, PubsubHeader<N>>,
         body_stream: BoxStream<'static, PubsubBody<N>>,
+        compact_body_stream: BoxStream<'static, PubsubCompactBody<N>>,
+        transaction_lookup: TransactionLookup,
     ) -> Self {
         Self {
             network,
             header_stream,
             body_stream,
+            compact_body_stream,
+            transaction_lookup,
+            pending_bodies: FuturesUnordered::new(),
+            requested_bodies: FuturesUnordered::new(),
             cached_headers: TimeLimitedCache::new(Self::CACHE_TTL),
             cached_bodies: TimeLimitedCache::new(Self::CACHE_TTL),
+            cached_reconstructed_bodies: TimeLimitedCache::new(Self::CACHE_TTL),
         }
     }
 
@@ -75,10 +109,10 @@ impl<N: Network> BlockAssembler<N> {
         }
     }
 
-    fn reject_messages(&self, pubsub_id_header: N::PubsubId, pubsub_id_body: N::PubsubId) {
+    fn reject_messages(&self, pubsub_id_header: N::PubsubId, body_id: BodyId<N>) {
         let network = Arc::clone(&self.network);
         let peer_id_header = pubsub_id_header.propagation_source();
-        let peer_id_body = pubsub_id_body.propagation_source();
+        let peer_id_body = body_id.propagation_source();
         spawn(async move {
             network
                 .disconnect_peer(peer_id_header, CloseReason::MaliciousPeer)
@@ -92,8 +126,211 @@ impl<N: Network> BlockAssembler<N> {
 
         self.network
             .validate_message::<BlockHeaderTopic>(pubsub_id_header, MsgAcceptance::Reject);
-        self.network
-            .validate_message::<BlockBodyTopic>(pubsub_id_body, MsgAcceptance::Reject);
+        body_id.validate_message(&self.network, MsgAcceptance::Reject);
+    }
+
+    /// Reconstructs a compact body from the known transactions. If some transactions are
+    /// unknown, they are requested from the peer that sent us the compact body.
+    fn reconstruct_body(
+        &mut self,
+        message: CompactBlockBodyMessage,
+        pubsub_id: N::PubsubId,
+    ) -> Option<ReconstructedBody<N>> {
+        let body_id = BodyId::Compact(pubsub_id);
+        let transactions =
+            (self.transaction_lookup)(&message.block_hash, &message.body.short_ids());
+        let missing: Vec<u32> = transactions
+            .iter()
+            .enumerate()
+            .filter(|(_, tx)| tx.is_none())
+            .map(|(index, _)| index as u32)
+            .collect();
+
+        if missing.is_empty() {
+            let block_hash = message.block_hash.clone();
+            return match Self::complete_body(message, transactions) {
+                Some(body_message) => Some((body_message, block_hash, body_id)),
+                None => {
+                    body_id.validate_message(&self.network, MsgAcceptance::Ignore);
+                    None
+                }
+            };
+        }
+
+        let network = Arc::clone(&self.network);
+        let peer_id = body_id.propagation_source();
+        self.pending_bodies.push(
+            async move {
+                let request = RequestMissingTransactions {
+                    block_hash: message.block_hash.clone(),
+                    indexes: missing.clone(),
+                };
+                let missing_transactions = match network
+                    .request::<RequestMissingTransactions>(request, peer_id)
+                    .await
+                {
+                    Ok(Ok(missing_transactions)) => missing_transactions,
+                    result => {
+                        debug!(
+                            block_hash = %message.block_hash,
+                            %peer_id,
+                            ?result,
+                            "Failed to request missing transactions of compact block"
+                        );
+                        return Err(body_id);
+                    }
+                };
+                if missing_transactions.len() != missing.len() {
+                    return Err(body_id);
+                }
+
+                let mut transactions = transactions;
+                for (index, tx) in missing.into_iter().zip(missing_transactions) {
+                    transactions[index as usize] = Some(tx);
+                }
+                let block_hash = message.block_hash.clone();
+                match Self::complete_body(message, transactions) {
+                    Some(body_message) => Ok((body_message, block_hash, body_id)),
+                    None => Err(body_id),
+                }
+            }
+            .boxed(),
+        );
+        None
+    }
+
+    fn complete_body(
+        message: CompactBlockBodyMessage,
+        transactions: Vec<Option<Transaction>>,
+    ) -> Option<BlockBodyMessage> {
+        let transactions = transactions.into_iter().collect::<Option<Vec<_>>>()?;
+        let body = message
+            .body
+            .reconstruct(&message.block_hash, transactions)?;
+        Some(BlockBodyMessage {
+            header_message_hash: message.header_message_hash,
+            body: BlockBody::Micro(body),
+        })
+    }
+
+    /// Matches a reconstructed compact body with its cached header, or caches the body until the
+    /// header arrives. Since short ids are not unique, the reconstructed body might contain the
+    /// wrong transactions. In that case, the full body is requested from the peer that sent us the
+    /// compact body.
+    fn on_reconstructed_body(
+        &mut self,
+        body_message: BlockBodyMessage,
+        block_hash: Blake2bHash,
+        body_id: BodyId<N>,
+    ) -> Option<(Block, BlockSource<N>)> {
+        let Some((header_message, _)) = self.cached_headers.get(&body_message.header_message_hash)
+        else {
+            if let Some((_, _, replaced_body_id)) = self.cached_reconstructed_bodies.insert(
+                body_message.header_message_hash.clone(),
+                (body_message, block_hash, body_id),
+            ) {
+                replaced_body_id.validate_message(&self.network, MsgAcceptance::Ignore);
+            }
+            return None;
+        };
+
+        if *header_message.body_root() != body_message.body.hash() {
+            debug!(
+                %block_hash,
+                peer_id = %body_id.propagation_source(),
+                "Reconstructed compact body doesn't match the header, requesting the full body"
+            );
+            self.request_full_body(body_message.header_message_hash, block_hash, body_id);
+            return None;
+        }
+
+        self.on_body(body_message, body_id)
+    }
+
+    /// Requests the full body of the given block from the peer that sent us the compact body.
+    fn request_full_body(
+        &mut self,
+        header_message_hash: Blake2bHash,
+        block_hash: Blake2bHash,
+        body_id: BodyId<N>,
+    ) {
+        let network = Arc::clone(&self.network);
+        let peer_id = body_id.propagation_source();
+        self.requested_bodies.push(
+            async move {
+                let request = RequestBlock {
+                    hash: block_hash.clone(),
+                    include_body: true,
+                };
+                match network.request::<RequestBlock>(request, peer_id).await {
+                    Ok(Ok(Block::Micro(MicroBlock {
+                        body: Some(body), ..
+                    }))) => Ok((
+                        BlockBodyMessage {
+                            header_message_hash,
+                            body: BlockBody::Micro(body),
+                        },
+                        body_id,
+                    )),
+                    result => {
+                        debug!(
+                            %block_hash,
+                            %peer_id,
+                            ?result,
+                            "Failed to request full body of compact block"
+                        );
+                        Err(body_id)
+                    }
+                }
+            }
+            .boxed(),
+        );
+    }
+
+    /// Matches a body with its cached header, or caches the body until the header arrives.
+    fn on_body(
+        &mut self,
+        body_message: BlockBodyMessage,
+        body_id: BodyId<N>,
+    ) -> Option<(Block, BlockSource<N>)> {
+        let hash = body_message.body.hash();
+        if self
+            .cached_headers
+            .get(&body_message.header_message_hash)
+            .is_some_and(|(header_message, _)| *header_message.body_root() == hash)
+        {
+            let (header_message, header_id) = self
+                .cached_headers
+                .remove(&body_message.header_message_hash)
+                .unwrap();
+
+            // Check that header and body type match.
+            if header_message.ty() != body_message.body.ty() {
+                debug!(
+                    block = %header_message,
+                    peer_id_header = %header_id.propagation_source(),
+                    peer_id_body = %body_id.propagation_source(),
+                    "Discarding block - header and body types don't match"
+                );
+
+                self.reject_messages(header_id, body_id);
+
+                return None;
+            }
+
+            return Some((
+                Self::assemble_block(header_message, body_message.body),
+                BlockSource::announced(header_id, Some(body_id)),
+            ));
+        }
+
+        let body_key = CachedBodyKey {
+            body_root: hash,
+            header_message_hash: body_message.header_message_hash,
+        };
+        self.cached_bodies
+            .insert(body_key, (body_message.body, body_id));
+        None
     }
 }
 
@@ -134,7 +371,15 @@ impl<N: Network> Stream for BlockAssembler<N> {
             }
 
             self.cached_headers
-                .insert(hash, (header_message, header_id));
+                .insert(hash.clone(), (header_message, header_id));
+
+            if let Some((body_message, block_hash, body_id)) =
+                self.cached_reconstructed_bodies.remove(&hash)
+            {
+                if let Some(block) = self.on_reconstructed_body(body_message, block_hash, body_id) {
+                    return Poll::Ready(Some(block));
+                }
+            }
         }
 
         while let Poll::Ready(item) = self.body_stream.poll_next_unpin(cx) {
@@ -142,43 +387,47 @@ impl<N: Network> Stream for BlockAssembler<N> {
                 return Poll::Ready(None);
             };
 
-            let hash = body_message.body.hash();
-            if self
-                .cached_headers
-                .get(&body_message.header_message_hash)
-                .is_some_and(|(header_message, _)| *header_message.body_root() == hash)
-            {
-                let (header_message, header_id) = self
-                    .cached_headers
-                    .remove(&body_message.header_message_hash)
-                    .unwrap();
-
-                // Check that header and body type match.
-                if header_message.ty() != body_message.body.ty() {
-                    debug!(
-                        block = %header_message,
-                        peer_id_header = %header_id.propagation_source(),
-                        peer_id_body = %body_id.propagation_source(),
-                        "Discarding block - header and body types don't match"
-                    );
+            if let Some(block) = self.on_body(body_message, BodyId::Full(body_id)) {
+                return Poll::Ready(Some(block));
+            }
+        }
 
-                    self.reject_messages(header_id, body_id);
+        while let Poll::Ready(item) = self.compact_body_stream.poll_next_unpin(cx) {
+            let Some((compact_message, body_id)) = item else {
+                return Poll::Ready(None);
+            };
 
-                    continue;
+            if let Some((body_message, block_hash, body_id)) =
+                self.reconstruct_body(compact_message, body_id)
+            {
+                if let Some(block) = self.on_reconstructed_body(body_message, block_hash, body_id) {
+                    return Poll::Ready(Some(block));
                 }
+            }
+        }
 
-                return Poll::Ready(Some((
-                    Self::assemble_block(header_message, body_message.body),
-                    BlockSource::announced(header_id, Some(body_id)),
-                )));
+        while let Poll::Ready(Some(result)) = self.pending_bodies.poll_next_unpin(cx) {
+            match result {
+                Ok((body_message, block_hash, body_id)) => {
+                    if let Some(block) =
+                        self.on_reconstructed_body(body_message, block_hash, body_id)
+                    {
+                        return Poll::Ready(Some(block));
+                    }
+                }
+                Err(body_id) => body_id.validate_message(&self.network, MsgAcceptance::Ignore),
             }
+        }
 
-            let body_key = CachedBodyKey {
-                body_root: hash,
-                header_message_hash: body_message.header_message_hash,
-            };
-            self.cached_bodies
-                .insert(body_key, (body_message.body, body_id));
+        while let Poll::Ready(Some(result)) = self.requested_bodies.poll_next_unpin(cx) {
+            match result {
+                Ok((body_message, body_id)) => {
+                    if let Some(block) = self.on_body(body_message, body_id) {
+                        return Poll::Ready(Some(block));
+                    }
+                }
+                Err(body_id) => body_id.validate_message(&self.network, MsgAcceptance::Ignore),
+            }
         }
 
         // The cache stream never returns None, so it's ok to ignore that case here.
@@ -194,8 +443,16 @@ impl<N: Network> Stream for BlockAssembler<N> {
             trace!(num_evicted, "Evicted {} bodies from cache", num_evicted);
 
             for (_, body) in evicted_entries {
-                self.network
-                    .validate_message::<BlockBodyTopic>(body.1, MsgAcceptance::Ignore);
+                body.1
+                    .validate_message(&self.network, MsgAcceptance::Ignore);
+            }
+        }
+        while let Poll::Ready(Some(evicted_entries)) =
+            self.cached_reconstructed_bodies.poll_next_unpin(cx)
+        {
+            for (_, body) in evicted_entries {
+                body.2
+                    .validate_message(&self.network, MsgAcceptance::Ignore);
             }
         }
 
@@ -279,17 +536,29 @@ mod tests {
     use core::task::Poll;
     use std::{sync::Arc, time::Duration};
 
-    use futures::{poll, stream::StreamExt};
-    use nimiq_block::{Block, MacroBlock};
-    use nimiq_network_interface::network::PubsubId;
+    use futures::{
+        poll,
+        stream::{self, StreamExt},
+    };
+    use nimiq_block::{
+        Block, MacroBlock, MicroBlock, MicroBody, MicroHeader, MicroJustification,
+        ShortTransactionId,
+    };
+    use nimiq_hash::{Blake2bHash, Hash};
+    use nimiq_keys::{Ed25519Signature, KeyPair};
+    use nimiq_network_interface::network::{Network, PubsubId};
     use nimiq_network_mock::{MockHub, MockId, MockNetwork, MockPeerId};
+    use nimiq_primitives::networks::NetworkId;
     use nimiq_test_log::test;
-    use nimiq_time::sleep;
+    use nimiq_test_utils::blockchain::generate_transactions;
+    use nimiq_time::{sleep, timeout};
+    use nimiq_transaction::ExecutedTransaction;
+    use nimiq_utils::spawn;
     use tokio::sync::mpsc;
     use tokio_stream::wrappers::ReceiverStream;
 
     use crate::{
-        messages::BlockHeaderMessage,
+        messages::{BlockHeaderMessage, RequestBlock, RequestMissingTransactions},
         sync::live::block_queue::assembler::{BlockAssembler, TimeLimitedCache},
     };
 
@@ -303,6 +572,10 @@ mod tests {
             network,
             ReceiverStream::new(header_rx).boxed(),
             ReceiverStream::new(body_rx).boxed(),
+            stream::pending().boxed(),
+            Arc::new(|_: &Blake2bHash, short_ids: &[ShortTransactionId]| {
+                vec![None; short_ids.len()]
+            }),
         );
 
         let block = Block::Macro(MacroBlock::non_empty_default());
@@ -339,6 +612,10 @@ mod tests {
             network,
             ReceiverStream::new(header_rx).boxed(),
             ReceiverStream::new(body_rx).boxed(),
+            stream::pending().boxed(),
+            Arc::new(|_: &Blake2bHash, short_ids: &[ShortTransactionId]| {
+                vec![None; short_ids.len()]
+            }),
         );
 
         let block = Block::Macro(MacroBlock::non_empty_default());
@@ -365,6 +642,261 @@ mod tests {
         assert!(assembler.cached_bodies.is_empty());
     }
 
+    #[test(tokio::test)]
+    async fn it_assembles_header_and_compact_body() {
+        let (header_tx, header_rx) = mpsc::channel(16);
+        let (compact_body_tx, compact_body_rx) = mpsc::channel(16);
+
+        let transactions =
+            generate_transactions(&KeyPair::default(), 1, NetworkId::UnitAlbatross, 2, 0);
+        let body = MicroBody {
+            equivocation_proofs: vec![],
+            transactions: transactions
+                .iter()
+                .map(|tx| ExecutedTransaction::Ok(tx.clone()))
+                .collect(),
+        };
+        let block = Block::Micro(MicroBlock {
+            header: MicroHeader {
+                body_root: body.hash(),
+                ..Default::default()
+            },
+            justification: Some(MicroJustification::Micro(Ed25519Signature::default())),
+            body: Some(body),
+        });
+
+        // All transactions of the block are known locally.
+        let network = Arc::new(MockHub::new().new_network());
+        let mut assembler = BlockAssembler::<MockNetwork>::new(
+            network,
+            ReceiverStream::new(header_rx).boxed(),
+            stream::pending().boxed(),
+            ReceiverStream::new(compact_body_rx).boxed(),
+            Arc::new(
+                move |block_hash: &Blake2bHash, short_ids: &[ShortTransactionId]| {
+                    short_ids
+                        .iter()
+                        .map(|short_id| {
+                            transactions
+                                .iter()
+                                .find(|tx| {
+                                    ShortTransactionId::from_transaction(block_hash, tx)
+                                        == *short_id
+                                })
+                                .cloned()
+                        })
+                        .collect()
+                },
+            ),
+        );
+
+        let (header, body) = BlockHeaderMessage::split_block(block.clone());
+        let compact_body = body.to_compact(block.hash()).unwrap();
+
+        let body_sender = MockId::new(MockPeerId::from(1));
+        compact_body_tx
+            .try_send((compact_body, body_sender))
+            .unwrap();
+
+        let _ = poll!(assembler.next());
+        assert_eq!(assembler.cached_reconstructed_bodies.len(), 1);
+
+        let header_sender = MockId::new(MockPeerId::from(0));
+        header_tx.try_send((header, header_sender.clone())).unwrap();
+
+        match poll!(assembler.next()) {
+            Poll::Ready(Some((block1, sender))) => {
+                assert_eq!(block1, block);
+                assert_eq!(sender.peer_id(), header_sender.propagation_source());
+            }
+            _ => panic!("Unexpected return value"),
+        }
+
+        assert!(assembler.cached_headers.is_empty());
+        assert!(assembler.cached_bodies.is_empty());
+    }
+
+    #[test(tokio::test)]
+    async fn it_requests_missing_transactions_of_compact_body() {
+        let (header_tx, header_rx) = mpsc::channel(16);
+        let (compact_body_tx, compact_body_rx) = mpsc::channel(16);
+
+        let transactions =
+            generate_transactions(&KeyPair::default(), 1, NetworkId::UnitAlbatross, 2, 0);
+        let body = MicroBody {
+            equivocation_proofs: vec![],
+            transactions: transactions
+                .iter()
+                .map(|tx| ExecutedTransaction::Ok(tx.clone()))
+                .collect(),
+        };
+        let block = Block::Micro(MicroBlock {
+            header: MicroHeader {
+                body_root: body.hash(),
+                ..Default::default()
+            },
+            justification: Some(MicroJustification::Micro(Ed25519Signature::default())),
+            body: Some(body),
+        });
+
+        // Only the first transaction is known locally, the other one has to be requested from
+        // the peer that sent the compact body.
+        let mut hub = MockHub::new();
+        let network = Arc::new(hub.new_network());
+        let peer = Arc::new(hub.new_network());
+        network.dial_mock(&peer);
+
+        let known_tx = transactions[0].clone();
+        let mut assembler = BlockAssembler::<MockNetwork>::new(
+            Arc::clone(&network),
+            ReceiverStream::new(header_rx).boxed(),
+            stream::pending().boxed(),
+            ReceiverStream::new(compact_body_rx).boxed(),
+            Arc::new(
+                move |block_hash: &Blake2bHash, short_ids: &[ShortTransactionId]| {
+                    short_ids
+                        .iter()
+                        .map(|short_id| {
+                            (ShortTransactionId::from_transaction(block_hash, &known_tx)
+                                == *short_id)
+                                .then(|| known_tx.clone())
+                        })
+                        .collect()
+                },
+            ),
+        );
+
+        let block_hash = block.hash();
+        let mut requests = peer.receive_requests::<RequestMissingTransactions>();
+        let peer1 = Arc::clone(&peer);
+        let missing_tx = transactions[1].clone();
+        spawn(async move {
+            let (request, request_id, _) = requests.next().await.unwrap();
+            assert_eq!(request.block_hash, block_hash);
+            assert_eq!(request.indexes, vec![1]);
+            peer1
+                .respond::<RequestMissingTransactions>(request_id, Ok(vec![missing_tx]))
+                .await
+                .unwrap();
+        });
+
+        let (header, body) = BlockHeaderMessage::split_block(block.clone());
+        let compact_body = body.to_compact(block.hash()).unwrap();
+
+        let header_sender = MockId::new(MockPeerId::from(0));
+        header_tx.try_send((header, header_sender.clone())).unwrap();
+        let body_sender = MockId::new(peer.peer_id());
+        compact_body_tx
+            .try_send((compact_body, body_sender))
+            .unwrap();
+
+        match timeout(Duration::from_secs(1), assembler.next()).await {
+            Ok(Some((block1, sender))) => {
+                assert_eq!(block1, block);
+                assert_eq!(sender.peer_id(), header_sender.propagation_source());
+            }
+            _ => panic!("Unexpected return value"),
+        }
+
+        assert!(assembler.cached_headers.is_empty());
+        assert!(assembler.cached_bodies.is_empty());
+    }
+
+    #[test(tokio::test)]
+    async fn it_requests_full_body_if_compact_body_does_not_match() {
+        let (header_tx, header_rx) = mpsc::channel(16);
+        let (compact_body_tx, compact_body_rx) = mpsc::channel(16);
+
+        let transactions =
+            generate_transactions(&KeyPair::default(), 1, NetworkId::UnitAlbatross, 3, 0);
+        let body = MicroBody {
+            equivocation_proofs: vec![],
+            transactions: transactions[..2]
+                .iter()
+                .map(|tx| ExecutedTransaction::Ok(tx.clone()))
+                .collect(),
+        };
+        let block = Block::Micro(MicroBlock {
+            header: MicroHeader {
+                body_root: body.hash(),
+                ..Default::default()
+            },
+            justification: Some(MicroJustification::Micro(Ed25519Signature::default())),
+            body: Some(body),
+        });
+        let block_hash = block.hash();
+
+        // The short id of the second transaction collides with the one of a different transaction
+        // known locally, such that the reconstructed body doesn't match the header.
+        let (header, body) = BlockHeaderMessage::split_block(block.clone());
+        let mut compact_body = body.to_compact(block_hash.clone()).unwrap();
+        let colliding_tx = transactions[2].clone();
+        compact_body.body.transactions[1].short_id =
+            ShortTransactionId::from_transaction(&block_hash, &colliding_tx);
+
+        let mut hub = MockHub::new();
+        let network = Arc::new(hub.new_network());
+        let peer = Arc::new(hub.new_network());
+        network.dial_mock(&peer);
+
+        let known_transactions = vec![transactions[0].clone(), colliding_tx];
+        let mut assembler = BlockAssembler::<MockNetwork>::new(
+            Arc::clone(&network),
+            ReceiverStream::new(header_rx).boxed(),
+            stream::pending().boxed(),
+            ReceiverStream::new(compact_body_rx).boxed(),
+            Arc::new(
+                move |block_hash: &Blake2bHash, short_ids: &[ShortTransactionId]| {
+                    short_ids
+                        .iter()
+                        .map(|short_id| {
+                            known_transactions
+                                .iter()
+                                .find(|tx| {
+                                    ShortTransactionId::from_transaction(block_hash, tx)
+                                        == *short_id
+                                })
+                                .cloned()
+                        })
+                        .collect()
+                },
+            ),
+        );
+
+        // The peer that sent the compact body is asked for the full block.
+        let mut requests = peer.receive_requests::<RequestBlock>();
+        let peer1 = Arc::clone(&peer);
+        let requested_block = block.clone();
+        spawn(async move {
+            let (request, request_id, _) = requests.next().await.unwrap();
+            assert_eq!(request.hash, requested_block.hash());
+            assert!(request.include_body);
+            peer1
+                .respond::<RequestBlock>(request_id, Ok(requested_block))
+                .await
+                .unwrap();
+        });
+
+        let header_sender = MockId::new(MockPeerId::from(0));
+        header_tx.try_send((header, header_sender.clone())).unwrap();
+        let body_sender = MockId::new(peer.peer_id());
+        compact_body_tx
+            .try_send((compact_body, body_sender))
+            .unwrap();
+
+        match timeout(Duration::from_secs(1), assembler.next()).await {
+            Ok(Some((block1, sender))) => {
+                assert_eq!(block1, block);
+                assert_eq!(sender.peer_id(), header_sender.propagation_source());
+            }
+            _ => panic!("Unexpected return value"),
+        }
+
+        assert!(assembler.cached_headers.is_empty());
+        assert!(assembler.cached_bodies.is_empty());
+        assert!(assembler.cached_reconstructed_bodies.is_empty());
+    }
+
     #[test(tokio::test)]
     async fn it_evicts_expired_items() {
         let ttl = Duration::from_secs(1);
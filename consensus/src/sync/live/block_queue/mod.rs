@@ -6,7 +6,7 @@ use tokio::sync::oneshot;
 
 use crate::{
     consensus::ResolveBlockError,
-    messages::{BlockBodyTopic, BlockHeaderTopic},
+    messages::{BlockBodyTopic, BlockHeaderTopic, CompactBlockBodyTopic},
 };
 
 mod assembler;
@@ -30,11 +30,46 @@ pub enum QueuedBlock<N: Network> {
     TooFarBehind(N::PeerId),
 }
 
+/// The gossipsub message a block body was announced with.
+#[derive(Debug)]
+pub enum BodyId<N: Network> {
+    /// The body was announced in full on the [`BlockBodyTopic`].
+    Full(N::PubsubId),
+    /// The body was announced on the [`CompactBlockBodyTopic`] and reconstructed locally.
+    Compact(N::PubsubId),
+}
+
+impl<N: Network> BodyId<N> {
+    pub fn propagation_source(&self) -> N::PeerId {
+        match self {
+            BodyId::Full(id) | BodyId::Compact(id) => id.propagation_source(),
+        }
+    }
+
+    pub fn validate_message(&self, network: &N, acceptance: MsgAcceptance) {
+        match self {
+            BodyId::Full(id) => network.validate_message::<BlockBodyTopic>(id.clone(), acceptance),
+            BodyId::Compact(id) => {
+                network.validate_message::<CompactBlockBodyTopic>(id.clone(), acceptance)
+            }
+        }
+    }
+}
+
+impl<N: Network> Clone for BodyId<N> {
+    fn clone(&self) -> Self {
+        match self {
+            BodyId::Full(id) => BodyId::Full(id.clone()),
+            BodyId::Compact(id) => BodyId::Compact(id.clone()),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum BlockSource<N: Network> {
     Announced {
         header_id: N::PubsubId,
-        body_id: Option<N::PubsubId>,
+        body_id: Option<BodyId<N>>,
     },
     Requested {
         id: N::PeerId,
@@ -42,7 +77,7 @@ pub enum BlockSource<N: Network> {
 }
 
 impl<N: Network> BlockSource<N> {
-    pub fn announced(header_id: N::PubsubId, body_id: Option<N::PubsubId>) -> Self {
+    pub fn announced(header_id: N::PubsubId, body_id: Option<BodyId<N>>) -> Self {
         Self::Announced { header_id, body_id }
     }
 
@@ -82,7 +117,7 @@ impl<N: Network> BlockSource<N> {
             BlockSource::Announced { header_id, body_id } => {
                 network.validate_message::<BlockHeaderTopic>(header_id.clone(), acceptance);
                 if let Some(body_id) = body_id {
-                    network.validate_message::<BlockBodyTopic>(body_id.clone(), acceptance);
+                    body_id.validate_message(network, acceptance);
                 }
             }
             BlockSource::Requested { .. } => {}
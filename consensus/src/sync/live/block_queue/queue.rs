@@ -10,7 +10,7 @@ use std::{
 };
 
 use futures::{stream::BoxStream, Stream, StreamExt};
-use nimiq_block::Block;
+use nimiq_block::{Block, ShortTransactionId};
 use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainEvent, Direction, ForkEvent};
 use nimiq_blockchain_proxy::BlockchainProxy;
 use nimiq_hash::Blake2bHash;
@@ -22,11 +22,12 @@ use tokio::sync::oneshot;
 
 use crate::{
     consensus::{ResolveBlockError, ResolveBlockRequest},
-    messages::{BlockBodyTopic, BlockHeaderTopic},
+    messages::{BlockBodyTopic, BlockHeaderTopic, CompactBlockBodyTopic},
     sync::{
         live::{
             block_queue::{
-                assembler::BlockAssembler, block_request_component::BlockRequestComponent,
+                assembler::{BlockAssembler, TransactionLookup},
+                block_request_component::BlockRequestComponent,
                 BlockAndSource, BlockSource, BlockStream, GossipSubBlockStream, QueuedBlock,
             },
             queue::{LiveSyncQueue, QueueConfig},
@@ -92,7 +93,19 @@ impl<N: Network> BlockQueue<N> {
 
         let block_stream = if config.include_body {
             let body_stream = network.subscribe::<BlockBodyTopic>().await.unwrap().boxed();
-            BlockAssembler::<N>::new(Arc::clone(&network), header_stream, body_stream).boxed()
+            let compact_body_stream = network
+                .subscribe::<CompactBlockBodyTopic>()
+                .await
+                .unwrap()
+                .boxed();
+            BlockAssembler::<N>::new(
+                Arc::clone(&network),
+                header_stream,
+                body_stream,
+                compact_body_stream,
+                Self::transaction_lookup(&blockchain),
+            )
+            .boxed()
         } else {
             header_stream
                 .map(|(header, pubsub_id)| (header.into(), BlockSource::announced(pubsub_id, None)))
@@ -102,6 +115,28 @@ impl<N: Network> BlockQueue<N> {
         Self::with_block_stream(blockchain, network, block_stream, config)
     }
 
+    /// Compact block bodies are reconstructed from the transactions known to the blockchain's
+    /// `TransactionVerificationCache`, i.e. the mempool. It is looked up on every use since the mempool is set up after the syncer.
+    fn transaction_lookup(blockchain: &BlockchainProxy) -> TransactionLookup {
+        match blockchain {
+            #[cfg(feature = "full")]
+            BlockchainProxy::Full(blockchain) => {
+                let blockchain = Arc::clone(blockchain);
+                Arc::new(
+                    move |block_hash: &Blake2bHash, short_ids: &[ShortTransactionId]| {
+                        let cache = Arc::clone(&blockchain.read().tx_verification_cache);
+                        cache.get_by_short_ids(block_hash, short_ids)
+                    },
+                )
+            }
+            BlockchainProxy::Light(_) => {
+                Arc::new(|_: &Blake2bHash, short_ids: &[ShortTransactionId]| {
+                    vec![None; short_ids.len()]
+                })
+            }
+        }
+    }
+
     pub fn with_gossipsub_block_stream(
         blockchain: BlockchainProxy,
         network: Arc<N>,
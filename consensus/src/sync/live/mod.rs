@@ -16,7 +16,7 @@ use tokio_stream::wrappers::ReceiverStream;
 #[cfg(feature = "full")]
 use self::state_queue::StateQueue;
 use self::{block_queue::BlockQueue, queue::LiveSyncQueue};
-use super::syncer::{LiveSync, LiveSyncEvent};
+use super::syncer::{LiveSync, LiveSyncEvent, SyncProgress};
 use crate::{
     consensus::ResolveBlockRequest,
     sync::live::block_queue::{BlockAndSource, BlockSource},
@@ -103,6 +103,10 @@ impl<N: Network, Q: LiveSyncQueue<N>> LiveSync<N> for LiveSyncer<N, Q> {
         self.queue.state_complete()
     }
 
+    fn state_sync_progress(&self) -> Option<SyncProgress> {
+        self.queue.state_sync_progress()
+    }
+
     fn resolve_block(&mut self, request: ResolveBlockRequest<N>) {
         self.queue.resolve_block(request)
     }
@@ -29,7 +29,7 @@ use crate::{
     consensus::ResolveBlockRequest,
     sync::{
         live::block_queue::{BlockAndSource, BlockSource},
-        syncer::LiveSyncEvent,
+        syncer::{LiveSyncEvent, SyncProgress},
     },
     BlsCache,
 };
@@ -112,6 +112,10 @@ pub trait LiveSyncQueue<N: Network>: Stream<Item = Self::QueueResult> + Send + U
         true
     }
 
+    fn state_sync_progress(&self) -> Option<SyncProgress> {
+        None
+    }
+
     /// Initiates an attempt to resolve a ResolveBlockRequest.
     fn resolve_block(&mut self, request: ResolveBlockRequest<N>);
 
@@ -431,6 +435,8 @@ impl<N: Network> PostValidationHook for MessageValidator<N> {
                 }
                 PushResult::Forked | PushResult::Ignored => MsgAcceptance::Ignore,
             },
+            // The block was rejected by local policy, not because it is invalid.
+            Err(PushError::Vetoed(_)) => MsgAcceptance::Ignore,
             Err(_) => {
                 // TODO Ban peer
                 MsgAcceptance::Reject
@@ -21,7 +21,7 @@ use crate::{
             block_queue::{live_sync::PushOpResult as BlockPushOpResult, BlockAndSource},
             queue::{self, LiveSyncQueue},
         },
-        syncer::{LiveSyncEvent, LiveSyncPeerEvent, LiveSyncPushEvent},
+        syncer::{LiveSyncEvent, LiveSyncPeerEvent, LiveSyncPushEvent, SyncProgress},
     },
     BlsCache,
 };
@@ -79,6 +79,18 @@ impl<N: Network> PushOpResult<N> {
         }
     }
 
+    pub fn committed_chunks(&self) -> usize {
+        match self {
+            PushOpResult::Head(_, Ok(ChunksPushResult::Chunks(committed, _)), _)
+            | PushOpResult::HeadChunk(Ok(ChunksPushResult::Chunks(committed, _)), _)
+            | PushOpResult::Buffered(_, Ok(ChunksPushResult::Chunks(committed, _)), _)
+            | PushOpResult::Missing(_, Ok(ChunksPushResult::Chunks(committed, _)), _, _) => {
+                *committed
+            }
+            _ => 0,
+        }
+    }
+
     fn into_block_push_result(self) -> Option<BlockPushOpResult<N>> {
         match self {
             PushOpResult::Head(push_result, _, block_hash) => {
@@ -205,6 +217,10 @@ impl<N: Network> LiveSyncQueue<N> for StateQueue<N> {
             self.reset_chunk_request_chain();
         }
 
+        if item.committed_chunks() > 0 {
+            self.reload_progress();
+        }
+
         match item {
             PushOpResult::HeadChunk(Ok(ChunksPushResult::Chunks(committed, _)), block_hash)
                 if committed > 0 =>
@@ -267,6 +283,17 @@ impl<N: Network> LiveSyncQueue<N> for StateQueue<N> {
         self.start_key.is_complete()
     }
 
+    fn state_sync_progress(&self) -> Option<SyncProgress> {
+        if self.start_key.is_complete() {
+            return None;
+        }
+
+        Some(SyncProgress {
+            chunks_applied: self.progress.chunks_applied,
+            estimated_total: self.progress.estimated_total(),
+        })
+    }
+
     fn resolve_block(&mut self, request: ResolveBlockRequest<N>) {
         self.diff_queue.resolve_block(request)
     }
@@ -11,7 +11,7 @@ use std::{
 
 use futures::{future::BoxFuture, stream::BoxStream, Stream, StreamExt};
 use nimiq_block::Block;
-use nimiq_blockchain::Blockchain;
+use nimiq_blockchain::{chain_store::StateSyncProgress, Blockchain};
 use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainEvent};
 use nimiq_hash::Blake2bHash;
 use nimiq_network_interface::{
@@ -87,6 +87,7 @@ impl RequestCommon for RequestChunk {
     type Response = ResponseChunk;
 
     const MAX_REQUESTS: u32 = MAX_REQUEST_RESPONSE_CHUNKS;
+    const SEEDING: bool = true;
 }
 
 pub enum QueuedStateChunks<N: Network> {
@@ -171,6 +172,9 @@ pub struct StateQueue<N: Network> {
     /// or a rebranch event.
     start_key: ChunkRequestState,
 
+    /// The progress of the state sync, as persisted by the blockchain.
+    progress: StateSyncProgress,
+
     /// The blockchain event stream.
     blockchain_rx: BoxStream<'static, BlockchainEvent>,
 
@@ -197,6 +201,7 @@ impl<N: Network> StateQueue<N> {
         let current_macro_height = Policy::last_macro_block(bc.block_number());
         let blockchain_rx = bc.notifier_as_stream();
         let accounts_complete = bc.accounts_complete();
+        let progress = bc.get_state_sync_progress().unwrap_or_default();
         drop(bc);
 
         // When initializing the state sync, we assume it to be complete if we have the full state.
@@ -218,6 +223,7 @@ impl<N: Network> StateQueue<N> {
             buffer_size: 0,
             current_macro_height,
             start_key,
+            progress,
             blockchain_rx,
             peers_became_nonempty: None,
         }
@@ -479,6 +485,16 @@ impl<N: Network> StateQueue<N> {
         }
     }
 
+    /// Reloads the state sync progress after chunks were committed. The blockchain persists the
+    /// progress together with the chunks, such that it survives restarts.
+    fn reload_progress(&mut self) {
+        self.progress = self
+            .blockchain
+            .read()
+            .get_state_sync_progress()
+            .unwrap_or_default();
+    }
+
     pub fn num_buffered_chunks(&self) -> usize {
         self.buffer_size
     }
@@ -1,7 +1,7 @@
 use std::{
     cmp,
     cmp::Ordering,
-    collections::{BinaryHeap, VecDeque},
+    collections::{BinaryHeap, HashMap, VecDeque},
     fmt::{Debug, Display, Formatter},
     future::Future,
     pin::Pin,
@@ -20,45 +20,48 @@ use crate::sync::peer_list::PeerListIndex;
 
 #[pin_project]
 #[derive(Debug)]
-struct OrderWrapper<TId, TOutput> {
+struct OrderWrapper<TId, TPeerId, TOutput> {
     id: TId,
     #[pin]
     data: TOutput, // A future or a future's output
     index: usize,
-    peer: PeerListIndex, // The peer the data is requested from
-    num_tries: usize,    // The number of tries this id has been requested
+    peer: PeerListIndex,      // The peer the data is requested from
+    peer_id: Option<TPeerId>, // The id of the peer the data is requested from, if any
+    num_tries: usize,         // The number of tries this id has been requested
 }
 
-impl<TId: Clone, TOutput: Future> Future for OrderWrapper<TId, TOutput> {
-    type Output = OrderWrapper<TId, TOutput::Output>;
+impl<TId: Clone, TPeerId: Clone, TOutput: Future> Future for OrderWrapper<TId, TPeerId, TOutput> {
+    type Output = OrderWrapper<TId, TPeerId, TOutput::Output>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
         let id = self.id.clone();
         let index = self.index;
         let peer = self.peer.clone();
+        let peer_id = self.peer_id.clone();
         let num_tries = self.num_tries;
         self.project().data.poll(cx).map(|output| OrderWrapper {
             id,
             data: output,
             index,
             peer,
+            peer_id,
             num_tries,
         })
     }
 }
 
-impl<TId, TOutput> PartialEq for OrderWrapper<TId, TOutput> {
+impl<TId, TPeerId, TOutput> PartialEq for OrderWrapper<TId, TPeerId, TOutput> {
     fn eq(&self, other: &Self) -> bool {
         self.index == other.index
     }
 }
-impl<TId, TOutput> Eq for OrderWrapper<TId, TOutput> {}
-impl<TId, TOutput> PartialOrd for OrderWrapper<TId, TOutput> {
+impl<TId, TPeerId, TOutput> Eq for OrderWrapper<TId, TPeerId, TOutput> {}
+impl<TId, TPeerId, TOutput> PartialOrd for OrderWrapper<TId, TPeerId, TOutput> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
-impl<TId, TOutput> Ord for OrderWrapper<TId, TOutput> {
+impl<TId, TPeerId, TOutput> Ord for OrderWrapper<TId, TPeerId, TOutput> {
     fn cmp(&self, other: &Self) -> Ordering {
         // BinaryHeap is a max heap, so compare backwards here.
         other.index.cmp(&self.index)
@@ -94,10 +97,20 @@ pub struct SyncQueue<
     pub(crate) peers: Arc<RwLock<PeerList<TNetwork>>>,
     network: Arc<TNetwork>,
     desired_pending_size: usize,
+    /// The number of pending requests per peer. Scales the number of pending requests with the
+    /// number of peers, up to `max_pending_size`.
+    pending_per_peer: usize,
+    max_pending_size: usize,
+    /// The number of consecutive failed requests after which a peer is removed from the peer list.
+    max_peer_failures: Option<usize>,
+    /// The number of consecutive failed requests per peer.
+    peer_failures: HashMap<TNetwork::PeerId, usize>,
     ids_to_request: VecDeque<(TId, Option<TNetwork::PeerId>)>,
-    pending_futures:
-        FuturesUnordered<OrderWrapper<TId, BoxFuture<'static, Option<Result<TOutput, TError>>>>>,
-    queued_outputs: BinaryHeap<OrderWrapper<TId, Option<TOutput>>>,
+    #[allow(clippy::type_complexity)]
+    pending_futures: FuturesUnordered<
+        OrderWrapper<TId, TNetwork::PeerId, BoxFuture<'static, Option<Result<TOutput, TError>>>>,
+    >,
+    queued_outputs: BinaryHeap<OrderWrapper<TId, TNetwork::PeerId, Option<TOutput>>>,
     next_incoming_index: usize,
     next_outgoing_index: usize,
     current_peer_index: PeerListIndex,
@@ -158,6 +171,10 @@ where
             network,
             peers,
             desired_pending_size,
+            pending_per_peer: 0,
+            max_pending_size: desired_pending_size,
+            max_peer_failures: None,
+            peer_failures: HashMap::new(),
             ids_to_request: VecDeque::from(ids),
             pending_futures: FuturesUnordered::new(),
             queued_outputs: BinaryHeap::new(),
@@ -171,13 +188,64 @@ where
         }
     }
 
+    /// Spreads the requests across all peers: the number of pending requests grows by
+    /// `pending_per_peer` for each peer, but never exceeds `max_pending_size`.
+    #[cfg(feature = "full")]
+    pub fn with_pending_per_peer(
+        mut self,
+        pending_per_peer: usize,
+        max_pending_size: usize,
+    ) -> Self {
+        self.pending_per_peer = pending_per_peer;
+        self.max_pending_size = cmp::max(max_pending_size, self.desired_pending_size);
+        self
+    }
+
+    /// Removes a peer from the peer list once `max_peer_failures` consecutive requests to it failed.
+    /// Its pending and future requests are served by the remaining peers.
+    #[cfg(feature = "full")]
+    pub fn with_max_peer_failures(mut self, max_peer_failures: usize) -> Self {
+        self.max_peer_failures = Some(max_peer_failures);
+        self
+    }
+
+    /// The number of pending requests to maintain given the current number of peers.
+    fn pending_size(&self) -> usize {
+        let scaled_pending_size = self.pending_per_peer * self.peers.read().len();
+        cmp::max(
+            self.desired_pending_size,
+            cmp::min(scaled_pending_size, self.max_pending_size),
+        )
+    }
+
+    fn on_peer_success(&mut self, peer_id: Option<TNetwork::PeerId>) {
+        if let Some(peer_id) = peer_id {
+            self.peer_failures.remove(&peer_id);
+        }
+    }
+
+    fn on_peer_failure(&mut self, peer_id: Option<TNetwork::PeerId>) {
+        let (Some(peer_id), Some(max_peer_failures)) = (peer_id, self.max_peer_failures) else {
+            return;
+        };
+
+        let num_failures = self.peer_failures.entry(peer_id).or_default();
+        *num_failures += 1;
+        if *num_failures >= max_peer_failures {
+            self.peer_failures.remove(&peer_id);
+            if self.peers.write().remove_peer(&peer_id) {
+                debug!(%peer_id, max_peer_failures, "Removing peer after consecutive failed requests");
+            }
+        }
+    }
+
     fn try_push_futures(&mut self) {
-        // Determine number of new futures required to maintain desired_pending_size.
+        // Determine number of new futures required to maintain the pending size.
         let num_ids_to_request = cmp::min(
             self.ids_to_request.len(), // At most all of the ids
             // The number of pending futures can be higher than the desired pending size
             // (e.g., if there is an error and we re-request)
-            self.desired_pending_size
+            self.pending_size()
                 .saturating_sub(self.pending_futures.len() + self.queued_outputs.len()),
         );
 
@@ -221,6 +289,7 @@ where
                         id,
                         index: self.next_incoming_index,
                         peer: peer_index,
+                        peer_id: Some(peer_id),
                         num_tries: 1,
                     }
                 }
@@ -229,6 +298,7 @@ where
                     id,
                     index: self.next_incoming_index,
                     peer: PeerListIndex::default(),
+                    peer_id: None,
                     num_tries: 1,
                 },
             };
@@ -243,7 +313,7 @@ where
                 "Requesting {} ids (ids_to_request={}, remaining_until_limit={}, pending_futures={}, queued_outputs={}, num_peers={})",
                 num_ids_to_request,
                 self.ids_to_request.len(),
-                self.desired_pending_size
+                self.pending_size()
                     .saturating_sub(self.pending_futures.len() + self.queued_outputs.len()),
                 self.pending_futures.len(),
                 self.queued_outputs.len(),
@@ -295,6 +365,7 @@ where
             id,
             index,
             peer: peer_index,
+            peer_id: Some(peer),
             num_tries: num_tries + 1,
         };
 
@@ -379,6 +450,7 @@ where
                             return Poll::Ready(Some(Ok(data)));
                         } else {
                             debug!(peer_id = %request.peer, id = ?request.id, "Verification failed");
+                            self.on_peer_failure(request.peer_id);
                             let id = request.id.clone();
                             if !self.retry_request(
                                 request.id,
@@ -404,6 +476,7 @@ where
                 Poll::Ready(Some(result)) => {
                     match result.data {
                         Some(Ok(mut output)) => {
+                            self.on_peer_success(result.peer_id);
                             if result.index == self.next_outgoing_index {
                                 if (self.verify_fn)(&result.id, &mut output, &mut self.verify_state)
                                 {
@@ -411,6 +484,7 @@ where
                                     return Poll::Ready(Some(Ok(output)));
                                 } else {
                                     debug!(peer_id = %result.peer, id = ?result.id, "Verification failed");
+                                    self.on_peer_failure(result.peer_id);
                                 }
                             } else {
                                 self.queued_outputs.push(OrderWrapper {
@@ -418,6 +492,7 @@ where
                                     data: Some(output),
                                     index: result.index,
                                     peer: result.peer,
+                                    peer_id: result.peer_id,
                                     num_tries: result.num_tries,
                                 });
                                 continue;
@@ -425,6 +500,7 @@ where
                         }
                         Some(Err(error)) => {
                             debug!(peer_id = %result.peer, id = ?result.id, %error, "Request error");
+                            self.on_peer_failure(result.peer_id);
                         }
                         None => {
                             debug!(id = ?result.id, "Request error: no peers available");
@@ -444,6 +520,7 @@ where
                                 data: None,
                                 index: result.index,
                                 peer,
+                                peer_id: None,
                                 num_tries: result.num_tries,
                             });
                         }
@@ -484,10 +561,11 @@ mod tests {
     };
 
     use futures::{future, task::noop_waker_ref, FutureExt, StreamExt};
-    use nimiq_network_mock::MockHub;
+    use nimiq_network_mock::{MockHub, MockPeerId};
+    use parking_lot::RwLock;
     use thiserror::Error;
 
-    use crate::sync::sync_queue::SyncQueue;
+    use crate::sync::{peer_list::PeerList, sync_queue::SyncQueue};
 
     #[test]
     fn it_can_handle_no_peers() {
@@ -511,4 +589,49 @@ mod tests {
             _ => panic!("Expected error"),
         };
     }
+
+    #[test]
+    fn it_removes_failing_peers() {
+        #[derive(Debug, Error)]
+        #[error("error")]
+        struct Error;
+
+        let mut hub = MockHub::new();
+        let network = Arc::new(hub.new_network());
+
+        let mut peers = PeerList::default();
+        peers.add_peer(MockPeerId(1));
+        peers.add_peer(MockPeerId(2));
+        let peers = Arc::new(RwLock::new(peers));
+
+        let mut queue: SyncQueue<_, _, i32, Error, _> = SyncQueue::new(
+            network,
+            vec![(1, None), (2, None), (3, None), (4, None)],
+            Arc::clone(&peers),
+            1,
+            |id, _, peer_id| {
+                if peer_id == MockPeerId(1) {
+                    future::ready(Err(Error)).boxed()
+                } else {
+                    future::ready(Ok(id)).boxed()
+                }
+            },
+        )
+        .with_pending_per_peer(2, 4)
+        .with_max_peer_failures(1);
+
+        let mut cx = Context::from_waker(noop_waker_ref());
+        let mut results = vec![];
+        for _ in 0..100 {
+            if let Poll::Ready(Some(result)) = queue.poll_next_unpin(&mut cx) {
+                results.push(result);
+            }
+            if results.len() == 4 {
+                break;
+            }
+        }
+
+        assert_eq!(results, vec![Ok(1), Ok(2), Ok(3), Ok(4)]);
+        assert_eq!(peers.read().peers(), &[MockPeerId(2)]);
+    }
 }
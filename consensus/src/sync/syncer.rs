@@ -54,6 +54,11 @@ pub trait LiveSync<N: Network>: Stream<Item = LiveSyncEvent<N::PeerId>> + Unpin
     fn state_complete(&self) -> bool {
         true
     }
+    /// Returns the progress of the state sync while it is running (or `None` if there is no
+    /// state sync in progress)
+    fn state_sync_progress(&self) -> Option<SyncProgress> {
+        None
+    }
     /// Initiates an attempt to resolve a ResolveBlockRequest.
     fn resolve_block(&mut self, request: ResolveBlockRequest<N>);
     /// The maximum number of blocks a peer can be ahead before it is considered out-of-sync.
@@ -97,6 +102,15 @@ pub enum LiveSyncPushEvent {
     AcceptedChunks(Blake2bHash),
 }
 
+/// The progress of a running state sync.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SyncProgress {
+    /// The number of chunks applied to the accounts trie, including those applied before a restart.
+    pub chunks_applied: u64,
+    /// The estimated total number of chunks, if an estimation is possible yet.
+    pub estimated_total: Option<u64>,
+}
+
 #[derive(Clone, Debug)]
 /// Enumeration for the LiveSync stream events related to peers
 pub enum LiveSyncPeerEvent<TPeerId> {
@@ -187,6 +201,19 @@ impl<N: Network, M: MacroSync<N::PeerId>, L: LiveSync<N>> Syncer<N, M, L> {
         self.live_sync.add_peer(peer_id);
     }
 
+    /// Adds a peer to the syncer. The peer is first handed to the macro sync.
+    pub fn add_peer(&mut self, peer_id: N::PeerId) {
+        self.move_peer_into_macro_sync(peer_id);
+    }
+
+    pub fn blockchain(&self) -> &BlockchainProxy {
+        &self.blockchain
+    }
+
+    pub fn network(&self) -> Arc<N> {
+        Arc::clone(&self.network)
+    }
+
     pub fn num_peers(&self) -> usize {
         self.live_sync.num_peers()
     }
@@ -203,6 +230,10 @@ impl<N: Network, M: MacroSync<N::PeerId>, L: LiveSync<N>> Syncer<N, M, L> {
         self.live_sync.state_complete()
     }
 
+    pub fn state_sync_progress(&self) -> Option<SyncProgress> {
+        self.live_sync.state_sync_progress()
+    }
+
     /// Initiates an attempt to resolve a ResolveBlockRequest.
     pub fn resolve_block(&mut self, request: ResolveBlockRequest<N>) {
         self.live_sync.resolve_block(request)
@@ -1,6 +1,7 @@
 #[cfg(feature = "full")]
 use std::cmp::max;
 use std::{
+    fmt,
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
@@ -30,9 +31,9 @@ use crate::{
             queue::QueueConfig,
             BlockLiveSync,
         },
-        syncer::{LiveSyncPushEvent, Syncer},
+        syncer::{LiveSyncPushEvent, SyncProgress, Syncer},
     },
-    BlsCache,
+    BlsCache, SyncerModeError,
 };
 
 macro_rules! gen_syncer_match {
@@ -47,6 +48,39 @@ macro_rules! gen_syncer_match {
     };
 }
 
+/// The synchronization strategy used by a `SyncerProxy`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SyncerMode {
+    /// History sync: Downloads the full history and keeps the state.
+    History,
+    /// Full sync: Light macro sync followed by a state sync of the accounts tree.
+    Full,
+    /// Light sync: Light macro sync without any state.
+    Light,
+}
+
+impl SyncerMode {
+    /// Returns whether this mode requires a full blockchain.
+    pub fn requires_full_blockchain(&self) -> bool {
+        match self {
+            SyncerMode::History | SyncerMode::Full => true,
+            SyncerMode::Light => false,
+        }
+    }
+
+    /// Returns whether a syncer running in this mode can be switched to `target` without
+    /// restarting the node.
+    pub fn can_switch_to(&self, target: SyncerMode) -> bool {
+        matches!((self, target), (SyncerMode::Light, SyncerMode::Full))
+    }
+}
+
+impl fmt::Display for SyncerMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
 #[pin_project(project = SyncerProxyProj)]
 /// The `SyncerProxy` is an abstraction over multiple types of `Syncer`s.
 pub enum SyncerProxy<N: Network> {
@@ -61,6 +95,55 @@ pub enum SyncerProxy<N: Network> {
 }
 
 impl<N: Network> SyncerProxy<N> {
+    /// Creates a new instance of a `SyncerProxy` using the given sync `mode`.
+    /// The `full_sync_threshold` is only used by the `Full` mode.
+    ///
+    /// Panics if `mode` requires a full blockchain but a light blockchain was given or if
+    /// the mode is not supported with the enabled features.
+    pub async fn new(
+        mode: SyncerMode,
+        blockchain_proxy: BlockchainProxy,
+        network: Arc<N>,
+        bls_cache: Arc<Mutex<BlsCache>>,
+        zkp_component_proxy: ZKPComponentProxy<N>,
+        network_event_rx: SubscribeEvents<N::PeerId>,
+        full_sync_threshold: u32,
+    ) -> Self {
+        match mode {
+            #[cfg(feature = "full")]
+            SyncerMode::History => {
+                Self::new_history(blockchain_proxy, network, bls_cache, network_event_rx).await
+            }
+            #[cfg(feature = "full")]
+            SyncerMode::Full => {
+                Self::new_full(
+                    blockchain_proxy,
+                    network,
+                    bls_cache,
+                    zkp_component_proxy,
+                    network_event_rx,
+                    full_sync_threshold,
+                )
+                .await
+            }
+            #[cfg(not(feature = "full"))]
+            SyncerMode::History | SyncerMode::Full => {
+                let _ = full_sync_threshold;
+                panic!("{mode} sync requires the full feature to be enabled")
+            }
+            SyncerMode::Light => {
+                Self::new_light(
+                    blockchain_proxy,
+                    network,
+                    bls_cache,
+                    zkp_component_proxy,
+                    network_event_rx,
+                )
+                .await
+            }
+        }
+    }
+
     #[cfg(feature = "full")]
     /// Creates a new instance of a `SyncerProxy` for the `History` variant
     pub async fn new_history(
@@ -207,6 +290,89 @@ impl<N: Network> SyncerProxy<N> {
         ))
     }
 
+    /// Returns the sync mode this syncer is running in
+    pub fn mode(&self) -> SyncerMode {
+        match self {
+            #[cfg(feature = "full")]
+            SyncerProxy::History(_) => SyncerMode::History,
+            #[cfg(feature = "full")]
+            SyncerProxy::Full(_) => SyncerMode::Full,
+            SyncerProxy::Light(_) => SyncerMode::Light,
+        }
+    }
+
+    /// Returns a proxy to the blockchain this syncer is operating on
+    pub fn blockchain(&self) -> &BlockchainProxy {
+        gen_syncer_match!(self, blockchain)
+    }
+
+    /// Checks whether this syncer can be switched to the `target` mode in-place.
+    pub fn check_switch_to(&self, target: SyncerMode) -> Result<(), SyncerModeError> {
+        let current = self.mode();
+        if current == target {
+            return Err(SyncerModeError::AlreadyInMode(current));
+        }
+        if !current.can_switch_to(target) {
+            return Err(SyncerModeError::UnsupportedTransition {
+                from: current,
+                to: target,
+            });
+        }
+        #[cfg(feature = "full")]
+        let has_full_blockchain = matches!(self.blockchain(), BlockchainProxy::Full(_));
+        #[cfg(not(feature = "full"))]
+        let has_full_blockchain = false;
+        if target.requires_full_blockchain() && !has_full_blockchain {
+            return Err(SyncerModeError::RequiresFullBlockchain(target));
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "full")]
+    /// Upgrades a light syncer to a full syncer in-place.
+    ///
+    /// The new syncer operates on the same blockchain and network. All peers the current syncer
+    /// is live syncing with are handed over to the new syncer, which then performs the state sync
+    /// in the background.
+    pub async fn upgrade_to_full(
+        &mut self,
+        bls_cache: Arc<Mutex<BlsCache>>,
+        zkp_component_proxy: ZKPComponentProxy<N>,
+        full_sync_threshold: u32,
+    ) -> Result<(), SyncerModeError> {
+        self.check_switch_to(SyncerMode::Full)?;
+
+        let network = gen_syncer_match!(self, network);
+        let network_event_rx = network.subscribe_events();
+        let syncer = Self::new_full(
+            self.blockchain().clone(),
+            network,
+            bls_cache,
+            zkp_component_proxy,
+            network_event_rx,
+            full_sync_threshold,
+        )
+        .await;
+
+        self.replace(syncer);
+        Ok(())
+    }
+
+    /// Replaces this syncer by `syncer`, handing over all peers of the current syncer.
+    pub fn replace(&mut self, syncer: SyncerProxy<N>) {
+        let old_syncer = std::mem::replace(self, syncer);
+        info!(from = %old_syncer.mode(), to = %self.mode(), "Switched sync mode");
+
+        for peer_id in old_syncer.peers() {
+            self.add_peer(peer_id);
+        }
+    }
+
+    /// Adds a peer to the syncer. The peer will go through macro sync before being live synced.
+    pub fn add_peer(&mut self, peer_id: N::PeerId) {
+        gen_syncer_match!(self, add_peer, peer_id)
+    }
+
     /// Pushes a block for the live sync method
     pub fn push_block(&mut self, block: Block, block_source: BlockSource<N>) {
         gen_syncer_match!(self, push_block, block, block_source)
@@ -232,6 +398,11 @@ impl<N: Network> SyncerProxy<N> {
         gen_syncer_match!(self, state_complete)
     }
 
+    /// Returns the progress of the state sync while it is running
+    pub fn state_sync_progress(&self) -> Option<SyncProgress> {
+        gen_syncer_match!(self, state_sync_progress)
+    }
+
     pub fn resolve_block(&mut self, request: ResolveBlockRequest<N>) {
         gen_syncer_match!(self, resolve_block, request)
     }
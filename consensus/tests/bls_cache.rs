@@ -0,0 +1,76 @@
+use nimiq_bls::{KeyPair, LazyPublicKey};
+use nimiq_consensus::{BlsCache, BlsCacheStats};
+use nimiq_test_log::test;
+use nimiq_utils::key_rng::SecureGenerate;
+
+fn generate_keys(num: usize) -> Vec<LazyPublicKey> {
+    (0..num)
+        .map(|_| {
+            LazyPublicKey::from_compressed(
+                &KeyPair::generate_default_csprng().public_key.compress(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn it_counts_hits_misses_and_evictions() {
+    let keys = generate_keys(3);
+    let mut cache = BlsCache::with_capacity(2);
+
+    cache.cache(&keys[0]);
+    cache.cache(&keys[0]);
+    cache.cache(&keys[1]);
+    cache.cache(&keys[2]);
+
+    assert_eq!(cache.len(), 2);
+    assert_eq!(
+        cache.stats(),
+        BlsCacheStats {
+            hits: 1,
+            misses: 3,
+            evictions: 1,
+        }
+    );
+}
+
+#[test]
+fn it_evicts_keys_seen_once_first() {
+    let keys = generate_keys(10);
+    let mut cache = BlsCache::with_capacity(5);
+
+    // Promote the first key to the protected segment.
+    cache.cache(&keys[0]);
+    cache.cache(&keys[0]);
+
+    // A scan of keys seen only once doesn't evict the protected key.
+    for key in &keys[1..] {
+        cache.cache(key);
+    }
+    assert_eq!(cache.len(), 5);
+
+    let hits = cache.stats().hits;
+    cache.cache(&keys[0]);
+    assert_eq!(cache.stats().hits, hits + 1);
+}
+
+#[test]
+fn it_evicts_when_shrinking() {
+    let keys = generate_keys(10);
+    let mut cache = BlsCache::with_capacity(10);
+    for key in &keys {
+        cache.cache(key);
+        cache.cache(key);
+    }
+    assert_eq!(cache.len(), 10);
+
+    cache.set_capacity(4);
+    assert_eq!(cache.capacity(), 4);
+    assert_eq!(cache.len(), 4);
+    assert_eq!(cache.stats().evictions, 6);
+
+    // The most recently used keys are retained.
+    let hits = cache.stats().hits;
+    cache.cache(&keys[9]);
+    assert_eq!(cache.stats().hits, hits + 1);
+}
@@ -122,3 +122,88 @@ async fn test_request_transactions_by_address() {
         Policy::blocks_per_batch() - 1
     );
 }
+
+#[test(tokio::test)]
+async fn test_prove_inherents_by_block_range() {
+    let mut hub = MockHub::default();
+
+    // Create one node with a full epoch, such that the inherents are in a finalized epoch.
+    let blockchain1 = Arc::new(RwLock::new(
+        Blockchain::new(
+            MdbxDatabase::new_volatile(Default::default()).unwrap(),
+            BlockchainConfig::default(),
+            NetworkId::UnitAlbatross,
+            Arc::new(OffsetTime::new()),
+        )
+        .unwrap(),
+    ));
+
+    let producer = BlockProducer::new(signing_key(), voting_key());
+    let num_macro_blocks = (Policy::batches_per_epoch() + 1) as usize;
+    produce_macro_blocks(&producer, &blockchain1, num_macro_blocks);
+
+    let net1 = Arc::new(hub.new_network());
+    let zkp_prover1 =
+        ZKPComponent::new(BlockchainProxy::from(&blockchain1), Arc::clone(&net1), None)
+            .await
+            .proxy();
+    let blockchain1_proxy = BlockchainProxy::from(&blockchain1);
+
+    let syncer1 = SyncerProxy::new_history(
+        blockchain1_proxy.clone(),
+        Arc::clone(&net1),
+        Arc::new(Mutex::new(BlsCache::new_test())),
+        net1.subscribe_events(),
+    )
+    .await;
+
+    let _consensus1 = Consensus::from_network(
+        blockchain1_proxy.clone(),
+        Arc::clone(&net1),
+        syncer1,
+        zkp_prover1.clone(),
+    );
+
+    let net2 = Arc::new(hub.new_network());
+    let syncer2 = SyncerProxy::new_history(
+        blockchain1_proxy.clone(),
+        Arc::clone(&net2),
+        Arc::new(Mutex::new(BlsCache::new_test())),
+        net2.subscribe_events(),
+    )
+    .await;
+    let consensus2 = Consensus::from_network(
+        blockchain1_proxy.clone(),
+        Arc::clone(&net2),
+        syncer2,
+        zkp_prover1,
+    );
+    let consensus_proxy = consensus2.proxy();
+    net1.dial_mock(&net2);
+
+    // The rewards of the first batch are paid out in the second checkpoint block.
+    let start_block = Policy::genesis_block_number() + 1;
+    let end_block = Policy::macro_block_after(start_block) + Policy::blocks_per_batch();
+    let inherents = consensus_proxy
+        .prove_inherents_by_block_range(start_block, end_block, 1)
+        .await
+        .unwrap();
+
+    assert!(!inherents.is_empty());
+    assert!(inherents.iter().all(|hist_tx| hist_tx.is_not_basic()
+        && hist_tx.block_number >= start_block
+        && hist_tx.block_number <= end_block));
+    assert!(inherents
+        .iter()
+        .any(|hist_tx| matches!(hist_tx.data, HistoricTransactionData::Reward(_))));
+
+    // Ranges spanning several epochs can't be proven at once.
+    assert!(consensus_proxy
+        .prove_inherents_by_block_range(
+            start_block,
+            Policy::election_block_after(start_block) + 1,
+            1
+        )
+        .await
+        .is_err());
+}
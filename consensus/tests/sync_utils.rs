@@ -8,7 +8,7 @@ use nimiq_consensus::{
     consensus::Consensus,
     messages::{BlockBodyTopic, BlockHeaderMessage, BlockHeaderTopic},
     sync::{syncer::MacroSyncReturn, syncer_proxy::SyncerProxy},
-    BlsCache,
+    BlsCache, ConsensusEvent,
 };
 use nimiq_database::mdbx::MdbxDatabase;
 use nimiq_genesis::NetworkId;
@@ -202,7 +202,9 @@ pub async fn sync_two_peers(
             BlockchainEvent::Finalized(_) | BlockchainEvent::EpochFinalized(_)
         ))
     });
-    let mut consensus_events = consensus2_proxy.subscribe_events();
+    let mut consensus_events = consensus2_proxy
+        .subscribe_events()
+        .filter(|event| future::ready(matches!(event, Ok(ConsensusEvent::Established { .. }))));
     spawn(consensus2);
 
     for _ in 0..num_batches_live_sync {
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use nimiq_blockchain::{Blockchain, BlockchainConfig};
+use nimiq_blockchain_proxy::BlockchainProxy;
+use nimiq_consensus::{
+    sync::syncer_proxy::SyncerProxy, BlsCache, Consensus, SyncerMode, SyncerModeError,
+};
+use nimiq_database::mdbx::MdbxDatabase;
+use nimiq_network_interface::network::Network;
+use nimiq_network_mock::MockHub;
+use nimiq_primitives::networks::NetworkId;
+use nimiq_test_log::test;
+use nimiq_utils::{spawn, time::OffsetTime};
+use nimiq_zkp_component::ZKPComponent;
+use parking_lot::{Mutex, RwLock};
+
+#[test(tokio::test)]
+async fn light_syncer_can_be_upgraded_to_full() {
+    let mut hub = MockHub::default();
+
+    let blockchain = Arc::new(RwLock::new(
+        Blockchain::new(
+            MdbxDatabase::new_volatile(Default::default()).unwrap(),
+            BlockchainConfig::default(),
+            NetworkId::UnitAlbatross,
+            Arc::new(OffsetTime::new()),
+        )
+        .unwrap(),
+    ));
+    let blockchain_proxy = BlockchainProxy::from(&blockchain);
+
+    let net = Arc::new(hub.new_network());
+    let zkp_proxy = ZKPComponent::new(blockchain_proxy.clone(), Arc::clone(&net), None)
+        .await
+        .proxy();
+
+    let syncer = SyncerProxy::new(
+        SyncerMode::Light,
+        blockchain_proxy.clone(),
+        Arc::clone(&net),
+        Arc::new(Mutex::new(BlsCache::new_test())),
+        zkp_proxy.clone(),
+        net.subscribe_events(),
+        0,
+    )
+    .await;
+    assert_eq!(syncer.mode(), SyncerMode::Light);
+    assert_eq!(
+        syncer.check_switch_to(SyncerMode::Light),
+        Err(SyncerModeError::AlreadyInMode(SyncerMode::Light))
+    );
+    assert_eq!(
+        syncer.check_switch_to(SyncerMode::History),
+        Err(SyncerModeError::UnsupportedTransition {
+            from: SyncerMode::Light,
+            to: SyncerMode::History,
+        })
+    );
+    assert_eq!(syncer.check_switch_to(SyncerMode::Full), Ok(()));
+
+    let consensus = Consensus::from_network(blockchain_proxy, Arc::clone(&net), syncer, zkp_proxy);
+    let consensus_proxy = consensus.proxy();
+    spawn(consensus);
+
+    let bls_cache = Arc::new(Mutex::new(BlsCache::new_test()));
+    assert_eq!(
+        consensus_proxy
+            .upgrade_to_full_sync(Arc::clone(&bls_cache), 0)
+            .await,
+        Ok(())
+    );
+
+    // A second upgrade is rejected since the syncer already runs in full mode.
+    assert_eq!(
+        consensus_proxy.upgrade_to_full_sync(bls_cache, 0).await,
+        Err(SyncerModeError::AlreadyInMode(SyncerMode::Full))
+    );
+}
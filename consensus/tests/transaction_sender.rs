@@ -0,0 +1,213 @@
+use std::{str::FromStr, sync::Arc};
+
+use futures::{stream::BoxStream, StreamExt};
+use nimiq_block::Block;
+use nimiq_blockchain::{BlockProducer, Blockchain, BlockchainConfig};
+use nimiq_blockchain_interface::{AbstractBlockchain, PushResult};
+use nimiq_blockchain_proxy::BlockchainProxy;
+use nimiq_consensus::{
+    consensus::{
+        transaction_inclusion::TransactionState,
+        transaction_sender::{TransactionSender, TransactionSenderConfig, TransactionSenderEvent},
+    },
+    sync::syncer_proxy::SyncerProxy,
+    BlsCache, Consensus, ConsensusProxy,
+};
+use nimiq_database::mdbx::MdbxDatabase;
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_keys::{Address, KeyPair, PrivateKey};
+use nimiq_network_interface::network::Network;
+use nimiq_network_mock::{MockHub, MockNetwork};
+use nimiq_primitives::{coin::Coin, networks::NetworkId, policy::Policy};
+use nimiq_test_log::test;
+use nimiq_test_utils::blockchain::{produce_macro_blocks, signing_key, voting_key, REWARD_KEY};
+use nimiq_transaction::Transaction;
+use nimiq_transaction_builder::TransactionBuilder;
+use nimiq_utils::time::OffsetTime;
+use nimiq_zkp_component::ZKPComponent;
+use parking_lot::{Mutex, RwLock};
+
+/// Creates a consensus for the blockchain whose network is connected to a single peer, such that
+/// transactions can be published.
+async fn consensus(
+    blockchain: &Arc<RwLock<Blockchain>>,
+) -> (
+    Consensus<MockNetwork>,
+    ConsensusProxy<MockNetwork>,
+    Arc<MockNetwork>,
+) {
+    let mut hub = MockHub::default();
+    let network = Arc::new(hub.new_network());
+    let peer = Arc::new(hub.new_network());
+    network.dial_mock(&peer);
+    let blockchain_proxy = BlockchainProxy::from(blockchain);
+    let zkp_proxy = ZKPComponent::new(blockchain_proxy.clone(), Arc::clone(&network), None)
+        .await
+        .proxy();
+    let syncer = SyncerProxy::new_history(
+        blockchain_proxy.clone(),
+        Arc::clone(&network),
+        Arc::new(Mutex::new(BlsCache::new_test())),
+        network.subscribe_events(),
+    )
+    .await;
+
+    let consensus = Consensus::from_network(blockchain_proxy, network, syncer, zkp_proxy);
+    let proxy = consensus.proxy();
+    (consensus, proxy, peer)
+}
+
+fn blockchain() -> Arc<RwLock<Blockchain>> {
+    Arc::new(RwLock::new(
+        Blockchain::new(
+            MdbxDatabase::new_volatile(Default::default()).unwrap(),
+            BlockchainConfig::default(),
+            NetworkId::UnitAlbatross,
+            Arc::new(OffsetTime::new()),
+        )
+        .unwrap(),
+    ))
+}
+
+fn transaction(validity_start_height: u32, fee: u64) -> Transaction {
+    let key_pair = KeyPair::from(PrivateKey::from_str(REWARD_KEY).unwrap());
+    TransactionBuilder::new_basic(
+        &key_pair,
+        Address::from([1u8; 20]),
+        Coin::from_u64_unchecked(1),
+        Coin::from_u64_unchecked(fee),
+        validity_start_height,
+        NetworkId::UnitAlbatross,
+    )
+    .unwrap()
+}
+
+/// Pushes a micro block containing the given transaction.
+fn push_micro_block_with_tx(
+    producer: &BlockProducer,
+    blockchain: &Arc<RwLock<Blockchain>>,
+    tx: Transaction,
+) {
+    let blockchain = blockchain.upgradable_read();
+    let block = producer
+        .next_micro_block(
+            &blockchain,
+            blockchain.timestamp() + Policy::BLOCK_SEPARATION_TIME,
+            vec![],
+            vec![tx],
+            vec![0x42],
+            None,
+        )
+        .unwrap();
+    assert_eq!(
+        Blockchain::push(blockchain, Block::Micro(block)),
+        Ok(PushResult::Extended)
+    );
+}
+
+/// Produces enough macro blocks for a transaction sent at the current head to expire.
+fn expire_transactions(producer: &BlockProducer, blockchain: &Arc<RwLock<Blockchain>>) {
+    let num_batches = Policy::transaction_validity_window_blocks() / Policy::blocks_per_batch() + 1;
+    produce_macro_blocks(producer, blockchain, num_batches as usize);
+}
+
+async fn next_state(
+    events: &mut BoxStream<'static, TransactionSenderEvent>,
+) -> (Blake2bHash, TransactionState) {
+    match events.next().await {
+        Some(TransactionSenderEvent::State { tx_hash, state }) => (tx_hash, state),
+        event => panic!("Expected a state event, got {event:?}"),
+    }
+}
+
+#[test(tokio::test)]
+async fn it_tracks_transactions_until_they_are_confirmed() {
+    let blockchain = blockchain();
+    let producer = BlockProducer::new(signing_key(), voting_key());
+    let (_consensus, consensus_proxy, _peer) = consensus(&blockchain).await;
+
+    let tx = transaction(blockchain.read().block_number(), 2);
+    let tx_hash = tx.hash::<Blake2bHash>();
+    let sender = TransactionSender::new(consensus_proxy, TransactionSenderConfig::default());
+    let mut events = sender.send(tx.clone()).await.unwrap();
+    assert_eq!(
+        next_state(&mut events).await,
+        (tx_hash.clone(), TransactionState::Pending)
+    );
+
+    push_micro_block_with_tx(&producer, &blockchain, tx);
+    let block_number = blockchain.read().block_number();
+    assert_eq!(
+        next_state(&mut events).await,
+        (
+            tx_hash.clone(),
+            TransactionState::Included {
+                block_number,
+                block_hash: blockchain.read().head_hash(),
+            }
+        )
+    );
+
+    produce_macro_blocks(&producer, &blockchain, 1);
+    assert_eq!(
+        next_state(&mut events).await,
+        (tx_hash, TransactionState::Confirmed { block_number })
+    );
+    assert!(events.next().await.is_none());
+}
+
+#[test(tokio::test)]
+async fn it_replaces_expired_transactions() {
+    let blockchain = blockchain();
+    let producer = BlockProducer::new(signing_key(), voting_key());
+    let (_consensus, consensus_proxy, _peer) = consensus(&blockchain).await;
+
+    let tx = transaction(blockchain.read().block_number(), 2);
+    let tx_hash = tx.hash::<Blake2bHash>();
+    let escalation_blockchain = Arc::clone(&blockchain);
+    let sender = TransactionSender::new(
+        consensus_proxy,
+        TransactionSenderConfig {
+            min_peers: 1,
+            max_escalations: 1,
+        },
+    )
+    .with_fee_escalation(move |tx| {
+        Some(transaction(
+            escalation_blockchain.read().block_number(),
+            u64::from(tx.fee) * 2,
+        ))
+    });
+    let mut events = sender.send(tx).await.unwrap();
+    assert_eq!(
+        next_state(&mut events).await,
+        (tx_hash.clone(), TransactionState::Pending)
+    );
+
+    // The expired transaction is replaced by one with a higher fee.
+    expire_transactions(&producer, &blockchain);
+    assert_eq!(
+        next_state(&mut events).await,
+        (tx_hash.clone(), TransactionState::Expired)
+    );
+    let new_tx_hash = transaction(blockchain.read().block_number(), 4).hash::<Blake2bHash>();
+    assert_eq!(
+        events.next().await,
+        Some(TransactionSenderEvent::Replaced {
+            old_tx_hash: tx_hash,
+            new_tx_hash: new_tx_hash.clone(),
+        })
+    );
+    assert_eq!(
+        next_state(&mut events).await,
+        (new_tx_hash.clone(), TransactionState::Pending)
+    );
+
+    // The replacement isn't replaced again once the escalations are used up.
+    expire_transactions(&producer, &blockchain);
+    assert_eq!(
+        next_state(&mut events).await,
+        (new_tx_hash, TransactionState::Expired)
+    );
+    assert!(events.next().await.is_none());
+}
@@ -6,4 +6,10 @@ pub enum Error {
     CreateDirectory(#[from] std::io::Error),
     #[error("Mdbx error: {0}")]
     Mdbx(#[from] libmdbx::Error),
+    #[error("A database already exists at the destination")]
+    DestinationExists,
+    #[error("The database was opened in read-only mode")]
+    ReadOnly,
+    #[error("Table {0} does not exist in read-only database")]
+    TableNotFound(&'static str),
 }
@@ -1,5 +1,7 @@
 mod error;
 pub mod mdbx;
+/// Versioned migrations of the database schema.
+pub mod migration;
 /// Abstraction for methods related to the database.
 pub mod traits;
 pub mod utils;
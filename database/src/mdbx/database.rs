@@ -1,6 +1,6 @@
-use std::{any::TypeId, fs, ops::Range, path::Path, sync::Arc};
+use std::{any::TypeId, borrow::Cow, fs, ops::Range, path::Path, sync::Arc};
 
-use libmdbx::NoWriteMap;
+use libmdbx::{NoWriteMap, TableFlags, WriteFlags};
 use log::{debug, info};
 use tempfile::TempDir;
 
@@ -13,6 +13,9 @@ use crate::{
 const GIGABYTE: usize = 1024 * 1024 * 1024;
 const TERABYTE: usize = GIGABYTE * 1024;
 
+/// The maximum number of entries written per transaction when copying a database.
+const COPY_BATCH_SIZE: usize = 100_000;
+
 /// Database config options.
 pub struct DatabaseConfig {
     /// The maximum number of tables that can be opened.
@@ -34,7 +37,7 @@ pub struct DatabaseConfig {
 impl Default for DatabaseConfig {
     fn default() -> Self {
         DatabaseConfig {
-            max_tables: Some(20),
+            max_tables: Some(64),
             max_readers: None,
             no_rdahead: true,
             // Default max database size: 2TB
@@ -74,11 +77,27 @@ pub struct MdbxDatabase {
     /// For volatile databases, this is the temporary directory handle,
     /// which will clean up on `Drop`.
     temp_dir: Option<Arc<TempDir>>,
+    /// Whether the database was opened in read-only mode.
+    read_only: bool,
 }
 
 impl MdbxDatabase {
     /// Create a table with additional flags.
-    fn create_table<T: Table>(&self, _table: &T, mut flags: libmdbx::TableFlags) {
+    fn create_table<T: Table>(
+        &self,
+        _table: &T,
+        mut flags: libmdbx::TableFlags,
+    ) -> Result<(), Error> {
+        // Tables cannot be created in read-only mode, they must already exist.
+        if self.read_only {
+            let txn = self.db.begin_ro_txn()?;
+            return match txn.open_table(Some(T::NAME)) {
+                Ok(_) => Ok(()),
+                Err(libmdbx::Error::NotFound) => Err(Error::TableNotFound(T::NAME)),
+                Err(error) => Err(error.into()),
+            };
+        }
+
         // Ensure `CREATE` flag is set.
         flags.insert(libmdbx::TableFlags::CREATE);
 
@@ -89,26 +108,50 @@ impl MdbxDatabase {
         }
 
         // Create the table with an implicit transaction.
-        let txn = self.db.begin_rw_txn().unwrap();
+        let txn = self.db.begin_rw_txn()?;
         debug!("Creating table: {}, flags: {:?}", T::NAME, flags);
-        txn.create_table(Some(T::NAME), flags).unwrap();
-        txn.commit().unwrap();
+        txn.create_table(Some(T::NAME), flags)?;
+        txn.commit()?;
+        Ok(())
     }
 
     /// Creates a new database at the given path.
     pub fn new<P: AsRef<Path>>(path: P, config: DatabaseConfig) -> Result<Self, Error> {
         fs::create_dir_all(path.as_ref()).map_err(Error::CreateDirectory)?;
 
-        let db =
-            libmdbx::Database::open_with_options(path, libmdbx::DatabaseOptions::from(config))?;
+        Self::open(path, libmdbx::DatabaseOptions::from(config), false)
+    }
+
+    /// Opens an existing database at the given path in read-only mode.
+    ///
+    /// The database can safely be opened this way while another process (e.g. a running node)
+    /// has it opened for writing. Tables are not created and write transactions are refused.
+    pub fn new_read_only<P: AsRef<Path>>(path: P, config: DatabaseConfig) -> Result<Self, Error> {
+        let options = libmdbx::DatabaseOptions {
+            mode: libmdbx::Mode::ReadOnly,
+            // Adopt the settings of the process that already has the database open.
+            accede: true,
+            ..libmdbx::DatabaseOptions::from(config)
+        };
+
+        Self::open(path, options, true)
+    }
+
+    fn open<P: AsRef<Path>>(
+        path: P,
+        options: libmdbx::DatabaseOptions,
+        read_only: bool,
+    ) -> Result<Self, Error> {
+        let db = libmdbx::Database::open_with_options(path, options)?;
 
         let info = db.info()?;
         let cur_mapsize = info.map_size();
-        info!(cur_mapsize, "MDBX memory map size");
+        info!(cur_mapsize, read_only, "MDBX memory map size");
 
         let mdbx = MdbxDatabase {
             db: Arc::new(db),
             temp_dir: None,
+            read_only,
         };
 
         Ok(mdbx)
@@ -122,6 +165,71 @@ impl MdbxDatabase {
 
         Ok(mdbx)
     }
+
+    /// Copies all tables of this database into a new database at the given path and returns the
+    /// number of entries copied.
+    ///
+    /// The entries are written in key order into fresh pages, so the copy contains none of the
+    /// free space that updates left behind in this database and is usually considerably smaller.
+    /// All entries are read from a single snapshot, while the copy is written in transactions of
+    /// bounded size. The destination must not contain a database yet.
+    pub fn copy_compacted<P: AsRef<Path>>(
+        &self,
+        path: P,
+        config: DatabaseConfig,
+    ) -> Result<u64, Error> {
+        if path.as_ref().join("mdbx.dat").exists() {
+            return Err(Error::DestinationExists);
+        }
+        let copy = MdbxDatabase::new(path, config)?;
+
+        let txn = self.db.begin_ro_txn()?;
+        let main_table = txn.open_table(None)?;
+        let names = txn
+            .cursor(&main_table)?
+            .into_iter_start::<Cow<[u8]>, Cow<[u8]>>()
+            .map(|entry| Ok(String::from_utf8_lossy(&entry?.0).into_owned()))
+            .collect::<Result<Vec<_>, libmdbx::Error>>()?;
+
+        let mut num_entries = 0;
+        for name in names {
+            let table = txn.open_table(Some(&name))?;
+            let flags = txn.table_flags(&table)?;
+            let write_flags = if flags.contains(TableFlags::DUP_SORT) {
+                WriteFlags::APPEND_DUP
+            } else {
+                WriteFlags::APPEND
+            };
+            debug!("Copying table: {}, flags: {:?}", name, flags);
+
+            let mut entries = txn
+                .cursor(&table)?
+                .into_iter_start::<Cow<[u8]>, Cow<[u8]>>();
+            loop {
+                let copy_txn = copy.db.begin_rw_txn()?;
+                let copy_table = copy_txn.create_table(Some(&name), flags | TableFlags::CREATE)?;
+                let mut cursor = copy_txn.cursor(&copy_table)?;
+
+                let mut num_batch_entries = 0;
+                for entry in entries.by_ref().take(COPY_BATCH_SIZE) {
+                    let (key, value) = entry?;
+                    cursor.put(&key, &value, write_flags)?;
+                    num_batch_entries += 1;
+                }
+
+                drop(cursor);
+                copy_txn.commit()?;
+                num_entries += num_batch_entries as u64;
+
+                if num_batch_entries < COPY_BATCH_SIZE {
+                    break;
+                }
+            }
+        }
+
+        info!(num_entries, "Copied database");
+        Ok(num_entries)
+    }
 }
 
 impl Database for MdbxDatabase {
@@ -131,11 +239,19 @@ impl Database for MdbxDatabase {
 
     /// Creates a regular table (no-duplicates).
     fn create_regular_table<T: RegularTable>(&self, table: &T) {
+        self.try_create_regular_table(table).unwrap()
+    }
+
+    fn try_create_regular_table<T: RegularTable>(&self, table: &T) -> Result<(), Error> {
         self.create_table(table, libmdbx::TableFlags::empty())
     }
 
     /// Creates a regular table (no-duplicates).
     fn create_dup_table<T: DupTable>(&self, table: &T) {
+        self.try_create_dup_table(table).unwrap()
+    }
+
+    fn try_create_dup_table<T: DupTable>(&self, table: &T) -> Result<(), Error> {
         let mut dup_flags = libmdbx::TableFlags::DUP_SORT;
 
         // Set the fixed size flag if given.
@@ -150,7 +266,18 @@ impl Database for MdbxDatabase {
         MdbxReadTransaction::new_read(self.db.begin_ro_txn().unwrap())
     }
 
+    fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
     fn write_transaction(&self) -> Self::WriteTransaction<'_> {
-        MdbxWriteTransaction::new(self.db.begin_rw_txn().unwrap())
+        self.try_write_transaction().unwrap()
+    }
+
+    fn try_write_transaction(&self) -> Result<Self::WriteTransaction<'_>, Error> {
+        if self.read_only {
+            return Err(Error::ReadOnly);
+        }
+        Ok(MdbxWriteTransaction::new(self.db.begin_rw_txn()?))
     }
 }
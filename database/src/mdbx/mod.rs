@@ -39,6 +39,7 @@ mod tests {
     use crate::{
         declare_table,
         traits::{Database, DupReadCursor, ReadCursor, ReadTransaction, WriteTransaction},
+        Error,
     };
 
     declare_table!(TestTable, "test", String => String);
@@ -324,4 +325,108 @@ mod tests {
         }
         tempdir.close().unwrap();
     }
+
+    #[test]
+    fn it_can_copy_compacted() {
+        let tempdir = tempdir().unwrap();
+        let path = tempdir.path().join("test_copy");
+        let copy_path = tempdir.path().join("test_copy_compacted");
+        let table = U32Table {};
+        let dup_table = DupTestTable {};
+        {
+            let db = MdbxDatabase::new(&path, DatabaseConfig::default()).unwrap();
+            db.create_regular_table(&table);
+            db.create_dup_table(&dup_table);
+
+            let mut tx = db.write_transaction();
+            for i in 0..1000 {
+                tx.put(&table, &i, &i);
+            }
+            tx.put(&dup_table, &"test".to_string(), &5783);
+            tx.put(&dup_table, &"test".to_string(), &12);
+            tx.commit();
+
+            let mut tx = db.write_transaction();
+            for i in 10..1000 {
+                tx.remove(&table, &i);
+            }
+            tx.commit();
+
+            assert_eq!(
+                db.copy_compacted(&copy_path, DatabaseConfig::default())
+                    .unwrap(),
+                12
+            );
+            assert!(matches!(
+                db.copy_compacted(&copy_path, DatabaseConfig::default()),
+                Err(Error::DestinationExists)
+            ));
+        }
+        {
+            let db = MdbxDatabase::new(&copy_path, DatabaseConfig::default()).unwrap();
+            db.create_regular_table(&table);
+            db.create_dup_table(&dup_table);
+
+            let tx = db.read_transaction();
+            let mut cursor = tx.cursor(&table);
+            assert_eq!(cursor.first(), Some((0, 0)));
+            assert_eq!(cursor.last(), Some((9, 9)));
+            let mut cursor = tx.dup_cursor(&dup_table);
+            assert_eq!(cursor.set_key(&"test".to_string()), Some(12));
+            assert_eq!(cursor.count_duplicates(), 2);
+        }
+        tempdir.close().unwrap();
+    }
+
+    #[test]
+    fn it_can_open_read_only() {
+        let tempdir = tempdir().unwrap();
+        let path = tempdir.path().join("test_ro");
+        let table = TestTable {};
+        {
+            let db = MdbxDatabase::new(&path, DatabaseConfig::default()).unwrap();
+            db.create_regular_table(&table);
+            assert!(!db.is_read_only());
+
+            let mut tx = db.write_transaction();
+            tx.put(&table, &"test".to_string(), &"one".to_string());
+            tx.commit();
+        }
+        {
+            let db = MdbxDatabase::new_read_only(&path, DatabaseConfig::default()).unwrap();
+            // Creating an existing table is a no-op.
+            db.create_regular_table(&table);
+            assert!(db.is_read_only());
+
+            let tx = db.read_transaction();
+            assert_eq!(tx.get(&table, &"test".to_string()), Some("one".to_string()));
+        }
+        tempdir.close().unwrap();
+    }
+
+    #[test]
+    fn it_returns_errors_when_read_only() {
+        let tempdir = tempdir().unwrap();
+        let path = tempdir.path().join("test_ro_errors");
+        MdbxDatabase::new(&path, DatabaseConfig::default()).unwrap();
+
+        let db = MdbxDatabase::new_read_only(&path, DatabaseConfig::default()).unwrap();
+        assert!(matches!(db.try_write_transaction(), Err(Error::ReadOnly)));
+        assert!(matches!(
+            db.try_create_regular_table(&TestTable {}),
+            Err(Error::TableNotFound("test"))
+        ));
+        tempdir.close().unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn it_refuses_writes_when_read_only() {
+        let tempdir = tempdir().unwrap();
+        let path = tempdir.path().join("test_ro_write");
+        MdbxDatabase::new(&path, DatabaseConfig::default()).unwrap();
+
+        let db = MdbxDatabase::new_read_only(&path, DatabaseConfig::default()).unwrap();
+        db.write_transaction();
+    }
 }
@@ -0,0 +1,310 @@
+//! Versioned migrations of the database schema.
+//!
+//! The database stores the version of its schema. On startup, all migrations with a higher version
+//! are run in order. Each migration runs in a single write transaction together with the update of
+//! the version, so it is either fully applied or not at all.
+//!
+//! Databases without a stored version are considered to be at version 0. Migrations thus need to
+//! handle empty tables, which is the case for newly created databases.
+use std::error::Error as StdError;
+
+use log::info;
+use thiserror::Error;
+
+use crate::{
+    declare_table,
+    mdbx::{MdbxDatabase, MdbxWriteTransaction},
+    traits::{Database, ReadTransaction, WriteTransaction},
+};
+
+declare_table!(DatabaseVersionTable, "DatabaseVersion", () => u32);
+
+/// The error a migration failed with.
+pub type MigrationFailure = Box<dyn StdError + Send + Sync>;
+
+/// A migration of the database schema to a new version.
+pub trait Migration {
+    /// The version of the database after this migration ran. Versions start at 1.
+    fn version(&self) -> u32;
+
+    /// A short description of the migration, used for logging.
+    fn description(&self) -> &str;
+
+    /// Creates the tables the migration accesses. Tables cannot be created while the write
+    /// transaction of the migration is open, so this is called before the migration runs.
+    fn create_tables(&self, _db: &MdbxDatabase) {}
+
+    /// Migrates the data of the database. All changes must be made within the given transaction,
+    /// it is committed together with the version update once the migration succeeded.
+    fn migrate(
+        &self,
+        db: &MdbxDatabase,
+        txn: &mut MdbxWriteTransaction,
+        progress: &mut MigrationProgress,
+    ) -> Result<(), MigrationFailure>;
+}
+
+#[derive(Debug, Error)]
+pub enum MigrationError {
+    #[error("Database version {found} is newer than the latest supported version {latest}")]
+    UnsupportedVersion { found: u32, latest: u32 },
+    #[error("Migration to version {version} failed: {error}")]
+    Failed {
+        version: u32,
+        error: MigrationFailure,
+    },
+}
+
+/// Reports the progress of a running migration.
+pub struct MigrationProgress {
+    version: u32,
+    total: Option<u64>,
+    processed: u64,
+    /// The percentage at which the progress was last reported.
+    reported: u64,
+}
+
+impl MigrationProgress {
+    /// The interval in percent in which the progress is reported.
+    const REPORT_INTERVAL: u64 = 10;
+
+    fn new(version: u32) -> Self {
+        Self {
+            version,
+            total: None,
+            processed: 0,
+            reported: 0,
+        }
+    }
+
+    /// Sets the total number of items the migration processes.
+    pub fn set_total(&mut self, total: u64) {
+        self.total = Some(total);
+    }
+
+    /// Marks the given number of items as processed.
+    pub fn advance(&mut self, processed: u64) {
+        self.processed += processed;
+
+        let Some(total) = self.total.filter(|total| *total > 0) else {
+            return;
+        };
+        let percent = (self.processed * 100 / total).min(100);
+        if percent >= self.reported + Self::REPORT_INTERVAL {
+            self.reported = percent - percent % Self::REPORT_INTERVAL;
+            info!(
+                version = self.version,
+                processed = self.processed,
+                total,
+                "Migrating database: {}%",
+                self.reported,
+            );
+        }
+    }
+
+    /// Returns the number of processed items.
+    pub fn processed(&self) -> u64 {
+        self.processed
+    }
+}
+
+/// Runs the pending migrations of a database.
+#[derive(Default)]
+pub struct Migrator {
+    migrations: Vec<Box<dyn Migration>>,
+}
+
+impl Migrator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a migration.
+    ///
+    /// ## Panic
+    /// Panics if a migration to the same version is already registered.
+    pub fn with<M: Migration + 'static>(mut self, migration: M) -> Self {
+        assert!(
+            migration.version() > 0,
+            "Migration versions must start at 1"
+        );
+        assert!(
+            self.migrations
+                .iter()
+                .all(|registered| registered.version() != migration.version()),
+            "Duplicate migration to version {}",
+            migration.version(),
+        );
+        self.migrations.push(Box::new(migration));
+        self.migrations.sort_by_key(|migration| migration.version());
+        self
+    }
+
+    /// Returns the version of the database after all registered migrations ran.
+    pub fn latest_version(&self) -> u32 {
+        self.migrations
+            .last()
+            .map(|migration| migration.version())
+            .unwrap_or(0)
+    }
+
+    /// Returns the version of the given database.
+    pub fn version(db: &MdbxDatabase) -> u32 {
+        db.create_regular_table(&DatabaseVersionTable);
+        db.read_transaction()
+            .get(&DatabaseVersionTable, &())
+            .unwrap_or(0)
+    }
+
+    /// Runs all migrations the database has not seen yet, in order of their version.
+    /// Returns the version of the database afterwards.
+    pub fn run(&self, db: &MdbxDatabase) -> Result<u32, MigrationError> {
+        let mut version = Self::version(db);
+        let latest = self.latest_version();
+        if version > latest {
+            return Err(MigrationError::UnsupportedVersion {
+                found: version,
+                latest,
+            });
+        }
+
+        for migration in self
+            .migrations
+            .iter()
+            .filter(|migration| migration.version() > version)
+        {
+            info!(
+                from = version,
+                to = migration.version(),
+                "Migrating database: {}",
+                migration.description(),
+            );
+
+            migration.create_tables(db);
+
+            // The write transaction is aborted if it is dropped without being committed.
+            let mut txn = db.write_transaction();
+            let mut progress = MigrationProgress::new(migration.version());
+            migration
+                .migrate(db, &mut txn, &mut progress)
+                .map_err(|error| MigrationError::Failed {
+                    version: migration.version(),
+                    error,
+                })?;
+            txn.put(&DatabaseVersionTable, &(), &migration.version());
+            txn.commit();
+
+            version = migration.version();
+        }
+
+        Ok(version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mdbx::DatabaseConfig;
+
+    declare_table!(TestTable, "test", u32 => u32);
+
+    /// Doubles all values of the test table.
+    struct DoubleValues;
+
+    impl Migration for DoubleValues {
+        fn version(&self) -> u32 {
+            1
+        }
+
+        fn description(&self) -> &str {
+            "Double values"
+        }
+
+        fn migrate(
+            &self,
+            _db: &MdbxDatabase,
+            txn: &mut MdbxWriteTransaction,
+            progress: &mut MigrationProgress,
+        ) -> Result<(), MigrationFailure> {
+            progress.set_total(10);
+            for key in 0..10 {
+                if let Some(value) = txn.get(&TestTable, &key) {
+                    txn.put(&TestTable, &key, &(value * 2));
+                }
+                progress.advance(1);
+            }
+            Ok(())
+        }
+    }
+
+    /// Writes to the test table and fails afterwards.
+    struct Failing;
+
+    impl Migration for Failing {
+        fn version(&self) -> u32 {
+            2
+        }
+
+        fn description(&self) -> &str {
+            "Failing"
+        }
+
+        fn migrate(
+            &self,
+            _db: &MdbxDatabase,
+            txn: &mut MdbxWriteTransaction,
+            _progress: &mut MigrationProgress,
+        ) -> Result<(), MigrationFailure> {
+            txn.put(&TestTable, &0, &0);
+            Err("failed".into())
+        }
+    }
+
+    fn database() -> MdbxDatabase {
+        let db = MdbxDatabase::new_volatile(DatabaseConfig::default()).unwrap();
+        db.create_regular_table(&TestTable);
+        let mut txn = db.write_transaction();
+        txn.put(&TestTable, &0, &21);
+        txn.commit();
+        db
+    }
+
+    #[test]
+    fn it_runs_pending_migrations_once() {
+        let db = database();
+        let migrator = Migrator::new().with(DoubleValues);
+
+        assert_eq!(Migrator::version(&db), 0);
+        assert_eq!(migrator.run(&db).unwrap(), 1);
+        assert_eq!(migrator.run(&db).unwrap(), 1);
+
+        assert_eq!(db.read_transaction().get(&TestTable, &0), Some(42));
+    }
+
+    #[test]
+    fn it_does_not_apply_failed_migrations() {
+        let db = database();
+        let migrator = Migrator::new().with(Failing).with(DoubleValues);
+
+        assert!(matches!(
+            migrator.run(&db),
+            Err(MigrationError::Failed { version: 2, .. })
+        ));
+        assert_eq!(Migrator::version(&db), 1);
+        assert_eq!(db.read_transaction().get(&TestTable, &0), Some(42));
+    }
+
+    #[test]
+    fn it_rejects_newer_databases() {
+        let db = database();
+        Migrator::new().with(DoubleValues).run(&db).unwrap();
+
+        assert!(matches!(
+            Migrator::new().run(&db),
+            Err(MigrationError::UnsupportedVersion {
+                found: 1,
+                latest: 0
+            })
+        ));
+    }
+}
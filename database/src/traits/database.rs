@@ -1,4 +1,5 @@
 use super::{DupTable, ReadTransaction, RegularTable, WriteTransaction};
+use crate::Error;
 
 /// A database handle that can hold multiple tables.
 pub trait Database: Sized {
@@ -10,14 +11,34 @@ pub trait Database: Sized {
         Self: 'db;
 
     /// Creates a regular table (no-duplicates).
+    ///
+    /// Panics if the database was opened in read-only mode and the table doesn't exist.
     fn create_regular_table<T: RegularTable>(&self, table: &T);
 
+    /// Creates a regular table (no-duplicates). In read-only mode, only checks that the table
+    /// exists.
+    fn try_create_regular_table<T: RegularTable>(&self, table: &T) -> Result<(), Error>;
+
     /// Creates a table that can store duplicate keys.
+    ///
+    /// Panics if the database was opened in read-only mode and the table doesn't exist.
     fn create_dup_table<T: DupTable>(&self, table: &T);
 
+    /// Creates a table that can store duplicate keys. In read-only mode, only checks that the
+    /// table exists.
+    fn try_create_dup_table<T: DupTable>(&self, table: &T) -> Result<(), Error>;
+
     /// Creates a read transaction.
     fn read_transaction(&self) -> Self::ReadTransaction<'_>;
 
+    /// Returns whether the database was opened in read-only mode.
+    fn is_read_only(&self) -> bool;
+
     /// Creates a read/write transaction.
+    ///
+    /// Panics if the database was opened in read-only mode.
     fn write_transaction(&self) -> Self::WriteTransaction<'_>;
+
+    /// Creates a read/write transaction, failing if the database was opened in read-only mode.
+    fn try_write_transaction(&self) -> Result<Self::WriteTransaction<'_>, Error>;
 }
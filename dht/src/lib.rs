@@ -1,24 +1,149 @@
+use std::{collections::HashMap, sync::Arc};
+
+use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_blockchain_proxy::BlockchainProxy;
-use nimiq_keys::{Address, KeyPair};
+use nimiq_keys::{Address, Ed25519PublicKey, KeyPair};
 use nimiq_network_libp2p::{
     dht::{DhtRecord, DhtVerifierError, Verifier as DhtVerifier},
     libp2p::kad::Record,
     PeerId,
 };
 use nimiq_serde::Deserialize;
-use nimiq_utils::tagged_signing::{TaggedSignable, TaggedSigned};
-use nimiq_validator_network::validator_record::ValidatorRecord;
+use nimiq_utils::tagged_signing::{
+    TaggedKeyPair, TaggedSignable, TaggedSigned, TaggedVerificationError, TimestampedSignable,
+};
+use nimiq_validator_network::validator_record::{
+    validator_address_from_dht_key, LegacyValidatorRecord, ValidatorRecord,
+    VALIDATOR_RECORD_FRESHNESS, VALIDATOR_RECORD_MAX_ADDRESSES,
+};
+use parking_lot::Mutex;
+
+pub use crate::{record_handler::RecordHandler, signing_keys::ProvenSigningKeys};
+use crate::{record_handler::VerifyFn, verification_cache::VerificationCache};
+
+mod record_handler;
+mod signing_keys;
+mod verification_cache;
 
 pub struct Verifier {
     blockchain: BlockchainProxy,
+    /// Signing keys proven via accounts trie proofs, used if we don't have the staking contract.
+    proven_signing_keys: Arc<ProvenSigningKeys>,
+    /// Verification functions of the registered record types by their tag.
+    handlers: HashMap<u8, VerifyFn>,
+    /// Records that were verified recently, such that their signatures are not verified again.
+    verified_records: Mutex<VerificationCache>,
 }
 
 impl Verifier {
     pub fn new(blockchain: BlockchainProxy) -> Self {
-        Self { blockchain }
+        Self {
+            blockchain,
+            proven_signing_keys: Default::default(),
+            handlers: HashMap::new(),
+            verified_records: Mutex::new(VerificationCache::new()),
+        }
+    }
+
+    /// Registers a handler for records of type `TSignable`, such that they can be published in
+    /// the DHT. Records are identified by the tag of `TSignable`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a handler for the tag of `TSignable` was already registered or if the tag is
+    /// the one of the [`ValidatorRecord`] or the [`LegacyValidatorRecord`].
+    pub fn register<TSignable, TScheme>(&mut self, handler: impl RecordHandler<TSignable, TScheme>)
+    where
+        TSignable: TimestampedSignable + Deserialize,
+        TScheme: TaggedKeyPair,
+    {
+        assert!(
+            TSignable::TAG != ValidatorRecord::<PeerId>::TAG
+                && TSignable::TAG != LegacyValidatorRecord::<PeerId>::TAG,
+            "DHT record tag is reserved for validator records"
+        );
+        assert!(
+            !self.handlers.contains_key(&TSignable::TAG),
+            "DHT record tag {} is already registered",
+            TSignable::TAG
+        );
+        self.handlers
+            .insert(TSignable::TAG, record_handler::verify_fn(handler));
+    }
+
+    /// Returns the current time according to the blockchain's clock and the current epoch.
+    fn now_and_epoch(&self) -> (u64, u32) {
+        match self.blockchain {
+            BlockchainProxy::Light(ref light_blockchain) => {
+                let blockchain_read = light_blockchain.read();
+                (blockchain_read.time.now(), blockchain_read.epoch_number())
+            }
+            BlockchainProxy::Full(ref full_blockchain) => {
+                let blockchain_read = full_blockchain.read();
+                (blockchain_read.time.now(), blockchain_read.epoch_number())
+            }
+        }
+    }
+
+    /// Returns the store of proven signing keys. Light clients need to run
+    /// [`ProvenSigningKeys::prove`] to be able to verify validator records.
+    pub fn proven_signing_keys(&self) -> Arc<ProvenSigningKeys> {
+        Arc::clone(&self.proven_signing_keys)
     }
 
-    fn verify_validator_record(&self, record: &Record) -> Result<DhtRecord, DhtVerifierError> {
+    /// Returns the signing key of the validator with the given address, as well as the current
+    /// time and epoch.
+    fn signing_key(
+        &self,
+        validator_address: &Address,
+    ) -> Result<(Ed25519PublicKey, u64, u32), DhtVerifierError> {
+        match self.blockchain {
+            BlockchainProxy::Light(ref light_blockchain) => {
+                // Light clients don't have the staking contract, so we rely on signing keys proven
+                // against the accounts trie after the latest election block.
+                let blockchain_read = light_blockchain.read();
+                let election_block = blockchain_read.election_head().block_number();
+                let public_key = self
+                    .proven_signing_keys
+                    .get_or_request(validator_address, election_block)
+                    .ok_or(DhtVerifierError::SigningKeyUnavailable(
+                        validator_address.clone(),
+                    ))?;
+                Ok((
+                    public_key,
+                    blockchain_read.time.now(),
+                    blockchain_read.epoch_number(),
+                ))
+            }
+            BlockchainProxy::Full(ref full_blockchain) => {
+                let blockchain_read = full_blockchain.read();
+
+                // Get the staking contract to retrieve the public key for verification.
+                let staking_contract = blockchain_read
+                    .get_staking_contract_if_complete(None)
+                    .ok_or(DhtVerifierError::StateIncomplete)?;
+
+                let data_store = blockchain_read.get_staking_contract_store();
+                let txn = blockchain_read.read_transaction();
+                let public_key = staking_contract
+                    .get_validator(&data_store.read(&txn), validator_address)
+                    .ok_or(DhtVerifierError::UnknownValidator(
+                        validator_address.clone(),
+                    ))?
+                    .signing_key;
+                Ok((
+                    public_key,
+                    blockchain_read.time.now(),
+                    blockchain_read.epoch_number(),
+                ))
+            }
+        }
+    }
+
+    fn verify_validator_record(
+        &self,
+        record: &Record,
+    ) -> Result<(DhtRecord, u64), DhtVerifierError> {
         // Deserialize the value of the record, which is a ValidatorRecord. If it fails return an error.
         let validator_record =
             TaggedSigned::<ValidatorRecord<PeerId>, KeyPair>::deserialize_from_vec(&record.value)
@@ -37,8 +162,14 @@ impl Verifier {
             return Err(DhtVerifierError::PublisherMissing);
         }
 
-        // Deserialize the key of the record which is an Address. If it fails return an error.
-        let validator_address = Address::deserialize_from_vec(record.key.as_ref())
+        if validator_record.record.addresses.len() > VALIDATOR_RECORD_MAX_ADDRESSES {
+            return Err(DhtVerifierError::TooManyAddresses(
+                validator_record.record.addresses.len(),
+            ));
+        }
+
+        // Deserialize the key of the record which contains an Address. If it fails return an error.
+        let validator_address = validator_address_from_dht_key(record.key.as_ref())
             .map_err(DhtVerifierError::MalformedKey)?;
 
         // Make sure the validator address used as key is identical to the one in the record.
@@ -49,39 +180,82 @@ impl Verifier {
             ));
         }
 
-        // Acquire blockchain read access. For now exclude Light clients.
-        let blockchain = match self.blockchain {
-            BlockchainProxy::Light(ref _light_blockchain) => {
-                return Err(DhtVerifierError::UnknownTag)
-            }
-            BlockchainProxy::Full(ref full_blockchain) => full_blockchain,
+        // Get the public key needed for verification as well as the current time and epoch.
+        let (public_key, now, epoch_number) = self.signing_key(&validator_address)?;
+
+        // Reject records signed for an old epoch, validators republish their record in every epoch.
+        if !validator_record.record.is_valid_in_epoch(epoch_number) {
+            return Err(DhtVerifierError::InvalidEpoch(
+                validator_record.record.epoch_number,
+                epoch_number,
+            ));
+        }
+
+        // Verify the record, rejecting stale records such that they stop being served.
+        validator_record
+            .verify_fresh(&public_key, now, &VALIDATOR_RECORD_FRESHNESS)
+            .map_err(|error| match error {
+                TaggedVerificationError::InvalidSignature => DhtVerifierError::InvalidSignature,
+                error => DhtVerifierError::StaleRecord(error),
+            })?;
+
+        let fresh_until = VALIDATOR_RECORD_FRESHNESS.fresh_until(&validator_record.record);
+        Ok((
+            DhtRecord::Validator(
+                record.publisher.unwrap(),
+                validator_record.record,
+                record.clone(),
+            ),
+            fresh_until,
+        ))
+    }
+
+    /// Verifies a record of nodes that were not upgraded yet. These records are not bound to an
+    /// epoch and don't contain any addresses.
+    fn verify_legacy_validator_record(
+        &self,
+        record: &Record,
+    ) -> Result<(DhtRecord, u64), DhtVerifierError> {
+        let validator_record =
+            TaggedSigned::<LegacyValidatorRecord<PeerId>, KeyPair>::deserialize_from_vec(
+                &record.value,
+            )
+            .map_err(DhtVerifierError::MalformedValue)?;
+
+        let Some(publisher) = record.publisher else {
+            log::warn!("Validating a dht record without a publisher");
+            return Err(DhtVerifierError::PublisherMissing);
         };
-        let blockchain_read = blockchain.read();
-
-        // Get the staking contract to retrieve the public key for verification.
-        let staking_contract = blockchain_read
-            .get_staking_contract_if_complete(None)
-            .ok_or(DhtVerifierError::StateIncomplete)?;
-
-        // Get the public key needed for verification.
-        let data_store = blockchain_read.get_staking_contract_store();
-        let txn = blockchain_read.read_transaction();
-        let public_key = staking_contract
-            .get_validator(&data_store.read(&txn), &validator_address)
-            .ok_or(DhtVerifierError::UnknownValidator(validator_address))?
-            .signing_key;
-
-        // Verify the record.
+        if validator_record.record.peer_id != publisher {
+            return Err(DhtVerifierError::PublisherMismatch(
+                publisher,
+                validator_record.record.peer_id,
+            ));
+        }
+
+        // The key of legacy records is the validator address itself.
+        let validator_address = Address::deserialize_from_vec(record.key.as_ref())
+            .map_err(DhtVerifierError::MalformedKey)?;
+        if validator_record.record.validator_address != validator_address {
+            return Err(DhtVerifierError::AddressMismatch(
+                validator_address,
+                validator_record.record.validator_address,
+            ));
+        }
+
+        let (public_key, now, _) = self.signing_key(&validator_address)?;
         validator_record
-            .verify(&public_key)
-            .then(|| {
-                DhtRecord::Validator(
-                    record.publisher.unwrap(),
-                    validator_record.record,
-                    record.clone(),
-                )
-            })
-            .ok_or(DhtVerifierError::InvalidSignature)
+            .verify_fresh(&public_key, now, &VALIDATOR_RECORD_FRESHNESS)
+            .map_err(|error| match error {
+                TaggedVerificationError::InvalidSignature => DhtVerifierError::InvalidSignature,
+                error => DhtVerifierError::StaleRecord(error),
+            })?;
+
+        let fresh_until = VALIDATOR_RECORD_FRESHNESS.fresh_until(&validator_record.record);
+        Ok((
+            DhtRecord::LegacyValidator(publisher, validator_record.record, record.clone()),
+            fresh_until,
+        ))
     }
 }
 
@@ -94,13 +268,33 @@ impl DhtVerifier for Verifier {
             return Err(DhtVerifierError::MalformedTag);
         };
 
-        // Depending on tag perform the verification.
-        match tag {
-            ValidatorRecord::<PeerId>::TAG => self.verify_validator_record(record),
-            _ => {
-                log::error!(tag, "DHT invalid record tag received");
-                Err(DhtVerifierError::UnknownTag)
-            }
+        // Records are received and looked up repeatedly, skip verifying them again if they
+        // were verified recently.
+        let (now, epoch_number) = self.now_and_epoch();
+        if let Some(verified) = self.verified_records.lock().get(record, now, epoch_number) {
+            return Ok(verified);
         }
+
+        // Depending on tag perform the verification.
+        let (verified, fresh_until) = match tag {
+            ValidatorRecord::<PeerId>::TAG => self.verify_validator_record(record)?,
+            LegacyValidatorRecord::<PeerId>::TAG => self.verify_legacy_validator_record(record)?,
+            tag => match self.handlers.get(&tag) {
+                Some(verify) => verify(record, now)?,
+                None => {
+                    log::error!(tag, "DHT invalid record tag received");
+                    return Err(DhtVerifierError::UnknownTag);
+                }
+            },
+        };
+
+        self.verified_records.lock().insert(
+            record,
+            verified.clone(),
+            now,
+            fresh_until,
+            epoch_number,
+        );
+        Ok(verified)
     }
 }
@@ -0,0 +1,72 @@
+use nimiq_network_libp2p::{
+    dht::{DhtRecord, DhtVerifierError},
+    libp2p::kad::{Record, RecordKey},
+    PeerId,
+};
+use nimiq_serde::Deserialize;
+use nimiq_utils::tagged_signing::{
+    FreshnessPolicy, TaggedKeyPair, TaggedSigned, TaggedVerificationError, TimestampedSignable,
+};
+
+/// Handler for a record type published in the DHT in addition to the validator records.
+///
+/// The record is published as a [`TaggedSigned`] record using the signature scheme `TScheme`.
+/// Subsystems register their handler with [`Verifier::register`](crate::Verifier::register),
+/// which then deserializes records with the tag of `TSignable`, checks their freshness and
+/// verifies the signature using the public key provided by the handler.
+pub trait RecordHandler<TSignable, TScheme>: Send + Sync + 'static
+where
+    TSignable: TimestampedSignable,
+    TScheme: TaggedKeyPair,
+{
+    /// The policy used to reject stale records of this type.
+    fn freshness(&self) -> FreshnessPolicy;
+
+    /// Checks that `record` may be stored under `key` by `publisher` and returns the public key
+    /// its signature must be verified with.
+    fn public_key(
+        &self,
+        key: &RecordKey,
+        publisher: &PeerId,
+        record: &TSignable,
+    ) -> Result<TScheme::PublicKey, DhtVerifierError>;
+}
+
+/// Type erased verification function of a registered record type.
+/// It is given the record and the current time and returns the verified record together with
+/// the time until which it is fresh.
+pub(crate) type VerifyFn =
+    Box<dyn Fn(&Record, u64) -> Result<(DhtRecord, u64), DhtVerifierError> + Send + Sync>;
+
+/// Creates the verification function for records handled by `handler`.
+pub(crate) fn verify_fn<TSignable, TScheme, THandler>(handler: THandler) -> VerifyFn
+where
+    TSignable: TimestampedSignable + Deserialize,
+    TScheme: TaggedKeyPair,
+    THandler: RecordHandler<TSignable, TScheme>,
+{
+    Box::new(move |record, now| {
+        let signed_record = TaggedSigned::<TSignable, TScheme>::deserialize_from_vec(&record.value)
+            .map_err(DhtVerifierError::MalformedValue)?;
+
+        let Some(publisher) = record.publisher else {
+            log::warn!("Validating a dht record without a publisher");
+            return Err(DhtVerifierError::PublisherMissing);
+        };
+
+        let public_key = handler.public_key(&record.key, &publisher, &signed_record.record)?;
+
+        let freshness = handler.freshness();
+        signed_record
+            .verify_fresh(&public_key, now, &freshness)
+            .map_err(|error| match error {
+                TaggedVerificationError::InvalidSignature => DhtVerifierError::InvalidSignature,
+                error => DhtVerifierError::StaleRecord(error),
+            })?;
+
+        Ok((
+            DhtRecord::Custom(publisher, signed_record.record.issued_at(), record.clone()),
+            freshness.fresh_until(&signed_record.record),
+        ))
+    })
+}
@@ -0,0 +1,245 @@
+use std::collections::{HashMap, HashSet};
+
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_consensus::ConsensusProxy;
+use nimiq_keys::{Address, Ed25519PublicKey};
+use nimiq_network_libp2p::Network;
+use nimiq_primitives::policy::Policy;
+use parking_lot::{Mutex, RwLock};
+use tokio::sync::mpsc;
+
+/// Validator signing keys that have been proven remotely using accounts trie proofs.
+///
+/// Light clients don't have the staking contract, so they cannot look up the signing key of a
+/// validator when verifying its DHT record. Instead, the verifier looks up the key here and
+/// requests a proof for it if it is unknown. Keys are only used for the election block they were
+/// proven in, afterwards they are requested again.
+///
+/// Anyone can publish records for arbitrary addresses, so the number of proofs requested per
+/// election block is limited and addresses proven not to be validators aren't requested again
+/// before the next election block.
+pub struct ProvenSigningKeys {
+    /// The proven signing keys, together with the election block number they are valid for.
+    keys: RwLock<HashMap<Address, (u32, Ed25519PublicKey)>>,
+    /// The addresses for which a proof has been requested but not yet received.
+    pending: Mutex<HashSet<Address>>,
+    /// The proofs requested since the latest election block.
+    requests: Mutex<ElectionRequests>,
+    /// Sender to notify the prover about addresses that need a proof.
+    requests_tx: mpsc::UnboundedSender<Address>,
+    /// Receiver of the addresses that need a proof, taken by the prover.
+    requests_rx: Mutex<Option<mpsc::UnboundedReceiver<Address>>>,
+}
+
+impl Default for ProvenSigningKeys {
+    fn default() -> Self {
+        let (requests_tx, requests_rx) = mpsc::unbounded_channel();
+        Self {
+            keys: Default::default(),
+            pending: Default::default(),
+            requests: Default::default(),
+            requests_tx,
+            requests_rx: Mutex::new(Some(requests_rx)),
+        }
+    }
+}
+
+/// Bookkeeping of the proofs requested for an election block.
+#[derive(Default)]
+struct ElectionRequests {
+    /// The election block the requests were made for.
+    election_block: u32,
+    /// The number of addresses requested for the election block.
+    count: usize,
+    /// The addresses proven not to be validators at the election block.
+    unknown: HashSet<Address>,
+}
+
+impl ElectionRequests {
+    /// Moves on to the given election block if it is newer than the current one. Returns whether
+    /// the bookkeeping refers to the given election block afterwards.
+    fn advance_to(&mut self, election_block: u32) -> bool {
+        if election_block > self.election_block {
+            *self = ElectionRequests {
+                election_block,
+                ..Default::default()
+            };
+        }
+        election_block == self.election_block
+    }
+}
+
+impl ProvenSigningKeys {
+    /// Maximum number of validators to request a proof for at once.
+    const MAX_ADDRESSES_PER_REQUEST: usize = 32;
+
+    /// Maximum number of addresses to request a proof for per election block. There can't be
+    /// more elected validators than slots.
+    const MAX_REQUESTS_PER_ELECTION: usize = Policy::SLOTS as usize;
+
+    /// Minimum number of peers to request proofs from.
+    const MIN_PEERS: usize = 1;
+
+    /// Returns the signing key of the validator if it was proven for the given election block.
+    /// Otherwise a proof is requested and `None` is returned. No proof is requested if the
+    /// address is known not to be a validator or the request limit has been reached.
+    pub fn get_or_request(
+        &self,
+        address: &Address,
+        election_block: u32,
+    ) -> Option<Ed25519PublicKey> {
+        if let Some((proven_at, public_key)) = self.keys.read().get(address) {
+            if *proven_at >= election_block {
+                return Some(*public_key);
+            }
+        }
+
+        let mut requests = self.requests.lock();
+        if !requests.advance_to(election_block) || requests.unknown.contains(address) {
+            return None;
+        }
+
+        let mut pending = self.pending.lock();
+        if pending.contains(address) {
+            return None;
+        }
+        if requests.count >= Self::MAX_REQUESTS_PER_ELECTION {
+            log::trace!(%address, "Signing key proof request limit reached");
+            return None;
+        }
+
+        log::trace!(%address, "Requesting signing key proof for validator");
+        requests.count += 1;
+        pending.insert(address.clone());
+        self.requests_tx.send(address.clone()).ok();
+        None
+    }
+
+    /// Inserts a signing key that was proven at the given election block.
+    pub fn insert(&self, address: Address, election_block: u32, public_key: Ed25519PublicKey) {
+        self.pending.lock().remove(&address);
+        self.keys
+            .write()
+            .insert(address, (election_block, public_key));
+    }
+
+    /// Remembers that the address was proven not to be a validator at the given election block.
+    fn insert_unknown(&self, address: Address, election_block: u32) {
+        self.pending.lock().remove(&address);
+        let mut requests = self.requests.lock();
+        if requests.advance_to(election_block) {
+            requests.unknown.insert(address);
+        }
+    }
+
+    /// Requests proofs for all addresses the verifier asked for and stores the proven keys.
+    /// This must be spawned once consensus is available. It can only be run once.
+    pub async fn prove(&self, consensus: ConsensusProxy<Network>) {
+        let Some(mut requests_rx) = self.requests_rx.lock().take() else {
+            log::error!("Signing key prover is already running");
+            return;
+        };
+
+        let mut addresses = vec![];
+        while requests_rx
+            .recv_many(&mut addresses, Self::MAX_ADDRESSES_PER_REQUEST)
+            .await
+            > 0
+        {
+            // The proof is verified against a block of our chain which is at or after the latest
+            // election block known at this point.
+            let election_block = consensus.blockchain.read().election_head().block_number();
+
+            match consensus
+                .request_validators_by_addresses(addresses.clone(), Self::MIN_PEERS)
+                .await
+            {
+                Ok(validators) => {
+                    for (address, validator) in validators {
+                        match validator {
+                            Some(validator) => {
+                                self.insert(address, election_block, validator.signing_key)
+                            }
+                            None => {
+                                log::debug!(%address, "No validator found for signing key proof");
+                                self.insert_unknown(address, election_block);
+                            }
+                        }
+                    }
+                }
+                Err(error) => {
+                    log::debug!(%error, "Failed to prove validator signing keys");
+                    let mut pending = self.pending.lock();
+                    for address in &addresses {
+                        pending.remove(address);
+                    }
+                }
+            }
+
+            addresses.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nimiq_keys::{Address, Ed25519PublicKey};
+
+    use super::ProvenSigningKeys;
+
+    fn address(index: usize) -> Address {
+        let mut bytes = [0u8; Address::SIZE];
+        bytes[..8].copy_from_slice(&(index as u64).to_be_bytes());
+        Address::from(bytes)
+    }
+
+    #[test]
+    fn it_returns_proven_keys() {
+        let keys = ProvenSigningKeys::default();
+        let public_key = Ed25519PublicKey::default();
+
+        keys.insert(address(1), 10, public_key);
+        assert_eq!(keys.get_or_request(&address(1), 10), Some(public_key));
+        // The key has to be proven again after the next election block.
+        assert_eq!(keys.get_or_request(&address(1), 20), None);
+    }
+
+    #[test]
+    fn it_does_not_request_non_validators_again() {
+        let keys = ProvenSigningKeys::default();
+        let mut requests_rx = keys.requests_rx.lock().take().unwrap();
+
+        assert_eq!(keys.get_or_request(&address(1), 10), None);
+        assert_eq!(requests_rx.try_recv(), Ok(address(1)));
+        // A pending address is not requested twice.
+        assert_eq!(keys.get_or_request(&address(1), 10), None);
+        assert!(requests_rx.try_recv().is_err());
+
+        keys.insert_unknown(address(1), 10);
+        assert_eq!(keys.get_or_request(&address(1), 10), None);
+        assert!(requests_rx.try_recv().is_err());
+
+        // The address might have become a validator at the next election block.
+        assert_eq!(keys.get_or_request(&address(1), 20), None);
+        assert_eq!(requests_rx.try_recv(), Ok(address(1)));
+    }
+
+    #[test]
+    fn it_limits_the_requests_per_election_block() {
+        let keys = ProvenSigningKeys::default();
+        let mut requests_rx = keys.requests_rx.lock().take().unwrap();
+
+        for index in 0..ProvenSigningKeys::MAX_REQUESTS_PER_ELECTION + 10 {
+            assert_eq!(keys.get_or_request(&address(index), 10), None);
+        }
+        let mut requested = 0;
+        while requests_rx.try_recv().is_ok() {
+            requested += 1;
+        }
+        assert_eq!(requested, ProvenSigningKeys::MAX_REQUESTS_PER_ELECTION);
+
+        // The limit is reset with the next election block.
+        assert_eq!(keys.get_or_request(&address(0), 20), None);
+        assert_eq!(requests_rx.try_recv(), Ok(address(0)));
+    }
+}
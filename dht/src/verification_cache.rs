@@ -0,0 +1,153 @@
+use std::io::Write;
+
+use hashlink::LruCache;
+use nimiq_hash::{Blake2bHash, Blake2bHasher, Hasher};
+use nimiq_network_libp2p::{dht::DhtRecord, libp2p::kad::Record};
+
+/// Maximum number of verified records kept in the cache.
+const MAX_CACHED_RECORDS: usize = 4096;
+
+/// Time in milliseconds after which a cached verification result is discarded, such that
+/// changes of the signing key of a publisher are picked up.
+const MAX_CACHE_AGE: u64 = 60 * 1000; // 1 min
+
+struct CachedRecord {
+    record: DhtRecord,
+    /// Time after which the cached result must not be used anymore.
+    valid_until: u64,
+}
+
+/// Cache of records that passed the verification, such that records which are received or
+/// looked up repeatedly don't need to have their signature verified again.
+///
+/// Records are identified by the hash of their key, value and publisher. A cached result is
+/// only used in the epoch it was verified in and while the record is still fresh.
+pub(crate) struct VerificationCache {
+    epoch_number: u32,
+    records: LruCache<Blake2bHash, CachedRecord>,
+}
+
+impl VerificationCache {
+    pub fn new() -> Self {
+        Self {
+            epoch_number: 0,
+            records: LruCache::new(MAX_CACHED_RECORDS),
+        }
+    }
+
+    fn hash(record: &Record) -> Blake2bHash {
+        let key = record.key.as_ref();
+        let mut hasher = Blake2bHasher::default();
+        hasher.write_all(&(key.len() as u64).to_be_bytes()).unwrap();
+        hasher.write_all(key).unwrap();
+        hasher
+            .write_all(&(record.value.len() as u64).to_be_bytes())
+            .unwrap();
+        hasher.write_all(&record.value).unwrap();
+        if let Some(publisher) = record.publisher {
+            hasher.write_all(&publisher.to_bytes()).unwrap();
+        }
+        hasher.finish()
+    }
+
+    /// Returns the result of a previous verification of `record` if it is still valid at time
+    /// `now` in epoch `epoch_number`.
+    pub fn get(&mut self, record: &Record, now: u64, epoch_number: u32) -> Option<DhtRecord> {
+        if epoch_number != self.epoch_number {
+            self.records.clear();
+            self.epoch_number = epoch_number;
+            return None;
+        }
+
+        let hash = Self::hash(record);
+        let cached = self.records.get(&hash)?;
+        if now > cached.valid_until {
+            self.records.remove(&hash);
+            return None;
+        }
+        Some(cached.record.clone())
+    }
+
+    /// Caches a successfully verified `record` that was verified at time `now` in epoch
+    /// `epoch_number` and is fresh until `fresh_until`.
+    pub fn insert(
+        &mut self,
+        record: &Record,
+        verified: DhtRecord,
+        now: u64,
+        fresh_until: u64,
+        epoch_number: u32,
+    ) {
+        if epoch_number != self.epoch_number {
+            self.records.clear();
+            self.epoch_number = epoch_number;
+        }
+
+        self.records.insert(
+            Self::hash(record),
+            CachedRecord {
+                record: verified,
+                valid_until: fresh_until.min(now.saturating_add(MAX_CACHE_AGE)),
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nimiq_network_libp2p::{libp2p::kad::RecordKey, PeerId};
+
+    use super::*;
+
+    fn record(value: u8) -> (Record, DhtRecord) {
+        let publisher = PeerId::random();
+        let mut record = Record::new(RecordKey::new(&[1u8; 20]), vec![value]);
+        record.publisher = Some(publisher);
+        let verified = DhtRecord::Custom(publisher, 1000, record.clone());
+        (record, verified)
+    }
+
+    #[test]
+    fn it_returns_cached_records_while_valid() {
+        let mut cache = VerificationCache::new();
+        let (record, verified) = record(1);
+
+        assert!(cache.get(&record, 1000, 1).is_none());
+        cache.insert(&record, verified.clone(), 1000, 5000, 1);
+        assert!(cache.get(&record, 1000, 1) == Some(verified.clone()));
+
+        // The record is no longer fresh.
+        assert!(cache.get(&record, 5001, 1).is_none());
+
+        // The cached result expires even if the record is still fresh.
+        cache.insert(&record, verified.clone(), 1000, u64::MAX, 1);
+        assert!(cache.get(&record, 1000 + MAX_CACHE_AGE, 1).is_some());
+        assert!(cache.get(&record, 1001 + MAX_CACHE_AGE, 1).is_none());
+    }
+
+    #[test]
+    fn it_distinguishes_records() {
+        let mut cache = VerificationCache::new();
+        let (record, verified) = record(1);
+        cache.insert(&record, verified, 1000, 5000, 1);
+
+        let mut other_value = record.clone();
+        other_value.value = vec![2];
+        assert!(cache.get(&other_value, 1000, 1).is_none());
+
+        // A different publisher is a different record.
+        let mut other_publisher = record.clone();
+        other_publisher.publisher = Some(PeerId::random());
+        assert!(cache.get(&other_publisher, 1000, 1).is_none());
+    }
+
+    #[test]
+    fn it_clears_the_cache_in_a_new_epoch() {
+        let mut cache = VerificationCache::new();
+        let (record, verified) = record(1);
+        cache.insert(&record, verified, 1000, 5000, 1);
+
+        assert!(cache.get(&record, 1000, 2).is_none());
+        assert!(cache.records.is_empty());
+    }
+}
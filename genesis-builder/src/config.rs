@@ -2,7 +2,9 @@ use nimiq_block::MacroHeader;
 use nimiq_bls::PublicKey as BlsPublicKey;
 use nimiq_hash::Blake2bHash;
 use nimiq_keys::{Address, Ed25519PublicKey as SchnorrPublicKey};
-use nimiq_primitives::{coin::Coin, networks::NetworkId, slots_allocation::Validator};
+use nimiq_primitives::{
+    coin::Coin, networks::NetworkId, policy::Policy, slots_allocation::Validator,
+};
 use nimiq_serde::{Deserialize, Serialize};
 use nimiq_transaction::account::htlc_contract::AnyHash;
 use nimiq_vrf::VrfSeed;
@@ -62,6 +64,59 @@ pub struct GenesisConfig {
     /// The elected validators for the first epoch after the genesis block.
     #[serde(default)]
     pub slots: Vec<Validator>,
+
+    /// The policy of the network. The default policy is used if absent.
+    #[serde(default)]
+    pub policy: Option<GenesisPolicy>,
+}
+
+/// The `[policy]` section of a genesis config, overriding the values of the default [`Policy`].
+/// The genesis block number of the policy is the one of the genesis block.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct GenesisPolicy {
+    /// Length of a batch including the macro block.
+    pub blocks_per_batch: u32,
+    /// How many batches constitute an epoch.
+    pub batches_per_epoch: u16,
+    /// Maximum size of accounts trie chunks.
+    pub state_chunks_max_size: u32,
+    /// Number of batches a transaction is valid with Albatross consensus.
+    pub transaction_validity_window: u32,
+    /// Block number from which on version 2 of the protocol is used.
+    pub version_2_block_number: u32,
+}
+
+impl GenesisPolicy {
+    /// Returns the policy of a network with the given genesis block number.
+    pub fn to_policy(self, genesis_block_number: u32) -> Policy {
+        Policy {
+            blocks_per_batch: self.blocks_per_batch,
+            batches_per_epoch: self.batches_per_epoch,
+            state_chunks_max_size: self.state_chunks_max_size,
+            transaction_validity_window: self.transaction_validity_window,
+            genesis_block_number,
+            version_2_block_number: self.version_2_block_number,
+        }
+    }
+}
+
+impl Default for GenesisPolicy {
+    fn default() -> Self {
+        Self::from(Policy::default())
+    }
+}
+
+impl From<Policy> for GenesisPolicy {
+    fn from(policy: Policy) -> Self {
+        GenesisPolicy {
+            blocks_per_batch: policy.blocks_per_batch,
+            batches_per_epoch: policy.batches_per_epoch,
+            state_chunks_max_size: policy.state_chunks_max_size,
+            transaction_validity_window: policy.transaction_validity_window,
+            version_2_block_number: policy.version_2_block_number,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
@@ -70,12 +125,22 @@ pub struct GenesisValidator {
     pub signing_key: SchnorrPublicKey,
     pub voting_key: BlsPublicKey,
     pub reward_address: Address,
+    /// The commission of the validator in basis points. Defaults to the maximum commission, which
+    /// pays all rewards to the reward address.
+    #[serde(default = "GenesisValidator::default_commission")]
+    pub commission: u16,
     pub inactive_from: Option<u32>,
     pub jailed_from: Option<u32>,
     #[serde(default)]
     pub retired: bool,
 }
 
+impl GenesisValidator {
+    fn default_commission() -> u16 {
+        Policy::MAX_VALIDATOR_COMMISSION
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
 pub struct GenesisStaker {
     pub staker_address: Address,
@@ -170,6 +235,9 @@ impl GenesisConfig {
                 .expect("genesis block must have validators")
                 .validators
                 .clone(),
+
+            // Not part of the genesis block.
+            policy: None,
         }
     }
 }
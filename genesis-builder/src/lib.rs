@@ -20,7 +20,7 @@ use nimiq_primitives::{
     coin::Coin,
     key_nibbles::KeyNibbles,
     networks::NetworkId,
-    policy::Policy,
+    policy::{Policy, PolicyError},
     slots_allocation::{Validator, Validators},
     trie::TrieItem,
     TreeProof,
@@ -55,6 +55,9 @@ pub enum GenesisBuilderError {
     /// Data for both thin and full accounts specified
     #[error("Data for both thin and full accounts specified")]
     DataForBothThinAndFullAccounts,
+    /// The policy of the network is invalid
+    #[error("Invalid policy: {0}")]
+    InvalidPolicy(#[from] PolicyError),
 }
 
 /// Output of the Genesis builder that represents the Genesis block and its
@@ -85,6 +88,8 @@ pub struct GenesisBuilder {
     pub parent_election_hash: Option<Blake2bHash>,
     /// Merkle root over all of the transactions previous the genesis block.
     pub history_root: Option<Blake2bHash>,
+    /// The policy of the network, if it differs from the default one.
+    pub policy: Option<config::GenesisPolicy>,
     pub accounts_data: Option<GenesisBuilderAccounts>,
 }
 
@@ -205,6 +210,7 @@ impl GenesisBuilder {
             parent_election_hash: None,
             parent_hash: None,
             history_root: None,
+            policy: None,
             accounts_data: None,
         }
     }
@@ -285,6 +291,22 @@ impl GenesisBuilder {
         self
     }
 
+    /// The policy of the network, if it differs from the default one.
+    ///
+    /// It isn't part of the genesis block, see [`GenesisBuilder::policy`].
+    pub fn with_policy(&mut self, policy: config::GenesisPolicy) -> &mut Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    /// Returns the validated policy of the network, which has to be installed by the nodes
+    /// before they use any policy function.
+    pub fn policy(&self) -> Result<Policy, GenesisBuilderError> {
+        let policy = self.policy.unwrap_or_default().to_policy(self.block_number);
+        policy.validate()?;
+        Ok(policy)
+    }
+
     /// Add a validator to the genesis block.
     pub fn with_genesis_validator(
         &mut self,
@@ -304,6 +326,7 @@ impl GenesisBuilder {
                 signing_key,
                 voting_key,
                 reward_address,
+                commission: Policy::MAX_VALIDATOR_COMMISSION,
                 inactive_from,
                 jailed_from,
                 retired,
@@ -362,6 +385,7 @@ impl GenesisBuilder {
             supply,
             state_root,
             mut slots,
+            policy,
         } = config;
         self.with_network(network);
         timestamp.map(|t| self.with_timestamp(t));
@@ -370,6 +394,7 @@ impl GenesisBuilder {
         parent_election_hash.map(|hash| self.with_parent_election_hash(hash));
         parent_hash.map(|hash| self.with_parent_hash(hash));
         history_root.map(|history_root| self.with_history_root(history_root));
+        policy.map(|policy| self.with_policy(policy));
         if !validators.is_empty() {
             self.accounts_data
                 .full()?
@@ -411,6 +436,9 @@ impl GenesisBuilder {
 
     /// Add a basic account with a certain balance to the genesis block.
     pub fn generate(&self, db: MdbxDatabase) -> Result<GenesisInfo, GenesisBuilderError> {
+        // Don't generate a genesis block for a network the nodes would refuse to start.
+        self.policy()?;
+
         // Initialize the environment.
         let timestamp = self.timestamp.unwrap_or_else(OffsetDateTime::now_utc);
         let parent_election_hash = self.parent_election_hash.clone().unwrap_or_default();
@@ -610,6 +638,12 @@ impl GenesisBuilderFullAccounts {
                 validator.retired,
                 &mut TransactionLog::empty(),
             )?;
+            staking_contract.set_validator_commission(
+                &mut store,
+                &validator.validator_address,
+                validator.commission,
+                &mut TransactionLog::empty(),
+            )?;
         }
 
         for staker in &self.stakers {
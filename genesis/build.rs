@@ -3,8 +3,16 @@ use std::{env, fmt::Write, fs, path::Path};
 use nimiq_database::mdbx::{DatabaseConfig, MdbxDatabase};
 use nimiq_genesis_builder::GenesisBuilder;
 use nimiq_hash::Blake2bHash;
+use nimiq_primitives::policy::Policy;
 
-fn write_genesis_rs(directory: &Path, name: &str, genesis_hash: &Blake2bHash, have_accounts: bool) {
+fn write_genesis_rs(
+    directory: &Path,
+    name: &str,
+    genesis_hash: &Blake2bHash,
+    policy: &Policy,
+    have_accounts: bool,
+    have_checkpoints: bool,
+) {
     let hash = {
         let mut hash = String::new();
         write!(&mut hash, "0x{:02x}", genesis_hash.0[0]).unwrap();
@@ -20,13 +28,36 @@ fn write_genesis_rs(directory: &Path, name: &str, genesis_hash: &Blake2bHash, ha
         String::from("None")
     };
 
+    let checkpoints_expr = if have_checkpoints {
+        format!(
+            r#"include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/checkpoints/{name}.dat"))"#
+        )
+    } else {
+        String::from("&[]")
+    };
+
     let genesis_rs = format!(
         r#"GenesisData {{
             block: include_bytes!(concat!(env!("OUT_DIR"), "/genesis/{name}/block.dat")),
             decompressed_keys: include_bytes!(concat!(env!("OUT_DIR"), "/genesis/{name}/decompressed_keys.dat")),
             hash: Blake2bHash([{hash}]),
             accounts: {accounts_expr},
+            checkpoints: {checkpoints_expr},
+            policy: Policy {{
+                blocks_per_batch: {blocks_per_batch},
+                batches_per_epoch: {batches_per_epoch},
+                state_chunks_max_size: {state_chunks_max_size},
+                transaction_validity_window: {transaction_validity_window},
+                genesis_block_number: {genesis_block_number},
+                version_2_block_number: {version_2_block_number},
+            }},
     }}"#,
+        blocks_per_batch = policy.blocks_per_batch,
+        batches_per_epoch = policy.batches_per_epoch,
+        state_chunks_max_size = policy.state_chunks_max_size,
+        transaction_validity_window = policy.transaction_validity_window,
+        genesis_block_number = policy.genesis_block_number,
+        version_2_block_number = policy.version_2_block_number,
     );
     log::debug!("Writing genesis source code: {}", &genesis_rs);
     fs::write(directory.join("genesis.rs"), genesis_rs.as_bytes()).unwrap();
@@ -48,8 +79,23 @@ fn generate_albatross(name: &str, out_dir: &Path, src_dir: &Path) {
     })
     .expect("Could not open a volatile database");
     let builder = GenesisBuilder::from_config_file(genesis_config).unwrap();
+    let policy = builder.policy().unwrap();
     let (genesis_hash, have_accounts) = builder.write_to_files(db, &directory).unwrap();
-    write_genesis_rs(&directory, name, &genesis_hash, have_accounts);
+
+    // The sync checkpoints of a network are optional.
+    let checkpoints = src_dir
+        .parent()
+        .unwrap()
+        .join("checkpoints")
+        .join(format!("{name}.dat"));
+    write_genesis_rs(
+        &directory,
+        name,
+        &genesis_hash,
+        &policy,
+        have_accounts,
+        checkpoints.exists(),
+    );
 }
 
 fn main() {
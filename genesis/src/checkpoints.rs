@@ -0,0 +1,152 @@
+use nimiq_block::MacroBlock;
+use nimiq_keys::{Ed25519PublicKey, KeyPair};
+use nimiq_primitives::networks::NetworkId;
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_utils::tagged_signing::{TaggedSignable, TaggedSigned};
+
+/// A sync checkpoint: an election block together with the zk proof that it descends from the
+/// genesis block. Light clients can start syncing from the newest checkpoint instead of genesis.
+///
+/// The proof of a checkpoint is verified before it is used. Checkpoints are additionally signed,
+/// such that only checkpoints of a trusted issuer are considered, see [`SyncCheckpoints`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SyncCheckpoint {
+    /// The format version of the checkpoint.
+    pub version: u8,
+    /// The election block the sync starts from.
+    pub block: MacroBlock,
+    /// The serialized zk proof for the election block.
+    pub proof: Vec<u8>,
+}
+
+impl SyncCheckpoint {
+    /// The checkpoint format version supported by this client. Checkpoints of other versions are
+    /// ignored.
+    pub const VERSION: u8 = 1;
+
+    pub fn new(block: MacroBlock, proof: Vec<u8>) -> Self {
+        Self {
+            version: Self::VERSION,
+            block,
+            proof,
+        }
+    }
+}
+
+impl TaggedSignable for SyncCheckpoint {
+    const TAG: u8 = 0x05;
+}
+
+/// A sync checkpoint signed by its issuer.
+pub type SignedSyncCheckpoint = TaggedSigned<SyncCheckpoint, KeyPair>;
+
+/// The sync checkpoints a client may start syncing from, together with the keys of the issuers
+/// it trusts. Checkpoints that aren't signed by one of the trusted keys are ignored.
+#[derive(Clone, Debug, Default)]
+pub struct SyncCheckpoints {
+    pub trusted_keys: Vec<Ed25519PublicKey>,
+    pub checkpoints: Vec<SignedSyncCheckpoint>,
+}
+
+impl SyncCheckpoints {
+    pub fn new(trusted_keys: Vec<Ed25519PublicKey>) -> Self {
+        Self {
+            trusted_keys,
+            checkpoints: vec![],
+        }
+    }
+
+    /// Adds the given checkpoints, e.g. the ones embedded for a network or read from a file.
+    pub fn extend<I: IntoIterator<Item = SignedSyncCheckpoint>>(&mut self, checkpoints: I) {
+        self.checkpoints.extend(checkpoints);
+    }
+
+    /// Returns the most recent checkpoint for the given network that is signed by a trusted key,
+    /// if any.
+    pub fn latest(&self, network_id: NetworkId) -> Option<&SyncCheckpoint> {
+        self.checkpoints
+            .iter()
+            .filter(|checkpoint| {
+                checkpoint.record.version == SyncCheckpoint::VERSION
+                    && checkpoint.record.block.network() == network_id
+                    && checkpoint.record.block.is_election()
+                    && self.is_trusted(checkpoint)
+            })
+            .map(|checkpoint| &checkpoint.record)
+            .max_by_key(|checkpoint| checkpoint.block.block_number())
+    }
+
+    fn is_trusted(&self, checkpoint: &SignedSyncCheckpoint) -> bool {
+        self.trusted_keys
+            .iter()
+            .any(|public_key| checkpoint.verify(public_key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nimiq_block::MacroBlock;
+    use nimiq_keys::{KeyPair, PrivateKey};
+    use nimiq_primitives::{networks::NetworkId, policy::Policy};
+    use nimiq_utils::tagged_signing::{TaggedKeyPair, TaggedSigned};
+
+    use super::{SignedSyncCheckpoint, SyncCheckpoint, SyncCheckpoints};
+
+    fn key_pair(seed: u8) -> KeyPair {
+        KeyPair::from(PrivateKey::from([seed; 32]))
+    }
+
+    fn checkpoint(
+        key_pair: &KeyPair,
+        network_id: NetworkId,
+        block_number: u32,
+    ) -> SignedSyncCheckpoint {
+        let mut block = MacroBlock::default();
+        block.header.network = network_id;
+        block.header.block_number = block_number;
+        let checkpoint = SyncCheckpoint::new(block, vec![]);
+        let signature = key_pair.tagged_sign(&checkpoint);
+        TaggedSigned::new(checkpoint, signature)
+    }
+
+    #[test]
+    fn it_only_returns_checkpoints_of_trusted_issuers() {
+        let trusted = key_pair(1);
+        let untrusted = key_pair(2);
+        let network_id = NetworkId::UnitAlbatross;
+        let epoch = Policy::blocks_per_epoch();
+
+        let mut checkpoints = SyncCheckpoints::new(vec![trusted.public]);
+        checkpoints.extend([
+            checkpoint(&trusted, network_id, epoch),
+            checkpoint(&trusted, network_id, 2 * epoch),
+            checkpoint(&untrusted, network_id, 3 * epoch),
+            checkpoint(&trusted, NetworkId::TestAlbatross, 4 * epoch),
+        ]);
+        let latest = checkpoints.latest(network_id).unwrap();
+        assert_eq!(latest.block.block_number(), 2 * epoch);
+
+        // Checkpoints whose signature doesn't match the record are ignored too.
+        let mut forged = checkpoint(&trusted, network_id, 5 * epoch);
+        forged.record.block.header.block_number = 6 * epoch;
+        checkpoints.extend([forged]);
+        let latest = checkpoints.latest(network_id).unwrap();
+        assert_eq!(latest.block.block_number(), 2 * epoch);
+
+        assert!(SyncCheckpoints::default().latest(network_id).is_none());
+    }
+
+    #[test]
+    fn it_ignores_checkpoints_that_are_no_election_blocks() {
+        let trusted = key_pair(1);
+        let network_id = NetworkId::UnitAlbatross;
+
+        let mut checkpoints = SyncCheckpoints::new(vec![trusted.public]);
+        checkpoints.extend([checkpoint(
+            &trusted,
+            network_id,
+            Policy::blocks_per_epoch() + Policy::blocks_per_batch(),
+        )]);
+        assert!(checkpoints.latest(network_id).is_none());
+    }
+}
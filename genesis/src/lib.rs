@@ -1,3 +1,5 @@
+pub use checkpoints::{SignedSyncCheckpoint, SyncCheckpoint, SyncCheckpoints};
 pub use networks::{NetworkId, NetworkInfo};
 
+pub mod checkpoints;
 pub mod networks;
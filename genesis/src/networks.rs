@@ -10,17 +10,21 @@ use nimiq_database::mdbx::MdbxDatabase;
 use nimiq_genesis_builder::{GenesisBuilder, GenesisBuilderError, GenesisInfo};
 use nimiq_hash::Blake2bHash;
 pub use nimiq_primitives::networks::NetworkId;
-use nimiq_primitives::trie::TrieItem;
+use nimiq_primitives::{policy::Policy, trie::TrieItem};
 use nimiq_serde::Deserialize;
 #[cfg(feature = "genesis-override")]
 use nimiq_serde::Serialize;
 
+use crate::checkpoints::SignedSyncCheckpoint;
+
 #[derive(Clone, Debug)]
 struct GenesisData {
     block: &'static [u8],
     decompressed_keys: &'static [u8],
     hash: Blake2bHash,
     accounts: Option<&'static [u8]>,
+    checkpoints: &'static [u8],
+    policy: Policy,
 }
 
 #[derive(Clone, Debug)]
@@ -62,6 +66,23 @@ impl NetworkInfo {
         })
     }
 
+    /// Returns the policy of this network. It has to be installed with [`Policy::init`] before
+    /// any other policy function is used.
+    #[inline]
+    pub fn policy(&self) -> Policy {
+        self.genesis.policy
+    }
+
+    /// Returns the sync checkpoints embedded for this network. They are only used if they are
+    /// signed by a trusted key, see [`SyncCheckpoints`](crate::checkpoints::SyncCheckpoints).
+    pub fn sync_checkpoints(&self) -> Vec<SignedSyncCheckpoint> {
+        if self.genesis.checkpoints.is_empty() {
+            return vec![];
+        }
+        Deserialize::deserialize_from_vec(self.genesis.checkpoints)
+            .expect("Failed to deserialize sync checkpoints.")
+    }
+
     pub fn from_network_id(network_id: NetworkId) -> &'static Self {
         network(network_id).unwrap_or_else(|| panic!("No such network ID: {network_id}"))
     }
@@ -72,11 +93,13 @@ fn read_genesis_config(config: &Path) -> Result<GenesisData, GenesisBuilderError
     let env =
         MdbxDatabase::new_volatile(Default::default()).expect("Could not open a volatile database");
 
+    let builder = GenesisBuilder::from_config_file(config)?;
+    let policy = builder.policy()?;
     let GenesisInfo {
         block,
         hash,
         accounts,
-    } = GenesisBuilder::from_config_file(config)?.generate(env)?;
+    } = builder.generate(env)?;
 
     let block = block.serialize_to_vec();
     let accounts = accounts.map(|accounts| accounts.serialize_to_vec());
@@ -86,6 +109,8 @@ fn read_genesis_config(config: &Path) -> Result<GenesisData, GenesisBuilderError
         decompressed_keys: &[],
         hash,
         accounts: accounts.map(|accounts| Box::leak(accounts.into_boxed_slice()) as &'static _),
+        checkpoints: &[],
+        policy,
     })
 }
 
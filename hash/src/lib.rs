@@ -21,6 +21,7 @@ use nimiq_macros::{
 use nimiq_mmr::hash::Merge;
 use nimiq_serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256, Sha512};
+use sha3::Keccak256;
 use subtle::ConstantTimeEq;
 
 pub mod argon2kdf;
@@ -142,6 +143,11 @@ impl Blake2bHasher {
     pub fn new() -> Self {
         Blake2bHasher(Blake2b::new(BLAKE2B_LENGTH))
     }
+
+    /// Creates a hasher for keyed hashes. The key must not be longer than 64 bytes.
+    pub fn with_key(key: &[u8]) -> Self {
+        Blake2bHasher(Blake2b::with_key(BLAKE2B_LENGTH, key))
+    }
 }
 
 impl Default for Blake2bHasher {
@@ -336,6 +342,70 @@ impl Hasher for Sha256Hasher {
     }
 }
 
+// KECCAK256
+
+const KECCAK256_LENGTH: usize = 32;
+create_typed_array!(Keccak256Hash, u8, KECCAK256_LENGTH);
+add_hex_io_fns_typed_arr!(Keccak256Hash, KECCAK256_LENGTH);
+add_serialization_fns_typed_arr!(Keccak256Hash, KECCAK256_LENGTH);
+add_constant_time_eq_typed_arr!(Keccak256Hash);
+/// Keccak-256 as used by Ethereum, which differs from the standardized SHA3-256 in its padding.
+pub struct Keccak256Hasher(Keccak256);
+impl HashOutput for Keccak256Hash {
+    type Builder = Keccak256Hasher;
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+    fn len() -> usize {
+        KECCAK256_LENGTH
+    }
+}
+
+impl SerializeContent for Keccak256Hash {
+    fn serialize_content<W: io::Write, H: HashOutput>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(self.as_bytes())?;
+        Ok(())
+    }
+}
+
+impl Keccak256Hasher {
+    pub fn new() -> Self {
+        Keccak256Hasher(Keccak256::default())
+    }
+}
+
+impl Default for Keccak256Hasher {
+    fn default() -> Self {
+        Keccak256Hasher::new()
+    }
+}
+
+impl io::Write for Keccak256Hasher {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.update(buf);
+        Ok(buf.len())
+    }
+
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.0.update(buf);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Hasher for Keccak256Hasher {
+    type Output = Keccak256Hash;
+
+    fn finish(self) -> Keccak256Hash {
+        let result = self.0.finalize();
+        Keccak256Hash::from(result.as_slice())
+    }
+}
+
 add_hash_trait_arr!([u8; 32]);
 add_hash_trait_arr!([u8; 64]);
 add_hash_trait_arr!([u8]);
@@ -3,7 +3,8 @@ use std::io::Write;
 use nimiq_hash::{
     argon2kdf,
     sha512::{Sha512Hash, Sha512Hasher},
-    Blake2bHash, Blake2bHasher, Blake2sHash, Blake2sHasher, Hasher, Sha256Hash, Sha256Hasher,
+    Blake2bHash, Blake2bHasher, Blake2sHash, Blake2sHasher, Hasher, Keccak256Hash, Keccak256Hasher,
+    Sha256Hash, Sha256Hasher,
 };
 use nimiq_test_log::test;
 
@@ -27,6 +28,23 @@ fn it_can_compute_sha256() {
     );
 }
 
+#[test]
+fn it_can_compute_keccak256() {
+    // keccak256('test') = '9c22ff5f21f0b81b113e63f7db6da94fedef11b2119b4088b89664fb9a3cb658'
+
+    assert_eq!(
+        Keccak256Hasher::default().digest(b"test"),
+        Keccak256Hash::from("9c22ff5f21f0b81b113e63f7db6da94fedef11b2119b4088b89664fb9a3cb658")
+    );
+    let mut h = Keccak256Hasher::default();
+    h.write_all(b"te").unwrap();
+    h.write_all(b"st").unwrap();
+    assert_eq!(
+        h.finish(),
+        Keccak256Hash::from("9c22ff5f21f0b81b113e63f7db6da94fedef11b2119b4088b89664fb9a3cb658")
+    );
+}
+
 #[test]
 fn it_can_compute_blake2b() {
     // blake2b('test') = '928b20366943e2afd11ebc0eae2e53a93bf177a4fcf35bcc64d503704e65e202'
@@ -71,6 +71,12 @@ impl Address {
     }
 
     pub fn to_user_friendly_address(&self) -> String {
+        self.to_user_friendly_groups().join(" ")
+    }
+
+    /// Returns the user friendly address split into its nine groups of four characters. The first
+    /// group consists of the country code and the checksum.
+    pub fn to_user_friendly_groups(&self) -> Vec<String> {
         let mut spec = data_encoding::Specification::new();
         spec.symbols.push_str(Address::NIMIQ_ALPHABET);
         let encoding = spec.encoding().unwrap();
@@ -87,20 +93,19 @@ impl Address {
             .collect::<String>();
         // Fixme: Because of https://github.com/rust-lang/rust/issues/92178 we need to specify `as &str`
         let friendly_addr = Address::CCODE.to_string() + (&check as &str) + (&base32 as &str);
-        let mut friendly_spaces = String::with_capacity(36 + 8);
-        for i in 0..9 {
-            friendly_spaces.push_str(
-                &friendly_addr
-                    .chars()
-                    .skip(4 * i)
-                    .take(4)
-                    .collect::<String>(),
-            );
-            if i != 8 {
-                friendly_spaces.push(' ');
-            }
-        }
-        friendly_spaces
+        (0..9)
+            .map(|i| friendly_addr.chars().skip(4 * i).take(4).collect())
+            .collect()
+    }
+
+    /// Returns a shortened user friendly address, e.g. `NQ07 0000 … 0000`.
+    ///
+    /// Besides the checksum, it shows both the first and the last group of the address. Vanity
+    /// address generators can feasibly match a few chosen characters at one end of the address,
+    /// so showing only a prefix makes it easy to impersonate an address.
+    pub fn to_short_user_friendly_address(&self) -> String {
+        let groups = self.to_user_friendly_groups();
+        format!("{} {} … {}", groups[0], groups[1], groups[8])
     }
 
     fn iban_check(s: &str) -> u32 {
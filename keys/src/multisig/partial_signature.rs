@@ -36,3 +36,33 @@ impl<'a> From<&'a [u8; PartialSignature::SIZE]> for PartialSignature {
         PartialSignature::from(*bytes)
     }
 }
+
+#[cfg(feature = "serde-derive")]
+mod serde_derive {
+    use serde::{
+        de::{Deserialize, Deserializer},
+        ser::{Serialize, Serializer},
+    };
+
+    use super::PartialSignature;
+
+    impl Serialize for PartialSignature {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            nimiq_serde::FixedSizeByteArray::from(*self.as_bytes()).serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for PartialSignature {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            let buf: [u8; PartialSignature::SIZE] =
+                nimiq_serde::FixedSizeByteArray::deserialize(deserializer)?.into_inner();
+            Ok(Self::from(&buf))
+        }
+    }
+}
@@ -8,7 +8,7 @@ use hex::FromHex;
 
 use crate::{
     errors::{KeysError, ParseError},
-    Ed25519Signature, PrivateKey,
+    Address, Ed25519Signature, PrivateKey,
 };
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -41,6 +41,11 @@ impl Ed25519PublicKey {
         ))
     }
 
+    /// Derives the address of this public key, e.g. of a validator's signing key.
+    pub fn to_address(&self) -> Address {
+        Address::from(self)
+    }
+
     #[inline]
     pub fn to_hex(&self) -> String {
         hex::encode(self.as_bytes())
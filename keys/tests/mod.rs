@@ -160,6 +160,17 @@ fn it_computes_friendly_addresses() {
     );
 }
 
+#[test]
+fn it_computes_short_friendly_addresses() {
+    let addr = Address::from_user_friendly_address("NQ05 563U 530Y XDRT L7GQ M6HE YRNU 20FE 4PNR")
+        .unwrap();
+    assert_eq!(
+        addr.to_user_friendly_groups(),
+        vec!["NQ05", "563U", "530Y", "XDRT", "L7GQ", "M6HE", "YRNU", "20FE", "4PNR"]
+    );
+    assert_eq!(addr.to_short_user_friendly_address(), "NQ05 563U … 4PNR");
+}
+
 #[test]
 fn it_parses_friendly_addresses() {
     let addr = Address::from_user_friendly_address("NQ05 563U 530Y XDRT L7GQ M6HE YRNU 20FE 4PNR");
@@ -1,4 +1,4 @@
-use std::{fs, num::NonZeroU8, sync::Arc};
+use std::{num::NonZeroU8, sync::Arc};
 
 use instant::SystemTime;
 use nimiq_block::Block;
@@ -9,8 +9,8 @@ use nimiq_blockchain_proxy::BlockchainProxy;
 #[cfg(feature = "full-consensus")]
 use nimiq_consensus::Error::BlockchainError;
 use nimiq_consensus::{
-    sync::syncer_proxy::SyncerProxy, BlsCache, Consensus as AbstractConsensus,
-    ConsensusProxy as AbstractConsensusProxy,
+    consensus::consensus_policy::ConsensusPolicy, sync::syncer_proxy::SyncerProxy, BlsCache,
+    Consensus as AbstractConsensus, ConsensusProxy as AbstractConsensusProxy,
 };
 #[cfg(feature = "full-consensus")]
 use nimiq_dht::Verifier;
@@ -19,7 +19,7 @@ use nimiq_genesis::NetworkId;
 use nimiq_genesis::NetworkInfo;
 use nimiq_light_blockchain::LightBlockchain;
 #[cfg(feature = "validator")]
-use nimiq_mempool::mempool::Mempool;
+use nimiq_mempool::{mempool::Mempool, store::MempoolStore};
 #[cfg(feature = "validator")]
 use nimiq_mempool_task::MempoolTask as AbstractMempoolTask;
 use nimiq_network_interface::{
@@ -33,9 +33,11 @@ use nimiq_network_libp2p::{
 };
 use nimiq_primitives::policy::Policy;
 #[cfg(feature = "full-consensus")]
-use nimiq_utils::time::OffsetTime;
+use nimiq_utils::{spawn, time::OffsetTime};
 #[cfg(feature = "validator")]
-use nimiq_validator::key_utils::VotingKeys;
+use nimiq_validator::key_utils::{SigningKeys, VotingKeys};
+#[cfg(feature = "validator")]
+use nimiq_validator::signer::RemoteSigner;
 #[cfg(feature = "validator")]
 use nimiq_validator::validator::Validator as AbstractValidator;
 #[cfg(feature = "validator")]
@@ -47,8 +49,12 @@ use nimiq_wallet::WalletStore;
 use nimiq_zkp::ZKP_VERIFYING_DATA;
 #[cfg(feature = "zkp-prover")]
 use nimiq_zkp_circuits::setup::{all_files_created, load_verifying_data, setup, DEVELOPMENT_SEED};
+#[cfg(all(feature = "zkp-prover", feature = "database-storage"))]
+use nimiq_zkp_component::proof_store::{DBProofCache, ProofCache};
 #[cfg(feature = "database-storage")]
 use nimiq_zkp_component::proof_store::{DBProofStore, ProofStore};
+#[cfg(feature = "zkp-prover")]
+use nimiq_zkp_component::prover_backend::ProcessProverBackend;
 use nimiq_zkp_component::zkp_component::{
     ZKPComponent as AbstractZKPComponent, ZKPComponentProxy as AbstractZKPComponentProxy,
 };
@@ -59,7 +65,6 @@ use parking_lot::{Mutex, RwLock};
 use rand::SeedableRng;
 #[cfg(feature = "zkp-prover")]
 use rand_chacha::ChaCha20Rng;
-use rustls_pemfile::Item;
 
 use crate::{
     config::config::{ClientConfig, SyncMode},
@@ -111,6 +116,9 @@ pub(crate) struct ClientInner {
     wallet_store: Arc<WalletStore>,
 
     zkp_component: ZKPComponentProxy,
+
+    /// Cache of uncompressed BLS public keys shared by the blockchain and the validator.
+    bls_cache: Arc<Mutex<BlsCache>>,
 }
 
 /// This function is used to generate the services flags (provided, needed) based upon the configured sync mode
@@ -159,20 +167,11 @@ impl ClientInner {
         }
         let network_info = NetworkInfo::from_network_id(config.network_id);
 
-        let policy_config = Policy {
-            genesis_block_number: network_info.genesis_block().block_number(),
-            ..Default::default()
-        };
-
-        let _ = Policy::get_or_init(policy_config);
-
-        // Verify Policy is configured with the genesis block number we expect
-        if network_info.genesis_block().block_number() != Policy::genesis_block_number() {
-            log::error!("The genesis block number must be configured before using any other Policy function");
-            return Err(Error::config_error(
-                "There is a genesis block number configuration mismatch",
-            ));
-        }
+        // Install the policy of the network. It must be configured before using any other Policy
+        // function, so this fails if a different policy has been installed already.
+        Policy::init(network_info.policy()).map_err(|error| {
+            Error::config_error(format!("Invalid policy configuration: {error}"))
+        })?;
 
         // Load the correct verifying key.
         ZKP_VERIFYING_DATA.init_with_network_id(config.network_id);
@@ -241,6 +240,11 @@ impl ClientInner {
         let (mut provided_services, required_services) =
             generate_service_flags(config.consensus.sync_mode, config.consensus.index_history);
 
+        // Nodes that prune their history can't provide the full history to other peers.
+        if config.consensus.history_retention_epochs.is_some() {
+            provided_services.remove(Services::HISTORY);
+        }
+
         // We update the services flags depending on our validator configuration
         #[cfg(feature = "validator")]
         if config.validator.is_some() {
@@ -256,6 +260,11 @@ impl ClientInner {
             .advertised_addresses
             .clone()
             .unwrap_or_default();
+        // Behind sentries we only advertise the relay circuits on the sentries, which are added
+        // once they are established.
+        if !config.network.sentries.is_empty() {
+            peer_contact_addresses.clear();
+        }
         peer_contact_addresses.retain(|address| {
             let mut protocols = address.iter();
             match protocols.next() {
@@ -290,47 +299,19 @@ impl ClientInner {
             .map(|seed| seed.address)
             .collect();
 
-        let tls_config = if let Some(tls_config) = config.network.tls {
-            // Check that the provided private key has the expected format and convert the PEM file to DER format.
-            let private_key = fs::read(tls_config.private_key).and_then(|private_key_bytes| {
-                match rustls_pemfile::read_one(&mut &*private_key_bytes)? {
-                    Some(Item::Sec1Key(key)) => Ok(key.secret_sec1_der().to_vec()),
-                    Some(Item::Pkcs8Key(key)) => Ok(key.secret_pkcs8_der().to_vec()),
-                    Some(Item::Pkcs1Key(key)) => Ok(key.secret_pkcs1_der().to_vec()),
-                    _ => Err(std::io::Error::new(
-                        std::io::ErrorKind::InvalidData,
-                        "Invalid TLS private key",
-                    )),
-                }
-            })?;
-            // Check that the provided certificates have the expected format and convert the PEM file to a list of
-            // certificates in DER format.
-            // We could have several certificates in the same file, read them all and build the array of certificates
-            // that the network requires.
-            let certificates = fs::read(tls_config.certificates).and_then(|certificate_bytes| {
-                rustls_pemfile::read_all(&mut &*certificate_bytes)
-                    .map(|item| match item {
-                        Ok(Item::X509Certificate(cert)) => Ok(cert.to_vec()),
-                        _ => Err(std::io::Error::new(
-                            std::io::ErrorKind::InvalidData,
-                            "Invalid TLS certificate(s)",
-                        )),
-                    })
-                    .collect()
-            })?;
-            Some(NetworkTls {
-                private_key,
-                certificates,
+        let tls_config = config
+            .network
+            .tls
+            .map(|tls_config| {
+                NetworkTls::from_pem_files(tls_config.private_key, tls_config.certificates)
             })
-        } else {
-            None
-        };
+            .transpose()?;
 
         // Setup libp2p network
-        let network_config = NetworkConfig::new(
+        let mut network_config = NetworkConfig::new(
             identity_keypair,
             peer_contact,
-            seeds,
+            seeds.clone(),
             network_info.genesis_hash().clone(),
             false,
             required_services,
@@ -346,6 +327,28 @@ impl ClientInner {
                 .dht_quorum
                 .unwrap_or(NonZeroU8::new(3).unwrap()),
         );
+        if let Some(upload_bandwidth) = config.network.upload_bandwidth {
+            network_config.seeding.upload_bandwidth = upload_bandwidth;
+        }
+        if let Some(seeding_share) = config.network.seeding_share {
+            network_config.seeding.share = seeding_share;
+        }
+        if let Some(multiplier) = config.network.rate_limit_subnet_multiplier {
+            network_config.rate_limit_policy.subnet_multiplier =
+                (multiplier > 0).then_some(multiplier);
+        }
+        if let Some(multiplier) = config.network.rate_limit_global_multiplier {
+            network_config.rate_limit_policy.global_multiplier =
+                (multiplier > 0).then_some(multiplier);
+        }
+        network_config.peer_contacts_path = config.storage.peer_contacts_path(config.network_id);
+        if config.network.sentries.is_empty() {
+            network_config.relays = config.network.relays.unwrap_or_else(|| seeds.clone());
+        } else {
+            log::info!(sentries = ?config.network.sentries, "Connecting through sentries only");
+        }
+        network_config.sentries = config.network.sentries.clone();
+        network_config.relay_clients = config.network.relay_clients.clone();
 
         log::debug!(
             addresses = ?config.network.listen_addresses,
@@ -375,12 +378,21 @@ impl ClientInner {
             config.consensus.sync_mode,
             config.database,
         )?;
+        #[cfg(feature = "database-storage")]
+        crate::migrations::migrator().run(&environment)?;
 
-        let bls_cache = Arc::new(Mutex::new(BlsCache::default()));
+        // The BLS keys are interned, so this single cache keeps the uncompressed keys alive for the
+        // blockchain as well as the validator.
+        let bls_cache = Arc::new(Mutex::new(BlsCache::with_capacity(
+            config.consensus.bls_cache_size,
+        )));
 
         #[cfg(feature = "full-consensus")]
         let mut blockchain_config = BlockchainConfig {
             max_epochs_stored: config.consensus.max_epochs_stored,
+            history_retention_epochs: config.consensus.history_retention_epochs,
+            parallel_verification: config.consensus.parallel_block_verification,
+            fork_retention_batches: config.consensus.fork_retention_batches,
             ..Default::default()
         };
 
@@ -389,6 +401,11 @@ impl ClientInner {
             Some(Box::new(DBProofStore::new(environment.clone())));
         #[cfg(not(feature = "database-storage"))]
         let zkp_storage = None;
+        #[cfg(all(feature = "zkp-prover", feature = "database-storage"))]
+        let zkp_cache: Option<Arc<dyn ProofCache>> =
+            Some(Arc::new(DBProofCache::new(environment.clone())));
+        #[cfg(all(feature = "zkp-prover", not(feature = "database-storage")))]
+        let zkp_cache = None;
 
         let blockchain_proxy = match config.consensus.sync_mode {
             #[cfg(not(feature = "full-consensus"))]
@@ -415,6 +432,12 @@ impl ClientInner {
                         return Err(Error::Consensus(BlockchainError(err)));
                     }
                 };
+                if let Some(path) = &config.consensus.recording_path {
+                    let file = std::fs::File::create(path)?;
+                    blockchain
+                        .read()
+                        .start_recording(std::io::BufWriter::new(file))?;
+                }
                 BlockchainProxy::from(&blockchain)
             }
             SyncMode::Light => BlockchainProxy::from(&Arc::new(RwLock::new(LightBlockchain::new(
@@ -425,6 +448,8 @@ impl ClientInner {
         // Create the Dht verifier
         #[cfg(feature = "full-consensus")]
         let dht_verifier = Verifier::new(blockchain_proxy.clone());
+        #[cfg(feature = "full-consensus")]
+        let proven_signing_keys = dht_verifier.proven_signing_keys();
 
         // Create the network.
         let network = Arc::new(
@@ -456,9 +481,10 @@ impl ClientInner {
                         blockchain_proxy.clone(),
                         Arc::clone(&network),
                         true,
-                        None,
+                        Arc::new(ProcessProverBackend::default()),
                         zk_prover_config.prover_keys_path,
                         zkp_storage,
+                        zkp_cache,
                     )
                     .await
                 } else {
@@ -472,7 +498,7 @@ impl ClientInner {
                 let syncer = SyncerProxy::new_history(
                     blockchain_proxy.clone(),
                     Arc::clone(&network),
-                    bls_cache,
+                    Arc::clone(&bls_cache),
                     network_events,
                 )
                 .await;
@@ -486,9 +512,10 @@ impl ClientInner {
                         blockchain_proxy.clone(),
                         Arc::clone(&network),
                         true,
-                        None,
+                        Arc::new(ProcessProverBackend::default()),
                         zk_prover_config.prover_keys_path,
                         zkp_storage,
+                        zkp_cache,
                     )
                     .await
                 } else {
@@ -503,7 +530,7 @@ impl ClientInner {
                 let syncer = SyncerProxy::new_full(
                     blockchain_proxy.clone(),
                     Arc::clone(&network),
-                    bls_cache,
+                    Arc::clone(&bls_cache),
                     zkp_component.proxy(),
                     network_events,
                     config.consensus.full_sync_threshold,
@@ -512,13 +539,17 @@ impl ClientInner {
                 (syncer, zkp_component)
             }
             SyncMode::Light => {
+                // Light clients start from the newest trusted sync checkpoint, if any.
+                let mut sync_checkpoints = config.consensus.sync_checkpoints.clone();
+                sync_checkpoints.extend(network_info.sync_checkpoints());
                 let zkp_component =
                     ZKPComponent::new(blockchain_proxy.clone(), Arc::clone(&network), zkp_storage)
-                        .await;
+                        .await
+                        .with_sync_checkpoints(&sync_checkpoints);
                 let syncer = SyncerProxy::new_light(
                     blockchain_proxy.clone(),
                     Arc::clone(&network),
-                    bls_cache,
+                    Arc::clone(&bls_cache),
                     zkp_component.proxy(),
                     network_events,
                 )
@@ -527,18 +558,37 @@ impl ClientInner {
             }
         };
 
-        // Open wallet
+        // Open wallet and import the configured wallet keys
         #[cfg(feature = "wallet")]
         let wallet_store = Arc::new(WalletStore::new(environment.clone()));
+        #[cfg(feature = "wallet")]
+        config.storage.import_wallet_keys(&wallet_store)?;
 
         // Initialize consensus
-        let consensus = Consensus::new(
+        let mut consensus = Consensus::new(
             blockchain_proxy.clone(),
             Arc::clone(&network),
             syncer_proxy,
-            config.consensus.min_peers,
+            ConsensusPolicy::new(config.consensus.min_peers)
+                .with_min_peers_providing(Services::HISTORY, config.consensus.min_history_peers)
+                .with_min_peers_providing(
+                    Services::VALIDATOR,
+                    config.consensus.min_validator_peers,
+                ),
             zkp_component.proxy(),
-        );
+        )
+        .with_head_request_schedule(config.consensus.head_request_schedule)
+        .with_transaction_gossip(config.network.transaction_gossip.clone());
+        if let Some(recovery) = config.consensus.recovery {
+            consensus = consensus.with_recovery(recovery, seeds);
+        }
+
+        // Light clients need to prove the signing keys of validators to verify their DHT records.
+        #[cfg(feature = "full-consensus")]
+        if let BlockchainProxy::Light(_) = blockchain_proxy {
+            let consensus_proxy = consensus.proxy();
+            spawn(async move { proven_signing_keys.prove(consensus_proxy).await });
+        }
 
         #[cfg(feature = "validator")]
         let mut validator_or_mempool = None;
@@ -553,6 +603,8 @@ impl ClientInner {
                     // Load validator address
                     let automatic_reactivate = validator_config.automatic_reactivate;
 
+                    let dht_republish_interval = validator_config.dht_republish_interval;
+
                     let dht_fallback_url = validator_config.dht_fallback_url;
 
                     let dht_fallback = {
@@ -584,11 +636,22 @@ impl ClientInner {
                         }
                     };
 
-                    // Load signing key (before we give away ownership of the storage config)
-                    let signing_key = config.storage.signing_keypair()?;
+                    // Load signing keys (before we give away ownership of the storage config)
+                    let mut signing_keys = SigningKeys::new(config.storage.signing_keypairs()?);
 
                     // Load validator key (before we give away ownership of the storage config)
-                    let voting_keys = VotingKeys::new(config.storage.voting_keypairs()?);
+                    let mut voting_keys = VotingKeys::new(config.storage.voting_keypairs()?);
+
+                    // Load the keys added by previous key rotations.
+                    let key_store = config.storage.rotated_key_store();
+                    if let Some(key_store) = &key_store {
+                        for key in key_store.signing_keys()? {
+                            signing_keys.add_key(key);
+                        }
+                        for key in key_store.voting_keys()? {
+                            voting_keys.add_key(key);
+                        }
+                    }
 
                     // Load fee key (before we give away ownership of the storage config)
                     let fee_key = config.storage.fee_keypair()?;
@@ -598,19 +661,30 @@ impl ClientInner {
                         Arc::new(dht_fallback),
                     ));
 
-                    let validator = Validator::new(
+                    let mut validator = Validator::new(
                         environment.clone(),
                         &consensus,
                         Arc::clone(blockchain),
                         validator_network,
                         validator_address,
                         automatic_reactivate,
-                        signing_key,
+                        dht_republish_interval,
+                        signing_keys,
                         voting_keys,
                         fee_key,
                         config.mempool.clone(),
                     );
 
+                    if let Some(remote_signer) = validator_config.remote_signer {
+                        let signer =
+                            RemoteSigner::connect(remote_signer.address, remote_signer.psk.0)
+                                .await?;
+                        validator.set_signing_backend(Arc::new(signer));
+                    }
+                    if let Some(key_store) = key_store {
+                        validator.set_key_store(Arc::new(key_store));
+                    }
+
                     // Use the validator's mempool as TransactionVerificationCache in the blockchain.
                     blockchain.write().tx_verification_cache =
                         Arc::<Mempool>::clone(&validator.mempool_task.mempool);
@@ -634,16 +708,31 @@ impl ClientInner {
         ) && validator_or_mempool.is_none()
         {
             if let BlockchainProxy::Full(ref blockchain) = blockchain_proxy {
-                validator_or_mempool = Some(ValidatorOrMempool::Mempool(MempoolTask::new(
+                let mempool_store = config
+                    .mempool
+                    .persist
+                    .then(|| MempoolStore::new(environment.clone()));
+                let mempool_task = MempoolTask::new(
                     &consensus,
                     Arc::clone(blockchain),
                     config.mempool,
-                )));
+                    mempool_store,
+                );
+
+                // Use the mempool as TransactionVerificationCache in the blockchain, such that
+                // compact blocks can be reconstructed from it.
+                blockchain.write().tx_verification_cache =
+                    Arc::<Mempool>::clone(&mempool_task.mempool);
+
+                validator_or_mempool = Some(ValidatorOrMempool::Mempool(mempool_task));
             }
         }
 
-        // Start network.
-        network.listen_on(config.network.listen_addresses).await;
+        // Start network. Behind sentries we don't listen on our own addresses, such that we are
+        // only reachable through the sentries.
+        if config.network.sentries.is_empty() {
+            network.listen_on(config.network.listen_addresses).await;
+        }
         network.start_connecting().await;
 
         Ok(Client {
@@ -656,6 +745,7 @@ impl ClientInner {
                 #[cfg(feature = "wallet")]
                 wallet_store,
                 zkp_component: zkp_component.proxy(),
+                bls_cache,
             }),
             consensus: Some(consensus),
             #[cfg(feature = "validator")]
@@ -705,6 +795,11 @@ impl Client {
         self.inner.consensus.clone()
     }
 
+    /// Returns the cache of uncompressed BLS public keys.
+    pub fn bls_cache(&self) -> Arc<Mutex<BlsCache>> {
+        Arc::clone(&self.inner.bls_cache)
+    }
+
     /// Returns a reference to the *Network* stack
     pub fn network(&self) -> Arc<Network> {
         Arc::clone(&self.inner.network)
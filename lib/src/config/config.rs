@@ -1,36 +1,69 @@
+#[cfg(feature = "rpc-server")]
+use std::collections::HashMap;
+#[cfg(feature = "validator")]
+use std::io;
 #[cfg(any(feature = "rpc-server", feature = "metrics-server"))]
 use std::net::IpAddr;
 #[cfg(feature = "metrics-server")]
 use std::net::SocketAddr;
+#[cfg(feature = "nimiq-mempool")]
+use std::sync::Arc;
 use std::{
-    fmt,
+    fmt, fs,
     num::NonZeroU8,
     path::{Path, PathBuf},
     string::ToString,
+    time::Duration,
 };
 
 use derive_builder::Builder;
 #[cfg(feature = "validator")]
 use nimiq_bls::{KeyPair as BlsKeyPair, SecretKey as BlsSecretKey};
+use nimiq_consensus::consensus::{
+    head_request_schedule::HeadRequestSchedule, recovery::RecoveryStrategy,
+    transaction_gossip::TransactionGossipConfig,
+};
 #[cfg(feature = "database-storage")]
 use nimiq_database::mdbx::MdbxDatabase;
+use nimiq_genesis::{SignedSyncCheckpoint, SyncCheckpoints};
 use nimiq_hash::{Blake2bHash, Hash};
 #[cfg(feature = "validator")]
-use nimiq_keys::{Address, KeyPair, PrivateKey};
+use nimiq_hash::{Blake2bHasher, Hasher};
+use nimiq_keys::Ed25519PublicKey;
+#[cfg(any(feature = "validator", feature = "wallet"))]
+use nimiq_keys::KeyPair;
+#[cfg(feature = "validator")]
+use nimiq_keys::{Address, PrivateKey};
 #[cfg(feature = "nimiq-mempool")]
-use nimiq_mempool::{config::MempoolConfig, filter::MempoolRules};
+use nimiq_mempool::{config::MempoolConfig, filter::MempoolRules, priority::TransactionPriority};
 use nimiq_network_interface::Multiaddr;
-use nimiq_network_libp2p::{Keypair as IdentityKeypair, Libp2pKeyPair};
+#[cfg(feature = "validator")]
+use nimiq_network_libp2p::DHT_RECORD_TTL;
+use nimiq_network_libp2p::{Keypair as IdentityKeypair, Libp2pKeyPair, PeerId};
 use nimiq_primitives::{networks::NetworkId, policy::Policy};
 use nimiq_serde::Deserialize;
 #[cfg(feature = "validator")]
-use nimiq_utils::key_rng::SecureGenerate;
+use nimiq_serde::Serialize;
+#[cfg(any(feature = "validator", feature = "wallet"))]
+use nimiq_utils::encrypted_file_store::EncryptedFileStore;
+#[cfg(feature = "wallet")]
+use nimiq_utils::otp::Locked;
+#[cfg(feature = "validator")]
+use nimiq_utils::{encrypted_file_store, key_rng::SecureGenerate};
 use nimiq_utils::{file_store::FileStore, Sensitive};
+#[cfg(feature = "validator")]
+use nimiq_validator::{key_utils::KeyStore, signer::SignerAddress};
+#[cfg(feature = "validator")]
+use nimiq_validator_network::validator_record::VALIDATOR_RECORD_REFRESH_INTERVAL;
+#[cfg(feature = "wallet")]
+use nimiq_wallet::{WalletAccount, WalletStore};
 use nimiq_zkp_circuits::DEFAULT_PROVER_KEYS_PATH;
 use subtle::ConstantTimeEq;
 
 #[cfg(feature = "database-storage")]
 use crate::config::config_file::DatabaseSettings;
+#[cfg(feature = "validator")]
+use crate::config::config_file::ValidatorSettings;
 #[cfg(any(feature = "rpc-server", feature = "metrics-server"))]
 use crate::config::consts;
 #[cfg(feature = "metrics-server")]
@@ -83,15 +116,51 @@ pub struct ConsensusConfig {
     #[builder(default = "3")]
     /// Minimum number of peers necessary to reach consensus
     pub min_peers: usize,
+    #[builder(default)]
+    /// Minimum number of peers providing the full transaction history necessary to reach consensus
+    pub min_history_peers: usize,
+    #[builder(default)]
+    /// Minimum number of validator peers necessary to reach consensus
+    pub min_validator_peers: usize,
+    #[builder(default)]
+    /// Strategy to recover after consensus was lost. Recovery is disabled if `None`, which is the
+    /// default.
+    pub recovery: Option<RecoveryStrategy>,
+    #[builder(default)]
+    /// Schedule of the head requests sent to our peers to determine the state of the chain
+    pub head_request_schedule: HeadRequestSchedule,
     #[builder(default = "1")]
     /// Maximum number of epochs that are stored in the client
     pub max_epochs_stored: u32,
+    #[builder(default)]
+    /// Number of epochs for which history nodes keep the history. If `None`, the full history is
+    /// kept.
+    pub history_retention_epochs: Option<u32>,
     #[builder(default = "10800")]
     /// Minimum distance away, in number of blocks, from the head to switch from state sync to live sync
     pub full_sync_threshold: u32,
     #[builder(setter(custom))]
     /// History indices enabled. Defaults to `true` for history nodes and `false` to full/light nodes.
     pub index_history: bool,
+    #[builder(default)]
+    /// Verify the signatures of blocks in parallel
+    pub parallel_block_verification: bool,
+    #[builder(default)]
+    /// Number of batches for which forked blocks are kept once their batch is finalized. If
+    /// `None`, they are kept until their epoch is pruned.
+    pub fork_retention_batches: Option<u32>,
+    #[builder(default = "Policy::BLS_CACHE_MAX_CAPACITY")]
+    /// Maximum number of uncompressed BLS public keys kept in the cache shared by the blockchain
+    /// and the validator
+    pub bls_cache_size: usize,
+    #[builder(default)]
+    /// File to record all inputs applied to the blockchain to, such that they can be replayed
+    /// with `nimiq-replay`. Recording is disabled if `None`, which is the default.
+    pub recording_path: Option<PathBuf>,
+    #[builder(default)]
+    /// Sync checkpoints light clients may start syncing from, together with the keys of the
+    /// issuers they are trusted from. The checkpoints embedded for the network are added to these.
+    pub sync_checkpoints: SyncCheckpoints,
 }
 
 impl ConsensusConfigBuilder {
@@ -115,9 +184,19 @@ impl Default for ConsensusConfig {
         ConsensusConfig {
             sync_mode: SyncMode::default(),
             min_peers: 3,
+            min_history_peers: 0,
+            min_validator_peers: 0,
+            recovery: None,
+            head_request_schedule: HeadRequestSchedule::default(),
             max_epochs_stored: Policy::MIN_EPOCHS_STORED,
+            history_retention_epochs: None,
             full_sync_threshold: 10800,
             index_history: true,
+            parallel_block_verification: false,
+            fork_retention_batches: None,
+            bls_cache_size: Policy::BLS_CACHE_MAX_CAPACITY,
+            recording_path: None,
+            sync_checkpoints: SyncCheckpoints::default(),
         }
     }
 }
@@ -147,6 +226,28 @@ pub struct NetworkConfig {
     #[builder(default)]
     pub seeds: Vec<Seed>,
 
+    /// Optional, list of relay addresses to listen on if none of our addresses are publicly
+    /// reachable. The addresses must include the peer ID of the relay.
+    ///
+    /// Default uses the seed addresses.
+    ///
+    #[builder(default)]
+    pub relays: Option<Vec<Multiaddr>>,
+
+    /// Optional, list of sentry nodes to exclusively connect to. If set, the node doesn't
+    /// listen on its own addresses and is only reachable through relay circuits on the
+    /// sentries, which are published in its validator record. The addresses must include the
+    /// peer ID of the sentry.
+    ///
+    #[builder(default)]
+    pub sentries: Vec<Multiaddr>,
+
+    /// Optional, list of peer IDs to relay circuits for, e.g. the validators this node is a
+    /// sentry of. If empty, the node doesn't act as a relay.
+    ///
+    #[builder(default)]
+    pub relay_clients: Vec<PeerId>,
+
     /// Optional, TLS configuration for secure WebSocket.
     #[builder(default)]
     pub tls: Option<TlsConfig>,
@@ -179,6 +280,29 @@ pub struct NetworkConfig {
     /// Optional, quorum value for the network DHT.
     #[builder(default)]
     pub dht_quorum: Option<NonZeroU8>,
+
+    /// Optional, upload bandwidth of the node in bytes per second.
+    #[builder(default)]
+    pub upload_bandwidth: Option<u64>,
+
+    /// Optional, share of the upload bandwidth and processing time that may be spent on serving
+    /// history and state chunks to other peers.
+    #[builder(default)]
+    pub seeding_share: Option<f64>,
+
+    /// Optional, multiple of the per-peer request limits that is shared by all peers connected
+    /// from the same IP subnet. 0 disables the limits per subnet.
+    #[builder(default)]
+    pub rate_limit_subnet_multiplier: Option<u32>,
+
+    /// Optional, multiple of the per-peer request limits that is shared by all peers.
+    /// 0 disables the global limits.
+    #[builder(default)]
+    pub rate_limit_global_multiplier: Option<u32>,
+
+    /// How the transactions sent by this node are gossiped.
+    #[builder(default)]
+    pub transaction_gossip: TransactionGossipConfig,
 }
 
 /// Configuration for setting TLS for secure WebSocket
@@ -229,6 +353,10 @@ pub struct FileStorageConfig {
     #[cfg(feature = "validator")]
     pub signing_key_path: Option<PathBuf>,
 
+    // Paths to signing keys.
+    #[cfg(feature = "validator")]
+    pub signing_key_paths: Option<Vec<PathBuf>>,
+
     /// The signing key used for the validator, if the file is not present.
     #[cfg(feature = "validator")]
     pub signing_key: Option<Sensitive<String>>,
@@ -240,6 +368,20 @@ pub struct FileStorageConfig {
     /// The fee key used for the validator, if the file is not present.
     #[cfg(feature = "validator")]
     pub fee_key: Option<Sensitive<String>>,
+
+    /// The password the validator key files are encrypted with. If set, unencrypted key files
+    /// are encrypted when they are loaded and inline keys have to be encrypted with it.
+    #[cfg(feature = "validator")]
+    pub key_password: Option<Sensitive<String>>,
+
+    /// Paths to key files whose keys are imported into the wallet.
+    #[cfg(feature = "wallet")]
+    pub wallet_key_paths: Vec<PathBuf>,
+
+    /// The password the wallet key files are encrypted with. The imported wallet accounts are
+    /// locked with it as well.
+    #[cfg(feature = "wallet")]
+    pub wallet_key_password: Option<Sensitive<String>>,
 }
 
 impl FileStorageConfig {
@@ -264,8 +406,94 @@ impl FileStorageConfig {
             #[cfg(feature = "validator")]
             signing_key_path: Some(path.join("signing_key.dat")),
             #[cfg(feature = "validator")]
+            signing_key_paths: None,
+            #[cfg(feature = "validator")]
             signing_key: None,
+            #[cfg(feature = "validator")]
+            key_password: None,
+            #[cfg(feature = "wallet")]
+            wallet_key_paths: vec![],
+            #[cfg(feature = "wallet")]
+            wallet_key_password: None,
+        }
+    }
+
+    /// Parses a validator key given inline in hex format. If a key password is set, the key has to
+    /// be encrypted with it, in the format of an encrypted key file.
+    #[cfg(feature = "validator")]
+    fn inline_key<T: Deserialize>(
+        &self,
+        key: Option<&Sensitive<String>>,
+    ) -> Result<Option<T>, Error> {
+        let Some(key) = key else {
+            return Ok(None);
+        };
+        let mut data = hex::decode(key)
+            .map_err(|_| Error::config_error("Inline validator keys must be in hex format"))?;
+        let result = match &self.key_password {
+            Some(password) => match encrypted_file_store::decrypt(&data, password.as_bytes()) {
+                Err(encrypted_file_store::Error::NotEncrypted) => Err(Error::config_error(
+                    "Inline validator keys must be encrypted if a key password is set",
+                )),
+                result => result.map_err(Error::from),
+            },
+            None => T::deserialize_from_vec(&data).map_err(Error::from),
+        };
+
+        // Always overwrite unencrypted vector.
+        data.fill(0);
+
+        result.map(Some)
+    }
+
+    /// Loads a validator key from the given file. The file is encrypted if a key password is set.
+    #[cfg(feature = "validator")]
+    fn load_key<T, P>(&self, key_path: P) -> Result<T, Error>
+    where
+        T: Serialize + Deserialize,
+        P: AsRef<Path>,
+    {
+        Ok(match &self.key_password {
+            Some(password) => {
+                let store = EncryptedFileStore::new(key_path);
+                store.lock::<T>(password.as_bytes())?;
+                store.load(password.as_bytes())?
+            }
+            None => FileStore::new(key_path).load()?,
+        })
+    }
+
+    /// Stores a validator key in the given file. The file is encrypted if a key password is set.
+    #[cfg(feature = "validator")]
+    fn store_key<T, P>(&self, key_path: P, key: &T) -> Result<(), Error>
+    where
+        T: Serialize,
+        P: AsRef<Path>,
+    {
+        match &self.key_password {
+            Some(password) => EncryptedFileStore::new(key_path).store(key, password.as_bytes())?,
+            None => FileStore::new(key_path).store(key)?,
         }
+        Ok(())
+    }
+
+    /// Loads a validator key from the given file, storing and returning a default value if the
+    /// file does not exist. The file is encrypted if a key password is set.
+    #[cfg(feature = "validator")]
+    fn load_or_store_key<T, P, F>(&self, key_path: P, f: F) -> Result<T, Error>
+    where
+        T: Serialize + Deserialize,
+        P: AsRef<Path>,
+        F: FnMut() -> T,
+    {
+        Ok(match &self.key_password {
+            Some(password) => {
+                let store = EncryptedFileStore::new(key_path);
+                store.lock::<T>(password.as_bytes())?;
+                store.load_or_store(password.as_bytes(), f)?
+            }
+            None => FileStore::new(key_path).load_or_store(f)?,
+        })
     }
 
     /// Stores the database in the users home directory, i.e. `$HOME/.nimiq/`. This is the default.
@@ -286,6 +514,85 @@ impl Default for FileStorageConfig {
     }
 }
 
+/// Stores the keys added by a key rotation at runtime in the `rotated_keys` directory next to the
+/// database, such that they are loaded again after a restart. The key files are encrypted if a key
+/// password is set.
+#[cfg(feature = "validator")]
+pub struct RotatedKeyStore {
+    file_storage: FileStorageConfig,
+}
+
+#[cfg(feature = "validator")]
+impl RotatedKeyStore {
+    const SIGNING_KEY_PREFIX: &'static str = "signing_key_";
+    const VOTING_KEY_PREFIX: &'static str = "voting_key_";
+
+    pub fn new(file_storage: FileStorageConfig) -> Self {
+        Self { file_storage }
+    }
+
+    fn directory(&self) -> PathBuf {
+        self.file_storage.database_parent.join("rotated_keys")
+    }
+
+    fn key_path(&self, prefix: &str, public_key: &[u8]) -> PathBuf {
+        let hash: Blake2bHash = Blake2bHasher::default().digest(public_key);
+        self.directory()
+            .join(format!("{prefix}{}.dat", hash.to_hex()))
+    }
+
+    /// Loads all stored signing keys.
+    pub fn signing_keys(&self) -> Result<Vec<KeyPair>, Error> {
+        self.load_keys(Self::SIGNING_KEY_PREFIX)
+    }
+
+    /// Loads all stored voting keys.
+    pub fn voting_keys(&self) -> Result<Vec<BlsKeyPair>, Error> {
+        self.load_keys(Self::VOTING_KEY_PREFIX)
+    }
+
+    fn load_keys<T: Serialize + Deserialize>(&self, prefix: &str) -> Result<Vec<T>, Error> {
+        let entries = match fs::read_dir(self.directory()) {
+            Ok(entries) => entries,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(error) => return Err(error.into()),
+        };
+
+        let mut keys = vec![];
+        for entry in entries {
+            let path = entry?.path();
+            let is_key_file = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(prefix) && name.ends_with(".dat"));
+            if is_key_file {
+                keys.push(self.file_storage.load_key(&path)?);
+            }
+        }
+        Ok(keys)
+    }
+
+    fn store_key<T: Serialize>(&self, key_path: PathBuf, key: &T) -> io::Result<()> {
+        self.file_storage
+            .store_key(key_path, key)
+            .map_err(|error| io::Error::other(error.to_string()))
+    }
+}
+
+#[cfg(feature = "validator")]
+impl KeyStore for RotatedKeyStore {
+    fn store_signing_key(&self, key: &KeyPair) -> io::Result<()> {
+        let key_path = self.key_path(Self::SIGNING_KEY_PREFIX, key.public.as_bytes());
+        self.store_key(key_path, key)
+    }
+
+    fn store_voting_key(&self, key: &BlsKeyPair) -> io::Result<()> {
+        let public_key = key.public_key.compress().serialize_to_vec();
+        let key_path = self.key_path(Self::VOTING_KEY_PREFIX, &public_key);
+        self.store_key(key_path, key)
+    }
+}
+
 /// Configuration options for the database
 #[cfg(feature = "database-storage")]
 #[derive(Debug, Clone, Builder, Eq, PartialEq)]
@@ -295,8 +602,9 @@ pub struct DatabaseConfig {
     #[builder(default = "1024 * 1024 * 1024 * 1024")]
     size: usize,
 
-    /// Max number of DBs. Recommended: 20
-    #[builder(default = "20")]
+    /// Max number of DBs. A validator with all optional features opens about 30.
+    /// Recommended: 64
+    #[builder(default = "64")]
     max_dbs: u32,
 
     /// Max number of threads that can open read transactions.
@@ -312,7 +620,7 @@ impl Default for DatabaseConfig {
         Self {
             // 1 TB
             size: 1024 * 1024 * 1024 * 1024,
-            max_dbs: 20,
+            max_dbs: 64,
             max_readers: 600,
         }
     }
@@ -417,6 +725,19 @@ impl StorageConfig {
         }
     }
 
+    /// Returns the path of the file the known peer contacts for the given network ID are
+    /// persisted to. Peer contacts are not persisted with volatile storage.
+    pub fn peer_contacts_path(&self, network_id: NetworkId) -> Option<PathBuf> {
+        match self {
+            StorageConfig::Volatile => None,
+            StorageConfig::Filesystem(file_storage) => Some(
+                file_storage
+                    .database_parent
+                    .join(format!("{network_id}-peer-contacts.dat").to_lowercase()),
+            ),
+        }
+    }
+
     /// Internal helper function to initiate a `MdbxDatabase` with the given `DatabaseConfig`.
     #[cfg(feature = "database-storage")]
     fn open_database(
@@ -466,7 +787,7 @@ impl StorageConfig {
                     }
                     let mut keypairs = Vec::new();
                     for key_path in voting_key_paths {
-                        let keypair = FileStore::new(key_path).load()?;
+                        let keypair = file_storage.load_key(key_path)?;
                         keypairs.push(keypair);
                     }
                     keypairs
@@ -484,22 +805,30 @@ impl StorageConfig {
                         })?
                         .to_string();
 
-                    vec![FileStore::new(key_path).load_or_store(|| {
-                        if let Some(key) = file_storage.voting_key.as_ref() {
-                            // TODO: handle errors
-                            let secret_key =
-                                BlsSecretKey::deserialize_from_vec(&hex::decode(key).unwrap())
-                                    .unwrap();
-                            secret_key.into()
-                        } else {
-                            BlsKeyPair::generate_default_csprng()
-                        }
+                    let mut secret_key: Option<BlsSecretKey> =
+                        file_storage.inline_key(file_storage.voting_key.as_ref())?;
+                    vec![file_storage.load_or_store_key(key_path, || {
+                        secret_key
+                            .take()
+                            .map(BlsKeyPair::from)
+                            .unwrap_or_else(BlsKeyPair::generate_default_csprng)
                     })?]
                 }
             }
         })
     }
 
+    /// Returns the store of the keys added by key rotations, if the keys are stored on disk.
+    #[cfg(feature = "validator")]
+    pub fn rotated_key_store(&self) -> Option<RotatedKeyStore> {
+        match self {
+            StorageConfig::Volatile => None,
+            StorageConfig::Filesystem(file_storage) => {
+                Some(RotatedKeyStore::new(file_storage.clone()))
+            }
+        }
+    }
+
     #[cfg(feature = "validator")]
     pub(crate) fn fee_keypair(&self) -> Result<KeyPair, Error> {
         Ok(match self {
@@ -519,20 +848,38 @@ impl StorageConfig {
                     })?
                     .to_string();
 
-                FileStore::new(key_path).load_or_store(|| {
-                    if let Some(key) = file_storage.fee_key.as_ref() {
-                        // TODO: handle errors
-                        KeyPair::from(
-                            PrivateKey::deserialize_from_vec(&hex::decode(key).unwrap()).unwrap(),
-                        )
-                    } else {
-                        KeyPair::generate_default_csprng()
-                    }
+                let mut private_key: Option<PrivateKey> =
+                    file_storage.inline_key(file_storage.fee_key.as_ref())?;
+                file_storage.load_or_store_key(key_path, || {
+                    private_key
+                        .take()
+                        .map(KeyPair::from)
+                        .unwrap_or_else(KeyPair::generate_default_csprng)
                 })?
             }
         })
     }
 
+    #[cfg(feature = "validator")]
+    pub(crate) fn signing_keypairs(&self) -> Result<Vec<KeyPair>, Error> {
+        if let StorageConfig::Filesystem(file_storage) = self {
+            if let Some(signing_key_paths) = &file_storage.signing_key_paths {
+                if file_storage.signing_key.is_some() {
+                    return Err(Error::config_error(
+                        "Option signing_key_paths can't be set at the same time as signing_key",
+                    ));
+                }
+                let mut keypairs = Vec::new();
+                for key_path in signing_key_paths {
+                    let keypair = file_storage.load_key(key_path)?;
+                    keypairs.push(keypair);
+                }
+                return Ok(keypairs);
+            }
+        }
+        Ok(vec![self.signing_keypair()?])
+    }
+
     #[cfg(feature = "validator")]
     pub(crate) fn signing_keypair(&self) -> Result<KeyPair, Error> {
         Ok(match self {
@@ -552,20 +899,52 @@ impl StorageConfig {
                     })?
                     .to_string();
 
-                FileStore::new(key_path).load_or_store(|| {
-                    if let Some(key) = file_storage.signing_key.as_ref() {
-                        // TODO: handle errors
-                        KeyPair::from(
-                            PrivateKey::deserialize_from_vec(&hex::decode(key).unwrap()).unwrap(),
-                        )
-                    } else {
-                        KeyPair::generate_default_csprng()
-                    }
+                let mut private_key: Option<PrivateKey> =
+                    file_storage.inline_key(file_storage.signing_key.as_ref())?;
+                file_storage.load_or_store_key(key_path, || {
+                    private_key
+                        .take()
+                        .map(KeyPair::from)
+                        .unwrap_or_else(KeyPair::generate_default_csprng)
                 })?
             }
         })
     }
 
+    /// Imports the keys of the configured wallet key files into the wallet store, locked with the
+    /// wallet key password. Unencrypted key files are encrypted with the password.
+    #[cfg(feature = "wallet")]
+    pub(crate) fn import_wallet_keys(&self, wallet_store: &WalletStore) -> Result<(), Error> {
+        let StorageConfig::Filesystem(file_storage) = self else {
+            return Ok(());
+        };
+        if file_storage.wallet_key_paths.is_empty() {
+            return Ok(());
+        }
+        let password = file_storage
+            .wallet_key_password
+            .as_ref()
+            .ok_or_else(|| Error::config_error("Wallet key files require a wallet key password"))?;
+
+        for key_path in &file_storage.wallet_key_paths {
+            let store = EncryptedFileStore::new(key_path);
+            store.lock::<KeyPair>(password.as_bytes())?;
+            let account = WalletAccount::from(store.load::<KeyPair>(password.as_bytes())?);
+
+            let address = account.address.clone();
+            if wallet_store.get(&address, None).is_some() {
+                continue;
+            }
+            let account = Locked::with_defaults(account, password.as_bytes())
+                .map_err(|e| Error::config_error(format!("Failed to lock wallet account: {e}")))?;
+            let mut txn = wallet_store.create_write_transaction();
+            wallet_store.put(&address, &account, &mut txn);
+            txn.commit();
+            log::info!(%address, "Imported wallet key");
+        }
+        Ok(())
+    }
+
     pub(crate) fn identity_keypair(&self) -> Result<IdentityKeypair, Error> {
         match self {
             StorageConfig::Volatile => Ok(IdentityKeypair::generate_ed25519()),
@@ -607,6 +986,47 @@ pub struct ValidatorConfig {
 
     /// Config if the validator automatically reactivates itself.
     pub automatic_reactivate: bool,
+
+    /// Interval in which the validator record is re-signed and republished to the DHT.
+    pub dht_republish_interval: Duration,
+
+    /// Remote signer to sign proposals, votes, VRF seeds, the validator record and reactivate
+    /// transactions with. If not set, the local keys are used.
+    pub remote_signer: Option<RemoteSignerConfig>,
+}
+
+#[cfg(feature = "validator")]
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct RemoteSignerConfig {
+    /// Where the remote signer listens.
+    pub address: SignerAddress,
+
+    /// Pre-shared key authenticating validator and signer to each other.
+    pub psk: Sensitive<[u8; 32]>,
+}
+
+#[cfg(feature = "validator")]
+impl RemoteSignerConfig {
+    fn from_settings(settings: &ValidatorSettings) -> Result<Option<Self>, Error> {
+        let Some(address) = &settings.remote_signer else {
+            return Ok(None);
+        };
+        let address = address
+            .parse::<SignerAddress>()
+            .map_err(|e| Error::config_error(e.to_string()))?;
+        let psk = settings
+            .remote_signer_psk
+            .as_ref()
+            .and_then(|psk| hex::decode(&psk.0).ok())
+            .and_then(|psk| <[u8; 32]>::try_from(psk).ok())
+            .ok_or_else(|| {
+                Error::config_error("The remote_signer_psk must be a 32 byte key in hex format")
+            })?;
+        Ok(Some(Self {
+            address,
+            psk: Sensitive(psk),
+        }))
+    }
 }
 
 /// Credentials for JSON RPC server, metrics server or websocket RPC server
@@ -667,6 +1087,26 @@ pub struct RpcServerConfig {
     /// If specified, require HTTP basic auth with these credentials
     #[builder(setter(strip_option))]
     pub credentials: Option<Credentials>,
+
+    /// The maximum number of requests per minute and client IP address for the listed RPC
+    /// methods
+    ///
+    #[builder(default)]
+    pub rate_limits: HashMap<String, u32>,
+
+    /// The maximum number of requests in a batch
+    ///
+    /// Default: `100`
+    ///
+    #[builder(default = "consts::RPC_DEFAULT_MAX_BATCH_SIZE")]
+    pub max_batch_size: usize,
+
+    /// The maximum number of requests of a client IP address that are processed at the same time
+    ///
+    /// Default: `10`
+    ///
+    #[builder(default = "consts::RPC_DEFAULT_MAX_CONCURRENT_REQUESTS")]
+    pub max_concurrent_requests: usize,
 }
 
 #[cfg(feature = "metrics-server")]
@@ -812,12 +1252,41 @@ impl ClientConfigBuilder {
             control_size_limit,
             filter_rules,
             filter_limit,
+            ..Default::default()
         });
         self
     }
 
+    #[cfg(feature = "nimiq-mempool")]
+    /// Sets the ordering in which the mempool hands transactions to the block producer. This
+    /// must be set after `mempool`, which replaces the whole mempool config. Defaults to the
+    /// highest fee per byte first.
+    pub fn mempool_transaction_priority(
+        &mut self,
+        transaction_priority: Arc<dyn TransactionPriority>,
+    ) -> &mut Self {
+        self.mempool
+            .get_or_insert_with(MempoolConfig::default)
+            .transaction_priority = Some(transaction_priority);
+        self
+    }
+
     /// Applies settings from a configuration file
     pub fn config_file(&mut self, config_file: &ConfigFile) -> Result<&mut Self, Error> {
+        let mut transaction_gossip = TransactionGossipConfig {
+            batching: config_file.network.transaction_batching,
+            ..Default::default()
+        };
+        if let Some(batch_delay) = config_file.network.transaction_batch_delay {
+            transaction_gossip.batch_delay = Duration::from_millis(batch_delay);
+        }
+        if let Some(max_batch_size) = config_file.network.transaction_batch_max_size {
+            transaction_gossip.max_batch_size = max_batch_size;
+        }
+        if let Some(announce_threshold) = config_file.network.transaction_announce_threshold {
+            transaction_gossip.announce_threshold = announce_threshold;
+        }
+
         // TODO: if the config field of `listen_addresses` is empty, we should at least add `/ip4/127.0.0.1/...`
         self.network(NetworkConfig {
             listen_addresses: config_file
@@ -849,6 +1318,36 @@ impl ClientConfigBuilder {
 
             seeds: config_file.network.seed_nodes.clone(),
 
+            relays: config_file
+                .network
+                .relay_nodes
+                .as_ref()
+                .map(|relays| {
+                    relays
+                        .iter()
+                        .map(|addr| addr.parse())
+                        .collect::<Result<Vec<Multiaddr>, _>>()
+                })
+                .transpose()?,
+
+            sentries: config_file
+                .network
+                .sentry_nodes
+                .iter()
+                .map(|addr| addr.parse())
+                .collect::<Result<Vec<Multiaddr>, _>>()?,
+
+            relay_clients: config_file
+                .network
+                .relay_clients
+                .iter()
+                .map(|peer_id| {
+                    peer_id.parse().map_err(|error| {
+                        Error::config_error(format!("Invalid relay client {peer_id}: {error}"))
+                    })
+                })
+                .collect::<Result<Vec<PeerId>, _>>()?,
+
             desired_peer_count: config_file.network.desired_peer_count,
 
             peer_count_max: config_file.network.peer_count_max,
@@ -859,6 +1358,11 @@ impl ClientConfigBuilder {
             only_secure_ws_connections: false,
             allow_loopback_addresses: config_file.network.allow_loopback_addresses,
             dht_quorum: config_file.network.dht_quorum,
+            upload_bandwidth: config_file.network.upload_bandwidth,
+            seeding_share: config_file.network.seeding_share,
+            rate_limit_subnet_multiplier: config_file.network.rate_limit_subnet_multiplier,
+            rate_limit_global_multiplier: config_file.network.rate_limit_global_multiplier,
+            transaction_gossip,
         });
 
         // Configure consensus
@@ -869,14 +1373,72 @@ impl ClientConfigBuilder {
                 config_file.consensus.sync_mode.into(),
             )
             .max_epochs_stored(config_file.consensus.max_epochs_stored as u32)
+            .history_retention_epochs(config_file.consensus.history_retention_epochs)
+            .parallel_block_verification(config_file.consensus.parallel_block_verification)
+            .fork_retention_batches(config_file.consensus.fork_retention_batches)
             .build()
             .unwrap();
         if let Some(min_peers) = config_file.consensus.min_peers {
             consensus.min_peers = min_peers;
         }
+        if let Some(min_history_peers) = config_file.consensus.min_history_peers {
+            consensus.min_history_peers = min_history_peers;
+        }
+        if let Some(min_validator_peers) = config_file.consensus.min_validator_peers {
+            consensus.min_validator_peers = min_validator_peers;
+        }
+        if let Some(recovery) = config_file.consensus.recovery {
+            consensus.recovery = recovery.then(RecoveryStrategy::default);
+        }
+        if let (Some(recovery), Some(max_backoff)) = (
+            consensus.recovery.as_mut(),
+            config_file.consensus.recovery_max_backoff,
+        ) {
+            recovery.max_backoff = Duration::from_secs(max_backoff);
+        }
+        if let Some(min_interval) = config_file.consensus.head_requests_min_interval {
+            consensus.head_request_schedule.min_interval = Duration::from_secs(min_interval);
+        }
+        if let Some(max_interval) = config_file.consensus.head_requests_max_interval {
+            consensus.head_request_schedule.max_interval = Duration::from_secs(max_interval);
+        }
         if let Some(full_sync_threshold) = config_file.consensus.full_sync_threshold {
             consensus.full_sync_threshold = full_sync_threshold;
         }
+        if let Some(bls_cache_size) = config_file.consensus.bls_cache_size {
+            consensus.bls_cache_size = bls_cache_size;
+        }
+        consensus.recording_path = config_file
+            .consensus
+            .record_blockchain_inputs
+            .as_ref()
+            .map(PathBuf::from);
+        consensus.sync_checkpoints = SyncCheckpoints::new(
+            config_file
+                .consensus
+                .sync_checkpoint_keys
+                .iter()
+                .map(|key| {
+                    key.parse::<Ed25519PublicKey>().map_err(|error| {
+                        Error::config_error(format!("Invalid sync checkpoint key {key}: {error}"))
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+        );
+        for path in &config_file.consensus.sync_checkpoints {
+            let checkpoints = fs::read(path)
+                .map_err(|error| error.to_string())
+                .and_then(|data| {
+                    Vec::<SignedSyncCheckpoint>::deserialize_from_vec(&data)
+                        .map_err(|error| error.to_string())
+                })
+                .map_err(|error| {
+                    Error::config_error(format!(
+                        "Failed to read sync checkpoints from {path}: {error}"
+                    ))
+                })?;
+            consensus.sync_checkpoints.extend(checkpoints);
+        }
         self.consensus(consensus);
 
         // Configure network
@@ -899,10 +1461,23 @@ impl ClientConfigBuilder {
         }
         #[cfg(feature = "validator")]
         if let Some(validator_config) = config_file.validator.as_ref() {
+            let dht_republish_interval = validator_config
+                .dht_republish_interval
+                .map(Duration::from_secs)
+                .unwrap_or(VALIDATOR_RECORD_REFRESH_INTERVAL);
+            // The record has to be republished before it expires in the DHT.
+            if dht_republish_interval.is_zero() || dht_republish_interval >= DHT_RECORD_TTL {
+                return Err(Error::config_error(format!(
+                    "Validator: dht_republish_interval must be between 1 and {} seconds",
+                    DHT_RECORD_TTL.as_secs() - 1,
+                )));
+            }
             self.validator(ValidatorConfig {
                 validator_address: Address::from_any_str(&validator_config.validator_address)?,
                 dht_fallback_url: validator_config.dht_fallback_url.clone(),
                 automatic_reactivate: validator_config.automatic_reactivate,
+                dht_republish_interval,
+                remote_signer: RemoteSignerConfig::from_settings(validator_config)?,
             });
 
             if let Some(key_paths) = &validator_config.voting_key_files {
@@ -920,12 +1495,26 @@ impl ClientConfigBuilder {
             if let Some(key) = &validator_config.fee_key {
                 file_storage.fee_key = Some(key.to_owned());
             }
+            if let Some(key_paths) = &validator_config.signing_key_files {
+                file_storage.signing_key_paths = Some(key_paths.iter().map(PathBuf::from).collect())
+            }
             if let Some(key_path) = &validator_config.signing_key_file {
                 file_storage.signing_key_path = Some(PathBuf::from(key_path));
             }
             if let Some(key) = &validator_config.signing_key {
                 file_storage.signing_key = Some(key.to_owned());
             }
+            if let Some(password_file) = &validator_config.key_password_file {
+                file_storage.key_password = Some(read_key_password(password_file)?);
+            }
+        }
+        #[cfg(feature = "wallet")]
+        if let Some(wallet_config) = config_file.wallet.as_ref() {
+            file_storage.wallet_key_paths =
+                wallet_config.key_files.iter().map(PathBuf::from).collect();
+            if let Some(password_file) = &wallet_config.key_password_file {
+                file_storage.wallet_key_password = Some(read_key_password(password_file)?);
+            }
         }
         self.storage = Some(file_storage.into());
 
@@ -983,6 +1572,20 @@ impl ClientConfigBuilder {
                     }
                 };
 
+                if rpc_config.rate_limits.values().any(|limit| *limit == 0) {
+                    return Err(Error::config_error("RPC: Rate limits have to be positive."));
+                }
+                if rpc_config.max_batch_size == Some(0) {
+                    return Err(Error::config_error(
+                        "RPC: The maximum batch size has to be positive.",
+                    ));
+                }
+                if rpc_config.max_concurrent_requests == Some(0) {
+                    return Err(Error::config_error(
+                        "RPC: The maximum number of concurrent requests has to be positive.",
+                    ));
+                }
+
                 self.rpc_server = Some(Some(RpcServerConfig {
                     bind_to,
                     port: rpc_config.port.unwrap_or(consts::RPC_DEFAULT_PORT),
@@ -990,6 +1593,13 @@ impl ClientConfigBuilder {
                     allow_ips,
                     allowed_methods: Some(rpc_config.methods.clone()),
                     credentials,
+                    rate_limits: rpc_config.rate_limits.clone(),
+                    max_batch_size: rpc_config
+                        .max_batch_size
+                        .unwrap_or(consts::RPC_DEFAULT_MAX_BATCH_SIZE),
+                    max_concurrent_requests: rpc_config
+                        .max_concurrent_requests
+                        .unwrap_or(consts::RPC_DEFAULT_MAX_CONCURRENT_REQUESTS),
                 }));
             }
         }
@@ -1031,7 +1641,16 @@ impl ClientConfigBuilder {
         #[cfg(feature = "nimiq-mempool")]
         {
             if let Some(mempool_settings) = &config_file.mempool {
-                self.mempool = Some(MempoolConfig::from(mempool_settings.clone()));
+                // The transaction priority can't be set in the config file, keep the one set
+                // programmatically.
+                let transaction_priority = self
+                    .mempool
+                    .as_ref()
+                    .and_then(|mempool| mempool.transaction_priority.clone());
+                self.mempool = Some(MempoolConfig {
+                    transaction_priority,
+                    ..MempoolConfig::from(mempool_settings.clone())
+                });
             }
         }
 
@@ -1057,6 +1676,16 @@ impl ClientConfigBuilder {
     }
 }
 
+/// Reads a key password from the given file, ignoring a trailing line break.
+#[cfg(any(feature = "validator", feature = "wallet"))]
+fn read_key_password(password_file: &str) -> Result<Sensitive<String>, Error> {
+    let password = fs::read_to_string(password_file)
+        .map_err(|e| Error::config_error(format!("Failed to read key password file: {e}")))?;
+    Ok(Sensitive(
+        password.trim_end_matches(['\r', '\n']).to_owned(),
+    ))
+}
+
 /// Contains the configurations for the ZK proof generation.
 #[derive(Debug, Clone, Builder)]
 pub struct ZKProverConfig {
@@ -48,6 +48,8 @@ pub struct ConfigFile {
     pub mempool: Option<MempoolSettings>,
     #[serde(default)]
     pub validator: Option<ValidatorSettings>,
+    #[serde(default)]
+    pub wallet: Option<WalletSettings>,
 }
 
 impl ConfigFile {
@@ -179,6 +181,15 @@ pub struct NetworkSettings {
 
     #[serde(default)]
     pub seed_nodes: Vec<Seed>,
+    /// Relays to stay reachable through if the node is behind a NAT. Defaults to the seed nodes.
+    #[serde(default)]
+    pub relay_nodes: Option<Vec<String>>,
+    /// Sentry nodes a validator exclusively connects to, hiding its own IP address.
+    #[serde(default)]
+    pub sentry_nodes: Vec<String>,
+    /// Peer IDs to relay circuits for, e.g. of the validators this node is a sentry of.
+    #[serde(default)]
+    pub relay_clients: Vec<String>,
     #[serde(default)]
     pub user_agent: Option<String>,
 
@@ -190,6 +201,31 @@ pub struct NetworkSettings {
     pub allow_loopback_addresses: bool,
     #[serde(default)]
     pub dht_quorum: Option<NonZeroU8>,
+    /// Upload bandwidth in bytes per second.
+    #[serde(default)]
+    pub upload_bandwidth: Option<u64>,
+    /// Share of the resources that may be spent on serving history and state chunks.
+    #[serde(default)]
+    pub seeding_share: Option<f64>,
+    /// Multiple of the per-peer request limits shared by the peers of an IP subnet. 0 disables.
+    #[serde(default)]
+    pub rate_limit_subnet_multiplier: Option<u32>,
+    /// Multiple of the per-peer request limits shared by all peers. 0 disables.
+    #[serde(default)]
+    pub rate_limit_global_multiplier: Option<u32>,
+    /// Batch and announce the transactions sent by this node instead of publishing them
+    /// individually.
+    #[serde(default)]
+    pub transaction_batching: bool,
+    /// Maximum delay in milliseconds before a batch of transactions is published.
+    #[serde(default)]
+    pub transaction_batch_delay: Option<u64>,
+    /// Maximum size of a batch of transactions in bytes.
+    #[serde(default)]
+    pub transaction_batch_max_size: Option<usize>,
+    /// Size in bytes above which transactions are announced instead of being batched.
+    #[serde(default)]
+    pub transaction_announce_threshold: Option<usize>,
 }
 
 impl NetworkSettings {
@@ -236,15 +272,49 @@ pub struct ConsensusSettings {
     #[serde(default)]
     /// The maximum amount of epochs that are stored in the client
     pub max_epochs_stored: usize,
+    /// The number of epochs for which history nodes keep the history
+    #[serde(default)]
+    pub history_retention_epochs: Option<u32>,
     /// Different possible networks (MainAlbatross, TestAlbatross, DevAlbatross, UnitAlbatross)
     pub network: Option<NetworkId>,
     /// Minimum number of peers necessary to reach consensus
     pub min_peers: Option<usize>,
+    /// Minimum number of peers providing the full transaction history necessary to reach consensus
+    pub min_history_peers: Option<usize>,
+    /// Minimum number of validator peers necessary to reach consensus
+    pub min_validator_peers: Option<usize>,
+    /// Automatic recovery after consensus was lost enabled
+    pub recovery: Option<bool>,
+    /// Maximum delay between two recovery attempts, in seconds
+    pub recovery_max_backoff: Option<u64>,
+    /// Interval between head requests while the chain is stalled, in seconds
+    pub head_requests_min_interval: Option<u64>,
+    /// Maximum interval between head requests while the chain progresses normally, in seconds
+    pub head_requests_max_interval: Option<u64>,
     /// Minimum distance away, in number of blocks, from the head to switch from state sync to live sync
     pub full_sync_threshold: Option<u32>,
     /// History indices enabled. Only effective for history and full nodes.
     #[serde(default)]
     pub index_history: Option<bool>,
+    /// Verify the signatures of blocks in parallel. Only effective for history and full nodes.
+    #[serde(default)]
+    pub parallel_block_verification: bool,
+    /// Number of batches for which forked blocks are kept once their batch is finalized.
+    #[serde(default)]
+    pub fork_retention_batches: Option<u32>,
+    /// Maximum number of uncompressed BLS public keys kept in the cache.
+    #[serde(default)]
+    pub bls_cache_size: Option<usize>,
+    /// File to record all inputs applied to the blockchain to. Only effective for history and
+    /// full nodes.
+    #[serde(default)]
+    pub record_blockchain_inputs: Option<String>,
+    /// Public keys of the issuers whose sync checkpoints are trusted, in hex.
+    #[serde(default)]
+    pub sync_checkpoint_keys: Vec<String>,
+    /// Files with sync checkpoints in addition to the ones embedded for the network.
+    #[serde(default)]
+    pub sync_checkpoints: Vec<String>,
 }
 
 impl Default for ConsensusSettings {
@@ -252,10 +322,23 @@ impl Default for ConsensusSettings {
         Self {
             sync_mode: SyncMode::default(),
             max_epochs_stored: 1,
+            history_retention_epochs: None,
             network: None,
             min_peers: None,
+            min_history_peers: None,
+            min_validator_peers: None,
+            recovery: None,
+            recovery_max_backoff: None,
+            head_requests_min_interval: None,
+            head_requests_max_interval: None,
             full_sync_threshold: None,
             index_history: None,
+            parallel_block_verification: false,
+            fork_retention_batches: None,
+            bls_cache_size: None,
+            record_blockchain_inputs: None,
+            sync_checkpoint_keys: vec![],
+            sync_checkpoints: vec![],
         }
     }
 }
@@ -313,6 +396,10 @@ pub struct RpcServerSettings {
     pub allowip: Vec<String>,
     #[serde(default)]
     pub methods: Vec<String>,
+    #[serde(default)]
+    pub rate_limits: HashMap<String, u32>,
+    pub max_batch_size: Option<usize>,
+    pub max_concurrent_requests: Option<usize>,
     pub username: Option<String>,
     pub password: Option<Sensitive<String>>,
 }
@@ -361,6 +448,19 @@ pub struct LogSettings {
     pub loki: Option<LokiConfig>,
     #[serde(default)]
     pub tokio_console_bind_address: Option<String>,
+    #[serde(default)]
+    pub format: LogFormat,
+}
+
+#[derive(Clone, Copy, Deserialize, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+/// Output format of the log statements
+pub enum LogFormat {
+    /// Human readable log lines
+    #[default]
+    Text,
+    /// One JSON object per log statement
+    Json,
 }
 
 impl LogSettings {
@@ -379,6 +479,7 @@ impl Default for LogSettings {
             file: None,
             loki: None,
             tokio_console_bind_address: None,
+            format: LogFormat::default(),
         }
     }
 }
@@ -399,6 +500,8 @@ pub struct MempoolSettings {
     pub size_limit: Option<usize>,
     pub control_size_limit: Option<usize>,
     pub blacklist_limit: Option<usize>,
+    pub replacement_fee_bump: Option<u64>,
+    pub persist: Option<bool>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -463,6 +566,11 @@ impl From<MempoolSettings> for MempoolConfig {
                 .blacklist_limit
                 .unwrap_or(MempoolFilter::DEFAULT_BLACKLIST_SIZE),
             filter_rules: mempool.filter.map(MempoolRules::from).unwrap_or_default(),
+            replacement_fee_bump: mempool
+                .replacement_fee_bump
+                .unwrap_or(Mempool::DEFAULT_REPLACEMENT_FEE_BUMP),
+            persist: mempool.persist.unwrap_or(false),
+            transaction_priority: None,
         }
     }
 }
@@ -494,14 +602,33 @@ pub struct ValidatorSettings {
     pub validator_address: String,
     pub signing_key_file: Option<String>,
     pub signing_key: Option<Sensitive<String>>,
+    pub signing_key_files: Option<Vec<String>>,
     pub voting_key_file: Option<String>,
     pub voting_key_files: Option<Vec<String>>,
     pub voting_key: Option<Sensitive<String>>,
     pub fee_key_file: Option<String>,
     pub fee_key: Option<Sensitive<String>>,
+    /// File containing the password the validator key files are encrypted with
+    pub key_password_file: Option<String>,
     pub dht_fallback_url: Option<Url>,
     #[serde(default)]
     pub automatic_reactivate: bool,
+    /// Interval in which the validator record is re-signed and republished to the DHT, in seconds
+    pub dht_republish_interval: Option<u64>,
+    /// Address of a remote signer holding the consensus keys
+    pub remote_signer: Option<String>,
+    /// Pre-shared key authenticating the connection to the remote signer, in hex format
+    pub remote_signer_psk: Option<Sensitive<String>>,
+}
+
+#[derive(Clone, Debug, Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct WalletSettings {
+    /// Key files whose keys are imported into the wallet
+    #[serde(default)]
+    pub key_files: Vec<String>,
+    /// File containing the password the wallet key files are encrypted with
+    pub key_password_file: Option<String>,
 }
 
 #[derive(Clone, Debug, Deserialize, Default)]
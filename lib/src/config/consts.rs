@@ -9,6 +9,13 @@ pub const REVERSE_PROXY_DEFAULT_PORT: u16 = 8444;
 /// The default port for the RPC server
 pub const RPC_DEFAULT_PORT: u16 = 8648;
 
+/// The default maximum number of requests in a batch sent to the RPC server
+pub const RPC_DEFAULT_MAX_BATCH_SIZE: usize = 100;
+
+/// The default maximum number of requests of a client that the RPC server processes at the same
+/// time
+pub const RPC_DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 10;
+
 /// The default port for the metrics server
 pub const METRICS_DEFAULT_PORT: u16 = 9100;
 
@@ -11,6 +11,10 @@ pub enum Error {
     #[error("MDBX error: {0}")]
     Lmdb(#[from] nimiq_database::Error),
 
+    #[cfg(feature = "database-storage")]
+    #[error("Database migration error: {0}")]
+    Migration(#[from] nimiq_database::migration::MigrationError),
+
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
 
@@ -20,6 +24,10 @@ pub enum Error {
     #[error("File store error: {0}")]
     FileStore(#[from] nimiq_utils::file_store::Error),
 
+    #[cfg(any(feature = "validator", feature = "wallet"))]
+    #[error("Encrypted file store error: {0}")]
+    EncryptedFileStore(#[from] nimiq_utils::encrypted_file_store::Error),
+
     #[error("Consensus error: {0}")]
     Consensus(#[from] nimiq_consensus::Error),
 
@@ -29,6 +37,10 @@ pub enum Error {
     // #[cfg(feature = "validator")]
     // #[error("Validator error: {0}")]
     // Validator(#[from] ValidatorError),
+    #[cfg(feature = "validator")]
+    #[error("Remote signer error: {0}")]
+    RemoteSigner(#[from] nimiq_validator::signer::SigningError),
+
     #[cfg(feature = "rpc-server")]
     #[error("RPC server error: {0}")]
     RpcServer(#[from] nimiq_rpc_server::Error),
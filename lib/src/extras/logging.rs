@@ -8,13 +8,16 @@ use std::{
 };
 
 use log::{level_filters::LevelFilter, Level, Subscriber};
-use nimiq_log::{Formatting, MaybeSystemTime, TargetsExt};
+use nimiq_log::{Formatting, JsonFormatting, MaybeSystemTime, TargetsExt};
 use tracing_subscriber::{
     filter::Targets, layer::SubscriberExt, registry::LookupSpan, util::SubscriberInitExt, Layer,
 };
 
 use crate::{
-    config::{command_line::CommandLine, config_file::LogSettings},
+    config::{
+        command_line::CommandLine,
+        config_file::{LogFormat, LogSettings},
+    },
     error::Error,
 };
 
@@ -183,14 +186,21 @@ pub fn initialize_logging(
         tokio_console_bind_address.and_then(|addr| initialize_tokio_console(&addr));
 
     let mut formatting_layer = tracing_subscriber::fmt::layer().with_writer(out);
-    if settings.file.is_some() {
-        // Only disable ANSI colors when a log file is specified. Do not forcefully enable ANSI colors otherwise,
+    if settings.file.is_some() || settings.format == LogFormat::Json {
+        // Colors are neither wanted in log files nor in JSON output. Do not forcefully enable ANSI colors otherwise,
         // as they might be disabled through other means (e.g. with the NO_COLOR environment variable).
         formatting_layer = formatting_layer.with_ansi(false);
     }
-    let formatting_layer = formatting_layer
-        .event_format(Formatting(MaybeSystemTime(settings.timestamps)))
-        .with_filter(filter);
+    let formatting_layer = match settings.format {
+        LogFormat::Text => formatting_layer
+            .event_format(Formatting(MaybeSystemTime(settings.timestamps)))
+            .with_filter(filter)
+            .boxed(),
+        LogFormat::Json => formatting_layer
+            .event_format(JsonFormatting(MaybeSystemTime(settings.timestamps)))
+            .with_filter(filter)
+            .boxed(),
+    };
 
     #[cfg(feature = "loki")]
     {
@@ -1,11 +1,12 @@
 use std::{net::SocketAddr, sync::Arc};
 
 use nimiq_blockchain_proxy::BlockchainProxy;
-use nimiq_consensus::ConsensusProxy;
+use nimiq_consensus::{BlsCache, ConsensusProxy};
 #[cfg(feature = "nimiq-mempool")]
 use nimiq_mempool::mempool::Mempool;
 pub use nimiq_metrics_server::NimiqTaskMonitor;
 use nimiq_network_interface::network::Network;
+use parking_lot::Mutex;
 
 pub fn start_metrics_server<TNetwork: Network>(
     addr: SocketAddr,
@@ -13,6 +14,7 @@ pub fn start_metrics_server<TNetwork: Network>(
     #[cfg(feature = "nimiq-mempool")] mempool: Option<Arc<Mempool>>,
     consensus_proxy: ConsensusProxy<TNetwork>,
     network: Arc<nimiq_network_libp2p::Network>,
+    bls_cache: Arc<Mutex<BlsCache>>,
     task_monitors: &[NimiqTaskMonitor],
 ) {
     #[cfg(not(feature = "nimiq-mempool"))]
@@ -23,6 +25,7 @@ pub fn start_metrics_server<TNetwork: Network>(
         mempool,
         consensus_proxy,
         network,
+        bls_cache,
         task_monitors,
     );
 }
@@ -1,9 +1,9 @@
 use std::{collections::HashSet, iter::FromIterator, sync::Arc};
 
-use nimiq_jsonrpc_server::{
-    AllowListDispatcher, Config, Cors, Credentials, ModularDispatcher, Server as _Server,
+use nimiq_jsonrpc_server::{AllowListDispatcher, ModularDispatcher};
+use nimiq_rpc_server::{
+    dispatchers::*, rate_limit::RateLimits, Config, Credentials, Server as _Server,
 };
-use nimiq_rpc_server::dispatchers::*;
 use nimiq_wallet::WalletStore;
 
 #[cfg(feature = "rpc-server")]
@@ -23,7 +23,7 @@ pub fn initialize_rpc_server(
 
     // Configure RPC server
     let basic_auth = config.credentials.map(|credentials| {
-        Credentials::new_from_blake2b(credentials.username, credentials.password_hash.0 .0)
+        Credentials::new_from_blake2b(credentials.username, credentials.password_hash.0)
     });
 
     let allowed_methods = config.allowed_methods.unwrap_or_default();
@@ -33,48 +33,59 @@ pub fn initialize_rpc_server(
         Some(HashSet::from_iter(allowed_methods))
     };
 
-    let cors_domains = config.cors_domains.unwrap_or_default();
-    let is_cors_wildcard = cors_domains.iter().any(|origin| origin.trim() == "*");
-    let cors_config = if is_cors_wildcard {
-        Cors::new().with_any_origin()
-    } else {
-        Cors::new().with_origins(cors_domains)
-    };
-
-    let mut dispatcher = ModularDispatcher::default();
-
     let wallet_dispatcher = WalletDispatcher::new(wallet_store);
     let unlocked_wallets = Arc::clone(&wallet_dispatcher.unlocked_wallets);
 
-    dispatcher.add(BlockchainDispatcher::new(client.blockchain()));
-
-    dispatcher.add(ConsensusDispatcher::new(
+    let blockchain_dispatcher = BlockchainDispatcher::new(client.blockchain());
+    let consensus_dispatcher = ConsensusDispatcher::new(
         client.consensus_proxy(),
-        Some(unlocked_wallets),
-    ));
-    dispatcher.add(NetworkDispatcher::new(client.network()));
-    if let Some(mempool) = client.mempool() {
-        dispatcher.add(MempoolDispatcher::new(client.consensus_proxy(), mempool));
-    }
-    dispatcher.add(PolicyDispatcher {});
-    if let Some(validator_proxy) = client.validator_proxy() {
-        dispatcher.add(ValidatorDispatcher::new(
+        Some(Arc::clone(&unlocked_wallets)),
+    );
+    let network_dispatcher = NetworkDispatcher::new(client.network());
+    let mempool_dispatcher = client
+        .mempool()
+        .map(|mempool| MempoolDispatcher::new(client.consensus_proxy(), mempool));
+    let validator_dispatcher = client.validator_proxy().map(|validator_proxy| {
+        ValidatorDispatcher::new(
             validator_proxy,
             client.consensus_proxy(),
-        ));
-    }
-    dispatcher.add(wallet_dispatcher);
+            Some(Arc::clone(&unlocked_wallets)),
+        )
+    });
+    let zkp_component_dispatcher = ZKPComponentDispatcher::new(client.zkp_component());
+
+    // The server creates a dispatcher for every request that is processed concurrently. They
+    // share the state of the dispatchers created here.
+    let new_dispatcher = move || {
+        let mut dispatcher = ModularDispatcher::default();
 
-    dispatcher.add(ZKPComponentDispatcher::new(client.zkp_component()));
+        dispatcher.add(blockchain_dispatcher.clone());
+        dispatcher.add(consensus_dispatcher.clone());
+        dispatcher.add(network_dispatcher.clone());
+        if let Some(mempool_dispatcher) = &mempool_dispatcher {
+            dispatcher.add(mempool_dispatcher.clone());
+        }
+        dispatcher.add(PolicyDispatcher {});
+        if let Some(validator_dispatcher) = &validator_dispatcher {
+            dispatcher.add(validator_dispatcher.clone());
+        }
+        dispatcher.add(wallet_dispatcher.clone());
+        dispatcher.add(zkp_component_dispatcher.clone());
+
+        AllowListDispatcher::new(dispatcher, allowed_methods.clone())
+    };
 
     Ok(Server::new(
         Config {
             bind_to: (config.bind_to.unwrap_or_else(default_bind), config.port).into(),
-            enable_websocket: false,
-            ip_whitelist: None,
             basic_auth,
-            cors: Some(cors_config),
+            cors_domains: config.cors_domains.unwrap_or_default(),
+            rate_limits: RateLimits {
+                methods: config.rate_limits,
+                max_batch_size: config.max_batch_size,
+                max_concurrent_requests: config.max_concurrent_requests,
+            },
         },
-        AllowListDispatcher::new(dispatcher, allowed_methods),
+        new_dispatcher,
     ))
 }
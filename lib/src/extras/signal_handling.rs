@@ -2,21 +2,44 @@ use std::time::Duration;
 
 use nimiq_time::sleep;
 use nimiq_utils::spawn;
-use signal_hook::{consts::SIGINT, iterator::Signals};
+use parking_lot::Mutex;
+use signal_hook::{
+    consts::{SIGINT, SIGTERM},
+    iterator::Signals,
+};
+
+type ShutdownHook = Box<dyn FnOnce() + Send>;
+
+/// Hooks that are run before the client exits on SIGINT or SIGTERM.
+static SHUTDOWN_HOOKS: Mutex<Vec<ShutdownHook>> = Mutex::new(Vec::new());
+
+/// Registers a hook that is run before the client exits on SIGINT (Ctrl+C) or SIGTERM (e.g. sent
+/// by systemd or docker), e.g. to flush state to disk. Hooks are run in the order they were
+/// registered.
+pub fn on_shutdown<F: FnOnce() + Send + 'static>(hook: F) {
+    SHUTDOWN_HOOKS.lock().push(Box::new(hook));
+}
 
 pub fn initialize_signal_handler() {
-    let signals = Signals::new([SIGINT]);
+    let signals = Signals::new([SIGINT, SIGTERM]);
 
     if let Ok(mut signals) = signals {
         spawn(async move {
-            if signals.forever().next().is_some() {
-                log::warn!("Received Ctrl+C. Closing client");
+            if let Some(signal) = signals.forever().next() {
+                if signal == SIGTERM {
+                    log::warn!("Received SIGTERM. Closing client");
+                } else {
+                    log::warn!("Received Ctrl+C. Closing client");
+                }
+                for hook in std::mem::take(&mut *SHUTDOWN_HOOKS.lock()) {
+                    hook();
+                }
                 // Add some delay for the log message to propagate into loki
                 sleep(Duration::from_millis(200)).await;
                 std::process::exit(0);
             }
         });
     } else {
-        log::error!("Could not obtain SIGINT signal");
+        log::error!("Could not obtain SIGINT and SIGTERM signals");
     }
 }
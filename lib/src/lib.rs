@@ -2,6 +2,8 @@ pub mod client;
 pub mod config;
 pub mod error;
 pub mod extras;
+#[cfg(feature = "database-storage")]
+pub mod migrations;
 
 #[cfg(feature = "zkp-prover")]
 pub mod prover {
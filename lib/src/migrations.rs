@@ -0,0 +1,16 @@
+//! Migrations of the client database between releases.
+//!
+//! Whenever a release changes the layout of data stored in the database, a migration converting
+//! the existing data needs to be registered here with the next version. The migrations are run
+//! when the client opens its database.
+use nimiq_database::migration::Migrator;
+
+/// Returns the migrator with all migrations of the client database.
+pub fn migrator() -> Migrator {
+    let migrator = Migrator::new();
+
+    #[cfg(feature = "full-consensus")]
+    let migrator = migrator.with(nimiq_blockchain::chain_store::IndexForksMigration);
+
+    migrator
+}
@@ -1,10 +1,20 @@
 use std::path::PathBuf;
 
+#[cfg(feature = "validator")]
+use nimiq_bls::KeyPair as BlsKeyPair;
+#[cfg(feature = "validator")]
+use nimiq_keys::KeyPair;
+#[cfg(feature = "validator")]
+use nimiq_lib::config::config::StorageConfig;
 use nimiq_lib::config::{
     config::{ClientConfigBuilder, DatabaseConfig, DatabaseConfigBuilder, FileStorageConfig},
     config_file::ConfigFile,
 };
 use nimiq_test_log::test;
+#[cfg(feature = "validator")]
+use nimiq_utils::key_rng::SecureGenerate;
+#[cfg(feature = "validator")]
+use nimiq_validator::key_utils::KeyStore;
 
 #[test]
 fn config_file_no_db_entry() {
@@ -113,3 +123,26 @@ fn config_file_partial_db_entry() {
 
     assert_eq!(config.storage, db_config.into());
 }
+
+#[test]
+#[cfg(feature = "validator")]
+fn rotated_keys_are_loaded_after_a_restart() {
+    let directory = std::env::temp_dir().join(format!("nimiq-rotated-keys-{}", std::process::id()));
+    let storage = StorageConfig::Filesystem(FileStorageConfig::from_directory(&directory));
+
+    let key_store = storage.rotated_key_store().unwrap();
+    assert!(key_store.signing_keys().unwrap().is_empty());
+    assert!(key_store.voting_keys().unwrap().is_empty());
+
+    let signing_key = KeyPair::generate_default_csprng();
+    let voting_key = BlsKeyPair::generate_default_csprng();
+    key_store.store_signing_key(&signing_key).unwrap();
+    key_store.store_voting_key(&voting_key).unwrap();
+
+    // A new store, as created on startup, loads the stored keys.
+    let key_store = storage.rotated_key_store().unwrap();
+    assert_eq!(key_store.signing_keys().unwrap(), vec![signing_key]);
+    assert!(key_store.voting_keys().unwrap() == vec![voting_key]);
+
+    std::fs::remove_dir_all(directory).unwrap();
+}
@@ -2,6 +2,7 @@ use futures::{future, stream::BoxStream, StreamExt};
 use nimiq_block::{Block, MacroBlock};
 use nimiq_blockchain_interface::{
     AbstractBlockchain, BlockchainError, BlockchainEvent, ChainInfo, Direction, ForkEvent,
+    ReorgEvent,
 };
 use nimiq_genesis::NetworkId;
 use nimiq_hash::Blake2bHash;
@@ -136,4 +137,10 @@ impl AbstractBlockchain for LightBlockchain {
             .filter_map(|x| future::ready(x.ok()))
             .boxed()
     }
+
+    fn subscribe_reorgs(&self) -> BoxStream<'static, ReorgEvent> {
+        BroadcastStream::new(self.reorg_notifier.subscribe())
+            .filter_map(|x| future::ready(x.ok()))
+            .boxed()
+    }
 }
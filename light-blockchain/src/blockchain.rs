@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use nimiq_block::{Block, MacroBlock};
 use nimiq_blockchain_interface::{
-    AbstractBlockchain, BlockchainError, BlockchainEvent, ChainInfo, ForkEvent,
+    AbstractBlockchain, BlockchainError, BlockchainEvent, ChainInfo, ForkEvent, ReorgEvent,
 };
 use nimiq_genesis::NetworkInfo;
 use nimiq_primitives::{
@@ -41,6 +41,8 @@ pub struct LightBlockchain {
     pub notifier: broadcast::Sender<BlockchainEvent>,
     /// The fork notifier processes fork events.
     pub fork_notifier: broadcast::Sender<ForkEvent>,
+    /// The reorg notifier processes structured rebranch events.
+    pub reorg_notifier: broadcast::Sender<ReorgEvent>,
 }
 
 /// Implements methods to start a Blockchain.
@@ -76,6 +78,7 @@ impl LightBlockchain {
             chain_store,
             notifier: broadcast::Sender::new(BROADCAST_MAX_CAPACITY),
             fork_notifier: broadcast::Sender::new(BROADCAST_MAX_CAPACITY),
+            reorg_notifier: broadcast::Sender::new(BROADCAST_MAX_CAPACITY),
         }
     }
 
@@ -2,7 +2,8 @@ use std::ops::Deref;
 
 use nimiq_block::{Block, ForkProof, MacroHeader, MicroBlock};
 use nimiq_blockchain_interface::{
-    AbstractBlockchain, BlockchainEvent, ChainInfo, ChainOrdering, ForkEvent, PushError, PushResult,
+    AbstractBlockchain, BlockchainEvent, ChainInfo, ChainOrdering, ForkEvent, PushError,
+    PushResult, ReorgEvent,
 };
 use nimiq_hash::Blake2bHash;
 use nimiq_keys::Address;
@@ -304,6 +305,7 @@ impl LightBlockchain {
         // Update the main_chain_successor of the common ancestor block.
         ancestor.1.main_chain_successor = Some(fork_chain.last().unwrap().0.clone());
         this.chain_store.put_chain_info(ancestor.1);
+        let common_ancestor = ancestor.0;
 
         // Set on_main_chain flag / main_chain_successor on the fork.
         for i in (0..fork_chain.len()).rev() {
@@ -354,10 +356,23 @@ impl LightBlockchain {
             "Rebranched",
         );
 
+        let reorg_event = ReorgEvent {
+            reverted: reverted_blocks
+                .iter()
+                .map(|(hash, _)| hash.clone())
+                .collect(),
+            applied: adopted_blocks
+                .iter()
+                .map(|(hash, _)| hash.clone())
+                .collect(),
+            common_ancestor,
+        };
+
         // We do not log errors if there are no listeners
         this.notifier
             .send(BlockchainEvent::Rebranched(reverted_blocks, adopted_blocks))
             .ok();
+        this.reorg_notifier.send(reorg_event).ok();
 
         if this.head.is_election() {
             this.notifier
@@ -1,7 +1,12 @@
 use std::{env, fmt};
 
 use ansiterm::{Color, Style};
-use log::{level_filters::LevelFilter, Event, Level, Subscriber};
+use log::{
+    field::{Field, Visit},
+    level_filters::LevelFilter,
+    Event, Level, Subscriber,
+};
+use serde_json::{Map, Value};
 use time::format_description::well_known::Iso8601;
 use tracing_log::NormalizeEvent;
 use tracing_subscriber::{
@@ -232,6 +237,98 @@ where
     }
 }
 
+/// Formats each event as a single line JSON object, e.g. for ingestion into log pipelines.
+///
+/// The object contains the `timestamp` (if enabled), `level`, `target` and `message` of the
+/// event, the event's other fields (e.g. `peer_id` or `block_number`) nested in `fields` and the
+/// names of the spans the event occurred in. Nesting the fields keeps fields named e.g. `level`
+/// or `target` from overwriting the event's metadata.
+pub struct JsonFormatting<T: FormatTime>(pub T);
+
+impl<S, N, T: FormatTime> FormatEvent<S, N> for JsonFormatting<T>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let normalized_metadata = event.normalized_metadata();
+        let metadata = normalized_metadata
+            .as_ref()
+            .unwrap_or_else(|| event.metadata());
+
+        let mut object = Map::new();
+
+        let mut timestamp = String::new();
+        self.0.format_time(&mut Writer::new(&mut timestamp))?;
+        if !timestamp.is_empty() {
+            object.insert("timestamp".to_string(), timestamp.into());
+        }
+        object.insert("level".to_string(), metadata.level().as_str().into());
+        object.insert("target".to_string(), metadata.target().into());
+
+        let mut fields = Map::new();
+        event.record(&mut JsonVisitor(&mut fields));
+        if let Some(message) = fields.remove("message") {
+            object.insert("message".to_string(), message);
+        }
+        if !fields.is_empty() {
+            object.insert("fields".to_string(), fields.into());
+        }
+
+        if let Some(scope) = ctx.event_scope() {
+            let spans: Vec<Value> = scope.from_root().map(|span| span.name().into()).collect();
+            object.insert("spans".to_string(), spans.into());
+        }
+
+        let line = serde_json::to_string(&object).map_err(|_| fmt::Error)?;
+        writeln!(writer, "{line}")
+    }
+}
+
+/// Records the fields of an event into a JSON object.
+struct JsonVisitor<'a>(&'a mut Map<String, Value>);
+
+impl JsonVisitor<'_> {
+    fn insert(&mut self, field: &Field, value: Value) {
+        // Skip the fields added by `tracing-log`, they are part of the normalized metadata.
+        if field.name().starts_with("log.") {
+            return;
+        }
+        self.0.insert(field.name().to_string(), value);
+    }
+}
+
+impl Visit for JsonVisitor<'_> {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.insert(field, format!("{value:?}").into());
+    }
+}
+
 pub struct MaybeSystemTime(pub bool);
 
 impl FormatTime for MaybeSystemTime {
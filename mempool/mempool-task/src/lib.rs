@@ -9,10 +9,11 @@ use log::{debug, warn};
 use nimiq_blockchain::Blockchain;
 use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainEvent};
 use nimiq_consensus::{Consensus, ConsensusEvent, ConsensusProxy};
-use nimiq_mempool::{config::MempoolConfig, mempool::Mempool};
+use nimiq_mempool::{config::MempoolConfig, mempool::Mempool, store::MempoolStore};
 use nimiq_network_interface::network::Network;
 use nimiq_utils::spawn;
 use parking_lot::RwLock;
+use tokio::task::spawn_blocking;
 #[cfg(feature = "metrics")]
 use tokio_metrics::TaskMonitor;
 use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
@@ -48,16 +49,23 @@ impl<N: Network> MempoolTask<N> {
         consensus: &Consensus<N>,
         blockchain: Arc<RwLock<Blockchain>>,
         mempool_config: MempoolConfig,
+        store: Option<MempoolStore>,
     ) -> Self {
         let consensus_event_rx = consensus.subscribe_events();
 
-        let mempool = Arc::new(Mempool::new(Arc::clone(&blockchain), mempool_config));
+        let consensus = consensus.proxy();
+        let mut mempool = Mempool::new(Arc::clone(&blockchain), mempool_config)
+            .with_gossip_cache(consensus.transaction_gossip_cache());
+        if let Some(store) = store {
+            mempool = mempool.with_store(store);
+        }
+        let mempool = Arc::new(mempool);
         let mempool_active = false;
 
         let blockchain_event_rx = blockchain.read().notifier_as_stream();
 
         Self {
-            consensus: consensus.proxy(),
+            consensus,
 
             consensus_event_rx,
             blockchain_event_rx,
@@ -92,6 +100,15 @@ impl<N: Network> MempoolTask<N> {
 
         let mempool = Arc::clone(&self.mempool);
         let network = Arc::clone(&self.consensus.network);
+
+        // Bring back the transactions that were pending when the node was shut down. Verifying
+        // them takes a while, so this runs on a blocking thread alongside the executors. This is
+        // a no-op after the first time consensus was established.
+        let restored_mempool = Arc::clone(&mempool);
+        spawn_blocking(move || {
+            restored_mempool.restore();
+        });
+
         #[cfg(not(feature = "metrics"))]
         spawn({
             async move {
@@ -185,6 +202,8 @@ impl<N: Network> Stream for MempoolTask<N> {
                 | Ok(ConsensusEvent::Established {
                     synced_validity_window: false,
                 }) => self.pause(),
+                Ok(ConsensusEvent::PeerSetChanged { .. })
+                | Ok(ConsensusEvent::SyncProgress { .. }) => {}
                 Err(BroadcastStreamRecvError::Lagged(num)) => {
                     warn!("Consensus event stream lagging behind by {} messages", num);
                 }
@@ -1,6 +1,9 @@
+use std::sync::Arc;
+
 use crate::{
     filter::{MempoolFilter, MempoolRules},
     mempool::Mempool,
+    priority::TransactionPriority,
 };
 
 /// Struct defining a Mempool configuration
@@ -14,6 +17,14 @@ pub struct MempoolConfig {
     pub filter_rules: MempoolRules,
     /// Mempool filter limit or size
     pub filter_limit: usize,
+    /// Fee increase (in percent) a transaction must pay to replace a pending transaction of the
+    /// same sender that it re-issues
+    pub replacement_fee_bump: u64,
+    /// Whether pending transactions are persisted across restarts
+    pub persist: bool,
+    /// Ordering in which transactions are handed to the block producer. Defaults to the highest
+    /// fee per byte first, see `FeePerBytePriority`.
+    pub transaction_priority: Option<Arc<dyn TransactionPriority>>,
 }
 
 impl Default for MempoolConfig {
@@ -23,6 +34,9 @@ impl Default for MempoolConfig {
             control_size_limit: Mempool::DEFAULT_CONTROL_SIZE_LIMIT,
             filter_rules: MempoolRules::default(),
             filter_limit: MempoolFilter::DEFAULT_BLACKLIST_SIZE,
+            replacement_fee_bump: Mempool::DEFAULT_REPLACEMENT_FEE_BUMP,
+            persist: false,
+            transaction_priority: None,
         }
     }
 }
@@ -45,8 +45,9 @@ pub(crate) struct MempoolExecutor<N: Network, T: Topic + Unpin + Sync> {
     // Network ID, used for tx verification
     network_id: NetworkId,
 
-    // Transaction stream that is used to listen to transactions from the network
-    txn_stream: BoxStream<'static, (Transaction, <N as Network>::PubsubId)>,
+    // Transaction stream that is used to listen to transactions from the network. A single
+    // message might contain multiple transactions.
+    txn_stream: BoxStream<'static, (Vec<Transaction>, <N as Network>::PubsubId)>,
 
     // Phantom data for the unused type T
     _phantom: PhantomData<T>,
@@ -58,7 +59,7 @@ impl<N: Network, T: Topic + Unpin + Sync> MempoolExecutor<N, T> {
         state: Arc<RwLock<MempoolState>>,
         filter: Arc<RwLock<MempoolFilter>>,
         network: Arc<N>,
-        txn_stream: BoxStream<'static, (Transaction, <N as Network>::PubsubId)>,
+        txn_stream: BoxStream<'static, (Vec<Transaction>, <N as Network>::PubsubId)>,
         verification_tasks: Arc<AtomicU32>,
     ) -> Self {
         Self {
@@ -78,19 +79,22 @@ impl<N: Network, T: Topic + Unpin + Sync> Future for MempoolExecutor<N, T> {
     type Output = ();
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        struct Decrementer(Arc<AtomicU32>);
+        struct Decrementer(Arc<AtomicU32>, u32);
         impl Drop for Decrementer {
             fn drop(&mut self) {
-                self.0.fetch_sub(1, AtomicOrdering::Relaxed);
+                self.0.fetch_sub(self.1, AtomicOrdering::Relaxed);
             }
         }
 
-        while let Some((tx, pubsub_id)) = ready!(self.txn_stream.as_mut().poll_next_unpin(cx)) {
-            let decrement = Decrementer(Arc::clone(&self.verification_tasks));
+        while let Some((txs, pubsub_id)) = ready!(self.txn_stream.as_mut().poll_next_unpin(cx)) {
+            // Every transaction of a message counts as a verification task.
+            let num_txs = txs.len() as u32;
+            let decrement = Decrementer(Arc::clone(&self.verification_tasks), num_txs);
             if self
                 .verification_tasks
-                .fetch_add(1, AtomicOrdering::Relaxed)
-                >= CONCURRENT_VERIF_TASKS
+                .fetch_add(num_txs, AtomicOrdering::Relaxed)
+                .saturating_add(num_txs)
+                > CONCURRENT_VERIF_TASKS
             {
                 log::debug!("Reached the max number of verification tasks");
                 continue;
@@ -104,24 +108,40 @@ impl<N: Network, T: Topic + Unpin + Sync> Future for MempoolExecutor<N, T> {
 
             // Spawn the transaction verification task
             spawn(async move {
-                let verify_tx_ret = verify_tx(
-                    tx,
-                    blockchain,
-                    network_id,
-                    &mempool_state,
-                    filter,
-                    TxPriority::Medium,
-                );
-
-                let acceptance = match verify_tx_ret {
-                    Ok(_) => MsgAcceptance::Accept,
-                    // Reject the message if signature verification fails or transaction is invalid
-                    // for current validation window
-                    Err(VerifyErr::InvalidTransaction(_)) => MsgAcceptance::Reject,
-                    Err(VerifyErr::AlreadyIncluded) => MsgAcceptance::Reject,
-                    Err(_) => MsgAcceptance::Ignore,
+                // Each transaction is verified on its own, such that the valid transactions of a
+                // message are added to the mempool even if others are not. The message is only
+                // accepted if it contains a new valid transaction and none of its transactions is
+                // invalid.
+                let mut has_valid = false;
+                let mut has_invalid = false;
+                for tx in txs {
+                    let verify_tx_ret = verify_tx(
+                        tx,
+                        Arc::clone(&blockchain),
+                        network_id,
+                        &mempool_state,
+                        Arc::clone(&filter),
+                        TxPriority::Medium,
+                    );
+
+                    match verify_tx_ret {
+                        Ok(_) => has_valid = true,
+                        // Reject the message if signature verification fails or transaction is invalid
+                        // for current validation window
+                        Err(VerifyErr::InvalidTransaction(_)) | Err(VerifyErr::AlreadyIncluded) => {
+                            has_invalid = true
+                        }
+                        Err(_) => {}
+                    }
+                }
+
+                let acceptance = if has_invalid {
+                    MsgAcceptance::Reject
+                } else if has_valid {
+                    MsgAcceptance::Accept
+                } else {
+                    MsgAcceptance::Ignore
                 };
-
                 network.validate_message::<T>(pubsub_id, acceptance);
 
                 drop(decrement);
@@ -0,0 +1,93 @@
+use std::{collections::HashSet, sync::Arc};
+
+use futures::{
+    future,
+    stream::{BoxStream, StreamExt},
+};
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_network_interface::network::{MsgAcceptance, Network, PubsubId};
+use nimiq_transaction::{
+    gossip::{
+        RequestTransactionsByHash, TransactionAnnouncement, TransactionAnnouncementTopic,
+        TransactionGossipCache,
+    },
+    Transaction,
+};
+use parking_lot::RwLock;
+
+use crate::mempool_state::MempoolState;
+
+/// Maximum number of announcements that are resolved concurrently.
+const CONCURRENT_PULLS: usize = 16;
+
+/// Pulls the announced transactions we don't know yet from the peers that propagated the
+/// announcements.
+///
+/// The pulled transactions are added to the gossip cache, such that we can provide them to our
+/// peers once we forward the announcement. If an announcement can't be resolved, it is reported
+/// without transactions and is thus ignored. Announcements with more than
+/// [`TransactionAnnouncement::MAX_HASHES`] hashes are rejected right away.
+pub(crate) fn pull_announced_transactions<N: Network>(
+    network: Arc<N>,
+    state: Arc<RwLock<MempoolState>>,
+    cache: TransactionGossipCache,
+    announcements: BoxStream<'static, (TransactionAnnouncement, N::PubsubId)>,
+) -> BoxStream<'static, (Vec<Transaction>, N::PubsubId)> {
+    let validating_network = Arc::clone(&network);
+    announcements
+        .filter(move |(announcement, pubsub_id)| {
+            let valid = announcement.hashes.len() <= TransactionAnnouncement::MAX_HASHES;
+            if !valid {
+                validating_network.validate_message::<TransactionAnnouncementTopic>(
+                    pubsub_id.clone(),
+                    MsgAcceptance::Reject,
+                );
+            }
+            future::ready(valid)
+        })
+        .map(move |(announcement, pubsub_id)| {
+            let network = Arc::clone(&network);
+            let state = Arc::clone(&state);
+            let cache = cache.clone();
+            async move {
+                let hashes: HashSet<Blake2bHash> = {
+                    let state = state.read();
+                    announcement
+                        .hashes
+                        .into_iter()
+                        .filter(|hash| !state.contains(hash) && !cache.contains(hash))
+                        .collect()
+                };
+                if hashes.is_empty() {
+                    return (vec![], pubsub_id);
+                }
+
+                let request = RequestTransactionsByHash {
+                    hashes: hashes.iter().cloned().collect(),
+                };
+                let transactions = match network
+                    .request(request, pubsub_id.propagation_source())
+                    .await
+                {
+                    Ok(transactions) => transactions,
+                    Err(error) => {
+                        debug!(%error, "Failed to pull announced transactions");
+                        vec![]
+                    }
+                };
+
+                // Only consider the transactions that were actually announced.
+                let transactions: Vec<_> = transactions
+                    .into_iter()
+                    .filter(|tx| hashes.contains(&tx.hash()))
+                    .collect();
+                for tx in &transactions {
+                    cache.insert(tx.clone());
+                }
+
+                (transactions, pubsub_id)
+            }
+        })
+        .buffer_unordered(CONCURRENT_PULLS)
+        .boxed()
+}
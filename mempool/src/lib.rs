@@ -17,6 +17,8 @@ pub mod executor;
 
 /// Mempool filter module
 pub mod filter;
+/// Transaction batch and announcement gossip module
+mod gossip;
 /// Main mempool module
 pub mod mempool;
 /// Mempool metrics
@@ -24,5 +26,9 @@ pub mod mempool;
 mod mempool_metrics;
 /// Mempool transaction module
 pub mod mempool_transactions;
+/// Block transaction priority module
+pub mod priority;
+/// Mempool persistence module
+pub mod store;
 /// Verify transaction module
 pub mod verify;
@@ -1,5 +1,5 @@
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
     sync::{atomic::AtomicU32, Arc},
 };
 
@@ -9,7 +9,7 @@ use futures::{
     stream::{BoxStream, StreamExt},
 };
 use nimiq_account::ReservedBalance;
-use nimiq_block::Block;
+use nimiq_block::{Block, ShortTransactionId};
 use nimiq_blockchain::{Blockchain, TransactionVerificationCache};
 use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_hash::{Blake2bHash, Hash};
@@ -17,8 +17,9 @@ use nimiq_keys::Address;
 use nimiq_network_interface::network::{Network, Topic};
 use nimiq_serde::Serialize;
 use nimiq_transaction::{
-    historic_transaction::RawTransactionHash, ControlTransactionTopic, Transaction,
-    TransactionTopic,
+    gossip::{TransactionAnnouncementTopic, TransactionBatchTopic, TransactionGossipCache},
+    historic_transaction::RawTransactionHash,
+    ControlTransactionTopic, Transaction, TransactionTopic,
 };
 use nimiq_utils::spawn;
 use parking_lot::RwLock;
@@ -30,8 +31,11 @@ use crate::{
     config::MempoolConfig,
     executor::MempoolExecutor,
     filter::{MempoolFilter, MempoolRules},
+    gossip::pull_announced_transactions,
     mempool_state::{EvictionReason, MempoolState},
     mempool_transactions::{MempoolTransactions, TxPriority},
+    priority::{iter_prioritized, TransactionPriority},
+    store::MempoolStore,
     verify::{verify_tx, VerifyErr},
 };
 
@@ -52,8 +56,23 @@ pub struct Mempool {
     /// Mempool executor handle used to stop the control mempool executor
     pub(crate) control_executor_handle: Mutex<Option<AbortHandle>>,
 
+    /// Mempool executor handle used to stop the executor for transaction batches
+    pub(crate) batch_executor_handle: Mutex<Option<AbortHandle>>,
+
+    /// Mempool executor handle used to stop the executor for announced transactions
+    pub(crate) announcement_executor_handle: Mutex<Option<AbortHandle>>,
+
+    /// The announced transactions we provide to our peers
+    gossip_cache: TransactionGossipCache,
+
     /// Total number of ongoing verification tasks
     verification_tasks: Arc<AtomicU32>,
+
+    /// Storage the pending transactions are persisted to across restarts
+    store: Option<MempoolStore>,
+
+    /// Ordering in which transactions are returned to fill blocks, if not by fee per byte
+    transaction_priority: Option<Arc<dyn TransactionPriority>>,
 }
 
 impl Mempool {
@@ -63,11 +82,15 @@ impl Mempool {
     /// Default total size limit of control transactions in the mempool (bytes)
     pub const DEFAULT_CONTROL_SIZE_LIMIT: usize = 6_000_000;
 
+    /// Default fee increase (in percent) required to replace a pending transaction
+    pub const DEFAULT_REPLACEMENT_FEE_BUMP: u64 = 10;
+
     /// Creates a new mempool
     pub fn new(blockchain: Arc<RwLock<Blockchain>>, config: MempoolConfig) -> Self {
         let state = Arc::new(RwLock::new(MempoolState::new(
             config.size_limit,
             config.control_size_limit,
+            config.replacement_fee_bump,
         )));
 
         Self {
@@ -79,10 +102,91 @@ impl Mempool {
             ))),
             executor_handle: Mutex::new(None),
             control_executor_handle: Mutex::new(None),
+            batch_executor_handle: Mutex::new(None),
+            announcement_executor_handle: Mutex::new(None),
+            gossip_cache: TransactionGossipCache::default(),
             verification_tasks: Arc::new(AtomicU32::new(0)),
+            store: None,
+            transaction_priority: config.transaction_priority,
         }
     }
 
+    /// Sets the cache the announced transactions we pulled are added to, such that they can be
+    /// provided to our peers. This should be the cache the network requests for announced
+    /// transactions are served from.
+    pub fn with_gossip_cache(mut self, cache: TransactionGossipCache) -> Self {
+        self.gossip_cache = cache;
+        self
+    }
+
+    /// Sets the storage the pending transactions are persisted to by `persist` and restored from
+    /// by `restore`.
+    pub fn with_store(mut self, store: MempoolStore) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Writes all pending transactions to the mempool store, replacing the previously stored ones.
+    /// Does nothing if the mempool has no store.
+    pub fn persist(&self) {
+        let Some(store) = &self.store else {
+            return;
+        };
+
+        let state = self.state.read();
+        let num_persisted = store.save(
+            state
+                .control_transactions
+                .transactions
+                .values()
+                .chain(state.regular_transactions.transactions.values()),
+        );
+        info!(num_persisted, "Persisted pending mempool transactions");
+    }
+
+    /// Verifies the transactions in the mempool store against the current blockchain state and
+    /// adds the valid ones to the mempool. The store is emptied afterwards, such that transactions
+    /// are only restored once. Returns the number of restored transactions.
+    ///
+    /// This should only be called once the node has consensus. Restored transactions are added
+    /// with medium priority. Since every transaction is verified, this blocks for a while with
+    /// many stored transactions and should be run on a blocking thread.
+    pub fn restore(&self) -> usize {
+        let Some(store) = &self.store else {
+            return 0;
+        };
+
+        let mut transactions = store.load();
+        store.clear();
+
+        // Add the transactions in the order they are chained in, such that transactions depending
+        // on an earlier one of the same sender don't fail their balance check.
+        transactions.sort_by_key(|tx| tx.validity_start_height);
+
+        let num_stored = transactions.len();
+        let num_restored = transactions
+            .into_iter()
+            .filter(|tx| match self.add_transaction(tx.clone(), None) {
+                Ok(()) => true,
+                Err(error) => {
+                    debug!(
+                        tx_hash = %tx.hash::<Blake2bHash>(),
+                        %error,
+                        "Dropping persisted mempool transaction"
+                    );
+                    false
+                }
+            })
+            .count();
+
+        info!(
+            num_restored,
+            num_dropped = num_stored - num_restored,
+            "Restored persisted mempool transactions"
+        );
+        num_restored
+    }
+
     /// Start the `MempoolExecutor` for `Topic` `T` and instrument a monitor for the task if given.
     /// An `AbortHandle` will be stored in `handle`.
     fn start_executor<N: Network, T: Topic + Unpin + Send + Sync + 'static>(
@@ -90,7 +194,7 @@ impl Mempool {
         network: Arc<N>,
         monitor: Option<TaskMonitor>,
         mut handle: MutexGuard<'_, Option<AbortHandle>>,
-        txn_stream: BoxStream<'static, (Transaction, <N as Network>::PubsubId)>,
+        txn_stream: BoxStream<'static, (Vec<Transaction>, <N as Network>::PubsubId)>,
     ) {
         if handle.is_some() {
             // If we already have an executor running, don't do anything
@@ -130,6 +234,8 @@ impl Mempool {
     ///
     /// Once this function is called, the mempool executors are spawned.
     /// Both the regular and control txn executors are subscribed to their respective txn topics.
+    /// Batched and announced transactions are handled by their own executors, which are
+    /// instrumented with the regular transaction monitor.
     pub async fn start_executors<N: Network>(
         &self,
         network: Arc<N>,
@@ -138,6 +244,8 @@ impl Mempool {
     ) {
         let executor_handle = self.executor_handle.lock().await;
         let control_executor_handle = self.control_executor_handle.lock().await;
+        let batch_executor_handle = self.batch_executor_handle.lock().await;
+        let announcement_executor_handle = self.announcement_executor_handle.lock().await;
 
         if executor_handle.is_some() && control_executor_handle.is_some() {
             // If we already have both executors running dont do anything
@@ -145,21 +253,60 @@ impl Mempool {
         }
 
         // Subscribe to the network TX topic
-        let txn_stream = network.subscribe::<TransactionTopic>().await.unwrap();
+        let txn_stream = network
+            .subscribe::<TransactionTopic>()
+            .await
+            .unwrap()
+            .map(|(tx, pubsub_id)| (vec![tx], pubsub_id))
+            .boxed();
 
         self.start_executor::<N, TransactionTopic>(
             Arc::clone(&network),
-            monitor,
+            monitor.clone(),
             executor_handle,
             txn_stream,
         );
 
+        // Subscribe to the transaction batch topic
+        let txn_stream = network
+            .subscribe::<TransactionBatchTopic>()
+            .await
+            .unwrap()
+            .map(|(batch, pubsub_id)| (batch.transactions, pubsub_id))
+            .boxed();
+
+        self.start_executor::<N, TransactionBatchTopic>(
+            Arc::clone(&network),
+            monitor.clone(),
+            batch_executor_handle,
+            txn_stream,
+        );
+
+        // Subscribe to the transaction announcement topic
+        let announcements = network
+            .subscribe::<TransactionAnnouncementTopic>()
+            .await
+            .unwrap();
+        let txn_stream = pull_announced_transactions(
+            Arc::clone(&network),
+            Arc::clone(&self.state),
+            self.gossip_cache.clone(),
+            announcements,
+        );
+
+        self.start_executor::<N, TransactionAnnouncementTopic>(
+            Arc::clone(&network),
+            monitor,
+            announcement_executor_handle,
+            txn_stream,
+        );
+
         // Subscribe to the control transaction topic
         let txn_stream = network
             .subscribe::<ControlTransactionTopic>()
             .await
             .unwrap()
-            .map(|(tx, pubsub_id)| (Transaction::from(tx), pubsub_id))
+            .map(|(tx, pubsub_id)| (vec![Transaction::from(tx)], pubsub_id))
             .boxed();
 
         self.start_executor::<N, ControlTransactionTopic>(
@@ -184,7 +331,9 @@ impl Mempool {
             network,
             None,
             self.executor_handle.lock().await,
-            txn_stream,
+            txn_stream
+                .map(|(tx, pubsub_id)| (vec![tx], pubsub_id))
+                .boxed(),
         )
     }
 
@@ -202,7 +351,9 @@ impl Mempool {
             network,
             None,
             self.control_executor_handle.lock().await,
-            txn_stream,
+            txn_stream
+                .map(|(tx, pubsub_id)| (vec![tx], pubsub_id))
+                .boxed(),
         )
     }
 
@@ -235,6 +386,24 @@ impl Mempool {
             .take()
             .expect("Expected a control executor handle")
             .abort();
+
+        // Unsubscribe from the batch and announcement topics before killing their executors
+        network
+            .unsubscribe::<TransactionBatchTopic>()
+            .await
+            .unwrap();
+        network
+            .unsubscribe::<TransactionAnnouncementTopic>()
+            .await
+            .unwrap();
+
+        // Stop the batch and announcement executors
+        if let Some(handle) = self.batch_executor_handle.lock().await.take() {
+            handle.abort();
+        }
+        if let Some(handle) = self.announcement_executor_handle.lock().await.take() {
+            handle.abort();
+        }
     }
 
     /// Stops the mempool executor without TX stream
@@ -355,7 +524,9 @@ impl Mempool {
 
                     // Check if transaction is still valid.
                     let next_block_number = blockchain.block_number() + 1;
-                    if !tx.is_valid_at(next_block_number) {
+                    if !tx.is_valid_at(next_block_number)
+                        || tx.is_expired_at(blockchain.timestamp())
+                    {
                         continue;
                     }
 
@@ -450,7 +621,7 @@ impl Mempool {
                 None => {
                     // We don't have the sender account so we can't do any balance tracking.
                     // Remove all transactions from this sender.
-                    for hash in &sender_state.txns {
+                    for (_, hash) in &sender_state.txns {
                         mempool_state
                             .regular_transactions
                             .delete(hash)
@@ -460,10 +631,9 @@ impl Mempool {
                 }
             };
 
-            // TODO We should have per sender transactions ordered by fee to try to
-            //  keep the ones with higher fee
-
-            sender_state.txns.retain(|tx_hash| {
+            // Reserve the balance in the order the transactions are chained in, such that the
+            // transactions depending on an earlier one are evicted first.
+            sender_state.txns.retain(|(_, tx_hash)| {
                 let tx = match mempool_state.get(tx_hash) {
                     Some(transaction) => transaction,
                     None => return false,
@@ -490,7 +660,8 @@ impl Mempool {
         mempool_state: &mut MempoolState,
     ) {
         let next_block_number = blockchain.block_number() + 1;
-        let expired_txns = mempool_state.get_expired_txns(next_block_number);
+        let expired_txns =
+            mempool_state.get_expired_txns(next_block_number, blockchain.timestamp());
         for tx_hash in expired_txns {
             mempool_state.remove(blockchain, &tx_hash, EvictionReason::Expired);
         }
@@ -499,7 +670,7 @@ impl Mempool {
     /// Returns a vector with accepted transactions from the mempool.
     /// Note that this takes a read lock on blockchain.
     ///
-    /// Returns the highest fee per byte up to max_bytes transactions and removes them from the mempool.
+    /// Returns the highest priority up to max_bytes transactions and removes them from the mempool.
     /// It also return the sum of the serialized size of the returned transactions.
     pub fn get_transactions_for_block(&self, max_bytes: usize) -> (Vec<Transaction>, usize) {
         let blockchain = self.blockchain.read();
@@ -510,7 +681,7 @@ impl Mempool {
     /// If the caller already holds a blockchain lock, it can be passed to this function to prevent
     /// double-locking the blockchain.
     ///
-    /// Returns the highest fee per byte up to max_bytes transactions and removes them from the mempool.
+    /// Returns the highest priority up to max_bytes transactions and removes them from the mempool.
     /// It also return the sum of the serialized size of the returned transactions.
     pub fn get_transactions_for_block_locked(
         &self,
@@ -518,8 +689,11 @@ impl Mempool {
         max_bytes: usize,
     ) -> (Vec<Transaction>, usize) {
         let mut state = self.state.write();
-        let (txs, size) =
-            Self::get_transactions_for_block_impl(&mut state.regular_transactions, max_bytes);
+        let (txs, size) = self.get_transactions_for_block_impl(
+            &mut state.regular_transactions,
+            blockchain.block_number() + 1,
+            max_bytes,
+        );
 
         for tx in &txs {
             state.remove(blockchain, &tx.hash(), EvictionReason::BlockBuilding);
@@ -537,7 +711,7 @@ impl Mempool {
     /// Returns a vector with accepted control transactions from the mempool.
     /// Note that this takes a read lock on blockchain.
     ///
-    /// Returns the highest fee per byte up to max_bytes transactions and removes them from the mempool.
+    /// Returns the highest priority up to max_bytes transactions and removes them from the mempool.
     /// It also return the sum of the serialized size of the returned transactions.
     pub fn get_control_transactions_for_block(
         &self,
@@ -551,7 +725,7 @@ impl Mempool {
     /// If the caller already holds a blockchain lock, it can be passed to this function to prevent
     /// double-locking the blockchain.
     ///
-    /// Returns the highest fee per byte up to max_bytes transactions and removes them from the mempool.
+    /// Returns the highest priority up to max_bytes transactions and removes them from the mempool.
     /// It also return the sum of the serialized size of the returned transactions.
     pub fn get_control_transactions_for_block_locked(
         &self,
@@ -559,8 +733,11 @@ impl Mempool {
         max_bytes: usize,
     ) -> (Vec<Transaction>, usize) {
         let mut state = self.state.write();
-        let (txs, size) =
-            Self::get_transactions_for_block_impl(&mut state.control_transactions, max_bytes);
+        let (txs, size) = self.get_transactions_for_block_impl(
+            &mut state.control_transactions,
+            blockchain.block_number() + 1,
+            max_bytes,
+        );
 
         for tx in &txs {
             state.remove(blockchain, &tx.hash(), EvictionReason::BlockBuilding);
@@ -576,12 +753,38 @@ impl Mempool {
     }
 
     fn get_transactions_for_block_impl(
+        &self,
         transactions: &mut MempoolTransactions,
+        block_number: u32,
         max_bytes: usize,
     ) -> (Vec<Transaction>, usize) {
         let mut txs = vec![];
         let mut size = 0_usize;
 
+        // Fits transactions into the block until the next one doesn't fit anymore.
+        // TODO: We can optimize this. There might be a smaller transaction that still fits.
+        let mut fits = |tx: &Transaction| {
+            // We need to account for one extra byte per transaction to encode its final execution status
+            let next_size = size + 1 + tx.serialized_size();
+            if next_size > max_bytes {
+                return false;
+            }
+            size = next_size;
+            true
+        };
+
+        if let Some(transaction_priority) = &self.transaction_priority {
+            for tx in iter_prioritized(transactions, &**transaction_priority, block_number) {
+                if !fits(tx) {
+                    break;
+                }
+                // The caller needs to remove the transaction from the mempool.
+                txs.push(tx.clone());
+            }
+            return (txs, size);
+        }
+
+        // Without a custom priority, the transactions are already ordered by fee per byte.
         loop {
             // Get the hash of the highest paying transactions.
             let tx_hash = match transactions.best_transactions.peek() {
@@ -591,15 +794,9 @@ impl Mempool {
 
             // Get the transaction.
             let tx = transactions.get(&tx_hash).unwrap().clone();
-
-            // Calculate size. If we can't fit the transaction in the block, then we stop here.
-            // TODO: We can optimize this. There might be a smaller transaction that still fits.
-            // We need to account for one extra byte per transaction to encode its final execution status
-            let next_size = size + 1 + tx.serialized_size();
-            if next_size > max_bytes {
+            if !fits(&tx) {
                 break;
             }
-            size = next_size;
 
             // Remove the transaction from best_transactions so that we can advance.
             // The caller needs to clean up the rest of the data structures.
@@ -700,4 +897,31 @@ impl TransactionVerificationCache for Mempool {
             false
         }
     }
+
+    fn get_by_short_ids(
+        &self,
+        block_hash: &Blake2bHash,
+        short_ids: &[ShortTransactionId],
+    ) -> Vec<Option<Transaction>> {
+        let mut transactions = vec![None; short_ids.len()];
+        let indexes: HashMap<&ShortTransactionId, usize> = short_ids
+            .iter()
+            .enumerate()
+            .map(|(index, short_id)| (short_id, index))
+            .collect();
+
+        let state = self.state.read();
+        for (hash, transaction) in state
+            .regular_transactions
+            .transactions
+            .iter()
+            .chain(state.control_transactions.transactions.iter())
+        {
+            if let Some(&index) = indexes.get(&ShortTransactionId::new(block_hash, hash)) {
+                transactions[index] = Some(transaction.clone());
+            }
+        }
+
+        transactions
+    }
 }
@@ -22,6 +22,7 @@ enum TxRemovedReason {
     AlreadyIncludedTx,
     Invalid,
     TooFull,
+    Replaced,
 }
 
 impl MempoolMetrics {
@@ -39,6 +40,7 @@ impl MempoolMetrics {
             EvictionReason::AlreadyIncluded => TxRemovedReason::AlreadyIncludedTx,
             EvictionReason::Invalid => TxRemovedReason::Invalid,
             EvictionReason::TooFull => TxRemovedReason::TooFull,
+            EvictionReason::Replaced => TxRemovedReason::Replaced,
             _ => return,
         };
         self.evicted_tx
@@ -1,8 +1,9 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap};
 #[cfg(feature = "metrics")]
 use std::sync::Arc;
 
-use nimiq_account::ReservedBalance;
+use hashlink::LruCache;
+use nimiq_account::{Account, ReservedBalance};
 use nimiq_blockchain::Blockchain;
 use nimiq_hash::{Blake2bHash, Hash};
 use nimiq_keys::Address;
@@ -26,16 +27,31 @@ pub(crate) struct MempoolState {
     // The pending balance per sender.
     pub(crate) state_by_sender: HashMap<Address, SenderPendingState>,
 
+    // The fee increase (in percent) a transaction must pay to replace a pending one it re-issues.
+    pub(crate) replacement_fee_bump: u64,
+
+    // Hashes of the most recently replaced transactions, which are not accepted again.
+    pub(crate) replaced: LruCache<Blake2bHash, ()>,
+
     #[cfg(feature = "metrics")]
     pub(crate) metrics: Arc<MempoolMetrics>,
 }
 
 impl MempoolState {
-    pub fn new(regular_txns_limit: usize, control_txns_limit: usize) -> Self {
+    /// Maximum number of replaced transactions that are remembered
+    const MAX_REPLACED_TRANSACTIONS: usize = 10_000;
+
+    pub fn new(
+        regular_txns_limit: usize,
+        control_txns_limit: usize,
+        replacement_fee_bump: u64,
+    ) -> Self {
         MempoolState {
             regular_transactions: MempoolTransactions::new(regular_txns_limit),
             control_transactions: MempoolTransactions::new(control_txns_limit),
             state_by_sender: HashMap::new(),
+            replacement_fee_bump,
+            replaced: LruCache::new(Self::MAX_REPLACED_TRANSACTIONS),
             #[cfg(feature = "metrics")]
             metrics: Default::default(),
         }
@@ -67,26 +83,149 @@ impl MempoolState {
             return Err(VerifyErr::Known);
         }
 
+        // Don't accept replaced transactions again, which would also relay them.
+        if self.replaced.contains_key(&tx_hash) {
+            return Err(VerifyErr::Replaced);
+        }
+
         // Reserve the balance necessary for this transaction on the sender account.
         let sender_account = blockchain
             .get_account_if_complete(&tx.sender)
             .ok_or(VerifyErr::NoConsensus)?;
 
+        // A transaction that re-issues a pending one, e.g. because the pending one is stuck with
+        // a too low fee, replaces it if it pays enough more.
+        if let Some(replaced_hash) = self.find_reissued(&tx) {
+            return self.replace(blockchain, &sender_account, replaced_hash, tx, priority);
+        }
+
         if let Some(sender_state) = self.state_by_sender.get_mut(&tx.sender) {
             let reserved_balance = &mut sender_state.reserved_balance;
             blockchain.reserve_balance(&sender_account, &tx, reserved_balance)?;
-            sender_state.txns.insert(tx.hash());
+            sender_state
+                .txns
+                .insert((tx.validity_start_height, tx_hash));
         } else {
             let mut reserved_balance = ReservedBalance::new(tx.sender.clone());
             blockchain.reserve_balance(&sender_account, &tx, &mut reserved_balance)?;
 
             let sender_state = SenderPendingState {
                 reserved_balance,
-                txns: HashSet::from([tx.hash()]),
+                txns: BTreeSet::from([(tx.validity_start_height, tx_hash)]),
             };
             self.state_by_sender.insert(tx.sender.clone(), sender_state);
         }
 
+        self.insert(blockchain, tx, priority);
+
+        Ok(())
+    }
+
+    /// Returns the hash of the pending transaction that the given transaction re-issues, if any.
+    fn find_reissued(&self, tx: &Transaction) -> Option<Blake2bHash> {
+        let sender_state = self.state_by_sender.get(&tx.sender)?;
+        sender_state
+            .txns
+            .iter()
+            .filter(|(validity_start_height, _)| *validity_start_height == tx.validity_start_height)
+            .map(|(_, hash)| hash)
+            .find(|hash| {
+                self.get(hash)
+                    .is_some_and(|pending_tx| is_reissue(pending_tx, tx))
+            })
+            .cloned()
+    }
+
+    /// Replaces a pending transaction by the given transaction that re-issues it with a higher
+    /// fee. The balance of the sender is reserved again for its transactions in the order they
+    /// are chained in, such that subsequent transactions that can no longer be paid for because
+    /// of the higher fee are evicted.
+    ///
+    /// Replacement is best effort: Nodes that received the replaced transaction before still
+    /// include it in their blocks, which invalidates the replacement. The replaced transaction is
+    /// remembered, such that it is neither accepted nor relayed again by this node.
+    fn replace(
+        &mut self,
+        blockchain: &Blockchain,
+        sender_account: &Account,
+        replaced_hash: Blake2bHash,
+        tx: Transaction,
+        priority: TxPriority,
+    ) -> Result<(), VerifyErr> {
+        let replaced_fee = u64::from(self.get(&replaced_hash).expect("Tx must be pending").fee);
+        let required_fee = replaced_fee
+            .saturating_add(replaced_fee.saturating_mul(self.replacement_fee_bump) / 100);
+        let fee = u64::from(tx.fee);
+        if fee <= replaced_fee || fee < required_fee {
+            return Err(VerifyErr::ReplacementUnderpriced);
+        }
+
+        let sender_state = self
+            .state_by_sender
+            .get(&tx.sender)
+            .expect("Sender of pending tx must be known");
+        let mut reserved_balance = ReservedBalance::new(tx.sender.clone());
+        let mut evicted = vec![];
+        for (_, hash) in &sender_state.txns {
+            let chained_tx = if *hash == replaced_hash {
+                &tx
+            } else {
+                self.get(hash).expect("Tx must be pending")
+            };
+            if let Err(error) =
+                blockchain.reserve_balance(sender_account, chained_tx, &mut reserved_balance)
+            {
+                if *hash == replaced_hash {
+                    return Err(error.into());
+                }
+                evicted.push(hash.clone());
+            }
+        }
+
+        // The balance is reserved anew as a whole, so the replaced and evicted transactions are
+        // deleted without releasing their balance.
+        let sender_state = self
+            .state_by_sender
+            .get_mut(&tx.sender)
+            .expect("Sender of pending tx must be known");
+        sender_state.reserved_balance = reserved_balance;
+        sender_state
+            .txns
+            .retain(|(_, hash)| *hash != replaced_hash && !evicted.contains(hash));
+        sender_state
+            .txns
+            .insert((tx.validity_start_height, tx.hash()));
+
+        debug!(
+            replaced = %replaced_hash,
+            replacement = %tx.hash::<Blake2bHash>(),
+            evicted = evicted.len(),
+            "Replacing pending transaction"
+        );
+        self.delete(&replaced_hash, EvictionReason::Replaced);
+        self.replaced.insert(replaced_hash, ());
+        for hash in &evicted {
+            self.delete(hash, EvictionReason::Invalid);
+        }
+
+        self.insert(blockchain, tx, priority);
+
+        Ok(())
+    }
+
+    /// Deletes a transaction from the containers without updating the state of its sender.
+    fn delete(&mut self, tx_hash: &Blake2bHash, #[allow(unused_variables)] reason: EvictionReason) {
+        self.regular_transactions
+            .delete(tx_hash)
+            .or_else(|| self.control_transactions.delete(tx_hash));
+
+        #[cfg(feature = "metrics")]
+        self.metrics.note_evicted(reason);
+    }
+
+    /// Inserts a transaction whose balance has already been reserved into its container and
+    /// evicts the worst transactions if the container became too full.
+    fn insert(&mut self, blockchain: &Blockchain, tx: Transaction, priority: TxPriority) {
         // If we are adding a staking transaction we insert it into the control txns container
         // Staking txns are control txns
         if tx.sender_type == AccountType::Staking || tx.recipient_type == AccountType::Staking {
@@ -105,8 +244,6 @@ impl MempoolState {
             let (tx_hash, _) = self.control_transactions.worst_transactions.pop().unwrap();
             self.remove(blockchain, &tx_hash, EvictionReason::TooFull);
         }
-
-        Ok(())
     }
 
     pub(crate) fn remove(
@@ -130,7 +267,7 @@ impl MempoolState {
             None => {
                 // We don't know the sender account so we can't do any balance tracking.
                 // Throw away all transactions from this sender.
-                for hash in &sender_state.txns {
+                for (_, hash) in &sender_state.txns {
                     self.regular_transactions
                         .delete(hash)
                         .or_else(|| self.control_transactions.delete(hash));
@@ -140,7 +277,10 @@ impl MempoolState {
             }
         };
 
-        if !sender_state.txns.remove(tx_hash) {
+        if !sender_state
+            .txns
+            .remove(&(tx.validity_start_height, tx_hash.clone()))
+        {
             return Some(tx);
         }
 
@@ -159,9 +299,15 @@ impl MempoolState {
     }
 
     /// Retrieves all expired transaction hashes from both the `regular_transactions` and `control_transactions` vectors
-    pub fn get_expired_txns(&mut self, block_number: u32) -> Vec<Blake2bHash> {
-        let mut expired_txns = self.control_transactions.get_expired_txns(block_number);
-        expired_txns.append(&mut self.regular_transactions.get_expired_txns(block_number));
+    pub fn get_expired_txns(&mut self, block_number: u32, timestamp: u64) -> Vec<Blake2bHash> {
+        let mut expired_txns = self
+            .control_transactions
+            .get_expired_txns(block_number, timestamp);
+        expired_txns.append(
+            &mut self
+                .regular_transactions
+                .get_expired_txns(block_number, timestamp),
+        );
         expired_txns
     }
 }
@@ -173,12 +319,29 @@ pub(crate) enum EvictionReason {
     AlreadyIncluded,
     Invalid,
     TooFull,
+    Replaced,
 }
 
 pub(crate) struct SenderPendingState {
     // The balance reserved by transactions that are currently stored in the mempool for this sender.
     pub(crate) reserved_balance: ReservedBalance,
 
-    // Transaction hashes for this sender.
-    pub(crate) txns: HashSet<Blake2bHash>,
+    // Transaction hashes for this sender, ordered by validity start height. This is the order in
+    // which the transactions are chained, i.e. in which their balance is reserved.
+    pub(crate) txns: BTreeSet<(u32, Blake2bHash)>,
+}
+
+/// Returns whether `tx` re-issues `pending_tx`, i.e. whether it is identical except for its fee
+/// and proof.
+fn is_reissue(pending_tx: &Transaction, tx: &Transaction) -> bool {
+    pending_tx.sender == tx.sender
+        && pending_tx.sender_type == tx.sender_type
+        && pending_tx.sender_data == tx.sender_data
+        && pending_tx.recipient == tx.recipient
+        && pending_tx.recipient_type == tx.recipient_type
+        && pending_tx.recipient_data == tx.recipient_data
+        && pending_tx.value == tx.value
+        && pending_tx.validity_start_height == tx.validity_start_height
+        && pending_tx.network_id == tx.network_id
+        && pending_tx.flags == tx.flags
 }
@@ -28,9 +28,9 @@ pub enum TxPriority {
 //       we might prefer basic transactions over staking contract transactions, etc, etc.
 #[derive(PartialEq)]
 pub struct BestTxOrder {
-    priority: TxPriority,
+    pub(crate) priority: TxPriority,
     fee_per_byte: f64,
-    insertion_order: u64,
+    pub(crate) insertion_order: u64,
 }
 
 impl Eq for BestTxOrder {}
@@ -108,6 +108,10 @@ pub(crate) struct MempoolTransactions {
     // This ordering is used to evict expired transactions from the mempool.
     pub(crate) oldest_transactions: KeyedPriorityQueue<Blake2bHash, Reverse<u32>>,
 
+    // Transactions with an expiry timestamp ordered by it (earliest to latest).
+    // This ordering is used to evict transactions from the mempool once their expiry timestamp passed.
+    pub(crate) expiring_transactions: KeyedPriorityQueue<Blake2bHash, Reverse<u64>>,
+
     // Maximum allowed total size (in bytes) of all transactions in the mempool.
     pub(crate) total_size_limit: usize,
 
@@ -125,6 +129,7 @@ impl MempoolTransactions {
             best_transactions: KeyedPriorityQueue::new(),
             worst_transactions: KeyedPriorityQueue::new(),
             oldest_transactions: KeyedPriorityQueue::new(),
+            expiring_transactions: KeyedPriorityQueue::new(),
             total_size_limit: size_limit,
             total_size: 0,
             tx_counter: 0,
@@ -139,8 +144,9 @@ impl MempoolTransactions {
         self.transactions.len()
     }
 
-    // This function is used to remove the transactions that are no longer valid at a given block number.
-    pub fn get_expired_txns(&mut self, block_number: u32) -> Vec<Blake2bHash> {
+    // This function is used to remove the transactions that are no longer valid at a given block number
+    // or whose expiry timestamp has passed at the given timestamp.
+    pub fn get_expired_txns(&mut self, block_number: u32, timestamp: u64) -> Vec<Blake2bHash> {
         let mut expired_txns = vec![];
         loop {
             // Get the hash of the oldest transaction.
@@ -162,6 +168,21 @@ impl MempoolTransactions {
                 expired_txns.push(tx_hash);
             }
         }
+        loop {
+            // Get the hash of the transaction that expires first.
+            let tx_hash = match self.expiring_transactions.peek() {
+                Some((tx_hash, Reverse(expiry_timestamp))) if timestamp > *expiry_timestamp => {
+                    tx_hash.clone()
+                }
+                // No need to process more transactions, the remaining ones didn't expire yet.
+                _ => break,
+            };
+
+            self.expiring_transactions.remove(&tx_hash);
+            if self.oldest_transactions.remove(&tx_hash).is_some() {
+                expired_txns.push(tx_hash);
+            }
+        }
         expired_txns
     }
 
@@ -197,6 +218,10 @@ impl MempoolTransactions {
 
         self.oldest_transactions
             .push(tx_hash.clone(), Reverse(tx.validity_start_height));
+        if let Some(expiry_timestamp) = tx.expiry_timestamp {
+            self.expiring_transactions
+                .push(tx_hash.clone(), Reverse(expiry_timestamp));
+        }
 
         // Update total tx size
         self.total_size += tx.serialized_size();
@@ -212,6 +237,7 @@ impl MempoolTransactions {
         self.best_transactions.remove(tx_hash);
         self.worst_transactions.remove(tx_hash);
         self.oldest_transactions.remove(tx_hash);
+        self.expiring_transactions.remove(tx_hash);
 
         self.total_size -= tx.serialized_size();
 
@@ -0,0 +1,79 @@
+use std::{cmp::Ordering, collections::BinaryHeap, fmt::Debug};
+
+use nimiq_transaction::Transaction;
+
+use crate::mempool_transactions::{MempoolTransactions, TxPriority};
+
+/// Determines the order in which pending transactions are handed to the block producer to fill
+/// a micro block. Transactions with a higher score are included first.
+pub trait TransactionPriority: Debug + Send + Sync {
+    /// Scores `transaction` for inclusion in the block at `block_number`.
+    fn score(&self, transaction: &Transaction, block_number: u32) -> f64;
+}
+
+/// The default transaction priority, preferring transactions that pay the highest fee per byte.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FeePerBytePriority;
+
+impl TransactionPriority for FeePerBytePriority {
+    fn score(&self, transaction: &Transaction, _block_number: u32) -> f64 {
+        transaction.fee_per_byte()
+    }
+}
+
+/// A transaction scored for inclusion in a block. Compares by mempool priority first, then by
+/// score (higher first) and finally by insertion order (lower i.e. older first).
+struct ScoredTx<'a> {
+    tx: &'a Transaction,
+    priority: TxPriority,
+    score: f64,
+    insertion_order: u64,
+}
+
+impl PartialEq for ScoredTx<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for ScoredTx<'_> {}
+
+impl PartialOrd for ScoredTx<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ScoredTx<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.priority as u8)
+            .cmp(&(other.priority as u8))
+            .then(self.score.total_cmp(&other.score))
+            .then(self.insertion_order.cmp(&other.insertion_order).reverse())
+    }
+}
+
+/// Returns the transactions in the order in which they should be included in the block at
+/// `block_number` according to `priority`. The transactions are scored once and then taken
+/// from a heap, such that only the transactions that are actually consumed are ordered.
+pub(crate) fn iter_prioritized<'a>(
+    transactions: &'a MempoolTransactions,
+    priority: &dyn TransactionPriority,
+    block_number: u32,
+) -> impl Iterator<Item = &'a Transaction> {
+    let mut heap: BinaryHeap<_> = transactions
+        .best_transactions
+        .iter()
+        .map(|(tx_hash, order)| {
+            let tx = &transactions.transactions[tx_hash];
+            ScoredTx {
+                tx,
+                priority: order.priority,
+                score: priority.score(tx, block_number),
+                insertion_order: order.insertion_order,
+            }
+        })
+        .collect();
+
+    std::iter::from_fn(move || heap.pop().map(|scored| scored.tx))
+}
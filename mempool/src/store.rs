@@ -0,0 +1,57 @@
+use nimiq_database::{
+    declare_table,
+    mdbx::MdbxDatabase,
+    traits::{Database, ReadCursor, ReadTransaction, WriteTransaction},
+};
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_transaction::Transaction;
+
+declare_table!(MempoolTable, "MempoolTransactions", Blake2bHash => Transaction);
+
+/// Persistent storage for the pending transactions of the mempool.
+///
+/// The transactions are written to the database when the node shuts down and are read back on
+/// startup, where they are verified again before they are added to the mempool.
+#[derive(Debug)]
+pub struct MempoolStore {
+    /// Environment for the DB creation and transaction handling.
+    env: MdbxDatabase,
+}
+
+impl MempoolStore {
+    /// Creates a new mempool store, creating the underlying table if it doesn't exist yet.
+    pub fn new(env: MdbxDatabase) -> Self {
+        env.create_regular_table(&MempoolTable);
+
+        Self { env }
+    }
+
+    /// Replaces the stored transactions with the given ones.
+    pub fn save<'a, I: IntoIterator<Item = &'a Transaction>>(&self, transactions: I) -> usize {
+        let mut txn = self.env.write_transaction();
+        txn.clear_table(&MempoolTable);
+
+        let mut count = 0;
+        for transaction in transactions {
+            txn.put(&MempoolTable, &transaction.hash(), transaction);
+            count += 1;
+        }
+
+        txn.commit();
+        count
+    }
+
+    /// Returns all stored transactions.
+    pub fn load(&self) -> Vec<Transaction> {
+        let txn = self.env.read_transaction();
+        let cursor = txn.cursor(&MempoolTable);
+        cursor.into_iter_start().map(|(_, tx)| tx).collect()
+    }
+
+    /// Removes all stored transactions.
+    pub fn clear(&self) {
+        let mut txn = self.env.write_transaction();
+        txn.clear_table(&MempoolTable);
+        txn.commit();
+    }
+}
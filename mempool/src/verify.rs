@@ -20,6 +20,8 @@ pub enum VerifyErr {
     AlreadyIncluded,
     #[error("Transaction not valid at current block number")]
     InvalidBlockNumber,
+    #[error("Transaction expiry timestamp has passed")]
+    Expired,
     #[error("Transaction cannot be applied to sender account: {0}")]
     InvalidAccount(#[from] AccountError),
     #[error("Transaction already in mempool")]
@@ -28,6 +30,10 @@ pub enum VerifyErr {
     Filtered,
     #[error("Can't verify transaction without consensus")]
     NoConsensus,
+    #[error("Transaction doesn't pay enough to replace the pending transaction it re-issues")]
+    ReplacementUnderpriced,
+    #[error("Transaction was replaced by a transaction re-issuing it")]
+    Replaced,
 }
 
 /// Verifies a transaction and adds it to the mempool.
@@ -45,12 +51,16 @@ pub(crate) fn verify_tx(
     // 2. Acquire blockchain read lock
     let blockchain = blockchain.read();
 
-    // 3. Check validity window and already included
+    // 3. Check validity window, expiry timestamp and already included
     let block_number = blockchain.block_number() + 1;
     if !transaction.is_valid_at(block_number) {
         return Err(VerifyErr::InvalidBlockNumber);
     }
 
+    if transaction.is_expired_at(blockchain.timestamp()) {
+        return Err(VerifyErr::Expired);
+    }
+
     let hash: Blake2bHash = transaction.hash();
     if blockchain.contains_tx_in_validity_window(&hash.clone().into(), None) {
         return Err(VerifyErr::AlreadyIncluded);
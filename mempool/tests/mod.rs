@@ -11,7 +11,10 @@ use nimiq_keys::{
     Address, Ed25519PublicKey as SchnorrPublicKey, KeyPair as SchnorrKeyPair,
     PrivateKey as SchnorrPrivateKey, SecureGenerate,
 };
-use nimiq_mempool::{config::MempoolConfig, mempool::Mempool, mempool_transactions::TxPriority};
+use nimiq_mempool::{
+    config::MempoolConfig, mempool::Mempool, mempool_transactions::TxPriority,
+    priority::TransactionPriority, store::MempoolStore, verify::VerifyErr,
+};
 use nimiq_network_mock::{MockHub, MockId, MockNetwork, MockPeerId};
 use nimiq_primitives::{coin::Coin, networks::NetworkId, policy::Policy};
 use nimiq_serde::{Deserialize, Serialize};
@@ -2205,3 +2208,238 @@ async fn it_can_reject_invalid_vesting_contract_transaction() {
         "Number of txns in the mempools is not what is expected"
     );
 }
+
+#[test(tokio::test)]
+async fn replaces_reissued_tx_with_higher_fee() {
+    let mut rng = test_rng(true);
+    let mut genesis_builder = GenesisBuilder::default();
+    genesis_builder.with_network(NetworkId::UnitAlbatross);
+
+    let recipient_accounts = generate_accounts(vec![0], &mut genesis_builder, false, &mut rng);
+    let sender_accounts = generate_accounts(vec![100], &mut genesis_builder, true, &mut rng);
+
+    // The same transaction issued with increasing fees.
+    let (txns, _) = generate_transactions(
+        [0, 20, 21]
+            .into_iter()
+            .map(|fee| TestTransaction {
+                fee,
+                value: 10,
+                recipient: recipient_accounts[0].clone(),
+                sender: sender_accounts[0].clone(),
+            })
+            .collect(),
+        true,
+    );
+
+    let time = Arc::new(OffsetTime::new());
+    let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+    genesis_builder.with_genesis_validator(
+        Address::from(&SchnorrKeyPair::generate(&mut rng)),
+        SchnorrPublicKey::from([0u8; 32]),
+        BlsKeyPair::generate(&mut rng).public_key,
+        Address::default(),
+        None,
+        None,
+        false,
+    );
+    let genesis_info = genesis_builder.generate(env.clone()).unwrap();
+    let genesis_block = match genesis_info.block {
+        Block::Macro(mut block) => {
+            block.header.block_number = Policy::genesis_block_number();
+            Block::Macro(block)
+        }
+        Block::Micro(_) => panic!(),
+    };
+    let blockchain = Arc::new(RwLock::new(
+        Blockchain::with_genesis(
+            env,
+            BlockchainConfig::default(),
+            time,
+            NetworkId::UnitAlbatross,
+            genesis_block,
+            genesis_info.accounts,
+        )
+        .unwrap(),
+    ));
+    let mempool = Mempool::new(blockchain, MempoolConfig::default());
+
+    // The re-issued transaction replaces the pending one.
+    mempool.add_transaction(txns[0].clone(), None).unwrap();
+    mempool.add_transaction(txns[1].clone(), None).unwrap();
+    assert_eq!(
+        mempool.get_transaction_hashes(),
+        vec![txns[1].hash::<Blake2bHash>()]
+    );
+
+    // A replacement must pay the configured fee bump.
+    assert_eq!(
+        mempool.add_transaction(txns[2].clone(), None),
+        Err(VerifyErr::ReplacementUnderpriced)
+    );
+    assert_eq!(
+        mempool.get_transaction_hashes(),
+        vec![txns[1].hash::<Blake2bHash>()]
+    );
+
+    // The replaced transaction is not accepted again.
+    assert_eq!(
+        mempool.add_transaction(txns[0].clone(), None),
+        Err(VerifyErr::Replaced)
+    );
+    assert_eq!(
+        mempool.get_transaction_hashes(),
+        vec![txns[1].hash::<Blake2bHash>()]
+    );
+}
+
+#[test(tokio::test)]
+async fn persists_and_restores_transactions() {
+    let mut rng = test_rng(true);
+    let mut genesis_builder = GenesisBuilder::default();
+    genesis_builder.with_network(NetworkId::UnitAlbatross);
+
+    let recipient_accounts = generate_accounts(vec![0, 0], &mut genesis_builder, false, &mut rng);
+    let sender_accounts = generate_accounts(vec![100], &mut genesis_builder, true, &mut rng);
+
+    let (txns, _) = generate_transactions(
+        recipient_accounts
+            .iter()
+            .map(|recipient| TestTransaction {
+                fee: 0,
+                value: 10,
+                recipient: recipient.clone(),
+                sender: sender_accounts[0].clone(),
+            })
+            .collect(),
+        true,
+    );
+
+    let time = Arc::new(OffsetTime::new());
+    let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+    genesis_builder.with_genesis_validator(
+        Address::from(&SchnorrKeyPair::generate(&mut rng)),
+        SchnorrPublicKey::from([0u8; 32]),
+        BlsKeyPair::generate(&mut rng).public_key,
+        Address::default(),
+        None,
+        None,
+        false,
+    );
+    let genesis_info = genesis_builder.generate(env.clone()).unwrap();
+    let genesis_block = match genesis_info.block {
+        Block::Macro(mut block) => {
+            block.header.block_number = Policy::genesis_block_number();
+            Block::Macro(block)
+        }
+        Block::Micro(_) => panic!(),
+    };
+    let blockchain = Arc::new(RwLock::new(
+        Blockchain::with_genesis(
+            env.clone(),
+            BlockchainConfig::default(),
+            time,
+            NetworkId::UnitAlbatross,
+            genesis_block,
+            genesis_info.accounts,
+        )
+        .unwrap(),
+    ));
+
+    let mempool = Mempool::new(Arc::clone(&blockchain), MempoolConfig::default())
+        .with_store(MempoolStore::new(env.clone()));
+    for tx in &txns {
+        mempool.add_transaction(tx.clone(), None).unwrap();
+    }
+    mempool.persist();
+
+    // A fresh mempool restores the persisted transactions exactly once.
+    let mempool =
+        Mempool::new(blockchain, MempoolConfig::default()).with_store(MempoolStore::new(env));
+    assert_eq!(mempool.restore(), 2);
+    assert_eq!(mempool.num_transactions(), 2);
+    for tx in &txns {
+        assert!(mempool.contains_transaction_by_hash(&tx.hash()));
+    }
+    assert_eq!(mempool.restore(), 0);
+}
+
+#[derive(Debug)]
+struct RecipientPriority(Address);
+
+impl TransactionPriority for RecipientPriority {
+    fn score(&self, transaction: &Transaction, _block_number: u32) -> f64 {
+        if transaction.recipient == self.0 {
+            1.0
+        } else {
+            0.0
+        }
+    }
+}
+
+#[test(tokio::test)]
+async fn orders_block_transactions_by_custom_priority() {
+    let mut rng = test_rng(true);
+    let mut genesis_builder = GenesisBuilder::default();
+    genesis_builder.with_network(NetworkId::UnitAlbatross);
+
+    let recipient_accounts = generate_accounts(vec![0, 0], &mut genesis_builder, false, &mut rng);
+    let sender_accounts = generate_accounts(vec![100], &mut genesis_builder, true, &mut rng);
+
+    let (txns, _) = generate_transactions(
+        recipient_accounts
+            .iter()
+            .map(|recipient| TestTransaction {
+                fee: 0,
+                value: 10,
+                recipient: recipient.clone(),
+                sender: sender_accounts[0].clone(),
+            })
+            .collect(),
+        true,
+    );
+
+    let time = Arc::new(OffsetTime::new());
+    let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+    genesis_builder.with_genesis_validator(
+        Address::from(&SchnorrKeyPair::generate(&mut rng)),
+        SchnorrPublicKey::from([0u8; 32]),
+        BlsKeyPair::generate(&mut rng).public_key,
+        Address::default(),
+        None,
+        None,
+        false,
+    );
+    let genesis_info = genesis_builder.generate(env.clone()).unwrap();
+    let genesis_block = match genesis_info.block {
+        Block::Macro(mut block) => {
+            block.header.block_number = Policy::genesis_block_number();
+            Block::Macro(block)
+        }
+        Block::Micro(_) => panic!(),
+    };
+    let blockchain = Arc::new(RwLock::new(
+        Blockchain::with_genesis(
+            env,
+            BlockchainConfig::default(),
+            time,
+            NetworkId::UnitAlbatross,
+            genesis_block,
+            genesis_info.accounts,
+        )
+        .unwrap(),
+    ));
+
+    // The priority prefers the transaction that was added last.
+    let mempool_config = MempoolConfig {
+        transaction_priority: Some(Arc::new(RecipientPriority(txns[1].recipient.clone()))),
+        ..Default::default()
+    };
+    let mempool = Mempool::new(blockchain, mempool_config);
+    for tx in &txns {
+        mempool.add_transaction(tx.clone(), None).unwrap();
+    }
+
+    let (block_txns, _) = mempool.get_transactions_for_block(10_000);
+    assert_eq!(block_txns, vec![txns[1].clone(), txns[0].clone()]);
+}
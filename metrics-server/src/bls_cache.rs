@@ -0,0 +1,39 @@
+use std::sync::Arc;
+
+use nimiq_consensus::BlsCache;
+use parking_lot::Mutex;
+use prometheus_client::registry::Registry;
+
+use crate::{ClosureCounterMetric, NumericClosureMetric};
+
+pub struct BlsCacheMetrics {}
+
+impl BlsCacheMetrics {
+    pub fn register(registry: &mut Registry, bls_cache: Arc<Mutex<BlsCache>>) {
+        let sub_registry = registry.sub_registry_with_prefix("bls_cache");
+
+        let cache = Arc::clone(&bls_cache);
+        let closure = ClosureCounterMetric::new(Box::new(move || cache.lock().stats().hits));
+        sub_registry.register("hits", "Number of keys found in the cache", closure);
+
+        let cache = Arc::clone(&bls_cache);
+        let closure = ClosureCounterMetric::new(Box::new(move || cache.lock().stats().misses));
+        sub_registry.register("misses", "Number of keys missing from the cache", closure);
+
+        let cache = Arc::clone(&bls_cache);
+        let closure = ClosureCounterMetric::new(Box::new(move || cache.lock().stats().evictions));
+        sub_registry.register(
+            "evictions",
+            "Number of keys evicted from the cache",
+            closure,
+        );
+
+        let cache = Arc::clone(&bls_cache);
+        let closure = NumericClosureMetric::new_gauge(Box::new(move || cache.lock().len() as i64));
+        sub_registry.register("size", "Number of keys currently in the cache", closure);
+
+        let closure =
+            NumericClosureMetric::new_gauge(Box::new(move || bls_cache.lock().capacity() as i64));
+        sub_registry.register("capacity", "Maximum number of keys in the cache", closure);
+    }
+}
@@ -1,14 +1,14 @@
 use std::{fmt::Debug, net::SocketAddr, sync::Arc};
 
 use nimiq_blockchain_proxy::BlockchainProxy;
-use nimiq_consensus::ConsensusProxy;
+use nimiq_consensus::{BlsCache, ConsensusProxy};
 use nimiq_mempool::mempool::Mempool;
 use nimiq_network_interface::network::Network;
 use nimiq_utils::spawn;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use prometheus_client::{
     encoding::{EncodeGaugeValue, EncodeMetric, MetricEncoder},
-    metrics::MetricType,
+    metrics::{exemplar::Exemplar, MetricType},
     registry::Registry,
 };
 #[cfg(tokio_unstable)]
@@ -18,10 +18,12 @@ use tokio_metrics::TaskMonitor;
 #[cfg(tokio_unstable)]
 use crate::tokio_runtime::TokioRuntimeMetrics;
 use crate::{
-    chain::BlockMetrics, consensus::ConsensusMetrics, mempool::MempoolMetrics,
-    network::NetworkMetrics, server::metrics_server, tokio_task::TokioTaskMetrics,
+    bls_cache::BlsCacheMetrics, chain::BlockMetrics, consensus::ConsensusMetrics,
+    mempool::MempoolMetrics, network::NetworkMetrics, server::metrics_server,
+    tokio_task::TokioTaskMetrics,
 };
 
+mod bls_cache;
 mod chain;
 mod consensus;
 mod mempool;
@@ -92,12 +94,44 @@ impl<T: EncodeGaugeValue + Sized + Debug> Debug for NumericClosureMetric<T> {
     }
 }
 
+/// A counter whose value is read from a closure, for counts that are kept elsewhere.
+struct ClosureCounterMetric {
+    lambda: Box<dyn Fn() -> u64 + Sync + Send>,
+}
+
+impl ClosureCounterMetric {
+    pub fn new(lambda: Box<dyn Fn() -> u64 + Sync + Send>) -> ClosureCounterMetric {
+        ClosureCounterMetric { lambda }
+    }
+}
+
+impl EncodeMetric for ClosureCounterMetric {
+    fn encode(&self, mut encoder: MetricEncoder) -> Result<(), std::fmt::Error> {
+        encoder.encode_counter(&(self.lambda)(), None::<&Exemplar<(), f64>>)?;
+
+        Ok(())
+    }
+
+    fn metric_type(&self) -> MetricType {
+        MetricType::Counter
+    }
+}
+
+impl Debug for ClosureCounterMetric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClosureCounterMetric")
+            .field("value", &(self.lambda)())
+            .finish()
+    }
+}
+
 pub fn start_metrics_server<TNetwork: Network>(
     addr: SocketAddr,
     blockchain_proxy: BlockchainProxy,
     mempool: Option<Arc<Mempool>>,
     consensus_proxy: ConsensusProxy<TNetwork>,
     network: Arc<nimiq_network_libp2p::Network>,
+    bls_cache: Arc<Mutex<BlsCache>>,
     task_monitors: &[NimiqTaskMonitor],
 ) {
     let mut registry = Registry::default();
@@ -106,6 +140,7 @@ pub fn start_metrics_server<TNetwork: Network>(
     BlockMetrics::register(nimiq_registry, blockchain_proxy);
     ConsensusMetrics::register(nimiq_registry, consensus_proxy);
     NetworkMetrics::register(nimiq_registry, network);
+    BlsCacheMetrics::register(nimiq_registry, bls_cache);
 
     if let Some(mempool) = mempool {
         MempoolMetrics::register(nimiq_registry, mempool);
@@ -7,13 +7,14 @@ use std::{
 use async_trait::async_trait;
 use futures::stream::BoxStream;
 use nimiq_serde::{Deserialize, DeserializeError, Serialize};
-use nimiq_utils::tagged_signing::{TaggedKeyPair, TaggedSignable};
+use nimiq_utils::tagged_signing::{TaggedKeyPair, TaggedSignable, TaggedSigned};
 use thiserror::Error;
 use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
 
 use crate::{
     peer_info::*,
     request::{Message, Request, RequestError},
+    Multiaddr,
 };
 
 /// Network events that the network will report when subscribing
@@ -25,6 +26,18 @@ pub enum NetworkEvent<P> {
     PeerLeft(P),
     /// DHT is ready (bootstrapped and in server mode) to publish records
     DhtReady,
+    /// The reachability of the local peer from the outside changed
+    ReachabilityChanged(Reachability),
+}
+
+/// Whether the local peer can be reached by other peers.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Reachability {
+    /// At least one of our addresses is publicly reachable.
+    Public,
+    /// None of our addresses are publicly reachable, we are behind a NAT or firewall.
+    /// Inbound connections are only possible through relays.
+    Private,
 }
 
 pub type SubscribeEvents<PeerId> =
@@ -32,6 +45,17 @@ pub type SubscribeEvents<PeerId> =
 
 const DEFAULT_GOSSIPSUB_RATE_LIMIT_TIME_WINDOW: Duration = Duration::from_secs(10);
 
+/// The priority with which outgoing messages and requests are dispatched by the network.
+/// On saturated connections, consensus traffic is sent ahead of background traffic.
+#[derive(Copy, Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Priority {
+    /// Time critical traffic, such as Tendermint proposals and votes or skip block aggregations.
+    Consensus,
+    /// All other traffic, such as history chunks and state sync.
+    #[default]
+    Background,
+}
+
 pub trait Topic {
     type Item: Serialize + Deserialize + Send + Sync + Debug + 'static;
 
@@ -40,6 +64,13 @@ pub trait Topic {
     const VALIDATE: bool;
     const MAX_MESSAGES: u32;
     const TIME_WINDOW: Duration = DEFAULT_GOSSIPSUB_RATE_LIMIT_TIME_WINDOW;
+    const PRIORITY: Priority = Priority::Background;
+}
+
+/// Returns the name of the subtopic under which the instance of a topic scoped to the given epoch
+/// is published.
+pub fn epoch_subtopic(epoch_number: u32) -> String {
+    format!("epoch-{epoch_number}")
 }
 
 /// Network implementations have to at least support messages of this size.
@@ -88,7 +119,7 @@ pub enum SendError {
 #[async_trait]
 pub trait Network: Send + Sync + Unpin + 'static {
     type PeerId: Copy + Debug + Display + Ord + Hash + Send + Sync + Unpin + 'static;
-    type AddressType: Debug + Display + 'static;
+    type AddressType: Clone + Debug + Display + Send + Sync + 'static;
     type Error: std::error::Error;
     type PubsubId: PubsubId<Self::PeerId> + Send + Sync + Unpin;
     type RequestId: Copy + Debug + Display + Eq + Send + Sync + 'static;
@@ -179,6 +210,36 @@ pub trait Network: Send + Sync + Unpin + 'static {
         V: Serialize + Send + Sync + TaggedSignable + Clone + Ord,
         T: TaggedKeyPair + Send + Sync + Serialize + Deserialize;
 
+    /// Puts a value that has been signed beforehand to the distributed hash table. This allows
+    /// signing the value without having access to the key pair.
+    async fn dht_put_signed<K, V, T>(
+        &self,
+        k: &K,
+        signed_record: &TaggedSigned<V, T>,
+    ) -> Result<(), Self::Error>
+    where
+        K: AsRef<[u8]> + Send + Sync,
+        V: Serialize + Send + Sync + TaggedSignable + Clone + Ord,
+        T: TaggedKeyPair + Send + Sync + Serialize + Deserialize;
+
+    /// Announces in the distributed hash table that this node provides the given key.
+    /// The announcement is republished by the network until `dht_stop_providing` is called.
+    /// Other nodes only accept the announcement if the record stored under the same key was
+    /// published by this node.
+    async fn dht_start_providing<K>(&self, k: &K) -> Result<(), Self::Error>
+    where
+        K: AsRef<[u8]> + Send + Sync;
+
+    /// Stops announcing in the distributed hash table that this node provides the given key.
+    async fn dht_stop_providing<K>(&self, k: &K) -> Result<(), Self::Error>
+    where
+        K: AsRef<[u8]> + Send + Sync;
+
+    /// Gets the peers that announced in the distributed hash table that they provide the given key.
+    async fn dht_get_providers<K>(&self, k: &K) -> Result<Vec<Self::PeerId>, Self::Error>
+    where
+        K: AsRef<[u8]> + Send + Sync;
+
     /// Dials a peer
     async fn dial_peer(&self, peer_id: Self::PeerId) -> Result<(), Self::Error>;
 
@@ -188,6 +249,9 @@ pub trait Network: Send + Sync + Unpin + 'static {
     /// Gets the local peer ID
     fn get_local_peer_id(&self) -> Self::PeerId;
 
+    /// Gets the addresses the local peer advertises to other peers
+    fn get_local_addresses(&self) -> Vec<Multiaddr>;
+
     /// Sends a message to a specific peer
     async fn message<M: Message>(
         &self,
@@ -7,7 +7,7 @@ use thiserror::Error;
 /// The range to restrict the responses to the requests on the network layer.
 pub const DEFAULT_MAX_REQUEST_RESPONSE_TIME_WINDOW: Duration = Duration::from_secs(10);
 
-use crate::network::Network;
+use crate::network::{Network, Priority};
 
 #[derive(Copy, Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
 pub struct RequestType(pub u16);
@@ -112,6 +112,9 @@ pub enum InboundRequestError {
     /// The request exceeded the maximum defined rate limit for its request type.
     #[error("Request exceeds the maximum rate limit")]
     ExceedsRateLimit = 5,
+    /// The peer is too busy to serve the request, it may be retried later or with another peer.
+    #[error("Peer is too busy to serve the request")]
+    Busy = 6,
 }
 
 pub trait RequestKind {
@@ -136,6 +139,12 @@ pub trait RequestCommon:
     type Response: Deserialize + Serialize + Send;
     const MAX_REQUESTS: u32;
     const TIME_WINDOW: Duration = DEFAULT_MAX_REQUEST_RESPONSE_TIME_WINDOW;
+    /// Whether serving this request is an altruistic service to other peers, such as providing
+    /// history or state chunks. The network may throttle these requests to preserve resources
+    /// for the node's own duties.
+    const SEEDING: bool = false;
+    /// The priority with which requests of this type and their responses are dispatched.
+    const PRIORITY: Priority = Priority::Background;
 
     /// Returns the type name of the given request type `T`.
     /// This only works for
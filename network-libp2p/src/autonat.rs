@@ -26,6 +26,11 @@ pub(crate) struct NatState {
 }
 
 impl NatState {
+    /// Returns the general NAT status of the local peer
+    pub fn status(&self) -> NatStatus {
+        self.status
+    }
+
     /// Adds an address to track its NAT status
     pub fn add_address(&mut self, address: Multiaddr) {
         self.address_status.insert(address, NatStatus::Unknown);
@@ -61,8 +66,10 @@ impl NatState {
 
     /// External address expired thus no longer publicly reachable
     pub fn remove_confirmed_address(&mut self, address: &Multiaddr) {
-        let address_status = self.address_status.entry(address.clone()).or_default();
-        *address_status = NatStatus::Private;
+        // The address might no longer be tracked if we stopped listening on it.
+        if let Some(address_status) = self.address_status.get_mut(address) {
+            *address_status = NatStatus::Private;
+        }
 
         self.confirmed_addresses.remove(address);
         self.update_state();
@@ -90,7 +97,7 @@ impl NatState {
 
         if self.status == NatStatus::Private {
             log::warn!("Couldn't detect a public reachable address. Validator network operations won't be possible");
-            log::warn!("You may need to find a relay to enable validator network operations");
+            log::warn!("Listening on the configured relays, if any, to stay reachable");
         } else if self.status == NatStatus::Public {
             log::info!(
                 ?old_nat_status,
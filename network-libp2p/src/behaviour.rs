@@ -1,11 +1,16 @@
-use std::{iter, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    iter,
+    sync::Arc,
+    time::Duration,
+};
 
 use libp2p::{
     autonat::v2::{self as autonat, client::Config as AutonatConfig},
     connection_limits, gossipsub,
     kad::{self, store::MemoryStore},
-    ping, request_response,
-    swarm::NetworkBehaviour,
+    ping, relay, request_response,
+    swarm::{behaviour::toggle::Toggle, NetworkBehaviour},
     Multiaddr, PeerId, StreamProtocol,
 };
 use parking_lot::RwLock;
@@ -16,12 +21,24 @@ use crate::{
     connection_pool::behaviour::Config as PoolConfig,
     discovery::{self, peer_contacts::PeerContactBook},
     dispatch::codecs::MessageCodec,
+    latency::PeerLatency,
     Config,
 };
 
 /// Maximum simultaneous libp2p connections per peer
 const MAX_CONNECTIONS_PER_PEER: u32 = 5;
 
+/// Maximum number of circuits a relay server relays simultaneously to each of its relay clients
+const MAX_RELAYED_CIRCUITS_PER_CLIENT: usize = 128;
+
+/// Maximum duration of a relayed circuit. Circuits of validators behind sentries carry all of
+/// their traffic, thus they must not be limited to the short circuits meant for hole punching.
+/// Circuits that ended are re-established on demand.
+const MAX_RELAYED_CIRCUIT_DURATION: Duration = Duration::from_secs(60 * 60); // 1h
+
+/// Maximum number of bytes relayed per direction of a circuit before it is closed.
+const MAX_RELAYED_CIRCUIT_BYTES: u64 = 1 << 30; // 1 GiB
+
 /// Network behaviour.
 /// This is composed of several other behaviours that build a tree of behaviours using
 /// the `NetworkBehaviour` macro and the order of listed behaviours matters.
@@ -37,6 +54,8 @@ pub struct Behaviour {
     pub discovery: discovery::Behaviour,
     pub autonat_server: autonat::server::Behaviour,
     pub autonat_client: autonat::client::Behaviour,
+    pub relay_client: relay::client::Behaviour,
+    pub relay_server: Toggle<relay::Behaviour>,
     #[cfg(feature = "kad")]
     pub dht: kad::Behaviour<MemoryStore>,
     pub gossipsub: gossipsub::Behaviour,
@@ -50,6 +69,7 @@ impl Behaviour {
         contacts: Arc<RwLock<PeerContactBook>>,
         peer_score_params: gossipsub::PeerScoreParams,
         force_dht_server_mode: bool,
+        relay_client: relay::client::Behaviour,
     ) -> Self {
         let public_key = config.keypair.public();
         let peer_id = public_key.to_peer_id();
@@ -100,6 +120,7 @@ impl Behaviour {
             Arc::clone(&contacts),
             peer_id,
             config.seeds,
+            config.sentries,
             config.discovery.required_services,
             pool_config,
         );
@@ -118,6 +139,24 @@ impl Behaviour {
         // AutoNAT client behaviour
         let autonat_client = autonat::client::Behaviour::new(OsRng, AutonatConfig::default());
 
+        // Relay server behaviour, only relaying circuits to the configured relay clients
+        let relay_server = (!config.relay_clients.is_empty())
+            .then(|| {
+                let mut relay_config = relay::Config {
+                    max_circuits: MAX_RELAYED_CIRCUITS_PER_CLIENT * config.relay_clients.len(),
+                    max_circuit_duration: MAX_RELAYED_CIRCUIT_DURATION,
+                    max_circuit_bytes: MAX_RELAYED_CIRCUIT_BYTES,
+                    ..Default::default()
+                };
+                relay_config
+                    .reservation_rate_limiters
+                    .push(Box::new(RelayClients(
+                        config.relay_clients.iter().copied().collect(),
+                    )));
+                relay::Behaviour::new(peer_id, relay_config)
+            })
+            .into();
+
         // Connection limits behaviour
         let limits = connection_limits::ConnectionLimits::default()
             .with_max_pending_incoming(Some(16))
@@ -137,6 +176,8 @@ impl Behaviour {
             request_response,
             autonat_client,
             autonat_server,
+            relay_client,
+            relay_server,
             connection_limits,
         }
     }
@@ -167,8 +208,40 @@ impl Behaviour {
     }
 
     /// Updates the scores of all peers in the peer contact book.
-    /// Updates are performed with the score values of Gossipsub
-    pub fn update_scores(&self, contacts: Arc<RwLock<PeerContactBook>>) {
-        contacts.read().update_scores(&self.gossipsub);
+    /// Updates are performed with the score values of Gossipsub and the measured latencies
+    pub fn update_scores(
+        &self,
+        contacts: Arc<RwLock<PeerContactBook>>,
+        latencies: &HashMap<PeerId, PeerLatency>,
+    ) {
+        contacts.read().update_scores(&self.gossipsub, latencies);
+    }
+}
+
+/// Only accepts reservations of the relay clients, such that nobody else can use the relay server.
+/// The number of reservations per client is limited by the relay server itself.
+struct RelayClients(HashSet<PeerId>);
+
+impl relay::RateLimiter for RelayClients {
+    fn try_next(&mut self, peer: PeerId, _addr: &Multiaddr, _now: web_time::Instant) -> bool {
+        self.0.contains(&peer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use libp2p::{relay::RateLimiter, Multiaddr, PeerId};
+
+    use super::RelayClients;
+
+    #[test]
+    fn it_only_accepts_reservations_of_relay_clients() {
+        let client = PeerId::random();
+        let address = Multiaddr::empty();
+        let now = web_time::Instant::now();
+        let mut relay_clients = RelayClients([client].into_iter().collect());
+
+        assert!(relay_clients.try_next(client, &address, now));
+        assert!(!relay_clients.try_next(PeerId::random(), &address, now));
     }
 }
@@ -1,13 +1,21 @@
-use std::{num::NonZeroU8, time::Duration};
+use std::{
+    fmt, fs, io,
+    num::NonZeroU8,
+    path::{Path, PathBuf},
+    time::Duration,
+};
 
 use libp2p::{gossipsub, identity::Keypair, kad, Multiaddr, StreamProtocol};
 use nimiq_hash::Blake2bHash;
 use nimiq_network_interface::{network::MIN_SUPPORTED_MSG_SIZE, peer_info::Services};
+use rustls_pemfile::Item;
 use sha2::{Digest, Sha256};
 
 use crate::{
     discovery::{self, peer_contacts::PeerContact},
-    DHT_PROTOCOL,
+    rate_limiting::RateLimitPolicy,
+    seeding::SeedingConfig,
+    DHT_PROTOCOL, DHT_RECORD_TTL,
 };
 
 /// TLS settings for configuring a secure WebSocket
@@ -19,6 +27,57 @@ pub struct TlsConfig {
     pub certificates: Vec<Vec<u8>>,
 }
 
+impl TlsConfig {
+    /// Reads the TLS settings from a PEM-encoded private key file and a PEM-encoded certificates
+    /// file, converting them to DER format.
+    /// The certificates file may contain several certificates for certificate chaining.
+    pub fn from_pem_files<P: AsRef<Path>, Q: AsRef<Path>>(
+        private_key: P,
+        certificates: Q,
+    ) -> io::Result<Self> {
+        // Check that the provided private key has the expected format and convert the PEM file to DER format.
+        let private_key =
+            fs::read(private_key).and_then(|private_key_bytes| match rustls_pemfile::read_one(
+                &mut &*private_key_bytes,
+            )? {
+                Some(Item::Sec1Key(key)) => Ok(key.secret_sec1_der().to_vec()),
+                Some(Item::Pkcs8Key(key)) => Ok(key.secret_pkcs8_der().to_vec()),
+                Some(Item::Pkcs1Key(key)) => Ok(key.secret_pkcs1_der().to_vec()),
+                _ => Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "Invalid TLS private key",
+                )),
+            })?;
+        // Check that the provided certificates have the expected format and convert the PEM file to a list of
+        // certificates in DER format.
+        let certificates = fs::read(certificates).and_then(|certificate_bytes| {
+            rustls_pemfile::read_all(&mut &*certificate_bytes)
+                .map(|item| match item {
+                    Ok(Item::X509Certificate(cert)) => Ok(cert.to_vec()),
+                    _ => Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        "Invalid TLS certificate(s)",
+                    )),
+                })
+                .collect()
+        })?;
+
+        Ok(Self {
+            private_key,
+            certificates,
+        })
+    }
+}
+
+impl fmt::Debug for TlsConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Never print the private key.
+        f.debug_struct("TlsConfig")
+            .field("certificates", &self.certificates.len())
+            .finish_non_exhaustive()
+    }
+}
+
 /// LibP2P network configuration
 pub struct Config {
     pub keypair: Keypair,
@@ -42,6 +101,24 @@ pub struct Config {
     pub only_secure_ws_connections: bool,
     pub allow_loopback_addresses: bool,
     pub dht_quorum: NonZeroU8,
+    /// Limits for serving history and state chunks to other peers.
+    pub seeding: SeedingConfig,
+    /// Optional file the known peer contacts are persisted to, such that they can be used to
+    /// reconnect to the network after a restart without having to go through the seed nodes.
+    pub peer_contacts_path: Option<PathBuf>,
+    /// Relay nodes used to stay reachable by other peers if AutoNAT determines that none of our
+    /// addresses are publicly reachable. The addresses must include the peer ID of the relay.
+    pub relays: Vec<Multiaddr>,
+    /// Sentry nodes of a validator. If any are configured, the node connects directly only to
+    /// its sentries and is reachable by other peers only through relay circuits on the sentries,
+    /// such that its own IP address is never published. The addresses must include the peer ID
+    /// of the sentry.
+    pub sentries: Vec<Multiaddr>,
+    /// Peers to relay circuits for, e.g. the validators this node is a sentry of. Reservations of
+    /// other peers are denied. If empty, the node doesn't act as a relay.
+    pub relay_clients: Vec<PeerId>,
+    /// Limits for inbound requests that apply across peers.
+    pub rate_limit_policy: RateLimitPolicy,
 }
 
 impl Config {
@@ -82,7 +159,7 @@ impl Config {
 
         let mut kademlia = kad::Config::new(StreamProtocol::new(DHT_PROTOCOL));
         kademlia.set_kbucket_inserts(kad::BucketInserts::OnConnected);
-        kademlia.set_record_ttl(Some(Duration::from_secs(2 * 60 * 60))); // 2h
+        kademlia.set_record_ttl(Some(DHT_RECORD_TTL));
         kademlia.set_publication_interval(Some(Duration::from_secs(10 * 60))); // 10 min
         kademlia.set_replication_interval(Some(Duration::from_secs(60))); // 1 min
         kademlia.set_provider_record_ttl(Some(Duration::from_secs(60 * 60))); // 1h
@@ -111,6 +188,12 @@ impl Config {
             only_secure_ws_connections,
             allow_loopback_addresses,
             dht_quorum,
+            seeding: SeedingConfig::default(),
+            peer_contacts_path: None,
+            relays: Vec::new(),
+            sentries: Vec::new(),
+            relay_clients: Vec::new(),
+            rate_limit_policy: RateLimitPolicy::default(),
         }
     }
 }
@@ -23,12 +23,16 @@ use libp2p::{
 use nimiq_network_interface::{network::CloseReason, peer_info::Services};
 use nimiq_time::{interval, sleep_until, Instant, Interval, Sleep};
 use nimiq_utils::WakerExt as _;
+use nimiq_validator_network::validator_record::VALIDATOR_RECORD_MAX_ADDRESSES;
 use parking_lot::RwLock;
 use rand::{seq::IteratorRandom, thread_rng};
 use void::Void;
 
 use super::Error;
-use crate::discovery::{handler, peer_contacts::PeerContactBook};
+use crate::{
+    discovery::{handler, peer_contacts::PeerContactBook},
+    utils::{is_address_of_peer, is_relayed_address, peer_id_of_address, relay_peer_id_of_address},
+};
 
 /// Current state of connections and peers for connection limits
 #[derive(Clone, Debug)]
@@ -347,6 +351,14 @@ pub struct Behaviour {
     /// Set of seeds useful when starting to discover other peers.
     seeds: Vec<Multiaddr>,
 
+    /// Sentry nodes per peer ID. If any are configured, these are the only peers we connect to
+    /// directly. All other peers are only reachable through relay circuits.
+    sentries: HashMap<PeerId, Multiaddr>,
+
+    /// Addresses validators published in their validator records, in addition to the ones of
+    /// their peer contacts.
+    validator_addresses: HashMap<PeerId, Vec<Multiaddr>>,
+
     /// The set of services that this peer requires.
     required_services: Services,
 
@@ -380,6 +392,7 @@ impl Behaviour {
         contacts: Arc<RwLock<PeerContactBook>>,
         own_peer_id: PeerId,
         seeds: Vec<Multiaddr>,
+        sentries: Vec<Multiaddr>,
         required_services: Services,
         config: Config,
     ) -> Self {
@@ -389,11 +402,23 @@ impl Behaviour {
             peer_count: 0,
         };
         let housekeeping_timer = interval(config.housekeeping_interval);
+        let sentries = sentries
+            .into_iter()
+            .filter_map(|address| match peer_id_of_address(&address) {
+                Some(peer_id) => Some((peer_id, address)),
+                None => {
+                    warn!(%address, "Ignoring sentry without a peer ID in its address");
+                    None
+                }
+            })
+            .collect();
 
         Self {
             contacts,
             own_peer_id,
             seeds,
+            sentries,
+            validator_addresses: HashMap::new(),
             required_services,
             peer_ids: ConnectionState::new(
                 2,
@@ -446,6 +471,20 @@ impl Behaviour {
             "Maintaining peers"
         );
 
+        // Sentries are always dialed, independent of the number of other connections.
+        if self.active {
+            for (peer_id, address) in self.choose_sentries_to_dial() {
+                debug!(%peer_id, %address, "Dialing sentry");
+                self.peer_ids.mark_dialing(peer_id);
+                self.actions.push_back(ToSwarm::Dial {
+                    opts: DialOpts::peer_id(peer_id)
+                        .addresses(vec![address])
+                        .condition(PeerCondition::Disconnected)
+                        .build(),
+                });
+            }
+        }
+
         // If we are active and have less connections than the desired amount
         // and we are not dialing anyone, it is most likely because we went down
         // (i.e. we are or were offline).
@@ -525,6 +564,11 @@ impl Behaviour {
     }
 
     fn choose_peers_to_dial(&self) -> Vec<PeerId> {
+        // Behind sentries, other peers are only connected to on demand through relay circuits.
+        if self.is_sentry_mode() {
+            return vec![];
+        }
+
         let num_peers = usize::min(
             self.config.desired_peer_count - self.peer_ids.num_connected(true),
             self.config.dialing_count_max - self.peer_ids.num_dialing(),
@@ -580,7 +624,11 @@ impl Behaviour {
     fn choose_seeds_to_dial(&self) -> Vec<Multiaddr> {
         // We prefer to connect to non-seed peers. Thus, we only choose any seeds here if we're
         // not already dialing any peers and at most one seed at a time.
-        if self.peer_ids.num_dialing() > 0 || self.addresses.num_dialing() > 0 {
+        // Behind sentries, the sentries take the role of the seeds.
+        if self.is_sentry_mode()
+            || self.peer_ids.num_dialing() > 0
+            || self.addresses.num_dialing() > 0
+        {
             return vec![];
         }
 
@@ -595,6 +643,54 @@ impl Behaviour {
             .choose_multiple(&mut thread_rng(), num_seeds)
     }
 
+    fn choose_sentries_to_dial(&self) -> Vec<(PeerId, Multiaddr)> {
+        self.sentries
+            .iter()
+            .filter(|(peer_id, _)| self.peer_ids.can_dial(peer_id))
+            .map(|(peer_id, address)| (*peer_id, address.clone()))
+            .collect()
+    }
+
+    /// Returns whether sentries are configured, in which case we only connect directly to them.
+    fn is_sentry_mode(&self) -> bool {
+        !self.sentries.is_empty()
+    }
+
+    /// Returns whether we may connect to a peer directly instead of through a relay circuit.
+    /// Behind sentries, this is only allowed for the sentries and the relays other validators
+    /// are reachable through according to their verified records, since those are the only way
+    /// to reach validators that are themselves behind sentries.
+    fn may_connect_directly(&self, peer_id: &PeerId) -> bool {
+        !self.is_sentry_mode()
+            || self.sentries.contains_key(peer_id)
+            || self
+                .validator_addresses
+                .values()
+                .flatten()
+                .any(|address| relay_peer_id_of_address(address).as_ref() == Some(peer_id))
+    }
+
+    /// Sets the addresses a validator published in its validator record, such that the
+    /// validator can be dialed even if we don't have its peer contact. Since the relays in these
+    /// addresses may be connected to directly behind sentries, they must only be taken from
+    /// records that passed verification. Addresses that can't be addresses of the validator
+    /// are ignored.
+    pub fn set_validator_addresses(&mut self, peer_id: PeerId, mut addresses: Vec<Multiaddr>) {
+        if peer_id == self.own_peer_id {
+            return;
+        }
+        addresses.retain(|address| {
+            is_address_of_peer(address, &peer_id)
+                && relay_peer_id_of_address(address) != Some(self.own_peer_id)
+        });
+        addresses.truncate(VALIDATOR_RECORD_MAX_ADDRESSES);
+        if addresses.is_empty() {
+            self.validator_addresses.remove(&peer_id);
+        } else {
+            self.validator_addresses.insert(peer_id, addresses);
+        }
+    }
+
     fn housekeeping(&mut self) {
         trace!("Doing housekeeping in connection pool");
 
@@ -943,11 +1039,26 @@ impl NetworkBehaviour for Behaviour {
             Some(peer) => peer,
         };
 
-        Ok(self
+        let mut addresses = self
             .contacts
             .read()
             .get_addresses(&peer_id)
-            .unwrap_or_default())
+            .unwrap_or_default();
+        if let Some(validator_addresses) = self.validator_addresses.get(&peer_id) {
+            for address in validator_addresses {
+                if !addresses.contains(address) {
+                    addresses.push(address.clone());
+                }
+            }
+        }
+
+        // Don't reveal our own address by dialing peers directly that we may only reach through
+        // relay circuits.
+        if !self.may_connect_directly(&peer_id) {
+            addresses.retain(is_relayed_address);
+        }
+
+        Ok(addresses)
     }
 
     fn handle_pending_inbound_connection(
@@ -992,11 +1103,15 @@ impl NetworkBehaviour for Behaviour {
     fn handle_established_outbound_connection(
         &mut self,
         _connection_id: ConnectionId,
-        _peer: PeerId,
+        peer: PeerId,
         addr: &Multiaddr,
         _role_override: Endpoint,
         _port_use: PortUse,
     ) -> Result<THandler<Self>, ConnectionDenied> {
+        if !is_relayed_address(addr) && !self.may_connect_directly(&peer) {
+            debug!(peer_id = %peer, address = %addr, "Direct connection not allowed behind sentries");
+            return Err(ConnectionDenied::new(Error::DirectConnectionNotAllowed));
+        }
         if let Some(ip_info) = self.get_ip_info_from_multiaddr(addr) {
             self.reached_ip_limit(&ip_info)?;
         }
@@ -24,4 +24,8 @@ pub enum Error {
     ///Maximum peers connections per IP has been reached
     #[error("Maximum peers connections per IP has been reached")]
     MaxPeerPerIPConnectionsReached,
+
+    /// Direct connections are only allowed to the sentries when running behind sentries
+    #[error("Direct connection to a peer that is not a sentry")]
+    DirectConnectionNotAllowed,
 }
@@ -2,6 +2,7 @@ use libp2p::{kad::Record, PeerId};
 use nimiq_keys::Address;
 use nimiq_network_interface::network::Network as NetworkInterface;
 use nimiq_serde::DeserializeError;
+use nimiq_utils::tagged_signing::TaggedVerificationError;
 use nimiq_validator_network::validator_record::ValidatorRecord;
 
 pub use crate::network_types::DhtRecord;
@@ -21,7 +22,16 @@ pub enum DhtVerifierError {
         <Network as NetworkInterface>::PeerId,
     ),
     StateIncomplete,
+    SigningKeyUnavailable(Address),
     InvalidSignature,
+    StaleRecord(TaggedVerificationError),
+    /// The record was signed for an epoch that is too old or in the future. Contains the epoch of
+    /// the record and the current epoch.
+    InvalidEpoch(u32, u32),
+    /// The record contains more addresses than allowed.
+    TooManyAddresses(usize),
+    /// The record was rejected by the handler registered for its type.
+    Rejected(String),
 }
 
 pub trait Verifier: Send + Sync {
@@ -34,7 +44,7 @@ impl Verifier for () {
         let peer_id = PeerId::random();
         Ok(DhtRecord::Validator(
             peer_id,
-            ValidatorRecord::<PeerId>::new(peer_id, Address::default(), 0u64),
+            ValidatorRecord::<PeerId>::new(peer_id, Address::default(), 0, 0u64, vec![]),
             record.clone(),
         ))
     }
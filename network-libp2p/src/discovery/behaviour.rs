@@ -149,6 +149,13 @@ impl Behaviour {
             .add_own_addresses(addresses, &self.keypair)
     }
 
+    /// Removes addresses from our own contact within the peer contact book
+    pub fn remove_own_addresses(&self, addresses: Vec<Multiaddr>) {
+        self.peer_contact_book
+            .write()
+            .remove_own_addresses(addresses, &self.keypair)
+    }
+
     /// Returns whether an address in `Multiaddr` format is a dialable websocket address
     pub fn is_address_dialable(&self, address: &Multiaddr) -> bool {
         self.peer_contact_book.read().is_address_dialable(address)
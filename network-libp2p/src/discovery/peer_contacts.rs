@@ -1,3 +1,5 @@
+#[cfg(not(target_family = "wasm"))]
+use std::path::Path;
 use std::{
     collections::{HashMap, HashSet},
     sync::Arc,
@@ -16,13 +18,15 @@ use nimiq_network_interface::{
     network::Network as NetworkInterface,
     peer_info::{PeerInfo, Services},
 };
+#[cfg(not(target_family = "wasm"))]
+use nimiq_utils::file_store::{Error as FileStoreError, FileStore};
 use nimiq_utils::tagged_signing::{TaggedKeyPair, TaggedSignable, TaggedSignature};
 use nimiq_validator_network::validator_record::ValidatorRecord;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
-use crate::{utils, Network};
+use crate::{latency::PeerLatency, utils, Network};
 
 #[derive(Debug, Error)]
 pub enum PeerContactError {
@@ -211,6 +215,13 @@ struct PeerContactMeta {
     score: f64,
 }
 
+/// A signed peer contact together with the score we assigned to the peer, as it is persisted to disk.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct PersistedPeerContact {
+    contact: SignedPeerContact,
+    score: f64,
+}
+
 /// This encapsulates a peer contact (signed), but also pre-computes frequently used values such as `peer_id` and
 /// `protocols`. It also contains meta-data that can be mutated.
 #[derive(Debug)]
@@ -335,6 +346,9 @@ impl PeerContactBook {
     /// If a peer's age exceeds this value in seconds, it is removed (30 minutes)
     pub const MAX_PEER_AGE: u64 = 30 * 60;
 
+    /// If a persisted peer's age exceeds this value in seconds, it is not loaded (7 days)
+    pub const MAX_PERSISTED_PEER_AGE: u64 = 7 * 24 * 60 * 60;
+
     /// Creates a new `PeerContactBook` given our own peer contact information.
     pub fn new(
         own_peer_contact: SignedPeerContact,
@@ -522,13 +536,21 @@ impl PeerContactBook {
     }
 
     /// Updates the score of every peer in the contact book with the gossipsub
-    /// peer score.
-    pub fn update_scores(&self, gossipsub: &gossipsub::Behaviour) {
+    /// peer score, penalized by the latency measured to the peer.
+    pub fn update_scores(
+        &self,
+        gossipsub: &gossipsub::Behaviour,
+        latencies: &HashMap<PeerId, PeerLatency>,
+    ) {
         let contacts = self.peer_contacts.iter();
 
         for contact in contacts {
             if let Some(score) = gossipsub.peer_score(contact.0) {
-                contact.1.set_score(score);
+                let penalty = latencies
+                    .get(contact.0)
+                    .map(PeerLatency::score_penalty)
+                    .unwrap_or(0.0);
+                contact.1.set_score(score - penalty);
             } else {
                 debug!(peer_id = %contact.0, "No score for peer");
             }
@@ -603,6 +625,64 @@ impl PeerContactBook {
         }
     }
 
+    /// Writes the contacts of all known peers together with their scores to the given file.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn save_to_file(&self, path: &Path) -> Result<(), FileStoreError> {
+        let contacts: Vec<PersistedPeerContact> = self
+            .peer_contacts
+            .values()
+            .map(|info| PersistedPeerContact {
+                contact: info.contact.clone(),
+                score: info.get_score(),
+            })
+            .collect();
+        FileStore::new(path).store(&contacts)
+    }
+
+    /// Loads the peer contacts previously written by [`Self::save_to_file`] from the given file
+    /// and returns the number of contacts that were added to the contact book.
+    ///
+    /// Contacts with an invalid signature or exceeding `MAX_PERSISTED_PEER_AGE` are skipped, as
+    /// are contacts of peers we already know about. A missing file is not considered an error.
+    /// Note that loaded contacts are still subject to the regular house keeping. They are meant to
+    /// be dialed right away at startup, after which the peers will provide us with fresh contacts.
+    #[cfg(not(target_family = "wasm"))]
+    pub fn load_from_file(&mut self, path: &Path) -> Result<usize, FileStoreError> {
+        let contacts: Vec<PersistedPeerContact> = match FileStore::new(path).load() {
+            Err(FileStoreError::Io(error)) if error.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(0)
+            }
+            result => result?,
+        };
+
+        let unix_time = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap();
+        let max_age = Duration::from_secs(Self::MAX_PERSISTED_PEER_AGE);
+
+        let mut num_loaded = 0;
+        for persisted in contacts {
+            if persisted.contact.peer_id() == self.own_peer_id || !persisted.contact.verify() {
+                continue;
+            }
+
+            let info = PeerContactInfo::from(persisted.contact);
+            if info.exceeds_age(max_age, unix_time) {
+                continue;
+            }
+            info.set_score(persisted.score);
+
+            if let std::collections::hash_map::Entry::Vacant(entry) =
+                self.peer_contacts.entry(info.peer_id)
+            {
+                entry.insert(Arc::new(info));
+                num_loaded += 1;
+            }
+        }
+
+        Ok(num_loaded)
+    }
+
     /// Returns true if an address is valid for dialing.
     /// It performs basic checks against unsupported addresses.
     pub fn is_address_dialable(&self, address: &Multiaddr) -> bool {
@@ -610,6 +690,17 @@ impl PeerContactBook {
         if self.memory_transport {
             return true;
         }
+        // Addresses on a relay are dialable if the address of the relay is
+        if address
+            .iter()
+            .any(|protocol| protocol == Protocol::P2pCircuit)
+        {
+            let relay_address: Multiaddr = address
+                .iter()
+                .take_while(|protocol| *protocol != Protocol::P2pCircuit)
+                .collect();
+            return self.is_address_dialable(&relay_address);
+        }
         // Otherwise check for an appropriate WS address
         let mut protocols = address.iter();
         let mut ip = protocols.next();
@@ -8,7 +8,10 @@
 
 use std::{io, mem};
 
-use futures::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use futures::{
+    future::{AbortRegistration, Abortable},
+    AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt,
+};
 use libp2p::{request_response, StreamProtocol};
 use nimiq_network_interface::network;
 
@@ -18,16 +21,37 @@ const U64_LENGTH: usize = mem::size_of::<u64>();
 const MAX_REQUEST_SIZE: u64 = network::MIN_SUPPORTED_REQ_SIZE as u64 + U64_LENGTH as u64;
 const MAX_RESPONSE_SIZE: u64 = network::MIN_SUPPORTED_RESP_SIZE as u64 + U64_LENGTH as u64;
 
-#[derive(Default, Debug, Clone)]
-pub struct MessageCodec;
+#[derive(Default, Debug)]
+pub struct MessageCodec {
+    /// The abort registration of the outbound request this codec instance is handling.
+    /// libp2p clones the codec for every outbound stream and uses the clone for writing the
+    /// request and reading its response.
+    abort_registration: Option<AbortRegistration>,
+}
+
+impl Clone for MessageCodec {
+    fn clone(&self) -> Self {
+        // The registration belongs to a single request and isn't shared with other streams.
+        Self::default()
+    }
+}
 
 pub type IncomingRequest = Vec<u8>;
 pub type OutgoingResponse = Vec<u8>;
 
+/// A request as handled by the codec. Only the data is sent to the peer.
+#[derive(Debug)]
+pub struct CodecRequest {
+    pub data: IncomingRequest,
+    /// Aborting an outbound request closes its stream while its response is being read.
+    /// Inbound requests don't have a registration.
+    pub abort_registration: Option<AbortRegistration>,
+}
+
 #[async_trait::async_trait]
 impl request_response::Codec for MessageCodec {
     type Protocol = StreamProtocol;
-    type Request = Option<IncomingRequest>;
+    type Request = Option<CodecRequest>;
     type Response = Option<OutgoingResponse>;
 
     async fn read_request<T>(&mut self, _: &Self::Protocol, io: &mut T) -> io::Result<Self::Request>
@@ -55,7 +79,10 @@ impl request_response::Codec for MessageCodec {
         if vec.len() - U64_LENGTH >= len {
             // Skip the length header we already read
             vec.drain(..U64_LENGTH);
-            Ok(Some(vec))
+            Ok(Some(CodecRequest {
+                data: vec,
+                abort_registration: None,
+            }))
         } else {
             Ok(None)
         }
@@ -70,7 +97,14 @@ impl request_response::Codec for MessageCodec {
         T: AsyncRead + Unpin + Send,
     {
         let mut vec = Vec::new();
-        io.take(MAX_RESPONSE_SIZE).read_to_end(&mut vec).await?;
+        let read = io.take(MAX_RESPONSE_SIZE).read_to_end(&mut vec);
+        match self.abort_registration.take() {
+            // Failing here makes libp2p drop the stream, which closes it on the wire.
+            Some(abort_registration) => Abortable::new(read, abort_registration)
+                .await
+                .map_err(|_| io::Error::new(io::ErrorKind::Interrupted, "Request aborted"))??,
+            None => read.await?,
+        };
         if vec.len() < U64_LENGTH {
             return Ok(None);
         }
@@ -105,7 +139,9 @@ impl request_response::Codec for MessageCodec {
     where
         T: AsyncWrite + Send + Unpin,
     {
-        let src = req.expect("No data to write");
+        let req = req.expect("No data to write");
+        self.abort_registration = req.abort_registration;
+        let src = req.data;
         io.write_all(&(src.len() as u64).to_be_bytes()).await?;
         io.write_all(&src).await?;
         Ok(())
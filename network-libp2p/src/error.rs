@@ -28,6 +28,15 @@ pub enum NetworkError {
     #[error("DHT PutRecord error: {0:?}")]
     DhtPutRecord(libp2p::kad::PutRecordError),
 
+    #[error("DHT AddProvider error: {0:?}")]
+    DhtAddProvider(libp2p::kad::AddProviderError),
+
+    #[error("DHT GetProviders error: {0:?}")]
+    DhtGetProviders(libp2p::kad::GetProvidersError),
+
+    #[error("The DHT is not supported by this build")]
+    DhtDisabled,
+
     #[error("Gossipsub Publish error: {0:?}")]
     GossipsubPublish(libp2p::gossipsub::PublishError),
 
@@ -53,6 +62,18 @@ pub enum NetworkError {
 
     #[error("Peer contact error: {0}")]
     PeerContactError(#[from] PeerContactError),
+
+    #[error("Failed to listen on address: {0}")]
+    Listen(#[from] libp2p::TransportError<std::io::Error>),
+
+    #[error("Not listening on address: {0}")]
+    UnknownListenAddress(libp2p::Multiaddr),
+
+    #[error("Invalid TLS configuration: {0}")]
+    InvalidTlsConfig(String),
+
+    #[error("TLS is not supported by the transport")]
+    TlsNotSupported,
 }
 
 impl<T> From<tokio::sync::mpsc::error::SendError<T>> for NetworkError {
@@ -85,6 +106,18 @@ impl From<libp2p::kad::PutRecordError> for NetworkError {
     }
 }
 
+impl From<libp2p::kad::AddProviderError> for NetworkError {
+    fn from(e: libp2p::kad::AddProviderError) -> Self {
+        Self::DhtAddProvider(e)
+    }
+}
+
+impl From<libp2p::kad::GetProvidersError> for NetworkError {
+    fn from(e: libp2p::kad::GetProvidersError) -> Self {
+        Self::DhtGetProviders(e)
+    }
+}
+
 impl From<libp2p::gossipsub::PublishError> for NetworkError {
     fn from(e: libp2p::gossipsub::PublishError) -> Self {
         Self::GossipsubPublish(e)
@@ -0,0 +1,125 @@
+use std::{collections::HashMap, time::Duration};
+
+use libp2p::PeerId;
+use nimiq_network_interface::request::{RequestCommon, RequestMarker};
+use nimiq_serde::{Deserialize, Serialize};
+use parking_lot::RwLock;
+
+/// Interval in which the latency to every connected peer is measured.
+pub(crate) const LATENCY_MEASUREMENT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Penalty applied to the score of a peer per second of average round trip time.
+const LATENCY_SCORE_PENALTY: f64 = 1.0;
+
+/// Weight of a new sample in the moving average of the round trip time.
+const LATENCY_AVERAGE_WEIGHT: f64 = 0.2;
+
+/// Request that is answered by the network layer of the remote peer with the given nonce.
+///
+/// Unlike the libp2p ping, it goes through the full request-response path including the codec,
+/// rate limiting and the request dispatching, such that the measured round trip time reflects the
+/// latency experienced by the requests of the application.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) struct EchoRequest {
+    pub(crate) nonce: u64,
+}
+
+impl RequestCommon for EchoRequest {
+    type Kind = RequestMarker;
+    const TYPE_ID: u16 = 100;
+    type Response = u64;
+    const MAX_REQUESTS: u32 = 10;
+}
+
+/// Round trip times of echo requests measured to a peer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PeerLatency {
+    /// The round trip time of the latest echo request.
+    pub last: Duration,
+    /// The exponential moving average of the round trip times.
+    pub average: Duration,
+    /// The number of echo requests answered by the peer.
+    pub samples: u32,
+}
+
+impl PeerLatency {
+    fn new(rtt: Duration) -> Self {
+        Self {
+            last: rtt,
+            average: rtt,
+            samples: 1,
+        }
+    }
+
+    fn update(&mut self, rtt: Duration) {
+        self.last = rtt;
+        self.average = self
+            .average
+            .mul_f64(1.0 - LATENCY_AVERAGE_WEIGHT)
+            .saturating_add(rtt.mul_f64(LATENCY_AVERAGE_WEIGHT));
+        self.samples = self.samples.saturating_add(1);
+    }
+
+    /// The penalty this latency contributes to the score of the peer.
+    pub(crate) fn score_penalty(&self) -> f64 {
+        self.average.as_secs_f64() * LATENCY_SCORE_PENALTY
+    }
+}
+
+/// Keeps track of the latencies measured to the connected peers.
+#[derive(Default)]
+pub(crate) struct LatencyTracker {
+    peers: RwLock<HashMap<PeerId, PeerLatency>>,
+}
+
+impl LatencyTracker {
+    /// Records the round trip time of an echo request answered by the given peer.
+    pub(crate) fn record(&self, peer_id: PeerId, rtt: Duration) {
+        self.peers
+            .write()
+            .entry(peer_id)
+            .and_modify(|latency| latency.update(rtt))
+            .or_insert_with(|| PeerLatency::new(rtt));
+    }
+
+    /// Returns the latency measured to the given peer, if any.
+    pub(crate) fn get(&self, peer_id: &PeerId) -> Option<PeerLatency> {
+        self.peers.read().get(peer_id).copied()
+    }
+
+    /// Returns the latencies measured to all peers.
+    pub(crate) fn all(&self) -> HashMap<PeerId, PeerLatency> {
+        self.peers.read().clone()
+    }
+
+    /// Forgets the latencies of all peers for which `keep` returns false.
+    pub(crate) fn retain<F: FnMut(&PeerId) -> bool>(&self, mut keep: F) {
+        self.peers.write().retain(|peer_id, _| keep(peer_id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use libp2p::PeerId;
+
+    use super::LatencyTracker;
+
+    #[test]
+    fn it_averages_round_trip_times() {
+        let tracker = LatencyTracker::default();
+        let peer_id = PeerId::random();
+
+        tracker.record(peer_id, Duration::from_millis(100));
+        tracker.record(peer_id, Duration::from_millis(200));
+
+        let latency = tracker.get(&peer_id).unwrap();
+        assert_eq!(latency.last, Duration::from_millis(200));
+        assert!((latency.average.as_secs_f64() - 0.12).abs() < 1e-6);
+        assert_eq!(latency.samples, 2);
+
+        tracker.retain(|_| false);
+        assert!(tracker.get(&peer_id).is_none());
+    }
+}
@@ -9,13 +9,17 @@ pub mod dht;
 pub mod discovery;
 pub mod dispatch;
 mod error;
+mod latency;
 mod network;
 #[cfg(feature = "metrics")]
 mod network_metrics;
 mod network_types;
 mod only_secure_ws_transport;
+mod priority;
 mod rate_limiting;
+mod seeding;
 mod swarm;
+mod tls_reload_transport;
 mod utils;
 
 pub const DISCOVERY_PROTOCOL: &str = "/nimiq/discovery/0.0.1";
@@ -23,15 +27,21 @@ pub const DHT_PROTOCOL: &str = "/nimiq/kad/0.0.1";
 pub const AUTONAT_DIAL_REQUEST_PROTOCOL: &str = "/libp2p/autonat/2/dial-request";
 pub const AUTONAT_DIAL_BACK_PROTOCOL: &str = "/libp2p/autonat/2/dial-back";
 
+/// Time after which records expire in the DHT unless they are republished.
+pub const DHT_RECORD_TTL: std::time::Duration = std::time::Duration::from_secs(2 * 60 * 60); // 2h
+
 pub use config::{Config, TlsConfig};
 pub use error::NetworkError;
+pub use latency::PeerLatency;
 pub use libp2p::{
     self,
     identity::{ed25519::Keypair as Ed25519KeyPair, Keypair},
     swarm::NetworkInfo,
     PeerId,
 };
-pub use network::Network;
+pub use network::{Network, RequestAbortHandle};
+pub use rate_limiting::RateLimitPolicy;
+pub use seeding::SeedingConfig;
 use serde::{
     de::Error, ser::Error as SerializationError, Deserialize, Deserializer, Serialize, Serializer,
 };
@@ -6,17 +6,28 @@ use std::{
     task::{Context, Poll},
     time::Duration,
 };
+#[cfg(not(target_family = "wasm"))]
+use std::{path::PathBuf, sync::Weak};
 
 use async_trait::async_trait;
 use bytes::Bytes;
-use futures::{future::BoxFuture, ready, stream::BoxStream, Stream, StreamExt};
+use futures::{
+    future::{join_all, AbortHandle, AbortRegistration, BoxFuture},
+    ready,
+    stream::BoxStream,
+    FutureExt, Stream, StreamExt,
+};
+use instant::Instant;
 use libp2p::{
-    gossipsub, request_response::InboundRequestId, swarm::NetworkInfo, Multiaddr, PeerId, Swarm,
+    gossipsub,
+    request_response::{InboundRequestId, OutboundRequestId},
+    swarm::NetworkInfo,
+    Multiaddr, PeerId, Swarm,
 };
 use nimiq_network_interface::{
     network::{
-        CloseReason, MsgAcceptance, Network as NetworkInterface, NetworkEvent, SubscribeEvents,
-        Topic,
+        CloseReason, MsgAcceptance, Network as NetworkInterface, NetworkEvent, Priority,
+        SubscribeEvents, Topic,
     },
     peer_info::{PeerInfo, Services},
     request::{
@@ -39,14 +50,20 @@ use crate::network_metrics::NetworkMetrics;
 use crate::{
     dht,
     discovery::peer_contacts::PeerContactBook,
+    latency::{EchoRequest, LatencyTracker, PeerLatency, LATENCY_MEASUREMENT_INTERVAL},
     network_types::{GossipsubId, NetworkAction, ValidateMessage},
     rate_limiting::RateLimitConfig,
+    seeding::SeedingScheduler,
     swarm::{new_swarm, swarm_task},
-    Config, NetworkError,
+    Config, NetworkError, TlsConfig,
 };
 
 const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
 
+/// Interval in which the known peer contacts are written to disk, if configured.
+#[cfg(not(target_family = "wasm"))]
+const PEER_CONTACTS_PERSIST_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
 pub struct Network {
     /// The local ID that is used to identify our peer
     local_peer_id: PeerId,
@@ -58,6 +75,9 @@ pub struct Network {
     events_tx: broadcast::Sender<NetworkEvent<PeerId>>,
     /// Stream used to send action messages
     action_tx: mpsc::Sender<NetworkAction>,
+    /// Stream used to send action messages of consensus critical traffic, which the swarm task
+    /// performs ahead of the ones sent through `action_tx`.
+    priority_action_tx: mpsc::Sender<NetworkAction>,
     /// Stream used to send validation messages
     validate_tx: mpsc::UnboundedSender<ValidateMessage<PeerId>>,
     /// Metrics used for data analysis
@@ -67,6 +87,10 @@ pub struct Network {
     required_services: Services,
     /// Reference to PeerContactBook, used to satisfy rpc requests for it.
     contacts: Arc<RwLock<PeerContactBook>>,
+    /// Scheduler throttling the requests for history and state chunks we serve to other peers.
+    seeding: Arc<SeedingScheduler>,
+    /// Latencies measured to the connected peers using echo requests.
+    latencies: Arc<LatencyTracker>,
 }
 
 impl Network {
@@ -90,17 +114,35 @@ impl Network {
             config.allow_loopback_addresses,
             config.memory_transport,
         )));
+        #[cfg(not(target_family = "wasm"))]
+        let peer_contacts_path = config.peer_contacts_path.clone();
+        #[cfg(not(target_family = "wasm"))]
+        if let Some(path) = &peer_contacts_path {
+            match contacts.write().load_from_file(path) {
+                Ok(num_loaded) => {
+                    debug!(num_loaded, path = %path.display(), "Loaded persisted peer contacts")
+                }
+                Err(error) => {
+                    warn!(%error, path = %path.display(), "Failed to load persisted peer contacts")
+                }
+            }
+        }
         let params = gossipsub::PeerScoreParams {
             ip_colocation_factor_threshold: 20.0,
             ..Default::default()
         };
         let dht_quorum = config.dht_quorum;
+        let seeding = Arc::new(SeedingScheduler::new(&config.seeding));
+        spawn(Arc::clone(&seeding).run());
         // Only force the server mode if we are doing a memory transport.
         // Otherwise expect the regular flow: DHT will get in server mode once a confirmed address is obtained using Autonat.
         // In memory transport we don't have a mechanism that sets the DHT in server mode such as confirming an address
         // with Autonat. This is because Autonat v1 only works with IP addresses.
         let force_dht_server_mode = config.memory_transport;
-        let swarm = new_swarm(
+        let relays = config.relays.clone();
+        let sentries = config.sentries.clone();
+        let rate_limit_policy = config.rate_limit_policy.clone();
+        let (swarm, tls_handle) = new_swarm(
             config,
             Arc::clone(&contacts),
             params.clone(),
@@ -109,12 +151,15 @@ impl Network {
 
         let local_peer_id = *Swarm::local_peer_id(&swarm);
         let connected_peers = Arc::new(RwLock::new(HashMap::new()));
+        let latencies = Arc::new(LatencyTracker::default());
 
         let events_tx = broadcast::Sender::new(64);
         let (action_tx, action_rx) = mpsc::channel(64);
+        let (priority_action_tx, priority_action_rx) = mpsc::channel(64);
         let (validate_tx, validate_rx) = mpsc::unbounded_channel();
 
-        let update_scores = interval(params.decay_interval);
+        // Spread the score updates of different nodes over time.
+        let update_scores = interval(params.decay_interval).with_jitter(0.1);
 
         #[cfg(feature = "metrics")]
         let metrics = Arc::new(NetworkMetrics::default());
@@ -123,29 +168,52 @@ impl Network {
             swarm,
             events_tx.clone(),
             action_rx,
+            priority_action_rx,
             validate_rx,
             Arc::clone(&connected_peers),
             update_scores,
             Arc::clone(&contacts),
+            Arc::clone(&latencies),
             #[cfg(feature = "kad")]
             dht_verifier,
             force_dht_server_mode,
             dht_quorum,
+            tls_handle,
+            relays,
+            sentries,
+            rate_limit_policy,
             #[cfg(feature = "metrics")]
             metrics.clone(),
         )));
 
-        Self {
+        #[cfg(not(target_family = "wasm"))]
+        if let Some(path) = peer_contacts_path {
+            spawn(persist_peer_contacts(Arc::downgrade(&contacts), path));
+        }
+
+        spawn(measure_latencies(
+            action_tx.downgrade(),
+            Arc::clone(&connected_peers),
+            Arc::clone(&latencies),
+        ));
+
+        let network = Self {
             contacts,
             local_peer_id,
             connected_peers,
             events_tx,
             action_tx,
+            priority_action_tx,
             validate_tx,
             #[cfg(feature = "metrics")]
             metrics,
             required_services,
-        }
+            seeding,
+            latencies,
+        };
+        spawn(network.answer_echo_requests());
+
+        network
     }
 
     pub fn local_peer_id(&self) -> &PeerId {
@@ -182,6 +250,61 @@ impl Network {
         }
     }
 
+    /// Starts listening on additional addresses while the network is running. Addresses we
+    /// already listen on are ignored.
+    /// The new addresses are advertised to other peers once the listeners are established.
+    pub async fn add_listen_addresses(
+        &self,
+        listen_addresses: Vec<Multiaddr>,
+    ) -> Result<(), NetworkError> {
+        let (output_tx, output_rx) = oneshot::channel();
+
+        self.action_tx
+            .clone()
+            .send(NetworkAction::AddListenAddresses {
+                listen_addresses,
+                output: output_tx,
+            })
+            .await?;
+        output_rx.await?
+    }
+
+    /// Stops listening on the given addresses, which must have been passed to `listen_on` or
+    /// `add_listen_addresses` before. Established connections are kept open.
+    /// The addresses are no longer advertised to other peers and no longer considered for the
+    /// NAT status.
+    pub async fn remove_listen_addresses(
+        &self,
+        listen_addresses: Vec<Multiaddr>,
+    ) -> Result<(), NetworkError> {
+        let (output_tx, output_rx) = oneshot::channel();
+
+        self.action_tx
+            .clone()
+            .send(NetworkAction::RemoveListenAddresses {
+                listen_addresses,
+                output: output_tx,
+            })
+            .await?;
+        output_rx.await?
+    }
+
+    /// Replaces the TLS configuration used for secure WebSockets, e.g. to rotate certificates.
+    /// Connections established afterwards use the new configuration, while listeners and
+    /// established connections are kept.
+    pub async fn update_tls_config(&self, tls: TlsConfig) -> Result<(), NetworkError> {
+        let (output_tx, output_rx) = oneshot::channel();
+
+        self.action_tx
+            .clone()
+            .send(NetworkAction::UpdateTlsConfig {
+                tls,
+                output: output_tx,
+            })
+            .await?;
+        output_rx.await?
+    }
+
     /// Tells the network to start connecting to any available peer or seed
     /// until meeting the configured number of desired peer connections.
     /// If there are no dial attempts being made and no connections to any
@@ -199,92 +322,77 @@ impl Network {
         }
     }
 
-    async fn request_impl<Req: RequestCommon>(
-        &self,
-        request: Req,
-        peer_id: PeerId,
-    ) -> Result<Req::Response, RequestError> {
-        let (output_tx, output_rx) = oneshot::channel();
-        let (response_tx, response_rx) = oneshot::channel();
-
-        let action = NetworkAction::SendRequest {
-            peer_id,
-            request: request.serialize_request()[..].into(),
-            response_channel: response_tx,
-            output: output_tx,
-        };
-
-        if self.action_tx.clone().send(action).await.is_err() {
-            return Err(OutboundRequestError::SendError.into());
-        }
-
-        let Ok(request_id) = output_rx.await else {
-            return Err(OutboundRequestError::SendError.into());
-        };
-
-        trace!(
-            r#type = Req::type_name::<Req>(),
-            %request_id,
-            %peer_id,
-            "Request sent",
-        );
-
-        let Ok(result) = timeout(REQUEST_TIMEOUT, response_rx).await else {
-            debug!(
-                r#type = Req::type_name::<Req>(),
-                %request_id,
-                %peer_id,
-                "Request timed out with no response from libp2p"
-            );
-            return Err(OutboundRequestError::Timeout.into());
-        };
+    /// Returns the latency measured to the given peer, if any.
+    pub fn peer_latency(&self, peer_id: &PeerId) -> Option<PeerLatency> {
+        self.latencies.get(peer_id)
+    }
 
-        let Ok(result) = result else {
-            debug!(
-                r#type = Req::type_name::<Req>(),
-                %request_id,
-                %peer_id,
-                "Request failed - sender future dropped"
-            );
-            return Err(OutboundRequestError::SenderFutureDropped.into());
-        };
+    /// Returns the latencies measured to all connected peers.
+    pub fn peer_latencies(&self) -> HashMap<PeerId, PeerLatency> {
+        self.latencies.all()
+    }
 
-        let data = result?;
-
-        let result = <Result<Req::Response, InboundRequestError>>::deserialize_take(&data);
-        let Ok((message, left_over)) = result else {
-            debug!(
-                r#type = Req::type_name::<Req>(),
-                %request_id,
-                %peer_id,
-                "Failed to deserialize response",
-            );
-            return Err(InboundRequestError::DeSerializationError.into());
-        };
+    /// Answers the echo requests of other peers, which they use to measure their latency to us.
+    fn answer_echo_requests(&self) -> impl Future<Output = ()> + Send + 'static {
+        let mut requests = self.receive_requests_impl::<EchoRequest>();
+        // Only keep a weak sender to not keep the swarm task alive.
+        let action_tx = self.action_tx.downgrade();
 
-        if !left_over.is_empty() {
-            debug!(
-                r#type = Req::type_name::<Req>(),
-                %request_id,
-                %peer_id,
-                unread_data_len = left_over.len(),
-                "Unexpected content size deserializing response",
-            );
+        async move {
+            while let Some((request, request_id, peer_id)) = requests.next().await {
+                let Some(action_tx) = action_tx.upgrade() else {
+                    break;
+                };
+                if let Err(error) =
+                    send_response::<EchoRequest>(&action_tx, request_id, request.nonce).await
+                {
+                    trace!(%peer_id, %error, "Failed to answer echo request");
+                }
+            }
         }
+    }
 
-        // Check if there was an actual response from the application or a default response from
-        // the network. If the network replied with the default response, it was because there wasn't a
-        // receiver for the request.
-        if message.is_ok() {
-            trace!(
-                r#type = Req::type_name::<Req>(),
-                %request_id,
-                %peer_id,
-                "Response received",
-            );
+    /// Returns the action channel for traffic of the given priority.
+    fn action_tx(&self, priority: Priority) -> &mpsc::Sender<NetworkAction> {
+        match priority {
+            Priority::Consensus => &self.priority_action_tx,
+            Priority::Background => &self.action_tx,
         }
+    }
 
-        message.map_err(Into::into)
+    async fn request_impl<Req: RequestCommon>(
+        &self,
+        request: Req,
+        peer_id: PeerId,
+    ) -> Result<Req::Response, RequestError> {
+        send_request(self.action_tx(Req::PRIORITY), request, peer_id).await
+    }
+
+    /// Sends a request to a peer like [`NetworkInterface::request`], but additionally returns a
+    /// handle to abort the request while it is in flight. Dropping the returned future aborts
+    /// the request as well.
+    pub fn request_with_abort_handle<Req: Request>(
+        &self,
+        request: Req,
+        peer_id: PeerId,
+    ) -> (
+        BoxFuture<'static, Result<Req::Response, RequestError>>,
+        RequestAbortHandle,
+    ) {
+        let (abort_handle, abort_registration) = AbortHandle::new_pair();
+        let action_tx = self.action_tx(Req::PRIORITY).clone();
+        let handle = RequestAbortHandle(abort_handle.clone());
+        let request = async move {
+            send_abortable_request(
+                &action_tx,
+                request,
+                peer_id,
+                abort_handle,
+                abort_registration,
+            )
+            .await
+        };
+        (request.boxed(), handle)
     }
 
     fn receive_requests_impl<Req: RequestCommon>(
@@ -319,15 +427,26 @@ impl Network {
         }
 
         let action_tx = self.action_tx.clone();
+        let seeding = Arc::clone(&self.seeding);
         ReceiveStream::WaitingForRegister(Box::pin(async move {
             // TODO Make buffer size configurable
             let (tx, rx) = mpsc::channel(1024);
 
+            // Seeding requests pass through the scheduler before they are handed out.
+            let output = if Req::SEEDING {
+                let (seeding_tx, seeding_rx) = mpsc::channel(1024);
+                spawn(seeding.forward(seeding_rx, tx, action_tx.downgrade()));
+                seeding_tx
+            } else {
+                tx
+            };
+
             action_tx
                 .send(NetworkAction::ReceiveRequests {
                     type_id: RequestType::from_request::<Req>(),
-                    output: tx,
+                    output,
                     rate_limit_config: RateLimitConfig::from_request::<Req>(),
+                    priority: Req::PRIORITY,
                 })
                 .await
                 .expect("Sending action to network task failed.");
@@ -440,8 +559,7 @@ impl Network {
     {
         let (output_tx, output_rx) = oneshot::channel();
 
-        self.action_tx
-            .clone()
+        self.action_tx(T::PRIORITY)
             .send(NetworkAction::Publish {
                 topic_name,
                 data: item.serialize_to_vec(),
@@ -654,7 +772,20 @@ impl NetworkInterface for Network {
     {
         // Sign the record before transmitting it to the swarm
         let signature = keypair.tagged_sign(v);
-        let signed_record = TaggedSigned::new(v.clone(), signature);
+        self.dht_put_signed(k, &TaggedSigned::new(v.clone(), signature))
+            .await
+    }
+
+    async fn dht_put_signed<K, V, T>(
+        &self,
+        k: &K,
+        signed_record: &TaggedSigned<V, T>,
+    ) -> Result<(), Self::Error>
+    where
+        K: AsRef<[u8]> + Send + Sync,
+        V: Serialize + Send + Sync + TaggedSignable + Clone + Ord,
+        T: TaggedKeyPair + Send + Sync + Serialize + Deserialize,
+    {
         let (output_tx, output_rx) = oneshot::channel();
 
         self.action_tx
@@ -668,6 +799,46 @@ impl NetworkInterface for Network {
         output_rx.await?
     }
 
+    async fn dht_start_providing<K>(&self, k: &K) -> Result<(), Self::Error>
+    where
+        K: AsRef<[u8]> + Send + Sync,
+    {
+        let (output_tx, output_rx) = oneshot::channel();
+        self.action_tx
+            .send(NetworkAction::DhtStartProviding {
+                key: k.as_ref().to_owned(),
+                output: output_tx,
+            })
+            .await?;
+        output_rx.await?
+    }
+
+    async fn dht_stop_providing<K>(&self, k: &K) -> Result<(), Self::Error>
+    where
+        K: AsRef<[u8]> + Send + Sync,
+    {
+        self.action_tx
+            .send(NetworkAction::DhtStopProviding {
+                key: k.as_ref().to_owned(),
+            })
+            .await?;
+        Ok(())
+    }
+
+    async fn dht_get_providers<K>(&self, k: &K) -> Result<Vec<PeerId>, Self::Error>
+    where
+        K: AsRef<[u8]> + Send + Sync,
+    {
+        let (output_tx, output_rx) = oneshot::channel();
+        self.action_tx
+            .send(NetworkAction::DhtGetProviders {
+                key: k.as_ref().to_owned(),
+                output: output_tx,
+            })
+            .await?;
+        output_rx.await?
+    }
+
     async fn dial_peer(&self, peer_id: PeerId) -> Result<(), NetworkError> {
         let (output_tx, output_rx) = oneshot::channel();
         self.action_tx
@@ -696,6 +867,15 @@ impl NetworkInterface for Network {
         self.local_peer_id
     }
 
+    fn get_local_addresses(&self) -> Vec<Multiaddr> {
+        self.contacts
+            .read()
+            .get_own_contact()
+            .addresses()
+            .cloned()
+            .collect()
+    }
+
     async fn message<M: Message>(&self, message: M, peer_id: PeerId) -> Result<(), RequestError> {
         self.request_impl(message, peer_id).await
     }
@@ -725,22 +905,277 @@ impl NetworkInterface for Network {
         request_id: InboundRequestId,
         response: Req::Response,
     ) -> Result<(), Self::Error> {
-        let (output_tx, output_rx) = oneshot::channel();
-
         // Encapsulate it in a `Result` to signal the network that this
         // was a successful response from the application.
         let response: Result<Req::Response, InboundRequestError> = Ok(response);
         let ser_response = response.serialize_to_vec();
+        self.seeding.complete(request_id, ser_response.len());
 
-        self.action_tx
-            .clone()
-            .send(NetworkAction::SendResponse {
-                request_id,
-                response: ser_response,
-                output: output_tx,
-            })
-            .await?;
+        send_serialized_response(self.action_tx(Req::PRIORITY), request_id, ser_response).await
+    }
+}
 
-        output_rx.await?
+/// Handle to abort an outbound request sent with [`Network::request_with_abort_handle`].
+#[derive(Clone, Debug)]
+pub struct RequestAbortHandle(AbortHandle);
+
+impl RequestAbortHandle {
+    /// Aborts the request. Its stream is closed and the request resolves with an error.
+    /// Aborting a request that already completed has no effect.
+    pub fn abort(&self) {
+        self.0.abort();
+    }
+}
+
+/// Guard of an outbound request that is in flight in the swarm task. Dropping the guard aborts
+/// the request, unless it has been disarmed because the request completed.
+struct RequestGuard {
+    action_tx: mpsc::Sender<NetworkAction>,
+    abort_handle: AbortHandle,
+    request_id: Option<OutboundRequestId>,
+}
+
+impl RequestGuard {
+    fn new(
+        action_tx: mpsc::Sender<NetworkAction>,
+        abort_handle: AbortHandle,
+        request_id: OutboundRequestId,
+    ) -> Self {
+        Self {
+            action_tx,
+            abort_handle,
+            request_id: Some(request_id),
+        }
+    }
+
+    /// Aborts the request, discarding any response that might still arrive for it.
+    fn abort(mut self) {
+        self.cancel();
+    }
+
+    /// Marks the request as completed, such that dropping the guard doesn't abort it.
+    fn disarm(mut self) {
+        self.request_id = None;
+    }
+
+    fn cancel(&mut self) {
+        if let Some(request_id) = self.request_id.take() {
+            // Closes the request's stream, see `MessageCodec`.
+            self.abort_handle.abort();
+
+            // If the action channel is full or closed, the swarm task cleans up the request once
+            // libp2p reports the failure caused by the abort.
+            if let Err(error) = self
+                .action_tx
+                .try_send(NetworkAction::CancelRequest { request_id })
+            {
+                trace!(%request_id, %error, "Failed to cancel request");
+            }
+        }
+    }
+}
+
+impl Drop for RequestGuard {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+/// Sends a request to a peer and awaits its response.
+async fn send_request<Req: RequestCommon>(
+    action_tx: &mpsc::Sender<NetworkAction>,
+    request: Req,
+    peer_id: PeerId,
+) -> Result<Req::Response, RequestError> {
+    let (abort_handle, abort_registration) = AbortHandle::new_pair();
+    send_abortable_request(
+        action_tx,
+        request,
+        peer_id,
+        abort_handle,
+        abort_registration,
+    )
+    .await
+}
+
+/// Sends a request to a peer and awaits its response. The request is aborted if the given abort
+/// handle is used or if the returned future is dropped before the response arrived.
+async fn send_abortable_request<Req: RequestCommon>(
+    action_tx: &mpsc::Sender<NetworkAction>,
+    request: Req,
+    peer_id: PeerId,
+    abort_handle: AbortHandle,
+    abort_registration: AbortRegistration,
+) -> Result<Req::Response, RequestError> {
+    let (output_tx, output_rx) = oneshot::channel();
+    let (response_tx, response_rx) = oneshot::channel();
+
+    let action = NetworkAction::SendRequest {
+        peer_id,
+        request: request.serialize_request()[..].into(),
+        abort_registration,
+        response_channel: response_tx,
+        output: output_tx,
+    };
+
+    if action_tx.send(action).await.is_err() {
+        return Err(OutboundRequestError::SendError.into());
+    }
+
+    let Ok(request_id) = output_rx.await else {
+        return Err(OutboundRequestError::SendError.into());
+    };
+    // Aborts the request in the swarm task if we return early or if this future is dropped
+    // before a response has been received.
+    let guard = RequestGuard::new(action_tx.clone(), abort_handle, request_id);
+
+    trace!(
+        r#type = Req::type_name::<Req>(),
+        %request_id,
+        %peer_id,
+        "Request sent",
+    );
+
+    let Ok(result) = timeout(REQUEST_TIMEOUT, response_rx).await else {
+        debug!(
+            r#type = Req::type_name::<Req>(),
+            %request_id,
+            %peer_id,
+            "Request timed out with no response from libp2p"
+        );
+        guard.abort();
+        return Err(OutboundRequestError::Timeout.into());
+    };
+
+    let Ok(result) = result else {
+        debug!(
+            r#type = Req::type_name::<Req>(),
+            %request_id,
+            %peer_id,
+            "Request failed - sender future dropped"
+        );
+        return Err(OutboundRequestError::SenderFutureDropped.into());
+    };
+    guard.disarm();
+
+    let data = result?;
+
+    let result = <Result<Req::Response, InboundRequestError>>::deserialize_take(&data);
+    let Ok((message, left_over)) = result else {
+        debug!(
+            r#type = Req::type_name::<Req>(),
+            %request_id,
+            %peer_id,
+            "Failed to deserialize response",
+        );
+        return Err(InboundRequestError::DeSerializationError.into());
+    };
+
+    if !left_over.is_empty() {
+        debug!(
+            r#type = Req::type_name::<Req>(),
+            %request_id,
+            %peer_id,
+            unread_data_len = left_over.len(),
+            "Unexpected content size deserializing response",
+        );
+    }
+
+    // Check if there was an actual response from the application or a default response from
+    // the network. If the network replied with the default response, it was because there wasn't a
+    // receiver for the request.
+    if message.is_ok() {
+        trace!(
+            r#type = Req::type_name::<Req>(),
+            %request_id,
+            %peer_id,
+            "Response received",
+        );
+    }
+
+    message.map_err(Into::into)
+}
+
+/// Sends a successful response to a request received from another peer.
+async fn send_response<Req: RequestCommon>(
+    action_tx: &mpsc::Sender<NetworkAction>,
+    request_id: InboundRequestId,
+    response: Req::Response,
+) -> Result<(), NetworkError> {
+    let response: Result<Req::Response, InboundRequestError> = Ok(response);
+    send_serialized_response(action_tx, request_id, response.serialize_to_vec()).await
+}
+
+pub(crate) async fn send_serialized_response(
+    action_tx: &mpsc::Sender<NetworkAction>,
+    request_id: InboundRequestId,
+    response: Vec<u8>,
+) -> Result<(), NetworkError> {
+    let (output_tx, output_rx) = oneshot::channel();
+
+    action_tx
+        .send(NetworkAction::SendResponse {
+            request_id,
+            response,
+            output: output_tx,
+        })
+        .await?;
+
+    output_rx.await?
+}
+
+/// Periodically writes the known peer contacts to disk. Stops once the peer contact book is dropped.
+#[cfg(not(target_family = "wasm"))]
+async fn persist_peer_contacts(contacts: Weak<RwLock<PeerContactBook>>, path: PathBuf) {
+    let mut interval = interval(PEER_CONTACTS_PERSIST_INTERVAL);
+    while interval.next().await.is_some() {
+        let Some(contacts) = contacts.upgrade() else {
+            break;
+        };
+
+        if let Err(error) = contacts.read().save_to_file(&path) {
+            warn!(%error, path = %path.display(), "Failed to persist peer contacts");
+        }
+    }
+}
+
+/// Periodically measures the latency to all connected peers using echo requests. Stops once the
+/// network is dropped.
+async fn measure_latencies(
+    action_tx: mpsc::WeakSender<NetworkAction>,
+    connected_peers: Arc<RwLock<HashMap<PeerId, PeerInfo>>>,
+    latencies: Arc<LatencyTracker>,
+) {
+    let mut interval = interval(LATENCY_MEASUREMENT_INTERVAL).with_jitter(0.1);
+    while interval.next().await.is_some() {
+        let Some(action_tx) = action_tx.upgrade() else {
+            break;
+        };
+
+        let peers: Vec<PeerId> = connected_peers.read().keys().copied().collect();
+        latencies.retain(|peer_id| peers.contains(peer_id));
+
+        let measurements = peers.into_iter().map(|peer_id| {
+            let action_tx = &action_tx;
+            async move {
+                let nonce = rand::random();
+                let start = Instant::now();
+                match send_request(action_tx, EchoRequest { nonce }, peer_id).await {
+                    Ok(echo) if echo == nonce => Some((peer_id, start.elapsed())),
+                    Ok(_) => {
+                        debug!(%peer_id, "Echo request answered with a wrong nonce");
+                        None
+                    }
+                    Err(error) => {
+                        trace!(%peer_id, %error, "Echo request failed");
+                        None
+                    }
+                }
+            }
+        });
+
+        for (peer_id, rtt) in join_all(measurements).await.into_iter().flatten() {
+            latencies.record(peer_id, rtt);
+        }
     }
 }
@@ -1,16 +1,21 @@
 use std::time::Duration;
 
 use libp2p::gossipsub::TopicHash;
+use nimiq_network_interface::network::Priority;
 use prometheus_client::{
     encoding::EncodeLabelSet,
     metrics::{counter::Counter, family::Family, histogram::Histogram},
     registry::Registry,
 };
 
+use crate::rate_limiting::RateLimitScope;
+
 pub struct NetworkMetrics {
     gossipsub_messages_received: Family<TopicLabels, Counter>,
     gossipsub_messages_published: Family<TopicLabels, Counter>,
     response_times: Histogram,
+    actions_performed: Family<LaneLabels, Counter>,
+    requests_rate_limited: Family<RateLimitLabels, Counter>,
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
@@ -18,12 +23,24 @@ struct TopicLabels {
     topic: String,
 }
 
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct LaneLabels {
+    lane: String,
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+struct RateLimitLabels {
+    scope: String,
+}
+
 impl Default for NetworkMetrics {
     fn default() -> Self {
         NetworkMetrics {
             gossipsub_messages_received: Default::default(),
             gossipsub_messages_published: Default::default(),
             response_times: Histogram::new([0.01, 0.02, 0.05, 0.1, 0.2, 0.5, 1.0, 2.0]),
+            actions_performed: Default::default(),
+            requests_rate_limited: Default::default(),
         }
     }
 }
@@ -47,6 +64,18 @@ impl NetworkMetrics {
             "Time between requests and responses",
             self.response_times.clone(),
         );
+
+        registry.register(
+            "actions_performed",
+            "Number of network actions performed per priority lane",
+            self.actions_performed.clone(),
+        );
+
+        registry.register(
+            "requests_rate_limited",
+            "Number of inbound requests rejected per exceeded rate limit scope",
+            self.requests_rate_limited.clone(),
+        );
     }
 
     pub(crate) fn note_received_pubsub_message(&self, topic: &TopicHash) {
@@ -83,6 +112,26 @@ impl NetworkMetrics {
             .inc();
     }
 
+    pub(crate) fn note_action(&self, priority: Priority) {
+        let lane = match priority {
+            Priority::Consensus => "consensus",
+            Priority::Background => "background",
+        };
+        self.actions_performed
+            .get_or_create(&LaneLabels {
+                lane: String::from(lane),
+            })
+            .inc();
+    }
+
+    pub(crate) fn note_rate_limited_request(&self, scope: RateLimitScope) {
+        self.requests_rate_limited
+            .get_or_create(&RateLimitLabels {
+                scope: scope.to_string(),
+            })
+            .inc();
+    }
+
     pub(crate) fn note_response_time(&self, duration: Duration) {
         self.response_times.observe(duration.as_secs_f64());
     }
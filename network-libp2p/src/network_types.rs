@@ -1,9 +1,11 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use bytes::Bytes;
+use futures::future::AbortRegistration;
 #[cfg(feature = "metrics")]
 use instant::Instant;
 use libp2p::{
+    core::transport::ListenerId,
     gossipsub,
     kad::{QueryId, Record},
     request_response::{InboundRequestId, OutboundRequestId, ResponseChannel},
@@ -12,21 +14,23 @@ use libp2p::{
 };
 use nimiq_keys::KeyPair;
 use nimiq_network_interface::{
-    network::{CloseReason, MsgAcceptance, PubsubId, Topic},
+    network::{CloseReason, MsgAcceptance, Priority, PubsubId, Reachability, Topic},
     peer_info::Services,
     request::{RequestError, RequestType},
 };
 use nimiq_serde::{Deserialize, DeserializeError};
 use nimiq_utils::tagged_signing::{TaggedSignable, TaggedSigned};
-use nimiq_validator_network::validator_record::ValidatorRecord;
+use nimiq_validator_network::validator_record::{LegacyValidatorRecord, ValidatorRecord};
 use thiserror::Error;
 use tokio::sync::{mpsc, oneshot};
 
 use crate::{
     autonat::NatState,
     dispatch::codecs::{IncomingRequest, OutgoingResponse},
+    priority::PriorityLanes,
     rate_limiting::RateLimitConfig,
-    NetworkError,
+    tls_reload_transport::TlsConfigHandle,
+    NetworkError, TlsConfig,
 };
 
 #[derive(Debug)]
@@ -48,6 +52,17 @@ pub(crate) enum NetworkAction {
         value: Vec<u8>,
         output: oneshot::Sender<Result<(), NetworkError>>,
     },
+    DhtStartProviding {
+        key: Vec<u8>,
+        output: oneshot::Sender<Result<(), NetworkError>>,
+    },
+    DhtStopProviding {
+        key: Vec<u8>,
+    },
+    DhtGetProviders {
+        key: Vec<u8>,
+        output: oneshot::Sender<Result<Vec<PeerId>, NetworkError>>,
+    },
     Subscribe {
         topic_name: String,
         buffer_size: usize,
@@ -76,13 +91,18 @@ pub(crate) enum NetworkAction {
         type_id: RequestType,
         output: mpsc::Sender<(Bytes, InboundRequestId, PeerId)>,
         rate_limit_config: RateLimitConfig,
+        priority: Priority,
     },
     SendRequest {
         peer_id: PeerId,
         request: IncomingRequest,
+        abort_registration: AbortRegistration,
         response_channel: oneshot::Sender<Result<Bytes, RequestError>>,
         output: oneshot::Sender<OutboundRequestId>,
     },
+    CancelRequest {
+        request_id: OutboundRequestId,
+    },
     SendResponse {
         request_id: InboundRequestId,
         response: OutgoingResponse,
@@ -91,6 +111,18 @@ pub(crate) enum NetworkAction {
     ListenOn {
         listen_addresses: Vec<Multiaddr>,
     },
+    AddListenAddresses {
+        listen_addresses: Vec<Multiaddr>,
+        output: oneshot::Sender<Result<(), NetworkError>>,
+    },
+    RemoveListenAddresses {
+        listen_addresses: Vec<Multiaddr>,
+        output: oneshot::Sender<Result<(), NetworkError>>,
+    },
+    UpdateTlsConfig {
+        tls: TlsConfig,
+        output: oneshot::Sender<Result<(), NetworkError>>,
+    },
     ConnectPeersByServices {
         services: Services,
         num_peers: usize,
@@ -144,24 +176,36 @@ pub enum DhtRecord {
     /// Validator record with its publisher Peer ID,
     /// the decoded validator record and the original serialized record.
     Validator(PeerId, ValidatorRecord<PeerId>, Record),
+    /// Legacy validator record with its publisher Peer ID,
+    /// the decoded validator record and the original serialized record.
+    LegacyValidator(PeerId, LegacyValidatorRecord<PeerId>, Record),
+    /// Record of a type registered with the DHT verifier with its publisher Peer ID,
+    /// the timestamp it was issued at and the original serialized record.
+    Custom(PeerId, u64, Record),
 }
 
 impl DhtRecord {
     pub(crate) fn get_signed_record(self) -> Record {
         match self {
             Self::Validator(_, _, signed_record) => signed_record,
+            Self::LegacyValidator(_, _, signed_record) => signed_record,
+            Self::Custom(_, _, signed_record) => signed_record,
         }
     }
 
     pub(crate) fn get_peer_id(&self) -> PeerId {
         match self {
             Self::Validator(peer_id, _, _) => *peer_id,
+            Self::LegacyValidator(peer_id, _, _) => *peer_id,
+            Self::Custom(peer_id, _, _) => *peer_id,
         }
     }
 
     pub(crate) fn get_timestamp(&self) -> u64 {
         match self {
             Self::Validator(_, record, _) => record.timestamp,
+            Self::LegacyValidator(_, record, _) => record.timestamp,
+            Self::Custom(_, timestamp, _) => *timestamp,
         }
     }
 }
@@ -202,6 +246,17 @@ impl TryFrom<&Record> for DhtRecord {
                         ))
                     }
                 }
+                LegacyValidatorRecord::<PeerId>::TAG => {
+                    let validator_record = TaggedSigned::<
+                        LegacyValidatorRecord<PeerId>,
+                        KeyPair,
+                    >::deserialize_from_vec(&record.value)?;
+                    Ok(DhtRecord::LegacyValidator(
+                        record.publisher.unwrap(),
+                        validator_record.record,
+                        record.clone(),
+                    ))
+                }
                 _ => Err(DhtRecordError::UnknownTag),
             }
         } else {
@@ -234,6 +289,16 @@ pub(crate) struct TaskState {
     pub(crate) dht_gets: HashMap<QueryId, oneshot::Sender<Result<Vec<u8>, NetworkError>>>,
     /// Get results for DHT (kad) get operation
     pub(crate) dht_get_results: HashMap<QueryId, DhtResults>,
+    /// Senders for DHT (kad) start providing operations
+    pub(crate) dht_start_providing: HashMap<QueryId, oneshot::Sender<Result<(), NetworkError>>>,
+    /// Senders and the providers found so far for DHT (kad) get providers operations
+    pub(crate) dht_get_providers: HashMap<
+        QueryId,
+        (
+            oneshot::Sender<Result<Vec<PeerId>, NetworkError>>,
+            HashSet<PeerId>,
+        ),
+    >,
     /// Senders per Gossibsub topic
     pub(crate) gossip_topics: HashMap<gossipsub::TopicHash, GossipsubTopicInfo>,
     /// DHT (kad) has been bootstrapped
@@ -242,22 +307,43 @@ pub(crate) struct TaskState {
     pub(crate) dht_server_mode: bool,
     /// The NAT status of the local peer
     pub(crate) nat_status: NatState,
+    /// The reachability last reported to the network event subscribers
+    pub(crate) reported_reachability: Option<Reachability>,
+    /// The relays to listen on if none of our addresses are publicly reachable
+    pub(crate) relays: Vec<Multiaddr>,
+    /// The listeners on the relays, which are only present while we are not publicly reachable
+    pub(crate) relay_listeners: Vec<ListenerId>,
+    /// The sentries per peer ID that we are exclusively reachable through, if any
+    pub(crate) sentries: HashMap<PeerId, Multiaddr>,
+    /// The listeners on the sentries per sentry peer ID, which are kept regardless of our NAT status
+    pub(crate) sentry_listeners: HashMap<PeerId, ListenerId>,
+    /// The listeners per listen address they were requested for
+    pub(crate) listeners: HashMap<Multiaddr, ListenerId>,
+    /// Handle to replace the TLS configuration of the transport
+    pub(crate) tls_handle: TlsConfigHandle,
     /// Senders per `OutboundRequestId` for request-response
     pub(crate) requests: HashMap<OutboundRequestId, oneshot::Sender<Result<Bytes, RequestError>>>,
     /// Time spent per `OutboundRequestId` for request-response
     #[cfg(feature = "metrics")]
     pub(crate) requests_initiated: HashMap<OutboundRequestId, Instant>,
-    /// Senders for receiving responses per `InboundRequestId` for request-response
+    /// Requesting peers and senders for receiving responses per `InboundRequestId` for request-response
     pub(crate) response_channels:
-        HashMap<InboundRequestId, ResponseChannel<Option<OutgoingResponse>>>,
-    /// Senders and respective rate limiting constants for replying to requests per `RequestType` for request-response
+        HashMap<InboundRequestId, (PeerId, ResponseChannel<Option<OutgoingResponse>>)>,
+    /// Senders, respective rate limiting constants and priorities for replying to requests per `RequestType` for request-response
     pub(crate) receive_requests: HashMap<
         RequestType,
         (
             mpsc::Sender<(Bytes, InboundRequestId, PeerId)>,
             RateLimitConfig,
+            Priority,
         ),
     >,
+    /// Consensus traffic in flight per peer and the background traffic held back meanwhile
+    pub(crate) priority_lanes: PriorityLanes<NetworkAction>,
+    /// Peers per `OutboundRequestId` of the consensus requests in flight
+    pub(crate) priority_requests: HashMap<OutboundRequestId, PeerId>,
+    /// Peers per `InboundRequestId` of the consensus requests being answered
+    pub(crate) priority_responses: HashMap<InboundRequestId, PeerId>,
     /// DHT quorum value
     pub(crate) dht_quorum: u8,
 }
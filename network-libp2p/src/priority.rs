@@ -0,0 +1,151 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    time::Duration,
+};
+
+use instant::Instant;
+use libp2p::PeerId;
+
+/// The maximum time background traffic to a peer is held back in favor of consensus traffic.
+pub(crate) const MAX_DEFERRAL: Duration = Duration::from_secs(1);
+
+/// Per-peer prioritization of consensus traffic on the wire.
+///
+/// Every request and its response use their own stream, and the streams to a peer share the
+/// bandwidth of the connection. Large background transfers, like history or state chunks, thus
+/// delay consensus messages to the same peer. While consensus requests to or from a peer are in
+/// flight, background requests and responses to that peer are held back, for at most
+/// [`MAX_DEFERRAL`].
+pub(crate) struct PriorityLanes<T> {
+    /// Number of consensus requests and responses in flight per peer.
+    in_flight: HashMap<PeerId, usize>,
+    /// Background traffic held back per peer, together with the time it was held back.
+    deferred: HashMap<PeerId, VecDeque<(Instant, T)>>,
+}
+
+impl<T> Default for PriorityLanes<T> {
+    fn default() -> Self {
+        Self {
+            in_flight: HashMap::new(),
+            deferred: HashMap::new(),
+        }
+    }
+}
+
+impl<T> PriorityLanes<T> {
+    /// Notes that consensus traffic to or from the given peer is in flight.
+    pub(crate) fn start(&mut self, peer_id: PeerId) {
+        *self.in_flight.entry(peer_id).or_default() += 1;
+    }
+
+    /// Notes that consensus traffic to or from the given peer completed. Returns the background
+    /// traffic to send now that no consensus traffic is in flight anymore.
+    pub(crate) fn finish(&mut self, peer_id: &PeerId) -> Vec<T> {
+        let Some(count) = self.in_flight.get_mut(peer_id) else {
+            return vec![];
+        };
+        *count -= 1;
+        if *count > 0 {
+            return vec![];
+        }
+
+        self.in_flight.remove(peer_id);
+        self.release(peer_id)
+    }
+
+    /// Returns whether consensus traffic to or from the given peer is in flight.
+    pub(crate) fn is_busy(&self, peer_id: &PeerId) -> bool {
+        self.in_flight.contains_key(peer_id)
+    }
+
+    /// Schedules background traffic to the given peer. Returns the background traffic to send
+    /// now: the given item if no consensus traffic is in flight, and any traffic that has been
+    /// held back for longer than [`MAX_DEFERRAL`].
+    pub(crate) fn schedule(&mut self, peer_id: PeerId, item: T, now: Instant) -> Vec<T> {
+        if !self.is_busy(&peer_id) {
+            return vec![item];
+        }
+
+        let deferred = self.deferred.entry(peer_id).or_default();
+        deferred.push_back((now, item));
+
+        // Items are sent in order, so everything is released once the oldest one expired.
+        let expired = deferred
+            .front()
+            .is_some_and(|(since, _)| now.duration_since(*since) >= MAX_DEFERRAL);
+        if expired {
+            self.release(&peer_id)
+        } else {
+            vec![]
+        }
+    }
+
+    fn release(&mut self, peer_id: &PeerId) -> Vec<T> {
+        self.deferred
+            .remove(peer_id)
+            .map(|deferred| deferred.into_iter().map(|(_, item)| item).collect())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use instant::Instant;
+    use libp2p::PeerId;
+
+    use super::{PriorityLanes, MAX_DEFERRAL};
+
+    #[test]
+    fn it_sends_background_traffic_to_idle_peers() {
+        let mut lanes = PriorityLanes::default();
+        let busy_peer = PeerId::random();
+        let idle_peer = PeerId::random();
+        let now = Instant::now();
+
+        lanes.start(busy_peer);
+        assert_eq!(lanes.schedule(idle_peer, 1, now), vec![1]);
+        assert!(lanes.schedule(busy_peer, 2, now).is_empty());
+    }
+
+    #[test]
+    fn it_holds_back_background_traffic_until_consensus_traffic_finished() {
+        let mut lanes = PriorityLanes::default();
+        let peer_id = PeerId::random();
+        let now = Instant::now();
+
+        lanes.start(peer_id);
+        lanes.start(peer_id);
+        assert!(lanes.schedule(peer_id, 1, now).is_empty());
+        assert!(lanes.schedule(peer_id, 2, now).is_empty());
+
+        assert!(lanes.finish(&peer_id).is_empty());
+        assert!(lanes.is_busy(&peer_id));
+        assert_eq!(lanes.finish(&peer_id), vec![1, 2]);
+        assert!(!lanes.is_busy(&peer_id));
+
+        // Unbalanced finishes are ignored.
+        assert!(lanes.finish(&peer_id).is_empty());
+        assert_eq!(lanes.schedule(peer_id, 3, now), vec![3]);
+    }
+
+    #[test]
+    fn it_releases_background_traffic_after_max_deferral() {
+        let mut lanes = PriorityLanes::default();
+        let peer_id = PeerId::random();
+        let now = Instant::now();
+
+        lanes.start(peer_id);
+        assert!(lanes.schedule(peer_id, 1, now).is_empty());
+        assert!(lanes
+            .schedule(peer_id, 2, now + MAX_DEFERRAL / 2)
+            .is_empty());
+        assert_eq!(
+            lanes.schedule(peer_id, 3, now + MAX_DEFERRAL),
+            vec![1, 2, 3]
+        );
+
+        // The consensus traffic is still in flight, so new background traffic is held back again.
+        assert!(lanes.schedule(peer_id, 4, now + MAX_DEFERRAL).is_empty());
+        assert_eq!(lanes.finish(&peer_id), vec![4]);
+    }
+}
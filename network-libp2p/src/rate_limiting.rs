@@ -1,11 +1,13 @@
 use std::{
     cmp::Ordering,
     collections::{BTreeSet, HashMap},
+    fmt,
     time::Duration,
 };
 
 use instant::Instant;
-use libp2p::{gossipsub::TopicHash, PeerId};
+use ip_network::IpNetwork;
+use libp2p::{gossipsub::TopicHash, multiaddr::Protocol, Multiaddr, PeerId};
 use nimiq_network_interface::{
     network::Topic,
     request::{RequestCommon, RequestType},
@@ -37,6 +39,54 @@ impl RateLimitConfig {
     }
 }
 
+/// Policy for limiting inbound requests beyond the limits per peer, such that peers cannot
+/// bypass the limits by rotating their peer IDs.
+/// The limits are expressed as multiples of the per-peer limit of each request type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RateLimitPolicy {
+    /// Multiple of the per-peer limit shared by all peers connected from the same IP subnet.
+    /// `None` disables the limits per subnet.
+    pub subnet_multiplier: Option<u32>,
+    /// Multiple of the per-peer limit shared by all peers. `None` disables the global limits.
+    pub global_multiplier: Option<u32>,
+    /// IPv4 subnet prefix length used to aggregate peers into subnets.
+    pub ipv4_subnet_prefix_len: u8,
+    /// IPv6 subnet prefix length used to aggregate peers into subnets.
+    pub ipv6_subnet_prefix_len: u8,
+}
+
+impl Default for RateLimitPolicy {
+    fn default() -> Self {
+        Self {
+            subnet_multiplier: Some(10),
+            global_multiplier: None,
+            ipv4_subnet_prefix_len: 24,
+            ipv6_subnet_prefix_len: 96,
+        }
+    }
+}
+
+/// The scope of a rate limit that was exceeded.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum RateLimitScope {
+    /// The limit of an individual peer.
+    Peer,
+    /// The limit shared by all peers of an IP subnet.
+    Subnet,
+    /// The limit shared by all peers.
+    Global,
+}
+
+impl fmt::Display for RateLimitScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RateLimitScope::Peer => write!(f, "peer"),
+            RateLimitScope::Subnet => write!(f, "subnet"),
+            RateLimitScope::Global => write!(f, "global"),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub(crate) enum RateLimitId {
     Request(RequestType),
@@ -182,22 +232,69 @@ impl RateLimit {
 
 // Rate limiting overarching structure. It holds the rate limits by peer and request type.
 // This handles the case of a peer reconnecting within the time window to attempt to bypass the rate limits established.
+// Requests are additionally limited per IP subnet and globally, according to the policy.
 #[derive(Default)]
 pub(crate) struct RateLimits {
+    /// The policy for the limits per subnet and the global limits.
+    policy: RateLimitPolicy,
     /// The rate limits per active peer.
     rate_limits: HashMap<PeerId, HashMap<RateLimitId, RateLimit>>,
     /// All the pending deletion rate limits.
     rate_limits_pending_deletion: PendingDeletion,
+    /// The IP subnets of the connected peers.
+    peer_subnets: HashMap<PeerId, IpNetwork>,
+    /// The request rate limits shared by the peers of an IP subnet.
+    subnet_rate_limits: HashMap<(IpNetwork, RequestType), RateLimit>,
+    /// The request rate limits shared by all peers.
+    global_rate_limits: HashMap<RequestType, RateLimit>,
 }
 
 impl RateLimits {
-    /// Increases the counter of the rate limit and returns a bool in case the defined rate limit is surpassed.
+    pub(crate) fn new(policy: RateLimitPolicy) -> Self {
+        Self {
+            policy,
+            ..Default::default()
+        }
+    }
+
+    /// Records the IP subnet of a connected peer, derived from the address of its connection.
+    /// Connections without an IP address, e.g. through relays, are not attributed to a subnet.
+    pub(crate) fn add_peer_address(&mut self, peer_id: PeerId, address: &Multiaddr) {
+        if address
+            .iter()
+            .any(|protocol| protocol == Protocol::P2pCircuit)
+        {
+            return;
+        }
+        let subnet = match address.iter().next() {
+            Some(Protocol::Ip4(ip)) => {
+                IpNetwork::new_truncate(ip, self.policy.ipv4_subnet_prefix_len).ok()
+            }
+            Some(Protocol::Ip6(ip)) => {
+                IpNetwork::new_truncate(ip, self.policy.ipv6_subnet_prefix_len).ok()
+            }
+            _ => None,
+        };
+        if let Some(subnet) = subnet {
+            self.peer_subnets.entry(peer_id).or_insert(subnet);
+        }
+    }
+
+    /// Increases the counters of the rate limits and returns the scope of the first limit that
+    /// is surpassed, if any.
+    /// Gossipsub messages are only limited per peer, while requests are also limited per subnet
+    /// and globally.
     pub(crate) fn exceeds_rate_limit(
         &mut self,
         peer_id: PeerId,
         rate_limit_id: RateLimitId,
         rate_limit_config: &RateLimitConfig,
-    ) -> bool {
+    ) -> Option<RateLimitScope> {
+        let request_type = match rate_limit_id {
+            RateLimitId::Request(request_type) => Some(request_type),
+            RateLimitId::Gossipsub(_) => None,
+        };
+
         // If the peer has never sent a request of this type, creates a new entry.
         let requests_limit = self
             .rate_limits
@@ -213,7 +310,48 @@ impl RateLimits {
             });
 
         // Ensures that the request is allowed based on the set limits and updates the counter.
-        !requests_limit.increment_and_is_allowed(1)
+        if !requests_limit.increment_and_is_allowed(1) {
+            return Some(RateLimitScope::Peer);
+        }
+
+        let request_type = request_type?;
+
+        if let (Some(multiplier), Some(subnet)) = (
+            self.policy.subnet_multiplier,
+            self.peer_subnets.get(&peer_id),
+        ) {
+            let subnet_limit = self
+                .subnet_rate_limits
+                .entry((*subnet, request_type))
+                .or_insert_with(|| {
+                    RateLimit::new(
+                        rate_limit_config.max_requests.saturating_mul(multiplier),
+                        rate_limit_config.time_window,
+                        Instant::now(),
+                    )
+                });
+            if !subnet_limit.increment_and_is_allowed(1) {
+                return Some(RateLimitScope::Subnet);
+            }
+        }
+
+        if let Some(multiplier) = self.policy.global_multiplier {
+            let global_limit = self
+                .global_rate_limits
+                .entry(request_type)
+                .or_insert_with(|| {
+                    RateLimit::new(
+                        rate_limit_config.max_requests.saturating_mul(multiplier),
+                        rate_limit_config.time_window,
+                        Instant::now(),
+                    )
+                });
+            if !global_limit.increment_and_is_allowed(1) {
+                return Some(RateLimitScope::Global);
+            }
+        }
+
+        None
     }
 
     /// Mark all rate limits of a given peer as pending for deletion.
@@ -222,6 +360,9 @@ impl RateLimits {
         // Every time a peer disconnects, we delete all expired pending limits.
         self.clean_up();
 
+        // The limits of the subnet outlive the peer, such that reconnecting doesn't reset them.
+        self.peer_subnets.remove(&peer_id);
+
         // Go through all existing request types of the given peer and deletes the limit counters if possible or marks it for deletion.
         if let Some(rate_limits) = self.rate_limits.get_mut(&peer_id) {
             rate_limits.retain(|rate_limit_id, rate_limit| {
@@ -245,7 +386,14 @@ impl RateLimits {
     }
 
     /// Deletes the rate limits that were previously marked as pending if its expiration time has passed.
+    /// Also deletes the expired limits per subnet and the expired global limits.
     fn clean_up(&mut self) {
+        let now = Instant::now();
+        self.subnet_rate_limits
+            .retain(|_, rate_limit| !rate_limit.can_delete(now));
+        self.global_rate_limits
+            .retain(|_, rate_limit| !rate_limit.can_delete(now));
+
         // Iterates from the oldest to the most recent expiration date and deletes the entries that have expired.
         // The pending to deletion is ordered from the oldest to the most recent expiration date, thus we break early
         // from the loop once we find a non expired rate limit.
@@ -287,3 +435,82 @@ impl RateLimits {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use libp2p::{gossipsub::TopicHash, Multiaddr, PeerId};
+    use nimiq_network_interface::request::RequestType;
+
+    use super::{RateLimitConfig, RateLimitId, RateLimitPolicy, RateLimitScope, RateLimits};
+
+    const CONFIG: RateLimitConfig = RateLimitConfig {
+        max_requests: 2,
+        time_window: Duration::from_secs(60),
+    };
+
+    fn request(rate_limits: &mut RateLimits, peer_id: PeerId) -> Option<RateLimitScope> {
+        rate_limits.exceeds_rate_limit(peer_id, RateLimitId::Request(RequestType(1)), &CONFIG)
+    }
+
+    #[test]
+    fn it_limits_peers_of_the_same_subnet() {
+        let mut rate_limits = RateLimits::new(RateLimitPolicy {
+            subnet_multiplier: Some(2),
+            global_multiplier: None,
+            ..Default::default()
+        });
+        let address: Multiaddr = "/ip4/10.0.0.1/tcp/8443/ws".parse().unwrap();
+        let other_address: Multiaddr = "/ip4/10.0.1.1/tcp/8443/ws".parse().unwrap();
+
+        // Rotating peer IDs within the same subnet doesn't bypass the subnet limit.
+        let peers: Vec<_> = (0..3).map(|_| PeerId::random()).collect();
+        for peer_id in &peers {
+            rate_limits.add_peer_address(*peer_id, &address);
+        }
+        assert_eq!(request(&mut rate_limits, peers[0]), None);
+        assert_eq!(request(&mut rate_limits, peers[0]), None);
+        assert_eq!(
+            request(&mut rate_limits, peers[0]),
+            Some(RateLimitScope::Peer)
+        );
+        assert_eq!(request(&mut rate_limits, peers[1]), None);
+        assert_eq!(request(&mut rate_limits, peers[1]), None);
+        assert_eq!(
+            request(&mut rate_limits, peers[2]),
+            Some(RateLimitScope::Subnet)
+        );
+
+        // Peers of other subnets are not affected.
+        let other_peer = PeerId::random();
+        rate_limits.add_peer_address(other_peer, &other_address);
+        assert_eq!(request(&mut rate_limits, other_peer), None);
+    }
+
+    #[test]
+    fn it_limits_requests_globally() {
+        let mut rate_limits = RateLimits::new(RateLimitPolicy {
+            subnet_multiplier: None,
+            global_multiplier: Some(1),
+            ..Default::default()
+        });
+
+        assert_eq!(request(&mut rate_limits, PeerId::random()), None);
+        assert_eq!(request(&mut rate_limits, PeerId::random()), None);
+        assert_eq!(
+            request(&mut rate_limits, PeerId::random()),
+            Some(RateLimitScope::Global)
+        );
+
+        // Gossipsub messages are only limited per peer.
+        assert_eq!(
+            rate_limits.exceeds_rate_limit(
+                PeerId::random(),
+                RateLimitId::Gossipsub(TopicHash::from_raw("topic")),
+                &CONFIG
+            ),
+            None
+        );
+    }
+}
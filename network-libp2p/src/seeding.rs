@@ -0,0 +1,241 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    time::Duration,
+};
+
+use bytes::Bytes;
+use instant::Instant;
+use libp2p::{request_response::InboundRequestId, PeerId};
+use nimiq_network_interface::request::InboundRequestError;
+use nimiq_serde::Serialize;
+use nimiq_time::sleep;
+use parking_lot::Mutex;
+use tokio::sync::{mpsc, Notify};
+
+use crate::{network::send_serialized_response, network_types::NetworkAction};
+
+/// An inbound request as forwarded to the request receivers.
+type InboundRequest = (Bytes, InboundRequestId, PeerId);
+
+/// Maximum number of requests queued per peer. Further requests of that peer are answered with
+/// [`InboundRequestError::Busy`].
+const MAX_QUEUED_REQUESTS_PER_PEER: usize = 16;
+
+/// Maximum duration for which unused budget is accumulated. This limits the size of bursts after
+/// an idle period.
+const MAX_BURST: Duration = Duration::from_secs(1);
+
+/// Interval at which the budget is checked again once it is exhausted.
+const THROTTLE_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Requests not responded to within this time are no longer accounted for.
+const IN_FLIGHT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Seeding configuration, limiting the resources spent on serving history and state chunks to
+/// other peers.
+#[derive(Clone, Debug)]
+pub struct SeedingConfig {
+    /// The upload bandwidth of the node in bytes per second.
+    pub upload_bandwidth: u64,
+    /// The share of the upload bandwidth and of the processing time that may be spent on serving
+    /// seeding requests, between 0 and 1.
+    pub share: f64,
+}
+
+impl Default for SeedingConfig {
+    fn default() -> Self {
+        Self {
+            // 100 Mbit/s
+            upload_bandwidth: 12_500_000,
+            share: 0.5,
+        }
+    }
+}
+
+struct State {
+    /// Number of bytes that may still be sent. Negative if overdrawn.
+    bandwidth_budget: f64,
+    /// Processing time in seconds that may still be spent. Negative if overdrawn.
+    processing_budget: f64,
+    /// The last time the budgets were refilled.
+    last_refill: Instant,
+    /// Queued requests per peer together with the receiver they need to be forwarded to.
+    queues: HashMap<PeerId, VecDeque<(InboundRequest, mpsc::Sender<InboundRequest>)>>,
+    /// Peers with queued requests in round robin order.
+    rotation: VecDeque<PeerId>,
+    /// Requests that were forwarded but not yet responded to, with the time they were forwarded.
+    in_flight: HashMap<InboundRequestId, Instant>,
+}
+
+enum Next {
+    Request(InboundRequest, mpsc::Sender<InboundRequest>),
+    Throttled,
+    Idle,
+}
+
+/// Scheduler for seeding requests, i.e. requests for which the node serves history or state
+/// chunks to other peers.
+///
+/// Requests are queued per peer and forwarded in round robin order, such that a single peer
+/// cannot starve the others. They are only forwarded while there is budget left. The budget
+/// refills at the configured share of the upload bandwidth and processing time, and is charged
+/// with the size of each response and the time it took to produce it.
+pub(crate) struct SeedingScheduler {
+    /// Bandwidth in bytes per second available for seeding.
+    bandwidth_rate: f64,
+    /// Processing time in seconds per second available for seeding.
+    processing_rate: f64,
+    state: Mutex<State>,
+    notify: Notify,
+}
+
+impl SeedingScheduler {
+    pub(crate) fn new(config: &SeedingConfig) -> Self {
+        let share = config.share.clamp(0.0, 1.0);
+        let bandwidth_rate = config.upload_bandwidth as f64 * share;
+        let processing_rate = share;
+        let burst = MAX_BURST.as_secs_f64();
+
+        Self {
+            bandwidth_rate,
+            processing_rate,
+            state: Mutex::new(State {
+                bandwidth_budget: bandwidth_rate * burst,
+                processing_budget: processing_rate * burst,
+                last_refill: Instant::now(),
+                queues: HashMap::new(),
+                rotation: VecDeque::new(),
+                in_flight: HashMap::new(),
+            }),
+            notify: Notify::new(),
+        }
+    }
+
+    /// Queues all requests received on `input` and forwards them to `output` once scheduled.
+    /// Requests that can't be queued are answered with a busy error using `action_tx`. Only a
+    /// weak sender is kept to not keep the swarm task alive.
+    pub(crate) async fn forward(
+        self: Arc<Self>,
+        mut input: mpsc::Receiver<InboundRequest>,
+        output: mpsc::Sender<InboundRequest>,
+        action_tx: mpsc::WeakSender<NetworkAction>,
+    ) {
+        while let Some(request) = input.recv().await {
+            let Err((_, request_id, peer_id)) = self.enqueue(request, output.clone()) else {
+                continue;
+            };
+
+            debug!(
+                %peer_id,
+                %request_id,
+                "Rejecting seeding request, too many queued requests",
+            );
+            let Some(action_tx) = action_tx.upgrade() else {
+                break;
+            };
+            let response: Result<(), InboundRequestError> = Err(InboundRequestError::Busy);
+            if let Err(error) =
+                send_serialized_response(&action_tx, request_id, response.serialize_to_vec()).await
+            {
+                debug!(%peer_id, %request_id, %error, "Could not send busy response");
+            }
+        }
+    }
+
+    /// Queues `request` to be forwarded to `output`. Returns the request if the queue of its
+    /// peer is full.
+    fn enqueue(
+        &self,
+        request: InboundRequest,
+        output: mpsc::Sender<InboundRequest>,
+    ) -> Result<(), InboundRequest> {
+        let peer_id = request.2;
+        let mut state = self.state.lock();
+
+        let queue = state.queues.entry(peer_id).or_default();
+        if queue.len() >= MAX_QUEUED_REQUESTS_PER_PEER {
+            return Err(request);
+        }
+        queue.push_back((request, output));
+        if queue.len() == 1 {
+            state.rotation.push_back(peer_id);
+        }
+        drop(state);
+
+        self.notify.notify_one();
+        Ok(())
+    }
+
+    /// Forwards queued requests as long as there is budget left.
+    pub(crate) async fn run(self: Arc<Self>) {
+        loop {
+            match self.next() {
+                Next::Request(request, output) => {
+                    // If the receiver was dropped, nobody is interested in these requests anymore.
+                    output.send(request).await.ok();
+                }
+                Next::Throttled => sleep(THROTTLE_INTERVAL).await,
+                Next::Idle => self.notify.notified().await,
+            }
+        }
+    }
+
+    fn next(&self) -> Next {
+        let mut state = self.state.lock();
+
+        let Some(peer_id) = state.rotation.pop_front() else {
+            return Next::Idle;
+        };
+
+        self.refill(&mut state);
+        if state.bandwidth_budget <= 0.0 || state.processing_budget <= 0.0 {
+            state.rotation.push_front(peer_id);
+            return Next::Throttled;
+        }
+
+        let queue = state
+            .queues
+            .get_mut(&peer_id)
+            .expect("Peers in rotation have a queue");
+        let (request, output) = queue.pop_front().expect("Queues in rotation are not empty");
+        if queue.is_empty() {
+            state.queues.remove(&peer_id);
+        } else {
+            state.rotation.push_back(peer_id);
+        }
+
+        let now = Instant::now();
+        state
+            .in_flight
+            .retain(|_, forwarded_at| now.duration_since(*forwarded_at) < IN_FLIGHT_TIMEOUT);
+        state.in_flight.insert(request.1, now);
+
+        Next::Request(request, output)
+    }
+
+    /// Charges the budget for the response to a seeding request. Responses to other requests
+    /// are ignored.
+    pub(crate) fn complete(&self, request_id: InboundRequestId, response_size: usize) {
+        let mut state = self.state.lock();
+        let Some(forwarded_at) = state.in_flight.remove(&request_id) else {
+            return;
+        };
+
+        self.refill(&mut state);
+        state.bandwidth_budget -= response_size as f64;
+        state.processing_budget -= forwarded_at.elapsed().as_secs_f64();
+    }
+
+    fn refill(&self, state: &mut State) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+        let burst = MAX_BURST.as_secs_f64();
+        state.last_refill = now;
+
+        state.bandwidth_budget = (state.bandwidth_budget + elapsed * self.bandwidth_rate)
+            .min(self.bandwidth_rate * burst);
+        state.processing_budget = (state.processing_budget + elapsed * self.processing_rate)
+            .min(self.processing_rate * burst);
+    }
+}
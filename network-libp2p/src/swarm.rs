@@ -1,7 +1,11 @@
-use std::{collections::HashMap, num::NonZeroU8, sync::Arc, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    num::NonZeroU8,
+    sync::Arc,
+    time::Duration,
+};
 
 use futures::StreamExt;
-#[cfg(feature = "metrics")]
 use instant::Instant;
 #[cfg(all(target_family = "wasm", not(feature = "tokio-websocket")))]
 use libp2p::websocket_websys;
@@ -15,28 +19,32 @@ use libp2p::{
     gossipsub,
     identity::Keypair,
     kad::{
-        self, store::RecordStore, BootstrapError, BootstrapOk, GetRecordError, GetRecordOk,
-        InboundRequest, Mode, ProgressStep, PutRecordError, PutRecordOk, QueryId, QueryResult,
+        self, store::RecordStore, AddProviderError, AddProviderOk, BootstrapError, BootstrapOk,
+        GetProvidersError, GetProvidersOk, GetRecordError, GetRecordOk, InboundRequest, Mode,
+        ProgressStep, ProviderRecord, PutRecordError, PutRecordOk, QueryId, QueryResult,
         QueryStats, Quorum, Record,
     },
-    noise, ping,
+    multiaddr::Protocol,
+    noise, ping, relay,
     request_response::{self, InboundRequestId, OutboundRequestId, ResponseChannel},
     swarm::{
         dial_opts::{DialOpts, PeerCondition},
         ConnectionId, SwarmEvent,
     },
-    yamux, PeerId, Swarm, SwarmBuilder, Transport,
+    yamux, Multiaddr, PeerId, Swarm, SwarmBuilder, Transport,
 };
 #[cfg(feature = "tokio-websocket")]
 use libp2p::{dns, tcp, websocket};
 use log::Instrument;
 use nimiq_network_interface::{
-    network::{CloseReason, NetworkEvent},
+    network::{CloseReason, NetworkEvent, Priority, Reachability},
     peer_info::PeerInfo,
     request::{peek_type, InboundRequestError, OutboundRequestError, RequestError},
 };
 use nimiq_serde::Serialize;
 use nimiq_time::Interval;
+#[cfg(feature = "kad")]
+use nimiq_validator_network::validator_record::VALIDATOR_RECORD_MAX_ADDRESSES;
 use parking_lot::RwLock;
 use tokio::sync::{broadcast, mpsc};
 
@@ -46,11 +54,15 @@ use crate::{
     autonat::NatStatus,
     behaviour, dht,
     discovery::{self, peer_contacts::PeerContactBook},
+    dispatch::codecs::CodecRequest,
+    latency::LatencyTracker,
     network_types::{
         DhtBootStrapState, DhtRecord, DhtResults, GossipsubTopicInfo, NetworkAction, TaskState,
         ValidateMessage,
     },
-    rate_limiting::{RateLimitId, RateLimits},
+    rate_limiting::{RateLimitId, RateLimitPolicy, RateLimits},
+    tls_reload_transport::{self, TlsConfigHandle},
+    utils::{is_relayed_address, peer_id_of_address},
     Config, NetworkError, TlsConfig,
 };
 
@@ -73,18 +85,29 @@ pub(crate) fn new_swarm(
     contacts: Arc<RwLock<PeerContactBook>>,
     peer_score_params: gossipsub::PeerScoreParams,
     force_dht_server_mode: bool,
-) -> Swarm<behaviour::Behaviour> {
+) -> (Swarm<behaviour::Behaviour>, TlsConfigHandle) {
     let keypair = config.keypair.clone();
+    let tls_handle = TlsConfigHandle::default();
     let transport = new_transport(
         &keypair,
         config.memory_transport,
         config.only_secure_ws_connections,
         config.tls.as_ref(),
+        &tls_handle,
     )
     .unwrap();
 
-    let behaviour =
-        behaviour::Behaviour::new(config, contacts, peer_score_params, force_dht_server_mode);
+    // The relay client is always part of the behaviour, but it is only used once we listen on a
+    // relay because none of our addresses are publicly reachable.
+    let behaviour = |relay_client| {
+        behaviour::Behaviour::new(
+            config,
+            contacts,
+            peer_score_params,
+            force_dht_server_mode,
+            relay_client,
+        )
+    };
 
     // TODO add proper config
     #[cfg(not(target_family = "wasm"))]
@@ -92,7 +115,9 @@ pub(crate) fn new_swarm(
         .with_tokio()
         .with_other_transport(|_| transport)
         .unwrap()
-        .with_behaviour(|_| behaviour)
+        .with_relay_client(noise::Config::new, yamux::Config::default)
+        .unwrap()
+        .with_behaviour(|_, relay_client| behaviour(relay_client))
         .unwrap()
         .build();
     #[cfg(target_family = "wasm")]
@@ -100,31 +125,46 @@ pub(crate) fn new_swarm(
         .with_wasm_bindgen()
         .with_other_transport(|_| transport)
         .unwrap()
-        .with_behaviour(|_| behaviour)
+        .with_relay_client(noise::Config::new, yamux::Config::default)
+        .unwrap()
+        .with_behaviour(|_, relay_client| behaviour(relay_client))
         .unwrap()
         .build();
-    swarm
+    (swarm, tls_handle)
 }
 
 pub(crate) async fn swarm_task(
     mut swarm: NimiqSwarm,
     events_tx: broadcast::Sender<NetworkEvent<PeerId>>,
     mut action_rx: mpsc::Receiver<NetworkAction>,
+    mut priority_action_rx: mpsc::Receiver<NetworkAction>,
     mut validate_rx: mpsc::UnboundedReceiver<ValidateMessage<PeerId>>,
     connected_peers: Arc<RwLock<HashMap<PeerId, PeerInfo>>>,
     mut update_scores: Interval,
     contacts: Arc<RwLock<PeerContactBook>>,
+    latencies: Arc<LatencyTracker>,
     #[cfg(feature = "kad")] dht_verifier: impl dht::Verifier,
     force_dht_server_mode: bool,
     dht_quorum: NonZeroU8,
+    tls_handle: TlsConfigHandle,
+    relays: Vec<Multiaddr>,
+    sentries: Vec<Multiaddr>,
+    rate_limit_policy: RateLimitPolicy,
     #[cfg(feature = "metrics")] metrics: Arc<NetworkMetrics>,
 ) {
     let mut task_state = TaskState {
         dht_server_mode: force_dht_server_mode,
         dht_quorum: dht_quorum.into(),
+        tls_handle,
+        relays,
+        sentries: sentries
+            .into_iter()
+            .filter_map(|sentry| Some((peer_id_of_address(&sentry)?, sentry)))
+            .collect(),
         ..Default::default()
     };
-    let mut rate_limiting = RateLimits::default();
+    let mut rate_limiting = RateLimits::new(rate_limit_policy);
+    let mut priority_lane_open = true;
 
     let peer_id = Swarm::local_peer_id(&swarm);
     let task_span = trace_span!("swarm task", peer_id=?peer_id);
@@ -168,9 +208,40 @@ pub(crate) async fn swarm_task(
                         );
                     }
                 },
+                action = priority_action_rx.recv(), if priority_lane_open => {
+                    if let Some(action) = action {
+                        #[cfg(feature = "metrics")]
+                        metrics.note_action(Priority::Consensus);
+                        perform_action(action, Priority::Consensus, &mut swarm, &mut task_state);
+                    } else {
+                        priority_lane_open = false;
+                    }
+                },
                 action = action_rx.recv() => {
                     if let Some(action) = action {
-                        perform_action(action, &mut swarm, &mut task_state);
+                        // Consensus critical actions that are already queued go first.
+                        while let Ok(priority_action) = priority_action_rx.try_recv() {
+                            #[cfg(feature = "metrics")]
+                            metrics.note_action(Priority::Consensus);
+                            perform_action(
+                                priority_action,
+                                Priority::Consensus,
+                                &mut swarm,
+                                &mut task_state,
+                            );
+                        }
+                        #[cfg(feature = "metrics")]
+                        metrics.note_action(Priority::Background);
+                        // Background requests and responses to peers with consensus traffic in
+                        // flight are held back, such that they don't compete for the connection.
+                        for action in schedule_background_action(action, &mut task_state) {
+                            perform_action(
+                                action,
+                                Priority::Background,
+                                &mut swarm,
+                                &mut task_state,
+                            );
+                        }
                     }
                     else {
                         // `action_rx.next()` will return `None` if all senders (i.e. the `Network` object) are dropped.
@@ -178,7 +249,7 @@ pub(crate) async fn swarm_task(
                     }
                 },
                 _ = update_scores.next() => {
-                    swarm.behaviour().update_scores(Arc::clone(&contacts));
+                    swarm.behaviour().update_scores(Arc::clone(&contacts), &latencies.all());
                 },
             };
         }
@@ -192,6 +263,7 @@ fn new_transport(
     memory_transport: bool,
     only_secure_ws_connections: bool,
     tls: Option<&TlsConfig>,
+    tls_handle: &TlsConfigHandle,
 ) -> std::io::Result<Boxed<(PeerId, StreamMuxerBox)>> {
     let yamux = yamux::Config::default();
 
@@ -207,18 +279,13 @@ fn new_transport(
         // Configure TLS if the configuration has the corresponding entry
         #[cfg(feature = "tokio-websocket")]
         if let Some(tls) = tls {
-            let priv_key = websocket::tls::PrivateKey::new(tls.private_key.clone());
-            let certificates: Vec<_> = tls
-                .certificates
-                .clone()
-                .into_iter()
-                .map(websocket::tls::Certificate::new)
-                .collect();
-            transport.set_tls_config(websocket::tls::Config::new(priv_key, certificates).unwrap());
+            transport.set_tls_config(tls_reload_transport::websocket_tls_config(tls).unwrap());
         }
+        #[cfg(feature = "tokio-websocket")]
+        let transport = tls_reload_transport::Transport::new(transport, tls_handle.clone());
 
         #[cfg(not(feature = "tokio-websocket"))]
-        let _ = tls; // silence unused variable warning
+        let _ = (tls, tls_handle); // silence unused variable warning
 
         #[cfg(feature = "tokio-websocket")]
         let transport = transport.or_transport(MemoryTransport::default());
@@ -250,15 +317,13 @@ fn new_transport(
         // Configure TLS if the configuration has the corresponding entry
         #[cfg(feature = "tokio-websocket")]
         if let Some(tls) = tls {
-            let priv_key = websocket::tls::PrivateKey::new(tls.private_key.clone());
-            let certificates: Vec<_> = tls
-                .certificates
-                .clone()
-                .into_iter()
-                .map(websocket::tls::Certificate::new)
-                .collect();
-            transport.set_tls_config(websocket::tls::Config::new(priv_key, certificates).unwrap());
+            transport.set_tls_config(tls_reload_transport::websocket_tls_config(tls).unwrap());
         }
+        #[cfg(feature = "tokio-websocket")]
+        let transport = tls_reload_transport::Transport::new(transport, tls_handle.clone());
+
+        #[cfg(not(feature = "tokio-websocket"))]
+        let _ = (tls, tls_handle); // silence unused variable warning
 
         #[cfg(all(target_family = "wasm", not(feature = "tokio-websocket")))]
         let transport = websocket_websys::Transport::default();
@@ -304,6 +369,12 @@ fn handle_event(event: SwarmEvent<behaviour::BehaviourEvent>, event_info: EventI
                 "Connection established",
             );
 
+            event_info
+                .rate_limiting
+                .add_peer_address(peer_id, endpoint.get_remote_address());
+
+            listen_on_sentry(peer_id, event_info.swarm, event_info.state);
+
             if let Some(dial_errors) = concurrent_dial_errors {
                 for (addr, error) in dial_errors {
                     trace!(%peer_id, address = %addr, %error, "Removing addresses that caused dial failures");
@@ -403,24 +474,52 @@ fn handle_event(event: SwarmEvent<behaviour::BehaviourEvent>, event_info: EventI
                 .behaviour_mut()
                 .discovery
                 .add_own_addresses([address.clone()].to_vec());
-            if event_info.swarm.behaviour().is_address_dialable(&address) {
+            // Addresses on relays are reachable by definition and must not influence the NAT status.
+            if event_info.swarm.behaviour().is_address_dialable(&address)
+                && !is_relayed_address(&address)
+            {
                 event_info.state.nat_status.add_address(address);
+                handle_nat_status_change(event_info);
             }
         }
 
-        SwarmEvent::ListenerClosed {
+        SwarmEvent::ExpiredListenAddr {
             listener_id: _,
+            address,
+        } => {
+            debug!(%address, "Expired listen address");
+            remove_own_address(address, event_info.swarm, event_info.state);
+            handle_nat_status_change(event_info);
+        }
+
+        SwarmEvent::ListenerClosed {
+            listener_id,
             addresses,
-            reason: _,
+            reason,
         } => {
-            addresses.iter().for_each(|address| {
-                event_info.state.nat_status.remove_address(address);
-            });
+            debug!(%listener_id, ?addresses, ?reason, "Listener closed");
+            event_info
+                .state
+                .listeners
+                .retain(|_, id| *id != listener_id);
+            event_info
+                .state
+                .relay_listeners
+                .retain(|id| *id != listener_id);
+            event_info
+                .state
+                .sentry_listeners
+                .retain(|_, id| *id != listener_id);
+            for address in addresses {
+                remove_own_address(address, event_info.swarm, event_info.state);
+            }
+            handle_nat_status_change(event_info);
         }
 
         SwarmEvent::ExternalAddrConfirmed { address } => {
             log::trace!(%address, "Address is confirmed and externally reachable");
             event_info.state.nat_status.add_confirmed_address(address);
+            handle_nat_status_change(event_info);
         }
 
         SwarmEvent::ExternalAddrExpired { address } => {
@@ -429,6 +528,7 @@ fn handle_event(event: SwarmEvent<behaviour::BehaviourEvent>, event_info: EventI
                 .state
                 .nat_status
                 .remove_confirmed_address(&address);
+            handle_nat_status_change(event_info);
         }
 
         SwarmEvent::Behaviour(event) => handle_behaviour_event(event, event_info),
@@ -437,6 +537,83 @@ fn handle_event(event: SwarmEvent<behaviour::BehaviourEvent>, event_info: EventI
     }
 }
 
+/// Stops advertising an address we no longer listen on and stops tracking its NAT status.
+fn remove_own_address(address: Multiaddr, swarm: &mut NimiqSwarm, state: &mut TaskState) {
+    swarm
+        .behaviour_mut()
+        .discovery
+        .remove_own_addresses(vec![address.clone()]);
+    swarm.remove_external_address(&address);
+    state.nat_status.remove_address(&address);
+}
+
+/// Listens on a relay circuit through the given peer if it is one of our sentries and we don't
+/// listen on it yet. This is how we stay reachable behind sentries, thus these listeners are kept
+/// regardless of our NAT status and are re-established whenever we reconnect to the sentry.
+fn listen_on_sentry(peer_id: PeerId, swarm: &mut NimiqSwarm, state: &mut TaskState) {
+    let Some(sentry) = state.sentries.get(&peer_id) else {
+        return;
+    };
+    if state.sentry_listeners.contains_key(&peer_id) {
+        return;
+    }
+    match swarm.listen_on(sentry.clone().with(Protocol::P2pCircuit)) {
+        Ok(listener_id) => {
+            debug!(%sentry, "Listening on sentry");
+            state.sentry_listeners.insert(peer_id, listener_id);
+        }
+        Err(error) => warn!(%sentry, %error, "Failed to listen on sentry"),
+    }
+}
+
+/// Reports changes of the reachability of the local peer to the network event subscribers.
+/// Once none of our addresses are publicly reachable, we listen on the configured relays so
+/// that other peers can still connect to us. The relays are left again once we are publicly
+/// reachable.
+fn handle_nat_status_change(event_info: EventInfo) {
+    let reachability = match event_info.state.nat_status.status() {
+        NatStatus::Public => Reachability::Public,
+        NatStatus::Private => Reachability::Private,
+        NatStatus::Unknown => return,
+    };
+    if event_info.state.reported_reachability == Some(reachability) {
+        return;
+    }
+    event_info.state.reported_reachability = Some(reachability);
+    let _ = event_info
+        .events_tx
+        .send(NetworkEvent::ReachabilityChanged(reachability));
+
+    match reachability {
+        Reachability::Private => {
+            for relay in &event_info.state.relays {
+                if !relay
+                    .iter()
+                    .any(|protocol| matches!(protocol, Protocol::P2p(_)))
+                {
+                    debug!(%relay, "Not listening on relay without a peer ID in its address");
+                    continue;
+                }
+                match event_info
+                    .swarm
+                    .listen_on(relay.clone().with(Protocol::P2pCircuit))
+                {
+                    Ok(listener_id) => {
+                        debug!(%relay, "Listening on relay");
+                        event_info.state.relay_listeners.push(listener_id);
+                    }
+                    Err(error) => warn!(%relay, %error, "Failed to listen on relay"),
+                }
+            }
+        }
+        Reachability::Public => {
+            for listener_id in event_info.state.relay_listeners.drain(..) {
+                event_info.swarm.remove_listener(listener_id);
+            }
+        }
+    }
+}
+
 fn handle_behaviour_event(event: behaviour::BehaviourEvent, event_info: EventInfo) {
     match event {
         behaviour::BehaviourEvent::AutonatClient(event) => {
@@ -445,6 +622,8 @@ fn handle_behaviour_event(event: behaviour::BehaviourEvent, event_info: EventInf
         behaviour::BehaviourEvent::AutonatServer(event) => {
             handle_autonat_server_event(event, event_info)
         }
+        behaviour::BehaviourEvent::RelayClient(event) => handle_relay_client_event(event),
+        behaviour::BehaviourEvent::RelayServer(event) => handle_relay_server_event(event),
         behaviour::BehaviourEvent::ConnectionLimits(event) => match event {},
         behaviour::BehaviourEvent::Pool(event) => match event {},
         #[cfg(feature = "kad")]
@@ -470,12 +649,56 @@ fn handle_autonat_client_event(event: autonat::v2::client::Event, event_info: Ev
             .nat_status
             .set_address_nat(event.tested_addr, NatStatus::Private),
     }
+    handle_nat_status_change(event_info);
 }
 
 fn handle_autonat_server_event(event: autonat::v2::server::Event, _event_info: EventInfo) {
     log::trace!(?event, "AutoNAT inbound probe");
 }
 
+fn handle_relay_client_event(event: relay::client::Event) {
+    match event {
+        relay::client::Event::ReservationReqAccepted {
+            relay_peer_id,
+            renewal,
+            ..
+        } => {
+            if !renewal {
+                info!(%relay_peer_id, "Reservation on relay accepted, reachable through the relay");
+            }
+        }
+        relay::client::Event::OutboundCircuitEstablished { relay_peer_id, .. } => {
+            trace!(%relay_peer_id, "Outbound circuit through relay established");
+        }
+        relay::client::Event::InboundCircuitEstablished { src_peer_id, .. } => {
+            trace!(%src_peer_id, "Inbound circuit through relay established");
+        }
+    }
+}
+
+fn handle_relay_server_event(event: relay::Event) {
+    match event {
+        relay::Event::ReservationReqAccepted {
+            src_peer_id,
+            renewed,
+        } => {
+            if !renewed {
+                debug!(%src_peer_id, "Accepted reservation, relaying circuits to the peer");
+            }
+        }
+        relay::Event::ReservationTimedOut { src_peer_id } => {
+            debug!(%src_peer_id, "Reservation timed out");
+        }
+        relay::Event::CircuitReqDenied {
+            src_peer_id,
+            dst_peer_id,
+        } => {
+            debug!(%src_peer_id, %dst_peer_id, "Denied circuit request");
+        }
+        event => trace!(?event, "Relay server event"),
+    }
+}
+
 #[cfg(feature = "kad")]
 fn handle_dht_event(event: kad::Event, event_info: EventInfo) {
     match event {
@@ -493,6 +716,20 @@ fn handle_dht_event(event: kad::Event, event_info: EventInfo) {
             step,
         } => handle_dht_put_record(id, result, stats, step, event_info),
 
+        kad::Event::OutboundQueryProgressed {
+            id,
+            result: QueryResult::StartProviding(result),
+            stats,
+            step,
+        } => handle_dht_start_providing(id, result, stats, step, event_info),
+
+        kad::Event::OutboundQueryProgressed {
+            id,
+            result: QueryResult::GetProviders(result),
+            stats,
+            step,
+        } => handle_dht_get_providers(id, result, stats, step, event_info),
+
         kad::Event::OutboundQueryProgressed {
             id,
             result: QueryResult::Bootstrap(result),
@@ -509,6 +746,13 @@ fn handle_dht_event(event: kad::Event, event_info: EventInfo) {
                 },
         } => handle_dht_inbound_put(source, connection, record, event_info),
 
+        kad::Event::InboundRequest {
+            request:
+                InboundRequest::AddProvider {
+                    record: Some(record),
+                },
+        } => handle_dht_inbound_add_provider(record, event_info),
+
         kad::Event::ModeChanged { new_mode } => handle_dht_mode_change(new_mode, event_info),
 
         _ => {}
@@ -578,6 +822,20 @@ fn handle_dht_get(
                 return;
             };
 
+            // Validators behind sentries are only reachable through the addresses of their record.
+            // Only verified records are kept as results, so the addresses are signed by the
+            // validator.
+            if let DhtRecord::Validator(_, validator_record, _) = &results.best_value {
+                event_info
+                    .swarm
+                    .behaviour_mut()
+                    .pool
+                    .set_validator_addresses(
+                        validator_record.peer_id,
+                        validator_record.addresses.clone(),
+                    );
+            }
+
             let signed_best_record = results.best_value.clone().get_signed_record();
             // Send the best result to the application layer
             if let Some(output) = event_info.state.dht_gets.remove(&id) {
@@ -642,6 +900,69 @@ fn handle_dht_put_record(
     }
 }
 
+#[cfg(feature = "kad")]
+fn handle_dht_start_providing(
+    id: QueryId,
+    result: Result<AddProviderOk, AddProviderError>,
+    _stats: QueryStats,
+    _step: ProgressStep,
+    event_info: EventInfo,
+) {
+    // dht_start_providing resolved
+    if let Some(output) = event_info.state.dht_start_providing.remove(&id) {
+        if output.send(result.map(|_| ()).map_err(Into::into)).is_err() {
+            error!(query_id = ?id, error = "receiver hung up", "could not send start providing query result to channel");
+        }
+    }
+    // Otherwise this is a republishing of a provider record triggered by kad itself.
+}
+
+#[cfg(feature = "kad")]
+fn handle_dht_get_providers(
+    id: QueryId,
+    result: Result<GetProvidersOk, GetProvidersError>,
+    _stats: QueryStats,
+    step: ProgressStep,
+    event_info: EventInfo,
+) {
+    match result {
+        Ok(GetProvidersOk::FoundProviders { providers, .. }) => {
+            if let Some((_, found_providers)) = event_info.state.dht_get_providers.get_mut(&id) {
+                found_providers.extend(providers);
+            }
+        }
+        Ok(GetProvidersOk::FinishedWithNoAdditionalRecord { .. }) => {}
+        Err(error) => {
+            // Report the providers found so far, if any, since the query only fails on a timeout.
+            if let Some((output, found_providers)) = event_info.state.dht_get_providers.remove(&id)
+            {
+                let result = if found_providers.is_empty() {
+                    Err(error.into())
+                } else {
+                    Ok(found_providers.into_iter().collect())
+                };
+                if output.send(result).is_err() {
+                    error!(query_id = ?id, error = "receiver hung up", "could not send get providers query result to channel");
+                }
+            }
+            return;
+        }
+    }
+
+    if step.last {
+        if let Some((output, found_providers)) = event_info.state.dht_get_providers.remove(&id) {
+            if output
+                .send(Ok(found_providers.into_iter().collect()))
+                .is_err()
+            {
+                error!(query_id = ?id, error = "receiver hung up", "could not send get providers query result to channel");
+            }
+        } else {
+            warn!(query_id = ?id, ?step, "GetProviders query result for unknown query ID");
+        }
+    }
+}
+
 #[cfg(feature = "kad")]
 fn handle_dht_bootstrap(
     _id: QueryId,
@@ -685,7 +1006,8 @@ fn handle_dht_inbound_put(
     let mut overwrite = true;
     let store = event_info.swarm.behaviour_mut().dht.store_mut();
     if let Some(current_record) = store.get(&record.key) {
-        if let Ok(current_dht_record) = DhtRecord::try_from(&current_record.into_owned()) {
+        // Records of registered types can only be decoded by the verifier.
+        if let Ok(current_dht_record) = event_info.dht_verifier.verify(&current_record) {
             if current_dht_record > dht_record {
                 overwrite = false;
             }
@@ -696,6 +1018,31 @@ fn handle_dht_inbound_put(
     }
 }
 
+#[cfg(feature = "kad")]
+fn handle_dht_inbound_add_provider(mut record: ProviderRecord, event_info: EventInfo) {
+    // Provider records are filtered as well, so we need to store them ourselves. Only the peer
+    // of the verified record stored under the same key may announce that it provides the key,
+    // such that peers can't fill our store with provider records for arbitrary keys.
+    let store = event_info.swarm.behaviour_mut().dht.store_mut();
+    let provider = store
+        .get(&record.key)
+        .and_then(|current_record| event_info.dht_verifier.verify(&current_record).ok())
+        .map(|current_dht_record| current_dht_record.get_peer_id());
+    if provider != Some(record.provider) {
+        debug!(
+            key = ?record.key,
+            provider = %record.provider,
+            "Rejecting provider record without a matching DHT record"
+        );
+        return;
+    }
+
+    record.addresses.truncate(VALIDATOR_RECORD_MAX_ADDRESSES);
+    if store.add_provider(record).is_err() {
+        error!("Could not store provider record in DHT record store");
+    }
+}
+
 #[cfg(feature = "kad")]
 fn handle_dht_mode_change(new_mode: Mode, event_info: EventInfo) {
     debug!(%new_mode, "DHT mode changed");
@@ -755,11 +1102,15 @@ fn handle_gossipsup_event(event: gossipsub::Event, event_info: EventInfo) {
                 return;
             };
 
-            if event_info.rate_limiting.exceeds_rate_limit(
-                propagation_source,
-                RateLimitId::Gossipsub(topic.clone()),
-                &topic_info.rate_limit_config,
-            ) {
+            if event_info
+                .rate_limiting
+                .exceeds_rate_limit(
+                    propagation_source,
+                    RateLimitId::Gossipsub(topic.clone()),
+                    &topic_info.rate_limit_config,
+                )
+                .is_some()
+            {
                 debug!(
                     %topic,
                     peer_id = %propagation_source,
@@ -822,7 +1173,7 @@ fn handle_ping_event(event: ping::Event, event_info: EventInfo) {
 }
 
 fn handle_request_response_event(
-    event: request_response::Event<Option<Vec<u8>>, Option<Vec<u8>>>,
+    event: request_response::Event<Option<CodecRequest>, Option<Vec<u8>>>,
     event_info: EventInfo,
 ) {
     match event {
@@ -850,19 +1201,23 @@ fn handle_request_response_event(
             request_id,
             error,
         } => handle_request_response_inbound_failure(peer_id, request_id, error, event_info),
-        request_response::Event::ResponseSent { .. } => {}
+        request_response::Event::ResponseSent { request_id, .. } => {
+            if let Some(peer_id) = event_info.state.priority_responses.remove(&request_id) {
+                finish_priority_traffic(&peer_id, event_info.swarm, event_info.state);
+            }
+        }
     }
 }
 
 fn handle_request_response_request(
     peer_id: PeerId,
     request_id: InboundRequestId,
-    request: Option<Vec<u8>>,
+    request: Option<CodecRequest>,
     channel: ResponseChannel<Option<Vec<u8>>>,
     event_info: EventInfo,
 ) {
     // We might get empty requests (None) because of our codec implementation
-    let Some(request) = request else {
+    let Some(request) = request.map(|request| request.data) else {
         return;
     };
 
@@ -880,16 +1235,19 @@ fn handle_request_response_request(
         .filter(|(sender, ..)| !sender.is_closed());
 
     // If we have a receiver, pass the request. Otherwise send a default empty response
-    if let Some((sender, rate_limit_config)) = sender_data {
-        if event_info.rate_limiting.exceeds_rate_limit(
+    if let Some((sender, rate_limit_config, priority)) = sender_data {
+        if let Some(scope) = event_info.rate_limiting.exceeds_rate_limit(
             peer_id,
             RateLimitId::Request(type_id),
             rate_limit_config,
         ) {
+            #[cfg(feature = "metrics")]
+            event_info.metrics.note_rate_limited_request(scope);
             debug!(
                 %type_id,
                 %request_id,
                 %peer_id,
+                %scope,
                 max_requests = %rate_limit_config.max_requests,
                 time_window = ?rate_limit_config.time_window,
                 "Denied request - exceeded max requests rate",
@@ -911,7 +1269,14 @@ fn handle_request_response_request(
                 event_info
                     .state
                     .response_channels
-                    .insert(request_id, channel);
+                    .insert(request_id, (peer_id, channel));
+                if *priority == Priority::Consensus {
+                    event_info.state.priority_lanes.start(peer_id);
+                    event_info
+                        .state
+                        .priority_responses
+                        .insert(request_id, peer_id);
+                }
             } else {
                 // Respond on behalf of the actual receiver because the actual receiver isn't interested in responding.
                 let response: Result<(), InboundRequestError> = Ok(());
@@ -953,6 +1318,10 @@ fn handle_request_response_response(
     response: Option<Vec<u8>>,
     event_info: EventInfo,
 ) {
+    if let Some(peer_id) = event_info.state.priority_requests.remove(&request_id) {
+        finish_priority_traffic(&peer_id, event_info.swarm, event_info.state);
+    }
+
     let Some(channel) = event_info.state.requests.remove(&request_id) else {
         debug!(%request_id, "No request found for response");
         return;
@@ -979,13 +1348,21 @@ fn handle_request_response_outbound_failure(
     error: OutboundFailure,
     event_info: EventInfo,
 ) {
-    error!(%request_id, %peer_id, %error, "Failed to send request to peer");
+    #[cfg(feature = "metrics")]
+    event_info.state.requests_initiated.remove(&request_id);
+    if let Some(peer_id) = event_info.state.priority_requests.remove(&request_id) {
+        finish_priority_traffic(&peer_id, event_info.swarm, event_info.state);
+    }
 
+    // Requests that were cancelled by their initiator are no longer tracked. Their failure is
+    // expected since aborting a request closes its stream.
     let Some(channel) = event_info.state.requests.remove(&request_id) else {
-        debug!(%request_id, %peer_id, "No request found for outbound failure");
+        debug!(%request_id, %peer_id, %error, "No request found for outbound failure");
         return;
     };
 
+    error!(%request_id, %peer_id, %error, "Failed to send request to peer");
+
     // The request initiator might no longer exist, so silently ignore
     // any errors while delivering the response.
     channel.send(Err(to_response_error(error))).ok();
@@ -995,12 +1372,49 @@ fn handle_request_response_inbound_failure(
     peer_id: PeerId,
     request_id: InboundRequestId,
     error: InboundFailure,
-    _event_info: EventInfo,
+    event_info: EventInfo,
 ) {
     error!(%request_id, %peer_id, %error, "Inbound request failed");
+
+    if let Some(peer_id) = event_info.state.priority_responses.remove(&request_id) {
+        finish_priority_traffic(&peer_id, event_info.swarm, event_info.state);
+    }
 }
 
-fn perform_action(action: NetworkAction, swarm: &mut NimiqSwarm, state: &mut TaskState) {
+/// Schedules an action of the background lane. Requests and responses to peers with consensus
+/// traffic in flight are held back, see `PriorityLanes`. Returns the actions to perform now.
+fn schedule_background_action(action: NetworkAction, state: &mut TaskState) -> Vec<NetworkAction> {
+    let peer_id = match &action {
+        NetworkAction::SendRequest { peer_id, .. } => Some(*peer_id),
+        NetworkAction::SendResponse { request_id, .. } => state
+            .response_channels
+            .get(request_id)
+            .map(|(peer_id, _)| *peer_id),
+        _ => None,
+    };
+
+    match peer_id {
+        Some(peer_id) => state
+            .priority_lanes
+            .schedule(peer_id, action, Instant::now()),
+        None => vec![action],
+    }
+}
+
+/// Notes that consensus traffic to or from the given peer completed and performs the background
+/// actions that were held back meanwhile.
+fn finish_priority_traffic(peer_id: &PeerId, swarm: &mut NimiqSwarm, state: &mut TaskState) {
+    for action in state.priority_lanes.finish(peer_id) {
+        perform_action(action, Priority::Background, swarm, state);
+    }
+}
+
+fn perform_action(
+    action: NetworkAction,
+    priority: Priority,
+    swarm: &mut NimiqSwarm,
+    state: &mut TaskState,
+) {
     match action {
         NetworkAction::Dial { peer_id, output } => {
             let dial_opts = DialOpts::peer_id(peer_id)
@@ -1021,6 +1435,11 @@ fn perform_action(action: NetworkAction, swarm: &mut NimiqSwarm, state: &mut Tas
             let query_id = swarm.behaviour_mut().dht.get_record(key.into());
             #[cfg(feature = "kad")]
             state.dht_gets.insert(query_id, output);
+            #[cfg(not(feature = "kad"))]
+            {
+                let _ = key;
+                output.send(Err(NetworkError::DhtDisabled)).ok();
+            }
         }
         NetworkAction::DhtPut { key, value, output } => {
             let local_peer_id = Swarm::local_peer_id(swarm);
@@ -1042,6 +1461,45 @@ fn perform_action(action: NetworkAction, swarm: &mut NimiqSwarm, state: &mut Tas
                     output.send(Err(e.into())).ok();
                 }
             }
+            #[cfg(not(feature = "kad"))]
+            {
+                let _ = record;
+                output.send(Err(NetworkError::DhtDisabled)).ok();
+            }
+        }
+        NetworkAction::DhtStartProviding { key, output } => {
+            #[cfg(feature = "kad")]
+            match swarm.behaviour_mut().dht.start_providing(key.into()) {
+                Ok(query_id) => {
+                    // Remember the operation to resolve when we receive a `QueryResult::StartProviding`
+                    state.dht_start_providing.insert(query_id, output);
+                }
+                Err(e) => {
+                    output.send(Err(e.into())).ok();
+                }
+            }
+            #[cfg(not(feature = "kad"))]
+            {
+                let _ = key;
+                output.send(Err(NetworkError::DhtDisabled)).ok();
+            }
+        }
+        NetworkAction::DhtStopProviding { key } => {
+            #[cfg(feature = "kad")]
+            swarm.behaviour_mut().dht.stop_providing(&key.into());
+        }
+        NetworkAction::DhtGetProviders { key, output } => {
+            #[cfg(feature = "kad")]
+            let query_id = swarm.behaviour_mut().dht.get_providers(key.into());
+            #[cfg(feature = "kad")]
+            state
+                .dht_get_providers
+                .insert(query_id, (output, HashSet::new()));
+            #[cfg(not(feature = "kad"))]
+            {
+                let _ = key;
+                output.send(Err(NetworkError::DhtDisabled)).ok();
+            }
         }
         NetworkAction::Subscribe {
             topic_name,
@@ -1159,35 +1617,56 @@ fn perform_action(action: NetworkAction, swarm: &mut NimiqSwarm, state: &mut Tas
             type_id,
             output,
             rate_limit_config,
+            priority,
         } => {
             state
                 .receive_requests
-                .insert(type_id, (output, rate_limit_config));
+                .insert(type_id, (output, rate_limit_config, priority));
         }
         NetworkAction::SendRequest {
             peer_id,
             request,
+            abort_registration,
             response_channel,
             output,
         } => {
-            let request_id = swarm
-                .behaviour_mut()
-                .request_response
-                .send_request(&peer_id, Some(request));
+            let request_id = swarm.behaviour_mut().request_response.send_request(
+                &peer_id,
+                Some(CodecRequest {
+                    data: request,
+                    abort_registration: Some(abort_registration),
+                }),
+            );
 
             state.requests.insert(request_id, response_channel);
             #[cfg(feature = "metrics")]
             state.requests_initiated.insert(request_id, Instant::now());
+            if priority == Priority::Consensus {
+                state.priority_lanes.start(peer_id);
+                state.priority_requests.insert(request_id, peer_id);
+            }
 
             // The request initiator might no longer exist, so we silently ignore any errors here.
             output.send(request_id).ok();
         }
+        NetworkAction::CancelRequest { request_id } => {
+            // The initiator already aborted the request, which closes its stream. The failure
+            // libp2p reports for it later on is ignored.
+            if state.requests.remove(&request_id).is_some() {
+                trace!(%request_id, "Request cancelled");
+            }
+            #[cfg(feature = "metrics")]
+            state.requests_initiated.remove(&request_id);
+            if let Some(peer_id) = state.priority_requests.remove(&request_id) {
+                finish_priority_traffic(&peer_id, swarm, state);
+            }
+        }
         NetworkAction::SendResponse {
             request_id,
             response,
             output,
         } => {
-            let Some(response_channel) = state.response_channels.remove(&request_id) else {
+            let Some((_, response_channel)) = state.response_channels.remove(&request_id) else {
                 error!(%request_id, "Tried to respond to a non existing request");
                 // The request initiator might no longer exist, so we silently ignore any errors here.
                 output.send(Err(NetworkError::UnknownRequestId)).ok();
@@ -1205,9 +1684,61 @@ fn perform_action(action: NetworkAction, swarm: &mut NimiqSwarm, state: &mut Tas
         }
         NetworkAction::ListenOn { listen_addresses } => {
             for listen_address in listen_addresses {
-                Swarm::listen_on(swarm, listen_address)
+                let listener_id = Swarm::listen_on(swarm, listen_address.clone())
                     .expect("Failed to listen on provided address");
+                state.listeners.insert(listen_address, listener_id);
+            }
+        }
+        NetworkAction::AddListenAddresses {
+            listen_addresses,
+            output,
+        } => {
+            let result = listen_addresses
+                .into_iter()
+                .filter(|listen_address| !state.listeners.contains_key(listen_address))
+                .try_for_each(|listen_address| {
+                    let listener_id = Swarm::listen_on(swarm, listen_address.clone())?;
+                    info!(address = %listen_address, "Listening on new address");
+                    state.listeners.insert(listen_address, listener_id);
+                    Ok(())
+                });
+            output.send(result).ok();
+        }
+        NetworkAction::RemoveListenAddresses {
+            listen_addresses,
+            output,
+        } => {
+            // Don't remove any listener if one of the addresses is unknown.
+            if let Some(unknown) = listen_addresses
+                .iter()
+                .find(|listen_address| !state.listeners.contains_key(listen_address))
+            {
+                output
+                    .send(Err(NetworkError::UnknownListenAddress(unknown.clone())))
+                    .ok();
+                return;
+            }
+
+            for listen_address in listen_addresses {
+                let listener_id = state.listeners.remove(&listen_address).unwrap();
+                // The advertised addresses and the NAT state are updated once the
+                // `ListenerClosed` event is emitted. Established connections are kept.
+                swarm.remove_listener(listener_id);
+                info!(address = %listen_address, "Stopped listening on address");
             }
+            output.send(Ok(())).ok();
+        }
+        NetworkAction::UpdateTlsConfig { tls, output } => {
+            #[cfg(feature = "tokio-websocket")]
+            let result = tls_reload_transport::websocket_tls_config(&tls)
+                .map(|_| state.tls_handle.replace(tls))
+                .map_err(|error| NetworkError::InvalidTlsConfig(error.to_string()));
+            #[cfg(not(feature = "tokio-websocket"))]
+            let result = {
+                let _ = tls;
+                Err(NetworkError::TlsNotSupported)
+            };
+            output.send(result).ok();
         }
         NetworkAction::StartConnecting => {
             swarm.behaviour_mut().pool.start_connecting();
@@ -0,0 +1,122 @@
+use std::sync::Arc;
+#[cfg(feature = "tokio-websocket")]
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+#[cfg(feature = "tokio-websocket")]
+use libp2p::{
+    core::transport::{DialOpts, ListenerId, TransportError, TransportEvent},
+    multiaddr::Multiaddr,
+    websocket,
+};
+use parking_lot::Mutex;
+
+use crate::TlsConfig;
+
+/// Shared slot through which a new TLS configuration is handed over to a running [`Transport`].
+#[derive(Clone, Default)]
+pub(crate) struct TlsConfigHandle(Arc<Mutex<Option<TlsConfig>>>);
+
+impl TlsConfigHandle {
+    /// Schedules the TLS configuration to be used for all connections established from now on.
+    /// Established connections keep using the configuration they were upgraded with.
+    pub(crate) fn replace(&self, tls: TlsConfig) {
+        *self.0.lock() = Some(tls);
+    }
+
+    #[cfg_attr(not(feature = "tokio-websocket"), allow(dead_code))]
+    fn take(&self) -> Option<TlsConfig> {
+        self.0.lock().take()
+    }
+}
+
+/// Converts the TLS settings into the configuration used by the WebSocket transport.
+#[cfg(feature = "tokio-websocket")]
+pub(crate) fn websocket_tls_config(
+    tls: &TlsConfig,
+) -> Result<websocket::tls::Config, websocket::tls::Error> {
+    let priv_key = websocket::tls::PrivateKey::new(tls.private_key.clone());
+    let certificates: Vec<_> = tls
+        .certificates
+        .clone()
+        .into_iter()
+        .map(websocket::tls::Certificate::new)
+        .collect();
+    websocket::tls::Config::new(priv_key, certificates)
+}
+
+/// Wraps a WebSocket transport such that its TLS configuration can be replaced at runtime
+/// without closing any listener, e.g. to rotate certificates.
+#[cfg(feature = "tokio-websocket")]
+pub(crate) struct Transport<T> {
+    inner: websocket::WsConfig<T>,
+    handle: TlsConfigHandle,
+}
+
+#[cfg(feature = "tokio-websocket")]
+impl<T> Transport<T> {
+    pub(crate) fn new(transport: websocket::WsConfig<T>, handle: TlsConfigHandle) -> Self {
+        Transport {
+            inner: transport,
+            handle,
+        }
+    }
+
+    /// Applies a pending TLS configuration, if any.
+    fn update_tls_config(&mut self) {
+        let Some(tls) = self.handle.take() else {
+            return;
+        };
+        match websocket_tls_config(&tls) {
+            Ok(config) => {
+                self.inner.set_tls_config(config);
+                info!("Updated TLS configuration");
+            }
+            Err(error) => error!(%error, "Failed to update TLS configuration"),
+        }
+    }
+}
+
+#[cfg(feature = "tokio-websocket")]
+impl<T> libp2p::Transport for Transport<T>
+where
+    websocket::WsConfig<T>: libp2p::Transport + Unpin,
+{
+    type Output = <websocket::WsConfig<T> as libp2p::Transport>::Output;
+    type Error = <websocket::WsConfig<T> as libp2p::Transport>::Error;
+    type ListenerUpgrade = <websocket::WsConfig<T> as libp2p::Transport>::ListenerUpgrade;
+    type Dial = <websocket::WsConfig<T> as libp2p::Transport>::Dial;
+
+    fn listen_on(
+        &mut self,
+        id: ListenerId,
+        addr: Multiaddr,
+    ) -> Result<(), TransportError<Self::Error>> {
+        self.update_tls_config();
+        self.inner.listen_on(id, addr)
+    }
+
+    fn remove_listener(&mut self, id: ListenerId) -> bool {
+        self.inner.remove_listener(id)
+    }
+
+    fn dial(
+        &mut self,
+        addr: Multiaddr,
+        opts: DialOpts,
+    ) -> Result<Self::Dial, TransportError<Self::Error>> {
+        self.update_tls_config();
+        self.inner.dial(addr, opts)
+    }
+
+    fn poll(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<TransportEvent<Self::ListenerUpgrade, Self::Error>> {
+        // Incoming connections are upgraded with the configuration in place when they are polled.
+        self.update_tls_config();
+        Pin::new(&mut self.inner).poll(cx)
+    }
+}
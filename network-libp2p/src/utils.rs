@@ -1,7 +1,129 @@
-use libp2p::{multiaddr::Protocol, Multiaddr};
+use libp2p::{multiaddr::Protocol, Multiaddr, PeerId};
 
 /// Returns true if an address is a secure websocket connection.
 /// If address doesn't have the websocket protocol, it will return `false`.
 pub fn is_address_ws_secure(address: &Multiaddr) -> bool {
     address.into_iter().any(|p| matches!(p, Protocol::Wss(_)))
 }
+
+/// Returns whether the address is an address on a relay (i.e. contains a `/p2p-circuit`).
+pub fn is_relayed_address(address: &Multiaddr) -> bool {
+    address
+        .iter()
+        .any(|protocol| protocol == Protocol::P2pCircuit)
+}
+
+/// Returns the peer ID an address ends with, if any.
+pub fn peer_id_of_address(address: &Multiaddr) -> Option<PeerId> {
+    match address.iter().last() {
+        Some(Protocol::P2p(peer_id)) => Some(peer_id),
+        _ => None,
+    }
+}
+
+/// Returns the peer ID of the relay that an address on a relay goes through, if any.
+pub fn relay_peer_id_of_address(address: &Multiaddr) -> Option<PeerId> {
+    let mut relay_peer_id = None;
+    for protocol in address.iter() {
+        match protocol {
+            Protocol::P2p(peer_id) => relay_peer_id = Some(peer_id),
+            Protocol::P2pCircuit => return relay_peer_id,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Returns whether an address is plausibly an address of the given peer: It must not end with the
+/// peer ID of another peer, and an address on a relay must go through exactly one relay other than
+/// the peer itself.
+pub fn is_address_of_peer(address: &Multiaddr, peer_id: &PeerId) -> bool {
+    let ends_with_peer = match address.iter().last() {
+        Some(Protocol::P2p(last_peer_id)) => last_peer_id == *peer_id,
+        _ => true,
+    };
+    if !ends_with_peer {
+        return false;
+    }
+
+    match address
+        .iter()
+        .filter(|protocol| *protocol == Protocol::P2pCircuit)
+        .count()
+    {
+        0 => true,
+        1 => {
+            relay_peer_id_of_address(address).is_some_and(|relay_peer_id| relay_peer_id != *peer_id)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use libp2p::{multiaddr::Protocol, Multiaddr, PeerId};
+
+    use super::{is_address_of_peer, relay_peer_id_of_address};
+
+    fn relayed_address(relay_peer_id: PeerId) -> Multiaddr {
+        "/dns4/sentry.example.com/tcp/8443/wss"
+            .parse::<Multiaddr>()
+            .unwrap()
+            .with(Protocol::P2p(relay_peer_id))
+            .with(Protocol::P2pCircuit)
+    }
+
+    #[test]
+    fn it_accepts_addresses_of_the_peer() {
+        let peer_id = PeerId::random();
+        let relay_peer_id = PeerId::random();
+        let direct: Multiaddr = "/ip4/1.2.3.4/tcp/8443/ws".parse().unwrap();
+
+        assert!(is_address_of_peer(&direct, &peer_id));
+        assert!(is_address_of_peer(
+            &direct.clone().with(Protocol::P2p(peer_id)),
+            &peer_id
+        ));
+        assert!(is_address_of_peer(
+            &relayed_address(relay_peer_id),
+            &peer_id
+        ));
+        assert!(is_address_of_peer(
+            &relayed_address(relay_peer_id).with(Protocol::P2p(peer_id)),
+            &peer_id
+        ));
+        assert_eq!(
+            relay_peer_id_of_address(&relayed_address(relay_peer_id)),
+            Some(relay_peer_id)
+        );
+    }
+
+    #[test]
+    fn it_rejects_addresses_of_other_peers() {
+        let peer_id = PeerId::random();
+        let other_peer_id = PeerId::random();
+        let direct: Multiaddr = "/ip4/1.2.3.4/tcp/8443/ws".parse().unwrap();
+
+        assert!(!is_address_of_peer(
+            &direct.clone().with(Protocol::P2p(other_peer_id)),
+            &peer_id
+        ));
+        assert!(!is_address_of_peer(
+            &relayed_address(other_peer_id).with(Protocol::P2p(other_peer_id)),
+            &peer_id
+        ));
+        // The peer can't be its own relay and relays can't be chained.
+        assert!(!is_address_of_peer(&relayed_address(peer_id), &peer_id));
+        assert!(!is_address_of_peer(
+            &relayed_address(other_peer_id)
+                .with(Protocol::P2p(PeerId::random()))
+                .with(Protocol::P2pCircuit),
+            &peer_id
+        ));
+        // Circuit addresses must name their relay.
+        assert!(!is_address_of_peer(
+            &direct.clone().with(Protocol::P2pCircuit),
+            &peer_id
+        ));
+    }
+}
@@ -308,3 +308,53 @@ fn test_housekeeping() {
         .get(&old_contact.public_key().clone().to_peer_id())
         .is_none());
 }
+
+#[test]
+fn test_persisting_peer_contacts() {
+    let mut peer_contact_book = PeerContactBook::new(
+        random_peer_contact(1, Services::FULL_BLOCKS),
+        false,
+        true,
+        true,
+    );
+
+    let peer_contacts: Vec<SignedPeerContact> = (0..10)
+        .map(|i| random_peer_contact(i, Services::FULL_BLOCKS))
+        .collect();
+    peer_contact_book.insert_all(peer_contacts.clone());
+    let scored_peer_id = peer_contacts[0].peer_id();
+    peer_contact_book
+        .get(&scored_peer_id)
+        .unwrap()
+        .set_score(42.0);
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("peer-contacts.dat");
+    peer_contact_book.save_to_file(&path).unwrap();
+
+    // Load the contacts into a fresh contact book and check that all of them were restored
+    let mut restored_contact_book = PeerContactBook::new(
+        random_peer_contact(2, Services::FULL_BLOCKS),
+        false,
+        true,
+        true,
+    );
+    assert_eq!(restored_contact_book.load_from_file(&path).unwrap(), 10);
+    test_peers_in_contact_book(&restored_contact_book, &peer_contacts);
+    assert_eq!(
+        restored_contact_book
+            .get(&scored_peer_id)
+            .unwrap()
+            .get_score(),
+        42.0
+    );
+
+    // Loading again doesn't add any contacts, and a missing file is not an error
+    assert_eq!(restored_contact_book.load_from_file(&path).unwrap(), 0);
+    assert_eq!(
+        restored_contact_book
+            .load_from_file(&dir.path().join("missing.dat"))
+            .unwrap(),
+        0
+    );
+}
@@ -16,7 +16,7 @@ use nimiq_network_interface::{
 use nimiq_network_libp2p::{
     dht,
     discovery::{self, peer_contacts::PeerContact},
-    Config, Network,
+    Config, Network, NetworkError,
 };
 use nimiq_serde::{Deserialize, Serialize};
 use nimiq_test_log::test;
@@ -79,6 +79,9 @@ fn network_config(address: Multiaddr) -> Config {
         only_secure_ws_connections: false,
         allow_loopback_addresses: true,
         dht_quorum: NonZeroU8::new(1).unwrap(),
+        seeding: Default::default(),
+        peer_contacts_path: None,
+        relays: Vec::new(),
     }
 }
 
@@ -426,6 +429,46 @@ async fn two_networks_can_connect_double_dial() {
     assert_eq!(peer1, net1.get_local_peer_id());
 }
 
+#[test(tokio::test)]
+async fn listen_addresses_can_be_reconfigured() {
+    let mut rng = thread_rng();
+    let addr1 = multiaddr![Memory(rng.gen::<u64>())];
+    let addr2 = multiaddr![Memory(rng.gen::<u64>())];
+    let addr3 = multiaddr![Memory(rng.gen::<u64>())];
+
+    let net1 = Network::new(network_config(addr1.clone()), ()).await;
+    net1.listen_on(vec![addr1.clone()]).await;
+    net1.add_listen_addresses(vec![addr2.clone()])
+        .await
+        .unwrap();
+
+    // Peers can connect through the new address.
+    let net2 = Network::new(network_config(addr3.clone()), ()).await;
+    net2.listen_on(vec![addr3]).await;
+    let mut events1 = net1.subscribe_events();
+    net2.dial_address(addr2.clone()).await.unwrap();
+    let event1 = helper::get_next_peer_event(&mut events1).await;
+    helper::assert_peer_joined(&event1, &net2.get_local_peer_id());
+
+    // Nothing is removed if one of the addresses is unknown.
+    let unknown = multiaddr![Memory(rng.gen::<u64>())];
+    assert!(matches!(
+        net1.remove_listen_addresses(vec![addr2.clone(), unknown])
+            .await,
+        Err(NetworkError::UnknownListenAddress(_))
+    ));
+
+    // Established connections are kept when the listener is removed.
+    net1.remove_listen_addresses(vec![addr2.clone()])
+        .await
+        .unwrap();
+    assert!(net1.has_peer(net2.get_local_peer_id()));
+    assert!(matches!(
+        net1.remove_listen_addresses(vec![addr2]).await,
+        Err(NetworkError::UnknownListenAddress(_))
+    ));
+}
+
 #[test(tokio::test)]
 async fn connections_are_properly_closed_events() {
     let (net1, net2) = create_connected_networks().await;
@@ -500,7 +543,9 @@ async fn dht_put_and_get() {
     let put_record = ValidatorRecord {
         peer_id: net1.get_local_peer_id(),
         validator_address: key.clone(),
+        epoch_number: 0,
         timestamp: 0x42u64,
+        addresses: vec![],
     };
 
     assert!(keys.write().insert(key.clone(), keypair.public).is_none());
@@ -517,6 +562,35 @@ async fn dht_put_and_get() {
     assert_eq!(fetched_record, Some(put_record));
 }
 
+#[test(tokio::test)]
+async fn dht_providers() {
+    let (networks, keys) = create_network_with_n_peers(3).await;
+    let net1 = &networks[0];
+    let net2 = &networks[1];
+
+    // FIXME: Add delay while networks share their addresses
+    sleep(Duration::from_secs(10)).await;
+
+    let mut rng = test_rng(false);
+    let keypair = KeyPair::generate(&mut rng);
+    let key: Address = (&keypair.public).into();
+    assert!(keys.write().insert(key.clone(), keypair.public).is_none());
+
+    // Provider records are only accepted from the peer of the record stored under the same key.
+    let put_record = ValidatorRecord {
+        peer_id: net1.get_local_peer_id(),
+        validator_address: key.clone(),
+        epoch_number: 0,
+        timestamp: 0x42u64,
+        addresses: vec![],
+    };
+    net1.dht_put(&key, &put_record, &keypair).await.unwrap();
+    net1.dht_start_providing(&key).await.unwrap();
+
+    let providers = net2.dht_get_providers(&key).await.unwrap();
+    assert_eq!(providers, vec![net1.get_local_peer_id()]);
+}
+
 #[test(tokio::test)]
 async fn ban_peer() {
     let (net1, net2) = create_connected_networks().await;
@@ -17,7 +17,7 @@ use nimiq_network_interface::{
 };
 use nimiq_network_libp2p::{
     discovery::{self, peer_contacts::PeerContact},
-    Config, Network,
+    Config, Network, SeedingConfig,
 };
 use nimiq_serde::{Deserialize, Serialize};
 use nimiq_test_log::test;
@@ -103,17 +103,38 @@ struct TestResponse4 {
     response: u32,
 }
 
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+struct TestSeedingRequest {
+    request: u64,
+}
+impl RequestCommon for TestSeedingRequest {
+    type Kind = RequestMarker;
+    const TYPE_ID: u16 = 43;
+    type Response = TestResponse;
+
+    const MAX_REQUESTS: u32 = MAX_REQUEST_RESPONSE_TEST_REQUEST;
+    const SEEDING: bool = true;
+}
+
 #[derive(Clone, Debug)]
 struct TestNetwork {}
 
 impl TestNetwork {
     async fn create_connected_networks() -> (Network, Network) {
+        Self::create_connected_networks_with_seeding(Default::default()).await
+    }
+
+    /// Creates two connected networks, the first one serving seeding requests according to
+    /// `seeding`.
+    async fn create_connected_networks_with_seeding(seeding: SeedingConfig) -> (Network, Network) {
         log::debug!("Creating connected test networks");
         let mut rng = thread_rng();
         let addr1 = multiaddr![Memory(rng.gen::<u64>())];
         let addr2 = multiaddr![Memory(rng.gen::<u64>())];
 
-        let net1 = Network::new(network_config(addr1.clone()), ()).await;
+        let mut config1 = network_config(addr1.clone());
+        config1.seeding = seeding;
+        let net1 = Network::new(config1, ()).await;
         net1.listen_on(vec![addr1.clone()]).await;
 
         let net2 = Network::new(network_config(addr2.clone()), ()).await;
@@ -253,6 +274,9 @@ fn network_config(address: Multiaddr) -> Config {
         only_secure_ws_connections: false,
         allow_loopback_addresses: true,
         dht_quorum: NonZeroU8::new(1).unwrap(),
+        seeding: Default::default(),
+        peer_contacts_path: None,
+        relays: Vec::new(),
     }
 }
 
@@ -494,6 +518,66 @@ async fn test_valid_request_no_response_no_receiver() {
     };
 }
 
+// Test that aborting a request closes its stream right away instead of waiting for the request
+// to time out, and that the connection can still be used for other requests.
+#[test(tokio::test)]
+async fn test_aborted_request_fails_immediately() {
+    let (net1, net2) = TestNetwork::create_connected_networks().await;
+
+    let test_response = TestResponse { response: 43 };
+
+    // Only respond to the second request.
+    let net1 = Arc::new(net1);
+    let request_stream = net1.receive_requests::<TestRequest>();
+    spawn({
+        let net1 = Arc::clone(&net1);
+        let test_response = test_response.clone();
+        request_stream.for_each(move |(request, request_id, _peer_id)| {
+            let net1 = Arc::clone(&net1);
+            let test_response = test_response.clone();
+            async move {
+                if request.request == 2 {
+                    let result = net1.respond::<TestRequest>(request_id, test_response).await;
+                    assert!(result.is_ok());
+                }
+            }
+        })
+    });
+
+    sleep(Duration::from_secs(1)).await;
+
+    log::info!("Sending request to be aborted");
+    let (response, abort_handle) = net2.request_with_abort_handle::<TestRequest>(
+        TestRequest { request: 1 },
+        net1.get_local_peer_id(),
+    );
+    let response = tokio::spawn(response);
+
+    sleep(Duration::from_secs(1)).await;
+    let aborted_at = Instant::now();
+    abort_handle.abort();
+
+    let received_response = response.await.unwrap();
+    log::info!(response = ?received_response, "Received response");
+    assert!(received_response.is_err());
+    // The request timeout is 10 seconds.
+    assert!(aborted_at.elapsed() < Duration::from_secs(5));
+
+    log::info!("Sending request to be answered");
+    let received_response = net2
+        .request::<TestRequest>(TestRequest { request: 2 }, net1.get_local_peer_id())
+        .await;
+    assert_eq!(received_response, Ok(test_response.clone()));
+
+    // Aborting a completed request has no effect.
+    let (response, abort_handle) = net2.request_with_abort_handle::<TestRequest>(
+        TestRequest { request: 2 },
+        net1.get_local_peer_id(),
+    );
+    assert_eq!(response.await, Ok(test_response));
+    abort_handle.abort();
+}
+
 async fn disconnect_successfully(net1: &Arc<Network>, net2: &Arc<Network>) {
     log::debug!("Creating connected test networks");
 
@@ -787,3 +871,34 @@ async fn it_can_reset_requests_rate_with_reconnections() {
     send_n_request_to_fail(&net1, &net3, 1).await;
     send_n_request_to_fail(&net1, &net2, 1).await;
 }
+
+#[test(tokio::test)]
+async fn it_rejects_seeding_requests_when_busy() {
+    // Without any share for seeding, requests are queued but never served.
+    let (net1, net2) = TestNetwork::create_connected_networks_with_seeding(SeedingConfig {
+        upload_bandwidth: 0,
+        share: 0.0,
+    })
+    .await;
+
+    spawn(
+        net1.receive_requests::<TestSeedingRequest>()
+            .for_each(|_| async { panic!("Seeding request must not be served") }),
+    );
+    sleep(Duration::from_secs(1)).await;
+
+    // The requests exceeding the queue limit are answered right away.
+    let responses = (0..20).map(|request| {
+        net2.request::<TestSeedingRequest>(TestSeedingRequest { request }, net1.get_local_peer_id())
+    });
+    let busy = tokio::time::timeout(Duration::from_secs(5), async {
+        futures::future::select_all(responses.map(Box::pin)).await.0
+    })
+    .await
+    .expect("Busy response must be received before the requests time out");
+
+    assert_eq!(
+        busy,
+        Err(RequestError::InboundRequest(InboundRequestError::Busy))
+    );
+}
@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeSet, HashMap, HashSet},
     hash::Hash,
     sync::{atomic::AtomicBool, Arc},
 };
@@ -66,6 +66,9 @@ pub(crate) struct MockHubInner {
     /// DHT
     pub dht: HashMap<Vec<u8>, Vec<u8>>,
 
+    /// Providers per DHT key
+    pub dht_providers: HashMap<Vec<u8>, BTreeSet<MockPeerId>>,
+
     /// Arcs to `AtomicBool`s for each network if they're connected.
     pub is_connected: HashMap<MockAddress, Arc<AtomicBool>>,
 }
@@ -171,6 +171,23 @@ pub mod tests {
         assert_eq!(fetched_record, Some(put_record));
     }
 
+    #[test(tokio::test)]
+    async fn dht_providers() {
+        let mut hub = MockHub::new();
+        let net1 = hub.new_network();
+        let net2 = hub.new_network();
+        net1.dial_mock(&net2);
+
+        net1.dht_start_providing(b"foo").await.unwrap();
+        assert_eq!(
+            net2.dht_get_providers(b"foo").await.unwrap(),
+            vec![net1.peer_id()]
+        );
+
+        net1.dht_stop_providing(b"foo").await.unwrap();
+        assert!(net2.dht_get_providers(b"foo").await.unwrap().is_empty());
+    }
+
     pub struct TestTopic;
 
     impl Topic for TestTopic {
@@ -17,10 +17,11 @@ use nimiq_network_interface::{
         InboundRequestError, Message, OutboundRequestError, Request, RequestCommon, RequestError,
         RequestKind, RequestSerialize, RequestType,
     },
+    Multiaddr,
 };
 use nimiq_serde::{Deserialize, DeserializeError, Serialize};
 use nimiq_time::timeout;
-use nimiq_utils::tagged_signing::{TaggedKeyPair, TaggedSignable};
+use nimiq_utils::tagged_signing::{TaggedKeyPair, TaggedSignable, TaggedSigned};
 use parking_lot::{Mutex, RwLock};
 use thiserror::Error;
 use tokio::sync::{broadcast, mpsc, oneshot};
@@ -570,6 +571,73 @@ impl Network for MockNetwork {
         }
     }
 
+    async fn dht_put_signed<K, V, T>(
+        &self,
+        k: &K,
+        signed_record: &TaggedSigned<V, T>,
+    ) -> Result<(), Self::Error>
+    where
+        K: AsRef<[u8]> + Send + Sync,
+        V: Serialize + Send + Sync + TaggedSignable + Clone + Ord,
+        T: TaggedKeyPair + Send + Sync + Serialize + Deserialize,
+    {
+        if self.is_connected.load(Ordering::SeqCst) {
+            let mut hub = self.hub.lock();
+
+            let data = signed_record.record.serialize_to_vec();
+            hub.dht.insert(k.as_ref().to_owned(), data);
+            Ok(())
+        } else {
+            Err(MockNetworkError::NotConnected)
+        }
+    }
+
+    async fn dht_start_providing<K>(&self, k: &K) -> Result<(), Self::Error>
+    where
+        K: AsRef<[u8]> + Send + Sync,
+    {
+        if self.is_connected.load(Ordering::SeqCst) {
+            let mut hub = self.hub.lock();
+
+            hub.dht_providers
+                .entry(k.as_ref().to_owned())
+                .or_default()
+                .insert(self.peer_id());
+            Ok(())
+        } else {
+            Err(MockNetworkError::NotConnected)
+        }
+    }
+
+    async fn dht_stop_providing<K>(&self, k: &K) -> Result<(), Self::Error>
+    where
+        K: AsRef<[u8]> + Send + Sync,
+    {
+        let mut hub = self.hub.lock();
+
+        if let Some(providers) = hub.dht_providers.get_mut(k.as_ref()) {
+            providers.remove(&self.peer_id());
+        }
+        Ok(())
+    }
+
+    async fn dht_get_providers<K>(&self, k: &K) -> Result<Vec<MockPeerId>, Self::Error>
+    where
+        K: AsRef<[u8]> + Send + Sync,
+    {
+        if self.is_connected.load(Ordering::SeqCst) {
+            let hub = self.hub.lock();
+
+            Ok(hub
+                .dht_providers
+                .get(k.as_ref())
+                .map(|providers| providers.iter().copied().collect())
+                .unwrap_or_default())
+        } else {
+            Err(MockNetworkError::NotConnected)
+        }
+    }
+
     async fn dial_peer(&self, peer_id: MockPeerId) -> Result<(), Self::Error> {
         self.dial_mock_address(peer_id.into())
     }
@@ -582,6 +650,10 @@ impl Network for MockNetwork {
         self.address.into()
     }
 
+    fn get_local_addresses(&self) -> Vec<Multiaddr> {
+        vec![]
+    }
+
     async fn message<M: Message>(
         &self,
         message: M,
@@ -134,6 +134,7 @@ pub async fn get_pos_genesis(
         supply: None,
         state_root: None,
         slots: Vec::new(),
+        policy: None,
     })
 }
 
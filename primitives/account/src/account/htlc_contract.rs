@@ -35,6 +35,58 @@ pub struct HashedTimeLockedContract {
     pub total_amount: Coin,
 }
 
+/// How much of an HTLC can be redeemed by revealing the pre-image at a specific hash depth.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct HTLCDepthRedemption {
+    pub hash_depth: u8,
+    /// The balance that has to remain in the contract when redeeming at this hash depth.
+    pub min_balance: Coin,
+    /// The amount that can still be redeemed at this hash depth.
+    pub claimable: Coin,
+}
+
+impl HashedTimeLockedContract {
+    /// Returns the balance that has to remain in the contract when redeeming at the given hash
+    /// depth.
+    pub fn min_balance_at(&self, hash_depth: u8) -> Coin {
+        let cap_ratio = 1f64 - (f64::from(hash_depth) / f64::from(self.hash_count));
+        let min_cap = (cap_ratio * u64::from(self.total_amount) as f64)
+            .floor()
+            .max(0f64) as u64;
+        // The minimum cap never exceeds the total amount, which is a valid coin value.
+        Coin::from_u64_unchecked(min_cap)
+    }
+
+    /// Returns the amount that can still be redeemed at the given hash depth.
+    pub fn claimable_at(&self, hash_depth: u8) -> Coin {
+        self.balance.saturating_sub(self.min_balance_at(hash_depth))
+    }
+
+    /// Returns the amount that has been redeemed from the contract so far.
+    pub fn redeemed_amount(&self) -> Coin {
+        self.total_amount.saturating_sub(self.balance)
+    }
+
+    /// Returns the smallest hash depth that allows redeeming the amount redeemed so far, i.e. the
+    /// depth up to which the hash chain has been revealed at least.
+    pub fn redeemed_hash_depth(&self) -> u8 {
+        (0..=self.hash_count)
+            .find(|&hash_depth| self.min_balance_at(hash_depth) <= self.balance)
+            .unwrap_or(self.hash_count)
+    }
+
+    /// Returns what can still be redeemed at each hash depth of the contract.
+    pub fn redemptions(&self) -> Vec<HTLCDepthRedemption> {
+        (1..=self.hash_count)
+            .map(|hash_depth| HTLCDepthRedemption {
+                hash_depth,
+                min_balance: self.min_balance_at(hash_depth),
+                claimable: self.claimable_at(hash_depth),
+            })
+            .collect()
+    }
+}
+
 #[cfg(feature = "interaction-traits")]
 impl HashedTimeLockedContract {
     fn can_change_balance(
@@ -69,14 +121,7 @@ impl HashedTimeLockedContract {
                 }
 
                 // Check min cap.
-                let cap_ratio = 1f64 - (f64::from(hash_depth) / f64::from(self.hash_count));
-
-                let min_cap = Coin::try_from(
-                    (cap_ratio * u64::from(self.total_amount) as f64)
-                        .floor()
-                        .max(0f64) as u64,
-                )?;
-
+                let min_cap = self.min_balance_at(hash_depth);
                 if new_balance < min_cap {
                     return Err(AccountError::InsufficientFunds {
                         balance: self.balance - min_cap,
@@ -89,6 +134,14 @@ impl HashedTimeLockedContract {
                     pre_image,
                     hash_depth,
                 });
+                if hash_depth < self.hash_count {
+                    tx_logger.push_log(Log::HTLCPartialRedemption {
+                        contract_address: transaction.sender.clone(),
+                        hash_depth,
+                        amount: self.balance - new_balance,
+                        remaining_balance: new_balance,
+                    });
+                }
             }
             OutgoingHTLCTransactionProof::EarlyResolve {
                 signature_proof_recipient,
@@ -232,6 +285,7 @@ impl AccountTransactionInteraction for HashedTimeLockedContract {
         _data_store: DataStoreWrite,
         tx_logger: &mut TransactionLog,
     ) -> Result<(), AccountError> {
+        let remaining_balance = self.balance;
         self.balance += transaction.total_value();
 
         match OutgoingHTLCTransactionProof::deserialize_all(&transaction.proof)? {
@@ -240,6 +294,14 @@ impl AccountTransactionInteraction for HashedTimeLockedContract {
                 pre_image,
                 ..
             } => {
+                if hash_depth < self.hash_count {
+                    tx_logger.push_log(Log::HTLCPartialRedemption {
+                        contract_address: transaction.sender.clone(),
+                        hash_depth,
+                        amount: transaction.total_value(),
+                        remaining_balance,
+                    });
+                }
                 tx_logger.push_log(Log::HTLCRegularTransfer {
                     contract_address: transaction.sender.clone(),
                     pre_image,
@@ -1,4 +1,4 @@
-use std::collections::BTreeMap;
+use std::{collections::BTreeMap, ops::Range};
 
 use nimiq_keys::Address;
 use nimiq_primitives::{
@@ -8,16 +8,21 @@ use nimiq_primitives::{
 };
 use nimiq_vrf::{DiscreteDistribution, VrfSeed, VrfUseCase};
 pub use receipts::*;
+pub use rewards::{StakerRewards, ValidatorRewards};
 use serde::{Deserialize, Serialize};
-pub use staker::Staker;
+pub use staker::{ScheduledWithdrawal, Staker};
 pub use store::StakingContractStore;
 #[cfg(feature = "interaction-traits")]
 pub use store::StakingContractStoreWrite;
 pub use validator::{Tombstone, Validator};
+#[cfg(feature = "interaction-traits")]
+pub(crate) use validator_stakers::ValidatorStakersIndex;
 
+#[cfg(feature = "interaction-traits")]
+use crate::data_store::DataStoreRead;
 use crate::{
     account::staking_contract::{
-        punished_slots::PunishedSlots,
+        punished_slots::{PunishedSlots, ValidatorPunishments},
         store::{StakingContractStoreRead, StakingContractStoreReadOps},
     },
     data_store_ops::{DataStoreIterOps, DataStoreReadOps},
@@ -25,11 +30,14 @@ use crate::{
 
 pub mod punished_slots;
 mod receipts;
+mod rewards;
 mod staker;
 mod store;
 #[cfg(feature = "interaction-traits")]
 mod traits;
 mod validator;
+#[cfg(feature = "interaction-traits")]
+mod validator_stakers;
 
 /// The struct representing the staking contract. The staking contract is a special contract that
 /// handles most functions related to validators and staking.
@@ -42,8 +50,10 @@ mod validator;
 /// STAKING_CONTRACT_ADDRESS: StakingContract
 ///     |--> PREFIX_VALIDATOR || VALIDATOR_ADDRESS: Validator
 ///     |--> PREFIX_TOMBSTONE || VALIDATOR_ADDRESS: Tombstone
+///     |--> PREFIX_VALIDATOR_REWARDS || VALIDATOR_ADDRESS: ValidatorRewards
 ///     |
 ///     |--> PREFIX_STAKER || STAKER_ADDRESS: Staker
+///     |--> PREFIX_STAKER_REWARDS || STAKER_ADDRESS: StakerRewards
 /// ```
 ///
 /// So, for example, if you want to get the validator with a given address then you just fetch the
@@ -55,6 +65,8 @@ mod validator;
 ///     - A list of Validators. Each of them is a subtrie containing the Validator struct, with all
 ///       the information relative to the Validator.
 ///     - A list of Stakers, with each Staker struct containing all information about a staker.
+///     - The reward bookkeeping of validators and stakers. These entries only exist if they differ
+///       from their default, i.e. once a validator shares its rewards with its stakers.
 #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct StakingContract {
     // The total amount of coins staked (also includes validators deposits).
@@ -76,6 +88,29 @@ impl StakingContract {
         StakingContractStoreRead::new(data_store).get_validator(address)
     }
 
+    /// Returns the punishments of the validator with the given address within the window retained
+    /// by the staking contract, if the validator exists. See [`PunishedSlots::punished_batches`]
+    /// for the meaning of `previous_batch_slot_range`.
+    pub fn get_validator_punishments<T: DataStoreReadOps>(
+        &self,
+        data_store: &T,
+        address: &Address,
+        block_number: u32,
+        previous_batch_slot_range: Option<Range<u16>>,
+    ) -> Option<ValidatorPunishments> {
+        let validator = self.get_validator(data_store, address)?;
+
+        Some(ValidatorPunishments {
+            punished_batches: self.punished_slots.punished_batches(
+                address,
+                block_number,
+                previous_batch_slot_range,
+            ),
+            jailed_from: validator.jailed_from,
+            jail_release: validator.jailed_from.map(Policy::block_after_jail),
+        })
+    }
+
     /// Get a staker given its address, if it exists.
     pub fn get_staker<T: DataStoreReadOps>(
         &self,
@@ -85,6 +120,49 @@ impl StakingContract {
         StakingContractStoreRead::new(data_store).get_staker(address)
     }
 
+    /// Get the commission the validator with the given address charges on its rewards, in basis
+    /// points. Validators that never set a commission keep all of their rewards.
+    pub fn get_validator_commission<T: DataStoreReadOps>(
+        &self,
+        data_store: &T,
+        address: &Address,
+    ) -> u16 {
+        StakingContractStoreRead::new(data_store)
+            .get_validator_rewards(address)
+            .commission
+    }
+
+    /// Get the rewards accrued by the staker with the given address that haven't been credited to
+    /// its balance yet, if the staker exists.
+    pub fn get_staker_pending_reward<T: DataStoreReadOps>(
+        &self,
+        data_store: &T,
+        address: &Address,
+    ) -> Option<Coin> {
+        let store = StakingContractStoreRead::new(data_store);
+        Some(store.get_staker(address)?.pending_reward(&store))
+    }
+
+    /// Get the reward bookkeeping of the staker with the given address, which is the default if
+    /// the staker has never been credited any rewards or changed its reward compounding.
+    pub fn get_staker_rewards<T: DataStoreReadOps>(
+        &self,
+        data_store: &T,
+        address: &Address,
+    ) -> StakerRewards {
+        StakingContractStoreRead::new(data_store).get_staker_rewards(address)
+    }
+
+    /// Get the scheduled withdrawals of the staker with the given address, ordered by their
+    /// release block height.
+    pub fn get_scheduled_withdrawals<T: DataStoreReadOps>(
+        &self,
+        data_store: &T,
+        address: &Address,
+    ) -> Vec<ScheduledWithdrawal> {
+        StakingContractStoreRead::new(data_store).get_scheduled_withdrawals(address)
+    }
+
     /// Get a tombstone given its address, if it exists.
     pub fn get_tombstone<T: DataStoreReadOps>(
         &self,
@@ -96,16 +174,47 @@ impl StakingContract {
 
     /// Get the list of all stakers that are delegating for the given validator.
     /// IMPORTANT: This is potentially a very expensive operation!
-    pub fn get_stakers_for_validator<T: DataStoreReadOps + DataStoreIterOps>(
+    #[cfg(feature = "interaction-traits")]
+    pub fn get_stakers_for_validator(
         &self,
-        data_store: &T,
+        data_store: &DataStoreRead,
         address: &Address,
     ) -> Vec<Staker> {
-        self.iter_stakers_for_validator(data_store, address)
-            .collect()
+        self.get_stakers_for_validator_paged(data_store, address, None, usize::MAX)
+    }
+
+    /// Get at most `limit` stakers that are delegating for the given validator, ordered by their
+    /// address. If `start` is given, the stakers begin at that staker address (inclusive), so the
+    /// address of the last staker of a page can be used to fetch the next one.
+    /// The stakers are looked up in the local validator stakers index, so only the stakers of the
+    /// given validator are visited.
+    #[cfg(feature = "interaction-traits")]
+    pub fn get_stakers_for_validator_paged(
+        &self,
+        data_store: &DataStoreRead,
+        address: &Address,
+        start: Option<&Address>,
+        limit: usize,
+    ) -> Vec<Staker> {
+        let read = StakingContractStoreRead::new(data_store);
+
+        ValidatorStakersIndex::staker_addresses(
+            data_store.txn(),
+            address,
+            start.unwrap_or(&Address::START_ADDRESS),
+            limit,
+        )
+        .into_iter()
+        .map(|staker_address| {
+            read.get_staker(&staker_address)
+                .expect("inconsistent validator stakers index")
+        })
+        .collect()
     }
 
     /// Get an iterator over all stakers that are delegating for the given validator.
+    /// This iterates over all stakers in the contract, which doesn't require the local validator
+    /// stakers index.
     pub fn iter_stakers_for_validator<'a, T: DataStoreReadOps + DataStoreIterOps + 'a>(
         &self,
         data_store: &'a T,
@@ -140,6 +249,19 @@ impl StakingContract {
         StakingContractStoreRead::new(data_store).iter_validators()
     }
 
+    /// Get an iterator over at most `limit` validators in the contract, ordered by their address.
+    /// If `start` is given, the iteration begins at that validator address (inclusive).
+    pub fn iter_validators_paged<'a, T: DataStoreReadOps + DataStoreIterOps + 'a>(
+        &self,
+        data_store: &'a T,
+        start: Option<&Address>,
+        limit: usize,
+    ) -> impl Iterator<Item = Validator> + 'a {
+        StakingContractStoreRead::new(data_store)
+            .iter_validators_from(start.unwrap_or(&Address::START_ADDRESS))
+            .take(limit)
+    }
+
     /// Given a seed, it randomly distributes the validator slots across all validators. It is
     /// used to select the validators for the next epoch.
     pub fn select_validators<T: DataStoreReadOps>(
@@ -159,19 +281,28 @@ impl StakingContract {
 
         let lookup = DiscreteDistribution::new(&validator_stakes);
 
+        // Sample all slots first, so that every chosen validator only needs to be read once.
+        let mut num_slots = vec![0u16; validator_addresses.len()];
+        for _ in 0..Policy::SLOTS {
+            num_slots[lookup.sample(&mut rng)] += 1;
+        }
+
         let mut slots_builder = ValidatorsBuilder::default();
 
-        for _ in 0..Policy::SLOTS {
-            let index = lookup.sample(&mut rng);
+        for (address, num_slots) in validator_addresses.into_iter().zip(num_slots) {
+            if num_slots == 0 {
+                continue;
+            }
 
             let chosen_validator = self
-                .get_validator(data_store, validator_addresses[index])
+                .get_validator(data_store, address)
                 .expect("Couldn't find a validator that was in the active validators list!");
 
-            slots_builder.push(
+            slots_builder.push_slots(
                 chosen_validator.address,
                 chosen_validator.voting_key,
                 chosen_validator.signing_key,
+                num_slots,
             );
         }
 
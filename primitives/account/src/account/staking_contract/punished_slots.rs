@@ -23,6 +23,28 @@ pub struct PunishedSlots {
     pub previous_batch_punished_slots: BitSet,
 }
 
+/// The slots of a validator that lost rewards in a batch.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PunishedBatch {
+    /// The batch number.
+    pub batch: u32,
+    /// The epoch the batch belongs to.
+    pub epoch: u32,
+    /// The punished slots of the validator in the batch.
+    pub slots: BTreeSet<u16>,
+}
+
+/// The punishments of a validator within the window retained by the staking contract.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidatorPunishments {
+    /// The batches of the retained window in which the validator had slots punished.
+    pub punished_batches: Vec<PunishedBatch>,
+    /// The block height at which the validator was jailed, if it was ever jailed.
+    pub jailed_from: Option<u32>,
+    /// The block height at which the validator is released from jail, if it was ever jailed.
+    pub jail_release: Option<u32>,
+}
+
 impl PunishedSlots {
     pub fn new(
         current_batch_punished_slots: BTreeMap<Address, BTreeSet<u16>>,
@@ -263,4 +285,47 @@ impl PunishedSlots {
     pub fn previous_batch_punished_slots(&self) -> &BitSet {
         &self.previous_batch_punished_slots
     }
+
+    /// Returns the batches within the retained window, i.e. the previous and the current batch
+    /// after the given block, in which the given validator had slots punished.
+    /// Since the previous batch set only keeps track of slots, `previous_batch_slot_range` must be
+    /// the validator's slot range in the epoch of the previous batch, if it was elected in it.
+    pub fn punished_batches(
+        &self,
+        validator_address: &Address,
+        block_number: u32,
+        previous_batch_slot_range: Option<Range<u16>>,
+    ) -> Vec<PunishedBatch> {
+        let mut punished_batches = vec![];
+
+        // The previous batch is the one finalized by the last macro block.
+        let last_macro_block = Policy::last_macro_block(block_number);
+
+        if let Some(slot_range) = previous_batch_slot_range {
+            let slots: BTreeSet<u16> = self
+                .previous_batch_punished_slots
+                .iter()
+                .map(|slot| slot as u16)
+                .filter(|slot| slot_range.contains(slot))
+                .collect();
+
+            if !slots.is_empty() {
+                punished_batches.push(PunishedBatch {
+                    batch: Policy::batch_at(last_macro_block),
+                    epoch: Policy::epoch_at(last_macro_block),
+                    slots,
+                });
+            }
+        }
+
+        if let Some(slots) = self.current_batch_punished_slots.get(validator_address) {
+            punished_batches.push(PunishedBatch {
+                batch: Policy::batch_at(last_macro_block + 1),
+                epoch: Policy::epoch_at(last_macro_block + 1),
+                slots: slots.clone(),
+            });
+        }
+
+        punished_batches
+    }
 }
@@ -7,7 +7,7 @@ use nimiq_keys::{Address, Ed25519PublicKey as SchnorrPublicKey};
 use nimiq_primitives::{account::AccountError, coin::Coin};
 use nimiq_serde::{Deserialize, Serialize};
 
-use crate::{convert_receipt, AccountReceipt};
+use crate::{account::staking_contract::ScheduledWithdrawal, convert_receipt, AccountReceipt};
 
 /// Penalize receipt for the inherent. This is necessary to be able to revert
 /// these inherents.
@@ -39,6 +39,39 @@ pub struct JailReceipt {
 }
 convert_receipt!(JailReceipt);
 
+/// Reward receipt for the inherent paying the stakers' share of a validator's reward. This is
+/// necessary to be able to revert these inherents.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct RewardReceipt {
+    /// the increase of the validator's reward per stake by this inherent
+    pub reward_per_stake: u128,
+}
+convert_receipt!(RewardReceipt);
+
+/// Receipt for the inherent removing the tombstones of deleted validators. This is necessary to
+/// be able to revert these inherents.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct RemoveTombstonesReceipt {
+    /// the stakers that were undelegated from a tombstone by this inherent, in the order they
+    /// were undelegated
+    pub undelegated_stakers: Vec<UndelegateStakerReceipt>,
+}
+convert_receipt!(RemoveTombstonesReceipt);
+
+/// Receipt for undelegating a staker from a tombstone. This is necessary to be able to revert
+/// the inherent removing the tombstones.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct UndelegateStakerReceipt {
+    /// the address of the undelegated staker
+    pub staker_address: Address,
+    /// the delegation before this inherent is applied
+    pub delegation: Address,
+    /// the reward per stake the staker was last credited before this inherent is applied
+    pub old_reward_per_stake_paid: u128,
+    /// the rewards credited by this inherent, if any
+    pub rewards_receipt: Option<StakerRewardsReceipt>,
+}
+
 /// Receipt for update validator transactions. This is necessary to be able to revert
 /// these transactions.
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
@@ -54,6 +87,15 @@ pub struct UpdateValidatorReceipt {
 }
 convert_receipt!(UpdateValidatorReceipt);
 
+/// Receipt for set validator commission transactions. This is necessary to be able to revert
+/// these transactions.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct SetValidatorCommissionReceipt {
+    /// the commission before this transaction is applied
+    pub old_commission: u16,
+}
+convert_receipt!(SetValidatorCommissionReceipt);
+
 /// Receipt for jailing a validator. This is necessary to be able to revert
 /// a jail.
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
@@ -121,9 +163,24 @@ pub struct StakerReceipt {
     pub active_balance: Coin,
     /// the inactivation block height before this transaction is applied
     pub inactive_from: Option<u32>,
+    /// the reward per stake the staker was last credited before this transaction is applied
+    pub old_reward_per_stake_paid: u128,
 }
 convert_receipt!(StakerReceipt);
 
+/// Receipt for crediting the rewards of a staker, which happens implicitly in most staker-related
+/// transactions. This is necessary to be able to revert these transactions.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct StakerRewardsReceipt {
+    /// the reward per stake the staker was last credited before this transaction is applied
+    pub old_reward_per_stake_paid: u128,
+    /// the rewards credited by this transaction
+    pub value: Coin,
+    /// true if the rewards were added to the active balance instead of the retired balance
+    pub compounded: bool,
+}
+convert_receipt!(StakerRewardsReceipt);
+
 /// Receipt for set active stake transactions. This is necessary to be able to revert
 /// these transactions.
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
@@ -132,6 +189,8 @@ pub struct SetActiveStakeReceipt {
     pub old_inactive_from: Option<u32>,
     /// the active balance before this transaction is applied
     pub old_active_balance: Coin,
+    /// the rewards credited by this transaction, if any
+    pub rewards_receipt: Option<StakerRewardsReceipt>,
 }
 convert_receipt!(SetActiveStakeReceipt);
 
@@ -144,11 +203,37 @@ pub struct RetireStakeReceipt {
 }
 convert_receipt!(RetireStakeReceipt);
 
+/// Receipt for set reward compounding transactions. This is necessary to be able to revert
+/// these transactions.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct SetRewardCompoundingReceipt {
+    /// the reward compounding flag before this transaction is applied
+    pub old_compound_rewards: bool,
+    /// the rewards credited by this transaction, if any
+    pub rewards_receipt: Option<StakerRewardsReceipt>,
+}
+convert_receipt!(SetRewardCompoundingReceipt);
+
+/// Receipt for claim withdrawals transactions. This is necessary to be able to revert
+/// these transactions.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+pub struct ClaimWithdrawalsReceipt {
+    /// the scheduled withdrawals that were claimed by this transaction
+    pub claimed: Vec<ScheduledWithdrawal>,
+    /// the rewards credited by this transaction, if any
+    pub rewards_receipt: Option<StakerRewardsReceipt>,
+}
+convert_receipt!(ClaimWithdrawalsReceipt);
+
 /// Receipt for remove stake transactions. This is necessary to be able to revert
 /// the staker removal in case all funds were withdrawn.
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
 pub struct DeleteStakerReceipt {
     /// the delegation before this transaction is applied
     pub delegation: Option<Address>,
+    /// the reward compounding flag before this transaction is applied
+    pub compound_rewards: bool,
+    /// the reward per stake the staker was last credited before this transaction is applied
+    pub reward_per_stake_paid: u128,
 }
 convert_receipt!(DeleteStakerReceipt);
@@ -0,0 +1,66 @@
+use nimiq_primitives::{coin::Coin, policy::Policy};
+use serde::{Deserialize, Serialize};
+
+/// The reward bookkeeping of a validator, stored separately from the validator itself.
+///
+/// Rewards of the stakers are accounted for with a reward per stake accumulator: Paying the
+/// stakers' share of a reward increases `reward_per_stake` by the reward divided by the stake
+/// delegated to the validator. A staker is only credited when it interacts with the staking
+/// contract, so paying rewards doesn't depend on the number of stakers.
+///
+/// The entry is kept when the validator is deleted, such that the remaining stakers can still be
+/// credited the rewards they accrued before. A re-created validator thus keeps its commission.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidatorRewards {
+    /// The commission the validator keeps from the rewards of its slots, in basis points. The rest
+    /// of the rewards is shared among its stakers, proportionally to their active balance.
+    /// A commission of `Policy::MAX_VALIDATOR_COMMISSION` pays all rewards to the reward address.
+    pub commission: u16,
+    /// The sum of the stakers' rewards per unit of delegated stake, scaled by
+    /// [`ValidatorRewards::PRECISION`].
+    pub reward_per_stake: u128,
+}
+
+impl ValidatorRewards {
+    /// The scaling factor of the reward per stake accumulator.
+    pub const PRECISION: u128 = 1_000_000_000_000_000_000;
+
+    /// Returns the increase of the reward per stake if `value` is shared among `delegated_stake`.
+    pub fn reward_per_stake_increase(value: Coin, delegated_stake: Coin) -> u128 {
+        u64::from(value) as u128 * Self::PRECISION / u64::from(delegated_stake) as u128
+    }
+}
+
+impl Default for ValidatorRewards {
+    fn default() -> Self {
+        Self {
+            commission: Policy::MAX_VALIDATOR_COMMISSION,
+            reward_per_stake: 0,
+        }
+    }
+}
+
+/// The reward bookkeeping of a staker, stored separately from the staker itself such that the
+/// layout of the staker entry is unchanged.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StakerRewards {
+    /// The reward per stake of the validator the staker delegates to at the time the staker was
+    /// last credited its rewards.
+    pub reward_per_stake_paid: u128,
+    /// Whether the staker's rewards are credited to its active balance. Otherwise, they are
+    /// credited to its retired balance, from where they can be removed from the staking contract.
+    pub compound_rewards: bool,
+}
+
+impl StakerRewards {
+    /// Returns the rewards accrued by the given active balance since the staker was last credited.
+    /// Rounding remainders stay in the staking contract.
+    pub fn pending_reward(&self, active_balance: Coin, reward_per_stake: u128) -> Coin {
+        // The active balance has been part of the delegated stake for every reward since the last
+        // credit, so the result is bounded by the rewards paid to the staking contract.
+        let pending = u64::from(active_balance) as u128
+            * reward_per_stake.saturating_sub(self.reward_per_stake_paid)
+            / ValidatorRewards::PRECISION;
+        Coin::from_u64_unchecked(pending as u64)
+    }
+}
@@ -9,15 +9,16 @@ use nimiq_primitives::coin::Coin;
 use nimiq_primitives::policy::Policy;
 use serde::{Deserialize, Serialize};
 
+use crate::account::staking_contract::store::StakingContractStoreReadOps;
 #[cfg(feature = "interaction-traits")]
 use crate::{
     account::staking_contract::{
-        store::{
-            StakingContractStoreReadOps, StakingContractStoreReadOpsExt, StakingContractStoreWrite,
-        },
-        StakerReceipt, StakingContract, Tombstone,
+        store::{StakingContractStoreReadOpsExt, StakingContractStoreWrite},
+        RemoveTombstonesReceipt, RewardReceipt, StakerReceipt, StakerRewards, StakerRewardsReceipt,
+        StakingContract, Tombstone, UndelegateStakerReceipt, ValidatorRewards,
     },
-    DeleteStakerReceipt, Log, RetireStakeReceipt, SetActiveStakeReceipt, TransactionLog,
+    ClaimWithdrawalsReceipt, DeleteStakerReceipt, InherentLogger, Log, RetireStakeReceipt,
+    SetActiveStakeReceipt, SetRewardCompoundingReceipt, TransactionLog,
 };
 
 /// Struct representing a staker in the staking contract.
@@ -44,6 +45,13 @@ use crate::{
 /// 5. RemoveStake:      Removes the retired balance from a staker to outside of the staking contract.
 ///                      The retired balance can always be withdrawn as long as it removes all retired stake.
 ///                      If the staker's total balance drops to 0, the staker is deleted.
+/// 6. ScheduleUnstake:  Moves the given amount of active stake to a scheduled withdrawal, which is released after
+///                      the withdrawal delay. A staker can have several scheduled withdrawals at the same time.
+///                      This action is only possible if:
+///                        (a) the resulting non-retired funds respect the invariant 1 - minimum stake for non-retired funds.
+/// 7. ClaimWithdrawals: Moves all released scheduled withdrawals to the retired balance, from where they can be removed.
+///                      This action is only possible if at least one scheduled withdrawal is released and the
+///                      validator the stake is delegated to is not jailed.
 ///
 /// (*)     For inactive balance to be released, the maximum of the lock-up period for inactive stake and the validator's
 ///         potential jail period must have passed.
@@ -57,7 +65,8 @@ use crate::{
 ///             (invariant 4) When there are no inactive funds, there is no inactivation block height:
 ///                          `inactive_from` is None && `inactive_balance` == 0
 ///
-/// Create, AddStake, SetActiveStake, Update and RetireStake are incoming transactions to the staking contract.
+/// Create, AddStake, SetActiveStake, Update, RetireStake and SetRewardCompounding are incoming transactions
+/// to the staking contract.
 /// RemoveStake is an outgoing transaction from the staking contract.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Staker {
@@ -87,9 +96,39 @@ pub struct Staker {
     pub delegation: Option<Address>,
 }
 
+/// An unstake scheduled by a staker. Its value was removed from the staker's active balance and
+/// can be claimed once the release block height has been reached.
+///
+/// The scheduled withdrawals of a staker are stored separately from the staker itself, ordered by
+/// their release block height. Their balance is neither active nor delegated anymore.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduledWithdrawal {
+    /// The value to be withdrawn.
+    pub value: Coin,
+    /// The block height from which the withdrawal can be claimed.
+    pub release_block: u32,
+}
+
+impl Staker {
+    /// Returns the rewards accrued by the staker's active balance that haven't been credited to
+    /// its balance yet.
+    pub(crate) fn pending_reward<T: StakingContractStoreReadOps>(&self, store: &T) -> Coin {
+        let Some(validator_address) = &self.delegation else {
+            return Coin::ZERO;
+        };
+        let reward_per_stake = store
+            .get_validator_rewards(validator_address)
+            .reward_per_stake;
+
+        store
+            .get_staker_rewards(&self.address)
+            .pending_reward(self.active_balance, reward_per_stake)
+    }
+}
+
 #[cfg(feature = "interaction-traits")]
 impl Staker {
-    /// Returns the current total balance of the staker.
+    /// Returns the current total balance of the staker, excluding its scheduled withdrawals.
     pub fn total_balance(&self) -> Coin {
         self.active_balance + self.inactive_balance + self.retired_balance
     }
@@ -224,6 +263,9 @@ impl StakingContract {
         if let Some(validator_address) = &staker.delegation {
             self.register_staker_on_validator(store, validator_address, false);
             self.increase_stake_to_validator(store, validator_address, staker.active_balance);
+
+            // The staker only accrues the validator's rewards from now on.
+            Self::reset_staker_rewards(store, staker_address, validator_address);
         }
 
         // Update balance.
@@ -263,8 +305,9 @@ impl StakingContract {
             self.unregister_staker_from_validator(store, validator_address);
         }
 
-        // Remove the staker entry.
+        // Remove the staker entries.
         store.remove_staker(&staker.address);
+        store.put_staker_rewards(&staker.address, StakerRewards::default());
 
         tx_logger.push_log(Log::CreateStaker {
             staker_address: staker_address.clone(),
@@ -283,7 +326,7 @@ impl StakingContract {
         staker_address: &Address,
         value: Coin,
         tx_logger: &mut TransactionLog,
-    ) -> Result<(), AccountError> {
+    ) -> Result<Option<StakerRewardsReceipt>, AccountError> {
         // Get the staker.
         let mut staker = store.expect_staker(staker_address)?;
 
@@ -294,12 +337,18 @@ impl StakingContract {
             staker.retired_balance,
         )?;
 
+        // Check that the delegation is still valid, i.e. the validator hasn't been deleted.
+        if let Some(validator_address) = &staker.delegation {
+            store.expect_validator(validator_address)?;
+        }
+
         // All checks passed, not allowed to fail from here on!
 
+        // Credit the rewards accrued by the previous active balance.
+        let receipt = self.credit_staker_rewards(store, &mut staker, tx_logger);
+
         // If we are delegating to a validator, we need to update it.
         if let Some(validator_address) = &staker.delegation {
-            // Check that the delegation is still valid, i.e. the validator hasn't been deleted.
-            store.expect_validator(validator_address)?;
             self.increase_stake_to_validator(store, validator_address, value);
         }
 
@@ -317,7 +366,7 @@ impl StakingContract {
         // Update the staker entry.
         store.put_staker(staker_address, staker);
 
-        Ok(())
+        Ok(receipt)
     }
 
     /// Reverts a stake transaction.
@@ -326,6 +375,7 @@ impl StakingContract {
         store: &mut StakingContractStoreWrite,
         staker_address: &Address,
         value: Coin,
+        receipt: Option<StakerRewardsReceipt>,
         tx_logger: &mut TransactionLog,
     ) -> Result<(), AccountError> {
         // Get the staker.
@@ -346,6 +396,11 @@ impl StakingContract {
             value,
         });
 
+        // Revert crediting the rewards.
+        if let Some(receipt) = receipt {
+            self.revert_credit_staker_rewards(store, &mut staker, receipt, tx_logger);
+        }
+
         // Update the staker entry.
         store.put_staker(staker_address, staker);
 
@@ -394,6 +449,19 @@ impl StakingContract {
             };
         }
 
+        // Fail if the delegation changes while there are scheduled withdrawals. They are released
+        // depending on the jail status of the validator they were scheduled from, which must thus
+        // remain the delegation until they are claimed.
+        if new_delegation != staker.delegation
+            && !store.get_scheduled_withdrawals(staker_address).is_empty()
+        {
+            debug!(
+                ?staker_address,
+                "Tried to update delegation of staker with scheduled withdrawals"
+            );
+            return Err(AccountError::InvalidForRecipient);
+        }
+
         // All checks passed, not allowed to fail from here on!
 
         // Create the receipt.
@@ -401,6 +469,9 @@ impl StakingContract {
             delegation: staker.delegation.clone(),
             active_balance: staker.active_balance,
             inactive_from: staker.inactive_from,
+            old_reward_per_stake_paid: store
+                .get_staker_rewards(staker_address)
+                .reward_per_stake_paid,
         };
 
         // Store old information for the log.
@@ -424,11 +495,18 @@ impl StakingContract {
         }
 
         // If we are now delegating to a validator we add ourselves to it.
+        // The staker had no active stake delegated before, so there are no rewards to credit and
+        // it only accrues the new validator's rewards from now on.
         if let Some(validator_address) = &staker.delegation {
             self.register_staker_on_validator(store, validator_address, false);
             if !staker.active_balance.is_zero() {
                 self.increase_stake_to_validator(store, validator_address, staker.active_balance);
             }
+            Self::reset_staker_rewards(store, staker_address, validator_address);
+        } else {
+            let mut rewards = store.get_staker_rewards(staker_address);
+            rewards.reward_per_stake_paid = 0;
+            store.put_staker_rewards(staker_address, rewards);
         }
 
         // Create log
@@ -488,6 +566,9 @@ impl StakingContract {
         staker.active_balance = receipt.active_balance;
         staker.inactive_balance = non_retired_balance - staker.active_balance;
         staker.inactive_from = receipt.inactive_from;
+        let mut rewards = store.get_staker_rewards(staker_address);
+        rewards.reward_per_stake_paid = receipt.old_reward_per_stake_paid;
+        store.put_staker_rewards(staker_address, rewards);
 
         // Update the staker entry.
         store.put_staker(staker_address, staker);
@@ -524,9 +605,13 @@ impl StakingContract {
 
         // All checks passed, not allowed to fail from here on!
 
+        // Credit the rewards accrued by the previous active balance.
+        let rewards_receipt = self.credit_staker_rewards(store, &mut staker, tx_logger);
+
         // Store old values for receipt.
         let old_inactive_from = staker.inactive_from;
         let old_active_balance = staker.active_balance;
+        let non_retired_balance = staker.non_retired_balance();
         let new_inactive_balance = non_retired_balance - new_active_balance;
 
         // Update the staker's balances.
@@ -564,6 +649,7 @@ impl StakingContract {
         Ok(SetActiveStakeReceipt {
             old_inactive_from,
             old_active_balance,
+            rewards_receipt,
         })
     }
 
@@ -604,6 +690,312 @@ impl StakingContract {
             inactive_from: old_inactive_from,
         });
 
+        // Revert crediting the rewards.
+        if let Some(rewards_receipt) = receipt.rewards_receipt {
+            self.revert_credit_staker_rewards(store, &mut staker, rewards_receipt, tx_logger);
+        }
+
+        // Update the staker entry.
+        store.put_staker(staker_address, staker);
+
+        Ok(())
+    }
+
+    /// Sets whether the staker's rewards are credited to its active balance instead of its retired
+    /// balance.
+    pub fn set_reward_compounding(
+        &mut self,
+        store: &mut StakingContractStoreWrite,
+        staker_address: &Address,
+        compound_rewards: bool,
+        tx_logger: &mut TransactionLog,
+    ) -> Result<SetRewardCompoundingReceipt, AccountError> {
+        // Get the staker.
+        let mut staker = store.expect_staker(staker_address)?;
+
+        // All checks passed, not allowed to fail from here on!
+
+        // Credit the rewards accrued so far with the previous setting.
+        let rewards_receipt = self.credit_staker_rewards(store, &mut staker, tx_logger);
+
+        // Store the old value for the receipt.
+        let mut rewards = store.get_staker_rewards(staker_address);
+        let old_compound_rewards = rewards.compound_rewards;
+
+        rewards.compound_rewards = compound_rewards;
+        store.put_staker_rewards(staker_address, rewards);
+
+        tx_logger.push_log(Log::SetRewardCompounding {
+            staker_address: staker_address.clone(),
+            validator_address: staker.delegation.clone(),
+            compound_rewards,
+        });
+
+        // Update the staker entry.
+        store.put_staker(staker_address, staker);
+
+        Ok(SetRewardCompoundingReceipt {
+            old_compound_rewards,
+            rewards_receipt,
+        })
+    }
+
+    /// Reverts a set reward compounding transaction.
+    pub fn revert_set_reward_compounding(
+        &mut self,
+        store: &mut StakingContractStoreWrite,
+        staker_address: &Address,
+        receipt: SetRewardCompoundingReceipt,
+        tx_logger: &mut TransactionLog,
+    ) -> Result<(), AccountError> {
+        // Get the staker.
+        let mut staker = store.expect_staker(staker_address)?;
+
+        // Restore the previous value.
+        let mut rewards = store.get_staker_rewards(staker_address);
+        tx_logger.push_log(Log::SetRewardCompounding {
+            staker_address: staker_address.clone(),
+            validator_address: staker.delegation.clone(),
+            compound_rewards: rewards.compound_rewards,
+        });
+
+        rewards.compound_rewards = receipt.old_compound_rewards;
+        store.put_staker_rewards(staker_address, rewards);
+
+        // Revert crediting the rewards.
+        if let Some(rewards_receipt) = receipt.rewards_receipt {
+            self.revert_credit_staker_rewards(store, &mut staker, rewards_receipt, tx_logger);
+        }
+
+        // Update the staker entry.
+        store.put_staker(staker_address, staker);
+
+        Ok(())
+    }
+
+    /// Schedules the withdrawal of the given amount of active stake. The value is removed from the
+    /// active balance and can be claimed after the withdrawal delay.
+    /// The schedule fails if the invariant 1 for the non-retired stake is violated.
+    pub fn schedule_unstake(
+        &mut self,
+        store: &mut StakingContractStoreWrite,
+        staker_address: &Address,
+        value: Coin,
+        block_number: u32,
+        tx_logger: &mut TransactionLog,
+    ) -> Result<Option<StakerRewardsReceipt>, AccountError> {
+        // Get the staker.
+        let mut staker = store.expect_staker(staker_address)?;
+
+        // Fail if staker does not have sufficient active funds.
+        if staker.active_balance < value {
+            return Err(AccountError::InsufficientFunds {
+                needed: value,
+                balance: staker.active_balance,
+            });
+        }
+
+        // Fail if the staker already has the maximum number of scheduled withdrawals.
+        let mut scheduled_withdrawals = store.get_scheduled_withdrawals(staker_address);
+        if scheduled_withdrawals.len() >= Policy::MAX_SCHEDULED_WITHDRAWALS {
+            debug!(
+                %staker_address,
+                "Staker already has the maximum number of scheduled withdrawals"
+            );
+            return Err(AccountError::InvalidForRecipient);
+        }
+
+        // Fail if the minimum stake is violated for the non-retired balances (invariant 1).
+        let new_active_balance = staker.active_balance - value;
+        Staker::enforce_min_stake(
+            new_active_balance,
+            staker.inactive_balance,
+            staker.retired_balance,
+        )?;
+
+        // All checks passed, not allowed to fail from here on!
+
+        // Credit the rewards accrued by the previous active balance.
+        let receipt = self.credit_staker_rewards(store, &mut staker, tx_logger);
+
+        // Update the staker's balance and schedule the withdrawal.
+        staker.active_balance -= value;
+        let release_block = Policy::block_after_withdrawal_delay(block_number);
+        scheduled_withdrawals.push(ScheduledWithdrawal {
+            value,
+            release_block,
+        });
+        store.put_scheduled_withdrawals(staker_address, scheduled_withdrawals);
+
+        // If we are delegating to a validator, we update the active stake of the validator.
+        if let Some(validator_address) = &staker.delegation {
+            self.decrease_stake_from_validator(store, validator_address, value);
+        }
+
+        tx_logger.push_log(Log::ScheduleUnstake {
+            staker_address: staker_address.clone(),
+            validator_address: staker.delegation.clone(),
+            value,
+            release_block,
+        });
+
+        // Update the staker entry.
+        store.put_staker(staker_address, staker);
+
+        Ok(receipt)
+    }
+
+    /// Reverts a schedule unstake transaction.
+    pub fn revert_schedule_unstake(
+        &mut self,
+        store: &mut StakingContractStoreWrite,
+        staker_address: &Address,
+        value: Coin,
+        receipt: Option<StakerRewardsReceipt>,
+        tx_logger: &mut TransactionLog,
+    ) -> Result<(), AccountError> {
+        // Get the staker.
+        let mut staker = store.expect_staker(staker_address)?;
+
+        // The withdrawal to revert is always the last one scheduled.
+        let mut scheduled_withdrawals = store.get_scheduled_withdrawals(staker_address);
+        let withdrawal = scheduled_withdrawals
+            .pop()
+            .ok_or(AccountError::InvalidReceipt)?;
+        if withdrawal.value != value {
+            return Err(AccountError::InvalidReceipt);
+        }
+        store.put_scheduled_withdrawals(staker_address, scheduled_withdrawals);
+
+        // Restore the staker's active balance.
+        staker.active_balance += value;
+
+        // If we are delegating to a validator, we update the active stake of the validator.
+        if let Some(validator_address) = &staker.delegation {
+            self.increase_stake_to_validator(store, validator_address, value);
+        }
+
+        tx_logger.push_log(Log::ScheduleUnstake {
+            staker_address: staker_address.clone(),
+            validator_address: staker.delegation.clone(),
+            value,
+            release_block: withdrawal.release_block,
+        });
+
+        // Revert crediting the rewards.
+        if let Some(receipt) = receipt {
+            self.revert_credit_staker_rewards(store, &mut staker, receipt, tx_logger);
+        }
+
+        // Update the staker entry.
+        store.put_staker(staker_address, staker);
+
+        Ok(())
+    }
+
+    /// Claims all released scheduled withdrawals of the staker by moving them to its retired
+    /// balance, from where they can be removed. Scheduled withdrawals are not released while the
+    /// validator the stake is delegated to is jailed. The delegation can't change while there are
+    /// scheduled withdrawals, so this is always the validator they were scheduled from.
+    pub fn claim_withdrawals(
+        &mut self,
+        store: &mut StakingContractStoreWrite,
+        staker_address: &Address,
+        block_number: u32,
+        tx_logger: &mut TransactionLog,
+    ) -> Result<ClaimWithdrawalsReceipt, AccountError> {
+        // Get the staker.
+        let mut staker = store.expect_staker(staker_address)?;
+
+        // Withdrawals are not released while the delegated validator is jailed.
+        let is_jailed = staker
+            .delegation
+            .as_ref()
+            .and_then(|validator_address| store.get_validator(validator_address))
+            .map(|validator| validator.is_jailed(block_number))
+            .unwrap_or(false);
+
+        // Scheduled withdrawals are ordered by their release block, so the released ones are
+        // always at the front.
+        let mut scheduled_withdrawals = store.get_scheduled_withdrawals(staker_address);
+        let num_released = if is_jailed {
+            0
+        } else {
+            scheduled_withdrawals
+                .iter()
+                .take_while(|withdrawal| withdrawal.release_block <= block_number)
+                .count()
+        };
+
+        // Fail if there is nothing to claim, neither released withdrawals nor rewards.
+        if num_released == 0 && staker.pending_reward(store).is_zero() {
+            debug!(%staker_address, "Staker has no released scheduled withdrawals or rewards");
+            return Err(AccountError::InvalidForRecipient);
+        }
+
+        // All checks passed, not allowed to fail from here on!
+
+        // Credit the rewards accrued by the active balance.
+        let rewards_receipt = self.credit_staker_rewards(store, &mut staker, tx_logger);
+
+        let claimed: Vec<ScheduledWithdrawal> =
+            scheduled_withdrawals.drain(..num_released).collect();
+        let value = claimed.iter().map(|withdrawal| withdrawal.value).sum();
+        store.put_scheduled_withdrawals(staker_address, scheduled_withdrawals);
+
+        // The claimed withdrawals are retired and can be removed from the staking contract.
+        staker.retired_balance += value;
+
+        tx_logger.push_log(Log::ClaimWithdrawals {
+            staker_address: staker_address.clone(),
+            validator_address: staker.delegation.clone(),
+            value,
+        });
+
+        // Update the staker entry.
+        store.put_staker(staker_address, staker);
+
+        Ok(ClaimWithdrawalsReceipt {
+            claimed,
+            rewards_receipt,
+        })
+    }
+
+    /// Reverts a claim withdrawals transaction.
+    pub fn revert_claim_withdrawals(
+        &mut self,
+        store: &mut StakingContractStoreWrite,
+        staker_address: &Address,
+        receipt: ClaimWithdrawalsReceipt,
+        tx_logger: &mut TransactionLog,
+    ) -> Result<(), AccountError> {
+        // Get the staker.
+        let mut staker = store.expect_staker(staker_address)?;
+
+        let value: Coin = receipt
+            .claimed
+            .iter()
+            .map(|withdrawal| withdrawal.value)
+            .sum();
+
+        // Move the claimed withdrawals back from the retired balance. They were scheduled before
+        // the remaining ones.
+        staker.retired_balance -= value;
+        let mut scheduled_withdrawals = receipt.claimed;
+        scheduled_withdrawals.append(&mut store.get_scheduled_withdrawals(staker_address));
+        store.put_scheduled_withdrawals(staker_address, scheduled_withdrawals);
+
+        tx_logger.push_log(Log::ClaimWithdrawals {
+            staker_address: staker_address.clone(),
+            validator_address: staker.delegation.clone(),
+            value,
+        });
+
+        // Revert crediting the rewards.
+        if let Some(rewards_receipt) = receipt.rewards_receipt {
+            self.revert_credit_staker_rewards(store, &mut staker, rewards_receipt, tx_logger);
+        }
+
         // Update the staker entry.
         store.put_staker(staker_address, staker);
 
@@ -763,12 +1155,16 @@ impl StakingContract {
         // We do not update the validator's stake balance because remove stake
         // is only referring to already retired stake. Thus this stake
         // has already been removed from the validator.
-        let receipt = if staker.total_balance().is_zero() {
+        let receipt = if staker.total_balance().is_zero()
+            && store.get_scheduled_withdrawals(staker_address).is_empty()
+        {
             // If staker is to be removed and it had delegation, we update the validator.
             if let Some(validator_address) = &staker.delegation {
                 self.unregister_staker_from_validator(store, validator_address);
             }
+            let rewards = store.get_staker_rewards(staker_address);
             store.remove_staker(staker_address);
+            store.put_staker_rewards(staker_address, StakerRewards::default());
 
             tx_logger.push_log(Log::DeleteStaker {
                 staker_address: staker_address.clone(),
@@ -777,6 +1173,8 @@ impl StakingContract {
 
             Some(DeleteStakerReceipt {
                 delegation: staker.delegation,
+                compound_rewards: rewards.compound_rewards,
+                reward_per_stake_paid: rewards.reward_per_stake_paid,
             })
         } else {
             store.put_staker(staker_address, staker);
@@ -806,6 +1204,14 @@ impl StakingContract {
                 validator_address: receipt.delegation.clone(),
             });
 
+            store.put_staker_rewards(
+                staker_address,
+                StakerRewards {
+                    reward_per_stake_paid: receipt.reward_per_stake_paid,
+                    compound_rewards: receipt.compound_rewards,
+                },
+            );
+
             Staker {
                 address: staker_address.clone(),
                 active_balance: Coin::ZERO,
@@ -837,6 +1243,120 @@ impl StakingContract {
         Ok(())
     }
 
+    /// Removes the tombstones of deleted validators by undelegating the stakers that are still
+    /// delegating to them. Their pending rewards are credited first. The tombstone is removed
+    /// together with its last staker.
+    /// Validators can only be deleted after their reporting window has passed, so the tombstones
+    /// are not needed to punish them anymore.
+    /// IMPORTANT: This iterates over all stakers if there is any tombstone!
+    pub(crate) fn remove_tombstones(
+        &mut self,
+        store: &mut StakingContractStoreWrite,
+        tx_logger: &mut TransactionLog,
+    ) -> RemoveTombstonesReceipt {
+        let mut undelegated_stakers = vec![];
+
+        if !store.has_tombstones() {
+            return RemoveTombstonesReceipt {
+                undelegated_stakers,
+            };
+        }
+
+        for mut staker in store.get_stakers() {
+            // Only stakers delegating to a deleted validator are undelegated.
+            let Some(validator_address) = staker.delegation.clone() else {
+                continue;
+            };
+            if store.get_tombstone(&validator_address).is_none() {
+                continue;
+            }
+
+            let staker_address = staker.address.clone();
+            let old_reward_per_stake_paid = store
+                .get_staker_rewards(&staker_address)
+                .reward_per_stake_paid;
+
+            // Credit the rewards accrued by the active balance.
+            let rewards_receipt = self.credit_staker_rewards(store, &mut staker, tx_logger);
+
+            // Remove the staker from the tombstone, which deletes it with its last staker.
+            if !staker.active_balance.is_zero() {
+                self.decrease_stake_from_validator(
+                    store,
+                    &validator_address,
+                    staker.active_balance,
+                );
+            }
+            self.unregister_staker_from_validator(store, &validator_address);
+
+            staker.delegation = None;
+            let mut rewards = store.get_staker_rewards(&staker_address);
+            rewards.reward_per_stake_paid = 0;
+            store.put_staker_rewards(&staker_address, rewards);
+
+            tx_logger.push_log(Log::UpdateStaker {
+                staker_address: staker_address.clone(),
+                old_validator_address: Some(validator_address.clone()),
+                new_validator_address: None,
+                active_balance: staker.active_balance,
+                inactive_from: staker.inactive_from,
+            });
+
+            // Update the staker entry.
+            store.put_staker(&staker_address, staker);
+
+            undelegated_stakers.push(UndelegateStakerReceipt {
+                staker_address,
+                delegation: validator_address,
+                old_reward_per_stake_paid,
+                rewards_receipt,
+            });
+        }
+
+        RemoveTombstonesReceipt {
+            undelegated_stakers,
+        }
+    }
+
+    /// Reverts removing the tombstones of deleted validators.
+    pub(crate) fn revert_remove_tombstones(
+        &mut self,
+        store: &mut StakingContractStoreWrite,
+        receipt: RemoveTombstonesReceipt,
+        tx_logger: &mut TransactionLog,
+    ) -> Result<(), AccountError> {
+        for receipt in receipt.undelegated_stakers.into_iter().rev() {
+            let mut staker = store.expect_staker(&receipt.staker_address)?;
+
+            tx_logger.push_log(Log::UpdateStaker {
+                staker_address: receipt.staker_address.clone(),
+                old_validator_address: Some(receipt.delegation.clone()),
+                new_validator_address: None,
+                active_balance: staker.active_balance,
+                inactive_from: staker.inactive_from,
+            });
+
+            // Restore the delegation, which recreates the tombstone with its last staker.
+            self.register_staker_on_validator(store, &receipt.delegation, true);
+            if !staker.active_balance.is_zero() {
+                self.increase_stake_to_validator(store, &receipt.delegation, staker.active_balance);
+            }
+            staker.delegation = Some(receipt.delegation);
+
+            if let Some(rewards_receipt) = receipt.rewards_receipt {
+                self.revert_credit_staker_rewards(store, &mut staker, rewards_receipt, tx_logger);
+            }
+            let mut rewards = store.get_staker_rewards(&receipt.staker_address);
+            rewards.reward_per_stake_paid = receipt.old_reward_per_stake_paid;
+            store.put_staker_rewards(&receipt.staker_address, rewards);
+
+            // Update the staker entry.
+            store.put_staker(&receipt.staker_address, staker);
+        }
+
+        Ok(())
+    }
+
     /* Helpers for delegation counter and balance update */
 
     /// Registers/links a new staker on the validator.
@@ -947,6 +1467,164 @@ impl StakingContract {
         }
     }
 
+    /// Pays the stakers' share of a validator's reward to the staking contract. The reward is
+    /// added to the validator's reward per stake and credited to the stakers' balances the next
+    /// time they interact with the staking contract.
+    pub(crate) fn distribute_reward(
+        &mut self,
+        store: &mut StakingContractStoreWrite,
+        validator_address: &Address,
+        value: Coin,
+        inherent_logger: &mut InherentLogger,
+    ) -> Result<RewardReceipt, AccountError> {
+        let validator = store.expect_validator(validator_address)?;
+
+        // Block producers never pay a reward to the stakers of a validator without delegated
+        // stake, see `Validator::stakers_reward`.
+        let delegated_stake = validator.total_stake.saturating_sub(validator.deposit);
+        if delegated_stake.is_zero() {
+            return Err(AccountError::InvalidForTarget);
+        }
+
+        // All checks passed, not allowed to fail from here on!
+
+        let reward_per_stake = ValidatorRewards::reward_per_stake_increase(value, delegated_stake);
+        let mut rewards = store.get_validator_rewards(validator_address);
+        rewards.reward_per_stake += reward_per_stake;
+        store.put_validator_rewards(validator_address, rewards);
+
+        self.balance += value;
+
+        inherent_logger.push_log(Log::PayoutReward {
+            to: Policy::STAKING_CONTRACT_ADDRESS,
+            value,
+        });
+
+        Ok(RewardReceipt { reward_per_stake })
+    }
+
+    /// Reverts paying the stakers' share of a validator's reward to the staking contract.
+    pub(crate) fn revert_distribute_reward(
+        &mut self,
+        store: &mut StakingContractStoreWrite,
+        validator_address: &Address,
+        value: Coin,
+        receipt: RewardReceipt,
+        inherent_logger: &mut InherentLogger,
+    ) -> Result<(), AccountError> {
+        let mut rewards = store.get_validator_rewards(validator_address);
+        rewards.reward_per_stake -= receipt.reward_per_stake;
+        store.put_validator_rewards(validator_address, rewards);
+
+        self.balance -= value;
+
+        inherent_logger.push_log(Log::PayoutReward {
+            to: Policy::STAKING_CONTRACT_ADDRESS,
+            value,
+        });
+
+        Ok(())
+    }
+
+    /// Credits the rewards accrued by the staker's active balance since it was last credited.
+    /// Stakers that compound their rewards have them added to their active balance, the rewards
+    /// of all other stakers are retired and can be removed from the staking contract.
+    /// The staker is changed in place and has to be stored by the caller. Returns a receipt if
+    /// the reward bookkeeping of the staker changed.
+    fn credit_staker_rewards(
+        &mut self,
+        store: &mut StakingContractStoreWrite,
+        staker: &mut Staker,
+        tx_logger: &mut TransactionLog,
+    ) -> Option<StakerRewardsReceipt> {
+        let validator_address = staker.delegation.clone()?;
+        let reward_per_stake = store
+            .get_validator_rewards(&validator_address)
+            .reward_per_stake;
+        let mut rewards = store.get_staker_rewards(&staker.address);
+        if rewards.reward_per_stake_paid == reward_per_stake {
+            return None;
+        }
+
+        let value = rewards.pending_reward(staker.active_balance, reward_per_stake);
+        let compounded = rewards.compound_rewards;
+        let receipt = StakerRewardsReceipt {
+            old_reward_per_stake_paid: rewards.reward_per_stake_paid,
+            value,
+            compounded,
+        };
+
+        rewards.reward_per_stake_paid = reward_per_stake;
+        store.put_staker_rewards(&staker.address, rewards);
+
+        if !value.is_zero() {
+            if compounded {
+                staker.active_balance += value;
+                self.increase_stake_to_validator(store, &validator_address, value);
+            } else {
+                staker.retired_balance += value;
+            }
+
+            tx_logger.push_log(Log::PayoutStakerReward {
+                staker_address: staker.address.clone(),
+                validator_address,
+                value,
+                compounded,
+            });
+        }
+
+        Some(receipt)
+    }
+
+    /// Reverts crediting the rewards of a staker. The staker is changed in place and has to be
+    /// stored by the caller.
+    fn revert_credit_staker_rewards(
+        &mut self,
+        store: &mut StakingContractStoreWrite,
+        staker: &mut Staker,
+        receipt: StakerRewardsReceipt,
+        tx_logger: &mut TransactionLog,
+    ) {
+        let validator_address = staker
+            .delegation
+            .clone()
+            .expect("Staker rewards are only credited to delegating stakers");
+
+        if !receipt.value.is_zero() {
+            if receipt.compounded {
+                staker.active_balance -= receipt.value;
+                self.decrease_stake_from_validator(store, &validator_address, receipt.value);
+            } else {
+                staker.retired_balance -= receipt.value;
+            }
+
+            tx_logger.push_log(Log::PayoutStakerReward {
+                staker_address: staker.address.clone(),
+                validator_address,
+                value: receipt.value,
+                compounded: receipt.compounded,
+            });
+        }
+
+        let mut rewards = store.get_staker_rewards(&staker.address);
+        rewards.reward_per_stake_paid = receipt.old_reward_per_stake_paid;
+        store.put_staker_rewards(&staker.address, rewards);
+    }
+
+    /// Sets the reward bookkeeping of a staker that starts delegating to the given validator,
+    /// such that it only accrues the rewards paid from now on.
+    fn reset_staker_rewards(
+        store: &mut StakingContractStoreWrite,
+        staker_address: &Address,
+        validator_address: &Address,
+    ) {
+        let mut rewards = store.get_staker_rewards(staker_address);
+        rewards.reward_per_stake_paid = store
+            .get_validator_rewards(validator_address)
+            .reward_per_stake;
+        store.put_staker_rewards(staker_address, rewards);
+    }
+
     /// Adds `value` coins to a given validator's total stake.
     fn increase_stake_to_validator(
         &mut self,
@@ -4,9 +4,14 @@ use nimiq_primitives::account::AccountError;
 use nimiq_primitives::key_nibbles::KeyNibbles;
 
 #[cfg(feature = "interaction-traits")]
-use crate::data_store::DataStoreWrite;
 use crate::{
-    account::staking_contract::{validator::Tombstone, Staker, Validator},
+    account::staking_contract::validator_stakers::ValidatorStakersIndex, data_store::DataStoreWrite,
+};
+use crate::{
+    account::staking_contract::{
+        validator::Tombstone, ScheduledWithdrawal, Staker, StakerRewards, Validator,
+        ValidatorRewards,
+    },
     data_store_ops::{DataStoreIterOps, DataStoreReadOps},
 };
 
@@ -17,6 +22,9 @@ impl StakingContractStore {
     const PREFIX_VALIDATOR: u8 = 0;
     const PREFIX_STAKER: u8 = 1;
     const PREFIX_TOMBSTONE: u8 = 2;
+    const PREFIX_VALIDATOR_REWARDS: u8 = 4;
+    const PREFIX_STAKER_REWARDS: u8 = 5;
+    const PREFIX_STAKER_WITHDRAWALS: u8 = 6;
 
     pub fn validator_key(address: &Address) -> KeyNibbles {
         Self::prefixed_address(Self::PREFIX_VALIDATOR, address)
@@ -30,6 +38,26 @@ impl StakingContractStore {
         Self::prefixed_address(Self::PREFIX_TOMBSTONE, address)
     }
 
+    pub fn validator_rewards_key(address: &Address) -> KeyNibbles {
+        Self::prefixed_address(Self::PREFIX_VALIDATOR_REWARDS, address)
+    }
+
+    pub fn staker_rewards_key(address: &Address) -> KeyNibbles {
+        Self::prefixed_address(Self::PREFIX_STAKER_REWARDS, address)
+    }
+
+    pub fn staker_withdrawals_key(address: &Address) -> KeyNibbles {
+        Self::prefixed_address(Self::PREFIX_STAKER_WITHDRAWALS, address)
+    }
+
+    /// Returns the address of the staker if the key is the key of a staker.
+    pub fn staker_address(key: &KeyNibbles) -> Option<Address> {
+        if !KeyNibbles::from(&[Self::PREFIX_STAKER][..]).is_prefix_of(key) {
+            return None;
+        }
+        key.suffix(2).to_address()
+    }
+
     fn prefixed_address(prefix: u8, address: &Address) -> KeyNibbles {
         let mut key = [0u8; 21];
         key[0] = prefix;
@@ -44,6 +72,15 @@ pub trait StakingContractStoreReadOps {
     fn get_staker(&self, address: &Address) -> Option<Staker>;
 
     fn get_tombstone(&self, address: &Address) -> Option<Tombstone>;
+
+    /// Returns the reward bookkeeping of the validator, which is the default if there is no entry.
+    fn get_validator_rewards(&self, address: &Address) -> ValidatorRewards;
+
+    /// Returns the reward bookkeeping of the staker, which is the default if there is no entry.
+    fn get_staker_rewards(&self, address: &Address) -> StakerRewards;
+
+    /// Returns the scheduled withdrawals of the staker, which are empty if there is no entry.
+    fn get_scheduled_withdrawals(&self, address: &Address) -> Vec<ScheduledWithdrawal>;
 }
 
 pub(crate) struct StakingContractStoreRead<'read, T: DataStoreReadOps>(&'read T);
@@ -66,6 +103,24 @@ impl<T: DataStoreReadOps> StakingContractStoreReadOps for StakingContractStoreRe
     fn get_tombstone(&self, address: &Address) -> Option<Tombstone> {
         self.0.get(&StakingContractStore::tombstone_key(address))
     }
+
+    fn get_validator_rewards(&self, address: &Address) -> ValidatorRewards {
+        self.0
+            .get(&StakingContractStore::validator_rewards_key(address))
+            .unwrap_or_default()
+    }
+
+    fn get_staker_rewards(&self, address: &Address) -> StakerRewards {
+        self.0
+            .get(&StakingContractStore::staker_rewards_key(address))
+            .unwrap_or_default()
+    }
+
+    fn get_scheduled_withdrawals(&self, address: &Address) -> Vec<ScheduledWithdrawal> {
+        self.0
+            .get(&StakingContractStore::staker_withdrawals_key(address))
+            .unwrap_or_default()
+    }
 }
 
 impl<T: DataStoreReadOps + DataStoreIterOps> StakingContractStoreRead<'_, T> {
@@ -77,8 +132,13 @@ impl<T: DataStoreReadOps + DataStoreIterOps> StakingContractStoreRead<'_, T> {
     }
 
     pub(crate) fn iter_validators(&self) -> impl Iterator<Item = Validator> {
+        self.iter_validators_from(&Address::START_ADDRESS)
+    }
+
+    /// Iterates over the validators in address order, starting at the given address (inclusive).
+    pub(crate) fn iter_validators_from(&self, start: &Address) -> impl Iterator<Item = Validator> {
         self.0.iter(
-            &StakingContractStore::validator_key(&Address::START_ADDRESS),
+            &StakingContractStore::validator_key(start),
             &StakingContractStore::validator_key(&Address::END_ADDRESS),
         )
     }
@@ -106,12 +166,34 @@ impl<'write, 'store, 'tree, 'txn, 'txni, 'env>
         self.0.remove(&StakingContractStore::validator_key(address))
     }
 
+    /// Stores the staker and keeps the local validator stakers index in sync with its delegation.
     pub fn put_staker(&mut self, address: &Address, staker: Staker) {
+        let old_delegation = self
+            .get_staker(address)
+            .and_then(|old_staker| old_staker.delegation);
+
+        if old_delegation != staker.delegation {
+            if let Some(validator_address) = &old_delegation {
+                ValidatorStakersIndex::remove(self.0.raw_txn(), validator_address, address);
+            }
+            if let Some(validator_address) = &staker.delegation {
+                ValidatorStakersIndex::add(self.0.raw_txn(), validator_address, address);
+            }
+        }
+
         self.0
             .put(&StakingContractStore::staker_key(address), staker)
     }
 
+    /// Removes the staker and its entry in the local validator stakers index.
     pub fn remove_staker(&mut self, address: &Address) {
+        if let Some(validator_address) = self
+            .get_staker(address)
+            .and_then(|staker| staker.delegation)
+        {
+            ValidatorStakersIndex::remove(self.0.raw_txn(), &validator_address, address);
+        }
+
         self.0.remove(&StakingContractStore::staker_key(address))
     }
 
@@ -123,6 +205,65 @@ impl<'write, 'store, 'tree, 'txn, 'txni, 'env>
     pub fn remove_tombstone(&mut self, address: &Address) {
         self.0.remove(&StakingContractStore::tombstone_key(address))
     }
+
+    /// Returns true if there is at least one tombstone in the contract.
+    pub(crate) fn has_tombstones(&self) -> bool {
+        self.0
+            .iter::<Tombstone>(
+                &StakingContractStore::tombstone_key(&Address::START_ADDRESS),
+                &StakingContractStore::tombstone_key(&Address::END_ADDRESS),
+            )
+            .next()
+            .is_some()
+    }
+
+    /// Returns all stakers in the contract.
+    /// IMPORTANT: This is potentially a very expensive operation!
+    pub(crate) fn get_stakers(&self) -> Vec<Staker> {
+        self.0
+            .iter(
+                &StakingContractStore::staker_key(&Address::START_ADDRESS),
+                &StakingContractStore::staker_key(&Address::END_ADDRESS),
+            )
+            .collect()
+    }
+
+    /// Stores the reward bookkeeping of the validator. Default values are not stored, so the
+    /// entry only exists once the validator shares its rewards.
+    pub fn put_validator_rewards(&mut self, address: &Address, rewards: ValidatorRewards) {
+        let key = StakingContractStore::validator_rewards_key(address);
+        if rewards == ValidatorRewards::default() {
+            self.0.remove(&key)
+        } else {
+            self.0.put(&key, rewards)
+        }
+    }
+
+    /// Stores the scheduled withdrawals of the staker. The entry only exists while there are
+    /// scheduled withdrawals.
+    pub fn put_scheduled_withdrawals(
+        &mut self,
+        address: &Address,
+        withdrawals: Vec<ScheduledWithdrawal>,
+    ) {
+        let key = StakingContractStore::staker_withdrawals_key(address);
+        if withdrawals.is_empty() {
+            self.0.remove(&key)
+        } else {
+            self.0.put(&key, withdrawals)
+        }
+    }
+
+    /// Stores the reward bookkeeping of the staker. Default values are not stored, so the entry
+    /// only exists once the staker delegates to a validator that shares its rewards.
+    pub fn put_staker_rewards(&mut self, address: &Address, rewards: StakerRewards) {
+        let key = StakingContractStore::staker_rewards_key(address);
+        if rewards == StakerRewards::default() {
+            self.0.remove(&key)
+        } else {
+            self.0.put(&key, rewards)
+        }
+    }
 }
 
 #[cfg(feature = "interaction-traits")]
@@ -138,6 +279,24 @@ impl StakingContractStoreReadOps for StakingContractStoreWrite<'_, '_, '_, '_, '
     fn get_tombstone(&self, address: &Address) -> Option<Tombstone> {
         self.0.get(&StakingContractStore::tombstone_key(address))
     }
+
+    fn get_validator_rewards(&self, address: &Address) -> ValidatorRewards {
+        self.0
+            .get(&StakingContractStore::validator_rewards_key(address))
+            .unwrap_or_default()
+    }
+
+    fn get_staker_rewards(&self, address: &Address) -> StakerRewards {
+        self.0
+            .get(&StakingContractStore::staker_rewards_key(address))
+            .unwrap_or_default()
+    }
+
+    fn get_scheduled_withdrawals(&self, address: &Address) -> Vec<ScheduledWithdrawal> {
+        self.0
+            .get(&StakingContractStore::staker_withdrawals_key(address))
+            .unwrap_or_default()
+    }
 }
 
 #[cfg(feature = "interaction-traits")]
@@ -12,7 +12,7 @@ use nimiq_transaction::{
 
 use crate::{
     account::staking_contract::{
-        receipts::PenalizeReceipt,
+        receipts::{PenalizeReceipt, RemoveTombstonesReceipt, RewardReceipt},
         store::{
             StakingContractStoreRead, StakingContractStoreReadOps, StakingContractStoreReadOpsExt,
             StakingContractStoreWrite,
@@ -152,6 +152,21 @@ impl AccountTransactionInteraction for StakingContract {
                 )
                 .map(|receipt| Some(receipt.into()))
             }
+            IncomingStakingTransactionData::SetValidatorCommission {
+                new_commission,
+                proof,
+            } => {
+                // Get the validator address from the proof.
+                let validator_address = proof.compute_signer();
+
+                self.set_validator_commission(
+                    &mut store,
+                    &validator_address,
+                    new_commission,
+                    tx_logger,
+                )
+                .map(|receipt| Some(receipt.into()))
+            }
             IncomingStakingTransactionData::CreateStaker { delegation, proof } => {
                 // Get the staker address from the proof.
                 let staker_address = proof.compute_signer();
@@ -169,7 +184,7 @@ impl AccountTransactionInteraction for StakingContract {
             }
             IncomingStakingTransactionData::AddStake { staker_address } => self
                 .add_stake(&mut store, &staker_address, transaction.value, tx_logger)
-                .map(|_| None),
+                .map(|receipt| receipt.map(Into::into)),
             IncomingStakingTransactionData::UpdateStaker {
                 new_delegation,
                 reactivate_all_stake,
@@ -220,6 +235,41 @@ impl AccountTransactionInteraction for StakingContract {
                 )
                 .map(|receipt| Some(receipt.into()))
             }
+            IncomingStakingTransactionData::SetRewardCompounding {
+                compound_rewards,
+                proof,
+            } => {
+                // Get the staker address from the proof.
+                let staker_address = proof.compute_signer();
+
+                self.set_reward_compounding(
+                    &mut store,
+                    &staker_address,
+                    compound_rewards,
+                    tx_logger,
+                )
+                .map(|receipt| Some(receipt.into()))
+            }
+            IncomingStakingTransactionData::ScheduleUnstake { value, proof } => {
+                // Get the staker address from the proof.
+                let staker_address = proof.compute_signer();
+
+                self.schedule_unstake(
+                    &mut store,
+                    &staker_address,
+                    value,
+                    block_state.number,
+                    tx_logger,
+                )
+                .map(|receipt| receipt.map(Into::into))
+            }
+            IncomingStakingTransactionData::ClaimWithdrawals { proof } => {
+                // Get the staker address from the proof.
+                let staker_address = proof.compute_signer();
+
+                self.claim_withdrawals(&mut store, &staker_address, block_state.number, tx_logger)
+                    .map(|receipt| Some(receipt.into()))
+            }
         }
     }
 
@@ -274,6 +324,19 @@ impl AccountTransactionInteraction for StakingContract {
 
                 self.revert_retire_validator(&mut store, &validator_address, receipt, tx_logger)
             }
+            IncomingStakingTransactionData::SetValidatorCommission { proof, .. } => {
+                // Get the validator address from the proof.
+                let validator_address = proof.compute_signer();
+
+                let receipt = receipt.ok_or(AccountError::InvalidReceipt)?.try_into()?;
+
+                self.revert_set_validator_commission(
+                    &mut store,
+                    &validator_address,
+                    receipt,
+                    tx_logger,
+                )
+            }
             IncomingStakingTransactionData::CreateStaker { proof, .. } => {
                 // Get the staker address from the proof.
                 let staker_address = proof.compute_signer();
@@ -281,7 +344,15 @@ impl AccountTransactionInteraction for StakingContract {
                 self.revert_create_staker(&mut store, &staker_address, transaction.value, tx_logger)
             }
             IncomingStakingTransactionData::AddStake { staker_address } => {
-                self.revert_add_stake(&mut store, &staker_address, transaction.value, tx_logger)
+                let receipt = receipt.map(TryInto::try_into).transpose()?;
+
+                self.revert_add_stake(
+                    &mut store,
+                    &staker_address,
+                    transaction.value,
+                    receipt,
+                    tx_logger,
+                )
             }
             IncomingStakingTransactionData::UpdateStaker { proof, .. } => {
                 // Get the staker address from the proof.
@@ -325,6 +396,30 @@ impl AccountTransactionInteraction for StakingContract {
                     tx_logger,
                 )
             }
+            IncomingStakingTransactionData::SetRewardCompounding { proof, .. } => {
+                // Get the staker address from the proof.
+                let staker_address = proof.compute_signer();
+
+                let receipt = receipt.ok_or(AccountError::InvalidReceipt)?.try_into()?;
+
+                self.revert_set_reward_compounding(&mut store, &staker_address, receipt, tx_logger)
+            }
+            IncomingStakingTransactionData::ScheduleUnstake { value, proof } => {
+                // Get the staker address from the proof.
+                let staker_address = proof.compute_signer();
+
+                let receipt = receipt.map(TryInto::try_into).transpose()?;
+
+                self.revert_schedule_unstake(&mut store, &staker_address, value, receipt, tx_logger)
+            }
+            IncomingStakingTransactionData::ClaimWithdrawals { proof } => {
+                // Get the staker address from the proof.
+                let staker_address = proof.compute_signer();
+
+                let receipt = receipt.ok_or(AccountError::InvalidReceipt)?.try_into()?;
+
+                self.revert_claim_withdrawals(&mut store, &staker_address, receipt, tx_logger)
+            }
         }
     }
 
@@ -797,7 +892,32 @@ impl AccountInherentInteraction for StakingContract {
                 // Since finalized epochs cannot be reverted, we don't need any receipts.
                 Ok(None)
             }
-            Inherent::Reward { .. } => Err(AccountError::InvalidForTarget),
+            Inherent::RemoveTombstones => {
+                let mut tx_logger = TransactionLog::empty();
+                let receipt = self.remove_tombstones(
+                    &mut StakingContractStoreWrite::new(&mut data_store),
+                    &mut tx_logger,
+                );
+                inherent_logger.push_tx_logger(tx_logger);
+
+                Ok(Some(receipt.into()))
+            }
+            Inherent::Reward {
+                validator_address,
+                value,
+                ..
+            } => {
+                // Rewards paid to the staking contract are the stakers' share of a validator's
+                // reward. They are credited to the stakers lazily.
+                let receipt = self.distribute_reward(
+                    &mut StakingContractStoreWrite::new(&mut data_store),
+                    validator_address,
+                    *value,
+                    inherent_logger,
+                )?;
+
+                Ok(Some(receipt.into()))
+            }
         }
     }
 
@@ -868,7 +988,36 @@ impl AccountInherentInteraction for StakingContract {
                 // We should not be able to revert finalized epochs or batches!
                 Err(AccountError::InvalidForTarget)
             }
-            Inherent::Reward { .. } => Err(AccountError::InvalidForTarget),
+            Inherent::RemoveTombstones => {
+                let receipt: RemoveTombstonesReceipt =
+                    receipt.ok_or(AccountError::InvalidReceipt)?.try_into()?;
+
+                let mut tx_logger = TransactionLog::empty();
+                self.revert_remove_tombstones(
+                    &mut StakingContractStoreWrite::new(&mut data_store),
+                    receipt,
+                    &mut tx_logger,
+                )?;
+                inherent_logger.push_tx_logger(tx_logger);
+
+                Ok(())
+            }
+            Inherent::Reward {
+                validator_address,
+                value,
+                ..
+            } => {
+                let receipt: RewardReceipt =
+                    receipt.ok_or(AccountError::InvalidReceipt)?.try_into()?;
+
+                self.revert_distribute_reward(
+                    &mut StakingContractStoreWrite::new(&mut data_store),
+                    validator_address,
+                    *value,
+                    receipt,
+                    inherent_logger,
+                )
+            }
         }
     }
 }
@@ -9,7 +9,10 @@ use serde::{Deserialize, Serialize};
 #[cfg(feature = "interaction-traits")]
 use crate::{
     account::staking_contract::{
-        receipts::{DeleteValidatorReceipt, ReactivateValidatorReceipt, UpdateValidatorReceipt},
+        receipts::{
+            DeleteValidatorReceipt, ReactivateValidatorReceipt, SetValidatorCommissionReceipt,
+            UpdateValidatorReceipt,
+        },
         store::{
             StakingContractStoreReadOps, StakingContractStoreReadOpsExt, StakingContractStoreWrite,
         },
@@ -26,6 +29,7 @@ use crate::{
 /// 4. Reactivate: Reactivates a validator.
 /// 5. Delete: Deletes a validator (validator must have been inactive for the cooldown period).
 /// 6. Retire: Permanently retires a validator. This action is required for deletion.
+/// 7. SetCommission: Sets the commission the validator keeps from the rewards of its slots.
 ///
 /// The actions can be summarized by the following state diagram:
 ///           +---+----+                            +---------+
@@ -47,9 +51,10 @@ use crate::{
 /// (**) The validator may be set to automatically reactivate itself upon inactivation.
 ///      If this setting is not enabled the state change can only be triggered manually.
 ///
-/// Create, Update, Deactivate, Retire and Re-activate are incoming transactions to the staking contract.
+/// Create, Update, Deactivate, Retire, Re-activate and SetCommission are incoming transactions to the
+/// staking contract.
 /// Delete is an outgoing transaction from the staking contract.
-/// To Create, Update or Delete, the cold key must be used (the one corresponding to the validator
+/// To Create, Update, SetCommission or Delete, the cold key must be used (the one corresponding to the validator
 /// address). For the other transactions, the signing key must be used.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Validator {
@@ -60,7 +65,8 @@ pub struct Validator {
     pub signing_key: SchnorrPublicKey,
     /// The voting public key, it is used to vote for skip and macro blocks.
     pub voting_key: BlsPublicKey,
-    /// The reward address of the validator. All the block rewards are paid to this address.
+    /// The reward address of the validator. The block rewards are paid to this address, except
+    /// for the stakers' share (see `ValidatorRewards`).
     pub reward_address: Address,
     /// Signaling field. Can be used to do chain upgrades or for any other purpose that requires
     /// validators to coordinate among themselves.
@@ -92,6 +98,25 @@ impl Validator {
         self.inactive_from.is_none()
     }
 
+    /// Returns the part of the given reward that is paid to the validator's stakers if the
+    /// validator charges the given commission. The validator keeps its commission as well as the
+    /// share of the remaining reward that corresponds to its deposit. If no stake is delegated to
+    /// the validator, it keeps the whole reward.
+    pub fn stakers_reward(&self, reward: Coin, commission: u16) -> Coin {
+        let delegated_stake = self.total_stake.saturating_sub(self.deposit);
+        if delegated_stake.is_zero() {
+            return Coin::ZERO;
+        }
+
+        let shared_reward = u64::from(reward) as u128
+            * (Policy::MAX_VALIDATOR_COMMISSION - commission) as u128
+            / Policy::MAX_VALIDATOR_COMMISSION as u128;
+        let stakers_reward = shared_reward * u64::from(delegated_stake) as u128
+            / u64::from(self.total_stake) as u128;
+
+        Coin::from_u64_unchecked(stakers_reward as u64)
+    }
+
     /// Checks if a validator is currently jailed.
     pub fn is_jailed(&self, block_number: u32) -> bool {
         if let Some(jailed_from) = self.jailed_from {
@@ -342,6 +367,56 @@ impl StakingContract {
         Ok(())
     }
 
+    /// Sets the commission the validator keeps from its rewards, in basis points.
+    /// This function is public to fill the genesis staking contract.
+    pub fn set_validator_commission(
+        &mut self,
+        store: &mut StakingContractStoreWrite,
+        validator_address: &Address,
+        commission: u16,
+        tx_logger: &mut TransactionLog,
+    ) -> Result<SetValidatorCommissionReceipt, AccountError> {
+        // Check that the validator exists.
+        store.expect_validator(validator_address)?;
+
+        // All checks passed, not allowed to fail from here on!
+
+        let mut rewards = store.get_validator_rewards(validator_address);
+        let receipt = SetValidatorCommissionReceipt {
+            old_commission: rewards.commission,
+        };
+        rewards.commission = commission;
+        store.put_validator_rewards(validator_address, rewards);
+
+        tx_logger.push_log(Log::SetValidatorCommission {
+            validator_address: validator_address.clone(),
+            commission,
+        });
+
+        Ok(receipt)
+    }
+
+    /// Reverts setting the commission of a validator.
+    pub fn revert_set_validator_commission(
+        &mut self,
+        store: &mut StakingContractStoreWrite,
+        validator_address: &Address,
+        receipt: SetValidatorCommissionReceipt,
+        tx_logger: &mut TransactionLog,
+    ) -> Result<(), AccountError> {
+        let mut rewards = store.get_validator_rewards(validator_address);
+
+        tx_logger.push_log(Log::SetValidatorCommission {
+            validator_address: validator_address.clone(),
+            commission: rewards.commission,
+        });
+
+        rewards.commission = receipt.old_commission;
+        store.put_validator_rewards(validator_address, rewards);
+
+        Ok(())
+    }
+
     /// Deactivates a validator. It is necessary to retire a validator before dropping it.
     pub fn deactivate_validator(
         &mut self,
@@ -710,7 +785,9 @@ impl StakingContract {
     /// at least one staker for a validator, we leave a tombstone for it behind that tracks the
     /// remaining total_stake. This is necessary to be able to correctly restore the validator entry
     /// in case it is created again. The tombstone is deleted once the last delegation to the
-    /// deleted validator is removed (e.g. by update_staker or remove_stake).
+    /// deleted validator is removed (e.g. by update_staker or remove_stake). From version 2 of the
+    /// protocol on, the remaining stakers are undelegated at the end of the epoch by the
+    /// `RemoveTombstones` inherent, which deletes the tombstone.
     pub fn delete_validator(
         &mut self,
         store: &mut StakingContractStoreWrite,
@@ -0,0 +1,243 @@
+use nimiq_database::{
+    declare_table,
+    mdbx::{MdbxDatabase, MdbxReadTransaction, MdbxWriteTransaction},
+    traits::{Database, DupReadCursor, ReadCursor, ReadTransaction, WriteTransaction},
+    utils::IndexedValue,
+};
+use nimiq_keys::Address;
+
+use crate::{
+    account::staking_contract::store::{StakingContractStoreRead, StakingContractStoreReadOps},
+    data_store::DataStore,
+};
+
+// Validator address -> staker address
+declare_table!(ValidatorStakersTable, "ValidatorStakers", Address => Address => ());
+// Present once the index has been built from the trie.
+declare_table!(ValidatorStakersInitializedTable, "ValidatorStakersInitialized", () => ());
+
+/// Local index of the stakers delegating to each validator.
+///
+/// The index isn't part of the accounts trie and thus not part of the consensus state. It only
+/// reflects the trie while the trie is complete and is rebuilt from the trie when the trie becomes
+/// complete, see `Accounts`. Whether the index has been built is recorded explicitly, since the
+/// index of a staking contract without delegating stakers is legitimately empty.
+pub(crate) struct ValidatorStakersIndex;
+
+impl ValidatorStakersIndex {
+    pub fn create_table(env: &MdbxDatabase) {
+        env.create_dup_table(&ValidatorStakersTable);
+        env.create_regular_table(&ValidatorStakersInitializedTable);
+    }
+
+    pub fn add(txn: &mut MdbxWriteTransaction, validator_address: &Address, staker: &Address) {
+        txn.put(
+            &ValidatorStakersTable,
+            validator_address,
+            &IndexedValue::new(staker.clone(), ()),
+        );
+    }
+
+    pub fn remove(txn: &mut MdbxWriteTransaction, validator_address: &Address, staker: &Address) {
+        txn.remove_item(
+            &ValidatorStakersTable,
+            validator_address,
+            &IndexedValue::new(staker.clone(), ()),
+        );
+    }
+
+    /// Returns the addresses of at most `limit` stakers delegating to the given validator in
+    /// address order, starting at the given staker address (inclusive).
+    pub fn staker_addresses(
+        txn: &MdbxReadTransaction,
+        validator_address: &Address,
+        start: &Address,
+        limit: usize,
+    ) -> Vec<Address> {
+        let mut addresses = vec![];
+        if limit == 0 {
+            return addresses;
+        }
+
+        let mut cursor = txn.dup_cursor(&ValidatorStakersTable);
+        let Some(first) = cursor.set_lowerbound_subkey(validator_address, start) else {
+            return addresses;
+        };
+        addresses.push(first.index);
+
+        while addresses.len() < limit {
+            match cursor.next_duplicate() {
+                Some((_, entry)) => addresses.push(entry.index),
+                None => break,
+            }
+        }
+        addresses
+    }
+
+    /// Replaces the entry of a staker whose delegation changed.
+    pub fn update(
+        txn: &mut MdbxWriteTransaction,
+        staker_address: &Address,
+        old_delegation: Option<&Address>,
+        new_delegation: Option<&Address>,
+    ) {
+        if old_delegation == new_delegation {
+            return;
+        }
+        if let Some(validator_address) = old_delegation {
+            Self::remove(txn, validator_address, staker_address);
+        }
+        if let Some(validator_address) = new_delegation {
+            Self::add(txn, validator_address, staker_address);
+        }
+    }
+
+    /// Returns whether the index has been built from the trie.
+    pub fn is_initialized(txn: &MdbxReadTransaction) -> bool {
+        txn.get(&ValidatorStakersInitializedTable, &()).is_some()
+    }
+
+    pub fn clear(txn: &mut MdbxWriteTransaction) {
+        txn.clear_table(&ValidatorStakersTable);
+        txn.remove(&ValidatorStakersInitializedTable, &());
+    }
+
+    /// Returns the validators the given stakers delegate to according to the data store.
+    pub fn delegations(
+        txn: &MdbxReadTransaction,
+        data_store: &DataStore,
+        stakers: &[Address],
+    ) -> Vec<Option<Address>> {
+        let data_store_read = data_store.read(txn);
+        let store = StakingContractStoreRead::new(&data_store_read);
+        stakers
+            .iter()
+            .map(|address| store.get_staker(address)?.delegation)
+            .collect()
+    }
+
+    /// Rebuilds the index from the stakers in the given staking contract data store.
+    pub fn rebuild(txn: &mut MdbxWriteTransaction, data_store: &DataStore) {
+        let entries: Vec<(Address, Address)> = StakingContractStoreRead::new(&data_store.read(txn))
+            .iter_stakers()
+            .filter_map(|staker| Some((staker.delegation?, staker.address)))
+            .collect();
+
+        Self::clear(txn);
+        for (validator_address, staker_address) in entries {
+            Self::add(txn, &validator_address, &staker_address);
+        }
+        txn.put(&ValidatorStakersInitializedTable, &(), &());
+    }
+}
+
+#[cfg(all(test, feature = "accounts"))]
+mod tests {
+    use nimiq_database::{
+        mdbx::MdbxDatabase,
+        traits::{Database, WriteTransaction},
+    };
+    use nimiq_keys::Address;
+    use nimiq_primitives::{
+        coin::Coin,
+        key_nibbles::KeyNibbles,
+        policy::Policy,
+        trie::trie_diff::{RevertDiffValue, RevertTrieDiff},
+    };
+    use nimiq_serde::Serialize;
+
+    use super::ValidatorStakersIndex;
+    use crate::{
+        Accounts, DataStore, Staker, StakingContract, StakingContractStore,
+        StakingContractStoreWrite,
+    };
+
+    fn staker_addresses(
+        env: &MdbxDatabase,
+        data_store: &DataStore,
+        validator: &Address,
+    ) -> Vec<Address> {
+        let txn = env.read_transaction();
+        StakingContract::default()
+            .get_stakers_for_validator(&data_store.read(&txn), validator)
+            .into_iter()
+            .map(|staker| staker.address)
+            .collect()
+    }
+
+    fn staker(address: Address, delegation: Option<Address>) -> Staker {
+        Staker {
+            address,
+            active_balance: Coin::from_u64_unchecked(1_000),
+            inactive_balance: Coin::ZERO,
+            inactive_from: None,
+            retired_balance: Coin::ZERO,
+            delegation,
+        }
+    }
+
+    #[test]
+    fn it_rebuilds_a_missing_index_on_startup() {
+        let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+        let validator_address = Address([0xaa; 20]);
+        {
+            let accounts = Accounts::new(env.clone());
+            let data_store = accounts.data_store(&Policy::STAKING_CONTRACT_ADDRESS);
+            let mut raw_txn = env.write_transaction();
+            let mut txn = (&mut raw_txn).into();
+            let mut data_store_write = data_store.write(&mut txn);
+            let mut store = StakingContractStoreWrite::new(&mut data_store_write);
+            for i in 0..3u8 {
+                let delegation = (i != 1).then(|| validator_address.clone());
+                store.put_staker(&Address([i; 20]), staker(Address([i; 20]), delegation));
+            }
+
+            // Simulate a database that was created before the index existed.
+            ValidatorStakersIndex::clear(&mut raw_txn);
+            assert!(!ValidatorStakersIndex::is_initialized(&raw_txn));
+            raw_txn.commit();
+        }
+
+        let accounts = Accounts::new(env.clone());
+        let data_store = accounts.data_store(&Policy::STAKING_CONTRACT_ADDRESS);
+        assert_eq!(
+            staker_addresses(&env, &data_store, &validator_address),
+            vec![Address([0; 20]), Address([2; 20])]
+        );
+    }
+
+    #[test]
+    fn it_updates_the_index_when_reverting_a_diff() {
+        let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+        let accounts = Accounts::new(env.clone());
+        let data_store = accounts.data_store(&Policy::STAKING_CONTRACT_ADDRESS);
+        let validator1 = Address([0xaa; 20]);
+        let validator2 = Address([0xbb; 20]);
+        let staker_address = Address([1; 20]);
+
+        let mut raw_txn = env.write_transaction();
+        let mut txn = (&mut raw_txn).into();
+        {
+            let mut data_store_write = data_store.write(&mut txn);
+            let mut store = StakingContractStoreWrite::new(&mut data_store_write);
+            store.put_staker(
+                &staker_address,
+                staker(staker_address.clone(), Some(validator1.clone())),
+            );
+        }
+
+        // Reverting the diff moves the staker to the other validator.
+        let key = &KeyNibbles::from(&Policy::STAKING_CONTRACT_ADDRESS)
+            + &StakingContractStore::staker_key(&staker_address);
+        let value = staker(staker_address.clone(), Some(validator2.clone())).serialize_to_vec();
+        let diff = RevertTrieDiff([(key, RevertDiffValue::Put(value))].into());
+        accounts.revert_diff(&mut txn, diff).unwrap();
+        raw_txn.commit();
+
+        assert!(staker_addresses(&env, &data_store, &validator1).is_empty());
+        assert_eq!(
+            staker_addresses(&env, &data_store, &validator2),
+            vec![staker_address]
+        );
+    }
+}
@@ -1,15 +1,16 @@
 use nimiq_keys::Address;
 #[cfg(feature = "interaction-traits")]
 use nimiq_primitives::account::AccountType;
-use nimiq_primitives::{account::AccountError, coin::Coin};
+use nimiq_primitives::{account::AccountError, coin::Coin, key_nibbles::KeyNibbles};
 use nimiq_serde::{Deserialize, Serialize};
 #[cfg(feature = "interaction-traits")]
 use nimiq_transaction::{
-    account::vesting_contract::CreationTransactionData, inherent::Inherent, SignatureProof,
-    Transaction,
+    account::vesting_contract::{CreationTransactionData, VestingBeneficiary},
+    inherent::Inherent,
+    SignatureProof, Transaction,
 };
 
-use crate::{convert_receipt, AccountReceipt};
+use crate::{convert_receipt, data_store_ops::DataStoreReadOps, AccountReceipt};
 #[cfg(feature = "interaction-traits")]
 use crate::{
     data_store::{DataStoreRead, DataStoreWrite},
@@ -24,7 +25,8 @@ use crate::{
 pub struct VestingContract {
     /// Total balance of the contract.
     pub balance: Coin,
-    /// The owner of the contract, the only address that can interact with it.
+    /// The owner of the contract, the only address that can interact with it if there are no
+    /// beneficiaries.
     pub owner: Address,
     /// The time at which the release schedule starts.
     #[serde(with = "nimiq_serde::fixint::be")]
@@ -38,12 +40,47 @@ pub struct VestingContract {
     pub total_amount: Coin,
 }
 
+/// A beneficiary of a vesting contract. The beneficiaries are stored in the data store of the
+/// contract such that the layout of the contract itself is unchanged.
+#[derive(Clone, PartialEq, PartialOrd, Eq, Ord, Debug, Serialize, Deserialize)]
+pub struct VestingContractBeneficiary {
+    /// The address of the beneficiary, the only address that can withdraw its share.
+    pub address: Address,
+    /// The weight of the beneficiary relative to the total weight of all beneficiaries.
+    pub weight: u16,
+    /// The amount (including fees) the beneficiary has withdrawn so far.
+    pub withdrawn: Coin,
+}
+
+impl VestingContract {
+    /// Returns the key of the beneficiaries in the data store of the contract.
+    pub fn beneficiaries_key() -> KeyNibbles {
+        KeyNibbles::from(&[0u8][..])
+    }
+
+    /// Get the beneficiaries the released funds are split between according to their weights.
+    /// If there are none, the owner can withdraw all released funds.
+    pub fn get_beneficiaries<T: DataStoreReadOps>(
+        &self,
+        data_store: &T,
+    ) -> Vec<VestingContractBeneficiary> {
+        data_store
+            .get(&Self::beneficiaries_key())
+            .unwrap_or_default()
+    }
+}
+
 #[cfg(feature = "interaction-traits")]
 impl VestingContract {
+    /// Checks that the contract can change to `new_balance` because of the given transaction,
+    /// which withdraws `value` from the contract. If the contract has beneficiaries, `value` is
+    /// charged to the beneficiary that signed the transaction.
     fn can_change_balance(
         &self,
         transaction: &Transaction,
         new_balance: Coin,
+        value: Coin,
+        beneficiaries: &[VestingContractBeneficiary],
         block_state: &BlockState,
     ) -> Result<(), AccountError> {
         // Check vesting min cap.
@@ -56,13 +93,125 @@ impl VestingContract {
             });
         }
 
-        // Check transaction signer is contract owner.
+        if beneficiaries.is_empty() {
+            // Check transaction signer is contract owner.
+            let signature_proof = SignatureProof::deserialize_all(&transaction.proof)?;
+
+            if !signature_proof.is_signed_by(&self.owner) {
+                return Err(AccountError::InvalidSignature);
+            }
+        } else {
+            // Check transaction signer is a beneficiary that doesn't exceed its share.
+            let index = Self::signing_beneficiary(beneficiaries, transaction)?;
+            let available = self.available_to_beneficiary(beneficiaries, index, block_state.time);
+
+            if value > available {
+                return Err(AccountError::InsufficientFunds {
+                    balance: available,
+                    needed: value,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the index of the beneficiary that signed the transaction.
+    fn signing_beneficiary(
+        beneficiaries: &[VestingContractBeneficiary],
+        transaction: &Transaction,
+    ) -> Result<usize, AccountError> {
         let signature_proof = SignatureProof::deserialize_all(&transaction.proof)?;
 
-        if !signature_proof.is_signed_by(&self.owner) {
-            return Err(AccountError::InvalidSignature);
+        beneficiaries
+            .iter()
+            .position(|beneficiary| signature_proof.is_signed_by(&beneficiary.address))
+            .ok_or(AccountError::InvalidSignature)
+    }
+
+    /// Returns the amount the beneficiary with the given index can still withdraw at `time`,
+    /// which is its share of all funds released so far minus what it has already withdrawn.
+    fn available_to_beneficiary(
+        &self,
+        beneficiaries: &[VestingContractBeneficiary],
+        index: usize,
+        time: u64,
+    ) -> Coin {
+        let total_withdrawn: Coin = beneficiaries
+            .iter()
+            .map(|beneficiary| beneficiary.withdrawn)
+            .sum();
+        let total_weight: u64 = beneficiaries
+            .iter()
+            .map(|beneficiary| u64::from(beneficiary.weight))
+            .sum();
+        let released = (self.balance + total_withdrawn).saturating_sub(self.min_cap(time));
+
+        let beneficiary = &beneficiaries[index];
+        let share =
+            u64::from(released) as u128 * u128::from(beneficiary.weight) / u128::from(total_weight);
+
+        Coin::from_u64_unchecked(share as u64).saturating_sub(beneficiary.withdrawn)
+    }
+
+    /// Withdraws `value` from the contract because of the given transaction. If the contract has
+    /// beneficiaries, `value` is charged to the beneficiary that signed the transaction. The
+    /// beneficiaries are removed from the data store once the contract is empty, since the
+    /// contract is pruned then, and returned in the receipt.
+    fn withdraw(
+        &mut self,
+        transaction: &Transaction,
+        value: Coin,
+        block_state: &BlockState,
+        mut data_store: DataStoreWrite,
+    ) -> Result<Option<AccountReceipt>, AccountError> {
+        let new_balance = self.balance.safe_sub(value)?;
+        let mut beneficiaries: Vec<VestingContractBeneficiary> = data_store
+            .get(&Self::beneficiaries_key())
+            .unwrap_or_default();
+        self.can_change_balance(transaction, new_balance, value, &beneficiaries, block_state)?;
+        self.balance = new_balance;
+
+        if beneficiaries.is_empty() {
+            return Ok(None);
+        }
+
+        let index = Self::signing_beneficiary(&beneficiaries, transaction)?;
+        beneficiaries[index].withdrawn += value;
+
+        if new_balance.is_zero() {
+            data_store.remove(&Self::beneficiaries_key());
+            return Ok(Some(VestingBeneficiariesReceipt { beneficiaries }.into()));
         }
 
+        data_store.put(&Self::beneficiaries_key(), beneficiaries);
+        Ok(None)
+    }
+
+    /// Reverts withdrawing `value` from the contract because of the given transaction.
+    fn revert_withdraw(
+        &mut self,
+        transaction: &Transaction,
+        value: Coin,
+        receipt: Option<AccountReceipt>,
+        mut data_store: DataStoreWrite,
+    ) -> Result<(), AccountError> {
+        self.balance += value;
+
+        let mut beneficiaries = match receipt {
+            Some(receipt) => VestingBeneficiariesReceipt::try_from(receipt)?.beneficiaries,
+            None => data_store
+                .get(&Self::beneficiaries_key())
+                .unwrap_or_default(),
+        };
+        if beneficiaries.is_empty() {
+            return Ok(());
+        }
+
+        let index = Self::signing_beneficiary(&beneficiaries, transaction)?;
+        beneficiaries[index].withdrawn -= value;
+        data_store.put(&Self::beneficiaries_key(), beneficiaries);
+
         Ok(())
     }
 
@@ -85,7 +234,7 @@ impl AccountTransactionInteraction for VestingContract {
         transaction: &Transaction,
         initial_balance: Coin,
         _block_state: &BlockState,
-        _data_store: DataStoreWrite,
+        mut data_store: DataStoreWrite,
         tx_logger: &mut TransactionLog,
     ) -> Result<Account, AccountError> {
         let data = CreationTransactionData::parse(transaction)?;
@@ -97,8 +246,22 @@ impl AccountTransactionInteraction for VestingContract {
             time_step: data.time_step,
             step_amount: data.step_amount,
             total_amount: data.total_amount,
+            beneficiaries: data.beneficiaries.clone(),
         });
 
+        if !data.beneficiaries.is_empty() {
+            let beneficiaries: Vec<_> = data
+                .beneficiaries
+                .into_iter()
+                .map(|beneficiary| VestingContractBeneficiary {
+                    address: beneficiary.address,
+                    weight: beneficiary.weight,
+                    withdrawn: Coin::ZERO,
+                })
+                .collect();
+            data_store.put(&VestingContract::beneficiaries_key(), beneficiaries);
+        }
+
         Ok(Account::Vesting(VestingContract {
             balance: initial_balance + transaction.value,
             owner: data.owner,
@@ -113,11 +276,22 @@ impl AccountTransactionInteraction for VestingContract {
         &mut self,
         transaction: &Transaction,
         _block_state: &BlockState,
-        _data_store: DataStoreWrite,
+        mut data_store: DataStoreWrite,
         tx_logger: &mut TransactionLog,
     ) -> Result<(), AccountError> {
         self.balance -= transaction.value;
 
+        let beneficiaries = data_store
+            .get::<Vec<VestingContractBeneficiary>>(&VestingContract::beneficiaries_key())
+            .unwrap_or_default()
+            .into_iter()
+            .map(|beneficiary| VestingBeneficiary {
+                address: beneficiary.address,
+                weight: beneficiary.weight,
+            })
+            .collect();
+        data_store.remove(&VestingContract::beneficiaries_key());
+
         tx_logger.push_log(Log::VestingCreate {
             contract_address: transaction.recipient.clone(),
             owner: self.owner.clone(),
@@ -125,6 +299,7 @@ impl AccountTransactionInteraction for VestingContract {
             time_step: self.time_step,
             step_amount: self.step_amount,
             total_amount: self.total_amount,
+            beneficiaries,
         });
 
         Ok(())
@@ -155,28 +330,31 @@ impl AccountTransactionInteraction for VestingContract {
         &mut self,
         transaction: &Transaction,
         block_state: &BlockState,
-        _data_store: DataStoreWrite,
+        data_store: DataStoreWrite,
         tx_logger: &mut TransactionLog,
     ) -> Result<Option<AccountReceipt>, AccountError> {
-        let new_balance = self.balance.safe_sub(transaction.total_value())?;
-        self.can_change_balance(transaction, new_balance, block_state)?;
-        self.balance = new_balance;
+        let receipt = self.withdraw(
+            transaction,
+            transaction.total_value(),
+            block_state,
+            data_store,
+        )?;
 
         tx_logger.push_log(Log::pay_fee_log(transaction));
         tx_logger.push_log(Log::transfer_log(transaction));
 
-        Ok(None)
+        Ok(receipt)
     }
 
     fn revert_outgoing_transaction(
         &mut self,
         transaction: &Transaction,
         _block_state: &BlockState,
-        _receipt: Option<AccountReceipt>,
-        _data_store: DataStoreWrite,
+        receipt: Option<AccountReceipt>,
+        data_store: DataStoreWrite,
         tx_logger: &mut TransactionLog,
     ) -> Result<(), AccountError> {
-        self.balance += transaction.total_value();
+        self.revert_withdraw(transaction, transaction.total_value(), receipt, data_store)?;
 
         tx_logger.push_log(Log::transfer_log(transaction));
         tx_logger.push_log(Log::pay_fee_log(transaction));
@@ -188,28 +366,26 @@ impl AccountTransactionInteraction for VestingContract {
         &mut self,
         transaction: &Transaction,
         block_state: &BlockState,
-        _data_store: DataStoreWrite,
+        data_store: DataStoreWrite,
         tx_logger: &mut TransactionLog,
     ) -> Result<Option<AccountReceipt>, AccountError> {
-        let new_balance = self.balance.safe_sub(transaction.fee)?;
         // XXX This check should not be necessary since are also checking this in reserve_balance()
-        self.can_change_balance(transaction, new_balance, block_state)?;
-        self.balance = new_balance;
+        let receipt = self.withdraw(transaction, transaction.fee, block_state, data_store)?;
 
         tx_logger.push_log(Log::pay_fee_log(transaction));
 
-        Ok(None)
+        Ok(receipt)
     }
 
     fn revert_failed_transaction(
         &mut self,
         transaction: &Transaction,
         _block_state: &BlockState,
-        _receipt: Option<AccountReceipt>,
-        _data_store: DataStoreWrite,
+        receipt: Option<AccountReceipt>,
+        data_store: DataStoreWrite,
         tx_logger: &mut TransactionLog,
     ) -> Result<(), AccountError> {
-        self.balance += transaction.fee;
+        self.revert_withdraw(transaction, transaction.fee, receipt, data_store)?;
 
         tx_logger.push_log(Log::pay_fee_log(transaction));
 
@@ -221,14 +397,32 @@ impl AccountTransactionInteraction for VestingContract {
         transaction: &Transaction,
         reserved_balance: &mut ReservedBalance,
         block_state: &BlockState,
-        _data_store: DataStoreRead,
+        data_store: DataStoreRead,
     ) -> Result<(), AccountError> {
         let needed = reserved_balance
             .balance()
             .checked_add(transaction.total_value())
             .ok_or(AccountError::InvalidCoinValue)?;
         let new_balance = self.balance.safe_sub(needed)?;
-        self.can_change_balance(transaction, new_balance, block_state)?;
+        let beneficiaries = self.get_beneficiaries(&data_store);
+        self.can_change_balance(
+            transaction,
+            new_balance,
+            transaction.total_value(),
+            &beneficiaries,
+            block_state,
+        )?;
+
+        // The share of a beneficiary must also cover what it already reserved.
+        if !beneficiaries.is_empty() {
+            let index = Self::signing_beneficiary(&beneficiaries, transaction)?;
+            let available = self.available_to_beneficiary(&beneficiaries, index, block_state.time);
+            reserved_balance.reserve_for(
+                &beneficiaries[index].address,
+                available,
+                transaction.total_value(),
+            )?;
+        }
 
         reserved_balance.reserve(self.balance, transaction.total_value())
     }
@@ -237,8 +431,13 @@ impl AccountTransactionInteraction for VestingContract {
         &self,
         transaction: &Transaction,
         reserved_balance: &mut ReservedBalance,
-        _data_store: DataStoreRead,
+        data_store: DataStoreRead,
     ) -> Result<(), AccountError> {
+        let beneficiaries = self.get_beneficiaries(&data_store);
+        if let Ok(index) = Self::signing_beneficiary(&beneficiaries, transaction) {
+            reserved_balance.release_for(&beneficiaries[index].address, transaction.total_value());
+        }
+
         reserved_balance.release(transaction.total_value());
         Ok(())
     }
@@ -324,3 +523,12 @@ impl From<PrunedVestingContract> for VestingContract {
 }
 
 convert_receipt!(PrunedVestingContract);
+
+/// The beneficiaries of a vesting contract that were removed from its data store because the
+/// contract became empty.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+struct VestingBeneficiariesReceipt {
+    pub beneficiaries: Vec<VestingContractBeneficiary>,
+}
+
+convert_receipt!(VestingBeneficiariesReceipt);
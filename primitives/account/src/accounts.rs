@@ -1,6 +1,8 @@
+use std::collections::BTreeMap;
+
 use nimiq_database::{
     declare_table,
-    mdbx::{MdbxDatabase, MdbxReadTransaction as DBTransaction},
+    mdbx::{MdbxDatabase, MdbxReadTransaction as DBTransaction, MdbxWriteTransaction},
     traits::{Database, WriteTransaction},
 };
 use nimiq_hash::{Blake2bHash, Hash};
@@ -8,6 +10,7 @@ use nimiq_keys::Address;
 use nimiq_primitives::{
     account::{AccountError, AccountType, FailReason},
     key_nibbles::KeyNibbles,
+    policy::Policy,
     trie::{
         error::IncompleteTrie,
         trie_chunk::{TrieChunk, TrieChunkPushResult},
@@ -19,9 +22,13 @@ use nimiq_primitives::{
     TreeProof,
 };
 use nimiq_transaction::{inherent::Inherent, ExecutedTransaction, Transaction, TransactionFlags};
-use nimiq_trie::{trie::MerkleRadixTrie, WriteTransactionProxy};
+use nimiq_trie::{
+    trie::{MerkleRadixTrie, TrieSubtreeStats},
+    WriteTransactionProxy,
+};
 
 use crate::{
+    account::staking_contract::{StakingContractStore, ValidatorStakersIndex},
     Account, AccountInherentInteraction, AccountPruningInteraction, AccountReceipt,
     AccountTransactionInteraction, AccountsError, BlockLogger, BlockState, DataStore,
     InherentLogger, InherentOperationReceipt, OperationReceipt, Receipts, ReservedBalance,
@@ -47,18 +54,43 @@ impl Accounts {
     /// Creates a new Accounts.
     pub fn new(env: MdbxDatabase) -> Self {
         let tree = AccountsTrie::new(&env, AccountsTrieTable);
-        Accounts { env, tree }
+        ValidatorStakersIndex::create_table(&env);
+        let accounts = Accounts { env, tree };
+
+        // Databases created before the validator stakers index existed don't contain it yet.
+        let txn = accounts.env.read_transaction();
+        if !accounts.env.is_read_only()
+            && accounts.tree.is_complete(&txn)
+            && !ValidatorStakersIndex::is_initialized(&txn)
+        {
+            drop(txn);
+            let mut txn = accounts.env.write_transaction();
+            accounts.rebuild_validator_stakers(&mut txn);
+            txn.commit();
+        }
+
+        accounts
     }
 
     /// Creates a new Accounts, marked as incomplete.
     pub fn new_incomplete(env: MdbxDatabase) -> Self {
         let tree = AccountsTrie::new_incomplete(&env, AccountsTrieTable);
+        ValidatorStakersIndex::create_table(&env);
         Accounts { env, tree }
     }
 
     /// Initializes the Accounts struct with a given list of accounts.
     pub fn init(&self, txn: &mut WriteTransactionProxy, genesis_accounts: Vec<TrieItem>) {
-        self.tree.init(txn, genesis_accounts)
+        self.tree.init(txn, genesis_accounts);
+        self.rebuild_validator_stakers(txn.raw());
+    }
+
+    /// Rebuilds the local index of the stakers delegating to each validator from the trie.
+    /// The index isn't part of the trie, so it must be rebuilt whenever the trie is modified
+    /// without going through the staking contract.
+    fn rebuild_validator_stakers(&self, txn: &mut MdbxWriteTransaction) {
+        let data_store = self.data_store(&Policy::STAKING_CONTRACT_ADDRESS);
+        ValidatorStakersIndex::rebuild(txn, &data_store);
     }
 
     /// Returns the number of accounts (incl. hybrid nodes) in the Accounts Trie.
@@ -72,6 +104,19 @@ impl Accounts {
         self.tree.num_branches(&self.env.read_transaction())
     }
 
+    /// Returns node counts and byte sizes of the Accounts Trie, grouped by the first `prefix_len`
+    /// nibbles of the node keys.
+    pub fn subtree_stats(&self, prefix_len: usize) -> BTreeMap<KeyNibbles, TrieSubtreeStats> {
+        self.tree
+            .subtree_stats(&self.env.read_transaction(), prefix_len)
+    }
+
+    /// Returns node counts and byte sizes of the Accounts Trie per account. The nodes of a
+    /// contract's data store are accounted to the contract itself.
+    pub fn stats_by_account(&self) -> BTreeMap<KeyNibbles, TrieSubtreeStats> {
+        self.subtree_stats(Address::SIZE * 2)
+    }
+
     pub fn get(
         &self,
         address: &Address,
@@ -237,7 +282,8 @@ impl Accounts {
     }
 
     pub fn reinitialize_as_incomplete(&self, txn: &mut WriteTransactionProxy) {
-        self.tree.reinitialize_as_incomplete(txn)
+        self.tree.reinitialize_as_incomplete(txn);
+        ValidatorStakersIndex::clear(txn.raw());
     }
 
     pub fn is_complete(&self, txn_option: Option<&DBTransaction>) -> bool {
@@ -595,7 +641,38 @@ impl Accounts {
         txn: &mut WriteTransactionProxy,
         diff: RevertTrieDiff,
     ) -> Result<(), AccountsError> {
+        // The diff bypasses the staking contract, so the index is updated for the stakers it
+        // touches if it is kept.
+        if !self.tree.is_complete(txn) {
+            self.tree.revert_diff(txn, diff)?;
+            return Ok(());
+        }
+
+        let staking_contract = KeyNibbles::from(&Policy::STAKING_CONTRACT_ADDRESS);
+        let stakers: Vec<Address> = diff
+            .0
+            .keys()
+            .filter(|key| staking_contract.is_prefix_of(key))
+            .filter_map(|key| {
+                StakingContractStore::staker_address(&key.suffix(staking_contract.len() as u8))
+            })
+            .collect();
+        let data_store = self.data_store(&Policy::STAKING_CONTRACT_ADDRESS);
+        let old_delegations = ValidatorStakersIndex::delegations(txn, &data_store, &stakers);
+
         self.tree.revert_diff(txn, diff)?;
+
+        let new_delegations = ValidatorStakersIndex::delegations(txn, &data_store, &stakers);
+        for ((staker_address, old_delegation), new_delegation) in
+            stakers.iter().zip(old_delegations).zip(new_delegations)
+        {
+            ValidatorStakersIndex::update(
+                txn.raw(),
+                staker_address,
+                old_delegation.as_ref(),
+                new_delegation.as_ref(),
+            );
+        }
         Ok(())
     }
 
@@ -824,9 +901,17 @@ impl Accounts {
         expected_hash: Blake2bHash,
         start_key: KeyNibbles,
     ) -> Result<TrieChunkPushResult, AccountError> {
-        self.tree
+        let result = self
+            .tree
             .put_chunk(txn, start_key, chunk, expected_hash)
-            .map_err(AccountError::from)
+            .map_err(AccountError::from)?;
+
+        // The index is only kept while the trie is complete, it is built once the last chunk
+        // is applied.
+        if matches!(result, TrieChunkPushResult::Applied) && self.tree.is_complete(txn) {
+            self.rebuild_validator_stakers(txn.raw());
+        }
+        Ok(result)
     }
 
     pub fn revert_chunk(
@@ -835,6 +920,7 @@ impl Accounts {
         start_key: KeyNibbles,
     ) -> Result<(), AccountError> {
         self.tree.remove_chunk(txn, start_key)?;
+        ValidatorStakersIndex::clear(txn.raw());
         Ok(())
     }
 
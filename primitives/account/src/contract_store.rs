@@ -0,0 +1,210 @@
+use nimiq_keys::Address;
+use nimiq_primitives::{account::AccountError, key_nibbles::KeyNibbles};
+use nimiq_serde::{Deserialize, Serialize};
+
+#[cfg(feature = "interaction-traits")]
+use crate::data_store::DataStoreWrite;
+use crate::data_store_ops::{DataStoreIterOps, DataStoreReadOps};
+
+/// Layout of the generic key-value store of a contract in the accounts trie.
+///
+/// Entries are stored below the address of the contract, like the entries of the staking
+/// contract. Each entry lives in a namespace, so a contract can keep several kinds of entries
+/// without their keys colliding. Keys and values are limited in size, such that every entry
+/// can be proven with a `RequestTrieProof` using the key returned by [`ContractStore::trie_key`].
+pub struct ContractStore {}
+
+impl ContractStore {
+    /// The maximum size of a key in bytes. The trie key of an entry consists of the contract
+    /// address, the namespace and the key and must fit into a [`KeyNibbles`].
+    pub const MAX_KEY_SIZE: usize = KeyNibbles::MAX_BYTES - Address::SIZE - 1;
+    /// The maximum size of a serialized value in bytes.
+    pub const MAX_VALUE_SIZE: usize = 1024;
+
+    /// Returns the key of an entry relative to the contract address.
+    pub fn key(namespace: u8, key: &[u8]) -> Result<KeyNibbles, AccountError> {
+        if key.len() > Self::MAX_KEY_SIZE {
+            return Err(AccountError::DataStoreLimitExceeded);
+        }
+
+        let mut bytes = Vec::with_capacity(key.len() + 1);
+        bytes.push(namespace);
+        bytes.extend_from_slice(key);
+        Ok(KeyNibbles::from(&bytes[..]))
+    }
+
+    /// Returns the key of an entry in the accounts trie, e.g. to request a proof for it.
+    pub fn trie_key(
+        contract: &Address,
+        namespace: u8,
+        key: &[u8],
+    ) -> Result<KeyNibbles, AccountError> {
+        Ok(&KeyNibbles::from(contract) + &Self::key(namespace, key)?)
+    }
+
+    fn check_value_size<T: Serialize>(value: &T) -> Result<(), AccountError> {
+        if value.serialized_size() > Self::MAX_VALUE_SIZE {
+            return Err(AccountError::DataStoreLimitExceeded);
+        }
+        Ok(())
+    }
+}
+
+pub trait ContractStoreReadOps {
+    /// Returns the value stored under `key` in the given namespace, if any.
+    /// Keys exceeding the size limit are never present.
+    fn get_entry<T: Deserialize>(&self, namespace: u8, key: &[u8]) -> Option<T>;
+}
+
+pub struct ContractStoreRead<'read, T: DataStoreReadOps>(&'read T);
+
+impl<'read, T: DataStoreReadOps> ContractStoreRead<'read, T> {
+    pub fn new(data_store: &'read T) -> Self {
+        ContractStoreRead(data_store)
+    }
+}
+
+impl<T: DataStoreReadOps> ContractStoreReadOps for ContractStoreRead<'_, T> {
+    fn get_entry<V: Deserialize>(&self, namespace: u8, key: &[u8]) -> Option<V> {
+        self.0.get(&ContractStore::key(namespace, key).ok()?)
+    }
+}
+
+impl<T: DataStoreReadOps + DataStoreIterOps> ContractStoreRead<'_, T> {
+    /// Iterates over the values of a namespace in key order.
+    pub fn iter_entries<V: Deserialize>(&self, namespace: u8) -> impl Iterator<Item = V> {
+        // The largest key of the namespace is the one of maximum size consisting of 0xff bytes.
+        let mut end = [u8::MAX; ContractStore::MAX_KEY_SIZE + 1];
+        end[0] = namespace;
+        self.0.iter(
+            &KeyNibbles::from(&[namespace][..]),
+            &KeyNibbles::from(&end[..]),
+        )
+    }
+}
+
+#[cfg(feature = "interaction-traits")]
+pub struct ContractStoreWrite<'write, 'store, 'tree, 'txn, 'txni, 'env>(
+    &'write mut DataStoreWrite<'store, 'tree, 'txn, 'txni, 'env>,
+);
+
+#[cfg(feature = "interaction-traits")]
+impl<'write, 'store, 'tree, 'txn, 'txni, 'env>
+    ContractStoreWrite<'write, 'store, 'tree, 'txn, 'txni, 'env>
+{
+    pub fn new(data_store: &'write mut DataStoreWrite<'store, 'tree, 'txn, 'txni, 'env>) -> Self {
+        ContractStoreWrite(data_store)
+    }
+
+    /// Stores `value` under `key` in the given namespace, replacing any previous value.
+    /// Fails if the key or the serialized value exceed their size limits.
+    pub fn put_entry<T: Serialize>(
+        &mut self,
+        namespace: u8,
+        key: &[u8],
+        value: T,
+    ) -> Result<(), AccountError> {
+        let key = ContractStore::key(namespace, key)?;
+        ContractStore::check_value_size(&value)?;
+        self.0.put(&key, value);
+        Ok(())
+    }
+
+    /// Removes the value stored under `key` in the given namespace, if any.
+    pub fn remove_entry(&mut self, namespace: u8, key: &[u8]) -> Result<(), AccountError> {
+        self.0.remove(&ContractStore::key(namespace, key)?);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "interaction-traits")]
+impl ContractStoreReadOps for ContractStoreWrite<'_, '_, '_, '_, '_, '_> {
+    fn get_entry<T: Deserialize>(&self, namespace: u8, key: &[u8]) -> Option<T> {
+        self.0.get(&ContractStore::key(namespace, key).ok()?)
+    }
+}
+
+#[cfg(all(test, feature = "accounts"))]
+mod tests {
+    use nimiq_database::{
+        mdbx::MdbxDatabase,
+        traits::{Database, WriteTransaction},
+    };
+    use nimiq_keys::Address;
+    use nimiq_primitives::account::AccountError;
+
+    use crate::{
+        accounts::AccountsTrieTable,
+        contract_store::{
+            ContractStore, ContractStoreRead, ContractStoreReadOps, ContractStoreWrite,
+        },
+        data_store::DataStore,
+        AccountsTrie,
+    };
+
+    #[test]
+    fn contract_store_works() {
+        let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+        let tree = AccountsTrie::new(&env, AccountsTrieTable);
+        let contract = Address::from([1u8; Address::SIZE]);
+        let store = DataStore::new(&tree, &contract);
+
+        let mut txn = env.write_transaction();
+        let mut trie_txn = (&mut txn).into();
+        let mut write = store.write(&mut trie_txn);
+        let mut contract_store = ContractStoreWrite::new(&mut write);
+
+        contract_store.put_entry(0, b"a", 1u32).unwrap();
+        contract_store.put_entry(0, b"b", 2u32).unwrap();
+        contract_store.put_entry(1, b"a", 3u32).unwrap();
+        contract_store.put_entry(u8::MAX, b"a", 4u32).unwrap();
+
+        // Namespaces keep equal keys apart.
+        assert_eq!(contract_store.get_entry(0, b"a"), Some(1u32));
+        assert_eq!(contract_store.get_entry(1, b"a"), Some(3u32));
+
+        contract_store.remove_entry(0, b"b").unwrap();
+        assert_eq!(contract_store.get_entry::<u32>(0, b"b"), None);
+
+        // Oversized keys and values are rejected.
+        let long_key = [0u8; ContractStore::MAX_KEY_SIZE + 1];
+        assert_eq!(
+            contract_store.put_entry(0, &long_key, 1u32),
+            Err(AccountError::DataStoreLimitExceeded)
+        );
+        assert_eq!(
+            contract_store.put_entry(0, b"c", vec![0u8; ContractStore::MAX_VALUE_SIZE]),
+            Err(AccountError::DataStoreLimitExceeded)
+        );
+        assert_eq!(contract_store.get_entry::<u32>(0, &long_key), None);
+
+        let max_key = [u8::MAX; ContractStore::MAX_KEY_SIZE];
+        contract_store.put_entry(0, &max_key, 5u32).unwrap();
+
+        drop(write);
+        txn.commit();
+
+        let txn = env.read_transaction();
+        let read = store.read(&txn);
+        let contract_store = ContractStoreRead::new(&read);
+
+        assert_eq!(contract_store.get_entry(0, b"a"), Some(1u32));
+        assert_eq!(
+            contract_store.iter_entries::<u32>(0).collect::<Vec<_>>(),
+            vec![1, 5]
+        );
+        assert_eq!(
+            contract_store
+                .iter_entries::<u32>(u8::MAX)
+                .collect::<Vec<_>>(),
+            vec![4]
+        );
+
+        // The entries are stored below the contract address in the accounts trie.
+        assert_eq!(
+            tree.get::<u32>(&txn, &ContractStore::trie_key(&contract, 1, b"a").unwrap())
+                .unwrap(),
+            Some(3)
+        );
+    }
+}
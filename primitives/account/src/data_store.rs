@@ -1,4 +1,4 @@
-use nimiq_database::mdbx::MdbxReadTransaction;
+use nimiq_database::mdbx::{MdbxReadTransaction, MdbxWriteTransaction};
 use nimiq_keys::Address;
 use nimiq_primitives::key_nibbles::KeyNibbles;
 use nimiq_serde::{Deserialize, Serialize};
@@ -59,6 +59,13 @@ pub struct DataStoreRead<'store, 'tree, 'txn, 'env> {
     txn: &'txn MdbxReadTransaction<'env>,
 }
 
+impl<'txn, 'env> DataStoreRead<'_, '_, 'txn, 'env> {
+    /// Returns the underlying database transaction, e.g. to access local indices of the contract.
+    pub(crate) fn txn(&self) -> &'txn MdbxReadTransaction<'env> {
+        self.txn
+    }
+}
+
 impl DataStoreReadOps for DataStoreRead<'_, '_, '_, '_> {
     fn get<T: Deserialize>(&self, key: &KeyNibbles) -> Option<T> {
         self.store.get(self.txn, key)
@@ -82,7 +89,7 @@ pub struct DataStoreWrite<'store, 'tree, 'txn, 'txni, 'env> {
     txn: &'txn mut WriteTransactionProxy<'txni, 'env>,
 }
 
-impl DataStoreWrite<'_, '_, '_, '_, '_> {
+impl<'env> DataStoreWrite<'_, '_, '_, '_, 'env> {
     pub fn get<T: Deserialize>(&self, key: &KeyNibbles) -> Option<T> {
         self.store.get(self.txn, key)
     }
@@ -94,6 +101,25 @@ impl DataStoreWrite<'_, '_, '_, '_, '_> {
     pub fn remove(&mut self, key: &KeyNibbles) {
         self.store.remove(self.txn, key)
     }
+
+    /// Returns an iterator over all items within a given range (inclusive).
+    pub fn iter<T: Deserialize>(
+        &self,
+        start_key: &KeyNibbles,
+        end_key: &KeyNibbles,
+    ) -> TrieNodeIter<'_, AccountsTrieTable, T> {
+        self.store.tree.iter_nodes(
+            self.txn,
+            &(&self.store.prefix + start_key),
+            &(&self.store.prefix + end_key),
+        )
+    }
+
+    /// Returns the underlying database transaction, e.g. to maintain local indices of the
+    /// contract. Changes made through it are not part of the accounts trie.
+    pub(crate) fn raw_txn(&mut self) -> &mut MdbxWriteTransaction<'env> {
+        self.txn.raw()
+    }
 }
 
 #[cfg(test)]
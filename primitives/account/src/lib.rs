@@ -9,14 +9,20 @@ use thiserror::Error;
 #[cfg(feature = "accounts")]
 pub use crate::accounts::{Accounts, AccountsTrie};
 #[cfg(feature = "interaction-traits")]
+pub use crate::contract_store::ContractStoreWrite;
+#[cfg(feature = "interaction-traits")]
 pub use crate::data_store::{DataStore, DataStoreRead, DataStoreWrite};
 #[cfg(feature = "interaction-traits")]
 pub use crate::interaction_traits::*;
 pub use crate::{
     account::{
-        basic_account::BasicAccount, htlc_contract::HashedTimeLockedContract, staking_contract::*,
-        vesting_contract::VestingContract, Account,
+        basic_account::BasicAccount,
+        htlc_contract::{HTLCDepthRedemption, HashedTimeLockedContract},
+        staking_contract::*,
+        vesting_contract::{VestingContract, VestingContractBeneficiary},
+        Account,
     },
+    contract_store::{ContractStore, ContractStoreRead, ContractStoreReadOps},
     data_store_ops::DataStoreReadOps,
     logs::*,
     receipts::*,
@@ -26,6 +32,7 @@ pub use crate::{
 mod account;
 #[cfg(feature = "accounts")]
 mod accounts;
+mod contract_store;
 #[cfg(feature = "interaction-traits")]
 mod data_store;
 mod data_store_ops;
@@ -6,7 +6,10 @@ use nimiq_primitives::{
 };
 use nimiq_serde::{Deserialize, Serialize};
 use nimiq_transaction::{
-    account::htlc_contract::{AnyHash, PreImage},
+    account::{
+        htlc_contract::{AnyHash, PreImage},
+        vesting_contract::VestingBeneficiary,
+    },
     Transaction,
 };
 
@@ -50,6 +53,16 @@ pub enum Log {
         hash_depth: u8,
     },
 
+    /// A regular transfer that redeemed only part of the contract, as the revealed hash depth
+    /// is smaller than the hash count.
+    #[serde(rename_all = "camelCase")]
+    HTLCPartialRedemption {
+        contract_address: Address,
+        hash_depth: u8,
+        amount: Coin,
+        remaining_balance: Coin,
+    },
+
     #[serde(rename_all = "camelCase")]
     HTLCEarlyResolve { contract_address: Address },
 
@@ -61,6 +74,7 @@ pub enum Log {
         time_step: u64,
         step_amount: Coin,
         total_amount: Coin,
+        beneficiaries: Vec<VestingBeneficiary>,
     },
 
     #[serde(rename_all = "camelCase")]
@@ -76,6 +90,12 @@ pub enum Log {
         new_reward_address: Option<Address>,
     },
 
+    #[serde(rename_all = "camelCase")]
+    SetValidatorCommission {
+        validator_address: Address,
+        commission: u16,
+    },
+
     #[serde(rename_all = "camelCase")]
     ValidatorFeeDeduction {
         validator_address: Address,
@@ -146,6 +166,36 @@ pub enum Log {
         retired_balance: Coin,
     },
 
+    #[serde(rename_all = "camelCase")]
+    SetRewardCompounding {
+        staker_address: Address,
+        validator_address: Option<Address>,
+        compound_rewards: bool,
+    },
+
+    #[serde(rename_all = "camelCase")]
+    ScheduleUnstake {
+        staker_address: Address,
+        validator_address: Option<Address>,
+        value: Coin,
+        release_block: u32,
+    },
+
+    #[serde(rename_all = "camelCase")]
+    ClaimWithdrawals {
+        staker_address: Address,
+        validator_address: Option<Address>,
+        value: Coin,
+    },
+
+    #[serde(rename_all = "camelCase")]
+    PayoutStakerReward {
+        staker_address: Address,
+        validator_address: Address,
+        value: Coin,
+        compounded: bool,
+    },
+
     #[serde(rename_all = "camelCase")]
     RemoveStake {
         staker_address: Address,
@@ -225,12 +275,22 @@ impl Log {
             Log::HTLCRegularTransfer {
                 contract_address, ..
             } => contract_address == address,
+            Log::HTLCPartialRedemption {
+                contract_address, ..
+            } => contract_address == address,
             Log::HTLCEarlyResolve { contract_address } => contract_address == address,
             Log::VestingCreate {
                 contract_address,
                 owner,
+                beneficiaries,
                 ..
-            } => contract_address == address || owner == address,
+            } => {
+                contract_address == address
+                    || owner == address
+                    || beneficiaries
+                        .iter()
+                        .any(|beneficiary| &beneficiary.address == address)
+            }
             Log::CreateValidator {
                 validator_address,
                 reward_address,
@@ -250,6 +310,9 @@ impl Log {
             Log::DeactivateValidator {
                 validator_address, ..
             }
+            | Log::SetValidatorCommission {
+                validator_address, ..
+            }
             | Log::ReactivateValidator { validator_address }
             | Log::RetireValidator { validator_address } => validator_address == address,
             Log::DeleteValidator {
@@ -304,6 +367,21 @@ impl Log {
                 validator_address,
                 ..
             }
+            | Log::SetRewardCompounding {
+                staker_address,
+                validator_address,
+                ..
+            }
+            | Log::ScheduleUnstake {
+                staker_address,
+                validator_address,
+                ..
+            }
+            | Log::ClaimWithdrawals {
+                staker_address,
+                validator_address,
+                ..
+            }
             | Log::RemoveStake {
                 staker_address,
                 validator_address,
@@ -319,6 +397,11 @@ impl Log {
                         .map(|validator_address| validator_address == address)
                         .unwrap_or(false)
             }
+            Log::PayoutStakerReward {
+                staker_address,
+                validator_address,
+                ..
+            } => staker_address == address || validator_address == address,
             Log::PayoutReward { to, .. } => to == address,
             Log::Penalize {
                 validator_address, ..
@@ -504,6 +587,11 @@ impl BlockLogger {
         }
     }
 
+    /// The logs collected so far.
+    pub fn block_log(&self) -> &BlockLog {
+        &self.block_log
+    }
+
     pub fn build(self, size: u64) -> BlockLog {
         let mut block_log = self.block_log;
         match block_log {
@@ -929,6 +929,7 @@ fn can_revert_transactions() {
             IncomingType::AddStake,
             IncomingType::UpdateStaker,
             IncomingType::SetActiveStake,
+            IncomingType::SetRewardCompounding,
         ] {
             // Don't send from the staking contract to the staking contract.
             if matches!(
@@ -945,6 +946,7 @@ fn can_revert_transactions() {
                     | IncomingType::AddStake
                     | IncomingType::UpdateStaker
                     | IncomingType::SetActiveStake
+                    | IncomingType::SetRewardCompounding
             ) {
                 continue;
             }
@@ -966,6 +968,7 @@ fn can_revert_transactions() {
                             | IncomingType::ReactivateValidator
                             | IncomingType::UpdateStaker
                             | IncomingType::SetActiveStake
+                            | IncomingType::SetRewardCompounding
                     )
                 {
                     continue;
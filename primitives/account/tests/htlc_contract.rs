@@ -2,7 +2,8 @@ use std::convert::TryInto;
 
 use nimiq_account::{
     Account, AccountPruningInteraction, AccountTransactionInteraction, Accounts, BasicAccount,
-    BlockState, HashedTimeLockedContract, Log, ReservedBalance, TransactionLog,
+    BlockState, HTLCDepthRedemption, HashedTimeLockedContract, Log, ReservedBalance,
+    TransactionLog,
 };
 use nimiq_database::traits::Database;
 use nimiq_hash::{Blake2bHasher, HashOutput, Hasher};
@@ -311,6 +312,66 @@ fn it_can_apply_and_revert_regular_transfer() {
     );
 }
 
+#[test]
+fn it_tracks_partial_redemptions() {
+    let (accounts, _key_1, _key_2) = init_tree();
+    let block_state = BlockState::new(1, 1);
+
+    let (mut htlc, mut tx, pre_image, _sender_signature_proof, recipient_signature_proof) =
+        prepare_outgoing_transaction();
+    tx.value = 400.try_into().unwrap();
+
+    // Revealing the first of two hashes unlocks half of the total amount.
+    let proof = OutgoingHTLCTransactionProof::RegularTransfer {
+        hash_depth: 1,
+        hash_root: htlc.hash_root.clone(),
+        pre_image: pre_image.clone(),
+        signature_proof: recipient_signature_proof,
+    };
+    tx.proof = proof.serialize_to_vec();
+
+    let mut tx_logger = TransactionLog::empty();
+    let _receipt = accounts
+        .test_commit_outgoing_transaction(&mut htlc, &tx, &block_state, &mut tx_logger, true)
+        .expect("Failed to commit transaction");
+
+    assert_eq!(htlc.balance, Coin::from_u64_unchecked(600));
+    assert_eq!(
+        tx_logger.logs[2..],
+        [
+            Log::HTLCRegularTransfer {
+                contract_address: tx.sender.clone(),
+                pre_image,
+                hash_depth: 1
+            },
+            Log::HTLCPartialRedemption {
+                contract_address: tx.sender,
+                hash_depth: 1,
+                amount: Coin::from_u64_unchecked(400),
+                remaining_balance: Coin::from_u64_unchecked(600),
+            }
+        ]
+    );
+
+    assert_eq!(htlc.redeemed_amount(), Coin::from_u64_unchecked(400));
+    assert_eq!(htlc.redeemed_hash_depth(), 1);
+    assert_eq!(
+        htlc.redemptions(),
+        vec![
+            HTLCDepthRedemption {
+                hash_depth: 1,
+                min_balance: Coin::from_u64_unchecked(500),
+                claimable: Coin::from_u64_unchecked(100),
+            },
+            HTLCDepthRedemption {
+                hash_depth: 2,
+                min_balance: Coin::ZERO,
+                claimable: Coin::from_u64_unchecked(600),
+            }
+        ]
+    );
+}
+
 #[test]
 fn it_can_apply_and_revert_early_resolve() {
     let (accounts, _key_1, _key_2) = init_tree();
@@ -4,7 +4,7 @@ use std::{
     vec,
 };
 
-use nimiq_account::punished_slots::PunishedSlots;
+use nimiq_account::punished_slots::{PunishedBatch, PunishedSlots};
 use nimiq_collections::BitSet;
 use nimiq_keys::Address;
 use nimiq_primitives::{
@@ -1004,3 +1004,56 @@ fn can_finalize_epoch() {
         current_punished_slots
     );
 }
+
+#[test]
+fn can_query_punished_batches_of_validator() {
+    let validator_address = Address([1u8; 20]);
+    let other_address = Address([2u8; 20]);
+
+    // A micro block in the second batch of the first epoch.
+    let block_number = Policy::blocks_per_batch() + 1 + Policy::genesis_block_number();
+
+    let mut current_batch_punished_slots = BTreeMap::new();
+    current_batch_punished_slots.insert(validator_address.clone(), BTreeSet::from([3, 4]));
+    current_batch_punished_slots.insert(other_address.clone(), BTreeSet::from([7]));
+
+    let mut previous_batch_punished_slots = BitSet::default();
+    previous_batch_punished_slots.insert(1);
+    previous_batch_punished_slots.insert(7);
+
+    let punished_slots =
+        PunishedSlots::new(current_batch_punished_slots, previous_batch_punished_slots);
+
+    // Slots of the previous batch are attributed through the validator's slot range.
+    assert_eq!(
+        punished_slots.punished_batches(&validator_address, block_number, Some(0..5)),
+        vec![
+            PunishedBatch {
+                batch: 1,
+                epoch: 1,
+                slots: BTreeSet::from([1]),
+            },
+            PunishedBatch {
+                batch: 2,
+                epoch: 1,
+                slots: BTreeSet::from([3, 4]),
+            },
+        ]
+    );
+
+    // Without a slot range, only the current batch is known.
+    assert_eq!(
+        punished_slots.punished_batches(&validator_address, block_number, None),
+        vec![PunishedBatch {
+            batch: 2,
+            epoch: 1,
+            slots: BTreeSet::from([3, 4]),
+        }]
+    );
+
+    // Validators without punished slots have no punished batches.
+    assert_eq!(
+        punished_slots.punished_batches(&Address([3u8; 20]), block_number, Some(10..20)),
+        vec![]
+    );
+}
@@ -5,6 +5,7 @@ use nimiq_primitives::{account::AccountError, coin::Coin, policy::Policy};
 use nimiq_test_log::test;
 use nimiq_transaction::{
     account::staking_contract::{IncomingStakingTransactionData, OutgoingStakingTransactionData},
+    inherent::Inherent,
     SignatureProof,
 };
 use nimiq_trie::WriteTransactionProxy;
@@ -173,6 +174,103 @@ fn can_iter_stakers() {
     );
 }
 
+#[test]
+fn can_get_stakers_for_validator_paged() {
+    let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+    let accounts = Accounts::new(env.clone());
+    let data_store = accounts.data_store(&Policy::STAKING_CONTRACT_ADDRESS);
+    let mut db_txn = env.write_transaction();
+    let mut db_txn = (&mut db_txn).into();
+
+    let (validator_address, staker_address, staking_contract) =
+        make_sample_contract(data_store.write(&mut db_txn), Some(150_000_000));
+    let staker_address = staker_address.unwrap();
+    let other_address = Address([0xff; 20]);
+
+    // Add more stakers, one of them not delegating to the validator.
+    {
+        let mut data_store_write = data_store.write(&mut db_txn);
+        let mut store = StakingContractStoreWrite::new(&mut data_store_write);
+        for (i, delegation) in [
+            Some(validator_address.clone()),
+            None,
+            Some(validator_address.clone()),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let address = Address([i as u8; 20]);
+            store.put_staker(
+                &address,
+                Staker {
+                    address: address.clone(),
+                    active_balance: Coin::from_u64_unchecked(1_000),
+                    inactive_balance: Coin::ZERO,
+                    inactive_from: None,
+                    retired_balance: Coin::ZERO,
+                    delegation,
+                },
+            );
+        }
+    }
+
+    let addresses = |data_store: &DataStoreRead, start: Option<&Address>, limit: usize| {
+        staking_contract
+            .get_stakers_for_validator_paged(data_store, &validator_address, start, limit)
+            .into_iter()
+            .map(|staker| staker.address)
+            .collect::<Vec<_>>()
+    };
+
+    let mut expected = vec![Address([0; 20]), Address([2; 20]), staker_address.clone()];
+    expected.sort();
+
+    assert_eq!(
+        addresses(&data_store.read(&db_txn), None, usize::MAX),
+        expected
+    );
+    assert_eq!(addresses(&data_store.read(&db_txn), None, 2), expected[..2]);
+    assert_eq!(
+        addresses(&data_store.read(&db_txn), Some(&expected[1]), 2),
+        expected[1..]
+    );
+    assert_eq!(
+        addresses(&data_store.read(&db_txn), Some(&other_address), 2),
+        vec![]
+    );
+
+    // Changing the delegation and removing a staker updates the index.
+    {
+        let mut data_store_write = data_store.write(&mut db_txn);
+        let mut store = StakingContractStoreWrite::new(&mut data_store_write);
+        store.put_staker(
+            &Address([0; 20]),
+            Staker {
+                address: Address([0; 20]),
+                active_balance: Coin::from_u64_unchecked(1_000),
+                inactive_balance: Coin::ZERO,
+                inactive_from: None,
+                retired_balance: Coin::ZERO,
+                delegation: Some(other_address.clone()),
+            },
+        );
+        store.remove_staker(&Address([2; 20]));
+    }
+
+    assert_eq!(
+        addresses(&data_store.read(&db_txn), None, usize::MAX),
+        vec![staker_address]
+    );
+    assert_eq!(
+        staking_contract
+            .get_stakers_for_validator(&data_store.read(&db_txn), &other_address)
+            .into_iter()
+            .map(|staker| staker.address)
+            .collect::<Vec<_>>(),
+        vec![Address([0; 20])]
+    );
+}
+
 #[test]
 fn create_staker_works() {
     let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
@@ -571,6 +669,7 @@ fn can_set_inactive_balance() {
             SetActiveStakeReceipt {
                 old_inactive_from: Some(staker_setup.effective_block_state.number),
                 old_active_balance: staker_setup.active_stake,
+                rewards_receipt: None,
             }
             .into()
         )
@@ -670,6 +769,7 @@ fn can_set_inactive_balance() {
             SetActiveStakeReceipt {
                 old_inactive_from: Some(staker_setup.effective_block_state.number),
                 old_active_balance: staker_setup.active_stake,
+                rewards_receipt: None,
             }
             .into()
         )
@@ -1196,6 +1296,544 @@ fn cannot_retire_active_stake() {
     );
 }
 
+#[test]
+fn set_reward_compounding_works() {
+    let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+    let accounts = Accounts::new(env.clone());
+    let data_store = accounts.data_store(&Policy::STAKING_CONTRACT_ADDRESS);
+    let block_state = BlockState::new(2, 2);
+    let mut db_txn = env.write_transaction();
+    let mut db_txn = (&mut db_txn).into();
+
+    let (validator_address, staker_address, mut staking_contract) =
+        make_sample_contract(data_store.write(&mut db_txn), Some(150_000_000));
+    let staker_address = staker_address.unwrap();
+    let staker_keypair = ed25519_key_pair(STAKER_PRIVATE_KEY);
+
+    // Works in the valid case.
+    let tx = make_signed_incoming_transaction(
+        IncomingStakingTransactionData::SetRewardCompounding {
+            compound_rewards: true,
+            proof: SignatureProof::default(),
+        },
+        0,
+        &staker_keypair,
+    );
+
+    let mut tx_logger = TransactionLog::empty();
+    let receipt = staking_contract
+        .commit_incoming_transaction(
+            &tx,
+            &block_state,
+            data_store.write(&mut db_txn),
+            &mut tx_logger,
+        )
+        .expect("Failed to commit transaction");
+
+    let expected_receipt = SetRewardCompoundingReceipt {
+        old_compound_rewards: false,
+        rewards_receipt: None,
+    };
+    assert_eq!(receipt, Some(expected_receipt.into()));
+    assert_eq!(
+        tx_logger.logs,
+        vec![Log::SetRewardCompounding {
+            staker_address: staker_address.clone(),
+            validator_address: Some(validator_address.clone()),
+            compound_rewards: true,
+        }]
+    );
+
+    assert!(
+        staking_contract
+            .get_staker_rewards(&data_store.read(&db_txn), &staker_address)
+            .compound_rewards
+    );
+
+    // Revert the transaction.
+    let mut tx_logger = TransactionLog::empty();
+    staking_contract
+        .revert_incoming_transaction(
+            &tx,
+            &block_state,
+            receipt,
+            data_store.write(&mut db_txn),
+            &mut tx_logger,
+        )
+        .expect("Failed to revert transaction");
+
+    assert!(
+        !staking_contract
+            .get_staker_rewards(&data_store.read(&db_txn), &staker_address)
+            .compound_rewards
+    );
+
+    // Doesn't work for a non-existent staker.
+    let fake_keypair = ed25519_key_pair(VALIDATOR_PRIVATE_KEY);
+
+    let tx = make_signed_incoming_transaction(
+        IncomingStakingTransactionData::SetRewardCompounding {
+            compound_rewards: true,
+            proof: SignatureProof::default(),
+        },
+        0,
+        &fake_keypair,
+    );
+
+    assert_eq!(
+        staking_contract.commit_incoming_transaction(
+            &tx,
+            &block_state,
+            data_store.write(&mut db_txn),
+            &mut TransactionLog::empty()
+        ),
+        Err(AccountError::NonExistentAddress {
+            address: (&fake_keypair.public).into()
+        })
+    );
+}
+
+#[test]
+fn staker_rewards_are_credited_on_interaction() {
+    let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+    let accounts = Accounts::new(env.clone());
+    let data_store = accounts.data_store(&Policy::STAKING_CONTRACT_ADDRESS);
+    let block_state = BlockState::new(2, 2);
+    let mut db_txn = env.write_transaction();
+    let mut db_txn = (&mut db_txn).into();
+
+    let (validator_address, staker_address, mut staking_contract) =
+        make_sample_contract(data_store.write(&mut db_txn), Some(150_000_000));
+    let staker_address = staker_address.unwrap();
+    let staker_keypair = ed25519_key_pair(STAKER_PRIVATE_KEY);
+    let total_stake = Coin::from_u64_unchecked(Policy::VALIDATOR_DEPOSIT + 150_000_000);
+
+    let reward = Inherent::Reward {
+        validator_address: validator_address.clone(),
+        target: Policy::STAKING_CONTRACT_ADDRESS,
+        value: Coin::from_u64_unchecked(1_500),
+    };
+    staking_contract
+        .commit_inherent(
+            &reward,
+            &block_state,
+            data_store.write(&mut db_txn),
+            &mut InherentLogger::empty(),
+        )
+        .expect("Failed to commit inherent");
+
+    // The rewards of a staker that doesn't compound its rewards are retired.
+    let tx = make_signed_incoming_transaction(
+        IncomingStakingTransactionData::AddStake {
+            staker_address: staker_address.clone(),
+        },
+        150_000_000,
+        &staker_keypair,
+    );
+
+    let mut tx_logger = TransactionLog::empty();
+    let receipt = staking_contract
+        .commit_incoming_transaction(
+            &tx,
+            &block_state,
+            data_store.write(&mut db_txn),
+            &mut tx_logger,
+        )
+        .expect("Failed to commit transaction");
+
+    let expected_receipt = StakerRewardsReceipt {
+        old_reward_per_stake_paid: 0,
+        value: Coin::from_u64_unchecked(1_500),
+        compounded: false,
+    };
+    assert_eq!(receipt, Some(expected_receipt.into()));
+    assert_eq!(
+        tx_logger.logs,
+        vec![
+            Log::PayoutStakerReward {
+                staker_address: staker_address.clone(),
+                validator_address: validator_address.clone(),
+                value: Coin::from_u64_unchecked(1_500),
+                compounded: false,
+            },
+            Log::Stake {
+                staker_address: staker_address.clone(),
+                validator_address: Some(validator_address.clone()),
+                value: tx.value,
+            }
+        ]
+    );
+
+    let staker = staking_contract
+        .get_staker(&data_store.read(&db_txn), &staker_address)
+        .expect("Staker should exist");
+    assert_eq!(staker.active_balance, Coin::from_u64_unchecked(300_000_000));
+    assert_eq!(staker.retired_balance, Coin::from_u64_unchecked(1_500));
+    assert_eq!(
+        staking_contract.get_staker_pending_reward(&data_store.read(&db_txn), &staker_address),
+        Some(Coin::ZERO)
+    );
+
+    // Revert the transaction.
+    staking_contract
+        .revert_incoming_transaction(
+            &tx,
+            &block_state,
+            receipt,
+            data_store.write(&mut db_txn),
+            &mut TransactionLog::empty(),
+        )
+        .expect("Failed to revert transaction");
+
+    let staker = staking_contract
+        .get_staker(&data_store.read(&db_txn), &staker_address)
+        .expect("Staker should exist");
+    assert_eq!(staker.active_balance, Coin::from_u64_unchecked(150_000_000));
+    assert_eq!(staker.retired_balance, Coin::ZERO);
+    assert_eq!(
+        staking_contract.get_staker_pending_reward(&data_store.read(&db_txn), &staker_address),
+        Some(Coin::from_u64_unchecked(1_500))
+    );
+
+    // The rewards of a compounding staker are added to its active balance. Enabling compounding
+    // credits the rewards accrued before with the previous setting.
+    let mut tx_logger = TransactionLog::empty();
+    staking_contract
+        .commit_incoming_transaction(
+            &make_signed_incoming_transaction(
+                IncomingStakingTransactionData::SetRewardCompounding {
+                    compound_rewards: true,
+                    proof: SignatureProof::default(),
+                },
+                0,
+                &staker_keypair,
+            ),
+            &block_state,
+            data_store.write(&mut db_txn),
+            &mut tx_logger,
+        )
+        .expect("Failed to commit transaction");
+
+    let staker = staking_contract
+        .get_staker(&data_store.read(&db_txn), &staker_address)
+        .expect("Staker should exist");
+    assert_eq!(staker.retired_balance, Coin::from_u64_unchecked(1_500));
+
+    staking_contract
+        .commit_inherent(
+            &reward,
+            &block_state,
+            data_store.write(&mut db_txn),
+            &mut InherentLogger::empty(),
+        )
+        .expect("Failed to commit inherent");
+
+    let receipt = staking_contract
+        .commit_incoming_transaction(
+            &tx,
+            &block_state,
+            data_store.write(&mut db_txn),
+            &mut TransactionLog::empty(),
+        )
+        .expect("Failed to commit transaction");
+    assert!(receipt.is_some());
+
+    let staker = staking_contract
+        .get_staker(&data_store.read(&db_txn), &staker_address)
+        .expect("Staker should exist");
+    assert_eq!(staker.active_balance, Coin::from_u64_unchecked(300_001_500));
+    assert_eq!(staker.retired_balance, Coin::from_u64_unchecked(1_500));
+
+    let validator = staking_contract
+        .get_validator(&data_store.read(&db_txn), &validator_address)
+        .expect("Validator should exist");
+    assert_eq!(
+        validator.total_stake,
+        total_stake + Coin::from_u64_unchecked(150_001_500)
+    );
+    assert_eq!(
+        staking_contract.active_validators.get(&validator_address),
+        Some(&validator.total_stake)
+    );
+}
+
+#[test]
+fn schedule_unstake_and_claim_withdrawals_work() {
+    let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+    let accounts = Accounts::new(env.clone());
+    let data_store = accounts.data_store(&Policy::STAKING_CONTRACT_ADDRESS);
+    let block_state = BlockState::new(2, 2);
+    let mut db_txn = env.write_transaction();
+    let mut db_txn = (&mut db_txn).into();
+
+    let (validator_address, staker_address, mut staking_contract) =
+        make_sample_contract(data_store.write(&mut db_txn), Some(150_000_000));
+    let staker_address = staker_address.unwrap();
+    let staker_keypair = ed25519_key_pair(STAKER_PRIVATE_KEY);
+
+    // Schedule an unstake.
+    let schedule_tx = make_signed_incoming_transaction(
+        IncomingStakingTransactionData::ScheduleUnstake {
+            value: Coin::from_u64_unchecked(50_000_000),
+            proof: SignatureProof::default(),
+        },
+        0,
+        &staker_keypair,
+    );
+
+    let mut tx_logger = TransactionLog::empty();
+    let schedule_receipt = staking_contract
+        .commit_incoming_transaction(
+            &schedule_tx,
+            &block_state,
+            data_store.write(&mut db_txn),
+            &mut tx_logger,
+        )
+        .expect("Failed to commit transaction");
+
+    let release_block = Policy::block_after_withdrawal_delay(block_state.number);
+    assert_eq!(schedule_receipt, None);
+    assert_eq!(
+        tx_logger.logs,
+        vec![Log::ScheduleUnstake {
+            staker_address: staker_address.clone(),
+            validator_address: Some(validator_address.clone()),
+            value: Coin::from_u64_unchecked(50_000_000),
+            release_block,
+        }]
+    );
+
+    let staker = staking_contract
+        .get_staker(&data_store.read(&db_txn), &staker_address)
+        .expect("Staker should exist");
+    assert_eq!(staker.active_balance, Coin::from_u64_unchecked(100_000_000));
+    assert_eq!(
+        staking_contract.get_scheduled_withdrawals(&data_store.read(&db_txn), &staker_address),
+        vec![ScheduledWithdrawal {
+            value: Coin::from_u64_unchecked(50_000_000),
+            release_block,
+        }]
+    );
+
+    let validator = staking_contract
+        .get_validator(&data_store.read(&db_txn), &validator_address)
+        .expect("Validator should exist");
+    assert_eq!(
+        validator.total_stake,
+        Coin::from_u64_unchecked(Policy::VALIDATOR_DEPOSIT + 100_000_000)
+    );
+
+    // Can't schedule more than the active balance.
+    let tx = make_signed_incoming_transaction(
+        IncomingStakingTransactionData::ScheduleUnstake {
+            value: Coin::from_u64_unchecked(100_000_001),
+            proof: SignatureProof::default(),
+        },
+        0,
+        &staker_keypair,
+    );
+    assert_eq!(
+        staking_contract.commit_incoming_transaction(
+            &tx,
+            &block_state,
+            data_store.write(&mut db_txn),
+            &mut TransactionLog::empty()
+        ),
+        Err(AccountError::InsufficientFunds {
+            needed: Coin::from_u64_unchecked(100_000_001),
+            balance: Coin::from_u64_unchecked(100_000_000)
+        })
+    );
+
+    // Can't claim before the release block.
+    let claim_tx = make_signed_incoming_transaction(
+        IncomingStakingTransactionData::ClaimWithdrawals {
+            proof: SignatureProof::default(),
+        },
+        0,
+        &staker_keypair,
+    );
+    assert_eq!(
+        staking_contract.commit_incoming_transaction(
+            &claim_tx,
+            &BlockState::new(release_block - 1, 1000),
+            data_store.write(&mut db_txn),
+            &mut TransactionLog::empty()
+        ),
+        Err(AccountError::InvalidForRecipient)
+    );
+
+    // Claim at the release block.
+    let claim_block_state = BlockState::new(release_block, 1000);
+    let mut tx_logger = TransactionLog::empty();
+    let claim_receipt = staking_contract
+        .commit_incoming_transaction(
+            &claim_tx,
+            &claim_block_state,
+            data_store.write(&mut db_txn),
+            &mut tx_logger,
+        )
+        .expect("Failed to commit transaction");
+
+    let expected_receipt = ClaimWithdrawalsReceipt {
+        claimed: vec![ScheduledWithdrawal {
+            value: Coin::from_u64_unchecked(50_000_000),
+            release_block,
+        }],
+        rewards_receipt: None,
+    };
+    assert_eq!(claim_receipt, Some(expected_receipt.into()));
+    assert_eq!(
+        tx_logger.logs,
+        vec![Log::ClaimWithdrawals {
+            staker_address: staker_address.clone(),
+            validator_address: Some(validator_address.clone()),
+            value: Coin::from_u64_unchecked(50_000_000),
+        }]
+    );
+
+    let staker = staking_contract
+        .get_staker(&data_store.read(&db_txn), &staker_address)
+        .expect("Staker should exist");
+    assert_eq!(staker.retired_balance, Coin::from_u64_unchecked(50_000_000));
+    assert!(staking_contract
+        .get_scheduled_withdrawals(&data_store.read(&db_txn), &staker_address)
+        .is_empty());
+
+    // Revert the claim.
+    staking_contract
+        .revert_incoming_transaction(
+            &claim_tx,
+            &claim_block_state,
+            claim_receipt,
+            data_store.write(&mut db_txn),
+            &mut TransactionLog::empty(),
+        )
+        .expect("Failed to revert transaction");
+
+    let staker = staking_contract
+        .get_staker(&data_store.read(&db_txn), &staker_address)
+        .expect("Staker should exist");
+    assert_eq!(staker.retired_balance, Coin::ZERO);
+    assert_eq!(
+        staking_contract
+            .get_scheduled_withdrawals(&data_store.read(&db_txn), &staker_address)
+            .len(),
+        1
+    );
+
+    // Revert the schedule.
+    staking_contract
+        .revert_incoming_transaction(
+            &schedule_tx,
+            &block_state,
+            schedule_receipt,
+            data_store.write(&mut db_txn),
+            &mut TransactionLog::empty(),
+        )
+        .expect("Failed to revert transaction");
+
+    let staker = staking_contract
+        .get_staker(&data_store.read(&db_txn), &staker_address)
+        .expect("Staker should exist");
+    assert_eq!(staker.active_balance, Coin::from_u64_unchecked(150_000_000));
+    assert!(staking_contract
+        .get_scheduled_withdrawals(&data_store.read(&db_txn), &staker_address)
+        .is_empty());
+
+    let validator = staking_contract
+        .get_validator(&data_store.read(&db_txn), &validator_address)
+        .expect("Validator should exist");
+    assert_eq!(
+        validator.total_stake,
+        Coin::from_u64_unchecked(Policy::VALIDATOR_DEPOSIT + 150_000_000)
+    );
+}
+
+#[test]
+fn update_staker_fails_with_scheduled_withdrawals() {
+    let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+    let accounts = Accounts::new(env.clone());
+    let data_store = accounts.data_store(&Policy::STAKING_CONTRACT_ADDRESS);
+    let block_state = BlockState::new(2, 2);
+    let mut db_txn = env.write_transaction();
+    let mut db_txn = (&mut db_txn).into();
+
+    let (_, staker_address, mut staking_contract) =
+        make_sample_contract(data_store.write(&mut db_txn), Some(150_000_000));
+    let staker_address = staker_address.unwrap();
+    let staker_keypair = ed25519_key_pair(STAKER_PRIVATE_KEY);
+
+    // Schedule an unstake of the whole active balance.
+    let schedule_tx = make_signed_incoming_transaction(
+        IncomingStakingTransactionData::ScheduleUnstake {
+            value: Coin::from_u64_unchecked(150_000_000),
+            proof: SignatureProof::default(),
+        },
+        0,
+        &staker_keypair,
+    );
+    staking_contract
+        .commit_incoming_transaction(
+            &schedule_tx,
+            &block_state,
+            data_store.write(&mut db_txn),
+            &mut TransactionLog::empty(),
+        )
+        .expect("Failed to commit transaction");
+    let release_block = Policy::block_after_withdrawal_delay(block_state.number);
+
+    // Can't change the delegation while the withdrawal is pending.
+    let update_tx = make_signed_incoming_transaction(
+        IncomingStakingTransactionData::UpdateStaker {
+            new_delegation: None,
+            reactivate_all_stake: false,
+            proof: SignatureProof::default(),
+        },
+        0,
+        &staker_keypair,
+    );
+    assert_eq!(
+        staking_contract.commit_incoming_transaction(
+            &update_tx,
+            &BlockState::new(release_block, 1000),
+            data_store.write(&mut db_txn),
+            &mut TransactionLog::empty()
+        ),
+        Err(AccountError::InvalidForRecipient)
+    );
+
+    // Works once the withdrawal has been claimed.
+    let claim_tx = make_signed_incoming_transaction(
+        IncomingStakingTransactionData::ClaimWithdrawals {
+            proof: SignatureProof::default(),
+        },
+        0,
+        &staker_keypair,
+    );
+    staking_contract
+        .commit_incoming_transaction(
+            &claim_tx,
+            &BlockState::new(release_block, 1000),
+            data_store.write(&mut db_txn),
+            &mut TransactionLog::empty(),
+        )
+        .expect("Failed to commit transaction");
+    staking_contract
+        .commit_incoming_transaction(
+            &update_tx,
+            &BlockState::new(release_block, 1000),
+            data_store.write(&mut db_txn),
+            &mut TransactionLog::empty(),
+        )
+        .expect("Failed to commit transaction");
+
+    let staker = staking_contract
+        .get_staker(&data_store.read(&db_txn), &staker_address)
+        .expect("Staker should exist");
+    assert_eq!(staker.delegation, None);
+}
+
 #[test]
 fn update_staker_works() {
     // -----------------------------------
@@ -1244,6 +1882,7 @@ fn update_staker_works() {
         delegation: Some(validator_address1.clone()),
         active_balance: staker.active_balance,
         inactive_from: staker.inactive_from,
+        old_reward_per_stake_paid: 0,
     };
     assert_eq!(receipt, Some(expected_receipt.into()));
 
@@ -1367,6 +2006,7 @@ fn update_staker_works() {
         delegation: Some(validator_address2.clone()),
         active_balance: staker.active_balance,
         inactive_from: staker.inactive_from,
+        old_reward_per_stake_paid: 0,
     };
     assert_eq!(receipt, Some(expected_receipt.into()));
 
@@ -1583,6 +2223,7 @@ fn update_staker_with_stake_reactivation_works() {
         delegation: Some(validator_address1.clone()),
         active_balance: Coin::ZERO,
         inactive_from: Some(staker_setup.effective_block_state.number),
+        old_reward_per_stake_paid: 0,
     };
     assert_eq!(receipt, Some(expected_receipt.into()));
 
@@ -1787,6 +2428,7 @@ fn update_staker_remove_delegation_with_stake_reactivation_works() {
         delegation: Some(validator_address1.clone()),
         active_balance: Coin::ZERO,
         inactive_from: Some(staker_setup.effective_block_state.number),
+        old_reward_per_stake_paid: 0,
     };
     assert_eq!(receipt, Some(expected_receipt.into()));
 
@@ -1965,6 +2607,7 @@ fn update_staker_same_validator() {
         delegation: Some(validator_address.clone()),
         active_balance: Coin::ZERO,
         inactive_from: Some(staker_setup.effective_block_state.number),
+        old_reward_per_stake_paid: 0,
     };
     assert_eq!(receipt, Some(expected_receipt.into()));
 
@@ -2163,6 +2806,8 @@ fn remove_stake_works() {
 
     let expected_receipt = DeleteStakerReceipt {
         delegation: Some(validator_address.clone()),
+        compound_rewards: false,
+        reward_per_stake_paid: 0,
     };
 
     assert_eq!(receipt, Some(expected_receipt.into()));
@@ -2338,6 +2983,8 @@ fn remove_stake_from_tombstone_works() {
 
     let expected_receipt = DeleteStakerReceipt {
         delegation: Some(validator_address.clone()),
+        compound_rewards: false,
+        reward_per_stake_paid: 0,
     };
     assert_eq!(remove_stake_receipt, Some(expected_receipt.into()));
 
@@ -2602,6 +3249,7 @@ fn can_delegate_if_no_delegation_prior_delegation() {
         delegation: None,
         active_balance: Coin::from_u64_unchecked(150_000_000),
         inactive_from: None,
+        old_reward_per_stake_paid: 0,
     };
     assert_eq!(receipt, Some(expected_receipt.into()));
 
@@ -3049,6 +3697,8 @@ fn commit_failed_remove_stake_works_when_removing_staker() {
 
     let expected_receipt = DeleteStakerReceipt {
         delegation: Some(validator_address.clone()),
+        compound_rewards: false,
+        reward_per_stake_paid: 0,
     };
     assert_eq!(receipt_2, Some(expected_receipt.into()));
     assert_eq!(
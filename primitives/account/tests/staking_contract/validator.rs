@@ -1061,7 +1061,100 @@ fn delete_validator_works() {
 }
 
 #[test]
-fn reward_inherents_not_allowed() {
+fn reward_inherents_work() {
+    let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+    let accounts = Accounts::new(env.clone());
+    let data_store = accounts.data_store(&Policy::STAKING_CONTRACT_ADDRESS);
+    let block_state = BlockState::new(2, 2);
+    let mut db_txn = env.write_transaction();
+    let mut db_txn = (&mut db_txn).into();
+
+    let (validator_address, staker_address, mut staking_contract) =
+        make_sample_contract(data_store.write(&mut db_txn), Some(150_000_000));
+    let staker_address = staker_address.unwrap();
+
+    // Doesn't work for a non-existent validator.
+    let inherent = Inherent::Reward {
+        validator_address: Address::burn_address(),
+        target: Policy::STAKING_CONTRACT_ADDRESS,
+        value: Coin::from_u64_unchecked(1_500),
+    };
+
+    assert_eq!(
+        staking_contract.commit_inherent(
+            &inherent,
+            &block_state,
+            data_store.write(&mut db_txn),
+            &mut InherentLogger::empty()
+        ),
+        Err(AccountError::NonExistentAddress {
+            address: Address::burn_address()
+        })
+    );
+
+    // Works in the valid case. The reward is only credited to the staker later on.
+    let inherent = Inherent::Reward {
+        validator_address: validator_address.clone(),
+        target: Policy::STAKING_CONTRACT_ADDRESS,
+        value: Coin::from_u64_unchecked(1_500),
+    };
+    let total_stake = Coin::from_u64_unchecked(Policy::VALIDATOR_DEPOSIT + 150_000_000);
+    let expected_logs = vec![Log::PayoutReward {
+        to: Policy::STAKING_CONTRACT_ADDRESS,
+        value: Coin::from_u64_unchecked(1_500),
+    }];
+
+    let mut logs = vec![];
+    let receipt = staking_contract
+        .commit_inherent(
+            &inherent,
+            &block_state,
+            data_store.write(&mut db_txn),
+            &mut InherentLogger::new(&mut logs),
+        )
+        .expect("Failed to commit inherent");
+    assert_eq!(logs, expected_logs);
+
+    let staker = staking_contract
+        .get_staker(&data_store.read(&db_txn), &staker_address)
+        .expect("Staker should exist");
+    assert_eq!(staker.active_balance, Coin::from_u64_unchecked(150_000_000));
+    assert_eq!(
+        staking_contract.get_staker_pending_reward(&data_store.read(&db_txn), &staker_address),
+        Some(Coin::from_u64_unchecked(1_500))
+    );
+
+    let validator = staking_contract
+        .get_validator(&data_store.read(&db_txn), &validator_address)
+        .expect("Validator should exist");
+    assert_eq!(validator.total_stake, total_stake);
+    assert_eq!(
+        staking_contract.balance,
+        total_stake + Coin::from_u64_unchecked(1_500)
+    );
+
+    // Revert the inherent.
+    let mut logs = vec![];
+    staking_contract
+        .revert_inherent(
+            &inherent,
+            &block_state,
+            receipt,
+            data_store.write(&mut db_txn),
+            &mut InherentLogger::new(&mut logs),
+        )
+        .expect("Failed to revert inherent");
+    assert_eq!(logs, expected_logs);
+
+    assert_eq!(
+        staking_contract.get_staker_pending_reward(&data_store.read(&db_txn), &staker_address),
+        Some(Coin::ZERO)
+    );
+    assert_eq!(staking_contract.balance, total_stake);
+}
+
+#[test]
+fn reward_inherents_fail_without_delegated_stake() {
     let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
     let accounts = Accounts::new(env.clone());
     let data_store = accounts.data_store(&Policy::STAKING_CONTRACT_ADDRESS);
@@ -1072,10 +1165,11 @@ fn reward_inherents_not_allowed() {
     let (validator_address, _, mut staking_contract) =
         make_sample_contract(data_store.write(&mut db_txn), None);
 
+    // Block producers never pay a stakers' reward to a validator without stakers.
     let inherent = Inherent::Reward {
-        validator_address: Address::burn_address(),
-        target: validator_address,
-        value: Coin::ZERO,
+        validator_address,
+        target: Policy::STAKING_CONTRACT_ADDRESS,
+        value: Coin::from_u64_unchecked(1_000),
     };
 
     assert_eq!(
@@ -1089,6 +1183,139 @@ fn reward_inherents_not_allowed() {
     );
 }
 
+#[test]
+fn set_validator_commission_works() {
+    let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+    let accounts = Accounts::new(env.clone());
+    let data_store = accounts.data_store(&Policy::STAKING_CONTRACT_ADDRESS);
+    let block_state = BlockState::new(2, 2);
+    let mut db_txn = env.write_transaction();
+    let mut db_txn = (&mut db_txn).into();
+
+    let (validator_address, _, mut staking_contract) =
+        make_sample_contract(data_store.write(&mut db_txn), None);
+    let cold_keypair = ed25519_key_pair(VALIDATOR_PRIVATE_KEY);
+
+    assert_eq!(
+        staking_contract.get_validator_commission(&data_store.read(&db_txn), &validator_address),
+        Policy::MAX_VALIDATOR_COMMISSION
+    );
+
+    // Works in the valid case.
+    let tx = make_signed_incoming_transaction(
+        IncomingStakingTransactionData::SetValidatorCommission {
+            new_commission: 1_000,
+            proof: SignatureProof::default(),
+        },
+        0,
+        &cold_keypair,
+    );
+
+    let mut tx_logger = TransactionLog::empty();
+    let receipt = staking_contract
+        .commit_incoming_transaction(
+            &tx,
+            &block_state,
+            data_store.write(&mut db_txn),
+            &mut tx_logger,
+        )
+        .expect("Failed to commit transaction");
+
+    let expected_receipt = SetValidatorCommissionReceipt {
+        old_commission: Policy::MAX_VALIDATOR_COMMISSION,
+    };
+    assert_eq!(receipt, Some(expected_receipt.into()));
+    assert_eq!(
+        tx_logger.logs,
+        vec![Log::SetValidatorCommission {
+            validator_address: validator_address.clone(),
+            commission: 1_000,
+        }]
+    );
+    assert_eq!(
+        staking_contract.get_validator_commission(&data_store.read(&db_txn), &validator_address),
+        1_000
+    );
+
+    // Revert the transaction.
+    staking_contract
+        .revert_incoming_transaction(
+            &tx,
+            &block_state,
+            receipt,
+            data_store.write(&mut db_txn),
+            &mut TransactionLog::empty(),
+        )
+        .expect("Failed to revert transaction");
+
+    assert_eq!(
+        staking_contract.get_validator_commission(&data_store.read(&db_txn), &validator_address),
+        Policy::MAX_VALIDATOR_COMMISSION
+    );
+
+    // Doesn't work for a non-existent validator.
+    let fake_keypair = ed25519_key_pair(STAKER_PRIVATE_KEY);
+    let tx = make_signed_incoming_transaction(
+        IncomingStakingTransactionData::SetValidatorCommission {
+            new_commission: 1_000,
+            proof: SignatureProof::default(),
+        },
+        0,
+        &fake_keypair,
+    );
+
+    assert_eq!(
+        staking_contract.commit_incoming_transaction(
+            &tx,
+            &block_state,
+            data_store.write(&mut db_txn),
+            &mut TransactionLog::empty()
+        ),
+        Err(AccountError::NonExistentAddress {
+            address: (&fake_keypair.public).into()
+        })
+    );
+}
+
+#[test]
+fn stakers_reward_depends_on_commission_and_delegated_stake() {
+    let mut validator = Validator {
+        address: validator_address(),
+        signing_key: ed25519_public_key(VALIDATOR_SIGNING_KEY),
+        voting_key: bls_public_key(VALIDATOR_VOTING_KEY),
+        reward_address: validator_address(),
+        signal_data: None,
+        total_stake: Coin::from_u64_unchecked(4_000),
+        deposit: Coin::from_u64_unchecked(1_000),
+        num_stakers: 1,
+        inactive_from: None,
+        jailed_from: None,
+        retired: false,
+    };
+
+    // The stakers get 90% of the three quarters of the reward that correspond to their stake.
+    assert_eq!(
+        validator.stakers_reward(Coin::from_u64_unchecked(10_000), 1_000),
+        Coin::from_u64_unchecked(6_750)
+    );
+
+    // The validator keeps everything with the maximum commission.
+    assert_eq!(
+        validator.stakers_reward(
+            Coin::from_u64_unchecked(10_000),
+            Policy::MAX_VALIDATOR_COMMISSION
+        ),
+        Coin::ZERO
+    );
+
+    // The validator keeps everything without stakers.
+    validator.total_stake = validator.deposit;
+    assert_eq!(
+        validator.stakers_reward(Coin::from_u64_unchecked(10_000), 0),
+        Coin::ZERO
+    );
+}
+
 #[test]
 fn jail_inherents_work() {
     let genesis_block_number = Policy::genesis_block_number();
@@ -1516,6 +1743,143 @@ fn finalize_epoch_inherents_works() {
     );
 }
 
+#[test]
+fn remove_tombstones_inherents_work() {
+    let mut validator_setup = ValidatorSetup::setup_retired_validator(Some(150_000_000));
+    let data_store = validator_setup
+        .accounts
+        .data_store(&Policy::STAKING_CONTRACT_ADDRESS);
+    let mut db_txn = validator_setup.env.write_transaction();
+    let mut db_txn = (&mut db_txn).into();
+
+    let validator_address = validator_setup.validator_address.clone();
+    let staker_address = validator_setup.staker_address.clone().unwrap();
+    let staking_contract = &mut validator_setup.staking_contract;
+
+    // Reward the stakers before the validator is deleted.
+    let reward = Coin::from_u64_unchecked(1_500);
+    staking_contract
+        .commit_inherent(
+            &Inherent::Reward {
+                validator_address: validator_address.clone(),
+                target: Policy::STAKING_CONTRACT_ADDRESS,
+                value: reward,
+            },
+            &validator_setup.effective_state_block_state,
+            data_store.write(&mut db_txn),
+            &mut InherentLogger::empty(),
+        )
+        .expect("Failed to commit inherent");
+
+    // Does nothing if there are no tombstones.
+    let block_state = validator_setup.state_release_block_state.clone();
+    let mut logs = vec![];
+    let receipt = staking_contract
+        .commit_inherent(
+            &Inherent::RemoveTombstones,
+            &block_state,
+            data_store.write(&mut db_txn),
+            &mut InherentLogger::new(&mut logs),
+        )
+        .expect("Failed to commit inherent");
+    assert_eq!(
+        receipt,
+        Some(
+            RemoveTombstonesReceipt {
+                undelegated_stakers: vec![]
+            }
+            .into()
+        )
+    );
+    assert_eq!(logs, vec![]);
+
+    // Delete the validator, which leaves a tombstone behind for the staker.
+    staking_contract
+        .commit_outgoing_transaction(
+            &make_delete_validator_transaction(),
+            &block_state,
+            data_store.write(&mut db_txn),
+            &mut TransactionLog::empty(),
+        )
+        .expect("Failed to commit transaction");
+
+    let tombstone = Tombstone {
+        remaining_stake: Coin::from_u64_unchecked(150_000_000),
+        num_remaining_stakers: 1,
+    };
+    assert_eq!(
+        staking_contract.get_tombstone(&data_store.read(&db_txn), &validator_address),
+        Some(tombstone.clone())
+    );
+
+    // Works in the valid case. The staker is credited its rewards and undelegated.
+    let inherent = Inherent::RemoveTombstones;
+    let expected_logs = vec![
+        Log::PayoutStakerReward {
+            staker_address: staker_address.clone(),
+            validator_address: validator_address.clone(),
+            value: reward,
+            compounded: false,
+        },
+        Log::UpdateStaker {
+            staker_address: staker_address.clone(),
+            old_validator_address: Some(validator_address.clone()),
+            new_validator_address: None,
+            active_balance: Coin::from_u64_unchecked(150_000_000),
+            inactive_from: None,
+        },
+    ];
+
+    let mut logs = vec![];
+    let receipt = staking_contract
+        .commit_inherent(
+            &inherent,
+            &block_state,
+            data_store.write(&mut db_txn),
+            &mut InherentLogger::new(&mut logs),
+        )
+        .expect("Failed to commit inherent");
+    assert_eq!(logs, expected_logs);
+
+    assert_eq!(
+        staking_contract.get_tombstone(&data_store.read(&db_txn), &validator_address),
+        None
+    );
+    let staker = staking_contract
+        .get_staker(&data_store.read(&db_txn), &staker_address)
+        .expect("Staker should exist");
+    assert_eq!(staker.delegation, None);
+    assert_eq!(staker.active_balance, Coin::from_u64_unchecked(150_000_000));
+    assert_eq!(staker.retired_balance, reward);
+
+    // Revert the inherent.
+    let mut logs = vec![];
+    staking_contract
+        .revert_inherent(
+            &inherent,
+            &block_state,
+            receipt,
+            data_store.write(&mut db_txn),
+            &mut InherentLogger::new(&mut logs),
+        )
+        .expect("Failed to revert inherent");
+    assert_eq!(logs, expected_logs.into_iter().rev().collect::<Vec<_>>());
+
+    assert_eq!(
+        staking_contract.get_tombstone(&data_store.read(&db_txn), &validator_address),
+        Some(tombstone)
+    );
+    let staker = staking_contract
+        .get_staker(&data_store.read(&db_txn), &staker_address)
+        .expect("Staker should exist");
+    assert_eq!(staker.delegation, Some(validator_address.clone()));
+    assert_eq!(staker.retired_balance, Coin::ZERO);
+    assert_eq!(
+        staking_contract.get_staker_pending_reward(&data_store.read(&db_txn), &staker_address),
+        Some(reward)
+    );
+}
+
 /// This test makes sure that:
 /// - Validators cannot reactivate while being jailed
 /// - Validators can reactivate after jail release
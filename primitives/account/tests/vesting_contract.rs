@@ -2,9 +2,9 @@ use std::convert::{TryFrom, TryInto};
 
 use nimiq_account::{
     Account, AccountTransactionInteraction, BasicAccount, BlockState, Log, ReservedBalance,
-    TransactionLog, VestingContract,
+    TransactionLog, VestingContract, VestingContractBeneficiary,
 };
-use nimiq_database::traits::Database;
+use nimiq_database::traits::{Database, WriteTransaction};
 use nimiq_keys::{Address, KeyPair};
 use nimiq_primitives::{
     account::{AccountError, AccountType},
@@ -150,7 +150,8 @@ fn it_can_create_contract_from_transaction() {
             start_time: 0,
             time_step: 1000,
             step_amount: 100.try_into().unwrap(),
-            total_amount: 100.try_into().unwrap()
+            total_amount: 100.try_into().unwrap(),
+            beneficiaries: vec![],
         }]
     );
 
@@ -553,3 +554,231 @@ fn can_reserve_balance_after_time_step() {
         })
     );
 }
+
+fn make_signed_vesting_transaction(
+    contract_address: &Address,
+    signer: &KeyPair,
+    recipient: Address,
+    value: u64,
+) -> Transaction {
+    let mut tx = Transaction::new_basic(
+        contract_address.clone(),
+        recipient,
+        Coin::from_u64_unchecked(value),
+        Coin::ZERO,
+        1,
+        NetworkId::UnitAlbatross,
+    );
+    tx.sender_type = AccountType::Vesting;
+    let signature = signer.sign(&tx.serialize_content());
+    let signature_proof = SignatureProof::from_ed25519(signer.public, signature);
+    tx.proof = signature_proof.serialize_to_vec();
+
+    tx
+}
+
+fn init_beneficiaries(
+    accounts: &TestCommitRevert,
+    contract_address: &Address,
+    key_1: &KeyPair,
+    key_2: &KeyPair,
+) {
+    let beneficiaries = vec![
+        VestingContractBeneficiary {
+            address: Address::from(key_1),
+            weight: 3,
+            withdrawn: Coin::ZERO,
+        },
+        VestingContractBeneficiary {
+            address: Address::from(key_2),
+            weight: 1,
+            withdrawn: Coin::ZERO,
+        },
+    ];
+
+    let mut raw_txn = accounts.env().write_transaction();
+    let mut txn = (&mut raw_txn).into();
+    accounts.data_store(contract_address).put(
+        &mut txn,
+        &VestingContract::beneficiaries_key(),
+        beneficiaries,
+    );
+    raw_txn.commit();
+}
+
+#[test]
+fn beneficiaries_can_withdraw_their_share() {
+    let (accounts, mut vesting_contract, key_1, key_2) = init_tree();
+    let other_key = KeyPair::generate(&mut test_rng(false));
+    let contract_address = Address([1u8; 20]);
+    init_beneficiaries(&accounts, &contract_address, &key_1, &key_2);
+    let data_store = accounts.data_store(&contract_address);
+
+    // 200 coins are released, 150 for the first and 50 for the second beneficiary.
+    let block_state = BlockState::new(2, 200);
+
+    let tx = make_signed_vesting_transaction(&contract_address, &key_1, Address::from(&key_2), 150);
+    accounts
+        .test_commit_outgoing_transaction(
+            &mut vesting_contract,
+            &tx,
+            &block_state,
+            &mut TransactionLog::empty(),
+            true,
+        )
+        .expect("Failed to commit transaction");
+    assert_eq!(vesting_contract.balance, Coin::from_u64_unchecked(850));
+    assert_eq!(
+        vesting_contract.get_beneficiaries(&data_store.read(&accounts.env().read_transaction()))[0]
+            .withdrawn,
+        Coin::from_u64_unchecked(150)
+    );
+
+    // The first beneficiary can't withdraw the share of the second one.
+    let tx = make_signed_vesting_transaction(&contract_address, &key_1, Address::from(&key_2), 1);
+    assert_eq!(
+        accounts.test_commit_outgoing_transaction(
+            &mut vesting_contract,
+            &tx,
+            &block_state,
+            &mut TransactionLog::empty(),
+            true,
+        ),
+        Err(AccountError::InsufficientFunds {
+            needed: Coin::from_u64_unchecked(1),
+            balance: Coin::ZERO,
+        })
+    );
+
+    let tx = make_signed_vesting_transaction(&contract_address, &key_2, Address::from(&key_1), 50);
+    accounts
+        .test_commit_outgoing_transaction(
+            &mut vesting_contract,
+            &tx,
+            &block_state,
+            &mut TransactionLog::empty(),
+            true,
+        )
+        .expect("Failed to commit transaction");
+    assert_eq!(vesting_contract.balance, Coin::from_u64_unchecked(800));
+
+    // Further funds are split once they are released.
+    let block_state = BlockState::new(3, 300);
+
+    let tx = make_signed_vesting_transaction(&contract_address, &key_2, Address::from(&key_1), 26);
+    assert_eq!(
+        accounts.test_commit_outgoing_transaction(
+            &mut vesting_contract,
+            &tx,
+            &block_state,
+            &mut TransactionLog::empty(),
+            true,
+        ),
+        Err(AccountError::InsufficientFunds {
+            needed: Coin::from_u64_unchecked(26),
+            balance: Coin::from_u64_unchecked(25),
+        })
+    );
+
+    // Addresses that aren't beneficiaries can't withdraw.
+    let tx =
+        make_signed_vesting_transaction(&contract_address, &other_key, Address::from(&key_1), 1);
+    assert_eq!(
+        accounts.test_commit_outgoing_transaction(
+            &mut vesting_contract,
+            &tx,
+            &block_state,
+            &mut TransactionLog::empty(),
+            true,
+        ),
+        Err(AccountError::InvalidSignature)
+    );
+
+    // The beneficiaries are removed once the contract is empty and restored on revert.
+    let block_state = BlockState::new(4, 1000);
+    let tx = make_signed_vesting_transaction(&contract_address, &key_1, Address::from(&key_2), 600);
+    accounts
+        .test_commit_outgoing_transaction(
+            &mut vesting_contract,
+            &tx,
+            &block_state,
+            &mut TransactionLog::empty(),
+            true,
+        )
+        .expect("Failed to commit transaction");
+    let tx = make_signed_vesting_transaction(&contract_address, &key_2, Address::from(&key_1), 200);
+    let receipt = accounts
+        .test_commit_outgoing_transaction(
+            &mut vesting_contract,
+            &tx,
+            &block_state,
+            &mut TransactionLog::empty(),
+            true,
+        )
+        .expect("Failed to commit transaction");
+    assert!(receipt.is_some());
+    assert_eq!(vesting_contract.balance, Coin::ZERO);
+    assert!(vesting_contract
+        .get_beneficiaries(&data_store.read(&accounts.env().read_transaction()))
+        .is_empty());
+}
+
+#[test]
+fn beneficiaries_cant_reserve_more_than_their_share() {
+    let (accounts, vesting_contract, key_1, key_2) = init_tree();
+    let contract_address = Address([1u8; 20]);
+    init_beneficiaries(&accounts, &contract_address, &key_1, &key_2);
+    let db_txn = accounts.env().read_transaction();
+    let data_store = accounts.data_store(&contract_address);
+
+    // 200 coins are released, 50 of them for the second beneficiary.
+    let block_state = BlockState::new(2, 200);
+    let mut reserved_balance = ReservedBalance::new(contract_address.clone());
+
+    let tx = make_signed_vesting_transaction(&contract_address, &key_2, Address::from(&key_1), 30);
+    vesting_contract
+        .reserve_balance(
+            &tx,
+            &mut reserved_balance,
+            &block_state,
+            data_store.read(&db_txn),
+        )
+        .expect("Failed to reserve balance");
+
+    // Amounts already reserved by the beneficiary count towards its share.
+    assert_eq!(
+        vesting_contract.reserve_balance(
+            &tx,
+            &mut reserved_balance,
+            &block_state,
+            data_store.read(&db_txn),
+        ),
+        Err(AccountError::InsufficientFunds {
+            needed: Coin::from_u64_unchecked(60),
+            balance: Coin::from_u64_unchecked(50),
+        })
+    );
+    assert_eq!(reserved_balance.balance(), Coin::from_u64_unchecked(30));
+
+    // The share of the first beneficiary is unaffected.
+    let tx = make_signed_vesting_transaction(&contract_address, &key_1, Address::from(&key_2), 150);
+    vesting_contract
+        .reserve_balance(
+            &tx,
+            &mut reserved_balance,
+            &block_state,
+            data_store.read(&db_txn),
+        )
+        .expect("Failed to reserve balance");
+    assert_eq!(reserved_balance.balance(), Coin::from_u64_unchecked(180));
+
+    // Releasing frees the share again.
+    let tx = make_signed_vesting_transaction(&contract_address, &key_2, Address::from(&key_1), 30);
+    vesting_contract
+        .release_balance(&tx, &mut reserved_balance, data_store.read(&db_txn))
+        .expect("Failed to release balance");
+    assert_eq!(
+        reserved_balance.balance_for(&Address::from(&key_2)),
+        Coin::ZERO
+    );
+}
@@ -368,7 +368,7 @@ impl Block {
             // Perform block type specific body verification.
             if let Block::Micro(block) = self {
                 let body = block.body.as_ref().unwrap();
-                body.verify(self.is_skip(), self.block_number())?;
+                body.verify(self.is_skip(), self.block_number(), self.timestamp())?;
             }
         }
 
@@ -579,9 +579,19 @@ impl Block {
 
     /// Verifies that the block is valid for the given validators.
     pub fn verify_validators(&self, validators: &Validators) -> Result<(), BlockError> {
+        self.verify_validators_with(validators, false)
+    }
+
+    /// Verifies that the block is valid for the given validators. The signatures are verified in
+    /// parallel if `parallel` is set and the `parallel` feature is enabled.
+    pub fn verify_validators_with(
+        &self,
+        validators: &Validators,
+        parallel: bool,
+    ) -> Result<(), BlockError> {
         match self {
             Block::Micro(block) => block.verify_validators(validators),
-            Block::Macro(block) => block.verify_validators(validators),
+            Block::Macro(block) => block.verify_validators(validators, parallel),
         }
     }
 }
@@ -0,0 +1,110 @@
+use nimiq_hash::{Blake2bHash, Blake2bHasher, Hash, Hasher};
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_transaction::{ExecutedTransaction, Transaction};
+
+use crate::{EquivocationProof, MicroBody};
+
+/// Identifies a transaction within a compact block by the first bytes of its hash, keyed with
+/// the hash of the block.
+///
+/// Short ids are not unique. A node reconstructing a body from its mempool might therefore pick
+/// the wrong transaction, which is detected by comparing the body hash with the header. Keying
+/// the short ids with the block hash prevents collisions from being crafted for every block.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct ShortTransactionId(pub [u8; ShortTransactionId::SIZE]);
+
+impl ShortTransactionId {
+    pub const SIZE: usize = 8;
+
+    /// Computes the short id of the transaction with the given hash in the given block.
+    pub fn new(block_hash: &Blake2bHash, tx_hash: &Blake2bHash) -> Self {
+        let hash = Blake2bHasher::with_key(block_hash.as_bytes()).digest(tx_hash.as_bytes());
+        let mut short_id = [0u8; Self::SIZE];
+        short_id.copy_from_slice(&hash.as_bytes()[..Self::SIZE]);
+        ShortTransactionId(short_id)
+    }
+
+    /// Computes the short id of the transaction in the given block.
+    pub fn from_transaction(block_hash: &Blake2bHash, transaction: &Transaction) -> Self {
+        Self::new(block_hash, &transaction.hash())
+    }
+}
+
+/// A transaction of a compact block, see [`CompactMicroBody`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CompactTransaction {
+    pub short_id: ShortTransactionId,
+    /// Whether the transaction was executed successfully, see [`ExecutedTransaction`].
+    pub succeeded: bool,
+}
+
+/// The body of a micro block with the transactions replaced by their short ids.
+///
+/// Nodes usually already know most of the transactions of a block from their mempool, so
+/// announcing only the short ids saves bandwidth. The receiver reconstructs the body from its
+/// mempool and requests the transactions it doesn't know from the announcer.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CompactMicroBody {
+    pub equivocation_proofs: Vec<EquivocationProof>,
+    pub transactions: Vec<CompactTransaction>,
+}
+
+impl CompactMicroBody {
+    /// Creates the compact body of the micro block with the given hash.
+    pub fn new(body: &MicroBody, block_hash: &Blake2bHash) -> Self {
+        CompactMicroBody {
+            equivocation_proofs: body.equivocation_proofs.clone(),
+            transactions: body
+                .transactions
+                .iter()
+                .map(|tx| CompactTransaction {
+                    short_id: ShortTransactionId::from_transaction(
+                        block_hash,
+                        tx.get_raw_transaction(),
+                    ),
+                    succeeded: tx.succeeded(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the short ids of the transactions in the order of the body.
+    pub fn short_ids(&self) -> Vec<ShortTransactionId> {
+        self.transactions.iter().map(|tx| tx.short_id).collect()
+    }
+
+    /// Reconstructs the body of the micro block with the given hash given its transactions in the
+    /// order of the body. Returns `None` if the transactions don't match the short ids.
+    pub fn reconstruct(
+        self,
+        block_hash: &Blake2bHash,
+        transactions: Vec<Transaction>,
+    ) -> Option<MicroBody> {
+        if transactions.len() != self.transactions.len() {
+            return None;
+        }
+
+        let transactions = self
+            .transactions
+            .into_iter()
+            .zip(transactions)
+            .map(|(compact, transaction)| {
+                if ShortTransactionId::from_transaction(block_hash, &transaction)
+                    != compact.short_id
+                {
+                    return None;
+                }
+                Some(if compact.succeeded {
+                    ExecutedTransaction::Ok(transaction)
+                } else {
+                    ExecutedTransaction::Err(transaction)
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(MicroBody {
+            equivocation_proofs: self.equivocation_proofs,
+            transactions,
+        })
+    }
+}
@@ -2,6 +2,7 @@ use std::{cmp::Ordering, hash::Hasher, io, mem, ops::Range};
 
 use nimiq_bls::{AggregatePublicKey, AggregateSignature};
 use nimiq_collections::BitSet;
+use nimiq_database_value_derive::DbSerializable;
 use nimiq_hash::{Blake2bHash, Blake2sHash, Hash as _, HashOutput, SerializeContent};
 use nimiq_keys::{
     Address, Ed25519PublicKey as SchnorrPublicKey, Ed25519Signature as SchnorrSignature,
@@ -24,7 +25,9 @@ use crate::{MacroHeader, MicroHeader};
 ///
 /// This can come in several forms, but e.g. producing two blocks in a single slot or voting twice
 /// in the same round.
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, SerializedMaxSize)]
+#[derive(
+    Clone, Debug, Deserialize, Eq, PartialEq, Serialize, SerializedMaxSize, DbSerializable,
+)]
 pub enum EquivocationProof {
     Fork(ForkProof),
     DoubleProposal(DoubleProposalProof),
@@ -3,21 +3,25 @@ extern crate log;
 
 pub use block::*;
 pub use block_proof::*;
+pub use compact_block::*;
 pub use equivocation_proof::*;
 pub use macro_block::*;
 pub use micro_block::*;
 pub use multisig::*;
 use nimiq_primitives::transaction::TransactionError;
+pub use randomness_proof::*;
 pub use skip_block::*;
 pub use tendermint::*;
 use thiserror::Error;
 
 mod block;
 mod block_proof;
+mod compact_block;
 mod equivocation_proof;
 mod macro_block;
 mod micro_block;
 mod multisig;
+mod randomness_proof;
 mod skip_block;
 mod tendermint;
 
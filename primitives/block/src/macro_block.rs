@@ -114,10 +114,15 @@ impl MacroBlock {
         self.header.round
     }
 
-    /// Verifies that the block is valid for the given validators.
-    pub(crate) fn verify_validators(&self, validators: &Validators) -> Result<(), BlockError> {
+    /// Verifies that the block is valid for the given validators. The Tendermint proof is
+    /// verified in parallel if `parallel` is set and the `parallel` feature is enabled.
+    pub(crate) fn verify_validators(
+        &self,
+        validators: &Validators,
+        parallel: bool,
+    ) -> Result<(), BlockError> {
         // Verify the Tendermint proof.
-        if !TendermintProof::verify(self, validators) {
+        if !TendermintProof::verify_with(self, validators, parallel) {
             warn!(
                 block = %self,
                 reason = "Macro block with bad justification",
@@ -371,8 +376,9 @@ impl SerializeContent for MacroHeader {
 /// The struct representing the body of a Macro block (can be either checkpoint or election).
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize, SerializedMaxSize)]
 pub struct MacroBody {
-    /// The reward related transactions of this block.
-    #[serialize_size(seq_max_elems = Policy::SLOTS as usize)]
+    /// The reward related transactions of this block. Every validator can receive one reward for
+    /// itself and one for its stakers, plus there is one transaction for the burned rewards.
+    #[serialize_size(seq_max_elems = 2 * Policy::SLOTS as usize + 1)]
     pub transactions: Vec<RewardTransaction>,
 }
 
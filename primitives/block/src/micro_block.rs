@@ -271,7 +271,12 @@ impl MicroBody {
     }
 
     /// Verifies the micro block: size, proofs, transactions, etc.
-    pub(crate) fn verify(&self, is_skip: bool, block_number: u32) -> Result<(), BlockError> {
+    pub(crate) fn verify(
+        &self,
+        is_skip: bool,
+        block_number: u32,
+        timestamp: u64,
+    ) -> Result<(), BlockError> {
         // Check that the maximum body size is not exceeded.
         let body_size = self.serialized_size();
         if body_size > Policy::MAX_SIZE_MICRO_BODY {
@@ -318,7 +323,7 @@ impl MicroBody {
             previous_proof = Some(proof);
         }
 
-        // Ensure transactions are unique and within their validity window.
+        // Ensure transactions are unique, within their validity window and not expired.
         let mut uniq = HashSet::new();
         for tx in &self.get_raw_transactions() {
             // Check validity window.
@@ -326,6 +331,11 @@ impl MicroBody {
                 return Err(BlockError::ExpiredTransaction);
             }
 
+            // Check expiry timestamp, which is only supported from protocol version 2 on.
+            if Policy::version_at(block_number) >= 2 && tx.is_expired_at(timestamp) {
+                return Err(BlockError::ExpiredTransaction);
+            }
+
             // Check uniqueness.
             if !uniq.insert(tx.hash::<Blake2bHash>()) {
                 return Err(BlockError::DuplicateTransaction);
@@ -0,0 +1,78 @@
+use nimiq_hash::Blake2bHash;
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_vrf::VrfSeed;
+
+use crate::{BlockError, MacroBlock, MicroHeader};
+
+/// Proves the VRF seed, and thus the randomness beacon output, of a finalized block.
+///
+/// The proof consists of the segment of the chain from the target block up to the macro block
+/// finalizing it. The headers are linked by their parent hashes and the macro block is justified
+/// by the validators of the election block it references.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RandomnessProof {
+    /// The headers of the micro blocks from the target block up to, but excluding, the finalizing
+    /// macro block. Empty if the target block is the macro block itself.
+    pub micro_headers: Vec<MicroHeader>,
+    /// The macro block finalizing the target block, including its justification but no body.
+    pub macro_block: MacroBlock,
+}
+
+impl RandomnessProof {
+    /// Returns the block number of the target block.
+    pub fn block_number(&self) -> u32 {
+        self.micro_headers
+            .first()
+            .map_or(self.macro_block.block_number(), |header| {
+                header.block_number
+            })
+    }
+
+    /// Returns the hash of the target block.
+    pub fn block_hash(&self) -> Blake2bHash {
+        self.micro_headers
+            .first()
+            .map_or_else(|| self.macro_block.hash(), |header| header.hash())
+    }
+
+    /// Returns the VRF seed of the target block. It is only proven once the proof was verified.
+    pub fn seed(&self) -> &VrfSeed {
+        self.micro_headers
+            .first()
+            .map_or(&self.macro_block.header.seed, |header| &header.seed)
+    }
+
+    /// Verifies the proof against the election block whose validators produced the finalizing
+    /// macro block and returns the randomness beacon output of the target block.
+    ///
+    /// The caller must make sure that the election block is part of the chain, e.g. by checking
+    /// its hash against a trusted one.
+    pub fn verify(&self, election_block: &MacroBlock) -> Result<Blake2bHash, BlockError> {
+        let validators = election_block
+            .header
+            .validators
+            .as_ref()
+            .ok_or(BlockError::InvalidBlockType)?;
+
+        if self.macro_block.header.parent_election_hash != election_block.hash() {
+            return Err(BlockError::InvalidParentElectionHash);
+        }
+        self.macro_block.verify_validators(validators, false)?;
+
+        // Check that the headers form a chain ending in the macro block.
+        let mut next_block_number = self.macro_block.block_number();
+        let mut next_parent_hash = &self.macro_block.header.parent_hash;
+        for header in self.micro_headers.iter().rev() {
+            if header.block_number + 1 != next_block_number {
+                return Err(BlockError::InvalidBlockNumber);
+            }
+            if &header.hash() != next_parent_hash {
+                return Err(BlockError::InvalidParentHash);
+            }
+            next_block_number = header.block_number;
+            next_parent_hash = &header.parent_hash;
+        }
+
+        Ok(self.seed().randomness())
+    }
+}
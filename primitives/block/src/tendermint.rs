@@ -1,5 +1,5 @@
 use log::error;
-use nimiq_bls::AggregatePublicKey;
+use nimiq_bls::{AggregatePublicKey, PublicKey};
 use nimiq_primitives::{
     policy::Policy, slots_allocation::Validators, TendermintIdentifier, TendermintStep,
     TendermintVote,
@@ -26,6 +26,16 @@ impl TendermintProof {
     /// Verifies the proof. This only checks that the proof is valid for this block, not that the
     /// block itself is valid.
     pub fn verify(block: &MacroBlock, current_validators: &Validators) -> bool {
+        Self::verify_with(block, current_validators, false)
+    }
+
+    /// Verifies the proof like [`Self::verify`]. If `parallel` is set and the `parallel` feature
+    /// is enabled, the public keys of the signers are aggregated in parallel.
+    pub fn verify_with(
+        block: &MacroBlock,
+        current_validators: &Validators,
+        parallel: bool,
+    ) -> bool {
         // If there's no justification then the proof is false evidently.
         let justification = match &block.justification {
             None => {
@@ -57,15 +67,46 @@ impl TendermintProof {
 
         // Get the public key for each SLOT and add them together to get the aggregated public key
         // (if they are part of the Multisignature Bitset).
-        let mut agg_pk = AggregatePublicKey::new();
+        let voting_keys = current_validators.voting_keys();
+        let signers = voting_keys
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| justification.sig.signers.contains(*i))
+            .map(|(_, pk)| pk);
 
-        for (i, pk) in current_validators.voting_keys().iter().enumerate() {
-            if justification.sig.signers.contains(i) {
-                agg_pk.aggregate(pk);
-            }
-        }
+        let agg_pk = Self::aggregate(signers, parallel);
 
         // Verify the aggregated signature against our aggregated public key.
         agg_pk.verify(&message, &justification.sig.signature)
     }
+
+    /// Aggregates the given public keys, in parallel if `parallel` is set and the `parallel`
+    /// feature is enabled.
+    #[cfg_attr(not(feature = "parallel"), allow(unused_variables))]
+    fn aggregate<'a>(
+        public_keys: impl Iterator<Item = &'a PublicKey>,
+        parallel: bool,
+    ) -> AggregatePublicKey {
+        #[cfg(feature = "parallel")]
+        if parallel {
+            use rayon::prelude::*;
+
+            return public_keys
+                .collect::<Vec<_>>()
+                .into_par_iter()
+                .fold(AggregatePublicKey::new, |mut agg_pk, pk| {
+                    agg_pk.aggregate(pk);
+                    agg_pk
+                })
+                .reduce(AggregatePublicKey::new, |mut agg_pk, other| {
+                    agg_pk.merge_into(&other);
+                    agg_pk
+                });
+        }
+
+        public_keys.fold(AggregatePublicKey::new(), |mut agg_pk, pk| {
+            agg_pk.aggregate(pk);
+            agg_pk
+        })
+    }
 }
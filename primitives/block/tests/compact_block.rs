@@ -0,0 +1,62 @@
+use nimiq_block::{CompactMicroBody, MicroBody, ShortTransactionId};
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_keys::KeyPair;
+use nimiq_primitives::networks::NetworkId;
+use nimiq_test_utils::blockchain::generate_transactions;
+use nimiq_transaction::ExecutedTransaction;
+
+#[test]
+fn it_can_reconstruct_compact_micro_body() {
+    let transactions =
+        generate_transactions(&KeyPair::default(), 1, NetworkId::UnitAlbatross, 3, 0);
+    let body = MicroBody {
+        equivocation_proofs: vec![],
+        transactions: vec![
+            ExecutedTransaction::Ok(transactions[0].clone()),
+            ExecutedTransaction::Err(transactions[1].clone()),
+            ExecutedTransaction::Ok(transactions[2].clone()),
+        ],
+    };
+    let block_hash: Blake2bHash = "block".hash();
+
+    let compact_body = CompactMicroBody::new(&body, &block_hash);
+    assert_eq!(
+        compact_body.short_ids(),
+        transactions
+            .iter()
+            .map(|tx| ShortTransactionId::from_transaction(&block_hash, tx))
+            .collect::<Vec<_>>()
+    );
+
+    // The transactions must be given in the order of the body.
+    let mut reversed = transactions.clone();
+    reversed.reverse();
+    assert_eq!(
+        compact_body.clone().reconstruct(&block_hash, reversed),
+        None
+    );
+    assert_eq!(
+        compact_body
+            .clone()
+            .reconstruct(&block_hash, transactions[..2].to_vec()),
+        None
+    );
+
+    // The short ids depend on the block.
+    let other_block_hash: Blake2bHash = "other block".hash();
+    assert_eq!(
+        compact_body
+            .clone()
+            .reconstruct(&other_block_hash, transactions.clone()),
+        None
+    );
+    assert_ne!(
+        CompactMicroBody::new(&body, &other_block_hash).short_ids(),
+        compact_body.short_ids()
+    );
+
+    assert_eq!(
+        compact_body.reconstruct(&block_hash, transactions),
+        Some(body)
+    );
+}
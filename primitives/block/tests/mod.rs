@@ -10,6 +10,7 @@ use nimiq_test_log::test;
 use nimiq_vrf::VrfSeed;
 
 mod block_proof;
+mod compact_block;
 mod macro_block;
 
 #[test]
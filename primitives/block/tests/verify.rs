@@ -301,6 +301,59 @@ fn test_verify_micro_block_body_txns() {
     );
 }
 
+#[test]
+fn test_verify_micro_block_body_expiry_timestamp() {
+    let mut micro_header = MicroHeader {
+        network: NetworkId::UnitAlbatross,
+        version: Policy::VERSION,
+        block_number: 1,
+        timestamp: 1000,
+        ..Default::default()
+    };
+
+    let micro_justification = MicroJustification::Micro(Ed25519Signature::default());
+
+    let mut txns = generate_transactions(&KeyPair::default(), 1, NetworkId::UnitAlbatross, 2, 0);
+    txns[0].set_expiry_timestamp(Some(999));
+
+    let mut micro_body = MicroBody {
+        equivocation_proofs: vec![],
+        transactions: txns
+            .iter()
+            .map(|tx| ExecutedTransaction::Ok(tx.clone()))
+            .collect(),
+    };
+
+    // Build a block with body with a transaction whose expiry timestamp has passed
+    micro_header.body_root = micro_body.hash();
+    let block = Block::Micro(MicroBlock {
+        header: micro_header.clone(),
+        justification: Some(micro_justification.clone()),
+        body: Some(micro_body.clone()),
+    });
+
+    // The body check should fail
+    assert_eq!(
+        block.verify(NetworkId::UnitAlbatross),
+        Err(BlockError::ExpiredTransaction)
+    );
+
+    // The transaction can still be included at its expiry timestamp
+    txns[0].set_expiry_timestamp(Some(1000));
+    micro_body.transactions = txns
+        .iter()
+        .map(|tx| ExecutedTransaction::Ok(tx.clone()))
+        .collect();
+    micro_header.body_root = micro_body.hash();
+    let block = Block::Micro(MicroBlock {
+        header: micro_header,
+        justification: Some(micro_justification),
+        body: Some(micro_body),
+    });
+
+    assert_eq!(block.verify(NetworkId::UnitAlbatross), Ok(()));
+}
+
 #[test]
 fn test_verify_micro_block_body_fork_proofs() {
     let genesis_block_number = Policy::genesis_block_number();
@@ -98,6 +98,8 @@ pub enum AccountError {
     AlreadyExistentAddress { address: Address },
     #[error("Error during chunk processing: {0}")]
     ChunkError(#[from] MerkleRadixTrieError),
+    #[error("Contract data store entry exceeds the size limits")]
+    DataStoreLimitExceeded,
 }
 
 impl From<CoinUnderflowError> for AccountError {
@@ -150,6 +152,8 @@ pub enum FailReason {
     ChunkError,
     #[error("Failing transaction failed for unknown reason")]
     Incomplete,
+    #[error("Contract data store entry exceeds the size limits")]
+    DataStoreLimitExceeded,
 }
 
 impl From<AccountError> for FailReason {
@@ -171,6 +175,7 @@ impl From<AccountError> for FailReason {
             AccountError::NonExistentAddress { .. } => FailReason::NonExistentAddress,
             AccountError::AlreadyExistentAddress { .. } => FailReason::AlreadyExistentAddress,
             AccountError::ChunkError(_) => FailReason::ChunkError,
+            AccountError::DataStoreLimitExceeded => FailReason::DataStoreLimitExceeded,
         }
     }
 }
@@ -3,13 +3,14 @@ use std::cmp;
 use nimiq_keys::Address;
 use nimiq_utils::math::powi;
 use once_cell::sync::OnceCell;
+use thiserror::Error;
 #[cfg(feature = "ts-types")]
 use wasm_bindgen::prelude::*;
 
 /// Global policy
 static GLOBAL_POLICY: OnceCell<Policy> = OnceCell::new();
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "ts-types", cfg_eval::cfg_eval, wasm_bindgen)]
 pub struct Policy {
     /// Length of a batch including the macro block
@@ -27,6 +28,9 @@ pub struct Policy {
     /// Genesis block number
     #[cfg_attr(feature = "ts-types", wasm_bindgen(skip))]
     pub genesis_block_number: u32,
+    /// Block number from which on version 2 of the protocol is used.
+    #[cfg_attr(feature = "ts-types", wasm_bindgen(skip))]
+    pub version_2_block_number: u32,
 }
 
 impl Policy {
@@ -131,9 +135,23 @@ impl Policy {
     ///     - total stake balance
     pub const MINIMUM_STAKE: u64 = 10_000_000;
 
+    /// The maximum commission a validator can charge on its rewards, in basis points (1/100 of a percent).
+    /// A validator charging the maximum commission keeps the full reward for itself.
+    pub const MAX_VALIDATOR_COMMISSION: u16 = 10_000;
+
     /// The number of epochs a validator is put in jail for. The jailing only happens for severe offenses.
     pub const JAIL_EPOCHS: u32 = 8;
 
+    /// The number of election blocks that must pass before a scheduled unstake can be claimed. The
+    /// withdrawal is released after the reporting window of the last of these election blocks.
+    pub const WITHDRAWAL_DELAY_EPOCHS: u32 = 1;
+
+    /// The maximum number of scheduled withdrawals a staker can have at the same time.
+    pub const MAX_SCHEDULED_WITHDRAWALS: usize = 16;
+
+    /// The maximum number of beneficiaries a vesting contract can have.
+    pub const MAX_VESTING_BENEFICIARIES: usize = 64;
+
     /// Total supply in units.
     pub const TOTAL_SUPPLY: u64 = 2_100_000_000_000_000;
 
@@ -165,6 +183,75 @@ impl Policy {
     pub fn get_or_init(policy: Policy) -> Policy {
         *GLOBAL_POLICY.get_or_init(|| policy)
     }
+
+    /// Validates the policy and installs it as the global policy.
+    ///
+    /// This must happen before any other policy function is used, as those install the default
+    /// policy. Fails if a different policy is already installed.
+    pub fn init(policy: Policy) -> Result<(), PolicyError> {
+        policy.validate()?;
+        let installed = Self::get_or_init(policy);
+        if installed != policy {
+            return Err(PolicyError::Mismatch {
+                installed,
+                requested: policy,
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks that the policy values are consistent with each other.
+    pub fn validate(&self) -> Result<(), PolicyError> {
+        // A batch consists of at least one micro block and the macro block.
+        if self.blocks_per_batch < 2 {
+            return Err(PolicyError::InvalidBlocksPerBatch(self.blocks_per_batch));
+        }
+        if self.batches_per_epoch == 0 {
+            return Err(PolicyError::InvalidBatchesPerEpoch(self.batches_per_epoch));
+        }
+        if self
+            .blocks_per_batch
+            .checked_mul(self.batches_per_epoch as u32)
+            .is_none()
+        {
+            return Err(PolicyError::EpochTooLong);
+        }
+        if self.transaction_validity_window == 0
+            || self.transaction_validity_window > self.batches_per_epoch as u32
+        {
+            return Err(PolicyError::InvalidTransactionValidityWindow(
+                self.transaction_validity_window,
+            ));
+        }
+        if self.state_chunks_max_size == 0 {
+            return Err(PolicyError::InvalidStateChunksMaxSize);
+        }
+        Ok(())
+    }
+}
+
+/// Errors of invalid policy values or of installing a policy.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PolicyError {
+    #[error("A batch must consist of at least 2 blocks, got {0}")]
+    InvalidBlocksPerBatch(u32),
+    #[error("An epoch must consist of at least 1 batch, got {0}")]
+    InvalidBatchesPerEpoch(u16),
+    #[error("The number of blocks per epoch doesn't fit into a block number")]
+    EpochTooLong,
+    #[error(
+        "The transaction validity window must be between 1 and the batches per epoch, got {0}"
+    )]
+    InvalidTransactionValidityWindow(u32),
+    #[error("The maximum size of state chunks must be at least 1")]
+    InvalidStateChunksMaxSize,
+    #[error(
+        "A different policy is already in use: installed {installed:?}, requested {requested:?}"
+    )]
+    Mismatch {
+        installed: Policy,
+        requested: Policy,
+    },
 }
 
 #[cfg_attr(feature = "ts-types", wasm_bindgen)]
@@ -220,6 +307,26 @@ impl Policy {
             .genesis_block_number
     }
 
+    /// Block number from which on version 2 of the protocol is used.
+    #[inline]
+    #[cfg_attr(feature = "ts-types", wasm_bindgen(getter = VERSION_2_BLOCK_NUMBER))]
+    pub fn version_2_block_number() -> u32 {
+        GLOBAL_POLICY
+            .get_or_init(Self::default)
+            .version_2_block_number
+    }
+
+    /// Returns the version of the protocol that is used at a given block number (height).
+    #[inline]
+    #[cfg_attr(feature = "ts-types", wasm_bindgen(js_name = versionAt))]
+    pub fn version_at(block_number: u32) -> u16 {
+        if block_number >= Self::version_2_block_number() {
+            2
+        } else {
+            1
+        }
+    }
+
     /// Maximum size of accounts trie chunks.
     #[inline]
     #[cfg_attr(feature = "ts-types", wasm_bindgen(getter = STATE_CHUNKS_MAX_SIZE))]
@@ -487,6 +594,16 @@ impl Policy {
         block_number + Self::blocks_per_epoch() * Self::JAIL_EPOCHS + 1
     }
 
+    /// Returns the first block at which an unstake scheduled at the given block number can be claimed.
+    #[inline]
+    #[cfg_attr(feature = "ts-types", wasm_bindgen(js_name = blockAfterWithdrawalDelay))]
+    pub fn block_after_withdrawal_delay(block_number: u32) -> u32 {
+        Self::block_after_reporting_window(
+            Self::election_block_after(block_number)
+                + Self::blocks_per_epoch() * (Self::WITHDRAWAL_DELAY_EPOCHS - 1),
+        )
+    }
+
     /// Returns the supply at a given time (as Unix time) in Lunas (1 NIM = 100,000 Lunas). It is
     /// calculated using the following formula:
     /// ```text
@@ -616,12 +733,24 @@ impl Policy {
         Self::VALIDATOR_DEPOSIT
     }
 
+    /// The maximum commission a validator can charge on its rewards, in basis points (1/100 of a percent).
+    #[cfg_attr(feature = "ts-types", wasm_bindgen(getter = MAX_VALIDATOR_COMMISSION))]
+    pub fn wasm_max_validator_commission() -> u16 {
+        Self::MAX_VALIDATOR_COMMISSION
+    }
+
     /// The number of epochs a validator is put in jail for. The jailing only happens for severe offenses.
     #[cfg_attr(feature = "ts-types", wasm_bindgen(getter = JAIL_EPOCHS))]
     pub fn wasm_jail_epochs() -> u32 {
         Self::JAIL_EPOCHS
     }
 
+    /// The number of election blocks that must pass before a scheduled unstake can be claimed.
+    #[cfg_attr(feature = "ts-types", wasm_bindgen(getter = WITHDRAWAL_DELAY_EPOCHS))]
+    pub fn wasm_withdrawal_delay_epochs() -> u32 {
+        Self::WITHDRAWAL_DELAY_EPOCHS
+    }
+
     /// Total supply in units.
     #[cfg_attr(feature = "ts-types", wasm_bindgen(getter = TOTAL_SUPPLY))]
     pub fn wasm_total_supply() -> u64 {
@@ -650,6 +779,8 @@ impl Default for Policy {
             state_chunks_max_size: 1000,
             transaction_validity_window: 120,
             genesis_block_number: 0,
+            // Version 2 hasn't been scheduled yet.
+            version_2_block_number: u32::MAX,
         }
     }
 }
@@ -661,6 +792,7 @@ pub const TEST_POLICY: Policy = Policy {
     transaction_validity_window: 2,
     // This number should match the one that is defined in the `unit` network genesis file which is the genesis used for unit testing
     genesis_block_number: 200,
+    version_2_block_number: 0,
 };
 
 #[cfg(test)]
@@ -673,6 +805,49 @@ mod tests {
         let _ = Policy::get_or_init(TEST_POLICY);
     }
 
+    #[test]
+    fn it_validates_policies() {
+        assert_eq!(Policy::default().validate(), Ok(()));
+        assert_eq!(TEST_POLICY.validate(), Ok(()));
+        assert_eq!(
+            Policy {
+                blocks_per_batch: 1,
+                ..TEST_POLICY
+            }
+            .validate(),
+            Err(PolicyError::InvalidBlocksPerBatch(1))
+        );
+        assert_eq!(
+            Policy {
+                transaction_validity_window: TEST_POLICY.batches_per_epoch as u32 + 1,
+                ..TEST_POLICY
+            }
+            .validate(),
+            Err(PolicyError::InvalidTransactionValidityWindow(5))
+        );
+        assert_eq!(
+            Policy {
+                blocks_per_batch: u32::MAX,
+                ..TEST_POLICY
+            }
+            .validate(),
+            Err(PolicyError::EpochTooLong)
+        );
+    }
+
+    #[test]
+    fn it_rejects_a_different_policy() {
+        initialize_policy();
+        assert_eq!(Policy::init(TEST_POLICY), Ok(()));
+        assert_eq!(
+            Policy::init(Policy::default()),
+            Err(PolicyError::Mismatch {
+                installed: TEST_POLICY,
+                requested: Policy::default(),
+            })
+        );
+    }
+
     #[test]
     fn it_correctly_computes_epoch() {
         initialize_policy();
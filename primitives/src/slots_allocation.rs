@@ -14,7 +14,7 @@
 //!                      |             SlotBand                      |    SlotBand       |
 //!                      +-------------------------------------------+-------------------+
 //! ```
-use std::{cmp::max, collections::BTreeMap, ops::Range, slice::Iter};
+use std::{collections::BTreeMap, ops::Range, slice::Iter};
 
 use ark_ec::CurveGroup;
 use ark_serialize::CanonicalSerialize;
@@ -31,6 +31,32 @@ pub const PK_TREE_DEPTH: usize = 5;
 /// This is the number of leaves in the PKTree circuit.
 pub const PK_TREE_BREADTH: usize = 2_usize.pow(PK_TREE_DEPTH as u32);
 
+/// Computes the slot ranges of consecutive slot bands, given the number of slots of each band in
+/// order. The first band starts at slot 0.
+///
+/// Returns `None` if the total number of slots doesn't fit into a `u16`.
+pub fn slot_ranges<I: IntoIterator<Item = u16>>(num_slots: I) -> Option<Vec<Range<u16>>> {
+    let mut start_slot: u16 = 0;
+    num_slots
+        .into_iter()
+        .map(|num_slots| {
+            let range = start_slot..start_slot.checked_add(num_slots)?;
+            start_slot = range.end;
+            Some(range)
+        })
+        .collect()
+}
+
+/// Returns the slot band owning the given slot, given the consecutive slot ranges of all bands as
+/// returned by [`slot_ranges`]. Returns `None` if the slot is not part of any band.
+pub fn band_of_slot(ranges: &[Range<u16>], slot: u16) -> Option<u16> {
+    let band = ranges.partition_point(|range| range.end <= slot);
+    ranges
+        .get(band)
+        .filter(|range| range.contains(&slot))
+        .map(|_| band as u16)
+}
+
 /// A slot that is assigned to a validator.
 pub struct Slot {
     /// The number identifying this slot.
@@ -80,6 +106,11 @@ impl Validator {
     pub fn num_slots(&self) -> u16 {
         self.slots.len() as u16
     }
+
+    /// Returns whether the given slot is owned by this validator.
+    pub fn owns_slot(&self, slot: u16) -> bool {
+        self.slots.contains(&slot)
+    }
 }
 
 /// Identifies a penalized slot by the slot id.
@@ -141,25 +172,25 @@ impl Validators {
     /// This function requires the slot to be within bounds. If it is not this function will panic.
     pub fn get_band_from_slot(&self, slot: u16) -> u16 {
         assert!(slot < Policy::SLOTS);
+        self.get_slot_band(slot)
+            .expect("Slot must be owned by a validator")
+    }
 
-        let mut pivot = self.num_validators() / 2;
-        let mut last_pivot = 0usize;
-        loop {
-            let pivot_diff = if pivot < last_pivot {
-                last_pivot - pivot
-            } else {
-                pivot - last_pivot
-            };
-            last_pivot = pivot;
-            let slots = self.validators[pivot].slots.clone();
-            if slot < slots.start {
-                pivot -= max(pivot_diff / 2, 1);
-            } else if slot >= slots.end {
-                pivot += max(pivot_diff / 2, 1);
-            } else {
-                return pivot as u16;
-            }
-        }
+    /// Returns the slot band of the validator that owns the given slot, if any.
+    pub fn get_slot_band(&self, slot: u16) -> Option<u16> {
+        let band = self
+            .validators
+            .partition_point(|validator| validator.slots.end <= slot);
+        self.validators
+            .get(band)
+            .filter(|validator| validator.owns_slot(slot))
+            .map(|_| band as u16)
+    }
+
+    /// Returns the validator that owns the given slot, if any.
+    pub fn get_validator_by_slot(&self, slot: u16) -> Option<&Validator> {
+        let band = self.get_slot_band(slot)?;
+        Some(&self.validators[band as usize])
     }
 
     /// Returns the validator given the slot number.
@@ -189,6 +220,26 @@ impl Validators {
         self.validator_map.get(address).cloned()
     }
 
+    /// Returns the slot range of a validator given its address, if it exists.
+    pub fn get_slots_by_address(&self, address: &Address) -> Option<Range<u16>> {
+        self.get_validator_by_address(address)
+            .map(|validator| validator.slots.clone())
+    }
+
+    /// Returns the slot ranges of all slot bands, in order.
+    pub fn slot_ranges(&self) -> Vec<Range<u16>> {
+        self.iter()
+            .map(|validator| validator.slots.clone())
+            .collect()
+    }
+
+    /// Iterates over the slot bands together with the validator owning them.
+    pub fn slot_bands(&self) -> impl Iterator<Item = (u16, &Validator)> {
+        self.iter()
+            .enumerate()
+            .map(|(band, validator)| (band as u16, validator))
+    }
+
     /// Returns the G2 projective associated with each slot, in order.
     pub fn voting_keys_g2(&self) -> Vec<G2Projective> {
         self.voting_keys().iter().map(|pk| pk.public_key).collect()
@@ -276,27 +327,39 @@ impl ValidatorsBuilder {
         voting_key: TBlsKey,
         signing_key: TSchnorrKey,
     ) {
-        let (_, _, num_slots) = self
+        self.push_slots(validator_address, voting_key, signing_key, 1);
+    }
+
+    /// Push `num_slots` new validator slots at once. This will add the slots to the validator, if
+    /// it already exists
+    pub fn push_slots<TBlsKey: Into<LazyBlsPublicKey>, TSchnorrKey: Into<SchnorrPublicKey>>(
+        &mut self,
+        validator_address: Address,
+        voting_key: TBlsKey,
+        signing_key: TSchnorrKey,
+        num_slots: u16,
+    ) {
+        let (_, _, validator_slots) = self
             .validators
             .entry(validator_address)
             .or_insert_with(|| (voting_key.into(), signing_key.into(), 0));
-        *num_slots += 1;
+        *validator_slots += num_slots;
     }
 
     /// Builds a Validators struct.
     pub fn build(self) -> Validators {
-        let mut validators = Vec::new();
-        let mut start_slot = 0;
-
-        for (validator_address, (voting_key, signing_key, num_slots)) in self.validators {
-            validators.push(Validator::new(
-                validator_address,
-                voting_key,
-                signing_key,
-                start_slot..(start_slot + num_slots),
-            ));
-            start_slot += num_slots;
-        }
+        let ranges = slot_ranges(self.validators.values().map(|(_, _, num_slots)| *num_slots))
+            .expect("The total number of slots must fit into a u16");
+        let validators = self
+            .validators
+            .into_iter()
+            .zip(ranges)
+            .map(
+                |((validator_address, (voting_key, signing_key, _)), slots)| {
+                    Validator::new(validator_address, voting_key, signing_key, slots)
+                },
+            )
+            .collect();
 
         Validators::new(validators)
     }
@@ -337,3 +400,68 @@ mod serde_derive {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use nimiq_bls::CompressedPublicKey;
+    use nimiq_keys::{Address, Ed25519PublicKey};
+
+    use super::{band_of_slot, slot_ranges, Validator, Validators};
+
+    fn validators(num_slots: &[u16]) -> Validators {
+        let validators = slot_ranges(num_slots.iter().cloned())
+            .unwrap()
+            .into_iter()
+            .enumerate()
+            .map(|(i, slots)| {
+                Validator::new(
+                    Address::from([i as u8; 20]),
+                    CompressedPublicKey::default(),
+                    Ed25519PublicKey::default(),
+                    slots,
+                )
+            })
+            .collect();
+        Validators::new(validators)
+    }
+
+    #[test]
+    fn it_computes_slot_ranges() {
+        assert_eq!(slot_ranges([3, 1, 4]), Some(vec![0..3, 3..4, 4..8]));
+        assert_eq!(slot_ranges([]), Some(vec![]));
+        assert_eq!(
+            slot_ranges([u16::MAX, 0]),
+            Some(vec![0..u16::MAX, u16::MAX..u16::MAX])
+        );
+        assert_eq!(slot_ranges([u16::MAX, 1]), None);
+    }
+
+    #[test]
+    fn it_finds_the_band_of_a_slot() {
+        let ranges = slot_ranges([3, 1, 4]).unwrap();
+        assert_eq!(band_of_slot(&ranges, 0), Some(0));
+        assert_eq!(band_of_slot(&ranges, 2), Some(0));
+        assert_eq!(band_of_slot(&ranges, 3), Some(1));
+        assert_eq!(band_of_slot(&ranges, 4), Some(2));
+        assert_eq!(band_of_slot(&ranges, 7), Some(2));
+        assert_eq!(band_of_slot(&ranges, 8), None);
+    }
+
+    #[test]
+    fn it_maps_validators_to_slots() {
+        let validators = validators(&[3, 1, 4]);
+
+        for (band, validator) in validators.slot_bands() {
+            for slot in validator.slots.clone() {
+                assert_eq!(validators.get_band_from_slot(slot), band);
+                assert_eq!(validators.get_validator_by_slot(slot), Some(validator));
+            }
+        }
+        assert_eq!(validators.get_slot_band(8), None);
+        assert_eq!(
+            validators.get_slots_by_address(&Address::from([1; 20])),
+            Some(3..4)
+        );
+        assert_eq!(Some(validators.slot_ranges()), slot_ranges([3, 1, 4]));
+    }
+}
@@ -14,7 +14,7 @@ pub enum TrieChunkPushResult {
 }
 
 /// A helper structure for holding a trie chunk and the corresponding start key.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrieChunkWithStart {
     pub chunk: TrieChunk,
     pub start_key: KeyNibbles,
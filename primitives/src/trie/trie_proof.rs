@@ -1,4 +1,7 @@
-use std::{cmp::Ordering, collections::BTreeMap};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet},
+};
 
 use log::error;
 use nimiq_hash::{Blake2bHash, Hash};
@@ -132,6 +135,8 @@ impl TrieProof {
         true
     }
 
+    /// Verifies the proof against the given root hash and returns the proven values of the given
+    /// keys. Keys that are not part of the trie map to `None`. Duplicate keys are only proven once.
     pub fn verify_values(
         self,
         root_hash: &Blake2bHash,
@@ -150,6 +155,7 @@ impl TrieProof {
         keys.sort_by(|&(k1p, k1), &(k2p, k2)| {
             k1p.post_order_cmp(k2p).then_with(|| k1.post_order_cmp(k2))
         });
+        keys.dedup_by(|(_, k1), (_, k2)| k1 == k2);
 
         let mut keys = keys.into_iter();
         let mut nodes = self.nodes.into_iter();
@@ -207,6 +213,68 @@ impl TrieProof {
     }
 }
 
+/// Collects an arbitrary set of keys to be proven by a single [`TrieProof`].
+///
+/// The keys are deduplicated and a proof for all of them includes the nodes shared by their paths
+/// only once, so proving many keys at once is considerably smaller than proving them separately.
+/// The same builder is used to verify the proof once it was received.
+#[derive(Clone, Debug, Default)]
+pub struct TrieProofBuilder {
+    keys: BTreeSet<KeyNibbles>,
+}
+
+impl TrieProofBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the given keys to the set of keys to prove.
+    pub fn with_keys<I: IntoIterator<Item = KeyNibbles>>(mut self, keys: I) -> Self {
+        self.keys.extend(keys);
+        self
+    }
+
+    /// Adds a key to the set of keys to prove. Returns `false` if the key was already added.
+    pub fn add_key(&mut self, key: KeyNibbles) -> bool {
+        self.keys.insert(key)
+    }
+
+    /// Returns the (deduplicated) keys to prove in ascending order.
+    pub fn keys(&self) -> impl Iterator<Item = &KeyNibbles> {
+        self.keys.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Splits the keys into builders of at most `max_keys` keys each.
+    pub fn split(self, max_keys: usize) -> Vec<TrieProofBuilder> {
+        assert!(max_keys > 0, "Cannot split into empty builders");
+
+        let keys: Vec<_> = self.keys.into_iter().collect();
+        keys.chunks(max_keys)
+            .map(|keys| TrieProofBuilder::new().with_keys(keys.iter().cloned()))
+            .collect()
+    }
+
+    /// Verifies that the given proof is valid for the given root hash and that it covers exactly
+    /// the keys of this builder. Returns the proven values, keys that are not part of the trie
+    /// map to `None`.
+    pub fn verify(
+        &self,
+        proof: TrieProof,
+        root_hash: &Blake2bHash,
+    ) -> Result<BTreeMap<KeyNibbles, Option<Vec<u8>>>, Error> {
+        let keys: Vec<_> = self.keys.iter().collect();
+        proof.verify_values(root_hash, &keys)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use nimiq_hash::Hash;
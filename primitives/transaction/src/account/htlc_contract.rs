@@ -2,7 +2,7 @@ use std::{borrow::Cow, str::FromStr};
 
 use nimiq_hash::{
     sha512::{Sha512Hash, Sha512Hasher},
-    Blake2bHash, Blake2bHasher, Hasher, Sha256Hash, Sha256Hasher,
+    Blake2bHash, Blake2bHasher, Hasher, Keccak256Hash, Keccak256Hasher, Sha256Hash, Sha256Hasher,
 };
 use nimiq_keys::Address;
 use nimiq_macros::{add_hex_io_fns_typed_arr, add_serialization_fns_typed_arr, create_typed_array};
@@ -10,8 +10,8 @@ use nimiq_primitives::account::AccountType;
 use nimiq_serde::{Deserialize, Serialize};
 
 use crate::{
-    account::AccountTransactionVerification, PoWSignatureProof, SignatureProof, Transaction,
-    TransactionError, TransactionFlags,
+    account::{staking_contract::verify_protocol_version, AccountTransactionVerification},
+    PoWSignatureProof, SignatureProof, Transaction, TransactionError, TransactionFlags,
 };
 
 /// The verifier trait for a hash time locked contract. This only uses data available in the transaction.
@@ -58,7 +58,14 @@ impl AccountTransactionVerification for HashedTimeLockedContractVerifier {
                 return Err(TransactionError::InvalidData);
             }
 
-            CreationTransactionData::parse(transaction)?.verify()
+            let data = CreationTransactionData::parse(transaction)?;
+
+            // Keccak-256 hash roots were introduced with version 2 of the protocol.
+            if matches!(data.hash_root, AnyHash::Keccak256(_)) {
+                verify_protocol_version(transaction, 2)?;
+            }
+
+            data.verify()
         } else {
             // PoW HTLC creation data specified the timeout (last field) as a u32 block number instead of a timestamp.
             if transaction.recipient_data.len() != (20 * 2 + 1 + 32 + 1 + 4)
@@ -99,6 +106,7 @@ pub enum AnyHash {
     Blake2b(AnyHash32),
     Sha256(AnyHash32),
     Sha512(AnyHash64),
+    Keccak256(AnyHash32),
 }
 
 impl AnyHash {
@@ -108,6 +116,7 @@ impl AnyHash {
             AnyHash::Blake2b(hash) => hash.to_hex(),
             AnyHash::Sha256(hash) => hash.to_hex(),
             AnyHash::Sha512(hash) => hash.to_hex(),
+            AnyHash::Keccak256(hash) => hash.to_hex(),
         }
     }
 
@@ -117,6 +126,7 @@ impl AnyHash {
             AnyHash::Blake2b(hash) => &hash.0,
             AnyHash::Sha256(hash) => &hash.0,
             AnyHash::Sha512(hash) => &hash.0,
+            AnyHash::Keccak256(hash) => &hash.0,
         }
     }
 }
@@ -145,6 +155,12 @@ impl From<Sha512Hash> for AnyHash {
     }
 }
 
+impl From<Keccak256Hash> for AnyHash {
+    fn from(value: Keccak256Hash) -> Self {
+        AnyHash::Keccak256(AnyHash32(value.into()))
+    }
+}
+
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
 #[repr(u8)]
 pub enum PreImage {
@@ -194,6 +210,12 @@ impl From<Sha512Hash> for PreImage {
     }
 }
 
+impl From<Keccak256Hash> for PreImage {
+    fn from(value: Keccak256Hash) -> Self {
+        PreImage::PreImage32(AnyHash32(value.into()))
+    }
+}
+
 impl FromStr for PreImage {
     type Err = hex::FromHexError;
 
@@ -362,6 +384,11 @@ impl OutgoingHTLCTransactionProof {
                 pre_image,
                 signature_proof,
             } => {
+                // Keccak-256 hash roots were introduced with version 2 of the protocol.
+                if matches!(hash_root, AnyHash::Keccak256(_)) {
+                    verify_protocol_version(transaction, 2)?;
+                }
+
                 let mut tmp_hash = pre_image.clone();
                 for _ in 0..*hash_depth {
                     match &hash_root {
@@ -378,6 +405,11 @@ impl OutgoingHTLCTransactionProof {
                             tmp_hash =
                                 PreImage::from(Sha512Hasher::default().digest(tmp_hash.as_bytes()));
                         }
+                        AnyHash::Keccak256(_) => {
+                            tmp_hash = PreImage::from(
+                                Keccak256Hasher::default().digest(tmp_hash.as_bytes()),
+                            );
+                        }
                     }
                 }
 
@@ -494,6 +526,8 @@ impl PoWOutgoingHTLCTransactionProof {
                             tmp_hash =
                                 PreImage::from(Sha512Hasher::default().digest(tmp_hash.as_bytes()));
                         }
+                        // Keccak256 hash roots didn't exist in the PoW chain.
+                        AnyHash::Keccak256(_) => return Err(TransactionError::InvalidProof),
                     }
                 }
 
@@ -620,6 +654,14 @@ mod serde_derive {
                     }
                     state.serialize_field(ANYHASH_FIELDS[1], hash)?;
                 }
+                AnyHash::Keccak256(hash) => {
+                    if human_readable {
+                        state.serialize_field(ANYHASH_FIELDS[0], &"keccak256")?;
+                    } else {
+                        state.serialize_field(ANYHASH_FIELDS[0], &5u8)?;
+                    }
+                    state.serialize_field(ANYHASH_FIELDS[1], hash)?;
+                }
             }
             state.end()
         }
@@ -659,6 +701,12 @@ mod serde_derive {
                         .ok_or_else(|| A::Error::invalid_length(1, &self))?;
                     Ok(AnyHash::Sha512(hash))
                 }
+                5u8 => {
+                    let hash: AnyHash32 = seq
+                        .next_element()?
+                        .ok_or_else(|| A::Error::invalid_length(1, &self))?;
+                    Ok(AnyHash::Keccak256(hash))
+                }
                 _ => Err(A::Error::invalid_value(
                     serde::de::Unexpected::Unsigned(algorithm as u64),
                     &"an AnyHash variant",
@@ -705,6 +753,10 @@ mod serde_derive {
                     let hash = AnyHash64::from_str(hash.as_str()).map_err(A::Error::custom)?;
                     Ok(AnyHash::Sha512(hash))
                 }
+                "keccak256" => {
+                    let hash = AnyHash32::from_str(hash.as_str()).map_err(A::Error::custom)?;
+                    Ok(AnyHash::Keccak256(hash))
+                }
                 _ => Err(A::Error::invalid_value(
                     serde::de::Unexpected::Str(algorithm.as_str()),
                     &"an AnyHash variant",
@@ -848,7 +900,7 @@ mod serde_derive {
             };
 
             let pre_image = match hash_root {
-                AnyHash::Blake2b(_) | AnyHash::Sha256(_) => {
+                AnyHash::Blake2b(_) | AnyHash::Sha256(_) | AnyHash::Keccak256(_) => {
                     let pre_image: AnyHash32 = seq
                         .next_element()?
                         .ok_or_else(|| Error::invalid_length(3, &self))?;
@@ -896,13 +948,14 @@ mod tests {
 
     use super::{AnyHash, AnyHash32, AnyHash64, PoWOutgoingHTLCTransactionProof, PreImage};
 
-    fn sample_anyhashes() -> [AnyHash; 3] {
+    fn sample_anyhashes() -> [AnyHash; 4] {
         let hash_32 = AnyHash32([0xC; AnyHash32::SIZE]);
         let hash_64 = AnyHash64([0xC; AnyHash64::SIZE]);
         [
             AnyHash::Sha256(hash_32.clone()),
-            AnyHash::Blake2b(hash_32),
+            AnyHash::Blake2b(hash_32.clone()),
             AnyHash::Sha512(hash_64),
+            AnyHash::Keccak256(hash_32),
         ]
     }
 
@@ -929,6 +982,12 @@ mod tests {
         );
         let bin = hashes[2].serialize_to_vec();
         assert_eq!(hex::decode("040C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C").unwrap(), bin);
+        let bin = hashes[3].serialize_to_vec();
+        assert_eq!(
+            hex::decode("050C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C")
+                .unwrap(),
+            bin
+        );
     }
 
     #[test]
@@ -945,6 +1004,10 @@ mod tests {
         let bin = hex::decode("040C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C").unwrap();
         let set = AnyHash::deserialize_from_vec(&bin).unwrap();
         assert_eq!(hashes[2], set);
+        let bin = hex::decode("050C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C0C")
+            .unwrap();
+        let set = AnyHash::deserialize_from_vec(&bin).unwrap();
+        assert_eq!(hashes[3], set);
     }
 
     #[test]
@@ -962,6 +1025,10 @@ mod tests {
             serde_json::to_string(&hashes[2]).unwrap(),
             r#"{"algorithm":"sha512","hash":"0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c"}"#,
         );
+        assert_eq!(
+            serde_json::to_string(&hashes[3]).unwrap(),
+            r#"{"algorithm":"keccak256","hash":"0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c"}"#,
+        );
     }
 
     #[test]
@@ -982,6 +1049,11 @@ mod tests {
                 .unwrap(),
             hashes[2],
         );
+        assert_eq!(
+            serde_json::from_str::<AnyHash>(r#"{"algorithm":"keccak256","hash":"0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c0c"}"#)
+                .unwrap(),
+            hashes[3],
+        );
     }
 
     #[test]
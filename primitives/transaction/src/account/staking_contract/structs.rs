@@ -15,10 +15,16 @@ use crate::{SignatureProof, Transaction, TransactionError};
 ///         * Deactivate
 ///         * Reactivate
 ///         * Retire
+///         * SetCommission
 ///     - Staker
 ///         * Create
 ///         * Update
 ///         * AddStake
+///         * SetActiveStake
+///         * RetireStake
+///         * SetRewardCompounding
+///         * ScheduleUnstake
+///         * ClaimWithdrawals
 ///
 /// 2. Outgoing transactions. The type of transaction, parameters and proof are given in the `proof` field of this transaction.
 ///    Supported outgoing transactions are:
@@ -85,6 +91,22 @@ pub enum IncomingStakingTransactionData {
         retire_stake: Coin,
         proof: SignatureProof,
     },
+    SetRewardCompounding {
+        compound_rewards: bool,
+        proof: SignatureProof,
+    },
+    ScheduleUnstake {
+        value: Coin,
+        proof: SignatureProof,
+    },
+    ClaimWithdrawals {
+        proof: SignatureProof,
+    },
+    SetValidatorCommission {
+        new_commission: u16,
+        // This proof is signed with the validator cold key.
+        proof: SignatureProof,
+    },
 }
 
 impl IncomingStakingTransactionData {
@@ -98,6 +120,10 @@ impl IncomingStakingTransactionData {
                 | IncomingStakingTransactionData::UpdateStaker { .. }
                 | IncomingStakingTransactionData::SetActiveStake { .. }
                 | IncomingStakingTransactionData::RetireStake { .. }
+                | IncomingStakingTransactionData::SetRewardCompounding { .. }
+                | IncomingStakingTransactionData::ScheduleUnstake { .. }
+                | IncomingStakingTransactionData::ClaimWithdrawals { .. }
+                | IncomingStakingTransactionData::SetValidatorCommission { .. }
         )
     }
 
@@ -202,6 +228,46 @@ impl IncomingStakingTransactionData {
                     return Err(TransactionError::ZeroValue);
                 }
 
+                // Check that the signature is correct.
+                verify_transaction_signature(transaction, proof)?
+            }
+            IncomingStakingTransactionData::SetRewardCompounding { proof, .. } => {
+                // Reward compounding was introduced with version 2 of the protocol.
+                verify_protocol_version(transaction, 2)?;
+
+                // Check that the signature is correct.
+                verify_transaction_signature(transaction, proof)?
+            }
+            IncomingStakingTransactionData::ScheduleUnstake { value, proof } => {
+                // Scheduled unstakes were introduced with version 2 of the protocol.
+                verify_protocol_version(transaction, 2)?;
+
+                // Check that the scheduled value is greater than 0.
+                if value.is_zero() {
+                    warn!("Schedule unstake transactions must unstake a non-zero amount of stake. The offending transaction is the following:\n{:?}", transaction);
+                    return Err(TransactionError::ZeroValue);
+                }
+
+                // Check that the signature is correct.
+                verify_transaction_signature(transaction, proof)?
+            }
+            IncomingStakingTransactionData::ClaimWithdrawals { proof } => {
+                // Scheduled unstakes were introduced with version 2 of the protocol.
+                verify_protocol_version(transaction, 2)?;
+
+                // Check that the signature is correct.
+                verify_transaction_signature(transaction, proof)?
+            }
+            IncomingStakingTransactionData::SetValidatorCommission {
+                new_commission,
+                proof,
+            } => {
+                // Validator commissions were introduced with version 2 of the protocol.
+                verify_protocol_version(transaction, 2)?;
+
+                // The commission can't exceed the full reward.
+                verify_commission(*new_commission)?;
+
                 // Check that the signature is correct.
                 verify_transaction_signature(transaction, proof)?
             }
@@ -220,7 +286,11 @@ impl IncomingStakingTransactionData {
             | IncomingStakingTransactionData::CreateStaker { proof, .. }
             | IncomingStakingTransactionData::UpdateStaker { proof, .. }
             | IncomingStakingTransactionData::SetActiveStake { proof, .. }
-            | IncomingStakingTransactionData::RetireStake { proof, .. } => {
+            | IncomingStakingTransactionData::RetireStake { proof, .. }
+            | IncomingStakingTransactionData::SetRewardCompounding { proof, .. }
+            | IncomingStakingTransactionData::ScheduleUnstake { proof, .. }
+            | IncomingStakingTransactionData::ClaimWithdrawals { proof }
+            | IncomingStakingTransactionData::SetValidatorCommission { proof, .. } => {
                 *proof = signature_proof;
             }
             IncomingStakingTransactionData::AddStake { .. } => {}
@@ -304,3 +374,33 @@ pub fn verify_proof_of_knowledge(
 
     Ok(())
 }
+
+/// Checks that the transaction can only be included in blocks of the given protocol version or
+/// later. Transactions can't be included in blocks prior to their validity start height.
+pub fn verify_protocol_version(
+    transaction: &Transaction,
+    version: u16,
+) -> Result<(), TransactionError> {
+    if Policy::version_at(transaction.validity_start_height) < version {
+        warn!(
+            validity_start_height = transaction.validity_start_height,
+            version, "Transaction is not supported before the activation of its protocol version"
+        );
+        return Err(TransactionError::InvalidData);
+    }
+
+    Ok(())
+}
+
+pub fn verify_commission(commission: u16) -> Result<(), TransactionError> {
+    if commission > Policy::MAX_VALIDATOR_COMMISSION {
+        warn!(
+            commission,
+            max_commission = Policy::MAX_VALIDATOR_COMMISSION,
+            "Validator commission exceeds the maximum commission"
+        );
+        return Err(TransactionError::InvalidData);
+    }
+
+    Ok(())
+}
@@ -1,10 +1,12 @@
+use std::collections::BTreeSet;
+
 use nimiq_keys::Address;
-use nimiq_primitives::{account::AccountType, coin::Coin};
+use nimiq_primitives::{account::AccountType, coin::Coin, policy::Policy};
 use nimiq_serde::{Deserialize, Serialize, SerializedSize};
 
 use crate::{
-    account::AccountTransactionVerification, PoWSignatureProof, SignatureProof, Transaction,
-    TransactionError, TransactionFlags,
+    account::{staking_contract::verify_protocol_version, AccountTransactionVerification},
+    PoWSignatureProof, SignatureProof, Transaction, TransactionError, TransactionFlags,
 };
 
 /// The verifier trait for a basic account. This only uses data available in the transaction.
@@ -73,12 +75,23 @@ impl AccountTransactionVerification for VestingContractVerifier {
     }
 }
 
+/// A beneficiary of a vesting contract and its weight. Each beneficiary can withdraw the share
+/// of the released funds corresponding to its weight relative to the total weight.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+pub struct VestingBeneficiary {
+    /// The address of the beneficiary.
+    pub address: Address,
+    /// The weight of the beneficiary.
+    pub weight: u16,
+}
+
 /// Data used to create vesting contracts.
 ///
 /// Used in [`Transaction::recipient_data`].
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct CreationTransactionData {
-    /// The owner of the contract, the only address that can interact with it.
+    /// The owner of the contract, the only address that can interact with it if there are no
+    /// beneficiaries.
     pub owner: Address,
     /// The timestamp at which the release schedule starts.
     pub start_time: u64,
@@ -88,6 +101,9 @@ pub struct CreationTransactionData {
     pub step_amount: Coin,
     /// Initially locked balance.
     pub total_amount: Coin,
+    /// The beneficiaries the released funds are split between. If empty, the owner can withdraw
+    /// all released funds.
+    pub beneficiaries: Vec<VestingBeneficiary>,
 }
 
 #[derive(Deserialize, Serialize, SerializedSize)]
@@ -120,6 +136,17 @@ struct CreationTransactionData32 {
     pub step_amount: Coin,
     pub total_amount: Coin,
 }
+#[derive(Deserialize, Serialize)]
+struct CreationTransactionDataWithBeneficiaries {
+    pub owner: Address,
+    #[serde(with = "nimiq_serde::fixint::be")]
+    pub start_time: u64,
+    #[serde(with = "nimiq_serde::fixint::be")]
+    pub time_step: u64,
+    pub step_amount: Coin,
+    pub total_amount: Coin,
+    pub beneficiaries: Vec<VestingBeneficiary>,
+}
 
 impl CreationTransactionData {
     pub fn parse_data(data: &[u8], tx_value: Coin) -> Result<Self, TransactionError> {
@@ -134,6 +161,7 @@ impl CreationTransactionData {
                     time_step,
                     step_amount: tx_value,
                     total_amount: tx_value,
+                    beneficiaries: vec![],
                 }
             }
             CreationTransactionData24::SIZE => {
@@ -149,6 +177,7 @@ impl CreationTransactionData {
                     time_step,
                     step_amount,
                     total_amount: tx_value,
+                    beneficiaries: vec![],
                 }
             }
             CreationTransactionData32::SIZE => {
@@ -165,14 +194,67 @@ impl CreationTransactionData {
                     time_step,
                     step_amount,
                     total_amount,
+                    beneficiaries: vec![],
+                }
+            }
+            len if len > CreationTransactionData32::SIZE => {
+                let CreationTransactionDataWithBeneficiaries {
+                    owner,
+                    start_time,
+                    time_step,
+                    step_amount,
+                    total_amount,
+                    beneficiaries,
+                } = CreationTransactionDataWithBeneficiaries::deserialize_all(data)?;
+                Self::verify_beneficiaries(&beneficiaries)?;
+                CreationTransactionData {
+                    owner,
+                    start_time,
+                    time_step,
+                    step_amount,
+                    total_amount,
+                    beneficiaries,
                 }
             }
             _ => return Err(TransactionError::InvalidData),
         })
     }
 
+    /// Checks that there is at least one and at most [`Policy::MAX_VESTING_BENEFICIARIES`]
+    /// beneficiaries, that all of them have a positive weight and that no address is listed twice.
+    fn verify_beneficiaries(beneficiaries: &[VestingBeneficiary]) -> Result<(), TransactionError> {
+        if beneficiaries.is_empty() || beneficiaries.len() > Policy::MAX_VESTING_BENEFICIARIES {
+            warn!(
+                num_beneficiaries = beneficiaries.len(),
+                "Invalid number of vesting beneficiaries"
+            );
+            return Err(TransactionError::InvalidData);
+        }
+
+        let mut addresses = BTreeSet::new();
+        for beneficiary in beneficiaries {
+            if beneficiary.weight == 0 || !addresses.insert(&beneficiary.address) {
+                warn!(
+                    address = %beneficiary.address,
+                    "Vesting beneficiaries must have a positive weight and be unique"
+                );
+                return Err(TransactionError::InvalidData);
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn parse(transaction: &Transaction) -> Result<Self, TransactionError> {
-        CreationTransactionData::parse_data(&transaction.recipient_data, transaction.value)
+        let data =
+            CreationTransactionData::parse_data(&transaction.recipient_data, transaction.value)?;
+
+        // Beneficiaries were introduced with version 2 of the protocol.
+        if !data.beneficiaries.is_empty() {
+            verify_protocol_version(transaction, 2)?;
+        }
+
+        Ok(data)
     }
 
     pub fn to_tx_data(&self) -> Vec<u8> {
@@ -182,8 +264,19 @@ impl CreationTransactionData {
             time_step,
             step_amount,
             total_amount,
+            beneficiaries,
         } = self.clone();
-        if step_amount == total_amount {
+        if !beneficiaries.is_empty() {
+            CreationTransactionDataWithBeneficiaries {
+                owner,
+                start_time,
+                time_step,
+                step_amount,
+                total_amount,
+                beneficiaries,
+            }
+            .serialize_to_vec()
+        } else if step_amount == total_amount {
             if start_time == 0 {
                 CreationTransactionData8 { owner, time_step }.serialize_to_vec()
             } else {
@@ -328,6 +421,7 @@ impl PoWCreationTransactionData {
             time_step,
             step_amount: self.step_amount,
             total_amount: self.total_amount,
+            beneficiaries: vec![],
         }
     }
 }
@@ -0,0 +1,116 @@
+use nimiq_database_value_derive::DbSerializable;
+use nimiq_hash::{Blake2bHasher, Hasher};
+use nimiq_keys::Address;
+use nimiq_serde::{Deserialize, Serialize};
+
+/// A bloom filter over the addresses touched by the historic transactions of a batch.
+///
+/// It allows light clients to cheaply determine which batches may contain transactions relevant
+/// to an address before requesting any receipts. A filter never yields false negatives, but it may
+/// yield false positives, especially for batches with a lot of activity.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, DbSerializable)]
+pub struct AddressFilter {
+    bits: Vec<u8>,
+}
+
+impl AddressFilter {
+    /// The size of a filter in bytes.
+    pub const SIZE: usize = 2048;
+    /// The number of bits set per address.
+    pub const NUM_HASHES: usize = 7;
+
+    /// Creates an empty filter.
+    pub fn new() -> Self {
+        Self {
+            bits: vec![0; Self::SIZE],
+        }
+    }
+
+    /// Returns true if no address has been inserted into this filter.
+    pub fn is_empty(&self) -> bool {
+        self.bits.iter().all(|byte| *byte == 0)
+    }
+
+    /// Inserts an address into the filter.
+    pub fn insert(&mut self, address: &Address) {
+        for position in Self::positions(address, self.bits.len() * 8) {
+            self.bits[position / 8] |= 1 << (position % 8);
+        }
+    }
+
+    /// Returns true if the address may have been inserted into this filter and false if it has
+    /// definitely not been inserted.
+    /// A filter without any bits (e.g. received from a misbehaving peer) can't rule out any address.
+    pub fn contains(&self, address: &Address) -> bool {
+        if self.bits.is_empty() {
+            return true;
+        }
+        Self::positions(address, self.bits.len() * 8)
+            .all(|position| self.bits[position / 8] & (1 << (position % 8)) != 0)
+    }
+
+    /// Adds all addresses of another filter of the same size to this one.
+    pub fn merge(&mut self, other: &AddressFilter) {
+        assert_eq!(self.bits.len(), other.bits.len(), "Filter sizes differ");
+        for (byte, other) in self.bits.iter_mut().zip(other.bits.iter()) {
+            *byte |= other;
+        }
+    }
+
+    /// Derives the bit positions of an address from its hash.
+    fn positions(address: &Address, num_bits: usize) -> impl Iterator<Item = usize> {
+        let hash = Blake2bHasher::default().digest(address.as_bytes()).0;
+        (0..Self::NUM_HASHES).map(move |i| {
+            let word = u32::from_le_bytes(hash[i * 4..(i + 1) * 4].try_into().unwrap());
+            word as usize % num_bits
+        })
+    }
+}
+
+impl Default for AddressFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Extend<&'a Address> for AddressFilter {
+    fn extend<T: IntoIterator<Item = &'a Address>>(&mut self, addresses: T) {
+        for address in addresses {
+            self.insert(address);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nimiq_keys::Address;
+
+    use super::AddressFilter;
+
+    #[test]
+    fn it_contains_inserted_addresses() {
+        let addresses: Vec<_> = (0..100u8).map(|i| Address::from([i; 20])).collect();
+
+        let mut filter = AddressFilter::new();
+        assert!(filter.is_empty());
+        filter.extend(&addresses);
+        assert!(!filter.is_empty());
+
+        for address in &addresses {
+            assert!(filter.contains(address));
+        }
+        assert!(!filter.contains(&Address::from([0xff; 20])));
+    }
+
+    #[test]
+    fn it_merges_filters() {
+        let mut filter = AddressFilter::new();
+        filter.insert(&Address::from([1; 20]));
+        let mut other = AddressFilter::new();
+        other.insert(&Address::from([2; 20]));
+
+        filter.merge(&other);
+        assert!(filter.contains(&Address::from([1; 20])));
+        assert!(filter.contains(&Address::from([2; 20])));
+    }
+}
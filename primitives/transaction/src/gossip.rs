@@ -0,0 +1,187 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_network_interface::{
+    network::{Network, Topic},
+    request::{Handle, RequestCommon, RequestMarker},
+};
+use nimiq_serde::{Deserialize, Serialize};
+
+use crate::Transaction;
+
+/// Multiple small transactions published in a single gossip message.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TransactionBatch {
+    pub transactions: Vec<Transaction>,
+}
+
+/// Transaction batch topic for the Mempool to request batches of transactions from the network
+#[derive(Clone, Debug, Default)]
+pub struct TransactionBatchTopic;
+
+impl Topic for TransactionBatchTopic {
+    type Item = TransactionBatch;
+
+    const BUFFER_SIZE: usize = 256;
+    const NAME: &'static str = "transaction-batch";
+    const VALIDATE: bool = true;
+    const MAX_MESSAGES: u32 = 1_000;
+}
+
+/// Announces large transactions by their hash. The transactions can be pulled from the peer
+/// that propagated the announcement using [`RequestTransactionsByHash`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TransactionAnnouncement {
+    pub hashes: Vec<Blake2bHash>,
+}
+
+impl TransactionAnnouncement {
+    /// Maximum number of hashes in a single announcement. Announcements with more hashes are
+    /// rejected.
+    pub const MAX_HASHES: usize = 64;
+}
+
+/// Transaction announcement topic for the Mempool to learn about large transactions
+#[derive(Clone, Debug, Default)]
+pub struct TransactionAnnouncementTopic;
+
+impl Topic for TransactionAnnouncementTopic {
+    type Item = TransactionAnnouncement;
+
+    const BUFFER_SIZE: usize = 256;
+    const NAME: &'static str = "transaction-announcement";
+    const VALIDATE: bool = true;
+    const MAX_MESSAGES: u32 = 1_000;
+}
+
+/// Requests announced transactions by their hash. At most
+/// [`TransactionAnnouncement::MAX_HASHES`] transactions are provided.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RequestTransactionsByHash {
+    pub hashes: Vec<Blake2bHash>,
+}
+
+impl RequestCommon for RequestTransactionsByHash {
+    type Kind = RequestMarker;
+    const TYPE_ID: u16 = 219;
+    /// The requested transactions that are known, in no particular order.
+    type Response = Vec<Transaction>;
+    const MAX_REQUESTS: u32 = 1_000;
+}
+
+/// The transactions this node can provide to peers pulling announced transactions, i.e. the
+/// transactions it announced itself and the announced transactions it pulled from other peers.
+///
+/// The cache is bounded, the oldest transactions are evicted first.
+#[derive(Clone, Debug)]
+pub struct TransactionGossipCache {
+    inner: Arc<Mutex<CacheInner>>,
+}
+
+#[derive(Debug)]
+struct CacheInner {
+    capacity: usize,
+    transactions: HashMap<Blake2bHash, Transaction>,
+    order: VecDeque<Blake2bHash>,
+}
+
+impl TransactionGossipCache {
+    /// Default maximum number of cached transactions.
+    pub const DEFAULT_CAPACITY: usize = 1024;
+
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(CacheInner {
+                capacity,
+                transactions: HashMap::new(),
+                order: VecDeque::new(),
+            })),
+        }
+    }
+
+    /// Adds a transaction to the cache, evicting the oldest one if the cache is full.
+    pub fn insert(&self, transaction: Transaction) {
+        let hash = transaction.hash::<Blake2bHash>();
+        let mut inner = self.inner.lock().unwrap();
+        if inner
+            .transactions
+            .insert(hash.clone(), transaction)
+            .is_some()
+        {
+            return;
+        }
+        inner.order.push_back(hash);
+
+        while inner.order.len() > inner.capacity {
+            if let Some(evicted) = inner.order.pop_front() {
+                inner.transactions.remove(&evicted);
+            }
+        }
+    }
+
+    /// Returns the cached transactions among the given hashes.
+    pub fn get_all(&self, hashes: &[Blake2bHash]) -> Vec<Transaction> {
+        let inner = self.inner.lock().unwrap();
+        hashes
+            .iter()
+            .filter_map(|hash| inner.transactions.get(hash).cloned())
+            .collect()
+    }
+
+    pub fn contains(&self, hash: &Blake2bHash) -> bool {
+        self.inner.lock().unwrap().transactions.contains_key(hash)
+    }
+}
+
+impl Default for TransactionGossipCache {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl<N: Network> Handle<N, TransactionGossipCache> for RequestTransactionsByHash {
+    fn handle(&self, _peer_id: N::PeerId, cache: &TransactionGossipCache) -> Vec<Transaction> {
+        let num_hashes = self.hashes.len().min(TransactionAnnouncement::MAX_HASHES);
+        cache.get_all(&self.hashes[..num_hashes])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nimiq_hash::{Blake2bHash, Hash};
+    use nimiq_keys::Address;
+    use nimiq_primitives::{coin::Coin, networks::NetworkId};
+
+    use super::TransactionGossipCache;
+    use crate::Transaction;
+
+    fn transaction(value: u64) -> Transaction {
+        Transaction::new_basic(
+            Address::default(),
+            Address::default(),
+            Coin::from_u64_unchecked(value),
+            Coin::ZERO,
+            1,
+            NetworkId::UnitAlbatross,
+        )
+    }
+
+    #[test]
+    fn it_evicts_the_oldest_transactions() {
+        let cache = TransactionGossipCache::new(2);
+        let transactions: Vec<_> = (1..=3).map(transaction).collect();
+        let hashes: Vec<Blake2bHash> = transactions.iter().map(|tx| tx.hash()).collect();
+
+        for tx in &transactions {
+            cache.insert(tx.clone());
+        }
+        // Inserting a known transaction again does not evict anything.
+        cache.insert(transactions[2].clone());
+
+        assert!(!cache.contains(&hashes[0]));
+        assert_eq!(cache.get_all(&hashes), transactions[1..].to_vec());
+    }
+}
@@ -199,6 +199,7 @@ impl HistoricTransaction {
                 }),
                 Inherent::FinalizeBatch => {}
                 Inherent::FinalizeEpoch => {}
+                Inherent::RemoveTombstones => {}
             }
         }
 
@@ -219,6 +220,7 @@ impl HistoricTransaction {
                     Inherent::Jail { .. } => Some(()),
                     Inherent::FinalizeBatch => None,
                     Inherent::FinalizeEpoch => None,
+                    Inherent::RemoveTombstones => None,
                 })
                 .count()
             + equivocation_locator.len()
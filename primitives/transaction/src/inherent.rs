@@ -39,6 +39,9 @@ pub enum Inherent {
     FinalizeBatch,
     /// Emitted only on epoch finalization.
     FinalizeEpoch,
+    /// Emitted on epoch finalization from version 2 of the protocol on. It removes the tombstones
+    /// of deleted validators by undelegating their remaining stakers.
+    RemoveTombstones,
 }
 
 impl Inherent {
@@ -48,7 +51,8 @@ impl Inherent {
             Inherent::Penalize { .. }
             | Inherent::Jail { .. }
             | Inherent::FinalizeBatch
-            | Inherent::FinalizeEpoch => &Policy::STAKING_CONTRACT_ADDRESS,
+            | Inherent::FinalizeEpoch
+            | Inherent::RemoveTombstones => &Policy::STAKING_CONTRACT_ADDRESS,
         }
     }
 }
@@ -10,6 +10,7 @@ use std::{
 
 use bitflags::bitflags;
 use historic_transaction::RawTransactionHash;
+use nimiq_database_value_derive::DbSerializable;
 use nimiq_hash::{Blake2bHash, Hash, SerializeContent};
 use nimiq_keys::{Address, PublicKey};
 use nimiq_network_interface::network::Topic;
@@ -24,7 +25,7 @@ use thiserror::Error;
 
 use crate::account::{
     htlc_contract::{CreationTransactionData as HtlcCreationData, OutgoingHTLCTransactionProof},
-    staking_contract::IncomingStakingTransactionData,
+    staking_contract::{verify_protocol_version, IncomingStakingTransactionData},
     vesting_contract::CreationTransactionData as VestingCreationData,
     AccountTransactionVerification,
 };
@@ -33,6 +34,8 @@ mod control_transaction;
 mod equivocation_locator;
 
 pub mod account;
+pub mod address_filter;
+pub mod gossip;
 pub mod historic_transaction;
 pub mod history_proof;
 pub mod inherent;
@@ -104,6 +107,7 @@ bitflags! {
     pub struct TransactionFlags: u8 {
         const CONTRACT_CREATION = 0b1;
         const SIGNALING = 0b10;
+        const EXPIRY_TIMESTAMP = 0b100;
     }
 }
 
@@ -183,7 +187,7 @@ impl SerializeContent for ExecutedTransaction {
     }
 }
 
-#[derive(Clone, Eq, Debug)]
+#[derive(Clone, Eq, Debug, DbSerializable)]
 pub struct Transaction {
     pub sender: Address,
     pub sender_type: AccountType,
@@ -196,6 +200,10 @@ pub struct Transaction {
     pub validity_start_height: u32,
     pub network_id: NetworkId,
     pub flags: TransactionFlags,
+    /// The timestamp (in milliseconds) after which the transaction can't be included in a block
+    /// anymore, even if it is still within its validity window. Only present if the
+    /// `EXPIRY_TIMESTAMP` flag is set.
+    pub expiry_timestamp: Option<u64>,
     pub proof: Vec<u8>,
     valid: bool,
 }
@@ -224,6 +232,7 @@ impl Transaction {
             validity_start_height,
             network_id,
             flags: TransactionFlags::empty(),
+            expiry_timestamp: None,
             proof: Vec::new(),
             valid: false,
         }
@@ -253,6 +262,7 @@ impl Transaction {
             validity_start_height,
             network_id,
             flags: TransactionFlags::empty(),
+            expiry_timestamp: None,
             proof: Vec::new(),
             valid: false,
         }
@@ -280,6 +290,7 @@ impl Transaction {
             validity_start_height,
             network_id,
             flags: TransactionFlags::SIGNALING,
+            expiry_timestamp: None,
             proof: Vec::new(),
             valid: false,
         }
@@ -308,6 +319,7 @@ impl Transaction {
             validity_start_height,
             network_id,
             flags: TransactionFlags::CONTRACT_CREATION,
+            expiry_timestamp: None,
             proof: Vec::new(),
             valid: false,
         };
@@ -345,14 +357,28 @@ impl Transaction {
             return Ok(());
         }
 
-        if self.sender == self.recipient {
-            return Err(TransactionError::SenderEqualsRecipient);
-        }
-
         if self.network_id != network_id {
             return Err(TransactionError::ForeignNetwork);
         }
 
+        self.verify_fields()?;
+
+        // Check transaction validity for sender account.
+        AccountType::verify_outgoing_transaction(self)?;
+
+        // Check transaction validity for recipient account.
+        AccountType::verify_incoming_transaction(self)?;
+
+        Ok(())
+    }
+
+    /// Checks the value and fee of the transaction and that sender and recipient differ,
+    /// independently of the network and the account types.
+    pub fn verify_fields(&self) -> Result<(), TransactionError> {
+        if self.sender == self.recipient {
+            return Err(TransactionError::SenderEqualsRecipient);
+        }
+
         // Check that value > 0 except if it is a signaling transaction.
         if self.flags.contains(TransactionFlags::SIGNALING) {
             if self.value != Coin::ZERO {
@@ -362,6 +388,18 @@ impl Transaction {
             return Err(TransactionError::ZeroValue);
         }
 
+        // Check that the expiry timestamp is present if and only if it is flagged.
+        if self.flags.contains(TransactionFlags::EXPIRY_TIMESTAMP)
+            != self.expiry_timestamp.is_some()
+        {
+            return Err(TransactionError::InvalidData);
+        }
+
+        // Expiry timestamps are only supported from protocol version 2 on.
+        if self.flags.contains(TransactionFlags::EXPIRY_TIMESTAMP) {
+            verify_protocol_version(self, 2)?;
+        }
+
         // Check that value + fee doesn't overflow.
         match self.value.checked_add(self.fee) {
             Some(coin) => {
@@ -372,12 +410,6 @@ impl Transaction {
             None => return Err(TransactionError::Overflow),
         }
 
-        // Check transaction validity for sender account.
-        AccountType::verify_outgoing_transaction(self)?;
-
-        // Check transaction validity for recipient account.
-        AccountType::verify_incoming_transaction(self)?;
-
         Ok(())
     }
 
@@ -390,6 +422,28 @@ impl Transaction {
             && block_height < self.validity_start_height + window
     }
 
+    /// Sets or removes the expiry timestamp (in milliseconds) of the transaction, updating its
+    /// flags accordingly. This changes the transaction hash, so it must be done before signing.
+    pub fn set_expiry_timestamp(&mut self, expiry_timestamp: Option<u64>) {
+        self.flags.set(
+            TransactionFlags::EXPIRY_TIMESTAMP,
+            expiry_timestamp.is_some(),
+        );
+        self.expiry_timestamp = expiry_timestamp;
+
+        // The address of a new contract depends on the transaction hash.
+        if self.flags.contains(TransactionFlags::CONTRACT_CREATION) {
+            self.recipient = self.contract_creation_address();
+        }
+    }
+
+    /// Returns true if the transaction can't be included in a block with the given timestamp
+    /// because its expiry timestamp has passed.
+    pub fn is_expired_at(&self, timestamp: u64) -> bool {
+        self.expiry_timestamp
+            .is_some_and(|expiry_timestamp| timestamp > expiry_timestamp)
+    }
+
     pub fn contract_creation_address(&self) -> Address {
         let mut tx = self.clone();
         tx.recipient = Address::from([0u8; Address::SIZE]);
@@ -471,8 +525,14 @@ impl Transaction {
             AccountType::Basic => {}
             AccountType::Vesting => {
                 if let Ok(contract_data) = VestingCreationData::parse(self) {
-                    // Add the owner of the new vesting contract
+                    // Add the owner and the beneficiaries of the new vesting contract
                     addresses.insert(contract_data.owner);
+                    addresses.extend(
+                        contract_data
+                            .beneficiaries
+                            .into_iter()
+                            .map(|beneficiary| beneficiary.address),
+                    );
                 }
             }
             AccountType::HTLC => {
@@ -569,6 +629,24 @@ impl Transaction {
                             // The signer of the internal proof is the staker address
                             addresses.insert(proof.compute_signer());
                         }
+                        IncomingStakingTransactionData::SetRewardCompounding { proof, .. } => {
+                            // The signer of the internal proof is the staker address
+                            addresses.insert(proof.compute_signer());
+                        }
+                        IncomingStakingTransactionData::ScheduleUnstake { proof, .. } => {
+                            // The signer of the internal proof is the staker address
+                            addresses.insert(proof.compute_signer());
+                        }
+                        IncomingStakingTransactionData::ClaimWithdrawals { proof } => {
+                            // The signer of the internal proof is the staker address
+                            addresses.insert(proof.compute_signer());
+                        }
+                        IncomingStakingTransactionData::SetValidatorCommission {
+                            proof, ..
+                        } => {
+                            // The signer of the internal proof is the validator address
+                            addresses.insert(proof.compute_signer());
+                        }
                     }
                 }
             }
@@ -601,6 +679,11 @@ impl SerializeContent for Transaction {
         if self.network_id.is_albatross() {
             self.sender_data.serialize_to_writer(writer)?;
         }
+        // The expiry timestamp is only serialized if present, such that the hashes of
+        // transactions without one are unaffected.
+        if let Some(expiry_timestamp) = self.expiry_timestamp {
+            writer.write_all(&expiry_timestamp.to_be_bytes())?;
+        }
         Ok(())
     }
 }
@@ -616,6 +699,7 @@ impl PartialEq for Transaction {
             && self.validity_start_height == other.validity_start_height
             && self.network_id == other.network_id
             && self.flags == other.flags
+            && self.expiry_timestamp == other.expiry_timestamp
             && self.recipient_data == other.recipient_data
             && self.sender_data == other.sender_data
     }
@@ -638,6 +722,7 @@ impl Ord for Transaction {
             .then_with(|| self.recipient_type.cmp(&other.recipient_type))
             .then_with(|| self.sender_type.cmp(&other.sender_type))
             .then_with(|| self.flags.cmp(&other.flags))
+            .then_with(|| self.expiry_timestamp.cmp(&other.expiry_timestamp))
             .then_with(|| self.recipient_data.len().cmp(&other.recipient_data.len()))
             .then_with(|| self.recipient_data.cmp(&other.recipient_data))
             .then_with(|| self.sender_data.len().cmp(&other.sender_data.len()))
@@ -684,6 +769,7 @@ mod serde_derive {
         "network_id",
         "flags",
         "proof",
+        "expiry_timestamp",
     ];
 
     struct TransactionVisitor;
@@ -761,6 +847,12 @@ mod serde_derive {
                     sv.serialize_field(EXTENDED_FIELDS[9], &self.network_id)?;
                     sv.serialize_field(EXTENDED_FIELDS[10], &self.flags)?;
                     sv.serialize_field(EXTENDED_FIELDS[11], &self.proof)?;
+                    if self.flags.contains(TransactionFlags::EXPIRY_TIMESTAMP) {
+                        let expiry_timestamp = self.expiry_timestamp.ok_or_else(|| {
+                            S::Error::custom("Flagged expiry timestamp is missing")
+                        })?;
+                        sv.serialize_field(EXTENDED_FIELDS[12], &expiry_timestamp.to_be_bytes())?;
+                    }
                     sv.end()
                 }
             }
@@ -877,6 +969,7 @@ mod serde_derive {
                 validity_start_height: u32::from_be_bytes(validity_start_height),
                 network_id,
                 flags: TransactionFlags::empty(),
+                expiry_timestamp: None,
                 proof: SignatureProof::from(public_key, signature, webauthn_fields)
                     .serialize_to_vec(),
                 valid: false,
@@ -950,6 +1043,14 @@ mod serde_derive {
             let proof: Vec<u8> = seq
                 .next_element()?
                 .ok_or_else(|| Error::invalid_length(11, &self))?;
+            let expiry_timestamp = if flags.contains(TransactionFlags::EXPIRY_TIMESTAMP) {
+                let expiry_timestamp: [u8; 8] = seq
+                    .next_element()?
+                    .ok_or_else(|| Error::invalid_length(12, &self))?;
+                Some(u64::from_be_bytes(expiry_timestamp))
+            } else {
+                None
+            };
             Ok(Transaction {
                 sender,
                 sender_type,
@@ -962,6 +1063,7 @@ mod serde_derive {
                 validity_start_height: u32::from_be_bytes(validity_start_height),
                 network_id,
                 flags,
+                expiry_timestamp,
                 proof,
                 valid: false,
             })
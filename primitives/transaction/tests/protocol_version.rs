@@ -0,0 +1,172 @@
+//! Transactions introduced with version 2 of the protocol must be rejected before its activation.
+//! This runs in its own test binary, since it installs a policy that activates version 2 at a
+//! later block than the test policy.
+
+use std::str::FromStr;
+
+use nimiq_hash::{Hasher, Keccak256Hasher};
+use nimiq_keys::{Address, KeyPair, PrivateKey};
+use nimiq_primitives::{
+    account::AccountType,
+    networks::NetworkId,
+    policy::{Policy, TEST_POLICY},
+    transaction::TransactionError,
+};
+use nimiq_serde::Serialize;
+use nimiq_transaction::{
+    account::{
+        htlc_contract::{
+            AnyHash, AnyHash32, CreationTransactionData, OutgoingHTLCTransactionProof, PreImage,
+        },
+        staking_contract::IncomingStakingTransactionData,
+        AccountTransactionVerification,
+    },
+    SignatureProof, Transaction,
+};
+
+const VERSION_2_BLOCK_NUMBER: u32 = 1000;
+
+const STAKER_PRIVATE_KEY: &str = "62f21a296f00562c43999094587d02c0001676ddbd3f0acf9318efbcad0c8b43";
+
+fn initialize_policy() {
+    Policy::init(Policy {
+        version_2_block_number: VERSION_2_BLOCK_NUMBER,
+        ..TEST_POLICY
+    })
+    .unwrap();
+}
+
+fn make_signed_staking_tx(
+    data: IncomingStakingTransactionData,
+    validity_start_height: u32,
+) -> Transaction {
+    let key_pair = KeyPair::from(PrivateKey::from_str(STAKER_PRIVATE_KEY).unwrap());
+    let mut tx = Transaction::new_signaling(
+        Address::from(&key_pair),
+        AccountType::Basic,
+        Policy::STAKING_CONTRACT_ADDRESS,
+        AccountType::Staking,
+        100.try_into().unwrap(),
+        data.serialize_to_vec(),
+        validity_start_height,
+        NetworkId::UnitAlbatross,
+    );
+
+    let proof =
+        SignatureProof::from_ed25519(key_pair.public, key_pair.sign(&tx.serialize_content()));
+    tx.recipient_data =
+        IncomingStakingTransactionData::set_signature_on_data(&tx.recipient_data, proof).unwrap();
+    tx
+}
+
+/// Asserts that the transaction is rejected right before the activation of version 2 and
+/// accepted from its activation on.
+fn assert_rejected_before_version_2(data: IncomingStakingTransactionData) {
+    initialize_policy();
+
+    let tx = make_signed_staking_tx(data.clone(), VERSION_2_BLOCK_NUMBER - 1);
+    assert_eq!(
+        AccountType::verify_incoming_transaction(&tx),
+        Err(TransactionError::InvalidData)
+    );
+
+    let tx = make_signed_staking_tx(data, VERSION_2_BLOCK_NUMBER);
+    assert_eq!(AccountType::verify_incoming_transaction(&tx), Ok(()));
+}
+
+#[test]
+fn set_reward_compounding_requires_version_2() {
+    assert_rejected_before_version_2(IncomingStakingTransactionData::SetRewardCompounding {
+        compound_rewards: true,
+        proof: SignatureProof::default(),
+    });
+}
+
+#[test]
+fn scheduled_unstakes_require_version_2() {
+    assert_rejected_before_version_2(IncomingStakingTransactionData::ScheduleUnstake {
+        value: 1000.try_into().unwrap(),
+        proof: SignatureProof::default(),
+    });
+    assert_rejected_before_version_2(IncomingStakingTransactionData::ClaimWithdrawals {
+        proof: SignatureProof::default(),
+    });
+}
+
+#[test]
+fn keccak_htlcs_require_version_2() {
+    initialize_policy();
+
+    let key_pair = KeyPair::from(PrivateKey::from_str(STAKER_PRIVATE_KEY).unwrap());
+    let address = Address::from(&key_pair);
+    let pre_image = AnyHash32::from([0u8; 32]);
+    let hash_root = AnyHash::from(Keccak256Hasher::default().digest(&pre_image.0));
+
+    // The creation of an HTLC with a Keccak-256 hash root.
+    let data = CreationTransactionData {
+        sender: address.clone(),
+        recipient: address.clone(),
+        hash_root: hash_root.clone(),
+        hash_count: 1,
+        timeout: 1000,
+    };
+    let make_creation_tx = |validity_start_height| {
+        let mut tx = Transaction::new_contract_creation(
+            address.clone(),
+            AccountType::Basic,
+            vec![],
+            AccountType::HTLC,
+            data.serialize_to_vec(),
+            100.try_into().unwrap(),
+            0.try_into().unwrap(),
+            validity_start_height,
+            NetworkId::UnitAlbatross,
+        );
+        tx.recipient = tx.contract_creation_address();
+        tx
+    };
+
+    assert_eq!(
+        AccountType::verify_incoming_transaction(&make_creation_tx(VERSION_2_BLOCK_NUMBER - 1)),
+        Err(TransactionError::InvalidData)
+    );
+    assert_eq!(
+        AccountType::verify_incoming_transaction(&make_creation_tx(VERSION_2_BLOCK_NUMBER)),
+        Ok(())
+    );
+
+    // The redemption of an HTLC with a Keccak-256 hash root.
+    let make_redeem_tx = |validity_start_height| {
+        let mut tx = Transaction::new_contract_creation(
+            address.clone(),
+            AccountType::HTLC,
+            vec![],
+            AccountType::Basic,
+            vec![],
+            100.try_into().unwrap(),
+            0.try_into().unwrap(),
+            validity_start_height,
+            NetworkId::UnitAlbatross,
+        );
+        tx.proof = OutgoingHTLCTransactionProof::RegularTransfer {
+            hash_depth: 1,
+            hash_root: hash_root.clone(),
+            pre_image: PreImage::PreImage32(pre_image.clone()),
+            signature_proof: SignatureProof::from_ed25519(
+                key_pair.public,
+                key_pair.sign(&tx.serialize_content()),
+            ),
+        }
+        .serialize_to_vec();
+        tx
+    };
+
+    assert_eq!(
+        AccountType::verify_outgoing_transaction(&make_redeem_tx(VERSION_2_BLOCK_NUMBER - 1)),
+        Err(TransactionError::InvalidData)
+    );
+    assert_eq!(
+        AccountType::verify_outgoing_transaction(&make_redeem_tx(VERSION_2_BLOCK_NUMBER)),
+        Ok(())
+    );
+}
@@ -1,9 +1,12 @@
 use std::convert::{TryFrom, TryInto};
 
 use log::error;
-use nimiq_hash::Blake2bHasher;
+use nimiq_hash::{Blake2bHash, Blake2bHasher, Hash};
 use nimiq_keys::{Address, ES256PublicKey, ES256Signature, PublicKey, Signature};
-use nimiq_primitives::{account::AccountType, coin::Coin, networks::NetworkId, policy::Policy};
+use nimiq_primitives::{
+    account::AccountType, coin::Coin, networks::NetworkId, policy::Policy,
+    transaction::TransactionError,
+};
 use nimiq_serde::{Deserialize, DeserializeError, Serialize, SerializedMaxSize};
 use nimiq_test_log::test;
 use nimiq_transaction::*;
@@ -50,6 +53,27 @@ fn it_can_serialize_historic_transaction() {
     assert_eq!(hex::encode(v2), EXTENDED_TRANSACTION);
 }
 
+#[test]
+fn it_can_serialize_transaction_with_expiry_timestamp() {
+    let mut t =
+        Transaction::deserialize_from_vec(&hex::decode(EXTENDED_TRANSACTION).unwrap()).unwrap();
+    let hash_without_expiry = t.hash::<Blake2bHash>();
+
+    t.set_expiry_timestamp(Some(1_700_000_000_000));
+    assert!(t.flags.contains(TransactionFlags::EXPIRY_TIMESTAMP));
+    assert_ne!(t.hash::<Blake2bHash>(), hash_without_expiry);
+    assert!(!t.is_expired_at(1_700_000_000_000));
+    assert!(t.is_expired_at(1_700_000_000_001));
+
+    let t2 = Transaction::deserialize_from_vec(&t.serialize_to_vec()).unwrap();
+    assert_eq!(t2.expiry_timestamp, Some(1_700_000_000_000));
+    assert_eq!(t2, t);
+
+    // The expiry timestamp must be flagged.
+    t.flags = TransactionFlags::empty();
+    assert_eq!(t.verify_fields(), Err(TransactionError::InvalidData));
+}
+
 #[test]
 fn it_can_deserialize_basic_transaction() {
     let t = Transaction::deserialize_from_vec(&hex::decode(BASIC_TRANSACTION).unwrap()).unwrap();
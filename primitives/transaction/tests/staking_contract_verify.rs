@@ -465,6 +465,67 @@ fn retire_validator() {
     );
 }
 
+#[test]
+fn set_validator_commission() {
+    let mut rng = test_rng(false);
+    let keypair = ed25519_key_pair(VALIDATOR_PRIVATE_KEY);
+
+    // Works in the valid case.
+    let mut tx = make_signed_incoming_tx(
+        IncomingStakingTransactionData::SetValidatorCommission {
+            new_commission: 1_000,
+            proof: SignatureProof::default(),
+        },
+        0,
+        &keypair,
+        None,
+    );
+
+    assert_eq!(AccountType::verify_incoming_transaction(&tx), Ok(()));
+
+    // Signaling transaction with a non-zero value.
+    tx.value = Coin::from_u64_unchecked(1);
+
+    assert_eq!(
+        AccountType::verify_incoming_transaction(&tx),
+        Err(TransactionError::InvalidValue)
+    );
+
+    // Commission above the maximum.
+    let tx = make_signed_incoming_tx(
+        IncomingStakingTransactionData::SetValidatorCommission {
+            new_commission: Policy::MAX_VALIDATOR_COMMISSION + 1,
+            proof: SignatureProof::default(),
+        },
+        0,
+        &keypair,
+        None,
+    );
+
+    assert_eq!(
+        AccountType::verify_incoming_transaction(&tx),
+        Err(TransactionError::InvalidData)
+    );
+
+    // Invalid signature.
+    let other_pair = KeyPair::generate(&mut rng);
+
+    let tx = make_signed_incoming_tx(
+        IncomingStakingTransactionData::SetValidatorCommission {
+            new_commission: 1_000,
+            proof: SignatureProof::default(),
+        },
+        0,
+        &keypair,
+        Some(other_pair.public),
+    );
+
+    assert_eq!(
+        AccountType::verify_incoming_transaction(&tx),
+        Err(TransactionError::InvalidProof)
+    );
+}
+
 #[test]
 fn create_staker() {
     let mut rng = test_rng(false);
@@ -4,7 +4,10 @@ use nimiq_primitives::{
 };
 use nimiq_serde::{Deserialize, DeserializeError, Serialize};
 use nimiq_transaction::{
-    account::{vesting_contract::CreationTransactionData, AccountTransactionVerification},
+    account::{
+        vesting_contract::{CreationTransactionData, VestingBeneficiary},
+        AccountTransactionVerification,
+    },
     SignatureProof, Transaction, TransactionFlags,
 };
 
@@ -102,6 +105,7 @@ fn it_can_verify_creation_transaction() {
         time_step: 0,
         step_amount: Coin::try_from(1000).unwrap(),
         total_amount: Coin::try_from(100).unwrap(),
+        beneficiaries: vec![],
     };
     transaction.recipient_data = data.to_tx_data();
     transaction.recipient = transaction.contract_creation_address();
@@ -151,3 +155,44 @@ fn it_can_verify_outgoing_transactions() {
         Err(TransactionError::InvalidProof)
     );
 }
+
+#[test]
+fn it_can_parse_creation_data_with_beneficiaries() {
+    let mut data = CreationTransactionData {
+        owner: Address::from([0u8; 20]),
+        start_time: 100,
+        time_step: 10,
+        step_amount: Coin::try_from(10).unwrap(),
+        total_amount: Coin::try_from(100).unwrap(),
+        beneficiaries: vec![
+            VestingBeneficiary {
+                address: Address::from([1u8; 20]),
+                weight: 2,
+            },
+            VestingBeneficiary {
+                address: Address::from([2u8; 20]),
+                weight: 1,
+            },
+        ],
+    };
+
+    let parsed =
+        CreationTransactionData::parse_data(&data.to_tx_data(), Coin::try_from(100).unwrap())
+            .unwrap();
+    assert_eq!(parsed.beneficiaries, data.beneficiaries);
+    assert_eq!(parsed.total_amount, data.total_amount);
+
+    // Beneficiaries must have a positive weight.
+    data.beneficiaries[1].weight = 0;
+    assert_eq!(
+        CreationTransactionData::parse_data(&data.to_tx_data(), Coin::try_from(100).unwrap()).err(),
+        Some(TransactionError::InvalidData)
+    );
+
+    // Beneficiaries must be unique.
+    data.beneficiaries[1] = data.beneficiaries[0].clone();
+    assert_eq!(
+        CreationTransactionData::parse_data(&data.to_tx_data(), Coin::try_from(100).unwrap()).err(),
+        Some(TransactionError::InvalidData)
+    );
+}
@@ -18,7 +18,7 @@ use nimiq_primitives::{
         trie_chunk::{TrieChunk, TrieChunkPushResult, TrieItem},
         trie_diff::{RevertDiffValue, RevertTrieDiff, TrieDiff},
         trie_node::{RootData, TrieNode, TrieNodeKind},
-        trie_proof::TrieProof,
+        trie_proof::{TrieProof, TrieProofBuilder},
         trie_proof_node::TrieProofNode,
     },
 };
@@ -45,6 +45,28 @@ pub struct MerkleRadixTrie<T: TrieTable> {
     table: T,
 }
 
+/// Node counts and storage size of a subtree of the Merkle Radix Trie.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TrieSubtreeStats {
+    pub num_branches: u64,
+    pub num_hybrids: u64,
+    pub num_leaves: u64,
+    /// The approximate number of bytes occupied by the keys and nodes in the database.
+    pub num_bytes: u64,
+}
+
+impl TrieSubtreeStats {
+    fn add_node(&mut self, node: &TrieNode) {
+        match node.kind() {
+            None | Some(TrieNodeKind::Root) => {}
+            Some(TrieNodeKind::Branch) => self.num_branches += 1,
+            Some(TrieNodeKind::Hybrid) => self.num_hybrids += 1,
+            Some(TrieNodeKind::Leaf) => self.num_leaves += 1,
+        }
+        self.num_bytes += (node.key.serialized_size() + node.serialized_size()) as u64;
+    }
+}
+
 /// Counts the number of updates performed.
 #[derive(Default)]
 struct CountUpdates {
@@ -108,6 +130,11 @@ impl<T: TrieTable> MerkleRadixTrie<T> {
 
         db.create_regular_table(&tree.table);
 
+        // A read-only database already contains the root, if any.
+        if db.is_read_only() {
+            return tree;
+        }
+
         let mut txn = db.write_transaction();
         tree.init_root(&mut (&mut txn).into(), incomplete);
         txn.commit();
@@ -233,6 +260,27 @@ impl<T: TrieTable> MerkleRadixTrie<T> {
         self.get_root(txn).unwrap().root_data.unwrap().num_leaves
     }
 
+    /// Returns node counts and byte sizes for every subtree of the Merkle Radix Trie, grouped by
+    /// the first `prefix_len` nibbles of the node keys. Nodes with shorter keys (i.e. the nodes
+    /// above the subtrees) are accounted under their full key.
+    ///
+    /// This scans the whole table, so it should not be called on a hot path.
+    pub fn subtree_stats(
+        &self,
+        txn: &MdbxReadTransaction,
+        prefix_len: usize,
+    ) -> BTreeMap<KeyNibbles, TrieSubtreeStats> {
+        let mut stats: BTreeMap<KeyNibbles, TrieSubtreeStats> = BTreeMap::new();
+
+        for (key, mut node) in txn.cursor(&self.table).into_iter_start() {
+            let prefix = key.slice(0, cmp::min(prefix_len, key.len()));
+            node.key = key;
+            stats.entry(prefix).or_default().add_node(&node);
+        }
+
+        stats
+    }
+
     #[cfg(test)]
     fn count_nodes(&self, txn: &MdbxReadTransaction) -> (u64, u64, u64) {
         let mut num_branches = 0;
@@ -918,7 +966,7 @@ impl<T: TrieTable> MerkleRadixTrie<T> {
 
         // Update the hashes and check that we're good.
         let actual_hash = self
-            .update_hashes(txn, &KeyNibbles::ROOT, &missing_range)
+            .update_hashes(txn, &KeyNibbles::ROOT, &missing_range, false)
             .map_err(|_| MerkleRadixTrieError::ChunkHashMismatch)?;
 
         if actual_hash != expected_hash {
@@ -1152,6 +1200,16 @@ impl<T: TrieTable> MerkleRadixTrie<T> {
         }
     }
 
+    /// Produces a single proof for all keys collected by the given builder. The nodes shared by
+    /// the paths to several keys are only included once. See [`Self::get_proof`].
+    pub fn build_proof(
+        &self,
+        txn: &MdbxReadTransaction,
+        builder: &TrieProofBuilder,
+    ) -> Result<TrieProof, IncompleteTrie> {
+        self.get_proof(txn, builder.keys().collect())
+    }
+
     /// Produces a Merkle proof of the inclusion of the given keys in the
     /// Merkle Radix Trie.
     ///
@@ -1183,8 +1241,14 @@ impl<T: TrieTable> MerkleRadixTrie<T> {
         txn: &MdbxReadTransaction,
         mut keys: Vec<&KeyNibbles>,
     ) -> Result<TrieProof, IncompleteTrie> {
-        // We sort the keys in post-order.
+        // We sort the keys in post-order. Duplicate keys only need to be proven once.
         keys.sort_by(|&k1, &k2| k1.post_order_cmp(k2));
+        keys.dedup();
+
+        // Without any keys, the proof only consists of the root.
+        if keys.is_empty() {
+            keys.push(&KeyNibbles::ROOT);
+        }
 
         let missing_range = self.get_missing_range(txn);
         if let Some(missing) = &missing_range {
@@ -1286,7 +1350,17 @@ impl<T: TrieTable> MerkleRadixTrie<T> {
 
     pub fn update_root(&self, txn: &mut WriteTransactionProxy) -> Result<(), MerkleRadixTrieError> {
         let missing_range = self.get_missing_range(txn);
-        self.update_hashes(txn, &KeyNibbles::ROOT, &missing_range)
+        self.update_hashes(txn, &KeyNibbles::ROOT, &missing_range, false)
+            .map_err(|_| MerkleRadixTrieError::IncompleteTrie)?;
+        Ok(())
+    }
+
+    /// Recomputes the hashes of all nodes from the stored values, discarding the stored hashes.
+    /// This repairs a root hash that is inconsistent with the values in the trie, e.g. because
+    /// the database was corrupted. It walks the whole trie and should only be used for maintenance.
+    pub fn rehash(&self, txn: &mut WriteTransactionProxy) -> Result<(), MerkleRadixTrieError> {
+        let missing_range = self.get_missing_range(txn);
+        self.update_hashes(txn, &KeyNibbles::ROOT, &missing_range, true)
             .map_err(|_| MerkleRadixTrieError::IncompleteTrie)?;
         Ok(())
     }
@@ -1421,12 +1495,14 @@ impl<T: TrieTable> MerkleRadixTrie<T> {
         }
     }
 
-    /// Updates the hashes of all dirty nodes in the subtree specified by `key`.
+    /// Updates the hashes of all dirty nodes in the subtree specified by `key`. If `all` is set,
+    /// the hashes of all nodes are recomputed.
     fn update_hashes(
         &self,
         txn: &mut WriteTransactionProxy,
         key: &KeyNibbles,
         missing_range: &Option<ops::RangeFrom<KeyNibbles>>,
+        all: bool,
     ) -> Result<Blake2bHash, IncompleteTrie> {
         let mut node: TrieNode = self.get_node(txn, key).unwrap();
         if !node.has_children() {
@@ -1436,7 +1512,7 @@ impl<T: TrieTable> MerkleRadixTrie<T> {
         // Compute sub hashes if necessary.
         // Update everything that is possible to be updated and only return the potential error afterwards.
         for child in node.iter_children_mut() {
-            if !child.has_hash() {
+            if all || !child.has_hash() {
                 // We only update the hashes for non-stump children.
                 let child_key = match child.key(key, missing_range) {
                     Ok(key) => key,
@@ -1444,7 +1520,7 @@ impl<T: TrieTable> MerkleRadixTrie<T> {
                     _ => unreachable!(),
                 };
                 // TODO This could be parallelized.
-                if let Ok(hash) = self.update_hashes(txn, &child_key, missing_range) {
+                if let Ok(hash) = self.update_hashes(txn, &child_key, missing_range, all) {
                     child.hash = hash;
                 }
             }
@@ -1763,6 +1839,143 @@ mod tests {
         assert!(proof.verify(&trie.root_hash_assert(&txn)));
     }
 
+    #[test]
+    fn rehash_repairs_corrupted_hashes() {
+        let key_1: KeyNibbles = "cfb986f5a".parse().unwrap();
+        let key_2: KeyNibbles = "cfb986ab9".parse().unwrap();
+        let key_3: KeyNibbles = "413b39931".parse().unwrap();
+
+        let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+        let trie = MerkleRadixTrie::new(&env, TestTrie);
+        let mut raw_txn = env.write_transaction();
+        let mut txn: WriteTransactionProxy = (&mut raw_txn).into();
+
+        trie.put(&mut txn, &key_1, 9u8).expect("complete trie");
+        trie.put(&mut txn, &key_2, 8u8).expect("complete trie");
+        trie.put(&mut txn, &key_3, 7u8).expect("complete trie");
+        trie.update_root(&mut txn).expect("complete trie");
+        let root_hash = trie.root_hash_assert(&txn);
+
+        // Corrupt the hash of a child of the root.
+        let mut root = trie.get_root(&txn).unwrap();
+        root.iter_children_mut().next().unwrap().hash = Blake2bHash::from([1u8; 32]);
+        trie.put_node(&mut txn, &root, OldValue::Unchanged);
+        assert_ne!(trie.root_hash_assert(&txn), root_hash);
+
+        // Updating the root only recomputes missing hashes.
+        trie.update_root(&mut txn).expect("complete trie");
+        assert_ne!(trie.root_hash_assert(&txn), root_hash);
+
+        trie.rehash(&mut txn).expect("complete trie");
+        assert_eq!(trie.root_hash_assert(&txn), root_hash);
+    }
+
+    #[test]
+    fn subtree_stats_work() {
+        let key_1: KeyNibbles = "cfb986f5a".parse().unwrap();
+        let key_2: KeyNibbles = "cfb986ab9".parse().unwrap();
+        let key_3: KeyNibbles = "cfb98e0f6".parse().unwrap();
+        let key_4: KeyNibbles = "413b39931".parse().unwrap();
+
+        let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+        let trie = MerkleRadixTrie::new(&env, TestTrie);
+        let mut raw_txn = env.write_transaction();
+        let mut txn: WriteTransactionProxy = (&mut raw_txn).into();
+
+        trie.put(&mut txn, &key_1, 9u8).expect("complete trie");
+        trie.put(&mut txn, &key_2, 8u8).expect("complete trie");
+        trie.put(&mut txn, &key_3, 7u8).expect("complete trie");
+        trie.put(&mut txn, &key_4, 6u8).expect("complete trie");
+        trie.update_root(&mut txn).expect("complete trie");
+
+        let stats = trie.subtree_stats(&txn, 1);
+        assert_eq!(stats.len(), 3);
+        assert_eq!(stats[&KeyNibbles::ROOT].num_leaves, 0);
+        assert!(stats[&KeyNibbles::ROOT].num_bytes > 0);
+        let stats_c = stats[&"c".parse::<KeyNibbles>().unwrap()];
+        assert_eq!(
+            stats_c,
+            TrieSubtreeStats {
+                num_branches: 2,
+                num_hybrids: 0,
+                num_leaves: 3,
+                ..stats_c
+            }
+        );
+        let stats_4 = stats[&"4".parse::<KeyNibbles>().unwrap()];
+        assert_eq!(
+            stats_4,
+            TrieSubtreeStats {
+                num_branches: 0,
+                num_hybrids: 0,
+                num_leaves: 1,
+                ..stats_4
+            }
+        );
+        let total_bytes: u64 = stats.values().map(|stats| stats.num_bytes).sum();
+        assert_eq!(
+            trie.subtree_stats(&txn, 0)[&KeyNibbles::ROOT].num_bytes,
+            total_bytes
+        );
+    }
+
+    #[test]
+    fn build_proof_works() {
+        //          |
+        //         cfb98
+        //        /     |
+        //       6    e0f6
+        //      /  \
+        //   ab9   f5a
+
+        let key_1: KeyNibbles = "cfb986f5a".parse().unwrap();
+        let key_2: KeyNibbles = "cfb986ab9".parse().unwrap();
+        let key_3: KeyNibbles = "cfb98e0f6".parse().unwrap();
+        let key_4: KeyNibbles = "cfb98e0f5".parse().unwrap();
+
+        let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+        let trie = MerkleRadixTrie::new(&env, TestTrie);
+        let mut raw_txn = env.write_transaction();
+        let mut txn: WriteTransactionProxy = (&mut raw_txn).into();
+
+        trie.put(&mut txn, &key_1, 9u8).expect("complete trie");
+        trie.put(&mut txn, &key_2, 8u8).expect("complete trie");
+        trie.put(&mut txn, &key_3, 7u8).expect("complete trie");
+        trie.update_root(&mut txn).expect("complete trie");
+        let root_hash = trie.root_hash_assert(&txn);
+
+        // Duplicate keys are only proven once and shared nodes are only included once.
+        let builder = TrieProofBuilder::new().with_keys([
+            key_1.clone(),
+            key_2.clone(),
+            key_4.clone(),
+            key_1.clone(),
+            key_4.clone(),
+        ]);
+        assert_eq!(builder.len(), 3);
+
+        let proof = trie.build_proof(&txn, &builder).unwrap();
+        assert_eq!(proof.nodes.len(), 5);
+
+        let values = builder.verify(proof, &root_hash).unwrap();
+        assert_eq!(values.len(), 3);
+        assert_eq!(values[&key_1], Some(vec![9]));
+        assert_eq!(values[&key_2], Some(vec![8]));
+        assert_eq!(values[&key_4], None);
+
+        // A proof for different keys doesn't verify.
+        let proof = trie
+            .build_proof(&txn, &TrieProofBuilder::new().with_keys([key_3]))
+            .unwrap();
+        assert!(builder.verify(proof, &root_hash).is_err());
+
+        // An empty builder proves only the root.
+        let builder = TrieProofBuilder::new();
+        let proof = trie.build_proof(&txn, &builder).unwrap();
+        assert_eq!(proof.nodes.len(), 1);
+        assert!(builder.verify(proof, &root_hash).unwrap().is_empty());
+    }
+
     #[test]
     fn get_proof_values() {
         //          |
@@ -173,6 +173,14 @@ pub enum BlockchainCommand {
         #[clap(short = 'l', long, value_enum)]
         log_types: Vec<LogType>,
     },
+
+    /// Follow the staking contract events associated with the specified addresses.
+    /// If no addresses are provided it fetches all staking contract events.
+    FollowStakingEvents {
+        /// List of all address to follow. If empty it does not filter by address.
+        #[clap(short = 'a', long)]
+        addresses: Vec<Address>,
+    },
 }
 
 #[async_trait]
@@ -371,6 +379,16 @@ impl HandleSubcommand for BlockchainCommand {
                     println!("{blocklog:#?}");
                 }
             }
+            BlockchainCommand::FollowStakingEvents { addresses } => {
+                let mut stream = client
+                    .blockchain
+                    .subscribe_for_staking_events(addresses)
+                    .await?;
+
+                while let Some(event) = stream.next().await {
+                    println!("{event:#?}");
+                }
+            }
         }
         Ok(client)
     }
@@ -17,6 +17,32 @@ pub enum NetworkCommand {
         #[clap(short, long)]
         count: bool,
     },
+
+    /// Returns the round trip times measured to the connected peers.
+    Latencies {},
+
+    /// Starts listening on additional addresses.
+    Listen {
+        /// The addresses to listen on, e.g. `/ip4/0.0.0.0/tcp/8443/wss`.
+        #[clap(required = true)]
+        addresses: Vec<String>,
+    },
+
+    /// Stops listening on the given addresses.
+    StopListening {
+        /// The addresses to stop listening on.
+        #[clap(required = true)]
+        addresses: Vec<String>,
+    },
+
+    /// Reloads the TLS private key and certificates from PEM files on the node.
+    UpdateTls {
+        /// Path to the private key file on the node.
+        private_key: String,
+
+        /// Path to the certificates file on the node.
+        certificates: String,
+    },
 }
 
 #[async_trait]
@@ -33,6 +59,33 @@ impl HandleSubcommand for NetworkCommand {
                     println!("{:#?}", client.network.get_peer_list().await?);
                 }
             }
+            NetworkCommand::Latencies {} => {
+                println!("{:#?}", client.network.get_peer_latencies().await?);
+            }
+            NetworkCommand::Listen { addresses } => {
+                println!(
+                    "{:#?}",
+                    client.network.add_listen_addresses(addresses).await?
+                );
+            }
+            NetworkCommand::StopListening { addresses } => {
+                println!(
+                    "{:#?}",
+                    client.network.remove_listen_addresses(addresses).await?
+                );
+            }
+            NetworkCommand::UpdateTls {
+                private_key,
+                certificates,
+            } => {
+                println!(
+                    "{:#?}",
+                    client
+                        .network
+                        .update_tls_certificates(private_key, certificates)
+                        .await?
+                );
+            }
         }
         Ok(client)
     }
@@ -152,6 +152,62 @@ pub enum TransactionCommand {
         tx_commons: TxCommon,
     },
 
+    /// Sends a `schedule_unstake` transaction to the network. You can pay the transaction fee from a basic
+    /// account (by providing the sender wallet) or from the staker account's balance (by not providing a sender wallet).
+    /// The given amount of active stake is withdrawn from the delegation and can be claimed after the withdrawal delay.
+    ScheduleUnstake {
+        /// The fee will be paid by this wallet if any is provided. In such case the sender wallet must be unlocked prior to this action.
+        /// If absent the fee is paid by the staker's account.
+        #[clap(long)]
+        sender_wallet: Option<Address>,
+
+        /// Destination address for the update. This wallet must be already unlocked.
+        staker_wallet: Address,
+
+        /// The amount of active funds to be withdrawn.
+        value: Coin,
+
+        #[clap(flatten)]
+        tx_commons: TxCommon,
+    },
+
+    /// Sends a `claim_withdrawals` transaction to the network. You can pay the transaction fee from a basic
+    /// account (by providing the sender wallet) or from the staker account's balance (by not providing a sender wallet).
+    /// All released scheduled withdrawals are moved to the retired balance, from where they can be removed.
+    ClaimWithdrawals {
+        /// The fee will be paid by this wallet if any is provided. In such case the sender wallet must be unlocked prior to this action.
+        /// If absent the fee is paid by the staker's account.
+        #[clap(long)]
+        sender_wallet: Option<Address>,
+
+        /// Destination address for the update. This wallet must be already unlocked.
+        staker_wallet: Address,
+
+        #[clap(flatten)]
+        tx_commons: TxCommon,
+    },
+
+    /// Sends a `set_reward_compounding` transaction to the network. You can pay the transaction fee from a basic
+    /// account (by providing the sender wallet) or from the staker account's balance (by not providing a sender wallet).
+    /// If compounding is enabled, the staker's share of its validator's rewards is credited to its active balance.
+    /// Otherwise, it is credited to its retired balance, from where it can be removed.
+    SetRewardCompounding {
+        /// The fee will be paid by this wallet if any is provided. In such case the sender wallet must be unlocked prior to this action.
+        /// If absent the fee is paid by the staker's account.
+        #[clap(long)]
+        sender_wallet: Option<Address>,
+
+        /// Destination address for the update. This wallet must be already unlocked.
+        staker_wallet: Address,
+
+        /// Compound the staker's rewards. If absent, compounding is disabled.
+        #[clap(long)]
+        compound_rewards: bool,
+
+        #[clap(flatten)]
+        tx_commons: TxCommon,
+    },
+
     /// Sends a `remove_stake` transaction to the network. The transaction fee will be paid from the funds
     /// being removed.
     /// This transaction must withdraw the full retired balance, Otherwise it fails.
@@ -348,6 +404,7 @@ impl TransactionCommand {
             HashAlgorithm::Blake2b => Ok(AnyHash::Blake2b(AnyHash32::from_str(&hash_str)?)),
             HashAlgorithm::Sha256 => Ok(AnyHash::Sha256(AnyHash32::from_str(&hash_str)?)),
             HashAlgorithm::Sha512 => Ok(AnyHash::Sha512(AnyHash64::from_str(&hash_str)?)),
+            HashAlgorithm::Keccak256 => Ok(AnyHash::Keccak256(AnyHash32::from_str(&hash_str)?)),
         }
     }
 }
@@ -552,6 +609,99 @@ impl HandleSubcommand for TransactionCommand {
                     println!("{txid:#?}");
                 }
             }
+            TransactionCommand::ScheduleUnstake {
+                sender_wallet,
+                staker_wallet,
+                value,
+                tx_commons,
+            } => {
+                if tx_commons.dry {
+                    let tx = client
+                        .consensus
+                        .create_schedule_unstake_transaction(
+                            sender_wallet,
+                            staker_wallet,
+                            value,
+                            tx_commons.fee,
+                            tx_commons.validity_start_height,
+                        )
+                        .await?;
+                    println!("{tx:#?}");
+                } else {
+                    let txid = client
+                        .consensus
+                        .send_schedule_unstake_transaction(
+                            sender_wallet,
+                            staker_wallet,
+                            value,
+                            tx_commons.fee,
+                            tx_commons.validity_start_height,
+                        )
+                        .await?;
+                    println!("{txid:#?}");
+                }
+            }
+            TransactionCommand::ClaimWithdrawals {
+                sender_wallet,
+                staker_wallet,
+                tx_commons,
+            } => {
+                if tx_commons.dry {
+                    let tx = client
+                        .consensus
+                        .create_claim_withdrawals_transaction(
+                            sender_wallet,
+                            staker_wallet,
+                            tx_commons.fee,
+                            tx_commons.validity_start_height,
+                        )
+                        .await?;
+                    println!("{tx:#?}");
+                } else {
+                    let txid = client
+                        .consensus
+                        .send_claim_withdrawals_transaction(
+                            sender_wallet,
+                            staker_wallet,
+                            tx_commons.fee,
+                            tx_commons.validity_start_height,
+                        )
+                        .await?;
+                    println!("{txid:#?}");
+                }
+            }
+            TransactionCommand::SetRewardCompounding {
+                sender_wallet,
+                staker_wallet,
+                compound_rewards,
+                tx_commons,
+            } => {
+                if tx_commons.dry {
+                    let tx = client
+                        .consensus
+                        .create_set_reward_compounding_transaction(
+                            sender_wallet,
+                            staker_wallet,
+                            compound_rewards,
+                            tx_commons.fee,
+                            tx_commons.validity_start_height,
+                        )
+                        .await?;
+                    println!("{tx:#?}");
+                } else {
+                    let txid = client
+                        .consensus
+                        .send_set_reward_compounding_transaction(
+                            sender_wallet,
+                            staker_wallet,
+                            compound_rewards,
+                            tx_commons.fee,
+                            tx_commons.validity_start_height,
+                        )
+                        .await?;
+                    println!("{txid:#?}");
+                }
+            }
             TransactionCommand::RemoveStake {
                 staker_wallet,
                 recipient,
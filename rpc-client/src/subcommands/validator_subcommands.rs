@@ -85,6 +85,21 @@ pub enum ValidatorCommand {
         tx_commons: TxCommon,
     },
 
+    /// Sends a transaction to set the commission this validator keeps from its rewards. The rest of
+    /// the rewards is shared among its stakers. You need to provide the address of a basic account
+    /// (the sender wallet) to pay the transaction fee and the sender wallet must be unlocked prior
+    /// to this command.
+    SetValidatorCommission {
+        /// The fee will be paid from this address. This wallet must be already unlocked.
+        sender_wallet: Address,
+
+        /// The new commission in basis points, at most 10000.
+        new_commission: u16,
+
+        #[clap(flatten)]
+        tx_commons: TxCommon,
+    },
+
     /// Sends a transaction to deactivate this validator. In order to avoid having the validator reactivated soon after
     /// this transaction takes effect, use the command set-auto-reactivate-validator to make sure the automatic reactivation
     /// configuration is turned off.
@@ -229,6 +244,39 @@ impl HandleSubcommand for ValidatorCommand {
                 }
             }
 
+            ValidatorCommand::SetValidatorCommission {
+                sender_wallet,
+                new_commission,
+                tx_commons,
+            } => {
+                let validator_address = client.validator.get_address().await?.data;
+                if tx_commons.dry {
+                    let tx = client
+                        .consensus
+                        .create_set_validator_commission_transaction(
+                            sender_wallet,
+                            validator_address,
+                            new_commission,
+                            tx_commons.fee,
+                            tx_commons.validity_start_height,
+                        )
+                        .await?;
+                    println!("{tx:#?}");
+                } else {
+                    let txid = client
+                        .consensus
+                        .send_set_validator_commission_transaction(
+                            sender_wallet,
+                            validator_address,
+                            new_commission,
+                            tx_commons.fee,
+                            tx_commons.validity_start_height,
+                        )
+                        .await?;
+                    println!("{txid:#?}");
+                }
+            }
+
             ValidatorCommand::DeactivateValidator {
                 sender_wallet,
                 tx_commons,
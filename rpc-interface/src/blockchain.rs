@@ -4,8 +4,10 @@ use nimiq_hash::Blake2bHash;
 use nimiq_keys::Address;
 
 use crate::types::{
-    Account, Block, BlockLog, BlockchainState, ExecutedTransaction, Inherent, LogType,
-    PenalizedSlots, RPCData, RPCResult, Slot, Staker, Validator,
+    Account, AddressSummary, Block, BlockLog, BlockchainState, ChainStatistics,
+    ExecutedTransaction, Inherent, LogType, PenalizedSlots, RPCData, RPCResult, RandomnessBeacon,
+    RawMacroBlock, RewardPreview, Slot, Staker, StakingEvent, TransactionInclusionProof, Validator,
+    ValidatorEpochPerformance, ValidatorParticipation,
 };
 
 #[nimiq_jsonrpc_derive::proxy(name = "BlockchainProxy", rename_all = "camelCase")]
@@ -90,6 +92,15 @@ pub trait BlockchainInterface {
         batch_number: u32,
     ) -> RPCResult<Vec<Inherent>, (), Self::Error>;
 
+    /// Returns all the inherents (including reward inherents) for the blocks from `start_block`
+    /// to `end_block` (both inclusive), in ascending block order. At most 1000 blocks can be
+    /// requested at once. Note that this only considers blocks in the main chain.
+    async fn get_inherents_by_block_range(
+        &mut self,
+        start_block: u32,
+        end_block: u32,
+    ) -> RPCResult<Vec<Inherent>, (), Self::Error>;
+
     /// Returns the hashes for the latest transactions for a given address. All the transactions
     /// where the given address is listed as a recipient or as a sender are considered. Reward
     /// transactions are also returned. It has an option to specify the maximum number of hashes to
@@ -107,7 +118,7 @@ pub trait BlockchainInterface {
     /// where the given address is listed as a recipient or as a sender are considered. Reward
     /// transactions are also returned. It has an option to specify the maximum number of transactions
     /// to fetch, it defaults to 500. It has also an option to retrieve transactions before a given
-    /// transaction hash (exclusive). If this hash is not found or does not belong to this address, it will return an empty list.
+    /// transaction hash (exclusive). If this hash is not found, it will return an empty list.
     /// The transactions are returned in descending order, meaning the latest transaction is the first.
     async fn get_transactions_by_address(
         &mut self,
@@ -116,6 +127,14 @@ pub trait BlockchainInterface {
         start_at: Option<Blake2bHash>,
     ) -> RPCResult<Vec<ExecutedTransaction>, (), Self::Error>;
 
+    /// Returns a summary of the activity of a given address: the first and last block it was
+    /// involved in, its number of transactions and the total value it received and sent. Reward
+    /// inherents count as received value. Returns `None` if the address has no history.
+    async fn get_address_summary(
+        &mut self,
+        address: Address,
+    ) -> RPCResult<Option<AddressSummary>, (), Self::Error>;
+
     /// Tries to fetch the account at the given address.
     async fn get_account_by_address(
         &mut self,
@@ -156,19 +175,82 @@ pub trait BlockchainInterface {
     async fn get_validators(&mut self) -> RPCResult<Vec<Validator>, BlockchainState, Self::Error>;
 
     /// Fetches all stakers for a given validator.
-    /// IMPORTANT: This operation iterates over all stakers of the staking contract
-    /// and thus is extremely computationally expensive.
+    /// IMPORTANT: This operation iterates over all stakers of the given validator
+    /// and thus can be computationally expensive for validators with many stakers.
     async fn get_stakers_by_validator_address(
         &mut self,
         address: Address,
     ) -> RPCResult<Vec<Staker>, BlockchainState, Self::Error>;
 
+    /// Fetches a page of the stakers for a given validator, ordered by their address. It has an
+    /// option to specify the maximum number of stakers to fetch, it defaults to 500. It has also
+    /// an option to start at a given staker address (inclusive), so the address of the last staker
+    /// of a page can be used to fetch the next one.
+    async fn get_stakers_by_validator_address_paged(
+        &mut self,
+        address: Address,
+        max: Option<u16>,
+        start_at: Option<Address>,
+    ) -> RPCResult<Vec<Staker>, BlockchainState, Self::Error>;
+
     /// Tries to fetch a staker information given its address.
     async fn get_staker_by_address(
         &mut self,
         address: Address,
     ) -> RPCResult<Staker, BlockchainState, Self::Error>;
 
+    /// Returns rolling statistics over the most recent batches, such as the transaction
+    /// throughput, the average block fullness and the skip block rate.
+    async fn get_chain_statistics(
+        &mut self,
+    ) -> RPCResult<ChainStatistics, BlockchainState, Self::Error>;
+
+    /// Returns the micro block production participation of each validator over the most recent
+    /// batches.
+    async fn get_validator_participation(
+        &mut self,
+    ) -> RPCResult<Vec<ValidatorParticipation>, BlockchainState, Self::Error>;
+
+    /// Returns the block production, punishments and rewards of a validator in the given epoch,
+    /// which defaults to the current epoch. The history of the epoch must be available.
+    async fn get_validator_performance(
+        &mut self,
+        validator_address: Address,
+        epoch_number: Option<u32>,
+    ) -> RPCResult<ValidatorEpochPerformance, BlockchainState, Self::Error>;
+
+    /// Returns the expected reward of a validator for the batch that is paid out by the next macro
+    /// block, assuming that block is produced now or on time. The epoch number must be the one
+    /// of the validator slots that are rewarded by that block. Batch delay penalties and the slots
+    /// punished so far are taken into account.
+    async fn get_reward_preview(
+        &mut self,
+        epoch_number: u32,
+        validator_address: Address,
+    ) -> RPCResult<RewardPreview, BlockchainState, Self::Error>;
+
+    /// Returns the serialized macro block at the given block number together with its
+    /// justification.
+    async fn get_raw_macro_block(
+        &mut self,
+        block_number: u32,
+    ) -> RPCResult<RawMacroBlock, (), Self::Error>;
+
+    /// Returns an inclusion proof for a finalized transaction against the history root of the
+    /// macro block finalizing it. The macro block is included in its serialized form, so that the
+    /// proof can be verified starting from the justification of the block.
+    async fn get_transaction_inclusion_proof(
+        &mut self,
+        hash: Blake2bHash,
+    ) -> RPCResult<TransactionInclusionProof, BlockchainState, Self::Error>;
+
+    /// Returns the randomness beacon output of the finalized block at the given block number,
+    /// together with a proof that can be verified against the hash of an election block.
+    async fn get_randomness_beacon(
+        &mut self,
+        block_number: u32,
+    ) -> RPCResult<RandomnessBeacon, BlockchainState, Self::Error>;
+
     /// Subscribes to new block events (retrieves the full block).
     #[stream]
     async fn subscribe_for_head_block(
@@ -198,4 +280,14 @@ pub trait BlockchainInterface {
         addresses: Vec<Address>,
         log_types: Vec<LogType>,
     ) -> Result<BoxStream<'static, RPCData<BlockLog, BlockchainState>>, Self::Error>;
+
+    /// Subscribes to staking contract events (e.g. created stakers, added stake, unstaking,
+    /// deactivated or jailed validators) related to any of the given addresses. If addresses is
+    /// empty, all staking contract events are pushed. Events of reverted blocks are pushed again
+    /// with `reverted` set.
+    #[stream]
+    async fn subscribe_for_staking_events(
+        &mut self,
+        addresses: Vec<Address>,
+    ) -> Result<BoxStream<'static, RPCData<StakingEvent, BlockchainState>>, Self::Error>;
 }
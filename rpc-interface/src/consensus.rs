@@ -1,10 +1,11 @@
 use async_trait::async_trait;
+use futures::stream::BoxStream;
 use nimiq_hash::Blake2bHash;
 use nimiq_keys::Address;
 use nimiq_primitives::coin::Coin;
 use nimiq_transaction::account::htlc_contract::{AnyHash, PreImage};
 
-use crate::types::{RPCResult, Transaction, ValidityStartHeight};
+use crate::types::{RPCData, RPCResult, Transaction, TransactionInclusion, ValidityStartHeight};
 
 #[nimiq_jsonrpc_derive::proxy(name = "ConsensusProxy", rename_all = "camelCase")]
 #[async_trait]
@@ -28,6 +29,15 @@ pub trait ConsensusInterface {
         raw_tx: String,
     ) -> RPCResult<Blake2bHash, (), Self::Error>;
 
+    /// Sends the given serialized transaction to the network and streams its progress: its
+    /// inclusion in a block together with the producing validator, and its finalization. The
+    /// stream ends once the transaction is final or its validity window expired.
+    #[stream]
+    async fn send_raw_transaction_tracked(
+        &mut self,
+        raw_tx: String,
+    ) -> Result<BoxStream<'static, RPCData<TransactionInclusion, ()>>, Self::Error>;
+
     /// Returns a serialized basic transaction.
     async fn create_basic_transaction(
         &mut self,
@@ -358,6 +368,124 @@ pub trait ConsensusInterface {
         validity_start_height: ValidityStartHeight,
     ) -> RPCResult<Blake2bHash, (), Self::Error>;
 
+    /// Returns a serialized `set_reward_compounding` transaction. You can pay the transaction fee from a basic
+    /// account (by providing the sender wallet) or from the staker account's balance (by not
+    /// providing a sender wallet).
+    async fn create_set_reward_compounding_transaction(
+        &mut self,
+        sender_wallet: Option<Address>,
+        staker_wallet: Address,
+        compound_rewards: bool,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<String, (), Self::Error>;
+
+    /// Sends a `set_reward_compounding` transaction to the network. You can pay the transaction fee from a basic
+    /// account (by providing the sender wallet) or from the staker account's balance (by not
+    /// providing a sender wallet).
+    async fn send_set_reward_compounding_transaction(
+        &mut self,
+        sender_wallet: Option<Address>,
+        staker_wallet: Address,
+        compound_rewards: bool,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<Blake2bHash, (), Self::Error>;
+
+    /// Returns a serialized `schedule_unstake` transaction. You can pay the transaction fee from a basic
+    /// account (by providing the sender wallet) or from the staker account's balance (by not
+    /// providing a sender wallet).
+    async fn create_schedule_unstake_transaction(
+        &mut self,
+        sender_wallet: Option<Address>,
+        staker_wallet: Address,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<String, (), Self::Error>;
+
+    /// Sends a `schedule_unstake` transaction to the network. You can pay the transaction fee from a basic
+    /// account (by providing the sender wallet) or from the staker account's balance (by not
+    /// providing a sender wallet).
+    async fn send_schedule_unstake_transaction(
+        &mut self,
+        sender_wallet: Option<Address>,
+        staker_wallet: Address,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<Blake2bHash, (), Self::Error>;
+
+    /// Returns a serialized `set_reward_compounding` transaction. You can pay the transaction fee from a basic
+    /// account (by providing the sender wallet) or from the staker account's balance (by not
+    /// providing a sender wallet).
+    async fn create_set_reward_compounding_transaction(
+        &mut self,
+        sender_wallet: Option<Address>,
+        staker_wallet: Address,
+        compound_rewards: bool,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<String, (), Self::Error>;
+
+    /// Sends a `set_reward_compounding` transaction to the network. You can pay the transaction fee from a basic
+    /// account (by providing the sender wallet) or from the staker account's balance (by not
+    /// providing a sender wallet).
+    async fn send_set_reward_compounding_transaction(
+        &mut self,
+        sender_wallet: Option<Address>,
+        staker_wallet: Address,
+        compound_rewards: bool,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<Blake2bHash, (), Self::Error>;
+
+    /// Returns a serialized `claim_withdrawals` transaction. You can pay the transaction fee from a basic
+    /// account (by providing the sender wallet) or from the staker account's balance (by not
+    /// providing a sender wallet).
+    async fn create_claim_withdrawals_transaction(
+        &mut self,
+        sender_wallet: Option<Address>,
+        staker_wallet: Address,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<String, (), Self::Error>;
+
+    /// Sends a `claim_withdrawals` transaction to the network. You can pay the transaction fee from a basic
+    /// account (by providing the sender wallet) or from the staker account's balance (by not
+    /// providing a sender wallet).
+    async fn send_claim_withdrawals_transaction(
+        &mut self,
+        sender_wallet: Option<Address>,
+        staker_wallet: Address,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<Blake2bHash, (), Self::Error>;
+
+    /// Returns a serialized `set_reward_compounding` transaction. You can pay the transaction fee from a basic
+    /// account (by providing the sender wallet) or from the staker account's balance (by not
+    /// providing a sender wallet).
+    async fn create_set_reward_compounding_transaction(
+        &mut self,
+        sender_wallet: Option<Address>,
+        staker_wallet: Address,
+        compound_rewards: bool,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<String, (), Self::Error>;
+
+    /// Sends a `set_reward_compounding` transaction to the network. You can pay the transaction fee from a basic
+    /// account (by providing the sender wallet) or from the staker account's balance (by not
+    /// providing a sender wallet).
+    async fn send_set_reward_compounding_transaction(
+        &mut self,
+        sender_wallet: Option<Address>,
+        staker_wallet: Address,
+        compound_rewards: bool,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<Blake2bHash, (), Self::Error>;
+
     /// Returns a serialized `remove_stake` transaction. The transaction fee will be paid from the funds
     /// being removed.
     async fn create_remove_stake_transaction(
@@ -518,6 +646,28 @@ pub trait ConsensusInterface {
         validity_start_height: ValidityStartHeight,
     ) -> RPCResult<Blake2bHash, (), Self::Error>;
 
+    /// Returns a serialized `set_validator_commission` transaction. You need to provide the address of a basic
+    /// account (the sender wallet) to pay the transaction fee. The commission is given in basis points.
+    async fn create_set_validator_commission_transaction(
+        &mut self,
+        sender_wallet: Address,
+        validator_wallet: Address,
+        new_commission: u16,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<String, (), Self::Error>;
+
+    /// Sends a `set_validator_commission` transaction to the network. You need to provide the address of a basic
+    /// account (the sender wallet) to pay the transaction fee. The commission is given in basis points.
+    async fn send_set_validator_commission_transaction(
+        &mut self,
+        sender_wallet: Address,
+        validator_wallet: Address,
+        new_commission: u16,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<Blake2bHash, (), Self::Error>;
+
     /// Returns a serialized `delete_validator` transaction. The transaction fee will be paid from the
     /// validator deposit that is being returned.
     /// Note in order for this transaction to be accepted fee + value should be equal to the validator deposit, which is not a fixed value:
@@ -1,6 +1,6 @@
 use async_trait::async_trait;
 
-use crate::types::RPCResult;
+use crate::types::{PeerLatency, RPCResult};
 
 #[nimiq_jsonrpc_derive::proxy(name = "NetworkProxy", rename_all = "camelCase")]
 #[async_trait]
@@ -15,4 +15,28 @@ pub trait NetworkInterface {
 
     /// Returns a list with the IDs of all our peers.
     async fn get_peer_list(&mut self) -> RPCResult<Vec<String>, (), Self::Error>;
+
+    /// Returns the latencies measured to the connected peers. Peers that didn't answer an echo
+    /// request yet are omitted.
+    async fn get_peer_latencies(&mut self) -> RPCResult<Vec<PeerLatency>, (), Self::Error>;
+
+    /// Starts listening on additional addresses without restarting the node.
+    async fn add_listen_addresses(
+        &mut self,
+        addresses: Vec<String>,
+    ) -> RPCResult<(), (), Self::Error>;
+
+    /// Stops listening on the given addresses. Established connections are kept.
+    async fn remove_listen_addresses(
+        &mut self,
+        addresses: Vec<String>,
+    ) -> RPCResult<(), (), Self::Error>;
+
+    /// Reloads the TLS private key and certificates used for secure WebSockets from the given
+    /// PEM files on the node, e.g. to rotate certificates without restarting the node.
+    async fn update_tls_certificates(
+        &mut self,
+        private_key_path: String,
+        certificates_path: String,
+    ) -> RPCResult<(), (), Self::Error>;
 }
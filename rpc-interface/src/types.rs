@@ -8,8 +8,13 @@ use std::{
 };
 
 use clap::ValueEnum;
-use nimiq_account::{BlockLog as BBlockLog, Log, TransactionLog};
+use nimiq_account::{
+    BlockLog as BBlockLog, HTLCDepthRedemption, Log, TransactionLog, VestingContractBeneficiary,
+};
 use nimiq_block::{MicroJustification, MultiSignature};
+use nimiq_blockchain::{
+    reward::RewardPreview as BRewardPreview, AddressSummary as BAddressSummary,
+};
 use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainError};
 use nimiq_blockchain_proxy::BlockchainReadProxy;
 use nimiq_bls::CompressedPublicKey;
@@ -122,6 +127,7 @@ pub enum HashAlgorithm {
     Blake2b = 1,
     Sha256 = 3,
     Sha512 = 4,
+    Keccak256 = 5,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -447,6 +453,213 @@ pub struct PenalizedSlots {
     pub disabled: BitSet,
 }
 
+/// Rolling statistics over the most recent batches of the main chain.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainStatistics {
+    /// The first block included in the statistics.
+    pub first_block_number: u32,
+    /// The last block included in the statistics.
+    pub last_block_number: u32,
+    /// The number of micro blocks included in the statistics (including skip blocks).
+    pub num_micro_blocks: u32,
+    /// The number of skip blocks included in the statistics.
+    pub num_skip_blocks: u32,
+    /// The number of transactions included in the statistics.
+    pub num_transactions: u64,
+    /// The average number of transactions per second.
+    pub transactions_per_second: f64,
+    /// The average size of micro block bodies relative to the maximum body size, between 0 and 1.
+    pub average_block_fullness: f64,
+    /// The fraction of micro blocks that were skip blocks, between 0 and 1.
+    pub skip_block_rate: f64,
+}
+
+/// The participation of a validator in micro block production over the most recent batches.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorParticipation {
+    pub validator: Address,
+    /// The number of micro blocks the validator produced.
+    pub produced_blocks: u32,
+    /// The number of micro blocks of the validator that were replaced by skip blocks.
+    pub missed_blocks: u32,
+    /// The fraction of assigned micro blocks the validator produced, between 0 and 1.
+    pub participation_rate: f64,
+}
+
+/// The performance of a validator during a single epoch.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValidatorEpochPerformance {
+    pub epoch_number: u32,
+    /// The number of slots the validator had in the epoch, if it was elected.
+    pub num_slots: Option<u16>,
+    /// The number of micro blocks the validator produced. This is only available while the
+    /// micro blocks of the epoch are kept in the chain store.
+    pub produced_blocks: Option<u32>,
+    /// The number of micro blocks of the validator that were replaced by skip blocks.
+    pub missed_blocks: u32,
+    /// The number of distinct slots of the validator that were punished.
+    pub num_punished_slots: u16,
+    /// The number of times the validator was jailed.
+    pub num_jails: u32,
+    /// The rewards paid out to the validator during the epoch.
+    pub rewards: Coin,
+}
+
+/// A macro block in its serialized form, as consumed by cross-chain bridge verifiers.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawMacroBlock {
+    pub block_number: u32,
+    pub hash: Blake2bHash,
+    /// The hex-encoded serialized macro header.
+    pub header: String,
+    /// The hex-encoded serialized Tendermint proof. Only the genesis block has no justification.
+    pub justification: Option<String>,
+}
+
+impl RawMacroBlock {
+    pub fn from_macro_block(macro_block: &nimiq_block::MacroBlock) -> Self {
+        RawMacroBlock {
+            block_number: macro_block.block_number(),
+            hash: macro_block.hash(),
+            header: hex::encode(macro_block.header.serialize_to_vec()),
+            justification: macro_block
+                .justification
+                .as_ref()
+                .map(|justification| hex::encode(justification.serialize_to_vec())),
+        }
+    }
+}
+
+/// An inclusion proof of a transaction against the history root of a finalized macro block.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionInclusionProof {
+    pub transaction_hash: Blake2bHash,
+    /// The macro block whose history root the proof has to be verified against.
+    pub block: RawMacroBlock,
+    /// The hex-encoded serialized history tree proof, which includes the historic transaction.
+    pub proof: String,
+}
+
+/// The randomness beacon output of a finalized block together with the proof of its VRF seed.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RandomnessBeacon {
+    pub block_number: u32,
+    pub block_hash: Blake2bHash,
+    pub seed: VrfSeed,
+    /// The randomness derived from the seed, see `VrfSeed::randomness`.
+    pub randomness: Blake2bHash,
+    /// The hash of the election block the proof has to be verified against.
+    pub election_hash: Blake2bHash,
+    /// The hex-encoded serialized randomness proof, which consists of the chain segment from the
+    /// block up to the macro block finalizing it.
+    pub proof: String,
+}
+
+impl RandomnessBeacon {
+    pub fn from_proof(proof: &nimiq_block::RandomnessProof) -> Self {
+        RandomnessBeacon {
+            block_number: proof.block_number(),
+            block_hash: proof.block_hash(),
+            seed: proof.seed().clone(),
+            randomness: proof.seed().randomness(),
+            election_hash: proof.macro_block.header.parent_election_hash.clone(),
+            proof: hex::encode(proof.serialize_to_vec()),
+        }
+    }
+}
+
+/// A summary of the activity of an address in the transaction history.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressSummary {
+    /// The block number of the first transaction involving the address.
+    pub first_seen: u32,
+    /// The block number of the latest transaction involving the address.
+    pub last_seen: u32,
+    /// The number of transactions involving the address.
+    pub tx_count: u64,
+    pub total_received: Coin,
+    /// The total value sent by the address, including fees.
+    pub total_sent: Coin,
+}
+
+impl From<BAddressSummary> for AddressSummary {
+    fn from(summary: BAddressSummary) -> Self {
+        Self {
+            first_seen: summary.first_seen,
+            last_seen: summary.last_seen,
+            tx_count: summary.tx_count,
+            total_received: summary.total_received,
+            total_sent: summary.total_sent,
+        }
+    }
+}
+
+/// Round trip times of the echo requests the node sent to a connected peer.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerLatency {
+    pub peer_id: String,
+    /// The round trip time of the latest echo request in milliseconds.
+    pub last: u64,
+    /// The moving average of the round trip times in milliseconds.
+    pub average: u64,
+    /// The number of echo requests answered by the peer.
+    pub samples: u32,
+}
+
+/// The expected reward of a validator for the batch that is paid out by the next macro block.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RewardPreview {
+    /// The epoch of the validator slots that are rewarded.
+    pub epoch_number: u32,
+    /// The block number of the macro block that pays out the rewards.
+    pub payout_block_number: u32,
+    /// The delay of the batch in milliseconds, assuming the payout block is produced now.
+    pub batch_delay: u64,
+    /// The block reward for the batch after the batch delay penalty, excluding transaction fees.
+    pub block_reward: Coin,
+    pub tx_fees: Coin,
+    /// The reward for a single slot.
+    pub slot_reward: Coin,
+    pub num_slots: u16,
+    /// The number of slots of the validator that are punished and don't receive a reward.
+    pub num_punished_slots: u16,
+    pub reward_address: Address,
+    /// The reward paid to the reward address, without the remainder given to a random slot.
+    pub reward: Coin,
+    /// The part of the reward that is distributed among the validator's compounding stakers.
+    pub stakers_reward: Coin,
+    /// The part of the reward of the validator that is burned instead.
+    pub burned: Coin,
+}
+
+impl From<BRewardPreview> for RewardPreview {
+    fn from(preview: BRewardPreview) -> Self {
+        Self {
+            epoch_number: preview.epoch_number,
+            payout_block_number: preview.payout_block_number,
+            batch_delay: preview.batch_delay,
+            block_reward: preview.block_reward,
+            tx_fees: preview.tx_fees,
+            slot_reward: preview.slot_reward,
+            num_slots: preview.num_slots,
+            num_punished_slots: preview.num_punished_slots,
+            reward_address: preview.reward_address,
+            reward: preview.reward,
+            stakers_reward: preview.stakers_reward,
+            burned: preview.burned,
+        }
+    }
+}
+
 /// An equivocation proof proves that a validator misbehaved.
 ///
 /// This can come in several forms, but e.g. producing two blocks in a single slot or voting twice
@@ -626,6 +839,8 @@ pub struct Transaction {
     pub recipient_data: Vec<u8>,
     pub flags: u8,
     pub validity_start_height: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiry_timestamp: Option<u64>,
     #[serde(with = "crate::serde_helpers::hex")]
     pub proof: Vec<u8>,
     pub network_id: u8,
@@ -676,6 +891,7 @@ impl Transaction {
             sender_data: transaction.sender_data,
             recipient_data: transaction.recipient_data,
             validity_start_height: transaction.validity_start_height,
+            expiry_timestamp: transaction.expiry_timestamp,
             proof: transaction.proof,
             network_id: transaction.network_id as u8,
         }
@@ -710,12 +926,35 @@ impl Transaction {
             recipient_data: vec![],
             flags: 0,
             validity_start_height: block_number,
+            expiry_timestamp: None,
             proof: vec![],
             network_id: network as u8,
         }
     }
 }
 
+/// Progress of a sent transaction towards its finalization in the chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "type")]
+pub enum TransactionInclusion {
+    /// The transaction was included in a block that is not final yet.
+    #[serde(rename_all = "camelCase")]
+    Included {
+        block_number: u32,
+        block_hash: Blake2bHash,
+        /// The validator that produced the block, if known.
+        producer: Option<Address>,
+    },
+    /// The block including the transaction was reverted.
+    #[serde(rename_all = "camelCase")]
+    Reverted { block_number: u32 },
+    /// The block including the transaction was finalized.
+    #[serde(rename_all = "camelCase")]
+    Finalized { block_number: u32 },
+    /// The validity window of the transaction ended without it being finalized.
+    Expired,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase", tag = "type")]
 pub enum Inherent {
@@ -838,6 +1077,9 @@ pub enum AccountAdditionalFields {
         vesting_step_amount: Coin,
         /// The total amount (in Luna) that was provided at contract creation.
         vesting_total_amount: Coin,
+        /// The beneficiaries the released funds are split between. If empty, the owner can
+        /// withdraw all released funds.
+        vesting_beneficiaries: Vec<VestingBeneficiary>,
     },
 
     /// Additional account information for HTLC contracts.
@@ -855,15 +1097,73 @@ pub enum AccountAdditionalFields {
         timeout: u64,
         /// The total amount (in smallest unit) that was provided at the contract creation.
         total_amount: Coin,
+        /// The amount (in smallest unit) that has been redeemed so far.
+        #[serde(default)]
+        redeemed_amount: Coin,
+        /// The smallest hash depth that allows redeeming the amount redeemed so far.
+        #[serde(default)]
+        redeemed_hash_depth: u8,
+        /// What can still be redeemed at each hash depth.
+        #[serde(default)]
+        redemptions: Vec<HtlcRedemption>,
     },
     /// Additional account information for the staking contract.
     #[serde(rename_all = "camelCase")]
     Staking {},
 }
 
+/// How much of an HTLC contract can be redeemed at a specific hash depth.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HtlcRedemption {
+    /// The hash depth of the pre-image that is revealed.
+    pub hash_depth: u8,
+    /// The balance (in smallest unit) that has to remain in the contract at this hash depth.
+    pub min_balance: Coin,
+    /// The amount (in smallest unit) that can still be redeemed at this hash depth.
+    pub claimable: Coin,
+}
+
+impl From<HTLCDepthRedemption> for HtlcRedemption {
+    fn from(redemption: HTLCDepthRedemption) -> Self {
+        HtlcRedemption {
+            hash_depth: redemption.hash_depth,
+            min_balance: redemption.min_balance,
+            claimable: redemption.claimable,
+        }
+    }
+}
+
+/// A beneficiary of a vesting contract.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VestingBeneficiary {
+    /// User friendly address (NQ-address) of the beneficiary.
+    pub address: Address,
+    /// The weight of the beneficiary relative to the total weight of all beneficiaries.
+    pub weight: u16,
+    /// The amount (in Luna) the beneficiary has withdrawn so far, including fees.
+    pub withdrawn: Coin,
+}
+
+impl From<VestingContractBeneficiary> for VestingBeneficiary {
+    fn from(beneficiary: VestingContractBeneficiary) -> Self {
+        VestingBeneficiary {
+            address: beneficiary.address,
+            weight: beneficiary.weight,
+            withdrawn: beneficiary.withdrawn,
+        }
+    }
+}
+
 impl Account {
-    /// Maps an account to the RPC account type
-    pub fn from_account(address: Address, account: nimiq_account::Account) -> Self {
+    /// Maps an account to the RPC account type. The beneficiaries of a vesting contract are
+    /// stored in the data store of the contract and must be passed separately.
+    pub fn from_account(
+        address: Address,
+        account: nimiq_account::Account,
+        vesting_beneficiaries: Vec<VestingContractBeneficiary>,
+    ) -> Self {
         match account {
             nimiq_account::Account::Basic(basic) => Account {
                 address,
@@ -879,12 +1179,19 @@ impl Account {
                     vesting_time_step: vesting.time_step,
                     vesting_step_amount: vesting.step_amount,
                     vesting_total_amount: vesting.total_amount,
+                    vesting_beneficiaries: vesting_beneficiaries
+                        .into_iter()
+                        .map(Into::into)
+                        .collect(),
                 },
             },
             nimiq_account::Account::HTLC(htlc) => Account {
                 address,
                 balance: htlc.balance,
                 account_additional_fields: AccountAdditionalFields::Htlc {
+                    redeemed_amount: htlc.redeemed_amount(),
+                    redeemed_hash_depth: htlc.redeemed_hash_depth(),
+                    redemptions: htlc.redemptions().into_iter().map(Into::into).collect(),
                     sender: htlc.sender,
                     recipient: htlc.recipient,
                     hash_root: htlc.hash_root,
@@ -905,10 +1212,11 @@ impl Account {
     pub fn from_account_with_state(
         address: Address,
         account: nimiq_account::Account,
+        vesting_beneficiaries: Vec<VestingContractBeneficiary>,
         blockchain_state: BlockchainState,
     ) -> RPCData<Self, BlockchainState> {
         RPCData {
-            data: Self::from_account(address, account),
+            data: Self::from_account(address, account, vesting_beneficiaries),
             metadata: blockchain_state,
         }
     }
@@ -931,10 +1239,16 @@ pub struct Staker {
     pub inactive_balance: Coin,
     pub inactive_from: Option<u32>,
     pub retired_balance: Coin,
+    pub compound_rewards: bool,
+    pub scheduled_withdrawals: Vec<ScheduledWithdrawal>,
 }
 
 impl Staker {
-    pub fn from_staker(staker: &nimiq_account::Staker) -> Self {
+    pub fn from_staker(
+        staker: &nimiq_account::Staker,
+        rewards: &nimiq_account::StakerRewards,
+        scheduled_withdrawals: &[nimiq_account::ScheduledWithdrawal],
+    ) -> Self {
         Staker {
             address: staker.address.clone(),
             balance: staker.active_balance,
@@ -942,10 +1256,25 @@ impl Staker {
             inactive_balance: staker.inactive_balance,
             inactive_from: staker.inactive_from,
             retired_balance: staker.retired_balance,
+            compound_rewards: rewards.compound_rewards,
+            scheduled_withdrawals: scheduled_withdrawals
+                .iter()
+                .map(|withdrawal| ScheduledWithdrawal {
+                    value: withdrawal.value,
+                    release_block: withdrawal.release_block,
+                })
+                .collect(),
         }
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledWithdrawal {
+    pub value: Coin,
+    pub release_block: u32,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Validator {
@@ -953,6 +1282,7 @@ pub struct Validator {
     pub signing_key: Ed25519PublicKey,
     pub voting_key: CompressedPublicKey,
     pub reward_address: Address,
+    pub commission: u16,
     pub signal_data: Option<Blake2bHash>,
     pub balance: Coin,
     pub num_stakers: u64,
@@ -962,12 +1292,14 @@ pub struct Validator {
 }
 
 impl Validator {
-    pub fn from_validator(validator: &nimiq_account::Validator) -> Self {
+    /// Creates the RPC representation of the given validator, which charges the given commission.
+    pub fn from_validator(validator: &nimiq_account::Validator, commission: u16) -> Self {
         Validator {
             address: validator.address.clone(),
             signing_key: validator.signing_key,
             voting_key: validator.voting_key.clone(),
             reward_address: validator.reward_address.clone(),
+            commission,
             signal_data: validator.signal_data.clone(),
             balance: validator.total_stake,
             num_stakers: validator.num_stakers,
@@ -1081,10 +1413,12 @@ pub enum LogType {
     HtlcCreate,
     HtlcTimeoutResolve,
     HtlcRegularTransfer,
+    HtlcPartialRedemption,
     HtlcEarlyResolve,
     VestingCreate,
     CreateValidator,
     UpdateValidator,
+    SetValidatorCommission,
     ValidatorFeeDeduction,
     DeactivateValidator,
     ReactivateValidator,
@@ -1096,6 +1430,10 @@ pub enum LogType {
     UpdateStaker,
     SetActiveStake,
     RetireStake,
+    SetRewardCompounding,
+    ScheduleUnstake,
+    ClaimWithdrawals,
+    PayoutStakerReward,
     RemoveStake,
     DeleteStaker,
     StakerFeeDeduction,
@@ -1135,10 +1473,12 @@ impl LogType {
             Log::HTLCCreate { .. } => Self::HtlcCreate,
             Log::HTLCTimeoutResolve { .. } => Self::HtlcTimeoutResolve,
             Log::HTLCRegularTransfer { .. } => Self::HtlcRegularTransfer,
+            Log::HTLCPartialRedemption { .. } => Self::HtlcPartialRedemption,
             Log::HTLCEarlyResolve { .. } => Self::HtlcEarlyResolve,
             Log::VestingCreate { .. } => Self::VestingCreate,
             Log::CreateValidator { .. } => Self::CreateValidator,
             Log::UpdateValidator { .. } => Self::UpdateValidator,
+            Log::SetValidatorCommission { .. } => Self::SetValidatorCommission,
             Log::DeactivateValidator { .. } => Self::DeactivateValidator,
             Log::ReactivateValidator { .. } => Self::ReactivateValidator,
 
@@ -1150,6 +1490,10 @@ impl LogType {
             Log::RemoveStake { .. } => Self::RemoveStake,
             Log::SetActiveStake { .. } => Self::SetActiveStake,
             Log::RetireStake { .. } => Self::RetireStake,
+            Log::SetRewardCompounding { .. } => Self::SetRewardCompounding,
+            Log::ScheduleUnstake { .. } => Self::ScheduleUnstake,
+            Log::ClaimWithdrawals { .. } => Self::ClaimWithdrawals,
+            Log::PayoutStakerReward { .. } => Self::PayoutStakerReward,
             Log::DeleteStaker { .. } => Self::DeleteStaker,
             Log::PayoutReward { .. } => Self::PayoutReward,
             Log::Penalize { .. } => Self::Penalize,
@@ -1162,6 +1506,126 @@ impl LogType {
     }
 }
 
+impl LogType {
+    /// Returns true if logs of this type are emitted by the staking contract, either through
+    /// staking transactions or through the penalize and jail inherents.
+    pub fn is_staking(&self) -> bool {
+        match self {
+            Self::CreateValidator
+            | Self::UpdateValidator
+            | Self::SetValidatorCommission
+            | Self::ValidatorFeeDeduction
+            | Self::DeactivateValidator
+            | Self::ReactivateValidator
+            | Self::RetireValidator
+            | Self::DeleteValidator
+            | Self::CreateStaker
+            | Self::Stake
+            | Self::UpdateStaker
+            | Self::SetActiveStake
+            | Self::RetireStake
+            | Self::SetRewardCompounding
+            | Self::ScheduleUnstake
+            | Self::ClaimWithdrawals
+            | Self::PayoutStakerReward
+            | Self::RemoveStake
+            | Self::DeleteStaker
+            | Self::StakerFeeDeduction
+            | Self::Penalize
+            | Self::JailValidator => true,
+            Self::PayFee
+            | Self::Transfer
+            | Self::HtlcCreate
+            | Self::HtlcTimeoutResolve
+            | Self::HtlcRegularTransfer
+            | Self::HtlcPartialRedemption
+            | Self::HtlcEarlyResolve
+            | Self::VestingCreate
+            | Self::PayoutReward
+            | Self::RevertContract
+            | Self::FailedTransaction => false,
+        }
+    }
+}
+
+/// A single staking contract event, as pushed by the staking events subscription.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakingEvent {
+    /// The staking contract log describing the event.
+    pub event: Log,
+    /// The hash of the transaction that caused the event or `None` if it was caused by an inherent.
+    pub transaction_hash: Option<Blake2bHash>,
+    /// The timestamp of the block. `None` if the block was reverted.
+    pub timestamp: Option<u64>,
+    /// Whether the event was reverted, i.e. its block was removed from the main chain.
+    pub reverted: bool,
+}
+
+/// Extracts the staking contract events from a block log that are related to any of the given
+/// addresses. If addresses is empty, all staking contract events are returned.
+pub fn staking_events_from_block_log(
+    block_log: BBlockLog,
+    addresses: &[Address],
+) -> Vec<RPCData<StakingEvent, BlockchainState>> {
+    let (inherent_logs, tx_logs, block_number, block_hash, timestamp, reverted) = match block_log {
+        BBlockLog::AppliedBlock {
+            inherent_logs,
+            block_hash,
+            block_number,
+            timestamp,
+            tx_logs,
+            total_tx_size: _,
+        } => (
+            inherent_logs,
+            tx_logs,
+            block_number,
+            block_hash,
+            Some(timestamp),
+            false,
+        ),
+        BBlockLog::RevertedBlock {
+            inherent_logs,
+            block_hash,
+            block_number,
+            tx_logs,
+            total_tx_size: _,
+        } => (inherent_logs, tx_logs, block_number, block_hash, None, true),
+    };
+
+    let tx_events = tx_logs.into_iter().flat_map(|tx_log| {
+        let tx_hash = tx_log.tx_hash;
+        tx_log
+            .logs
+            .into_iter()
+            .map(move |log| (log, Some(tx_hash.clone())))
+    });
+    let inherent_events = inherent_logs.into_iter().map(|log| (log, None));
+
+    tx_events
+        .chain(inherent_events)
+        .filter(|(log, _)| {
+            LogType::from_log(log).is_staking()
+                && (addresses.is_empty()
+                    || addresses.iter().any(|addr| log.is_related_to_address(addr)))
+        })
+        .map(|(event, transaction_hash)| {
+            RPCData::new(
+                StakingEvent {
+                    event,
+                    transaction_hash,
+                    timestamp,
+                    reverted,
+                },
+                BlockchainState {
+                    block_number,
+                    block_hash: block_hash.clone(),
+                },
+            )
+        })
+        .collect()
+}
+
 /// Checks if a given log is related to any of the addresses provided and if it is of any of the log types provided.
 /// If no addresses and log_types are provided it will return false.
 /// If the vec of addresses is empty, compares only to the log_types (meaning it will not care about the addresses
@@ -1,7 +1,9 @@
 use async_trait::async_trait;
+use nimiq_hash::Blake2bHash;
 use nimiq_keys::Address;
+use nimiq_primitives::coin::Coin;
 
-use crate::types::RPCResult;
+use crate::types::{RPCResult, ValidityStartHeight};
 
 #[nimiq_jsonrpc_derive::proxy(name = "ValidatorProxy", rename_all = "camelCase")]
 #[async_trait]
@@ -14,6 +16,12 @@ pub trait ValidatorInterface {
     /// Returns our validator signing key.
     async fn get_signing_key(&mut self) -> RPCResult<String, (), Self::Error>;
 
+    /// Returns all available signing keys.
+    async fn get_signing_keys(&mut self) -> RPCResult<Vec<String>, (), Self::Error>;
+
+    // Adds a signing key that will be used when the key expected by the chain changes
+    async fn add_signing_key(&mut self, secret_key: String) -> RPCResult<(), (), Self::Error>;
+
     /// Returns our current validator voting key.
     async fn get_voting_key(&mut self) -> RPCResult<String, (), Self::Error>;
 
@@ -23,6 +31,21 @@ pub trait ValidatorInterface {
     // Adds a voting key that will be used when the key expected by the chain changes
     async fn add_voting_key(&mut self, secret_key: String) -> RPCResult<(), (), Self::Error>;
 
+    /// Rotates our signing and voting keys to the given ones, or to newly generated keys if they
+    /// are omitted. Sends the update validator transaction, which is signed by the unlocked
+    /// wallet of our validator address and paid by our fee key, and returns its hash.
+    /// The new keys are used as soon as the chain expects them, i.e. from the first epoch after
+    /// the transaction is included. They are persisted next to the database before the
+    /// transaction is sent, such that they are loaded again after a restart, and can be
+    /// retrieved with `getSigningKeys` and `getVotingKeys`.
+    async fn rotate_keys(
+        &mut self,
+        new_signing_secret_key: Option<String>,
+        new_voting_secret_key: Option<String>,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<Blake2bHash, (), Self::Error>;
+
     /// Updates the configuration setting to automatically reactivate our validator.
     async fn set_automatic_reactivation(
         &mut self,
@@ -1,5 +1,8 @@
+use nimiq_account::Log;
 use nimiq_hash::Blake2bHash;
-use nimiq_rpc_interface::types::Block;
+use nimiq_keys::Address;
+use nimiq_primitives::coin::Coin;
+use nimiq_rpc_interface::types::{Block, LogType};
 
 #[test]
 fn it_can_deserialize_result_blocks() {
@@ -7,3 +10,31 @@ fn it_can_deserialize_result_blocks() {
     let value = serde_json::from_str(data).unwrap();
     let _result: Result<Block, Blake2bHash> = serde_json::from_value(value).unwrap();
 }
+
+#[test]
+fn it_classifies_staking_log_types() {
+    assert!(LogType::Stake.is_staking());
+    assert!(LogType::CreateValidator.is_staking());
+    assert!(LogType::StakerFeeDeduction.is_staking());
+    assert!(LogType::Penalize.is_staking());
+    assert!(LogType::JailValidator.is_staking());
+
+    assert!(!LogType::Transfer.is_staking());
+    assert!(!LogType::PayoutReward.is_staking());
+    assert!(!LogType::RevertContract.is_staking());
+    assert!(!LogType::FailedTransaction.is_staking());
+
+    let log = Log::PayFee {
+        from: Address::default(),
+        fee: Coin::ZERO,
+    };
+    assert!(!LogType::from_log(&log).is_staking());
+
+    let log = Log::Penalize {
+        validator_address: Address::default(),
+        offense_event_block: 1,
+        slot: 0,
+        newly_deactivated: false,
+    };
+    assert!(LogType::from_log(&log).is_staking());
+}
@@ -0,0 +1,229 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+};
+
+use futures::StreamExt;
+use nimiq_block::Block;
+use nimiq_blockchain::Blockchain;
+use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainEvent};
+use nimiq_keys::Address;
+use nimiq_primitives::policy::Policy;
+use nimiq_rpc_interface::types::{ChainStatistics, ValidatorParticipation};
+use nimiq_serde::Serialize;
+use nimiq_utils::spawn;
+use parking_lot::RwLock;
+
+/// The number of batches the statistics are computed over, including the current batch.
+const NUM_BATCHES: u32 = 10;
+
+/// The data kept for each micro block within the statistics window.
+struct MicroBlockEntry {
+    block_number: u32,
+    timestamp: u64,
+    num_transactions: u64,
+    body_size: u64,
+    is_skip: bool,
+    /// The validator that was supposed to produce the block, if it could be determined.
+    proposer: Option<Address>,
+}
+
+/// Rolling chain statistics over the most recent [`NUM_BATCHES`] batches.
+///
+/// The statistics are updated whenever blocks are added to or reverted from the main chain, so
+/// that requests only need to read the running sums.
+#[derive(Default)]
+pub(crate) struct ChainStatisticsTracker {
+    entries: VecDeque<MicroBlockEntry>,
+    num_skip_blocks: u32,
+    num_transactions: u64,
+    total_body_size: u64,
+    /// Maps validator address -> (produced blocks, missed blocks).
+    participation: HashMap<Address, (u32, u32)>,
+}
+
+impl ChainStatisticsTracker {
+    /// Returns the first batch within the statistics window if the given batch is the current one.
+    fn first_batch_of_window(batch: u32) -> u32 {
+        batch.saturating_sub(NUM_BATCHES - 1).max(1)
+    }
+
+    /// Creates a tracker for the given blockchain, initialized from the most recent blocks of
+    /// the main chain, and spawns a task keeping it up to date.
+    pub(crate) fn spawn(blockchain: Arc<RwLock<Blockchain>>) -> Arc<RwLock<Self>> {
+        let mut tracker = Self::default();
+
+        let blockchain_rg = blockchain.read();
+        let head = blockchain_rg.block_number();
+        let first_batch = Self::first_batch_of_window(Policy::batch_at(head));
+        let first = Policy::first_block_of_batch(first_batch)
+            .expect("batch within the chain")
+            .max(Policy::genesis_block_number() + 1);
+        for block_number in first..=head {
+            if let Ok(block) = blockchain_rg.get_block_at(block_number, true, None) {
+                tracker.push(&blockchain_rg, &block);
+            }
+        }
+        let mut stream = blockchain_rg.notifier_as_stream();
+        drop(blockchain_rg);
+
+        let tracker = Arc::new(RwLock::new(tracker));
+        let weak_tracker = Arc::downgrade(&tracker);
+        spawn(async move {
+            while let Some(event) = stream.next().await {
+                let Some(tracker) = weak_tracker.upgrade() else {
+                    break;
+                };
+
+                let blockchain_rg = blockchain.read();
+                let mut tracker = tracker.write();
+                match event {
+                    BlockchainEvent::Extended(hash) | BlockchainEvent::HistoryAdopted(hash) => {
+                        if let Ok(block) = blockchain_rg.get_block(&hash, true, None) {
+                            tracker.push(&blockchain_rg, &block);
+                        }
+                    }
+                    BlockchainEvent::Rebranched(reverted_blocks, adopted_blocks) => {
+                        if let Some(block_number) = reverted_blocks
+                            .iter()
+                            .map(|(_, block)| block.block_number())
+                            .min()
+                        {
+                            tracker.revert_to(block_number);
+                        }
+                        for (_, block) in &adopted_blocks {
+                            tracker.push(&blockchain_rg, block);
+                        }
+                    }
+                    BlockchainEvent::Stored(_)
+                    | BlockchainEvent::Finalized(_)
+                    | BlockchainEvent::EpochFinalized(_) => {}
+                }
+            }
+        });
+
+        tracker
+    }
+
+    /// Adds a block of the main chain to the statistics and removes the blocks of batches that
+    /// fell out of the window. Macro blocks are ignored.
+    fn push(&mut self, blockchain: &Blockchain, block: &Block) {
+        let Block::Micro(micro_block) = block else {
+            return;
+        };
+
+        let proposer = blockchain
+            .get_proposer_at(block.block_number(), block.vrf_offset(), None)
+            .map(|slot| slot.validator.address)
+            .ok();
+        let entry = MicroBlockEntry {
+            block_number: block.block_number(),
+            timestamp: block.timestamp(),
+            num_transactions: block.num_transactions() as u64,
+            body_size: micro_block
+                .body
+                .as_ref()
+                .map_or(0, |body| body.serialized_size() as u64),
+            is_skip: block.is_skip(),
+            proposer,
+        };
+
+        self.num_skip_blocks += entry.is_skip as u32;
+        self.num_transactions += entry.num_transactions;
+        self.total_body_size += entry.body_size;
+        if let Some(proposer) = &entry.proposer {
+            let (produced, missed) = self.participation.entry(proposer.clone()).or_default();
+            if entry.is_skip {
+                *missed += 1;
+            } else {
+                *produced += 1;
+            }
+        }
+        let first_batch = Self::first_batch_of_window(Policy::batch_at(entry.block_number));
+        self.entries.push_back(entry);
+
+        while self
+            .entries
+            .front()
+            .is_some_and(|entry| Policy::batch_at(entry.block_number) < first_batch)
+        {
+            let entry = self.entries.pop_front().unwrap();
+            self.remove(&entry);
+        }
+    }
+
+    /// Removes all blocks at or after the given block number from the statistics.
+    fn revert_to(&mut self, block_number: u32) {
+        while self
+            .entries
+            .back()
+            .is_some_and(|entry| entry.block_number >= block_number)
+        {
+            let entry = self.entries.pop_back().unwrap();
+            self.remove(&entry);
+        }
+    }
+
+    fn remove(&mut self, entry: &MicroBlockEntry) {
+        self.num_skip_blocks -= entry.is_skip as u32;
+        self.num_transactions -= entry.num_transactions;
+        self.total_body_size -= entry.body_size;
+        if let Some(proposer) = &entry.proposer {
+            if let Some((produced, missed)) = self.participation.get_mut(proposer) {
+                if entry.is_skip {
+                    *missed -= 1;
+                } else {
+                    *produced -= 1;
+                }
+                if *produced == 0 && *missed == 0 {
+                    self.participation.remove(proposer);
+                }
+            }
+        }
+    }
+
+    pub(crate) fn statistics(&self) -> ChainStatistics {
+        let (Some(first), Some(last)) = (self.entries.front(), self.entries.back()) else {
+            return ChainStatistics::default();
+        };
+
+        let num_micro_blocks = self.entries.len() as u32;
+        let num_produced_blocks = num_micro_blocks - self.num_skip_blocks;
+        let duration_ms = last.timestamp.saturating_sub(first.timestamp);
+
+        ChainStatistics {
+            first_block_number: first.block_number,
+            last_block_number: last.block_number,
+            num_micro_blocks,
+            num_skip_blocks: self.num_skip_blocks,
+            num_transactions: self.num_transactions,
+            transactions_per_second: if duration_ms > 0 {
+                self.num_transactions as f64 * 1000.0 / duration_ms as f64
+            } else {
+                0.0
+            },
+            average_block_fullness: if num_produced_blocks > 0 {
+                self.total_body_size as f64
+                    / (num_produced_blocks as f64 * Policy::MAX_SIZE_MICRO_BODY as f64)
+            } else {
+                0.0
+            },
+            skip_block_rate: self.num_skip_blocks as f64 / num_micro_blocks as f64,
+        }
+    }
+
+    pub(crate) fn participation(&self) -> Vec<ValidatorParticipation> {
+        let mut participation: Vec<_> = self
+            .participation
+            .iter()
+            .map(|(validator, (produced, missed))| ValidatorParticipation {
+                validator: validator.clone(),
+                produced_blocks: *produced,
+                missed_blocks: *missed,
+                participation_rate: *produced as f64 / (*produced + *missed) as f64,
+            })
+            .collect();
+        participation.sort_by(|a, b| a.validator.cmp(&b.validator));
+        participation
+    }
+}
@@ -1,31 +1,61 @@
+use std::{collections::BTreeSet, sync::Arc};
+
 use async_trait::async_trait;
-use futures::{future, stream::BoxStream, StreamExt};
-use nimiq_account::{BlockLog as BBlockLog, TransactionLog};
-use nimiq_blockchain::interface::{HistoryIndexInterface, HistoryInterface};
-use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainEvent};
+use futures::{
+    future,
+    stream::{self, BoxStream},
+    StreamExt,
+};
+use nimiq_account::{BlockLog as BBlockLog, TransactionLog, VestingContractBeneficiary};
+use nimiq_blockchain::{
+    interface::{HistoryIndexInterface, HistoryInterface},
+    Blockchain,
+};
+use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainError, BlockchainEvent};
 use nimiq_blockchain_proxy::{BlockchainProxy, BlockchainReadProxy};
+use nimiq_database::mdbx::MdbxReadTransaction;
 use nimiq_hash::Blake2bHash;
 use nimiq_keys::Address;
-use nimiq_primitives::{key_nibbles::KeyNibbles, policy::Policy};
+use nimiq_primitives::{coin::Coin, key_nibbles::KeyNibbles, policy::Policy};
 use nimiq_rpc_interface::{
     blockchain::BlockchainInterface,
     types::{
-        is_of_log_type_and_related_to_addresses, Account, Block, BlockLog, BlockchainState,
-        ExecutedTransaction, Inherent, LogType, PenalizedSlots, RPCData, RPCResult, Slot, Staker,
-        Validator,
+        is_of_log_type_and_related_to_addresses, staking_events_from_block_log, Account,
+        AddressSummary, Block, BlockLog, BlockchainState, ChainStatistics, ExecutedTransaction,
+        Inherent, LogType, PenalizedSlots, RPCData, RPCResult, RandomnessBeacon, RawMacroBlock,
+        RewardPreview, Slot, Staker, StakingEvent, TransactionInclusionProof, Validator,
+        ValidatorEpochPerformance, ValidatorParticipation,
     },
 };
+use nimiq_serde::Serialize;
+use nimiq_transaction::historic_transaction::HistoricTransactionData;
+use parking_lot::RwLock;
 use tokio_stream::wrappers::BroadcastStream;
 
-use crate::error::Error;
+use crate::{chain_statistics::ChainStatisticsTracker, error::Error};
 
+/// The maximum number of blocks that can be requested by `get_inherents_by_block_range` at once.
+const MAX_INHERENTS_BLOCK_RANGE: u32 = 1000;
+
+#[derive(Clone)]
 pub struct BlockchainDispatcher {
     blockchain: BlockchainProxy,
+    /// Only available for full blockchains, since light blockchains don't store block bodies.
+    chain_statistics: Option<Arc<RwLock<ChainStatisticsTracker>>>,
 }
 
 impl BlockchainDispatcher {
     pub fn new(blockchain: BlockchainProxy) -> Self {
-        Self { blockchain }
+        let chain_statistics = match &blockchain {
+            BlockchainProxy::Full(blockchain) => {
+                Some(ChainStatisticsTracker::spawn(Arc::clone(blockchain)))
+            }
+            BlockchainProxy::Light(_) => None,
+        };
+        Self {
+            blockchain,
+            chain_statistics,
+        }
     }
 }
 
@@ -61,9 +91,11 @@ fn get_validator_by_address(
         let validator = staking_contract
             .get_validator(&data_store.read(&db_txn), address)
             .ok_or_else(|| Error::ValidatorNotFound(address.clone()))?;
+        let commission =
+            staking_contract.get_validator_commission(&data_store.read(&db_txn), address);
 
         Ok(RPCData::with_blockchain(
-            Validator::from_validator(&validator),
+            Validator::from_validator(&validator, commission),
             blockchain_proxy,
         ))
     } else {
@@ -71,6 +103,117 @@ fn get_validator_by_address(
     }
 }
 
+/// Returns the beneficiaries of the given account if it is a vesting contract. They are stored in
+/// the data store of the contract.
+fn get_vesting_beneficiaries(
+    blockchain: &Blockchain,
+    db_txn: &MdbxReadTransaction,
+    address: &Address,
+    account: &nimiq_account::Account,
+) -> Vec<VestingContractBeneficiary> {
+    match account {
+        nimiq_account::Account::Vesting(vesting_contract) => {
+            vesting_contract.get_beneficiaries(&blockchain.get_account_store(address).read(db_txn))
+        }
+        _ => vec![],
+    }
+}
+
+/// Computes the performance of a validator in the given epoch from the rewards and punishments in
+/// the history store and, if they are still stored, the micro blocks of the epoch.
+/// This function requires the read lock acquisition prior to its execution.
+fn get_validator_epoch_performance(
+    blockchain: &Blockchain,
+    validator_address: &Address,
+    epoch_number: u32,
+) -> ValidatorEpochPerformance {
+    let num_slots = blockchain
+        .get_validators_for_epoch(epoch_number, None)
+        .ok()
+        .and_then(|validators| {
+            validators
+                .get_validator_by_address(validator_address)
+                .map(|validator| validator.num_slots())
+        });
+
+    let mut performance = ValidatorEpochPerformance {
+        epoch_number,
+        num_slots,
+        produced_blocks: None,
+        missed_blocks: 0,
+        num_punished_slots: 0,
+        num_jails: 0,
+        rewards: Coin::ZERO,
+    };
+
+    let mut punished_slots = BTreeSet::new();
+    for hist_tx in blockchain
+        .history_store
+        .get_epoch_transactions(epoch_number, None)
+    {
+        match hist_tx.data {
+            HistoricTransactionData::Reward(event)
+                if event.validator_address == *validator_address =>
+            {
+                performance.rewards += event.value;
+            }
+            HistoricTransactionData::Penalize(event)
+                if event.validator_address == *validator_address =>
+            {
+                performance.missed_blocks += 1;
+                punished_slots.insert(event.slot);
+            }
+            HistoricTransactionData::Jail(event)
+                if event.validator_address == *validator_address =>
+            {
+                performance.num_jails += 1;
+                punished_slots.extend(event.slots);
+            }
+            _ => {}
+        }
+    }
+    performance.num_punished_slots = punished_slots.len() as u16;
+
+    if num_slots.is_some() {
+        performance.produced_blocks =
+            count_produced_blocks(blockchain, validator_address, epoch_number);
+    }
+
+    performance
+}
+
+/// Counts the micro blocks of the given epoch that were produced by the validator. Only the block
+/// headers are loaded. Returns `None` if the micro blocks of the epoch are no longer stored.
+fn count_produced_blocks(
+    blockchain: &Blockchain,
+    validator_address: &Address,
+    epoch_number: u32,
+) -> Option<u32> {
+    let first_block = Policy::first_block_of(epoch_number)?;
+    let last_block = Policy::election_block_of(epoch_number)?.min(blockchain.block_number());
+
+    let mut produced_blocks = 0;
+    for block_number in first_block..=last_block {
+        if Policy::is_macro_block_at(block_number) {
+            continue;
+        }
+
+        let block = blockchain.get_block_at(block_number, false, None).ok()?;
+        if block.is_skip() {
+            continue;
+        }
+
+        let proposer = blockchain
+            .get_proposer_at(block_number, block.vrf_offset(), None)
+            .ok()?;
+        if proposer.validator.address == *validator_address {
+            produced_blocks += 1;
+        }
+    }
+
+    Some(produced_blocks)
+}
+
 #[nimiq_jsonrpc_derive::service(rename_all = "camelCase")]
 #[async_trait]
 impl BlockchainInterface for BlockchainDispatcher {
@@ -303,6 +446,36 @@ impl BlockchainInterface for BlockchainDispatcher {
         }
     }
 
+    async fn get_inherents_by_block_range(
+        &mut self,
+        start_block: u32,
+        end_block: u32,
+    ) -> RPCResult<Vec<Inherent>, (), Self::Error> {
+        if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            if start_block > end_block {
+                return Err(Error::InvalidArgument("Invalid block range".to_string()));
+            }
+            if end_block - start_block >= MAX_INHERENTS_BLOCK_RANGE {
+                return Err(Error::InvalidArgument(format!(
+                    "At most {MAX_INHERENTS_BLOCK_RANGE} blocks can be requested"
+                )));
+            }
+
+            let inherents: Vec<_> = (start_block..=end_block)
+                .flat_map(|block_number| {
+                    blockchain
+                        .history_store
+                        .get_block_transactions(block_number, None)
+                })
+                .filter_map(Inherent::try_from)
+                .collect();
+
+            Ok(inherents.into())
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
     async fn get_transaction_hashes_by_address(
         &mut self,
         address: Address,
@@ -322,6 +495,24 @@ impl BlockchainInterface for BlockchainDispatcher {
         }
     }
 
+    async fn get_address_summary(
+        &mut self,
+        address: Address,
+    ) -> RPCResult<Option<AddressSummary>, (), Self::Error> {
+        if let BlockchainProxy::Full(blockchain) = &self.blockchain {
+            Ok(blockchain
+                .read()
+                .history_store
+                .history_index()
+                .ok_or(Error::RequiresHistoryIndex)?
+                .get_address_summary(&address, None)
+                .map(AddressSummary::from)
+                .into())
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
     async fn get_transactions_by_address(
         &mut self,
         address: Address,
@@ -329,23 +520,29 @@ impl BlockchainInterface for BlockchainDispatcher {
         start_at: Option<Blake2bHash>,
     ) -> RPCResult<Vec<ExecutedTransaction>, (), Self::Error> {
         if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
-            // Get the transaction hashes for this address.
-            let tx_hashes = blockchain
+            let history_index = blockchain
                 .history_store
                 .history_index()
-                .ok_or(Error::RequiresHistoryIndex)?
-                .get_tx_hashes_by_address(&address, max.unwrap_or(500), start_at, None);
+                .ok_or(Error::RequiresHistoryIndex)?;
+
+            // Continue after the given transaction, if any.
+            let before = match start_at {
+                Some(hash) => match history_index.get_address_tx_cursor(&hash, None) {
+                    Some(cursor) => Some(cursor),
+                    None => return Ok(vec![].into()),
+                },
+                None => None,
+            };
 
             let mut txs = vec![];
 
-            for hash in tx_hashes {
-                // Get all the historic transactions that correspond to this hash.
-                let hist_tx = blockchain
-                    .history_store
-                    .history_index()
-                    .ok_or(Error::RequiresHistoryIndex)?
-                    .get_hist_tx_by_hash(&hash, None)
-                    .ok_or_else(|| Error::TransactionNotFound(hash.clone()))?;
+            for (_, hist_tx) in history_index.get_transactions_by_address(
+                &address,
+                before,
+                max.unwrap_or(500),
+                None,
+            ) {
+                let hash = hist_tx.tx_hash();
 
                 // Convert the historic transaction into a regular transaction. This will also convert
                 // reward inherents.
@@ -354,7 +551,7 @@ impl BlockchainInterface for BlockchainDispatcher {
                         hist_tx,
                         Some(blockchain.block_number()),
                     )
-                    .ok_or_else(|| Error::TransactionNotFound(hash.clone()))?,
+                    .ok_or_else(|| Error::TransactionNotFound(hash.into()))?,
                 )
             }
 
@@ -373,9 +570,16 @@ impl BlockchainInterface for BlockchainDispatcher {
             let account = blockchain
                 .get_account_if_complete(&address)
                 .ok_or(Error::NoConsensus)?;
+            let vesting_beneficiaries = get_vesting_beneficiaries(
+                blockchain,
+                &blockchain.read_transaction(),
+                &address,
+                &account,
+            );
             Ok(Account::from_account_with_state(
                 address,
                 account,
+                vesting_beneficiaries,
                 BlockchainState::new(blockchain.block_number(), blockchain.head_hash()),
             ))
         } else {
@@ -392,8 +596,14 @@ impl BlockchainInterface for BlockchainDispatcher {
             while start.is_some() {
                 let chunk = blockchain.get_accounts_chunk(Some(&db_txn), start.unwrap(), 1000);
                 start = chunk.end_key;
-                for account in chunk.accounts {
-                    accounts.push(Account::from_account(account.0, account.1));
+                for (address, account) in chunk.accounts {
+                    let vesting_beneficiaries =
+                        get_vesting_beneficiaries(blockchain, &db_txn, &address, &account);
+                    accounts.push(Account::from_account(
+                        address,
+                        account,
+                        vesting_beneficiaries,
+                    ));
                 }
             }
             Ok(RPCData::with_blockchain(accounts, &blockchain_proxy))
@@ -515,11 +725,60 @@ impl BlockchainInterface for BlockchainDispatcher {
                 .ok_or(Error::NoConsensus)?;
             let data_store = blockchain.get_staking_contract_store();
             let db_txn = blockchain.read_transaction();
-            let staker =
-                staking_contract.get_stakers_for_validator(&data_store.read(&db_txn), &address);
+            let data_store_read = data_store.read(&db_txn);
+            let stakers = staking_contract.get_stakers_for_validator(&data_store_read, &address);
+
+            Ok(RPCData::with_blockchain(
+                stakers
+                    .iter()
+                    .map(|staker| {
+                        let rewards =
+                            staking_contract.get_staker_rewards(&data_store_read, &staker.address);
+                        let scheduled_withdrawals = staking_contract
+                            .get_scheduled_withdrawals(&data_store_read, &staker.address);
+                        Staker::from_staker(staker, &rewards, &scheduled_withdrawals)
+                    })
+                    .collect(),
+                &blockchain_proxy,
+            ))
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
+    async fn get_stakers_by_validator_address_paged(
+        &mut self,
+        address: Address,
+        max: Option<u16>,
+        start_at: Option<Address>,
+    ) -> RPCResult<Vec<Staker>, BlockchainState, Self::Error> {
+        let blockchain_proxy = self.blockchain.read();
+
+        if let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy {
+            let staking_contract = blockchain
+                .get_staking_contract_if_complete(None)
+                .ok_or(Error::NoConsensus)?;
+            let data_store = blockchain.get_staking_contract_store();
+            let db_txn = blockchain.read_transaction();
+            let data_store_read = data_store.read(&db_txn);
+            let stakers = staking_contract.get_stakers_for_validator_paged(
+                &data_store_read,
+                &address,
+                start_at.as_ref(),
+                max.unwrap_or(500) as usize,
+            );
 
             Ok(RPCData::with_blockchain(
-                staker.iter().map(Staker::from_staker).collect(),
+                stakers
+                    .iter()
+                    .map(|staker| {
+                        let rewards =
+                            staking_contract.get_staker_rewards(&data_store_read, &staker.address);
+                        let scheduled_withdrawals = staking_contract
+                            .get_scheduled_withdrawals(&data_store_read, &staker.address);
+                        Staker::from_staker(staker, &rewards, &scheduled_withdrawals)
+                    })
+                    .collect(),
                 &blockchain_proxy,
             ))
         } else {
@@ -540,10 +799,205 @@ impl BlockchainInterface for BlockchainDispatcher {
             let db_txn = blockchain.read_transaction();
             let staker = staking_contract
                 .get_staker(&data_store.read(&db_txn), &address)
-                .ok_or(Error::StakerNotFound(address))?;
+                .ok_or(Error::StakerNotFound(address.clone()))?;
+            let rewards = staking_contract.get_staker_rewards(&data_store.read(&db_txn), &address);
+            let scheduled_withdrawals =
+                staking_contract.get_scheduled_withdrawals(&data_store.read(&db_txn), &address);
+
+            Ok(RPCData::with_blockchain(
+                Staker::from_staker(&staker, &rewards, &scheduled_withdrawals),
+                &blockchain_proxy,
+            ))
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
+    async fn get_chain_statistics(
+        &mut self,
+    ) -> RPCResult<ChainStatistics, BlockchainState, Self::Error> {
+        let chain_statistics = self
+            .chain_statistics
+            .as_ref()
+            .ok_or(Error::NotSupportedForLightBlockchain)?;
+        let statistics = chain_statistics.read().statistics();
+
+        Ok(RPCData::with_blockchain(
+            statistics,
+            &self.blockchain.read(),
+        ))
+    }
+
+    async fn get_validator_participation(
+        &mut self,
+    ) -> RPCResult<Vec<ValidatorParticipation>, BlockchainState, Self::Error> {
+        let chain_statistics = self
+            .chain_statistics
+            .as_ref()
+            .ok_or(Error::NotSupportedForLightBlockchain)?;
+        let participation = chain_statistics.read().participation();
+
+        Ok(RPCData::with_blockchain(
+            participation,
+            &self.blockchain.read(),
+        ))
+    }
+
+    async fn get_validator_performance(
+        &mut self,
+        validator_address: Address,
+        epoch_number: Option<u32>,
+    ) -> RPCResult<ValidatorEpochPerformance, BlockchainState, Self::Error> {
+        let blockchain_proxy = self.blockchain.read();
+        let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy else {
+            return Err(Error::NotSupportedForLightBlockchain);
+        };
+
+        let current_epoch = Policy::epoch_at(blockchain.block_number());
+        let epoch_number = epoch_number.unwrap_or(current_epoch);
+        if epoch_number == 0 || epoch_number > current_epoch {
+            return Err(Error::InvalidArgument("Invalid epoch number".to_string()));
+        }
+
+        // The rewards and punishments can only be determined if the history is available.
+        let (first_history_block, _) = blockchain.history_store.history_store_range(None);
+        if Policy::epoch_at(first_history_block) > epoch_number {
+            return Err(Error::InvalidArgument(format!(
+                "History of epoch {epoch_number} is not available"
+            )));
+        }
+
+        let performance =
+            get_validator_epoch_performance(blockchain, &validator_address, epoch_number);
+
+        Ok(RPCData::with_blockchain(performance, &blockchain_proxy))
+    }
+
+    async fn get_reward_preview(
+        &mut self,
+        epoch_number: u32,
+        validator_address: Address,
+    ) -> RPCResult<RewardPreview, BlockchainState, Self::Error> {
+        let blockchain_proxy = self.blockchain.read();
+        if let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy {
+            let preview = blockchain.preview_reward(
+                epoch_number,
+                &validator_address,
+                blockchain.time.now(),
+            )?;
+
+            Ok(RPCData::with_blockchain(
+                RewardPreview::from(preview),
+                &blockchain_proxy,
+            ))
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
+    async fn get_raw_macro_block(
+        &mut self,
+        block_number: u32,
+    ) -> RPCResult<RawMacroBlock, (), Self::Error> {
+        if !Policy::is_macro_block_at(block_number) {
+            return Err(Error::InvalidArgument(format!(
+                "Block {block_number} is not a macro block"
+            )));
+        }
+
+        let block = self
+            .blockchain
+            .read()
+            .get_block_at(block_number, false)
+            .map_err(|_| Error::BlockNotFound(block_number))?;
+
+        Ok(RawMacroBlock::from_macro_block(&block.unwrap_macro()).into())
+    }
+
+    async fn get_transaction_inclusion_proof(
+        &mut self,
+        hash: Blake2bHash,
+    ) -> RPCResult<TransactionInclusionProof, BlockchainState, Self::Error> {
+        let blockchain_proxy = self.blockchain.read();
+        if let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy {
+            let history_index = blockchain
+                .history_store
+                .history_index()
+                .ok_or(Error::RequiresHistoryIndex)?;
+            let hist_tx = history_index
+                .get_hist_tx_by_hash(&hash, None)
+                .ok_or_else(|| Error::TransactionNotFound(hash.clone()))?;
+
+            let election_head = blockchain.election_head().block_number();
+            let macro_head = blockchain.macro_head().block_number();
+            let current_head = blockchain.head().block_number();
+
+            // Only finalized transactions can be proven against the history root of a macro block.
+            let proving_block_number = if hist_tx.block_number <= election_head {
+                Policy::election_block_after(hist_tx.block_number)
+            } else if hist_tx.block_number <= macro_head {
+                macro_head
+            } else {
+                return Err(Error::TransactionNotFinalized(hash));
+            };
+
+            let macro_block = blockchain
+                .chain_store
+                .get_block_at(proving_block_number, false, None)
+                .map_err(|_| Error::BlockNotFound(proving_block_number))?
+                .unwrap_macro();
+
+            // A checkpoint block of the current epoch is proven against a prefix of the history
+            // tree, since the tree has grown since then.
+            let verifier_state =
+                if proving_block_number > election_head && proving_block_number < current_head {
+                    let chain_info = blockchain
+                        .chain_store
+                        .get_chain_info(&macro_block.hash(), false, None)
+                        .map_err(|_| Error::BlockNotFound(proving_block_number))?;
+                    Some(chain_info.history_tree_len as usize)
+                } else {
+                    None
+                };
+
+            let proof = history_index
+                .prove(
+                    Policy::epoch_at(proving_block_number),
+                    vec![&hash],
+                    verifier_state,
+                    None,
+                )
+                .ok_or_else(|| Error::TransactionInclusionNotProvable(hash.clone()))?;
+
+            Ok(RPCData::with_blockchain(
+                TransactionInclusionProof {
+                    transaction_hash: hash,
+                    block: RawMacroBlock::from_macro_block(&macro_block),
+                    proof: hex::encode(proof.serialize_to_vec()),
+                },
+                &blockchain_proxy,
+            ))
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
+
+    async fn get_randomness_beacon(
+        &mut self,
+        block_number: u32,
+    ) -> RPCResult<RandomnessBeacon, BlockchainState, Self::Error> {
+        let blockchain_proxy = self.blockchain.read();
+        if let BlockchainReadProxy::Full(ref blockchain) = blockchain_proxy {
+            let proof = match blockchain.get_randomness_proof(block_number) {
+                Ok(proof) => proof,
+                Err(BlockchainError::BlockNotFinalized(_)) => {
+                    return Err(Error::BlockNotFinalized(block_number))
+                }
+                Err(_) => return Err(Error::BlockNotFound(block_number)),
+            };
 
             Ok(RPCData::with_blockchain(
-                Staker::from_staker(&staker),
+                RandomnessBeacon::from_proof(&proof),
                 &blockchain_proxy,
             ))
         } else {
@@ -743,4 +1197,23 @@ impl BlockchainInterface for BlockchainDispatcher {
             Err(Error::NotSupportedForLightBlockchain)
         }
     }
+
+    #[stream]
+    async fn subscribe_for_staking_events(
+        &mut self,
+        addresses: Vec<Address>,
+    ) -> Result<BoxStream<'static, RPCData<StakingEvent, BlockchainState>>, Self::Error> {
+        if let BlockchainReadProxy::Full(blockchain) = self.blockchain.read() {
+            let stream = BroadcastStream::new(blockchain.log_notifier.subscribe());
+
+            Ok(stream
+                .filter_map(|event| future::ready(event.ok()))
+                .flat_map(move |block_log| {
+                    stream::iter(staking_events_from_block_log(block_log, &addresses))
+                })
+                .boxed())
+        } else {
+            Err(Error::NotSupportedForLightBlockchain)
+        }
+    }
 }
@@ -1,17 +1,24 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
 use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_blockchain_proxy::BlockchainReadProxy;
 use nimiq_bls::{KeyPair as BlsKeyPair, SecretKey as BlsSecretKey};
-use nimiq_consensus::ConsensusProxy;
+use nimiq_consensus::{
+    consensus::transaction_inclusion::TransactionInclusion as ConsensusTransactionInclusion,
+    ConsensusProxy,
+};
 use nimiq_hash::{Blake2bHash, Hash};
 use nimiq_keys::{Address, Ed25519PublicKey, KeyPair, PrivateKey};
 use nimiq_network_libp2p::Network;
 use nimiq_primitives::{coin::Coin, networks::NetworkId};
 use nimiq_rpc_interface::{
     consensus::ConsensusInterface,
-    types::{RPCResult, Transaction as RPCTransaction, ValidityStartHeight},
+    types::{
+        RPCData, RPCResult, Transaction as RPCTransaction, TransactionInclusion,
+        ValidityStartHeight,
+    },
 };
 use nimiq_serde::{Deserialize, Serialize};
 use nimiq_transaction::{
@@ -23,12 +30,17 @@ use parking_lot::RwLock;
 
 use crate::{error::Error, wallets::UnlockedWallets};
 
+#[derive(Clone)]
 pub struct ConsensusDispatcher {
     consensus: ConsensusProxy<Network>,
     unlocked_wallets: Option<Arc<RwLock<UnlockedWallets>>>,
 }
 
 impl ConsensusDispatcher {
+    /// Number of peers that need to prove the inclusion of a tracked transaction if our blockchain
+    /// doesn't store block bodies.
+    const TRACKED_TRANSACTION_MIN_PEERS: usize = 1;
+
     pub fn new(
         consensus: ConsensusProxy<Network>,
         unlocked_wallets: Option<Arc<RwLock<UnlockedWallets>>>,
@@ -68,6 +80,27 @@ fn transaction_to_hex_string(transaction: &Transaction) -> String {
     hex::encode(transaction.serialize_to_vec())
 }
 
+fn transaction_inclusion_to_rpc(inclusion: ConsensusTransactionInclusion) -> TransactionInclusion {
+    match inclusion {
+        ConsensusTransactionInclusion::Included {
+            block_number,
+            block_hash,
+            producer,
+        } => TransactionInclusion::Included {
+            block_number,
+            block_hash,
+            producer,
+        },
+        ConsensusTransactionInclusion::Reverted { block_number } => {
+            TransactionInclusion::Reverted { block_number }
+        }
+        ConsensusTransactionInclusion::Finalized { block_number } => {
+            TransactionInclusion::Finalized { block_number }
+        }
+        ConsensusTransactionInclusion::Expired => TransactionInclusion::Expired,
+    }
+}
+
 #[nimiq_jsonrpc_derive::service(rename_all = "camelCase")]
 #[async_trait]
 impl ConsensusInterface for ConsensusDispatcher {
@@ -98,6 +131,24 @@ impl ConsensusInterface for ConsensusDispatcher {
         }
     }
 
+    #[stream]
+    async fn send_raw_transaction_tracked(
+        &mut self,
+        raw_tx: String,
+    ) -> Result<BoxStream<'static, RPCData<TransactionInclusion, ()>>, Self::Error> {
+        let tx = Transaction::deserialize_from_vec(&hex::decode(&raw_tx)?)?;
+
+        let stream = self
+            .consensus
+            .send_transaction_tracked(tx, Self::TRACKED_TRANSACTION_MIN_PEERS)
+            .await
+            .map_err(Error::NetworkError)?;
+
+        Ok(stream
+            .map(|inclusion| transaction_inclusion_to_rpc(inclusion).into())
+            .boxed())
+    }
+
     async fn create_basic_transaction(
         &mut self,
         wallet: Address,
@@ -742,6 +793,232 @@ impl ConsensusInterface for ConsensusDispatcher {
         self.send_raw_transaction(raw_tx).await
     }
 
+    async fn create_set_reward_compounding_transaction(
+        &mut self,
+        sender_wallet: Option<Address>,
+        staker_wallet: Address,
+        compound_rewards: bool,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<String, (), Self::Error> {
+        let sender_key = match sender_wallet {
+            None => None,
+            Some(address) => Some(self.get_wallet_keypair(&address)?),
+        };
+
+        let transaction = TransactionBuilder::new_set_reward_compounding(
+            sender_key.as_ref(),
+            &self.get_wallet_keypair(&staker_wallet)?,
+            compound_rewards,
+            fee,
+            self.validity_start_height(validity_start_height),
+            self.get_network_id(),
+        )?;
+
+        Ok(transaction_to_hex_string(&transaction).into())
+    }
+
+    async fn send_set_reward_compounding_transaction(
+        &mut self,
+        sender_wallet: Option<Address>,
+        staker_wallet: Address,
+        compound_rewards: bool,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<Blake2bHash, (), Self::Error> {
+        let raw_tx = self
+            .create_set_reward_compounding_transaction(
+                sender_wallet,
+                staker_wallet,
+                compound_rewards,
+                fee,
+                validity_start_height,
+            )
+            .await?
+            .data;
+        self.send_raw_transaction(raw_tx).await
+    }
+
+    async fn create_schedule_unstake_transaction(
+        &mut self,
+        sender_wallet: Option<Address>,
+        staker_wallet: Address,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<String, (), Self::Error> {
+        let sender_key = match sender_wallet {
+            None => None,
+            Some(address) => Some(self.get_wallet_keypair(&address)?),
+        };
+
+        let transaction = TransactionBuilder::new_schedule_unstake(
+            sender_key.as_ref(),
+            &self.get_wallet_keypair(&staker_wallet)?,
+            value,
+            fee,
+            self.validity_start_height(validity_start_height),
+            self.get_network_id(),
+        )?;
+
+        Ok(transaction_to_hex_string(&transaction).into())
+    }
+
+    async fn send_schedule_unstake_transaction(
+        &mut self,
+        sender_wallet: Option<Address>,
+        staker_wallet: Address,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<Blake2bHash, (), Self::Error> {
+        let raw_tx = self
+            .create_schedule_unstake_transaction(
+                sender_wallet,
+                staker_wallet,
+                value,
+                fee,
+                validity_start_height,
+            )
+            .await?
+            .data;
+        self.send_raw_transaction(raw_tx).await
+    }
+
+    async fn create_set_reward_compounding_transaction(
+        &mut self,
+        sender_wallet: Option<Address>,
+        staker_wallet: Address,
+        compound_rewards: bool,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<String, (), Self::Error> {
+        let sender_key = match sender_wallet {
+            None => None,
+            Some(address) => Some(self.get_wallet_keypair(&address)?),
+        };
+
+        let transaction = TransactionBuilder::new_set_reward_compounding(
+            sender_key.as_ref(),
+            &self.get_wallet_keypair(&staker_wallet)?,
+            compound_rewards,
+            fee,
+            self.validity_start_height(validity_start_height),
+            self.get_network_id(),
+        )?;
+
+        Ok(transaction_to_hex_string(&transaction).into())
+    }
+
+    async fn send_set_reward_compounding_transaction(
+        &mut self,
+        sender_wallet: Option<Address>,
+        staker_wallet: Address,
+        compound_rewards: bool,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<Blake2bHash, (), Self::Error> {
+        let raw_tx = self
+            .create_set_reward_compounding_transaction(
+                sender_wallet,
+                staker_wallet,
+                compound_rewards,
+                fee,
+                validity_start_height,
+            )
+            .await?
+            .data;
+        self.send_raw_transaction(raw_tx).await
+    }
+
+    async fn create_claim_withdrawals_transaction(
+        &mut self,
+        sender_wallet: Option<Address>,
+        staker_wallet: Address,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<String, (), Self::Error> {
+        let sender_key = match sender_wallet {
+            None => None,
+            Some(address) => Some(self.get_wallet_keypair(&address)?),
+        };
+
+        let transaction = TransactionBuilder::new_claim_withdrawals(
+            sender_key.as_ref(),
+            &self.get_wallet_keypair(&staker_wallet)?,
+            fee,
+            self.validity_start_height(validity_start_height),
+            self.get_network_id(),
+        )?;
+
+        Ok(transaction_to_hex_string(&transaction).into())
+    }
+
+    async fn send_claim_withdrawals_transaction(
+        &mut self,
+        sender_wallet: Option<Address>,
+        staker_wallet: Address,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<Blake2bHash, (), Self::Error> {
+        let raw_tx = self
+            .create_claim_withdrawals_transaction(
+                sender_wallet,
+                staker_wallet,
+                fee,
+                validity_start_height,
+            )
+            .await?
+            .data;
+        self.send_raw_transaction(raw_tx).await
+    }
+
+    async fn create_set_reward_compounding_transaction(
+        &mut self,
+        sender_wallet: Option<Address>,
+        staker_wallet: Address,
+        compound_rewards: bool,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<String, (), Self::Error> {
+        let sender_key = match sender_wallet {
+            None => None,
+            Some(address) => Some(self.get_wallet_keypair(&address)?),
+        };
+
+        let transaction = TransactionBuilder::new_set_reward_compounding(
+            sender_key.as_ref(),
+            &self.get_wallet_keypair(&staker_wallet)?,
+            compound_rewards,
+            fee,
+            self.validity_start_height(validity_start_height),
+            self.get_network_id(),
+        )?;
+
+        Ok(transaction_to_hex_string(&transaction).into())
+    }
+
+    async fn send_set_reward_compounding_transaction(
+        &mut self,
+        sender_wallet: Option<Address>,
+        staker_wallet: Address,
+        compound_rewards: bool,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<Blake2bHash, (), Self::Error> {
+        let raw_tx = self
+            .create_set_reward_compounding_transaction(
+                sender_wallet,
+                staker_wallet,
+                compound_rewards,
+                fee,
+                validity_start_height,
+            )
+            .await?
+            .data;
+        self.send_raw_transaction(raw_tx).await
+    }
+
     async fn create_remove_stake_transaction(
         &mut self,
         staker_wallet: Address,
@@ -1126,6 +1403,62 @@ impl ConsensusInterface for ConsensusDispatcher {
         self.send_raw_transaction(raw_tx).await
     }
 
+    async fn create_set_validator_commission_transaction(
+        &mut self,
+        sender_wallet: Address,
+        validator_wallet: Address,
+        new_commission: u16,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<String, (), Self::Error> {
+        let transaction = TransactionBuilder::new_set_validator_commission(
+            &self.get_wallet_keypair(&sender_wallet)?,
+            &self.get_wallet_keypair(&validator_wallet)?,
+            new_commission,
+            fee,
+            self.validity_start_height(validity_start_height),
+            self.get_network_id(),
+        );
+
+        Ok(transaction_to_hex_string(&transaction).into())
+    }
+
+    async fn send_set_validator_commission_transaction(
+        &mut self,
+        sender_wallet: Address,
+        validator_wallet: Address,
+        new_commission: u16,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<Blake2bHash, (), Self::Error> {
+        // If the node is in the position of having a full state, it can check upfront if this transaction makes sense
+        if let BlockchainReadProxy::Full(blockchain) = self.consensus.blockchain.read() {
+            let staking_contract = blockchain
+                .get_staking_contract_if_complete(None)
+                .ok_or(Error::NoConsensus)?;
+            let data_store = blockchain.get_staking_contract_store();
+            let db_txn = blockchain.read_transaction();
+            if staking_contract
+                .get_validator(&data_store.read(&db_txn), &validator_wallet)
+                .is_none()
+            {
+                return Err(Error::ValidatorNotFound(validator_wallet.clone()));
+            }
+        }
+
+        let raw_tx = self
+            .create_set_validator_commission_transaction(
+                sender_wallet,
+                validator_wallet,
+                new_commission,
+                fee,
+                validity_start_height,
+            )
+            .await?
+            .data;
+        self.send_raw_transaction(raw_tx).await
+    }
+
     async fn create_delete_validator_transaction(
         &mut self,
         validator_wallet: Address,
@@ -15,6 +15,7 @@ use nimiq_transaction::Transaction;
 use crate::error::Error;
 
 #[allow(dead_code)]
+#[derive(Clone)]
 pub struct MempoolDispatcher {
     consensus: ConsensusProxy<Network>,
     mempool: Arc<Mempool>,
@@ -2,11 +2,15 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use nimiq_network_interface::network::Network as InterfaceNetwork;
-use nimiq_network_libp2p::Network;
-use nimiq_rpc_interface::{network::NetworkInterface, types::RPCResult};
+use nimiq_network_libp2p::{libp2p::Multiaddr, Network, TlsConfig};
+use nimiq_rpc_interface::{
+    network::NetworkInterface,
+    types::{PeerLatency, RPCResult},
+};
 
 use crate::error::Error;
 
+#[derive(Clone)]
 pub struct NetworkDispatcher {
     network: Arc<Network>,
 }
@@ -39,4 +43,60 @@ impl NetworkInterface for NetworkDispatcher {
             .collect::<Vec<_>>()
             .into())
     }
+
+    async fn get_peer_latencies(&mut self) -> RPCResult<Vec<PeerLatency>, (), Self::Error> {
+        Ok(self
+            .network
+            .peer_latencies()
+            .into_iter()
+            .map(|(peer_id, latency)| PeerLatency {
+                peer_id: peer_id.to_string(),
+                last: latency.last.as_millis() as u64,
+                average: latency.average.as_millis() as u64,
+                samples: latency.samples,
+            })
+            .collect::<Vec<_>>()
+            .into())
+    }
+
+    async fn add_listen_addresses(
+        &mut self,
+        addresses: Vec<String>,
+    ) -> RPCResult<(), (), Self::Error> {
+        self.network
+            .add_listen_addresses(parse_addresses(addresses)?)
+            .await?;
+        Ok(().into())
+    }
+
+    async fn remove_listen_addresses(
+        &mut self,
+        addresses: Vec<String>,
+    ) -> RPCResult<(), (), Self::Error> {
+        self.network
+            .remove_listen_addresses(parse_addresses(addresses)?)
+            .await?;
+        Ok(().into())
+    }
+
+    async fn update_tls_certificates(
+        &mut self,
+        private_key_path: String,
+        certificates_path: String,
+    ) -> RPCResult<(), (), Self::Error> {
+        let tls = TlsConfig::from_pem_files(private_key_path, certificates_path)?;
+        self.network.update_tls_config(tls).await?;
+        Ok(().into())
+    }
+}
+
+fn parse_addresses(addresses: Vec<String>) -> Result<Vec<Multiaddr>, Error> {
+    addresses
+        .into_iter()
+        .map(|address| {
+            address
+                .parse()
+                .map_err(|_| Error::InvalidArgument(format!("Invalid address: {address}")))
+        })
+        .collect()
 }
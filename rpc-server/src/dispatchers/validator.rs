@@ -1,28 +1,55 @@
-use std::sync::atomic::Ordering;
+use std::sync::{atomic::Ordering, Arc};
 
 use async_trait::async_trait;
+use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_bls::{KeyPair as BlsKeyPair, SecretKey as BlsSecretKey};
 use nimiq_consensus::ConsensusProxy;
-use nimiq_keys::Address;
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_keys::{Address, KeyPair, PrivateKey, SecureGenerate};
 use nimiq_network_libp2p::Network;
-use nimiq_rpc_interface::{types::RPCResult, validator::ValidatorInterface};
+use nimiq_primitives::coin::Coin;
+use nimiq_rpc_interface::{
+    types::{RPCResult, ValidityStartHeight},
+    validator::ValidatorInterface,
+};
 use nimiq_serde::{Deserialize, Serialize};
 use nimiq_validator::validator::ValidatorProxy;
+use parking_lot::RwLock;
 
-use crate::error::Error;
+use crate::{error::Error, wallets::UnlockedWallets};
 
+#[derive(Clone)]
 pub struct ValidatorDispatcher {
     validator: ValidatorProxy,
     consensus: ConsensusProxy<Network>,
+    unlocked_wallets: Option<Arc<RwLock<UnlockedWallets>>>,
 }
 
 impl ValidatorDispatcher {
-    pub fn new(validator: ValidatorProxy, consensus: ConsensusProxy<Network>) -> Self {
+    pub fn new(
+        validator: ValidatorProxy,
+        consensus: ConsensusProxy<Network>,
+        unlocked_wallets: Option<Arc<RwLock<UnlockedWallets>>>,
+    ) -> Self {
         ValidatorDispatcher {
             validator,
             consensus,
+            unlocked_wallets,
         }
     }
+
+    /// Tries to fetch the key pair for the wallet with the given address.
+    fn get_wallet_keypair(&self, address: &Address) -> Result<KeyPair, Error> {
+        Ok(self
+            .unlocked_wallets
+            .as_ref()
+            .ok_or_else(|| Error::UnlockedWalletNotFound(address.clone()))?
+            .read()
+            .get(address)
+            .ok_or_else(|| Error::UnlockedWalletNotFound(address.clone()))?
+            .key_pair
+            .clone())
+    }
 }
 
 #[nimiq_jsonrpc_derive::service(rename_all = "camelCase")]
@@ -35,7 +62,34 @@ impl ValidatorInterface for ValidatorDispatcher {
     }
 
     async fn get_signing_key(&mut self) -> RPCResult<String, (), Self::Error> {
-        Ok(hex::encode(self.validator.signing_key.read().private.serialize_to_vec()).into())
+        Ok(hex::encode(
+            self.validator
+                .signing_keys
+                .read()
+                .get_current_key()
+                .private
+                .serialize_to_vec(),
+        )
+        .into())
+    }
+
+    async fn get_signing_keys(&mut self) -> RPCResult<Vec<String>, (), Self::Error> {
+        Ok(self
+            .validator
+            .signing_keys
+            .read()
+            .get_keys()
+            .into_iter()
+            .map(|key| hex::encode(key.private.serialize_to_vec()))
+            .collect::<Vec<String>>()
+            .into())
+    }
+
+    async fn add_signing_key(&mut self, secret_key: String) -> RPCResult<(), (), Self::Error> {
+        self.validator.signing_keys.write().add_key(KeyPair::from(
+            PrivateKey::deserialize_from_vec(&hex::decode(secret_key)?)?,
+        ));
+        Ok(().into())
     }
 
     async fn get_voting_key(&mut self) -> RPCResult<String, (), Self::Error> {
@@ -69,6 +123,61 @@ impl ValidatorInterface for ValidatorDispatcher {
         Ok(().into())
     }
 
+    async fn rotate_keys(
+        &mut self,
+        new_signing_secret_key: Option<String>,
+        new_voting_secret_key: Option<String>,
+        fee: Coin,
+        validity_start_height: ValidityStartHeight,
+    ) -> RPCResult<Blake2bHash, (), Self::Error> {
+        let new_signing_key = match new_signing_secret_key {
+            Some(secret_key) => KeyPair::from(
+                PrivateKey::deserialize_from_vec(&hex::decode(secret_key)?)
+                    .map_err(|_| Error::InvalidArgument("Signing Key".to_string()))?,
+            ),
+            None => KeyPair::generate_default_csprng(),
+        };
+        let new_voting_key = match new_voting_secret_key {
+            Some(secret_key) => BlsKeyPair::from(
+                BlsSecretKey::deserialize_from_vec(&hex::decode(secret_key)?)
+                    .map_err(|_| Error::InvalidArgument("Voting Key".to_string()))?,
+            ),
+            None => BlsKeyPair::generate_default_csprng(),
+        };
+
+        let validator_address = self.validator.validator_address.read().clone();
+        let cold_key = self.get_wallet_keypair(&validator_address)?;
+
+        let (validity_start_height, network_id) = {
+            let blockchain = self.consensus.blockchain.read();
+            (
+                validity_start_height.block_number(blockchain.block_number()),
+                blockchain.network_id(),
+            )
+        };
+
+        let transaction = self.validator.rotate_keys(
+            &cold_key,
+            &new_signing_key,
+            &new_voting_key,
+            fee,
+            validity_start_height,
+            network_id,
+        )?;
+        let txid = transaction.hash::<Blake2bHash>();
+
+        self.consensus
+            .send_transaction(transaction)
+            .await
+            .map_err(Error::NetworkError)?;
+
+        // Only use the new keys once the transaction announcing them was sent.
+        self.validator.add_keys(new_signing_key, new_voting_key);
+
+        log::info!(%txid, "Sent transaction to rotate the validator keys");
+        Ok(txid.into())
+    }
+
     async fn set_automatic_reactivation(
         &mut self,
         automatic_reactivate: bool,
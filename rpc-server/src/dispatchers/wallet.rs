@@ -22,6 +22,7 @@ fn message_from_maybe_hex(s: String, is_hex: bool) -> Result<Vec<u8>, Error> {
     }
 }
 
+#[derive(Clone)]
 pub struct WalletDispatcher {
     wallet_store: Arc<WalletStore>,
     pub unlocked_wallets: Arc<RwLock<UnlockedWallets>>,
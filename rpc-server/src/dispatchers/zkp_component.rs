@@ -8,6 +8,7 @@ use nimiq_zkp_component::zkp_component::ZKPComponentProxy;
 
 use crate::error::Error;
 
+#[derive(Clone)]
 pub struct ZKPComponentDispatcher {
     zkp_component: ZKPComponentProxy<Network>,
 }
@@ -21,6 +21,9 @@ pub enum Error {
     #[error("Block not found: {0}")]
     BlockNotFoundByHash(Blake2bHash),
 
+    #[error("Block not finalized yet: {0}")]
+    BlockNotFinalized(u32),
+
     #[error("Block number cannot be smaller than genesis block")]
     BlockNumberBeforeGenesis,
 
@@ -78,12 +81,24 @@ pub enum Error {
     #[error("Transaction not found: {0}")]
     TransactionNotFound(Blake2bHash),
 
+    #[error("Transaction not finalized yet: {0}")]
+    TransactionNotFinalized(Blake2bHash),
+
+    #[error("Could not prove the inclusion of transaction: {0}")]
+    TransactionInclusionNotProvable(Blake2bHash),
+
     #[error("Multiple transactions found: {0}")]
     MultipleTransactionsFound(Blake2bHash),
 
     #[error("Invalid argument: {0}")]
     InvalidArgument(String),
 
+    #[error("{0}")]
+    RewardPreview(#[from] nimiq_blockchain::reward::RewardPreviewError),
+
+    #[error("Rate limit exceeded for method: {0}")]
+    RateLimitExceeded(String),
+
     #[error("No consensus")]
     NoConsensus,
 
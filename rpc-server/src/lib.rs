@@ -1,6 +1,10 @@
 pub use error::Error;
-pub use nimiq_jsonrpc_server::{Config, Server};
+pub use server::{Config, Credentials, Server};
 
 pub mod dispatchers;
 pub mod error;
+pub mod rate_limit;
 pub mod wallets;
+
+mod chain_statistics;
+mod server;
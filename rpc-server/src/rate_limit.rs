@@ -0,0 +1,158 @@
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv6Addr},
+    sync::Arc,
+    time::Duration,
+};
+
+use nimiq_time::{now, Instant, RateLimiter};
+use parking_lot::Mutex;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// The period over which the per-method request limits are counted.
+const RATE_LIMIT_PERIOD: Duration = Duration::from_secs(60);
+
+/// The maximum number of clients whose limits are tracked. Once reached, the least recently seen
+/// client without requests in flight is forgotten to make room for a new one.
+const MAX_CLIENTS: usize = 100_000;
+
+/// The limits the RPC server enforces for each client IP address.
+#[derive(Clone, Debug)]
+pub struct RateLimits {
+    /// The maximum number of requests per minute for each of the listed methods. Methods without
+    /// a limit are not restricted. The limits must be positive.
+    pub methods: HashMap<String, u32>,
+    /// The maximum number of requests in a batch.
+    pub max_batch_size: usize,
+    /// The maximum number of HTTP requests of a client that are processed at the same time.
+    pub max_concurrent_requests: usize,
+}
+
+/// The limiters of a single client.
+struct Client {
+    limiters: HashMap<String, RateLimiter>,
+    concurrency: Arc<Semaphore>,
+    last_seen: Instant,
+}
+
+struct Clients {
+    /// The clients by their key, see [`client_key`].
+    clients: HashMap<IpAddr, Client>,
+    last_prune: Instant,
+}
+
+/// Enforces the [`RateLimits`] separately for each client IP address.
+pub struct RateLimiting {
+    limits: RateLimits,
+    clients: Mutex<Clients>,
+}
+
+impl RateLimiting {
+    pub fn new(limits: RateLimits) -> Self {
+        Self {
+            limits,
+            clients: Mutex::new(Clients {
+                clients: HashMap::new(),
+                last_prune: now(),
+            }),
+        }
+    }
+
+    /// The maximum number of requests in a batch.
+    pub fn max_batch_size(&self) -> usize {
+        self.limits.max_batch_size
+    }
+
+    /// Reserves a slot for processing an HTTP request of the given client. Returns `None` if the
+    /// client already has the maximum number of requests in flight, or if the maximum number of
+    /// clients is tracked and all of them have requests in flight. The slot is released when the
+    /// returned permit is dropped.
+    pub fn try_acquire_slot(&self, ip: IpAddr) -> Option<OwnedSemaphorePermit> {
+        let mut clients = self.clients.lock();
+        let client = self.client(&mut clients, ip)?;
+        Arc::clone(&client.concurrency).try_acquire_owned().ok()
+    }
+
+    /// Consumes the given number of requests of each method from the client's limits. Either all
+    /// of the requests are allowed and consumed or none of them, in which case a method whose
+    /// limit would be exceeded is returned.
+    pub fn try_acquire<'m>(
+        &self,
+        ip: IpAddr,
+        requests: &HashMap<&'m str, u32>,
+    ) -> Result<(), &'m str> {
+        let mut clients = self.clients.lock();
+        let Some(client) = self.client(&mut clients, ip) else {
+            return Err(requests.keys().next().copied().unwrap_or_default());
+        };
+
+        for (&method, &count) in requests {
+            let Some(&requests_per_minute) = self.limits.methods.get(method) else {
+                continue;
+            };
+            let limiter = client
+                .limiters
+                .entry(method.to_owned())
+                .or_insert_with(|| RateLimiter::new(requests_per_minute, RATE_LIMIT_PERIOD));
+            if limiter.available() < count {
+                return Err(method);
+            }
+        }
+
+        // All requests are allowed, so consume them. Only limited methods have a limiter.
+        for (&method, &count) in requests {
+            if let Some(limiter) = client.limiters.get_mut(method) {
+                limiter.try_acquire_many(count);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the state of the given client, creating it if necessary. Clients that were idle for
+    /// a whole period are forgotten, as their limiters are full again anyway. Returns `None` if
+    /// the maximum number of clients is tracked and none of them can be forgotten.
+    fn client<'a>(&self, clients: &'a mut Clients, ip: IpAddr) -> Option<&'a mut Client> {
+        let now = now();
+        if now.duration_since(clients.last_prune) >= RATE_LIMIT_PERIOD {
+            clients.clients.retain(|_, client| {
+                now.duration_since(client.last_seen) < RATE_LIMIT_PERIOD
+                    || self.has_requests_in_flight(client)
+            });
+            clients.last_prune = now;
+        }
+
+        let key = client_key(ip);
+        if clients.clients.len() >= MAX_CLIENTS && !clients.clients.contains_key(&key) {
+            // Clients with requests in flight are kept, since their concurrency limit would be
+            // reset otherwise.
+            let least_recently_seen = clients
+                .clients
+                .iter()
+                .filter(|(_, client)| !self.has_requests_in_flight(client))
+                .min_by_key(|(_, client)| client.last_seen)
+                .map(|(oldest, _)| *oldest)?;
+            clients.clients.remove(&least_recently_seen);
+        }
+
+        let client = clients.clients.entry(key).or_insert_with(|| Client {
+            limiters: HashMap::new(),
+            concurrency: Arc::new(Semaphore::new(self.limits.max_concurrent_requests)),
+            last_seen: now,
+        });
+        client.last_seen = now;
+        Some(client)
+    }
+
+    fn has_requests_in_flight(&self, client: &Client) -> bool {
+        client.concurrency.available_permits() < self.limits.max_concurrent_requests
+    }
+}
+
+/// Returns the key the limits of the given IP address are tracked under. IPv6 clients usually
+/// have a whole /64 network assigned, so they are limited by their /64 prefix.
+fn client_key(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V4(_) => ip,
+        IpAddr::V6(ip) => IpAddr::V6(Ipv6Addr::from(u128::from(ip) & (u128::MAX << 64))),
+    }
+}
@@ -0,0 +1,303 @@
+use std::{
+    collections::HashMap,
+    net::{IpAddr, SocketAddr},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use axum::{
+    body::Bytes,
+    extract::{ConnectInfo, State},
+    http::{header, HeaderMap, HeaderValue, Method, StatusCode},
+    response::{IntoResponse, Response as HttpResponse},
+    routing::post,
+    Json, Router,
+};
+use base64::{prelude::BASE64_STANDARD, Engine};
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_jsonrpc_core::{Request, Response, RpcError};
+use nimiq_jsonrpc_server::Dispatcher;
+use parking_lot::Mutex;
+use serde_json::Value;
+use subtle::ConstantTimeEq;
+use tokio::net::TcpListener;
+use tower_http::cors::{Any, CorsLayer};
+
+use crate::{
+    error::Error,
+    rate_limit::{RateLimiting, RateLimits},
+};
+
+/// Credentials for HTTP basic authentication.
+#[derive(Clone)]
+pub struct Credentials {
+    username: String,
+    password_hash: Blake2bHash,
+}
+
+impl Credentials {
+    /// Creates credentials from the username and the Blake2b hash of the password.
+    pub fn new_from_blake2b(username: String, password_hash: Blake2bHash) -> Self {
+        Self {
+            username,
+            password_hash,
+        }
+    }
+
+    fn check(&self, username: &str, password: &str) -> bool {
+        (self.username.as_bytes().ct_eq(username.as_bytes())
+            & self.password_hash.ct_eq(&password.hash()))
+        .into()
+    }
+}
+
+/// The configuration of the RPC [`Server`].
+pub struct Config {
+    /// The address the server listens on.
+    pub bind_to: SocketAddr,
+    /// If set, HTTP basic authentication with these credentials is required.
+    pub basic_auth: Option<Credentials>,
+    /// The origins that are allowed to make cross-origin requests, `*` allows all origins.
+    pub cors_domains: Vec<String>,
+    /// The limits enforced for each client IP address.
+    pub rate_limits: RateLimits,
+}
+
+struct ServerState<D: Dispatcher> {
+    /// The dispatchers that are currently idle. Each request takes a dispatcher for as long as it
+    /// is dispatched, so that slow requests don't hold up the others.
+    dispatchers: Mutex<Vec<D>>,
+    new_dispatcher: Box<dyn Fn() -> D + Send + Sync>,
+    basic_auth: Option<Credentials>,
+    rate_limiting: RateLimiting,
+    next_id: AtomicU64,
+}
+
+/// A JSON-RPC server over HTTP. It supports batch requests and enforces the rate limits per
+/// client IP address before passing requests on to the dispatcher.
+pub struct Server<D: Dispatcher> {
+    bind_to: SocketAddr,
+    cors: CorsLayer,
+    state: Arc<ServerState<D>>,
+}
+
+impl<D: Dispatcher> Server<D> {
+    /// Creates the server. Dispatchers are created with `new_dispatcher` whenever all existing
+    /// ones are busy.
+    pub fn new(config: Config, new_dispatcher: impl Fn() -> D + Send + Sync + 'static) -> Self {
+        Self {
+            bind_to: config.bind_to,
+            cors: cors_layer(&config.cors_domains),
+            state: Arc::new(ServerState {
+                dispatchers: Mutex::new(vec![]),
+                new_dispatcher: Box::new(new_dispatcher),
+                basic_auth: config.basic_auth,
+                rate_limiting: RateLimiting::new(config.rate_limits),
+                next_id: AtomicU64::new(1),
+            }),
+        }
+    }
+
+    /// Runs the server until it fails.
+    pub async fn run(self) {
+        let listener = match TcpListener::bind(self.bind_to).await {
+            Ok(listener) => listener,
+            Err(error) => {
+                log::error!(%error, bind_to = %self.bind_to, "Could not bind RPC server");
+                return;
+            }
+        };
+
+        let app = Router::new()
+            .route("/", post(handle::<D>))
+            .with_state(self.state)
+            .layer(self.cors);
+
+        if let Err(error) = axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .await
+        {
+            log::error!(%error, "RPC server failed");
+        }
+    }
+}
+
+fn cors_layer(cors_domains: &[String]) -> CorsLayer {
+    let cors = CorsLayer::new()
+        .allow_methods([Method::POST])
+        .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE]);
+
+    if cors_domains.iter().any(|origin| origin.trim() == "*") {
+        cors.allow_origin(Any)
+    } else {
+        cors.allow_origin(
+            cors_domains
+                .iter()
+                .filter_map(|origin| HeaderValue::from_str(origin.trim()).ok())
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+/// Returns the username and password of the basic authentication header, if there is one.
+fn basic_auth(headers: &HeaderMap) -> Option<(String, String)> {
+    let encoded = headers
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Basic ")?;
+    let decoded = String::from_utf8(BASE64_STANDARD.decode(encoded.trim()).ok()?).ok()?;
+    let (username, password) = decoded.split_once(':')?;
+    Some((username.to_owned(), password.to_owned()))
+}
+
+async fn handle<D: Dispatcher>(
+    State(state): State<Arc<ServerState<D>>>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> HttpResponse {
+    if let Some(credentials) = &state.basic_auth {
+        let authorized = basic_auth(&headers)
+            .is_some_and(|(username, password)| credentials.check(&username, &password));
+        if !authorized {
+            return (
+                StatusCode::UNAUTHORIZED,
+                [(header::WWW_AUTHENTICATE, "Basic realm=\"nimiq\"")],
+            )
+                .into_response();
+        }
+    }
+
+    let client = ClientRequest {
+        state: &state,
+        addr,
+    };
+    let Some(_slot) = state.rate_limiting.try_acquire_slot(client.ip()) else {
+        log::debug!(%addr, "Too many concurrent requests");
+        return StatusCode::TOO_MANY_REQUESTS.into_response();
+    };
+
+    match serde_json::from_slice::<Value>(&body) {
+        Ok(Value::Array(requests)) => match client.dispatch_batch(requests).await {
+            Ok(responses) if responses.is_empty() => StatusCode::NO_CONTENT.into_response(),
+            Ok(responses) => Json(responses).into_response(),
+            Err(response) => Json(response).into_response(),
+        },
+        Ok(request) => match client.dispatch(request).await {
+            Some(response) => Json(response).into_response(),
+            None => StatusCode::NO_CONTENT.into_response(),
+        },
+        Err(error) => Json(Response::new_error(
+            Value::Null,
+            RpcError::parse_error(Some(Value::String(error.to_string()))),
+        ))
+        .into_response(),
+    }
+}
+
+/// The requests of a single HTTP request, made by the client at `addr`.
+struct ClientRequest<'a, D: Dispatcher> {
+    state: &'a ServerState<D>,
+    addr: SocketAddr,
+}
+
+impl<D: Dispatcher> ClientRequest<'_, D> {
+    /// The IP address the limits are enforced for, IPv6 addresses by their /64 prefix. IPv4
+    /// clients connecting to an IPv6 socket count as the IPv4 address.
+    fn ip(&self) -> IpAddr {
+        self.addr.ip().to_canonical()
+    }
+
+    /// Dispatches the requests of a batch in order. The whole batch is rejected if it is empty or
+    /// larger than allowed, or if its requests exceed the limit of any method. The requests are
+    /// counted towards the limits before any of them is dispatched.
+    async fn dispatch_batch(&self, requests: Vec<Value>) -> Result<Vec<Response>, Response> {
+        let max_batch_size = self.state.rate_limiting.max_batch_size();
+        if requests.is_empty() || requests.len() > max_batch_size {
+            return Err(Response::new_error(
+                Value::Null,
+                RpcError::invalid_request(Some(Value::String(format!(
+                    "Batches must contain between 1 and {max_batch_size} requests"
+                )))),
+            ));
+        }
+
+        let requests: Vec<_> = requests.into_iter().map(parse_request).collect();
+
+        let mut num_requests = HashMap::new();
+        for request in requests.iter().flatten() {
+            *num_requests.entry(request.method.as_str()).or_insert(0) += 1;
+        }
+        if let Err(method) = self.acquire(&num_requests) {
+            return Err(Response::new_error(
+                Value::Null,
+                RpcError::from(Error::RateLimitExceeded(method.to_owned())),
+            ));
+        }
+
+        let mut responses = Vec::with_capacity(requests.len());
+        for request in requests {
+            match request {
+                Ok(request) => responses.extend(self.dispatch_request(request).await),
+                Err(response) => responses.push(response),
+            }
+        }
+        Ok(responses)
+    }
+
+    async fn dispatch(&self, request: Value) -> Option<Response> {
+        let request = match parse_request(request) {
+            Ok(request) => request,
+            Err(response) => return Some(response),
+        };
+
+        if self
+            .acquire(&HashMap::from([(request.method.as_str(), 1)]))
+            .is_err()
+        {
+            // Notifications don't get a response, not even an error.
+            return request.id.map(|id| {
+                Response::new_error(id, RpcError::from(Error::RateLimitExceeded(request.method)))
+            });
+        }
+
+        self.dispatch_request(request).await
+    }
+
+    /// Counts the given number of requests of each method towards the limits of the client.
+    fn acquire<'m>(&self, num_requests: &HashMap<&'m str, u32>) -> Result<(), &'m str> {
+        let ip = self.ip();
+        self.state
+            .rate_limiting
+            .try_acquire(ip, num_requests)
+            .inspect_err(|method| log::debug!(%ip, %method, "Rate limit exceeded"))
+    }
+
+    /// Dispatches a request that was already counted towards the limits.
+    async fn dispatch_request(&self, request: Request) -> Option<Response> {
+        let id = self.state.next_id.fetch_add(1, Ordering::Relaxed);
+
+        // Take an idle dispatcher, so that no lock is held while the request is dispatched. The
+        // dispatcher isn't returned if the request is cancelled.
+        let dispatcher = self.state.dispatchers.lock().pop();
+        let mut dispatcher = dispatcher.unwrap_or_else(|| (self.state.new_dispatcher)());
+        let response = dispatcher.dispatch(request, None, id).await;
+        self.state.dispatchers.lock().push(dispatcher);
+
+        response
+    }
+}
+
+fn parse_request(request: Value) -> Result<Request, Response> {
+    serde_json::from_value(request).map_err(|error| {
+        Response::new_error(
+            Value::Null,
+            RpcError::invalid_request(Some(Value::String(error.to_string()))),
+        )
+    })
+}
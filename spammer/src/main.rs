@@ -247,6 +247,7 @@ async fn main_inner() -> Result<(), Error> {
             client.mempool(),
             client.consensus_proxy(),
             client.network(),
+            client.bls_cache(),
             &[],
         )
     }
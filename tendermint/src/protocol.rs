@@ -111,7 +111,7 @@ pub trait Protocol: Clone + Send + Sync + Unpin + Sized + 'static {
     fn sign_proposal(
         &self,
         proposal_message: &ProposalMessage<Self::Proposal>,
-    ) -> Self::ProposalSignature;
+    ) -> Result<Self::ProposalSignature, ProtocolError>;
 
     /// Verifies a given `proposal`. Optionally a precomputed `precalculated_inherent` can be provided if the inherent has been computed before.
     /// All checks except for the signature verification can be skipped using the `signature_only` flag
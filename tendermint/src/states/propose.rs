@@ -118,7 +118,7 @@ impl<TProtocol: Protocol> Tendermint<TProtocol> {
             };
 
             // Sign the proposal message
-            let signature = self.protocol.sign_proposal(&message);
+            let signature = self.protocol.sign_proposal(&message)?;
 
             // Store the proposal for the current round.
             proposals.insert(proposal_hash.clone(), (Some(*valid_round), signature));
@@ -136,7 +136,7 @@ impl<TProtocol: Protocol> Tendermint<TProtocol> {
             let (message, inherent) = self.protocol.create_proposal(self.state.current_round)?;
 
             // Sign the proposal message
-            let signature = self.protocol.sign_proposal(&message);
+            let signature = self.protocol.sign_proposal(&message)?;
 
             // Hash it for identification and voting.
             let proposal_hash = message.proposal.hash();
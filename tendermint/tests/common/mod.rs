@@ -158,8 +158,8 @@ impl Protocol for Validator {
     fn sign_proposal(
         &self,
         _proposal_message: &ProposalMessage<Self::Proposal>,
-    ) -> Self::ProposalSignature {
-        true
+    ) -> Result<Self::ProposalSignature, ProtocolError> {
+        Ok(true)
     }
 
     fn verify_proposal(
@@ -91,7 +91,7 @@ impl TemporaryBlockProducer {
         Self::with_blockchain(blockchain)
     }
 
-    fn with_blockchain(blockchain: Blockchain) -> Self {
+    pub fn with_blockchain(blockchain: Blockchain) -> Self {
         let blockchain = Arc::new(RwLock::new(blockchain));
 
         let signing_key = SchnorrKeyPair::from(
@@ -12,6 +12,7 @@ use nimiq_primitives::{networks::NetworkId, trie::TrieItem};
 use nimiq_utils::{spawn, time::OffsetTime};
 use nimiq_zkp_component::{
     proof_store::{DBProofStore, ProofStore},
+    prover_backend::ProcessProverBackend,
     ZKPComponent,
 };
 use parking_lot::{Mutex, RwLock};
@@ -80,9 +81,10 @@ impl<N: NetworkInterface + TestNetwork> Node<N> {
             BlockchainProxy::from(&blockchain),
             Arc::clone(&network),
             is_prover_active,
-            prover_path,
+            Arc::new(ProcessProverBackend::new(prover_path)),
             PathBuf::from(ZKP_TEST_KEYS_PATH),
             zkp_storage,
+            None,
         )
         .await;
 
@@ -59,10 +59,14 @@ impl From<OutgoingType> for AccountType {
 
 impl OutgoingType {
     pub fn is_staking(&self) -> bool {
-        matches!(
-            self,
-            OutgoingType::RemoveStake | OutgoingType::DeleteValidator
-        )
+        match self {
+            OutgoingType::RemoveStake | OutgoingType::DeleteValidator => true,
+            OutgoingType::Basic
+            | OutgoingType::Vesting
+            | OutgoingType::HTLCRegularTransfer
+            | OutgoingType::HTLCEarlyResolve
+            | OutgoingType::HTLCTimeoutResolve => false,
+        }
     }
 }
 
@@ -82,6 +86,7 @@ pub enum IncomingType {
     UpdateStaker,
     SetActiveStake,
     RetireStake,
+    SetRewardCompounding,
 }
 
 impl IncomingType {
@@ -104,6 +109,7 @@ impl IncomingType {
                 | IncomingType::UpdateStaker
                 | IncomingType::SetActiveStake
                 | IncomingType::RetireStake
+                | IncomingType::SetRewardCompounding
         )
     }
 }
@@ -124,6 +130,7 @@ impl From<IncomingType> for AccountType {
             IncomingType::UpdateStaker => AccountType::Staking,
             IncomingType::SetActiveStake => AccountType::Staking,
             IncomingType::RetireStake => AccountType::Staking,
+            IncomingType::SetRewardCompounding => AccountType::Staking,
         }
     }
 }
@@ -241,7 +248,7 @@ impl<R: Rng + CryptoRng> TransactionsGenerator<R> {
             IncomingType::CreateValidator => {
                 value = Coin::from_u64_unchecked(Policy::VALIDATOR_DEPOSIT);
             }
-            IncomingType::SetActiveStake => {
+            IncomingType::SetActiveStake | IncomingType::SetRewardCompounding => {
                 value = Coin::ZERO;
             }
             IncomingType::CreateStaker | IncomingType::RetireStake => {
@@ -569,6 +576,7 @@ impl<R: Rng + CryptoRng> TransactionsGenerator<R> {
                     time_step: 1,
                     step_amount: balance,
                     total_amount: balance,
+                    beneficiaries: vec![],
                 },
             },
             IncomingType::CreateHTLC => IncomingAccountData::Htlc {
@@ -788,6 +796,24 @@ impl<R: Rng + CryptoRng> TransactionsGenerator<R> {
                     staker_key_pair,
                 }
             }
+            IncomingType::SetRewardCompounding => {
+                let (validator_key_pair, _, mut staker_key_pair) =
+                    self.create_validator_and_staker(ValidatorState::Active, false, false);
+
+                // We can make the transaction fail by using a non-existing staker address.
+                if fail_recipient {
+                    staker_key_pair = KeyPair::generate(&mut self.rng);
+                }
+
+                IncomingAccountData::Staking {
+                    parameters: IncomingStakingTransactionData::SetRewardCompounding {
+                        compound_rewards: true,
+                        proof: SignatureProof::default(),
+                    },
+                    validator_key_pair,
+                    staker_key_pair,
+                }
+            }
         }
     }
 
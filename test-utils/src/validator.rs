@@ -13,8 +13,13 @@ use nimiq_network_mock::MockHub;
 use nimiq_primitives::{networks::NetworkId, policy::Policy};
 use nimiq_serde::{Deserialize, Serialize};
 use nimiq_utils::spawn;
-use nimiq_validator::{key_utils::VotingKeys, validator::Validator};
-use nimiq_validator_network::network_impl::ValidatorNetworkImpl;
+use nimiq_validator::{
+    key_utils::{SigningKeys, VotingKeys},
+    validator::Validator,
+};
+use nimiq_validator_network::{
+    network_impl::ValidatorNetworkImpl, validator_record::VALIDATOR_RECORD_REFRESH_INTERVAL,
+};
 use rand::{rngs::StdRng, SeedableRng};
 use tokio_stream::wrappers::BroadcastStream;
 
@@ -52,7 +57,8 @@ where
             validator_network,
             validator_address,
             automatic_reactivate,
-            signing_key,
+            VALIDATOR_RECORD_REFRESH_INTERVAL,
+            SigningKeys::new(vec![signing_key]),
             VotingKeys::new(vec![voting_key]),
             fee_key,
             MempoolConfig::default(),
@@ -140,7 +146,12 @@ where
         spawn(consensus);
     }
 
-    future::join_all(events.iter_mut().map(|e| e.next())).await;
+    future::join_all(events.iter_mut().map(|e| async move {
+        e.filter(|event| future::ready(matches!(event, Ok(ConsensusEvent::Established { .. }))))
+            .next()
+            .await
+    }))
+    .await;
 
     validators
 }
@@ -0,0 +1,139 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+
+use futures::Stream;
+use pin_project_lite::pin_project;
+
+use crate::{limit_duration, sleep, Sleep};
+
+pin_project! {
+    /// Stream returned by [`debounce`].
+    pub struct Debounce<S: Stream> {
+        #[pin]
+        stream: S,
+        duration: Duration,
+        pending: Option<S::Item>,
+        sleep: Option<Pin<Box<Sleep>>>,
+        done: bool,
+    }
+}
+
+/// Debounces the given stream: an item is only emitted once no further item was received
+/// within `duration`, in which case the latest item is emitted and the preceding ones are
+/// dropped. A pending item is emitted right away when the stream ends.
+pub fn debounce<S: Stream>(stream: S, duration: Duration) -> Debounce<S> {
+    limit_duration(duration);
+    Debounce {
+        stream,
+        duration,
+        pending: None,
+        sleep: None,
+        done: false,
+    }
+}
+
+impl<S: Stream> Stream for Debounce<S> {
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+        let mut this = self.project();
+
+        while !*this.done {
+            match this.stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    *this.pending = Some(item);
+                    *this.sleep = Some(Box::pin(sleep(*this.duration)));
+                }
+                Poll::Ready(None) => *this.done = true,
+                Poll::Pending => break,
+            }
+        }
+
+        if *this.done {
+            *this.sleep = None;
+            return Poll::Ready(this.pending.take());
+        }
+
+        if let Some(timer) = this.sleep.as_mut() {
+            ready!(timer.as_mut().poll(cx));
+            *this.sleep = None;
+            return Poll::Ready(this.pending.take());
+        }
+
+        Poll::Pending
+    }
+}
+
+pin_project! {
+    /// Stream returned by [`throttle`].
+    pub struct Throttle<S> {
+        #[pin]
+        stream: S,
+        duration: Duration,
+        sleep: Option<Pin<Box<Sleep>>>,
+    }
+}
+
+/// Throttles the given stream such that at most one item is emitted per `duration`. Items are
+/// delayed, not dropped: the stream is not polled again until `duration` has passed since the
+/// last item was emitted.
+pub fn throttle<S: Stream>(stream: S, duration: Duration) -> Throttle<S> {
+    limit_duration(duration);
+    Throttle {
+        stream,
+        duration,
+        sleep: None,
+    }
+}
+
+impl<S: Stream> Stream for Throttle<S> {
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<S::Item>> {
+        let this = self.project();
+
+        if let Some(timer) = this.sleep.as_mut() {
+            ready!(timer.as_mut().poll(cx));
+            *this.sleep = None;
+        }
+
+        let item = ready!(this.stream.poll_next(cx));
+        if item.is_some() {
+            *this.sleep = Some(Box::pin(sleep(*this.duration)));
+        }
+        Poll::Ready(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::{stream, StreamExt};
+
+    use super::{debounce, throttle};
+    use crate::Instant;
+
+    #[tokio::test]
+    async fn it_debounces_bursts() {
+        let items: Vec<_> = debounce(stream::iter(1..=5), Duration::from_millis(50))
+            .collect()
+            .await;
+        assert_eq!(items, vec![5]);
+    }
+
+    #[tokio::test]
+    async fn it_throttles_items() {
+        let start = Instant::now();
+        let items: Vec<_> = throttle(stream::iter(1..=3), Duration::from_millis(50))
+            .collect()
+            .await;
+
+        assert_eq!(items, vec![1, 2, 3]);
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+}
@@ -6,17 +6,11 @@ use std::{
     time::Duration,
 };
 
-use gloo_timers::future::{IntervalStream, TimeoutFuture};
+use gloo_timers::future::TimeoutFuture;
 use instant::Instant;
 use pin_project_lite::pin_project;
 use send_wrapper::SendWrapper;
 
-pub type Interval = SendWrapper<IntervalStream>;
-
-pub fn interval(period: Duration) -> Interval {
-    SendWrapper::new(IntervalStream::new(millis(period)))
-}
-
 pub type Sleep = SendWrapper<TimeoutFuture>;
 
 pub fn sleep(duration: Duration) -> Sleep {
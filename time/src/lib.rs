@@ -3,16 +3,31 @@ use std::{
     fmt,
     future::Future,
     pin::Pin,
-    task::{Context, Poll},
+    task::{ready, Context, Poll},
     time::Duration,
 };
 
-use futures::{Stream, StreamExt as _};
+use futures::Stream;
 pub use instant::Instant;
 use pin_project_lite::pin_project;
+use rand::Rng;
 
+#[cfg(any(test, feature = "test-utils"))]
+pub use crate::mock::TestClock;
+pub use crate::{
+    debounce::{debounce, throttle, Debounce, Throttle},
+    rate_limiter::{Acquire, RateLimiter},
+};
+
+mod debounce;
 #[cfg(target_family = "wasm")]
 mod gloo;
+#[cfg(any(test, feature = "test-utils"))]
+mod mock;
+#[cfg(not(any(test, feature = "test-utils")))]
+#[path = "mock_disabled.rs"]
+mod mock;
+mod rate_limiter;
 #[cfg(not(target_family = "wasm"))]
 mod tokio;
 
@@ -21,59 +36,141 @@ use gloo as sys;
 #[cfg(not(target_family = "wasm"))]
 use tokio as sys;
 
+/// Returns the current time. This is the time of the `TestClock` if one was frozen on the
+/// current thread, which is only available with the `test-utils` feature.
+pub fn now() -> Instant {
+    match mock::current() {
+        Some(clock) => clock.now(),
+        None => Instant::now(),
+    }
+}
+
+/// A stream that yields at a fixed period. Created by [`interval`] or [`interval_at`].
+///
+/// If ticks are missed because the stream was not polled in time, they are yielded right away
+/// once it is polled again.
 pub struct Interval {
-    sys: sys::Interval,
+    period: Duration,
+    /// The maximum fraction of the period by which each tick is shifted randomly.
+    jitter: f64,
+    /// The time the next tick is scheduled at, before applying the jitter.
+    next_tick: Instant,
+    sleep: Pin<Box<Sleep>>,
 }
 
-// TODO: decide on first tick. right now or after one period?
+/// Creates an interval that first ticks after one `period` and then every `period`.
 pub fn interval(period: Duration) -> Interval {
+    interval_at(now() + period, period)
+}
+
+/// Creates an interval that first ticks at `start` and then every `period`.
+/// Use `interval_at(now(), period)` for an interval that ticks right away.
+pub fn interval_at(start: Instant, period: Duration) -> Interval {
     limit_duration(period);
-    #[allow(clippy::disallowed_methods)]
+    assert!(!period.is_zero(), "Period must be positive");
     Interval {
-        sys: sys::interval(period),
+        period,
+        jitter: 0.0,
+        next_tick: start,
+        sleep: Box::pin(sleep_until(start)),
+    }
+}
+
+impl Interval {
+    /// Randomly shifts every tick by up to `fraction` of the period in either direction, such
+    /// that nodes using the same period do not all wake up at the same time. The jitter does not
+    /// accumulate: each tick is shifted relative to its regular schedule.
+    ///
+    /// The fraction is clamped to `[0, 0.5]` so that the order of the ticks is preserved.
+    pub fn with_jitter(mut self, fraction: f64) -> Self {
+        self.jitter = fraction.clamp(0.0, 0.5);
+        self.sleep = Box::pin(sleep_until(self.jittered(self.next_tick)));
+        self
+    }
+
+    fn jittered(&self, tick: Instant) -> Instant {
+        if self.jitter == 0.0 {
+            return tick;
+        }
+        let max_shift = self.period.mul_f64(self.jitter);
+        let shift = max_shift.mul_f64(2.0 * rand::thread_rng().gen::<f64>());
+        tick.checked_sub(max_shift).unwrap_or(tick) + shift
     }
 }
 
 impl Stream for Interval {
     type Item = ();
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<()>> {
-        self.sys
-            .poll_next_unpin(cx)
-            .map(|option| option.map(|_| ()))
+        ready!(self.sleep.as_mut().poll(cx));
+
+        let period = self.period;
+        self.next_tick += period;
+        let next_tick = self.jittered(self.next_tick);
+        self.sleep = Box::pin(sleep_until(next_tick));
+
+        Poll::Ready(Some(()))
     }
     fn size_hint(&self) -> (usize, Option<usize>) {
         (usize::MAX, None)
     }
 }
 
+pin_project! {
+    #[project = SleepProj]
+    enum SleepKind {
+        Sys {
+            #[pin]
+            sys: sys::Sleep,
+        },
+        Mock {
+            mock: mock::Sleep,
+        },
+    }
+}
+
 pin_project! {
     pub struct Sleep {
         #[pin]
-        sys: sys::Sleep,
+        kind: SleepKind,
     }
 }
 
 pub fn sleep(duration: Duration) -> Sleep {
     limit_duration(duration);
-    #[allow(clippy::disallowed_methods)]
-    Sleep {
-        sys: sys::sleep(duration),
-    }
+    let kind = match mock::current() {
+        Some(clock) => SleepKind::Mock {
+            mock: clock.sleep(duration),
+        },
+        #[allow(clippy::disallowed_methods)]
+        None => SleepKind::Sys {
+            sys: sys::sleep(duration),
+        },
+    };
+    Sleep { kind }
 }
 
 pub fn sleep_until(deadline: Instant) -> Sleep {
-    if let Some(duration) = deadline.checked_duration_since(Instant::now()) {
+    if let Some(duration) = deadline.checked_duration_since(now()) {
         limit_duration(duration);
     }
-    Sleep {
-        sys: sys::sleep_until(deadline),
-    }
+    let kind = match mock::current() {
+        Some(clock) => SleepKind::Mock {
+            mock: clock.sleep_until(deadline),
+        },
+        None => SleepKind::Sys {
+            sys: sys::sleep_until(deadline),
+        },
+    };
+    Sleep { kind }
 }
 
 impl Future for Sleep {
     type Output = ();
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
-        self.project().sys.poll(cx)
+        match self.project().kind.project() {
+            SleepProj::Sys { sys } => sys.poll(cx),
+            SleepProj::Mock { mock } => Pin::new(mock).poll(cx),
+        }
     }
 }
 
@@ -88,25 +185,55 @@ impl fmt::Display for Elapsed {
 
 impl Error for Elapsed {}
 
+pin_project! {
+    #[project = TimeoutProj]
+    enum TimeoutKind<F: Future> {
+        Sys {
+            #[pin]
+            sys: sys::Timeout<F>,
+        },
+        Mock {
+            #[pin]
+            future: F,
+            deadline: mock::Sleep,
+        },
+    }
+}
+
 pin_project! {
     pub struct Timeout<F: Future> {
         #[pin]
-        sys: sys::Timeout<F>,
+        kind: TimeoutKind<F>,
     }
 }
 
 pub fn timeout<F: Future>(timeout: Duration, future: F) -> Timeout<F> {
     limit_duration(timeout);
-    #[allow(clippy::disallowed_methods)]
-    Timeout {
-        sys: sys::timeout(timeout, future),
-    }
+    let kind = match mock::current() {
+        Some(clock) => TimeoutKind::Mock {
+            future,
+            deadline: clock.sleep(timeout),
+        },
+        #[allow(clippy::disallowed_methods)]
+        None => TimeoutKind::Sys {
+            sys: sys::timeout(timeout, future),
+        },
+    };
+    Timeout { kind }
 }
 
 impl<F: Future> Future for Timeout<F> {
     type Output = Result<F::Output, Elapsed>;
     fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<F::Output, Elapsed>> {
-        self.project().sys.poll(cx).map_err(|_| Elapsed(()))
+        match self.project().kind.project() {
+            TimeoutProj::Sys { sys } => sys.poll(cx).map_err(|_| Elapsed(())),
+            TimeoutProj::Mock { future, deadline } => {
+                if let Poll::Ready(output) = future.poll(cx) {
+                    return Poll::Ready(Ok(output));
+                }
+                Pin::new(deadline).poll(cx).map(|_| Err(Elapsed(())))
+            }
+        }
     }
 }
 
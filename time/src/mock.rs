@@ -0,0 +1,263 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+use crate::Instant;
+
+thread_local! {
+    /// The mock clock installed on the current thread, if any.
+    static CLOCK: RefCell<Option<Clock>> = const { RefCell::new(None) };
+}
+
+/// Returns the mock clock installed on the current thread, if any.
+pub(crate) fn current() -> Option<Clock> {
+    CLOCK.with(|clock| clock.borrow().clone())
+}
+
+struct ClockState {
+    /// The real time at which the clock was created.
+    start: Instant,
+    /// The time that passed on the clock since it was created.
+    elapsed: Duration,
+    next_timer_id: u64,
+    /// The pending timers by their id, with their deadline and the waker to wake once it is
+    /// reached.
+    timers: HashMap<u64, (Duration, Waker)>,
+}
+
+/// A clock that only advances when told to.
+#[derive(Clone)]
+pub(crate) struct Clock(Arc<Mutex<ClockState>>);
+
+impl Clock {
+    fn new() -> Self {
+        Clock(Arc::new(Mutex::new(ClockState {
+            start: Instant::now(),
+            elapsed: Duration::ZERO,
+            next_timer_id: 0,
+            timers: HashMap::new(),
+        })))
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, ClockState> {
+        self.0.lock().unwrap_or_else(|error| error.into_inner())
+    }
+
+    pub(crate) fn now(&self) -> Instant {
+        let state = self.state();
+        state.start + state.elapsed
+    }
+
+    fn elapsed(&self) -> Duration {
+        self.state().elapsed
+    }
+
+    fn advance(&self, duration: Duration) {
+        let wakers: Vec<_> = {
+            let mut state = self.state();
+            state.elapsed += duration;
+            let elapsed = state.elapsed;
+
+            let expired: Vec<_> = state
+                .timers
+                .iter()
+                .filter(|(_, (deadline, _))| *deadline <= elapsed)
+                .map(|(id, _)| *id)
+                .collect();
+            expired
+                .into_iter()
+                .filter_map(|id| state.timers.remove(&id))
+                .map(|(_, waker)| waker)
+                .collect()
+        };
+
+        // Wake the timers without holding the lock, as they might be polled right away.
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+
+    fn timer(&self, deadline: Duration) -> Timer {
+        let mut state = self.state();
+        let id = state.next_timer_id;
+        state.next_timer_id += 1;
+        Timer {
+            clock: self.clone(),
+            id,
+            deadline,
+        }
+    }
+
+    pub(crate) fn sleep(&self, duration: Duration) -> Sleep {
+        let deadline = self.elapsed() + duration;
+        Sleep(self.timer(deadline))
+    }
+
+    pub(crate) fn sleep_until(&self, deadline: Instant) -> Sleep {
+        let start = self.state().start;
+        Sleep(self.timer(deadline.saturating_duration_since(start)))
+    }
+}
+
+/// A timer registered with a [`Clock`].
+struct Timer {
+    clock: Clock,
+    id: u64,
+    /// The time on the clock at which the timer expires.
+    deadline: Duration,
+}
+
+impl Timer {
+    fn poll_expired(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.clock.state();
+        if state.elapsed >= self.deadline {
+            state.timers.remove(&self.id);
+            return Poll::Ready(());
+        }
+        state
+            .timers
+            .insert(self.id, (self.deadline, cx.waker().clone()));
+        Poll::Pending
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        self.clock.state().timers.remove(&self.id);
+    }
+}
+
+pub(crate) struct Sleep(Timer);
+
+impl Future for Sleep {
+    type Output = ();
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.0.poll_expired(cx)
+    }
+}
+
+/// A mock clock for deterministic tests.
+///
+/// While a `TestClock` exists, time is frozen on the thread that created it: [`now`](crate::now)
+/// returns the time of the clock, and all timers created on that thread by [`sleep`](crate::sleep),
+/// [`sleep_until`](crate::sleep_until), [`interval`](crate::interval) and
+/// [`timeout`](crate::timeout) only expire once the clock is advanced past their deadline using
+/// [`TestClock::advance`]. Timers created before the clock was frozen are not affected.
+///
+/// The clock is bound to the current thread, so it should be used with a single threaded runtime.
+/// Dropping it unfreezes the time again.
+pub struct TestClock {
+    clock: Clock,
+    /// The clock that was installed before this one.
+    previous: Option<Clock>,
+}
+
+impl TestClock {
+    /// Freezes the time on the current thread.
+    pub fn freeze() -> Self {
+        let clock = Clock::new();
+        let previous = CLOCK.with(|current| current.replace(Some(clock.clone())));
+        TestClock { clock, previous }
+    }
+
+    /// Advances the clock by the given duration, waking all timers that expire.
+    pub fn advance(&self, duration: Duration) {
+        self.clock.advance(duration);
+    }
+
+    /// Returns the current time of the clock.
+    pub fn now(&self) -> Instant {
+        self.clock.now()
+    }
+
+    /// Returns the time the clock was advanced by since it was frozen.
+    pub fn elapsed(&self) -> Duration {
+        self.clock.elapsed()
+    }
+}
+
+impl Drop for TestClock {
+    fn drop(&mut self) {
+        CLOCK.with(|current| *current.borrow_mut() = self.previous.take());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{pin::pin, time::Duration};
+
+    use futures::{future, FutureExt, StreamExt};
+
+    use super::TestClock;
+    use crate::{interval, interval_at, now, sleep, timeout};
+
+    #[test]
+    fn sleep_only_expires_when_advanced() {
+        let clock = TestClock::freeze();
+        let start = now();
+
+        let mut sleep = pin!(sleep(Duration::from_secs(10)));
+        assert!(sleep.as_mut().now_or_never().is_none());
+
+        clock.advance(Duration::from_secs(9));
+        assert!(sleep.as_mut().now_or_never().is_none());
+
+        clock.advance(Duration::from_secs(1));
+        assert!(sleep.as_mut().now_or_never().is_some());
+        assert_eq!(now().duration_since(start), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn interval_ticks_once_per_period() {
+        let clock = TestClock::freeze();
+
+        let mut interval = interval(Duration::from_secs(2));
+        assert!(interval.next().now_or_never().is_none());
+
+        clock.advance(Duration::from_secs(5));
+        assert!(interval.next().now_or_never().is_some());
+        assert!(interval.next().now_or_never().is_some());
+        assert!(interval.next().now_or_never().is_none());
+    }
+
+    #[test]
+    fn interval_at_ticks_at_start() {
+        let clock = TestClock::freeze();
+
+        let mut interval = interval_at(now(), Duration::from_secs(2));
+        assert!(interval.next().now_or_never().is_some());
+        assert!(interval.next().now_or_never().is_none());
+
+        clock.advance(Duration::from_secs(2));
+        assert!(interval.next().now_or_never().is_some());
+    }
+
+    #[test]
+    fn interval_jitter_is_bounded() {
+        let clock = TestClock::freeze();
+
+        let mut interval = interval(Duration::from_secs(10)).with_jitter(0.2);
+        clock.advance(Duration::from_millis(7999));
+        assert!(interval.next().now_or_never().is_none());
+
+        clock.advance(Duration::from_millis(4001));
+        assert!(interval.next().now_or_never().is_some());
+    }
+
+    #[test]
+    fn timeout_elapses_when_advanced() {
+        let clock = TestClock::freeze();
+
+        let mut timeout = pin!(timeout(Duration::from_secs(3), future::pending::<()>()));
+        assert!(timeout.as_mut().now_or_never().is_none());
+
+        clock.advance(Duration::from_secs(3));
+        assert!(matches!(timeout.as_mut().now_or_never(), Some(Err(_))));
+    }
+}
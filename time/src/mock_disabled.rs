@@ -0,0 +1,43 @@
+//! Stand-in for the mock clock if the `test-utils` feature is disabled. No clock can ever be
+//! installed, so the time functions always use the system time.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use crate::Instant;
+
+/// Returns the mock clock installed on the current thread, which never exists.
+#[inline]
+pub(crate) fn current() -> Option<Clock> {
+    None
+}
+
+#[derive(Clone)]
+pub(crate) enum Clock {}
+
+impl Clock {
+    pub(crate) fn now(&self) -> Instant {
+        match *self {}
+    }
+
+    pub(crate) fn sleep(&self, _duration: Duration) -> Sleep {
+        match *self {}
+    }
+
+    pub(crate) fn sleep_until(&self, _deadline: Instant) -> Sleep {
+        match *self {}
+    }
+}
+
+pub(crate) enum Sleep {}
+
+impl Future for Sleep {
+    type Output = ();
+    fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        match *self {}
+    }
+}
@@ -0,0 +1,134 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{ready, Context, Poll},
+    time::Duration,
+};
+
+use crate::{limit_duration, now, sleep, Instant, Sleep};
+
+/// A token bucket rate limiter.
+///
+/// The bucket holds up to `capacity` tokens and refills at a rate of `capacity` tokens per
+/// `period`. It starts full, so up to `capacity` operations are allowed in a burst.
+pub struct RateLimiter {
+    /// Maximum number of tokens in the bucket.
+    capacity: f64,
+    /// Number of tokens added per second.
+    rate: f64,
+    /// Number of tokens currently in the bucket.
+    tokens: f64,
+    /// The last time tokens were added to the bucket.
+    last_refill: Instant,
+    /// The timer waiting for the next token to become available.
+    sleep: Option<Pin<Box<Sleep>>>,
+}
+
+impl RateLimiter {
+    /// Creates a rate limiter allowing `capacity` operations per `period`.
+    pub fn new(capacity: u32, period: Duration) -> Self {
+        assert!(capacity > 0, "Capacity must be positive");
+        assert!(!period.is_zero(), "Period must be positive");
+        limit_duration(period);
+
+        let capacity = capacity as f64;
+        RateLimiter {
+            capacity,
+            rate: capacity / period.as_secs_f64(),
+            tokens: capacity,
+            last_refill: now(),
+            sleep: None,
+        }
+    }
+
+    fn refill(&mut self) {
+        let current = now();
+        let elapsed = current.duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        self.last_refill = current;
+    }
+
+    /// Returns the number of operations that are currently allowed.
+    pub fn available(&mut self) -> u32 {
+        self.refill();
+        self.tokens as u32
+    }
+
+    /// Consumes a token if one is available and returns whether it was.
+    pub fn try_acquire(&mut self) -> bool {
+        self.try_acquire_many(1)
+    }
+
+    /// Consumes `count` tokens if that many are available and returns whether they were.
+    pub fn try_acquire_many(&mut self, count: u32) -> bool {
+        self.refill();
+        if self.tokens < count as f64 {
+            return false;
+        }
+        self.tokens -= count as f64;
+        true
+    }
+
+    /// Polls for a token, consuming it once it becomes available.
+    pub fn poll_acquire(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        loop {
+            if self.try_acquire() {
+                self.sleep = None;
+                return Poll::Ready(());
+            }
+
+            let wait = Duration::from_secs_f64((1.0 - self.tokens) / self.rate);
+            let timer = self.sleep.get_or_insert_with(|| Box::pin(sleep(wait)));
+            ready!(timer.as_mut().poll(cx));
+            self.sleep = None;
+        }
+    }
+
+    /// Returns a future that resolves once a token was consumed.
+    pub fn acquire(&mut self) -> Acquire<'_> {
+        Acquire { limiter: self }
+    }
+}
+
+/// Future returned by [`RateLimiter::acquire`].
+pub struct Acquire<'a> {
+    limiter: &'a mut RateLimiter,
+}
+
+impl Future for Acquire<'_> {
+    type Output = ();
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.limiter.poll_acquire(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::RateLimiter;
+    use crate::Instant;
+
+    #[test]
+    fn it_allows_bursts_up_to_capacity() {
+        let mut limiter = RateLimiter::new(3, Duration::from_secs(60));
+
+        assert_eq!(limiter.available(), 3);
+        assert!(limiter.try_acquire());
+        assert!(limiter.try_acquire_many(2));
+        assert!(!limiter.try_acquire());
+        assert_eq!(limiter.available(), 0);
+    }
+
+    #[tokio::test]
+    async fn it_waits_for_tokens_to_refill() {
+        let mut limiter = RateLimiter::new(1, Duration::from_millis(50));
+
+        let start = Instant::now();
+        limiter.acquire().await;
+        limiter.acquire().await;
+        limiter.acquire().await;
+
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+}
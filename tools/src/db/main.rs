@@ -0,0 +1,192 @@
+use std::{fs, path::Path, process, str::FromStr, sync::Arc};
+
+use anyhow::Error;
+use clap::{crate_authors, crate_version, value_parser, Arg, ArgAction, Command};
+use nimiq_account::Accounts;
+use nimiq_blockchain::{Blockchain, BlockchainConfig};
+use nimiq_database::mdbx::{DatabaseConfig, MdbxDatabase};
+use nimiq_primitives::networks::NetworkId;
+use nimiq_trie::trie::TrieSubtreeStats;
+use nimiq_utils::time::OffsetTime;
+
+fn database_arg() -> Arg {
+    Arg::new("database")
+        .short('d')
+        .long("database")
+        .value_name("PATH")
+        .required(true)
+        .help("Database directory of the node")
+}
+
+/// Returns the size of the data file of the database in `path`.
+fn data_file_size(path: &Path) -> Result<u64, Error> {
+    Ok(fs::metadata(path.join("mdbx.dat"))?.len())
+}
+
+fn print_stats_row(name: &str, stats: &TrieSubtreeStats) {
+    println!(
+        "{:<42} {:>10} {:>10} {:>10} {:>14}",
+        name, stats.num_branches, stats.num_hybrids, stats.num_leaves, stats.num_bytes
+    );
+}
+
+fn run_app() -> Result<(), Error> {
+    let stats = Command::new("stats")
+        .about(
+            "Print node counts and sizes of the accounts trie, grouped by key prefix. The node \
+             may keep running.",
+        )
+        .arg(database_arg())
+        .arg(
+            Arg::new("prefix_len")
+                .short('p')
+                .long("prefix-len")
+                .value_name("NIBBLES")
+                .value_parser(value_parser!(usize))
+                .default_value("2")
+                .conflicts_with("by_account")
+                .help("Number of key nibbles the nodes are grouped by"),
+        )
+        .arg(
+            Arg::new("by_account")
+                .long("by-account")
+                .action(ArgAction::SetTrue)
+                .help("Group the nodes by account, including the data stores of contracts"),
+        );
+    let compact = Command::new("compact")
+        .about(
+            "Write a compacted copy of the database that contains none of the space freed by \
+             updates. Stop the node and replace its database directory with the copy afterwards.",
+        )
+        .arg(database_arg())
+        .arg(
+            Arg::new("output")
+                .short('o')
+                .long("output")
+                .value_name("PATH")
+                .required(true)
+                .help("Directory the compacted database is written to"),
+        );
+
+    let check = Command::new("check")
+        .about(
+            "Check the consistency of the chain store, the accounts and the history with the \
+             stored head. The node must be stopped. Loading the database rewinds a corrupted \
+             head like the node does on startup.",
+        )
+        .arg(database_arg())
+        .arg(
+            Arg::new("network")
+                .short('n')
+                .long("network")
+                .value_name("NETWORK")
+                .default_value("main-albatross")
+                .help("Network of the database"),
+        )
+        .arg(
+            Arg::new("full")
+                .long("full")
+                .action(ArgAction::SetTrue)
+                .help("The database is the one of a full node that doesn't keep the history"),
+        )
+        .arg(
+            Arg::new("repair")
+                .long("repair")
+                .action(ArgAction::SetTrue)
+                .help("Repair orphaned chain store records and the hashes of the accounts trie"),
+        );
+
+    let matches = Command::new("nimiq-db")
+        .version(crate_version!())
+        .author(crate_authors!())
+        .about("Inspect and maintain the database of a Nimiq node")
+        .subcommand_required(true)
+        .subcommand(stats)
+        .subcommand(compact)
+        .subcommand(check)
+        .get_matches();
+
+    match matches.subcommand() {
+        Some(("stats", matches)) => {
+            let env = MdbxDatabase::new_read_only(
+                matches.get_one::<String>("database").unwrap(),
+                DatabaseConfig::default(),
+            )?;
+            let accounts = Accounts::new(env);
+            let stats = if matches.get_flag("by_account") {
+                accounts.stats_by_account()
+            } else {
+                accounts.subtree_stats(*matches.get_one::<usize>("prefix_len").unwrap())
+            };
+
+            println!(
+                "{:<42} {:>10} {:>10} {:>10} {:>14}",
+                "prefix", "branches", "hybrids", "leaves", "bytes"
+            );
+            let mut total = TrieSubtreeStats::default();
+            for (prefix, stats) in &stats {
+                let name = prefix.to_string();
+                print_stats_row(if name.is_empty() { "<root>" } else { &name }, stats);
+                total.num_branches += stats.num_branches;
+                total.num_hybrids += stats.num_hybrids;
+                total.num_leaves += stats.num_leaves;
+                total.num_bytes += stats.num_bytes;
+            }
+            print_stats_row("total", &total);
+            Ok(())
+        }
+        Some(("compact", matches)) => {
+            let path = Path::new(matches.get_one::<String>("database").unwrap());
+            let output = Path::new(matches.get_one::<String>("output").unwrap());
+
+            let env = MdbxDatabase::new_read_only(path, DatabaseConfig::default())?;
+            let num_entries = env.copy_compacted(output, DatabaseConfig::default())?;
+
+            println!(
+                "Copied {num_entries} entries, data file size {} -> {} bytes",
+                data_file_size(path)?,
+                data_file_size(output)?
+            );
+            Ok(())
+        }
+        Some(("check", matches)) => {
+            let network_id = NetworkId::from_str(matches.get_one::<String>("network").unwrap())?;
+            let keep_history = !matches.get_flag("full");
+            let config = BlockchainConfig {
+                keep_history,
+                index_history: keep_history,
+                ..Default::default()
+            };
+
+            let env = MdbxDatabase::new(
+                matches.get_one::<String>("database").unwrap(),
+                DatabaseConfig::default(),
+            )?;
+            let blockchain = Blockchain::new(env, config, network_id, Arc::new(OffsetTime::new()))?;
+            let repair = matches.get_flag("repair");
+            let report = blockchain.check_integrity(repair);
+            println!("{report:#?}");
+
+            // Check again to see whether the repair succeeded.
+            if repair
+                && !report.is_consistent()
+                && !blockchain.check_integrity(false).is_consistent()
+            {
+                eprintln!("The database could not be fully repaired");
+                process::exit(1);
+            }
+            if !repair && !report.is_consistent() {
+                process::exit(1);
+            }
+            Ok(())
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn main() {
+    if let Err(e) = run_app() {
+        eprintln!("Error: {e}");
+        process::exit(1);
+    }
+}
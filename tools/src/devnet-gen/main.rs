@@ -0,0 +1,533 @@
+use std::{
+    fmt::Display,
+    fs,
+    io::{self, BufRead, Write},
+    path::PathBuf,
+    str::FromStr,
+};
+
+use anyhow::{bail, Error};
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
+use nimiq_bls::KeyPair as BlsKeyPair;
+use nimiq_database::mdbx::{DatabaseConfig, MdbxDatabase};
+use nimiq_genesis_builder::{
+    config::{GenesisAccount, GenesisConfig, GenesisPolicy, GenesisStaker, GenesisValidator},
+    GenesisBuilder,
+};
+use nimiq_keys::{Address, KeyPair, SecureGenerate};
+use nimiq_primitives::{
+    coin::Coin,
+    networks::NetworkId,
+    policy::{Policy, TEST_POLICY},
+};
+use nimiq_serde::Serialize as _;
+use serde::Serialize;
+use time::OffsetDateTime;
+
+/// Name of the genesis file inside the output directory and inside the containers.
+const GENESIS_FILE: &str = "genesis.toml";
+/// Directory the client reads its configuration from inside the containers.
+const CONTAINER_CONFIG_DIR: &str = "/home/nimiq/.nimiq";
+/// The containers are attached to this subnet, validator `i` gets the address `SUBNET.(i + 1)`.
+const SUBNET: &str = "7.0.0";
+const P2P_PORT: u16 = 8443;
+const RPC_PORT: u16 = 8648;
+
+/// The policy presets a devnet can be generated for.
+#[derive(Clone, Copy, Debug)]
+enum PolicyPreset {
+    /// The policy of the main network.
+    Default,
+    /// The policy used by the unit tests, with short batches and epochs.
+    Test,
+}
+
+impl PolicyPreset {
+    fn policy(self) -> Policy {
+        match self {
+            PolicyPreset::Default => Policy::default(),
+            PolicyPreset::Test => TEST_POLICY,
+        }
+    }
+}
+
+impl FromStr for PolicyPreset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "default" => Ok(PolicyPreset::Default),
+            "test" => Ok(PolicyPreset::Test),
+            _ => Err(format!("Unknown policy preset: {s}")),
+        }
+    }
+}
+
+impl Display for PolicyPreset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PolicyPreset::Default => write!(f, "default"),
+            PolicyPreset::Test => write!(f, "test"),
+        }
+    }
+}
+
+/// The parameters of the devnet to generate.
+struct Settings {
+    output: PathBuf,
+    validators: usize,
+    stakers: usize,
+    validator_stake: Coin,
+    staker_stake: Coin,
+    account_balance: Coin,
+    /// The commission of each validator in basis points.
+    commission: u16,
+    policy: PolicyPreset,
+    image: String,
+}
+
+/// The keys of a validator, written to its key file.
+#[derive(Serialize)]
+struct ValidatorKeys {
+    validator_address: String,
+    /// The cold key controlling the validator.
+    validator_private_key: String,
+    signing_public_key: String,
+    signing_private_key: String,
+    voting_public_key: String,
+    voting_secret_key: String,
+    /// The address receiving the rewards. Its key is also used as fee key.
+    reward_address: String,
+    reward_private_key: String,
+}
+
+/// The keys of a staker, written to the staker key file.
+#[derive(Serialize)]
+struct StakerKeys {
+    staker_address: String,
+    private_key: String,
+    delegation: String,
+}
+
+#[derive(Serialize)]
+struct StakerKeyFile {
+    stakers: Vec<StakerKeys>,
+}
+
+fn hex_private_key(key_pair: &KeyPair) -> String {
+    hex::encode(key_pair.private.as_bytes())
+}
+
+fn coin_arg(
+    id: &'static str,
+    long: &'static str,
+    default: &'static str,
+    help: &'static str,
+) -> Arg {
+    Arg::new(id)
+        .long(long)
+        .value_name("NIM")
+        .default_value(default)
+        .value_parser(|s: &str| Coin::from_str(s).map_err(|e| e.to_string()))
+        .help(help)
+}
+
+/// Asks for a value on the terminal, returning `default` if the answer is empty.
+fn prompt<T: FromStr + Display>(question: &str, default: T) -> Result<T, Error>
+where
+    T::Err: Display,
+{
+    let stdin = io::stdin();
+    loop {
+        print!("{question} [{default}]: ");
+        io::stdout().flush()?;
+
+        let mut answer = String::new();
+        if stdin.lock().read_line(&mut answer)? == 0 {
+            return Ok(default);
+        }
+        let answer = answer.trim();
+        if answer.is_empty() {
+            return Ok(default);
+        }
+        match T::from_str(answer) {
+            Ok(value) => return Ok(value),
+            Err(error) => eprintln!("{error}"),
+        }
+    }
+}
+
+fn settings(matches: &ArgMatches) -> Result<Settings, Error> {
+    let mut settings = Settings {
+        output: matches.get_one::<PathBuf>("output").unwrap().clone(),
+        validators: *matches.get_one::<usize>("validators").unwrap(),
+        stakers: *matches.get_one::<usize>("stakers").unwrap(),
+        validator_stake: *matches.get_one::<Coin>("validator_stake").unwrap(),
+        staker_stake: *matches.get_one::<Coin>("staker_stake").unwrap(),
+        account_balance: *matches.get_one::<Coin>("account_balance").unwrap(),
+        commission: *matches.get_one::<u16>("commission").unwrap(),
+        policy: *matches.get_one::<PolicyPreset>("policy").unwrap(),
+        image: matches.get_one::<String>("image").unwrap().clone(),
+    };
+
+    if matches.get_flag("interactive") {
+        settings.validators = prompt("Number of validators", settings.validators)?;
+        settings.stakers = prompt("Number of additional stakers", settings.stakers)?;
+        settings.validator_stake = prompt("Stake of each validator", settings.validator_stake)?;
+        settings.staker_stake = prompt("Stake of each staker", settings.staker_stake)?;
+        settings.account_balance = prompt("Balance of each account", settings.account_balance)?;
+        settings.commission = prompt(
+            "Commission of each validator in basis points",
+            settings.commission,
+        )?;
+        settings.policy = prompt("Policy preset (default, test)", settings.policy)?;
+        settings.image = prompt("Docker image", settings.image)?;
+    }
+
+    if settings.validators == 0 {
+        bail!("A devnet needs at least one validator");
+    }
+    if settings.validators > 150 {
+        // The validators get consecutive addresses in the subnet, see `SUBNET`.
+        bail!("A devnet can have at most 150 validators");
+    }
+    if settings.commission > Policy::MAX_VALIDATOR_COMMISSION {
+        bail!(
+            "The commission can be at most {} basis points",
+            Policy::MAX_VALIDATOR_COMMISSION
+        );
+    }
+    Ok(settings)
+}
+
+fn client_config(
+    index: usize,
+    validator: &GenesisValidator,
+    signing_key: &KeyPair,
+    voting_key: &BlsKeyPair,
+    fee_key: &KeyPair,
+) -> String {
+    let seed_nodes = if index == 0 {
+        String::new()
+    } else {
+        format!("seed_nodes = [\n    {{ address = \"/ip4/{SUBNET}.2/tcp/{P2P_PORT}/ws\" }},\n]\n")
+    };
+
+    format!(
+        r#"[network]
+peer_key_file = "{CONTAINER_CONFIG_DIR}/peer_key.dat"
+listen_addresses = [
+    "/ip4/{SUBNET}.{ip}/tcp/{P2P_PORT}/ws",
+]
+{seed_nodes}
+[consensus]
+network = "dev-albatross"
+sync_mode = "full"
+min_peers = 1
+
+[database]
+path = "{CONTAINER_CONFIG_DIR}"
+
+[log]
+level = "info"
+timestamps = true
+
+[rpc-server]
+bind = "0.0.0.0"
+port = {RPC_PORT}
+
+[validator]
+validator_address = "{validator_address}"
+signing_key_file = "{CONTAINER_CONFIG_DIR}/signing_key.dat"
+signing_key = "{signing_key}"
+voting_key_file = "{CONTAINER_CONFIG_DIR}/voting_key.dat"
+voting_key = "{voting_key}"
+fee_key_file = "{CONTAINER_CONFIG_DIR}/fee_key.dat"
+fee_key = "{fee_key}"
+automatic_reactivate = true
+"#,
+        ip = index + 2,
+        validator_address = validator.validator_address.to_user_friendly_address(),
+        signing_key = hex_private_key(signing_key),
+        voting_key = hex::encode(voting_key.secret_key.serialize_to_vec()),
+        fee_key = hex_private_key(fee_key),
+    )
+}
+
+fn docker_compose(settings: &Settings) -> String {
+    let mut compose = format!(
+        r#"networks:
+  devnet:
+    name: nimiq.local
+    driver: bridge
+    ipam:
+      driver: default
+      config:
+        - subnet: {SUBNET}.0/24
+
+services:
+"#
+    );
+
+    for index in 0..settings.validators {
+        let name = format!("validator{}", index + 1);
+        let depends_on = if index == 0 {
+            String::new()
+        } else {
+            "    depends_on:\n      - validator1\n".to_string()
+        };
+        compose.push_str(&format!(
+            r#"  {name}:
+    image: {image}
+{depends_on}    environment:
+      - NIMIQ_OVERRIDE_DEVNET_CONFIG=/home/nimiq/{GENESIS_FILE}
+    ports:
+      - {P2P_PORT}
+      - {rpc_port}:{RPC_PORT}
+    networks:
+      devnet:
+        ipv4_address: {SUBNET}.{ip}
+    volumes:
+      - ./{name}:{CONTAINER_CONFIG_DIR}:rw
+      - ./{GENESIS_FILE}:/home/nimiq/{GENESIS_FILE}:ro
+
+"#,
+            image = settings.image,
+            rpc_port = RPC_PORT as usize + index,
+            ip = index + 2,
+        ));
+    }
+    compose
+}
+
+fn generate(settings: &Settings) -> Result<(), Error> {
+    let output = &settings.output;
+    if output.exists() && fs::read_dir(output)?.next().is_some() {
+        bail!("Output directory {} is not empty", output.display());
+    }
+    fs::create_dir_all(output)?;
+
+    // The genesis block is verified below, which requires the policy to be installed.
+    let policy = settings.policy.policy();
+    let _ = Policy::get_or_init(policy);
+
+    // All nodes build the genesis block from the config, so the timestamp must be fixed.
+    // Genesis timestamps need to be on whole seconds.
+    let now = OffsetDateTime::now_utc();
+    let timestamp = OffsetDateTime::from_unix_timestamp(now.unix_timestamp())?;
+
+    let mut genesis = GenesisConfig {
+        network: NetworkId::DevAlbatross,
+        timestamp: Some(timestamp),
+        vrf_seed: None,
+        parent_election_hash: None,
+        parent_hash: None,
+        history_root: None,
+        block_number: policy.genesis_block_number,
+        validators: Vec::new(),
+        stakers: Vec::new(),
+        basic_accounts: Vec::new(),
+        vesting_accounts: Vec::new(),
+        htlc_accounts: Vec::new(),
+        supply: None,
+        state_root: None,
+        slots: Vec::new(),
+        policy: match settings.policy {
+            PolicyPreset::Default => None,
+            PolicyPreset::Test => Some(GenesisPolicy::from(policy)),
+        },
+    };
+
+    for index in 0..settings.validators {
+        let validator_key = KeyPair::generate_default_csprng();
+        let signing_key = KeyPair::generate_default_csprng();
+        let voting_key = BlsKeyPair::generate_default_csprng();
+        let reward_key = KeyPair::generate_default_csprng();
+
+        let validator = GenesisValidator {
+            validator_address: Address::from(&validator_key),
+            signing_key: signing_key.public,
+            voting_key: voting_key.public_key,
+            reward_address: Address::from(&reward_key),
+            commission: settings.commission,
+            inactive_from: None,
+            jailed_from: None,
+            retired: false,
+        };
+
+        // The reward address stakes for the validator and pays the fees of the validator.
+        genesis.stakers.push(GenesisStaker {
+            staker_address: validator.reward_address.clone(),
+            balance: settings.validator_stake,
+            delegation: validator.validator_address.clone(),
+            inactive_balance: Coin::ZERO,
+            inactive_from: None,
+        });
+        genesis.basic_accounts.push(GenesisAccount {
+            address: validator.reward_address.clone(),
+            balance: settings.account_balance,
+        });
+
+        let keys = ValidatorKeys {
+            validator_address: validator.validator_address.to_user_friendly_address(),
+            validator_private_key: hex_private_key(&validator_key),
+            signing_public_key: signing_key.public.to_hex(),
+            signing_private_key: hex_private_key(&signing_key),
+            voting_public_key: voting_key.public_key.compress().to_hex(),
+            voting_secret_key: hex::encode(voting_key.secret_key.serialize_to_vec()),
+            reward_address: validator.reward_address.to_user_friendly_address(),
+            reward_private_key: hex_private_key(&reward_key),
+        };
+
+        let dir = output.join(format!("validator{}", index + 1));
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join("keys.toml"), toml::to_string(&keys)?)?;
+        fs::write(
+            dir.join("client.toml"),
+            client_config(index, &validator, &signing_key, &voting_key, &reward_key),
+        )?;
+
+        genesis.validators.push(validator);
+    }
+
+    let mut staker_keys = Vec::with_capacity(settings.stakers);
+    for index in 0..settings.stakers {
+        let key_pair = KeyPair::generate_default_csprng();
+        let staker_address = Address::from(&key_pair);
+        // Spread the stakers evenly across the validators.
+        let delegation = genesis.validators[index % settings.validators]
+            .validator_address
+            .clone();
+
+        genesis.stakers.push(GenesisStaker {
+            staker_address: staker_address.clone(),
+            balance: settings.staker_stake,
+            delegation: delegation.clone(),
+            inactive_balance: Coin::ZERO,
+            inactive_from: None,
+        });
+        genesis.basic_accounts.push(GenesisAccount {
+            address: staker_address.clone(),
+            balance: settings.account_balance,
+        });
+        staker_keys.push(StakerKeys {
+            staker_address: staker_address.to_user_friendly_address(),
+            private_key: hex_private_key(&key_pair),
+            delegation: delegation.to_user_friendly_address(),
+        });
+    }
+    if !staker_keys.is_empty() {
+        fs::write(
+            output.join("stakers.toml"),
+            toml::to_string(&StakerKeyFile {
+                stakers: staker_keys,
+            })?,
+        )?;
+    }
+
+    // Make sure the nodes will be able to build the genesis block from the config.
+    let db = MdbxDatabase::new_volatile(DatabaseConfig {
+        size: Some(0..100 * 1024 * 1024 * 1024),
+        ..Default::default()
+    })?;
+    let genesis_info = GenesisBuilder::from_config(genesis.clone())?.generate(db)?;
+
+    fs::write(output.join(GENESIS_FILE), toml::to_string(&genesis)?)?;
+    fs::write(output.join("docker-compose.yml"), docker_compose(settings))?;
+
+    println!(
+        "Generated devnet with {} validators and {} stakers in {}",
+        settings.validators,
+        settings.stakers,
+        output.display()
+    );
+    println!("Genesis block hash: {}", genesis_info.block.hash());
+    println!("Policy preset: {}", settings.policy);
+    println!(
+        "Start it with `docker compose up` in {}. The RPC server of validator i listens on port {} + i - 1.",
+        output.display(),
+        RPC_PORT
+    );
+    Ok(())
+}
+
+fn main() -> Result<(), Error> {
+    let matches = Command::new("nimiq-devnet-gen")
+        .about("Generates the genesis, keys and configuration files of a local devnet")
+        .arg(
+            Arg::new("output")
+                .value_name("OUTPUT_DIR")
+                .value_parser(value_parser!(PathBuf))
+                .required(true)
+                .help("Directory to write the devnet to, must be empty"),
+        )
+        .arg(
+            Arg::new("validators")
+                .short('n')
+                .long("validators")
+                .value_name("N")
+                .default_value("4")
+                .value_parser(value_parser!(usize))
+                .help("Number of validators"),
+        )
+        .arg(
+            Arg::new("stakers")
+                .short('m')
+                .long("stakers")
+                .value_name("M")
+                .default_value("0")
+                .value_parser(value_parser!(usize))
+                .help("Number of stakers in addition to the ones staking for each validator"),
+        )
+        .arg(coin_arg(
+            "validator_stake",
+            "validator-stake",
+            "1000000",
+            "Stake delegated to each validator by its reward address",
+        ))
+        .arg(coin_arg(
+            "staker_stake",
+            "staker-stake",
+            "10000",
+            "Stake of each additional staker",
+        ))
+        .arg(coin_arg(
+            "account_balance",
+            "account-balance",
+            "1000000",
+            "Balance of the basic account of each reward address and staker",
+        ))
+        .arg(
+            Arg::new("commission")
+                .long("commission")
+                .value_name("BASIS_POINTS")
+                .default_value("1000")
+                .value_parser(value_parser!(u16))
+                .help("Commission each validator charges on its rewards, in basis points (1/100 of a percent)"),
+        )
+        .arg(
+            Arg::new("policy")
+                .long("policy")
+                .value_name("PRESET")
+                .default_value("default")
+                .value_parser(PolicyPreset::from_str)
+                .help("Policy preset, `default` or `test`. Written to the policy section of the genesis config"),
+        )
+        .arg(
+            Arg::new("image")
+                .long("image")
+                .value_name("IMAGE")
+                .default_value("ghcr.io/nimiq/core-rs-albatross:latest")
+                .help("Docker image of the nodes"),
+        )
+        .arg(
+            Arg::new("interactive")
+                .short('i')
+                .long("interactive")
+                .action(ArgAction::SetTrue)
+                .help("Ask for the parameters, using the arguments as defaults"),
+        )
+        .get_matches();
+
+    generate(&settings(&matches)?)
+}
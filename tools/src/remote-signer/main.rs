@@ -0,0 +1,129 @@
+use std::{process::exit, str::FromStr, sync::Arc};
+
+use anyhow::Error;
+use clap::{crate_authors, crate_description, crate_version, Arg, Command};
+use nimiq_bls::{KeyPair as BlsKeyPair, SecretKey as BlsSecretKey};
+use nimiq_keys::{KeyPair, PrivateKey};
+use nimiq_primitives::networks::NetworkId;
+use nimiq_serde::Deserialize;
+use nimiq_validator::{
+    key_utils::{SigningKeys, VotingKeys},
+    signer::{DoubleSignGuard, LocalSigner, RemoteSignerServer, SignerAddress},
+};
+use parking_lot::RwLock;
+use thiserror::Error;
+
+async fn run_app() -> Result<(), Error> {
+    let matches = Command::new("Remote signer")
+        .version(crate_version!())
+        .author(crate_authors!())
+        .about(crate_description!())
+        .arg(
+            Arg::new("listen")
+                .short('l')
+                .long("listen")
+                .value_name("ADDRESS")
+                .help("Listen on ADDRESS, either a TCP socket address or unix:<path>."),
+        )
+        .arg(
+            Arg::new("psk")
+                .short('p')
+                .long("psk")
+                .value_name("PSK")
+                .help("The 32 byte pre-shared key shared with the validator in hex format."),
+        )
+        .arg(
+            Arg::new("signing_key")
+                .short('s')
+                .long("signing-key")
+                .value_name("SECRET_KEY")
+                .help("The Ed25519 signing key of the validator in hex format."),
+        )
+        .arg(
+            Arg::new("voting_key")
+                .short('v')
+                .long("voting-key")
+                .value_name("SECRET_KEY")
+                .help("The BLS voting key of the validator in hex format."),
+        )
+        .arg(
+            Arg::new("network")
+                .short('n')
+                .long("network")
+                .value_name("NETWORK")
+                .help("The network the validator runs on. Messages for other networks are refused."),
+        )
+        .arg(
+            Arg::new("state")
+                .long("state")
+                .value_name("FILE")
+                .help("Persist the heights signed for to FILE, such that the signer doesn't sign conflicting messages after a restart."),
+        )
+        .get_matches();
+
+    let address = SignerAddress::from_str(
+        matches
+            .get_one::<String>("listen")
+            .ok_or(AppError::ListenAddress)?,
+    )?;
+    let psk: [u8; 32] = hex::decode(matches.get_one::<String>("psk").ok_or(AppError::Psk)?)?
+        .try_into()
+        .map_err(|_| AppError::Psk)?;
+    let signing_key: KeyPair = PrivateKey::from_str(
+        matches
+            .get_one::<String>("signing_key")
+            .ok_or(AppError::SigningKey)?,
+    )?
+    .into();
+    let voting_key: BlsKeyPair = BlsSecretKey::deserialize_from_vec(&hex::decode(
+        matches
+            .get_one::<String>("voting_key")
+            .ok_or(AppError::VotingKey)?,
+    )?)?
+    .into();
+
+    let network_id = NetworkId::from_str(
+        matches
+            .get_one::<String>("network")
+            .ok_or(AppError::Network)?,
+    )?;
+    let guard = match matches.get_one::<String>("state") {
+        Some(path) => DoubleSignGuard::with_file(network_id, path)?,
+        None => DoubleSignGuard::new(network_id),
+    };
+
+    let signer = LocalSigner::new(
+        Arc::new(RwLock::new(SigningKeys::new(vec![signing_key]))),
+        Arc::new(RwLock::new(VotingKeys::new(vec![voting_key]))),
+    );
+    println!("Serving signing requests on {address}");
+    RemoteSignerServer::new(Arc::new(signer), guard, psk)
+        .serve(&address)
+        .await?;
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() {
+    exit(match run_app().await {
+        Ok(_) => 0,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            1
+        }
+    });
+}
+
+#[derive(Debug, Error)]
+enum AppError {
+    #[error("Listen address is missing")]
+    ListenAddress,
+    #[error("Pre-shared key is missing or not 32 bytes long")]
+    Psk,
+    #[error("Signing key is missing")]
+    SigningKey,
+    #[error("Voting key is missing")]
+    VotingKey,
+    #[error("Network is missing")]
+    Network,
+}
@@ -0,0 +1,144 @@
+use std::{fs::File, io::BufReader, process, str::FromStr, sync::Arc};
+
+use clap::{Arg, ArgAction, Command};
+use nimiq_blockchain::{Blockchain, BlockchainConfig, ReplayResult};
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_database::mdbx::{DatabaseConfig, MdbxDatabase};
+use nimiq_hash::Blake2bHash;
+use nimiq_primitives::networks::NetworkId;
+use nimiq_utils::time::OffsetTime;
+use parking_lot::RwLock;
+
+fn db(path: Option<&String>) -> MdbxDatabase {
+    let config = DatabaseConfig {
+        size: Some(0..100 * 1024 * 1024 * 1024),
+        ..Default::default()
+    };
+    match path {
+        Some(path) => MdbxDatabase::new(path, config).expect("couldn't open database"),
+        None => MdbxDatabase::new_volatile(config).expect("couldn't open volatile database"),
+    }
+}
+
+fn summary(result: &ReplayResult) -> String {
+    match result {
+        ReplayResult::Push(Ok(result)) => format!("{result:?}"),
+        ReplayResult::Push(Err(error)) => format!("error: {error}"),
+        ReplayResult::PushWithChunks(Ok((result, Ok(chunks)))) => {
+            format!("{result:?}, {chunks:?}")
+        }
+        ReplayResult::PushWithChunks(Ok((result, Err(error)))) => {
+            format!("{result:?}, chunk error: {error}")
+        }
+        ReplayResult::PushWithChunks(Err(error)) => format!("error: {error}"),
+        ReplayResult::Chunks(Ok(result)) => format!("{result:?}"),
+        ReplayResult::Chunks(Err(error)) => format!("chunk error: {error}"),
+        ReplayResult::ValiditySync(root) => format!("history root {root}"),
+    }
+}
+
+fn main() {
+    let matches = Command::new("nimiq-replay")
+        .about("Replays recorded blockchain inputs and compares the resulting state roots")
+        .arg(
+            Arg::new("recording")
+                .value_name("RECORDING")
+                .help("Path to a recording of blockchain inputs")
+                .required(true)
+                .num_args(1..),
+        )
+        .arg(
+            Arg::new("network")
+                .short('n')
+                .long("network")
+                .value_name("NETWORK")
+                .default_value("main-albatross")
+                .help("Network the recordings were taken on"),
+        )
+        .arg(
+            Arg::new("database")
+                .short('d')
+                .long("database")
+                .value_name("PATH")
+                .help("Database to replay a single recording into, instead of a fresh blockchain"),
+        )
+        .arg(
+            Arg::new("state-root")
+                .long("state-root")
+                .value_name("HASH")
+                .help("Expected state root after replaying"),
+        )
+        .arg(
+            Arg::new("verbose")
+                .short('v')
+                .long("verbose")
+                .action(ArgAction::SetTrue)
+                .help("Show the result of every replayed input"),
+        )
+        .get_matches();
+
+    let recordings: Vec<&String> = matches.get_many::<String>("recording").unwrap().collect();
+    let network_id = NetworkId::from_str(matches.get_one::<String>("network").unwrap())
+        .unwrap_or_else(|error| {
+            eprintln!("{error}");
+            process::exit(2);
+        });
+    let database = matches.get_one::<String>("database");
+    let expected_state_root = matches.get_one::<String>("state-root").map(|hash| {
+        Blake2bHash::from_str(hash).unwrap_or_else(|error| {
+            eprintln!("invalid state root: {error}");
+            process::exit(2);
+        })
+    });
+    let verbose = matches.get_flag("verbose");
+
+    if database.is_some() && recordings.len() > 1 {
+        eprintln!("only a single recording can be replayed into a database");
+        process::exit(2);
+    }
+
+    let time = Arc::new(OffsetTime::new());
+    let mut state_roots = vec![];
+    for recording in recordings {
+        let blockchain = Blockchain::new(
+            db(database),
+            BlockchainConfig::default(),
+            network_id,
+            Arc::clone(&time),
+        )
+        .expect("couldn't create blockchain");
+        let blockchain = RwLock::new(blockchain);
+
+        let file = File::open(recording).unwrap_or_else(|error| {
+            eprintln!("{recording}: {error}");
+            process::exit(2);
+        });
+
+        let mut num_inputs = 0;
+        let result = Blockchain::replay(&blockchain, BufReader::new(file), |_, result| {
+            num_inputs += 1;
+            if verbose {
+                eprintln!("{recording}: input {num_inputs}: {}", summary(result));
+            }
+        });
+        if let Err(error) = result {
+            eprintln!("{recording}: replay failed after {num_inputs} inputs: {error}");
+            process::exit(1);
+        }
+
+        let blockchain = blockchain.read();
+        let state_root = blockchain.state.accounts.get_root_hash_assert(None);
+        println!(
+            "{recording}: replayed {num_inputs} inputs, head #{} {}, state root {state_root}",
+            blockchain.block_number(),
+            blockchain.head_hash(),
+        );
+        state_roots.push(state_root);
+    }
+
+    let expected_state_root = expected_state_root.unwrap_or_else(|| state_roots[0].clone());
+    if state_roots.iter().any(|root| *root != expected_state_root) {
+        eprintln!("state roots don't match, expected {expected_state_root}");
+        process::exit(1);
+    }
+}
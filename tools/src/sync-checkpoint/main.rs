@@ -0,0 +1,124 @@
+use std::{fs, path::Path, process, str::FromStr, sync::Arc};
+
+use clap::{Arg, Command};
+use nimiq_blockchain::{Blockchain, BlockchainConfig};
+use nimiq_blockchain_interface::AbstractBlockchain;
+use nimiq_database::mdbx::{DatabaseConfig, MdbxDatabase};
+use nimiq_genesis::{SignedSyncCheckpoint, SyncCheckpoint};
+use nimiq_keys::{KeyPair, PrivateKey};
+use nimiq_primitives::networks::NetworkId;
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_utils::{
+    tagged_signing::{TaggedKeyPair, TaggedSigned},
+    time::OffsetTime,
+};
+use nimiq_zkp_component::proof_store::{DBProofStore, ProofStore};
+
+fn exit_with(message: String) -> ! {
+    eprintln!("{message}");
+    process::exit(2);
+}
+
+/// Reads the checkpoints already in the file at `path`, if it exists.
+fn read_checkpoints(path: &Path) -> Vec<SignedSyncCheckpoint> {
+    if !path.exists() {
+        return vec![];
+    }
+    let data = fs::read(path).unwrap_or_else(|error| exit_with(format!("{path:?}: {error}")));
+    Deserialize::deserialize_from_vec(&data)
+        .unwrap_or_else(|error| exit_with(format!("{path:?}: invalid checkpoints: {error}")))
+}
+
+fn main() {
+    let matches = Command::new("nimiq-sync-checkpoint")
+        .about(
+            "Creates a signed sync checkpoint from the latest zk proof of a node's database and \
+             adds it to a checkpoints file",
+        )
+        .arg(
+            Arg::new("database")
+                .short('d')
+                .long("database")
+                .value_name("PATH")
+                .required(true)
+                .help("Database of a node that has verified the latest zk proof"),
+        )
+        .arg(
+            Arg::new("network")
+                .short('n')
+                .long("network")
+                .value_name("NETWORK")
+                .default_value("main-albatross")
+                .help("Network of the database"),
+        )
+        .arg(
+            Arg::new("secret-key")
+                .short('k')
+                .long("secret-key")
+                .value_name("SECRET_KEY")
+                .required(true)
+                .help("Ed25519 secret key the checkpoint is signed with, in hex"),
+        )
+        .arg(
+            Arg::new("output")
+                .short('o')
+                .long("output")
+                .value_name("FILE")
+                .required(true)
+                .help(
+                    "Checkpoints file the new checkpoint is added to, created if it doesn't exist",
+                ),
+        )
+        .get_matches();
+
+    let network_id = NetworkId::from_str(matches.get_one::<String>("network").unwrap())
+        .unwrap_or_else(|error| exit_with(error.to_string()));
+    let key_pair = PrivateKey::from_str(matches.get_one::<String>("secret-key").unwrap())
+        .map(KeyPair::from)
+        .unwrap_or_else(|error| exit_with(format!("invalid secret key: {error}")));
+    let output = Path::new(matches.get_one::<String>("output").unwrap());
+
+    let env = MdbxDatabase::new(
+        matches.get_one::<String>("database").unwrap(),
+        DatabaseConfig::default(),
+    )
+    .unwrap_or_else(|error| exit_with(format!("couldn't open database: {error}")));
+    let blockchain = Blockchain::new(
+        env.clone(),
+        BlockchainConfig::default(),
+        network_id,
+        Arc::new(OffsetTime::new()),
+    )
+    .unwrap_or_else(|error| exit_with(format!("couldn't load blockchain: {error}")));
+
+    let Some(zk_proof) = DBProofStore::new(env).get_zkp() else {
+        exit_with("the database contains no zk proof".to_owned());
+    };
+    if zk_proof.proof.is_none() {
+        exit_with("the zk proof of the database is still the one of the genesis block".to_owned());
+    }
+    let block = blockchain
+        .get_block_at(zk_proof.block_number, true)
+        .unwrap_or_else(|error| {
+            exit_with(format!(
+                "couldn't get election block {}: {error}",
+                zk_proof.block_number
+            ))
+        })
+        .unwrap_macro();
+
+    let checkpoint = SyncCheckpoint::new(block, zk_proof.serialize_to_vec());
+    let signature = key_pair.tagged_sign(&checkpoint);
+
+    let mut checkpoints = read_checkpoints(output);
+    checkpoints
+        .retain(|existing| existing.record.block.block_number() != checkpoint.block.block_number());
+    checkpoints.push(TaggedSigned::new(checkpoint, signature));
+    fs::write(output, checkpoints.serialize_to_vec())
+        .unwrap_or_else(|error| exit_with(format!("{output:?}: {error}")));
+
+    println!(
+        "Added checkpoint at block {} signed by {} to {output:?}",
+        zk_proof.block_number, key_pair.public
+    );
+}
@@ -55,8 +55,10 @@ fn main() {
     let hash = genesis_info.block.hash();
     log!("{}: generated genesis block hash", hash);
     log!("generating config...");
-    let genesis_config_trimmed =
+    let mut genesis_config_trimmed =
         GenesisConfig::trimmed_from_genesis(&genesis_info.block.unwrap_macro().header);
+    // The policy isn't part of the genesis block, so it has to be kept explicitly.
+    genesis_config_trimmed.policy = genesis_builder.policy;
 
     if !no_verify {
         log!("reading trimmed config...");
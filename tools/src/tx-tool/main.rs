@@ -0,0 +1,250 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    io::{stdin, Read},
+    process::exit,
+    str::FromStr,
+};
+
+use anyhow::Error;
+use clap::{
+    crate_authors, crate_description, crate_version, value_parser, Arg, ArgAction, ArgMatches,
+    Command,
+};
+use nimiq_primitives::networks::NetworkId;
+use nimiq_serde::Deserialize;
+use nimiq_transaction::{Transaction, TransactionFlags};
+use nimiq_web_client::common::transaction::{PlainTransaction, Transaction as WebTransaction};
+use serde::Serialize;
+use serde_json::Value;
+use thiserror::Error;
+
+/// The decoded transaction together with the information that is not part of its plain
+/// representation.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Inspection {
+    transaction: PlainTransaction,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    contract_creation_address: Option<String>,
+    verification: Verification,
+}
+
+/// The result of verifying the transaction for a specific network.
+#[derive(Serialize)]
+struct Verification {
+    network: String,
+    valid: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+fn transaction_arg(id: &'static str, help: &'static str) -> Arg {
+    Arg::new(id).value_name("TRANSACTION").help(help)
+}
+
+fn genesis_args(command: Command) -> Command {
+    command
+        .arg(
+            Arg::new("genesis_block_number")
+                .long("genesis-block-number")
+                .value_name("HEIGHT")
+                .value_parser(value_parser!(u32))
+                .help("Genesis block number used to convert vesting and HTLC data of PoW transactions"),
+        )
+        .arg(
+            Arg::new("genesis_timestamp")
+                .long("genesis-timestamp")
+                .value_name("TIMESTAMP")
+                .value_parser(value_parser!(u64))
+                .help("Genesis timestamp used to convert vesting and HTLC data of PoW transactions"),
+        )
+}
+
+fn run_app() -> Result<(), Error> {
+    let decode = Command::new("decode")
+        .about("Decode a transaction, verify it and print it as JSON")
+        .arg(transaction_arg(
+            "transaction",
+            "The serialized transaction as hex. Read from STDIN if omitted.",
+        ))
+        .arg(
+            Arg::new("file")
+                .short('f')
+                .long("file")
+                .value_name("FILE")
+                .conflicts_with("transaction")
+                .help("Read the serialized transaction as raw bytes from FILE"),
+        )
+        .arg(
+            Arg::new("network_id")
+                .short('N')
+                .long("network")
+                .value_name("NETWORK")
+                .help("Verify the transaction for NETWORK instead of its own network"),
+        );
+    let diff = Command::new("diff")
+        .about("Decode two transactions and print the fields in which they differ")
+        .arg(transaction_arg("first", "The first serialized transaction as hex").required(true))
+        .arg(transaction_arg("second", "The second serialized transaction as hex").required(true))
+        .arg(
+            Arg::new("raw")
+                .long("raw")
+                .help("Read the transactions as raw bytes from the given files instead")
+                .action(ArgAction::SetTrue),
+        );
+
+    let matches = Command::new("nimiq-tx-tool")
+        .version(crate_version!())
+        .author(crate_authors!())
+        .about(crate_description!())
+        .subcommand_required(true)
+        .subcommand(genesis_args(decode))
+        .subcommand(genesis_args(diff))
+        .get_matches();
+
+    match matches.subcommand() {
+        Some(("decode", matches)) => {
+            let tx = match (
+                matches.get_one::<String>("transaction"),
+                matches.get_one::<String>("file"),
+            ) {
+                (Some(hex), _) => parse_hex(hex)?,
+                (None, Some(path)) => parse_bytes(&fs::read(path)?)?,
+                (None, None) => {
+                    let mut hex = String::new();
+                    stdin().read_to_string(&mut hex)?;
+                    parse_hex(&hex)?
+                }
+            };
+            let network_id = match matches.get_one::<String>("network_id") {
+                Some(s) => NetworkId::from_str(s)?,
+                None => tx.network_id,
+            };
+
+            let inspection = inspect(tx, network_id, matches);
+            println!("{}", serde_json::to_string_pretty(&inspection)?);
+            Ok(())
+        }
+        Some(("diff", matches)) => {
+            let read = |id: &str| -> Result<Transaction, Error> {
+                let arg = matches.get_one::<String>(id).expect("argument is required");
+                if matches.get_flag("raw") {
+                    parse_bytes(&fs::read(arg)?)
+                } else {
+                    parse_hex(arg)
+                }
+            };
+            let first = to_json(read("first")?, matches)?;
+            let second = to_json(read("second")?, matches)?;
+
+            let differences = diff(&first, &second);
+            if differences.is_empty() {
+                println!("Transactions are identical");
+            }
+            for (field, (first, second)) in differences {
+                println!("{field}: {first} != {second}");
+            }
+            Ok(())
+        }
+        _ => unreachable!("a subcommand is required"),
+    }
+}
+
+fn parse_hex(hex: &str) -> Result<Transaction, Error> {
+    let hex = hex.trim();
+    let hex = hex.strip_prefix("0x").unwrap_or(hex);
+    parse_bytes(&hex::decode(hex)?)
+}
+
+fn parse_bytes(bytes: &[u8]) -> Result<Transaction, Error> {
+    let (tx, rest) = Transaction::deserialize_take(bytes)?;
+    if !rest.is_empty() {
+        return Err(AppError::TrailingBytes(rest.len()).into());
+    }
+    Ok(tx)
+}
+
+fn to_plain(tx: Transaction, matches: &ArgMatches) -> PlainTransaction {
+    WebTransaction::from(tx).to_plain_transaction(
+        matches.get_one::<u32>("genesis_block_number").copied(),
+        matches.get_one::<u64>("genesis_timestamp").copied(),
+    )
+}
+
+fn inspect(tx: Transaction, network_id: NetworkId, matches: &ArgMatches) -> Inspection {
+    let contract_creation_address = tx
+        .flags
+        .contains(TransactionFlags::CONTRACT_CREATION)
+        .then(|| tx.contract_creation_address().to_user_friendly_address());
+    let error = tx.verify(network_id).err().map(|error| error.to_string());
+
+    Inspection {
+        transaction: to_plain(tx, matches),
+        contract_creation_address,
+        verification: Verification {
+            network: network_id.to_string(),
+            valid: error.is_none(),
+            error,
+        },
+    }
+}
+
+fn to_json(tx: Transaction, matches: &ArgMatches) -> Result<Value, Error> {
+    Ok(serde_json::to_value(to_plain(tx, matches))?)
+}
+
+/// Compares two JSON values field by field and returns the differing fields by their path.
+fn diff(first: &Value, second: &Value) -> BTreeMap<String, (Value, Value)> {
+    let mut differences = BTreeMap::new();
+    diff_into(String::new(), first, second, &mut differences);
+    differences
+}
+
+fn diff_into(
+    path: String,
+    first: &Value,
+    second: &Value,
+    differences: &mut BTreeMap<String, (Value, Value)>,
+) {
+    match (first, second) {
+        (Value::Object(first), Value::Object(second)) => {
+            let mut keys: Vec<_> = first.keys().chain(second.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                diff_into(
+                    path,
+                    first.get(key).unwrap_or(&Value::Null),
+                    second.get(key).unwrap_or(&Value::Null),
+                    differences,
+                );
+            }
+        }
+        (first, second) if first != second => {
+            differences.insert(path, (first.clone(), second.clone()));
+        }
+        _ => {}
+    }
+}
+
+fn main() {
+    exit(match run_app() {
+        Ok(_) => 0,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            1
+        }
+    });
+}
+
+#[derive(Debug, Error)]
+enum AppError {
+    #[error("Transaction is followed by {0} unexpected bytes")]
+    TrailingBytes(usize),
+}
@@ -1,6 +1,6 @@
 use nimiq_bls::KeyPair as BlsKeyPair;
 use nimiq_hash::Blake2bHash;
-use nimiq_keys::{Address, Ed25519PublicKey, KeyPair};
+use nimiq_keys::{Address, Ed25519PublicKey, Ed25519Signature, KeyPair};
 use nimiq_primitives::{coin::Coin, networks::NetworkId, policy::Policy};
 use nimiq_transaction::{
     account::htlc_contract::{AnyHash, PreImage},
@@ -96,6 +96,7 @@ pub struct TransactionBuilder {
     fee: Option<Coin>,
     recipient: Option<Recipient>,
     validity_start_height: Option<u32>,
+    expiry_timestamp: Option<u64>,
     network_id: Option<NetworkId>,
 }
 
@@ -325,6 +326,26 @@ impl TransactionBuilder {
         self
     }
 
+    /// Sets the `expiry_timestamp` for the transaction.
+    ///
+    /// The expiry timestamp is an *optional* field. If set, the transaction can only be included
+    /// in blocks with a timestamp (in milliseconds) up to and including it, even if the
+    /// transaction is still within its validity window. Expiry timestamps are only supported from
+    /// protocol version 2 on, so the validity start height must not be before its activation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use nimiq_transaction_builder::TransactionBuilder;
+    ///
+    /// let mut builder = TransactionBuilder::new();
+    /// builder.with_expiry_timestamp(1_700_000_000_000);
+    /// ```
+    pub fn with_expiry_timestamp(&mut self, expiry_timestamp: u64) -> &mut Self {
+        self.expiry_timestamp = Some(expiry_timestamp);
+        self
+    }
+
     /// This method tries putting together the preliminary transaction
     /// in order to move to the proof building phase by returning a [`TransactionProofBuilder`].
     ///
@@ -373,7 +394,7 @@ impl TransactionBuilder {
         }
 
         // Currently, the flags for creation & signaling can never occur at the same time.
-        let tx = if recipient.is_creation() {
+        let mut tx = if recipient.is_creation() {
             Transaction::new_contract_creation(
                 sender.address(),
                 sender.account_type(),
@@ -410,6 +431,7 @@ impl TransactionBuilder {
                 network_id,
             )
         };
+        tx.set_expiry_timestamp(self.expiry_timestamp);
 
         Ok(TransactionProofBuilder::new(tx))
     }
@@ -1160,6 +1182,317 @@ impl TransactionBuilder {
         Ok(builder.generate().unwrap())
     }
 
+    /// Creates a transaction to set whether a given staker compounds its rewards, i.e. whether its
+    /// share of its validator's rewards is credited to its active balance instead of its retired
+    /// balance.
+    ///
+    /// # Arguments
+    ///
+    ///  - `key_pair`:              The optional key pair used to sign the outgoing transaction. If
+    ///                             it is given, the fee will be paid from the basic account
+    ///                             belonging to this key pair.
+    ///  - `staker_key_pair`:       The key pair used to sign the incoming transaction. The staker
+    ///                             address will be derived from this key pair.
+    ///  - `compound_rewards`:      Whether the staker's rewards are compounded.
+    ///  - `fee`:                   Transaction fee.
+    ///  - `validity_start_height`: Block height from which this transaction is valid.
+    ///  - `network_id`:            ID of network for which the transaction is meant.
+    ///
+    /// # Returns
+    ///
+    /// The finalized transaction.
+    ///
+    /// # Note
+    ///
+    /// This is a *signaling transaction*.
+    ///
+    pub fn new_set_reward_compounding(
+        key_pair: Option<&KeyPair>,
+        staker_key_pair: &KeyPair,
+        compound_rewards: bool,
+        fee: Coin,
+        validity_start_height: u32,
+        network_id: NetworkId,
+    ) -> Result<Transaction, TransactionBuilderError> {
+        let mut recipient = Recipient::new_staking_builder();
+        recipient.set_reward_compounding(compound_rewards);
+
+        let mut builder = Self::new();
+        builder
+            .with_recipient(recipient.generate().unwrap())
+            .with_value(Coin::ZERO)
+            .with_fee(fee)
+            .with_validity_start_height(validity_start_height)
+            .with_network_id(network_id);
+
+        match key_pair {
+            None => {
+                builder.with_sender(Sender::new_basic(Address::from(staker_key_pair)));
+            }
+            Some(key) => {
+                builder.with_sender(Sender::new_basic(Address::from(key)));
+            }
+        }
+
+        let proof_builder = builder.generate()?;
+        let mut staking_data_builder = proof_builder.unwrap_in_staking();
+        staking_data_builder.sign_with_key_pair(staker_key_pair);
+        let mut builder = staking_data_builder.generate().unwrap().unwrap_basic();
+        match key_pair {
+            None => builder.sign_with_key_pair(staker_key_pair),
+            Some(key) => builder.sign_with_key_pair(key),
+        };
+        Ok(builder.generate().unwrap())
+    }
+
+    /// Creates a transaction to schedule the withdrawal of some active stake of a given staker.
+    /// The withdrawal can be claimed after the withdrawal delay.
+    ///
+    /// # Arguments
+    ///
+    ///  - `key_pair`:              The optional key pair used to sign the outgoing transaction. If
+    ///                             it is given, the fee will be paid from the basic account
+    ///                             belonging to this key pair.
+    ///  - `staker_key_pair`:       The key pair used to sign the incoming transaction. The staker
+    ///                             address will be derived from this key pair.
+    ///  - `value`:                 The amount of active stake to be withdrawn.
+    ///  - `fee`:                   Transaction fee.
+    ///  - `validity_start_height`: Block height from which this transaction is valid.
+    ///  - `network_id`:            ID of network for which the transaction is meant.
+    ///
+    /// # Returns
+    ///
+    /// The finalized transaction.
+    ///
+    /// # Note
+    ///
+    /// This is a *signaling transaction*.
+    ///
+    pub fn new_schedule_unstake(
+        key_pair: Option<&KeyPair>,
+        staker_key_pair: &KeyPair,
+        value: Coin,
+        fee: Coin,
+        validity_start_height: u32,
+        network_id: NetworkId,
+    ) -> Result<Transaction, TransactionBuilderError> {
+        let mut recipient = Recipient::new_staking_builder();
+        recipient.schedule_unstake(value);
+
+        let mut builder = Self::new();
+        builder
+            .with_recipient(recipient.generate().unwrap())
+            .with_value(Coin::ZERO)
+            .with_fee(fee)
+            .with_validity_start_height(validity_start_height)
+            .with_network_id(network_id);
+
+        match key_pair {
+            None => {
+                builder.with_sender(Sender::new_basic(Address::from(staker_key_pair)));
+            }
+            Some(key) => {
+                builder.with_sender(Sender::new_basic(Address::from(key)));
+            }
+        }
+
+        let proof_builder = builder.generate()?;
+        let mut staking_data_builder = proof_builder.unwrap_in_staking();
+        staking_data_builder.sign_with_key_pair(staker_key_pair);
+        let mut builder = staking_data_builder.generate().unwrap().unwrap_basic();
+        match key_pair {
+            None => builder.sign_with_key_pair(staker_key_pair),
+            Some(key) => builder.sign_with_key_pair(key),
+        };
+        Ok(builder.generate().unwrap())
+    }
+
+    /// Creates a transaction to set whether a given staker compounds its rewards, i.e. whether its
+    /// share of its validator's rewards is credited to its active balance instead of its retired
+    /// balance.
+    ///
+    /// # Arguments
+    ///
+    ///  - `key_pair`:              The optional key pair used to sign the outgoing transaction. If
+    ///                             it is given, the fee will be paid from the basic account
+    ///                             belonging to this key pair.
+    ///  - `staker_key_pair`:       The key pair used to sign the incoming transaction. The staker
+    ///                             address will be derived from this key pair.
+    ///  - `compound_rewards`:      Whether the staker's rewards are compounded.
+    ///  - `fee`:                   Transaction fee.
+    ///  - `validity_start_height`: Block height from which this transaction is valid.
+    ///  - `network_id`:            ID of network for which the transaction is meant.
+    ///
+    /// # Returns
+    ///
+    /// The finalized transaction.
+    ///
+    /// # Note
+    ///
+    /// This is a *signaling transaction*.
+    ///
+    pub fn new_set_reward_compounding(
+        key_pair: Option<&KeyPair>,
+        staker_key_pair: &KeyPair,
+        compound_rewards: bool,
+        fee: Coin,
+        validity_start_height: u32,
+        network_id: NetworkId,
+    ) -> Result<Transaction, TransactionBuilderError> {
+        let mut recipient = Recipient::new_staking_builder();
+        recipient.set_reward_compounding(compound_rewards);
+
+        let mut builder = Self::new();
+        builder
+            .with_recipient(recipient.generate().unwrap())
+            .with_value(Coin::ZERO)
+            .with_fee(fee)
+            .with_validity_start_height(validity_start_height)
+            .with_network_id(network_id);
+
+        match key_pair {
+            None => {
+                builder.with_sender(Sender::new_basic(Address::from(staker_key_pair)));
+            }
+            Some(key) => {
+                builder.with_sender(Sender::new_basic(Address::from(key)));
+            }
+        }
+
+        let proof_builder = builder.generate()?;
+        let mut staking_data_builder = proof_builder.unwrap_in_staking();
+        staking_data_builder.sign_with_key_pair(staker_key_pair);
+        let mut builder = staking_data_builder.generate().unwrap().unwrap_basic();
+        match key_pair {
+            None => builder.sign_with_key_pair(staker_key_pair),
+            Some(key) => builder.sign_with_key_pair(key),
+        };
+        Ok(builder.generate().unwrap())
+    }
+
+    /// Creates a transaction to claim the released scheduled withdrawals of a given staker. The
+    /// claimed withdrawals are moved to the staker's retired balance.
+    ///
+    /// # Arguments
+    ///
+    ///  - `key_pair`:              The optional key pair used to sign the outgoing transaction. If
+    ///                             it is given, the fee will be paid from the basic account
+    ///                             belonging to this key pair.
+    ///  - `staker_key_pair`:       The key pair used to sign the incoming transaction. The staker
+    ///                             address will be derived from this key pair.
+    ///  - `fee`:                   Transaction fee.
+    ///  - `validity_start_height`: Block height from which this transaction is valid.
+    ///  - `network_id`:            ID of network for which the transaction is meant.
+    ///
+    /// # Returns
+    ///
+    /// The finalized transaction.
+    ///
+    /// # Note
+    ///
+    /// This is a *signaling transaction*.
+    ///
+    pub fn new_claim_withdrawals(
+        key_pair: Option<&KeyPair>,
+        staker_key_pair: &KeyPair,
+        fee: Coin,
+        validity_start_height: u32,
+        network_id: NetworkId,
+    ) -> Result<Transaction, TransactionBuilderError> {
+        let mut recipient = Recipient::new_staking_builder();
+        recipient.claim_withdrawals();
+
+        let mut builder = Self::new();
+        builder
+            .with_recipient(recipient.generate().unwrap())
+            .with_value(Coin::ZERO)
+            .with_fee(fee)
+            .with_validity_start_height(validity_start_height)
+            .with_network_id(network_id);
+
+        match key_pair {
+            None => {
+                builder.with_sender(Sender::new_basic(Address::from(staker_key_pair)));
+            }
+            Some(key) => {
+                builder.with_sender(Sender::new_basic(Address::from(key)));
+            }
+        }
+
+        let proof_builder = builder.generate()?;
+        let mut staking_data_builder = proof_builder.unwrap_in_staking();
+        staking_data_builder.sign_with_key_pair(staker_key_pair);
+        let mut builder = staking_data_builder.generate().unwrap().unwrap_basic();
+        match key_pair {
+            None => builder.sign_with_key_pair(staker_key_pair),
+            Some(key) => builder.sign_with_key_pair(key),
+        };
+        Ok(builder.generate().unwrap())
+    }
+
+    /// Creates a transaction to set whether a given staker compounds its rewards, i.e. whether its
+    /// share of its validator's rewards is credited to its active balance instead of its retired
+    /// balance.
+    ///
+    /// # Arguments
+    ///
+    ///  - `key_pair`:              The optional key pair used to sign the outgoing transaction. If
+    ///                             it is given, the fee will be paid from the basic account
+    ///                             belonging to this key pair.
+    ///  - `staker_key_pair`:       The key pair used to sign the incoming transaction. The staker
+    ///                             address will be derived from this key pair.
+    ///  - `compound_rewards`:      Whether the staker's rewards are compounded.
+    ///  - `fee`:                   Transaction fee.
+    ///  - `validity_start_height`: Block height from which this transaction is valid.
+    ///  - `network_id`:            ID of network for which the transaction is meant.
+    ///
+    /// # Returns
+    ///
+    /// The finalized transaction.
+    ///
+    /// # Note
+    ///
+    /// This is a *signaling transaction*.
+    ///
+    pub fn new_set_reward_compounding(
+        key_pair: Option<&KeyPair>,
+        staker_key_pair: &KeyPair,
+        compound_rewards: bool,
+        fee: Coin,
+        validity_start_height: u32,
+        network_id: NetworkId,
+    ) -> Result<Transaction, TransactionBuilderError> {
+        let mut recipient = Recipient::new_staking_builder();
+        recipient.set_reward_compounding(compound_rewards);
+
+        let mut builder = Self::new();
+        builder
+            .with_recipient(recipient.generate().unwrap())
+            .with_value(Coin::ZERO)
+            .with_fee(fee)
+            .with_validity_start_height(validity_start_height)
+            .with_network_id(network_id);
+
+        match key_pair {
+            None => {
+                builder.with_sender(Sender::new_basic(Address::from(staker_key_pair)));
+            }
+            Some(key) => {
+                builder.with_sender(Sender::new_basic(Address::from(key)));
+            }
+        }
+
+        let proof_builder = builder.generate()?;
+        let mut staking_data_builder = proof_builder.unwrap_in_staking();
+        staking_data_builder.sign_with_key_pair(staker_key_pair);
+        let mut builder = staking_data_builder.generate().unwrap().unwrap_basic();
+        match key_pair {
+            None => builder.sign_with_key_pair(staker_key_pair),
+            Some(key) => builder.sign_with_key_pair(key),
+        };
+        Ok(builder.generate().unwrap())
+    }
+
     /// Creates a transaction to remove stake of a given staker (from the staking contract) to a
     /// basic `recipient` address.
     ///
@@ -1435,6 +1768,67 @@ impl TransactionBuilder {
         }
     }
 
+    /// Creates a transaction that reactivates a validator, like
+    /// [`new_reactivate_validator`](Self::new_reactivate_validator), but signs the staking data
+    /// with `sign` instead of the signing key pair.
+    ///
+    /// # Arguments
+    ///
+    ///  - `key_pair`:              The key pair used to sign the transaction. The transaction
+    ///                             fee is taken from the account belonging to this key pair.
+    ///  - `validator_address`:     The address of the validator to be reactivated.
+    ///  - `signing_public_key`:    The public key of the validator's signing key.
+    ///  - `sign`:                  Signs the content of the given transaction with the
+    ///                             validator's signing key.
+    ///  - `fee`:                   Transaction fee.
+    ///  - `validity_start_height`: Block height from which this transaction is valid.
+    ///  - `network_id`:            ID of network for which the transaction is valid.
+    ///
+    /// # Returns
+    ///
+    /// The finalized transaction, or the error returned by `sign`.
+    ///
+    /// # Note
+    ///
+    /// This is a *signaling transaction*.
+    ///
+    pub fn new_reactivate_validator_with_signer<E>(
+        key_pair: &KeyPair,
+        validator_address: Address,
+        signing_public_key: Ed25519PublicKey,
+        sign: impl FnOnce(&Transaction) -> Result<Ed25519Signature, E>,
+        fee: Coin,
+        validity_start_height: u32,
+        network_id: NetworkId,
+    ) -> Result<Transaction, E> {
+        let mut recipient = Recipient::new_staking_builder();
+        recipient.reactivate_validator(validator_address);
+
+        let mut builder = Self::new();
+        builder
+            .with_sender(Sender::new_basic(Address::from(key_pair)))
+            .with_recipient(recipient.generate().unwrap())
+            .with_value(Coin::ZERO)
+            .with_fee(fee)
+            .with_validity_start_height(validity_start_height)
+            .with_network_id(network_id);
+
+        let proof_builder = builder.generate().unwrap();
+        match proof_builder {
+            TransactionProofBuilder::InStaking(mut builder) => {
+                let signature = sign(&builder.transaction)?;
+                builder.with_signature_proof(SignatureProof::from_ed25519(
+                    signing_public_key,
+                    signature,
+                ));
+                let mut builder = builder.generate().unwrap().unwrap_basic();
+                builder.sign_with_key_pair(key_pair);
+                Ok(builder.generate().unwrap())
+            }
+            _ => unreachable!(),
+        }
+    }
+
     /// Creates a transaction that retires a validator.
     ///
     /// # Arguments
@@ -1486,6 +1880,61 @@ impl TransactionBuilder {
         }
     }
 
+    /// Creates a transaction that sets the commission a validator keeps from its rewards. It can
+    /// only be included in blocks from version 2 of the protocol on.
+    ///
+    /// # Arguments
+    ///
+    ///  - `key_pair`:                 The key pair used to sign the transaction. The transaction
+    ///                                fee is taken from the account belonging to this key pair.
+    ///  - `cold_key_pair`:            The key pair that corresponds to the validator address. The
+    ///                                data is signed using this key pair.
+    ///  - `new_commission`:           The new commission in basis points, at most
+    ///                                `Policy::MAX_VALIDATOR_COMMISSION`.
+    ///  - `fee`:                      Transaction fee.
+    ///  - `validity_start_height`:    Block height from which this transaction is valid.
+    ///  - `network_id`:               ID of network for which the transaction is valid.
+    ///
+    /// # Returns
+    ///
+    /// The finalized transaction.
+    ///
+    /// # Note
+    ///
+    /// This is a *signaling transaction*.
+    ///
+    pub fn new_set_validator_commission(
+        key_pair: &KeyPair,
+        cold_key_pair: &KeyPair,
+        new_commission: u16,
+        fee: Coin,
+        validity_start_height: u32,
+        network_id: NetworkId,
+    ) -> Transaction {
+        let mut recipient = Recipient::new_staking_builder();
+        recipient.set_validator_commission(new_commission);
+
+        let mut builder = Self::new();
+        builder
+            .with_sender(Sender::new_basic(Address::from(key_pair)))
+            .with_recipient(recipient.generate().unwrap())
+            .with_value(Coin::ZERO)
+            .with_fee(fee)
+            .with_validity_start_height(validity_start_height)
+            .with_network_id(network_id);
+
+        let proof_builder = builder.generate().unwrap();
+        match proof_builder {
+            TransactionProofBuilder::InStaking(mut builder) => {
+                builder.sign_with_key_pair(cold_key_pair);
+                let mut builder = builder.generate().unwrap().unwrap_basic();
+                builder.sign_with_key_pair(key_pair);
+                builder.generate().unwrap()
+            }
+            _ => unreachable!(),
+        }
+    }
+
     /// Creates a transaction that deletes an *inactive* validator. The validator must have been
     /// *inactive* for the minimum cool-down period.
     ///
@@ -12,6 +12,7 @@ use crate::proof::{
 };
 
 pub mod htlc_contract;
+pub mod multisig;
 pub mod staking_contract;
 
 /// The `TransactionProofBuilder` subsumes the builders used to populate a transaction
@@ -0,0 +1,326 @@
+use nimiq_hash::Blake2bHasher;
+use nimiq_keys::{
+    multisig::{
+        commitment::{Commitment, CommitmentPair},
+        error::PartialSignatureError,
+        partial_signature::PartialSignature,
+        public_key::DelinearizedPublicKey,
+        CommitmentsBuilder, CommitmentsData, MUSIG2_PARAMETER_V,
+    },
+    Ed25519PublicKey, KeyPair, PublicKey, SecureGenerate, Signature,
+};
+use nimiq_serde::Serialize;
+use nimiq_transaction::{SignatureProof, Transaction};
+use nimiq_utils::merkle::Blake2bMerklePath;
+use thiserror::Error;
+
+/// Building a multisig proof can fail if the co-signers don't agree on the signing session.
+#[derive(Debug, Error)]
+pub enum MultisigError {
+    /// The public key is not part of the signers of the session.
+    #[error("The public key {0} is not a signer of this session.")]
+    UnknownSigner(Ed25519PublicKey),
+    /// A signer is part of the session more than once.
+    #[error("The signer {0} is part of the session more than once.")]
+    DuplicateSigner(Ed25519PublicKey),
+    /// The partial signature does not match the public key and commitments of the signer.
+    #[error("The partial signature of signer {0} is invalid.")]
+    InvalidPartialSignature(Ed25519PublicKey),
+    /// Not all signers of the session have provided their partial signature yet.
+    #[error("The partial signatures of {0} signers are missing.")]
+    MissingPartialSignatures(usize),
+    /// The aggregate public key of the signers is not one of the combined public keys of the
+    /// multisig address.
+    #[error("The signers' aggregate public key is not part of the multisig address.")]
+    AggregatePublicKeyNotFound,
+    /// A signing session requires at least one signer.
+    #[error("There are no signers in this session.")]
+    NoSigners,
+    #[error("{0}")]
+    PartialSignature(#[from] PartialSignatureError),
+}
+
+/// The public commitments of a co-signer. They have to be sent to all other co-signers in the
+/// first round of the signing process.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-derive", derive(serde::Serialize, serde::Deserialize))]
+pub struct SignerCommitments {
+    pub public_key: Ed25519PublicKey,
+    pub commitments: [Commitment; MUSIG2_PARAMETER_V],
+}
+
+/// The partial signature of a co-signer. It has to be sent to the co-signer assembling the proof
+/// in the second round of the signing process.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde-derive", derive(serde::Serialize, serde::Deserialize))]
+pub struct SignerPartialSignature {
+    pub public_key: Ed25519PublicKey,
+    pub partial_signature: PartialSignature,
+}
+
+/// The state of a co-signer between the two rounds of the MuSig2 signing process.
+///
+/// It contains the secret nonces of the co-signer, so it must never be shared with others. It
+/// can't be copied and is consumed when signing, such that the nonces are never used for more
+/// than one signature. To keep it in between the rounds, see [`MultisigSigner::persist`].
+pub struct MultisigSigner {
+    public_key: Ed25519PublicKey,
+    commitment_pairs: [CommitmentPair; MUSIG2_PARAMETER_V],
+}
+
+/// The persisted state of a [`MultisigSigner`], which can be taken out exactly once.
+///
+/// With the `serde-derive` feature, it can be serialized to store it in between the rounds.
+/// Taking the signer leaves the state empty. The empty state has to replace the stored one before
+/// signing, otherwise the nonces could be restored and used for another signature.
+#[cfg_attr(feature = "serde-derive", derive(serde::Serialize, serde::Deserialize))]
+pub struct PersistedMultisigSigner {
+    signer: Option<(Ed25519PublicKey, [CommitmentPair; MUSIG2_PARAMETER_V])>,
+}
+
+impl PersistedMultisigSigner {
+    /// Takes the signer out of the persisted state. Returns `None` if it was taken before.
+    pub fn take(&mut self) -> Option<MultisigSigner> {
+        self.signer.take().map(|(public_key, commitment_pairs)| {
+            MultisigSigner::with_commitment_pairs(public_key, commitment_pairs)
+        })
+    }
+
+    /// Returns true if the signer was already taken out of the persisted state.
+    pub fn is_taken(&self) -> bool {
+        self.signer.is_none()
+    }
+}
+
+impl MultisigSigner {
+    /// Starts a signing session for the co-signer with the given public key by generating fresh
+    /// nonces.
+    pub fn new(public_key: Ed25519PublicKey) -> Self {
+        let commitment_pairs =
+            [(); MUSIG2_PARAMETER_V].map(|_| CommitmentPair::generate_default_csprng());
+        Self::with_commitment_pairs(public_key, commitment_pairs)
+    }
+
+    /// Starts a signing session with the given nonces and commitments.
+    /// The nonces must be random and must never be used for more than one signature.
+    pub fn with_commitment_pairs(
+        public_key: Ed25519PublicKey,
+        commitment_pairs: [CommitmentPair; MUSIG2_PARAMETER_V],
+    ) -> Self {
+        MultisigSigner {
+            public_key,
+            commitment_pairs,
+        }
+    }
+
+    /// Returns the public commitments to be sent to the other co-signers.
+    pub fn commitments(&self) -> SignerCommitments {
+        SignerCommitments {
+            public_key: self.public_key,
+            commitments: CommitmentPair::to_commitments(&self.commitment_pairs),
+        }
+    }
+
+    /// Consumes the signer to persist it in between the rounds.
+    pub fn persist(self) -> PersistedMultisigSigner {
+        PersistedMultisigSigner {
+            signer: Some((self.public_key, self.commitment_pairs)),
+        }
+    }
+
+    /// Creates the partial signature of the `transaction`, given the commitments of all
+    /// co-signers. The commitments of this co-signer may or may not be part of `signers`.
+    pub fn partial_sign(
+        self,
+        key_pair: &KeyPair,
+        transaction: &Transaction,
+        signers: &[SignerCommitments],
+    ) -> Result<SignerPartialSignature, MultisigError> {
+        if key_pair.public != self.public_key {
+            return Err(MultisigError::UnknownSigner(key_pair.public));
+        }
+
+        let mut builder =
+            CommitmentsBuilder::with_private_commitments(self.public_key, self.commitment_pairs);
+        for signer in signers {
+            if signer.public_key == self.public_key {
+                if signer.commitments != builder.own_commitments() {
+                    return Err(MultisigError::DuplicateSigner(signer.public_key));
+                }
+                continue;
+            }
+            builder.push_signer(signer.public_key, signer.commitments);
+        }
+
+        let content = transaction.serialize_content();
+        let commitments_data = builder.build(&content);
+        let partial_signature = key_pair.partial_sign(&commitments_data, &content)?;
+
+        Ok(SignerPartialSignature {
+            public_key: self.public_key,
+            partial_signature,
+        })
+    }
+}
+
+/// The `MultisigProofBuilder` assembles the signature proof of a transaction sent from a
+/// multisig address out of the partial signatures of its co-signers.
+///
+/// The signing process follows MuSig2 and consists of two rounds:
+/// 1. Each co-signer starts a session with [`MultisigSigner::new`] and sends its
+///    [`SignerCommitments`] to all others.
+/// 2. Each co-signer creates its [`SignerPartialSignature`] with
+///    [`MultisigSigner::partial_sign`] and sends it to the assembling party, which adds it to
+///    the builder.
+///
+/// With the `serde-derive` feature, all intermediate states are serializable, so the co-signers
+/// can run on different machines. A co-signer persists its state with [`MultisigSigner::persist`].
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde-derive", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultisigProofBuilder {
+    pub transaction: Transaction,
+    signers: Vec<SignerCommitments>,
+    partial_signatures: Vec<SignerPartialSignature>,
+}
+
+impl MultisigProofBuilder {
+    /// Creates a new `MultisigProofBuilder` for the `transaction` and the commitments of all
+    /// co-signers.
+    pub fn new(
+        transaction: Transaction,
+        signers: Vec<SignerCommitments>,
+    ) -> Result<Self, MultisigError> {
+        if signers.is_empty() {
+            return Err(MultisigError::NoSigners);
+        }
+        for (i, signer) in signers.iter().enumerate() {
+            if signers[..i]
+                .iter()
+                .any(|other| other.public_key == signer.public_key)
+            {
+                return Err(MultisigError::DuplicateSigner(signer.public_key));
+            }
+        }
+
+        Ok(MultisigProofBuilder {
+            transaction,
+            signers,
+            partial_signatures: Vec::new(),
+        })
+    }
+
+    /// Adds the partial signature of a co-signer after verifying it.
+    pub fn add_partial_signature(
+        &mut self,
+        partial_signature: SignerPartialSignature,
+    ) -> Result<&mut Self, MultisigError> {
+        let public_key = partial_signature.public_key;
+        if self
+            .partial_signatures
+            .iter()
+            .any(|other| other.public_key == public_key)
+        {
+            return Err(MultisigError::DuplicateSigner(public_key));
+        }
+
+        let content = self.transaction.serialize_content();
+        let commitments_data = self.commitments_data(&public_key, &content)?;
+        if !public_key.verify_partial(
+            &commitments_data,
+            &partial_signature.partial_signature,
+            &content,
+        ) {
+            return Err(MultisigError::InvalidPartialSignature(public_key));
+        }
+
+        self.partial_signatures.push(partial_signature);
+        Ok(self)
+    }
+
+    /// Returns true if all co-signers have provided their partial signature.
+    pub fn is_complete(&self) -> bool {
+        self.partial_signatures.len() == self.signers.len()
+    }
+
+    /// Returns the aggregate public key of the co-signers, which has to be one of the combined
+    /// public keys of the multisig address.
+    pub fn aggregate_public_key(&self) -> Ed25519PublicKey {
+        let public_keys: Vec<_> = self
+            .signers
+            .iter()
+            .map(|signer| signer.public_key)
+            .collect();
+        DelinearizedPublicKey::sum_delinearized(&public_keys)
+    }
+
+    /// Creates the signature proof from the partial signatures of all co-signers.
+    /// `combined_public_keys` are the combined public keys the multisig address was computed
+    /// from (see `nimiq_keys::multisig::address::combine_public_keys`). They are used to compute
+    /// the merkle path representing the signers.
+    pub fn signature_proof(
+        &self,
+        combined_public_keys: &[Ed25519PublicKey],
+    ) -> Result<SignatureProof, MultisigError> {
+        if !self.is_complete() {
+            return Err(MultisigError::MissingPartialSignatures(
+                self.signers.len() - self.partial_signatures.len(),
+            ));
+        }
+
+        let commitments_data = self.commitments_data(
+            &self.signers[0].public_key,
+            &self.transaction.serialize_content(),
+        )?;
+        if !combined_public_keys.contains(&commitments_data.aggregate_public_key) {
+            return Err(MultisigError::AggregatePublicKeyNotFound);
+        }
+
+        let aggregate_signature: PartialSignature = self
+            .partial_signatures
+            .iter()
+            .map(|signer| signer.partial_signature)
+            .sum();
+        let signature = aggregate_signature.to_signature(&commitments_data.aggregate_commitment);
+
+        Ok(SignatureProof {
+            public_key: PublicKey::Ed25519(commitments_data.aggregate_public_key),
+            merkle_path: Blake2bMerklePath::new::<Blake2bHasher, _>(
+                combined_public_keys,
+                &commitments_data.aggregate_public_key,
+            ),
+            signature: Signature::Ed25519(signature),
+            webauthn_fields: None,
+        })
+    }
+
+    /// This method generates the final transaction if all partial signatures have been added.
+    pub fn generate(
+        self,
+        combined_public_keys: &[Ed25519PublicKey],
+    ) -> Result<Transaction, MultisigError> {
+        let proof = self.signature_proof(combined_public_keys)?;
+        let mut tx = self.transaction;
+        tx.proof = proof.serialize_to_vec();
+        Ok(tx)
+    }
+
+    /// Computes the commitments data from the perspective of the given signer.
+    fn commitments_data(
+        &self,
+        public_key: &Ed25519PublicKey,
+        content: &[u8],
+    ) -> Result<CommitmentsData, MultisigError> {
+        let signer = self
+            .signers
+            .iter()
+            .find(|signer| &signer.public_key == public_key)
+            .ok_or(MultisigError::UnknownSigner(*public_key))?;
+
+        let mut builder =
+            CommitmentsBuilder::with_public_commitments(signer.public_key, signer.commitments);
+        for other in self.signers.iter().filter(|other| other != &signer) {
+            builder.push_signer(other.public_key, other.commitments);
+        }
+        Ok(builder.build(content))
+    }
+}
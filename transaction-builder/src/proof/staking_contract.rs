@@ -30,6 +30,16 @@ impl StakingDataBuilder {
     /// This method sets the required `signature` proof by signing the transaction
     /// using a key pair.
     pub fn sign_with_key_pair(&mut self, key_pair: &KeyPair) -> &mut Self {
+        let signature = key_pair.sign(&self.transaction.serialize_content());
+        self.with_signature_proof(SignatureProof::from_ed25519(key_pair.public, signature))
+    }
+
+    /// Manually sets the required `signature` proof for the builder.
+    /// In most cases, it is not necessary to call this method.
+    /// Instead, it is recommended to automatically generate the signature using [`sign_with_key_pair`].
+    ///
+    /// [`sign_with_key_pair`]: struct.StakingDataBuilder.html#method.sign_with_key_pair
+    pub fn with_signature_proof(&mut self, signature: SignatureProof) -> &mut Self {
         // Deserialize the data.
         let mut data =
             IncomingStakingTransactionData::deserialize_from_vec(&self.transaction.recipient_data)
@@ -38,11 +48,7 @@ impl StakingDataBuilder {
         // If this is a stake transaction, we don't need to sign it.
         match data {
             IncomingStakingTransactionData::AddStake { .. } => {}
-            _ => {
-                let signature = key_pair.sign(&self.transaction.serialize_content());
-                let proof = SignatureProof::from_ed25519(key_pair.public, signature);
-                data.set_signature(proof);
-            }
+            _ => data.set_signature(signature),
         }
 
         self.data = Some(data);
@@ -117,6 +117,17 @@ impl StakingRecipientBuilder {
         self
     }
 
+    /// This method allows to set the commission the validator keeps from its rewards, in basis
+    /// points. The rest of the rewards is shared with its stakers.
+    /// It needs to be signed by the key pair corresponding to the cold key.
+    pub fn set_validator_commission(&mut self, new_commission: u16) -> &mut Self {
+        self.data = Some(IncomingStakingTransactionData::SetValidatorCommission {
+            new_commission,
+            proof: Default::default(),
+        });
+        self
+    }
+
     /// This method allows to create a staker with a given (optional) delegation to a validator.
     /// It needs to be signed by the key pair corresponding to the staker address.
     pub fn create_staker(&mut self, delegation: Option<Address>) -> &mut Self {
@@ -173,6 +184,38 @@ impl StakingRecipientBuilder {
         self
     }
 
+    /// This method allows to set whether the staker's share of its validator's rewards is credited
+    /// to its active balance instead of being paid to the validator's reward address.
+    /// It needs to be signed by the key pair corresponding to the staker address.
+    pub fn set_reward_compounding(&mut self, compound_rewards: bool) -> &mut Self {
+        self.data = Some(IncomingStakingTransactionData::SetRewardCompounding {
+            compound_rewards,
+            proof: Default::default(),
+        });
+        self
+    }
+
+    /// This method allows to schedule the withdrawal of the given amount of active stake. The
+    /// withdrawal can be claimed after the withdrawal delay.
+    /// It needs to be signed by the key pair corresponding to the staker address.
+    pub fn schedule_unstake(&mut self, value: Coin) -> &mut Self {
+        self.data = Some(IncomingStakingTransactionData::ScheduleUnstake {
+            value,
+            proof: Default::default(),
+        });
+        self
+    }
+
+    /// This method allows to claim all released scheduled withdrawals of the staker, which moves
+    /// them to its retired balance.
+    /// It needs to be signed by the key pair corresponding to the staker address.
+    pub fn claim_withdrawals(&mut self) -> &mut Self {
+        self.data = Some(IncomingStakingTransactionData::ClaimWithdrawals {
+            proof: Default::default(),
+        });
+        self
+    }
+
     /// A method to generate a proof of knowledge of the secret key by signing the public key.
     pub fn generate_proof_of_knowledge(key_pair: &BlsKeyPair) -> CompressedSignature {
         key_pair.sign(&key_pair.public_key).compress()
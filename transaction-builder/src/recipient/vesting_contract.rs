@@ -2,7 +2,9 @@ use std::ops::Div;
 
 use nimiq_keys::Address;
 use nimiq_primitives::coin::Coin;
-use nimiq_transaction::account::vesting_contract::CreationTransactionData as VestingCreationData;
+use nimiq_transaction::account::vesting_contract::{
+    CreationTransactionData as VestingCreationData, VestingBeneficiary,
+};
 use thiserror::Error;
 
 use crate::recipient::Recipient;
@@ -65,6 +67,7 @@ pub struct VestingRecipientBuilder {
     time_step: Option<u64>,
     step_amount: Option<Coin>,
     total_amount: Option<Coin>,
+    beneficiaries: Vec<VestingBeneficiary>,
 }
 
 impl VestingRecipientBuilder {
@@ -137,6 +140,13 @@ impl VestingRecipientBuilder {
         self
     }
 
+    /// Sets the `beneficiaries` that the released funds are split between according to their
+    /// weights. Each beneficiary can only withdraw its own share, the owner can't withdraw at all.
+    pub fn with_beneficiaries(&mut self, beneficiaries: Vec<VestingBeneficiary>) -> &mut Self {
+        self.beneficiaries = beneficiaries;
+        self
+    }
+
     /// This method tries putting together the contract creation,
     /// returning a [`Recipient`] in case of success.
     /// In case of a failure, it returns a [`VestingRecipientBuilderError`].
@@ -178,6 +188,7 @@ impl VestingRecipientBuilder {
                 total_amount: self
                     .total_amount
                     .ok_or(VestingRecipientBuilderError::NoTotalAmount)?,
+                beneficiaries: self.beneficiaries,
             },
         })
     }
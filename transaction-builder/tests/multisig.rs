@@ -0,0 +1,120 @@
+use nimiq_keys::{
+    multisig::address::{combine_public_keys, compute_address},
+    Address, Ed25519PublicKey, KeyPair, SecureGenerate,
+};
+use nimiq_primitives::{coin::Coin, networks::NetworkId};
+use nimiq_test_log::test;
+use nimiq_transaction::Transaction;
+use nimiq_transaction_builder::proof::multisig::{
+    MultisigError, MultisigProofBuilder, MultisigSigner,
+};
+
+fn setup() -> (Vec<KeyPair>, Vec<Ed25519PublicKey>, Transaction) {
+    let key_pairs: Vec<_> = (0..3).map(|_| KeyPair::generate_default_csprng()).collect();
+    let combined_public_keys = combine_public_keys(
+        key_pairs.iter().map(|key_pair| key_pair.public).collect(),
+        2,
+    );
+    let transaction = Transaction::new_basic(
+        compute_address(&combined_public_keys),
+        Address::from(&KeyPair::generate_default_csprng().public),
+        Coin::from_u64_unchecked(100),
+        Coin::from_u64_unchecked(1),
+        1,
+        NetworkId::UnitAlbatross,
+    );
+    (key_pairs, combined_public_keys, transaction)
+}
+
+#[test]
+fn it_can_create_a_multisig_transaction() {
+    let (key_pairs, combined_public_keys, transaction) = setup();
+    let signing_key_pairs = &key_pairs[1..];
+
+    // First round: every co-signer publishes its commitments.
+    let signers: Vec<_> = signing_key_pairs
+        .iter()
+        .map(|key_pair| MultisigSigner::new(key_pair.public))
+        .collect();
+    let commitments: Vec<_> = signers.iter().map(|signer| signer.commitments()).collect();
+
+    // Second round: every co-signer creates its partial signature.
+    let mut proof_builder =
+        MultisigProofBuilder::new(transaction.clone(), commitments.clone()).unwrap();
+    for (signer, key_pair) in signers.into_iter().zip(signing_key_pairs) {
+        assert!(!proof_builder.is_complete());
+        let partial_signature = signer
+            .partial_sign(key_pair, &transaction, &commitments)
+            .unwrap();
+        proof_builder
+            .add_partial_signature(partial_signature)
+            .unwrap();
+    }
+    assert!(proof_builder.is_complete());
+    assert!(combined_public_keys.contains(&proof_builder.aggregate_public_key()));
+
+    let transaction = proof_builder.generate(&combined_public_keys).unwrap();
+    assert!(transaction.verify(NetworkId::UnitAlbatross).is_ok());
+}
+
+#[test]
+fn it_restores_a_persisted_signer_only_once() {
+    let (key_pairs, _, transaction) = setup();
+
+    let signer = MultisigSigner::new(key_pairs[0].public);
+    let commitments = vec![signer.commitments()];
+    let mut persisted = signer.persist();
+    assert!(!persisted.is_taken());
+
+    let signer = persisted.take().unwrap();
+    assert!(persisted.is_taken());
+    assert!(persisted.take().is_none());
+
+    assert_eq!(signer.commitments(), commitments[0]);
+    assert!(signer
+        .partial_sign(&key_pairs[0], &transaction, &commitments)
+        .is_ok());
+}
+
+#[test]
+fn it_rejects_invalid_partial_signatures() {
+    let (key_pairs, combined_public_keys, transaction) = setup();
+
+    let signer_a = MultisigSigner::new(key_pairs[0].public);
+    let signer_b = MultisigSigner::new(key_pairs[1].public);
+    let commitments = vec![signer_a.commitments(), signer_b.commitments()];
+    let mut proof_builder =
+        MultisigProofBuilder::new(transaction.clone(), commitments.clone()).unwrap();
+
+    // A partial signature over a different transaction is rejected.
+    let mut other_transaction = transaction.clone();
+    other_transaction.value = Coin::from_u64_unchecked(200);
+    let partial_signature = signer_a
+        .partial_sign(&key_pairs[0], &other_transaction, &commitments)
+        .unwrap();
+    assert!(matches!(
+        proof_builder.add_partial_signature(partial_signature),
+        Err(MultisigError::InvalidPartialSignature(_))
+    ));
+
+    // A signer that is not part of the session is rejected.
+    let partial_signature = MultisigSigner::new(key_pairs[2].public)
+        .partial_sign(&key_pairs[2], &transaction, &commitments)
+        .unwrap();
+    assert!(matches!(
+        proof_builder.add_partial_signature(partial_signature),
+        Err(MultisigError::UnknownSigner(_))
+    ));
+
+    // The proof can't be created before all partial signatures are present.
+    let partial_signature = signer_b
+        .partial_sign(&key_pairs[1], &transaction, &commitments)
+        .unwrap();
+    proof_builder
+        .add_partial_signature(partial_signature)
+        .unwrap();
+    assert!(matches!(
+        proof_builder.generate(&combined_public_keys),
+        Err(MultisigError::MissingPartialSignatures(1))
+    ));
+}
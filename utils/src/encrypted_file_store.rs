@@ -0,0 +1,279 @@
+use std::{
+    fs::{self, File},
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+};
+
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    XChaCha20Poly1305, XNonce,
+};
+use nimiq_hash::argon2kdf::{compute_argon2_kdf, Argon2Error, Argon2Variant};
+use nimiq_serde::{Deserialize, DeserializeError, Serialize};
+use rand::{rngs::OsRng, RngCore};
+use thiserror::Error;
+
+use crate::file_store::{self, FileStore};
+
+/// Identifies encrypted key files. It is followed by the format version.
+const MAGIC: &[u8; 4] = b"NQKS";
+const VERSION: u8 = 1;
+
+const SALT_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 24;
+const KEY_LENGTH: usize = 32;
+
+/// The content of an encrypted key file following the header.
+#[derive(Serialize, Deserialize)]
+struct EncryptedKey {
+    salt: [u8; SALT_LENGTH],
+    iterations: u32,
+    nonce: [u8; NONCE_LENGTH],
+    ciphertext: Vec<u8>,
+}
+
+/// Stores items in a file encrypted with a password. The encryption key is derived from the
+/// password using Argon2id and the item is encrypted with XChaCha20-Poly1305, such that a wrong
+/// password or a modified file is detected upon loading.
+pub struct EncryptedFileStore {
+    path: PathBuf,
+    iterations: u32,
+}
+
+impl EncryptedFileStore {
+    // Taken from https://cheatsheetseries.owasp.org/cheatsheets/Password_Storage_Cheat_Sheet.html#argon2id, 2024-06-20.
+    pub const DEFAULT_ITERATIONS: u32 = 3;
+    /// Upper bound for the number of iterations, such that a crafted file can't stall the key
+    /// derivation.
+    pub const MAX_ITERATIONS: u32 = 64;
+
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        EncryptedFileStore {
+            path: path.as_ref().to_owned(),
+            iterations: Self::DEFAULT_ITERATIONS,
+        }
+    }
+
+    /// Sets the number of Argon2id iterations used when storing items. It must be between 1 and
+    /// [`Self::MAX_ITERATIONS`].
+    pub fn with_iterations(mut self, iterations: u32) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Returns whether the file exists and is encrypted.
+    pub fn is_encrypted(&self) -> Result<bool, Error> {
+        let mut header = [0u8; MAGIC.len()];
+        match File::open(&self.path).and_then(|mut file| file.read_exact(&mut header)) {
+            Ok(()) => Ok(&header == MAGIC),
+            Err(err)
+                if matches!(
+                    err.kind(),
+                    io::ErrorKind::NotFound | io::ErrorKind::UnexpectedEof
+                ) =>
+            {
+                Ok(false)
+            }
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Calling code should make sure to clear the password from memory after use.
+    pub fn load<T: Deserialize>(&self, password: &[u8]) -> Result<T, Error> {
+        log::debug!(path = ?self.path.display(), "Reading from encrypted file");
+
+        decrypt(&fs::read(&self.path)?, password)
+    }
+
+    /// Loads from the file, storing and returning a default value if the file does not exist.
+    /// Calling code should make sure to clear the password from memory after use.
+    pub fn load_or_store<T, F>(&self, password: &[u8], mut f: F) -> Result<T, Error>
+    where
+        T: Serialize + Deserialize,
+        F: FnMut() -> T,
+    {
+        match self.load(password) {
+            Err(Error::Io(err)) if err.kind() == io::ErrorKind::NotFound => {
+                log::debug!(path = ?self.path.display(), "File does not exist, falling back to default");
+                let default = f();
+                self.store(&default, password)?;
+                Ok(default)
+            }
+            Ok(result) => Ok(result),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Calling code should make sure to clear the password from memory after use.
+    pub fn store<T: Serialize>(&self, item: &T, password: &[u8]) -> Result<(), Error> {
+        log::debug!(path = ?self.path.display(), "Writing to encrypted file");
+
+        self.write(&encrypt(item, password, self.iterations)?)
+    }
+
+    /// Encrypts an unencrypted file, as written by a [`FileStore`], in place. Files that don't
+    /// exist or are already encrypted are left untouched.
+    /// Calling code should make sure to clear the password from memory after use.
+    pub fn lock<T: Serialize + Deserialize>(&self, password: &[u8]) -> Result<(), Error> {
+        if !self.path.exists() || self.is_encrypted()? {
+            return Ok(());
+        }
+
+        let item: T = FileStore::new(&self.path).load()?;
+        log::info!(path = ?self.path.display(), "Encrypting unencrypted key file");
+        self.store(&item, password)
+    }
+
+    /// Writes the data to a temporary file first, such that the previous file is only replaced
+    /// once the new one was written completely.
+    fn write(&self, data: &[u8]) -> Result<(), Error> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".tmp");
+
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let mut file = options.open(&tmp_path)?;
+        file.write_all(data)?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+/// Encrypts the item in the format of an encrypted key file, such that it can be stored elsewhere,
+/// e.g. inline in a config file.
+/// Calling code should make sure to clear the password from memory after use.
+pub fn encrypt<T: Serialize>(item: &T, password: &[u8], iterations: u32) -> Result<Vec<u8>, Error> {
+    check_iterations(iterations)?;
+
+    let mut salt = [0u8; SALT_LENGTH];
+    OsRng.fill_bytes(&mut salt);
+    let mut nonce = [0u8; NONCE_LENGTH];
+    OsRng.fill_bytes(&mut nonce);
+
+    let mut key = derive_key(password, &salt, iterations)?;
+    let cipher = XChaCha20Poly1305::new_from_slice(&key).expect("Key has the correct length");
+    key.fill(0);
+
+    let mut plaintext = item.serialize_to_vec();
+    let ciphertext = cipher
+        .encrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: &plaintext,
+                aad: &header(),
+            },
+        )
+        .map_err(|_| Error::Encryption);
+
+    // Always overwrite unencrypted vector.
+    plaintext.fill(0);
+
+    let encrypted = EncryptedKey {
+        salt,
+        iterations,
+        nonce,
+        ciphertext: ciphertext?,
+    };
+    let mut data = header().to_vec();
+    data.extend(encrypted.serialize_to_vec());
+    Ok(data)
+}
+
+/// Decrypts an item in the format of an encrypted key file.
+/// Calling code should make sure to clear the password from memory after use.
+pub fn decrypt<T: Deserialize>(data: &[u8], password: &[u8]) -> Result<T, Error> {
+    let Some(data) = data.strip_prefix(MAGIC) else {
+        return Err(Error::NotEncrypted);
+    };
+    let (&version, data) = data.split_first().ok_or(Error::NotEncrypted)?;
+    if version != VERSION {
+        return Err(Error::UnsupportedVersion(version));
+    }
+
+    let encrypted = EncryptedKey::deserialize_from_vec(data)?;
+    // The number of iterations is read from the untrusted input, so it has to be checked before
+    // deriving the key.
+    check_iterations(encrypted.iterations)?;
+
+    let mut key = derive_key(password, &encrypted.salt, encrypted.iterations)?;
+    let cipher = XChaCha20Poly1305::new_from_slice(&key).expect("Key has the correct length");
+    key.fill(0);
+
+    let mut plaintext = cipher
+        .decrypt(
+            XNonce::from_slice(&encrypted.nonce),
+            Payload {
+                msg: &encrypted.ciphertext,
+                aad: &header(),
+            },
+        )
+        .map_err(|_| Error::Decryption)?;
+    let result = T::deserialize_from_vec(&plaintext);
+
+    // Always overwrite unencrypted vector.
+    plaintext.fill(0);
+
+    Ok(result?)
+}
+
+fn check_iterations(iterations: u32) -> Result<(), Error> {
+    if iterations == 0 || iterations > EncryptedFileStore::MAX_ITERATIONS {
+        return Err(Error::InvalidIterations(iterations));
+    }
+    Ok(())
+}
+
+fn header() -> [u8; MAGIC.len() + 1] {
+    let mut header = [0u8; MAGIC.len() + 1];
+    header[..MAGIC.len()].copy_from_slice(MAGIC);
+    header[MAGIC.len()] = VERSION;
+    header
+}
+
+fn derive_key(password: &[u8], salt: &[u8], iterations: u32) -> Result<Vec<u8>, Argon2Error> {
+    compute_argon2_kdf(
+        password,
+        salt,
+        iterations,
+        KEY_LENGTH,
+        Argon2Variant::Argon2id,
+    )
+}
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] DeserializeError),
+
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("{0}")]
+    FileStore(#[from] file_store::Error),
+
+    #[error("Key derivation failed: {0}")]
+    KeyDerivation(#[from] Argon2Error),
+
+    #[error("File is not encrypted")]
+    NotEncrypted,
+
+    #[error("Unsupported encrypted file version: {0}")]
+    UnsupportedVersion(u8),
+
+    #[error("Invalid number of key derivation iterations: {0}")]
+    InvalidIterations(u32),
+
+    #[error("Encryption failed")]
+    Encryption,
+
+    #[error("Wrong password or corrupted file")]
+    Decryption,
+}
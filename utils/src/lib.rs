@@ -1,5 +1,7 @@
 #[cfg(feature = "crc")]
 pub mod crc;
+#[cfg(feature = "encrypted-key-store")]
+pub mod encrypted_file_store;
 #[cfg(feature = "key-store")]
 pub mod file_store;
 pub mod interner;
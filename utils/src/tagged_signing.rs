@@ -8,9 +8,11 @@
 use std::{
     io::{Cursor, Write},
     marker::PhantomData,
+    time::Duration,
 };
 
 use nimiq_serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 /// A trait for objects that can be signed. You have to choose an unique `TAG` that is used as prefix for
 /// the message that will be signed. This is used to avoid replay attacks.
@@ -23,7 +25,9 @@ use nimiq_serde::{Deserialize, Serialize};
 ///
 ///  - `0x01`: [`ChallengeNonce`](../../nimiq_network_libp2p/discovery/protocol/struct.ChallengeNonce.html)
 ///  - `0x02`: [`PeerContact`](../../nimiq_network_libp2p/discovery/peer_contacts/struct.PeerContact.html)
-///  - `0x03`: [`ValidatorRecord`](../../nimiq_validator_network/validator_record/struct.ValidatorRecord.html)
+///  - `0x03`: [`LegacyValidatorRecord`](../../nimiq_validator_network/validator_record/struct.LegacyValidatorRecord.html)
+///  - `0x04`: [`ValidatorRecord`](../../nimiq_validator_network/validator_record/struct.ValidatorRecord.html)
+///  - `0x05`: [`SyncCheckpoint`](../../nimiq_genesis/checkpoints/struct.SyncCheckpoint.html)
 ///
 pub trait TaggedSignable: Serialize {
     const TAG: u8;
@@ -41,6 +45,111 @@ pub trait TaggedSignable: Serialize {
     }
 }
 
+/// A signable record that carries the time it was issued at and optionally an expiry time.
+///
+/// Both timestamps are part of the record itself and thus covered by the signature. They are
+/// given in milliseconds since 1970-01-01 00:00:00 UTC, excluding leap seconds (Unix time).
+pub trait TimestampedSignable: TaggedSignable {
+    /// Returns the time at which the record was issued.
+    fn issued_at(&self) -> u64;
+
+    /// Returns the time after which the record must no longer be considered valid, if any.
+    fn expires_at(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// Policy used to decide whether a [`TimestampedSignable`] record is fresh.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FreshnessPolicy {
+    /// Tolerated difference between our clock and the clock of the issuer.
+    pub max_clock_skew: Duration,
+    /// Maximum age of a record. This applies in addition to the record's own expiry time.
+    pub max_age: Option<Duration>,
+}
+
+impl FreshnessPolicy {
+    pub const fn new(max_clock_skew: Duration, max_age: Option<Duration>) -> Self {
+        Self {
+            max_clock_skew,
+            max_age,
+        }
+    }
+
+    /// Checks the timestamps of a record against the current time `now`.
+    pub fn check<TSignable: TimestampedSignable>(
+        &self,
+        record: &TSignable,
+        now: u64,
+    ) -> Result<(), TaggedVerificationError> {
+        let max_clock_skew = self.max_clock_skew.as_millis() as u64;
+        let issued_at = record.issued_at();
+
+        if issued_at > now.saturating_add(max_clock_skew) {
+            return Err(TaggedVerificationError::IssuedInFuture { issued_at, now });
+        }
+
+        if let Some(expires_at) = record.expires_at() {
+            if expires_at < issued_at {
+                return Err(TaggedVerificationError::InvalidValidity {
+                    issued_at,
+                    expires_at,
+                });
+            }
+            if expires_at.saturating_add(max_clock_skew) < now {
+                return Err(TaggedVerificationError::Expired { expires_at, now });
+            }
+        }
+
+        if let Some(max_age) = self.max_age {
+            let max_age = max_age.as_millis() as u64;
+            if issued_at
+                .saturating_add(max_age)
+                .saturating_add(max_clock_skew)
+                < now
+            {
+                return Err(TaggedVerificationError::TooOld { issued_at, now });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the time after which the record is no longer fresh according to this policy.
+    pub fn fresh_until<TSignable: TimestampedSignable>(&self, record: &TSignable) -> u64 {
+        let max_clock_skew = self.max_clock_skew.as_millis() as u64;
+
+        let expires_at = record.expires_at().map_or(u64::MAX, |expires_at| {
+            expires_at.saturating_add(max_clock_skew)
+        });
+        let too_old_at = self.max_age.map_or(u64::MAX, |max_age| {
+            record
+                .issued_at()
+                .saturating_add(max_age.as_millis() as u64)
+                .saturating_add(max_clock_skew)
+        });
+
+        expires_at.min(too_old_at)
+    }
+}
+
+/// Errors that can occur when verifying a [`TaggedSigned`] record.
+#[derive(Clone, Copy, Debug, Error, PartialEq, Eq)]
+pub enum TaggedVerificationError {
+    #[error("Invalid signature")]
+    InvalidSignature,
+    #[error("Record issued in the future (issued at {issued_at}, now {now})")]
+    IssuedInFuture { issued_at: u64, now: u64 },
+    #[error("Record expired (expires at {expires_at}, now {now})")]
+    Expired { expires_at: u64, now: u64 },
+    #[error("Record too old (issued at {issued_at}, now {now})")]
+    TooOld { issued_at: u64, now: u64 },
+    #[error(
+        "Record expires before it was issued (issued at {issued_at}, expires at {expires_at})"
+    )]
+    InvalidValidity { issued_at: u64, expires_at: u64 },
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct TaggedSignature<TSignable, TScheme>
 where
@@ -179,6 +288,19 @@ where
     pub signature: TaggedSignature<TSignable, TScheme>,
 }
 
+impl<TSignable, TScheme> std::fmt::Debug for TaggedSigned<TSignable, TScheme>
+where
+    TSignable: TaggedSignable + std::fmt::Debug,
+    TScheme: TaggedKeyPair,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("TaggedSigned")
+            .field("record", &self.record)
+            .field("signature", &self.signature)
+            .finish()
+    }
+}
+
 impl<TSignable, TScheme> TaggedSigned<TSignable, TScheme>
 where
     TSignable: TaggedSignable,
@@ -203,6 +325,36 @@ where
     }
 }
 
+impl<TSignable, TScheme> TaggedSigned<TSignable, TScheme>
+where
+    TSignable: TimestampedSignable,
+    TScheme: TaggedKeyPair,
+{
+    /// Checks that the record is fresh at time `now` according to the given `policy`.
+    pub fn check_freshness(
+        &self,
+        now: u64,
+        policy: &FreshnessPolicy,
+    ) -> Result<(), TaggedVerificationError> {
+        policy.check(&self.record, now)
+    }
+
+    /// Verifies that the record is fresh at time `now` and that the signature is valid.
+    pub fn verify_fresh(
+        &self,
+        public_key: &TScheme::PublicKey,
+        now: u64,
+        policy: &FreshnessPolicy,
+    ) -> Result<(), TaggedVerificationError> {
+        // The timestamp checks are cheaper than the signature verification, so do them first.
+        self.check_freshness(now, policy)?;
+        if !self.verify(public_key) {
+            return Err(TaggedVerificationError::InvalidSignature);
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use nimiq_keys::{Ed25519PublicKey, Ed25519Signature, KeyPair, SecureGenerate};
@@ -210,7 +362,12 @@ mod tests {
     use nimiq_test_log::test;
     use nimiq_test_utils::test_rng::test_rng;
 
-    use super::{TaggedKeyPair, TaggedPublicKey, TaggedSignable, TaggedSignature};
+    use std::time::Duration;
+
+    use super::{
+        FreshnessPolicy, TaggedKeyPair, TaggedPublicKey, TaggedSignable, TaggedSignature,
+        TaggedSigned, TaggedVerificationError, TimestampedSignable,
+    };
 
     struct TestKeypair(KeyPair);
     struct TestPublicKey(Ed25519PublicKey);
@@ -254,6 +411,26 @@ mod tests {
         const TAG: u8 = 0x02;
     }
 
+    #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+    struct ExpiringMessage {
+        issued_at: u64,
+        expires_at: Option<u64>,
+    }
+
+    impl TaggedSignable for ExpiringMessage {
+        const TAG: u8 = 0x03;
+    }
+
+    impl TimestampedSignable for ExpiringMessage {
+        fn issued_at(&self) -> u64 {
+            self.issued_at
+        }
+
+        fn expires_at(&self) -> Option<u64> {
+            self.expires_at
+        }
+    }
+
     #[test]
     fn it_signs_and_verifies() {
         let msg = Message(42);
@@ -306,6 +483,95 @@ mod tests {
         assert!(!sig2_replayed.tagged_verify(&msg1, &keypair.public_key()));
         assert!(!sig1_replayed.tagged_verify(&msg2, &keypair.public_key()));
     }
+
+    #[test]
+    fn it_checks_freshness() {
+        let policy =
+            FreshnessPolicy::new(Duration::from_millis(10), Some(Duration::from_millis(1000)));
+        let keypair = TestKeypair::generate();
+
+        let msg = ExpiringMessage {
+            issued_at: 1000,
+            expires_at: Some(1500),
+        };
+        let signed = TaggedSigned::new(msg.clone(), keypair.tagged_sign(&msg));
+
+        assert_eq!(
+            signed.verify_fresh(&keypair.public_key(), 1200, &policy),
+            Ok(())
+        );
+        // Within the tolerated clock skew.
+        assert_eq!(
+            signed.verify_fresh(&keypair.public_key(), 995, &policy),
+            Ok(())
+        );
+        assert_eq!(
+            signed.verify_fresh(&keypair.public_key(), 1505, &policy),
+            Ok(())
+        );
+
+        assert_eq!(
+            signed.check_freshness(900, &policy),
+            Err(TaggedVerificationError::IssuedInFuture {
+                issued_at: 1000,
+                now: 900
+            })
+        );
+        assert_eq!(
+            signed.check_freshness(1600, &policy),
+            Err(TaggedVerificationError::Expired {
+                expires_at: 1500,
+                now: 1600
+            })
+        );
+
+        // Records without expiry are limited by the maximum age of the policy.
+        let msg = ExpiringMessage {
+            issued_at: 1000,
+            expires_at: None,
+        };
+        let signed = TaggedSigned::new(msg.clone(), keypair.tagged_sign(&msg));
+        assert_eq!(signed.check_freshness(2000, &policy), Ok(()));
+        assert_eq!(
+            signed.check_freshness(2100, &policy),
+            Err(TaggedVerificationError::TooOld {
+                issued_at: 1000,
+                now: 2100
+            })
+        );
+
+        // A signature verified with a different key is rejected even if the record is fresh.
+        let other_keypair = TestKeypair::generate();
+        assert_eq!(
+            signed.verify_fresh(&other_keypair.public_key(), 1200, &policy),
+            Err(TaggedVerificationError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn it_computes_the_end_of_freshness() {
+        let policy =
+            FreshnessPolicy::new(Duration::from_millis(10), Some(Duration::from_millis(1000)));
+
+        let msg = ExpiringMessage {
+            issued_at: 1000,
+            expires_at: Some(1500),
+        };
+        assert_eq!(policy.fresh_until(&msg), 1510);
+        assert_eq!(policy.check(&msg, 1510), Ok(()));
+        assert!(policy.check(&msg, 1511).is_err());
+
+        let msg = ExpiringMessage {
+            issued_at: 1000,
+            expires_at: None,
+        };
+        assert_eq!(policy.fresh_until(&msg), 2010);
+        assert_eq!(policy.check(&msg, 2010), Ok(()));
+        assert!(policy.check(&msg, 2011).is_err());
+
+        let policy = FreshnessPolicy::new(Duration::from_millis(10), None);
+        assert_eq!(policy.fresh_until(&msg), u64::MAX);
+    }
 }
 
 #[cfg(feature = "libp2p")]
@@ -0,0 +1,74 @@
+use nimiq_keys::KeyPair;
+use nimiq_test_log::test;
+use nimiq_utils::{
+    encrypted_file_store::{decrypt, encrypt, EncryptedFileStore, Error},
+    file_store::FileStore,
+};
+
+#[test]
+fn it_can_store_and_load_encrypted() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("key.dat");
+    let keypair = KeyPair::generate_default_csprng();
+
+    let store = EncryptedFileStore::new(&path).with_iterations(1);
+    assert!(!store.is_encrypted().unwrap());
+    store.store(&keypair, b"password").unwrap();
+    assert!(store.is_encrypted().unwrap());
+
+    let loaded: KeyPair = store.load(b"password").unwrap();
+    assert_eq!(loaded, keypair);
+
+    assert!(matches!(
+        store.load::<KeyPair>(b"wrong_password"),
+        Err(Error::Decryption)
+    ));
+}
+
+#[test]
+fn it_can_lock_unencrypted_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("key.dat");
+    let keypair = KeyPair::generate_default_csprng();
+    FileStore::new(&path).store(&keypair).unwrap();
+
+    let store = EncryptedFileStore::new(&path).with_iterations(1);
+    assert!(matches!(
+        store.load::<KeyPair>(b"password"),
+        Err(Error::NotEncrypted)
+    ));
+
+    store.lock::<KeyPair>(b"password").unwrap();
+    assert!(store.is_encrypted().unwrap());
+    let loaded: KeyPair = store.load(b"password").unwrap();
+    assert_eq!(loaded, keypair);
+
+    // Locking an encrypted file again leaves it untouched.
+    store.lock::<KeyPair>(b"other_password").unwrap();
+    let loaded: KeyPair = store.load(b"password").unwrap();
+    assert_eq!(loaded, keypair);
+}
+
+#[test]
+fn it_rejects_excessive_iterations() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("key.dat");
+    let keypair = KeyPair::generate_default_csprng();
+
+    let store =
+        EncryptedFileStore::new(&path).with_iterations(EncryptedFileStore::MAX_ITERATIONS + 1);
+    assert!(matches!(
+        store.store(&keypair, b"password"),
+        Err(Error::InvalidIterations(_))
+    ));
+    assert!(!path.exists());
+
+    // Replace the number of iterations, which follows the header and the salt, with a huge one.
+    let mut data = encrypt(&keypair, b"password", 1).unwrap();
+    assert_eq!(data[37], 1);
+    data.splice(37..38, [0xff, 0xff, 0xff, 0xff, 0x0f]);
+    assert!(matches!(
+        decrypt::<KeyPair>(&data, b"password"),
+        Err(Error::InvalidIterations(u32::MAX))
+    ));
+}
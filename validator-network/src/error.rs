@@ -29,6 +29,10 @@ where
     #[error("Unknown validator: {0}")]
     UnknownValidator(u16),
 
+    /// The validator record couldn't be signed.
+    #[error("Failed to sign the validator record")]
+    Signing,
+
     #[error("Network error: {0}")]
     Network(#[from] TNetworkError),
 
@@ -5,7 +5,7 @@ pub mod validator_record;
 
 use async_trait::async_trait;
 use futures::stream::BoxStream;
-use nimiq_keys::{Address, KeyPair};
+use nimiq_keys::{Address, Ed25519Signature};
 use nimiq_network_interface::{
     network::{CloseReason, MsgAcceptance, Network, SubscribeEvents, Topic},
     request::{Message, Request, RequestCommon},
@@ -74,15 +74,41 @@ pub trait ValidatorNetwork: Send + Sync {
         &self,
     ) -> Result<BoxStream<'a, (TTopic::Item, PubsubId<Self>)>, Self::Error>;
 
+    /// Unsubscribes from a specific Gossipsub topic.
+    async fn unsubscribe<TTopic: Topic + Sync>(&self) -> Result<(), Self::Error>;
+
+    /// Publishes an item into the instance of a Gossipsub topic scoped to the given epoch.
+    async fn publish_epoch<TTopic: Topic + Sync>(
+        &self,
+        epoch_number: u32,
+        item: TTopic::Item,
+    ) -> Result<(), Self::Error>;
+
+    /// Subscribes to the instance of a Gossipsub topic scoped to the given epoch.
+    /// The subscription to any other epoch of the same topic is dropped, such that traffic of
+    /// stale epochs is no longer received. This is meant to be called at election boundaries.
+    async fn subscribe_epoch<'a, TTopic: Topic + Sync>(
+        &self,
+        epoch_number: u32,
+    ) -> Result<BoxStream<'a, (TTopic::Item, PubsubId<Self>)>, Self::Error>;
+
     /// Subscribes to network events
     fn subscribe_events(&self) -> SubscribeEvents<<Self::NetworkType as Network>::PeerId>;
 
-    /// Sets this node peer ID using its secret key and public key.
-    async fn set_public_key(
+    /// Sets this node peer ID using its secret key and public key. The record is only valid for a
+    /// limited number of epochs after `epoch_number` and thus needs to be republished in every
+    /// epoch.
+    ///
+    /// The record is signed by `sign`, which signs the given message with the signing key of the
+    /// validator, or returns `None` if it can't.
+    async fn set_public_key<F>(
         &self,
         validator_address: &Address,
-        signing_key_pair: &KeyPair,
-    ) -> Result<(), Self::Error>;
+        epoch_number: u32,
+        sign: F,
+    ) -> Result<(), Self::Error>
+    where
+        F: Fn(&[u8]) -> Option<Ed25519Signature> + Send + Sync;
 
     /// Closes the connection to the peer with `peer_id` with the given `close_reason`.
     async fn disconnect_peer(
@@ -1,21 +1,34 @@
-use std::{collections::BTreeMap, error::Error, fmt::Debug, future, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error,
+    fmt::Debug,
+    future,
+    sync::Arc,
+};
 
 use async_trait::async_trait;
 use futures::{future::BoxFuture, stream::BoxStream, FutureExt, StreamExt, TryFutureExt};
 use log::warn;
-use nimiq_keys::{Address, KeyPair};
+use nimiq_keys::{Address, Ed25519Signature, KeyPair};
 use nimiq_network_interface::{
-    network::{CloseReason, MsgAcceptance, Network, SubscribeEvents, Topic},
+    network::{
+        epoch_subtopic, CloseReason, MsgAcceptance, Network, Priority, SubscribeEvents, Topic,
+    },
     request::{InboundRequestError, Message, Request, RequestCommon, RequestError},
 };
 use nimiq_primitives::slots_allocation::{Validator, Validators};
 use nimiq_serde::{Deserialize, Serialize};
-use nimiq_utils::spawn;
+use nimiq_utils::{
+    spawn,
+    tagged_signing::{TaggedKeyPair, TaggedSignable, TaggedSignature, TaggedSigned},
+};
 use parking_lot::RwLock;
 use time::OffsetDateTime;
 
 use super::{MessageStream, NetworkError, PubsubId, ValidatorNetwork};
-use crate::validator_record::ValidatorRecord;
+use crate::validator_record::{
+    dht_key, LegacyValidatorRecord, ValidatorRecord, VALIDATOR_RECORD_MAX_ADDRESSES,
+};
 
 /// Validator `PeerId` cache state
 #[derive(Clone, Copy)]
@@ -69,6 +82,8 @@ where
     /// Cache for mapping validator public keys to peer IDs
     validator_peer_id_cache: Arc<RwLock<BTreeMap<Address, CacheState<N::PeerId>>>>,
     dht_fallback: Arc<DhtFallback<N>>,
+    /// The epoch currently subscribed to per epoch-scoped topic name
+    epoch_subscriptions: Arc<RwLock<HashMap<&'static str, u32>>>,
 }
 
 impl<N> ValidatorNetworkImpl<N>
@@ -88,6 +103,7 @@ where
             validators: Arc::new(RwLock::new(None)),
             validator_peer_id_cache: Arc::new(RwLock::new(BTreeMap::new())),
             dht_fallback,
+            epoch_subscriptions: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -99,6 +115,7 @@ where
             validators: Arc::clone(&self.validators),
             validator_peer_id_cache: Arc::clone(&self.validator_peer_id_cache),
             dht_fallback: Arc::clone(&self.dht_fallback),
+            epoch_subscriptions: Arc::clone(&self.epoch_subscriptions),
         }
     }
 
@@ -137,7 +154,15 @@ where
         validator_address: &Address,
     ) -> Result<Option<N::PeerId>, NetworkError<N::Error>> {
         if let Some(record) = network
-            .dht_get::<_, ValidatorRecord<N::PeerId>, KeyPair>(validator_address)
+            .dht_get::<_, ValidatorRecord<N::PeerId>, KeyPair>(&dht_key(validator_address))
+            .await?
+        {
+            return Ok(Some(record.peer_id));
+        }
+
+        // Validators that were not upgraded yet only publish the legacy record.
+        if let Some(record) = network
+            .dht_get::<_, LegacyValidatorRecord<N::PeerId>, KeyPair>(validator_address)
             .await?
         {
             Ok(Some(record.peer_id))
@@ -317,6 +342,7 @@ impl<M: RequestCommon> RequestCommon for ValidatorMessage<M> {
     // Use distinct type IDs for the validator network.
     const TYPE_ID: u16 = 10_000 + M::TYPE_ID;
     const MAX_REQUESTS: u32 = M::MAX_REQUESTS;
+    const PRIORITY: Priority = M::PRIORITY;
 }
 
 // Proposal - gossip
@@ -511,23 +537,97 @@ where
         Ok(self.network.subscribe::<TTopic>().await?)
     }
 
+    async fn unsubscribe<TTopic>(&self) -> Result<(), Self::Error>
+    where
+        TTopic: Topic + Sync,
+    {
+        Ok(self.network.unsubscribe::<TTopic>().await?)
+    }
+
+    async fn publish_epoch<TTopic>(
+        &self,
+        epoch_number: u32,
+        item: TTopic::Item,
+    ) -> Result<(), Self::Error>
+    where
+        TTopic: Topic + Sync,
+    {
+        self.network
+            .publish_subtopic::<TTopic>(epoch_subtopic(epoch_number), item)
+            .await?;
+        Ok(())
+    }
+
+    async fn subscribe_epoch<'a, TTopic>(
+        &self,
+        epoch_number: u32,
+    ) -> Result<BoxStream<'a, (TTopic::Item, PubsubId<Self>)>, Self::Error>
+    where
+        TTopic: Topic + Sync,
+    {
+        let stream = self
+            .network
+            .subscribe_subtopic::<TTopic>(epoch_subtopic(epoch_number))
+            .await?;
+
+        let previous_epoch = self
+            .epoch_subscriptions
+            .write()
+            .insert(TTopic::NAME, epoch_number);
+        if let Some(previous_epoch) = previous_epoch.filter(|epoch| *epoch != epoch_number) {
+            if let Err(error) = self
+                .network
+                .unsubscribe_subtopic::<TTopic>(epoch_subtopic(previous_epoch))
+                .await
+            {
+                warn!(
+                    %error,
+                    topic = TTopic::NAME,
+                    epoch_number = previous_epoch,
+                    "Failed to unsubscribe from stale epoch topic"
+                );
+            }
+        }
+
+        Ok(stream)
+    }
+
     fn subscribe_events(&self) -> SubscribeEvents<<Self::NetworkType as Network>::PeerId> {
         self.network.subscribe_events()
     }
 
-    async fn set_public_key(
+    async fn set_public_key<F>(
         &self,
         validator_address: &Address,
-        signing_key_pair: &KeyPair,
-    ) -> Result<(), Self::Error> {
+        epoch_number: u32,
+        sign: F,
+    ) -> Result<(), Self::Error>
+    where
+        F: Fn(&[u8]) -> Option<Ed25519Signature> + Send + Sync,
+    {
         let peer_id = self.network.get_local_peer_id();
         let record = ValidatorRecord::new(
             peer_id,
             validator_address.clone(),
+            epoch_number,
             (OffsetDateTime::now_utc().unix_timestamp_nanos() / 1_000_000) as u64,
+            self.network
+                .get_local_addresses()
+                .into_iter()
+                .take(VALIDATOR_RECORD_MAX_ADDRESSES)
+                .collect(),
         );
+        let signed_record = sign_record::<_, KeyPair, _>(record.clone(), &sign)?;
+        self.network
+            .dht_put_signed(&dht_key(validator_address), &signed_record)
+            .await?;
+
+        // Also publish the legacy record, such that nodes that were not upgraded yet can still
+        // find us.
+        let signed_record =
+            sign_record::<_, KeyPair, _>(LegacyValidatorRecord::from(record), &sign)?;
         self.network
-            .dht_put(validator_address, &record, signing_key_pair)
+            .dht_put_signed(validator_address, &signed_record)
             .await?;
 
         Ok(())
@@ -549,3 +649,20 @@ where
             .potentially_outdated_peer_id()
     }
 }
+
+/// Signs `record` with the given signing function.
+fn sign_record<V, T, E>(
+    record: V,
+    sign: impl Fn(&[u8]) -> Option<Ed25519Signature>,
+) -> Result<TaggedSigned<V, T>, NetworkError<E>>
+where
+    V: TaggedSignable,
+    T: TaggedKeyPair,
+    E: Error + 'static,
+{
+    let signature = sign(&record.message_data()).ok_or(NetworkError::Signing)?;
+    Ok(TaggedSigned::new(
+        record,
+        TaggedSignature::from_bytes(signature.to_bytes().to_vec()),
+    ))
+}
@@ -1,15 +1,68 @@
+use std::time::Duration;
+
 use nimiq_keys::Address;
-use nimiq_serde::{Deserialize, Serialize};
-use nimiq_utils::tagged_signing::TaggedSignable;
+use nimiq_network_interface::Multiaddr;
+use nimiq_serde::{Deserialize, DeserializeError, Serialize};
+use nimiq_utils::tagged_signing::{FreshnessPolicy, TaggedSignable, TimestampedSignable};
 
 impl<TPeerId> TaggedSignable for ValidatorRecord<TPeerId>
+where
+    TPeerId: Serialize + Deserialize,
+{
+    const TAG: u8 = 0x04;
+}
+
+impl<TPeerId> TaggedSignable for LegacyValidatorRecord<TPeerId>
 where
     TPeerId: Serialize + Deserialize,
 {
     const TAG: u8 = 0x03;
 }
 
-/// Validator record that is going to be stored into the DHT
+impl<TPeerId> TimestampedSignable for LegacyValidatorRecord<TPeerId>
+where
+    TPeerId: Serialize + Deserialize,
+{
+    fn issued_at(&self) -> u64 {
+        self.timestamp
+    }
+}
+
+impl<TPeerId> TimestampedSignable for ValidatorRecord<TPeerId>
+where
+    TPeerId: Serialize + Deserialize,
+{
+    fn issued_at(&self) -> u64 {
+        self.timestamp
+    }
+}
+
+/// Interval in which validators re-sign and republish their record.
+pub const VALIDATOR_RECORD_REFRESH_INTERVAL: Duration = Duration::from_secs(60 * 60); // 1h
+
+/// Fraction of the refresh interval by which the republishing is shifted randomly, such that
+/// validators do not all republish their records at the same time.
+pub const VALIDATOR_RECORD_REFRESH_JITTER: f64 = 0.1;
+
+/// Freshness policy for validator records. Records are considered stale after missing a few
+/// refreshes and are no longer accepted or served by honest nodes.
+pub const VALIDATOR_RECORD_FRESHNESS: FreshnessPolicy = FreshnessPolicy::new(
+    Duration::from_secs(5 * 60),            // 5 min
+    Some(Duration::from_secs(4 * 60 * 60)), // 4h
+);
+
+/// Number of epochs after the epoch it was signed for in which a validator record is still
+/// accepted. Validators republish their record in every epoch, so older records are stale.
+pub const VALIDATOR_RECORD_MAX_EPOCH_AGE: u32 = 1;
+
+/// Maximum number of addresses a validator record may contain.
+pub const VALIDATOR_RECORD_MAX_ADDRESSES: usize = 15;
+
+/// Validator record that is going to be stored into the DHT.
+///
+/// This is version 2 of the record, which added the epoch and the addresses. Since nodes that
+/// only know the [`LegacyValidatorRecord`] can't parse it, it is signed with a different tag and
+/// stored under a different key, see [`dht_key`].
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
 #[serde(bound = "TPeerId: Serialize + Deserialize")]
 pub struct ValidatorRecord<TPeerId>
@@ -20,21 +73,107 @@ where
     pub peer_id: TPeerId,
     /// The Address of the validator. This is the unique identifier of a validator.
     pub validator_address: Address,
+    /// The epoch the record was signed in.
+    pub epoch_number: u32,
     /// Record timestamp in milliseconds since 1970-01-01 00:00:00 UTC, excluding leap seconds (Unix time)
     pub timestamp: u64,
+    /// Addresses the validator is reachable at. For validators behind sentries, these are the
+    /// relay circuits through their sentries, such that their own IP address is never published.
+    pub addresses: Vec<Multiaddr>,
 }
 
 impl<TPeerId> ValidatorRecord<TPeerId>
 where
     TPeerId: Serialize + Deserialize,
 {
-    pub fn new(peer_id: TPeerId, validator_address: Address, timestamp: u64) -> Self {
+    pub fn new(
+        peer_id: TPeerId,
+        validator_address: Address,
+        epoch_number: u32,
+        timestamp: u64,
+        addresses: Vec<Multiaddr>,
+    ) -> Self {
         Self {
             peer_id,
             validator_address,
+            epoch_number,
             timestamp,
+            addresses,
         }
     }
+
+    /// Checks whether the record is still valid in the given epoch, i.e. whether it was signed at
+    /// most [`VALIDATOR_RECORD_MAX_EPOCH_AGE`] epochs ago. Records of the next epoch are accepted
+    /// as well since the publisher might be slightly ahead of us.
+    pub fn is_valid_in_epoch(&self, epoch_number: u32) -> bool {
+        self.epoch_number <= epoch_number.saturating_add(1)
+            && self
+                .epoch_number
+                .saturating_add(VALIDATOR_RECORD_MAX_EPOCH_AGE)
+                >= epoch_number
+    }
+}
+
+/// Returns the DHT key under which the [`ValidatorRecord`] of a validator is stored. The key of
+/// the [`LegacyValidatorRecord`] is the validator address itself.
+pub fn dht_key(validator_address: &Address) -> Vec<u8> {
+    (ValidatorRecord::<()>::TAG, validator_address).serialize_to_vec()
+}
+
+/// Returns the validator address from a DHT key returned by [`dht_key`].
+pub fn validator_address_from_dht_key(key: &[u8]) -> Result<Address, DeserializeError> {
+    let (tag, validator_address): (u8, Address) = Deserialize::deserialize_all(key)?;
+    if tag != ValidatorRecord::<()>::TAG {
+        return Err(DeserializeError::serde_custom());
+    }
+    Ok(validator_address)
+}
+
+/// Validator record as published by nodes before the [`ValidatorRecord`] was versioned. Nodes
+/// still publish and accept it, such that nodes that were not upgraded can find validators.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+#[serde(bound = "TPeerId: Serialize + Deserialize")]
+pub struct LegacyValidatorRecord<TPeerId>
+where
+    TPeerId: Serialize + Deserialize,
+{
+    /// Validator Peer ID
+    pub peer_id: TPeerId,
+    /// The Address of the validator. This is the unique identifier of a validator.
+    pub validator_address: Address,
+    /// Record timestamp in milliseconds since 1970-01-01 00:00:00 UTC, excluding leap seconds (Unix time)
+    pub timestamp: u64,
+}
+
+impl<TPeerId> From<ValidatorRecord<TPeerId>> for LegacyValidatorRecord<TPeerId>
+where
+    TPeerId: Serialize + Deserialize,
+{
+    fn from(record: ValidatorRecord<TPeerId>) -> Self {
+        Self {
+            peer_id: record.peer_id,
+            validator_address: record.validator_address,
+            timestamp: record.timestamp,
+        }
+    }
+}
+
+impl<TPeerId> PartialOrd for LegacyValidatorRecord<TPeerId>
+where
+    TPeerId: Serialize + Deserialize + PartialEq,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.timestamp.partial_cmp(&other.timestamp)
+    }
+}
+
+impl<TPeerId> Ord for LegacyValidatorRecord<TPeerId>
+where
+    TPeerId: Serialize + Deserialize + PartialEq + Eq,
+{
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.timestamp.cmp(&other.timestamp)
+    }
 }
 
 impl<TPeerId> PartialOrd for ValidatorRecord<TPeerId>
@@ -54,3 +193,29 @@ where
         self.timestamp.cmp(&other.timestamp)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_uses_different_keys_and_tags_per_version() {
+        let validator_address = Address::from([1u8; Address::SIZE]);
+
+        let key = dht_key(&validator_address);
+        assert_ne!(key, validator_address.serialize_to_vec());
+        assert_eq!(
+            validator_address_from_dht_key(&key).unwrap(),
+            validator_address
+        );
+        assert!(validator_address_from_dht_key(&validator_address.serialize_to_vec()).is_err());
+
+        let record = ValidatorRecord::new(1u64, validator_address, 2, 3, vec![]);
+        let legacy_record = LegacyValidatorRecord::from(record.clone());
+        assert_ne!(
+            ValidatorRecord::<u64>::TAG,
+            LegacyValidatorRecord::<u64>::TAG
+        );
+        assert_ne!(record.message_data(), legacy_record.message_data());
+    }
+}
@@ -2,7 +2,7 @@ use std::{fmt, future::Future, sync::Arc, time::Duration};
 
 use futures::{future, stream::StreamExt};
 use nimiq_block::{MultiSignature, SignedSkipBlockInfo, SkipBlockInfo, SkipBlockProof};
-use nimiq_bls::{AggregateSignature, KeyPair};
+use nimiq_bls::AggregateSignature;
 use nimiq_collections::BitSet;
 use nimiq_handel::{
     aggregation::Aggregation,
@@ -17,7 +17,7 @@ use nimiq_handel::{
 };
 use nimiq_hash::Blake2sHash;
 use nimiq_network_interface::{
-    network::CloseReason,
+    network::{CloseReason, Priority},
     request::{MessageMarker, RequestCommon},
 };
 use nimiq_primitives::{policy::Policy, slots_allocation::Validators, Message};
@@ -109,6 +109,7 @@ impl RequestCommon for SkipBlockUpdate {
     const TYPE_ID: u16 = 123;
     const MAX_REQUESTS: u32 = 500;
     const TIME_WINDOW: Duration = Duration::from_millis(500);
+    const PRIORITY: Priority = Priority::Consensus;
 }
 
 struct SkipBlockAggregationProtocol {
@@ -205,8 +206,7 @@ pub struct SkipBlockAggregation {}
 
 impl SkipBlockAggregation {
     pub async fn start<N: ValidatorNetwork + 'static>(
-        skip_block_info: SkipBlockInfo,
-        voting_key: KeyPair,
+        signed_skip_block_info: SignedSkipBlockInfo,
         // TODO: This seems to be a SlotBand. Change this to a proper Validator ID.
         validator_id: u16,
         active_validators: Validators,
@@ -219,17 +219,13 @@ impl SkipBlockAggregation {
             .slots
             .clone();
 
+        let skip_block_info = signed_skip_block_info.message.clone();
         let message_hash = skip_block_info.hash_with_prefix();
         trace!(
             %message_hash,
             ?skip_block_info,
             "Starting skip block aggregation",
         );
-        let signed_skip_block_info = SignedSkipBlockInfo::from_message(
-            skip_block_info.clone(),
-            &voting_key.secret_key,
-            validator_id,
-        );
 
         let signature = AggregateSignature::from_signatures(&[signed_skip_block_info
             .signature
@@ -1,7 +1,7 @@
 use std::{collections::BTreeMap, ops};
 
 use nimiq_block::MultiSignature;
-use nimiq_bls::{AggregateSignature, SecretKey};
+use nimiq_bls::{AggregateSignature, Signature};
 use nimiq_collections::bitset::BitSet;
 use nimiq_handel::{
     contribution::{AggregatableContribution, ContributionError},
@@ -41,14 +41,14 @@ impl std::fmt::Display for TendermintContribution {
 impl TendermintContribution {
     pub(crate) fn from_vote(
         vote: TendermintVote,
-        secret_key: &SecretKey,
+        vote_signature: &Signature,
         validator_slots: ops::Range<u16>,
     ) -> Self {
         assert!(!validator_slots.is_empty());
-        // sign the hash
-        let signature = AggregateSignature::from_signatures(&[secret_key
-            .sign(&vote)
-            .multiply(validator_slots.len() as u16)]);
+        // weigh the signature over the vote by the number of slots
+        let signature = AggregateSignature::from_signatures(&[
+            vote_signature.multiply(validator_slots.len() as u16)
+        ]);
 
         // get the slots of the validator and insert them into the bitset
         let mut signers = BitSet::new();
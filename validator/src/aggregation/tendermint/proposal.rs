@@ -6,7 +6,7 @@ use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_hash::{Blake2sHash, Hash};
 use nimiq_keys::Ed25519Signature as SchnorrSignature;
 use nimiq_network_interface::{
-    network::Network,
+    network::{Network, Priority},
     request::{Handle, RequestCommon, RequestMarker},
 };
 use nimiq_serde::Serialize;
@@ -136,6 +136,7 @@ impl RequestCommon for RequestProposal {
     const TYPE_ID: u16 = 199;
     type Response = Option<SignedProposal>;
     const MAX_REQUESTS: u32 = MAX_REQUEST_RESPONSE_PROPOSAL;
+    const PRIORITY: Priority = Priority::Consensus;
 }
 
 impl<N: Network> Handle<N, Arc<RwLock<Option<MacroState>>>> for RequestProposal {
@@ -1,6 +1,9 @@
 use std::time::Duration;
 
-use nimiq_network_interface::request::{MessageMarker, RequestCommon};
+use nimiq_network_interface::{
+    network::Priority,
+    request::{MessageMarker, RequestCommon},
+};
 use nimiq_tendermint::TaggedAggregationMessage;
 use serde::{Deserialize, Serialize};
 
@@ -24,4 +27,5 @@ impl RequestCommon for TendermintUpdate {
     const TYPE_ID: u16 = 124;
     const MAX_REQUESTS: u32 = 500;
     const TIME_WINDOW: Duration = Duration::from_millis(500);
+    const PRIORITY: Priority = Priority::Consensus;
 }
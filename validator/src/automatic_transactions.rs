@@ -0,0 +1,225 @@
+use std::collections::{HashMap, HashSet};
+
+use nimiq_database::{
+    declare_table,
+    mdbx::MdbxDatabase,
+    traits::{Database, ReadCursor, ReadTransaction, WriteTransaction},
+};
+use nimiq_database_value_derive::DbSerializable;
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_transaction::Transaction;
+
+declare_table!(AutomaticTransactionsTable, "AutomaticTransactions", u16 => PendingTransaction);
+
+/// The transactions the validator sends on its own.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[repr(u8)]
+pub(crate) enum AutomaticTransactionKind {
+    /// Reactivates the validator after it was deactivated or its jail time ended.
+    Reactivate = 0,
+}
+
+/// An automatic transaction that was sent, but whose effect was not observed yet.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, DbSerializable)]
+pub(crate) struct PendingTransaction {
+    pub(crate) kind: AutomaticTransactionKind,
+    pub(crate) transaction: Transaction,
+}
+
+/// How to proceed with an automatic transaction, given the transactions sent before.
+#[derive(Debug, PartialEq)]
+pub(crate) enum SubmissionDecision {
+    /// No transaction of this kind is pending, a new one can be sent.
+    Send,
+    /// A transaction of this kind was sent before the validator restarted and can still be
+    /// included. It must be sent again instead of a new one, as it might not have reached the
+    /// network. Being identical, it can only be included (and pay fees) once.
+    Resend(Transaction),
+    /// A transaction of this kind was sent and can still be included.
+    Wait,
+}
+
+/// Persistent record of the automatic transactions sent by this validator.
+///
+/// A transaction is recorded before it is sent and stays pending until its effect is observed or
+/// its validity window ended. This prevents restarts or slowly confirming transactions from
+/// causing repeated submissions that each pay fees.
+pub(crate) struct AutomaticTransactions {
+    env: MdbxDatabase,
+    /// The pending transactions, mirroring the database.
+    pending: HashMap<AutomaticTransactionKind, Transaction>,
+    /// The kinds of pending transactions that were sent since the validator started.
+    sent: HashSet<AutomaticTransactionKind>,
+}
+
+impl AutomaticTransactions {
+    pub(crate) fn new(env: MdbxDatabase) -> Self {
+        env.create_regular_table(&AutomaticTransactionsTable);
+        let pending = env
+            .read_transaction()
+            .cursor(&AutomaticTransactionsTable)
+            .into_iter_start()
+            .map(|(_, pending)| (pending.kind, pending.transaction))
+            .collect();
+
+        Self {
+            env,
+            pending,
+            sent: HashSet::new(),
+        }
+    }
+
+    /// Decides whether a transaction of the given kind may be sent, given the current head block
+    /// number.
+    pub(crate) fn check(
+        &mut self,
+        kind: AutomaticTransactionKind,
+        block_number: u32,
+    ) -> SubmissionDecision {
+        let Some(transaction) = self.pending(kind) else {
+            return SubmissionDecision::Send;
+        };
+
+        // The transaction can't be included anymore once it is not valid in the next block.
+        if !transaction.is_valid_at(block_number + 1) {
+            debug!(
+                ?kind,
+                validity_start_height = transaction.validity_start_height,
+                "Automatic transaction expired"
+            );
+            self.clear(kind);
+            return SubmissionDecision::Send;
+        }
+
+        if self.sent.insert(kind) {
+            SubmissionDecision::Resend(transaction.clone())
+        } else {
+            SubmissionDecision::Wait
+        }
+    }
+
+    /// Returns the pending transaction of the given kind, if any.
+    pub(crate) fn pending(&self, kind: AutomaticTransactionKind) -> Option<&Transaction> {
+        self.pending.get(&kind)
+    }
+
+    /// Records a transaction as pending. This must be called before the transaction is sent.
+    pub(crate) fn record(&mut self, kind: AutomaticTransactionKind, transaction: Transaction) {
+        let mut write_transaction = self.env.write_transaction();
+        write_transaction.put(
+            &AutomaticTransactionsTable,
+            &(kind as u16),
+            &PendingTransaction {
+                kind,
+                transaction: transaction.clone(),
+            },
+        );
+        write_transaction.commit();
+        self.pending.insert(kind, transaction);
+        self.sent.insert(kind);
+    }
+
+    /// Clears the pending transaction of the given kind once its effect was observed.
+    /// Returns whether a transaction was pending.
+    pub(crate) fn clear(&mut self, kind: AutomaticTransactionKind) -> bool {
+        if self.pending.remove(&kind).is_none() {
+            return false;
+        }
+
+        let mut write_transaction = self.env.write_transaction();
+        write_transaction.remove(&AutomaticTransactionsTable, &(kind as u16));
+        write_transaction.commit();
+        self.sent.remove(&kind);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nimiq_database::mdbx::MdbxDatabase;
+    use nimiq_keys::{Address, KeyPair};
+    use nimiq_primitives::{coin::Coin, networks::NetworkId, policy::Policy};
+    use nimiq_transaction::Transaction;
+    use nimiq_transaction_builder::TransactionBuilder;
+    use nimiq_utils::key_rng::SecureGenerate;
+
+    use super::{AutomaticTransactionKind::Reactivate, AutomaticTransactions, SubmissionDecision};
+
+    fn reactivate_transaction(validity_start_height: u32) -> Transaction {
+        let key_pair = KeyPair::generate_default_csprng();
+        TransactionBuilder::new_reactivate_validator(
+            &key_pair,
+            Address::from(&key_pair),
+            &key_pair,
+            Coin::ZERO,
+            validity_start_height,
+            NetworkId::UnitAlbatross,
+        )
+    }
+
+    fn automatic_transactions() -> (MdbxDatabase, AutomaticTransactions) {
+        let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+        (env.clone(), AutomaticTransactions::new(env))
+    }
+
+    #[test]
+    fn it_sends_if_nothing_is_pending() {
+        let (_env, mut transactions) = automatic_transactions();
+
+        assert_eq!(transactions.check(Reactivate, 10), SubmissionDecision::Send);
+    }
+
+    #[test]
+    fn it_waits_for_a_pending_transaction() {
+        let (_env, mut transactions) = automatic_transactions();
+        transactions.record(Reactivate, reactivate_transaction(10));
+
+        assert_eq!(transactions.check(Reactivate, 11), SubmissionDecision::Wait);
+        assert_eq!(transactions.check(Reactivate, 12), SubmissionDecision::Wait);
+    }
+
+    #[test]
+    fn it_resends_the_pending_transaction_once_after_restart() {
+        // Crash after the transaction was recorded: it might not have been sent.
+        let (env, mut transactions) = automatic_transactions();
+        let transaction = reactivate_transaction(10);
+        transactions.record(Reactivate, transaction.clone());
+
+        let mut transactions = AutomaticTransactions::new(env);
+        assert_eq!(
+            transactions.check(Reactivate, 11),
+            SubmissionDecision::Resend(transaction)
+        );
+        assert_eq!(transactions.check(Reactivate, 12), SubmissionDecision::Wait);
+    }
+
+    #[test]
+    fn it_sends_again_once_the_pending_transaction_expired() {
+        let (env, mut transactions) = automatic_transactions();
+        transactions.record(Reactivate, reactivate_transaction(10));
+
+        let expired_at = 10 + Policy::transaction_validity_window_blocks() - 1;
+        assert_eq!(
+            transactions.check(Reactivate, expired_at - 1),
+            SubmissionDecision::Wait
+        );
+
+        // Also after a restart.
+        let mut transactions = AutomaticTransactions::new(env);
+        assert_eq!(
+            transactions.check(Reactivate, expired_at),
+            SubmissionDecision::Send
+        );
+        assert!(transactions.pending(Reactivate).is_none());
+    }
+
+    #[test]
+    fn it_sends_again_once_cleared() {
+        let (_env, mut transactions) = automatic_transactions();
+        transactions.record(Reactivate, reactivate_transaction(10));
+
+        assert!(transactions.clear(Reactivate));
+        assert!(!transactions.clear(Reactivate));
+        assert_eq!(transactions.check(Reactivate, 11), SubmissionDecision::Send);
+    }
+}
@@ -1,12 +1,86 @@
-use std::collections::HashSet;
+use std::collections::HashMap;
 
 use nimiq_block::{Block, EquivocationProof, MacroBlock, MacroHeader, MicroBlock};
+use nimiq_blockchain::Blockchain;
+use nimiq_database::{
+    declare_table,
+    mdbx::MdbxDatabase,
+    traits::{Database, ReadCursor, ReadTransaction, WriteTransaction},
+};
+use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_network_interface::network::{Priority, Topic};
 use nimiq_serde::Serialize;
+use nimiq_transaction::EquivocationLocator;
+
+/// Topic on which validators gossip the equivocation proofs they detect.
+#[derive(Clone, Debug, Default)]
+pub struct EquivocationProofTopic;
+
+impl Topic for EquivocationProofTopic {
+    type Item = EquivocationProof;
+
+    const BUFFER_SIZE: usize = 16;
+    const NAME: &'static str = "equivocation-proofs";
+    const VALIDATE: bool = true;
+    const MAX_MESSAGES: u32 = 10;
+    const PRIORITY: Priority = Priority::Consensus;
+}
+
+// The proofs are keyed by the hash of their locator, see `EquivocationProofStore::key`.
+declare_table!(EquivocationProofTable, "EquivocationProofs", Blake2bHash => EquivocationProof);
+
+/// Persistent storage for the equivocation proofs of the [`EquivocationProofPool`].
+///
+/// Proofs are written as soon as they enter the pool and removed once they are processed, such
+/// that a restart doesn't let an equivocating validator escape punishment.
+pub struct EquivocationProofStore {
+    env: MdbxDatabase,
+}
+
+impl EquivocationProofStore {
+    /// Creates a new store, creating the underlying table if it doesn't exist yet.
+    pub fn new(env: MdbxDatabase) -> Self {
+        env.create_regular_table(&EquivocationProofTable);
+        Self { env }
+    }
+
+    fn key(equivocation_proof: &EquivocationProof) -> Blake2bHash {
+        equivocation_proof.locator().hash()
+    }
+
+    fn put(&self, equivocation_proof: &EquivocationProof) {
+        let mut txn = self.env.write_transaction();
+        txn.put(
+            &EquivocationProofTable,
+            &Self::key(equivocation_proof),
+            equivocation_proof,
+        );
+        txn.commit();
+    }
+
+    fn remove<'a, I: IntoIterator<Item = &'a EquivocationProof>>(&self, equivocation_proofs: I) {
+        let mut txn = self.env.write_transaction();
+        for equivocation_proof in equivocation_proofs {
+            txn.remove(&EquivocationProofTable, &Self::key(equivocation_proof));
+        }
+        txn.commit();
+    }
+
+    fn load(&self) -> Vec<EquivocationProof> {
+        let txn = self.env.read_transaction();
+        let cursor = txn.cursor(&EquivocationProofTable);
+        cursor.into_iter_start().map(|(_, proof)| proof).collect()
+    }
+}
 
 /// Pool for holding distinct equivocation proofs that haven't been seen in blocks yet.
+///
+/// Only one proof per equivocation locator can be included in the chain, so proofs are
+/// deduplicated by their locator.
 #[derive(Default)]
 pub struct EquivocationProofPool {
-    equivocation_proofs: HashSet<EquivocationProof>,
+    equivocation_proofs: HashMap<EquivocationLocator, EquivocationProof>,
+    store: Option<EquivocationProofStore>,
 }
 
 impl EquivocationProofPool {
@@ -14,21 +88,61 @@ impl EquivocationProofPool {
         Self::default()
     }
 
-    /// Adds an equivocation proof if it is not yet part of the pool.
+    /// Creates a pool backed by the given store, restoring the proofs persisted in it.
+    /// Proofs that were included in the chain or expired in the meantime are discarded.
+    pub fn with_store(store: EquivocationProofStore, blockchain: &Blockchain) -> Self {
+        let next_block_number = blockchain.block_number() + 1;
+        let (equivocation_proofs, stale_proofs): (Vec<_>, Vec<_>) =
+            store.load().into_iter().partition(|proof| {
+                proof.is_valid_at(next_block_number)
+                    && !blockchain
+                        .history_store
+                        .has_equivocation_proof(proof.locator(), None)
+            });
+        store.remove(&stale_proofs);
+
+        Self {
+            equivocation_proofs: equivocation_proofs
+                .into_iter()
+                .map(|proof| (proof.locator(), proof))
+                .collect(),
+            store: Some(store),
+        }
+    }
+
+    /// Returns whether an equivocation proof for the same locator is part of the pool.
+    pub fn contains(&self, equivocation_proof: &EquivocationProof) -> bool {
+        self.equivocation_proofs
+            .contains_key(&equivocation_proof.locator())
+    }
+
+    /// Adds an equivocation proof if no proof for the same locator is part of the pool yet.
     /// Returns whether it has been added.
     pub fn insert(&mut self, equivocation_proof: EquivocationProof) -> bool {
-        self.equivocation_proofs.insert(equivocation_proof)
+        if self.contains(&equivocation_proof) {
+            return false;
+        }
+        if let Some(store) = &self.store {
+            store.put(&equivocation_proof);
+        }
+        self.equivocation_proofs
+            .insert(equivocation_proof.locator(), equivocation_proof);
+        true
     }
 
     /// Applies a block to the pool, removing processed equivocation proofs.
     pub fn apply_block(&mut self, block: &Block) {
+        let mut removed = Vec::new();
         match block {
             Block::Micro(MicroBlock {
                 body: Some(extrinsics),
                 ..
             }) => {
                 for equivocation_proof in extrinsics.equivocation_proofs.iter() {
-                    self.equivocation_proofs.remove(equivocation_proof);
+                    removed.extend(
+                        self.equivocation_proofs
+                            .remove(&equivocation_proof.locator()),
+                    );
                 }
             }
             Block::Macro(MacroBlock {
@@ -37,11 +151,22 @@ impl EquivocationProofPool {
             }) => {
                 // After a macro block, remove all equivocation proofs that would not be valid anymore
                 // from now on.
-                self.equivocation_proofs
-                    .retain(|proof| proof.is_valid_at(*block_number + 1));
+                self.equivocation_proofs.retain(|_, proof| {
+                    let valid = proof.is_valid_at(*block_number + 1);
+                    if !valid {
+                        removed.push(proof.clone());
+                    }
+                    valid
+                });
             }
             _ => {}
         }
+
+        if let Some(store) = &self.store {
+            if !removed.is_empty() {
+                store.remove(&removed);
+            }
+        }
     }
 
     /// Reverts a block, re-adding equivocation proofs.
@@ -52,18 +177,24 @@ impl EquivocationProofPool {
         }) = block
         {
             for equivocation_proof in extrinsics.equivocation_proofs.iter() {
-                self.equivocation_proofs.insert(equivocation_proof.clone());
+                self.insert(equivocation_proof.clone());
             }
         }
     }
 
     /// Returns a list of current equivocation proofs.
+    ///
+    /// The oldest proofs are returned first, since they are the first to expire at the end of
+    /// their reporting window.
     pub fn get_equivocation_proofs_for_block(&self, max_size: usize) -> Vec<EquivocationProof> {
+        let mut candidates: Vec<_> = self.equivocation_proofs.values().collect();
+        candidates.sort_by_key(|proof| (proof.block_number(), proof.sort_key()));
+
         let mut proofs = Vec::new();
         let mut size = 0;
-        for proof in self.equivocation_proofs.iter() {
+        for proof in candidates {
             let proof_len = proof.serialized_size();
-            if size + proof_len < max_size {
+            if size + proof_len <= max_size {
                 proofs.push(proof.clone());
                 size += proof_len;
             }
@@ -71,3 +202,88 @@ impl EquivocationProofPool {
         proofs
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use nimiq_block::{Block, EquivocationProof, ForkProof, MicroBlock, MicroBody, MicroHeader};
+    use nimiq_blockchain::{Blockchain, BlockchainConfig};
+    use nimiq_database::mdbx::MdbxDatabase;
+    use nimiq_keys::{Address, Ed25519Signature};
+    use nimiq_primitives::{networks::NetworkId, policy::Policy};
+    use nimiq_utils::time::OffsetTime;
+
+    use super::{EquivocationProofPool, EquivocationProofStore};
+
+    fn blockchain() -> Blockchain {
+        Blockchain::new(
+            MdbxDatabase::new_volatile(Default::default()).unwrap(),
+            BlockchainConfig::default(),
+            NetworkId::UnitAlbatross,
+            Arc::new(OffsetTime::new()),
+        )
+        .unwrap()
+    }
+
+    fn fork_proof(extra_data: u8) -> EquivocationProof {
+        let header = |timestamp| MicroHeader {
+            network: NetworkId::UnitAlbatross,
+            block_number: Policy::genesis_block_number() + 1,
+            timestamp,
+            extra_data: vec![extra_data],
+            ..Default::default()
+        };
+        ForkProof::new(
+            Address::default(),
+            header(1),
+            Ed25519Signature::default(),
+            header(2),
+            Ed25519Signature::default(),
+        )
+        .into()
+    }
+
+    #[test]
+    fn it_deduplicates_proofs_by_locator() {
+        let mut pool = EquivocationProofPool::new();
+
+        assert!(pool.insert(fork_proof(0)));
+        assert!(!pool.insert(fork_proof(0)));
+        // A different proof of the same equivocation is not added either.
+        assert!(!pool.insert(fork_proof(1)));
+        assert_eq!(pool.get_equivocation_proofs_for_block(usize::MAX).len(), 1);
+    }
+
+    #[test]
+    fn it_restores_unprocessed_proofs_after_restart() {
+        let blockchain = blockchain();
+        let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+
+        let mut pool = EquivocationProofPool::with_store(
+            EquivocationProofStore::new(env.clone()),
+            &blockchain,
+        );
+        assert!(pool.insert(fork_proof(0)));
+
+        let mut pool = EquivocationProofPool::with_store(
+            EquivocationProofStore::new(env.clone()),
+            &blockchain,
+        );
+        assert!(pool.contains(&fork_proof(0)));
+
+        // Once the proof is included in a block, it is no longer restored.
+        pool.apply_block(&Block::Micro(MicroBlock {
+            header: Default::default(),
+            justification: None,
+            body: Some(MicroBody {
+                equivocation_proofs: vec![fork_proof(0)],
+                transactions: vec![],
+            }),
+        }));
+        assert!(!pool.contains(&fork_proof(0)));
+
+        let pool = EquivocationProofPool::with_store(EquivocationProofStore::new(env), &blockchain);
+        assert!(!pool.contains(&fork_proof(0)));
+    }
+}
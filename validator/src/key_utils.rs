@@ -1,6 +1,7 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, io};
 
 use nimiq_bls::{CompressedPublicKey, KeyPair as BlsKeyPair};
+use nimiq_keys::{Ed25519PublicKey, KeyPair as SchnorrKeyPair};
 
 pub struct VotingKeys {
     keys: HashMap<CompressedPublicKey, BlsKeyPair>,
@@ -38,3 +39,51 @@ impl VotingKeys {
         Ok(())
     }
 }
+
+pub struct SigningKeys {
+    keys: HashMap<Ed25519PublicKey, SchnorrKeyPair>,
+    current_key: SchnorrKeyPair,
+}
+
+impl SigningKeys {
+    pub fn new(keys: Vec<SchnorrKeyPair>) -> Self {
+        assert!(!keys.is_empty());
+        let mut key_hm = HashMap::new();
+        for key in &keys {
+            key_hm.insert(key.public, key.clone());
+        }
+        SigningKeys {
+            keys: key_hm,
+            current_key: keys.first().unwrap().clone(),
+        }
+    }
+
+    pub fn add_key(&mut self, key: SchnorrKeyPair) {
+        self.keys.insert(key.public, key);
+    }
+
+    pub fn get_current_key(&self) -> SchnorrKeyPair {
+        self.current_key.clone()
+    }
+
+    pub fn get_key(&self, public_key: &Ed25519PublicKey) -> Option<SchnorrKeyPair> {
+        self.keys.get(public_key).cloned()
+    }
+
+    pub fn get_keys(&self) -> Vec<SchnorrKeyPair> {
+        self.keys.values().cloned().collect()
+    }
+
+    #[allow(clippy::result_unit_err)]
+    pub fn update_current_key(&mut self, public_key: &Ed25519PublicKey) -> Result<(), ()> {
+        self.current_key = self.keys.get(public_key).ok_or(())?.clone();
+        Ok(())
+    }
+}
+
+/// Persists keys that are added at runtime, such that they are still available after a restart.
+pub trait KeyStore: Send + Sync {
+    fn store_signing_key(&self, key: &SchnorrKeyPair) -> io::Result<()>;
+
+    fn store_voting_key(&self, key: &BlsKeyPair) -> io::Result<()>;
+}
@@ -2,10 +2,13 @@
 extern crate log;
 
 pub mod aggregation;
+mod automatic_transactions;
 mod jail;
 pub mod key_utils;
 mod r#macro;
 mod micro;
 mod proposal_buffer;
+pub mod signer;
+mod signing_journal;
 pub mod tendermint;
 pub mod validator;
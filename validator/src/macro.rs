@@ -7,10 +7,10 @@ use std::{
 
 use futures::stream::{BoxStream, Stream, StreamExt};
 use nimiq_block::MacroBlock;
-use nimiq_blockchain::{BlockProducer, Blockchain};
+use nimiq_blockchain::Blockchain;
 use nimiq_keys::Ed25519Signature as SchnorrSignature;
-use nimiq_network_interface::network::Topic;
-use nimiq_primitives::{networks::NetworkId, slots_allocation::Validators};
+use nimiq_network_interface::network::{Priority, Topic};
+use nimiq_primitives::{networks::NetworkId, policy::Policy, slots_allocation::Validators};
 use nimiq_tendermint::{
     Return as TendermintReturn, SignedProposalMessage, TaggedAggregationMessage, Tendermint,
 };
@@ -24,6 +24,7 @@ use crate::{
         state::MacroState,
         update_message::TendermintUpdate,
     },
+    signer::SigningBackend,
     tendermint::TendermintProtocol,
 };
 
@@ -58,6 +59,39 @@ where
     const NAME: &'static str = "tendermint-proposal";
     const VALIDATE: bool = true;
     const MAX_MESSAGES: u32 = 10;
+    const PRIORITY: Priority = Priority::Consensus;
+}
+
+impl<TValidatorNetwork: ValidatorNetwork + 'static> ProposalTopic<TValidatorNetwork>
+where
+    PubsubId<TValidatorNetwork>: std::fmt::Debug + Unpin,
+{
+    /// Returns the first epoch whose proposals are only gossiped on the topic scoped to their epoch.
+    ///
+    /// Validators that predate the epoch scoped topics only use the unscoped topic. Proposals of
+    /// earlier epochs are therefore published and received on both topics, such that validators
+    /// can be upgraded one by one. Protocol version 2 requires the epoch scoped topics.
+    pub(crate) fn scoped_only_epoch() -> u32 {
+        Policy::epoch_at(Policy::version_2_block_number())
+    }
+
+    /// Publishes a proposal for a block of the given epoch on the topics that are in use for the
+    /// epoch, see [`Self::scoped_only_epoch`].
+    pub(crate) async fn publish(
+        network: &TValidatorNetwork,
+        epoch_number: u32,
+        scoped_only_epoch: u32,
+        proposal: SignedProposal,
+    ) {
+        if epoch_number < scoped_only_epoch {
+            if let Err(error) = network.publish::<Self>(proposal.clone()).await {
+                log::debug!(%error, epoch_number, "Failed to publish proposal on the unscoped topic");
+            }
+        }
+        if let Err(error) = network.publish_epoch::<Self>(epoch_number, proposal).await {
+            log::debug!(%error, epoch_number, "Failed to publish proposal");
+        }
+    }
 }
 
 /// Pretty much just a wrapper for tendermint, doing some type conversions.
@@ -75,7 +109,7 @@ where
     pub fn new(
         blockchain: Arc<RwLock<Blockchain>>,
         network: Arc<TValidatorNetwork>,
-        block_producer: BlockProducer,
+        signer: Arc<dyn SigningBackend>,
         validator_slot_band: u16,
         current_validators: Validators,
         network_id: NetworkId,
@@ -103,7 +137,7 @@ where
         let dependencies = TendermintProtocol::new(
             blockchain,
             network,
-            block_producer,
+            signer,
             current_validators,
             validator_slot_band,
             network_id,
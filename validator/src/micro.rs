@@ -7,7 +7,7 @@ use std::{
 };
 
 use futures::{future::BoxFuture, ready, FutureExt, Stream};
-use nimiq_block::{Block, EquivocationProof, MicroBlock, SkipBlockInfo};
+use nimiq_block::{Block, EquivocationProof, MicroBlock, SignedSkipBlockInfo, SkipBlockInfo};
 use nimiq_blockchain::{BlockProducer, BlockProducerError, Blockchain};
 use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_mempool::mempool::Mempool;
@@ -18,7 +18,12 @@ use nimiq_validator_network::ValidatorNetwork;
 use nimiq_vrf::VrfSeed;
 use parking_lot::RwLock;
 
-use crate::{aggregation::skip_block::SkipBlockAggregation, validator::Validator};
+use crate::{
+    aggregation::skip_block::SkipBlockAggregation,
+    signer::SigningBackend,
+    signing_journal::{ProductionDecision, SigningJournal},
+    validator::Validator,
+};
 
 pub(crate) enum ProduceMicroBlockEvent {
     MicroBlock,
@@ -29,7 +34,8 @@ struct NextProduceMicroBlockEvent<TValidatorNetwork> {
     blockchain: Arc<RwLock<Blockchain>>,
     mempool: Arc<Mempool>,
     network: Arc<TValidatorNetwork>,
-    block_producer: BlockProducer,
+    signer: Arc<dyn SigningBackend>,
+    signing_journal: SigningJournal,
     validator_slot_band: u16,
     equivocation_proofs: Vec<EquivocationProof>,
     prev_seed: VrfSeed,
@@ -46,7 +52,8 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
         blockchain: Arc<RwLock<Blockchain>>,
         mempool: Arc<Mempool>,
         network: Arc<TValidatorNetwork>,
-        block_producer: BlockProducer,
+        signer: Arc<dyn SigningBackend>,
+        signing_journal: SigningJournal,
         validator_slot_band: u16,
         equivocation_proofs: Vec<EquivocationProof>,
         prev_seed: VrfSeed,
@@ -58,7 +65,8 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
             blockchain,
             mempool,
             network,
-            block_producer,
+            signer,
+            signing_journal,
             validator_slot_band,
             equivocation_proofs,
             prev_seed,
@@ -121,18 +129,43 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
                 self.block_number,
             );
 
-            let block = match self.produce_micro_block(&blockchain) {
-                Ok(block) => block,
-                Err(error) => {
-                    error!(
+            // Check whether we already signed a block for this slot, e.g. before a restart.
+            let block = match self
+                .signing_journal
+                .check(self.block_number, &blockchain.head_hash())
+            {
+                ProductionDecision::Produce => match self.produce_micro_block(&blockchain) {
+                    Ok(block) => {
+                        // Record the block before publishing it, such that we never sign a
+                        // different block for this slot.
+                        self.signing_journal.record(&block);
+                        block
+                    }
+                    Err(error) => {
+                        error!(
+                            block_number = self.block_number,
+                            %error,
+                            "Failed to construct micro block"
+                        );
+
+                        // Sleep a bit to allow the task to be cancelled externally if needed.
+                        delay = Duration::from_millis(50);
+                        continue;
+                    }
+                },
+                ProductionDecision::Republish(block) => {
+                    info!(
                         block_number = self.block_number,
-                        %error,
-                        "Failed to construct micro block"
+                        "Publishing the micro block we already signed for this slot"
                     );
-
-                    // Sleep a bit to allow the task to be cancelled externally if needed.
-                    delay = Duration::from_millis(50);
-                    continue;
+                    block
+                }
+                ProductionDecision::Skip => {
+                    warn!(
+                        block_number = self.block_number,
+                        "Already signed a micro block for this slot on a different parent, not producing another one"
+                    );
+                    break None;
                 }
             };
 
@@ -237,9 +270,25 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
             vrf_entropy: self.prev_seed.entropy(),
         };
 
+        let signature = match self.signer.sign_skip_block(&skip_block_info) {
+            Ok(signature) => signature,
+            Err(error) => {
+                error!(
+                    block_number = self.block_number,
+                    %error,
+                    "Failed to sign skip block info"
+                );
+                return (None, self);
+            }
+        };
+        let signed_skip_block_info = SignedSkipBlockInfo {
+            message: skip_block_info,
+            signer_idx: self.validator_slot_band,
+            signature,
+        };
+
         let (_, skip_block_proof) = SkipBlockAggregation::start(
-            skip_block_info.clone(),
-            self.block_producer.voting_key.clone(),
+            signed_skip_block_info,
             self.validator_slot_band,
             active_validators.unwrap(),
             Arc::clone(&self.network),
@@ -257,7 +306,8 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
             } else {
                 let timestamp = head.timestamp() + Policy::MIN_PRODUCER_TIMEOUT;
 
-                let skip_block = self.block_producer.next_micro_block(
+                let skip_block = BlockProducer::next_micro_block_with_signer(
+                    &*self.signer,
                     &blockchain,
                     timestamp,
                     vec![],
@@ -358,7 +408,11 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> NextProduceMicroBlockEvent<T
 
         transactions.append(&mut regular_transactions);
 
-        self.block_producer.next_micro_block(
+        // Drop transactions whose expiry timestamp has passed by the time of this block.
+        transactions.retain(|tx| !tx.is_expired_at(timestamp));
+
+        BlockProducer::next_micro_block_with_signer(
+            &*self.signer,
             blockchain,
             timestamp,
             self.equivocation_proofs.clone(),
@@ -397,7 +451,8 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> ProduceMicroBlock<TValidator
         blockchain: Arc<RwLock<Blockchain>>,
         mempool: Arc<Mempool>,
         network: Arc<TValidatorNetwork>,
-        block_producer: BlockProducer,
+        signer: Arc<dyn SigningBackend>,
+        signing_journal: SigningJournal,
         validator_slot_band: u16,
         equivocation_proofs: Vec<EquivocationProof>,
         prev_seed: VrfSeed,
@@ -409,7 +464,8 @@ impl<TValidatorNetwork: ValidatorNetwork + 'static> ProduceMicroBlock<TValidator
             blockchain,
             mempool,
             network,
-            block_producer,
+            signer,
+            signing_journal,
             validator_slot_band,
             equivocation_proofs,
             prev_seed,
@@ -440,6 +440,22 @@ where
             // Acquire the lock on the shared buffer.
             let mut shared = self.shared.lock();
 
+            // Until all validators use the epoch scoped proposal topics, proposals are received on
+            // two topics, see `ProposalTopic::scoped_only_epoch`. The second copy of a proposal
+            // must not evict the first one.
+            if let Some((buffered, _)) = shared.buffer.get(&source) {
+                if buffered.signer == proposal.0.signer
+                    && buffered.signature == proposal.0.signature
+                {
+                    self.network
+                        .validate_message::<ProposalTopic<TValidatorNetwork>>(
+                            proposal.1,
+                            MsgAcceptance::Ignore,
+                        );
+                    return;
+                }
+            }
+
             // Put the proposal into the buffer, potentially evicting an already existing proposal.
             if let Some((old_proposal, old_pubsub)) = shared.buffer.insert(source, proposal.clone())
             {
@@ -569,7 +585,7 @@ mod test {
         sync::syncer_proxy::SyncerProxy, BlsCache, Consensus, ConsensusEvent, ConsensusProxy,
     };
     use nimiq_keys::{KeyPair as SchnorrKeyPair, PrivateKey as SchnorrPrivateKey};
-    use nimiq_network_interface::network::Network as NetworkInterface;
+    use nimiq_network_interface::network::{epoch_subtopic, Network as NetworkInterface};
     use nimiq_network_mock::{MockHub, MockNetwork};
     use nimiq_primitives::{policy::Policy, TendermintProposal};
     use nimiq_serde::{Deserialize, Serialize};
@@ -587,7 +603,10 @@ mod test {
     use tokio::select;
 
     use super::{ProposalAndPubsubId, ProposalBuffer};
-    use crate::{aggregation::tendermint::proposal::Header, r#macro::ProposalTopic};
+    use crate::{
+        aggregation::tendermint::proposal::{Header, SignedProposal},
+        r#macro::ProposalTopic,
+    };
 
     /// Given a blockchain and a network creates an instance of Consensus.
     async fn consensus<N: NetworkInterface + TestNetwork>(
@@ -666,6 +685,8 @@ mod test {
                             }
                             established.0 = true;
                         }
+                        Some(Ok(ConsensusEvent::PeerSetChanged {..}))
+                        | Some(Ok(ConsensusEvent::SyncProgress {..})) => {}
                         _ => established.0 = false,
                     }
                 }
@@ -677,6 +698,8 @@ mod test {
                             }
                             established.1 = true;
                         }
+                        Some(Ok(ConsensusEvent::PeerSetChanged {..}))
+                        | Some(Ok(ConsensusEvent::SyncProgress {..})) => {}
                         _ => established.1 = false,
                     }
                 }
@@ -687,13 +710,7 @@ mod test {
         (consensus_proxy, producer1, producer2, nw1, nw2, signing_key)
     }
 
-    async fn create_proposal_msg(
-        nw1: Arc<MockNetwork>,
-        nw2: Arc<MockNetwork>,
-        signing_key: SchnorrKeyPair,
-        macro_header: MacroHeader,
-    ) -> ProposalAndPubsubId<ValidatorNetworkImpl<MockNetwork>> {
-        // Create the proposal message which chain 2 will receive.
+    fn signed_proposal(signing_key: &SchnorrKeyPair, macro_header: MacroHeader) -> SignedProposal {
         let proposal_message: ProposalMessage<Header<MacroHeader>> = ProposalMessage {
             proposal: Header(macro_header, None),
             round: 0,
@@ -708,10 +725,21 @@ mod test {
         .hash()
         .serialize_to_vec();
 
-        let signed_message = SignedProposalMessage {
+        SignedProposalMessage {
             message: proposal_message,
             signature: (signing_key.sign(&data), 0),
-        };
+        }
+        .into()
+    }
+
+    async fn create_proposal_msg(
+        nw1: Arc<MockNetwork>,
+        nw2: Arc<MockNetwork>,
+        signing_key: SchnorrKeyPair,
+        macro_header: MacroHeader,
+    ) -> ProposalAndPubsubId<ValidatorNetworkImpl<MockNetwork>> {
+        // Create the proposal message which chain 2 will receive.
+        let signed_proposal = signed_proposal(&signing_key, macro_header);
 
         // Send the proposal over gossipsup to get it correctly filled with a pubsub_id
         // First subscribe to the topic on network2. Note that nothing else subscribes to this.
@@ -721,7 +749,7 @@ mod test {
             .await
             .expect("subscribe must succeed");
         // Broadcast on nw1
-        nw1.publish::<ProposalTopic<ValidatorNetworkImpl<MockNetwork>>>(signed_message.into())
+        nw1.publish::<ProposalTopic<ValidatorNetworkImpl<MockNetwork>>>(signed_proposal)
             .await
             .expect("Publishing on the proposal topic must succeed.");
         // receive the proposal
@@ -962,4 +990,51 @@ mod test {
             .push(macro_block)
             .expect("pushing the macro block must work");
     }
+
+    /// Validators that predate the epoch scoped proposal topics only receive proposals on the
+    /// unscoped topic, so proposals are published there as well until the activation epoch.
+    #[test(tokio::test)]
+    async fn it_publishes_proposals_on_the_unscoped_topic_until_activation() {
+        type Proposals = ProposalTopic<ValidatorNetworkImpl<MockNetwork>>;
+
+        let mut hub = MockHub::default();
+        let nw1 = Arc::new(hub.new_network_with_address(0));
+        let nw2 = Arc::new(hub.new_network_with_address(1));
+        nw1.dial_mock(&nw2);
+        let network = ValidatorNetworkImpl::new(Arc::clone(&nw1));
+
+        let mut unscoped = nw2.subscribe::<Proposals>().await.unwrap();
+        let mut epoch_1 = nw2
+            .subscribe_subtopic::<Proposals>(epoch_subtopic(1))
+            .await
+            .unwrap();
+        let mut epoch_2 = nw2
+            .subscribe_subtopic::<Proposals>(epoch_subtopic(2))
+            .await
+            .unwrap();
+
+        let signing_key = SchnorrKeyPair::from(
+            SchnorrPrivateKey::deserialize_from_vec(&hex::decode(SIGNING_KEY).unwrap()).unwrap(),
+        );
+        let proposal = signed_proposal(&signing_key, MacroHeader::default());
+
+        // Before the activation epoch, proposals are published on both topics.
+        Proposals::publish(&network, 1, 2, proposal.clone()).await;
+        assert!(timeout(Duration::from_secs(1), unscoped.next())
+            .await
+            .unwrap()
+            .is_some());
+        assert!(timeout(Duration::from_secs(1), epoch_1.next())
+            .await
+            .unwrap()
+            .is_some());
+
+        // From the activation epoch on, proposals are only published on the epoch scoped topic.
+        Proposals::publish(&network, 2, 2, proposal).await;
+        assert!(timeout(Duration::from_secs(1), epoch_2.next())
+            .await
+            .unwrap()
+            .is_some());
+        assert!(unscoped.next().now_or_never().is_none());
+    }
 }
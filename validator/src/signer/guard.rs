@@ -0,0 +1,245 @@
+use std::{collections::BTreeMap, path::Path};
+
+use nimiq_block::{MicroHeader, SkipBlockInfo};
+use nimiq_hash::{Blake2bHash, Blake2sHash, Hash};
+use nimiq_primitives::{
+    networks::NetworkId, policy::Policy, Message, TendermintStep, TendermintVote,
+};
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_transaction::Transaction;
+use nimiq_utils::file_store::{Error as FileStoreError, FileStore};
+use parking_lot::Mutex;
+
+use super::SigningError;
+
+/// The messages signed so far, by the heights they were signed for.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+struct SignedHeights {
+    /// The highest block number anything was signed for.
+    highest_block_number: u32,
+    micro_blocks: BTreeMap<u32, Blake2bHash>,
+    /// Proposals by block number and round.
+    proposals: BTreeMap<(u32, u32), Blake2sHash>,
+    /// Tendermint votes by block number, round and step.
+    votes: BTreeMap<(u32, u32, TendermintStep), Option<Blake2sHash>>,
+    skip_blocks: BTreeMap<u32, Blake2sHash>,
+}
+
+impl SignedHeights {
+    /// Messages for blocks before the current batch can't be signed legitimately anymore, since
+    /// the macro block ending the batch is final.
+    fn lowest_block_number(&self) -> u32 {
+        self.highest_block_number
+            .saturating_sub(Policy::blocks_per_batch())
+    }
+
+    fn prune(&mut self) {
+        let lowest = self.lowest_block_number();
+        self.micro_blocks
+            .retain(|block_number, _| *block_number >= lowest);
+        self.proposals
+            .retain(|(block_number, _), _| *block_number >= lowest);
+        self.votes
+            .retain(|(block_number, _, _), _| *block_number >= lowest);
+        self.skip_blocks
+            .retain(|block_number, _| *block_number >= lowest);
+    }
+}
+
+/// Refuses to sign messages that conflict with messages signed before, which would be punished
+/// as equivocation. This is what makes it safe to run a remote signer for a validator: Even a
+/// compromised or misbehaving validator can't make the signer sign conflicting micro blocks,
+/// proposals, votes or skip blocks.
+///
+/// Signing the exact same message again is allowed, e.g. for a validator that restarted.
+/// Messages for blocks before the current batch are refused. The signed heights can be persisted
+/// to a file, such that they survive restarts of the signer.
+pub struct DoubleSignGuard {
+    network_id: NetworkId,
+    heights: Mutex<SignedHeights>,
+    file_store: Option<FileStore>,
+}
+
+impl DoubleSignGuard {
+    /// Creates a guard for messages of the given network that doesn't persist the signed heights.
+    pub fn new(network_id: NetworkId) -> Self {
+        Self {
+            network_id,
+            heights: Mutex::new(SignedHeights::default()),
+            file_store: None,
+        }
+    }
+
+    /// Creates a guard for messages of the given network that persists the signed heights to the
+    /// file at `path`, loading the previously signed heights from it.
+    pub fn with_file<P: AsRef<Path>>(
+        network_id: NetworkId,
+        path: P,
+    ) -> Result<Self, FileStoreError> {
+        let file_store = FileStore::new(path);
+        let heights = file_store.load_or_store(SignedHeights::default)?;
+        Ok(Self {
+            network_id,
+            heights: Mutex::new(heights),
+            file_store: Some(file_store),
+        })
+    }
+
+    pub fn check_micro_header(&self, header: &MicroHeader) -> Result<(), SigningError> {
+        self.check_network(header.network)?;
+        let hash: Blake2bHash = header.hash();
+        self.record(header.block_number, |heights| {
+            Self::check_entry(
+                &mut heights.micro_blocks,
+                header.block_number,
+                hash,
+                "micro block",
+            )
+        })
+    }
+
+    pub fn check_proposal(
+        &self,
+        block_number: u32,
+        round: u32,
+        proposal_hash: &Blake2sHash,
+    ) -> Result<(), SigningError> {
+        self.record(block_number, |heights| {
+            Self::check_entry(
+                &mut heights.proposals,
+                (block_number, round),
+                proposal_hash.clone(),
+                "proposal",
+            )
+        })
+    }
+
+    pub fn check_tendermint_vote(&self, vote: &TendermintVote) -> Result<(), SigningError> {
+        self.check_network(vote.id.network)?;
+        let id = &vote.id;
+        self.record(id.block_number, |heights| {
+            Self::check_entry(
+                &mut heights.votes,
+                (id.block_number, id.round_number, id.step),
+                vote.proposal_hash.clone(),
+                "Tendermint vote",
+            )
+        })
+    }
+
+    pub fn check_skip_block(&self, skip_block_info: &SkipBlockInfo) -> Result<(), SigningError> {
+        self.check_network(skip_block_info.network_id)?;
+        self.record(skip_block_info.block_number, |heights| {
+            Self::check_entry(
+                &mut heights.skip_blocks,
+                skip_block_info.block_number,
+                skip_block_info.hash_with_prefix(),
+                "skip block",
+            )
+        })
+    }
+
+    /// Transactions don't conflict with each other, so only their network is checked.
+    pub fn check_transaction(&self, transaction: &Transaction) -> Result<(), SigningError> {
+        self.check_network(transaction.network_id)
+    }
+
+    fn check_network(&self, network_id: NetworkId) -> Result<(), SigningError> {
+        if network_id != self.network_id {
+            return Err(SigningError::Refused(format!(
+                "message for network {network_id}, expected {}",
+                self.network_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Checks the message for `block_number` with `check` and persists the signed heights if the
+    /// message is signed for the first time. Returns an error if the message must not be signed.
+    fn record<F>(&self, block_number: u32, check: F) -> Result<(), SigningError>
+    where
+        F: FnOnce(&mut SignedHeights) -> Result<bool, SigningError>,
+    {
+        let mut heights = self.heights.lock();
+        if block_number < heights.lowest_block_number() {
+            return Err(SigningError::Refused(format!(
+                "block {block_number} is before the current batch"
+            )));
+        }
+
+        let mut updated = heights.clone();
+        if !check(&mut updated)? {
+            return Ok(());
+        }
+        if block_number > updated.highest_block_number {
+            updated.highest_block_number = block_number;
+            updated.prune();
+        }
+
+        // Only sign once the new heights are persisted, otherwise a restart of the signer would
+        // forget about the signed message.
+        if let Some(file_store) = &self.file_store {
+            file_store.store(&updated).map_err(|error| {
+                SigningError::Refused(format!("failed to persist the signed heights: {error}"))
+            })?;
+        }
+        *heights = updated;
+        Ok(())
+    }
+
+    /// Returns whether the entry is new, or an error if a different message was signed for the
+    /// same key before.
+    fn check_entry<K: Ord, V: PartialEq>(
+        entries: &mut BTreeMap<K, V>,
+        key: K,
+        value: V,
+        kind: &str,
+    ) -> Result<bool, SigningError> {
+        match entries.get(&key) {
+            Some(signed) if *signed == value => Ok(false),
+            Some(_) => Err(SigningError::Refused(format!(
+                "a conflicting {kind} was signed before"
+            ))),
+            None => {
+                entries.insert(key, value);
+                Ok(true)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use nimiq_hash::{Blake2sHasher, Hasher};
+    use nimiq_primitives::{networks::NetworkId, policy::Policy};
+
+    use super::DoubleSignGuard;
+
+    #[test]
+    fn it_remembers_signed_heights_across_restarts() {
+        let path =
+            std::env::temp_dir().join(format!("nimiq-signed-heights-{}", std::process::id()));
+        let proposal_hash = Blake2sHasher::default().digest(b"proposal");
+        let other_proposal_hash = Blake2sHasher::default().digest(b"other proposal");
+        {
+            let guard = DoubleSignGuard::with_file(NetworkId::UnitAlbatross, &path).unwrap();
+            guard.check_proposal(60, 0, &proposal_hash).unwrap();
+        }
+
+        let guard = DoubleSignGuard::with_file(NetworkId::UnitAlbatross, &path).unwrap();
+        guard.check_proposal(60, 0, &proposal_hash).unwrap();
+        assert!(guard.check_proposal(60, 0, &other_proposal_hash).is_err());
+        guard.check_proposal(60, 1, &other_proposal_hash).unwrap();
+
+        // Blocks before the current batch can't be signed for anymore.
+        let next_batch = 60 + Policy::blocks_per_batch();
+        guard
+            .check_proposal(next_batch + 1, 0, &proposal_hash)
+            .unwrap();
+        assert!(guard.check_proposal(60, 2, &proposal_hash).is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+}
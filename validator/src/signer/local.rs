@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use nimiq_block::{MicroHeader, SkipBlockInfo};
+use nimiq_bls::{PublicKey as BlsPublicKey, Signature as BlsSignature};
+use nimiq_hash::{Blake2bHash, Blake2sHash, Hash};
+use nimiq_keys::{Ed25519PublicKey, Ed25519Signature};
+use nimiq_primitives::{policy::Policy, Message, TendermintVote};
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_transaction::{account::staking_contract::IncomingStakingTransactionData, Transaction};
+use nimiq_utils::tagged_signing::TaggedSignable;
+use nimiq_validator_network::validator_record::{LegacyValidatorRecord, ValidatorRecord};
+use nimiq_vrf::VrfSeed;
+use parking_lot::RwLock;
+
+use super::{SigningBackend, SigningError};
+use crate::key_utils::{SigningKeys, VotingKeys};
+
+/// Signs with the current keys of the given key sets, so it follows key rotations of the
+/// validator.
+#[derive(Clone)]
+pub struct LocalSigner {
+    signing_keys: Arc<RwLock<SigningKeys>>,
+    voting_keys: Arc<RwLock<VotingKeys>>,
+}
+
+impl LocalSigner {
+    pub fn new(
+        signing_keys: Arc<RwLock<SigningKeys>>,
+        voting_keys: Arc<RwLock<VotingKeys>>,
+    ) -> Self {
+        Self {
+            signing_keys,
+            voting_keys,
+        }
+    }
+
+    fn sign(&self, data: &[u8]) -> Ed25519Signature {
+        self.signing_keys.read().get_current_key().sign(data)
+    }
+
+    fn sign_vote(&self, hash: Blake2sHash) -> BlsSignature {
+        self.voting_keys
+            .read()
+            .get_current_key()
+            .secret_key
+            .sign_hash(hash)
+    }
+}
+
+impl SigningBackend for LocalSigner {
+    fn signing_public_key(&self) -> Ed25519PublicKey {
+        self.signing_keys.read().get_current_key().public
+    }
+
+    fn voting_public_key(&self) -> BlsPublicKey {
+        self.voting_keys.read().get_current_key().public_key
+    }
+
+    fn sign_micro_header(&self, header: &MicroHeader) -> Result<Ed25519Signature, SigningError> {
+        let hash: Blake2bHash = header.hash();
+        Ok(self.sign(hash.as_slice()))
+    }
+
+    fn sign_proposal(
+        &self,
+        _block_number: u32,
+        _round: u32,
+        proposal_hash: &Blake2sHash,
+    ) -> Result<Ed25519Signature, SigningError> {
+        Ok(self.sign(&proposal_hash.serialize_to_vec()))
+    }
+
+    fn sign_tendermint_vote(&self, vote: &TendermintVote) -> Result<BlsSignature, SigningError> {
+        Ok(self.sign_vote(vote.hash()))
+    }
+
+    fn sign_skip_block(
+        &self,
+        skip_block_info: &SkipBlockInfo,
+    ) -> Result<BlsSignature, SigningError> {
+        Ok(self.sign_vote(skip_block_info.hash_with_prefix()))
+    }
+
+    fn sign_validator_record(&self, message: &[u8]) -> Result<Ed25519Signature, SigningError> {
+        // The message data of tagged signables starts with the tag, so this makes sure that
+        // nothing but validator records can be signed.
+        match message.first() {
+            Some(&ValidatorRecord::<()>::TAG) | Some(&LegacyValidatorRecord::<()>::TAG) => {
+                Ok(self.sign(message))
+            }
+            _ => Err(SigningError::Refused("not a validator record".to_owned())),
+        }
+    }
+
+    fn sign_reactivate_transaction(
+        &self,
+        transaction: &Transaction,
+    ) -> Result<Ed25519Signature, SigningError> {
+        let is_reactivate = transaction.recipient == Policy::STAKING_CONTRACT_ADDRESS
+            && matches!(
+                IncomingStakingTransactionData::deserialize_all(&transaction.recipient_data),
+                Ok(IncomingStakingTransactionData::ReactivateValidator { .. })
+            );
+        if !is_reactivate {
+            return Err(SigningError::Refused(
+                "not a reactivate validator transaction".to_owned(),
+            ));
+        }
+        Ok(self.sign(&transaction.serialize_content()))
+    }
+
+    fn next_vrf_seed(
+        &self,
+        prev_seed: &VrfSeed,
+        block_number: u32,
+    ) -> Result<VrfSeed, SigningError> {
+        Ok(prev_seed.sign_next(&self.signing_keys.read().get_current_key(), block_number))
+    }
+}
@@ -0,0 +1,122 @@
+//! Abstraction over where the consensus keys of the validator are kept.
+//!
+//! Everything the validator signs with its signing or voting key, i.e. proposals, votes, micro
+//! blocks, VRF seeds, its validator record and reactivate transactions, is signed through a
+//! [`SigningBackend`]. The [`LocalSigner`] signs with the keys loaded into the validator, the
+//! [`RemoteSigner`] forwards the requests to a signer process on another machine, which can be
+//! run with [`RemoteSignerServer`].
+//!
+//! The backend is asked to sign typed messages rather than arbitrary data, such that a signer
+//! only signs what a validator legitimately needs signed and can refuse to sign conflicting
+//! messages, see [`DoubleSignGuard`].
+
+mod guard;
+mod local;
+mod remote;
+
+use nimiq_block::{MicroHeader, SkipBlockInfo};
+use nimiq_blockchain::{BlockProducerError, BlockSigner};
+use nimiq_bls::{PublicKey as BlsPublicKey, Signature as BlsSignature};
+use nimiq_hash::Blake2sHash;
+use nimiq_keys::{Ed25519PublicKey, Ed25519Signature};
+use nimiq_primitives::TendermintVote;
+use nimiq_serde::DeserializeError;
+use nimiq_transaction::Transaction;
+use nimiq_vrf::VrfSeed;
+use thiserror::Error;
+
+pub use self::{
+    guard::DoubleSignGuard,
+    local::LocalSigner,
+    remote::{RemoteSigner, RemoteSignerServer, SignerAddress, SignerAddressParseError},
+};
+
+#[derive(Debug, Error)]
+pub enum SigningError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Noise protocol error: {0}")]
+    Noise(#[from] snow::Error),
+    #[error("Failed to deserialize message: {0}")]
+    Deserialize(#[from] DeserializeError),
+    #[error("Remote signer returned an invalid key or signature")]
+    InvalidResponse,
+    #[error("Remote signer refused to sign: {0}")]
+    Remote(String),
+    #[error("Refusing to sign: {0}")]
+    Refused(String),
+    #[error("Remote signer did not respond in time")]
+    Timeout,
+    #[error("Remote signer connection is unavailable")]
+    Unavailable,
+}
+
+/// Signs everything the validator contributes to consensus.
+pub trait SigningBackend: Send + Sync {
+    /// The Schnorr public key of the validator.
+    fn signing_public_key(&self) -> Ed25519PublicKey;
+
+    /// The BLS public key of the validator.
+    fn voting_public_key(&self) -> BlsPublicKey;
+
+    /// Signs the hash of a micro block header with the signing key.
+    fn sign_micro_header(&self, header: &MicroHeader) -> Result<Ed25519Signature, SigningError>;
+
+    /// Signs the hash of the macro block proposal for `round` of the macro block at
+    /// `block_number` with the signing key. The proposal is only passed by its hash, since the
+    /// header of an election block is too large to be sent to a remote signer.
+    fn sign_proposal(
+        &self,
+        block_number: u32,
+        round: u32,
+        proposal_hash: &Blake2sHash,
+    ) -> Result<Ed25519Signature, SigningError>;
+
+    /// Signs a Tendermint vote with the voting key.
+    fn sign_tendermint_vote(&self, vote: &TendermintVote) -> Result<BlsSignature, SigningError>;
+
+    /// Signs a skip block with the voting key.
+    fn sign_skip_block(
+        &self,
+        skip_block_info: &SkipBlockInfo,
+    ) -> Result<BlsSignature, SigningError>;
+
+    /// Signs the message data of a validator record with the signing key.
+    fn sign_validator_record(&self, message: &[u8]) -> Result<Ed25519Signature, SigningError>;
+
+    /// Signs the staking data of a transaction reactivating the validator with the signing key.
+    fn sign_reactivate_transaction(
+        &self,
+        transaction: &Transaction,
+    ) -> Result<Ed25519Signature, SigningError>;
+
+    /// Computes the VRF seed of the block at `block_number` from the seed of its predecessor.
+    fn next_vrf_seed(
+        &self,
+        prev_seed: &VrfSeed,
+        block_number: u32,
+    ) -> Result<VrfSeed, SigningError>;
+}
+
+impl BlockSigner for dyn SigningBackend {
+    fn signing_public_key(&self) -> Ed25519PublicKey {
+        SigningBackend::signing_public_key(self)
+    }
+
+    fn sign_micro_header(
+        &self,
+        header: &MicroHeader,
+    ) -> Result<Ed25519Signature, BlockProducerError> {
+        SigningBackend::sign_micro_header(self, header)
+            .map_err(|error| BlockProducerError::SigningError(error.to_string()))
+    }
+
+    fn next_vrf_seed(
+        &self,
+        prev_seed: &VrfSeed,
+        block_number: u32,
+    ) -> Result<VrfSeed, BlockProducerError> {
+        SigningBackend::next_vrf_seed(self, prev_seed, block_number)
+            .map_err(|error| BlockProducerError::SigningError(error.to_string()))
+    }
+}
@@ -0,0 +1,713 @@
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::{
+    fmt, io,
+    net::SocketAddr,
+    str::FromStr,
+    sync::{mpsc as std_mpsc, Arc},
+    time::Duration,
+};
+
+use nimiq_block::{MicroHeader, SkipBlockInfo};
+use nimiq_bls::{
+    CompressedPublicKey, CompressedSignature, PublicKey as BlsPublicKey, Signature as BlsSignature,
+};
+use nimiq_hash::Blake2sHash;
+use nimiq_keys::{Ed25519PublicKey, Ed25519Signature};
+use nimiq_primitives::{TendermintIdentifier, TendermintVote};
+use nimiq_serde::{Deserialize, Serialize};
+use nimiq_time::timeout;
+use nimiq_transaction::Transaction;
+use nimiq_utils::spawn;
+use nimiq_vrf::VrfSeed;
+use snow::{HandshakeState, TransportState};
+use thiserror::Error;
+#[cfg(unix)]
+use tokio::net::{UnixListener, UnixStream};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    runtime::{Handle, RuntimeFlavor},
+    sync::{mpsc, OwnedSemaphorePermit, Semaphore},
+    task,
+};
+
+use super::{DoubleSignGuard, SigningBackend, SigningError};
+
+/// The noise handshake used between validator and signer. Both sides authenticate each other
+/// through the pre-shared key, which is mixed into the very first handshake message.
+const NOISE_PARAMS: &str = "Noise_NNpsk0_25519_ChaChaPoly_BLAKE2s";
+
+/// Maximum size of a noise message.
+const MAX_MESSAGE_SIZE: usize = 65535;
+
+/// Size of the authentication tag noise appends to every encrypted message.
+const TAG_SIZE: usize = 16;
+
+/// Time the validator waits for the signer before giving up on a request. Also bounds the time
+/// the signer waits for a connecting validator to complete the handshake.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Maximum number of requests waiting to be sent to the signer.
+const MAX_PENDING_REQUESTS: usize = 16;
+
+/// Maximum number of connections a signer serves at the same time. Further connections are
+/// closed right away.
+const MAX_CONNECTIONS: usize = 4;
+
+#[derive(Debug, Serialize, Deserialize)]
+enum SignerRequest {
+    PublicKeys,
+    MicroHeader(MicroHeader),
+    Proposal {
+        block_number: u32,
+        round: u32,
+        proposal_hash: Blake2sHash,
+    },
+    TendermintVote {
+        proposal_hash: Option<Blake2sHash>,
+        id: TendermintIdentifier,
+    },
+    SkipBlock(SkipBlockInfo),
+    ValidatorRecord(Vec<u8>),
+    ReactivateTransaction(Transaction),
+    NextVrfSeed {
+        prev_seed: VrfSeed,
+        block_number: u32,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+enum SignerResponse {
+    PublicKeys {
+        signing_key: Ed25519PublicKey,
+        voting_key: CompressedPublicKey,
+    },
+    Signature(Ed25519Signature),
+    VoteSignature(CompressedSignature),
+    VrfSeed(VrfSeed),
+    Error(String),
+}
+
+#[derive(Debug, Error)]
+#[error("Invalid signer address: {0}")]
+pub struct SignerAddressParseError(String);
+
+/// Where a remote signer can be reached. Unix sockets are given as `unix:<path>`, everything
+/// else is parsed as a TCP socket address.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum SignerAddress {
+    Tcp(SocketAddr),
+    #[cfg(unix)]
+    Unix(PathBuf),
+}
+
+impl FromStr for SignerAddress {
+    type Err = SignerAddressParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(path) = s.strip_prefix("unix:") {
+            #[cfg(unix)]
+            return Ok(SignerAddress::Unix(PathBuf::from(path)));
+            #[cfg(not(unix))]
+            return Err(SignerAddressParseError(format!(
+                "unix sockets are not supported on this platform: {path}"
+            )));
+        }
+        s.parse()
+            .map(SignerAddress::Tcp)
+            .map_err(|_| SignerAddressParseError(s.to_owned()))
+    }
+}
+
+impl fmt::Display for SignerAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SignerAddress::Tcp(address) => write!(f, "{address}"),
+            #[cfg(unix)]
+            SignerAddress::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+impl SignerAddress {
+    async fn connect(&self) -> io::Result<SignerStream> {
+        let stream: SignerStream = match self {
+            SignerAddress::Tcp(address) => {
+                let stream = TcpStream::connect(address).await?;
+                stream.set_nodelay(true)?;
+                Box::new(stream)
+            }
+            #[cfg(unix)]
+            SignerAddress::Unix(path) => Box::new(UnixStream::connect(path).await?),
+        };
+        Ok(stream)
+    }
+}
+
+trait AsyncStream: AsyncRead + AsyncWrite + Send + Unpin {}
+
+impl<T: AsyncRead + AsyncWrite + Send + Unpin> AsyncStream for T {}
+
+type SignerStream = Box<dyn AsyncStream>;
+
+/// Writes a frame prefixed by its length as big endian `u16`.
+async fn write_frame(stream: &mut SignerStream, frame: &[u8]) -> io::Result<()> {
+    let len = u16::try_from(frame.len())
+        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "frame too large"))?;
+    stream.write_all(&len.to_be_bytes()).await?;
+    stream.write_all(frame).await?;
+    stream.flush().await
+}
+
+async fn read_frame(stream: &mut SignerStream) -> io::Result<Vec<u8>> {
+    let len = stream.read_u16().await?;
+    let mut frame = vec![0u8; len as usize];
+    stream.read_exact(&mut frame).await?;
+    Ok(frame)
+}
+
+fn noise_builder(psk: &[u8; 32]) -> Result<snow::Builder<'_>, SigningError> {
+    Ok(snow::Builder::new(NOISE_PARAMS.parse()?).psk(0, psk))
+}
+
+/// An encrypted and authenticated connection between validator and signer.
+struct NoiseChannel {
+    stream: SignerStream,
+    transport: TransportState,
+}
+
+impl NoiseChannel {
+    async fn initiate(mut stream: SignerStream, psk: &[u8; 32]) -> Result<Self, SigningError> {
+        let mut handshake = noise_builder(psk)?.build_initiator()?;
+        let mut buf = vec![0u8; MAX_MESSAGE_SIZE];
+
+        let len = handshake.write_message(&[], &mut buf)?;
+        write_frame(&mut stream, &buf[..len]).await?;
+        handshake.read_message(&read_frame(&mut stream).await?, &mut buf)?;
+
+        Self::finish(stream, handshake)
+    }
+
+    async fn respond(mut stream: SignerStream, psk: &[u8; 32]) -> Result<Self, SigningError> {
+        let mut handshake = noise_builder(psk)?.build_responder()?;
+        let mut buf = vec![0u8; MAX_MESSAGE_SIZE];
+
+        handshake.read_message(&read_frame(&mut stream).await?, &mut buf)?;
+        let len = handshake.write_message(&[], &mut buf)?;
+        write_frame(&mut stream, &buf[..len]).await?;
+
+        Self::finish(stream, handshake)
+    }
+
+    fn finish(stream: SignerStream, handshake: HandshakeState) -> Result<Self, SigningError> {
+        Ok(Self {
+            stream,
+            transport: handshake.into_transport_mode()?,
+        })
+    }
+
+    async fn send<T: Serialize>(&mut self, message: &T) -> Result<(), SigningError> {
+        let plaintext = message.serialize_to_vec();
+        let mut buf = vec![0u8; plaintext.len() + TAG_SIZE];
+        let len = self.transport.write_message(&plaintext, &mut buf)?;
+        write_frame(&mut self.stream, &buf[..len]).await?;
+        Ok(())
+    }
+
+    async fn receive<T: Deserialize>(&mut self) -> Result<T, SigningError> {
+        let frame = read_frame(&mut self.stream).await?;
+        let mut buf = vec![0u8; frame.len()];
+        let len = self.transport.read_message(&frame, &mut buf)?;
+        Ok(T::deserialize_all(&buf[..len])?)
+    }
+
+    async fn request(&mut self, request: &SignerRequest) -> Result<SignerResponse, SigningError> {
+        self.send(request).await?;
+        self.receive().await
+    }
+}
+
+type PendingRequest = (
+    SignerRequest,
+    std_mpsc::SyncSender<Result<SignerResponse, SigningError>>,
+);
+
+/// The connection of a [`RemoteSigner`] to the signer. It runs as a task that sends the requests
+/// one after the other and reconnects if the connection broke down.
+struct SignerConnection {
+    address: SignerAddress,
+    psk: [u8; 32],
+    channel: Option<NoiseChannel>,
+}
+
+impl SignerConnection {
+    async fn run(mut self, mut requests: mpsc::Receiver<PendingRequest>) {
+        while let Some((request, response_tx)) = requests.recv().await {
+            let response = match timeout(REQUEST_TIMEOUT, self.request(&request)).await {
+                Ok(response) => response,
+                Err(_) => {
+                    // The response might still arrive later on, so the connection can't be
+                    // used anymore.
+                    self.channel = None;
+                    Err(SigningError::Timeout)
+                }
+            };
+            // The requester might have given up already.
+            let _ = response_tx.try_send(response);
+        }
+    }
+
+    async fn request(&mut self, request: &SignerRequest) -> Result<SignerResponse, SigningError> {
+        // Reuse the established connection, but reconnect once if it broke down in the meantime.
+        if let Some(channel) = self.channel.as_mut() {
+            match channel.request(request).await {
+                Ok(response) => return Ok(response),
+                Err(error) => {
+                    debug!(%error, address = %self.address, "Reconnecting to remote signer");
+                    self.channel = None;
+                }
+            }
+        }
+
+        let mut channel = NoiseChannel::initiate(self.address.connect().await?, &self.psk).await?;
+        let response = channel.request(request).await?;
+        self.channel = Some(channel);
+
+        Ok(response)
+    }
+}
+
+/// Signs by forwarding every request to a [`RemoteSignerServer`].
+///
+/// The public keys are fetched from the signer when connecting, so the signer's keys can't be
+/// rotated without reconnecting. The connection to the signer is owned by a task, the signing
+/// methods wait for its response for at most a few seconds. They must not be called from a
+/// current thread runtime, since the connection task couldn't make progress while they wait.
+pub struct RemoteSigner {
+    signing_public_key: Ed25519PublicKey,
+    voting_public_key: BlsPublicKey,
+    requests: mpsc::Sender<PendingRequest>,
+}
+
+impl RemoteSigner {
+    /// Connects to the signer at `address` using the pre-shared key `psk`.
+    pub async fn connect(address: SignerAddress, psk: [u8; 32]) -> Result<Self, SigningError> {
+        let mut connection = SignerConnection {
+            address,
+            psk,
+            channel: None,
+        };
+        let response = timeout(
+            REQUEST_TIMEOUT,
+            connection.request(&SignerRequest::PublicKeys),
+        )
+        .await
+        .map_err(|_| SigningError::Timeout)??;
+        let (signing_public_key, voting_public_key) = match response {
+            SignerResponse::PublicKeys {
+                signing_key,
+                voting_key,
+            } => (
+                signing_key,
+                voting_key
+                    .uncompress()
+                    .map_err(|_| SigningError::InvalidResponse)?,
+            ),
+            SignerResponse::Error(error) => return Err(SigningError::Remote(error)),
+            _ => return Err(SigningError::InvalidResponse),
+        };
+
+        info!(
+            address = %connection.address,
+            %signing_public_key,
+            "Connected to remote signer"
+        );
+
+        let (requests, requests_rx) = mpsc::channel(MAX_PENDING_REQUESTS);
+        spawn(connection.run(requests_rx));
+
+        Ok(Self {
+            signing_public_key,
+            voting_public_key,
+            requests,
+        })
+    }
+
+    fn request(&self, request: SignerRequest) -> Result<SignerResponse, SigningError> {
+        let (response_tx, response_rx) = std_mpsc::sync_channel(1);
+        self.requests
+            .try_send((request, response_tx))
+            .map_err(|_| SigningError::Unavailable)?;
+
+        let wait = || {
+            response_rx
+                .recv_timeout(REQUEST_TIMEOUT)
+                .map_err(|_| SigningError::Timeout)?
+        };
+        match Handle::try_current() {
+            // Let the runtime move its other tasks to another worker while waiting.
+            Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+                task::block_in_place(wait)
+            }
+            Ok(_) => Err(SigningError::Unavailable),
+            Err(_) => wait(),
+        }
+    }
+
+    fn request_signature(&self, request: SignerRequest) -> Result<Ed25519Signature, SigningError> {
+        match self.request(request)? {
+            SignerResponse::Signature(signature) => Ok(signature),
+            SignerResponse::Error(error) => Err(SigningError::Remote(error)),
+            _ => Err(SigningError::InvalidResponse),
+        }
+    }
+
+    fn request_vote_signature(&self, request: SignerRequest) -> Result<BlsSignature, SigningError> {
+        match self.request(request)? {
+            SignerResponse::VoteSignature(signature) => signature
+                .uncompress()
+                .map_err(|_| SigningError::InvalidResponse),
+            SignerResponse::Error(error) => Err(SigningError::Remote(error)),
+            _ => Err(SigningError::InvalidResponse),
+        }
+    }
+}
+
+impl SigningBackend for RemoteSigner {
+    fn signing_public_key(&self) -> Ed25519PublicKey {
+        self.signing_public_key
+    }
+
+    fn voting_public_key(&self) -> BlsPublicKey {
+        self.voting_public_key
+    }
+
+    fn sign_micro_header(&self, header: &MicroHeader) -> Result<Ed25519Signature, SigningError> {
+        self.request_signature(SignerRequest::MicroHeader(header.clone()))
+    }
+
+    fn sign_proposal(
+        &self,
+        block_number: u32,
+        round: u32,
+        proposal_hash: &Blake2sHash,
+    ) -> Result<Ed25519Signature, SigningError> {
+        self.request_signature(SignerRequest::Proposal {
+            block_number,
+            round,
+            proposal_hash: proposal_hash.clone(),
+        })
+    }
+
+    fn sign_tendermint_vote(&self, vote: &TendermintVote) -> Result<BlsSignature, SigningError> {
+        self.request_vote_signature(SignerRequest::TendermintVote {
+            proposal_hash: vote.proposal_hash.clone(),
+            id: vote.id.clone(),
+        })
+    }
+
+    fn sign_skip_block(
+        &self,
+        skip_block_info: &SkipBlockInfo,
+    ) -> Result<BlsSignature, SigningError> {
+        self.request_vote_signature(SignerRequest::SkipBlock(skip_block_info.clone()))
+    }
+
+    fn sign_validator_record(&self, message: &[u8]) -> Result<Ed25519Signature, SigningError> {
+        self.request_signature(SignerRequest::ValidatorRecord(message.to_vec()))
+    }
+
+    fn sign_reactivate_transaction(
+        &self,
+        transaction: &Transaction,
+    ) -> Result<Ed25519Signature, SigningError> {
+        self.request_signature(SignerRequest::ReactivateTransaction(transaction.clone()))
+    }
+
+    fn next_vrf_seed(
+        &self,
+        prev_seed: &VrfSeed,
+        block_number: u32,
+    ) -> Result<VrfSeed, SigningError> {
+        let request = SignerRequest::NextVrfSeed {
+            prev_seed: prev_seed.clone(),
+            block_number,
+        };
+        match self.request(request)? {
+            SignerResponse::VrfSeed(seed) => Ok(seed),
+            SignerResponse::Error(error) => Err(SigningError::Remote(error)),
+            _ => Err(SigningError::InvalidResponse),
+        }
+    }
+}
+
+/// The signer side of the remote signing protocol. It answers the requests of [`RemoteSigner`]s
+/// using the given backend, usually a [`LocalSigner`](super::LocalSigner) holding the keys.
+///
+/// Only validators knowing the pre-shared key can connect. Every message is checked by the
+/// [`DoubleSignGuard`] before it is signed, so the signer never signs conflicting messages.
+pub struct RemoteSignerServer {
+    backend: Arc<dyn SigningBackend>,
+    guard: Arc<DoubleSignGuard>,
+    psk: [u8; 32],
+    connections: Arc<Semaphore>,
+}
+
+impl RemoteSignerServer {
+    pub fn new(backend: Arc<dyn SigningBackend>, guard: DoubleSignGuard, psk: [u8; 32]) -> Self {
+        Self {
+            backend,
+            guard: Arc::new(guard),
+            psk,
+            connections: Arc::new(Semaphore::new(MAX_CONNECTIONS)),
+        }
+    }
+
+    /// Listens on `address` and serves every incoming connection in its own task.
+    /// Only returns if the listener fails.
+    pub async fn serve(&self, address: &SignerAddress) -> Result<(), SigningError> {
+        match address {
+            SignerAddress::Tcp(address) => self.serve_tcp(TcpListener::bind(address).await?).await,
+            #[cfg(unix)]
+            SignerAddress::Unix(path) => {
+                let listener = UnixListener::bind(path)?;
+                loop {
+                    let (stream, _) = listener.accept().await?;
+                    debug!("Accepted remote signer connection");
+                    self.spawn_connection(Box::new(stream));
+                }
+            }
+        }
+    }
+
+    async fn serve_tcp(&self, listener: TcpListener) -> Result<(), SigningError> {
+        loop {
+            let (stream, peer) = listener.accept().await?;
+            stream.set_nodelay(true)?;
+            debug!(%peer, "Accepted remote signer connection");
+            self.spawn_connection(Box::new(stream));
+        }
+    }
+
+    fn spawn_connection(&self, stream: SignerStream) {
+        let Ok(permit) = Arc::clone(&self.connections).try_acquire_owned() else {
+            warn!("Too many remote signer connections, closing connection");
+            return;
+        };
+        let backend = Arc::clone(&self.backend);
+        let guard = Arc::clone(&self.guard);
+        let psk = self.psk;
+        spawn(async move {
+            if let Err(error) = Self::handle_connection(backend, guard, stream, psk, permit).await {
+                warn!(%error, "Remote signer connection closed");
+            }
+        });
+    }
+
+    async fn handle_connection(
+        backend: Arc<dyn SigningBackend>,
+        guard: Arc<DoubleSignGuard>,
+        stream: SignerStream,
+        psk: [u8; 32],
+        _permit: OwnedSemaphorePermit,
+    ) -> Result<(), SigningError> {
+        // Don't let connections that never complete the handshake occupy a connection slot.
+        let mut channel = timeout(REQUEST_TIMEOUT, NoiseChannel::respond(stream, &psk))
+            .await
+            .map_err(|_| SigningError::Timeout)??;
+        loop {
+            let request: SignerRequest = match channel.receive().await {
+                Ok(request) => request,
+                // The validator closed the connection.
+                Err(SigningError::Io(error)) if error.kind() == io::ErrorKind::UnexpectedEof => {
+                    return Ok(())
+                }
+                Err(error) => return Err(error),
+            };
+            trace!(?request, "Received signing request");
+
+            // Checking the request might involve persisting the signed heights.
+            let backend = Arc::clone(&backend);
+            let guard = Arc::clone(&guard);
+            let response =
+                task::spawn_blocking(move || Self::handle_request(&*backend, &guard, request))
+                    .await
+                    .unwrap_or_else(|error| SignerResponse::Error(error.to_string()));
+            channel.send(&response).await?;
+        }
+    }
+
+    fn handle_request(
+        backend: &dyn SigningBackend,
+        guard: &DoubleSignGuard,
+        request: SignerRequest,
+    ) -> SignerResponse {
+        let result = match request {
+            SignerRequest::PublicKeys => Ok(SignerResponse::PublicKeys {
+                signing_key: backend.signing_public_key(),
+                voting_key: backend.voting_public_key().compress(),
+            }),
+            SignerRequest::MicroHeader(header) => guard
+                .check_micro_header(&header)
+                .and_then(|_| backend.sign_micro_header(&header))
+                .map(SignerResponse::Signature),
+            SignerRequest::Proposal {
+                block_number,
+                round,
+                proposal_hash,
+            } => guard
+                .check_proposal(block_number, round, &proposal_hash)
+                .and_then(|_| backend.sign_proposal(block_number, round, &proposal_hash))
+                .map(SignerResponse::Signature),
+            SignerRequest::TendermintVote { proposal_hash, id } => {
+                let vote = TendermintVote { proposal_hash, id };
+                guard
+                    .check_tendermint_vote(&vote)
+                    .and_then(|_| backend.sign_tendermint_vote(&vote))
+                    .map(|signature| SignerResponse::VoteSignature(signature.compress()))
+            }
+            SignerRequest::SkipBlock(skip_block_info) => guard
+                .check_skip_block(&skip_block_info)
+                .and_then(|_| backend.sign_skip_block(&skip_block_info))
+                .map(|signature| SignerResponse::VoteSignature(signature.compress())),
+            SignerRequest::ValidatorRecord(message) => backend
+                .sign_validator_record(&message)
+                .map(SignerResponse::Signature),
+            SignerRequest::ReactivateTransaction(transaction) => guard
+                .check_transaction(&transaction)
+                .and_then(|_| backend.sign_reactivate_transaction(&transaction))
+                .map(SignerResponse::Signature),
+            SignerRequest::NextVrfSeed {
+                prev_seed,
+                block_number,
+            } => backend
+                .next_vrf_seed(&prev_seed, block_number)
+                .map(SignerResponse::VrfSeed),
+        };
+        result.unwrap_or_else(|error| {
+            warn!(%error, "Refused signing request");
+            SignerResponse::Error(error.to_string())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nimiq_bls::KeyPair as BlsKeyPair;
+    use nimiq_keys::{KeyPair as SchnorrKeyPair, SecureGenerate};
+    use nimiq_primitives::{networks::NetworkId, TendermintStep};
+    use nimiq_test_log::test;
+    use parking_lot::RwLock;
+
+    use super::*;
+    use crate::{
+        key_utils::{SigningKeys, VotingKeys},
+        signer::LocalSigner,
+    };
+
+    async fn start_server(psk: [u8; 32]) -> (LocalSigner, SignerAddress) {
+        let signer = LocalSigner::new(
+            Arc::new(RwLock::new(SigningKeys::new(vec![
+                SchnorrKeyPair::generate_default_csprng(),
+            ]))),
+            Arc::new(RwLock::new(VotingKeys::new(vec![
+                BlsKeyPair::generate_default_csprng(),
+            ]))),
+        );
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = SignerAddress::Tcp(listener.local_addr().unwrap());
+
+        let server = RemoteSignerServer::new(
+            Arc::new(signer.clone()),
+            DoubleSignGuard::new(NetworkId::UnitAlbatross),
+            psk,
+        );
+        spawn(async move {
+            let _ = server.serve_tcp(listener).await;
+        });
+
+        (signer, address)
+    }
+
+    fn vote(round_number: u32, proposal_hash: Option<Blake2sHash>) -> TendermintVote {
+        TendermintVote {
+            proposal_hash,
+            id: TendermintIdentifier {
+                network: NetworkId::UnitAlbatross,
+                block_number: 60,
+                round_number,
+                step: TendermintStep::PreVote,
+            },
+        }
+    }
+
+    #[test(tokio::test(flavor = "multi_thread", worker_threads = 2))]
+    async fn remote_signer_signs_like_local_signer() {
+        let (local, address) = start_server([1; 32]).await;
+        let remote = RemoteSigner::connect(address, [1; 32]).await.unwrap();
+
+        assert_eq!(remote.signing_public_key(), local.signing_public_key());
+        assert_eq!(remote.voting_public_key(), local.voting_public_key());
+
+        let proposal_hash = Blake2sHash::default();
+        assert_eq!(
+            remote.sign_proposal(60, 0, &proposal_hash).unwrap(),
+            local.sign_proposal(60, 0, &proposal_hash).unwrap()
+        );
+
+        let vote = vote(0, None);
+        assert_eq!(
+            remote.sign_tendermint_vote(&vote).unwrap().compress(),
+            local.sign_tendermint_vote(&vote).unwrap().compress()
+        );
+
+        let seed = remote.next_vrf_seed(&VrfSeed::default(), 1).unwrap();
+        assert!(seed
+            .verify(&VrfSeed::default(), &remote.signing_public_key(), 1)
+            .is_ok());
+    }
+
+    #[test(tokio::test(flavor = "multi_thread", worker_threads = 2))]
+    async fn remote_signer_refuses_conflicting_messages() {
+        let (_, address) = start_server([1; 32]).await;
+        let remote = RemoteSigner::connect(address, [1; 32]).await.unwrap();
+
+        // Signing the same message again is fine, signing a conflicting one isn't.
+        let signed_vote = vote(0, Some(Blake2sHash::default()));
+        remote.sign_tendermint_vote(&signed_vote).unwrap();
+        remote.sign_tendermint_vote(&signed_vote).unwrap();
+        assert!(remote.sign_tendermint_vote(&vote(0, None)).is_err());
+        remote.sign_tendermint_vote(&vote(1, None)).unwrap();
+
+        let skip_block_info = SkipBlockInfo {
+            network_id: NetworkId::UnitAlbatross,
+            block_number: 61,
+            vrf_entropy: VrfSeed::default().entropy(),
+        };
+        remote.sign_skip_block(&skip_block_info).unwrap();
+        let other_seed =
+            VrfSeed::default().sign_next(&SchnorrKeyPair::generate_default_csprng(), 61);
+        assert!(remote
+            .sign_skip_block(&SkipBlockInfo {
+                vrf_entropy: other_seed.entropy(),
+                ..skip_block_info.clone()
+            })
+            .is_err());
+        assert!(remote
+            .sign_skip_block(&SkipBlockInfo {
+                network_id: NetworkId::Main,
+                ..skip_block_info
+            })
+            .is_err());
+
+        // Only validator records are signed, not arbitrary data.
+        assert!(remote.sign_validator_record(b"proposal").is_err());
+    }
+
+    #[test(tokio::test(flavor = "multi_thread", worker_threads = 2))]
+    async fn remote_signer_rejects_wrong_psk() {
+        let (_, address) = start_server([1; 32]).await;
+        assert!(RemoteSigner::connect(address, [2; 32]).await.is_err());
+    }
+}
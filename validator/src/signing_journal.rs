@@ -0,0 +1,171 @@
+use nimiq_block::MicroBlock;
+use nimiq_database::{
+    declare_table,
+    mdbx::MdbxDatabase,
+    traits::{Database, ReadTransaction, WriteTransaction},
+};
+use nimiq_hash::Blake2bHash;
+
+declare_table!(SigningJournalTable, "SigningJournal", () => MicroBlock);
+
+/// How to proceed with the production of a micro block, given the blocks signed before.
+#[derive(Debug, PartialEq)]
+pub(crate) enum ProductionDecision {
+    /// No block was signed for this block number yet, a new block can be produced.
+    Produce,
+    /// A block was already signed for this block number on top of the same parent. It must be
+    /// published again instead of producing a different one.
+    Republish(MicroBlock),
+    /// A block was already signed for this block number on top of a different parent. Producing
+    /// another one would be an equivocation.
+    Skip,
+}
+
+/// Persistent journal of the last micro block signed by this validator.
+///
+/// The block is recorded before it is published. A validator restarting within its slot thus
+/// publishes the identical block again instead of signing a conflicting one.
+#[derive(Clone)]
+pub(crate) struct SigningJournal {
+    env: MdbxDatabase,
+}
+
+impl SigningJournal {
+    pub(crate) fn new(env: MdbxDatabase) -> Self {
+        env.create_regular_table(&SigningJournalTable);
+        Self { env }
+    }
+
+    /// Decides whether a micro block with the given block number may be produced on top of the
+    /// block with the given hash.
+    pub(crate) fn check(&self, block_number: u32, parent_hash: &Blake2bHash) -> ProductionDecision {
+        let read_transaction = self.env.read_transaction();
+        match read_transaction.get(&SigningJournalTable, &()) {
+            Some(block) if block.block_number() == block_number => {
+                if block.header.parent_hash == *parent_hash {
+                    ProductionDecision::Republish(block)
+                } else {
+                    ProductionDecision::Skip
+                }
+            }
+            _ => ProductionDecision::Produce,
+        }
+    }
+
+    /// Records a signed micro block. This must be called before the block is published.
+    pub(crate) fn record(&self, block: &MicroBlock) {
+        let mut write_transaction = self.env.write_transaction();
+        write_transaction.put(&SigningJournalTable, &(), block);
+        write_transaction.commit();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nimiq_block::{Block, MicroBlock, MicroHeader};
+    use nimiq_blockchain_interface::{AbstractBlockchain, PushResult};
+    use nimiq_database::mdbx::MdbxDatabase;
+    use nimiq_hash::{Blake2bHash, Hash};
+    use nimiq_test_log::test;
+    use nimiq_test_utils::block_production::TemporaryBlockProducer;
+
+    use super::{ProductionDecision, SigningJournal};
+
+    fn micro_block(block_number: u32, parent_hash: Blake2bHash) -> MicroBlock {
+        MicroBlock {
+            header: MicroHeader {
+                block_number,
+                parent_hash,
+                ..Default::default()
+            },
+            justification: None,
+            body: None,
+        }
+    }
+
+    fn journal() -> (MdbxDatabase, SigningJournal) {
+        let env = MdbxDatabase::new_volatile(Default::default()).unwrap();
+        (env.clone(), SigningJournal::new(env))
+    }
+
+    #[test]
+    fn it_produces_if_nothing_was_signed() {
+        // Crash before the block was recorded: nothing was published, so a new block is produced.
+        let (_env, journal) = journal();
+        let parent_hash: Blake2bHash = "parent".hash();
+
+        assert_eq!(journal.check(5, &parent_hash), ProductionDecision::Produce);
+    }
+
+    #[test]
+    fn it_republishes_the_signed_block_after_restart() {
+        // Crash after the block was recorded, published or pushed: the identical block is used.
+        let (env, journal) = journal();
+        let parent_hash: Blake2bHash = "parent".hash();
+        let block = micro_block(5, parent_hash.clone());
+        journal.record(&block);
+
+        let journal = SigningJournal::new(env);
+        assert_eq!(
+            journal.check(5, &parent_hash),
+            ProductionDecision::Republish(block)
+        );
+    }
+
+    #[test]
+    fn it_skips_if_a_block_was_signed_on_another_parent() {
+        let (_env, journal) = journal();
+        journal.record(&micro_block(5, "parent".hash()));
+
+        assert_eq!(
+            journal.check(5, &"other parent".hash()),
+            ProductionDecision::Skip
+        );
+    }
+
+    #[test]
+    fn it_produces_for_other_block_numbers() {
+        let (_env, journal) = journal();
+        let parent_hash: Blake2bHash = "parent".hash();
+        journal.record(&micro_block(5, parent_hash.clone()));
+
+        assert_eq!(journal.check(6, &parent_hash), ProductionDecision::Produce);
+        assert_eq!(journal.check(4, &parent_hash), ProductionDecision::Produce);
+    }
+
+    #[test]
+    fn it_republishes_a_block_lost_in_a_crash() {
+        let producer = TemporaryBlockProducer::new();
+        let (env, journal) = journal();
+
+        let block = producer.next_block_no_push(vec![], false).unwrap_micro();
+        let parent_hash = producer.blockchain.read().head_hash();
+        assert_eq!(
+            journal.check(block.block_number(), &parent_hash),
+            ProductionDecision::Produce
+        );
+        journal.record(&block);
+
+        // The validator crashes after recording the block but before publishing it.
+        drop(journal);
+        let journal = SigningJournal::new(env);
+
+        let ProductionDecision::Republish(republished) =
+            journal.check(block.block_number(), &parent_hash)
+        else {
+            panic!("The signed block must be republished after a restart");
+        };
+        assert_eq!(republished, block);
+        assert_eq!(
+            producer.push(Block::Micro(republished)),
+            Ok(PushResult::Extended)
+        );
+
+        // Once the block is part of the chain, the next one can be produced.
+        let head_hash = producer.blockchain.read().head_hash();
+        assert_eq!(
+            journal.check(block.block_number() + 1, &head_hash),
+            ProductionDecision::Produce
+        );
+    }
+}
@@ -2,7 +2,7 @@ use std::{future::Future, sync::Arc};
 
 use futures::{
     future::{self, BoxFuture, FutureExt},
-    stream::{BoxStream, StreamExt},
+    stream::{self, BoxStream, StreamExt},
 };
 use nimiq_block::{Block, MacroBlock, TendermintProof};
 use nimiq_blockchain::{BlockProducer, Blockchain};
@@ -44,6 +44,7 @@ use crate::{
         },
     },
     r#macro::ProposalTopic,
+    signer::SigningBackend,
 };
 
 // A note for the signing of the proposal:
@@ -121,8 +122,8 @@ pub struct TendermintProtocol<TValidatorNetwork: ValidatorNetwork> {
     pub network_id: NetworkId,
     // The block number of the macro block to produce.
     pub block_height: u32,
-    // Information relative to our validator that is necessary to produce blocks.
-    pub block_producer: BlockProducer,
+    // The signer for our validator's proposals, votes and VRF seeds.
+    pub signer: Arc<dyn SigningBackend>,
     // The validators for the current epoch.
     pub current_validators: Validators,
     // The main blockchain struct. Contains all of this validator information about the current chain.
@@ -138,7 +139,7 @@ impl<TValidatorNetwork: ValidatorNetwork> Clone for TendermintProtocol<TValidato
             validator_slot_band: self.validator_slot_band,
             network_id: self.network_id,
             block_height: self.block_height,
-            block_producer: self.block_producer.clone(),
+            signer: Arc::clone(&self.signer),
             current_validators: self.current_validators.clone(),
             blockchain: Arc::clone(&self.blockchain),
             validator_registry: Arc::clone(&self.validator_registry),
@@ -153,14 +154,14 @@ where
     pub fn new(
         blockchain: Arc<RwLock<Blockchain>>,
         network: Arc<TValidatorNetwork>,
-        block_producer: BlockProducer,
+        signer: Arc<dyn SigningBackend>,
         current_validators: Validators,
         validator_slot_band: u16,
         network_id: NetworkId,
         block_height: u32,
     ) -> Self {
         Self {
-            block_producer,
+            signer,
             blockchain,
             network_id,
             block_height,
@@ -230,10 +231,17 @@ where
 
         // Create the proposal.
         let time = blockchain.time.now();
-        let block = self
-            .block_producer
-            .next_macro_block_proposal(&blockchain, time, round, vec![])
-            .map_err(|_| ProtocolError::Abort)?;
+        let block = BlockProducer::next_macro_block_proposal_with_signer(
+            &*self.signer,
+            &blockchain,
+            time,
+            round,
+            vec![],
+        )
+        .map_err(|error| {
+            log::error!(%error, round, "Failed to create macro block proposal");
+            ProtocolError::Abort
+        })?;
 
         // Always `Some(…)` because the above function always sets it to `Some(…)`.
         let body = block.body.expect("produced blocks always have a body");
@@ -254,10 +262,15 @@ where
         proposal: SignedProposalMessage<Self::Proposal, Self::ProposalSignature>,
     ) {
         let nw = Arc::clone(&self.network);
+        let epoch_number = Policy::epoch_at(self.block_height);
         spawn(async move {
-            let _ = nw
-                .publish::<ProposalTopic<TValidatorNetwork>>(proposal.into())
-                .await;
+            ProposalTopic::<TValidatorNetwork>::publish(
+                &*nw,
+                epoch_number,
+                ProposalTopic::<TValidatorNetwork>::scoped_only_epoch(),
+                proposal.into(),
+            )
+            .await;
         });
     }
 
@@ -362,18 +375,21 @@ where
     fn sign_proposal(
         &self,
         proposal_message: &ProposalMessage<Self::Proposal>,
-    ) -> Self::ProposalSignature {
-        let data = TendermintProposal {
+    ) -> Result<Self::ProposalSignature, ProtocolError> {
+        let proposal_hash = TendermintProposal {
             proposal: &proposal_message.proposal.0,
             round: proposal_message.round,
             valid_round: proposal_message.valid_round,
         }
-        .hash()
-        .serialize_to_vec();
-        (
-            self.block_producer.signing_key.sign(&data),
-            self.validator_slot_band,
-        )
+        .hash();
+        let signature = self
+            .signer
+            .sign_proposal(self.block_height, proposal_message.round, &proposal_hash)
+            .map_err(|error| {
+                log::error!(%error, "Failed to sign macro block proposal");
+                ProtocolError::Abort
+            })?;
+        Ok((signature, self.validator_slot_band))
     }
 
     fn create_aggregation(
@@ -415,11 +431,21 @@ where
             .public_key(one_of_our_slots as usize)
             .expect("Key must be be present");
 
-        assert_eq!(self.block_producer.voting_key.public_key, public_key);
+        assert_eq!(self.signer.voting_public_key(), public_key);
+
+        let vote_signature = match self.signer.sign_tendermint_vote(&tendermint_vote) {
+            Ok(signature) => signature,
+            Err(error) => {
+                // Without our own contribution there is nothing to aggregate. The round will
+                // time out.
+                log::error!(%error, round, ?step, "Failed to sign Tendermint vote");
+                return stream::empty().boxed();
+            }
+        };
 
         let own_contribution = TendermintContribution::from_vote(
             tendermint_vote,
-            &self.block_producer.voting_key.secret_key,
+            &vote_signature,
             self.validator_registry.get_slots(self.validator_slot_band),
         );
 
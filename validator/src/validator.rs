@@ -1,6 +1,7 @@
 use std::{
     error::Error,
     future::Future,
+    io,
     pin::Pin,
     sync::{
         atomic::{AtomicBool, Ordering},
@@ -10,14 +11,14 @@ use std::{
     time::Duration,
 };
 
-use futures::stream::StreamExt;
+use futures::{channel::mpsc, stream::StreamExt};
 use nimiq_account::Validator as ValidatorAccount;
 use nimiq_block::{Block, BlockType, EquivocationProof};
-use nimiq_blockchain::{interface::HistoryInterface, BlockProducer, Blockchain};
+use nimiq_blockchain::{interface::HistoryInterface, Blockchain};
 use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainEvent, ForkEvent};
 use nimiq_bls::KeyPair as BlsKeyPair;
 use nimiq_consensus::{
-    messages::{BlockBodyTopic, BlockHeaderMessage, BlockHeaderTopic},
+    messages::{BlockBodyTopic, BlockHeaderMessage, BlockHeaderTopic, CompactBlockBodyTopic},
     Consensus, ConsensusEvent, ConsensusProxy,
 };
 use nimiq_database::{
@@ -25,18 +26,22 @@ use nimiq_database::{
     mdbx::MdbxDatabase,
     traits::{Database, ReadTransaction, WriteTransaction},
 };
-use nimiq_hash::{Blake2bHash, Hash};
+use nimiq_hash::Blake2bHash;
 use nimiq_keys::{Address, KeyPair as SchnorrKeyPair};
-use nimiq_mempool::config::MempoolConfig;
+use nimiq_mempool::{config::MempoolConfig, store::MempoolStore};
 use nimiq_mempool_task::MempoolTask;
 use nimiq_network_interface::{
-    network::{MsgAcceptance, Network, NetworkEvent, SubscribeEvents},
+    network::{MsgAcceptance, Network, NetworkEvent, SubscribeEvents, Topic},
     request::request_handler,
 };
-use nimiq_primitives::{coin::Coin, policy::Policy};
+use nimiq_primitives::{coin::Coin, networks::NetworkId, policy::Policy};
+use nimiq_time::{interval, Interval};
+use nimiq_transaction::Transaction;
 use nimiq_transaction_builder::TransactionBuilder;
 use nimiq_utils::spawn;
-use nimiq_validator_network::{PubsubId, ValidatorNetwork};
+use nimiq_validator_network::{
+    validator_record::VALIDATOR_RECORD_REFRESH_JITTER, PubsubId, ValidatorNetwork,
+};
 use parking_lot::RwLock;
 #[cfg(feature = "metrics")]
 use tokio_metrics::TaskMonitor;
@@ -44,11 +49,14 @@ use tokio_stream::wrappers::BroadcastStream;
 
 use crate::{
     aggregation::tendermint::{proposal::RequestProposal, state::MacroState},
-    jail::EquivocationProofPool,
-    key_utils::VotingKeys,
+    automatic_transactions::{AutomaticTransactionKind, AutomaticTransactions, SubmissionDecision},
+    jail::{EquivocationProofPool, EquivocationProofStore, EquivocationProofTopic},
+    key_utils::{KeyStore, SigningKeys, VotingKeys},
     micro::ProduceMicroBlock,
-    proposal_buffer::{ProposalBuffer, ProposalReceiver},
+    proposal_buffer::{ProposalBuffer, ProposalReceiver, ProposalSender},
     r#macro::{MappedReturn, ProduceMacroBlock, ProposalTopic},
+    signer::{LocalSigner, SigningBackend},
+    signing_journal::SigningJournal,
 };
 
 #[derive(PartialEq)]
@@ -62,17 +70,12 @@ pub struct ConsensusState {
     equivocation_proofs: EquivocationProofPool,
 }
 
-/// Validator inactivity
-struct InactivityState {
-    inactive_tx_hash: Blake2bHash,
-    inactive_tx_validity_window_start: u32,
-}
-
 pub struct ValidatorProxy {
     pub validator_address: Arc<RwLock<Address>>,
-    pub signing_key: Arc<RwLock<SchnorrKeyPair>>,
+    pub signing_keys: Arc<RwLock<SigningKeys>>,
     pub voting_keys: Arc<RwLock<VotingKeys>>,
     pub fee_key: Arc<RwLock<SchnorrKeyPair>>,
+    pub key_store: Option<Arc<dyn KeyStore>>,
     pub automatic_reactivate: Arc<AtomicBool>,
     pub slot_band: Arc<RwLock<Option<u16>>>,
     pub consensus_state: Arc<RwLock<ConsensusState>>,
@@ -82,9 +85,10 @@ impl Clone for ValidatorProxy {
     fn clone(&self) -> Self {
         Self {
             validator_address: Arc::clone(&self.validator_address),
-            signing_key: Arc::clone(&self.signing_key),
+            signing_keys: Arc::clone(&self.signing_keys),
             voting_keys: Arc::clone(&self.voting_keys),
             fee_key: Arc::clone(&self.fee_key),
+            key_store: self.key_store.clone(),
             automatic_reactivate: Arc::clone(&self.automatic_reactivate),
             slot_band: Arc::clone(&self.slot_band),
             consensus_state: Arc::clone(&self.consensus_state),
@@ -92,6 +96,53 @@ impl Clone for ValidatorProxy {
     }
 }
 
+impl ValidatorProxy {
+    /// Starts rotating the signing and voting keys of the validator.
+    ///
+    /// The new keys are persisted in the key store, if any, and the returned update validator
+    /// transaction announces them in the staking contract. The transaction is signed by the
+    /// given cold key of the validator and its fee is paid by the fee key.
+    /// The new keys are only made available to the validator by [`ValidatorProxy::add_keys`]
+    /// once the transaction was sent. The validator keeps using its current keys until it is
+    /// elected with the new ones, i.e. from the first epoch after the transaction is included,
+    /// and then switches to them without a restart.
+    pub fn rotate_keys(
+        &self,
+        cold_key: &SchnorrKeyPair,
+        new_signing_key: &SchnorrKeyPair,
+        new_voting_key: &BlsKeyPair,
+        fee: Coin,
+        validity_start_height: u32,
+        network_id: NetworkId,
+    ) -> io::Result<Transaction> {
+        // Persist the keys before the transaction is sent, the validator can't start anymore
+        // once the chain expects keys that were lost.
+        if let Some(key_store) = &self.key_store {
+            key_store.store_signing_key(new_signing_key)?;
+            key_store.store_voting_key(new_voting_key)?;
+        }
+
+        Ok(TransactionBuilder::new_update_validator(
+            &self.fee_key.read(),
+            cold_key,
+            Some(new_signing_key.public),
+            Some(new_voting_key),
+            None,
+            None,
+            None,
+            fee,
+            validity_start_height,
+            network_id,
+        ))
+    }
+
+    /// Adds the given keys to the keys available to the validator.
+    pub fn add_keys(&self, signing_key: SchnorrKeyPair, voting_key: BlsKeyPair) {
+        self.signing_keys.write().add_key(signing_key);
+        self.voting_keys.write().add_key(voting_key);
+    }
+}
+
 declare_table!(ValidatorTable, "ValidatorState", () => MacroState);
 
 pub struct Validator<TValidatorNetwork: ValidatorNetwork + 'static>
@@ -104,21 +155,38 @@ where
 
     table: ValidatorTable,
     env: MdbxDatabase,
+    signing_journal: SigningJournal,
 
     validator_address: Arc<RwLock<Address>>,
-    signing_key: Arc<RwLock<SchnorrKeyPair>>,
+    signing_keys: Arc<RwLock<SigningKeys>>,
     voting_keys: Arc<RwLock<VotingKeys>>,
     fee_key: Arc<RwLock<SchnorrKeyPair>>,
+    /// Signs proposals, votes and VRF seeds. Defaults to the local signing and voting keys.
+    signer: Arc<dyn SigningBackend>,
+    /// Persists keys added by a key rotation, if set.
+    key_store: Option<Arc<dyn KeyStore>>,
 
+    proposal_sender: Arc<ProposalSender<TValidatorNetwork>>,
     proposal_receiver: ProposalReceiver<TValidatorNetwork>,
+    /// The epoch whose proposal topic we are currently subscribed to.
+    proposal_epoch: Option<u32>,
+    /// Whether we are subscribed to the proposal topic that isn't scoped per epoch, see
+    /// `ProposalTopic::scoped_only_epoch`.
+    unscoped_proposals: bool,
 
     consensus_event_rx: BroadcastStream<ConsensusEvent>,
     network_event_rx: SubscribeEvents<<TValidatorNetwork::NetworkType as Network>::PeerId>,
     fork_event_rx: BroadcastStream<ForkEvent>,
+    equivocation_proof_rx: mpsc::Receiver<(EquivocationProof, PubsubId<TValidatorNetwork>)>,
+
+    /// Interval to re-sign and republish our validator record, set once the DHT is ready.
+    dht_refresh_interval: Option<Interval>,
+    /// Period of `dht_refresh_interval`.
+    dht_republish_period: Duration,
 
     slot_band: Arc<RwLock<Option<u16>>>,
     consensus_state: Arc<RwLock<ConsensusState>>,
-    validator_state: Option<InactivityState>,
+    automatic_transactions: AutomaticTransactions,
     automatic_reactivate: Arc<AtomicBool>,
 
     macro_producer: Option<ProduceMacroBlock<TValidatorNetwork>>,
@@ -153,7 +221,8 @@ where
         network: Arc<TValidatorNetwork>,
         validator_address: Address,
         automatic_reactivate: bool,
-        signing_key: SchnorrKeyPair,
+        dht_republish_period: Duration,
+        signing_keys: SigningKeys,
         voting_keys: VotingKeys,
         fee_key: SchnorrKeyPair,
         mempool_config: MempoolConfig,
@@ -162,13 +231,16 @@ where
 
         let blockchain_rg = blockchain.read();
         let fork_event_rx = BroadcastStream::new(blockchain_rg.fork_notifier.subscribe());
+        let blockchain_state = ConsensusState {
+            equivocation_proofs: EquivocationProofPool::with_store(
+                EquivocationProofStore::new(env.clone()),
+                &blockchain_rg,
+            ),
+        };
         drop(blockchain_rg);
 
         let network_event_rx = network.subscribe_events();
-
-        let blockchain_state = ConsensusState {
-            equivocation_proofs: EquivocationProofPool::new(),
-        };
+        let equivocation_proof_rx = Self::subscribe_equivocation_proofs(&network);
 
         env.create_regular_table(&ValidatorTable);
 
@@ -184,21 +256,26 @@ where
             consensus.proxy(),
         );
 
-        let mempool = MempoolTask::new(consensus, Arc::clone(&blockchain), mempool_config);
+        let mempool_store = mempool_config
+            .persist
+            .then(|| MempoolStore::new(env.clone()));
+        let mempool = MempoolTask::new(
+            consensus,
+            Arc::clone(&blockchain),
+            mempool_config,
+            mempool_store,
+        );
 
         let automatic_reactivate = Arc::new(AtomicBool::new(automatic_reactivate));
 
         Self::init_network_request_receivers(&consensus.network, &macro_state);
 
-        let network1 = Arc::clone(&network);
-        spawn(async move {
-            network1
-                .subscribe::<ProposalTopic<TValidatorNetwork>>()
-                .await
-                .expect("Failed to subscribe to proposal topic")
-                .for_each(|proposal| async { proposal_sender.send(proposal) })
-                .await
-        });
+        let signing_keys = Arc::new(RwLock::new(signing_keys));
+        let voting_keys = Arc::new(RwLock::new(voting_keys));
+        let signer = Arc::new(LocalSigner::new(
+            Arc::clone(&signing_keys),
+            Arc::clone(&voting_keys),
+        ));
 
         Self {
             consensus: consensus.proxy(),
@@ -206,22 +283,32 @@ where
             network,
 
             table: ValidatorTable,
+            signing_journal: SigningJournal::new(env.clone()),
+            automatic_transactions: AutomaticTransactions::new(env.clone()),
             env,
 
             validator_address: Arc::new(RwLock::new(validator_address)),
-            signing_key: Arc::new(RwLock::new(signing_key)),
-            voting_keys: Arc::new(RwLock::new(voting_keys)),
+            signing_keys,
+            voting_keys,
             fee_key: Arc::new(RwLock::new(fee_key)),
+            signer,
+            key_store: None,
 
+            proposal_sender: Arc::new(proposal_sender),
             proposal_receiver,
+            proposal_epoch: None,
+            unscoped_proposals: false,
 
             consensus_event_rx,
             network_event_rx,
             fork_event_rx,
+            equivocation_proof_rx,
+
+            dht_refresh_interval: None,
+            dht_republish_period,
 
             slot_band: Arc::new(RwLock::new(None)),
             consensus_state: Arc::new(RwLock::new(blockchain_state)),
-            validator_state: None,
             automatic_reactivate,
 
             macro_producer: None,
@@ -233,6 +320,18 @@ where
         }
     }
 
+    /// Replaces the backend used to sign proposals, votes, VRF seeds, the validator record and
+    /// reactivate transactions, e.g. with a [`RemoteSigner`](crate::signer::RemoteSigner). Local
+    /// signing keys are only used for reactivate transactions while a key rotation is pending.
+    pub fn set_signing_backend(&mut self, signer: Arc<dyn SigningBackend>) {
+        self.signer = signer;
+    }
+
+    /// Sets the store in which keys added by a key rotation are persisted.
+    pub fn set_key_store(&mut self, key_store: Arc<dyn KeyStore>) {
+        self.key_store = Some(key_store);
+    }
+
     fn init_network_request_receivers(
         network: &Arc<TValidatorNetwork::NetworkType>,
         macro_state: &Arc<RwLock<Option<MacroState>>>,
@@ -241,6 +340,28 @@ where
         spawn(Box::pin(request_handler(network, stream, macro_state)));
     }
 
+    /// Subscribes to the equivocation proofs gossiped by other validators. The subscription is
+    /// established in the background and its items are forwarded to the returned receiver.
+    fn subscribe_equivocation_proofs(
+        network: &Arc<TValidatorNetwork>,
+    ) -> mpsc::Receiver<(EquivocationProof, PubsubId<TValidatorNetwork>)> {
+        let (sender, receiver) = mpsc::channel(EquivocationProofTopic::BUFFER_SIZE);
+        let network = Arc::clone(network);
+        spawn(async move {
+            match network.subscribe::<EquivocationProofTopic>().await {
+                Ok(proofs) => {
+                    if proofs.map(Ok).forward(sender).await.is_err() {
+                        log::debug!("Validator stopped receiving equivocation proofs");
+                    }
+                }
+                Err(error) => {
+                    log::error!(%error, "Failed to subscribe to equivocation proof topic")
+                }
+            }
+        });
+        receiver
+    }
+
     fn init(&mut self, head_hash: Option<&Blake2bHash>) {
         self.init_epoch();
         self.init_block_producer(head_hash);
@@ -295,35 +416,6 @@ where
         timeout.clamp(Self::MIN_PRODUCER_TIMEOUT, Self::MAX_PRODUCER_TIMEOUT)
     }
 
-    /// Resets the reactivation state if we sent a reactivate transaction that expired.
-    /// This ensures that in future polls of the validator we continue trying to reactivate
-    /// ourselves if the previous tx didn't get included within the validity window.
-    fn check_reactivate(&mut self, block_number: u32) {
-        // Check if the reactivate/activate transaction was sent
-        if let Some(validator_state) = &self.validator_state {
-            // We check this in the last possible block of the validity window
-            let tx_validity_window_start = validator_state.inactive_tx_validity_window_start;
-            if block_number
-                >= tx_validity_window_start + Policy::transaction_validity_window_blocks() - 1
-            {
-                let blockchain = self.blockchain.read();
-                let staking_state = self.get_staking_state(&blockchain);
-                // Check that the transaction was sent in the validity window
-                if (matches!(staking_state, ValidatorStakingState::Inactive(..)))
-                    && !blockchain.history_store.tx_in_validity_window(
-                        &validator_state.inactive_tx_hash.clone().into(),
-                        None,
-                    )
-                {
-                    // If we are inactive and no transaction has been seen in the expected validity window
-                    // after an epoch, reset our inactive state
-                    log::debug!("Resetting state to re-send reactivate transactions since we are inactive and validity window doesn't contain the transaction sent");
-                    self.validator_state = None;
-                }
-            }
-        }
-    }
-
     fn init_epoch(&mut self) {
         *self.slot_band.write() = None;
 
@@ -334,6 +426,66 @@ where
         let blockchain = self.blockchain.read();
         let validators = blockchain.current_validators().unwrap();
 
+        // Proposals are gossiped on a topic scoped to the epoch of the blocks they are for.
+        // Subscribing to the topic of the upcoming epoch drops the subscription to the previous one.
+        let proposal_epoch = Policy::epoch_at(blockchain.block_number() + 1);
+        if self.proposal_epoch != Some(proposal_epoch) {
+            self.proposal_epoch = Some(proposal_epoch);
+            let network = Arc::clone(&self.network);
+            let proposal_sender = Arc::clone(&self.proposal_sender);
+            spawn(async move {
+                match network
+                    .subscribe_epoch::<ProposalTopic<TValidatorNetwork>>(proposal_epoch)
+                    .await
+                {
+                    Ok(proposals) => {
+                        proposals
+                            .for_each(|proposal| async { proposal_sender.send(proposal) })
+                            .await
+                    }
+                    Err(error) => log::error!(
+                        %error,
+                        epoch_number = proposal_epoch,
+                        "Failed to subscribe to proposal topic"
+                    ),
+                }
+            });
+        }
+
+        // Validators that don't know the epoch scoped topics yet only publish on the unscoped one.
+        let unscoped_proposals =
+            proposal_epoch < ProposalTopic::<TValidatorNetwork>::scoped_only_epoch();
+        if self.unscoped_proposals != unscoped_proposals {
+            self.unscoped_proposals = unscoped_proposals;
+            let network = Arc::clone(&self.network);
+            let proposal_sender = Arc::clone(&self.proposal_sender);
+            spawn(async move {
+                if !unscoped_proposals {
+                    if let Err(error) = network
+                        .unsubscribe::<ProposalTopic<TValidatorNetwork>>()
+                        .await
+                    {
+                        log::warn!(%error, "Failed to unsubscribe from unscoped proposal topic");
+                    }
+                    return;
+                }
+
+                match network
+                    .subscribe::<ProposalTopic<TValidatorNetwork>>()
+                    .await
+                {
+                    Ok(proposals) => {
+                        proposals
+                            .for_each(|proposal| async { proposal_sender.send(proposal) })
+                            .await
+                    }
+                    Err(error) => {
+                        log::error!(%error, "Failed to subscribe to unscoped proposal topic")
+                    }
+                }
+            });
+        }
+
         *self.slot_band.write() = validators.get_slot_band_by_address(&self.validator_address());
 
         if let Some(slot_band) = *self.slot_band.read() {
@@ -346,19 +498,34 @@ where
                 "We are ELECTED in this epoch"
             );
 
-            // Update the validator key to be the expected one (relevant in case of a key rotation).
-            if self
+            // Update the local keys to be the expected ones (relevant in case of a key rotation).
+            // Failing to do so is only fatal if the signing backend relies on them, which is
+            // checked below.
+            let voting_key_updated = self
                 .voting_keys
                 .write()
                 .update_current_key(epoch_validator.voting_key.compressed())
-                .is_err()
+                .is_ok();
+            let signing_key_updated = self
+                .signing_keys
+                .write()
+                .update_current_key(&epoch_validator.signing_key)
+                .is_ok();
+
+            if self.signer.voting_public_key().compress()
+                != *epoch_validator.voting_key.compressed()
             {
+                if voting_key_updated {
+                    panic!("Invalid signer configuration: The voting key of the signer doesn't match the one expected from this validator in the current epoch")
+                }
                 panic!("Invalid validator configuration: None of the voting keys match the one expected from this validator in the current epoch")
             }
 
-            // Compare configured validator signing key to the one in the current epoch to make sure it is the same.
-            if epoch_validator.signing_key != self.signing_key().public {
-                panic!("Invalid validator configuration: Configured signing key does not match signing key in this epoch");
+            if self.signer.signing_public_key() != epoch_validator.signing_key {
+                if signing_key_updated {
+                    panic!("Invalid signer configuration: The signing key of the signer doesn't match the one expected from this validator in the current epoch")
+                }
+                panic!("Invalid validator configuration: None of the signing keys match the one expected from this validator in the current epoch")
             }
         } else {
             log::info!(
@@ -395,7 +562,6 @@ where
         let head = blockchain.head();
         let next_block_number = head.block_number() + 1;
         let network_id = head.network();
-        let block_producer = BlockProducer::new(self.signing_key(), self.current_voting_key());
 
         debug!(
             next_block_number = next_block_number,
@@ -412,7 +578,7 @@ where
                 self.macro_producer = Some(ProduceMacroBlock::new(
                     Arc::clone(&self.blockchain),
                     Arc::clone(&self.network),
-                    block_producer,
+                    Arc::clone(&self.signer),
                     self.validator_slot_band(),
                     active_validators,
                     network_id,
@@ -433,7 +599,8 @@ where
                     Arc::clone(&self.blockchain),
                     Arc::clone(&self.mempool_task.mempool),
                     Arc::clone(&self.network),
-                    block_producer,
+                    Arc::clone(&self.signer),
+                    self.signing_journal.clone(),
                     self.validator_slot_band(),
                     equivocation_proofs,
                     prev_seed,
@@ -460,6 +627,11 @@ where
             }
             BlockchainEvent::EpochFinalized(ref hash) => {
                 self.init_epoch();
+                // Our record is bound to the epoch, so republish it for the new one once the DHT
+                // is ready.
+                if self.dht_refresh_interval.is_some() {
+                    self.publish_dht();
+                }
                 // The on_blockchain_extended is necessary for the order of events to not matter.
                 self.on_blockchain_extended(hash);
             }
@@ -486,7 +658,6 @@ where
             .equivocation_proofs
             .apply_block(&block);
 
-        self.check_reactivate(block.block_number());
         self.init_block_producer(Some(hash));
     }
 
@@ -515,19 +686,79 @@ where
         }
     }
 
+    /// Adds an equivocation proof we detected ourselves to the pool and gossips it to the
+    /// other validators.
     fn on_equivocation_proof(&mut self, proof: EquivocationProof) {
+        if !self.add_equivocation_proof(proof.clone()) {
+            return;
+        }
+
+        let network = Arc::clone(&self.network);
+        spawn(async move {
+            if let Err(error) = network.publish::<EquivocationProofTopic>(proof).await {
+                log::debug!(%error, "Failed to publish equivocation proof");
+            }
+        });
+    }
+
+    /// Verifies an equivocation proof received via gossip and adds it to the pool.
+    /// Proofs that are already known are not relayed again.
+    fn on_gossiped_equivocation_proof(&mut self, proof: EquivocationProof) -> MsgAcceptance {
+        let blockchain = self.blockchain.read();
+        if !proof.is_valid_at(blockchain.block_number() + 1)
+            || self
+                .consensus_state
+                .read()
+                .equivocation_proofs
+                .contains(&proof)
+        {
+            return MsgAcceptance::Ignore;
+        }
+
+        let Ok(validators) =
+            blockchain.get_validators_for_epoch(Policy::epoch_at(proof.block_number()), None)
+        else {
+            return MsgAcceptance::Ignore;
+        };
+        if let Err(error) = proof.verify(blockchain.network_id(), &validators) {
+            debug!(%error, "Received invalid equivocation proof");
+            return MsgAcceptance::Reject;
+        }
+        drop(blockchain);
+
+        if self.add_equivocation_proof(proof) {
+            MsgAcceptance::Accept
+        } else {
+            MsgAcceptance::Ignore
+        }
+    }
+
+    /// Adds an equivocation proof to the pool unless it was already included in the chain.
+    /// If it is new, the micro block producer is restarted to include it as early as possible.
+    /// Returns whether the proof has been added.
+    fn add_equivocation_proof(&mut self, proof: EquivocationProof) -> bool {
         // Keep the lock until the proof is added to the proof pool.
         let blockchain = self.blockchain.read();
         if blockchain
             .history_store
             .has_equivocation_proof(proof.locator(), None)
         {
-            return;
+            return false;
         }
-        self.consensus_state
+        if !self
+            .consensus_state
             .write()
             .equivocation_proofs
-            .insert(proof);
+            .insert(proof)
+        {
+            return false;
+        }
+        drop(blockchain);
+
+        if self.micro_producer.is_some() {
+            self.init_block_producer(None);
+        }
+        true
     }
 
     fn poll_macro(&mut self, cx: &mut Context<'_>) {
@@ -625,12 +856,22 @@ where
 
     /// Publish our own validator record to the DHT.
     fn publish_dht(&self) {
-        let key_pair = self.signing_key();
+        let signer = Arc::clone(&self.signer);
         let validator_address = self.validator_address();
+        let epoch_number = self.blockchain.read().epoch_number();
         let network = Arc::clone(&self.network);
 
         spawn(async move {
-            if let Err(err) = network.set_public_key(&validator_address, &key_pair).await {
+            let sign = |message: &[u8]| {
+                signer
+                    .sign_validator_record(message)
+                    .inspect_err(|error| error!(%error, "Failed to sign validator record"))
+                    .ok()
+            };
+            if let Err(err) = network
+                .set_public_key(&validator_address, epoch_number, sign)
+                .await
+            {
                 error!("could not set up DHT record: {:?}", err);
             }
         });
@@ -669,35 +910,66 @@ where
         )
     }
 
-    fn reactivate(&self, blockchain: &Blockchain) -> InactivityState {
-        let validity_start_height = blockchain.block_number();
-
-        let reactivate_transaction = TransactionBuilder::new_reactivate_validator(
-            &self.fee_key(),
-            self.validator_address(),
-            &self.signing_key(),
-            Coin::ZERO,
-            validity_start_height,
-            blockchain.network_id(),
-        );
-        let tx_hash = reactivate_transaction.hash();
+    /// Sends a reactivate transaction, unless one that can still be included was sent before.
+    fn reactivate(&mut self, blockchain: &Blockchain) {
+        let block_number = blockchain.block_number();
+        let reactivate_transaction = match self
+            .automatic_transactions
+            .check(AutomaticTransactionKind::Reactivate, block_number)
+        {
+            SubmissionDecision::Send => {
+                // The staking contract expects the latest signing key, which differs from the
+                // one we currently use while a key rotation is pending. Without a local copy of
+                // that key, the transaction is signed by the signing backend.
+                let expected_signing_key = self
+                    .get_validator(blockchain)
+                    .map(|validator| validator.signing_key);
+                let local_signing_key = expected_signing_key
+                    .as_ref()
+                    .and_then(|signing_key| self.signing_keys.read().get_key(signing_key));
+                let reactivate_transaction = match local_signing_key {
+                    Some(signing_key) => Ok(TransactionBuilder::new_reactivate_validator(
+                        &self.fee_key(),
+                        self.validator_address(),
+                        &signing_key,
+                        Coin::ZERO,
+                        block_number,
+                        blockchain.network_id(),
+                    )),
+                    None => TransactionBuilder::new_reactivate_validator_with_signer(
+                        &self.fee_key(),
+                        self.validator_address(),
+                        self.signer.signing_public_key(),
+                        |transaction| self.signer.sign_reactivate_transaction(transaction),
+                        Coin::ZERO,
+                        block_number,
+                        blockchain.network_id(),
+                    ),
+                };
+                let reactivate_transaction = match reactivate_transaction {
+                    Ok(reactivate_transaction) => reactivate_transaction,
+                    Err(error) => {
+                        error!(%error, "Failed to sign reactivate transaction");
+                        return;
+                    }
+                };
+                self.automatic_transactions.record(
+                    AutomaticTransactionKind::Reactivate,
+                    reactivate_transaction.clone(),
+                );
+                reactivate_transaction
+            }
+            SubmissionDecision::Resend(reactivate_transaction) => reactivate_transaction,
+            SubmissionDecision::Wait => return,
+        };
 
         let cn = self.consensus.clone();
         spawn(async move {
             debug!("Sending reactivate transaction to the network");
-            if cn
-                .send_transaction(reactivate_transaction.clone())
-                .await
-                .is_err()
-            {
+            if cn.send_transaction(reactivate_transaction).await.is_err() {
                 error!("Failed to send reactivate transaction");
             }
         });
-
-        InactivityState {
-            inactive_tx_hash: tx_hash,
-            inactive_tx_validity_window_start: validity_start_height,
-        }
     }
 
     pub fn validator_slot_band(&self) -> u16 {
@@ -713,7 +985,7 @@ where
     }
 
     pub fn signing_key(&self) -> SchnorrKeyPair {
-        self.signing_key.read().clone()
+        self.signing_keys.read().get_current_key()
     }
 
     pub fn fee_key(&self) -> SchnorrKeyPair {
@@ -723,9 +995,10 @@ where
     pub fn proxy(&self) -> ValidatorProxy {
         ValidatorProxy {
             validator_address: Arc::clone(&self.validator_address),
-            signing_key: Arc::clone(&self.signing_key),
+            signing_keys: Arc::clone(&self.signing_keys),
             voting_keys: Arc::clone(&self.voting_keys),
             fee_key: Arc::clone(&self.fee_key),
+            key_store: self.key_store.clone(),
             automatic_reactivate: Arc::clone(&self.automatic_reactivate),
             slot_band: Arc::clone(&self.slot_band),
             consensus_state: Arc::clone(&self.consensus_state),
@@ -742,7 +1015,8 @@ where
         self.mempool_task.get_control_mempool_monitor()
     }
 
-    /// Publishes the given block on both the BlockHeaderTopic and BlockBodyTopic.
+    /// Publishes the given block on the BlockHeaderTopic and its body on the BlockBodyTopic.
+    /// Micro block bodies are published on the CompactBlockBodyTopic instead.
     pub fn publish_block(network: Arc<TValidatorNetwork>, block: Block) {
         if block.is_election() {
             info!(%block, "Publishing Election MacroBlock");
@@ -754,6 +1028,10 @@ where
 
         spawn(async move {
             let block_id = format!("{}", block);
+            let block_hash = block.hash();
+            // Nodes that don't know the compact bodies yet only assemble blocks from full bodies,
+            // so those are published as well until protocol version 2 is active.
+            let publish_full_body = Policy::version_at(block.block_number()) < 2;
 
             let (header, body) = BlockHeaderMessage::split_block(block);
 
@@ -765,12 +1043,27 @@ where
                 );
             }
 
-            if let Err(e) = network.publish::<BlockBodyTopic>(body).await {
-                trace!(
-                    block = block_id,
-                    error = &e as &dyn Error,
-                    "Failed to publish block body"
-                );
+            let is_compact = match body.to_compact(block_hash) {
+                Some(compact_body) => {
+                    if let Err(e) = network.publish::<CompactBlockBodyTopic>(compact_body).await {
+                        trace!(
+                            block = block_id,
+                            error = &e as &dyn Error,
+                            "Failed to publish compact block body"
+                        );
+                    }
+                    true
+                }
+                None => false,
+            };
+            if !is_compact || publish_full_body {
+                if let Err(e) = network.publish::<BlockBodyTopic>(body).await {
+                    trace!(
+                        block = block_id,
+                        error = &e as &dyn Error,
+                        "Failed to publish block body"
+                    );
+                }
             }
         });
     }
@@ -793,6 +1086,8 @@ where
                 | Ok(ConsensusEvent::Established {
                     synced_validity_window: false,
                 }) => self.pause(),
+                Ok(ConsensusEvent::PeerSetChanged { .. })
+                | Ok(ConsensusEvent::SyncProgress { .. }) => {}
                 Err(_) => return Poll::Ready(()),
             }
         }
@@ -812,6 +1107,19 @@ where
             }
         }
 
+        // Process equivocation proofs gossiped by other validators.
+        while let Poll::Ready(Some((proof, pubsub_id))) =
+            self.equivocation_proof_rx.poll_next_unpin(cx)
+        {
+            let acceptance = if self.consensus.is_established() {
+                self.on_gossiped_equivocation_proof(proof)
+            } else {
+                MsgAcceptance::Ignore
+            };
+            self.network
+                .validate_message::<EquivocationProofTopic>(pubsub_id, acceptance);
+        }
+
         // If we are an active validator, participate in block production.
         if self.is_synced() && self.is_elected() {
             if self.macro_producer.is_some() {
@@ -824,27 +1132,27 @@ where
 
         // Once the validator can be active is established, check the validator staking state.
         if self.is_synced() {
-            let blockchain = self.blockchain.read();
+            let blockchain_lock = Arc::clone(&self.blockchain);
+            let blockchain = blockchain_lock.read();
             match self.get_staking_state(&blockchain) {
                 ValidatorStakingState::Active => {
                     drop(blockchain);
-                    if self.validator_state.is_some() {
-                        self.validator_state = None;
+                    if self
+                        .automatic_transactions
+                        .clear(AutomaticTransactionKind::Reactivate)
+                    {
                         info!("Automatically reactivated.");
                     }
                 }
                 ValidatorStakingState::Inactive(jailed_from) => {
-                    if self.validator_state.is_none()
-                        && jailed_from
-                            .map(|jailed_from| {
-                                blockchain.block_number() >= Policy::block_after_jail(jailed_from)
-                            })
-                            .unwrap_or(true)
+                    if jailed_from
+                        .map(|jailed_from| {
+                            blockchain.block_number() >= Policy::block_after_jail(jailed_from)
+                        })
+                        .unwrap_or(true)
                         && self.automatic_reactivate.load(Ordering::Acquire)
                     {
-                        let inactivity_state = self.reactivate(&blockchain);
-                        drop(blockchain);
-                        self.validator_state = Some(inactivity_state);
+                        self.reactivate(&blockchain);
                     }
                 }
                 ValidatorStakingState::UnknownOrNoStake => {}
@@ -856,12 +1164,146 @@ where
             match result {
                 Ok(NetworkEvent::DhtReady) => {
                     self.publish_dht();
+                    self.dht_refresh_interval = Some(
+                        interval(self.dht_republish_period)
+                            .with_jitter(VALIDATOR_RECORD_REFRESH_JITTER),
+                    );
                 }
                 Ok(_) => {}
                 Err(e) => error!("{}", e),
             }
         }
 
+        // Refresh our record regularly, otherwise it becomes stale and is no longer served.
+        if let Some(ref mut dht_refresh_interval) = self.dht_refresh_interval {
+            let mut refresh = false;
+            while dht_refresh_interval.poll_next_unpin(cx).is_ready() {
+                refresh = true;
+            }
+            if refresh {
+                self.publish_dht();
+            }
+        }
+
         Poll::Pending
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io,
+        sync::{atomic::AtomicBool, Arc},
+    };
+
+    use nimiq_bls::KeyPair as BlsKeyPair;
+    use nimiq_keys::{Address, KeyPair as SchnorrKeyPair};
+    use nimiq_primitives::{coin::Coin, networks::NetworkId};
+    use nimiq_utils::key_rng::SecureGenerate;
+    use parking_lot::{Mutex, RwLock};
+
+    use super::{ConsensusState, ValidatorProxy};
+    use crate::{
+        jail::EquivocationProofPool,
+        key_utils::{KeyStore, SigningKeys, VotingKeys},
+    };
+
+    #[derive(Default)]
+    struct TestKeyStore {
+        fail: bool,
+        signing_keys: Mutex<Vec<SchnorrKeyPair>>,
+        voting_keys: Mutex<Vec<BlsKeyPair>>,
+    }
+
+    impl KeyStore for TestKeyStore {
+        fn store_signing_key(&self, key: &SchnorrKeyPair) -> io::Result<()> {
+            if self.fail {
+                return Err(io::Error::other("disk full"));
+            }
+            self.signing_keys.lock().push(key.clone());
+            Ok(())
+        }
+
+        fn store_voting_key(&self, key: &BlsKeyPair) -> io::Result<()> {
+            self.voting_keys.lock().push(key.clone());
+            Ok(())
+        }
+    }
+
+    fn validator_proxy(key_store: Arc<TestKeyStore>) -> ValidatorProxy {
+        ValidatorProxy {
+            validator_address: Arc::new(RwLock::new(Address::default())),
+            signing_keys: Arc::new(RwLock::new(SigningKeys::new(vec![
+                SchnorrKeyPair::generate_default_csprng(),
+            ]))),
+            voting_keys: Arc::new(RwLock::new(VotingKeys::new(vec![
+                BlsKeyPair::generate_default_csprng(),
+            ]))),
+            fee_key: Arc::new(RwLock::new(SchnorrKeyPair::generate_default_csprng())),
+            key_store: Some(key_store),
+            automatic_reactivate: Arc::new(AtomicBool::new(false)),
+            slot_band: Arc::new(RwLock::new(None)),
+            consensus_state: Arc::new(RwLock::new(ConsensusState {
+                equivocation_proofs: EquivocationProofPool::new(),
+            })),
+        }
+    }
+
+    #[test]
+    fn rotate_keys_persists_the_keys_before_adding_them() {
+        let key_store = Arc::new(TestKeyStore::default());
+        let proxy = validator_proxy(Arc::clone(&key_store));
+        let cold_key = SchnorrKeyPair::generate_default_csprng();
+        let signing_key = SchnorrKeyPair::generate_default_csprng();
+        let voting_key = BlsKeyPair::generate_default_csprng();
+
+        proxy
+            .rotate_keys(
+                &cold_key,
+                &signing_key,
+                &voting_key,
+                Coin::ZERO,
+                1,
+                NetworkId::UnitAlbatross,
+            )
+            .unwrap();
+        assert_eq!(*key_store.signing_keys.lock(), vec![signing_key.clone()]);
+        assert!(*key_store.voting_keys.lock() == vec![voting_key.clone()]);
+
+        // The keys are only available once the transaction was sent.
+        assert!(proxy
+            .signing_keys
+            .read()
+            .get_key(&signing_key.public)
+            .is_none());
+        assert!(!proxy.voting_keys.read().get_keys().contains(&voting_key));
+
+        proxy.add_keys(signing_key.clone(), voting_key.clone());
+        assert!(proxy
+            .signing_keys
+            .read()
+            .get_key(&signing_key.public)
+            .is_some());
+        assert!(proxy.voting_keys.read().get_keys().contains(&voting_key));
+    }
+
+    #[test]
+    fn rotate_keys_fails_if_the_keys_cant_be_persisted() {
+        let key_store = Arc::new(TestKeyStore {
+            fail: true,
+            ..Default::default()
+        });
+        let proxy = validator_proxy(key_store);
+
+        assert!(proxy
+            .rotate_keys(
+                &SchnorrKeyPair::generate_default_csprng(),
+                &SchnorrKeyPair::generate_default_csprng(),
+                &BlsKeyPair::generate_default_csprng(),
+                Coin::ZERO,
+                1,
+                NetworkId::UnitAlbatross,
+            )
+            .is_err());
+    }
+}
@@ -0,0 +1,76 @@
+use std::sync::Arc;
+
+use nimiq_bls::KeyPair as BlsKeyPair;
+use nimiq_database::mdbx::MdbxDatabase;
+use nimiq_genesis_builder::GenesisBuilder;
+use nimiq_keys::{Address, KeyPair, SecureGenerate};
+use nimiq_mempool::config::MempoolConfig;
+use nimiq_network_mock::{MockHub, MockNetwork};
+use nimiq_primitives::{networks::NetworkId, policy::Policy};
+use nimiq_test_log::test;
+use nimiq_test_utils::{node::Node, validator::seeded_rng};
+use nimiq_validator::{
+    key_utils::{SigningKeys, VotingKeys},
+    validator::Validator,
+};
+use nimiq_validator_network::{
+    network_impl::ValidatorNetworkImpl, validator_record::VALIDATOR_RECORD_REFRESH_INTERVAL,
+};
+use nimiq_wallet::WalletStore;
+use nimiq_zkp_component::proof_store::DBProofCache;
+
+/// A validator with the history index, a persisted mempool, a proof cache and a wallet opens the
+/// most tables. All of them need to fit into the default table limit of the database.
+#[test(tokio::test)]
+async fn a_full_validator_opens_all_tables_within_the_default_limit() {
+    let voting_key = BlsKeyPair::generate(&mut seeded_rng(0));
+    let validator_key = KeyPair::generate(&mut seeded_rng(0));
+    let fee_key = KeyPair::generate(&mut seeded_rng(0));
+    let signing_key = KeyPair::generate(&mut seeded_rng(0));
+    let genesis_env =
+        MdbxDatabase::new_volatile(Default::default()).expect("Could not open a volatile database");
+    let genesis = GenesisBuilder::default()
+        .with_network(NetworkId::UnitAlbatross)
+        .with_genesis_block_number(Policy::genesis_block_number())
+        .with_genesis_validator(
+            Address::from(&validator_key),
+            signing_key.public,
+            voting_key.public_key,
+            Address::default(),
+            None,
+            None,
+            false,
+        )
+        .generate(genesis_env)
+        .unwrap();
+
+    // The node's blockchain indexes the history and stores its zk proof.
+    let node = Node::<MockNetwork>::history_with_genesis_info(
+        0,
+        genesis,
+        &mut Some(MockHub::default()),
+        false,
+    )
+    .await;
+    let env = node.environment.clone();
+    let consensus = node.consensus.expect("Could not create consensus");
+
+    let _validator = Validator::new(
+        env.clone(),
+        &consensus,
+        Arc::clone(&node.blockchain),
+        Arc::new(ValidatorNetworkImpl::new(Arc::clone(&consensus.network))),
+        Address::from(&validator_key),
+        false,
+        VALIDATOR_RECORD_REFRESH_INTERVAL,
+        SigningKeys::new(vec![signing_key]),
+        VotingKeys::new(vec![voting_key]),
+        fee_key,
+        MempoolConfig {
+            persist: true,
+            ..Default::default()
+        },
+    );
+    let _proof_cache = DBProofCache::new(env.clone());
+    let _wallet_store = WalletStore::new(env);
+}
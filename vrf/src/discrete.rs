@@ -44,28 +44,35 @@ impl DiscreteDistribution {
         // p - probabilities p_i. We will use this for U as well
         // T - total probability
         // n - number of probabilities
+        //
+        // The scaled probabilities are computed with 128-bit integers, since `p_i * n` can
+        // overflow a u64 for large numbers of events. Once the table is balanced, every U_i is
+        // at most T and thus fits into a u64 again.
 
         let n = p.len();
 
         // Construct scaled probabilities and total probability.
-        let mut T = 0;
+        let mut T: u64 = 0;
 
-        let mut U: Vec<u64> = p
+        let mut U: Vec<u128> = p
             .iter()
             .map(|p| {
-                T += *p;
-                p * n as u64
+                T = T
+                    .checked_add(*p)
+                    .expect("Total probability overflows a u64");
+                u128::from(*p) * n as u128
             })
             .collect();
 
         assert!(T != 0, "Must have positive total probability");
+        let T_scaled = u128::from(T);
 
         // Construct overfull and underfull stack. These contain only indices into U.
         let mut U_underfull = Vec::with_capacity(n);
         let mut U_overfull = Vec::with_capacity(n);
 
         for (i, U_i) in U.iter().enumerate() {
-            match U_i.cmp(&T) {
+            match U_i.cmp(&T_scaled) {
                 Ordering::Equal => (),
                 Ordering::Greater => U_overfull.push(i),
                 Ordering::Less => U_underfull.push(i),
@@ -80,10 +87,10 @@ impl DiscreteDistribution {
             K[i_u] = i_o;
 
             // Remove allocated space from U: U_o -= (T - U_u)
-            U[i_o] = U[i_o] + U[i_u] - T;
+            U[i_o] = U[i_o] + U[i_u] - T_scaled;
 
             // Assign entry i_o to the appropriate category base on the new value.
-            match U[i_o].cmp(&T) {
+            match U[i_o].cmp(&T_scaled) {
                 Ordering::Equal => (),
                 Ordering::Greater => U_overfull.push(i_o),
                 Ordering::Less => U_underfull.push(i_o),
@@ -96,9 +103,15 @@ impl DiscreteDistribution {
         // Entries that are "underfull" need an entry in the alias table.
         debug_assert!((0..n).all(|i| {
             // Both must be true or both must be false.
-            (U[i] < T) == (K[i] != i)
+            (U[i] < T_scaled) == (K[i] != i)
         }));
 
+        // All entries are at most T now.
+        let U = U
+            .into_iter()
+            .map(|U_i| u64::try_from(U_i).expect("Balanced probability must not exceed T"))
+            .collect();
+
         Self { T, n, K, U }
     }
 
@@ -117,3 +130,47 @@ impl DiscreteDistribution {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use nimiq_test_log::test;
+
+    use super::*;
+
+    /// A deterministic generator cycling through all values below its period.
+    struct CountingRng(u64);
+
+    impl Rng for CountingRng {
+        fn next_u64(&mut self) -> u64 {
+            self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            self.0
+        }
+    }
+
+    #[test]
+    fn it_samples_large_distributions_without_overflow() {
+        // The scaled probability of the first event exceeds a u64.
+        let mut p = vec![1; 9_999];
+        p.insert(0, u64::MAX / 2);
+
+        let distribution = DiscreteDistribution::new(&p);
+        assert!(distribution.U.iter().all(|U_i| *U_i <= distribution.T));
+
+        let mut rng = CountingRng(0);
+        let num_first = (0..1_000)
+            .filter(|_| distribution.sample(&mut rng) == 0)
+            .count();
+        assert!(num_first > 990);
+    }
+
+    #[test]
+    fn it_never_samples_events_without_probability() {
+        let distribution = DiscreteDistribution::new(&[0, 5, 0, 3, 0]);
+
+        let mut rng = CountingRng(0);
+        for _ in 0..1_000 {
+            let index = distribution.sample(&mut rng);
+            assert!(index == 1 || index == 3);
+        }
+    }
+}
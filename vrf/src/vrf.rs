@@ -39,6 +39,8 @@ pub enum VrfUseCase {
     ViewSlotSelection = 3,
     /// Used to randomly distribute the rewards.
     RewardDistribution = 4,
+    /// Used to derive the randomness beacon output served to third-party applications.
+    RandomnessBeacon = 5,
 }
 
 create_typed_array!(VrfEntropy, u8, 32);
@@ -255,6 +257,14 @@ impl VrfSeed {
         // Pass the entropy to the VRF RNG.
         VrfRng::new(entropy, use_case)
     }
+
+    // Returns the canonical randomness beacon output of the current VRF Seed. Like the entropy, it
+    // is unique for a given message and public key, but it is domain separated from the randomness
+    // used by the protocol itself. We assume that the VRF Seed is valid, if it is not this
+    // function might panic.
+    pub fn randomness(&self) -> Blake2bHash {
+        self.rng(VrfUseCase::RandomnessBeacon).next_hash()
+    }
 }
 
 impl Default for VrfSeed {
@@ -392,6 +402,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn randomness_is_unique() {
+        let mut rng = test_rng(false);
+        let key_pair = KeyPair::generate(&mut rng);
+        let prev_seed = VrfSeed::default();
+
+        // The seeds differ, but their randomness doesn't.
+        let seed_1 = prev_seed.sign_next(&key_pair, 0);
+        let seed_2 = prev_seed.sign_next(&key_pair, 0);
+        assert_ne!(seed_1, seed_2);
+        assert_eq!(seed_1.randomness(), seed_2.randomness());
+
+        let other_seed = prev_seed.sign_next(&key_pair, 1);
+        assert_ne!(seed_1.randomness(), other_seed.randomness());
+        assert_ne!(seed_1.randomness().as_bytes(), seed_1.entropy().as_slice());
+    }
+
     #[test]
     fn wrong_nonce() {
         let mut rng = test_rng(false);
@@ -57,7 +57,7 @@ impl WalletAccount {
         SignatureProof::from_ed25519(self.key_pair.public, signature)
     }
 
-    fn prepare_message_for_signature(message: &[u8]) -> Sha256Hash {
+    pub fn prepare_message_for_signature(message: &[u8]) -> Sha256Hash {
         /*
          * Adding a prefix to the message makes the calculated signature recognisable as
          * a Nimiq specific signature. This and the hashing prevents misuse where a malicious
@@ -0,0 +1,17 @@
+import * as Comlink from 'comlink';
+import { CryptoUtils } from './main-wasm/index.js';
+import { cryptoUtilsWorkerFactory } from '../launcher/browser/cryptoutils-worker-proxy.mjs';
+
+const CryptoUtilsWorker = cryptoUtilsWorkerFactory(
+    () => new Worker(new URL('./crypto.js', import.meta.url), { type : 'module' }),
+    worker => Comlink.wrap(worker),
+);
+for (const propName in CryptoUtilsWorker) {
+    const prop = CryptoUtilsWorker[propName];
+    if (typeof prop === 'function') {
+        CryptoUtils[propName] = prop;
+    }
+}
+
+export * from './main-wasm/index.js';
+export * from '../lib/bundler/index.mjs';
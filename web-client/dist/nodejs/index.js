@@ -2,9 +2,8 @@ const { join } = require('node:path');
 const { Worker } = require('node:worker_threads');
 const Comlink = require('comlink');
 const nodeEndpoint = require('comlink/dist/umd/node-adapter.js');
-const { Address, CryptoUtils, Transaction } = require('./main-wasm/index.js');
+const { Address, Transaction } = require('./primitives.js');
 const { clientFactory } = require('../launcher/node/client-proxy.js');
-const { cryptoUtilsWorkerFactory } = require('../launcher/node/cryptoutils-worker-proxy.js');
 const { setupMainThreadTransferHandlers } = require('../launcher/node/transfer-handlers.js');
 
 setupMainThreadTransferHandlers(Comlink, {
@@ -17,23 +16,8 @@ const Client = clientFactory(
     worker => Comlink.wrap(nodeEndpoint(worker)),
 );
 
-const CryptoUtilsWorker = cryptoUtilsWorkerFactory(
-    () => new Worker(join(__dirname, './crypto.js')),
-    worker => Comlink.wrap(nodeEndpoint(worker)),
-);
-for (const propName in CryptoUtilsWorker) {
-  const prop = CryptoUtilsWorker[propName];
-  if (typeof prop === 'function') {
-      CryptoUtils[propName] = prop;
-  }
-}
-
-const wasmexports = require('./main-wasm/index.js');
-Object.keys(wasmexports).forEach(key => {
-  exports[key] = wasmexports[key]
+const primitivesexports = require('./primitives.js');
+Object.keys(primitivesexports).forEach(key => {
+  exports[key] = primitivesexports[key]
 });
 exports.Client = Client;
-const libexports = require('../lib/node/index.js');
-Object.keys(libexports).forEach(key => {
-  exports[key] = libexports[key]
-});
@@ -0,0 +1,26 @@
+const { join } = require('node:path');
+const { Worker } = require('node:worker_threads');
+const Comlink = require('comlink');
+const nodeEndpoint = require('comlink/dist/umd/node-adapter.js');
+const { CryptoUtils } = require('./main-wasm/index.js');
+const { cryptoUtilsWorkerFactory } = require('../launcher/node/cryptoutils-worker-proxy.js');
+
+const CryptoUtilsWorker = cryptoUtilsWorkerFactory(
+    () => new Worker(join(__dirname, './crypto.js')),
+    worker => Comlink.wrap(nodeEndpoint(worker)),
+);
+for (const propName in CryptoUtilsWorker) {
+  const prop = CryptoUtilsWorker[propName];
+  if (typeof prop === 'function') {
+      CryptoUtils[propName] = prop;
+  }
+}
+
+const wasmexports = require('./main-wasm/index.js');
+Object.keys(wasmexports).forEach(key => {
+  exports[key] = wasmexports[key]
+});
+const libexports = require('../lib/node/index.js');
+Object.keys(libexports).forEach(key => {
+  exports[key] = libexports[key]
+});
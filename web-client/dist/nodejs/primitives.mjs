@@ -0,0 +1,25 @@
+import { webcrypto } from 'node:crypto';
+import { Worker } from 'node:worker_threads';
+import Comlink from 'comlink';
+import nodeEndpoint from 'comlink/dist/esm/node-adapter.min.mjs';
+import { CryptoUtils } from './main-wasm/index.js';
+import { cryptoUtilsWorkerFactory } from '../launcher/node/cryptoutils-worker-proxy.mjs';
+
+// NodeJS ES module support for getrandom (https://docs.rs/getrandom/latest/getrandom/#nodejs-es-module-support)
+if (typeof globalThis.crypto === 'undefined') {
+    globalThis.crypto = webcrypto;
+}
+
+const CryptoUtilsWorker = cryptoUtilsWorkerFactory(
+    () => new Worker(new URL('./crypto.mjs', import.meta.url)),
+    worker => Comlink.wrap(nodeEndpoint(worker)),
+);
+for (const propName in CryptoUtilsWorker) {
+    const prop = CryptoUtilsWorker[propName];
+    if (typeof prop === 'function') {
+        CryptoUtils[propName] = prop;
+    }
+}
+
+export * from './main-wasm/index.js';
+export * from '../lib/node/index.mjs';
@@ -0,0 +1,2 @@
+export * from './wasm/primitives-bundler'
+export * from '../lib'
@@ -0,0 +1,3 @@
+export { default } from './wasm/primitives-web'
+export * from './wasm/primitives-web'
+export * from '../lib'
@@ -0,0 +1,18 @@
+import * as Comlink from './comlink.min.mjs';
+import init, { CryptoUtils } from './main-wasm/index.js';
+import { cryptoUtilsWorkerFactory } from '../launcher/browser/cryptoutils-worker-proxy.mjs';
+
+const CryptoUtilsWorker = cryptoUtilsWorkerFactory(
+    () => new Worker(new URL('./crypto.js', import.meta.url)),
+    worker => Comlink.wrap(worker),
+);
+for (const propName in CryptoUtilsWorker) {
+    const prop = CryptoUtilsWorker[propName];
+    if (typeof prop === 'function') {
+        CryptoUtils[propName] = prop;
+    }
+}
+
+export * from './main-wasm/index.js';
+export * from '../lib/web/index.mjs';
+export default init;
@@ -121,7 +121,11 @@ if contains "types" "$TARGETS"; then
     compile "client,crypto,primitives"
     wasm-bindgen --weak-refs --target web --out-name web --out-dir dist/types/wasm "$CARGO_OUTPUT"
     wasm-bindgen --weak-refs --target bundler --out-name bundler --out-dir dist/types/wasm "$CARGO_OUTPUT"
-    find dist/types/wasm ! -name 'web.d.ts' ! -name 'bundler.d.ts' -type f -exec rm {} +
+    # The primitives entry points don't include the client
+    compile "crypto,primitives"
+    wasm-bindgen --weak-refs --target web --out-name primitives-web --out-dir dist/types/wasm "$CARGO_OUTPUT"
+    wasm-bindgen --weak-refs --target bundler --out-name primitives-bundler --out-dir dist/types/wasm "$CARGO_OUTPUT"
+    find dist/types/wasm ! -name '*web.d.ts' ! -name '*bundler.d.ts' -type f -exec rm {} +
 fi
 
 # Build launcher
@@ -17,6 +17,15 @@ pub struct PlainVestingContract {
     time_step: u64,
     step_amount: u64,
     total_amount: u64,
+    beneficiaries: Vec<PlainVestingBeneficiary>,
+}
+
+#[derive(serde::Serialize, Tsify)]
+#[serde(rename_all = "camelCase")]
+pub struct PlainVestingBeneficiary {
+    address: String,
+    weight: u16,
+    withdrawn: u64,
 }
 
 #[derive(serde::Serialize, Tsify)]
@@ -30,6 +39,17 @@ pub struct PlainHtlcContract {
     hash_count: u8,
     timeout: u64,
     total_amount: u64,
+    redeemed_amount: u64,
+    redeemed_hash_depth: u8,
+    redemptions: Vec<PlainHtlcRedemption>,
+}
+
+#[derive(serde::Serialize, Tsify)]
+#[serde(rename_all = "camelCase")]
+pub struct PlainHtlcRedemption {
+    hash_depth: u8,
+    min_balance: u64,
+    claimable: u64,
 }
 
 #[derive(serde::Serialize, Tsify)]
@@ -50,8 +70,13 @@ pub enum PlainAccount {
     Staking(PlainStakingContract),
 }
 
-impl From<&nimiq_account::Account> for PlainAccount {
-    fn from(account: &nimiq_account::Account) -> Self {
+impl PlainAccount {
+    /// Converts an account. The beneficiaries of a vesting contract are stored in the data store
+    /// of the contract and must be passed separately.
+    pub fn from_account(
+        account: &nimiq_account::Account,
+        vesting_beneficiaries: &[nimiq_account::VestingContractBeneficiary],
+    ) -> Self {
         match account {
             nimiq_account::Account::Basic(acc) => PlainAccount::Basic(PlainBasicAccount {
                 balance: acc.balance.into(),
@@ -63,6 +88,14 @@ impl From<&nimiq_account::Account> for PlainAccount {
                 time_step: acc.time_step,
                 step_amount: acc.step_amount.into(),
                 total_amount: acc.total_amount.into(),
+                beneficiaries: vesting_beneficiaries
+                    .iter()
+                    .map(|beneficiary| PlainVestingBeneficiary {
+                        address: beneficiary.address.to_user_friendly_address(),
+                        weight: beneficiary.weight,
+                        withdrawn: beneficiary.withdrawn.into(),
+                    })
+                    .collect(),
             }),
             nimiq_account::Account::HTLC(acc) => PlainAccount::Htlc(PlainHtlcContract {
                 balance: acc.balance.into(),
@@ -78,6 +111,9 @@ impl From<&nimiq_account::Account> for PlainAccount {
                     nimiq_transaction::account::htlc_contract::AnyHash::Sha512(_) => {
                         "sha512".to_string()
                     }
+                    nimiq_transaction::account::htlc_contract::AnyHash::Keccak256(_) => {
+                        "keccak256".to_string()
+                    }
                 },
                 hash_root: match &acc.hash_root {
                     nimiq_transaction::account::htlc_contract::AnyHash::Blake2b(hash) => {
@@ -89,10 +125,24 @@ impl From<&nimiq_account::Account> for PlainAccount {
                     nimiq_transaction::account::htlc_contract::AnyHash::Sha512(hash) => {
                         hash.to_hex()
                     }
+                    nimiq_transaction::account::htlc_contract::AnyHash::Keccak256(hash) => {
+                        hash.to_hex()
+                    }
                 },
                 hash_count: acc.hash_count,
                 timeout: acc.timeout,
                 total_amount: acc.total_amount.into(),
+                redeemed_amount: acc.redeemed_amount().into(),
+                redeemed_hash_depth: acc.redeemed_hash_depth(),
+                redemptions: acc
+                    .redemptions()
+                    .into_iter()
+                    .map(|redemption| PlainHtlcRedemption {
+                        hash_depth: redemption.hash_depth,
+                        min_balance: redemption.min_balance.into(),
+                        claimable: redemption.claimable.into(),
+                    })
+                    .collect(),
             }),
             nimiq_account::Account::Staking(acc) => PlainAccount::Staking(PlainStakingContract {
                 balance: acc.balance.into(),
@@ -158,10 +208,29 @@ pub struct PlainStaker {
     /// periods must have passed.
     /// Once retired, the funds are immediately available to be withdrawn (removed).
     retired_balance: u64,
+    /// Whether the staker's rewards are credited to its active balance. Otherwise, they are
+    /// credited to its retired balance.
+    compound_rewards: bool,
+    /// The unstakes scheduled by the staker that haven't been claimed yet.
+    scheduled_withdrawals: Vec<PlainScheduledWithdrawal>,
+}
+
+/// JSON-compatible format of a scheduled unstake of a staker.
+#[derive(serde::Serialize, Tsify)]
+#[serde(rename_all = "camelCase")]
+pub struct PlainScheduledWithdrawal {
+    /// The value to be withdrawn.
+    value: u64,
+    /// The block height from which the withdrawal can be claimed.
+    release_block: u32,
 }
 
-impl From<&nimiq_account::Staker> for PlainStaker {
-    fn from(staker: &nimiq_account::Staker) -> Self {
+impl PlainStaker {
+    pub fn from_staker(
+        staker: &nimiq_account::Staker,
+        rewards: &nimiq_account::StakerRewards,
+        scheduled_withdrawals: &[nimiq_account::ScheduledWithdrawal],
+    ) -> Self {
         PlainStaker {
             delegation: staker
                 .delegation
@@ -174,6 +243,14 @@ impl From<&nimiq_account::Staker> for PlainStaker {
                 .inactive_from
                 .map(Policy::block_after_reporting_window),
             retired_balance: staker.retired_balance.into(),
+            compound_rewards: rewards.compound_rewards,
+            scheduled_withdrawals: scheduled_withdrawals
+                .iter()
+                .map(|withdrawal| PlainScheduledWithdrawal {
+                    value: withdrawal.value.into(),
+                    release_block: withdrawal.release_block,
+                })
+                .collect(),
         }
     }
 }
@@ -241,6 +318,26 @@ impl From<&nimiq_account::Validator> for PlainValidator {
     }
 }
 
+/// JSON-compatible format of a tombstone, which remains in the staking contract after a validator
+/// was deleted while stakers were still delegating to it.
+#[derive(serde::Serialize, Tsify)]
+#[serde(rename_all = "camelCase")]
+pub struct PlainTombstone {
+    /// The stake that is still delegated to the deleted validator.
+    remaining_stake: u64,
+    /// The number of stakers that are still delegating to the deleted validator.
+    num_remaining_stakers: u64,
+}
+
+impl From<&nimiq_account::Tombstone> for PlainTombstone {
+    fn from(tombstone: &nimiq_account::Tombstone) -> PlainTombstone {
+        PlainTombstone {
+            remaining_stake: tombstone.remaining_stake.into(),
+            num_remaining_stakers: tombstone.num_remaining_stakers,
+        }
+    }
+}
+
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(typescript_type = "PlainAccount")]
@@ -260,4 +357,10 @@ extern "C" {
 
     #[wasm_bindgen(typescript_type = "(PlainValidator | undefined)[]")]
     pub type PlainValidatorArrayType;
+
+    #[wasm_bindgen(typescript_type = "PlainTombstone | undefined")]
+    pub type PlainTombstoneType;
+
+    #[wasm_bindgen(typescript_type = "(PlainTombstone | undefined)[]")]
+    pub type PlainTombstoneArrayType;
 }
@@ -9,7 +9,7 @@ use std::{
     time::Duration,
 };
 
-use futures::StreamExt;
+use futures::{future, stream, FutureExt, StreamExt, TryStreamExt};
 use js_sys::{global, Array, Function, JsString};
 use log::level_filters::LevelFilter;
 use nimiq::client::ConsensusProxy;
@@ -22,14 +22,21 @@ pub use nimiq::{
 };
 use nimiq_blockchain_interface::{AbstractBlockchain, BlockchainEvent};
 use nimiq_bls::LazyPublicKey;
-use nimiq_consensus::ConsensusEvent;
+use nimiq_consensus::{
+    consensus::remote_data_store::{RemoteData, RemoteDataKey},
+    ConsensusEvent,
+};
+use nimiq_genesis::{SignedSyncCheckpoint, SyncCheckpoints};
 use nimiq_hash::Blake2bHash;
+use nimiq_keys::Ed25519PublicKey;
 use nimiq_network_interface::{
     network::{CloseReason, Network, NetworkEvent},
     Multiaddr,
 };
-use nimiq_primitives::policy::Policy;
+use nimiq_primitives::{coin::Coin, policy::Policy};
+use nimiq_serde::Deserialize;
 use nimiq_time::timeout;
+use nimiq_transaction::historic_transaction::HistoricTransaction;
 use nimiq_utils::spawn_local;
 use tokio::sync::oneshot;
 use tsify::Tsify;
@@ -40,8 +47,8 @@ use crate::{
     client::{
         account::{
             PlainAccount, PlainAccountArrayType, PlainAccountType, PlainStaker,
-            PlainStakerArrayType, PlainStakerType, PlainValidator, PlainValidatorArrayType,
-            PlainValidatorType,
+            PlainStakerArrayType, PlainStakerType, PlainTombstone, PlainTombstoneArrayType,
+            PlainTombstoneType, PlainValidator, PlainValidatorArrayType, PlainValidatorType,
         },
         block::{PlainBlock, PlainBlockType},
         bls_cache::BlsCache,
@@ -64,6 +71,13 @@ use crate::{
 /// Maximum number of transactions that can be requested by address
 pub const MAX_TRANSACTIONS_BY_ADDRESS: u16 = 500;
 
+/// Maximum number of addresses whose transaction receipts are requested concurrently
+const MAX_CONCURRENT_RECEIPT_REQUESTS: usize = 4;
+
+/// Balance listeners by listener ID, with the last balance reported for each subscribed address.
+type BalanceListeners =
+    Rc<RefCell<HashMap<usize, (Function, HashMap<nimiq_keys::Address, Option<Coin>>)>>>;
+
 /// Describes the state of consensus of the client.
 #[derive(Tsify)]
 #[serde(rename_all = "lowercase")]
@@ -110,7 +124,11 @@ pub struct Client {
     consensus_changed_listeners: Rc<RefCell<HashMap<usize, Function>>>,
     head_changed_listeners: Rc<RefCell<HashMap<usize, Function>>>,
     peer_changed_listeners: Rc<RefCell<HashMap<usize, Function>>>,
+    peer_set_changed_listeners: Rc<RefCell<HashMap<usize, Function>>>,
+    sync_progress_listeners: Rc<RefCell<HashMap<usize, Function>>>,
     transaction_listeners: Rc<RefCell<HashMap<usize, (Function, HashSet<nimiq_keys::Address>)>>>,
+    /// Balance listeners with the last balance that was reported to them for each address.
+    balance_listeners: BalanceListeners,
 
     /// Map from transaction hash as hex string to oneshot sender.
     /// Used to await transaction events in `send_transaction`.
@@ -169,6 +187,29 @@ impl Client {
         config.network.peer_count_per_ip_max = web_config.peer_count_per_ip_max;
         config.network.peer_count_per_subnet_max = web_config.peer_count_per_subnet_max;
 
+        // Set the trusted sync checkpoints
+        let mut sync_checkpoints = SyncCheckpoints::new(
+            web_config
+                .sync_checkpoint_keys
+                .iter()
+                .map(|key| {
+                    Ed25519PublicKey::from_str(key)
+                        .map_err(|err| JsError::new(&format!("Invalid sync checkpoint key: {err}")))
+                })
+                .collect::<Result<Vec<_>, JsError>>()?,
+        );
+        for checkpoints in &web_config.sync_checkpoints {
+            let checkpoints = hex::decode(checkpoints)
+                .map_err(|err| err.to_string())
+                .and_then(|data| {
+                    Vec::<SignedSyncCheckpoint>::deserialize_from_vec(&data)
+                        .map_err(|err| err.to_string())
+                })
+                .map_err(|err| JsError::new(&format!("Invalid sync checkpoints: {err}")))?;
+            sync_checkpoints.extend(checkpoints);
+        }
+        config.consensus.sync_checkpoints = sync_checkpoints;
+
         log::info!(?config, "Final configuration");
 
         // Create client from config.
@@ -196,7 +237,10 @@ impl Client {
             consensus_changed_listeners: Rc::new(RefCell::new(HashMap::with_capacity(1))),
             head_changed_listeners: Rc::new(RefCell::new(HashMap::with_capacity(1))),
             peer_changed_listeners: Rc::new(RefCell::new(HashMap::with_capacity(1))),
+            peer_set_changed_listeners: Rc::new(RefCell::new(HashMap::with_capacity(1))),
+            sync_progress_listeners: Rc::new(RefCell::new(HashMap::with_capacity(1))),
             transaction_listeners: Rc::new(RefCell::new(HashMap::new())),
+            balance_listeners: Rc::new(RefCell::new(HashMap::new())),
             transaction_oneshots: Rc::new(RefCell::new(HashMap::new())),
             bls_cache: Rc::new(bls_cache),
         };
@@ -206,6 +250,7 @@ impl Client {
         client.setup_blockchain_events();
         client.setup_network_events();
         client.setup_transaction_events().await;
+        client.setup_balance_events();
 
         if let Err(err) = client.bls_cache.init().await {
             log::warn!("Failed loading bls cache {}", err);
@@ -265,6 +310,42 @@ impl Client {
         Ok(listener_id)
     }
 
+    /// Adds an event listener for changes in the composition of the synced peers, such as the number
+    /// of peers providing the full transaction history or being validators.
+    #[wasm_bindgen(js_name = addPeerSetChangedListener)]
+    pub async fn add_peer_set_changed_listener(
+        &self,
+        listener: PeerSetChangedListener,
+    ) -> Result<usize, JsError> {
+        let listener = listener
+            .dyn_into::<Function>()
+            .map_err(|_| JsError::new("listener is not a function"))?;
+
+        let listener_id = self.next_listener_id();
+        self.peer_set_changed_listeners
+            .borrow_mut()
+            .insert(listener_id, listener);
+        Ok(listener_id)
+    }
+
+    /// Adds an event listener for the progress of the state sync, reporting the number of chunks
+    /// applied so far and the estimated total number of chunks, if already known.
+    #[wasm_bindgen(js_name = addSyncProgressListener)]
+    pub async fn add_sync_progress_listener(
+        &self,
+        listener: SyncProgressListener,
+    ) -> Result<usize, JsError> {
+        let listener = listener
+            .dyn_into::<Function>()
+            .map_err(|_| JsError::new("listener is not a function"))?;
+
+        let listener_id = self.next_listener_id();
+        self.sync_progress_listeners
+            .borrow_mut()
+            .insert(listener_id, listener);
+        Ok(listener_id)
+    }
+
     /// Adds an event listener for transactions to and from the provided addresses.
     ///
     /// The listener is called for transactions when they are _included_ in the blockchain.
@@ -280,18 +361,6 @@ impl Client {
 
         let addresses: HashSet<_, _> = Client::unpack_addresses(addresses)?.into_iter().collect();
 
-        // Add addresses to our global list of subscribed addresses
-        {
-            // Borrow RefCell in a new scope, as Clippy did not detect usage of drop(...).
-            let mut subscribed_addresses = self.subscribed_addresses.borrow_mut();
-            for address in addresses.iter() {
-                subscribed_addresses
-                    .entry(address.clone())
-                    .and_modify(|count| *count += 1)
-                    .or_insert(1);
-            }
-        }
-
         // Add to our listeners
         let listener_id = self.next_listener_id();
         self.transaction_listeners
@@ -299,12 +368,47 @@ impl Client {
             .insert(listener_id, (listener, addresses.clone()));
 
         // Then subscribe at network
-        // Ignore failure because we still want to return the listener ID to the caller.
-        let _ = self
-            .inner
-            .consensus_proxy()
-            .subscribe_to_addresses(addresses.into_iter().collect(), 1, None)
-            .await;
+        self.subscribe_addresses(addresses).await;
+
+        Ok(listener_id)
+    }
+
+    /// Subscribes to the balances of the provided addresses.
+    ///
+    /// The listener is called with the current balance of each address once it is known and
+    /// afterwards whenever the balance changes. Balances are requested together with trie
+    /// proofs from the network, so they are verified. They are only requested when a
+    /// transaction of a subscribed address is included in the blockchain, when a macro block
+    /// pays out rewards, when the chain is rebranched or when consensus is (re-)established.
+    #[wasm_bindgen(js_name = subscribeBalances)]
+    pub async fn subscribe_balances(
+        &self,
+        addresses: &AddressAnyArrayType,
+        listener: BalanceListener,
+    ) -> Result<usize, JsError> {
+        let listener = listener
+            .dyn_into::<Function>()
+            .map_err(|_| JsError::new("listener is not a function"))?;
+
+        let addresses: HashSet<_> = Client::unpack_addresses(addresses)?.into_iter().collect();
+        let balances = addresses
+            .iter()
+            .map(|address| (address.clone(), None))
+            .collect();
+
+        let listener_id = self.next_listener_id();
+        self.balance_listeners
+            .borrow_mut()
+            .insert(listener_id, (listener, balances));
+
+        // Get notified about transactions of the addresses.
+        self.subscribe_addresses(addresses.clone()).await;
+
+        // Report the current balances right away instead of waiting for the next event.
+        let consensus = self.inner.consensus_proxy();
+        if consensus.is_established() {
+            Client::update_balances(&consensus, &self.balance_listeners, Some(&addresses)).await;
+        }
 
         Ok(listener_id)
     }
@@ -317,32 +421,69 @@ impl Client {
             .remove(&handle);
         self.head_changed_listeners.borrow_mut().remove(&handle);
         self.peer_changed_listeners.borrow_mut().remove(&handle);
+        self.peer_set_changed_listeners.borrow_mut().remove(&handle);
+        self.sync_progress_listeners.borrow_mut().remove(&handle);
+
+        let transaction_listener = self.transaction_listeners.borrow_mut().remove(&handle);
+        if let Some((_, unsubscribed_addresses)) = transaction_listener {
+            self.unsubscribe_addresses(unsubscribed_addresses);
+        }
 
-        if let Some((_, unsubscribed_addresses)) =
-            self.transaction_listeners.borrow_mut().remove(&handle)
+        let balance_listener = self.balance_listeners.borrow_mut().remove(&handle);
+        if let Some((_, balances)) = balance_listener {
+            self.unsubscribe_addresses(balances.into_keys());
+        }
+    }
+
+    /// Adds the addresses to the global list of subscribed addresses and subscribes to their
+    /// transactions at the network.
+    async fn subscribe_addresses(&self, addresses: HashSet<nimiq_keys::Address>) {
         {
+            // Borrow RefCell in a new scope, as Clippy did not detect usage of drop(...).
             let mut subscribed_addresses = self.subscribed_addresses.borrow_mut();
-            let mut removed_addresses = vec![];
-            for unsubscribed_address in unsubscribed_addresses {
-                if let Entry::Occupied(mut entry) =
-                    subscribed_addresses.entry(unsubscribed_address.clone())
-                {
-                    *entry.get_mut() -= 1;
+            for address in addresses.iter() {
+                subscribed_addresses
+                    .entry(address.clone())
+                    .and_modify(|count| *count += 1)
+                    .or_insert(1);
+            }
+        }
 
-                    if entry.get() == &0 {
-                        entry.remove_entry();
-                        removed_addresses.push(unsubscribed_address);
-                    }
+        // Ignore failure because we still want to return the listener ID to the caller.
+        let _ = self
+            .inner
+            .consensus_proxy()
+            .subscribe_to_addresses(addresses.into_iter().collect(), 1, None)
+            .await;
+    }
+
+    /// Removes the addresses from the global list of subscribed addresses and unsubscribes from
+    /// the ones that are no longer subscribed by any listener at the network.
+    fn unsubscribe_addresses(
+        &self,
+        unsubscribed_addresses: impl IntoIterator<Item = nimiq_keys::Address>,
+    ) {
+        let mut subscribed_addresses = self.subscribed_addresses.borrow_mut();
+        let mut removed_addresses = vec![];
+        for unsubscribed_address in unsubscribed_addresses {
+            if let Entry::Occupied(mut entry) =
+                subscribed_addresses.entry(unsubscribed_address.clone())
+            {
+                *entry.get_mut() -= 1;
+
+                if entry.get() == &0 {
+                    entry.remove_entry();
+                    removed_addresses.push(unsubscribed_address);
                 }
             }
-            if !removed_addresses.is_empty() {
-                let owned_consensus = self.inner.consensus_proxy();
-                spawn_local(async move {
-                    let _ = owned_consensus
-                        .unsubscribe_from_addresses(removed_addresses, 1)
-                        .await;
-                });
-            }
+        }
+        if !removed_addresses.is_empty() {
+            let owned_consensus = self.inner.consensus_proxy();
+            spawn_local(async move {
+                let _ = owned_consensus
+                    .unsubscribe_from_addresses(removed_addresses, 1)
+                    .await;
+            });
         }
     }
 
@@ -531,6 +672,34 @@ impl Client {
         Ok(serde_wasm_bindgen::to_value(&plain_validators)?.into())
     }
 
+    /// Fetches the tombstone of the deleted validator with the provided address from the network.
+    ///
+    /// Throws if the address cannot be parsed and on network errors.
+    #[wasm_bindgen(js_name = getTombstone)]
+    pub async fn get_tombstone(
+        &self,
+        address: &AddressAnyType,
+    ) -> Result<PlainTombstoneType, JsError> {
+        let address = Address::from_any(address)?.take_native();
+        let plain_tombstones = self.get_plain_tombstones(vec![address]).await?;
+        let tombstone = plain_tombstones.first().unwrap();
+        Ok(serde_wasm_bindgen::to_value(tombstone)?.into())
+    }
+
+    /// Fetches the tombstones of the deleted validators with the provided addresses from the
+    /// network.
+    ///
+    /// Throws if an address cannot be parsed and on network errors.
+    #[wasm_bindgen(js_name = getTombstones)]
+    pub async fn get_tombstones(
+        &self,
+        addresses: &AddressAnyArrayType,
+    ) -> Result<PlainTombstoneArrayType, JsError> {
+        let addresses = Client::unpack_addresses(addresses)?;
+        let plain_tombstones = self.get_plain_tombstones(addresses).await?;
+        Ok(serde_wasm_bindgen::to_value(&plain_tombstones)?.into())
+    }
+
     /// Sends a transaction to the network and returns {@link PlainTransactionDetails}.
     ///
     /// Throws in case of network errors.
@@ -862,37 +1031,7 @@ impl Client {
             .await?;
 
         let current_height = self.get_head_height().await;
-
-        // Convert historic transactions into the plain transaction details result type.
-        // TODO: Optimization: If the receipts are ordered, we can only compare with the earliest receipt instead of potentially iterating over all receipts.
-        let genesis = if ext_txs
-            .iter()
-            .any(|tx| tx.block_number < Policy::genesis_block_number())
-        {
-            Some(
-                self.inner
-                    .consensus_proxy()
-                    .blockchain
-                    .read()
-                    .get_genesis_block(),
-            )
-        } else {
-            None
-        };
-        let mut txs: Vec<_> = ext_txs
-            .into_iter()
-            .map(|hist_tx| {
-                // This method automatically marks the PlainTransactionDetails as status `Included`
-                // or `confirmed` from the `current_height`.
-                PlainTransactionDetails::try_from_historic_transaction(
-                    hist_tx,
-                    current_height,
-                    genesis.as_ref().map(|block| block.block_number()),
-                    genesis.as_ref().map(|block| block.timestamp()),
-                )
-                .expect("no non-reward inherent")
-            })
-            .collect();
+        let mut txs = self.to_plain_transaction_details(ext_txs, current_height);
 
         // Track known new or pending transactions.
         for details in known_txs.values() {
@@ -931,6 +1070,92 @@ impl Client {
         Ok(serde_wasm_bindgen::to_value(&txs)?.into())
     }
 
+    /// This function is used to query the network for transactions from and to any of the
+    /// given addresses, that have been included in the chain.
+    ///
+    /// The transaction receipts of all addresses are requested concurrently and merged, so
+    /// transactions between two of the addresses are only fetched and returned once. The
+    /// obtained transactions are verified before being returned.
+    ///
+    /// Provide the `since_block_height` parameter to exclude any history from before that block
+    /// height, see `getTransactionsByAddress`.
+    ///
+    /// Up to a `limit` number of transactions are returned from newest to oldest across all
+    /// addresses. If the network does not have at least `min_peers` to query, an error is returned.
+    #[wasm_bindgen(js_name = getTransactionsByAddresses)]
+    pub async fn get_transactions_by_addresses(
+        &self,
+        addresses: &AddressAnyArrayType,
+        since_block_height: Option<u32>,
+        limit: Option<u16>,
+        min_peers: Option<usize>,
+    ) -> Result<PlainTransactionDetailsArrayType, JsError> {
+        let since_block_height = since_block_height.unwrap_or(0);
+        let min_peers = min_peers.unwrap_or(1);
+
+        if let Some(max) = limit {
+            if max > MAX_TRANSACTIONS_BY_ADDRESS {
+                return Err(JsError::new(
+                    "The maximum number of transactions exceeds the one that is supported",
+                ));
+            }
+        }
+
+        let addresses: HashSet<_> = Client::unpack_addresses(addresses)?.into_iter().collect();
+        let include_pre_genesis = since_block_height < Policy::genesis_block_number();
+
+        // Fetch the transaction receipts of the addresses, a few at a time. Each address returns
+        // its newest `limit` receipts, so the newest `limit` receipts across all addresses
+        // are contained in the union.
+        let consensus = self.inner.consensus_proxy();
+        let responses: Vec<_> = stream::iter(addresses)
+            .map(|address| {
+                consensus.request_transaction_receipts_by_address(
+                    address,
+                    min_peers,
+                    limit,
+                    None,
+                    include_pre_genesis,
+                )
+            })
+            .buffer_unordered(MAX_CONCURRENT_RECEIPT_REQUESTS)
+            .try_collect()
+            .await?;
+
+        let receipts: HashMap<_, _> = responses
+            .into_iter()
+            .flatten()
+            .filter(|(_, block_number)| *block_number >= since_block_height)
+            .collect();
+
+        // Only prove the newest `limit` receipts.
+        let mut receipts_to_fetch: Vec<_> = receipts
+            .into_iter()
+            .map(|(hash, block_number)| (hash, Some(block_number)))
+            .collect();
+        receipts_to_fetch
+            .sort_unstable_by(|(hash_a, a), (hash_b, b)| b.cmp(a).then_with(|| hash_a.cmp(hash_b)));
+        if let Some(limit) = limit {
+            receipts_to_fetch.truncate(limit as usize);
+        }
+
+        let ext_txs = consensus
+            .prove_transactions_from_receipts(receipts_to_fetch, min_peers)
+            .await?;
+
+        let current_height = self.get_head_height().await;
+        let mut txs = self.to_plain_transaction_details(ext_txs, current_height);
+        txs.sort_unstable_by(|a, b| {
+            b.block_height.cmp(&a.block_height).then_with(|| {
+                a.transaction
+                    .transaction_hash
+                    .cmp(&b.transaction.transaction_hash)
+            })
+        });
+
+        Ok(serde_wasm_bindgen::to_value(&txs)?.into())
+    }
+
     /// This function is used to tell the network to disconnect from every connected
     /// peer and stop trying to connect to other peers.
     ///
@@ -1003,6 +1228,8 @@ impl Client {
         let mut consensus_events = consensus.subscribe_events();
 
         let consensus_listeners = Rc::clone(&self.consensus_changed_listeners);
+        let peer_set_listeners = Rc::clone(&self.peer_set_changed_listeners);
+        let sync_progress_listeners = Rc::clone(&self.sync_progress_listeners);
 
         spawn_local(async move {
             loop {
@@ -1017,6 +1244,38 @@ impl Client {
                             Some(ConsensusState::Connecting)
                         }
                     }
+                    Some(Ok(ConsensusEvent::PeerSetChanged {
+                        num_peers,
+                        num_history_peers,
+                        num_validator_peers,
+                    })) => {
+                        let args = Array::new();
+                        args.push(&num_peers.into());
+                        args.push(&num_history_peers.into());
+                        args.push(&num_validator_peers.into());
+
+                        let this = JsValue::null();
+                        for listener in peer_set_listeners.borrow().values() {
+                            let _ = listener.apply(&this, &args);
+                        }
+                        None
+                    }
+                    Some(Ok(ConsensusEvent::SyncProgress {
+                        chunks_applied,
+                        estimated_total,
+                    })) => {
+                        let args = Array::new();
+                        args.push(&(chunks_applied as f64).into());
+                        args.push(
+                            &estimated_total.map_or(JsValue::null(), |total| (total as f64).into()),
+                        );
+
+                        let this = JsValue::null();
+                        for listener in sync_progress_listeners.borrow().values() {
+                            let _ = listener.apply(&this, &args);
+                        }
+                        None
+                    }
                     Some(Err(_)) => {
                         None // Ignore stream errors
                     }
@@ -1226,9 +1485,17 @@ impl Client {
 
         let transaction_listeners = Rc::clone(&self.transaction_listeners);
         let transaction_oneshots = Rc::clone(&self.transaction_oneshots);
+        let balance_listeners = Rc::clone(&self.balance_listeners);
 
         spawn_local(async move {
-            let mut address_notifications = consensus.subscribe_address_notifications().await;
+            let mut address_notifications = match consensus.subscribe_address_notifications().await
+            {
+                Ok(address_notifications) => address_notifications,
+                Err(error) => {
+                    log::error!(%error, "Failed to subscribe to address notifications");
+                    return;
+                }
+            };
 
             while let Some((notification, _)) = address_notifications.next().await {
                 {
@@ -1264,6 +1531,7 @@ impl Client {
                     })
                 {
                     let this = JsValue::null();
+                    let mut changed_addresses = HashSet::new();
 
                     for hist_tx in hist_txs {
                         let block_number = hist_tx.block_number;
@@ -1298,6 +1566,10 @@ impl Client {
                             None
                         };
 
+                        changed_addresses.insert(sender.clone());
+                        changed_addresses.insert(recipient.clone());
+                        changed_addresses.extend(staker_address.clone());
+
                         if let Ok(js_value) = serde_wasm_bindgen::to_value(&details) {
                             for (listener, addresses) in transaction_listeners.borrow().values() {
                                 if addresses.contains(&sender)
@@ -1313,11 +1585,119 @@ impl Client {
                             }
                         }
                     }
+
+                    Client::update_balances(
+                        &consensus,
+                        &balance_listeners,
+                        Some(&changed_addresses),
+                    )
+                    .await;
                 }
             }
         });
     }
 
+    /// Updates the balances on the events that change balances without a transaction of the
+    /// subscribed addresses, see `subscribe_balances`. Transactions are handled together with the
+    /// address notifications in `setup_transaction_events`.
+    fn setup_balance_events(&self) {
+        let consensus = self.inner.consensus_proxy();
+
+        // Rewards are only paid out in macro blocks and rebranches can revert transactions.
+        let blockchain_events = consensus
+            .blockchain
+            .read()
+            .notifier_as_stream()
+            .filter(|event| {
+                future::ready(matches!(
+                    event,
+                    BlockchainEvent::Rebranched(..)
+                        | BlockchainEvent::Finalized(_)
+                        | BlockchainEvent::EpochFinalized(_)
+                ))
+            })
+            .map(|_| ());
+        let consensus_events = consensus
+            .subscribe_events()
+            .filter(|event| future::ready(matches!(event, Ok(ConsensusEvent::Established { .. }))))
+            .map(|_| ());
+        let mut events = stream::select(blockchain_events, consensus_events).boxed_local();
+
+        let balance_listeners = Rc::clone(&self.balance_listeners);
+
+        spawn_local(async move {
+            while events.next().await.is_some() {
+                // Skip the events that queued up during the previous update,
+                // a single request covers all of them.
+                while let Some(Some(_)) = events.next().now_or_never() {}
+
+                if !consensus.is_established() {
+                    continue;
+                }
+
+                Client::update_balances(&consensus, &balance_listeners, None).await;
+            }
+        });
+    }
+
+    /// Requests the accounts of the subscribed addresses, restricted to `changed_addresses` if
+    /// given, and notifies the balance listeners of the balances that changed since they were
+    /// last notified.
+    async fn update_balances(
+        consensus: &ConsensusProxy,
+        balance_listeners: &BalanceListeners,
+        changed_addresses: Option<&HashSet<nimiq_keys::Address>>,
+    ) {
+        let addresses: HashSet<_> = balance_listeners
+            .borrow()
+            .values()
+            .flat_map(|(_, balances)| balances.keys().cloned())
+            .filter(|address| changed_addresses.map_or(true, |changed| changed.contains(address)))
+            .collect();
+        if addresses.is_empty() {
+            return;
+        }
+
+        let accounts = match consensus
+            .request_accounts_by_addresses(addresses.into_iter().collect(), 1)
+            .await
+        {
+            Ok(accounts) => accounts,
+            Err(error) => {
+                log::debug!(%error, "Failed to request accounts for balance listeners");
+                return;
+            }
+        };
+
+        // Collect the notifications first, so listeners can remove themselves when called.
+        let mut notifications = vec![];
+        for (listener, balances) in balance_listeners.borrow_mut().values_mut() {
+            for (address, known_balance) in balances.iter_mut() {
+                let Some(account) = accounts.get(address) else {
+                    continue;
+                };
+                let balance = account
+                    .as_ref()
+                    .map(|account| account.balance())
+                    .unwrap_or_default();
+
+                if *known_balance != Some(balance) {
+                    *known_balance = Some(balance);
+                    notifications.push((listener.clone(), address.clone(), balance));
+                }
+            }
+        }
+
+        let this = JsValue::null();
+        for (listener, address, balance) in notifications {
+            let _ = listener.call2(
+                &this,
+                &address.to_user_friendly_address().into(),
+                &JsValue::from_f64(u64::from(balance) as f64),
+            );
+        }
+    }
+
     fn next_listener_id(&self) -> usize {
         let mut id = self.listener_id.get();
         id += 1;
@@ -1327,6 +1707,43 @@ impl Client {
 }
 
 impl Client {
+    /// Converts historic transactions into the plain transaction details result type,
+    /// marking them as `Included` or `Confirmed` depending on the `current_height`.
+    fn to_plain_transaction_details(
+        &self,
+        ext_txs: Vec<HistoricTransaction>,
+        current_height: u32,
+    ) -> Vec<PlainTransactionDetails> {
+        // TODO: Optimization: If the receipts are ordered, we can only compare with the earliest receipt instead of potentially iterating over all receipts.
+        let genesis = if ext_txs
+            .iter()
+            .any(|tx| tx.block_number < Policy::genesis_block_number())
+        {
+            Some(
+                self.inner
+                    .consensus_proxy()
+                    .blockchain
+                    .read()
+                    .get_genesis_block(),
+            )
+        } else {
+            None
+        };
+
+        ext_txs
+            .into_iter()
+            .map(|hist_tx| {
+                PlainTransactionDetails::try_from_historic_transaction(
+                    hist_tx,
+                    current_height,
+                    genesis.as_ref().map(|block| block.block_number()),
+                    genesis.as_ref().map(|block| block.timestamp()),
+                )
+                .expect("no non-reward inherent")
+            })
+            .collect()
+    }
+
     fn unpack_addresses(
         addresses: &AddressAnyArrayType,
     ) -> Result<Vec<nimiq_keys::Address>, JsError> {
@@ -1353,26 +1770,45 @@ impl Client {
         &self,
         addresses: Vec<nimiq_keys::Address>,
     ) -> Result<Vec<PlainAccount>, JsError> {
-        let accounts = self
+        // The beneficiaries of a vesting contract are stored separately from the contract, so both
+        // entries are requested with the same proof.
+        let keys = addresses
+            .iter()
+            .flat_map(|address| {
+                [
+                    RemoteDataKey::Account(address.clone()),
+                    RemoteDataKey::VestingBeneficiaries(address.clone()),
+                ]
+            })
+            .collect();
+        let mut data = self
             .inner
             .consensus_proxy()
-            .request_accounts_by_addresses(addresses.clone(), 1)
+            .request_remote_data(keys, 1)
             .await?;
 
         let mut ordered_accounts = vec![];
         let default = nimiq_account::Account::default();
 
         for address in &addresses {
-            ordered_accounts.push(PlainAccount::from(
-                accounts
-                    .get(address)
-                    .ok_or(JsError::new(&format!(
-                        "Missing trie proof node for {}",
-                        address
-                    )))?
-                    .as_ref()
-                    .unwrap_or(&default),
-            ));
+            let missing_proof =
+                || JsError::new(&format!("Missing trie proof node for {}", address));
+            let account = data
+                .remove(&RemoteDataKey::Account(address.clone()))
+                .ok_or_else(missing_proof)?;
+            let beneficiaries = data
+                .remove(&RemoteDataKey::VestingBeneficiaries(address.clone()))
+                .ok_or_else(missing_proof)?;
+
+            let account = match account {
+                Some(RemoteData::Account(account)) => account,
+                _ => default.clone(),
+            };
+            let beneficiaries = match beneficiaries {
+                Some(RemoteData::VestingBeneficiaries(beneficiaries)) => beneficiaries,
+                _ => vec![],
+            };
+            ordered_accounts.push(PlainAccount::from_account(&account, &beneficiaries));
         }
 
         Ok(ordered_accounts)
@@ -1382,25 +1818,56 @@ impl Client {
         &self,
         addresses: Vec<nimiq_keys::Address>,
     ) -> Result<Vec<Option<PlainStaker>>, JsError> {
-        let stakers = self
+        // The reward compounding and the scheduled withdrawals of a staker are stored separately
+        // from the staker, so all entries are requested with the same proof.
+        let keys = addresses
+            .iter()
+            .flat_map(|address| {
+                [
+                    RemoteDataKey::Staker(address.clone()),
+                    RemoteDataKey::StakerRewards(address.clone()),
+                    RemoteDataKey::StakerWithdrawals(address.clone()),
+                ]
+            })
+            .collect();
+        let mut data = self
             .inner
             .consensus_proxy()
-            .request_stakers_by_addresses(addresses.clone(), 1)
+            .request_remote_data(keys, 1)
             .await?;
 
         let mut ordered_stakers = vec![];
 
         for address in &addresses {
-            ordered_stakers.push(
-                stakers
-                    .get(address)
-                    .ok_or(JsError::new(&format!(
-                        "Missing trie proof node for {}",
-                        address
-                    )))?
-                    .as_ref()
-                    .map(PlainStaker::from),
-            );
+            let missing_proof =
+                || JsError::new(&format!("Missing trie proof node for {}", address));
+            let staker = data
+                .remove(&RemoteDataKey::Staker(address.clone()))
+                .ok_or_else(missing_proof)?;
+            let rewards = data
+                .remove(&RemoteDataKey::StakerRewards(address.clone()))
+                .ok_or_else(missing_proof)?;
+            let withdrawals = data
+                .remove(&RemoteDataKey::StakerWithdrawals(address.clone()))
+                .ok_or_else(missing_proof)?;
+
+            let Some(RemoteData::Staker(staker)) = staker else {
+                ordered_stakers.push(None);
+                continue;
+            };
+            let rewards = match rewards {
+                Some(RemoteData::StakerRewards(rewards)) => rewards,
+                _ => nimiq_account::StakerRewards::default(),
+            };
+            let withdrawals = match withdrawals {
+                Some(RemoteData::StakerWithdrawals(withdrawals)) => withdrawals,
+                _ => vec![],
+            };
+            ordered_stakers.push(Some(PlainStaker::from_staker(
+                &staker,
+                &rewards,
+                &withdrawals,
+            )));
         }
 
         Ok(ordered_stakers)
@@ -1433,6 +1900,34 @@ impl Client {
 
         Ok(ordered_validators)
     }
+
+    async fn get_plain_tombstones(
+        &self,
+        addresses: Vec<nimiq_keys::Address>,
+    ) -> Result<Vec<Option<PlainTombstone>>, JsError> {
+        let tombstones = self
+            .inner
+            .consensus_proxy()
+            .request_tombstones_by_addresses(addresses.clone(), 1)
+            .await?;
+
+        let mut ordered_tombstones = vec![];
+
+        for address in &addresses {
+            ordered_tombstones.push(
+                tombstones
+                    .get(address)
+                    .ok_or(JsError::new(&format!(
+                        "Missing trie proof node for {}",
+                        address
+                    )))?
+                    .as_ref()
+                    .map(PlainTombstone::from),
+            );
+        }
+
+        Ok(ordered_tombstones)
+    }
 }
 
 #[wasm_bindgen]
@@ -1450,8 +1945,21 @@ extern "C" {
     )]
     pub type PeerChangedListener;
 
+    #[wasm_bindgen(
+        typescript_type = "(peer_count: number, history_peer_count: number, validator_peer_count: number) => any"
+    )]
+    pub type PeerSetChangedListener;
+
+    #[wasm_bindgen(
+        typescript_type = "(chunks_applied: number, estimated_total: number | null) => any"
+    )]
+    pub type SyncProgressListener;
+
     #[wasm_bindgen(typescript_type = "(transaction: PlainTransactionDetails) => any")]
     pub type TransactionListener;
+
+    #[wasm_bindgen(typescript_type = "(address: string, balance: number) => any")]
+    pub type BalanceListener;
 }
 
 #[wasm_bindgen]
@@ -103,6 +103,24 @@ impl Address {
         self.inner.to_user_friendly_address()
     }
 
+    /// Returns the nine groups of four characters of the user-friendly address.
+    /// The first group consists of the country code and the checksum.
+    #[cfg(feature = "primitives")]
+    #[wasm_bindgen(js_name = toUserFriendlyGroups)]
+    pub fn to_user_friendly_groups(&self) -> Vec<String> {
+        self.inner.to_user_friendly_groups()
+    }
+
+    /// Formats the address into a shortened user-friendly format, e.g. `NQ07 0000 … 0000`.
+    ///
+    /// It shows the first and the last group of the address, since vanity addresses can easily
+    /// be created that only match the beginning of another address.
+    #[cfg(feature = "primitives")]
+    #[wasm_bindgen(js_name = toShortUserFriendlyAddress)]
+    pub fn to_short_user_friendly_address(&self) -> String {
+        self.inner.to_short_user_friendly_address()
+    }
+
     /// Formats the address into hex format.
     #[cfg(feature = "primitives")]
     #[wasm_bindgen(js_name = toHex)]
@@ -31,6 +31,10 @@ pub struct ClientConfiguration {
     pub peer_count_per_ip_max: usize,
     #[wasm_bindgen(skip)]
     pub peer_count_per_subnet_max: usize,
+    #[wasm_bindgen(skip)]
+    pub sync_checkpoint_keys: Vec<String>,
+    #[wasm_bindgen(skip)]
+    pub sync_checkpoints: Vec<String>,
 }
 
 #[cfg(any(feature = "client", feature = "primitives"))]
@@ -58,6 +62,10 @@ pub struct PlainClientConfiguration {
     pub peer_count_per_ip_max: Option<usize>,
     #[cfg_attr(feature = "client", serde(skip_serializing_if = "Option::is_none"))]
     pub peer_count_per_subnet_max: Option<usize>,
+    #[cfg_attr(feature = "client", serde(skip_serializing_if = "Option::is_none"))]
+    pub sync_checkpoint_keys: Option<Vec<String>>,
+    #[cfg_attr(feature = "client", serde(skip_serializing_if = "Option::is_none"))]
+    pub sync_checkpoints: Option<Vec<String>>,
 }
 
 impl Default for ClientConfiguration {
@@ -86,6 +94,8 @@ impl Default for ClientConfiguration {
             peer_count_max: 50,
             peer_count_per_ip_max: 10,
             peer_count_per_subnet_max: 10,
+            sync_checkpoint_keys: vec![],
+            sync_checkpoints: vec![],
         }
     }
 }
@@ -166,6 +176,30 @@ impl ClientConfiguration {
         self.peer_count_per_subnet_max = peer_count_per_subnet_max;
     }
 
+    /// Sets the public keys of the issuers whose sync checkpoints are trusted, in hex. The client
+    /// starts syncing from the newest sync checkpoint signed by one of these keys.
+    /// Default is `[]`.
+    #[wasm_bindgen(js_name = syncCheckpointKeys)]
+    #[allow(clippy::boxed_local)]
+    pub fn sync_checkpoint_keys(&mut self, keys: Box<[JsValue]>) {
+        self.sync_checkpoint_keys = keys
+            .iter()
+            .map(|key| serde_wasm_bindgen::from_value(key.clone()).unwrap())
+            .collect::<Vec<String>>();
+    }
+
+    /// Sets sync checkpoints in addition to the ones embedded for the network. Each array entry
+    /// is the hex encoded content of a checkpoints file created with `nimiq-sync-checkpoint`.
+    /// Default is `[]`.
+    #[wasm_bindgen(js_name = syncCheckpoints)]
+    #[allow(clippy::boxed_local)]
+    pub fn sync_checkpoints(&mut self, checkpoints: Box<[JsValue]>) {
+        self.sync_checkpoints = checkpoints
+            .iter()
+            .map(|checkpoint| serde_wasm_bindgen::from_value(checkpoint.clone()).unwrap())
+            .collect::<Vec<String>>();
+    }
+
     // TODO: Find a way to make this method work, maybe by using the synthetic Client from the main thread as an import?
     // /// Instantiates a client from this configuration builder.
     // #[wasm_bindgen(js_name = instantiateClient)]
@@ -187,6 +221,8 @@ impl ClientConfiguration {
             peer_count_max: Some(self.peer_count_max),
             peer_count_per_ip_max: Some(self.peer_count_per_ip_max),
             peer_count_per_subnet_max: Some(self.peer_count_per_subnet_max),
+            sync_checkpoint_keys: Some(self.sync_checkpoint_keys.clone()),
+            sync_checkpoints: Some(self.sync_checkpoints.clone()),
         })
         .unwrap()
         .into()
@@ -233,6 +269,14 @@ impl TryFrom<PlainClientConfiguration> for ClientConfiguration {
             client_config.peer_count_per_subnet_max = peer_count_per_subnet_max;
         }
 
+        if let Some(sync_checkpoint_keys) = config.sync_checkpoint_keys {
+            client_config.sync_checkpoint_keys = sync_checkpoint_keys;
+        }
+
+        if let Some(sync_checkpoints) = config.sync_checkpoints {
+            client_config.sync_checkpoints = sync_checkpoints;
+        }
+
         Ok(client_config)
     }
 }
@@ -61,6 +61,7 @@ impl HashedTimeLockedContract {
                 AnyHash::Blake2b(_) => "blake2b".to_string(),
                 AnyHash::Sha256(_) => "sha256".to_string(),
                 AnyHash::Sha512(_) => "sha512".to_string(),
+                AnyHash::Keccak256(_) => "keccak256".to_string(),
             },
             hash_root: data.hash_root.to_hex(),
             hash_count: data.hash_count,
@@ -87,6 +88,7 @@ impl HashedTimeLockedContract {
                     AnyHash::Blake2b(_) => "blake2b".to_string(),
                     AnyHash::Sha256(_) => "sha256".to_string(),
                     AnyHash::Sha512(_) => "sha512".to_string(),
+                    AnyHash::Keccak256(_) => "keccak256".to_string(),
                 },
                 hash_depth,
                 hash_root: hash_root.to_hex(),
@@ -2,6 +2,8 @@ use std::str;
 
 #[cfg(feature = "primitives")]
 use js_sys::Array;
+#[cfg(feature = "primitives")]
+use nimiq_hash::HashOutput;
 use nimiq_serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
@@ -150,6 +152,16 @@ impl SignatureProof {
         self.inner.verify(data)
     }
 
+    /// Verifies the signature proof against a message signed with the Nimiq signed-message scheme,
+    /// e.g. by `KeyPair.signMessage`.
+    ///
+    /// To authenticate ownership of an address, additionally check the proof with `isSignedBy`.
+    #[wasm_bindgen(js_name = verifyMessage)]
+    pub fn verify_message(&self, message: &[u8]) -> bool {
+        let hash = nimiq_wallet::WalletAccount::prepare_message_for_signature(message);
+        self.inner.verify(hash.as_bytes())
+    }
+
     /// Checks if the signature proof is signed by the provided address.
     #[wasm_bindgen(js_name = isSignedBy)]
     pub fn is_signed_by(&self, sender: &Address) -> bool {
@@ -8,7 +8,8 @@ use crate::common::{
     signature_proof::SignatureProof,
     transaction::{
         PlainAddStakeData, PlainCreateStakerData, PlainCreateValidatorData, PlainRawData,
-        PlainRetireStakeData, PlainSetActiveStakeData, PlainTransactionProof,
+        PlainRetireStakeData, PlainScheduleUnstakeData, PlainSetActiveStakeData,
+        PlainSetRewardCompoundingData, PlainSetValidatorCommissionData, PlainTransactionProof,
         PlainTransactionRecipientData, PlainUpdateStakerData, PlainUpdateValidatorData,
         PlainValidatorData,
     },
@@ -113,6 +114,15 @@ impl StakingContract {
                     raw: hex::encode(bytes),
                 })
             }
+            IncomingStakingTransactionData::SetValidatorCommission {
+                new_commission,
+                proof: _proof,
+            } => PlainTransactionRecipientData::SetValidatorCommission(
+                PlainSetValidatorCommissionData {
+                    raw: hex::encode(bytes),
+                    new_commission,
+                },
+            ),
             IncomingStakingTransactionData::SetActiveStake {
                 new_active_balance,
                 proof: _proof,
@@ -127,6 +137,27 @@ impl StakingContract {
                 raw: hex::encode(bytes),
                 retire_stake: retire_stake.into(),
             }),
+            IncomingStakingTransactionData::SetRewardCompounding {
+                compound_rewards,
+                proof: _proof,
+            } => {
+                PlainTransactionRecipientData::SetRewardCompounding(PlainSetRewardCompoundingData {
+                    raw: hex::encode(bytes),
+                    compound_rewards,
+                })
+            }
+            IncomingStakingTransactionData::ScheduleUnstake {
+                value,
+                proof: _proof,
+            } => PlainTransactionRecipientData::ScheduleUnstake(PlainScheduleUnstakeData {
+                raw: hex::encode(bytes),
+                value: value.into(),
+            }),
+            IncomingStakingTransactionData::ClaimWithdrawals { proof: _proof } => {
+                PlainTransactionRecipientData::ClaimWithdrawals(PlainRawData {
+                    raw: hex::encode(bytes),
+                })
+            }
         })
     }
 
@@ -3,10 +3,15 @@ use std::str::FromStr;
 use nimiq_hash::{Blake2bHash, Hash};
 #[cfg(feature = "client")]
 use nimiq_primitives::policy::Policy;
-use nimiq_primitives::{account::AccountType, coin::Coin, networks::NetworkId};
+use nimiq_primitives::{
+    account::AccountType, coin::Coin, networks::NetworkId, transaction::TransactionError,
+};
 use nimiq_serde::{Deserialize, Serialize};
+#[cfg(feature = "primitives")]
+use nimiq_transaction::account::htlc_contract::{AnyHash, AnyHash32, AnyHash64, PreImage};
 use nimiq_transaction::{
-    account::staking_contract::OutgoingStakingTransactionData, PoWSignatureProof, TransactionFormat,
+    account::{staking_contract::OutgoingStakingTransactionData, AccountTransactionVerification},
+    PoWSignatureProof, TransactionFormat,
 };
 #[cfg(feature = "client")]
 use nimiq_transaction::{
@@ -14,7 +19,7 @@ use nimiq_transaction::{
     ExecutedTransaction,
 };
 #[cfg(feature = "primitives")]
-use nimiq_transaction_builder::TransactionProofBuilder;
+use nimiq_transaction_builder::{proof::htlc_contract::HtlcProofBuilder, TransactionProofBuilder};
 #[cfg(feature = "client")]
 use serde::ser::SerializeStruct;
 use tsify::Tsify;
@@ -141,7 +146,8 @@ impl Transaction {
     /// of the signature proof required for the transaction.
     ///
     /// ### Limitations
-    /// - HTLC redemption is not supported and will throw.
+    /// - HTLC redemption is not supported and will throw. Use `signHtlcRegularTransfer`,
+    ///   `signHtlcTimeoutResolve` or `signHtlcEarlyResolve` instead.
     /// - For transaction to the staking contract, both signatures are made with the same keypair,
     ///   so it is not possible to interact with a staker that is different from the sender address
     ///   or using a different cold or signing key for validator transactions.
@@ -157,28 +163,10 @@ impl Transaction {
                 builder.sign_with_key_pair(key_pair.native_ref());
                 builder.generate().unwrap().proof
             }
-            TransactionProofBuilder::Htlc(mut _builder) => {
-                // TODO: Create a separate HTLC signing method that takes the type of proof as an argument
+            TransactionProofBuilder::Htlc(_) => {
                 return Err(JsError::new(
-                    "HTLC redemption transactions are not supported",
+                    "HTLC redemption transactions must be signed with signHtlcRegularTransfer, signHtlcTimeoutResolve or signHtlcEarlyResolve",
                 ));
-
-                // // Redeem
-                // let sig = builder.signature_with_key_pair(key_pair);
-                // builder.regular_transfer(hash_algorithm, pre_image, hash_count, hash_root, sig);
-
-                // // Refund
-                // let sig = builder.signature_with_key_pair(key_pair);
-                // builder.timeout_resolve(sig);
-
-                // // Early resolve
-                // builder.early_resolve(htlc_sender_signature, htlc_recipient_signature);
-
-                // // Sign early
-                // let sig = builder.signature_with_key_pair(key_pair);
-                // return Ok(sig);
-
-                // builder.generate().unwrap()
             }
             TransactionProofBuilder::OutStaking(mut builder) => {
                 builder.sign_with_key_pair(key_pair.native_ref());
@@ -206,6 +194,90 @@ impl Transaction {
         Ok(())
     }
 
+    /// Signs an HTLC redemption by the HTLC recipient before the timeout, revealing a pre-image
+    /// that hashes to the contract's hash root.
+    ///
+    /// `hash_algorithm` is one of `blake2b`, `sha256`, `sha512` or `keccak256`. `pre_image` and
+    /// `hash_root` are hex-encoded. `hash_depth` is the number of times the pre-image must be
+    /// hashed to yield the hash root, which determines the share of the funds that can be redeemed.
+    ///
+    /// Throws when the transaction is not an HTLC redemption, the hash algorithm is unknown
+    /// or the hashes are not valid hex strings of the algorithm's length.
+    #[cfg(feature = "primitives")]
+    #[wasm_bindgen(js_name = signHtlcRegularTransfer)]
+    pub fn sign_htlc_regular_transfer(
+        &mut self,
+        key_pair: &KeyPair,
+        hash_algorithm: &str,
+        pre_image: &str,
+        hash_root: &str,
+        hash_depth: u8,
+    ) -> Result<(), JsError> {
+        let hash_root = Transaction::parse_htlc_hash(hash_algorithm, hash_root)?;
+        let pre_image = PreImage::from_str(pre_image)?;
+        if pre_image.as_bytes().len() != hash_root.as_bytes().len() {
+            return Err(JsError::new(
+                "Pre-image length does not match the hash algorithm",
+            ));
+        }
+
+        let mut builder = self.htlc_proof_builder()?;
+        let signature = builder.signature_with_key_pair(key_pair.native_ref());
+        builder.regular_transfer(pre_image, hash_depth, hash_root, signature);
+        self.set_proof(builder.generate().unwrap().proof);
+
+        Ok(())
+    }
+
+    /// Signs an HTLC redemption by the HTLC sender after the timeout has passed.
+    ///
+    /// Throws when the transaction is not an HTLC redemption.
+    #[cfg(feature = "primitives")]
+    #[wasm_bindgen(js_name = signHtlcTimeoutResolve)]
+    pub fn sign_htlc_timeout_resolve(&mut self, key_pair: &KeyPair) -> Result<(), JsError> {
+        let mut builder = self.htlc_proof_builder()?;
+        let signature = builder.signature_with_key_pair(key_pair.native_ref());
+        builder.timeout_resolve(signature);
+        self.set_proof(builder.generate().unwrap().proof);
+
+        Ok(())
+    }
+
+    /// Creates the signature proof of one party of an HTLC for an early resolve, which
+    /// requires the signatures of both the HTLC sender and recipient. The proof can be sent
+    /// to the other party, which combines both proofs with `signHtlcEarlyResolve`.
+    ///
+    /// Throws when the transaction is not an HTLC redemption.
+    #[cfg(feature = "primitives")]
+    #[wasm_bindgen(js_name = signHtlcEarly)]
+    pub fn sign_htlc_early(&self, key_pair: &KeyPair) -> Result<SignatureProof, JsError> {
+        let builder = self.htlc_proof_builder()?;
+        Ok(SignatureProof::from(
+            builder.signature_with_key_pair(key_pair.native_ref()),
+        ))
+    }
+
+    /// Sets the proof of an HTLC redemption that is signed by both the HTLC sender and
+    /// recipient, which allows to redeem the funds at any time.
+    ///
+    /// Throws when the transaction is not an HTLC redemption.
+    #[cfg(feature = "primitives")]
+    #[wasm_bindgen(js_name = signHtlcEarlyResolve)]
+    pub fn sign_htlc_early_resolve(
+        &mut self,
+        htlc_sender_signature: &SignatureProof,
+        htlc_recipient_signature: &SignatureProof,
+    ) -> Result<(), JsError> {
+        let mut builder = self.htlc_proof_builder()?;
+        builder.early_resolve(
+            htlc_sender_signature.native_ref().clone(),
+            htlc_recipient_signature.native_ref().clone(),
+        );
+        self.set_proof(builder.generate().unwrap().proof);
+
+        Ok(())
+    }
+
     /// Computes the transaction's hash, which is used as its unique identifier on the blockchain.
     pub fn hash(&self) -> String {
         let hash: Blake2bHash = self.inner.hash();
@@ -228,6 +300,39 @@ impl Transaction {
         self.inner.verify(network_id).map_err(JsError::from)
     }
 
+    /// Verifies the transaction like `verify`, but instead of throwing on the first failed
+    /// check, runs all checks and returns a {@link PlainTransactionValidity} object describing
+    /// the result of each check, to be able to show actionable error messages.
+    ///
+    /// Optionally checks if the transaction is valid on the provided network.
+    ///
+    /// Throws when the given networkId is unknown.
+    #[wasm_bindgen(js_name = verifyDetailed)]
+    pub fn verify_detailed(
+        &self,
+        network_id: Option<u8>,
+    ) -> Result<PlainTransactionValidityType, JsError> {
+        let network_id = match network_id {
+            Some(id) => to_network_id(id)?,
+            None => self.inner.network_id,
+        };
+
+        let network = if self.inner.network_id == network_id {
+            Ok(())
+        } else {
+            Err(TransactionError::ForeignNetwork)
+        };
+
+        let validity = PlainTransactionValidity::new(
+            network,
+            self.inner.verify_fields(),
+            AccountType::verify_outgoing_transaction(&self.inner),
+            AccountType::verify_incoming_transaction(&self.inner),
+        );
+
+        Ok(serde_wasm_bindgen::to_value(&validity)?.into())
+    }
+
     /// Tests if the transaction is valid at the specified block height.
     #[wasm_bindgen(js_name = isValidAt)]
     pub fn is_valid_at(&self, block_height: u32) -> bool {
@@ -438,6 +543,25 @@ impl Transaction {
         self.inner
     }
 
+    #[cfg(feature = "primitives")]
+    fn htlc_proof_builder(&self) -> Result<HtlcProofBuilder, JsError> {
+        match TransactionProofBuilder::new(self.native_ref().clone()) {
+            TransactionProofBuilder::Htlc(builder) => Ok(builder),
+            _ => Err(JsError::new("Transaction is not an HTLC redemption")),
+        }
+    }
+
+    #[cfg(feature = "primitives")]
+    fn parse_htlc_hash(hash_algorithm: &str, hash: &str) -> Result<AnyHash, JsError> {
+        Ok(match hash_algorithm {
+            "blake2b" => AnyHash::Blake2b(AnyHash32::from_str(hash)?),
+            "sha256" => AnyHash::Sha256(AnyHash32::from_str(hash)?),
+            "sha512" => AnyHash::Sha512(AnyHash64::from_str(hash)?),
+            "keccak256" => AnyHash::Keccak256(AnyHash32::from_str(hash)?),
+            _ => return Err(JsError::new("Unknown hash algorithm")),
+        })
+    }
+
     pub fn to_plain_transaction(
         &self,
         genesis_block_number: Option<u32>,
@@ -530,7 +654,8 @@ impl Transaction {
                 }
             },
             size: self.serialized_size(),
-            valid: self.verify(None).is_ok(),
+            // Verify the inner transaction directly, such that this also works outside of wasm.
+            valid: self.inner.verify(self.inner.network_id).is_ok(),
         }
     }
 
@@ -554,11 +679,15 @@ impl Transaction {
                 PlainTransactionRecipientData::DeactivateValidator(ref data) => &data.raw,
                 PlainTransactionRecipientData::ReactivateValidator(ref data) => &data.raw,
                 PlainTransactionRecipientData::RetireValidator(ref data) => &data.raw,
+                PlainTransactionRecipientData::SetValidatorCommission(ref data) => &data.raw,
                 PlainTransactionRecipientData::CreateStaker(ref data) => &data.raw,
                 PlainTransactionRecipientData::AddStake(ref data) => &data.raw,
                 PlainTransactionRecipientData::UpdateStaker(ref data) => &data.raw,
                 PlainTransactionRecipientData::SetActiveStake(ref data) => &data.raw,
                 PlainTransactionRecipientData::RetireStake(ref data) => &data.raw,
+                PlainTransactionRecipientData::SetRewardCompounding(ref data) => &data.raw,
+                PlainTransactionRecipientData::ScheduleUnstake(ref data) => &data.raw,
+                PlainTransactionRecipientData::ClaimWithdrawals(ref data) => &data.raw,
             })?),
             plain.value,
             plain.fee,
@@ -629,11 +758,15 @@ pub enum PlainTransactionRecipientData {
     DeactivateValidator(PlainValidatorData),
     ReactivateValidator(PlainValidatorData),
     RetireValidator(PlainRawData),
+    SetValidatorCommission(PlainSetValidatorCommissionData),
     CreateStaker(PlainCreateStakerData),
     AddStake(PlainAddStakeData),
     UpdateStaker(PlainUpdateStakerData),
     SetActiveStake(PlainSetActiveStakeData),
     RetireStake(PlainRetireStakeData),
+    SetRewardCompounding(PlainSetRewardCompoundingData),
+    ScheduleUnstake(PlainScheduleUnstakeData),
+    ClaimWithdrawals(PlainRawData),
 }
 
 impl<'a> serde::Deserialize<'a> for PlainTransactionRecipientData {
@@ -750,6 +883,30 @@ pub struct PlainRetireStakeData {
     pub retire_stake: u64,
 }
 
+/// JSON-compatible and human-readable format of set validator commission data.
+#[derive(Clone, serde::Serialize, serde::Deserialize, Tsify)]
+#[serde(rename_all = "camelCase")]
+pub struct PlainSetValidatorCommissionData {
+    pub raw: String,
+    pub new_commission: u16,
+}
+
+/// JSON-compatible and human-readable format of set reward compounding data.
+#[derive(Clone, serde::Serialize, serde::Deserialize, Tsify)]
+#[serde(rename_all = "camelCase")]
+pub struct PlainSetRewardCompoundingData {
+    pub raw: String,
+    pub compound_rewards: bool,
+}
+
+/// JSON-compatible and human-readable format of schedule unstake data.
+#[derive(Clone, serde::Serialize, serde::Deserialize, Tsify)]
+#[serde(rename_all = "camelCase")]
+pub struct PlainScheduleUnstakeData {
+    pub raw: String,
+    pub value: u64,
+}
+
 /// Enum over all possible meanings of a transaction's proof.
 #[derive(Clone, serde::Serialize, Tsify)]
 #[serde(tag = "type", rename_all = "kebab-case")]
@@ -1088,6 +1245,91 @@ impl PlainTransactionDetails {
     }
 }
 
+/// The result of the individual checks of a transaction's validity.
+#[derive(serde::Serialize, Tsify)]
+#[serde(rename_all = "camelCase")]
+pub struct PlainTransactionValidity {
+    /// Whether all checks passed.
+    pub valid: bool,
+    /// Whether the transaction belongs to the network it was verified for.
+    pub network: bool,
+    /// Whether the value and fee are valid and sender and recipient differ.
+    pub fields: bool,
+    /// Whether the proof, e.g. the signature, and the sender data are valid for the sender
+    /// account type.
+    pub signature: bool,
+    /// Whether the recipient data has the correct format for the recipient account type.
+    pub data: bool,
+    /// The errors of all failed checks.
+    pub errors: Vec<PlainTransactionValidityError>,
+}
+
+/// A failed check of a transaction's validity.
+#[derive(serde::Serialize, Tsify)]
+#[serde(rename_all = "camelCase")]
+pub struct PlainTransactionValidityError {
+    /// The check that failed: `network`, `fields`, `signature` or `data`.
+    pub check: String,
+    /// A machine-readable error code, e.g. `zero-value` or `invalid-proof`.
+    pub code: String,
+    /// A human-readable error message.
+    pub message: String,
+}
+
+impl PlainTransactionValidity {
+    fn new(
+        network: Result<(), TransactionError>,
+        fields: Result<(), TransactionError>,
+        signature: Result<(), TransactionError>,
+        data: Result<(), TransactionError>,
+    ) -> Self {
+        let checks = [
+            ("network", network),
+            ("fields", fields),
+            ("signature", signature),
+            ("data", data),
+        ];
+
+        let errors: Vec<_> = checks
+            .iter()
+            .filter_map(|(check, result)| {
+                result
+                    .as_ref()
+                    .err()
+                    .map(|error| PlainTransactionValidityError {
+                        check: check.to_string(),
+                        code: PlainTransactionValidity::error_code(error).to_string(),
+                        message: error.to_string(),
+                    })
+            })
+            .collect();
+
+        PlainTransactionValidity {
+            valid: errors.is_empty(),
+            network: checks[0].1.is_ok(),
+            fields: checks[1].1.is_ok(),
+            signature: checks[2].1.is_ok(),
+            data: checks[3].1.is_ok(),
+            errors,
+        }
+    }
+
+    fn error_code(error: &TransactionError) -> &'static str {
+        match error {
+            TransactionError::ForeignNetwork => "foreign-network",
+            TransactionError::ZeroValue => "zero-value",
+            TransactionError::InvalidValue => "invalid-value",
+            TransactionError::Overflow => "overflow",
+            TransactionError::SenderEqualsRecipient => "sender-equals-recipient",
+            TransactionError::InvalidForSender => "invalid-for-sender",
+            TransactionError::InvalidProof => "invalid-proof",
+            TransactionError::InvalidForRecipient => "invalid-for-recipient",
+            TransactionError::InvalidData => "invalid-data",
+            TransactionError::InvalidSerialization(_) => "invalid-serialization",
+        }
+    }
+}
+
 /// JSON-compatible and human-readable format of transaction receipts.
 #[cfg(feature = "client")]
 #[derive(serde::Serialize, serde::Deserialize, Tsify)]
@@ -1129,6 +1371,9 @@ extern "C" {
 
     #[wasm_bindgen(typescript_type = "PlainTransactionProof")]
     pub type PlainTransactionProofType;
+
+    #[wasm_bindgen(typescript_type = "PlainTransactionValidity")]
+    pub type PlainTransactionValidityType;
 }
 
 #[cfg(feature = "primitives")]
@@ -19,7 +19,8 @@ mod tests {
     use crate::{
         common::address::Address,
         primitives::{
-            bls_key_pair::BLSKeyPair, key_pair::KeyPair, transaction_builder::TransactionBuilder,
+            bls_key_pair::BLSKeyPair, key_derivation::KeyDerivation, key_pair::KeyPair,
+            transaction_builder::TransactionBuilder,
         },
     };
 
@@ -66,4 +67,46 @@ mod tests {
         tx.sign(&keypair).map_err(JsValue::from).unwrap();
         assert_eq!(tx.verify(None).map_err(JsValue::from), Ok(()))
     }
+
+    #[wasm_bindgen_test]
+    pub fn it_can_derive_keys_from_mnemonics() {
+        let entropy = KeyDerivation::generate_entropy();
+        let mnemonic = KeyDerivation::entropy_to_mnemonic(&entropy)
+            .map_err(JsValue::from)
+            .unwrap();
+        assert_eq!(mnemonic.len(), 24);
+        assert_eq!(
+            KeyDerivation::mnemonic_to_entropy(mnemonic.clone())
+                .map_err(JsValue::from)
+                .unwrap(),
+            entropy
+        );
+
+        let seed = KeyDerivation::mnemonic_to_seed(mnemonic, None)
+            .map_err(JsValue::from)
+            .unwrap();
+        let path = KeyDerivation::default_path();
+        let private_key = KeyDerivation::derive_private_key(&seed, &path)
+            .map_err(JsValue::from)
+            .unwrap();
+        let address = KeyDerivation::derive_address(&seed, &path)
+            .map_err(JsValue::from)
+            .unwrap();
+        assert!(KeyPair::derive(&private_key).to_address().equals(&address));
+
+        assert!(KeyDerivation::derive_address(&seed, "m/44/242").is_err());
+    }
+
+    #[wasm_bindgen_test]
+    pub fn it_can_sign_and_verify_messages() {
+        let keypair = KeyPair::generate();
+        let message = b"Nimiq rocks!";
+
+        let proof = keypair.sign_message(message);
+        assert!(proof.verify_message(message));
+        assert!(proof.is_signed_by(&keypair.to_address()));
+        assert!(!proof.verify_message(b"Nimiq rolls!"));
+        // The signature must not be valid for the raw message.
+        assert!(!proof.verify(message));
+    }
 }
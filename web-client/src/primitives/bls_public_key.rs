@@ -48,6 +48,12 @@ impl BLSPublicKey {
         let vec = BLSPublicKey::serialize(self);
         hex::encode(vec)
     }
+
+    /// Returns the fingerprint of the public key as a hex string. Since public keys are long,
+    /// the fingerprint can be displayed instead to identify a key.
+    pub fn fingerprint(&self) -> String {
+        self.inner.compress().fingerprint().to_hex()
+    }
 }
 
 impl From<nimiq_bls::PublicKey> for BLSPublicKey {
@@ -0,0 +1,113 @@
+use nimiq_key_derivation::ExtendedPrivateKey;
+use nimiq_keys::SecureGenerate;
+use nimiq_mnemonic::{Entropy, Mnemonic, MnemonicType, WORDLIST_EN};
+use wasm_bindgen::prelude::*;
+
+use crate::{common::address::Address, primitives::private_key::PrivateKey};
+
+/// Helpers to create and restore BIP39 mnemonics and to derive keys from them, compatible
+/// with the account derivation of existing Nimiq wallets.
+#[wasm_bindgen]
+pub struct KeyDerivation;
+
+#[wasm_bindgen]
+impl KeyDerivation {
+    /// The derivation path of the first account in Nimiq wallets. Further accounts are derived
+    /// by incrementing the last segment, e.g. `m/44'/242'/0'/1'`.
+    #[wasm_bindgen(getter = DEFAULT_PATH)]
+    pub fn default_path() -> String {
+        "m/44'/242'/0'/0'".to_string()
+    }
+
+    /// Generates 32 bytes of entropy from secure randomness.
+    #[wasm_bindgen(js_name = generateEntropy)]
+    pub fn generate_entropy() -> Vec<u8> {
+        Entropy::generate_default_csprng().0.to_vec()
+    }
+
+    /// Converts 32 bytes of entropy into a 24-word BIP39 mnemonic.
+    ///
+    /// Throws when the entropy is not exactly 32 bytes long.
+    #[wasm_bindgen(js_name = entropyToMnemonic)]
+    pub fn entropy_to_mnemonic(entropy: &[u8]) -> Result<Vec<String>, JsError> {
+        Ok(KeyDerivation::parse_entropy(entropy)?
+            .to_mnemonic(WORDLIST_EN)
+            .as_words())
+    }
+
+    /// Converts a 24-word BIP39 mnemonic back into its entropy.
+    ///
+    /// Throws when the mnemonic contains unknown words or has an invalid checksum.
+    #[wasm_bindgen(js_name = mnemonicToEntropy)]
+    pub fn mnemonic_to_entropy(mnemonic: Vec<String>) -> Result<Vec<u8>, JsError> {
+        let entropy = Mnemonic::from_words_unchecked(mnemonic)
+            .to_entropy(WORDLIST_EN)
+            .ok_or_else(|| JsError::new("Invalid mnemonic"))?;
+        Ok(entropy.0.to_vec())
+    }
+
+    /// Checks whether the mnemonic is a valid BIP39 mnemonic. Mnemonics that are also valid
+    /// legacy Nimiq mnemonics are ambiguous and not considered valid.
+    #[wasm_bindgen(js_name = isValidMnemonic)]
+    pub fn is_valid_mnemonic(mnemonic: Vec<String>) -> bool {
+        Mnemonic::from_words_unchecked(mnemonic).get_type(WORDLIST_EN) == MnemonicType::BIP39
+    }
+
+    /// Computes the 64-byte BIP39 seed of a mnemonic, optionally protected by a password.
+    ///
+    /// Throws when the mnemonic contains unknown words or has an invalid checksum.
+    #[wasm_bindgen(js_name = mnemonicToSeed)]
+    pub fn mnemonic_to_seed(
+        mnemonic: Vec<String>,
+        password: Option<String>,
+    ) -> Result<Vec<u8>, JsError> {
+        let mnemonic = Mnemonic::from_words_unchecked(mnemonic);
+        if mnemonic.to_entropy(WORDLIST_EN).is_none() {
+            return Err(JsError::new("Invalid mnemonic"));
+        }
+
+        mnemonic
+            .to_seed(password.as_deref())
+            .map_err(|_| JsError::new("Failed to compute seed"))
+    }
+
+    /// Checks whether a string is a valid derivation path. Only hardened derivation
+    /// is supported, so every segment must end with `'`.
+    #[wasm_bindgen(js_name = isValidPath)]
+    pub fn is_valid_path(path: &str) -> bool {
+        ExtendedPrivateKey::is_valid_path(path)
+    }
+
+    /// Derives the Ed25519 private key at the given path from a BIP39 seed.
+    ///
+    /// Throws when the path is invalid.
+    #[wasm_bindgen(js_name = derivePrivateKey)]
+    pub fn derive_private_key(seed: &[u8], path: &str) -> Result<PrivateKey, JsError> {
+        let key = KeyDerivation::derive(seed, path)?;
+        Ok(PrivateKey::from(key.into_private_key()))
+    }
+
+    /// Derives the address at the given path from a BIP39 seed.
+    ///
+    /// Throws when the path is invalid.
+    #[wasm_bindgen(js_name = deriveAddress)]
+    pub fn derive_address(seed: &[u8], path: &str) -> Result<Address, JsError> {
+        let key = KeyDerivation::derive(seed, path)?;
+        Ok(Address::from(key.to_address()))
+    }
+}
+
+impl KeyDerivation {
+    fn parse_entropy(entropy: &[u8]) -> Result<Entropy, JsError> {
+        if entropy.len() != Entropy::SIZE {
+            return Err(JsError::new("Entropy must be 32 bytes long"));
+        }
+        Ok(Entropy::from(entropy))
+    }
+
+    fn derive(seed: &[u8], path: &str) -> Result<ExtendedPrivateKey, JsError> {
+        ExtendedPrivateKey::from_seed(seed.to_vec())
+            .derive_path(path)
+            .ok_or_else(|| JsError::new("Invalid derivation path"))
+    }
+}
@@ -5,7 +5,7 @@ use nimiq_serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
 use crate::{
-    common::{address::Address, transaction::Transaction},
+    common::{address::Address, signature_proof::SignatureProof, transaction::Transaction},
     primitives::{private_key::PrivateKey, public_key::PublicKey, signature::Signature},
 };
 
@@ -72,6 +72,20 @@ impl KeyPair {
         Signature::from(self.inner.sign(data))
     }
 
+    /// Signs an arbitrary message using the Nimiq signed-message scheme, returns a signature proof.
+    ///
+    /// The message is prefixed with `"\x16Nimiq Signed Message:\n"` and its length before being hashed
+    /// with SHA-256 and signed, so the resulting signature cannot be misused as a transaction signature.
+    /// The result can be verified with `SignatureProof.verifyMessage`.
+    #[wasm_bindgen(js_name = signMessage)]
+    pub fn sign_message(&self, message: &[u8]) -> SignatureProof {
+        let (public_key, signature) =
+            nimiq_wallet::WalletAccount::from(self.inner.clone()).sign_message(message);
+        SignatureProof::from(nimiq_transaction::SignatureProof::from_ed25519(
+            public_key, signature,
+        ))
+    }
+
     /// Signs a transaction and sets the signature proof on the transaction object.
     #[wasm_bindgen(js_name = signTransaction)]
     pub fn sign_transaction(&self, transaction: &mut Transaction) -> Result<(), JsError> {
@@ -4,9 +4,11 @@ pub mod bls_secret_key;
 pub mod es256_public_key;
 pub mod es256_signature;
 pub mod hash;
+pub mod key_derivation;
 pub mod key_pair;
 pub mod merkle_tree;
 pub mod private_key;
 pub mod public_key;
 pub mod signature;
+pub mod slot_allocation;
 pub mod transaction_builder;
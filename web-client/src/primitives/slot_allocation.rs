@@ -0,0 +1,78 @@
+use std::ops::Range;
+
+use nimiq_primitives::slots_allocation::{band_of_slot, slot_ranges};
+use wasm_bindgen::prelude::*;
+
+/// The allocation of slots to validators. Each validator owns a consecutive range of slots, its
+/// slot band, and the bands are ordered by their index.
+#[wasm_bindgen]
+pub struct SlotAllocation {
+    ranges: Vec<Range<u16>>,
+}
+
+#[wasm_bindgen]
+impl SlotAllocation {
+    /// Creates the slot allocation from the number of slots of each validator, in slot band order.
+    /// Throws if the total number of slots exceeds 65535.
+    #[wasm_bindgen(constructor)]
+    pub fn new(num_slots: Vec<u16>) -> Result<SlotAllocation, JsError> {
+        let ranges =
+            slot_ranges(num_slots).ok_or_else(|| JsError::new("Too many slots in total"))?;
+        Ok(SlotAllocation { ranges })
+    }
+
+    /// The number of slot bands, i.e. validators.
+    #[wasm_bindgen(getter, js_name = numBands)]
+    pub fn num_bands(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// The total number of slots.
+    #[wasm_bindgen(getter, js_name = numSlots)]
+    pub fn num_slots(&self) -> u16 {
+        self.ranges.last().map(|range| range.end).unwrap_or(0)
+    }
+
+    /// Returns the slot band, i.e. the index of the validator, that owns the given slot.
+    /// Returns `undefined` if the slot is out of bounds.
+    #[wasm_bindgen(js_name = bandOfSlot)]
+    pub fn band_of_slot(&self, slot: u16) -> Option<u16> {
+        band_of_slot(&self.ranges, slot)
+    }
+
+    /// Returns the slots owned by the given slot band as `[start, end]`, with the first slot being
+    /// inclusive and the last one exclusive. Returns `undefined` if the band does not exist.
+    #[wasm_bindgen(js_name = slotRange)]
+    pub fn slot_range(&self, band: u16) -> Option<Vec<u16>> {
+        self.ranges
+            .get(band as usize)
+            .map(|range| vec![range.start, range.end])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wasm_bindgen::JsValue;
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use crate::primitives::slot_allocation::SlotAllocation;
+
+    #[wasm_bindgen_test]
+    pub fn it_maps_slots_to_bands() {
+        let allocation = SlotAllocation::new(vec![3, 1, 4])
+            .map_err(JsValue::from)
+            .unwrap();
+
+        assert_eq!(allocation.num_bands(), 3);
+        assert_eq!(allocation.num_slots(), 8);
+        assert_eq!(allocation.band_of_slot(3), Some(1));
+        assert_eq!(allocation.band_of_slot(8), None);
+        assert_eq!(allocation.slot_range(2), Some(vec![4, 8]));
+        assert_eq!(allocation.slot_range(3), None);
+    }
+
+    #[wasm_bindgen_test]
+    pub fn it_rejects_too_many_slots() {
+        assert!(SlotAllocation::new(vec![u16::MAX, 1]).is_err());
+    }
+}
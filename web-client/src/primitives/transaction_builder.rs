@@ -254,6 +254,100 @@ impl TransactionBuilder {
         Ok(Transaction::from(tx))
     }
 
+    /// Sets whether the staker's share of its validator's rewards is credited to its active stake
+    /// balance instead of its retired balance, from where it can be removed from the staking
+    /// contract. This is a signaling transaction and as such does not transfer any value.
+    ///
+    /// The returned transaction is not yet signed. You can sign it e.g. with `tx.sign(keyPair)`.
+    ///
+    /// Throws when the number given for fee does not fit within a u64 or the networkId is unknown.
+    #[wasm_bindgen(js_name = newSetRewardCompounding)]
+    pub fn new_set_reward_compounding(
+        sender: &Address,
+        compound_rewards: bool,
+        fee: Option<u64>,
+        validity_start_height: u32,
+        network_id: u8,
+    ) -> Result<Transaction, JsError> {
+        let mut recipient = Recipient::new_staking_builder();
+        recipient.set_reward_compounding(compound_rewards);
+
+        let mut builder = nimiq_transaction_builder::TransactionBuilder::new();
+        builder
+            .with_sender(Sender::new_basic(sender.native_ref().clone()))
+            .with_recipient(recipient.generate().unwrap())
+            .with_value(Coin::ZERO)
+            .with_fee(Coin::try_from(fee.unwrap_or(0))?)
+            .with_validity_start_height(validity_start_height)
+            .with_network_id(to_network_id(network_id)?);
+
+        let proof_builder = builder.generate()?;
+        let tx = proof_builder.preliminary_transaction().to_owned();
+        Ok(Transaction::from(tx))
+    }
+
+    /// Schedules the withdrawal of `value` luna (NIM's smallest unit) of the staker's active stake.
+    /// The withdrawal can be claimed once the withdrawal delay has passed. This is a signaling
+    /// transaction and as such does not transfer any value.
+    ///
+    /// The returned transaction is not yet signed. You can sign it e.g. with `tx.sign(keyPair)`.
+    ///
+    /// Throws when the numbers given for value and fee do not fit within a u64 or the networkId is unknown.
+    #[wasm_bindgen(js_name = newScheduleUnstake)]
+    pub fn new_schedule_unstake(
+        sender: &Address,
+        value: u64,
+        fee: Option<u64>,
+        validity_start_height: u32,
+        network_id: u8,
+    ) -> Result<Transaction, JsError> {
+        let mut recipient = Recipient::new_staking_builder();
+        recipient.schedule_unstake(Coin::try_from(value)?);
+
+        let mut builder = nimiq_transaction_builder::TransactionBuilder::new();
+        builder
+            .with_sender(Sender::new_basic(sender.native_ref().clone()))
+            .with_recipient(recipient.generate().unwrap())
+            .with_value(Coin::ZERO)
+            .with_fee(Coin::try_from(fee.unwrap_or(0))?)
+            .with_validity_start_height(validity_start_height)
+            .with_network_id(to_network_id(network_id)?);
+
+        let proof_builder = builder.generate()?;
+        let tx = proof_builder.preliminary_transaction().to_owned();
+        Ok(Transaction::from(tx))
+    }
+
+    /// Claims all released scheduled withdrawals of the staker, moving them to its retired stake.
+    /// This is a signaling transaction and as such does not transfer any value.
+    ///
+    /// The returned transaction is not yet signed. You can sign it e.g. with `tx.sign(keyPair)`.
+    ///
+    /// Throws when the number given for fee does not fit within a u64 or the networkId is unknown.
+    #[wasm_bindgen(js_name = newClaimWithdrawals)]
+    pub fn new_claim_withdrawals(
+        sender: &Address,
+        fee: Option<u64>,
+        validity_start_height: u32,
+        network_id: u8,
+    ) -> Result<Transaction, JsError> {
+        let mut recipient = Recipient::new_staking_builder();
+        recipient.claim_withdrawals();
+
+        let mut builder = nimiq_transaction_builder::TransactionBuilder::new();
+        builder
+            .with_sender(Sender::new_basic(sender.native_ref().clone()))
+            .with_recipient(recipient.generate().unwrap())
+            .with_value(Coin::ZERO)
+            .with_fee(Coin::try_from(fee.unwrap_or(0))?)
+            .with_validity_start_height(validity_start_height)
+            .with_network_id(to_network_id(network_id)?);
+
+        let proof_builder = builder.generate()?;
+        let tx = proof_builder.preliminary_transaction().to_owned();
+        Ok(Transaction::from(tx))
+    }
+
     /// Removes stake from the staking contract and transfers `value` amount of luna (NIM's smallest unit)
     /// from the staker to the recipient.
     ///
@@ -463,4 +557,35 @@ impl TransactionBuilder {
         let tx = proof_builder.preliminary_transaction().to_owned();
         Ok(Transaction::from(tx))
     }
+
+    /// Sets the commission a validator keeps from its rewards, in basis points. The rest of the
+    /// rewards is shared among its stakers. Only valid from protocol version 2 on.
+    ///
+    /// The returned transaction is not yet signed. You can sign it e.g. with `tx.sign(keyPair)`.
+    ///
+    /// Throws when the fee does not fit within a u64 or the `networkId` is unknown.
+    #[wasm_bindgen(js_name = newSetValidatorCommission)]
+    pub fn new_set_validator_commission(
+        sender: &Address,
+        new_commission: u16,
+        fee: Option<u64>,
+        validity_start_height: u32,
+        network_id: u8,
+    ) -> Result<Transaction, JsError> {
+        let mut recipient = Recipient::new_staking_builder();
+        recipient.set_validator_commission(new_commission);
+
+        let mut builder = nimiq_transaction_builder::TransactionBuilder::new();
+        builder
+            .with_sender(Sender::new_basic(sender.native_ref().clone()))
+            .with_recipient(recipient.generate().unwrap())
+            .with_value(Coin::ZERO)
+            .with_fee(Coin::try_from(fee.unwrap_or(0))?)
+            .with_validity_start_height(validity_start_height)
+            .with_network_id(to_network_id(network_id)?);
+
+        let proof_builder = builder.generate()?;
+        let tx = proof_builder.preliminary_transaction().to_owned();
+        Ok(Transaction::from(tx))
+    }
 }
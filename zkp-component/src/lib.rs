@@ -5,6 +5,8 @@ pub mod proof_gen_utils;
 pub mod proof_store;
 pub mod proof_utils;
 #[cfg(feature = "zkp-prover")]
+pub mod prover_backend;
+#[cfg(feature = "zkp-prover")]
 pub mod prover_binary;
 pub mod types;
 pub mod zkp_component;
@@ -4,6 +4,7 @@ use nimiq_database::{
     mdbx::MdbxDatabase,
     traits::{Database, ReadTransaction, WriteTransaction},
 };
+use nimiq_hash::Blake2bHash;
 
 use crate::types::*;
 
@@ -16,9 +17,22 @@ pub trait ProofStore: Send {
     fn set_zkp(&self, zk_proof: &ZKProof);
 }
 
+/// Defines an interface for caching the ZK proofs we generated, keyed by the hash of the election
+/// block they prove. This allows a prover to skip proofs it already produced before a restart.
+pub trait ProofCache: Send + Sync {
+    /// Gets the cached ZK proof for the election block with the given hash.
+    fn get_proof(&self, block_hash: &Blake2bHash) -> Option<ZKProof>;
+
+    /// Caches the ZK proof for the election block with the given hash.
+    fn put_proof(&self, block_hash: &Blake2bHash, zk_proof: &ZKProof);
+}
+
 #[cfg(feature = "database-storage")]
 declare_table!(ZKProofTable, "ZKPState", () => ZKProof);
 
+#[cfg(feature = "database-storage")]
+declare_table!(ZKProofCacheTable, "ZKProofCache", Blake2bHash => ZKProof);
+
 #[cfg(feature = "database-storage")]
 /// DB implementation of a ProofStore meant for persistent storage
 #[derive(Debug)]
@@ -48,3 +62,35 @@ impl ProofStore for DBProofStore {
         tx.commit();
     }
 }
+
+#[cfg(feature = "database-storage")]
+/// DB implementation of a ProofCache meant for persistent storage
+#[derive(Debug)]
+pub struct DBProofCache {
+    /// Environment for the DB creation and transaction handling.
+    env: MdbxDatabase,
+}
+
+#[cfg(feature = "database-storage")]
+impl DBProofCache {
+    pub fn new(env: MdbxDatabase) -> Self {
+        env.create_regular_table(&ZKProofCacheTable);
+
+        Self { env }
+    }
+}
+
+#[cfg(feature = "database-storage")]
+impl ProofCache for DBProofCache {
+    fn get_proof(&self, block_hash: &Blake2bHash) -> Option<ZKProof> {
+        self.env
+            .read_transaction()
+            .get(&ZKProofCacheTable, block_hash)
+    }
+
+    fn put_proof(&self, block_hash: &Blake2bHash, zk_proof: &ZKProof) {
+        let mut tx = self.env.write_transaction();
+        tx.put(&ZKProofCacheTable, block_hash, zk_proof);
+        tx.commit();
+    }
+}
@@ -0,0 +1,41 @@
+use std::path::PathBuf;
+
+use futures::{future::BoxFuture, FutureExt};
+use tokio::sync::oneshot::Receiver;
+
+use crate::{proof_gen_utils::launch_generate_new_proof, types::*};
+
+/// Defines an interface for generating ZK proofs. This allows plugging in alternative proving
+/// systems or delegating the proof generation to an external prover service.
+pub trait ProverBackend: Send + Sync {
+    /// Generates the proof for the final block of the given input. The proof generation must be
+    /// aborted once the `abort` channel resolves.
+    fn prove(
+        &self,
+        proof_input: ProofInput,
+        abort: Receiver<()>,
+    ) -> BoxFuture<'static, Result<ZKPState, ZKProofGenerationError>>;
+}
+
+/// The default prover backend, generating the proofs in a child process.
+/// If no prover path is given, the current executable is launched with the `--prove` argument.
+#[derive(Clone, Debug, Default)]
+pub struct ProcessProverBackend {
+    prover_path: Option<PathBuf>,
+}
+
+impl ProcessProverBackend {
+    pub fn new(prover_path: Option<PathBuf>) -> Self {
+        Self { prover_path }
+    }
+}
+
+impl ProverBackend for ProcessProverBackend {
+    fn prove(
+        &self,
+        proof_input: ProofInput,
+        abort: Receiver<()>,
+    ) -> BoxFuture<'static, Result<ZKPState, ZKProofGenerationError>> {
+        launch_generate_new_proof(abort, proof_input, self.prover_path.clone()).boxed()
+    }
+}
@@ -79,6 +79,8 @@ impl ZKPState {
 pub enum ProofSource<N: Network> {
     PeerGenerated(N::PeerId),
     SelfGenerated,
+    /// The proof of a trusted sync checkpoint.
+    SyncCheckpoint,
 }
 
 impl<N: Network> Clone for ProofSource<N> {
@@ -86,6 +88,7 @@ impl<N: Network> Clone for ProofSource<N> {
         match self {
             Self::PeerGenerated(peer_id) => Self::PeerGenerated(*peer_id),
             Self::SelfGenerated => Self::SelfGenerated,
+            Self::SyncCheckpoint => Self::SyncCheckpoint,
         }
     }
 }
@@ -95,6 +98,9 @@ impl<N: Network> ProofSource<N> {
         match self {
             Self::PeerGenerated(peer_id) => *peer_id,
             Self::SelfGenerated => panic!("Called unwrap_peer_id on a self generated proof source"),
+            Self::SyncCheckpoint => {
+                panic!("Called unwrap_peer_id on a sync checkpoint proof source")
+            }
         }
     }
 }
@@ -10,18 +10,19 @@ use futures::{stream::BoxStream, Future, StreamExt};
 use nimiq_block::MacroBlock;
 use nimiq_blockchain_interface::AbstractBlockchain;
 use nimiq_blockchain_proxy::BlockchainProxy;
-use nimiq_genesis::NetworkInfo;
+use nimiq_genesis::{NetworkInfo, SyncCheckpoints};
 use nimiq_network_interface::{
     network::{MsgAcceptance, Network, PubsubId},
     request::request_handler,
 };
+use nimiq_serde::Deserialize;
 use nimiq_utils::spawn;
 use parking_lot::{Mutex, RwLock, RwLockUpgradableReadGuard};
 use tokio::sync::{broadcast, oneshot::error::RecvError};
 use tokio_stream::wrappers::BroadcastStream;
 
 #[cfg(feature = "zkp-prover")]
-use crate::zkp_prover::ZKProver;
+use crate::{proof_store::ProofCache, prover_backend::ProverBackend, zkp_prover::ZKProver};
 use crate::{proof_store::ProofStore, proof_utils::*, types::*, zkp_requests::ZKPRequests};
 
 pub type ZKProofsStream<N> = BoxStream<'static, (ZKProof, <N as Network>::PubsubId)>;
@@ -152,14 +153,18 @@ impl<N: Network> ZKPComponent<N> {
         zkp_component
     }
 
+    /// Creates a ZKP component that generates the proofs for new election blocks using the given
+    /// prover backend if the prover is active. Proofs we generated are added to the proof cache,
+    /// if any, such that they don't need to be recomputed after a restart.
     #[cfg(feature = "zkp-prover")]
     pub async fn with_prover(
         blockchain: BlockchainProxy,
         network: Arc<N>,
         is_prover_active: bool,
-        prover_path: Option<PathBuf>,
+        prover_backend: Arc<dyn ProverBackend>,
         prover_keys_path: PathBuf,
         proof_storage: Option<Box<dyn ProofStore>>,
+        proof_cache: Option<Arc<dyn ProofCache>>,
     ) -> Self {
         let mut zkp_component = Self::new(blockchain, network, proof_storage).await;
 
@@ -170,8 +175,9 @@ impl<N: Network> ZKPComponent<N> {
                     Arc::clone(blockchain),
                     Arc::clone(&zkp_component.network),
                     Arc::clone(&zkp_component.zkp_state),
-                    prover_path,
+                    prover_backend,
                     prover_keys_path,
+                    proof_cache,
                 )
                 .await,
             ),
@@ -237,6 +243,45 @@ impl<N: Network> ZKPComponent<N> {
         }
     }
 
+    /// Starts from the newest trusted sync checkpoint if it is more recent than the current state.
+    /// The checkpoint is fully verified before it is loaded into our state.
+    pub fn with_sync_checkpoints(mut self, sync_checkpoints: &SyncCheckpoints) -> Self {
+        self.load_sync_checkpoint(sync_checkpoints);
+        self
+    }
+
+    fn load_sync_checkpoint(&mut self, sync_checkpoints: &SyncCheckpoints) {
+        let network_id = self.blockchain.read().network_id();
+        let Some(checkpoint) = sync_checkpoints.latest(network_id) else {
+            return;
+        };
+
+        let block_number = checkpoint.block.block_number();
+        if block_number <= self.zkp_state.read().latest_block.block_number() {
+            return;
+        }
+
+        let zk_proof = match ZKProof::deserialize_from_vec(&checkpoint.proof) {
+            Ok(zk_proof) if zk_proof.block_number == block_number => zk_proof,
+            _ => {
+                log::error!(block_number, "Sync checkpoint contains an invalid zk proof");
+                return;
+            }
+        };
+
+        let this = Pin::new(self);
+        if let Err(e) = this.push_proof_from_peers(
+            zk_proof,
+            Some(checkpoint.block.clone()),
+            true,
+            ProofSource::SyncCheckpoint,
+        ) {
+            log::error!(block_number, "Error pushing the sync checkpoint {}", e);
+        } else {
+            log::info!(block_number, "Loaded sync checkpoint");
+        }
+    }
+
     /// Pushes the proof sent from an peer into our own state. If the proof is invalid or it's older than the
     /// current state it fails.
     fn push_proof_from_peers(
@@ -19,7 +19,7 @@ use nimiq_utils::spawn;
 use parking_lot::{lock_api::RwLockUpgradableReadGuard, RwLock, RwLockWriteGuard};
 use tokio::sync::oneshot::{channel, Sender};
 
-use crate::{proof_gen_utils::*, types::*};
+use crate::{proof_store::ProofCache, prover_backend::ProverBackend, types::*};
 
 /// ZK Prover generates the zk proof for an election block. It has:
 ///
@@ -30,7 +30,8 @@ use crate::{proof_gen_utils::*, types::*};
 /// - The current proof generation future if a proof is being generated
 /// - The channel to kill the current process generating the proof
 /// - The path of the proving keys directory
-/// - The path of the prover binary
+/// - The backend generating the proofs
+/// - The cache of the proofs we already generated
 ///
 /// The proofs are returned by polling the components.
 pub struct ZKProver<N: Network> {
@@ -43,7 +44,8 @@ pub struct ZKProver<N: Network> {
         Option<BoxFuture<'static, Result<(ZKPState, MacroBlock), ZKProofGenerationError>>>,
     proof_future_abort: Option<Sender<()>>,
     prover_keys_path: PathBuf,
-    prover_path: Option<PathBuf>,
+    prover_backend: Arc<dyn ProverBackend>,
+    proof_cache: Option<Arc<dyn ProofCache>>,
 }
 
 impl<N: Network> ZKProver<N> {
@@ -51,8 +53,9 @@ impl<N: Network> ZKProver<N> {
         blockchain: Arc<RwLock<Blockchain>>,
         network: Arc<N>,
         zkp_state: Arc<RwLock<ZKPState>>,
-        prover_path: Option<PathBuf>,
+        prover_backend: Arc<dyn ProverBackend>,
         prover_keys_path: PathBuf,
+        proof_cache: Option<Arc<dyn ProofCache>>,
     ) -> Self {
         let network_info = NetworkInfo::from_network_id(blockchain.read().network_id());
         let genesis_block = network_info.genesis_block().unwrap_macro();
@@ -113,7 +116,8 @@ impl<N: Network> ZKProver<N> {
             proof_future: None,
             proof_future_abort: None,
             prover_keys_path,
-            prover_path,
+            prover_backend,
+            proof_cache,
         }
     }
 
@@ -152,21 +156,37 @@ impl<N: Network> ZKProver<N> {
         if zkp_state.latest_block.block_number()
             == block.block_number() - Policy::blocks_per_epoch()
         {
+            // Reuses the proof if we already generated it before, e.g. prior to a restart.
+            let block_hash = block.hash();
+            if let Some(zk_proof) = self
+                .proof_cache
+                .as_ref()
+                .and_then(|cache| cache.get_proof(&block_hash))
+            {
+                log::debug!(%block_hash, "Found cached zkp for election block");
+                let state = ZKPState {
+                    latest_block: block.clone(),
+                    latest_proof: zk_proof.proof,
+                };
+                self.proof_future = Some(future::ready(Ok((state, block))).boxed());
+                return;
+            }
+
             let (abort_sender, abort_receiver) = channel();
             self.proof_future = Some(
-                launch_generate_new_proof(
-                    abort_receiver,
-                    ProofInput {
-                        previous_block: zkp_state.latest_block.clone(),
-                        previous_proof: zkp_state.latest_proof.clone(),
-                        final_block: block.clone(),
-                        genesis_header_hash: self.genesis_header_hash,
-                        prover_keys_path: self.prover_keys_path.clone(),
-                    },
-                    self.prover_path.clone(),
-                )
-                .map(|res| res.map(|state| (state, block)))
-                .boxed(),
+                self.prover_backend
+                    .prove(
+                        ProofInput {
+                            previous_block: zkp_state.latest_block.clone(),
+                            previous_proof: zkp_state.latest_proof.clone(),
+                            final_block: block.clone(),
+                            genesis_header_hash: self.genesis_header_hash,
+                            prover_keys_path: self.prover_keys_path.clone(),
+                        },
+                        abort_receiver,
+                    )
+                    .map(|res| res.map(|state| (state, block)))
+                    .boxed(),
             );
             self.proof_future_abort = Some(abort_sender);
         } else {
@@ -223,6 +243,9 @@ impl<N: Network> Stream for ZKProver<N> {
                         let zkp_state_lock = RwLockWriteGuard::downgrade(zkp_state_lock);
 
                         let proof: ZKProof = zkp_state_lock.clone().into();
+                        if let Some(proof_cache) = &self.proof_cache {
+                            proof_cache.put_proof(&block.hash(), &proof);
+                        }
                         Self::broadcast_zk_proof(&self.network, proof.clone());
                         return Poll::Ready(Some((proof, block)));
                     }
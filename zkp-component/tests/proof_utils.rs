@@ -4,6 +4,7 @@ use ark_groth16::Proof;
 use nimiq_blockchain::{BlockProducer, Blockchain, BlockchainConfig};
 use nimiq_blockchain_proxy::BlockchainProxy;
 use nimiq_database::mdbx::{DatabaseConfig, MdbxDatabase};
+use nimiq_hash::Blake2bHash;
 use nimiq_primitives::{networks::NetworkId, policy::Policy};
 use nimiq_test_log::test;
 use nimiq_test_utils::{
@@ -14,7 +15,7 @@ use nimiq_test_utils::{
 use nimiq_utils::time::OffsetTime;
 use nimiq_zkp::ZKP_VERIFYING_DATA;
 use nimiq_zkp_component::{
-    proof_store::{DBProofStore, ProofStore},
+    proof_store::{DBProofCache, DBProofStore, ProofCache, ProofStore},
     proof_utils::validate_proof,
     types::ZKProof,
 };
@@ -182,3 +183,27 @@ async fn can_store_and_load_zkp_state_from_db() {
         "Load from db was not successful"
     );
 }
+
+#[test(tokio::test)]
+async fn can_cache_proofs_by_election_block_hash() {
+    let env = MdbxDatabase::new_volatile(DatabaseConfig {
+        max_tables: Some(2),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let proof_cache = DBProofCache::new(env);
+    let block_hash = Blake2bHash::from([0u8; 32]);
+    assert!(proof_cache.get_proof(&block_hash).is_none());
+
+    let new_proof = ZKProof {
+        block_number: Policy::blocks_per_epoch(),
+        proof: Some(Proof::default()),
+    };
+
+    proof_cache.put_proof(&block_hash, &new_proof);
+    assert_eq!(proof_cache.get_proof(&block_hash).unwrap(), new_proof);
+    assert!(proof_cache
+        .get_proof(&Blake2bHash::from([1u8; 32]))
+        .is_none());
+}
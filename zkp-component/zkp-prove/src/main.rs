@@ -8,10 +8,7 @@ use nimiq_blockchain_proxy::BlockchainProxy;
 use nimiq_database::mdbx::MdbxDatabase;
 use nimiq_genesis::NetworkInfo;
 use nimiq_log::TargetsExt;
-use nimiq_primitives::{
-    networks::NetworkId,
-    policy::{Policy, TEST_POLICY},
-};
+use nimiq_primitives::{networks::NetworkId, policy::Policy};
 use nimiq_serde::Serialize;
 use nimiq_test_utils::{
     blockchain::{signing_key, voting_key},
@@ -49,20 +46,8 @@ fn initialize(network_id: NetworkId) {
         )
         .init();
     let network_info = NetworkInfo::from_network_id(network_id);
-    let genesis_block = network_info.genesis_block();
-
-    // Run tests with different policy values:
-    let mut policy_config = match network_id {
-        NetworkId::UnitAlbatross => TEST_POLICY,
-        NetworkId::TestAlbatross | NetworkId::DevAlbatross | NetworkId::MainAlbatross => {
-            Policy::default()
-        }
-        _ => panic!("Invalid network id"),
-    };
-    // The genesis block number must be set accordingly
-    policy_config.genesis_block_number = genesis_block.block_number();
-
-    let _ = Policy::get_or_init(policy_config);
+    // The policy of the network, e.g. `TEST_POLICY` for the unit test network.
+    let _ = Policy::get_or_init(network_info.policy());
 }
 
 #[tokio::main]